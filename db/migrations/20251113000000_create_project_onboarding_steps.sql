@@ -0,0 +1,13 @@
+-- Track guided-setup progress for new projects.
+-- Steps that can be detected from project data (team assigned, first story) are
+-- derived at read time; this table records explicit completions and skips.
+
+CREATE TABLE IF NOT EXISTS project_onboarding_steps (
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    step TEXT NOT NULL
+        CHECK (step IN ('team_added', 'first_story', 'readiness_policy_set', 'github_linked')),
+    status TEXT NOT NULL
+        CHECK (status IN ('pending', 'completed', 'skipped')),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, step)
+);
@@ -0,0 +1,9 @@
+-- Secret tokens that let public README badges read a project's readiness stats.
+-- One token per project; rotating it replaces the row and invalidates old badge URLs.
+
+CREATE TABLE IF NOT EXISTS project_badge_tokens (
+    project_id UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    organization_id UUID,
+    token TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
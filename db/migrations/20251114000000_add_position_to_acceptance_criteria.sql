@@ -0,0 +1,16 @@
+-- Explicit display order for acceptance criteria within a story.
+-- Existing rows are numbered by creation time so current ordering is preserved.
+
+ALTER TABLE acceptance_criteria
+    ADD COLUMN IF NOT EXISTS position INTEGER NOT NULL DEFAULT 0;
+
+UPDATE acceptance_criteria ac
+SET position = ordered.position
+FROM (
+    SELECT id, (ROW_NUMBER() OVER (PARTITION BY story_id ORDER BY created_at, id) - 1)::INTEGER AS position
+    FROM acceptance_criteria
+) ordered
+WHERE ac.id = ordered.id;
+
+CREATE INDEX IF NOT EXISTS idx_acceptance_criteria_story_position
+    ON acceptance_criteria (story_id, position);
@@ -0,0 +1,18 @@
+-- Planned absences and reduced schedules used for sprint capacity planning.
+-- Dates are inclusive calendar days; overlap between a user's entries is rejected
+-- by the application when the set is replaced.
+
+CREATE TABLE IF NOT EXISTS user_availability (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('vacation', 'part_time')),
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    capacity_percent SMALLINT NOT NULL CHECK (capacity_percent BETWEEN 0 AND 100),
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT user_availability_date_order CHECK (start_date <= end_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_availability_user_dates
+    ON user_availability (user_id, start_date, end_date);
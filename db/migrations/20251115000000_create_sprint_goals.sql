@@ -0,0 +1,24 @@
+-- Structured sprint goals. The free-text sprints.goal column is kept as the
+-- headline; each goal row can be linked to the stories that deliver it.
+
+CREATE TABLE IF NOT EXISTS sprint_goals (
+    id UUID PRIMARY KEY,
+    sprint_id UUID NOT NULL REFERENCES sprints(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    description TEXT,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_sprint_goals_sprint_position
+    ON sprint_goals (sprint_id, position);
+
+CREATE TABLE IF NOT EXISTS sprint_goal_stories (
+    goal_id UUID NOT NULL REFERENCES sprint_goals(id) ON DELETE CASCADE,
+    story_id UUID NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    PRIMARY KEY (goal_id, story_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sprint_goal_stories_story
+    ON sprint_goal_stories (story_id);
@@ -0,0 +1,3 @@
+-- Machine-actionable fix descriptors stored alongside the human-readable missing items
+ALTER TABLE readiness_evals
+    ADD COLUMN IF NOT EXISTS fixes JSONB NOT NULL DEFAULT '[]'::jsonb;
@@ -0,0 +1,92 @@
+-- Human-readable keys (e.g. PROJ-123) for stories and tasks.
+-- Every project gets a globally unique key prefix. Stories and tasks share one
+-- counter per project, so a short key identifies exactly one item.
+
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS key TEXT;
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS next_item_number INTEGER NOT NULL DEFAULT 1;
+
+-- Up to four letters from the project name, suffixed with a number on collision
+CREATE OR REPLACE FUNCTION derive_project_key(project_name TEXT)
+RETURNS TEXT AS $$
+DECLARE
+    base TEXT := LEFT(UPPER(REGEXP_REPLACE(COALESCE(project_name, ''), '[^A-Za-z]', '', 'g')), 4);
+    candidate TEXT;
+    suffix INTEGER := 1;
+BEGIN
+    IF LENGTH(base) < 2 THEN
+        base := 'PRJ';
+    END IF;
+    candidate := base;
+    WHILE EXISTS (SELECT 1 FROM projects WHERE key = candidate) LOOP
+        suffix := suffix + 1;
+        candidate := base || suffix;
+    END LOOP;
+    RETURN candidate;
+END;
+$$ LANGUAGE plpgsql;
+
+DO $$
+DECLARE
+    project RECORD;
+BEGIN
+    FOR project IN SELECT id, name FROM projects WHERE key IS NULL ORDER BY created_at, id LOOP
+        UPDATE projects SET key = derive_project_key(project.name) WHERE id = project.id;
+    END LOOP;
+END $$;
+
+CREATE OR REPLACE FUNCTION assign_project_key()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.key IS NULL THEN
+        NEW.key := derive_project_key(NEW.name);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS projects_assign_key ON projects;
+CREATE TRIGGER projects_assign_key
+    BEFORE INSERT ON projects
+    FOR EACH ROW EXECUTE FUNCTION assign_project_key();
+
+ALTER TABLE projects ALTER COLUMN key SET NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_projects_key ON projects (key);
+
+ALTER TABLE stories ADD COLUMN IF NOT EXISTS short_key TEXT;
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS short_key TEXT;
+
+-- Backfill existing items in creation order, stories before tasks on ties
+CREATE TEMP TABLE item_short_keys AS
+SELECT
+    items.id,
+    items.kind,
+    items.project_id,
+    p.key || '-' || ROW_NUMBER() OVER (
+        PARTITION BY items.project_id
+        ORDER BY items.created_at, items.kind, items.id
+    ) AS short_key
+FROM (
+    SELECT s.id, s.project_id, s.created_at, 'story' AS kind FROM stories s
+    UNION ALL
+    SELECT t.id, s.project_id, t.created_at, 'task' AS kind
+    FROM tasks t
+    JOIN stories s ON s.id = t.story_id
+) items
+JOIN projects p ON p.id = items.project_id;
+
+UPDATE stories SET short_key = k.short_key
+FROM item_short_keys k
+WHERE k.kind = 'story' AND k.id = stories.id AND stories.short_key IS NULL;
+
+UPDATE tasks SET short_key = k.short_key
+FROM item_short_keys k
+WHERE k.kind = 'task' AND k.id = tasks.id AND tasks.short_key IS NULL;
+
+UPDATE projects SET next_item_number = counts.total + 1
+FROM (SELECT project_id, COUNT(*) AS total FROM item_short_keys GROUP BY project_id) counts
+WHERE counts.project_id = projects.id;
+
+DROP TABLE item_short_keys;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_stories_short_key ON stories (short_key) WHERE short_key IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tasks_short_key ON tasks (short_key) WHERE short_key IS NOT NULL;
@@ -0,0 +1,22 @@
+-- Links created when a story, task, commit, pull request or comment mentions
+-- a story or task by its short key. Sources are polymorphic: internal sources
+-- carry source_id, GitHub sources carry source_url.
+
+CREATE TABLE IF NOT EXISTS item_references (
+    id UUID PRIMARY KEY,
+    organization_id UUID,
+    source_type TEXT NOT NULL
+        CHECK (source_type IN ('story', 'task', 'commit', 'pull_request', 'comment')),
+    source_id UUID,
+    source_url TEXT,
+    source_title TEXT,
+    target_type TEXT NOT NULL CHECK (target_type IN ('story', 'task')),
+    target_id UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (source_id IS NOT NULL OR source_url IS NOT NULL)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_item_references_unique
+    ON item_references (source_type, COALESCE(source_id::text, source_url), target_id);
+CREATE INDEX IF NOT EXISTS idx_item_references_source ON item_references (source_id);
+CREATE INDEX IF NOT EXISTS idx_item_references_target ON item_references (target_id);
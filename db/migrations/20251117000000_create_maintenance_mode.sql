@@ -0,0 +1,12 @@
+-- Single-row switch for maintenance mode. While enabled the api-gateway
+-- rejects mutating requests with 503 unless they carry the admin token.
+
+CREATE TABLE IF NOT EXISTS maintenance_mode (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    message TEXT,
+    retry_after_seconds INTEGER NOT NULL DEFAULT 300 CHECK (retry_after_seconds > 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO maintenance_mode (id) VALUES (TRUE) ON CONFLICT (id) DO NOTHING;
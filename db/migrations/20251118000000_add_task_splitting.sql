@@ -0,0 +1,20 @@
+-- Task splitting: an oversized task is replaced by smaller child tasks. The
+-- original stays as a 'superseded' record, and each child points back to it.
+
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS split_from_task_id UUID REFERENCES tasks(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_split_from_task_id
+    ON tasks(split_from_task_id) WHERE split_from_task_id IS NOT NULL;
+
+ALTER TABLE tasks DROP CONSTRAINT IF EXISTS tasks_status_check;
+ALTER TABLE tasks ADD CONSTRAINT tasks_status_check
+    CHECK (status IN ('available', 'owned', 'inprogress', 'completed', 'superseded'));
+
+-- A superseded task keeps whatever owner it had when it was split
+ALTER TABLE tasks DROP CONSTRAINT IF EXISTS tasks_ownership_consistency;
+ALTER TABLE tasks ADD CONSTRAINT tasks_ownership_consistency
+    CHECK (
+        (status = 'available' AND owner_user_id IS NULL AND owned_at IS NULL) OR
+        (status IN ('owned', 'inprogress', 'completed') AND owner_user_id IS NOT NULL AND owned_at IS NOT NULL) OR
+        status = 'superseded'
+    );
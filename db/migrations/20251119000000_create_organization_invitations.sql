@@ -0,0 +1,26 @@
+-- Email invitations to join an organization. Only a SHA-256 hash of the
+-- acceptance token is stored; the token itself travels in the invitation link.
+
+CREATE TABLE IF NOT EXISTS organization_invitations (
+    id UUID PRIMARY KEY,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    email TEXT NOT NULL,
+    role TEXT NOT NULL CHECK (role IN ('admin', 'member')),
+    token_hash TEXT NOT NULL UNIQUE,
+    invited_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'accepted', 'revoked', 'expired')),
+    expires_at TIMESTAMPTZ NOT NULL,
+    accepted_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    accepted_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one open invitation per address and organization
+CREATE UNIQUE INDEX IF NOT EXISTS idx_org_invitations_pending_email
+    ON organization_invitations (organization_id, lower(email))
+    WHERE status = 'pending';
+
+CREATE INDEX IF NOT EXISTS idx_org_invitations_org_status
+    ON organization_invitations (organization_id, status);
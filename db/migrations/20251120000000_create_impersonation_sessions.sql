@@ -0,0 +1,33 @@
+-- Time-boxed impersonation for support engineers. A super-admin mints a token
+-- that acts as the target user; every request made with it is written to
+-- impersonation_audit_log tagged with the super-admin who made it.
+
+CREATE TABLE IF NOT EXISTS impersonation_sessions (
+    id UUID PRIMARY KEY,
+    token_hash TEXT NOT NULL UNIQUE,
+    impersonated_by TEXT NOT NULL, -- Clerk user ID of the super-admin
+    target_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    organization_id UUID REFERENCES organizations(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_impersonation_sessions_target
+    ON impersonation_sessions (target_user_id, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS impersonation_audit_log (
+    id UUID PRIMARY KEY,
+    session_id UUID NOT NULL REFERENCES impersonation_sessions(id) ON DELETE CASCADE,
+    impersonated_by TEXT NOT NULL,
+    target_user_id UUID NOT NULL,
+    organization_id UUID,
+    method TEXT NOT NULL,
+    path TEXT NOT NULL,
+    status SMALLINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_impersonation_audit_session
+    ON impersonation_audit_log (session_id, created_at);
@@ -0,0 +1,30 @@
+-- Opt-in capture of LLM prompts and responses so maintainers can investigate
+-- bad generations. Nothing is recorded for an organization until it has an
+-- enabled row in llm_audit_settings; records expire after its retention period.
+
+CREATE TABLE IF NOT EXISTS llm_audit_settings (
+    organization_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    retention_days INTEGER NOT NULL DEFAULT 14 CHECK (retention_days BETWEEN 1 AND 90),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS llm_audit_log (
+    id UUID PRIMARY KEY,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    story_id UUID,
+    call_site TEXT NOT NULL,
+    model TEXT NOT NULL,
+    prompt TEXT NOT NULL, -- PII-scrubbed before storage
+    response TEXT,
+    error TEXT,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_audit_log_story
+    ON llm_audit_log (story_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_llm_audit_log_org
+    ON llm_audit_log (organization_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_llm_audit_log_expires_at
+    ON llm_audit_log (expires_at);
@@ -0,0 +1,18 @@
+-- One backlog health score per project per day, recorded when the score is
+-- read, so coaches can see how refinement habits trend over time.
+
+CREATE TABLE IF NOT EXISTS backlog_health_snapshots (
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    snapshot_date DATE NOT NULL,
+    organization_id UUID,
+    score SMALLINT NOT NULL,
+    readiness SMALLINT NOT NULL,
+    draft_age SMALLINT NOT NULL,
+    estimation SMALLINT NOT NULL,
+    acceptance_criteria SMALLINT NOT NULL,
+    duplicates SMALLINT NOT NULL,
+    dependencies SMALLINT NOT NULL,
+    backlog_stories INTEGER NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, snapshot_date)
+);
@@ -0,0 +1,38 @@
+-- Stories committed to a sprint, projected by prompt-builder from story events
+-- so plan pack generation can see the current sprint's scope. Both sprint
+-- projections are backfilled so existing sprints are visible straight away.
+
+CREATE TABLE IF NOT EXISTS prompt_sprint_story_projections (
+    story_id UUID PRIMARY KEY,
+    sprint_id UUID NOT NULL,
+    project_id UUID NOT NULL,
+    organization_id UUID,
+    title TEXT NOT NULL,
+    status TEXT NOT NULL,
+    story_points INTEGER,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_prompt_sprint_story_sprint
+    ON prompt_sprint_story_projections (sprint_id);
+CREATE INDEX IF NOT EXISTS idx_prompt_sprint_story_project
+    ON prompt_sprint_story_projections (project_id);
+
+INSERT INTO prompt_sprint_projections (
+    id, organization_id, team_id, name, goal, capacity_points, status,
+    start_date, end_date, committed_points, completed_points, created_at, updated_at
+)
+SELECT s.id, t.organization_id, s.team_id, s.name, NULLIF(s.goal, ''), s.capacity_points,
+       s.status, s.start_date, s.end_date, s.committed_points, s.completed_points,
+       s.created_at, s.updated_at
+FROM sprints s
+LEFT JOIN teams t ON t.id = s.team_id
+ON CONFLICT (id) DO NOTHING;
+
+INSERT INTO prompt_sprint_story_projections (
+    story_id, sprint_id, project_id, organization_id, title, status, story_points, updated_at
+)
+SELECT id, sprint_id, project_id, organization_id, title, status, story_points, updated_at
+FROM stories
+WHERE sprint_id IS NOT NULL AND deleted_at IS NULL
+ON CONFLICT (story_id) DO NOTHING;
@@ -0,0 +1,25 @@
+-- Snapshot of a story's title, description and labels after each edit, so
+-- reviewers can diff how the story evolved during refinement
+CREATE TABLE IF NOT EXISTS story_revisions (
+    id UUID PRIMARY KEY,
+    story_id UUID NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    organization_id UUID,
+    revision INTEGER NOT NULL CHECK (revision > 0),
+    title TEXT NOT NULL,
+    description TEXT,
+    labels TEXT[] NOT NULL DEFAULT '{}',
+    edited_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (story_id, revision)
+);
+
+CREATE INDEX IF NOT EXISTS idx_story_revisions_story
+    ON story_revisions(story_id, revision DESC);
+
+-- Existing stories start their history from their current state
+INSERT INTO story_revisions (id, story_id, organization_id, revision, title, description, labels, created_at)
+SELECT gen_random_uuid(), s.id, s.organization_id, 1, s.title, s.description,
+       COALESCE(s.labels, '{}'), s.updated_at
+FROM stories s
+WHERE s.deleted_at IS NULL
+ON CONFLICT (story_id, revision) DO NOTHING;
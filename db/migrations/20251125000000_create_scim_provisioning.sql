@@ -0,0 +1,46 @@
+-- SCIM 2.0 provisioning. Each organization's identity provider authenticates
+-- with its own bearer token and manages the organization's members (Users)
+-- and teams (Groups).
+
+CREATE TABLE IF NOT EXISTS scim_tokens (
+    id UUID PRIMARY KEY,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    description TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_scim_tokens_organization
+    ON scim_tokens(organization_id);
+
+-- Provisioning state of a user within one organization. A deactivated user
+-- keeps this row (active = false) but loses the organization membership.
+CREATE TABLE IF NOT EXISTS scim_users (
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    external_id TEXT,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, user_id)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_scim_users_external_id
+    ON scim_users(organization_id, external_id) WHERE external_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_scim_users_inactive
+    ON scim_users(user_id, organization_id) WHERE NOT active;
+
+-- Identity provider ids of teams managed as SCIM Groups
+CREATE TABLE IF NOT EXISTS scim_groups (
+    team_id UUID PRIMARY KEY REFERENCES teams(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    external_id TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_scim_groups_external_id
+    ON scim_groups(organization_id, external_id) WHERE external_id IS NOT NULL;
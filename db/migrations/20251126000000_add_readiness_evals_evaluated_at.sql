@@ -0,0 +1,7 @@
+-- Record when each readiness evaluation ran so the latest one can be found.
+-- Existing rows share the migration time; their relative order is unknown.
+ALTER TABLE readiness_evals
+    ADD COLUMN IF NOT EXISTS evaluated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+CREATE INDEX IF NOT EXISTS idx_readiness_evals_story_evaluated_at
+    ON readiness_evals (story_id, evaluated_at DESC);
@@ -0,0 +1,14 @@
+-- Users waiting to claim a task someone else owns. When the owner releases it
+-- before the entry expires, the earliest waiting user is offered the task.
+
+CREATE TABLE IF NOT EXISTS task_claim_queue (
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    organization_id UUID,
+    queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (task_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_claim_queue_task_queued_at
+    ON task_claim_queue(task_id, queued_at);
@@ -0,0 +1,16 @@
+-- Notes left alongside task status changes, e.g. by coding agents updating
+-- several tasks at once when they finish a story.
+
+CREATE TABLE IF NOT EXISTS task_status_notes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    organization_id UUID,
+    author_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    note TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_status_notes_task_created_at
+    ON task_status_notes(task_id, created_at);
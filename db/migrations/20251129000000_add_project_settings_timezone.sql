@@ -0,0 +1,5 @@
+-- IANA time zone used for a project's sprint boundaries, days remaining and
+-- day-by-day reporting. Sprint instants stay stored in UTC.
+
+ALTER TABLE project_settings
+    ADD COLUMN IF NOT EXISTS timezone TEXT NOT NULL DEFAULT 'UTC';
@@ -0,0 +1,15 @@
+-- Acceptance test files generated from a story's criteria. Every generation
+-- is kept as a new version per story and format.
+CREATE TABLE IF NOT EXISTS test_scaffolds (
+    id UUID PRIMARY KEY,
+    story_id UUID NOT NULL,
+    plan_pack_id UUID REFERENCES plan_packs(id) ON DELETE SET NULL,
+    format TEXT NOT NULL CHECK (format IN ('gherkin', 'rust')),
+    version INTEGER NOT NULL,
+    file_name TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (story_id, format, version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_test_scaffolds_story_id ON test_scaffolds(story_id);
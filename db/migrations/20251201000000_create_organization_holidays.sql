@@ -0,0 +1,9 @@
+-- Organization-wide days off, skipped when sprints are scheduled
+CREATE TABLE IF NOT EXISTS organization_holidays (
+    id UUID PRIMARY KEY,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    holiday_date DATE NOT NULL,
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (organization_id, holiday_date)
+);
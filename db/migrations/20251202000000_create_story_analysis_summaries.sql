@@ -0,0 +1,13 @@
+-- Per-story rollup of the latest task analyses, updated as tasks are analyzed
+CREATE TABLE IF NOT EXISTS story_analysis_summaries (
+    story_id UUID PRIMARY KEY,
+    organization_id UUID,
+    -- Latest clarity score and missing elements per task, keyed by task ID
+    tasks JSONB NOT NULL DEFAULT '{}',
+    task_count INTEGER NOT NULL DEFAULT 0,
+    average_clarity INTEGER,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_story_analysis_summaries_org_id
+    ON story_analysis_summaries(organization_id);
@@ -0,0 +1,18 @@
+-- Users' verdicts on task analysis recommendations. Heuristics an organization
+-- keeps marking as incorrect are left out of later analyses.
+CREATE TABLE IF NOT EXISTS task_analysis_feedback (
+    id UUID PRIMARY KEY,
+    task_id UUID NOT NULL,
+    story_id UUID NOT NULL,
+    organization_id UUID,
+    user_id TEXT NOT NULL,
+    heuristic TEXT NOT NULL,
+    recommendation TEXT NOT NULL,
+    verdict TEXT NOT NULL CHECK (verdict IN ('helpful', 'incorrect')),
+    comment TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (task_id, heuristic, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_analysis_feedback_org_id
+    ON task_analysis_feedback(organization_id, created_at DESC);
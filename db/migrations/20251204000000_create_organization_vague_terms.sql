@@ -0,0 +1,11 @@
+-- Organization changes to the task analyzer's built-in vague-term dictionary:
+-- added terms, replaced suggestions and removed built-in terms
+CREATE TABLE IF NOT EXISTS organization_vague_terms (
+    organization_id UUID NOT NULL,
+    term TEXT NOT NULL,
+    suggestion TEXT,
+    removed BOOLEAN NOT NULL DEFAULT FALSE,
+    position INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, term)
+);
@@ -0,0 +1,15 @@
+-- Per-organization clarity deductions for the task analyzer and the score at
+-- which a task counts as ready for an AI agent. Organizations without a row
+-- use the built-in profile.
+CREATE TABLE IF NOT EXISTS organization_scoring_profiles (
+    organization_id UUID PRIMARY KEY,
+    missing_description INTEGER NOT NULL,
+    missing_technical_details INTEGER NOT NULL,
+    vague_language INTEGER NOT NULL,
+    missing_ai_agent_compatibility INTEGER NOT NULL,
+    missing_acceptance_criteria INTEGER NOT NULL,
+    invalid_acceptance_criteria INTEGER NOT NULL,
+    missing_estimate INTEGER NOT NULL,
+    ai_ready_threshold INTEGER NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
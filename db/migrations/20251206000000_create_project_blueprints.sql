@@ -0,0 +1,16 @@
+-- Project conventions (labels, Definition of Done, story templates, sprint cadence)
+-- and organization blueprints that new projects can be created from
+ALTER TABLE project_settings
+    ADD COLUMN IF NOT EXISTS conventions JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+CREATE TABLE IF NOT EXISTS project_blueprints (
+    organization_id UUID NOT NULL,
+    key TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    estimation_scale TEXT NOT NULL,
+    dor_template JSONB NOT NULL,
+    conventions JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, key)
+);
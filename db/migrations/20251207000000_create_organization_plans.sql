@@ -0,0 +1,18 @@
+-- Plan tier per organization, with optional limits that replace the tier's.
+-- Organizations without a row are on the free tier.
+CREATE TABLE IF NOT EXISTS organization_plans (
+    organization_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    tier TEXT NOT NULL CHECK (tier IN ('free', 'team', 'enterprise')),
+    max_projects BIGINT CHECK (max_projects >= 0),
+    max_stories BIGINT CHECK (max_stories >= 0),
+    max_llm_calls_per_month BIGINT CHECK (max_llm_calls_per_month >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- LLM calls counted per organization and calendar month (UTC)
+CREATE TABLE IF NOT EXISTS organization_llm_usage (
+    organization_id UUID NOT NULL,
+    period_start TIMESTAMPTZ NOT NULL,
+    calls BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (organization_id, period_start)
+);
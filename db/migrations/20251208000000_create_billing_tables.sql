@@ -0,0 +1,31 @@
+-- Stripe customer linked to each paying organization
+CREATE TABLE IF NOT EXISTS billing_customers (
+    organization_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    stripe_customer_id TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Latest known state of each Stripe subscription, as reported by webhooks.
+-- last_event_at is the creation time of the event the row was last written
+-- from, so events delivered out of order never overwrite newer state.
+CREATE TABLE IF NOT EXISTS billing_subscriptions (
+    stripe_subscription_id TEXT PRIMARY KEY,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    stripe_customer_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    stripe_price_id TEXT,
+    current_period_end TIMESTAMPTZ,
+    cancel_at_period_end BOOLEAN NOT NULL DEFAULT FALSE,
+    last_event_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_billing_subscriptions_organization
+    ON billing_subscriptions (organization_id);
+
+-- Stripe events already handled; redeliveries are acknowledged without reapplying
+CREATE TABLE IF NOT EXISTS billing_webhook_events (
+    event_id TEXT PRIMARY KEY,
+    event_type TEXT NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,15 @@
+-- Every domain event published on the bus, kept for a limited time so a window
+-- can be exported (anonymized) and replayed against staging
+CREATE TABLE IF NOT EXISTS domain_event_journal (
+    id UUID PRIMARY KEY,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    kind TEXT NOT NULL,
+    organization_id UUID,
+    payload JSONB NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_domain_event_journal_occurred_at
+    ON domain_event_journal (occurred_at, id);
+CREATE INDEX IF NOT EXISTS idx_domain_event_journal_organization
+    ON domain_event_journal (organization_id, occurred_at);
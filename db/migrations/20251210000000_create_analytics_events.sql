@@ -0,0 +1,20 @@
+-- Anonymized product analytics. Organizations and subjects are stored only as
+-- salted hashes; nothing here can be joined back to other tables without the salt.
+CREATE TABLE IF NOT EXISTS analytics_events (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL,
+    organization_hash TEXT,
+    subject_hash TEXT,
+    occurred_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_events_name_occurred_at
+    ON analytics_events (name, occurred_at);
+CREATE INDEX IF NOT EXISTS idx_analytics_events_organization_hash
+    ON analytics_events (organization_hash);
+
+-- Organizations that do not want their usage recorded
+CREATE TABLE IF NOT EXISTS analytics_opt_outs (
+    organization_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    opted_out_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,11 @@
+-- Story types: bugs need reproduction steps to be ready, spikes a timebox
+-- instead of story points
+ALTER TABLE stories
+    ADD COLUMN IF NOT EXISTS story_type TEXT NOT NULL DEFAULT 'feature'
+        CHECK (story_type IN ('feature', 'bug', 'spike', 'chore')),
+    ADD COLUMN IF NOT EXISTS timebox_hours INTEGER
+        CHECK (timebox_hours IS NULL OR (timebox_hours > 0 AND timebox_hours <= 80));
+
+CREATE INDEX IF NOT EXISTS idx_stories_sprint_type
+    ON stories (sprint_id, story_type)
+    WHERE deleted_at IS NULL;
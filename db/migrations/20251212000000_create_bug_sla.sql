@@ -0,0 +1,35 @@
+-- Bug severity, priority and where it was found, with the times the SLA
+-- clocks stopped. Bugs without a row are medium severity and untriaged.
+CREATE TABLE IF NOT EXISTS bug_details (
+    story_id UUID PRIMARY KEY REFERENCES stories(id) ON DELETE CASCADE,
+    severity TEXT NOT NULL DEFAULT 'medium'
+        CHECK (severity IN ('critical', 'high', 'medium', 'low')),
+    priority TEXT CHECK (priority IN ('urgent', 'high', 'normal', 'low')),
+    environment_found TEXT,
+    triaged_at TIMESTAMPTZ,
+    fixed_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Per-organization SLA targets; severities without a row use the defaults
+CREATE TABLE IF NOT EXISTS bug_sla_targets (
+    organization_id UUID NOT NULL,
+    severity TEXT NOT NULL CHECK (severity IN ('critical', 'high', 'medium', 'low')),
+    triage_hours INTEGER NOT NULL CHECK (triage_hours > 0),
+    fix_hours INTEGER NOT NULL CHECK (fix_hours >= triage_hours),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, severity)
+);
+
+-- Breaches already alerted on, so each timer alerts once
+CREATE TABLE IF NOT EXISTS bug_sla_breaches (
+    story_id UUID NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    timer TEXT NOT NULL CHECK (timer IN ('triage', 'fix')),
+    due_at TIMESTAMPTZ NOT NULL,
+    alerted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (story_id, timer)
+);
+
+CREATE INDEX IF NOT EXISTS idx_stories_open_bugs
+    ON stories (organization_id)
+    WHERE story_type = 'bug' AND deleted_at IS NULL;
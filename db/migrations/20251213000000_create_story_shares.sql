@@ -0,0 +1,25 @@
+-- Read-only access for another organization to a project's stories: a single
+-- story, or the whole project when story_id is NULL. Revoking keeps the row.
+CREATE TABLE IF NOT EXISTS story_shares (
+    id UUID PRIMARY KEY,
+    owner_organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    shared_with_organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    story_id UUID REFERENCES stories(id) ON DELETE CASCADE,
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ,
+    CHECK (owner_organization_id <> shared_with_organization_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_story_shares_recipient
+    ON story_shares (shared_with_organization_id, project_id)
+    WHERE revoked_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_story_shares_project
+    ON story_shares (project_id, created_at DESC);
+
+-- One active share per recipient and story (or project)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_story_shares_active
+    ON story_shares (shared_with_organization_id, project_id, COALESCE(story_id, '00000000-0000-0000-0000-000000000000'::UUID))
+    WHERE revoked_at IS NULL;
@@ -0,0 +1,33 @@
+-- Incidents opened by the health monitor or by maintainers. `dependency` names
+-- the subsystem whose health checks failed; it is NULL for manual incidents.
+-- `status_message` is public and shown on the status page, notes are internal.
+CREATE TABLE IF NOT EXISTS incidents (
+    id UUID PRIMARY KEY,
+    title TEXT NOT NULL,
+    dependency TEXT,
+    auto_opened BOOLEAN NOT NULL DEFAULT FALSE,
+    status TEXT NOT NULL DEFAULT 'investigating'
+        CHECK (status IN ('investigating', 'identified', 'monitoring', 'resolved')),
+    status_message TEXT,
+    opened_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((status = 'resolved') = (resolved_at IS NOT NULL))
+);
+
+-- At most one open automatic incident per dependency, across replicas
+CREATE UNIQUE INDEX IF NOT EXISTS idx_incidents_open_dependency
+    ON incidents (dependency)
+    WHERE auto_opened AND resolved_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_incidents_opened_at ON incidents (opened_at DESC);
+
+CREATE TABLE IF NOT EXISTS incident_notes (
+    id UUID PRIMARY KEY,
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_incident_notes_incident
+    ON incident_notes (incident_id, created_at);
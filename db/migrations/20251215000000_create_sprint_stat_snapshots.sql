@@ -0,0 +1,11 @@
+-- Sprint board totals, recalculated in the background whenever a story or
+-- task in the sprint changes so that board requests need not aggregate them.
+CREATE TABLE IF NOT EXISTS sprint_stat_snapshots (
+    sprint_id UUID PRIMARY KEY REFERENCES sprints(id) ON DELETE CASCADE,
+    total_stories INTEGER NOT NULL DEFAULT 0,
+    total_tasks INTEGER NOT NULL DEFAULT 0,
+    completed_tasks INTEGER NOT NULL DEFAULT 0,
+    total_points INTEGER NOT NULL DEFAULT 0,
+    completed_points INTEGER NOT NULL DEFAULT 0,
+    refreshed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,3 @@
+-- Large task packs keep their markdown and JSON renderings in blob storage;
+-- content_key names the blob, and the inline columns are left empty.
+ALTER TABLE task_packs ADD COLUMN IF NOT EXISTS content_key TEXT;
@@ -0,0 +1,54 @@
+-- Cold-storage tier for long-accepted stories and their tasks. Rows are moved
+-- here out of stories/tasks so hot-path queries never scan them, and stay
+-- searchable through the archive search endpoint.
+
+CREATE TABLE IF NOT EXISTS archived_stories (
+    id UUID PRIMARY KEY,
+    project_id UUID NOT NULL,
+    organization_id UUID,
+    short_key TEXT,
+    title TEXT NOT NULL,
+    description TEXT,
+    status TEXT NOT NULL,
+    story_type TEXT NOT NULL,
+    labels TEXT[] NOT NULL DEFAULT '{}',
+    story_points INTEGER,
+    sprint_id UUID,
+    assigned_to_user_id UUID,
+    -- The story's acceptance criteria, in display order
+    acceptance_criteria JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    search_vector TSVECTOR GENERATED ALWAYS AS (
+        to_tsvector('simple', title || ' ' || COALESCE(description, ''))
+    ) STORED
+);
+
+CREATE INDEX IF NOT EXISTS idx_archived_stories_organization_project
+    ON archived_stories (organization_id, project_id, archived_at DESC);
+CREATE INDEX IF NOT EXISTS idx_archived_stories_search
+    ON archived_stories USING GIN (search_vector);
+
+CREATE TABLE IF NOT EXISTS archived_tasks (
+    id UUID PRIMARY KEY,
+    story_id UUID NOT NULL REFERENCES archived_stories(id) ON DELETE CASCADE,
+    organization_id UUID,
+    short_key TEXT,
+    title TEXT NOT NULL,
+    description TEXT,
+    acceptance_criteria_refs TEXT[] NOT NULL DEFAULT '{}',
+    status TEXT NOT NULL,
+    owner_user_id UUID,
+    estimated_hours INTEGER,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    completed_at TIMESTAMPTZ,
+    search_vector TSVECTOR GENERATED ALWAYS AS (
+        to_tsvector('simple', title || ' ' || COALESCE(description, ''))
+    ) STORED
+);
+
+CREATE INDEX IF NOT EXISTS idx_archived_tasks_story_id ON archived_tasks (story_id);
+CREATE INDEX IF NOT EXISTS idx_archived_tasks_search
+    ON archived_tasks USING GIN (search_vector);
@@ -0,0 +1,14 @@
+-- Links left when a duplicate story is merged into another. The source story
+-- is soft-deleted; the link lets clients send readers of the duplicate on to
+-- the story that absorbed it.
+
+CREATE TABLE IF NOT EXISTS story_merges (
+    source_story_id UUID PRIMARY KEY REFERENCES stories(id) ON DELETE CASCADE,
+    target_story_id UUID NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    organization_id UUID,
+    merged_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    merged_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_story_merges_target
+    ON story_merges (target_story_id);
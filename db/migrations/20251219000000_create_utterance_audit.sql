@@ -0,0 +1,25 @@
+-- Audit of natural-language requests to the context orchestrator: each
+-- /interpret call with what it understood, resolved by the /act call that
+-- follows it. Only a hash of the utterance is kept.
+
+CREATE TABLE IF NOT EXISTS utterance_audit (
+    id UUID PRIMARY KEY,
+    organization_id UUID,
+    user_id TEXT,
+    utterance_hash VARCHAR(64) NOT NULL,
+    intent_type TEXT NOT NULL,
+    parsed_intent JSONB NOT NULL,
+    entity_ids UUID[] NOT NULL DEFAULT '{}',
+    candidate_ids UUID[] NOT NULL DEFAULT '{}',
+    confidence DOUBLE PRECISION NOT NULL CHECK (confidence >= 0 AND confidence <= 1),
+    outcome TEXT NOT NULL DEFAULT 'pending'
+        CHECK (outcome IN ('pending', 'accepted', 'overridden', 'failed')),
+    action_type TEXT,
+    target_ids UUID[],
+    error TEXT,
+    interpreted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    acted_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_utterance_audit_organization_interpreted
+    ON utterance_audit (organization_id, interpreted_at DESC);
@@ -0,0 +1,29 @@
+-- How acceptance criteria were verified: a test run's result with a link to
+-- it, or someone checking by hand. The latest verification of a criterion
+-- decides whether it is covered. Criteria are rewritten under the same id when
+-- their story is saved, so verifications are keyed by id rather than
+-- referencing the row.
+CREATE TABLE IF NOT EXISTS acceptance_criteria_verifications (
+    id UUID PRIMARY KEY,
+    story_id UUID NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    criterion_id UUID NOT NULL,
+    organization_id UUID,
+    kind TEXT NOT NULL CHECK (kind IN ('evidence', 'manual')),
+    passed BOOLEAN NOT NULL,
+    evidence_url TEXT,
+    note TEXT,
+    verified_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    verified_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (kind <> 'evidence' OR evidence_url IS NOT NULL)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ac_verifications_story
+    ON acceptance_criteria_verifications (story_id, criterion_id, verified_at DESC);
+
+-- Per-organization rules for completing tasks; organizations without a row
+-- complete tasks without checks
+CREATE TABLE IF NOT EXISTS organization_task_policies (
+    organization_id UUID PRIMARY KEY,
+    require_criteria_coverage BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
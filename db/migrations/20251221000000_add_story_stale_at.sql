@@ -0,0 +1,7 @@
+-- When a draft story was flagged as stale for going untouched too long.
+-- Cleared once the story is edited or leaves draft.
+ALTER TABLE stories ADD COLUMN IF NOT EXISTS stale_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_stories_stale_drafts
+    ON stories (updated_at)
+    WHERE status = 'draft' AND deleted_at IS NULL;
@@ -0,0 +1,16 @@
+-- Stories moved into or out of a sprint, kept so sprint health can measure
+-- scope churn once the sprint has started.
+CREATE TABLE IF NOT EXISTS sprint_scope_changes (
+    id UUID PRIMARY KEY,
+    sprint_id UUID NOT NULL REFERENCES sprints(id) ON DELETE CASCADE,
+    story_id UUID NOT NULL,
+    organization_id UUID,
+    story_points INTEGER,
+    added BOOLEAN NOT NULL,
+    -- Sprint status at the time; changes while 'active' are churn
+    sprint_status TEXT NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_sprint_scope_changes_sprint
+    ON sprint_scope_changes (sprint_id, changed_at);
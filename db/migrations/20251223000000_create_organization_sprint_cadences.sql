@@ -0,0 +1,14 @@
+-- An organization's default sprint cadence. With auto_activate set, a
+-- scheduled job completes each team's sprint when it ends, rolling its
+-- unfinished stories over, and activates the next planned sprint when it
+-- starts. Organizations without a row schedule on the built-in default and
+-- start and end sprints by hand.
+CREATE TABLE IF NOT EXISTS organization_sprint_cadences (
+    organization_id UUID PRIMARY KEY,
+    length_days INTEGER NOT NULL DEFAULT 14 CHECK (length_days BETWEEN 7 AND 28),
+    -- Days from Monday, 0 to 6
+    start_weekday SMALLINT NOT NULL DEFAULT 0 CHECK (start_weekday BETWEEN 0 AND 6),
+    auto_activate BOOLEAN NOT NULL DEFAULT FALSE,
+    rollover TEXT NOT NULL DEFAULT 'next_sprint' CHECK (rollover IN ('next_sprint', 'backlog')),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,16 @@
+-- Browsers that asked to receive Web Push notifications. Each row is one
+-- PushSubscription from the browser's Push API: the push service endpoint and
+-- the keys used to encrypt payloads for it. A browser that subscribes again
+-- replaces its row; rows are dropped when the push service reports the
+-- subscription gone.
+CREATE TABLE IF NOT EXISTS push_subscriptions (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    endpoint TEXT NOT NULL UNIQUE,
+    -- base64url P-256 public key and auth secret from the subscription
+    p256dh TEXT NOT NULL,
+    auth TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_push_subscriptions_user ON push_subscriptions(user_id);
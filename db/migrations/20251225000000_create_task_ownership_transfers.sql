@@ -0,0 +1,24 @@
+-- Proposals to hand a task to someone else. The owner proposes, the
+-- recipient accepts or declines, and the task keeps its status and estimate
+-- when it changes hands. Resolved rows stay as the task's ownership history.
+CREATE TABLE IF NOT EXISTS task_ownership_transfers (
+    id UUID PRIMARY KEY,
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    organization_id UUID,
+    from_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    to_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    note TEXT,
+    -- The owner is stuck and cannot go on, as when an AI agent hands off to a person
+    handoff BOOLEAN NOT NULL DEFAULT FALSE,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'accepted', 'declined', 'cancelled')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_ownership_transfers_task
+    ON task_ownership_transfers(task_id, created_at DESC);
+
+-- A task has at most one open proposal
+CREATE UNIQUE INDEX IF NOT EXISTS idx_task_ownership_transfers_pending
+    ON task_ownership_transfers(task_id) WHERE status = 'pending';
@@ -0,0 +1,17 @@
+-- Per-project guardrails (architecture rules, forbidden dependencies and
+-- coding standards) that prompt-builder injects into every plan and task
+-- pack. required_categories lists the categories that must have rules
+-- before packs are generated for the project.
+CREATE TABLE IF NOT EXISTS project_guardrails (
+    project_id UUID PRIMARY KEY,
+    organization_id UUID,
+    architecture_rules TEXT[] NOT NULL DEFAULT '{}',
+    forbidden_dependencies TEXT[] NOT NULL DEFAULT '{}',
+    coding_standards TEXT[] NOT NULL DEFAULT '{}',
+    required_categories TEXT[] NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- The guardrails section each pack was generated with
+ALTER TABLE plan_packs ADD COLUMN IF NOT EXISTS guardrails JSONB NOT NULL DEFAULT '{}'::JSONB;
+ALTER TABLE task_packs ADD COLUMN IF NOT EXISTS guardrails JSONB NOT NULL DEFAULT '{}'::JSONB;
@@ -0,0 +1,8 @@
+-- Plan packs are reviewed before task packs are generated from them. New
+-- packs start as drafts; packs that already exist were in use without review
+-- and are treated as approved.
+ALTER TABLE plan_packs ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'approved'
+    CHECK (status IN ('draft', 'approved'));
+ALTER TABLE plan_packs ALTER COLUMN status SET DEFAULT 'draft';
+ALTER TABLE plan_packs ADD COLUMN IF NOT EXISTS approved_by TEXT;
+ALTER TABLE plan_packs ADD COLUMN IF NOT EXISTS approved_at TIMESTAMPTZ;
@@ -0,0 +1,15 @@
+-- Organization glossary: team-specific terms with their definitions and
+-- synonyms. Entries whose term or synonyms appear in a story are passed to
+-- the LLM when readiness and prompt-builder generate content for it.
+CREATE TABLE IF NOT EXISTS organization_glossary_terms (
+    id UUID PRIMARY KEY,
+    organization_id UUID NOT NULL,
+    term TEXT NOT NULL,
+    definition TEXT NOT NULL,
+    synonyms TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_organization_glossary_terms_term
+    ON organization_glossary_terms (organization_id, LOWER(term));
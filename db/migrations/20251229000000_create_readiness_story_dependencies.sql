@@ -0,0 +1,12 @@
+-- Readiness projection of story dependencies: a story depends on the stories
+-- it mentions by short key. Kept in step by StoryDependenciesChanged events
+-- so evaluations can flag stories waiting on unready work.
+CREATE TABLE IF NOT EXISTS readiness_story_dependencies (
+    story_id UUID NOT NULL,
+    depends_on_id UUID NOT NULL,
+    organization_id UUID,
+    PRIMARY KEY (story_id, depends_on_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_readiness_story_dependencies_depends_on
+    ON readiness_story_dependencies (depends_on_id);
@@ -0,0 +1,15 @@
+-- Each user's saved sprint task board swimlanes, per organization. A missing
+-- row means the default: one lane per story.
+CREATE TABLE IF NOT EXISTS task_board_configurations (
+    user_id UUID NOT NULL,
+    organization_id UUID,
+    group_by TEXT NOT NULL,
+    custom_field TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_task_board_configurations_user
+    ON task_board_configurations (
+        user_id,
+        COALESCE(organization_id, '00000000-0000-0000-0000-000000000000'::uuid)
+    );
@@ -0,0 +1,38 @@
+-- Bulk story imports, queued and worked through a chunk at a time.
+-- processed_items is the position of the next item to create and moves in the
+-- same transaction as the stories it covers, so a job resumed after a crash
+-- picks up exactly where it stopped. published_items trails it until the
+-- created stories have been announced on the event bus.
+CREATE TABLE IF NOT EXISTS import_jobs (
+    id UUID PRIMARY KEY,
+    project_id UUID NOT NULL,
+    organization_id UUID,
+    created_by UUID,
+    format TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'queued',
+    total_items INTEGER NOT NULL,
+    processed_items INTEGER NOT NULL DEFAULT 0,
+    published_items INTEGER NOT NULL DEFAULT 0,
+    created_items INTEGER NOT NULL DEFAULT 0,
+    failed_items INTEGER NOT NULL DEFAULT 0,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    -- A running job whose lease has expired lost its worker and is claimed again
+    lease_expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_import_jobs_pending
+    ON import_jobs (created_at)
+    WHERE status IN ('queued', 'running');
+
+CREATE TABLE IF NOT EXISTS import_job_items (
+    job_id UUID NOT NULL REFERENCES import_jobs(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    payload JSONB NOT NULL,
+    story_id UUID,
+    error TEXT,
+    PRIMARY KEY (job_id, position)
+);
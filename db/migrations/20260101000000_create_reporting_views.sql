@@ -0,0 +1,118 @@
+-- Stable, read-only views for BI tools such as Metabase. They are a contract:
+-- internal tables may change, but these columns keep their names, types and
+-- meaning. A breaking change ships as a new view (vw_stories_v2) next to the
+-- old one, which is dropped only after a deprecation period. The contract is
+-- served at GET /api/v1/admin/reporting-views.
+CREATE SCHEMA IF NOT EXISTS reporting;
+
+CREATE OR REPLACE VIEW reporting.vw_stories AS
+SELECT
+    s.id AS story_id,
+    s.organization_id,
+    s.project_id,
+    p.name AS project_name,
+    s.short_key,
+    s.title,
+    s.story_type,
+    s.status,
+    s.story_points,
+    s.sprint_id,
+    s.assigned_to_user_id AS assignee_user_id,
+    COALESCE(s.labels, '{}') AS labels,
+    s.readiness_override AS readiness_overridden,
+    s.stale_at IS NOT NULL AS is_stale,
+    s.created_at,
+    s.updated_at
+FROM stories s
+JOIN projects p ON p.id = s.project_id
+WHERE s.deleted_at IS NULL;
+
+CREATE OR REPLACE VIEW reporting.vw_sprint_metrics AS
+SELECT
+    sp.id AS sprint_id,
+    t.organization_id,
+    sp.team_id,
+    t.name AS team_name,
+    sp.name AS sprint_name,
+    sp.status,
+    sp.start_date,
+    sp.end_date,
+    sp.capacity_points,
+    sp.committed_points,
+    sp.completed_points,
+    CASE WHEN sp.committed_points > 0
+        THEN ROUND(sp.completed_points::NUMERIC / sp.committed_points, 4)
+    END AS completion_ratio,
+    story_totals.story_count,
+    story_totals.accepted_story_count,
+    story_totals.accepted_points,
+    scope.points_added_after_start,
+    scope.points_removed_after_start
+FROM sprints sp
+JOIN teams t ON t.id = sp.team_id
+CROSS JOIN LATERAL (
+    SELECT
+        COUNT(*) AS story_count,
+        COUNT(*) FILTER (WHERE st.status = 'accepted') AS accepted_story_count,
+        COALESCE(SUM(st.story_points) FILTER (WHERE st.status = 'accepted'), 0) AS accepted_points
+    FROM stories st
+    WHERE st.sprint_id = sp.id AND st.deleted_at IS NULL
+) story_totals
+CROSS JOIN LATERAL (
+    SELECT
+        COALESCE(SUM(c.story_points) FILTER (WHERE c.added), 0) AS points_added_after_start,
+        COALESCE(SUM(c.story_points) FILTER (WHERE NOT c.added), 0) AS points_removed_after_start
+    FROM sprint_scope_changes c
+    WHERE c.sprint_id = sp.id AND c.sprint_status = 'active'
+) scope;
+
+CREATE OR REPLACE VIEW reporting.vw_readiness_scores AS
+SELECT DISTINCT ON (e.story_id)
+    e.story_id,
+    s.organization_id,
+    s.project_id,
+    e.score,
+    cardinality(e.missing_items) AS missing_item_count,
+    e.missing_items,
+    COUNT(*) OVER (PARTITION BY e.story_id) AS evaluation_count,
+    e.evaluated_at
+FROM readiness_evals e
+JOIN stories s ON s.id = e.story_id AND s.deleted_at IS NULL
+ORDER BY e.story_id, e.evaluated_at DESC;
+
+COMMENT ON VIEW reporting.vw_stories IS 'One row per story that has not been deleted';
+COMMENT ON COLUMN reporting.vw_stories.story_type IS 'feature, bug, spike or chore';
+COMMENT ON COLUMN reporting.vw_stories.status IS 'draft, needsrefinement, ready, committed, inprogress, taskscomplete, deployed, awaitingacceptance or accepted';
+COMMENT ON COLUMN reporting.vw_stories.story_points IS 'Estimate from 1 to 8; null until estimated';
+COMMENT ON COLUMN reporting.vw_stories.sprint_id IS 'The sprint the story is planned into, if any';
+COMMENT ON COLUMN reporting.vw_stories.readiness_overridden IS 'Marked ready by hand despite its readiness score';
+COMMENT ON COLUMN reporting.vw_stories.is_stale IS 'A draft left untouched long enough to be flagged';
+
+COMMENT ON VIEW reporting.vw_sprint_metrics IS 'One row per sprint with its commitment, delivery and scope churn';
+COMMENT ON COLUMN reporting.vw_sprint_metrics.status IS 'planning, active, review or completed';
+COMMENT ON COLUMN reporting.vw_sprint_metrics.committed_points IS 'Points committed when the sprint started';
+COMMENT ON COLUMN reporting.vw_sprint_metrics.completed_points IS 'Points recorded as completed for the sprint';
+COMMENT ON COLUMN reporting.vw_sprint_metrics.completion_ratio IS 'completed_points / committed_points, to 4 decimals; null without a commitment';
+COMMENT ON COLUMN reporting.vw_sprint_metrics.accepted_points IS 'Points of the stories now in the sprint that are accepted';
+COMMENT ON COLUMN reporting.vw_sprint_metrics.points_added_after_start IS 'Points of stories added while the sprint was active';
+COMMENT ON COLUMN reporting.vw_sprint_metrics.points_removed_after_start IS 'Points of stories removed while the sprint was active';
+
+COMMENT ON VIEW reporting.vw_readiness_scores IS 'The latest readiness evaluation of each story that has not been deleted';
+COMMENT ON COLUMN reporting.vw_readiness_scores.score IS 'Readiness from 0 to 100';
+COMMENT ON COLUMN reporting.vw_readiness_scores.missing_items IS 'What the evaluation found missing';
+COMMENT ON COLUMN reporting.vw_readiness_scores.evaluation_count IS 'How many times the story has been evaluated';
+
+-- BI connections get this role and see only the reporting schema. Databases
+-- whose migration user cannot create roles grant access by hand instead.
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'reporting_reader') THEN
+        CREATE ROLE reporting_reader NOLOGIN;
+    END IF;
+    GRANT USAGE ON SCHEMA reporting TO reporting_reader;
+    GRANT SELECT ON ALL TABLES IN SCHEMA reporting TO reporting_reader;
+EXCEPTION
+    WHEN insufficient_privilege THEN
+        RAISE NOTICE 'Skipping reporting_reader role: %', SQLERRM;
+END
+$$;
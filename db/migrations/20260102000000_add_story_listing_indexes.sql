@@ -0,0 +1,22 @@
+-- Paged, sorted and filtered listings of a project's stories.
+-- Each sort order walks its own index within the project; id breaks ties so
+-- that pages stay stable.
+CREATE INDEX IF NOT EXISTS idx_stories_project_title
+    ON stories (project_id, title, id)
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_stories_project_created
+    ON stories (project_id, created_at, id)
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_stories_project_updated
+    ON stories (project_id, updated_at, id)
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_stories_project_status
+    ON stories (project_id, status)
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_stories_project_assignee
+    ON stories (project_id, assigned_to_user_id)
+    WHERE deleted_at IS NULL AND assigned_to_user_id IS NOT NULL;
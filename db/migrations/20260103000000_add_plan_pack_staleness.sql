@@ -0,0 +1,15 @@
+-- Plan packs go stale when their story's acceptance criteria or description
+-- change after generation. story_description records what the pack was
+-- generated from; packs generated before it was recorded leave it NULL and
+-- are only compared on their acceptance criteria.
+ALTER TABLE plan_packs ADD COLUMN IF NOT EXISTS story_description TEXT;
+ALTER TABLE plan_packs ADD COLUMN IF NOT EXISTS stale_reason TEXT;
+ALTER TABLE plan_packs ADD COLUMN IF NOT EXISTS stale_at TIMESTAMPTZ;
+
+-- Organization-level plan pack preferences. Organizations without a row
+-- leave stale packs for someone to regenerate.
+CREATE TABLE IF NOT EXISTS plan_pack_settings (
+    organization_id UUID PRIMARY KEY,
+    auto_regenerate_stale BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,23 @@
+-- Events a projection worker failed to apply, kept with the full envelope so
+-- operators can retry a single application instead of rehydrating everything.
+-- A row stays after a successful retry, marked resolved.
+
+CREATE TABLE IF NOT EXISTS projection_failures (
+    id UUID PRIMARY KEY,
+    service TEXT NOT NULL,
+    projection TEXT NOT NULL,
+    event_id UUID NOT NULL,
+    event_type TEXT NOT NULL,
+    envelope JSONB NOT NULL,
+    error TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_attempted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_projection_failures_unresolved
+    ON projection_failures (service, event_type, created_at DESC)
+    WHERE resolved_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_projection_failures_event
+    ON projection_failures (event_id);
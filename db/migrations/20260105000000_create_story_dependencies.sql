@@ -0,0 +1,27 @@
+-- Explicit blocking relationships between stories: story_id cannot be
+-- delivered before blocked_by_id is accepted. Dependencies implied by
+-- short-key mentions stay in item_references.
+
+CREATE TABLE IF NOT EXISTS story_dependencies (
+    story_id UUID NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    blocked_by_id UUID NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    organization_id UUID,
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (story_id, blocked_by_id),
+    CHECK (story_id <> blocked_by_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_story_dependencies_blocked_by
+    ON story_dependencies (blocked_by_id);
+CREATE INDEX IF NOT EXISTS idx_story_dependencies_org
+    ON story_dependencies (organization_id);
+
+-- The readiness projection keeps both kinds of dependency; explicit blockers
+-- hold a story back until they are accepted, mentions only while they are drafts
+ALTER TABLE readiness_story_dependencies
+    ADD COLUMN IF NOT EXISTS blocking BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE readiness_story_dependencies
+    DROP CONSTRAINT IF EXISTS readiness_story_dependencies_pkey;
+ALTER TABLE readiness_story_dependencies
+    ADD PRIMARY KEY (story_id, depends_on_id, blocking);
@@ -0,0 +1,3 @@
+-- The organization a user last switched to; requests that name no organization run in it
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS active_organization_id UUID REFERENCES organizations(id) ON DELETE SET NULL;
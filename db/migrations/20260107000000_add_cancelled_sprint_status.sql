@@ -0,0 +1,4 @@
+-- Sprints can be cancelled before they complete; their unaccepted stories return to the backlog
+ALTER TABLE sprints DROP CONSTRAINT IF EXISTS sprints_status_check;
+ALTER TABLE sprints ADD CONSTRAINT sprints_status_check
+    CHECK (status IN ('planning', 'active', 'review', 'completed', 'cancelled'));
@@ -0,0 +1,26 @@
+-- Destructive operations an organization requires a reason for, by action
+-- name (delete_story, override_readiness, cancel_sprint); organizations
+-- without a row never require one
+CREATE TABLE IF NOT EXISTS organization_reason_policies (
+    organization_id UUID PRIMARY KEY,
+    require_reason_for TEXT[] NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Who deleted, overrode or cancelled what, and why. Kept after the entity is
+-- gone, so entity_id is not a reference.
+CREATE TABLE IF NOT EXISTS destructive_action_audit_log (
+    id UUID PRIMARY KEY,
+    organization_id UUID,
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    action TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id UUID NOT NULL,
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_destructive_action_audit_org
+    ON destructive_action_audit_log (organization_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_destructive_action_audit_entity
+    ON destructive_action_audit_log (entity_type, entity_id);
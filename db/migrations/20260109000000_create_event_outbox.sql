@@ -0,0 +1,16 @@
+-- Domain events waiting to be delivered on the event bus, written in the same
+-- transaction as the change they describe. Delivered events are kept for 30
+-- days so projections can be replayed from them.
+CREATE TABLE IF NOT EXISTS event_outbox (
+    sequence BIGSERIAL PRIMARY KEY,
+    event_id UUID NOT NULL UNIQUE,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    envelope JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_outbox_pending
+    ON event_outbox (sequence) WHERE delivered_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_event_outbox_created
+    ON event_outbox (created_at);
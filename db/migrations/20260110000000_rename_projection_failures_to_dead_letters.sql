@@ -0,0 +1,15 @@
+-- Projection workers now retry a failing event with backoff before giving up
+-- on it, so the events kept here are dead letters. `attempts` counts the
+-- worker's attempts plus any retries from the admin endpoints.
+
+ALTER TABLE IF EXISTS projection_failures RENAME TO projection_dead_letters;
+ALTER INDEX IF EXISTS projection_failures_pkey RENAME TO projection_dead_letters_pkey;
+ALTER INDEX IF EXISTS idx_projection_failures_unresolved
+    RENAME TO idx_projection_dead_letters_unresolved;
+ALTER INDEX IF EXISTS idx_projection_failures_event
+    RENAME TO idx_projection_dead_letters_event;
+
+-- Re-driving works through one projection's dead letters, oldest first
+CREATE INDEX IF NOT EXISTS idx_projection_dead_letters_redrive
+    ON projection_dead_letters (service, projection, created_at)
+    WHERE resolved_at IS NULL;
@@ -0,0 +1,23 @@
+-- The sprint services read and write a sprint's project and organization, but
+-- the sprints table only recorded its team. Existing sprints take them from
+-- their team and from the team's oldest project.
+
+ALTER TABLE sprints
+    ADD COLUMN IF NOT EXISTS project_id UUID,
+    ADD COLUMN IF NOT EXISTS organization_id UUID;
+
+UPDATE sprints sp
+SET organization_id = t.organization_id
+FROM teams t
+WHERE t.id = sp.team_id AND sp.organization_id IS NULL;
+
+UPDATE sprints sp
+SET project_id = (
+    SELECT p.id FROM projects p
+    WHERE p.team_id = sp.team_id
+    ORDER BY p.created_at, p.id
+    LIMIT 1
+)
+WHERE sp.project_id IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_sprints_project_status ON sprints (project_id, status);
@@ -0,0 +1,30 @@
+-- The event outbox keeps delivered events for 30 days and is what event
+-- exports read, so every event is no longer stored a second time in
+-- domain_event_journal. Each event is stored with its organization so a
+-- window of one organization's events can be exported.
+ALTER TABLE event_outbox ADD COLUMN IF NOT EXISTS organization_id UUID;
+
+CREATE INDEX IF NOT EXISTS idx_event_outbox_occurred_at
+    ON event_outbox (occurred_at, sequence) WHERE delivered_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_event_outbox_organization
+    ON event_outbox (organization_id, occurred_at) WHERE delivered_at IS NOT NULL;
+
+-- Outbox rows written before this migration
+UPDATE event_outbox AS o
+SET organization_id = j.organization_id
+FROM domain_event_journal AS j
+WHERE j.id = o.event_id AND o.organization_id IS NULL;
+
+-- Journaled events the outbox does not hold yet stay exportable as delivered events
+INSERT INTO event_outbox (event_id, occurred_at, organization_id, envelope, created_at, delivered_at)
+SELECT j.id,
+       j.occurred_at,
+       j.organization_id,
+       jsonb_build_object('id', j.id, 'occurred_at', j.occurred_at, 'event', j.payload),
+       j.recorded_at,
+       j.recorded_at
+FROM domain_event_journal AS j
+ORDER BY j.occurred_at, j.id
+ON CONFLICT (event_id) DO NOTHING;
+
+DROP TABLE IF EXISTS domain_event_journal;
@@ -0,0 +1,7 @@
+-- A user deleted through SCIM keeps a tombstone row (active = false) so the
+-- gateway keeps refusing them in the organization. Tombstones are hidden from
+-- SCIM reads and are cleared when the identity provider provisions the user
+-- again.
+
+ALTER TABLE scim_users
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
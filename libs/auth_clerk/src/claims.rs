@@ -1,3 +1,4 @@
+use base64::{engine::general_purpose, Engine as _};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -32,3 +33,20 @@ pub struct OrgClaim {
     #[serde(rename = "nam")]
     pub name: Option<String>,
 }
+
+/// The `sub` claim of a JWT, read WITHOUT verifying the signature. Only fit
+/// for denying access early; anything that grants access must verify.
+pub fn unverified_subject(token: &str) -> Option<String> {
+    #[derive(Deserialize)]
+    struct Subject {
+        sub: String,
+    }
+
+    let payload = token.split('.').nth(1)?;
+    let bytes = general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    serde_json::from_slice::<Subject>(&bytes)
+        .ok()
+        .map(|subject| subject.sub)
+}
@@ -1,6 +1,8 @@
+use common::outbound_http::OutboundHttpClient;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -22,6 +24,7 @@ pub struct Jwks {
 pub struct JwksCache {
     pub jwks: Arc<RwLock<HashMap<String, Jwk>>>,
     jwks_url: String,
+    http: OutboundHttpClient,
 }
 
 impl JwksCache {
@@ -29,6 +32,9 @@ impl JwksCache {
         Self {
             jwks: Arc::new(RwLock::new(HashMap::new())),
             jwks_url,
+            http: OutboundHttpClient::builder()
+                .request_timeout(Duration::from_secs(5))
+                .build(),
         }
     }
 
@@ -38,7 +44,10 @@ impl JwksCache {
     }
 
     pub async fn refresh(&self) -> Result<(), reqwest::Error> {
-        let res = reqwest::get(&self.jwks_url).await?;
+        let res = self
+            .http
+            .send("auth_clerk.jwks", self.http.get(&self.jwks_url))
+            .await?;
         let jwks: Jwks = res.json().await?;
 
         let mut jwks_map = self.jwks.write().await;
@@ -11,11 +11,13 @@ use axum::{extract::FromRequestParts, http::request::Parts, RequestPartsExt};
 
 use axum_extra::headers::{authorization::Bearer, Authorization};
 use axum_extra::TypedHeader;
-use common::{error_context::ErrorContext, AppError};
+use common::{circuit_breaker::CircuitBreaker, error_context::ErrorContext, AppError};
 use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+/// Identity resolved by the gateway before extraction (API keys, impersonation
+/// tokens); takes precedence over the bearer token
 #[derive(Debug, Clone)]
 pub struct ApiKeyAuthClaims {
     pub sub: String,
@@ -151,8 +153,11 @@ impl JwtVerifier {
             debug_validation.validate_exp = false;
 
             if let Ok(debug_token) = decode::<Claims>(token, &decoding_key, &debug_validation) {
-                tracing::info!("JWT token issuer: {}", debug_token.claims.iss);
-                tracing::info!("Expected issuer: {:?}", self.validation.iss);
+                tracing::debug!(
+                    issuer = %debug_token.claims.iss,
+                    expected = ?self.validation.iss,
+                    "JWT issuer check"
+                );
             }
         }
 
@@ -189,10 +194,14 @@ impl JwtVerifier {
         }
 
         tracing::debug!("Key ID '{}' not in cache, refreshing JWKS", kid);
-        self.jwks_cache.refresh().await.map_err(|e| {
-            tracing::error!("Failed to refresh JWKS cache: {}", e);
-            AppError::ExternalServiceError(format!("Failed to refresh JWKS: {}", e))
-        })?;
+        CircuitBreaker::for_dependency("clerk_jwks")
+            .call(async {
+                self.jwks_cache.refresh().await.map_err(|e| {
+                    tracing::error!("Failed to refresh JWKS cache: {}", e);
+                    AppError::ExternalServiceError(format!("Failed to refresh JWKS: {}", e))
+                })
+            })
+            .await?;
 
         self.jwks_cache.get_key(kid).await.ok_or_else(|| {
             tracing::error!("Key ID '{}' not found in JWKS after refresh", kid);
@@ -0,0 +1,109 @@
+//! Anonymized product analytics: feature adoption and the story funnel
+//! (created → ready → accepted).
+//!
+//! Usecases hand events to an [`AnalyticsEmitter`]. Events carry only an event
+//! name, the organization and the subject (e.g. the story) they concern, never
+//! content or people. Before storage both ids are replaced by salted hashes
+//! ([`AnalyticsEvent::anonymize`]), so stored analytics cannot be joined back to
+//! an organization without the salt. Emitters skip organizations that opted out.
+
+use chrono::{DateTime, Utc};
+use ring::digest;
+use uuid::Uuid;
+
+pub const STORY_CREATED: &str = "story.created";
+pub const STORY_READY: &str = "story.ready";
+pub const STORY_ACCEPTED: &str = "story.accepted";
+
+pub const FEATURE_TASK_SPLIT: &str = "feature.task_split";
+pub const FEATURE_STANDUP_NARRATIVE: &str = "feature.standup_narrative";
+
+#[derive(Debug, Clone)]
+pub struct AnalyticsEvent {
+    pub name: &'static str,
+    pub organization_id: Option<Uuid>,
+    /// What the event is about, e.g. the story moving through the funnel
+    pub subject_id: Option<Uuid>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl AnalyticsEvent {
+    pub fn new(
+        name: &'static str,
+        organization_id: Option<Uuid>,
+        subject_id: Option<Uuid>,
+    ) -> Self {
+        Self {
+            name,
+            organization_id,
+            subject_id,
+            occurred_at: Utc::now(),
+        }
+    }
+
+    /// The event with its ids replaced by hashes under `salt`
+    pub fn anonymize(&self, salt: &str) -> AnonymizedAnalyticsEvent {
+        AnonymizedAnalyticsEvent {
+            name: self.name,
+            organization_hash: self.organization_id.map(|id| hash_id(salt, id)),
+            subject_hash: self.subject_id.map(|id| hash_id(salt, id)),
+            occurred_at: self.occurred_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnonymizedAnalyticsEvent {
+    pub name: &'static str,
+    pub organization_hash: Option<String>,
+    pub subject_hash: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Hex SHA-256 of the salted id; stable for a given salt
+pub fn hash_id(salt: &str, id: Uuid) -> String {
+    let hash = digest::digest(&digest::SHA256, format!("{}:{}", salt, id).as_bytes());
+    hash.as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Receives analytics events. Emitting must not fail or slow down the request,
+/// so implementations persist in the background and only log their own errors.
+pub trait AnalyticsEmitter: Send + Sync {
+    fn emit(&self, event: AnalyticsEvent);
+}
+
+/// Discards everything; the default when no analytics store is wired in
+pub struct NoopAnalyticsEmitter;
+
+impl AnalyticsEmitter for NoopAnalyticsEmitter {
+    fn emit(&self, _event: AnalyticsEvent) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_are_anonymized_with_the_salt() {
+        let organization_id = Uuid::new_v4();
+        let story_id = Uuid::new_v4();
+        let event = AnalyticsEvent::new(STORY_READY, Some(organization_id), Some(story_id));
+
+        let anonymized = event.anonymize("salt");
+        let organization_hash = anonymized.organization_hash.clone().unwrap();
+        assert_eq!(organization_hash.len(), 64);
+        assert!(!organization_hash.contains(&organization_id.simple().to_string()));
+        assert_eq!(anonymized, event.anonymize("salt"));
+        assert_ne!(
+            event.anonymize("other").organization_hash,
+            Some(organization_hash)
+        );
+        assert_ne!(anonymized.subject_hash, anonymized.organization_hash);
+
+        let personal = AnalyticsEvent::new(FEATURE_TASK_SPLIT, None, None).anonymize("salt");
+        assert!(personal.organization_hash.is_none());
+    }
+}
@@ -0,0 +1,219 @@
+//! Sprint calendar arithmetic in a project's time zone.
+//!
+//! Sprint boundaries are stored as UTC instants, but "today", "days remaining"
+//! and "working days" depend on where the team is. [`SprintCalendar`] does
+//! those calculations in an IANA time zone so that days which are 23 or 25
+//! hours long across DST transitions still count as one day.
+
+use chrono::{DateTime, Datelike, Duration, LocalResult, NaiveDate, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::i18n::LocalizedMessage;
+use crate::AppError;
+
+/// Time zone used when a project has none configured
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+
+/// Validate an IANA time zone name such as `Europe/Berlin`
+pub fn parse_timezone(name: &str) -> Result<Tz, AppError> {
+    name.trim().parse::<Tz>().map_err(|_| {
+        AppError::invalid(
+            LocalizedMessage::new("validation.invalid_value")
+                .with("field", "timezone")
+                .with("value", name),
+        )
+    })
+}
+
+/// The zone shared by every project of a team. Teams without projects, or
+/// whose projects disagree, use UTC.
+pub fn shared_timezone<S: AsRef<str>>(zones: &[S]) -> SprintCalendar {
+    let mut parsed = zones
+        .iter()
+        .filter_map(|zone| zone.as_ref().parse::<Tz>().ok());
+    match (parsed.next(), parsed.next()) {
+        (Some(tz), None) => SprintCalendar::new(tz),
+        (Some(first), Some(second)) if first == second && parsed.all(|tz| tz == first) => {
+            SprintCalendar::new(first)
+        }
+        _ => SprintCalendar::default(),
+    }
+}
+
+/// Day-based sprint calculations in one time zone
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SprintCalendar {
+    tz: Tz,
+}
+
+impl Default for SprintCalendar {
+    fn default() -> Self {
+        Self { tz: Tz::UTC }
+    }
+}
+
+/// One local calendar day as a half-open UTC range, for bucketing events
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DayBucket {
+    pub date: NaiveDate,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+impl SprintCalendar {
+    pub fn new(tz: Tz) -> Self {
+        Self { tz }
+    }
+
+    /// Calendar for a stored zone name, falling back to UTC if it is unknown
+    pub fn from_name(name: &str) -> Self {
+        name.parse::<Tz>().map(Self::new).unwrap_or_else(|_| {
+            tracing::warn!(timezone = name, "Unknown time zone, using UTC");
+            Self::default()
+        })
+    }
+
+    pub fn timezone(&self) -> Tz {
+        self.tz
+    }
+
+    /// The local date of an instant
+    pub fn local_date(&self, instant: DateTime<Utc>) -> NaiveDate {
+        instant.with_timezone(&self.tz).date_naive()
+    }
+
+    /// The first instant of a local date. When midnight is skipped by a DST
+    /// transition, the day starts at the first local time that exists.
+    pub fn start_of_day(&self, date: NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+        for minutes in (0..=24 * 60).step_by(15) {
+            match self
+                .tz
+                .from_local_datetime(&(midnight + Duration::minutes(minutes)))
+            {
+                LocalResult::Single(local) => return local.with_timezone(&Utc),
+                LocalResult::Ambiguous(earliest, _) => return earliest.with_timezone(&Utc),
+                LocalResult::None => continue,
+            }
+        }
+        Utc.from_utc_datetime(&midnight)
+    }
+
+    /// UTC instants for a sprint running from the start of `start` to the end of
+    /// `end` (inclusive) in local time
+    pub fn sprint_bounds(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> (DateTime<Utc>, DateTime<Utc>) {
+        let end_exclusive = self.start_of_day(end + Duration::days(1));
+        (
+            self.start_of_day(start),
+            end_exclusive - Duration::seconds(1),
+        )
+    }
+
+    /// Local calendar days from today until the sprint's last day; 0 on the
+    /// last day and negative once the sprint is over
+    pub fn days_remaining(&self, end: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+        (self.local_date(end) - self.local_date(now)).num_days()
+    }
+
+    /// Monday to Friday between two local dates, inclusive
+    pub fn working_days(&self, start: NaiveDate, end: NaiveDate) -> u32 {
+        start
+            .iter_days()
+            .take_while(|day| *day <= end)
+            .filter(|day| !matches!(day.weekday(), Weekday::Sat | Weekday::Sun))
+            .count() as u32
+    }
+
+    /// One bucket per local day the range touches, e.g. for burndown charts
+    pub fn daily_buckets(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<DayBucket> {
+        let (first, last) = (self.local_date(from), self.local_date(to));
+        first
+            .iter_days()
+            .take_while(|day| *day <= last)
+            .map(|date| DayBucket {
+                date,
+                starts_at: self.start_of_day(date),
+                ends_at: self.start_of_day(date + Duration::days(1)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn utc(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_sprint_bounds_follow_local_midnight() {
+        let sydney = SprintCalendar::new(parse_timezone("Australia/Sydney").unwrap());
+        let (start, end) = sydney.sprint_bounds(date(2025, 3, 3), date(2025, 3, 14));
+        assert_eq!(start, utc("2025-03-02T13:00:00Z"));
+        assert_eq!(end, utc("2025-03-14T12:59:59Z"));
+
+        assert!(parse_timezone("Mars/Olympus_Mons").is_err());
+    }
+
+    #[test]
+    fn test_days_remaining_uses_local_dates() {
+        let la = SprintCalendar::new(parse_timezone("America/Los_Angeles").unwrap());
+        // 23:59:59 on the 13th in LA
+        let end = utc("2025-06-14T06:59:59Z");
+        // Already the 13th in UTC, but still the evening of the 12th in LA
+        let now = utc("2025-06-13T02:00:00Z");
+
+        assert_eq!(la.days_remaining(end, now), 1);
+        assert_eq!(SprintCalendar::default().days_remaining(end, now), 1);
+        assert_eq!(la.days_remaining(end, utc("2025-06-13T20:00:00Z")), 0);
+    }
+
+    #[test]
+    fn test_dst_days_are_single_buckets() {
+        let berlin = SprintCalendar::new(parse_timezone("Europe/Berlin").unwrap());
+        // Clocks go forward on 30 March 2025 and back on 26 October 2025
+        let spring = berlin.daily_buckets(utc("2025-03-30T10:00:00Z"), utc("2025-03-30T12:00:00Z"));
+        assert_eq!(spring.len(), 1);
+        assert_eq!(spring[0].ends_at - spring[0].starts_at, Duration::hours(23));
+
+        let autumn = berlin.daily_buckets(utc("2025-10-25T12:00:00Z"), utc("2025-10-26T12:00:00Z"));
+        assert_eq!(autumn.len(), 2);
+        assert_eq!(autumn[1].ends_at - autumn[1].starts_at, Duration::hours(25));
+
+        // Midnight does not exist in Santiago when DST starts
+        let santiago = SprintCalendar::new(parse_timezone("America/Santiago").unwrap());
+        let start = santiago.start_of_day(date(2025, 9, 7));
+        assert_eq!(santiago.local_date(start), date(2025, 9, 7));
+    }
+
+    #[test]
+    fn test_working_days_and_shared_timezone() {
+        let calendar = SprintCalendar::default();
+        assert_eq!(
+            calendar.working_days(date(2025, 3, 3), date(2025, 3, 16)),
+            10
+        );
+
+        assert_eq!(
+            shared_timezone(&["Europe/Berlin", "Europe/Berlin"]).timezone(),
+            Tz::Europe__Berlin
+        );
+        assert_eq!(
+            shared_timezone(&["Europe/Berlin", "Asia/Tokyo"]).timezone(),
+            Tz::UTC
+        );
+        assert_eq!(shared_timezone::<&str>(&[]).timezone(), Tz::UTC);
+    }
+}
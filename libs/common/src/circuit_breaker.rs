@@ -0,0 +1,306 @@
+//! Circuit breakers for external dependencies (LLM providers, Clerk JWKS, ...).
+//!
+//! Each dependency gets one shared breaker that tracks the outcome of its most
+//! recent calls. When the failure rate crosses the threshold the breaker opens
+//! and calls fail fast with `ExternalServiceError`. After a cool-down a single
+//! probe call is let through: success closes the breaker, failure re-opens it.
+
+use crate::AppError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitBreakerConfig {
+    /// How many recent calls the failure rate is computed over
+    pub window_size: usize,
+    /// Calls needed in the window before the breaker may open
+    pub minimum_calls: usize,
+    /// Failure share (0.0-1.0) at or above which the breaker opens
+    pub failure_rate_threshold: f64,
+    /// How long the breaker stays open before letting a probe through
+    pub open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            window_size: 20,
+            minimum_calls: 5,
+            failure_rate_threshold: 0.5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerSnapshot {
+    pub dependency: String,
+    pub state: BreakerState,
+    pub failure_rate: f64,
+    pub recent_calls: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opened_at: Option<DateTime<Utc>>,
+}
+
+enum Phase {
+    Closed,
+    Open { retry_at: Instant },
+    HalfOpen { probe_in_flight: bool },
+}
+
+struct Inner {
+    phase: Phase,
+    /// `true` for failures, newest last
+    outcomes: VecDeque<bool>,
+    opened_at: Option<DateTime<Utc>>,
+}
+
+pub struct CircuitBreaker {
+    dependency: String,
+    config: CircuitBreakerConfig,
+    inner: Mutex<Inner>,
+}
+
+fn registry() -> &'static Mutex<HashMap<String, Arc<CircuitBreaker>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, Arc<CircuitBreaker>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// State of every registered breaker, for health reporting
+pub fn snapshots() -> Vec<CircuitBreakerSnapshot> {
+    let breakers: Vec<Arc<CircuitBreaker>> = registry()
+        .lock()
+        .map(|map| map.values().cloned().collect())
+        .unwrap_or_default();
+    let mut snapshots: Vec<_> = breakers.iter().map(|breaker| breaker.snapshot()).collect();
+    snapshots.sort_by(|a, b| a.dependency.cmp(&b.dependency));
+    snapshots
+}
+
+impl CircuitBreaker {
+    pub fn new(dependency: impl Into<String>, config: CircuitBreakerConfig) -> Self {
+        Self {
+            dependency: dependency.into(),
+            config,
+            inner: Mutex::new(Inner {
+                phase: Phase::Closed,
+                outcomes: VecDeque::new(),
+                opened_at: None,
+            }),
+        }
+    }
+
+    /// The process-wide breaker for a dependency, created with the default config on first use
+    pub fn for_dependency(dependency: &str) -> Arc<Self> {
+        let mut map = registry()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        map.entry(dependency.to_string())
+            .or_insert_with(|| Arc::new(Self::new(dependency, CircuitBreakerConfig::default())))
+            .clone()
+    }
+
+    /// Run `call` through the breaker. Any `Err` counts as a failure, so wrap only
+    /// the part that talks to the dependency.
+    pub async fn call<T, F>(&self, call: F) -> Result<T, AppError>
+    where
+        F: Future<Output = Result<T, AppError>>,
+    {
+        let permit = self.acquire()?;
+        let result = call.await;
+        permit.finish(result.is_ok());
+        result
+    }
+
+    fn acquire(&self) -> Result<Permit<'_>, AppError> {
+        let mut inner = self.lock();
+        match inner.phase {
+            Phase::Closed => {}
+            Phase::Open { retry_at } if Instant::now() >= retry_at => {
+                tracing::info!(dependency = %self.dependency, "Circuit half-open; sending probe");
+                inner.phase = Phase::HalfOpen {
+                    probe_in_flight: true,
+                };
+            }
+            Phase::HalfOpen {
+                probe_in_flight: false,
+            } => {
+                inner.phase = Phase::HalfOpen {
+                    probe_in_flight: true,
+                };
+            }
+            Phase::Open { .. } | Phase::HalfOpen { .. } => {
+                return Err(AppError::ExternalServiceError(format!(
+                    "{} is unavailable (circuit open)",
+                    self.dependency
+                )));
+            }
+        }
+        Ok(Permit {
+            breaker: self,
+            finished: false,
+        })
+    }
+
+    fn record(&self, success: bool) {
+        let mut inner = self.lock();
+        match inner.phase {
+            Phase::HalfOpen { .. } if success => {
+                tracing::info!(dependency = %self.dependency, "Circuit closed after successful probe");
+                inner.phase = Phase::Closed;
+                inner.outcomes.clear();
+                inner.opened_at = None;
+            }
+            Phase::HalfOpen { .. } => self.open(&mut inner),
+            Phase::Closed => {
+                inner.outcomes.push_back(!success);
+                while inner.outcomes.len() > self.config.window_size {
+                    inner.outcomes.pop_front();
+                }
+                if inner.outcomes.len() >= self.config.minimum_calls
+                    && failure_rate(&inner.outcomes) >= self.config.failure_rate_threshold
+                {
+                    self.open(&mut inner);
+                }
+            }
+            // A call admitted before the breaker opened; the breaker already knows
+            Phase::Open { .. } => {}
+        }
+    }
+
+    fn open(&self, inner: &mut Inner) {
+        tracing::warn!(
+            dependency = %self.dependency,
+            failure_rate = failure_rate(&inner.outcomes),
+            "Circuit opened"
+        );
+        inner.phase = Phase::Open {
+            retry_at: Instant::now() + self.config.open_duration,
+        };
+        inner.opened_at = Some(Utc::now());
+    }
+
+    pub fn snapshot(&self) -> CircuitBreakerSnapshot {
+        let inner = self.lock();
+        CircuitBreakerSnapshot {
+            dependency: self.dependency.clone(),
+            state: match inner.phase {
+                Phase::Closed => BreakerState::Closed,
+                Phase::Open { .. } => BreakerState::Open,
+                Phase::HalfOpen { .. } => BreakerState::HalfOpen,
+            },
+            failure_rate: failure_rate(&inner.outcomes),
+            recent_calls: inner.outcomes.len(),
+            opened_at: inner.opened_at,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn failure_rate(outcomes: &VecDeque<bool>) -> f64 {
+    if outcomes.is_empty() {
+        0.0
+    } else {
+        outcomes.iter().filter(|failed| **failed).count() as f64 / outcomes.len() as f64
+    }
+}
+
+/// Records a failure if the call is dropped before it finishes, so a cancelled
+/// probe cannot leave the breaker stuck half-open
+struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    finished: bool,
+}
+
+impl Permit<'_> {
+    fn finish(mut self, success: bool) {
+        self.finished = true;
+        self.breaker.record(success);
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.breaker.record(false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(open_duration: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(
+            "test",
+            CircuitBreakerConfig {
+                window_size: 4,
+                minimum_calls: 4,
+                failure_rate_threshold: 0.5,
+                open_duration,
+            },
+        )
+    }
+
+    async fn fail(breaker: &CircuitBreaker) -> Result<(), AppError> {
+        breaker
+            .call(async { Err::<(), _>(AppError::InternalServerError) })
+            .await
+    }
+
+    async fn succeed(breaker: &CircuitBreaker) -> Result<(), AppError> {
+        breaker.call(async { Ok(()) }).await
+    }
+
+    #[tokio::test]
+    async fn test_opens_at_failure_rate_and_fails_fast() {
+        let breaker = breaker(Duration::from_secs(60));
+        succeed(&breaker).await.unwrap();
+        succeed(&breaker).await.unwrap();
+        let _ = fail(&breaker).await;
+        assert_eq!(breaker.snapshot().state, BreakerState::Closed);
+
+        let _ = fail(&breaker).await;
+        assert_eq!(breaker.snapshot().state, BreakerState::Open);
+        assert!(matches!(
+            succeed(&breaker).await,
+            Err(AppError::ExternalServiceError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_half_open_probe_closes_or_reopens() {
+        let breaker = breaker(Duration::ZERO);
+        for _ in 0..4 {
+            let _ = fail(&breaker).await;
+        }
+        assert_eq!(breaker.snapshot().state, BreakerState::Open);
+
+        // Cool-down has elapsed, so the next call is a probe
+        let _ = fail(&breaker).await;
+        assert_eq!(breaker.snapshot().state, BreakerState::Open);
+
+        succeed(&breaker).await.unwrap();
+        let snapshot = breaker.snapshot();
+        assert_eq!(snapshot.state, BreakerState::Closed);
+        assert_eq!(snapshot.recent_calls, 0);
+    }
+}
@@ -0,0 +1,225 @@
+//! Conditional GET support.
+//!
+//! Handlers tag a response with an [`EntityTag`] built from the versions of
+//! the entities in it (ids and `updated_at` timestamps), which is cheap to
+//! compute and changes whenever any of them does. [`conditional_get_middleware`]
+//! then answers `If-None-Match` requests whose tag still matches with an empty
+//! `304 Not Modified`, so clients polling large payloads skip the download.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderValue, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use ring::digest;
+use std::fmt;
+use uuid::Uuid;
+
+/// Hex digits of the SHA-256 digest kept in a tag
+const TAG_LENGTH: usize = 32;
+
+/// Headers a `304` must repeat from the `200` it stands in for
+const NOT_MODIFIED_HEADERS: [header::HeaderName; 5] = [
+    header::ETAG,
+    header::CACHE_CONTROL,
+    header::VARY,
+    header::CONTENT_LOCATION,
+    header::EXPIRES,
+];
+
+/// A weak validator accumulated from the parts a response is built from
+pub struct EntityTag {
+    context: digest::Context,
+}
+
+impl Default for EntityTag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for EntityTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EntityTag").finish_non_exhaustive()
+    }
+}
+
+impl EntityTag {
+    pub fn new() -> Self {
+        Self {
+            context: digest::Context::new(&digest::SHA256),
+        }
+    }
+
+    /// Mix in an entity's id and last modification time
+    pub fn version(self, id: Uuid, updated_at: DateTime<Utc>) -> Self {
+        self.part(id).part(updated_at.timestamp_micros())
+    }
+
+    /// Mix in anything else the response depends on. Parts are delimited, so
+    /// `("ab", "c")` and `("a", "bc")` give different tags.
+    pub fn part(mut self, part: impl fmt::Display) -> Self {
+        let part = part.to_string();
+        self.context.update(&(part.len() as u64).to_be_bytes());
+        self.context.update(part.as_bytes());
+        self
+    }
+
+    /// The `ETag` header value, e.g. `W/"3f2a…"`
+    pub fn finish(self) -> String {
+        let digest = self.context.finish();
+        let hex: String = digest
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        format!("W/\"{}\"", &hex[..TAG_LENGTH])
+    }
+}
+
+/// A response body sent with an `ETag`. Clients must revalidate before reusing
+/// it, so a stale copy is never shown.
+pub struct Tagged<T>(pub EntityTag, pub T);
+
+impl<T: IntoResponse> IntoResponse for Tagged<T> {
+    fn into_response(self) -> Response {
+        let Tagged(tag, body) = self;
+        let mut response = body.into_response();
+        if let Ok(etag) = HeaderValue::from_str(&tag.finish()) {
+            let headers = response.headers_mut();
+            headers.insert(header::ETAG, etag);
+            headers
+                .entry(header::CACHE_CONTROL)
+                .or_insert(HeaderValue::from_static("private, no-cache"));
+        }
+        response
+    }
+}
+
+/// Whether an `If-None-Match` header matches `etag`, using the weak
+/// comparison RFC 9110 prescribes for this header
+pub fn if_none_match(if_none_match: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    if_none_match
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == etag)
+}
+
+/// Turn a tagged `200` to a `GET` or `HEAD` into an empty `304 Not Modified`
+/// when the request's `If-None-Match` still matches the tag
+pub async fn conditional_get_middleware(req: Request<Body>, next: Next) -> Response {
+    let condition = match *req.method() {
+        Method::GET | Method::HEAD => req
+            .headers()
+            .get(header::IF_NONE_MATCH)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        _ => None,
+    };
+
+    let response = next.run(req).await;
+    let Some(condition) = condition else {
+        return response;
+    };
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+    let matched = response
+        .headers()
+        .get(header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|etag| if_none_match(&condition, etag));
+    if !matched {
+        return response;
+    }
+
+    let mut headers = HeaderMap::new();
+    for name in NOT_MODIFIED_HEADERS {
+        for value in response.headers().get_all(&name) {
+            headers.append(name.clone(), value.clone());
+        }
+    }
+    (StatusCode::NOT_MODIFIED, headers).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::get, Router};
+    use chrono::TimeZone;
+    use tower::ServiceExt;
+
+    fn story_tag(updated_at: DateTime<Utc>) -> String {
+        EntityTag::new().version(Uuid::nil(), updated_at).finish()
+    }
+
+    #[test]
+    fn test_tag_changes_with_version_and_matches_weakly() {
+        let before = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let after = before + chrono::Duration::milliseconds(1);
+        let tag = story_tag(before);
+
+        assert_eq!(tag, story_tag(before));
+        assert_ne!(tag, story_tag(after));
+        assert_ne!(
+            EntityTag::new().part("ab").part("c").finish(),
+            EntityTag::new().part("a").part("bc").finish()
+        );
+
+        let strong = tag.trim_start_matches("W/");
+        assert!(if_none_match(strong, &tag));
+        assert!(if_none_match(&format!("\"other\", {}", tag), &tag));
+        assert!(if_none_match("*", &tag));
+        assert!(!if_none_match(&story_tag(after), &tag));
+    }
+
+    #[tokio::test]
+    async fn test_middleware_answers_matching_requests_with_not_modified() {
+        let updated_at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let app = Router::new()
+            .route(
+                "/story",
+                get(move || async move {
+                    Tagged(
+                        EntityTag::new().version(Uuid::nil(), updated_at),
+                        "a large payload",
+                    )
+                }),
+            )
+            .layer(middleware::from_fn(conditional_get_middleware));
+        let request = |if_none_match: Option<String>| {
+            let mut request = Request::builder().uri("/story");
+            if let Some(tag) = if_none_match {
+                request = request.header(header::IF_NONE_MATCH, tag);
+            }
+            request.body(Body::empty()).unwrap()
+        };
+
+        let first = app.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers()[header::ETAG].to_str().unwrap().to_string();
+
+        let revalidated = app
+            .clone()
+            .oneshot(request(Some(etag.clone())))
+            .await
+            .unwrap();
+        assert_eq!(revalidated.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(revalidated.headers()[header::ETAG], etag.as_str());
+        let body = axum::body::to_bytes(revalidated.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(body.is_empty());
+
+        let stale = app
+            .oneshot(request(Some(story_tag(
+                updated_at - chrono::Duration::seconds(1),
+            ))))
+            .await
+            .unwrap();
+        assert_eq!(stale.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,265 @@
+//! Browser-facing hardening: which origins may call the API cross-origin, and
+//! the security headers added to every response.
+//!
+//! Allowed origins are exact (`https://app.gamalan.dev`) or cover every
+//! subdomain of a host (`https://*.gamalan.dev` allows
+//! `https://pr-42.gamalan.dev` but not `https://gamalan.dev`). Scheme and port
+//! must match either way.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, HeaderMap, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use tower_http::cors::AllowOrigin;
+
+pub const DEFAULT_REFERRER_POLICY: &str = "strict-origin-when-cross-origin";
+/// Applied to documents only. Permits the inline styles API docs such as
+/// Swagger UI use, and nothing from other origins.
+pub const DEFAULT_CONTENT_SECURITY_POLICY: &str = "default-src 'self'; img-src 'self' data:; style-src 'self' 'unsafe-inline'; object-src 'none'; base-uri 'self'; frame-ancestors 'none'";
+/// One year, the minimum HSTS preload lists accept
+pub const DEFAULT_HSTS_MAX_AGE_SECS: u64 = 365 * 24 * 60 * 60;
+
+/// One allowed origin, possibly with a `*.` wildcard for subdomains
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginPattern {
+    scheme: String,
+    /// Lowercase host; for wildcards the suffix after `*`, starting with `.`
+    host: String,
+    wildcard: bool,
+    port: Option<u16>,
+}
+
+impl OriginPattern {
+    /// `None` for anything that is not `scheme://host[:port]`, including a
+    /// bare `*`, which would let any site make credentialed requests
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let (scheme, host, port) = split_origin(pattern.trim())?;
+        let (host, wildcard) = match host.strip_prefix("*.") {
+            Some(domain) => (format!(".{}", domain), true),
+            None => (host, false),
+        };
+        if host.contains('*') || host.trim_start_matches('.').is_empty() {
+            return None;
+        }
+        Some(Self {
+            scheme,
+            host,
+            wildcard,
+            port,
+        })
+    }
+
+    pub fn matches(&self, origin: &str) -> bool {
+        let Some((scheme, host, port)) = split_origin(origin) else {
+            return false;
+        };
+        if scheme != self.scheme || port != self.port {
+            return false;
+        }
+        if !self.wildcard {
+            return host == self.host;
+        }
+        host.strip_suffix(&self.host)
+            .is_some_and(|subdomain| !subdomain.is_empty() && !subdomain.ends_with('.'))
+    }
+}
+
+/// Lowercase scheme, host and explicit port of `scheme://host[:port]`
+fn split_origin(origin: &str) -> Option<(String, String, Option<u16>)> {
+    let (scheme, authority) = origin.split_once("://")?;
+    if scheme.is_empty() || authority.is_empty() || authority.contains(['/', '@', '?', '#']) {
+        return None;
+    }
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port.parse::<u16>().ok()?)),
+        None => (authority, None),
+    };
+    let valid = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '*');
+    if host.is_empty() || !host.chars().all(valid) {
+        return None;
+    }
+    Some((scheme.to_ascii_lowercase(), host.to_ascii_lowercase(), port))
+}
+
+/// The origins allowed to make cross-origin requests
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorsOrigins {
+    patterns: Vec<OriginPattern>,
+}
+
+impl CorsOrigins {
+    /// Parse a comma-separated list, skipping (and logging) invalid entries
+    pub fn parse(list: &str) -> Self {
+        let patterns = list
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                let pattern = OriginPattern::parse(entry);
+                if pattern.is_none() {
+                    tracing::warn!(origin = entry, "Ignoring invalid CORS origin");
+                }
+                pattern
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub fn allows(&self, origin: &HeaderValue) -> bool {
+        origin
+            .to_str()
+            .is_ok_and(|origin| self.patterns.iter().any(|pattern| pattern.matches(origin)))
+    }
+
+    /// For `CorsLayer::allow_origin`; echoes back only the allowed origins
+    pub fn allow_origin(&self) -> AllowOrigin {
+        let origins = self.clone();
+        AllowOrigin::predicate(move |origin, _| origins.allows(origin))
+    }
+}
+
+/// Headers added to every response that does not already set them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityHeaders {
+    /// `Strict-Transport-Security`; `None` where the API is served over plain HTTP
+    pub hsts: Option<HeaderValue>,
+    pub referrer_policy: HeaderValue,
+    /// `Content-Security-Policy` for HTML and SVG documents
+    pub content_security_policy: HeaderValue,
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> Self {
+        Self {
+            hsts: Some(hsts_value(DEFAULT_HSTS_MAX_AGE_SECS)),
+            referrer_policy: HeaderValue::from_static(DEFAULT_REFERRER_POLICY),
+            content_security_policy: HeaderValue::from_static(DEFAULT_CONTENT_SECURITY_POLICY),
+        }
+    }
+}
+
+impl SecurityHeaders {
+    /// Add the headers to `headers`, keeping any a handler already set
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        headers
+            .entry(header::X_CONTENT_TYPE_OPTIONS)
+            .or_insert(HeaderValue::from_static("nosniff"));
+        headers
+            .entry(header::REFERRER_POLICY)
+            .or_insert(self.referrer_policy.clone());
+        if let Some(hsts) = &self.hsts {
+            headers
+                .entry(header::STRICT_TRANSPORT_SECURITY)
+                .or_insert(hsts.clone());
+        }
+
+        let document = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|content_type| {
+                let content_type = content_type.to_ascii_lowercase();
+                content_type.starts_with("text/html") || content_type.starts_with("image/svg+xml")
+            });
+        if document {
+            headers
+                .entry(header::CONTENT_SECURITY_POLICY)
+                .or_insert(self.content_security_policy.clone());
+            headers
+                .entry(header::X_FRAME_OPTIONS)
+                .or_insert(HeaderValue::from_static("DENY"));
+        }
+    }
+}
+
+/// `max-age=<secs>; includeSubDomains`
+pub fn hsts_value(max_age_secs: u64) -> HeaderValue {
+    HeaderValue::from_str(&format!("max-age={}; includeSubDomains", max_age_secs))
+        .expect("HSTS header is ASCII")
+}
+
+/// Adds the configured [`SecurityHeaders`] to every response
+pub async fn security_headers_middleware(
+    State(headers): State<Arc<SecurityHeaders>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let mut response = next.run(req).await;
+    headers.apply(response.headers_mut());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_origins_cover_subdomains_only() {
+        let origins = CorsOrigins::parse(
+            "https://app.gamalan.dev, https://*.preview.gamalan.dev, http://localhost:3000, *, nonsense",
+        );
+        let allows = |origin: &str| origins.allows(&HeaderValue::from_str(origin).unwrap());
+
+        assert!(allows("https://app.gamalan.dev"));
+        assert!(allows("https://APP.gamalan.dev"));
+        assert!(allows("https://pr-42.preview.gamalan.dev"));
+        assert!(allows("https://a.b.preview.gamalan.dev"));
+        assert!(allows("http://localhost:3000"));
+
+        assert!(!allows("https://preview.gamalan.dev"));
+        assert!(!allows("https://evilpreview.gamalan.dev"));
+        assert!(!allows("https://pr-42.preview.gamalan.dev.evil.com"));
+        assert!(!allows("http://pr-42.preview.gamalan.dev"));
+        assert!(!allows("https://app.gamalan.dev:8443"));
+        assert!(!allows("http://localhost:3001"));
+        assert!(!allows("null"));
+        assert_eq!(origins.patterns.len(), 3);
+    }
+
+    #[test]
+    fn test_security_headers_respect_handler_values() {
+        let headers = SecurityHeaders::default();
+
+        let mut json = HeaderMap::new();
+        json.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        json.insert(
+            header::REFERRER_POLICY,
+            HeaderValue::from_static("no-referrer"),
+        );
+        headers.apply(&mut json);
+        assert_eq!(json[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(json[header::REFERRER_POLICY], "no-referrer");
+        assert_eq!(
+            json[header::STRICT_TRANSPORT_SECURITY],
+            "max-age=31536000; includeSubDomains"
+        );
+        assert!(!json.contains_key(header::CONTENT_SECURITY_POLICY));
+
+        let mut html = HeaderMap::new();
+        html.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/html; charset=utf-8"),
+        );
+        SecurityHeaders {
+            hsts: None,
+            ..SecurityHeaders::default()
+        }
+        .apply(&mut html);
+        assert_eq!(
+            html[header::CONTENT_SECURITY_POLICY],
+            DEFAULT_CONTENT_SECURITY_POLICY
+        );
+        assert_eq!(html[header::X_FRAME_OPTIONS], "DENY");
+        assert!(!html.contains_key(header::STRICT_TRANSPORT_SECURITY));
+    }
+}
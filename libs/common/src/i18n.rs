@@ -0,0 +1,409 @@
+//! Localized user-facing messages.
+//!
+//! Errors that should read naturally in the caller's language carry a
+//! [`LocalizedMessage`] (a catalog key plus parameters) instead of a finished
+//! English string. [`locale_middleware`] negotiates the request's locale from
+//! `Accept-Language`, and the message is rendered in that locale when the error
+//! becomes a response. Error codes are never translated, so programmatic
+//! clients keep matching on `code`.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::fmt;
+
+/// Locales with a message catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Fr,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 4] = [Locale::En, Locale::Es, Locale::Fr, Locale::De];
+
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::Fr => "fr",
+            Locale::De => "de",
+        }
+    }
+
+    /// Match a language tag such as `fr-CA` by its primary subtag
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split(['-', '_']).next()?.trim();
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.tag().eq_ignore_ascii_case(primary))
+    }
+
+    /// Pick the supported locale with the highest quality in an
+    /// `Accept-Language` header, falling back to English
+    pub fn negotiate(accept_language: Option<&str>) -> Self {
+        let mut best: Option<(Locale, f32)> = None;
+        for range in accept_language.unwrap_or_default().split(',') {
+            let mut parts = range.split(';');
+            let tag = parts.next().unwrap_or_default().trim();
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            let Some(locale) = Self::from_tag(tag) else {
+                continue;
+            };
+            if quality > 0.0 && best.is_none_or(|(_, best_quality)| quality > best_quality) {
+                best = Some((locale, quality));
+            }
+        }
+        best.map(|(locale, _)| locale).unwrap_or_default()
+    }
+}
+
+tokio::task_local! {
+    static REQUEST_LOCALE: Locale;
+}
+
+/// The locale negotiated for the current request, or English outside one
+pub fn current_locale() -> Locale {
+    REQUEST_LOCALE
+        .try_with(|locale| *locale)
+        .unwrap_or_default()
+}
+
+/// Negotiate the locale from `Accept-Language` for the rest of the request and
+/// report it in `Content-Language`
+pub async fn locale_middleware(req: Request<Body>, next: Next) -> Response {
+    let locale = Locale::negotiate(
+        req.headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok()),
+    );
+
+    let mut response = REQUEST_LOCALE.scope(locale, next.run(req)).await;
+    response
+        .headers_mut()
+        .entry(header::CONTENT_LANGUAGE)
+        .or_insert(HeaderValue::from_static(locale.tag()));
+    response
+}
+
+/// A catalog key and the parameters substituted into its `{name}` placeholders
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalizedMessage {
+    pub key: &'static str,
+    pub params: Vec<(&'static str, String)>,
+}
+
+impl LocalizedMessage {
+    pub fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            params: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.params.push((name, value.to_string()));
+        self
+    }
+
+    /// Render in `locale`, falling back to English, then to the key itself
+    pub fn render(&self, locale: Locale) -> String {
+        let Some(template) = template(self.key, locale).or_else(|| template(self.key, Locale::En))
+        else {
+            return self.key.to_string();
+        };
+
+        self.params
+            .iter()
+            .fold(template.to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), value)
+            })
+    }
+
+    /// Parameters as a JSON object, for clients that render messages themselves
+    pub fn params_json(&self) -> serde_json::Value {
+        self.params
+            .iter()
+            .map(|(name, value)| (name.to_string(), serde_json::json!(value)))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+}
+
+/// Logs and `Display` use English
+impl fmt::Display for LocalizedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(Locale::En))
+    }
+}
+
+fn template(key: &str, locale: Locale) -> Option<&'static str> {
+    use Locale::*;
+
+    let text = match (key, locale) {
+        ("error.internal", En) => "Internal server error",
+        ("error.internal", Es) => "Error interno del servidor",
+        ("error.internal", Fr) => "Erreur interne du serveur",
+        ("error.internal", De) => "Interner Serverfehler",
+
+        ("error.rate_limited", En) => "Rate limit exceeded",
+        ("error.rate_limited", Es) => "Se ha superado el límite de solicitudes",
+        ("error.rate_limited", Fr) => "Limite de requêtes dépassée",
+        ("error.rate_limited", De) => "Anfragelimit überschritten",
+
+        ("error.database", En) => "A database error occurred",
+        ("error.database", Es) => "Se ha producido un error de base de datos",
+        ("error.database", Fr) => "Une erreur de base de données s'est produite",
+        ("error.database", De) => "Ein Datenbankfehler ist aufgetreten",
+
+        ("error.configuration", En) => "Service configuration error",
+        ("error.configuration", Es) => "Error de configuración del servicio",
+        ("error.configuration", Fr) => "Erreur de configuration du service",
+        ("error.configuration", De) => "Fehler in der Dienstkonfiguration",
+
+        ("validation.invalid_value", En) => "Invalid {field}: {value}",
+        ("validation.invalid_value", Es) => "Valor no válido para {field}: {value}",
+        ("validation.invalid_value", Fr) => "Valeur invalide pour {field} : {value}",
+        ("validation.invalid_value", De) => "Ungültiger Wert für {field}: {value}",
+
+        ("validation.too_long", En) => "{field} cannot exceed {max} characters",
+        ("validation.too_long", Es) => "{field} no puede superar los {max} caracteres",
+        ("validation.too_long", Fr) => "{field} ne peut pas dépasser {max} caractères",
+        ("validation.too_long", De) => "{field} darf höchstens {max} Zeichen lang sein",
+
+        ("validation.batch_empty", En) => "Batch must contain at least one update",
+        ("validation.batch_empty", Es) => "El lote debe contener al menos una actualización",
+        ("validation.batch_empty", Fr) => "Le lot doit contenir au moins une mise à jour",
+        ("validation.batch_empty", De) => "Der Stapel muss mindestens eine Änderung enthalten",
+
+        ("validation.batch_too_large", En) => "Batch cannot contain more than {max} updates",
+        ("validation.batch_too_large", Es) => {
+            "El lote no puede contener más de {max} actualizaciones"
+        }
+        ("validation.batch_too_large", Fr) => {
+            "Le lot ne peut pas contenir plus de {max} mises à jour"
+        }
+        ("validation.batch_too_large", De) => {
+            "Der Stapel darf höchstens {max} Änderungen enthalten"
+        }
+
+        ("validation.batch_duplicate", En) => "Task {task_id} appears more than once in the batch",
+        ("validation.batch_duplicate", Es) => {
+            "La tarea {task_id} aparece más de una vez en el lote"
+        }
+        ("validation.batch_duplicate", Fr) => {
+            "La tâche {task_id} apparaît plusieurs fois dans le lot"
+        }
+        ("validation.batch_duplicate", De) => "Die Aufgabe {task_id} kommt mehrfach im Stapel vor",
+
+        ("validation.batch_duplicate_story", En) => {
+            "Story {story_id} appears more than once in the batch"
+        }
+        ("validation.batch_duplicate_story", Es) => {
+            "La historia {story_id} aparece más de una vez en el lote"
+        }
+        ("validation.batch_duplicate_story", Fr) => {
+            "La story {story_id} apparaît plusieurs fois dans le lot"
+        }
+        ("validation.batch_duplicate_story", De) => {
+            "Die Story {story_id} kommt mehrfach im Stapel vor"
+        }
+
+        ("validation.reason_required", En) => "A reason is required for {action}",
+        ("validation.reason_required", Es) => "Se requiere un motivo para {action}",
+        ("validation.reason_required", Fr) => "Un motif est requis pour {action}",
+        ("validation.reason_required", De) => "Für {action} ist eine Begründung erforderlich",
+
+        ("story.title_required", En) => "Story title cannot be empty",
+        ("story.title_required", Es) => "El título de la historia no puede estar vacío",
+        ("story.title_required", Fr) => "Le titre de la story ne peut pas être vide",
+        ("story.title_required", De) => "Der Titel der Story darf nicht leer sein",
+
+        ("story.title_too_long", En) => "Story title cannot exceed {max} characters",
+        ("story.title_too_long", Es) => {
+            "El título de la historia no puede superar los {max} caracteres"
+        }
+        ("story.title_too_long", Fr) => {
+            "Le titre de la story ne peut pas dépasser {max} caractères"
+        }
+        ("story.title_too_long", De) => {
+            "Der Titel der Story darf höchstens {max} Zeichen lang sein"
+        }
+
+        ("task.title_required", En) => "Task title cannot be empty",
+        ("task.title_required", Es) => "El título de la tarea no puede estar vacío",
+        ("task.title_required", Fr) => "Le titre de la tâche ne peut pas être vide",
+        ("task.title_required", De) => "Der Titel der Aufgabe darf nicht leer sein",
+
+        ("task.ac_refs_required", En) => {
+            "Task must have at least one acceptance criteria reference"
+        }
+        ("task.ac_refs_required", Es) => {
+            "La tarea debe hacer referencia al menos a un criterio de aceptación"
+        }
+        ("task.ac_refs_required", Fr) => {
+            "La tâche doit référencer au moins un critère d'acceptation"
+        }
+        ("task.ac_refs_required", De) => {
+            "Die Aufgabe muss mindestens ein Akzeptanzkriterium referenzieren"
+        }
+
+        ("task.ac_ref_blank", En) => "Acceptance criteria references cannot be empty",
+        ("task.ac_ref_blank", Es) => {
+            "Las referencias a criterios de aceptación no pueden estar vacías"
+        }
+        ("task.ac_ref_blank", Fr) => {
+            "Les références aux critères d'acceptation ne peuvent pas être vides"
+        }
+        ("task.ac_ref_blank", De) => "Verweise auf Akzeptanzkriterien dürfen nicht leer sein",
+
+        ("task.estimate_zero", En) => "Estimated hours must be greater than 0",
+        ("task.estimate_zero", Es) => "Las horas estimadas deben ser mayores que 0",
+        ("task.estimate_zero", Fr) => "Les heures estimées doivent être supérieures à 0",
+        ("task.estimate_zero", De) => "Die geschätzten Stunden müssen größer als 0 sein",
+
+        ("task.estimate_too_large", En) => {
+            "Task cannot exceed {max} hours (split into smaller tasks)"
+        }
+        ("task.estimate_too_large", Es) => {
+            "La tarea no puede superar las {max} horas (divídela en tareas más pequeñas)"
+        }
+        ("task.estimate_too_large", Fr) => {
+            "La tâche ne peut pas dépasser {max} heures (découpez-la en tâches plus petites)"
+        }
+        ("task.estimate_too_large", De) => {
+            "Die Aufgabe darf höchstens {max} Stunden umfassen (in kleinere Aufgaben aufteilen)"
+        }
+
+        ("quota.projects_exceeded", En) => {
+            "Your {plan} plan allows {limit} projects. Upgrade the plan or delete a project first."
+        }
+        ("quota.projects_exceeded", Es) => {
+            "Tu plan {plan} permite {limit} proyectos. Mejora el plan o elimina un proyecto primero."
+        }
+        ("quota.projects_exceeded", Fr) => {
+            "Votre offre {plan} autorise {limit} projets. Passez à une offre supérieure ou supprimez d'abord un projet."
+        }
+        ("quota.projects_exceeded", De) => {
+            "Ihr Tarif {plan} erlaubt {limit} Projekte. Wechseln Sie den Tarif oder löschen Sie zuerst ein Projekt."
+        }
+
+        ("quota.stories_exceeded", En) => {
+            "Your {plan} plan allows {limit} stories. Upgrade the plan or delete stories first."
+        }
+        ("quota.stories_exceeded", Es) => {
+            "Tu plan {plan} permite {limit} historias. Mejora el plan o elimina historias primero."
+        }
+        ("quota.stories_exceeded", Fr) => {
+            "Votre offre {plan} autorise {limit} stories. Passez à une offre supérieure ou supprimez d'abord des stories."
+        }
+        ("quota.stories_exceeded", De) => {
+            "Ihr Tarif {plan} erlaubt {limit} Storys. Wechseln Sie den Tarif oder löschen Sie zuerst Storys."
+        }
+
+        ("quota.llm_calls_exceeded", En) => {
+            "Your {plan} plan allows {limit} AI generations per month. Upgrade the plan or wait for next month."
+        }
+        ("quota.llm_calls_exceeded", Es) => {
+            "Tu plan {plan} permite {limit} generaciones con IA al mes. Mejora el plan o espera al próximo mes."
+        }
+        ("quota.llm_calls_exceeded", Fr) => {
+            "Votre offre {plan} autorise {limit} générations IA par mois. Passez à une offre supérieure ou attendez le mois prochain."
+        }
+        ("quota.llm_calls_exceeded", De) => {
+            "Ihr Tarif {plan} erlaubt {limit} KI-Generierungen pro Monat. Wechseln Sie den Tarif oder warten Sie bis zum nächsten Monat."
+        }
+
+        _ => return None,
+    };
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_accept_language() {
+        assert_eq!(Locale::negotiate(None), Locale::En);
+        assert_eq!(
+            Locale::negotiate(Some("fr-CA,fr;q=0.9,en;q=0.8")),
+            Locale::Fr
+        );
+        assert_eq!(Locale::negotiate(Some("en;q=0.5, de")), Locale::De);
+        assert_eq!(Locale::negotiate(Some("ja, es;q=0.3")), Locale::Es);
+        assert_eq!(Locale::negotiate(Some("ja, zh")), Locale::En);
+        assert_eq!(Locale::negotiate(Some("es;q=0")), Locale::En);
+    }
+
+    #[test]
+    fn test_render_substitutes_params_and_falls_back() {
+        let message = LocalizedMessage::new("task.estimate_too_large").with("max", 40);
+        assert_eq!(
+            message.render(Locale::En),
+            "Task cannot exceed 40 hours (split into smaller tasks)"
+        );
+        assert!(message.render(Locale::De).contains("40 Stunden"));
+        assert_eq!(message.params_json()["max"], "40");
+
+        assert_eq!(
+            LocalizedMessage::new("no.such.key").render(Locale::Fr),
+            "no.such.key"
+        );
+    }
+
+    #[test]
+    fn test_every_key_has_every_locale() {
+        let keys = [
+            "error.internal",
+            "error.rate_limited",
+            "error.database",
+            "error.configuration",
+            "validation.invalid_value",
+            "validation.too_long",
+            "validation.batch_empty",
+            "validation.batch_too_large",
+            "validation.batch_duplicate",
+            "validation.batch_duplicate_story",
+            "validation.reason_required",
+            "story.title_required",
+            "story.title_too_long",
+            "task.title_required",
+            "task.ac_refs_required",
+            "task.ac_ref_blank",
+            "task.estimate_zero",
+            "task.estimate_too_large",
+            "quota.projects_exceeded",
+            "quota.stories_exceeded",
+            "quota.llm_calls_exceeded",
+        ];
+        for key in keys {
+            for locale in Locale::ALL {
+                assert!(
+                    template(key, locale).is_some(),
+                    "{} missing for {}",
+                    key,
+                    locale.tag()
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_current_locale_is_scoped_to_request() {
+        assert_eq!(current_locale(), Locale::En);
+        let inside = REQUEST_LOCALE
+            .scope(Locale::Es, async { current_locale() })
+            .await;
+        assert_eq!(inside, Locale::Es);
+    }
+}
@@ -1,6 +1,6 @@
 use axum::{
     body::Body,
-    http::{Request, StatusCode},
+    http::{header, HeaderValue, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
     Json,
@@ -10,26 +10,46 @@ use serde_json::json;
 use std::collections::HashMap;
 use std::time::Instant;
 use thiserror::Error;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, Instrument};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+pub mod analytics;
+pub mod calendar;
+pub mod circuit_breaker;
 pub mod error_context;
+pub mod etag;
 pub mod feature_flags;
+pub mod http_security;
+pub mod i18n;
+pub mod llm_audit;
 pub mod observability;
+pub mod outbound_http;
+pub mod quota;
+pub mod reason_policy;
+pub mod references;
+pub mod route_registry;
 
 use error_context::ErrorContext;
+use i18n::{current_locale, LocalizedMessage};
 
 const X_REQUEST_ID: &str = "x-request-id";
+/// Longer client-supplied request IDs are replaced with a generated one
+const MAX_REQUEST_ID_LENGTH: usize = 128;
 
 /// Initialize production-ready tracing for a service
 pub fn init_tracing(service_name: &str) {
     if let Err(e) = observability::init_production_tracing(service_name) {
         eprintln!("Failed to initialize tracing: {}", e);
         // Fallback to basic tracing, but only if no subscriber is already set
-        if tracing::subscriber::set_global_default(tracing_subscriber::fmt().json().finish())
-            .is_err()
-        {
+        let fallback = tracing_subscriber::fmt()
+            .json()
+            .with_writer(observability::redaction::RedactingMakeWriter::new(
+                std::io::stdout,
+                observability::redaction::RedactionConfig::from_env(),
+            ))
+            .finish();
+        if tracing::subscriber::set_global_default(fallback).is_err() {
             eprintln!("Tracing subscriber already initialized, skipping fallback setup");
         }
     }
@@ -111,18 +131,24 @@ pub async fn enhanced_request_middleware(req: Request<Body>, next: Next) -> Resp
     response
 }
 
-/// Legacy correlation ID extractor - kept for backward compatibility
+/// Get or create the request ID and echo it on the response. Domain events
+/// published while handling the request carry it as their correlation ID.
 pub async fn correlation_id_extractor(mut req: Request<Body>, next: Next) -> Response {
     let id = req
         .headers()
         .get(X_REQUEST_ID)
         .and_then(|v| v.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LENGTH)
         .map(|s| s.to_string())
         .unwrap_or_else(|| Uuid::new_v4().to_string());
 
     req.headers_mut().insert(X_REQUEST_ID, id.parse().unwrap());
 
-    let mut res = next.run(req).await;
+    let span = tracing::info_span!("request", request_id = %id);
+    let mut res = event_bus::EventCorrelation::from_request(id.clone())
+        .scope(next.run(req))
+        .instrument(span)
+        .await;
 
     res.headers_mut().insert(X_REQUEST_ID, id.parse().unwrap());
 
@@ -149,6 +175,17 @@ pub enum AppError {
     #[error("bad request: {0}")]
     BadRequest(String),
 
+    /// An error whose message is rendered in the request's negotiated locale.
+    /// `code` is never translated.
+    #[error("{message}")]
+    Localized {
+        #[schema(value_type = u16)]
+        status: StatusCode,
+        code: String,
+        #[schema(value_type = String)]
+        message: LocalizedMessage,
+    },
+
     #[error("bad request: {message}")]
     BadRequestWithContext {
         message: String,
@@ -172,12 +209,23 @@ pub enum AppError {
     #[error("conflict: {0}")]
     Conflict(String),
 
+    /// A conflict clients are expected to handle, identified by `error_code`
+    #[error("conflict: {message}")]
+    ConflictWithCode { message: String, error_code: String },
+
     #[error("rate limit exceeded")]
     RateLimitExceeded,
 
     #[error("external service error: {0}")]
     ExternalServiceError(String),
 
+    /// Temporarily refusing requests; sent with a `Retry-After` header
+    #[error("service unavailable: {message}")]
+    ServiceUnavailable {
+        message: String,
+        retry_after_secs: u64,
+    },
+
     #[error("database error: {message}")]
     DatabaseError {
         message: String,
@@ -222,6 +270,17 @@ pub struct DebugInfo {
     pub stack_trace: Option<String>,
 }
 
+impl AppError {
+    /// A `BAD_REQUEST` validation error with a localized message
+    pub fn invalid(message: LocalizedMessage) -> Self {
+        AppError::Localized {
+            status: StatusCode::BAD_REQUEST,
+            code: "BAD_REQUEST".to_string(),
+            message,
+        }
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let is_debug = std::env::var("RUST_ENV")
@@ -255,7 +314,7 @@ impl IntoResponse for AppError {
                     "INTERNAL_SERVER_ERROR".to_string(),
                     create_error_response(
                         "INTERNAL_SERVER_ERROR",
-                        "Internal server error",
+                        &LocalizedMessage::new("error.internal").render(current_locale()),
                         None,
                         debug_info,
                         is_debug,
@@ -319,6 +378,25 @@ impl IntoResponse for AppError {
                     create_error_response("BAD_REQUEST", msg, None, None, is_debug),
                 )
             }
+            AppError::Localized {
+                status,
+                code,
+                message,
+            } => {
+                info!(code = %code, message_key = message.key, "{}", message);
+                let mut response = create_error_response(
+                    code,
+                    &message.render(current_locale()),
+                    None,
+                    None,
+                    is_debug,
+                );
+                response.error.details = Some(json!({
+                    "message_key": message.key,
+                    "params": message.params_json(),
+                }));
+                (*status, code.clone(), response)
+            }
             AppError::BadRequestWithContext {
                 message,
                 details,
@@ -418,6 +496,17 @@ impl IntoResponse for AppError {
                     create_error_response("CONFLICT", msg, None, None, is_debug),
                 )
             }
+            AppError::ConflictWithCode {
+                message,
+                error_code,
+            } => {
+                info!(error_code = %error_code, "Conflict: {}", message);
+                (
+                    StatusCode::CONFLICT,
+                    error_code.clone(),
+                    create_error_response(error_code, message, None, None, is_debug),
+                )
+            }
             AppError::RateLimitExceeded => {
                 info!("Rate limit exceeded");
                 (
@@ -425,7 +514,7 @@ impl IntoResponse for AppError {
                     "RATE_LIMIT_EXCEEDED".to_string(),
                     create_error_response(
                         "RATE_LIMIT_EXCEEDED",
-                        "Rate limit exceeded",
+                        &LocalizedMessage::new("error.rate_limited").render(current_locale()),
                         None,
                         None,
                         is_debug,
@@ -440,6 +529,14 @@ impl IntoResponse for AppError {
                     create_error_response("EXTERNAL_SERVICE_ERROR", msg, None, None, is_debug),
                 )
             }
+            AppError::ServiceUnavailable { message, .. } => {
+                info!("Service unavailable: {}", message);
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "SERVICE_UNAVAILABLE".to_string(),
+                    create_error_response("SERVICE_UNAVAILABLE", message, None, None, is_debug),
+                )
+            }
             AppError::DatabaseError {
                 message,
                 operation,
@@ -473,7 +570,7 @@ impl IntoResponse for AppError {
                     "DATABASE_ERROR".to_string(),
                     create_error_response(
                         "DATABASE_ERROR",
-                        "A database error occurred",
+                        &LocalizedMessage::new("error.database").render(current_locale()),
                         Some(context.request_id.clone()),
                         debug_info,
                         is_debug,
@@ -513,7 +610,7 @@ impl IntoResponse for AppError {
                     "CONFIGURATION_ERROR".to_string(),
                     create_error_response(
                         "CONFIGURATION_ERROR",
-                        "Service configuration error",
+                        &LocalizedMessage::new("error.configuration").render(current_locale()),
                         Some(context.request_id.clone()),
                         debug_info,
                         is_debug,
@@ -526,7 +623,16 @@ impl IntoResponse for AppError {
         // TODO: Re-enable metrics when the feature is properly configured
         // observability::metrics::increment_error_counter(&error_code);
 
-        (status, Json(error_response)).into_response()
+        let mut response = (status, Json(error_response)).into_response();
+        if let AppError::ServiceUnavailable {
+            retry_after_secs, ..
+        } = self
+        {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+        }
+        response
     }
 }
 
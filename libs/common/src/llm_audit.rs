@@ -0,0 +1,113 @@
+//! Opt-in capture of LLM prompts and responses for debugging generations.
+//!
+//! LLM adapters hand every exchange to an [`LlmAuditSink`]; the sink decides
+//! whether the organization has opted in and how long the record is kept.
+//! Exchanges are scrubbed of email addresses, JWTs and bearer credentials and
+//! capped in size before they leave the adapter, so a sink never sees raw PII.
+
+use crate::observability::redaction::mask_patterns;
+use uuid::Uuid;
+
+/// Longest prompt or response kept, in characters
+pub const MAX_AUDITED_CHARS: usize = 32_000;
+
+#[derive(Debug, Clone)]
+pub struct LlmExchange {
+    pub organization_id: Option<Uuid>,
+    pub story_id: Option<Uuid>,
+    /// Same name the outbound HTTP client uses, e.g. `backlog.openai.task_split`
+    pub call_site: &'static str,
+    pub model: String,
+    pub prompt: String,
+    pub response: Option<String>,
+    pub error: Option<String>,
+}
+
+impl LlmExchange {
+    pub fn new(
+        call_site: &'static str,
+        model: &str,
+        organization_id: Option<Uuid>,
+        story_id: Option<Uuid>,
+        prompt: &str,
+        outcome: Result<&str, String>,
+    ) -> Self {
+        let (response, error) = match outcome {
+            Ok(response) => (Some(scrub(response)), None),
+            Err(error) => (None, Some(scrub(&error))),
+        };
+        Self {
+            organization_id,
+            story_id,
+            call_site,
+            model: model.to_string(),
+            prompt: scrub(prompt),
+            response,
+            error,
+        }
+    }
+}
+
+/// Mask PII patterns and cap the length of text headed for the audit store
+pub fn scrub(text: &str) -> String {
+    let masked = mask_patterns(text);
+    match masked.char_indices().nth(MAX_AUDITED_CHARS) {
+        Some((cut, _)) => format!("{}…[truncated]", &masked[..cut]),
+        None => masked,
+    }
+}
+
+/// Receives LLM exchanges. Recording must not fail or slow down the LLM call,
+/// so implementations persist in the background and only log their own errors.
+pub trait LlmAuditSink: Send + Sync {
+    fn record(&self, exchange: LlmExchange);
+}
+
+/// Discards everything; the default when no audit store is wired in
+pub struct NoopLlmAuditSink;
+
+impl LlmAuditSink for NoopLlmAuditSink {
+    fn record(&self, _exchange: LlmExchange) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exchange_is_scrubbed() {
+        let exchange = LlmExchange::new(
+            "test.call",
+            "gpt-test",
+            None,
+            None,
+            "Story owner jane.doe@example.com asked for this",
+            Ok("Contact jane.doe@example.com"),
+        );
+        assert_eq!(
+            exchange.prompt,
+            "Story owner [REDACTED_EMAIL] asked for this"
+        );
+        assert_eq!(
+            exchange.response.as_deref(),
+            Some("Contact [REDACTED_EMAIL]")
+        );
+        assert!(exchange.error.is_none());
+
+        let failed = LlmExchange::new("test.call", "gpt-test", None, None, "p", Err("boom".into()));
+        assert_eq!(failed.error.as_deref(), Some("boom"));
+        assert!(failed.response.is_none());
+    }
+
+    #[test]
+    fn test_scrub_truncates_long_text() {
+        let long = "é".repeat(MAX_AUDITED_CHARS + 10);
+        let scrubbed = scrub(&long);
+        assert!(scrubbed.ends_with("…[truncated]"));
+        assert_eq!(
+            scrubbed.chars().count(),
+            MAX_AUDITED_CHARS + "…[truncated]".chars().count()
+        );
+        assert_eq!(scrub("short"), "short");
+    }
+}
@@ -1,4 +1,7 @@
+pub mod redaction;
+
 use color_eyre::eyre::Result;
+use redaction::{RedactingMakeWriter, RedactionConfig};
 use std::env;
 use tracing_subscriber::{
     fmt::{self, format::JsonFields},
@@ -39,7 +42,11 @@ pub fn init_production_tracing(service_name: &str) -> Result<()> {
             .with_thread_names(true)
             .json()
             .fmt_fields(JsonFields::new())
-            .with_span_events(fmt::format::FmtSpan::CLOSE);
+            .with_span_events(fmt::format::FmtSpan::CLOSE)
+            .with_writer(RedactingMakeWriter::new(
+                std::io::stdout,
+                RedactionConfig::from_env(),
+            ));
 
         let error_layer = tracing_error::ErrorLayer::default();
 
@@ -119,8 +126,13 @@ fn setup_panic_handler(service_name: &str) {
 
 /// Create a detailed health check response with subsystem status
 pub async fn detailed_health_check() -> serde_json::Value {
+    let breakers = crate::circuit_breaker::snapshots();
+    let degraded = breakers
+        .iter()
+        .any(|breaker| breaker.state != crate::circuit_breaker::BreakerState::Closed);
+
     serde_json::json!({
-        "status": "healthy",
+        "status": if degraded { "degraded" } else { "healthy" },
         "timestamp": chrono::Utc::now(),
         "version": env!("CARGO_PKG_VERSION"),
         "service": env!("CARGO_PKG_NAME"),
@@ -128,6 +140,8 @@ pub async fn detailed_health_check() -> serde_json::Value {
         "subsystems": {
             "logging": check_logging_health(),
             "memory": check_memory_health(),
+            "circuit_breakers": breakers,
+            "outbound_http": crate::outbound_http::call_site_stats(),
         }
     })
 }
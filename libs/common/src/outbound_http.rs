@@ -0,0 +1,251 @@
+//! Shared client for outbound HTTP calls to third parties (LLM providers, Clerk, ...).
+//!
+//! Every client gets the same connect and request timeouts. Idempotent requests
+//! (GET, HEAD, PUT, DELETE, OPTIONS) are retried a bounded number of times with
+//! jittered exponential backoff on connection errors, timeouts, 429 and 5xx
+//! responses. Each call site keeps request, failure, retry and latency counters,
+//! reported by the detailed health check.
+
+use rand::Rng;
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub struct OutboundHttpPolicy {
+    pub connect_timeout: Duration,
+    /// Whole-request timeout, covering reading the response body
+    pub request_timeout: Duration,
+    /// Retries after the first attempt, for idempotent requests only
+    pub max_retries: u32,
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for OutboundHttpPolicy {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(5),
+            request_timeout: Duration::from_secs(30),
+            max_retries: 2,
+            base_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+pub struct OutboundHttpClientBuilder {
+    policy: OutboundHttpPolicy,
+}
+
+impl OutboundHttpClientBuilder {
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.policy.connect_timeout = timeout;
+        self
+    }
+
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.policy.request_timeout = timeout;
+        self
+    }
+
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.policy.max_retries = retries;
+        self
+    }
+
+    pub fn build(self) -> OutboundHttpClient {
+        let client = reqwest::Client::builder()
+            .connect_timeout(self.policy.connect_timeout)
+            .timeout(self.policy.request_timeout)
+            .build()
+            .unwrap_or_else(|e| {
+                tracing::error!(error = %e, "Failed to build outbound HTTP client; using defaults");
+                reqwest::Client::new()
+            });
+
+        OutboundHttpClient {
+            client,
+            policy: self.policy,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OutboundHttpClient {
+    client: reqwest::Client,
+    policy: OutboundHttpPolicy,
+}
+
+impl Default for OutboundHttpClient {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl OutboundHttpClient {
+    pub fn builder() -> OutboundHttpClientBuilder {
+        OutboundHttpClientBuilder {
+            policy: OutboundHttpPolicy::default(),
+        }
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    pub fn put(&self, url: &str) -> RequestBuilder {
+        self.client.put(url)
+    }
+
+    pub fn delete(&self, url: &str) -> RequestBuilder {
+        self.client.delete(url)
+    }
+
+    /// Send `request`, retrying idempotent requests per the policy. `call_site`
+    /// names the caller in metrics, e.g. `"readiness.openai.chat"`.
+    pub async fn send(
+        &self,
+        call_site: &'static str,
+        request: RequestBuilder,
+    ) -> Result<Response, reqwest::Error> {
+        let mut request = request.build()?;
+        let started = Instant::now();
+        let retries = if is_idempotent(request.method()) {
+            self.policy.max_retries
+        } else {
+            0
+        };
+
+        let mut attempt = 0;
+        let result = loop {
+            // Requests with streaming bodies cannot be cloned and get a single attempt
+            let retry = if attempt < retries {
+                request.try_clone()
+            } else {
+                None
+            };
+            let result = self.client.execute(request).await;
+
+            match retry {
+                Some(next) if should_retry(&result) => {
+                    let delay = backoff_delay(&self.policy, attempt, rand::thread_rng().gen());
+                    tracing::warn!(
+                        call_site,
+                        attempt = attempt + 1,
+                        delay_ms = delay.as_millis() as u64,
+                        "Retrying outbound request"
+                    );
+                    record(call_site, |stats| stats.retries += 1);
+                    tokio::time::sleep(delay).await;
+                    request = next;
+                    attempt += 1;
+                }
+                _ => break result,
+            }
+        };
+
+        let failed = !matches!(&result, Ok(response) if response.status().is_success());
+        let elapsed = started.elapsed();
+        record(call_site, |stats| {
+            stats.requests += 1;
+            stats.total_latency_ms += elapsed.as_millis() as u64;
+            if failed {
+                stats.failures += 1;
+            }
+        });
+        result
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    )
+}
+
+fn should_retry(result: &Result<Response, reqwest::Error>) -> bool {
+    match result {
+        Ok(response) => {
+            let status = response.status();
+            status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+        }
+        Err(e) => e.is_connect() || e.is_timeout(),
+    }
+}
+
+/// Exponential backoff capped at `max_backoff`, with "full jitter": a uniform
+/// share of the capped delay picked by `jitter` (0.0-1.0)
+fn backoff_delay(policy: &OutboundHttpPolicy, attempt: u32, jitter: f64) -> Duration {
+    let exponential = policy
+        .base_backoff
+        .saturating_mul(2u32.saturating_pow(attempt));
+    exponential
+        .min(policy.max_backoff)
+        .mul_f64(jitter.clamp(0.0, 1.0))
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CallSiteStats {
+    pub call_site: String,
+    pub requests: u64,
+    pub failures: u64,
+    pub retries: u64,
+    pub total_latency_ms: u64,
+}
+
+fn stats_registry() -> &'static Mutex<HashMap<&'static str, CallSiteStats>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<&'static str, CallSiteStats>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn record(call_site: &'static str, update: impl FnOnce(&mut CallSiteStats)) {
+    let mut registry = stats_registry()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    update(registry.entry(call_site).or_insert_with(|| CallSiteStats {
+        call_site: call_site.to_string(),
+        ..CallSiteStats::default()
+    }));
+}
+
+/// Counters for every call site that has sent a request, for health reporting
+pub fn call_site_stats() -> Vec<CallSiteStats> {
+    let mut stats: Vec<CallSiteStats> = stats_registry()
+        .lock()
+        .map(|registry| registry.values().cloned().collect())
+        .unwrap_or_default();
+    stats.sort_by(|a, b| a.call_site.cmp(&b.call_site));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay_grows_and_is_capped() {
+        let policy = OutboundHttpPolicy::default();
+        assert_eq!(backoff_delay(&policy, 0, 1.0), Duration::from_millis(200));
+        assert_eq!(backoff_delay(&policy, 2, 1.0), Duration::from_millis(800));
+        assert_eq!(backoff_delay(&policy, 10, 1.0), Duration::from_secs(2));
+        assert_eq!(backoff_delay(&policy, 2, 0.5), Duration::from_millis(400));
+        assert_eq!(backoff_delay(&policy, 2, 0.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_only_idempotent_methods_are_retried() {
+        assert!(is_idempotent(&Method::GET));
+        assert!(is_idempotent(&Method::PUT));
+        assert!(is_idempotent(&Method::DELETE));
+        assert!(!is_idempotent(&Method::POST));
+        assert!(!is_idempotent(&Method::PATCH));
+    }
+}
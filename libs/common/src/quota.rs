@@ -0,0 +1,295 @@
+//! Plan tiers and the usage limits they put on an organization.
+//!
+//! Usecases ask a [`QuotaGuard`] before creating projects or stories and before
+//! every LLM call. Work outside an organization is never limited. Exceeding a
+//! limit fails with a `QUOTA_EXCEEDED` error whose message names the plan and
+//! the limit, so clients can point users at an upgrade.
+
+use crate::i18n::LocalizedMessage;
+use crate::AppError;
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const QUOTA_EXCEEDED_CODE: &str = "QUOTA_EXCEEDED";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PlanTier {
+    #[default]
+    Free,
+    Team,
+    Enterprise,
+}
+
+impl PlanTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Free => "free",
+            Self::Team => "team",
+            Self::Enterprise => "enterprise",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "free" => Some(Self::Free),
+            "team" => Some(Self::Team),
+            "enterprise" => Some(Self::Enterprise),
+            _ => None,
+        }
+    }
+
+    /// The tier's built-in limits; `None` is unlimited
+    pub fn limits(&self) -> PlanLimits {
+        match self {
+            Self::Free => PlanLimits {
+                max_projects: Some(3),
+                max_stories: Some(500),
+                max_llm_calls_per_month: Some(200),
+            },
+            Self::Team => PlanLimits {
+                max_projects: Some(25),
+                max_stories: Some(10_000),
+                max_llm_calls_per_month: Some(5_000),
+            },
+            Self::Enterprise => PlanLimits {
+                max_projects: None,
+                max_stories: None,
+                max_llm_calls_per_month: None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanLimits {
+    pub max_projects: Option<u64>,
+    pub max_stories: Option<u64>,
+    pub max_llm_calls_per_month: Option<u64>,
+}
+
+impl PlanLimits {
+    /// These limits, with any limit set in `overrides` taking precedence
+    pub fn with_overrides(self, overrides: PlanLimits) -> Self {
+        Self {
+            max_projects: overrides.max_projects.or(self.max_projects),
+            max_stories: overrides.max_stories.or(self.max_stories),
+            max_llm_calls_per_month: overrides
+                .max_llm_calls_per_month
+                .or(self.max_llm_calls_per_month),
+        }
+    }
+}
+
+/// An organization's tier and the limits that apply to it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationPlan {
+    pub tier: PlanTier,
+    pub limits: PlanLimits,
+}
+
+impl OrganizationPlan {
+    pub fn new(tier: PlanTier, overrides: PlanLimits) -> Self {
+        Self {
+            tier,
+            limits: tier.limits().with_overrides(overrides),
+        }
+    }
+
+    /// Fail with `QUOTA_EXCEEDED` when `additional` more units would go over the limit
+    pub fn ensure_within(
+        &self,
+        resource: QuotaResource,
+        used: u64,
+        additional: u64,
+    ) -> Result<(), AppError> {
+        match resource.limit(&self.limits) {
+            Some(limit) if used.saturating_add(additional) > limit => {
+                Err(quota_exceeded(self.tier, resource, limit))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for OrganizationPlan {
+    fn default() -> Self {
+        Self::new(PlanTier::default(), PlanLimits::default())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum QuotaResource {
+    Projects,
+    Stories,
+    /// LLM calls in the current calendar month (UTC)
+    LlmCalls,
+}
+
+impl QuotaResource {
+    pub fn limit(&self, limits: &PlanLimits) -> Option<u64> {
+        match self {
+            Self::Projects => limits.max_projects,
+            Self::Stories => limits.max_stories,
+            Self::LlmCalls => limits.max_llm_calls_per_month,
+        }
+    }
+
+    fn message_key(&self) -> &'static str {
+        match self {
+            Self::Projects => "quota.projects_exceeded",
+            Self::Stories => "quota.stories_exceeded",
+            Self::LlmCalls => "quota.llm_calls_exceeded",
+        }
+    }
+}
+
+/// How much of one limited resource an organization has used
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaUsage {
+    pub resource: QuotaResource,
+    pub used: u64,
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+}
+
+impl QuotaUsage {
+    pub fn new(resource: QuotaResource, used: u64, limits: &PlanLimits) -> Self {
+        let limit = resource.limit(limits);
+        Self {
+            resource,
+            used,
+            limit,
+            remaining: limit.map(|limit| limit.saturating_sub(used)),
+        }
+    }
+}
+
+/// A 402 with code `QUOTA_EXCEEDED`, rendered in the request's locale
+pub fn quota_exceeded(tier: PlanTier, resource: QuotaResource, limit: u64) -> AppError {
+    AppError::Localized {
+        status: StatusCode::PAYMENT_REQUIRED,
+        code: QUOTA_EXCEEDED_CODE.to_string(),
+        message: LocalizedMessage::new(resource.message_key())
+            .with("plan", tier.as_str())
+            .with("limit", limit),
+    }
+}
+
+/// Start of the calendar month (UTC) that monthly limits are counted in
+pub fn billing_period_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// Start of the following billing period
+pub fn billing_period_end(now: DateTime<Utc>) -> DateTime<Utc> {
+    let (year, month) = match now.month() {
+        12 => (now.year() + 1, 1),
+        month => (now.year(), month + 1),
+    };
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// Checks an organization's usage against its plan
+#[async_trait]
+pub trait QuotaGuard: Send + Sync {
+    /// Fail when creating `additional` more of the resource would exceed the plan
+    async fn ensure_capacity(
+        &self,
+        organization_id: Option<Uuid>,
+        resource: QuotaResource,
+        additional: u64,
+    ) -> Result<(), AppError>;
+
+    /// Count one LLM call against the monthly limit, failing instead when the
+    /// limit has been reached
+    async fn consume_llm_call(&self, organization_id: Option<Uuid>) -> Result<(), AppError>;
+}
+
+/// Allows everything; the default when no quota store is wired in
+pub struct UnlimitedQuotaGuard;
+
+#[async_trait]
+impl QuotaGuard for UnlimitedQuotaGuard {
+    async fn ensure_capacity(
+        &self,
+        _organization_id: Option<Uuid>,
+        _resource: QuotaResource,
+        _additional: u64,
+    ) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn consume_llm_call(&self, _organization_id: Option<Uuid>) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Locale;
+
+    #[test]
+    fn test_overrides_replace_tier_limits() {
+        let plan = OrganizationPlan::new(
+            PlanTier::Free,
+            PlanLimits {
+                max_projects: Some(10),
+                ..PlanLimits::default()
+            },
+        );
+        assert_eq!(plan.limits.max_projects, Some(10));
+        assert_eq!(plan.limits.max_stories, Some(500));
+
+        plan.ensure_within(QuotaResource::Projects, 9, 1).unwrap();
+        let error = plan
+            .ensure_within(QuotaResource::Projects, 10, 1)
+            .unwrap_err();
+        match error {
+            AppError::Localized {
+                status,
+                code,
+                message,
+            } => {
+                assert_eq!(status, StatusCode::PAYMENT_REQUIRED);
+                assert_eq!(code, QUOTA_EXCEEDED_CODE);
+                assert!(message.render(Locale::En).contains("10 projects"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let enterprise = OrganizationPlan::new(PlanTier::Enterprise, PlanLimits::default());
+        enterprise
+            .ensure_within(QuotaResource::LlmCalls, u64::MAX, 1)
+            .unwrap();
+        assert_eq!(
+            QuotaUsage::new(QuotaResource::Stories, 600, &plan.limits).remaining,
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_billing_period_is_the_calendar_month() {
+        let now = Utc.with_ymd_and_hms(2025, 12, 17, 9, 30, 0).unwrap();
+        assert_eq!(
+            billing_period_start(now),
+            Utc.with_ymd_and_hms(2025, 12, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            billing_period_end(now),
+            Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+}
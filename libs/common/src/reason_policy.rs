@@ -0,0 +1,160 @@
+//! Reasons for destructive operations.
+//!
+//! An organization can make a reason mandatory before stories are deleted,
+//! readiness is overridden or sprints are cancelled. Services check the
+//! organization's [`ReasonPolicy`] before acting and keep the reason in the
+//! audit log alongside who did what. A missing reason fails with a
+//! `REASON_REQUIRED` error naming the action.
+
+use crate::i18n::LocalizedMessage;
+use crate::AppError;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+pub const REASON_REQUIRED_CODE: &str = "REASON_REQUIRED";
+/// Longest reason kept, in characters
+pub const MAX_REASON_LENGTH: usize = 1_000;
+
+/// Operations an organization can require a reason for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DestructiveAction {
+    DeleteStory,
+    OverrideReadiness,
+    CancelSprint,
+}
+
+impl DestructiveAction {
+    pub const ALL: [DestructiveAction; 3] = [
+        DestructiveAction::DeleteStory,
+        DestructiveAction::OverrideReadiness,
+        DestructiveAction::CancelSprint,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DeleteStory => "delete_story",
+            Self::OverrideReadiness => "override_readiness",
+            Self::CancelSprint => "cancel_sprint",
+        }
+    }
+
+    /// What the action is performed on, as recorded in the audit log
+    pub fn entity_type(&self) -> &'static str {
+        match self {
+            Self::DeleteStory | Self::OverrideReadiness => "story",
+            Self::CancelSprint => "sprint",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == value)
+    }
+}
+
+/// The destructive operations an organization wants a reason for
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReasonPolicy {
+    #[serde(default)]
+    pub require_reason_for: Vec<DestructiveAction>,
+}
+
+impl ReasonPolicy {
+    /// Policy from the action names stored for an organization; names no
+    /// longer known are ignored
+    pub fn from_stored(actions: &[String]) -> Self {
+        let mut require_reason_for: Vec<DestructiveAction> = Vec::new();
+        for action in actions.iter().filter_map(|a| DestructiveAction::parse(a)) {
+            if !require_reason_for.contains(&action) {
+                require_reason_for.push(action);
+            }
+        }
+        Self { require_reason_for }
+    }
+
+    /// Action names to store, without duplicates
+    pub fn stored(&self) -> Vec<String> {
+        let mut stored: Vec<String> = Vec::new();
+        for action in &self.require_reason_for {
+            let name = action.as_str().to_string();
+            if !stored.contains(&name) {
+                stored.push(name);
+            }
+        }
+        stored
+    }
+
+    pub fn requires(&self, action: DestructiveAction) -> bool {
+        self.require_reason_for.contains(&action)
+    }
+
+    /// The trimmed reason to record for `action`. Blank reasons count as
+    /// missing, which is an error when the policy requires one.
+    pub fn check(
+        &self,
+        action: DestructiveAction,
+        reason: Option<&str>,
+    ) -> Result<Option<String>, AppError> {
+        let reason = reason.map(str::trim).filter(|reason| !reason.is_empty());
+        match reason {
+            Some(reason) if reason.chars().count() > MAX_REASON_LENGTH => Err(AppError::invalid(
+                LocalizedMessage::new("validation.too_long")
+                    .with("field", "reason")
+                    .with("max", MAX_REASON_LENGTH),
+            )),
+            Some(reason) => Ok(Some(reason.to_string())),
+            None if self.requires(action) => Err(AppError::Localized {
+                status: StatusCode::BAD_REQUEST,
+                code: REASON_REQUIRED_CODE.to_string(),
+                message: LocalizedMessage::new("validation.reason_required")
+                    .with("action", action.as_str()),
+            }),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reason_required_only_for_configured_actions() {
+        let policy = ReasonPolicy {
+            require_reason_for: vec![DestructiveAction::DeleteStory],
+        };
+
+        assert!(matches!(
+            policy.check(DestructiveAction::DeleteStory, Some("   ")),
+            Err(AppError::Localized { code, .. }) if code == REASON_REQUIRED_CODE
+        ));
+        assert_eq!(
+            policy
+                .check(DestructiveAction::DeleteStory, Some(" duplicate "))
+                .unwrap(),
+            Some("duplicate".to_string())
+        );
+        assert_eq!(
+            policy.check(DestructiveAction::CancelSprint, None).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stored_actions_round_trip() {
+        let stored = vec![
+            "cancel_sprint".to_string(),
+            "archive_everything".to_string(),
+            "cancel_sprint".to_string(),
+        ];
+        let policy = ReasonPolicy::from_stored(&stored);
+        assert_eq!(
+            policy.require_reason_for,
+            vec![DestructiveAction::CancelSprint]
+        );
+        assert_eq!(policy.stored(), vec!["cancel_sprint".to_string()]);
+    }
+}
@@ -0,0 +1,204 @@
+//! Finds story and task short keys (e.g. `PROJ-123`) mentioned in free text
+//! and in GitHub webhook payloads.
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// True for keys like `PROJ-123`: an uppercase project prefix, a dash and a positive number
+pub fn is_short_key(candidate: &str) -> bool {
+    let Some((prefix, number)) = candidate.split_once('-') else {
+        return false;
+    };
+
+    let mut prefix_chars = prefix.chars();
+    prefix_chars
+        .next()
+        .is_some_and(|first| first.is_ascii_uppercase())
+        && prefix_chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        && !number.is_empty()
+        && !number.starts_with('0')
+        && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Every distinct short key mentioned in `text`, in order of first mention.
+///
+/// A key must stand on its own: `PROJ-12` matches in `fix PROJ-12.` and in the
+/// branch name `feature/PROJ-12-login`, but not inside `XPROJ-12a`.
+pub fn extract_short_keys(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut keys: Vec<String> = Vec::new();
+    let mut start = 0;
+
+    while start < chars.len() {
+        let boundary_before = start == 0 || !is_key_char(chars[start - 1]);
+        if !boundary_before || !chars[start].is_ascii_uppercase() {
+            start += 1;
+            continue;
+        }
+
+        let mut end = start;
+        while end < chars.len() && (chars[end].is_ascii_uppercase() || chars[end].is_ascii_digit())
+        {
+            end += 1;
+        }
+        if end < chars.len() && chars[end] == '-' {
+            end += 1;
+            while end < chars.len() && chars[end].is_ascii_digit() {
+                end += 1;
+            }
+        }
+
+        let boundary_after = end == chars.len() || !chars[end].is_ascii_alphanumeric();
+        let candidate: String = chars[start..end].iter().collect();
+        if boundary_after && is_short_key(&candidate) && !keys.contains(&candidate) {
+            keys.push(candidate);
+        }
+        start = end.max(start + 1);
+    }
+
+    keys
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalSourceType {
+    Commit,
+    PullRequest,
+    Comment,
+}
+
+impl ExternalSourceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExternalSourceType::Commit => "commit",
+            ExternalSourceType::PullRequest => "pull_request",
+            ExternalSourceType::Comment => "comment",
+        }
+    }
+}
+
+/// A commit, pull request or comment that mentions one or more short keys
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExternalMention {
+    pub source_type: ExternalSourceType,
+    pub url: String,
+    pub title: String,
+    pub keys: Vec<String>,
+}
+
+/// Mentions in a GitHub webhook delivery. `event` is the `X-GitHub-Event` header;
+/// events that carry no text (and unknown events) yield nothing.
+pub fn github_mentions(event: &str, payload: &Value) -> Vec<ExternalMention> {
+    let text = |value: &Value, field: &str| value[field].as_str().unwrap_or_default().to_string();
+    let mut mentions = Vec::new();
+    let mut push = |source_type, url: String, title: String, body: &str| {
+        let keys = extract_short_keys(body);
+        if !keys.is_empty() && !url.is_empty() {
+            mentions.push(ExternalMention {
+                source_type,
+                url,
+                title,
+                keys,
+            });
+        }
+    };
+
+    match event {
+        "push" => {
+            for commit in payload["commits"].as_array().into_iter().flatten() {
+                let message = text(commit, "message");
+                let title = message.lines().next().unwrap_or_default().to_string();
+                push(
+                    ExternalSourceType::Commit,
+                    text(commit, "url"),
+                    title,
+                    &message,
+                );
+            }
+        }
+        "pull_request" => {
+            let pull = &payload["pull_request"];
+            let body = format!(
+                "{}\n{}\n{}",
+                text(pull, "title"),
+                text(pull, "body"),
+                text(&pull["head"], "ref")
+            );
+            push(
+                ExternalSourceType::PullRequest,
+                text(pull, "html_url"),
+                text(pull, "title"),
+                &body,
+            );
+        }
+        "issue_comment" | "pull_request_review_comment" | "commit_comment" => {
+            let comment = &payload["comment"];
+            let title = payload["issue"]["title"]
+                .as_str()
+                .or_else(|| payload["pull_request"]["title"].as_str())
+                .unwrap_or("Comment")
+                .to_string();
+            push(
+                ExternalSourceType::Comment,
+                text(comment, "html_url"),
+                title,
+                &text(comment, "body"),
+            );
+        }
+        _ => {}
+    }
+
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_is_short_key() {
+        assert!(is_short_key("PROJ-123"));
+        assert!(is_short_key("AB2-1"));
+        assert!(!is_short_key("proj-123"));
+        assert!(!is_short_key("PROJ-"));
+        assert!(!is_short_key("PROJ-012"));
+        assert!(!is_short_key("2PROJ-1"));
+        assert!(!is_short_key("PROJ-1-2"));
+        assert!(!is_short_key("0b4e3f5a-1c2d-4e5f-8a9b-0c1d2e3f4a5b"));
+    }
+
+    #[test]
+    fn test_extract_short_keys() {
+        assert_eq!(
+            extract_short_keys("Fixes PROJ-12, see also (WEB-3) and PROJ-12 again."),
+            vec!["PROJ-12", "WEB-3"]
+        );
+        assert_eq!(
+            extract_short_keys("Merge feature/PROJ-7-login"),
+            vec!["PROJ-7"]
+        );
+        assert!(extract_short_keys("XPROJ-12a, my-PROJ-1, PROJ-01").is_empty());
+    }
+
+    #[test]
+    fn test_github_mentions_from_push() {
+        let payload = json!({
+            "commits": [
+                {"message": "PROJ-4: add login\n\nAlso touches PROJ-5", "url": "https://github.com/o/r/commit/abc"},
+                {"message": "tidy up", "url": "https://github.com/o/r/commit/def"}
+            ]
+        });
+
+        let mentions = github_mentions("push", &payload);
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].source_type, ExternalSourceType::Commit);
+        assert_eq!(mentions[0].title, "PROJ-4: add login");
+        assert_eq!(mentions[0].keys, vec!["PROJ-4", "PROJ-5"]);
+        assert!(github_mentions("ping", &payload).is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+//! The paths each service mounts, so the gateway can list every route and
+//! refuse to start when two services claim the same one.
+//!
+//! axum routers cannot be inspected once built, so services build theirs with
+//! [`ServiceRouter`], which records its paths under the service's name when the
+//! router is finished. Building a service's router again replaces its paths.
+
+use axum::extract::Request;
+use axum::response::IntoResponse;
+use axum::routing::{MethodRouter, Route};
+use axum::Router;
+use std::collections::{BTreeSet, HashMap};
+use std::convert::Infallible;
+use std::sync::{Mutex, OnceLock};
+use tower::{Layer, Service};
+
+fn registry() -> &'static Mutex<HashMap<String, BTreeSet<String>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, BTreeSet<String>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Paths the service's most recently built router mounts, sorted; empty if it
+/// never built one with [`ServiceRouter`]
+pub fn service_paths(service: &str) -> Vec<String> {
+    registry()
+        .lock()
+        .map(|registry| {
+            registry
+                .get(service)
+                .map(|paths| paths.iter().cloned().collect())
+                .unwrap_or_default()
+        })
+        .unwrap_or_default()
+}
+
+/// A [`Router`] that remembers the paths routed on it
+pub struct ServiceRouter<S = ()> {
+    service: &'static str,
+    paths: BTreeSet<String>,
+    router: Router<S>,
+}
+
+impl<S> ServiceRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    pub fn new(service: &'static str) -> Self {
+        Self {
+            service,
+            paths: BTreeSet::new(),
+            router: Router::new(),
+        }
+    }
+
+    pub fn route(mut self, path: &str, method_router: MethodRouter<S>) -> Self {
+        self.paths.insert(path.to_string());
+        self.router = self.router.route(path, method_router);
+        self
+    }
+
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + Sync + 'static,
+        L::Service: Service<Request> + Clone + Send + Sync + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        self.router = self.router.layer(layer);
+        self
+    }
+
+    /// Record the paths and hand over the router with its state
+    pub fn with_state<S2>(self, state: S) -> Router<S2> {
+        self.into_router().with_state(state)
+    }
+
+    /// Record the paths and hand over the router
+    pub fn into_router(self) -> Router<S> {
+        if let Ok(mut registry) = registry().lock() {
+            registry.insert(self.service.to_string(), self.paths);
+        }
+        self.router
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+
+    #[test]
+    fn test_records_paths_when_the_router_is_finished() {
+        let router = ServiceRouter::new("route-registry-test")
+            .route("/b", get(|| async {}))
+            .route("/a/{id}", get(|| async {}).post(|| async {}));
+        assert!(service_paths("route-registry-test").is_empty());
+
+        let _router: Router = router.with_state(());
+        assert_eq!(
+            service_paths("route-registry-test"),
+            vec!["/a/{id}".to_string(), "/b".to_string()]
+        );
+    }
+}
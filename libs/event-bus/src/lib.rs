@@ -1,15 +1,29 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use pubsub::PubSub;
 use serde::{Deserialize, Serialize};
 
+use std::future::Future;
 use std::sync::Arc;
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedReceiver},
     Mutex,
 };
+use tracing::Instrument;
 use uuid::Uuid;
 
+pub mod outbox;
+mod projection_failures;
+pub mod retry;
+pub mod transport;
+
+use projection_failures::ProjectionRegistry;
+pub use projection_failures::{
+    ProjectionFailure, ProjectionFailureSink, ProjectionId, ProjectionReplayer,
+};
+pub use retry::{apply_with_retry, RetryPolicy};
+pub use transport::{EventTransport, TransportConfig};
+use transport::{InMemoryTransport, TransportSubscription};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AcceptanceCriterionRecord {
     pub id: Uuid,
@@ -18,6 +32,9 @@ pub struct AcceptanceCriterionRecord {
     pub given: String,
     pub when: String,
     pub then: String,
+    /// Display order within the story; absent in events published before ordering existed
+    #[serde(default)]
+    pub position: u32,
     pub created_at: DateTime<Utc>,
 }
 
@@ -71,6 +88,11 @@ pub enum BacklogEvent {
         story_id: Uuid,
         organization_id: Option<Uuid>,
     },
+    /// Moved with its tasks to the archive tier; gone from every default view
+    StoryArchived {
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    },
     TaskCreated {
         task: TaskRecord,
     },
@@ -82,6 +104,19 @@ pub enum BacklogEvent {
         story_id: Uuid,
         organization_id: Option<Uuid>,
     },
+    /// The stories `story_id` now depends on, i.e. those it mentions by short key
+    StoryDependenciesChanged {
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        depends_on: Vec<Uuid>,
+    },
+    /// The stories explicitly recorded as blocking `story_id`, which hold it
+    /// back until they are accepted
+    StoryBlockersChanged {
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        blocked_by: Vec<Uuid>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -101,6 +136,18 @@ pub struct SprintRecord {
     pub updated_at: DateTime<Utc>,
 }
 
+/// A story moving into or out of a sprint, for scope tracking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintScopeChange {
+    pub sprint_id: Uuid,
+    pub story_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub story_points: Option<u32>,
+    /// Sprint status at the time of the change; changes while `active` are scope creep or cuts
+    pub sprint_status: String,
+    pub changed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SprintEvent {
     Created {
@@ -113,6 +160,12 @@ pub enum SprintEvent {
         sprint_id: Uuid,
         organization_id: Option<Uuid>,
     },
+    StoryAdded {
+        change: SprintScopeChange,
+    },
+    StoryRemoved {
+        change: SprintScopeChange,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -121,28 +174,138 @@ pub enum DomainEvent {
     Sprint(SprintEvent),
 }
 
+impl DomainEvent {
+    /// The organization the event belongs to; `None` for personal work
+    pub fn organization_id(&self) -> Option<Uuid> {
+        match self {
+            DomainEvent::Backlog(event) => match event {
+                BacklogEvent::StoryCreated { story } | BacklogEvent::StoryUpdated { story } => {
+                    story.organization_id
+                }
+                BacklogEvent::TaskCreated { task } | BacklogEvent::TaskUpdated { task } => {
+                    task.organization_id
+                }
+                BacklogEvent::StoryDeleted {
+                    organization_id, ..
+                }
+                | BacklogEvent::StoryArchived {
+                    organization_id, ..
+                }
+                | BacklogEvent::TaskDeleted {
+                    organization_id, ..
+                }
+                | BacklogEvent::StoryDependenciesChanged {
+                    organization_id, ..
+                }
+                | BacklogEvent::StoryBlockersChanged {
+                    organization_id, ..
+                } => *organization_id,
+            },
+            DomainEvent::Sprint(event) => match event {
+                SprintEvent::Created { sprint } | SprintEvent::Updated { sprint } => {
+                    sprint.organization_id
+                }
+                SprintEvent::Deleted {
+                    organization_id, ..
+                } => *organization_id,
+                SprintEvent::StoryAdded { change } | SprintEvent::StoryRemoved { change } => {
+                    change.organization_id
+                }
+            },
+        }
+    }
+}
+
+/// What led to an event: the HTTP request that started the chain, and the
+/// event being handled when it was published
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventCorrelation {
+    /// The originating request's `x-request-id`
+    pub correlation_id: Option<String>,
+    /// The event whose handling published this one
+    pub causation_id: Option<Uuid>,
+}
+
+tokio::task_local! {
+    static CURRENT_CORRELATION: EventCorrelation;
+}
+
+impl EventCorrelation {
+    pub fn from_request(request_id: impl Into<String>) -> Self {
+        Self {
+            correlation_id: Some(request_id.into()),
+            causation_id: None,
+        }
+    }
+
+    /// The correlation of the running task, empty outside a request or event
+    pub fn current() -> Self {
+        CURRENT_CORRELATION
+            .try_with(Clone::clone)
+            .unwrap_or_default()
+    }
+
+    /// Run `future` so that the events it publishes carry this correlation
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT_CORRELATION.scope(self, future).await
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventEnvelope {
     pub id: Uuid,
     pub occurred_at: DateTime<Utc>,
+    /// Request id of the HTTP request the event chain started from; absent for
+    /// events from background jobs and in events published before correlation existed
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+    /// Id of the event whose handling published this one
+    #[serde(default)]
+    pub causation_id: Option<Uuid>,
     pub event: DomainEvent,
 }
 
 impl EventEnvelope {
+    /// Wrap `event`, correlated with the request or event being handled
     pub fn new(event: DomainEvent) -> Self {
+        let correlation = EventCorrelation::current();
         Self {
             id: Uuid::new_v4(),
             occurred_at: Utc::now(),
+            correlation_id: correlation.correlation_id,
+            causation_id: correlation.causation_id,
             event,
         }
     }
-}
 
-const CHANNEL: &str = "domain-events";
+    /// Correlation for whatever is published while handling this event
+    pub fn follow_up(&self) -> EventCorrelation {
+        EventCorrelation {
+            correlation_id: self.correlation_id.clone(),
+            causation_id: Some(self.id),
+        }
+    }
+
+    /// Run a handler for this event. Its logs carry the event, correlation and
+    /// causation ids, and the events it publishes are caused by this one.
+    pub async fn handle<F: Future>(&self, handler: F) -> F::Output {
+        let span = tracing::info_span!(
+            "domain_event",
+            event_id = %self.id,
+            correlation_id = self.correlation_id.as_deref().unwrap_or_default(),
+            causation_id = tracing::field::Empty,
+        );
+        if let Some(causation_id) = self.causation_id {
+            span.record("causation_id", tracing::field::display(causation_id));
+        }
+        self.follow_up().scope(handler).instrument(span).await
+    }
+}
 
 #[derive(Clone)]
 pub struct EventBus {
-    inner: Arc<PubSub>,
+    transport: Arc<dyn EventTransport>,
+    projections: Arc<ProjectionRegistry>,
 }
 
 impl Default for EventBus {
@@ -152,37 +315,96 @@ impl Default for EventBus {
 }
 
 impl EventBus {
+    /// A bus whose events stay in this process
     pub fn new() -> Self {
+        Self::with_transport(Arc::new(InMemoryTransport::new()))
+    }
+
+    pub fn with_transport(transport: Arc<dyn EventTransport>) -> Self {
         Self {
-            inner: Arc::new(PubSub::new(2)),
+            transport,
+            projections: Arc::new(ProjectionRegistry::default()),
         }
     }
 
     pub async fn publish_envelope(&self, event: EventEnvelope) {
         if let Ok(payload) = serde_json::to_string(&event) {
-            self.inner.notify(CHANNEL, &payload);
+            if let Err(e) = self.transport.publish(payload).await {
+                tracing::error!(error = %e, event_id = %event.id, "Failed to publish event");
+            }
         }
     }
 
     pub fn subscribe(&self) -> EventSubscription {
         let (tx, rx) = unbounded_channel();
         let sender = tx.clone();
-        let subscription = self.inner.lazy_subscribe(CHANNEL).activate(move |message| {
+        let subscription = self.transport.subscribe(Box::new(move |message| {
             if let Ok(envelope) = serde_json::from_str::<EventEnvelope>(&message) {
                 let _ = sender.send(envelope);
             }
-        });
+        }));
 
         EventSubscription {
             receiver: Arc::new(Mutex::new(rx)),
             _subscription: subscription,
         }
     }
+
+    /// Keep the events projections fail to apply; without a sink they are only logged
+    pub fn set_failure_sink(&self, sink: Arc<dyn ProjectionFailureSink>) {
+        self.projections.set_failure_sink(sink);
+    }
+
+    /// Make a projection's failed events retryable through [`EventBus::replay`]
+    pub fn register_projection(
+        &self,
+        projection: ProjectionId,
+        replayer: Arc<dyn ProjectionReplayer>,
+    ) {
+        self.projections.register(projection, replayer);
+    }
+
+    /// Called by a projection worker when it could not apply `envelope` and
+    /// does not retry it; see [`apply_with_retry`] for one that does
+    pub fn report_failure(
+        &self,
+        projection: ProjectionId,
+        envelope: &EventEnvelope,
+        error: String,
+    ) {
+        self.projections.record(ProjectionFailure {
+            projection,
+            envelope: envelope.clone(),
+            error,
+            attempts: 1,
+        });
+    }
+
+    /// Hand an event a projection gave up on to the failure sink
+    pub(crate) fn dead_letter(&self, failure: ProjectionFailure) {
+        self.projections.record(failure);
+    }
+
+    pub fn has_projection(&self, service: &str, projection: &str) -> bool {
+        self.projections.replayer(service, projection).is_some()
+    }
+
+    /// Apply `envelope` to one projection again. `None` when no such
+    /// projection is registered on this bus.
+    pub async fn replay(
+        &self,
+        service: &str,
+        projection: &str,
+        envelope: &EventEnvelope,
+    ) -> Option<Result<(), String>> {
+        let replayer = self.projections.replayer(service, projection)?;
+        Some(replayer.reapply(envelope).await)
+    }
 }
 
 pub struct EventSubscription {
     receiver: Arc<Mutex<UnboundedReceiver<EventEnvelope>>>,
-    _subscription: pubsub::Subscription,
+    _subscription: TransportSubscription,
 }
 
 impl EventSubscription {
@@ -0,0 +1,252 @@
+//! Transactional outbox for domain events.
+//!
+//! Events are written to the `event_outbox` table before anyone hears about
+//! them: inside the transaction of the change they describe when the caller
+//! has one ([`enqueue`]), otherwise on their own ([`OutboxPublisher`]). The
+//! [`OutboxDispatcher`] publishes stored events on the in-process bus in the
+//! order they were written and marks them delivered, so events written just
+//! before a crash are delivered after the restart. Delivery is at least once:
+//! a crash between publishing and marking delivers the event again.
+//!
+//! Delivered events are kept for [`OUTBOX_RETENTION_DAYS`], and a registered
+//! projection can be rebuilt from them with [`replay_projection`]. Each event
+//! is stored with its organization so a window of one organization's events
+//! can be read back.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::postgres::PgListener;
+use sqlx::{PgExecutor, PgPool};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::task::JoinHandle;
+
+use crate::{DomainEvent, EventBus, EventEnvelope, EventPublisher};
+
+/// Postgres channel notified whenever an event is written
+pub const OUTBOX_CHANNEL: &str = "event_outbox";
+/// How long delivered events are kept before they are pruned
+pub const OUTBOX_RETENTION_DAYS: i64 = 30;
+/// How often the outbox is checked when no notification arrives
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+const PRUNE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+/// Events claimed and delivered per transaction
+const DISPATCH_BATCH: i64 = 100;
+/// Most events one replay reads from the outbox
+pub const MAX_REPLAY_EVENTS: i64 = 10_000;
+
+/// Write `envelope` to the outbox. Pass the transaction of the change it
+/// describes, so the event is stored exactly when the change commits.
+pub async fn enqueue<'e, E: PgExecutor<'e>>(
+    executor: E,
+    envelope: &EventEnvelope,
+) -> Result<(), sqlx::Error> {
+    let payload = serde_json::to_value(envelope).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+    // Notifications sent inside a transaction go out when it commits
+    sqlx::query(
+        "WITH stored AS (
+             INSERT INTO event_outbox (event_id, occurred_at, organization_id, envelope)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (event_id) DO NOTHING
+         )
+         SELECT pg_notify($5, '')",
+    )
+    .bind(envelope.id)
+    .bind(envelope.occurred_at)
+    .bind(envelope.event.organization_id())
+    .bind(payload)
+    .bind(OUTBOX_CHANNEL)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Stores published events in the outbox for the dispatcher to deliver.
+/// For events that belong to a change made outside a transaction.
+#[derive(Clone)]
+pub struct OutboxPublisher {
+    pool: Arc<PgPool>,
+}
+
+impl OutboxPublisher {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EventPublisher for OutboxPublisher {
+    async fn publish(&self, event: DomainEvent) {
+        let envelope = EventEnvelope::new(event);
+        if let Err(e) = enqueue(&*self.pool, &envelope).await {
+            // The change is already committed; losing its event is the lesser evil
+            tracing::error!(error = %e, event_id = %envelope.id, "SQL error storing event in outbox");
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct OutboxRow {
+    sequence: i64,
+    envelope: serde_json::Value,
+}
+
+fn read_envelope(row: &OutboxRow) -> Option<EventEnvelope> {
+    match serde_json::from_value::<EventEnvelope>(row.envelope.clone()) {
+        Ok(envelope) => Some(envelope),
+        Err(e) => {
+            // Written before a breaking change to the event's shape
+            tracing::warn!(error = %e, sequence = row.sequence, "Skipping unreadable outbox event");
+            None
+        }
+    }
+}
+
+/// Deliver the oldest undelivered events. Returns how many were claimed;
+/// replicas claim disjoint batches.
+pub async fn dispatch_pending(pool: &PgPool, bus: &EventBus) -> Result<usize, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let rows = sqlx::query_as::<_, OutboxRow>(
+        "SELECT sequence, envelope FROM event_outbox
+         WHERE delivered_at IS NULL
+         ORDER BY sequence
+         LIMIT $1
+         FOR UPDATE SKIP LOCKED",
+    )
+    .bind(DISPATCH_BATCH)
+    .fetch_all(&mut *tx)
+    .await?;
+    if rows.is_empty() {
+        return Ok(0);
+    }
+
+    for envelope in rows.iter().filter_map(read_envelope) {
+        bus.publish_envelope(envelope).await;
+    }
+    let sequences: Vec<i64> = rows.iter().map(|row| row.sequence).collect();
+    sqlx::query("UPDATE event_outbox SET delivered_at = NOW() WHERE sequence = ANY($1)")
+        .bind(&sequences)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(rows.len())
+}
+
+async fn prune_delivered(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let pruned = sqlx::query(
+        "DELETE FROM event_outbox WHERE delivered_at IS NOT NULL AND delivered_at < $1",
+    )
+    .bind(Utc::now() - Duration::days(OUTBOX_RETENTION_DAYS))
+    .execute(pool)
+    .await?;
+    Ok(pruned.rows_affected())
+}
+
+pub struct OutboxDispatcher {
+    #[allow(dead_code)]
+    handle: JoinHandle<()>,
+}
+
+impl OutboxDispatcher {
+    /// Deliver outbox events on `bus` as soon as they are written, checking
+    /// every few seconds in case a notification was missed. Events left over
+    /// from before a restart go out first.
+    pub fn spawn(pool: Arc<PgPool>, bus: Arc<EventBus>) -> Self {
+        let handle = tokio::spawn(async move {
+            let mut listener = match PgListener::connect_with(&pool).await {
+                Ok(mut listener) => match listener.listen(OUTBOX_CHANNEL).await {
+                    Ok(()) => Some(listener),
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Outbox notifications unavailable, polling");
+                        None
+                    }
+                },
+                Err(e) => {
+                    tracing::warn!(error = %e, "Outbox notifications unavailable, polling");
+                    None
+                }
+            };
+            let mut last_pruned: Option<Instant> = None;
+            loop {
+                loop {
+                    match dispatch_pending(&pool, &bus).await {
+                        Ok(claimed) if claimed > 0 => continue,
+                        Ok(_) => break,
+                        Err(e) => {
+                            tracing::error!(error = %e, "SQL error dispatching outbox events");
+                            break;
+                        }
+                    }
+                }
+                if last_pruned.is_none_or(|at| at.elapsed() >= PRUNE_INTERVAL) {
+                    last_pruned = Some(Instant::now());
+                    if let Err(e) = prune_delivered(&pool).await {
+                        tracing::error!(error = %e, "SQL error pruning event outbox");
+                    }
+                }
+                match listener.as_mut() {
+                    // Any notification, or none in time, means look again
+                    Some(listener) => {
+                        let _ = tokio::time::timeout(POLL_INTERVAL, listener.recv()).await;
+                    }
+                    None => tokio::time::sleep(POLL_INTERVAL).await,
+                }
+            }
+        });
+
+        Self { handle }
+    }
+}
+
+/// Outcome of replaying the outbox into one projection
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutboxReplay {
+    pub replayed: usize,
+    /// Ids of the events the projection failed to apply again
+    pub failed: Vec<uuid::Uuid>,
+    /// More events were written since `since` than one replay reads
+    pub truncated: bool,
+}
+
+/// Apply the delivered events written since `since` to one projection again,
+/// in the order they were written. `None` when the projection is not
+/// registered on `bus`.
+pub async fn replay_projection(
+    pool: &PgPool,
+    bus: &EventBus,
+    service: &str,
+    projection: &str,
+    since: DateTime<Utc>,
+) -> Result<Option<OutboxReplay>, sqlx::Error> {
+    if !bus.has_projection(service, projection) {
+        return Ok(None);
+    }
+    let mut rows = sqlx::query_as::<_, OutboxRow>(
+        "SELECT sequence, envelope FROM event_outbox
+         WHERE delivered_at IS NOT NULL AND created_at >= $1
+         ORDER BY sequence
+         LIMIT $2",
+    )
+    .bind(since)
+    .bind(MAX_REPLAY_EVENTS + 1)
+    .fetch_all(pool)
+    .await?;
+    let truncated = rows.len() as i64 > MAX_REPLAY_EVENTS;
+    rows.truncate(MAX_REPLAY_EVENTS as usize);
+
+    let mut outcome = OutboxReplay {
+        truncated,
+        ..OutboxReplay::default()
+    };
+    for envelope in rows.iter().filter_map(read_envelope) {
+        match bus.replay(service, projection, &envelope).await {
+            Some(Ok(())) => outcome.replayed += 1,
+            Some(Err(error)) => {
+                tracing::warn!(event_id = %envelope.id, error = %error, "Outbox replay failed");
+                outcome.failed.push(envelope.id);
+            }
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(outcome))
+}
@@ -0,0 +1,146 @@
+//! Events a projection failed to apply, and the projections that can retry them.
+//!
+//! Projection workers report an event they gave up on to the bus instead of
+//! only logging it. Whatever failure sink is installed keeps the envelope as a
+//! dead letter, and an operator can later hand it back to the same projection
+//! through its registered replayer, without rehydrating everything.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::EventEnvelope;
+
+/// Names a projection: the service that owns it and the read model it maintains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProjectionId {
+    pub service: &'static str,
+    pub name: &'static str,
+}
+
+impl ProjectionId {
+    pub const fn new(service: &'static str, name: &'static str) -> Self {
+        Self { service, name }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectionFailure {
+    pub projection: ProjectionId,
+    pub envelope: EventEnvelope,
+    /// The error of the last attempt
+    pub error: String,
+    /// How many times the projection tried the event
+    pub attempts: u32,
+}
+
+/// Receives failed event applications. Recording must not hold up the worker,
+/// so implementations persist in the background and only log their own errors.
+pub trait ProjectionFailureSink: Send + Sync {
+    fn record(&self, failure: ProjectionFailure);
+}
+
+/// Applies a single event to one projection again
+#[async_trait]
+pub trait ProjectionReplayer: Send + Sync {
+    async fn reapply(&self, envelope: &EventEnvelope) -> Result<(), String>;
+}
+
+/// The failure sink and replayers registered on an [`crate::EventBus`]
+#[derive(Default)]
+pub(crate) struct ProjectionRegistry {
+    sink: RwLock<Option<Arc<dyn ProjectionFailureSink>>>,
+    replayers: RwLock<HashMap<ProjectionId, Arc<dyn ProjectionReplayer>>>,
+}
+
+impl ProjectionRegistry {
+    pub(crate) fn set_failure_sink(&self, sink: Arc<dyn ProjectionFailureSink>) {
+        *self.sink.write().unwrap_or_else(|e| e.into_inner()) = Some(sink);
+    }
+
+    pub(crate) fn register(&self, projection: ProjectionId, replayer: Arc<dyn ProjectionReplayer>) {
+        self.replayers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(projection, replayer);
+    }
+
+    pub(crate) fn record(&self, failure: ProjectionFailure) {
+        let sink = self.sink.read().unwrap_or_else(|e| e.into_inner()).clone();
+        if let Some(sink) = sink {
+            sink.record(failure);
+        }
+    }
+
+    pub(crate) fn replayer(
+        &self,
+        service: &str,
+        projection: &str,
+    ) -> Option<Arc<dyn ProjectionReplayer>> {
+        self.replayers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|(id, _)| id.service == service && id.name == projection)
+            .map(|(_, replayer)| replayer.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DomainEvent, EventBus, SprintEvent};
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    struct CollectingSink(Mutex<Vec<ProjectionFailure>>);
+
+    impl ProjectionFailureSink for CollectingSink {
+        fn record(&self, failure: ProjectionFailure) {
+            self.0.lock().unwrap().push(failure);
+        }
+    }
+
+    struct Succeeds;
+
+    #[async_trait]
+    impl ProjectionReplayer for Succeeds {
+        async fn reapply(&self, _envelope: &EventEnvelope) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failures_reach_the_sink_and_replay_by_projection() {
+        const PROJECTION: ProjectionId = ProjectionId::new("readiness", "stories");
+        let bus = EventBus::new();
+        let envelope = EventEnvelope::new(DomainEvent::Sprint(SprintEvent::Deleted {
+            sprint_id: Uuid::new_v4(),
+            organization_id: None,
+        }));
+
+        // Failures reported before a sink is installed are dropped
+        bus.report_failure(PROJECTION, &envelope, "lost".to_string());
+        let sink = Arc::new(CollectingSink(Mutex::new(Vec::new())));
+        bus.set_failure_sink(sink.clone());
+        bus.report_failure(PROJECTION, &envelope, "deadlock detected".to_string());
+
+        {
+            let failures = sink.0.lock().unwrap();
+            assert_eq!(failures.len(), 1);
+            assert_eq!(failures[0].projection, PROJECTION);
+            assert_eq!(failures[0].error, "deadlock detected");
+        }
+
+        assert!(bus
+            .replay("readiness", "stories", &envelope)
+            .await
+            .is_none());
+        bus.register_projection(PROJECTION, Arc::new(Succeeds));
+        assert_eq!(
+            bus.replay("readiness", "stories", &envelope).await,
+            Some(Ok(()))
+        );
+        assert!(bus.replay("readiness", "tasks", &envelope).await.is_none());
+    }
+}
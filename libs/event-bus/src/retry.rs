@@ -0,0 +1,148 @@
+//! Retrying projection work before giving up on an event.
+//!
+//! Most failures to apply an event are transient: a deadlock, a dropped
+//! connection, a row the event depends on that has not committed yet. A
+//! projection worker hands each event to [`apply_with_retry`], which tries it
+//! again with exponential backoff. An event that still fails after the last
+//! attempt becomes a dead letter: it is reported to the bus's failure sink
+//! with its error and attempt count, and the worker moves on.
+
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+
+use crate::{EventBus, EventEnvelope, ProjectionFailure, ProjectionId};
+
+/// How often and how patiently an event is retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, the first one included
+    pub max_attempts: u32,
+    /// Wait after the first failed attempt; doubles after each one
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before the attempt after failed attempt `attempt` (1-based)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay)
+    }
+}
+
+/// Apply `envelope` to `projection` with `apply`, retrying failures under
+/// `policy`. Returns whether it was applied; when it was not, the event has
+/// been dead-lettered. Later events wait while this one is retried, so a
+/// projection still sees events in order.
+pub async fn apply_with_retry<F, Fut, E>(
+    bus: &EventBus,
+    projection: ProjectionId,
+    envelope: &EventEnvelope,
+    policy: RetryPolicy,
+    mut apply: F,
+) -> bool
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: Display,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        let error = match apply().await {
+            Ok(()) => return true,
+            Err(error) => error.to_string(),
+        };
+        if attempt >= max_attempts {
+            tracing::error!(
+                event_id = %envelope.id,
+                service = projection.service,
+                projection = projection.name,
+                attempts = attempt,
+                error = %error,
+                "Projection gave up on event, dead-lettering it"
+            );
+            bus.dead_letter(ProjectionFailure {
+                projection,
+                envelope: envelope.clone(),
+                error,
+                attempts: attempt,
+            });
+            return false;
+        }
+        tokio::time::sleep(policy.delay(attempt)).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DomainEvent, ProjectionFailureSink, SprintEvent};
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    struct CollectingSink(Mutex<Vec<ProjectionFailure>>);
+
+    impl ProjectionFailureSink for CollectingSink {
+        fn record(&self, failure: ProjectionFailure) {
+            self.0.lock().unwrap().push(failure);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_with_backoff_then_dead_letters() {
+        const PROJECTION: ProjectionId = ProjectionId::new("readiness", "stories");
+        let bus = EventBus::new();
+        let sink = Arc::new(CollectingSink(Mutex::new(Vec::new())));
+        bus.set_failure_sink(sink.clone());
+        let envelope = EventEnvelope::new(DomainEvent::Sprint(SprintEvent::Deleted {
+            sprint_id: Uuid::new_v4(),
+            organization_id: None,
+        }));
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+        };
+        assert_eq!(policy.delay(1), Duration::from_millis(1));
+        assert_eq!(policy.delay(5), Duration::from_millis(2));
+
+        // Succeeds on the second attempt
+        let mut calls = 0;
+        let applied = apply_with_retry(&bus, PROJECTION, &envelope, policy, || {
+            calls += 1;
+            let outcome = if calls < 2 { Err("deadlock") } else { Ok(()) };
+            async move { outcome }
+        })
+        .await;
+        assert!(applied);
+        assert_eq!(calls, 2);
+        assert!(sink.0.lock().unwrap().is_empty());
+
+        let mut calls = 0;
+        let applied = apply_with_retry(&bus, PROJECTION, &envelope, policy, || {
+            calls += 1;
+            async { Err::<(), _>("constraint violation") }
+        })
+        .await;
+        assert!(!applied);
+        assert_eq!(calls, 3);
+        let dead_letters = sink.0.lock().unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].attempts, 3);
+        assert_eq!(dead_letters[0].error, "constraint violation");
+    }
+}
@@ -0,0 +1,237 @@
+//! How events travel between the publishers and subscribers of an
+//! [`EventBus`](crate::EventBus).
+//!
+//! The in-memory transport only reaches subscribers in the same process and
+//! is what tests use. The Redis transport goes through Redis pub/sub, so
+//! projection workers can run in another process than the one publishing.
+//! Either way delivery is fire and forget: a subscriber that is disconnected
+//! misses what is published meanwhile, and catches up by replaying the
+//! outbox.
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use pubsub::PubSub;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tokio::task::JoinHandle;
+
+/// Channel events are published on, whatever the transport
+pub const CHANNEL: &str = "domain-events";
+/// Longest wait before a lost Redis subscription reconnects
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Called with every payload published while the subscription lasts
+pub type Deliver = Box<dyn Fn(String) + Send + Sync>;
+
+#[async_trait]
+pub trait EventTransport: Send + Sync {
+    /// Hand `payload` to every current subscriber
+    async fn publish(&self, payload: String) -> Result<(), String>;
+
+    /// Deliver every payload published from now on until the returned
+    /// subscription is dropped
+    fn subscribe(&self, deliver: Deliver) -> TransportSubscription;
+}
+
+/// Keeps a subscription alive; dropping it unsubscribes
+pub struct TransportSubscription {
+    _guard: Box<dyn Any + Send + Sync>,
+}
+
+impl TransportSubscription {
+    pub fn new(guard: impl Any + Send + Sync) -> Self {
+        Self {
+            _guard: Box::new(guard),
+        }
+    }
+}
+
+/// Subscribers in this process only
+pub struct InMemoryTransport {
+    inner: PubSub,
+}
+
+impl Default for InMemoryTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryTransport {
+    pub fn new() -> Self {
+        Self {
+            inner: PubSub::new(2),
+        }
+    }
+}
+
+#[async_trait]
+impl EventTransport for InMemoryTransport {
+    async fn publish(&self, payload: String) -> Result<(), String> {
+        self.inner.notify(CHANNEL, &payload);
+        Ok(())
+    }
+
+    fn subscribe(&self, deliver: Deliver) -> TransportSubscription {
+        TransportSubscription::new(self.inner.lazy_subscribe(CHANNEL).activate(deliver))
+    }
+}
+
+/// Subscribers anywhere, through Redis pub/sub
+pub struct RedisTransport {
+    client: redis::Client,
+    /// Opened on first publish, reconnects by itself
+    publisher: OnceCell<ConnectionManager>,
+}
+
+impl RedisTransport {
+    /// Fails only on a malformed `url`; the server is contacted on first use
+    pub fn new(url: &str) -> Result<Self, String> {
+        let client = redis::Client::open(url).map_err(|e| e.to_string())?;
+        Ok(Self {
+            client,
+            publisher: OnceCell::new(),
+        })
+    }
+}
+
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Forward messages until the connection is lost
+async fn forward_messages(client: &redis::Client, deliver: &Deliver) -> redis::RedisResult<()> {
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(CHANNEL).await?;
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        match message.get_payload::<String>() {
+            Ok(payload) => deliver(payload),
+            Err(e) => tracing::warn!(error = %e, "Skipping unreadable event from Redis"),
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl EventTransport for RedisTransport {
+    async fn publish(&self, payload: String) -> Result<(), String> {
+        let mut connection = self
+            .publisher
+            .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+            .await
+            .map_err(|e| e.to_string())?
+            .clone();
+        connection
+            .publish::<_, _, i64>(CHANNEL, payload)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Must be called inside a Tokio runtime. The subscription connects in
+    /// the background and reconnects with backoff whenever it is lost.
+    fn subscribe(&self, deliver: Deliver) -> TransportSubscription {
+        let client = self.client.clone();
+        let handle = tokio::spawn(async move {
+            let mut delay = Duration::from_secs(1);
+            loop {
+                match forward_messages(&client, &deliver).await {
+                    Ok(()) => {
+                        tracing::warn!("Redis event subscription closed, reconnecting");
+                        delay = Duration::from_secs(1);
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Redis event subscription failed, reconnecting")
+                    }
+                }
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+        });
+        TransportSubscription::new(AbortOnDrop(handle))
+    }
+}
+
+/// Which transport the bus uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransportConfig {
+    InMemory,
+    Redis { url: String },
+}
+
+impl TransportConfig {
+    /// Read `EVENT_TRANSPORT` (`memory`, the default, or `redis`) and, for
+    /// Redis, `EVENT_TRANSPORT_URL`
+    pub fn from_secrets(get: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let kind = get("EVENT_TRANSPORT").unwrap_or_default();
+        match kind.trim().to_ascii_lowercase().as_str() {
+            "" | "memory" => Ok(Self::InMemory),
+            "redis" => get("EVENT_TRANSPORT_URL")
+                .map(|url| url.trim().to_string())
+                .filter(|url| !url.is_empty())
+                .map(|url| Self::Redis { url })
+                .ok_or_else(|| "EVENT_TRANSPORT_URL must be set for the redis transport".into()),
+            other => Err(format!("Unknown EVENT_TRANSPORT '{}'", other)),
+        }
+    }
+
+    pub fn connect(&self) -> Result<Arc<dyn EventTransport>, String> {
+        Ok(match self {
+            Self::InMemory => Arc::new(InMemoryTransport::new()),
+            Self::Redis { url } => Arc::new(RedisTransport::new(url)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DomainEvent, EventBus, EventEnvelope, SprintEvent};
+
+    #[tokio::test]
+    async fn test_subscribers_receive_events_through_the_transport() {
+        let bus = EventBus::with_transport(Arc::new(InMemoryTransport::new()));
+        let subscription = bus.subscribe();
+        let envelope = EventEnvelope::new(DomainEvent::Sprint(SprintEvent::Deleted {
+            sprint_id: uuid::Uuid::new_v4(),
+            organization_id: None,
+        }));
+
+        bus.publish_envelope(envelope.clone()).await;
+
+        let received = tokio::time::timeout(Duration::from_secs(5), subscription.recv())
+            .await
+            .expect("event delivered");
+        assert_eq!(received.id, envelope.id);
+    }
+
+    #[test]
+    fn test_transport_config_from_secrets() {
+        let config = |transport: Option<&str>, url: Option<&str>| {
+            TransportConfig::from_secrets(|key| match key {
+                "EVENT_TRANSPORT" => transport.map(str::to_string),
+                "EVENT_TRANSPORT_URL" => url.map(str::to_string),
+                _ => None,
+            })
+        };
+
+        assert_eq!(config(None, None), Ok(TransportConfig::InMemory));
+        assert_eq!(
+            config(Some(" Redis "), Some("redis://events:6379")),
+            Ok(TransportConfig::Redis {
+                url: "redis://events:6379".to_string()
+            })
+        );
+        assert!(config(Some("redis"), Some("  ")).is_err());
+        assert!(config(Some("kafka"), None).is_err());
+    }
+}
@@ -0,0 +1,340 @@
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::models::{
+    AcceptanceCriterion, ArchiveQuery, ArchivedItem, NewStory, NewTask, PlanPack,
+    ReadinessEvaluation, Story, StoryQuery, Task, TaskPack,
+};
+use crate::pagination::{collect_pages, DEFAULT_PAGE_SIZE};
+use crate::routes::{self, Route};
+use crate::{ClientError, RetryPolicy};
+
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// A client for the Gamalan API, authenticated with an API key. Cloning it is
+/// cheap and clones share their connection pool.
+#[derive(Debug, Clone)]
+pub struct GamalanClient {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    retry_policy: RetryPolicy,
+}
+
+impl GamalanClient {
+    /// `base_url` is the gateway's address, e.g. `https://api.example.com`
+    pub fn new(base_url: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key: api_key.into(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Send requests through `http`, e.g. one with a proxy or timeouts
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    pub async fn project_stories(
+        &self,
+        project_id: Uuid,
+        query: &StoryQuery,
+    ) -> Result<Vec<Story>, ClientError> {
+        let mut params = Vec::new();
+        if let Some(status) = &query.status {
+            params.push(("status", status.clone()));
+        }
+        if let Some(sprint_id) = query.sprint_id {
+            params.push(("sprintId", sprint_id.to_string()));
+        }
+        if let Some(q) = &query.q {
+            params.push(("q", q.clone()));
+        }
+        self.json(&routes::PROJECT_STORIES, &[&project_id], &params, None)
+            .await
+    }
+
+    /// Create a story and return its ID
+    pub async fn create_story(
+        &self,
+        project_id: Uuid,
+        story: &NewStory,
+    ) -> Result<Uuid, ClientError> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Created {
+            story_id: Uuid,
+        }
+
+        let created: Created = self
+            .json(
+                &routes::CREATE_STORY,
+                &[&project_id],
+                &[],
+                Some(serde_json::to_value(story)?),
+            )
+            .await?;
+        Ok(created.story_id)
+    }
+
+    pub async fn story(&self, story_id: Uuid) -> Result<Story, ClientError> {
+        self.json(&routes::STORY, &[&story_id], &[], None).await
+    }
+
+    /// Move a story to `status`, e.g. `inprogress` or `accepted`
+    pub async fn update_story_status(
+        &self,
+        story_id: Uuid,
+        status: &str,
+    ) -> Result<(), ClientError> {
+        self.send(
+            &routes::STORY_STATUS,
+            &[&story_id],
+            &[],
+            Some(json!({ "status": status })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn story_tasks(&self, story_id: Uuid) -> Result<Vec<Task>, ClientError> {
+        self.json(&routes::STORY_TASKS, &[&story_id], &[], None)
+            .await
+    }
+
+    /// Create a task in the story and return its ID
+    pub async fn create_task(&self, story_id: Uuid, task: &NewTask) -> Result<Uuid, ClientError> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Created {
+            task_id: Uuid,
+        }
+
+        let created: Created = self
+            .json(
+                &routes::CREATE_TASK,
+                &[&story_id],
+                &[],
+                Some(serde_json::to_value(task)?),
+            )
+            .await?;
+        Ok(created.task_id)
+    }
+
+    /// The tasks the API key's user owns
+    pub async fn owned_tasks(&self) -> Result<Vec<Task>, ClientError> {
+        self.json(&routes::OWNED_TASKS, &[], &[], None).await
+    }
+
+    pub async fn take_task(&self, task_id: Uuid) -> Result<(), ClientError> {
+        self.send(&routes::TAKE_TASK, &[&task_id], &[], None)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn release_task(&self, task_id: Uuid) -> Result<(), ClientError> {
+        self.send(&routes::RELEASE_TASK, &[&task_id], &[], None)
+            .await?;
+        Ok(())
+    }
+
+    /// Move a task to `status`, e.g. `inprogress` or `completed`
+    pub async fn update_task_status(
+        &self,
+        task_id: Uuid,
+        status: &str,
+    ) -> Result<Task, ClientError> {
+        self.json(
+            &routes::TASK_STATUS,
+            &[&task_id],
+            &[],
+            Some(json!({ "status": status })),
+        )
+        .await
+    }
+
+    /// One page of archived stories and tasks
+    pub async fn search_archive(
+        &self,
+        query: &ArchiveQuery,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<ArchivedItem>, ClientError> {
+        let mut params = vec![("offset", offset.to_string()), ("limit", limit.to_string())];
+        if let Some(q) = &query.q {
+            params.push(("q", q.clone()));
+        }
+        if let Some(kind) = &query.kind {
+            params.push(("type", kind.clone()));
+        }
+        if let Some(project_id) = query.project_id {
+            params.push(("projectId", project_id.to_string()));
+        }
+        self.json(&routes::ARCHIVE_SEARCH, &[], &params, None).await
+    }
+
+    /// Every archived story and task matching `query`, page by page
+    pub async fn search_archive_all(
+        &self,
+        query: &ArchiveQuery,
+    ) -> Result<Vec<ArchivedItem>, ClientError> {
+        collect_pages(DEFAULT_PAGE_SIZE, |offset, limit| {
+            self.search_archive(query, offset, limit)
+        })
+        .await
+    }
+
+    pub async fn evaluate_readiness(
+        &self,
+        story_id: Uuid,
+    ) -> Result<ReadinessEvaluation, ClientError> {
+        self.json(&routes::EVALUATE_READINESS, &[&story_id], &[], None)
+            .await
+    }
+
+    pub async fn acceptance_criteria(
+        &self,
+        story_id: Uuid,
+    ) -> Result<Vec<AcceptanceCriterion>, ClientError> {
+        self.json(&routes::CRITERIA, &[&story_id], &[], None).await
+    }
+
+    /// Have the LLM propose acceptance criteria for the story
+    pub async fn generate_acceptance_criteria(
+        &self,
+        story_id: Uuid,
+    ) -> Result<Vec<AcceptanceCriterion>, ClientError> {
+        self.json(&routes::GENERATE_CRITERIA, &[&story_id], &[], None)
+            .await
+    }
+
+    /// The story's plan pack, if one was generated
+    pub async fn plan_pack(&self, story_id: Uuid) -> Result<Option<PlanPack>, ClientError> {
+        not_found_as_none(self.json(&routes::PLAN_PACK, &[&story_id], &[], None).await)
+    }
+
+    pub async fn generate_plan_pack(&self, story_id: Uuid) -> Result<PlanPack, ClientError> {
+        self.json(&routes::GENERATE_PLAN_PACK, &[&story_id], &[], None)
+            .await
+    }
+
+    pub async fn approve_plan_pack(&self, story_id: Uuid) -> Result<PlanPack, ClientError> {
+        self.json(&routes::APPROVE_PLAN_PACK, &[&story_id], &[], None)
+            .await
+    }
+
+    /// The task's task pack, if one was generated
+    pub async fn task_pack(&self, task_id: Uuid) -> Result<Option<TaskPack>, ClientError> {
+        not_found_as_none(self.json(&routes::TASK_PACK, &[&task_id], &[], None).await)
+    }
+
+    /// Generate the task's pack; fails with `PLAN_NOT_APPROVED` until the
+    /// story's plan pack is approved
+    pub async fn generate_task_pack(&self, task_id: Uuid) -> Result<TaskPack, ClientError> {
+        self.json(&routes::GENERATE_TASK_PACK, &[&task_id], &[], None)
+            .await
+    }
+
+    /// The task pack as the Markdown handed to a developer or agent
+    pub async fn task_pack_markdown(&self, task_id: Uuid) -> Result<String, ClientError> {
+        let response = self
+            .send(&routes::TASK_PACK_MARKDOWN, &[&task_id], &[], None)
+            .await?;
+        Ok(response.text().await?)
+    }
+
+    async fn json<T: DeserializeOwned>(
+        &self,
+        route: &Route,
+        params: &[&dyn std::fmt::Display],
+        query: &[(&str, String)],
+        body: Option<Value>,
+    ) -> Result<T, ClientError> {
+        let response = self.send(route, params, query, body).await?;
+        let bytes = response.bytes().await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Send the request, retrying as the policy allows, and fail on an error status
+    async fn send(
+        &self,
+        route: &Route,
+        params: &[&dyn std::fmt::Display],
+        query: &[(&str, String)],
+        body: Option<Value>,
+    ) -> Result<Response, ClientError> {
+        let url = format!("{}{}", self.base_url, route.path(params));
+        let mut attempt = 0;
+        loop {
+            let mut request = self
+                .http
+                .request(route.method.clone(), &url)
+                .header(API_KEY_HEADER, &self.api_key)
+                .query(query);
+            if let Some(body) = &body {
+                request = request.json(body);
+            }
+
+            let result = request.send().await;
+            let status = result.as_ref().ok().map(Response::status);
+            let failed = status.is_none_or(|status| !status.is_success());
+            if failed
+                && attempt < self.retry_policy.max_retries
+                && RetryPolicy::is_retryable(&route.method, status)
+            {
+                attempt += 1;
+                let retry_after = result.as_ref().ok().and_then(retry_after);
+                tokio::time::sleep(self.retry_policy.backoff(attempt, retry_after)).await;
+                continue;
+            }
+
+            let response = result?;
+            let status = response.status();
+            if status.is_success() {
+                return Ok(response);
+            }
+            let body = response.text().await.unwrap_or_default();
+            return Err(ClientError::from_response(status, &body));
+        }
+    }
+}
+
+/// The `Retry-After` of a rate-limited or unavailable response, in seconds
+fn retry_after(response: &Response) -> Option<Duration> {
+    if !matches!(
+        response.status(),
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    ) {
+        return None;
+    }
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+fn not_found_as_none<T>(result: Result<T, ClientError>) -> Result<Option<T>, ClientError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(err) if err.is_not_found() => Ok(None),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,65 @@
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    /// The API answered with an error status
+    #[error("{status}: {message}")]
+    Api {
+        status: StatusCode,
+        /// The API's error code, e.g. `NOT_FOUND` or `PLAN_NOT_APPROVED`
+        code: Option<String>,
+        message: String,
+    },
+    /// The request could not be sent or its response not read
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    /// The response body was not what the client expected
+    #[error("unexpected response body: {0}")]
+    Decode(#[from] serde_json::Error),
+}
+
+impl ClientError {
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Api { status, .. } => Some(*status),
+            Self::Http(err) => err.status(),
+            Self::Decode(_) => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(StatusCode::NOT_FOUND)
+    }
+
+    /// The error of a response with an error status, from the API's
+    /// `{"error": {"code", "message"}}` body when it has one
+    pub(crate) fn from_response(status: StatusCode, body: &str) -> Self {
+        #[derive(Deserialize)]
+        struct ErrorBody {
+            error: ErrorDetails,
+        }
+        #[derive(Deserialize)]
+        struct ErrorDetails {
+            code: String,
+            message: String,
+        }
+
+        match serde_json::from_str::<ErrorBody>(body) {
+            Ok(ErrorBody { error }) => Self::Api {
+                status,
+                code: Some(error.code),
+                message: error.message,
+            },
+            Err(_) => Self::Api {
+                status,
+                code: None,
+                message: if body.trim().is_empty() {
+                    status.canonical_reason().unwrap_or("error").to_string()
+                } else {
+                    body.trim().to_string()
+                },
+            },
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! A typed client for the Gamalan REST API, for tools outside the platform
+//! such as CI plugins and bots.
+//!
+//! ```no_run
+//! use gamalan_client::{GamalanClient, NewTask};
+//! # async fn run(story_id: uuid::Uuid) -> Result<(), gamalan_client::ClientError> {
+//! let client = GamalanClient::new("https://api.example.com", "gk_live_...");
+//!
+//! let evaluation = client.evaluate_readiness(story_id).await?;
+//! if evaluation.is_ready {
+//!     let task = NewTask {
+//!         title: "Wire up the webhook".to_string(),
+//!         acceptance_criteria_refs: vec!["AC1".to_string()],
+//!         ..NewTask::default()
+//!     };
+//!     let task_id = client.create_task(story_id, &task).await?;
+//!     println!("{}", client.task_pack_markdown(task_id).await?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod client;
+mod error;
+mod models;
+mod pagination;
+mod retry;
+pub mod routes;
+
+pub use client::GamalanClient;
+pub use error::ClientError;
+pub use models::*;
+pub use pagination::{collect_pages, DEFAULT_PAGE_SIZE};
+pub use retry::RetryPolicy;
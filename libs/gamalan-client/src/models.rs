@@ -0,0 +1,159 @@
+//! The API's request and response bodies, as far as third-party tools need
+//! them. Fields the client does not know are ignored, so additions to the API
+//! do not break older clients.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Story {
+    pub id: Uuid,
+    pub short_key: Option<String>,
+    pub project_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub story_type: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub story_points: Option<u32>,
+    pub sprint_id: Option<Uuid>,
+    pub assigned_to_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewStory {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    /// `feature`, `bug`, `chore` or `spike`; the API defaults to a feature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub story_type: Option<String>,
+}
+
+/// Filters for a project's stories
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StoryQuery {
+    pub status: Option<String>,
+    pub sprint_id: Option<Uuid>,
+    /// Story filter, e.g. `status:ready AND label:backend`
+    pub q: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    pub id: Uuid,
+    pub short_key: Option<String>,
+    pub story_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub acceptance_criteria_refs: Vec<String>,
+    pub status: String,
+    pub owner_user_id: Option<Uuid>,
+    pub estimated_hours: Option<u32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTask {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// IDs of the story's acceptance criteria the task covers, e.g. `AC1`
+    pub acceptance_criteria_refs: Vec<String>,
+}
+
+/// Filters for a search of archived stories and tasks
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArchiveQuery {
+    /// Words to find in titles and descriptions
+    pub q: Option<String>,
+    /// `story` or `task`; both when omitted
+    pub kind: Option<String>,
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedItem {
+    /// `story` or `task`
+    pub kind: String,
+    pub id: Uuid,
+    pub story_id: Uuid,
+    pub project_id: Uuid,
+    pub short_key: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub archived_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptanceCriterion {
+    pub id: Uuid,
+    pub story_id: Uuid,
+    /// The criterion's ID within the story, e.g. `AC1`
+    pub ac_id: String,
+    pub given: String,
+    pub when: String,
+    pub then: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessEvaluation {
+    pub score: i32,
+    pub is_ready: bool,
+    #[serde(default)]
+    pub missing_items: Vec<String>,
+    #[serde(default)]
+    pub recommendations: Vec<String>,
+    #[serde(default)]
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanPack {
+    pub id: Uuid,
+    pub story_id: Uuid,
+    pub proposed_tasks: serde_json::Value,
+    pub architecture_impact: Option<String>,
+    #[serde(default)]
+    pub risks: Vec<String>,
+    #[serde(default)]
+    pub unknowns: Vec<String>,
+    /// `draft` or `approved`; task packs are generated from approved plans
+    pub status: String,
+    pub approved_by: Option<String>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskPack {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub plan_pack_id: Option<Uuid>,
+    pub objectives: String,
+    #[serde(default)]
+    pub non_goals: Vec<String>,
+    #[serde(default)]
+    pub run_instructions: Vec<String>,
+    pub markdown_content: String,
+    pub json_content: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
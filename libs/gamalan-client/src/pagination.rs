@@ -0,0 +1,51 @@
+use std::future::Future;
+
+use crate::ClientError;
+
+/// Items per page when the client walks every page of a listing
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// Every item of a `limit`/`offset` listing. `fetch` is called with the
+/// offset and limit of each page until a page comes back short.
+pub async fn collect_pages<T, F, Fut>(page_size: u32, mut fetch: F) -> Result<Vec<T>, ClientError>
+where
+    F: FnMut(u32, u32) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, ClientError>>,
+{
+    let page_size = page_size.max(1);
+    let mut items = Vec::new();
+    loop {
+        let page = fetch(items.len() as u32, page_size).await?;
+        let last = page.len() < page_size as usize;
+        items.extend(page);
+        if last {
+            return Ok(items);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_collects_until_a_short_page() {
+        let source: Vec<u32> = (0..7).collect();
+        let mut requests = Vec::new();
+        let items = collect_pages(3, |offset, limit| {
+            requests.push(offset);
+            let page = source
+                .iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .copied()
+                .collect();
+            async move { Ok(page) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(items, source);
+        assert_eq!(requests, vec![0, 3, 6]);
+    }
+}
@@ -0,0 +1,89 @@
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+/// How often and how patiently failed requests are retried. Requests are
+/// retried when the API is rate limiting or temporarily unavailable; requests
+/// that may have changed something are not retried after a gateway timeout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Wait before the first retry, doubled for each one after it
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// The wait before retry `attempt`, counting from 1, or the API's
+    /// `Retry-After` when it sent one; never more than `max_backoff`
+    pub(crate) fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let exponential = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        retry_after.unwrap_or(exponential).min(self.max_backoff)
+    }
+
+    /// Whether a request answered with `status`, or not answered at all, may
+    /// be sent again
+    pub(crate) fn is_retryable(method: &Method, status: Option<StatusCode>) -> bool {
+        let idempotent = matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+        );
+        match status {
+            Some(StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE) => true,
+            Some(StatusCode::BAD_GATEWAY | StatusCode::GATEWAY_TIMEOUT) | None => idempotent,
+            Some(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_limit() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+        assert_eq!(policy.backoff(1, None), Duration::from_millis(100));
+        assert_eq!(policy.backoff(3, None), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4, None), Duration::from_millis(500));
+        assert_eq!(
+            policy.backoff(1, Some(Duration::from_secs(60))),
+            Duration::from_millis(500)
+        );
+
+        assert!(RetryPolicy::is_retryable(
+            &Method::POST,
+            Some(StatusCode::TOO_MANY_REQUESTS)
+        ));
+        assert!(!RetryPolicy::is_retryable(&Method::POST, None));
+        assert!(RetryPolicy::is_retryable(&Method::GET, None));
+        assert!(!RetryPolicy::is_retryable(
+            &Method::GET,
+            Some(StatusCode::BAD_REQUEST)
+        ));
+    }
+}
@@ -0,0 +1,116 @@
+//! Every endpoint the client calls. The gateway's contract tests check each
+//! one against the routes the services mount, so the client cannot drift from
+//! the API unnoticed.
+
+use reqwest::Method;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub method: Method,
+    /// The path as the services route it, with `{name}` parameters
+    pub template: &'static str,
+}
+
+impl Route {
+    const fn new(method: Method, template: &'static str) -> Self {
+        Self { method, template }
+    }
+
+    /// The template with its parameters replaced by `params`, in order
+    pub fn path(&self, params: &[&dyn Display]) -> String {
+        let mut params = params.iter();
+        self.template
+            .split('/')
+            .map(|segment| {
+                if segment.starts_with('{') {
+                    params
+                        .next()
+                        .map(|param| param.to_string())
+                        .unwrap_or_else(|| segment.to_string())
+                } else {
+                    segment.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+pub const PROJECT_STORIES: Route = Route::new(Method::GET, "/api/v1/projects/{project_id}/stories");
+pub const CREATE_STORY: Route = Route::new(Method::POST, "/api/v1/projects/{project_id}/stories");
+pub const STORY: Route = Route::new(Method::GET, "/api/v1/stories/{id}");
+pub const STORY_STATUS: Route = Route::new(Method::PATCH, "/api/v1/stories/{id}/status");
+pub const STORY_TASKS: Route = Route::new(Method::GET, "/api/v1/stories/{id}/tasks");
+pub const CREATE_TASK: Route = Route::new(Method::POST, "/api/v1/stories/{id}/tasks");
+pub const OWNED_TASKS: Route = Route::new(Method::GET, "/api/v1/tasks/owned");
+pub const TAKE_TASK: Route = Route::new(Method::PUT, "/api/v1/tasks/{task_id}/ownership");
+pub const RELEASE_TASK: Route = Route::new(Method::DELETE, "/api/v1/tasks/{task_id}/ownership");
+pub const TASK_STATUS: Route = Route::new(Method::PATCH, "/api/v1/tasks/{task_id}/status");
+pub const ARCHIVE_SEARCH: Route = Route::new(Method::GET, "/api/v1/archive/search");
+pub const EVALUATE_READINESS: Route =
+    Route::new(Method::POST, "/api/v1/readiness/{story_id}/evaluate");
+pub const CRITERIA: Route = Route::new(Method::GET, "/api/v1/readiness/criteria/{story_id}");
+pub const GENERATE_CRITERIA: Route = Route::new(
+    Method::POST,
+    "/api/v1/readiness/criteria/{story_id}/generate",
+);
+pub const PLAN_PACK: Route =
+    Route::new(Method::GET, "/api/v1/prompt-builder/plans/story/{story_id}");
+pub const GENERATE_PLAN_PACK: Route = Route::new(
+    Method::POST,
+    "/api/v1/prompt-builder/plans/from-story/{story_id}",
+);
+pub const APPROVE_PLAN_PACK: Route = Route::new(
+    Method::POST,
+    "/api/v1/prompt-builder/plans/story/{story_id}/approve",
+);
+pub const TASK_PACK: Route = Route::new(
+    Method::GET,
+    "/api/v1/prompt-builder/work-packets/task/{task_id}",
+);
+pub const GENERATE_TASK_PACK: Route = Route::new(
+    Method::POST,
+    "/api/v1/prompt-builder/work-packets/from-task/{task_id}",
+);
+pub const TASK_PACK_MARKDOWN: Route = Route::new(
+    Method::GET,
+    "/api/v1/prompt-builder/work-packets/task/{task_id}/markdown",
+);
+
+pub const ALL: &[Route] = &[
+    PROJECT_STORIES,
+    CREATE_STORY,
+    STORY,
+    STORY_STATUS,
+    STORY_TASKS,
+    CREATE_TASK,
+    OWNED_TASKS,
+    TAKE_TASK,
+    RELEASE_TASK,
+    TASK_STATUS,
+    ARCHIVE_SEARCH,
+    EVALUATE_READINESS,
+    CRITERIA,
+    GENERATE_CRITERIA,
+    PLAN_PACK,
+    GENERATE_PLAN_PACK,
+    APPROVE_PLAN_PACK,
+    TASK_PACK,
+    GENERATE_TASK_PACK,
+    TASK_PACK_MARKDOWN,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_fills_parameters_in_order() {
+        assert_eq!(
+            TASK_PACK_MARKDOWN.path(&[&"abc"]),
+            "/api/v1/prompt-builder/work-packets/task/abc/markdown"
+        );
+        assert_eq!(OWNED_TASKS.path(&[]), "/api/v1/tasks/owned");
+    }
+}
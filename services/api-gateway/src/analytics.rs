@@ -0,0 +1,289 @@
+//! Product analytics: the Postgres-backed [`AnalyticsEmitter`], the
+//! organization-level opt-out, and a summary of feature adoption and the story
+//! funnel for maintainers holding the admin token. Only salted hashes of
+//! organization and subject ids are stored.
+
+use crate::auth::require_organization_admin;
+use crate::maintenance::MaintenanceState;
+use crate::sql_error;
+use auth_clerk::{AuthenticatedWithOrg, JwtVerifier};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    routing::get,
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use common::analytics::{
+    hash_id, AnalyticsEmitter, AnalyticsEvent, STORY_ACCEPTED, STORY_CREATED, STORY_READY,
+};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const DEFAULT_WINDOW_DAYS: i64 = 30;
+
+/// Stores events of organizations that have not opted out, in the background
+#[derive(Clone)]
+pub struct PgAnalyticsEmitter {
+    pool: Arc<PgPool>,
+    salt: Arc<str>,
+}
+
+impl PgAnalyticsEmitter {
+    pub fn new(pool: Arc<PgPool>, salt: &str) -> Self {
+        Self {
+            pool,
+            salt: Arc::from(salt),
+        }
+    }
+}
+
+impl AnalyticsEmitter for PgAnalyticsEmitter {
+    fn emit(&self, event: AnalyticsEvent) {
+        let pool = self.pool.clone();
+        let salt = self.salt.clone();
+        tokio::spawn(async move {
+            if let Err(e) = store_event(&pool, &salt, &event).await {
+                tracing::error!(error = %e, event = event.name, "SQL error recording analytics event");
+            }
+        });
+    }
+}
+
+async fn store_event(pool: &PgPool, salt: &str, event: &AnalyticsEvent) -> Result<(), sqlx::Error> {
+    // The opt-out is checked against the real id, which is never stored
+    let anonymized = event.anonymize(salt);
+    sqlx::query(
+        "INSERT INTO analytics_events (id, name, organization_hash, subject_hash, occurred_at)
+         SELECT $1, $2, $3, $4, $5
+         WHERE NOT EXISTS (
+             SELECT 1 FROM analytics_opt_outs WHERE organization_id = $6
+         )",
+    )
+    .bind(Uuid::new_v4())
+    .bind(anonymized.name)
+    .bind(&anonymized.organization_hash)
+    .bind(&anonymized.subject_hash)
+    .bind(anonymized.occurred_at)
+    .bind(event.organization_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsSettings {
+    pub opted_out: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsSummaryQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsEventCount {
+    pub name: String,
+    pub events: i64,
+    /// Distinct organizations that produced the event
+    pub organizations: i64,
+}
+
+/// Stories created in the window, and how many of them have since become
+/// ready and been accepted
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryFunnel {
+    pub created: i64,
+    pub ready: i64,
+    pub accepted: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsSummary {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub events: Vec<AnalyticsEventCount>,
+    pub funnel: StoryFunnel,
+}
+
+#[derive(Clone)]
+pub struct AnalyticsState {
+    pool: Arc<PgPool>,
+    admin: MaintenanceState,
+    /// Needed to find an organization's events when it opts out; `None` when
+    /// analytics are not collected
+    salt: Option<Arc<str>>,
+}
+
+impl AnalyticsState {
+    /// The summary is checked against the same token as the maintenance switch
+    pub fn new(pool: Arc<PgPool>, admin: MaintenanceState, salt: Option<&str>) -> Self {
+        Self {
+            pool,
+            admin,
+            salt: salt.map(Arc::from),
+        }
+    }
+}
+
+/// GET /api/v1/analytics/settings
+async fn get_analytics_settings(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    State(state): State<AnalyticsState>,
+) -> Result<Json<AnalyticsSettings>, AppError> {
+    let organization_id = org_context.effective_organization_uuid().ok_or_else(|| {
+        AppError::BadRequest("Analytics settings are per organization".to_string())
+    })?;
+
+    let opted_out = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (SELECT 1 FROM analytics_opt_outs WHERE organization_id = $1)",
+    )
+    .bind(organization_id)
+    .fetch_one(&*state.pool)
+    .await
+    .map_err(sql_error("reading analytics opt-out"))?;
+
+    Ok(Json(AnalyticsSettings { opted_out }))
+}
+
+/// PUT /api/v1/analytics/settings
+///
+/// Opting out deletes the organization's recorded events.
+async fn update_analytics_settings(
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
+    State(state): State<AnalyticsState>,
+    Json(request): Json<AnalyticsSettings>,
+) -> Result<Json<AnalyticsSettings>, AppError> {
+    let organization_id = org_context.effective_organization_uuid().ok_or_else(|| {
+        AppError::BadRequest("Analytics settings are per organization".to_string())
+    })?;
+    require_organization_admin(
+        &state.pool,
+        organization_id,
+        &auth.sub,
+        "change analytics settings",
+    )
+    .await?;
+
+    let mut tx = state
+        .pool
+        .begin()
+        .await
+        .map_err(sql_error("starting analytics settings update"))?;
+    if request.opted_out {
+        sqlx::query(
+            "INSERT INTO analytics_opt_outs (organization_id, opted_out_at)
+             SELECT id, NOW() FROM organizations WHERE id = $1
+             ON CONFLICT (organization_id) DO NOTHING",
+        )
+        .bind(organization_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(sql_error("recording analytics opt-out"))?;
+        if let Some(salt) = &state.salt {
+            sqlx::query("DELETE FROM analytics_events WHERE organization_hash = $1")
+                .bind(hash_id(salt, organization_id))
+                .execute(&mut *tx)
+                .await
+                .map_err(sql_error("deleting analytics events"))?;
+        }
+    } else {
+        sqlx::query("DELETE FROM analytics_opt_outs WHERE organization_id = $1")
+            .bind(organization_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(sql_error("removing analytics opt-out"))?;
+    }
+    tx.commit()
+        .await
+        .map_err(sql_error("committing analytics settings update"))?;
+
+    Ok(Json(request))
+}
+
+/// GET /api/v1/admin/analytics/summary?from=&to=
+async fn get_analytics_summary(
+    State(state): State<AnalyticsState>,
+    headers: HeaderMap,
+    Query(query): Query<AnalyticsSummaryQuery>,
+) -> Result<Json<AnalyticsSummary>, AppError> {
+    state.admin.require_admin(&headers)?;
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query
+        .from
+        .unwrap_or(to - Duration::days(DEFAULT_WINDOW_DAYS));
+    if from >= to {
+        return Err(AppError::BadRequest(
+            "'from' must be before 'to'".to_string(),
+        ));
+    }
+
+    let events = sqlx::query_as::<_, AnalyticsEventCount>(
+        "SELECT name, COUNT(*) AS events, COUNT(DISTINCT organization_hash) AS organizations
+         FROM analytics_events
+         WHERE occurred_at >= $1 AND occurred_at < $2
+         GROUP BY name
+         ORDER BY name",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(&*state.pool)
+    .await
+    .map_err(sql_error("summarizing analytics events"))?;
+
+    let funnel = sqlx::query_as::<_, StoryFunnel>(
+        "WITH created AS (
+             SELECT DISTINCT subject_hash FROM analytics_events
+             WHERE name = $3 AND occurred_at >= $1 AND occurred_at < $2
+               AND subject_hash IS NOT NULL
+         )
+         SELECT
+             (SELECT COUNT(*) FROM created) AS created,
+             (SELECT COUNT(DISTINCT e.subject_hash) FROM analytics_events e
+              JOIN created c ON c.subject_hash = e.subject_hash
+              WHERE e.name = $4 AND e.occurred_at >= $1) AS ready,
+             (SELECT COUNT(DISTINCT e.subject_hash) FROM analytics_events e
+              JOIN created c ON c.subject_hash = e.subject_hash
+              WHERE e.name = $5 AND e.occurred_at >= $1) AS accepted",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(STORY_CREATED)
+    .bind(STORY_READY)
+    .bind(STORY_ACCEPTED)
+    .fetch_one(&*state.pool)
+    .await
+    .map_err(sql_error("computing story funnel"))?;
+
+    Ok(Json(AnalyticsSummary {
+        from,
+        to,
+        events,
+        funnel,
+    }))
+}
+
+pub fn build_analytics_router(state: AnalyticsState, verifier: Arc<Mutex<JwtVerifier>>) -> Router {
+    ServiceRouter::new("api-gateway/analytics")
+        .route(
+            "/api/v1/analytics/settings",
+            get(get_analytics_settings).put(update_analytics_settings),
+        )
+        .route(
+            "/api/v1/admin/analytics/summary",
+            get(get_analytics_summary),
+        )
+        .with_state(state)
+        .layer(Extension(verifier))
+}
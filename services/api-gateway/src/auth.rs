@@ -2,26 +2,37 @@ use auth_clerk::{ApiKeyAuthClaims, ContextType};
 use axum::{
     body::Body,
     extract::State,
-    http::{HeaderValue, Request},
+    http::{HeaderMap, HeaderValue, Request},
     middleware::Next,
 };
 use common::{error_context::ErrorContext, AppError};
 use sqlx::{PgPool, Row};
 use std::sync::Arc;
 
+use crate::organizations::MembershipCache;
+
 #[derive(Clone)]
 pub struct ApiKeyState {
     pool: Arc<PgPool>,
+    memberships: MembershipCache,
 }
 
 impl ApiKeyState {
     pub fn new(pool: Arc<PgPool>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            memberships: MembershipCache::new(),
+        }
     }
 
     pub fn pool(&self) -> &PgPool {
         &self.pool
     }
+
+    /// Memberships resolved for requests; share it with anything that changes them
+    pub fn membership_cache(&self) -> MembershipCache {
+        self.memberships.clone()
+    }
 }
 
 pub async fn api_key_auth(
@@ -29,7 +40,8 @@ pub async fn api_key_auth(
     mut req: Request<Body>,
     next: Next,
 ) -> Result<axum::response::Response, AppError> {
-    maybe_inject_org_id(&mut req, state.pool()).await?;
+    maybe_inject_org_id(&mut req, &state).await?;
+    reject_deprovisioned_user(req.headers(), &state).await?;
 
     if let Some(key) = extract_api_key(&req) {
         let record = lookup_api_key(state.pool(), key).await?;
@@ -89,6 +101,57 @@ pub async fn api_key_auth(
     Ok(next.run(req).await)
 }
 
+/// Refuse a signed-in user whose identity provider has deprovisioned them from
+/// the requested organization, without waiting for their session to expire.
+/// The bearer token is verified later by the extractors; its subject is only
+/// used here to deny.
+async fn reject_deprovisioned_user(
+    headers: &HeaderMap,
+    state: &ApiKeyState,
+) -> Result<(), AppError> {
+    let Some(org_id) = headers
+        .get("x-organization-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| uuid::Uuid::parse_str(value.trim()).ok())
+    else {
+        return Ok(());
+    };
+    let Some(subject) = bearer_subject(headers) else {
+        return Ok(());
+    };
+
+    // Read on every request rather than from the membership cache, so a SCIM
+    // deprovision takes effect at once on every gateway instance
+    let deprovisioned: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM scim_users
+            JOIN users ON users.id = scim_users.user_id
+            WHERE users.external_id = $1
+              AND scim_users.organization_id = $2
+              AND NOT scim_users.active
+        )
+        "#,
+    )
+    .bind(&subject)
+    .bind(org_id)
+    .fetch_one(state.pool())
+    .await
+    .map_err(|e| AppError::DatabaseError {
+        message: e.to_string(),
+        operation: "reject_deprovisioned_user".to_string(),
+        context: Box::new(ErrorContext::new("api_key_auth")),
+    })?;
+
+    if deprovisioned {
+        return Err(AppError::Forbidden(
+            "Your access to this organization has been removed".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 fn extract_api_key<B>(req: &Request<B>) -> Option<&str> {
     if let Some(value) = req.headers().get("x-api-key") {
         if let Ok(key) = value.to_str() {
@@ -229,7 +292,45 @@ async fn lookup_api_key(pool: &PgPool, token: &str) -> Result<ApiKeyRecord, AppE
     Err(AppError::Unauthorized("Invalid API key".to_string()))
 }
 
-async fn maybe_inject_org_id(req: &mut Request<Body>, pool: &PgPool) -> Result<(), AppError> {
+/// Fail unless the Clerk user is an owner or admin of the organization
+pub(crate) async fn require_organization_admin(
+    pool: &PgPool,
+    organization_id: uuid::Uuid,
+    external_user_id: &str,
+    action: &str,
+) -> Result<(), AppError> {
+    let role = sqlx::query_scalar::<_, String>(
+        "SELECT m.role FROM organization_memberships m
+         JOIN users u ON u.id = m.user_id
+         WHERE m.organization_id = $1 AND u.external_id = $2",
+    )
+    .bind(organization_id)
+    .bind(external_user_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error checking organization role");
+        AppError::InternalServerError
+    })?;
+    if !matches!(role.as_deref(), Some("owner" | "admin")) {
+        return Err(AppError::Forbidden(format!(
+            "Only organization owners and admins can {}",
+            action
+        )));
+    }
+    Ok(())
+}
+
+/// The subject of the request's bearer token, unverified
+fn bearer_subject(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .and_then(|token| auth_clerk::claims::unverified_subject(token.trim()))
+}
+
+async fn maybe_inject_org_id(req: &mut Request<Body>, state: &ApiKeyState) -> Result<(), AppError> {
     let has_org_id = req.headers().contains_key("x-organization-id");
     if has_org_id {
         return Ok(());
@@ -240,55 +341,60 @@ async fn maybe_inject_org_id(req: &mut Request<Body>, pool: &PgPool) -> Result<(
         .get("x-organization-external-id")
         .and_then(|value| value.to_str().ok())
         .map(str::trim)
-        .filter(|value| !value.is_empty());
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
 
     if let Some(external_id) = external {
-        if let Some(record) = lookup_org_by_external_id(pool, external_id).await? {
+        if let Some(record) = state
+            .memberships
+            .organization_by_external_id(state.pool(), &external_id)
+            .await?
+        {
+            insert_organization_headers(req, record.id, &record.name)?;
+        }
+    } else if !req.headers().contains_key("x-context-type") {
+        // A signed-in user who names no organization and no context works in
+        // the organization they last switched to
+        let Some(subject) = bearer_subject(req.headers()) else {
+            return Ok(());
+        };
+        let memberships = state
+            .memberships
+            .memberships(state.pool(), &subject)
+            .await?;
+        if let Some(active) = memberships.active_organization() {
+            insert_organization_headers(req, active.organization_id, &active.name)?;
             req.headers_mut().insert(
-                "x-organization-id",
-                HeaderValue::from_str(&record.id).map_err(|_| AppError::InternalServerError)?,
+                "x-organization-external-id",
+                HeaderValue::from_str(&active.external_id)
+                    .map_err(|_| AppError::InternalServerError)?,
             );
-
-            if !req.headers().contains_key("x-organization-name") {
-                if let Some(name) = record.name {
-                    req.headers_mut().insert(
-                        "x-organization-name",
-                        HeaderValue::from_str(&name).map_err(|_| AppError::InternalServerError)?,
-                    );
-                }
-            }
-
-            if !req.headers().contains_key("x-context-type") {
-                req.headers_mut()
-                    .insert("x-context-type", HeaderValue::from_static("organization"));
-            }
         }
     }
 
     Ok(())
 }
 
-async fn lookup_org_by_external_id(
-    pool: &PgPool,
-    external_id: &str,
-) -> Result<Option<OrgRecord>, AppError> {
-    let record = sqlx::query("SELECT id::text, name FROM organizations WHERE external_id = $1")
-        .bind(external_id)
-        .fetch_optional(pool)
-        .await
-        .map_err(|e| AppError::DatabaseError {
-            message: e.to_string(),
-            operation: "lookup_org_by_external_id".to_string(),
-            context: Box::new(ErrorContext::new("api_key_auth")),
-        })?;
+fn insert_organization_headers(
+    req: &mut Request<Body>,
+    id: uuid::Uuid,
+    name: &str,
+) -> Result<(), AppError> {
+    req.headers_mut().insert(
+        "x-organization-id",
+        HeaderValue::from_str(&id.to_string()).map_err(|_| AppError::InternalServerError)?,
+    );
 
-    Ok(record.map(|row| OrgRecord {
-        id: row.try_get::<String, _>("id").unwrap_or_default(),
-        name: row.try_get::<Option<String>, _>("name").unwrap_or(None),
-    }))
-}
+    if !req.headers().contains_key("x-organization-name") {
+        req.headers_mut().insert(
+            "x-organization-name",
+            HeaderValue::from_str(name).map_err(|_| AppError::InternalServerError)?,
+        );
+    }
 
-struct OrgRecord {
-    id: String,
-    name: Option<String>,
+    if !req.headers().contains_key("x-context-type") {
+        req.headers_mut()
+            .insert("x-context-type", HeaderValue::from_static("organization"));
+    }
+    Ok(())
 }
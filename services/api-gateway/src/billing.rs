@@ -0,0 +1,618 @@
+//! Stripe billing: links each organization to a Stripe customer, keeps its
+//! subscriptions in sync from Stripe webhooks and moves the organization's plan
+//! tier with them. Billing state lives in its own tables (`billing_customers`,
+//! `billing_subscriptions`, `billing_webhook_events`); the only thing written
+//! elsewhere is the tier in `organization_plans`, which the quota guard reads.
+
+use crate::auth::require_organization_admin;
+use crate::maintenance::constant_time_eq;
+use crate::sql_error;
+use auth_clerk::{AuthenticatedWithOrg, JwtVerifier};
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use common::outbound_http::OutboundHttpClient;
+use common::quota::PlanTier;
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use ring::hmac;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const STRIPE_API: &str = "https://api.stripe.com/v1";
+const SIGNATURE_HEADER: &str = "stripe-signature";
+/// Webhooks signed longer ago than this are rejected as possible replays
+const SIGNATURE_TOLERANCE_SECS: i64 = 300;
+/// Subscription statuses that keep the paid tier; `past_due` keeps it while Stripe retries payment
+const ENTITLED_STATUSES: [&str; 3] = ["active", "trialing", "past_due"];
+
+/// Stripe keys and the price each paid tier is sold at
+pub struct BillingConfig {
+    secret_key: String,
+    webhook_secret: String,
+    price_tiers: HashMap<String, PlanTier>,
+    portal_return_url: String,
+}
+
+impl BillingConfig {
+    /// Read `STRIPE_SECRET_KEY`, `STRIPE_WEBHOOK_SECRET`, `STRIPE_PRICE_TEAM`,
+    /// `STRIPE_PRICE_ENTERPRISE` and `BILLING_PORTAL_RETURN_URL`; `None` unless
+    /// both keys are set
+    pub fn from_secrets(get: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let value = |key: &str| get(key).filter(|value| !value.trim().is_empty());
+        let secret_key = value("STRIPE_SECRET_KEY")?;
+        let webhook_secret = value("STRIPE_WEBHOOK_SECRET")?;
+
+        let mut price_tiers = HashMap::new();
+        for (key, tier) in [
+            ("STRIPE_PRICE_TEAM", PlanTier::Team),
+            ("STRIPE_PRICE_ENTERPRISE", PlanTier::Enterprise),
+        ] {
+            if let Some(price) = value(key) {
+                price_tiers.insert(price, tier);
+            }
+        }
+
+        Some(Self {
+            secret_key,
+            webhook_secret,
+            price_tiers,
+            portal_return_url: value("BILLING_PORTAL_RETURN_URL")
+                .unwrap_or_else(|| "http://localhost:3000/settings/billing".to_string()),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct BillingState {
+    pool: Arc<PgPool>,
+    config: Option<Arc<BillingConfig>>,
+    client: OutboundHttpClient,
+}
+
+impl BillingState {
+    /// Without a config every billing endpoint is refused
+    pub fn new(pool: Arc<PgPool>, config: Option<BillingConfig>) -> Self {
+        Self {
+            pool,
+            config: config.map(Arc::new),
+            client: OutboundHttpClient::builder().build(),
+        }
+    }
+
+    fn config(&self) -> Result<&BillingConfig, AppError> {
+        self.config
+            .as_deref()
+            .ok_or_else(|| AppError::Forbidden("Billing is not configured".to_string()))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Check a `Stripe-Signature` header (`t=<unix time>,v1=<hex hmac>,...`)
+/// against the raw payload
+fn verify_signature(
+    secret: &str,
+    header: &str,
+    payload: &[u8],
+    now: DateTime<Utc>,
+) -> Result<(), AppError> {
+    let invalid = || AppError::BadRequest("Invalid Stripe signature".to_string());
+
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+    let timestamp = timestamp.ok_or_else(invalid)?;
+    if (now.timestamp() - timestamp).abs() > SIGNATURE_TOLERANCE_SECS {
+        return Err(invalid());
+    }
+
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let mut signed = format!("{}.", timestamp).into_bytes();
+    signed.extend_from_slice(payload);
+    let expected = to_hex(hmac::sign(&key, &signed).as_ref());
+
+    if signatures
+        .iter()
+        .any(|signature| constant_time_eq(signature.as_bytes(), expected.as_bytes()))
+    {
+        Ok(())
+    } else {
+        Err(invalid())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEvent {
+    id: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    created: i64,
+    data: StripeEventData,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEventData {
+    object: serde_json::Value,
+}
+
+impl StripeEvent {
+    fn object<T: DeserializeOwned>(&self) -> Result<T, AppError> {
+        serde_json::from_value(self.data.object.clone()).map_err(|e| {
+            AppError::BadRequest(format!("Unexpected {} payload: {}", self.event_type, e))
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckoutSession {
+    customer: Option<String>,
+    client_reference_id: Option<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeSubscription {
+    id: String,
+    customer: String,
+    status: String,
+    current_period_end: Option<i64>,
+    #[serde(default)]
+    cancel_at_period_end: bool,
+    items: SubscriptionItems,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubscriptionItems {
+    data: Vec<SubscriptionItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubscriptionItem {
+    price: StripePrice,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripePrice {
+    id: String,
+}
+
+impl StripeSubscription {
+    fn price_id(&self) -> Option<&str> {
+        self.items.data.first().map(|item| item.price.id.as_str())
+    }
+}
+
+/// Organization a checkout or subscription was started for, as set by our checkout links
+fn organization_reference(
+    client_reference_id: Option<&str>,
+    metadata: &HashMap<String, String>,
+) -> Option<Uuid> {
+    client_reference_id
+        .or(metadata.get("organization_id").map(String::as_str))
+        .and_then(|value| Uuid::parse_str(value).ok())
+}
+
+/// The tier an organization's subscriptions entitle it to: the highest tier
+/// among entitled subscriptions, or free without any. `None` when an entitled
+/// subscription is for a price no tier is configured for, so a misconfigured
+/// price never downgrades a paying organization.
+fn entitled_tier(
+    subscriptions: &[(String, Option<String>)],
+    price_tiers: &HashMap<String, PlanTier>,
+) -> Option<PlanTier> {
+    let mut tier = PlanTier::Free;
+    for (status, price_id) in subscriptions {
+        if !ENTITLED_STATUSES.contains(&status.as_str()) {
+            continue;
+        }
+        let price_tier = price_id
+            .as_ref()
+            .and_then(|price_id| price_tiers.get(price_id))?;
+        tier = tier.max(*price_tier);
+    }
+    Some(tier)
+}
+
+async fn link_customer(
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    customer_id: &str,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO billing_customers (organization_id, stripe_customer_id, created_at)
+         SELECT id, $2, NOW() FROM organizations WHERE id = $1
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(organization_id)
+    .bind(customer_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(sql_error("linking Stripe customer"))?;
+    Ok(())
+}
+
+async fn apply_checkout(
+    tx: &mut Transaction<'_, Postgres>,
+    session: CheckoutSession,
+) -> Result<(), AppError> {
+    let organization_id =
+        organization_reference(session.client_reference_id.as_deref(), &session.metadata);
+    match (organization_id, session.customer) {
+        (Some(organization_id), Some(customer_id)) => {
+            link_customer(tx, organization_id, &customer_id).await
+        }
+        _ => {
+            tracing::warn!("Checkout session without organization reference or customer");
+            Ok(())
+        }
+    }
+}
+
+async fn apply_subscription(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &BillingConfig,
+    subscription: StripeSubscription,
+    event_created: DateTime<Utc>,
+    deleted: bool,
+) -> Result<(), AppError> {
+    if let Some(organization_id) = organization_reference(None, &subscription.metadata) {
+        link_customer(tx, organization_id, &subscription.customer).await?;
+    }
+    let organization_id = sqlx::query_scalar::<_, Uuid>(
+        "SELECT organization_id FROM billing_customers WHERE stripe_customer_id = $1",
+    )
+    .bind(&subscription.customer)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(sql_error("resolving Stripe customer"))?;
+    let Some(organization_id) = organization_id else {
+        tracing::warn!(
+            subscription_id = %subscription.id,
+            "Subscription for a Stripe customer not linked to any organization"
+        );
+        return Ok(());
+    };
+
+    let status = if deleted {
+        "canceled"
+    } else {
+        subscription.status.as_str()
+    };
+    // Stripe does not guarantee delivery order; older events never overwrite newer state
+    let applied = sqlx::query_scalar::<_, String>(
+        "INSERT INTO billing_subscriptions
+             (stripe_subscription_id, organization_id, stripe_customer_id, status, stripe_price_id,
+              current_period_end, cancel_at_period_end, last_event_at, updated_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())
+         ON CONFLICT (stripe_subscription_id) DO UPDATE SET
+             status = EXCLUDED.status,
+             stripe_price_id = EXCLUDED.stripe_price_id,
+             current_period_end = EXCLUDED.current_period_end,
+             cancel_at_period_end = EXCLUDED.cancel_at_period_end,
+             last_event_at = EXCLUDED.last_event_at,
+             updated_at = NOW()
+         WHERE billing_subscriptions.last_event_at <= EXCLUDED.last_event_at
+         RETURNING stripe_subscription_id",
+    )
+    .bind(&subscription.id)
+    .bind(organization_id)
+    .bind(&subscription.customer)
+    .bind(status)
+    .bind(subscription.price_id())
+    .bind(
+        subscription
+            .current_period_end
+            .and_then(|end| DateTime::from_timestamp(end, 0)),
+    )
+    .bind(subscription.cancel_at_period_end)
+    .bind(event_created)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(sql_error("storing Stripe subscription"))?;
+    if applied.is_none() {
+        return Ok(());
+    }
+
+    let subscriptions = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT status, stripe_price_id FROM billing_subscriptions WHERE organization_id = $1",
+    )
+    .bind(organization_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(sql_error("loading Stripe subscriptions"))?;
+    let Some(tier) = entitled_tier(&subscriptions, &config.price_tiers) else {
+        tracing::warn!(
+            %organization_id,
+            subscription_id = %subscription.id,
+            "Subscription price is not mapped to a plan tier; plan left unchanged"
+        );
+        return Ok(());
+    };
+
+    // Limit overrides set by maintainers are kept across tier changes
+    sqlx::query(
+        "INSERT INTO organization_plans (organization_id, tier, updated_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (organization_id) DO UPDATE SET tier = EXCLUDED.tier, updated_at = NOW()",
+    )
+    .bind(organization_id)
+    .bind(tier.as_str())
+    .execute(&mut **tx)
+    .await
+    .map_err(sql_error("updating plan from subscription"))?;
+    tracing::info!(%organization_id, tier = tier.as_str(), "Plan updated from Stripe subscription");
+    Ok(())
+}
+
+/// POST /api/v1/billing/webhook
+///
+/// Each event is applied once; redeliveries of an event already handled are acknowledged.
+async fn handle_webhook(
+    State(state): State<BillingState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, AppError> {
+    let config = state.config()?;
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| AppError::BadRequest("Missing Stripe signature".to_string()))?;
+    verify_signature(&config.webhook_secret, signature, &body, Utc::now())?;
+
+    let event: StripeEvent = serde_json::from_slice(&body)
+        .map_err(|e| AppError::BadRequest(format!("Invalid Stripe event: {}", e)))?;
+    let event_created = DateTime::from_timestamp(event.created, 0).unwrap_or_else(Utc::now);
+
+    let mut tx = state
+        .pool
+        .begin()
+        .await
+        .map_err(sql_error("starting webhook transaction"))?;
+    let first_delivery = sqlx::query(
+        "INSERT INTO billing_webhook_events (event_id, event_type, received_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (event_id) DO NOTHING",
+    )
+    .bind(&event.id)
+    .bind(&event.event_type)
+    .execute(&mut *tx)
+    .await
+    .map_err(sql_error("recording Stripe event"))?
+    .rows_affected()
+        > 0;
+    if !first_delivery {
+        return Ok(StatusCode::OK);
+    }
+
+    match event.event_type.as_str() {
+        "checkout.session.completed" => apply_checkout(&mut tx, event.object()?).await?,
+        "customer.subscription.created"
+        | "customer.subscription.updated"
+        | "customer.subscription.deleted" => {
+            let deleted = event.event_type == "customer.subscription.deleted";
+            apply_subscription(&mut tx, config, event.object()?, event_created, deleted).await?
+        }
+        other => tracing::debug!(event_type = other, "Ignoring Stripe event"),
+    }
+
+    tx.commit()
+        .await
+        .map_err(sql_error("committing webhook transaction"))?;
+    Ok(StatusCode::OK)
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeObject {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripePortalSession {
+    url: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortalLinkResponse {
+    pub url: String,
+}
+
+async fn stripe_post<T: DeserializeOwned>(
+    state: &BillingState,
+    call_site: &'static str,
+    path: &str,
+    form: &[(&str, String)],
+    idempotency_key: Option<String>,
+) -> Result<T, AppError> {
+    let config = state.config()?;
+    let mut request = state
+        .client
+        .post(&format!("{}{}", STRIPE_API, path))
+        .bearer_auth(&config.secret_key)
+        .form(form);
+    if let Some(key) = idempotency_key {
+        request = request.header("Idempotency-Key", key);
+    }
+
+    let response = state.client.send(call_site, request).await.map_err(|e| {
+        tracing::error!(error = %e, call_site, "Stripe request failed");
+        AppError::ExternalServiceError("Stripe is unavailable".to_string())
+    })?;
+    if !response.status().is_success() {
+        tracing::error!(status = %response.status(), call_site, "Stripe rejected request");
+        return Err(AppError::ExternalServiceError(
+            "Stripe rejected the request".to_string(),
+        ));
+    }
+    response.json().await.map_err(|e| {
+        tracing::error!(error = %e, call_site, "Unreadable Stripe response");
+        AppError::ExternalServiceError("Unreadable Stripe response".to_string())
+    })
+}
+
+/// The organization's Stripe customer, created on first use
+async fn ensure_customer(state: &BillingState, organization_id: Uuid) -> Result<String, AppError> {
+    let lookup = || async {
+        sqlx::query_scalar::<_, String>(
+            "SELECT stripe_customer_id FROM billing_customers WHERE organization_id = $1",
+        )
+        .bind(organization_id)
+        .fetch_optional(&*state.pool)
+        .await
+        .map_err(sql_error("loading Stripe customer"))
+    };
+    if let Some(customer_id) = lookup().await? {
+        return Ok(customer_id);
+    }
+
+    let name = sqlx::query_scalar::<_, String>("SELECT name FROM organizations WHERE id = $1")
+        .bind(organization_id)
+        .fetch_optional(&*state.pool)
+        .await
+        .map_err(sql_error("loading organization for billing"))?
+        .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+    // The idempotency key makes concurrent first requests share one customer
+    let customer: StripeObject = stripe_post(
+        state,
+        "api-gateway.stripe.customer",
+        "/customers",
+        &[
+            ("name", name),
+            ("metadata[organization_id]", organization_id.to_string()),
+        ],
+        Some(format!("customer-{}", organization_id)),
+    )
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO billing_customers (organization_id, stripe_customer_id, created_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(organization_id)
+    .bind(&customer.id)
+    .execute(&*state.pool)
+    .await
+    .map_err(sql_error("storing Stripe customer"))?;
+    lookup().await?.ok_or(AppError::InternalServerError)
+}
+
+/// GET /api/v1/billing/portal-link
+///
+/// A short-lived Stripe customer portal session where owners and admins manage
+/// the subscription, payment methods and invoices.
+async fn get_portal_link(
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
+    State(state): State<BillingState>,
+) -> Result<Json<PortalLinkResponse>, AppError> {
+    let config = state.config()?;
+    let organization_id = org_context
+        .effective_organization_uuid()
+        .ok_or_else(|| AppError::BadRequest("Billing is managed per organization".to_string()))?;
+
+    require_organization_admin(&state.pool, organization_id, &auth.sub, "manage billing").await?;
+
+    let customer_id = ensure_customer(&state, organization_id).await?;
+    let session: StripePortalSession = stripe_post(
+        &state,
+        "api-gateway.stripe.portal",
+        "/billing_portal/sessions",
+        &[
+            ("customer", customer_id),
+            ("return_url", config.portal_return_url.clone()),
+        ],
+        None,
+    )
+    .await?;
+
+    Ok(Json(PortalLinkResponse { url: session.url }))
+}
+
+pub fn build_billing_router(state: BillingState, verifier: Arc<Mutex<JwtVerifier>>) -> Router {
+    ServiceRouter::new("api-gateway/billing")
+        .route("/api/v1/billing/portal-link", get(get_portal_link))
+        .route("/api/v1/billing/webhook", post(handle_webhook))
+        .with_state(state)
+        .layer(Extension(verifier))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &str, timestamp: i64, payload: &str) -> String {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+        let signature = hmac::sign(&key, format!("{}.{}", timestamp, payload).as_bytes());
+        format!("t={},v1={}", timestamp, to_hex(signature.as_ref()))
+    }
+
+    #[test]
+    fn test_webhook_signature_is_verified() {
+        let now = Utc::now();
+        let payload = r#"{"id":"evt_1"}"#;
+        let header = sign("whsec_test", now.timestamp(), payload);
+
+        verify_signature("whsec_test", &header, payload.as_bytes(), now).unwrap();
+        assert!(verify_signature("whsec_other", &header, payload.as_bytes(), now).is_err());
+        assert!(verify_signature("whsec_test", &header, b"{}", now).is_err());
+
+        let stale = sign("whsec_test", now.timestamp() - 600, payload);
+        assert!(verify_signature("whsec_test", &stale, payload.as_bytes(), now).is_err());
+        assert!(verify_signature("whsec_test", "v1=abc", payload.as_bytes(), now).is_err());
+    }
+
+    #[test]
+    fn test_entitled_tier_follows_active_subscriptions() {
+        let prices = HashMap::from([
+            ("price_team".to_string(), PlanTier::Team),
+            ("price_enterprise".to_string(), PlanTier::Enterprise),
+        ]);
+        let subscription =
+            |status: &str, price: &str| (status.to_string(), Some(price.to_string()));
+
+        assert_eq!(entitled_tier(&[], &prices), Some(PlanTier::Free));
+        assert_eq!(
+            entitled_tier(&[subscription("active", "price_team")], &prices),
+            Some(PlanTier::Team)
+        );
+        assert_eq!(
+            entitled_tier(
+                &[
+                    subscription("canceled", "price_enterprise"),
+                    subscription("past_due", "price_team"),
+                ],
+                &prices
+            ),
+            Some(PlanTier::Team)
+        );
+        assert_eq!(
+            entitled_tier(&[subscription("unpaid", "price_team")], &prices),
+            Some(PlanTier::Free)
+        );
+        assert_eq!(
+            entitled_tier(&[subscription("active", "price_unknown")], &prices),
+            None
+        );
+    }
+}
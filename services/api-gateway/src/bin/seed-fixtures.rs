@@ -0,0 +1,48 @@
+//! Loads seed fixtures into a local development database, so a fresh
+//! `docker compose up` has organizations, projects, sprints and stories to
+//! work with. Refuses to run when `ENVIRONMENT` is `production`.
+//!
+//! ```text
+//! DATABASE_URL=... cargo run -p api-gateway --bin seed-fixtures -- [fixtures/dev.json ...]
+//! ```
+
+use anyhow::{bail, Context, Result};
+use api_gateway::fixtures::Fixture;
+use sqlx::PgPool;
+
+const DEFAULT_FIXTURE: &str = "fixtures/dev.json";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    if std::env::var("ENVIRONMENT").is_ok_and(|environment| environment == "production") {
+        bail!("Seed fixtures are for development databases only");
+    }
+    let mut paths: Vec<String> = std::env::args().skip(1).collect();
+    if paths.is_empty() {
+        paths.push(DEFAULT_FIXTURE.to_string());
+    }
+
+    let database_url = std::env::var("DATABASE_URL").context("DATABASE_URL must be set")?;
+    let pool = PgPool::connect(&database_url)
+        .await
+        .context("connecting to the database")?;
+
+    for path in paths {
+        let json = std::fs::read_to_string(&path).with_context(|| format!("reading {}", path))?;
+        let summary = Fixture::from_json(&json)
+            .with_context(|| format!("in {}", path))?
+            .load(&pool)
+            .await
+            .with_context(|| format!("loading {}", path))?;
+        println!(
+            "{}: added {} organizations, {} projects, {} sprints, {} stories, {} tasks",
+            path,
+            summary.organizations,
+            summary.projects,
+            summary.sprints,
+            summary.stories,
+            summary.tasks
+        );
+    }
+    Ok(())
+}
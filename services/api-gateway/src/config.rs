@@ -0,0 +1,117 @@
+//! Gateway settings that differ between environments, read once at startup.
+
+use axum::http::HeaderValue;
+use common::http_security::{hsts_value, CorsOrigins, SecurityHeaders, DEFAULT_HSTS_MAX_AGE_SECS};
+
+const DEFAULT_ENVIRONMENT: &str = "development";
+const LOCAL_CORS_ORIGINS: &str = "http://localhost:3000";
+
+pub struct AppConfig {
+    pub environment: String,
+    pub cors_origins: CorsOrigins,
+    pub security_headers: SecurityHeaders,
+}
+
+impl AppConfig {
+    /// Read `ENVIRONMENT` (default `development`) and the origins allowed
+    /// cross-origin from `CORS_ALLOWED_ORIGINS_<ENVIRONMENT>`, falling back to
+    /// `CORS_ALLOWED_ORIGINS`; local environments default to the web app on
+    /// localhost. `HSTS_MAX_AGE_SECS` (default a year, `0` and local
+    /// environments send none), `REFERRER_POLICY` and `CONTENT_SECURITY_POLICY`
+    /// override the security headers.
+    pub fn from_secrets(get: impl Fn(&str) -> Option<String>) -> Self {
+        let value = |key: &str| get(key).filter(|value| !value.trim().is_empty());
+        let environment = value("ENVIRONMENT")
+            .map(|environment| environment.trim().to_ascii_lowercase())
+            .unwrap_or_else(|| DEFAULT_ENVIRONMENT.to_string());
+        let local = matches!(environment.as_str(), "development" | "local" | "test");
+
+        let origins = value(&format!(
+            "CORS_ALLOWED_ORIGINS_{}",
+            environment.to_ascii_uppercase()
+        ))
+        .or_else(|| value("CORS_ALLOWED_ORIGINS"))
+        .or_else(|| local.then(|| LOCAL_CORS_ORIGINS.to_string()));
+        let cors_origins = CorsOrigins::parse(origins.as_deref().unwrap_or_default());
+        if cors_origins.is_empty() {
+            tracing::warn!(%environment, "No CORS origins configured; cross-origin requests will be refused");
+        }
+
+        let header = |key: &str, default: HeaderValue| match value(key) {
+            Some(raw) => HeaderValue::from_str(raw.trim()).unwrap_or_else(|_| {
+                tracing::warn!(key, "Invalid header value; using the default");
+                default
+            }),
+            None => default,
+        };
+        let defaults = SecurityHeaders::default();
+        let hsts_max_age = match value("HSTS_MAX_AGE_SECS") {
+            Some(raw) => raw.trim().parse::<u64>().unwrap_or_else(|_| {
+                tracing::warn!(value = %raw, "Invalid HSTS_MAX_AGE_SECS; using the default");
+                DEFAULT_HSTS_MAX_AGE_SECS
+            }),
+            None if local => 0,
+            None => DEFAULT_HSTS_MAX_AGE_SECS,
+        };
+        let security_headers = SecurityHeaders {
+            hsts: (hsts_max_age > 0).then(|| hsts_value(hsts_max_age)),
+            referrer_policy: header("REFERRER_POLICY", defaults.referrer_policy),
+            content_security_policy: header(
+                "CONTENT_SECURITY_POLICY",
+                defaults.content_security_policy,
+            ),
+        };
+
+        Self {
+            environment,
+            cors_origins,
+            security_headers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(values: &[(&str, &str)]) -> AppConfig {
+        let values: HashMap<String, String> = values
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        AppConfig::from_secrets(|key| values.get(key).cloned())
+    }
+
+    #[test]
+    fn test_settings_follow_the_environment() {
+        let origin = |value: &str| HeaderValue::from_str(value).unwrap();
+
+        let local = config(&[]);
+        assert_eq!(local.environment, "development");
+        assert!(local.cors_origins.allows(&origin("http://localhost:3000")));
+        assert_eq!(local.security_headers.hsts, None);
+
+        let production = config(&[
+            ("ENVIRONMENT", "Production"),
+            ("CORS_ALLOWED_ORIGINS", "http://localhost:3000"),
+            ("CORS_ALLOWED_ORIGINS_PRODUCTION", "https://*.gamalan.dev"),
+            ("REFERRER_POLICY", "no-referrer"),
+        ]);
+        assert!(production
+            .cors_origins
+            .allows(&origin("https://app.gamalan.dev")));
+        assert!(!production
+            .cors_origins
+            .allows(&origin("http://localhost:3000")));
+        assert_eq!(
+            production.security_headers.hsts,
+            Some(hsts_value(DEFAULT_HSTS_MAX_AGE_SECS))
+        );
+        assert_eq!(production.security_headers.referrer_policy, "no-referrer");
+
+        let staging = config(&[("ENVIRONMENT", "staging"), ("HSTS_MAX_AGE_SECS", "0")]);
+        assert!(staging.cors_origins.is_empty());
+        assert_eq!(staging.security_headers.hsts, None);
+    }
+}
@@ -0,0 +1,509 @@
+//! Configuration bundles: an organization's readiness configuration exported
+//! as one versioned JSON document and imported into other organizations, so
+//! platform teams can roll a standard setup out to every organization they run.
+//!
+//! A bundle holds the scoring profile, the changes to the vague-term
+//! dictionaries, and the organization's blueprints, which carry the readiness
+//! policy (Definition of Ready) and conventions new projects start with.
+//! Prompt templates are built into the prompt builder and not configurable per
+//! organization, so they are not part of the schema.
+//!
+//! Every section is optional. An imported section replaces the target's scoring
+//! profile and vague-term changes; blueprints are created or replaced by key and
+//! the target's other blueprints are kept. The whole bundle is validated before
+//! anything is written, and a dry run returns the changes without applying them.
+
+use crate::auth::require_organization_admin;
+use auth_clerk::{AuthenticatedWithOrg, JwtVerifier, OrganizationContext};
+use axum::{
+    extract::{Query, State},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use projects_api::{ProjectBlueprint, ProjectUsecases};
+use readiness_api::{
+    validate_vague_term_overrides, ContentLanguage, ReadinessUsecases, ScoringProfile,
+    ScoringProfilePayload, VagueTermOverride,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// The bundle format this server writes and reads
+pub const CONFIG_BUNDLE_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBundle {
+    pub schema_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exported_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scoring_profile: Option<ScoringProfilePayload>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vague_terms: Option<Vec<VagueTermOverride>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blueprints: Option<Vec<ProjectBlueprint>>,
+}
+
+/// A bundle that passed validation, in the form the services store it
+#[derive(Debug)]
+struct ValidatedBundle {
+    scoring_profile: Option<ScoringProfile>,
+    vague_terms: Option<Vec<VagueTermOverride>>,
+    blueprints: Option<Vec<ProjectBlueprint>>,
+}
+
+impl ConfigBundle {
+    fn validate(self) -> Result<ValidatedBundle, AppError> {
+        if self.schema_version != CONFIG_BUNDLE_SCHEMA_VERSION {
+            return Err(AppError::BadRequest(format!(
+                "Unsupported config bundle schema version {}; expected {}",
+                self.schema_version, CONFIG_BUNDLE_SCHEMA_VERSION
+            )));
+        }
+
+        let scoring_profile = self.scoring_profile.map(ScoringProfile::from);
+        if let Some(profile) = &scoring_profile {
+            profile.validate()?;
+        }
+
+        let vague_terms = self
+            .vague_terms
+            .map(|terms| {
+                let terms = terms
+                    .into_iter()
+                    .map(|entry| {
+                        VagueTermOverride::new(&entry.term, entry.suggestion, entry.removed)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                validate_vague_term_overrides(&terms)?;
+                Ok::<_, AppError>(terms)
+            })
+            .transpose()?;
+
+        let blueprints = self
+            .blueprints
+            .map(|blueprints| {
+                let mut keys = HashSet::new();
+                blueprints
+                    .into_iter()
+                    .map(|blueprint| {
+                        let blueprint = ProjectBlueprint::custom(
+                            &blueprint.key,
+                            &blueprint.name,
+                            blueprint.description,
+                            blueprint.estimation_scale,
+                            blueprint.dor_template,
+                            blueprint.conventions,
+                        )?;
+                        if !keys.insert(blueprint.key.clone()) {
+                            return Err(AppError::BadRequest(format!(
+                                "Blueprint '{}' is listed more than once",
+                                blueprint.key
+                            )));
+                        }
+                        Ok(blueprint)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        Ok(ValidatedBundle {
+            scoring_profile,
+            vague_terms,
+            blueprints,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigSection {
+    ScoringProfile,
+    VagueTerms,
+    Blueprints,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigChangeKind {
+    Added,
+    Updated,
+    Removed,
+}
+
+/// One difference between the target organization's configuration and a bundle
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChange {
+    pub section: ConfigSection,
+    /// The vague term or blueprint key; absent for the scoring profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub change: ConfigChangeKind,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl ConfigChange {
+    fn new<T: Serialize>(
+        section: ConfigSection,
+        key: Option<&str>,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> Self {
+        let change = match (before, after) {
+            (None, _) => ConfigChangeKind::Added,
+            (_, None) => ConfigChangeKind::Removed,
+            _ => ConfigChangeKind::Updated,
+        };
+        Self {
+            section,
+            key: key.map(str::to_string),
+            change,
+            before: before.and_then(|value| serde_json::to_value(value).ok()),
+            after: after.and_then(|value| serde_json::to_value(value).ok()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigImportReport {
+    pub dry_run: bool,
+    pub changes: Vec<ConfigChange>,
+}
+
+/// What importing `incoming` into an organization configured as `current`
+/// changes. Sections missing from the bundle change nothing.
+fn diff_config(current: &ValidatedBundle, incoming: &ValidatedBundle) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+
+    if let (Some(before), Some(after)) = (&current.scoring_profile, &incoming.scoring_profile) {
+        if before != after {
+            changes.push(ConfigChange::new(
+                ConfigSection::ScoringProfile,
+                None,
+                Some(&ScoringProfilePayload::from(*before)),
+                Some(&ScoringProfilePayload::from(*after)),
+            ));
+        }
+    }
+
+    if let Some(incoming_terms) = &incoming.vague_terms {
+        let before = by_key(current.vague_terms.as_deref(), |entry| &entry.term);
+        let after = by_key(Some(incoming_terms), |entry| &entry.term);
+        changes.extend(diff_keyed(ConfigSection::VagueTerms, &before, &after, true));
+    }
+
+    if let Some(incoming_blueprints) = &incoming.blueprints {
+        let before = by_key(current.blueprints.as_deref(), |blueprint| &blueprint.key);
+        let after = by_key(Some(incoming_blueprints), |blueprint| &blueprint.key);
+        changes.extend(diff_keyed(
+            ConfigSection::Blueprints,
+            &before,
+            &after,
+            false,
+        ));
+    }
+
+    changes
+}
+
+fn by_key<T>(items: Option<&[T]>, key: impl Fn(&T) -> &String) -> BTreeMap<&str, &T> {
+    items
+        .unwrap_or_default()
+        .iter()
+        .map(|item| (key(item).as_str(), item))
+        .collect()
+}
+
+/// Entries added or updated by `after`, and when the section is replaced as a
+/// whole, the entries of `before` it drops
+fn diff_keyed<T: Serialize + PartialEq>(
+    section: ConfigSection,
+    before: &BTreeMap<&str, &T>,
+    after: &BTreeMap<&str, &T>,
+    replaces: bool,
+) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+    for (key, entry) in after {
+        match before.get(key) {
+            Some(existing) if existing == entry => {}
+            existing => changes.push(ConfigChange::new(
+                section,
+                Some(key),
+                existing.copied(),
+                Some(*entry),
+            )),
+        }
+    }
+    if replaces {
+        for (key, existing) in before {
+            if !after.contains_key(key) {
+                changes.push(ConfigChange::new(section, Some(key), Some(*existing), None));
+            }
+        }
+    }
+    changes
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigImportQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Clone)]
+pub struct ConfigBundleState {
+    pool: Arc<PgPool>,
+    readiness: Arc<ReadinessUsecases>,
+    projects: Arc<ProjectUsecases>,
+}
+
+impl ConfigBundleState {
+    pub fn new(
+        pool: Arc<PgPool>,
+        readiness: Arc<ReadinessUsecases>,
+        projects: Arc<ProjectUsecases>,
+    ) -> Self {
+        Self {
+            pool,
+            readiness,
+            projects,
+        }
+    }
+
+    async fn current_config(&self, organization_id: Uuid) -> Result<ValidatedBundle, AppError> {
+        let organization_id = Some(organization_id);
+        let scoring_profile = self.readiness.scoring_profile(organization_id).await?;
+        let vague_terms = self
+            .readiness
+            .get_vague_term_overrides(organization_id)
+            .await?;
+        let blueprints = self
+            .projects
+            .list_blueprints(organization_id)
+            .await?
+            .into_iter()
+            .filter(|blueprint| !blueprint.built_in)
+            .collect();
+        Ok(ValidatedBundle {
+            scoring_profile: Some(scoring_profile),
+            vague_terms: Some(vague_terms),
+            blueprints: Some(blueprints),
+        })
+    }
+}
+
+fn organization_id(org_context: &OrganizationContext) -> Result<Uuid, AppError> {
+    org_context
+        .effective_organization_uuid()
+        .ok_or_else(|| AppError::BadRequest("Config bundles are per organization".to_string()))
+}
+
+/// GET /api/v1/config-bundles/export
+async fn export_config_bundle(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    State(state): State<ConfigBundleState>,
+) -> Result<Json<ConfigBundle>, AppError> {
+    let config = state.current_config(organization_id(&org_context)?).await?;
+
+    Ok(Json(ConfigBundle {
+        schema_version: CONFIG_BUNDLE_SCHEMA_VERSION,
+        exported_at: Some(Utc::now()),
+        scoring_profile: config.scoring_profile.map(ScoringProfilePayload::from),
+        vague_terms: config.vague_terms,
+        blueprints: config.blueprints,
+    }))
+}
+
+/// POST /api/v1/config-bundles/import?dryRun=
+async fn import_config_bundle(
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
+    State(state): State<ConfigBundleState>,
+    Query(query): Query<ConfigImportQuery>,
+    Json(bundle): Json<ConfigBundle>,
+) -> Result<Json<ConfigImportReport>, AppError> {
+    let organization_id = organization_id(&org_context)?;
+    require_organization_admin(
+        &state.pool,
+        organization_id,
+        &auth.sub,
+        "import configuration",
+    )
+    .await?;
+
+    let incoming = bundle.validate()?;
+    let current = state.current_config(organization_id).await?;
+    let changes = diff_config(&current, &incoming);
+
+    if !query.dry_run {
+        let organization_id = Some(organization_id);
+        let changed = |section| changes.iter().any(|change| change.section == section);
+        if let Some(profile) = incoming.scoring_profile {
+            if changed(ConfigSection::ScoringProfile) {
+                state
+                    .readiness
+                    .replace_scoring_profile(organization_id, profile)
+                    .await?;
+            }
+        }
+        if let Some(terms) = incoming.vague_terms {
+            if changed(ConfigSection::VagueTerms) {
+                state
+                    .readiness
+                    .replace_vague_term_overrides(
+                        organization_id,
+                        terms,
+                        ContentLanguage::default(),
+                    )
+                    .await?;
+            }
+        }
+        for blueprint in incoming.blueprints.iter().flatten() {
+            let blueprint_changed = changes.iter().any(|change| {
+                change.section == ConfigSection::Blueprints
+                    && change.key.as_deref() == Some(blueprint.key.as_str())
+            });
+            if blueprint_changed {
+                state
+                    .projects
+                    .save_blueprint(blueprint, organization_id)
+                    .await?;
+            }
+        }
+        tracing::info!(
+            organization_id = ?organization_id,
+            changes = changes.len(),
+            "Imported config bundle"
+        );
+    }
+
+    Ok(Json(ConfigImportReport {
+        dry_run: query.dry_run,
+        changes,
+    }))
+}
+
+pub fn build_config_bundle_router(
+    state: ConfigBundleState,
+    verifier: Arc<Mutex<JwtVerifier>>,
+) -> Router {
+    ServiceRouter::new("api-gateway/config-bundles")
+        .route("/api/v1/config-bundles/export", get(export_config_bundle))
+        .route("/api/v1/config-bundles/import", post(import_config_bundle))
+        .with_state(state)
+        .layer(Extension(verifier))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundle(value: Value) -> Result<ValidatedBundle, AppError> {
+        serde_json::from_value::<ConfigBundle>(value)
+            .unwrap()
+            .validate()
+    }
+
+    #[test]
+    fn test_bundles_are_validated_before_import() {
+        assert!(matches!(
+            bundle(json!({ "schemaVersion": 2 })),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(bundle(json!({
+            "schemaVersion": 1,
+            "vagueTerms": [
+                { "term": "Fix", "suggestion": null, "removed": true },
+                { "term": "fix ", "suggestion": "Name the bug", "removed": false }
+            ]
+        }))
+        .is_err());
+        assert!(bundle(json!({
+            "schemaVersion": 1,
+            "blueprints": [{
+                "key": "scrum-starter",
+                "name": "Shadows a built-in",
+                "description": null,
+                "estimationScale": "fibonacci",
+                "dorTemplate": {
+                    "required_fields": ["title"],
+                    "acceptance_criteria_required": true,
+                    "story_points_required": true,
+                    "labels_required": []
+                },
+                "conventions": {}
+            }]
+        }))
+        .is_err());
+
+        let valid = bundle(json!({
+            "schemaVersion": 1,
+            "scoringProfile": { "aiReadyThreshold": 80 },
+            "vagueTerms": [{ "term": " Soon ", "suggestion": "Give a date", "removed": false }]
+        }))
+        .unwrap();
+        assert_eq!(valid.scoring_profile.unwrap().ai_ready_threshold, 80);
+        assert_eq!(valid.vague_terms.unwrap()[0].term, "soon");
+        assert!(valid.blueprints.is_none());
+    }
+
+    #[test]
+    fn test_dry_run_diff_against_current_config() {
+        let term = |term: &str, suggestion: &str| {
+            VagueTermOverride::new(term, Some(suggestion.to_string()), false).unwrap()
+        };
+        let current = ValidatedBundle {
+            scoring_profile: Some(ScoringProfile::default()),
+            vague_terms: Some(vec![
+                term("soon", "Give a date"),
+                term("fast", "Give a number"),
+            ]),
+            blueprints: Some(Vec::new()),
+        };
+        let incoming = ValidatedBundle {
+            scoring_profile: Some(ScoringProfile::default()),
+            vague_terms: Some(vec![
+                term("soon", "Give a date"),
+                term("fast", "State a p95"),
+            ]),
+            blueprints: None,
+        };
+        let changes = diff_config(&current, &incoming);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].section, ConfigSection::VagueTerms);
+        assert_eq!(changes[0].key.as_deref(), Some("fast"));
+        assert_eq!(changes[0].change, ConfigChangeKind::Updated);
+
+        let incoming = ValidatedBundle {
+            scoring_profile: None,
+            vague_terms: Some(vec![term("asap", "Give a date")]),
+            blueprints: None,
+        };
+        let kinds: Vec<_> = diff_config(&current, &incoming)
+            .into_iter()
+            .map(|change| (change.key.unwrap(), change.change))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("asap".to_string(), ConfigChangeKind::Added),
+                ("fast".to_string(), ConfigChangeKind::Removed),
+                ("soon".to_string(), ConfigChangeKind::Removed),
+            ]
+        );
+        assert!(diff_config(&current, &current).is_empty());
+    }
+}
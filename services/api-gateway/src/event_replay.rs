@@ -0,0 +1,493 @@
+//! Event replay for staging: maintainers export a window of the domain events
+//! kept in the event outbox, with names, text and people pseudonymized, and a
+//! staging deployment replays the export through its own bus so projection and
+//! consumer changes meet realistic event streams before release.
+
+use crate::maintenance::MaintenanceState;
+use crate::sql_error;
+use axum::{
+    extract::{DefaultBodyLimit, Query, State},
+    http::HeaderMap,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use event_bus::{
+    AcceptanceCriterionRecord, BacklogEvent, DomainEvent, EventBus, EventEnvelope, SprintEvent,
+    SprintRecord, SprintScopeChange, StoryRecord, TaskRecord,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_WINDOW_HOURS: i64 = 24;
+const DEFAULT_LIMIT: i64 = 1_000;
+const MAX_LIMIT: i64 = 10_000;
+/// Replay bodies carry up to `MAX_LIMIT` full events
+const MAX_REPLAY_BODY_BYTES: usize = 64 * 1024 * 1024;
+
+/// Stable name of the event's variant, e.g. `backlog.story_created`
+pub(crate) fn event_kind(event: &DomainEvent) -> &'static str {
+    match event {
+        DomainEvent::Backlog(event) => match event {
+            BacklogEvent::StoryCreated { .. } => "backlog.story_created",
+            BacklogEvent::StoryUpdated { .. } => "backlog.story_updated",
+            BacklogEvent::StoryDeleted { .. } => "backlog.story_deleted",
+            BacklogEvent::StoryArchived { .. } => "backlog.story_archived",
+            BacklogEvent::TaskCreated { .. } => "backlog.task_created",
+            BacklogEvent::TaskUpdated { .. } => "backlog.task_updated",
+            BacklogEvent::TaskDeleted { .. } => "backlog.task_deleted",
+            BacklogEvent::StoryDependenciesChanged { .. } => "backlog.story_dependencies_changed",
+            BacklogEvent::StoryBlockersChanged { .. } => "backlog.story_blockers_changed",
+        },
+        DomainEvent::Sprint(event) => match event {
+            SprintEvent::Created { .. } => "sprint.created",
+            SprintEvent::Updated { .. } => "sprint.updated",
+            SprintEvent::Deleted { .. } => "sprint.deleted",
+            SprintEvent::StoryAdded { .. } => "sprint.story_added",
+            SprintEvent::StoryRemoved { .. } => "sprint.story_removed",
+        },
+    }
+}
+
+/// Replaces everything that could identify a customer or a person. Ids of
+/// organizations and people map to stable pseudonyms under the salt, so
+/// relations between exported events survive; text keeps its length and shape.
+struct Anonymizer<'a> {
+    salt: &'a str,
+}
+
+impl Anonymizer<'_> {
+    fn id(&self, id: Uuid) -> Uuid {
+        Uuid::new_v5(
+            &Uuid::NAMESPACE_OID,
+            format!("{}:{}", self.salt, id).as_bytes(),
+        )
+    }
+
+    fn text(&self, text: &str) -> String {
+        text.chars()
+            .map(|c| {
+                if c.is_alphabetic() {
+                    'x'
+                } else if c.is_numeric() {
+                    '0'
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+
+    fn label(&self, label: &str) -> String {
+        let pseudonym = Uuid::new_v5(
+            &Uuid::NAMESPACE_OID,
+            format!("{}:label:{}", self.salt, label.to_lowercase()).as_bytes(),
+        );
+        format!("label-{}", &pseudonym.simple().to_string()[..8])
+    }
+
+    fn story(&self, story: StoryRecord) -> StoryRecord {
+        StoryRecord {
+            organization_id: story.organization_id.map(|id| self.id(id)),
+            title: self.text(&story.title),
+            description: story.description.map(|text| self.text(&text)),
+            labels: story.labels.iter().map(|label| self.label(label)).collect(),
+            acceptance_criteria: story
+                .acceptance_criteria
+                .into_iter()
+                .map(|criterion| AcceptanceCriterionRecord {
+                    description: self.text(&criterion.description),
+                    given: self.text(&criterion.given),
+                    when: self.text(&criterion.when),
+                    then: self.text(&criterion.then),
+                    ..criterion
+                })
+                .collect(),
+            assigned_to_user_id: story.assigned_to_user_id.map(|id| self.id(id)),
+            readiness_override_by: story.readiness_override_by.map(|id| self.id(id)),
+            readiness_override_reason: story.readiness_override_reason.map(|text| self.text(&text)),
+            ..story
+        }
+    }
+
+    fn task(&self, task: TaskRecord) -> TaskRecord {
+        TaskRecord {
+            organization_id: task.organization_id.map(|id| self.id(id)),
+            title: self.text(&task.title),
+            description: task.description.map(|text| self.text(&text)),
+            owner_user_id: task.owner_user_id.map(|id| self.id(id)),
+            ..task
+        }
+    }
+
+    fn sprint(&self, sprint: SprintRecord) -> SprintRecord {
+        SprintRecord {
+            organization_id: sprint.organization_id.map(|id| self.id(id)),
+            name: self.text(&sprint.name),
+            goal: sprint.goal.map(|text| self.text(&text)),
+            ..sprint
+        }
+    }
+
+    fn scope_change(&self, change: SprintScopeChange) -> SprintScopeChange {
+        SprintScopeChange {
+            organization_id: change.organization_id.map(|id| self.id(id)),
+            ..change
+        }
+    }
+
+    fn event(&self, event: DomainEvent) -> DomainEvent {
+        let organization = |id: Option<Uuid>| id.map(|id| self.id(id));
+        match event {
+            DomainEvent::Backlog(event) => DomainEvent::Backlog(match event {
+                BacklogEvent::StoryCreated { story } => BacklogEvent::StoryCreated {
+                    story: self.story(story),
+                },
+                BacklogEvent::StoryUpdated { story } => BacklogEvent::StoryUpdated {
+                    story: self.story(story),
+                },
+                BacklogEvent::StoryDeleted {
+                    story_id,
+                    organization_id,
+                } => BacklogEvent::StoryDeleted {
+                    story_id,
+                    organization_id: organization(organization_id),
+                },
+                BacklogEvent::StoryArchived {
+                    story_id,
+                    organization_id,
+                } => BacklogEvent::StoryArchived {
+                    story_id,
+                    organization_id: organization(organization_id),
+                },
+                BacklogEvent::TaskCreated { task } => BacklogEvent::TaskCreated {
+                    task: self.task(task),
+                },
+                BacklogEvent::TaskUpdated { task } => BacklogEvent::TaskUpdated {
+                    task: self.task(task),
+                },
+                BacklogEvent::TaskDeleted {
+                    task_id,
+                    story_id,
+                    organization_id,
+                } => BacklogEvent::TaskDeleted {
+                    task_id,
+                    story_id,
+                    organization_id: organization(organization_id),
+                },
+                BacklogEvent::StoryDependenciesChanged {
+                    story_id,
+                    organization_id,
+                    depends_on,
+                } => BacklogEvent::StoryDependenciesChanged {
+                    story_id,
+                    organization_id: organization(organization_id),
+                    depends_on,
+                },
+                BacklogEvent::StoryBlockersChanged {
+                    story_id,
+                    organization_id,
+                    blocked_by,
+                } => BacklogEvent::StoryBlockersChanged {
+                    story_id,
+                    organization_id: organization(organization_id),
+                    blocked_by,
+                },
+            }),
+            DomainEvent::Sprint(event) => DomainEvent::Sprint(match event {
+                SprintEvent::Created { sprint } => SprintEvent::Created {
+                    sprint: self.sprint(sprint),
+                },
+                SprintEvent::Updated { sprint } => SprintEvent::Updated {
+                    sprint: self.sprint(sprint),
+                },
+                SprintEvent::Deleted {
+                    sprint_id,
+                    organization_id,
+                } => SprintEvent::Deleted {
+                    sprint_id,
+                    organization_id: organization(organization_id),
+                },
+                SprintEvent::StoryAdded { change } => SprintEvent::StoryAdded {
+                    change: self.scope_change(change),
+                },
+                SprintEvent::StoryRemoved { change } => SprintEvent::StoryRemoved {
+                    change: self.scope_change(change),
+                },
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportEventsQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Only events of this (real) organization
+    pub organization_id: Option<Uuid>,
+    pub limit: Option<i64>,
+}
+
+/// Anonymized events in the order they occurred. The body can be posted to a
+/// staging deployment's replay endpoint as is.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventExport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// More events fell in the window than were exported; narrow the window to get the rest
+    pub truncated: bool,
+    pub events: Vec<EventEnvelope>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplayEventsRequest {
+    pub events: Vec<EventEnvelope>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayEventsResponse {
+    pub replayed: usize,
+}
+
+#[derive(Clone)]
+pub struct EventReplayState {
+    pool: Arc<PgPool>,
+    admin: MaintenanceState,
+    event_bus: Arc<EventBus>,
+    export_salt: Option<Arc<str>>,
+    replay_enabled: bool,
+}
+
+impl EventReplayState {
+    /// Export is refused without a salt to pseudonymize with; replay is refused
+    /// unless `replay_enabled`, so production never replays into itself
+    pub fn new(
+        pool: Arc<PgPool>,
+        admin: MaintenanceState,
+        event_bus: Arc<EventBus>,
+        export_salt: Option<String>,
+        replay_enabled: bool,
+    ) -> Self {
+        Self {
+            pool,
+            admin,
+            event_bus,
+            export_salt: export_salt
+                .filter(|salt| !salt.trim().is_empty())
+                .map(Arc::from),
+            replay_enabled,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct OutboxRow {
+    event_id: Uuid,
+    envelope: serde_json::Value,
+}
+
+/// GET /api/v1/admin/events/export
+async fn export_events(
+    State(state): State<EventReplayState>,
+    headers: HeaderMap,
+    Query(query): Query<ExportEventsQuery>,
+) -> Result<Json<EventExport>, AppError> {
+    state.admin.require_admin(&headers)?;
+    let salt = state
+        .export_salt
+        .as_deref()
+        .ok_or_else(|| AppError::Forbidden("Event export is not configured".to_string()))?;
+
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query
+        .from
+        .unwrap_or(to - Duration::hours(DEFAULT_WINDOW_HOURS));
+    if from >= to {
+        return Err(AppError::BadRequest(
+            "'from' must be before 'to'".to_string(),
+        ));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let mut rows = sqlx::query_as::<_, OutboxRow>(
+        "SELECT event_id, envelope FROM event_outbox
+         WHERE delivered_at IS NOT NULL
+           AND occurred_at >= $1 AND occurred_at < $2
+           AND ($3::UUID IS NULL OR organization_id = $3)
+         ORDER BY occurred_at, sequence
+         LIMIT $4",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(query.organization_id)
+    .bind(limit + 1)
+    .fetch_all(&*state.pool)
+    .await
+    .map_err(sql_error("exporting events"))?;
+    let truncated = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+
+    let anonymizer = Anonymizer { salt };
+    let events = rows
+        .into_iter()
+        .filter_map(|row| match serde_json::from_value::<EventEnvelope>(row.envelope) {
+            Ok(envelope) => Some(EventEnvelope {
+                // Exports stay free of request ids
+                correlation_id: None,
+                causation_id: None,
+                event: anonymizer.event(envelope.event),
+                ..envelope
+            }),
+            Err(e) => {
+                // Events stored before a breaking change to their shape
+                tracing::warn!(error = %e, event_id = %row.event_id, "Skipping unreadable outbox event");
+                None
+            }
+        })
+        .collect();
+
+    Ok(Json(EventExport {
+        from,
+        to,
+        truncated,
+        events,
+    }))
+}
+
+/// POST /api/v1/admin/events/replay
+///
+/// Publishes the events on this deployment's bus in the order they occurred,
+/// keeping their ids and timestamps.
+async fn replay_events(
+    State(state): State<EventReplayState>,
+    headers: HeaderMap,
+    Json(request): Json<ReplayEventsRequest>,
+) -> Result<Json<ReplayEventsResponse>, AppError> {
+    state.admin.require_admin(&headers)?;
+    if !state.replay_enabled {
+        return Err(AppError::Forbidden(
+            "Event replay is not enabled on this deployment".to_string(),
+        ));
+    }
+    if request.events.len() > MAX_LIMIT as usize {
+        return Err(AppError::BadRequest(format!(
+            "At most {} events can be replayed at once",
+            MAX_LIMIT
+        )));
+    }
+
+    let mut events = request.events;
+    events.sort_by_key(|envelope| envelope.occurred_at);
+    let replayed = events.len();
+    for envelope in events {
+        state.event_bus.publish_envelope(envelope).await;
+    }
+
+    tracing::warn!(replayed, "Replayed exported events");
+    Ok(Json(ReplayEventsResponse { replayed }))
+}
+
+pub fn build_event_replay_router(state: EventReplayState) -> Router {
+    ServiceRouter::new("api-gateway/event-replay")
+        .route("/api/v1/admin/events/export", get(export_events))
+        .route(
+            "/api/v1/admin/events/replay",
+            post(replay_events).layer(DefaultBodyLimit::max(MAX_REPLAY_BODY_BYTES)),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(organization_id: Uuid, user_id: Uuid) -> StoryRecord {
+        let now = Utc::now();
+        StoryRecord {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            organization_id: Some(organization_id),
+            title: "Export invoices for ACME Corp".to_string(),
+            description: Some("Finance needs Q3 2025 invoices".to_string()),
+            status: "ready".to_string(),
+            labels: vec!["acme".to_string(), "Finance".to_string()],
+            acceptance_criteria: vec![AcceptanceCriterionRecord {
+                id: Uuid::new_v4(),
+                story_id: Uuid::new_v4(),
+                description: "Invoices export".to_string(),
+                given: "an invoice".to_string(),
+                when: "exported".to_string(),
+                then: "a CSV is produced".to_string(),
+                position: 0,
+                created_at: now,
+            }],
+            story_points: Some(5),
+            sprint_id: None,
+            assigned_to_user_id: Some(user_id),
+            readiness_override: true,
+            readiness_override_by: Some(user_id),
+            readiness_override_reason: Some("Jane approved".to_string()),
+            readiness_override_at: Some(now),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_anonymizer_keeps_structure_but_not_content() {
+        let organization_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let original = story(organization_id, user_id);
+        let anonymizer = Anonymizer { salt: "salt" };
+
+        let DomainEvent::Backlog(BacklogEvent::StoryUpdated { story: anonymized }) = anonymizer
+            .event(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
+                story: original.clone(),
+            }))
+        else {
+            panic!("event kind changed");
+        };
+
+        assert_eq!(anonymized.id, original.id);
+        assert_eq!(anonymized.project_id, original.project_id);
+        assert_eq!(anonymized.story_points, Some(5));
+        assert_eq!(anonymized.title, "xxxxxx xxxxxxxx xxx xxxx xxxx");
+        assert_eq!(
+            anonymized.description.as_deref(),
+            Some("xxxxxxx xxxxx x0 0000 xxxxxxxx")
+        );
+        assert_eq!(anonymized.acceptance_criteria[0].then, "x xxx xx xxxxxxxx");
+        assert!(anonymized
+            .labels
+            .iter()
+            .all(|label| label.starts_with("label-")));
+
+        let pseudonym = anonymized.organization_id.unwrap();
+        assert_ne!(pseudonym, organization_id);
+        assert_eq!(pseudonym, anonymizer.id(organization_id));
+        assert_eq!(
+            anonymized.assigned_to_user_id,
+            anonymized.readiness_override_by
+        );
+        assert_ne!(anonymized.assigned_to_user_id, Some(user_id));
+        assert_ne!(Anonymizer { salt: "other" }.id(organization_id), pseudonym);
+    }
+
+    #[test]
+    fn test_event_kind_and_organization() {
+        let organization_id = Uuid::new_v4();
+        let event = DomainEvent::Sprint(SprintEvent::Deleted {
+            sprint_id: Uuid::new_v4(),
+            organization_id: Some(organization_id),
+        });
+        assert_eq!(event_kind(&event), "sprint.deleted");
+        assert_eq!(event.organization_id(), Some(organization_id));
+    }
+}
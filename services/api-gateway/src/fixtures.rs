@@ -0,0 +1,623 @@
+//! Declarative seed data for local development.
+//!
+//! A fixture file is JSON describing organizations, their members, projects,
+//! sprints and stories, with acceptance criteria, tasks and readiness results.
+//! Every row gets an id derived from its place in the file, so loading the
+//! same file again leaves existing rows alone and only adds what is new.
+//! Load one with the `seed-fixtures` binary.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use event_bus::AcceptanceCriterionRecord;
+use serde::Deserialize;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Namespace of the v5 ids fixture rows are given
+const FIXTURE_NAMESPACE: Uuid = Uuid::from_u128(0x6d2c_95a1_4f0e_4b7a_9a53_1f0c_8e2d_7b44);
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Fixture {
+    pub organizations: Vec<OrganizationFixture>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct OrganizationFixture {
+    pub slug: String,
+    pub name: String,
+    /// The Clerk organization id to sign in to it with; `fixture_<slug>` by default
+    pub external_id: Option<String>,
+    #[serde(default)]
+    pub members: Vec<MemberFixture>,
+    #[serde(default)]
+    pub projects: Vec<ProjectFixture>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MemberFixture {
+    pub email: String,
+    /// `owner`, `admin` or `member`
+    #[serde(default = "default_member_role")]
+    pub role: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProjectFixture {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub sprints: Vec<SprintFixture>,
+    #[serde(default)]
+    pub stories: Vec<StoryFixture>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SprintFixture {
+    pub name: String,
+    #[serde(default)]
+    pub goal: String,
+    /// `planning`, `active`, `review` or `completed`
+    #[serde(default = "default_sprint_status")]
+    pub status: String,
+    pub capacity_points: u32,
+    /// Days before loading that the sprint started; negative for future sprints
+    #[serde(default)]
+    pub started_days_ago: i64,
+    #[serde(default = "default_sprint_days")]
+    pub length_days: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct StoryFixture {
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(default = "default_story_status")]
+    pub status: String,
+    #[serde(rename = "type", default = "default_story_type")]
+    pub story_type: String,
+    pub points: Option<u32>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Name of one of the project's sprints
+    pub sprint: Option<String>,
+    #[serde(default)]
+    pub acceptance_criteria: Vec<CriterionFixture>,
+    #[serde(default)]
+    pub tasks: Vec<TaskFixture>,
+    pub readiness: Option<ReadinessFixture>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CriterionFixture {
+    pub given: String,
+    pub when: String,
+    pub then: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TaskFixture {
+    pub title: String,
+    pub description: Option<String>,
+    /// `available`, `owned`, `inprogress` or `completed`
+    #[serde(default = "default_task_status")]
+    pub status: String,
+    /// Email of a member of the organization; required unless the task is available
+    pub owner: Option<String>,
+    pub estimated_hours: Option<u32>,
+    /// Acceptance criteria the task covers, e.g. `AC1`; all of the story's by default
+    #[serde(default)]
+    pub criteria: Vec<String>,
+}
+
+/// The story's latest readiness evaluation
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ReadinessFixture {
+    pub score: i32,
+    #[serde(default)]
+    pub missing_items: Vec<String>,
+    #[serde(default)]
+    pub summary: String,
+}
+
+fn default_member_role() -> String {
+    "member".to_string()
+}
+
+fn default_sprint_status() -> String {
+    "planning".to_string()
+}
+
+fn default_sprint_days() -> i64 {
+    14
+}
+
+fn default_story_status() -> String {
+    "draft".to_string()
+}
+
+fn default_story_type() -> String {
+    "feature".to_string()
+}
+
+fn default_task_status() -> String {
+    "available".to_string()
+}
+
+/// Rows added by a load; rows already present are not counted
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FixtureSummary {
+    pub organizations: u64,
+    pub projects: u64,
+    pub sprints: u64,
+    pub stories: u64,
+    pub tasks: u64,
+}
+
+fn fixture_id(path: &str) -> Uuid {
+    Uuid::new_v5(&FIXTURE_NAMESPACE, path.as_bytes())
+}
+
+impl Fixture {
+    pub fn from_json(json: &str) -> Result<Self> {
+        let fixture: Self = serde_json::from_str(json).context("parsing fixture")?;
+        fixture.validate()?;
+        Ok(fixture)
+    }
+
+    /// Catch references the database would reject with a less helpful error
+    fn validate(&self) -> Result<()> {
+        for org in &self.organizations {
+            for project in &org.projects {
+                for story in &project.stories {
+                    let at = || format!("story '{}' of project '{}'", story.title, project.name);
+                    if let Some(sprint) = &story.sprint {
+                        if !project.sprints.iter().any(|s| &s.name == sprint) {
+                            bail!("{} names unknown sprint '{}'", at(), sprint);
+                        }
+                    }
+                    for task in &story.tasks {
+                        if story.acceptance_criteria.is_empty() {
+                            bail!("{} has tasks but no acceptance criteria", at());
+                        }
+                        match &task.owner {
+                            Some(owner) if !org.members.iter().any(|m| &m.email == owner) => {
+                                bail!(
+                                    "task '{}' of {} is owned by non-member {}",
+                                    task.title,
+                                    at(),
+                                    owner
+                                )
+                            }
+                            Some(_) if task.status == "available" => {
+                                bail!(
+                                    "task '{}' of {} is available but has an owner",
+                                    task.title,
+                                    at()
+                                )
+                            }
+                            None if task.status != "available" => {
+                                bail!(
+                                    "task '{}' of {} is {} but has no owner",
+                                    task.title,
+                                    at(),
+                                    task.status
+                                )
+                            }
+                            _ => {}
+                        }
+                        for criterion in &task.criteria {
+                            let known = (1..=story.acceptance_criteria.len())
+                                .any(|n| criterion == &format!("AC{}", n));
+                            if !known {
+                                bail!(
+                                    "task '{}' of {} covers unknown {}",
+                                    task.title,
+                                    at(),
+                                    criterion
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Insert every row of the fixture that is not already there, in one transaction
+    pub async fn load(&self, pool: &PgPool) -> Result<FixtureSummary> {
+        let mut tx = pool.begin().await.context("starting transaction")?;
+        let mut summary = FixtureSummary::default();
+        let now = Utc::now();
+        for org in &self.organizations {
+            load_organization(&mut tx, org, now, &mut summary)
+                .await
+                .with_context(|| format!("loading organization '{}'", org.slug))?;
+        }
+        tx.commit().await.context("committing fixture")?;
+        Ok(summary)
+    }
+}
+
+async fn load_organization(
+    tx: &mut Transaction<'_, Postgres>,
+    org: &OrganizationFixture,
+    now: DateTime<Utc>,
+    summary: &mut FixtureSummary,
+) -> Result<()> {
+    let org_path = format!("org:{}", org.slug);
+    let org_id = fixture_id(&org_path);
+    let external_id = org
+        .external_id
+        .clone()
+        .unwrap_or_else(|| format!("fixture_{}", org.slug));
+    summary.organizations += sqlx::query(
+        "INSERT INTO organizations (id, external_id, name, slug, created_at, updated_at)
+         VALUES ($1, $2, $3, $4, $5, $5)
+         ON CONFLICT (id) DO NOTHING",
+    )
+    .bind(org_id)
+    .bind(&external_id)
+    .bind(&org.name)
+    .bind(&org.slug)
+    .bind(now)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+
+    let mut members = HashMap::new();
+    for member in &org.members {
+        let user_id = fixture_id(&format!("user:{}", member.email));
+        sqlx::query(
+            "INSERT INTO users (id, external_id, email, role, specialty, created_at, updated_at)
+             VALUES ($1, $2, $3, 'contributor', 'fullstack', $4, $4)
+             ON CONFLICT (id) DO NOTHING",
+        )
+        .bind(user_id)
+        .bind(format!("fixture_{}", member.email))
+        .bind(&member.email)
+        .bind(now)
+        .execute(&mut **tx)
+        .await
+        .with_context(|| format!("adding user {}", member.email))?;
+        sqlx::query(
+            "INSERT INTO organization_memberships (id, organization_id, user_id, role, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $5)
+             ON CONFLICT (organization_id, user_id) DO NOTHING",
+        )
+        .bind(fixture_id(&format!("{}/member:{}", org_path, member.email)))
+        .bind(org_id)
+        .bind(user_id)
+        .bind(&member.role)
+        .bind(now)
+        .execute(&mut **tx)
+        .await
+        .with_context(|| format!("adding member {}", member.email))?;
+        members.insert(member.email.as_str(), user_id);
+    }
+
+    for project in &org.projects {
+        load_project(
+            tx,
+            org_id,
+            &format!("{}/project:{}", org_path, project.name),
+            project,
+            &members,
+            now,
+            summary,
+        )
+        .await
+        .with_context(|| format!("loading project '{}'", project.name))?;
+    }
+    Ok(())
+}
+
+async fn load_project(
+    tx: &mut Transaction<'_, Postgres>,
+    org_id: Uuid,
+    project_path: &str,
+    project: &ProjectFixture,
+    members: &HashMap<&str, Uuid>,
+    now: DateTime<Utc>,
+    summary: &mut FixtureSummary,
+) -> Result<()> {
+    let project_id = fixture_id(project_path);
+    let team_id = fixture_id(&format!("{}/team", project_path));
+    sqlx::query(
+        "INSERT INTO teams (id, name, organization_id, created_at, updated_at)
+         VALUES ($1, $2, $3, $4, $4)
+         ON CONFLICT (id) DO NOTHING",
+    )
+    .bind(team_id)
+    .bind(format!("{} Team", project.name))
+    .bind(org_id)
+    .bind(now)
+    .execute(&mut **tx)
+    .await?;
+    summary.projects += sqlx::query(
+        "INSERT INTO projects (id, organization_id, name, description, team_id, created_at, updated_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $6)
+         ON CONFLICT (id) DO NOTHING",
+    )
+    .bind(project_id)
+    .bind(org_id)
+    .bind(&project.name)
+    .bind(&project.description)
+    .bind(team_id)
+    .bind(now)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+
+    let mut sprints = HashMap::new();
+    for sprint in &project.sprints {
+        let sprint_id = fixture_id(&format!("{}/sprint:{}", project_path, sprint.name));
+        let in_sprint = || {
+            project
+                .stories
+                .iter()
+                .filter(|story| story.sprint.as_deref() == Some(sprint.name.as_str()))
+        };
+        let committed: u32 = in_sprint().filter_map(|story| story.points).sum();
+        let completed: u32 = in_sprint()
+            .filter(|story| story.status == "accepted")
+            .filter_map(|story| story.points)
+            .sum();
+        let start = now - Duration::days(sprint.started_days_ago);
+        summary.sprints += sqlx::query(
+            "INSERT INTO sprints (id, team_id, name, goal, status, capacity_points,
+                                  committed_points, completed_points, start_date, end_date,
+                                  created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11)
+             ON CONFLICT (id) DO NOTHING",
+        )
+        .bind(sprint_id)
+        .bind(team_id)
+        .bind(&sprint.name)
+        .bind(&sprint.goal)
+        .bind(&sprint.status)
+        .bind(sprint.capacity_points as i32)
+        .bind(committed as i32)
+        .bind(completed as i32)
+        .bind(start)
+        .bind(start + Duration::days(sprint.length_days))
+        .bind(now)
+        .execute(&mut **tx)
+        .await
+        .with_context(|| format!("adding sprint '{}'", sprint.name))?
+        .rows_affected();
+        if sprint.status == "active" {
+            sqlx::query("UPDATE teams SET active_sprint_id = $1 WHERE id = $2")
+                .bind(sprint_id)
+                .bind(team_id)
+                .execute(&mut **tx)
+                .await?;
+        }
+        sprints.insert(sprint.name.as_str(), sprint_id);
+    }
+
+    for story in &project.stories {
+        let story_path = format!("{}/story:{}", project_path, story.title);
+        load_story(
+            tx,
+            org_id,
+            project_id,
+            &story_path,
+            story,
+            story
+                .sprint
+                .as_deref()
+                .and_then(|name| sprints.get(name).copied()),
+            members,
+            now,
+            summary,
+        )
+        .await
+        .with_context(|| format!("adding story '{}'", story.title))?;
+    }
+    Ok(())
+}
+
+/// The next `KEY-n` short key of the project
+async fn next_short_key(tx: &mut Transaction<'_, Postgres>, project_id: Uuid) -> Result<String> {
+    Ok(sqlx::query_scalar(
+        "UPDATE projects SET next_item_number = next_item_number + 1
+         WHERE id = $1
+         RETURNING key || '-' || (next_item_number - 1)",
+    )
+    .bind(project_id)
+    .fetch_one(&mut **tx)
+    .await?)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn load_story(
+    tx: &mut Transaction<'_, Postgres>,
+    org_id: Uuid,
+    project_id: Uuid,
+    story_path: &str,
+    story: &StoryFixture,
+    sprint_id: Option<Uuid>,
+    members: &HashMap<&str, Uuid>,
+    now: DateTime<Utc>,
+    summary: &mut FixtureSummary,
+) -> Result<()> {
+    let story_id = fixture_id(story_path);
+    let inserted = sqlx::query(
+        "INSERT INTO stories (id, project_id, organization_id, title, description, status,
+                              story_type, story_points, labels, sprint_id, created_at, updated_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11)
+         ON CONFLICT (id) DO NOTHING",
+    )
+    .bind(story_id)
+    .bind(project_id)
+    .bind(org_id)
+    .bind(&story.title)
+    .bind(&story.description)
+    .bind(&story.status)
+    .bind(&story.story_type)
+    .bind(story.points.map(|points| points as i32))
+    .bind(&story.labels)
+    .bind(sprint_id)
+    .bind(now)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+    if inserted == 0 {
+        return Ok(());
+    }
+    summary.stories += 1;
+    sqlx::query("UPDATE stories SET short_key = $1 WHERE id = $2")
+        .bind(next_short_key(tx, project_id).await?)
+        .bind(story_id)
+        .execute(&mut **tx)
+        .await?;
+
+    let mut criteria = Vec::new();
+    for (index, criterion) in story.acceptance_criteria.iter().enumerate() {
+        let ac_id = format!("AC{}", index + 1);
+        let record = AcceptanceCriterionRecord {
+            id: fixture_id(&format!("{}/{}", story_path, ac_id)),
+            story_id,
+            description: format!(
+                "Given {}, when {}, then {}",
+                criterion.given, criterion.when, criterion.then
+            ),
+            given: criterion.given.clone(),
+            when: criterion.when.clone(),
+            then: criterion.then.clone(),
+            position: index as u32,
+            created_at: now,
+        };
+        sqlx::query(
+            "INSERT INTO acceptance_criteria (id, story_id, organization_id, ac_id, description,
+                                              given, when_clause, then_clause, position, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+        )
+        .bind(record.id)
+        .bind(story_id)
+        .bind(org_id)
+        .bind(&ac_id)
+        .bind(&record.description)
+        .bind(&record.given)
+        .bind(&record.when)
+        .bind(&record.then)
+        .bind(record.position as i32)
+        .bind(now)
+        .execute(&mut **tx)
+        .await?;
+        criteria.push(record);
+    }
+
+    // Readiness reads stories from its projection, which events would otherwise fill
+    sqlx::query(
+        "INSERT INTO readiness_story_projections (id, organization_id, project_id, title,
+             description, status, labels, story_points, acceptance_criteria, sprint_id,
+             created_at, updated_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11)
+         ON CONFLICT (id) DO NOTHING",
+    )
+    .bind(story_id)
+    .bind(org_id)
+    .bind(project_id)
+    .bind(&story.title)
+    .bind(&story.description)
+    .bind(&story.status)
+    .bind(&story.labels)
+    .bind(story.points.map(|points| points as i32))
+    .bind(serde_json::to_value(&criteria)?)
+    .bind(sprint_id)
+    .bind(now)
+    .execute(&mut **tx)
+    .await?;
+
+    if let Some(readiness) = &story.readiness {
+        sqlx::query(
+            "INSERT INTO readiness_evals (id, story_id, organization_id, score, missing_items,
+                                          summary, evaluated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(fixture_id(&format!("{}/readiness", story_path)))
+        .bind(story_id)
+        .bind(org_id)
+        .bind(readiness.score)
+        .bind(&readiness.missing_items)
+        .bind(&readiness.summary)
+        .bind(now)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    let all_criteria: Vec<String> = (1..=criteria.len()).map(|n| format!("AC{}", n)).collect();
+    for task in &story.tasks {
+        let task_id = fixture_id(&format!("{}/task:{}", story_path, task.title));
+        let owner = task
+            .owner
+            .as_deref()
+            .and_then(|email| members.get(email).copied());
+        let owned_at = owner.map(|_| now);
+        let completed_at = (task.status == "completed").then_some(now);
+        let refs = if task.criteria.is_empty() {
+            &all_criteria
+        } else {
+            &task.criteria
+        };
+        for table in ["tasks", "readiness_task_projections"] {
+            sqlx::query(&format!(
+                "INSERT INTO {} (id, story_id, organization_id, title, description,
+                                 acceptance_criteria_refs, status, owner_user_id, estimated_hours,
+                                 owned_at, completed_at, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $12)",
+                table
+            ))
+            .bind(task_id)
+            .bind(story_id)
+            .bind(org_id)
+            .bind(&task.title)
+            .bind(&task.description)
+            .bind(refs)
+            .bind(&task.status)
+            .bind(owner)
+            .bind(task.estimated_hours.map(|hours| hours as i32))
+            .bind(owned_at)
+            .bind(completed_at)
+            .bind(now)
+            .execute(&mut **tx)
+            .await
+            .with_context(|| format!("adding task '{}'", task.title))?;
+        }
+        sqlx::query("UPDATE tasks SET short_key = $1 WHERE id = $2")
+            .bind(next_short_key(tx, project_id).await?)
+            .bind(task_id)
+            .execute(&mut **tx)
+            .await?;
+        summary.tasks += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_fixture_is_valid() {
+        let fixture = Fixture::from_json(include_str!("../../../fixtures/dev.json")).unwrap();
+        assert!(!fixture.organizations.is_empty());
+
+        let unknown_sprint = r#"{"organizations": [{"slug": "a", "name": "A", "projects": [
+            {"name": "P", "stories": [{"title": "S", "sprint": "Missing"}]}]}]}"#;
+        assert!(Fixture::from_json(unknown_sprint).is_err());
+        assert_eq!(fixture_id("org:a"), fixture_id("org:a"));
+    }
+}
@@ -0,0 +1,477 @@
+//! Support impersonation: a super-admin mints a short-lived token that acts as a
+//! target user, optionally inside one organization. Requests made with it
+//! (`Authorization: Impersonation <token>`) are written to the impersonation audit
+//! log tagged with the super-admin, and answered with an `X-Impersonating` header.
+
+use auth_clerk::{ApiKeyAuthClaims, Authenticated, ContextType, JwtVerifier};
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::{delete, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use ring::digest;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const AUTHORIZATION_SCHEME: &str = "Impersonation ";
+const IMPERSONATING_HEADER: &str = "x-impersonating";
+const DEFAULT_DURATION_MINUTES: i64 = 30;
+const MAX_DURATION_MINUTES: i64 = 120;
+
+#[derive(Clone)]
+pub struct ImpersonationState {
+    pool: Arc<PgPool>,
+    super_admins: Arc<HashSet<String>>,
+}
+
+impl ImpersonationState {
+    /// `super_admin_ids` is a comma-separated list of Clerk user IDs. Without it
+    /// nobody can mint tokens.
+    pub fn new(pool: Arc<PgPool>, super_admin_ids: Option<String>) -> Self {
+        Self {
+            pool,
+            super_admins: Arc::new(parse_super_admins(super_admin_ids.as_deref())),
+        }
+    }
+
+    fn require_super_admin(&self, auth: &Authenticated) -> Result<(), AppError> {
+        if !self.super_admins.contains(auth.sub.as_str()) {
+            return Err(AppError::Forbidden(
+                "Only super-admins can impersonate users".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn parse_super_admins(value: Option<&str>) -> HashSet<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Present on requests made with an impersonation token
+#[derive(Debug, Clone)]
+pub struct Impersonation {
+    pub session_id: Uuid,
+    pub impersonated_by: String,
+    pub target_user_id: Uuid,
+    pub organization_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartImpersonationRequest {
+    pub user_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub reason: String,
+    pub duration_minutes: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpersonationSessionResponse {
+    pub session_id: Uuid,
+    /// Send as `Authorization: Impersonation <token>`; shown only once
+    pub token: String,
+    pub target_user_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct SessionRecord {
+    id: Uuid,
+    impersonated_by: String,
+    target_user_id: Uuid,
+    user_external_id: String,
+    email: Option<String>,
+    organization_id: Option<Uuid>,
+    organization_external_id: Option<String>,
+    organization_name: Option<String>,
+    organization_role: Option<String>,
+}
+
+fn hash_token(token: &str) -> String {
+    digest::digest(&digest::SHA256, token.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn extract_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|raw| raw.strip_prefix(AUTHORIZATION_SCHEME))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// The requested session length, defaulted and bounds-checked
+fn session_duration(requested_minutes: Option<i64>) -> Result<Duration, AppError> {
+    match requested_minutes.unwrap_or(DEFAULT_DURATION_MINUTES) {
+        minutes if (1..=MAX_DURATION_MINUTES).contains(&minutes) => Ok(Duration::minutes(minutes)),
+        _ => Err(AppError::BadRequest(format!(
+            "durationMinutes must be between 1 and {}",
+            MAX_DURATION_MINUTES
+        ))),
+    }
+}
+
+async fn lookup_session(pool: &PgPool, token: &str) -> Result<SessionRecord, AppError> {
+    sqlx::query_as::<_, SessionRecord>(
+        r#"
+        SELECT
+            s.id,
+            s.impersonated_by,
+            s.target_user_id,
+            u.external_id AS user_external_id,
+            u.email,
+            s.organization_id,
+            o.external_id AS organization_external_id,
+            o.name AS organization_name,
+            m.role AS organization_role
+        FROM impersonation_sessions s
+        JOIN users u ON u.id = s.target_user_id
+        LEFT JOIN organizations o ON o.id = s.organization_id
+        LEFT JOIN organization_memberships m
+            ON m.organization_id = s.organization_id AND m.user_id = s.target_user_id
+        WHERE s.token_hash = $1 AND s.revoked_at IS NULL AND s.expires_at > NOW()
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error looking up impersonation session");
+        AppError::InternalServerError
+    })?
+    .ok_or_else(|| AppError::Unauthorized("Impersonation token is invalid or expired".to_string()))
+}
+
+async fn record_audit(
+    pool: &PgPool,
+    impersonation: &Impersonation,
+    method: &str,
+    path: &str,
+    status: StatusCode,
+) {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO impersonation_audit_log
+            (id, session_id, impersonated_by, target_user_id, organization_id, method, path, status)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(impersonation.session_id)
+    .bind(&impersonation.impersonated_by)
+    .bind(impersonation.target_user_id)
+    .bind(impersonation.organization_id)
+    .bind(method)
+    .bind(path)
+    .bind(status.as_u16() as i16)
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::error!(
+            error = %e,
+            session_id = %impersonation.session_id,
+            "SQL error writing impersonation audit entry"
+        );
+    }
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, AppError> {
+    HeaderValue::from_str(value).map_err(|_| AppError::InternalServerError)
+}
+
+/// Resolve impersonation tokens into the target user's identity, the same way
+/// API keys are resolved, and audit the request
+pub async fn impersonation_auth(
+    State(state): State<ImpersonationState>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(token) = extract_token(req.headers()) else {
+        return Ok(next.run(req).await);
+    };
+    let session = lookup_session(&state.pool, token).await?;
+
+    let headers = req.headers_mut();
+    headers.insert(
+        "x-user-id",
+        header_value(&session.target_user_id.to_string())?,
+    );
+    headers.insert(
+        "x-context-type",
+        HeaderValue::from_static(if session.organization_id.is_some() {
+            "organization"
+        } else {
+            "personal"
+        }),
+    );
+    // Client-supplied organization headers must not widen the session's scope
+    headers.remove("x-organization-id");
+    headers.remove("x-organization-external-id");
+    headers.remove("x-organization-name");
+    if let Some(org_id) = session.organization_id {
+        headers.insert("x-organization-id", header_value(&org_id.to_string())?);
+    }
+    if let Some(external_id) = session.organization_external_id.as_deref() {
+        headers.insert("x-organization-external-id", header_value(external_id)?);
+    }
+    if let Some(name) = session.organization_name.as_deref() {
+        headers.insert("x-organization-name", header_value(name)?);
+    }
+
+    let impersonation = Impersonation {
+        session_id: session.id,
+        impersonated_by: session.impersonated_by.clone(),
+        target_user_id: session.target_user_id,
+        organization_id: session.organization_id,
+    };
+    req.extensions_mut().insert(ApiKeyAuthClaims {
+        sub: session.user_external_id,
+        email: session.email,
+        org_id: session.organization_id.map(|id| id.to_string()),
+        org_slug: session.organization_external_id,
+        org_role: session.organization_role,
+        org_name: session.organization_name,
+        context_type: if session.organization_id.is_some() {
+            ContextType::Organization
+        } else {
+            ContextType::Personal
+        },
+    });
+    req.extensions_mut().insert(impersonation.clone());
+
+    let method = req.method().to_string();
+    let path = req.uri().path().to_string();
+    tracing::warn!(
+        session_id = %impersonation.session_id,
+        impersonated_by = %impersonation.impersonated_by,
+        target_user_id = %impersonation.target_user_id,
+        %method,
+        %path,
+        "Impersonated request"
+    );
+
+    let mut response = next.run(req).await;
+    record_audit(
+        &state.pool,
+        &impersonation,
+        &method,
+        &path,
+        response.status(),
+    )
+    .await;
+    response.headers_mut().insert(
+        IMPERSONATING_HEADER,
+        header_value(&impersonation.target_user_id.to_string())?,
+    );
+    Ok(response)
+}
+
+/// POST /api/v1/admin/impersonation
+async fn start_impersonation(
+    State(state): State<ImpersonationState>,
+    auth: Authenticated,
+    impersonating: Option<Extension<Impersonation>>,
+    Json(request): Json<StartImpersonationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if impersonating.is_some() {
+        return Err(AppError::Forbidden(
+            "Cannot start an impersonation session while impersonating".to_string(),
+        ));
+    }
+    state.require_super_admin(&auth)?;
+
+    let reason = request.reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::BadRequest(
+            "A reason is required to impersonate a user".to_string(),
+        ));
+    }
+    let expires_at = Utc::now() + session_duration(request.duration_minutes)?;
+
+    let (user_exists, is_member): (bool, bool) = sqlx::query_as(
+        r#"
+        SELECT
+            EXISTS (SELECT 1 FROM users WHERE id = $1),
+            EXISTS (
+                SELECT 1 FROM organization_memberships
+                WHERE user_id = $1 AND organization_id = $2
+            )
+        "#,
+    )
+    .bind(request.user_id)
+    .bind(request.organization_id)
+    .fetch_one(&*state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error checking impersonation target");
+        AppError::InternalServerError
+    })?;
+    if !user_exists {
+        return Err(AppError::NotFound("User not found".to_string()));
+    }
+    if request.organization_id.is_some() && !is_member {
+        return Err(AppError::BadRequest(
+            "User is not a member of that organization".to_string(),
+        ));
+    }
+
+    let session_id = Uuid::new_v4();
+    let token = format!("imp_{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    sqlx::query(
+        r#"
+        INSERT INTO impersonation_sessions
+            (id, token_hash, impersonated_by, target_user_id, organization_id, reason, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(session_id)
+    .bind(hash_token(&token))
+    .bind(&auth.sub)
+    .bind(request.user_id)
+    .bind(request.organization_id)
+    .bind(reason)
+    .bind(expires_at)
+    .execute(&*state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error creating impersonation session");
+        AppError::InternalServerError
+    })?;
+
+    tracing::warn!(
+        %session_id,
+        impersonated_by = %auth.sub,
+        target_user_id = %request.user_id,
+        reason,
+        "Impersonation session started"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ImpersonationSessionResponse {
+            session_id,
+            token,
+            target_user_id: request.user_id,
+            organization_id: request.organization_id,
+            expires_at,
+        }),
+    ))
+}
+
+/// DELETE /api/v1/admin/impersonation/{session_id}
+async fn end_impersonation(
+    State(state): State<ImpersonationState>,
+    auth: Authenticated,
+    impersonating: Option<Extension<Impersonation>>,
+    Path(session_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    // An impersonated request may end its own session, but nothing else
+    match impersonating {
+        Some(Extension(current)) if current.session_id == session_id => {}
+        Some(_) => {
+            return Err(AppError::Forbidden(
+                "Cannot manage other sessions while impersonating".to_string(),
+            ))
+        }
+        None => state.require_super_admin(&auth)?,
+    }
+
+    let result = sqlx::query(
+        "UPDATE impersonation_sessions SET revoked_at = NOW()
+         WHERE id = $1 AND revoked_at IS NULL",
+    )
+    .bind(session_id)
+    .execute(&*state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error ending impersonation session");
+        AppError::InternalServerError
+    })?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(
+            "Active impersonation session not found".to_string(),
+        ));
+    }
+
+    tracing::warn!(%session_id, "Impersonation session ended");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn build_impersonation_router(
+    state: ImpersonationState,
+    verifier: Arc<Mutex<JwtVerifier>>,
+) -> Router {
+    ServiceRouter::new("api-gateway/impersonation")
+        .route("/api/v1/admin/impersonation", post(start_impersonation))
+        .route(
+            "/api/v1/admin/impersonation/{session_id}",
+            delete(end_impersonation),
+        )
+        .layer(Extension(verifier))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_token_requires_impersonation_scheme() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(extract_token(&headers), None);
+
+        headers.insert("authorization", HeaderValue::from_static("Bearer abc"));
+        assert_eq!(extract_token(&headers), None);
+
+        headers.insert(
+            "authorization",
+            HeaderValue::from_static("Impersonation imp_123"),
+        );
+        assert_eq!(extract_token(&headers), Some("imp_123"));
+    }
+
+    #[test]
+    fn test_session_duration_bounds() {
+        assert_eq!(
+            session_duration(None).unwrap(),
+            Duration::minutes(DEFAULT_DURATION_MINUTES)
+        );
+        assert!(session_duration(Some(MAX_DURATION_MINUTES)).is_ok());
+        assert!(session_duration(Some(MAX_DURATION_MINUTES + 1)).is_err());
+        assert!(session_duration(Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_parse_super_admins() {
+        let admins = parse_super_admins(Some(" user_a, ,user_b "));
+        assert_eq!(admins.len(), 2);
+        assert!(admins.contains("user_a"));
+        assert!(admins.contains("user_b"));
+        assert!(parse_super_admins(None).is_empty());
+    }
+}
@@ -0,0 +1,625 @@
+//! Incidents: the health monitor opens one when a dependency (the database or
+//! a circuit-breaker-guarded service) fails several consecutive health checks
+//! and resolves it once the dependency is healthy again. Maintainers holding
+//! the admin token can also open, annotate and resolve incidents by hand.
+//! Open and recently resolved incidents make up the public status page, and
+//! every change is posted to `INCIDENT_WEBHOOK_URL` when it is set.
+
+use crate::maintenance::MaintenanceState;
+use crate::sql_error;
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use common::circuit_breaker::{self, BreakerState};
+use common::outbound_http::OutboundHttpClient;
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_CHECK_INTERVAL_SECS: u64 = 60;
+const DEFAULT_UNHEALTHY_CHECKS: u32 = 3;
+const DATABASE_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+const DATABASE_DEPENDENCY: &str = "database";
+/// How long resolved incidents stay on the status page
+const STATUS_PAGE_DAYS: i64 = 7;
+const MAX_TITLE_LENGTH: usize = 200;
+const MAX_TEXT_LENGTH: usize = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentStatus {
+    Investigating,
+    Identified,
+    Monitoring,
+    Resolved,
+}
+
+impl IncidentStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Investigating => "investigating",
+            Self::Identified => "identified",
+            Self::Monitoring => "monitoring",
+            Self::Resolved => "resolved",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Incident {
+    pub id: Uuid,
+    pub title: String,
+    pub dependency: Option<String>,
+    pub auto_opened: bool,
+    pub status: String,
+    pub status_message: Option<String>,
+    pub opened_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct IncidentNote {
+    pub id: Uuid,
+    pub incident_id: Uuid,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+const INCIDENT_COLUMNS: &str = "id, title, dependency, auto_opened, status, status_message,
+     opened_at, resolved_at, updated_at";
+
+/// Result of one health check of one dependency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyCheck {
+    pub dependency: String,
+    pub healthy: bool,
+}
+
+/// Counts consecutive failed checks per dependency
+#[derive(Debug)]
+struct HealthTracker {
+    threshold: u32,
+    failures: HashMap<String, u32>,
+}
+
+impl HealthTracker {
+    fn new(threshold: u32) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            failures: HashMap::new(),
+        }
+    }
+
+    /// Record a round of checks, returning the dependencies that have now
+    /// failed at least `threshold` checks in a row
+    fn observe(&mut self, checks: &[DependencyCheck]) -> Vec<String> {
+        let mut failing = Vec::new();
+        for check in checks {
+            if check.healthy {
+                self.failures.remove(&check.dependency);
+                continue;
+            }
+            let failures = self.failures.entry(check.dependency.clone()).or_default();
+            *failures += 1;
+            if *failures >= self.threshold {
+                failing.push(check.dependency.clone());
+            }
+        }
+        failing
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IncidentMonitorConfig {
+    pub check_interval: std::time::Duration,
+    pub unhealthy_checks: u32,
+}
+
+impl IncidentMonitorConfig {
+    /// Read `INCIDENT_CHECK_INTERVAL_SECS` (default 60, `0` disables the
+    /// monitor) and `INCIDENT_UNHEALTHY_CHECKS` (default 3)
+    pub fn from_secrets(get: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let number = |key: &str, default: u64| {
+            get(key)
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .unwrap_or(default)
+        };
+        let interval = number("INCIDENT_CHECK_INTERVAL_SECS", DEFAULT_CHECK_INTERVAL_SECS);
+        (interval > 0).then(|| Self {
+            check_interval: std::time::Duration::from_secs(interval),
+            unhealthy_checks: number("INCIDENT_UNHEALTHY_CHECKS", DEFAULT_UNHEALTHY_CHECKS as u64)
+                .clamp(1, 100) as u32,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct IncidentState {
+    pool: Arc<PgPool>,
+    admin: MaintenanceState,
+    webhook_url: Option<String>,
+    client: OutboundHttpClient,
+}
+
+impl IncidentState {
+    /// Admin access is checked against the same token as the maintenance switch
+    pub fn new(pool: Arc<PgPool>, admin: MaintenanceState, webhook_url: Option<String>) -> Self {
+        Self {
+            pool,
+            admin,
+            webhook_url: webhook_url.filter(|url| !url.trim().is_empty()),
+            client: OutboundHttpClient::builder()
+                .request_timeout(std::time::Duration::from_secs(10))
+                .build(),
+        }
+    }
+
+    /// Post the change to the webhook in the background
+    fn notify(&self, event: &'static str, incident: &Incident) {
+        let Some(url) = self.webhook_url.clone() else {
+            return;
+        };
+        let client = self.client.clone();
+        let payload = serde_json::json!({ "event": event, "incident": incident });
+        tokio::spawn(async move {
+            let request = client.post(&url).json(&payload);
+            match client.send("gateway.incidents.webhook", request).await {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => {
+                    tracing::warn!(status = %response.status(), event, "Incident webhook rejected")
+                }
+                Err(e) => tracing::warn!(error = %e, event, "Incident webhook failed"),
+            }
+        });
+    }
+
+    async fn check_dependencies(&self) -> Vec<DependencyCheck> {
+        let database = tokio::time::timeout(
+            DATABASE_CHECK_TIMEOUT,
+            sqlx::query("SELECT 1").execute(&*self.pool),
+        )
+        .await;
+        let mut checks = vec![DependencyCheck {
+            dependency: DATABASE_DEPENDENCY.to_string(),
+            healthy: matches!(database, Ok(Ok(_))),
+        }];
+        checks.extend(
+            circuit_breaker::snapshots()
+                .into_iter()
+                .map(|breaker| DependencyCheck {
+                    dependency: breaker.dependency,
+                    healthy: breaker.state == BreakerState::Closed,
+                }),
+        );
+        checks
+    }
+
+    /// Open an incident for the dependency unless one is already open
+    async fn open_for_dependency(&self, dependency: &str) -> Result<(), AppError> {
+        let incident = sqlx::query_as::<_, Incident>(&format!(
+            "INSERT INTO incidents (id, title, dependency, auto_opened, status)
+             VALUES ($1, $2, $3, TRUE, 'investigating')
+             ON CONFLICT DO NOTHING
+             RETURNING {}",
+            INCIDENT_COLUMNS
+        ))
+        .bind(Uuid::new_v4())
+        .bind(format!("{} is unavailable", dependency))
+        .bind(dependency)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(sql_error("opening incident"))?;
+
+        if let Some(incident) = incident {
+            tracing::warn!(dependency, incident_id = %incident.id, "Opened incident");
+            self.notify("incident.opened", &incident);
+        }
+        Ok(())
+    }
+
+    /// Resolve the dependency's open automatic incident, if any
+    async fn resolve_for_dependency(&self, dependency: &str) -> Result<(), AppError> {
+        let incidents = sqlx::query_as::<_, Incident>(&format!(
+            "UPDATE incidents
+             SET status = 'resolved', resolved_at = NOW(), updated_at = NOW()
+             WHERE dependency = $1 AND auto_opened AND resolved_at IS NULL
+             RETURNING {}",
+            INCIDENT_COLUMNS
+        ))
+        .bind(dependency)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(sql_error("resolving incident"))?;
+
+        for incident in incidents {
+            tracing::info!(dependency, incident_id = %incident.id, "Resolved incident");
+            self.notify("incident.resolved", &incident);
+        }
+        Ok(())
+    }
+
+    async fn incident(&self, id: Uuid) -> Result<Incident, AppError> {
+        sqlx::query_as::<_, Incident>(&format!(
+            "SELECT {} FROM incidents WHERE id = $1",
+            INCIDENT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(sql_error("fetching incident"))?
+        .ok_or_else(|| AppError::NotFound("Incident not found".to_string()))
+    }
+}
+
+/// Check dependency health every interval, opening an incident when one fails
+/// the configured number of checks in a row and resolving it on recovery.
+/// Every replica checks its own view of the dependencies; the database keeps
+/// one open automatic incident per dependency.
+pub fn spawn_incident_monitor(state: IncidentState, config: IncidentMonitorConfig) {
+    tokio::spawn(async move {
+        let mut tracker = HealthTracker::new(config.unhealthy_checks);
+        let mut ticks = tokio::time::interval(config.check_interval);
+        ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            let checks = state.check_dependencies().await;
+            let failing = tracker.observe(&checks);
+            for check in &checks {
+                let result = if failing.contains(&check.dependency) {
+                    state.open_for_dependency(&check.dependency).await
+                } else if check.healthy {
+                    state.resolve_for_dependency(&check.dependency).await
+                } else {
+                    Ok(())
+                };
+                if let Err(e) = result {
+                    tracing::error!(error = %e, dependency = %check.dependency, "Incident monitor failed");
+                }
+            }
+        }
+    });
+}
+
+fn validate_text(field: &str, value: &str, max: usize) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(AppError::BadRequest(format!("{} cannot be empty", field)));
+    }
+    if value.chars().count() > max {
+        return Err(AppError::BadRequest(format!(
+            "{} cannot exceed {} characters",
+            field, max
+        )));
+    }
+    Ok(value.to_string())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusPageIncident {
+    pub id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub status_message: Option<String>,
+    pub opened_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusPage {
+    /// `operational`, or `degraded` while any incident is open
+    pub status: &'static str,
+    pub incidents: Vec<StatusPageIncident>,
+}
+
+/// GET /api/v1/status
+/// Public: open incidents and those resolved in the last week
+async fn get_status_page(State(state): State<IncidentState>) -> Result<Json<StatusPage>, AppError> {
+    let incidents = sqlx::query_as::<_, Incident>(&format!(
+        "SELECT {} FROM incidents
+         WHERE resolved_at IS NULL OR resolved_at >= $1
+         ORDER BY opened_at DESC",
+        INCIDENT_COLUMNS
+    ))
+    .bind(Utc::now() - Duration::days(STATUS_PAGE_DAYS))
+    .fetch_all(&*state.pool)
+    .await
+    .map_err(sql_error("fetching status page incidents"))?;
+
+    let degraded = incidents
+        .iter()
+        .any(|incident| incident.resolved_at.is_none());
+    Ok(Json(StatusPage {
+        status: if degraded { "degraded" } else { "operational" },
+        incidents: incidents
+            .into_iter()
+            .map(|incident| StatusPageIncident {
+                id: incident.id,
+                title: incident.title,
+                status: incident.status,
+                status_message: incident.status_message,
+                opened_at: incident.opened_at,
+                resolved_at: incident.resolved_at,
+            })
+            .collect(),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IncidentsQuery {
+    /// Only open (`true`) or resolved (`false`) incidents
+    pub open: Option<bool>,
+}
+
+/// GET /api/v1/admin/incidents?open=
+async fn list_incidents(
+    State(state): State<IncidentState>,
+    headers: HeaderMap,
+    Query(query): Query<IncidentsQuery>,
+) -> Result<Json<Vec<Incident>>, AppError> {
+    state.admin.require_admin(&headers)?;
+    let incidents = sqlx::query_as::<_, Incident>(&format!(
+        "SELECT {} FROM incidents
+         WHERE $1::BOOLEAN IS NULL OR (resolved_at IS NULL) = $1
+         ORDER BY opened_at DESC
+         LIMIT 200",
+        INCIDENT_COLUMNS
+    ))
+    .bind(query.open)
+    .fetch_all(&*state.pool)
+    .await
+    .map_err(sql_error("listing incidents"))?;
+    Ok(Json(incidents))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateIncidentRequest {
+    pub title: String,
+    pub status_message: Option<String>,
+}
+
+/// POST /api/v1/admin/incidents
+async fn create_incident(
+    State(state): State<IncidentState>,
+    headers: HeaderMap,
+    Json(request): Json<CreateIncidentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    state.admin.require_admin(&headers)?;
+    let title = validate_text("title", &request.title, MAX_TITLE_LENGTH)?;
+    let status_message = request
+        .status_message
+        .as_deref()
+        .map(|message| validate_text("statusMessage", message, MAX_TEXT_LENGTH))
+        .transpose()?;
+
+    let incident = sqlx::query_as::<_, Incident>(&format!(
+        "INSERT INTO incidents (id, title, status_message) VALUES ($1, $2, $3) RETURNING {}",
+        INCIDENT_COLUMNS
+    ))
+    .bind(Uuid::new_v4())
+    .bind(title)
+    .bind(status_message)
+    .fetch_one(&*state.pool)
+    .await
+    .map_err(sql_error("creating incident"))?;
+
+    state.notify("incident.opened", &incident);
+    Ok((StatusCode::CREATED, Json(incident)))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncidentDetail {
+    #[serde(flatten)]
+    pub incident: Incident,
+    pub notes: Vec<IncidentNote>,
+}
+
+/// GET /api/v1/admin/incidents/{id}
+async fn get_incident(
+    State(state): State<IncidentState>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<IncidentDetail>, AppError> {
+    state.admin.require_admin(&headers)?;
+    let incident = state.incident(id).await?;
+    let notes = sqlx::query_as::<_, IncidentNote>(
+        "SELECT id, incident_id, body, created_at FROM incident_notes
+         WHERE incident_id = $1
+         ORDER BY created_at",
+    )
+    .bind(id)
+    .fetch_all(&*state.pool)
+    .await
+    .map_err(sql_error("fetching incident notes"))?;
+    Ok(Json(IncidentDetail { incident, notes }))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateIncidentRequest {
+    pub title: Option<String>,
+    pub status: Option<IncidentStatus>,
+    /// Public message for the status page; an empty string clears it
+    pub status_message: Option<String>,
+}
+
+/// PATCH /api/v1/admin/incidents/{id}
+/// Setting the status to `resolved` resolves it; any other status reopens it
+async fn update_incident(
+    State(state): State<IncidentState>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateIncidentRequest>,
+) -> Result<Json<Incident>, AppError> {
+    state.admin.require_admin(&headers)?;
+    let current = state.incident(id).await?;
+    let title = match request.title.as_deref() {
+        Some(title) => validate_text("title", title, MAX_TITLE_LENGTH)?,
+        None => current.title.clone(),
+    };
+    let status_message = match request.status_message.as_deref() {
+        Some(message) if message.trim().is_empty() => None,
+        Some(message) => Some(validate_text("statusMessage", message, MAX_TEXT_LENGTH)?),
+        None => current.status_message.clone(),
+    };
+    let status = request
+        .status
+        .map(|status| status.as_str().to_string())
+        .unwrap_or_else(|| current.status.clone());
+
+    let incident = sqlx::query_as::<_, Incident>(&format!(
+        "UPDATE incidents
+         SET title = $2, status = $3, status_message = $4,
+             resolved_at = CASE
+                 WHEN $3 <> 'resolved' THEN NULL
+                 ELSE COALESCE(resolved_at, NOW())
+             END,
+             updated_at = NOW()
+         WHERE id = $1
+         RETURNING {}",
+        INCIDENT_COLUMNS
+    ))
+    .bind(id)
+    .bind(title)
+    .bind(&status)
+    .bind(status_message)
+    .fetch_one(&*state.pool)
+    .await
+    .map_err(|e| match e {
+        // Reopening an automatic incident while the monitor opened a newer one
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            AppError::Conflict("Another incident is already open for this dependency".to_string())
+        }
+        e => sql_error("updating incident")(e),
+    })?;
+
+    let event = match (current.resolved_at, incident.resolved_at) {
+        (None, Some(_)) => "incident.resolved",
+        (Some(_), None) => "incident.reopened",
+        _ => "incident.updated",
+    };
+    state.notify(event, &incident);
+    Ok(Json(incident))
+}
+
+/// DELETE /api/v1/admin/incidents/{id}
+/// For false alarms; real incidents should be resolved instead
+async fn delete_incident(
+    State(state): State<IncidentState>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state.admin.require_admin(&headers)?;
+    let deleted = sqlx::query("DELETE FROM incidents WHERE id = $1")
+        .bind(id)
+        .execute(&*state.pool)
+        .await
+        .map_err(sql_error("deleting incident"))?;
+    if deleted.rows_affected() == 0 {
+        return Err(AppError::NotFound("Incident not found".to_string()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddIncidentNoteRequest {
+    pub body: String,
+}
+
+/// POST /api/v1/admin/incidents/{id}/notes
+/// Internal annotation; not shown on the status page
+async fn add_incident_note(
+    State(state): State<IncidentState>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(request): Json<AddIncidentNoteRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    state.admin.require_admin(&headers)?;
+    let body = validate_text("body", &request.body, MAX_TEXT_LENGTH)?;
+    state.incident(id).await?;
+
+    let note = sqlx::query_as::<_, IncidentNote>(
+        "INSERT INTO incident_notes (id, incident_id, body) VALUES ($1, $2, $3)
+         RETURNING id, incident_id, body, created_at",
+    )
+    .bind(Uuid::new_v4())
+    .bind(id)
+    .bind(body)
+    .fetch_one(&*state.pool)
+    .await
+    .map_err(sql_error("adding incident note"))?;
+    Ok((StatusCode::CREATED, Json(note)))
+}
+
+pub fn build_incident_router(state: IncidentState) -> Router {
+    ServiceRouter::new("api-gateway/incidents")
+        .route("/api/v1/status", get(get_status_page))
+        .route(
+            "/api/v1/admin/incidents",
+            get(list_incidents).post(create_incident),
+        )
+        .route(
+            "/api/v1/admin/incidents/{id}",
+            get(get_incident)
+                .patch(update_incident)
+                .delete(delete_incident),
+        )
+        .route(
+            "/api/v1/admin/incidents/{id}/notes",
+            post(add_incident_note),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(dependency: &str, healthy: bool) -> DependencyCheck {
+        DependencyCheck {
+            dependency: dependency.to_string(),
+            healthy,
+        }
+    }
+
+    #[test]
+    fn test_dependencies_fail_after_consecutive_unhealthy_checks() {
+        let mut tracker = HealthTracker::new(3);
+        let round = [check("database", false), check("openai", true)];
+        assert!(tracker.observe(&round).is_empty());
+        assert!(tracker.observe(&round).is_empty());
+        assert_eq!(tracker.observe(&round), vec!["database".to_string()]);
+        assert_eq!(tracker.observe(&round), vec!["database".to_string()]);
+
+        // A healthy check starts the count again
+        assert!(tracker.observe(&[check("database", true)]).is_empty());
+        assert!(tracker.observe(&[check("database", false)]).is_empty());
+    }
+
+    #[test]
+    fn test_monitor_config_reads_secrets() {
+        let config = IncidentMonitorConfig::from_secrets(|_| None).unwrap();
+        assert_eq!(config.check_interval.as_secs(), DEFAULT_CHECK_INTERVAL_SECS);
+        assert_eq!(config.unhealthy_checks, DEFAULT_UNHEALTHY_CHECKS);
+
+        let disabled = IncidentMonitorConfig::from_secrets(|key| {
+            (key == "INCIDENT_CHECK_INTERVAL_SECS").then(|| "0".to_string())
+        });
+        assert!(disabled.is_none());
+    }
+}
@@ -0,0 +1,327 @@
+//! LLM audit trail: prompts and responses from organizations that opted in are
+//! stored (already PII-scrubbed) until their retention period runs out, and can
+//! be read back by maintainers holding the admin token.
+
+use crate::maintenance::MaintenanceState;
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use common::llm_audit::{LlmAuditSink, LlmExchange};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_RETENTION_DAYS: i32 = 14;
+const MAX_RETENTION_DAYS: i32 = 90;
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+/// Persists exchanges for opted-in organizations in the background
+#[derive(Clone)]
+pub struct PgLlmAuditSink {
+    pool: Arc<PgPool>,
+}
+
+impl PgLlmAuditSink {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+impl LlmAuditSink for PgLlmAuditSink {
+    fn record(&self, exchange: LlmExchange) {
+        // Calls outside any organization have nobody who could have opted in
+        let Some(organization_id) = exchange.organization_id else {
+            return;
+        };
+        let pool = self.pool.clone();
+        tokio::spawn(async move {
+            if let Err(e) = store_exchange(&pool, organization_id, &exchange).await {
+                tracing::error!(
+                    error = %e,
+                    call_site = exchange.call_site,
+                    "SQL error recording LLM exchange"
+                );
+            }
+        });
+    }
+}
+
+async fn store_exchange(
+    pool: &PgPool,
+    organization_id: Uuid,
+    exchange: &LlmExchange,
+) -> Result<(), sqlx::Error> {
+    // Only inserts when the organization has auditing enabled
+    let inserted = sqlx::query(
+        "INSERT INTO llm_audit_log
+             (id, organization_id, story_id, call_site, model, prompt, response, error, expires_at)
+         SELECT $1, s.organization_id, $3, $4, $5, $6, $7, $8,
+                NOW() + make_interval(days => s.retention_days)
+         FROM llm_audit_settings s
+         WHERE s.organization_id = $2 AND s.enabled",
+    )
+    .bind(Uuid::new_v4())
+    .bind(organization_id)
+    .bind(exchange.story_id)
+    .bind(exchange.call_site)
+    .bind(&exchange.model)
+    .bind(&exchange.prompt)
+    .bind(&exchange.response)
+    .bind(&exchange.error)
+    .execute(pool)
+    .await?;
+
+    if inserted.rows_affected() > 0 {
+        prune_expired(pool).await?;
+    }
+    Ok(())
+}
+
+async fn prune_expired(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let pruned = sqlx::query("DELETE FROM llm_audit_log WHERE expires_at <= NOW()")
+        .execute(pool)
+        .await?;
+    Ok(pruned.rows_affected())
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmAuditSettings {
+    pub organization_id: Uuid,
+    pub enabled: bool,
+    pub retention_days: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateLlmAuditSettingsRequest {
+    pub enabled: bool,
+    pub retention_days: Option<i32>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmAuditRecord {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub story_id: Option<Uuid>,
+    pub call_site: String,
+    pub model: String,
+    pub prompt: String,
+    pub response: Option<String>,
+    pub error: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmAuditQuery {
+    #[serde(alias = "story_id")]
+    pub story_id: Option<Uuid>,
+    #[serde(alias = "organization_id")]
+    pub organization_id: Option<Uuid>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Clone)]
+pub struct LlmAuditState {
+    pool: Arc<PgPool>,
+    admin: MaintenanceState,
+}
+
+impl LlmAuditState {
+    /// Admin access is checked against the same token as the maintenance switch
+    pub fn new(pool: Arc<PgPool>, admin: MaintenanceState) -> Self {
+        Self { pool, admin }
+    }
+}
+
+fn validate_retention_days(retention_days: Option<i32>) -> Result<i32, AppError> {
+    let days = retention_days.unwrap_or(DEFAULT_RETENTION_DAYS);
+    if !(1..=MAX_RETENTION_DAYS).contains(&days) {
+        return Err(AppError::BadRequest(format!(
+            "retentionDays must be between 1 and {}",
+            MAX_RETENTION_DAYS
+        )));
+    }
+    Ok(days)
+}
+
+/// GET /api/v1/admin/llm-audit?story_id=&organization_id=&limit=
+async fn list_llm_audit(
+    State(state): State<LlmAuditState>,
+    headers: HeaderMap,
+    Query(query): Query<LlmAuditQuery>,
+) -> Result<Json<Vec<LlmAuditRecord>>, AppError> {
+    state.admin.require_admin(&headers)?;
+    if query.story_id.is_none() && query.organization_id.is_none() {
+        return Err(AppError::BadRequest(
+            "Filter by story_id or organization_id".to_string(),
+        ));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    // Expired records must never be served, even if no write has pruned them yet
+    prune_expired(&state.pool).await.map_err(|e| {
+        tracing::error!(error = %e, "SQL error pruning LLM audit log");
+        AppError::InternalServerError
+    })?;
+
+    let records = sqlx::query_as::<_, LlmAuditRecord>(
+        "SELECT id, organization_id, story_id, call_site, model, prompt, response, error,
+                expires_at, created_at
+         FROM llm_audit_log
+         WHERE ($1::uuid IS NULL OR story_id = $1)
+           AND ($2::uuid IS NULL OR organization_id = $2)
+         ORDER BY created_at DESC
+         LIMIT $3",
+    )
+    .bind(query.story_id)
+    .bind(query.organization_id)
+    .bind(limit)
+    .fetch_all(&*state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error listing LLM audit log");
+        AppError::InternalServerError
+    })?;
+
+    Ok(Json(records))
+}
+
+/// GET /api/v1/admin/llm-audit/organizations/{org_id}
+async fn get_llm_audit_settings(
+    State(state): State<LlmAuditState>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<LlmAuditSettings>, AppError> {
+    state.admin.require_admin(&headers)?;
+
+    let settings = sqlx::query_as::<_, LlmAuditSettings>(
+        "SELECT organization_id, enabled, retention_days, updated_at
+         FROM llm_audit_settings WHERE organization_id = $1",
+    )
+    .bind(org_id)
+    .fetch_optional(&*state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error reading LLM audit settings");
+        AppError::InternalServerError
+    })?
+    .unwrap_or_else(|| LlmAuditSettings {
+        organization_id: org_id,
+        enabled: false,
+        retention_days: DEFAULT_RETENTION_DAYS,
+        updated_at: Utc::now(),
+    });
+
+    Ok(Json(settings))
+}
+
+/// PUT /api/v1/admin/llm-audit/organizations/{org_id}
+///
+/// Opting out deletes everything already recorded for the organization.
+async fn update_llm_audit_settings(
+    State(state): State<LlmAuditState>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Json(request): Json<UpdateLlmAuditSettingsRequest>,
+) -> Result<Json<LlmAuditSettings>, AppError> {
+    state.admin.require_admin(&headers)?;
+    let retention_days = validate_retention_days(request.retention_days)?;
+
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        tracing::error!(error = %e, "SQL error starting LLM audit settings update");
+        AppError::InternalServerError
+    })?;
+
+    let settings = sqlx::query_as::<_, LlmAuditSettings>(
+        "INSERT INTO llm_audit_settings (organization_id, enabled, retention_days, updated_at)
+         SELECT id, $2, $3, NOW() FROM organizations WHERE id = $1
+         ON CONFLICT (organization_id) DO UPDATE SET
+             enabled = EXCLUDED.enabled,
+             retention_days = EXCLUDED.retention_days,
+             updated_at = NOW()
+         RETURNING organization_id, enabled, retention_days, updated_at",
+    )
+    .bind(org_id)
+    .bind(request.enabled)
+    .bind(retention_days)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error updating LLM audit settings");
+        AppError::InternalServerError
+    })?
+    .ok_or_else(|| AppError::NotFound(format!("Organization {} not found", org_id)))?;
+
+    // A shorter retention applies to what is already stored, too
+    let cleanup = if settings.enabled {
+        sqlx::query(
+            "UPDATE llm_audit_log
+             SET expires_at = LEAST(expires_at, created_at + make_interval(days => $2))
+             WHERE organization_id = $1",
+        )
+        .bind(org_id)
+        .bind(retention_days)
+    } else {
+        sqlx::query("DELETE FROM llm_audit_log WHERE organization_id = $1").bind(org_id)
+    };
+    cleanup.execute(&mut *tx).await.map_err(|e| {
+        tracing::error!(error = %e, "SQL error applying LLM audit retention");
+        AppError::InternalServerError
+    })?;
+
+    tx.commit().await.map_err(|e| {
+        tracing::error!(error = %e, "SQL error committing LLM audit settings");
+        AppError::InternalServerError
+    })?;
+
+    tracing::warn!(
+        organization_id = %org_id,
+        enabled = settings.enabled,
+        retention_days = settings.retention_days,
+        "LLM audit settings updated"
+    );
+    Ok(Json(settings))
+}
+
+pub fn build_llm_audit_router(state: LlmAuditState) -> Router {
+    ServiceRouter::new("api-gateway/llm-audit")
+        .route("/api/v1/admin/llm-audit", get(list_llm_audit))
+        .route(
+            "/api/v1/admin/llm-audit/organizations/{org_id}",
+            get(get_llm_audit_settings).put(update_llm_audit_settings),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retention_days_are_bounded() {
+        assert_eq!(
+            validate_retention_days(None).unwrap(),
+            DEFAULT_RETENTION_DAYS
+        );
+        assert_eq!(validate_retention_days(Some(1)).unwrap(), 1);
+        assert_eq!(
+            validate_retention_days(Some(MAX_RETENTION_DAYS)).unwrap(),
+            MAX_RETENTION_DAYS
+        );
+        assert!(validate_retention_days(Some(0)).is_err());
+        assert!(validate_retention_days(Some(MAX_RETENTION_DAYS + 1)).is_err());
+    }
+}
@@ -1,9 +1,8 @@
 use anyhow::Context;
-use axum::http::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
-use axum::http::{HeaderValue, Method};
+use axum::http::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderName, Method};
 use axum::middleware;
 use axum::routing::get;
-use axum::Router;
 use shuttle_axum::ShuttleAxum;
 use shuttle_shared_db::Postgres;
 use sqlx::PgPool;
@@ -16,12 +15,15 @@ mod migrations;
 
 use auth_clerk::JwtVerifier;
 use common::init_tracing;
+use common::route_registry::ServiceRouter;
 
 use api_gateway::{
-    build_backlog_router, build_prompt_builder_router, build_readiness_router, build_sprint_router,
-    PromptBacklogServiceAdapter, PromptReadinessServiceAdapter,
+    build_backlog_router_with_websockets, build_prompt_builder_router, build_readiness_router,
+    build_sprint_router, route_registry::RouteRegistry, PromptBacklogServiceAdapter,
+    PromptReadinessServiceAdapter,
 };
-use event_bus::{EventBus, EventPublisher};
+use event_bus::outbox::{OutboxDispatcher, OutboxPublisher};
+use event_bus::{EventBus, EventPublisher, TransportConfig};
 
 #[shuttle_runtime::main]
 async fn main(
@@ -63,14 +65,64 @@ async fn main(
     };
 
     // Core usecases
-    let event_bus = Arc::new(EventBus::new());
-    let event_publisher: Arc<dyn EventPublisher> = event_bus.clone();
-    let backlog_usecases = backlog::build_usecases(pool.clone(), event_publisher);
+    // In memory unless EVENT_TRANSPORT picks a broker
+    let event_transport = TransportConfig::from_secrets(|key| secrets.get(key))
+        .and_then(|config| config.connect())
+        .map_err(anyhow::Error::msg)
+        .context("Invalid event transport configuration")?;
+    let event_bus = Arc::new(EventBus::with_transport(event_transport));
+    // Events go through the outbox so they survive a restart; the dispatcher
+    // delivers them on the bus
+    let event_publisher: Arc<dyn EventPublisher> =
+        Arc::new(OutboxPublisher::new(Arc::new(pool.clone())));
+    let _outbox_dispatcher = OutboxDispatcher::spawn(Arc::new(pool.clone()), event_bus.clone());
+    event_bus.set_failure_sink(Arc::new(
+        api_gateway::projection_failures::PgProjectionFailureSink::new(Arc::new(pool.clone())),
+    ));
+    backlog_api::spawn_sprint_stats_projector(Arc::new(pool.clone()), event_bus.clone());
+    let llm_audit_sink: Arc<dyn common::llm_audit::LlmAuditSink> = Arc::new(
+        api_gateway::llm_audit::PgLlmAuditSink::new(Arc::new(pool.clone())),
+    );
+    let quota_guard: Arc<dyn common::quota::QuotaGuard> = Arc::new(
+        api_gateway::usage::PgQuotaGuard::new(Arc::new(pool.clone())),
+    );
+    let analytics_salt = secrets
+        .get("ANALYTICS_SALT")
+        .filter(|salt| !salt.trim().is_empty());
+    let analytics_emitter: Arc<dyn common::analytics::AnalyticsEmitter> = match &analytics_salt {
+        Some(salt) => Arc::new(api_gateway::analytics::PgAnalyticsEmitter::new(
+            Arc::new(pool.clone()),
+            salt,
+        )),
+        None => Arc::new(common::analytics::NoopAnalyticsEmitter),
+    };
+    let mut backlog_usecases =
+        backlog_api::BacklogUsecases::new(Arc::new(pool.clone()), event_publisher.clone())
+            .with_quota_guard(quota_guard.clone())
+            .with_analytics(analytics_emitter);
+    if let Some(narrator) = backlog_api::OpenAiStandupNarrator::from_env() {
+        backlog_usecases = backlog_usecases
+            .with_standup_narrator(Arc::new(narrator.with_audit_sink(llm_audit_sink.clone())));
+    }
+    if let Some(splitter) = backlog_api::OpenAiTaskSplitter::from_env() {
+        backlog_usecases = backlog_usecases
+            .with_task_split_proposer(Arc::new(splitter.with_audit_sink(llm_audit_sink.clone())));
+    }
+    if let Some(vapid) = backlog_api::VapidKeys::from_secrets(|key| secrets.get(key)) {
+        backlog_usecases = backlog_usecases.with_push_notifier(Arc::new(
+            backlog_api::WebPushNotifier::new(Arc::new(pool.clone()), vapid),
+        ));
+    }
+    let backlog_usecases = Arc::new(backlog_usecases);
 
-    let readiness_llm: Arc<dyn readiness::application::ports::LlmService> =
-        Arc::new(readiness::adapters::integrations::MockLlmService);
-    let readiness_usecases =
-        readiness::build_usecases(pool.clone(), event_bus.clone(), readiness_llm).await;
+    let readiness_llm: Arc<dyn readiness_api::LlmService> = Arc::new(readiness_api::MockLlmService);
+    let readiness_usecases = readiness_api::build_usecases(
+        pool.clone(),
+        event_bus.clone(),
+        readiness_llm,
+        quota_guard.clone(),
+    )
+    .await;
 
     let prompt_backlog_service = Arc::new(PromptBacklogServiceAdapter {
         backlog: backlog_usecases.clone(),
@@ -78,29 +130,40 @@ async fn main(
     let prompt_readiness_service = Arc::new(PromptReadinessServiceAdapter {
         readiness: readiness_usecases.clone(),
     });
-    let prompt_llm: Arc<dyn prompt_builder::application::ports::LlmService> =
-        Arc::new(prompt_builder::adapters::integrations::MockLlmService);
-    let prompt_builder_usecases = prompt_builder::build_usecases(
+    let prompt_llm: Arc<dyn prompt_builder_api::ports::LlmService> =
+        Arc::new(prompt_builder_api::MockLlmService);
+    let prompt_builder_usecases = prompt_builder_api::build_usecases(
         pool.clone(),
         event_bus.clone(),
         prompt_backlog_service,
         prompt_readiness_service,
         prompt_llm,
+        quota_guard.clone(),
     );
 
-    let sprint_usecases = Arc::new(sprint::SprintsUsecases::new(Arc::new(pool.clone())));
+    let sprint_usecases = Arc::new(sprint_api::SprintsUsecases::new(
+        Arc::new(pool.clone()),
+        event_publisher.clone(),
+    ));
 
-    let auth_router = auth_gateway::create_auth_router(pool.clone(), verifier.clone()).await;
-    let projects_router = projects::create_projects_router(pool.clone(), verifier.clone()).await;
-    let context_orchestrator_router = context_orchestrator::create_context_orchestrator_router(
+    let auth_router = auth_gateway_api::create_auth_router(pool.clone(), verifier.clone()).await;
+    let projects_router =
+        projects_api::create_projects_router(pool.clone(), verifier.clone(), quota_guard.clone())
+            .await;
+    let context_orchestrator_router = context_orchestrator_api::create_context_orchestrator_router(
         pool.clone(),
         verifier.clone(),
         event_bus.clone(),
     )
     .await;
 
-    let backlog_router =
-        build_backlog_router(backlog_usecases.clone(), pool.clone(), verifier.clone());
+    let ws_manager = Arc::new(backlog_api::WebSocketManager::from_env());
+    let backlog_router = build_backlog_router_with_websockets(
+        backlog_usecases.clone(),
+        pool.clone(),
+        verifier.clone(),
+        ws_manager.clone(),
+    );
     let readiness_router =
         build_readiness_router(pool.clone(), readiness_usecases.clone(), verifier.clone());
     let prompt_builder_router =
@@ -108,16 +171,88 @@ async fn main(
     let sprint_router = build_sprint_router(sprint_usecases.clone(), verifier.clone());
 
     let api_key_state = api_gateway::auth::ApiKeyState::new(Arc::new(pool.clone()));
+    let maintenance_state = api_gateway::maintenance::MaintenanceState::new(
+        Arc::new(pool.clone()),
+        secrets.get("ADMIN_API_TOKEN"),
+    );
+    let llm_audit_state = api_gateway::llm_audit::LlmAuditState::new(
+        Arc::new(pool.clone()),
+        maintenance_state.clone(),
+    );
+    let reporting_state = api_gateway::reporting::ReportingState::new(
+        Arc::new(pool.clone()),
+        maintenance_state.clone(),
+    );
+    let event_replay_state = api_gateway::event_replay::EventReplayState::new(
+        Arc::new(pool.clone()),
+        maintenance_state.clone(),
+        event_bus.clone(),
+        secrets.get("EVENT_EXPORT_SALT"),
+        secrets
+            .get("EVENT_REPLAY_ENABLED")
+            .is_some_and(|value| value.eq_ignore_ascii_case("true")),
+    );
+    let projection_failure_state = api_gateway::projection_failures::ProjectionFailureState::new(
+        Arc::new(pool.clone()),
+        maintenance_state.clone(),
+        event_bus.clone(),
+    );
+    let analytics_state = api_gateway::analytics::AnalyticsState::new(
+        Arc::new(pool.clone()),
+        maintenance_state.clone(),
+        analytics_salt.as_deref(),
+    );
+    let usage_state =
+        api_gateway::usage::UsageState::new(Arc::new(pool.clone()), maintenance_state.clone());
+    let billing_state = api_gateway::billing::BillingState::new(
+        Arc::new(pool.clone()),
+        api_gateway::billing::BillingConfig::from_secrets(|key| secrets.get(key)),
+    );
+    let project_usecases = projects_api::build_usecases(pool.clone(), quota_guard.clone());
+    let config_bundle_state = api_gateway::config_bundles::ConfigBundleState::new(
+        Arc::new(pool.clone()),
+        readiness_usecases.clone(),
+        project_usecases.clone(),
+    );
+    let organization_switch_state = api_gateway::organizations::OrganizationSwitchState::new(
+        Arc::new(pool.clone()),
+        api_key_state.membership_cache(),
+    );
+    let overview_state = api_gateway::overview::OverviewState::new(
+        Arc::new(pool.clone()),
+        backlog_usecases.clone(),
+        sprint_usecases.clone(),
+        readiness_usecases.clone(),
+    );
+    let workload_state = api_gateway::workload::WorkloadState::new(
+        Arc::new(pool.clone()),
+        backlog_usecases.clone(),
+        sprint_usecases.clone(),
+        project_usecases,
+    );
+    let impersonation_state = api_gateway::impersonation::ImpersonationState::new(
+        Arc::new(pool.clone()),
+        secrets.get("SUPER_ADMIN_USER_IDS"),
+    );
+    let websocket_admin_state =
+        api_gateway::websockets::WebSocketAdminState::new(ws_manager, maintenance_state.clone());
+    let incident_state = api_gateway::incidents::IncidentState::new(
+        Arc::new(pool.clone()),
+        maintenance_state.clone(),
+        secrets.get("INCIDENT_WEBHOOK_URL"),
+    );
+    if let Some(config) =
+        api_gateway::incidents::IncidentMonitorConfig::from_secrets(|key| secrets.get(key))
+    {
+        api_gateway::incidents::spawn_incident_monitor(incident_state.clone(), config);
+    }
 
     // Create unified router with path-based routing
-    let cors_origins = env::var("CORS_ALLOWED_ORIGINS")
-        .unwrap_or_else(|_| "http://localhost:3000".to_string())
-        .split(',')
-        .map(|s| s.parse::<HeaderValue>().unwrap())
-        .collect::<Vec<HeaderValue>>();
-
+    let config = api_gateway::config::AppConfig::from_secrets(|key| {
+        secrets.get(key).or_else(|| env::var(key).ok())
+    });
     let cors = CorsLayer::new()
-        .allow_origin(cors_origins)
+        .allow_origin(config.cors_origins.allow_origin())
         .allow_methods(vec![
             Method::GET,
             Method::POST,
@@ -130,34 +265,162 @@ async fn main(
             ACCEPT,
             AUTHORIZATION,
             CONTENT_TYPE,
+            IF_NONE_MATCH,
             "X-User-Id".parse().unwrap(),
             "X-Context-Type".parse().unwrap(),
             "X-Organization-Id".parse().unwrap(),
             "X-Organization-External-Id".parse().unwrap(),
             "X-Organization-Name".parse().unwrap(),
             "X-Api-Key".parse().unwrap(),
+            "X-Admin-Token".parse().unwrap(),
+        ])
+        .expose_headers([
+            ETAG,
+            "X-Impersonating".parse::<HeaderName>().unwrap(),
+            "X-Total-Count".parse::<HeaderName>().unwrap(),
         ])
         .allow_credentials(true);
 
-    let app = Router::new()
-        // Health checks at root level
+    // Every service is mounted through the registry, which refuses paths
+    // claimed twice and lists the rest at /api/v1/admin/routes
+    let health_router = ServiceRouter::new("api-gateway")
         .route("/health", get(health_check))
         .route("/ready", get(readiness_check))
-        // Service-specific routes with prefixes
-        .nest("/api/v1", auth_router)
-        .nest("/api/v1", projects_router)
-        .nest("/api/v1/context", context_orchestrator_router)
-        .merge(backlog_router)
-        .merge(readiness_router)
-        .merge(prompt_builder_router)
-        .merge(sprint_router)
+        .route("/health/detailed", get(common::detailed_health_check))
+        .into_router();
+    let routes = RouteRegistry::new()
+        .mount("api-gateway", "", health_router)
+        .mount("auth-gateway", "/api/v1", auth_router)
+        .mount("projects", "/api/v1", projects_router)
+        .mount(
+            "context-orchestrator",
+            "/api/v1/context",
+            context_orchestrator_router,
+        )
+        .mount("backlog", "", backlog_router)
+        .mount("readiness", "", readiness_router)
+        .mount("prompt-builder", "", prompt_builder_router)
+        .mount("sprint", "", sprint_router)
+        .mount(
+            "api-gateway/organizations",
+            "",
+            api_gateway::organizations::build_organization_switch_router(
+                organization_switch_state,
+                verifier.clone(),
+            ),
+        )
+        .mount(
+            "api-gateway/overview",
+            "",
+            api_gateway::overview::build_overview_router(overview_state, verifier.clone()),
+        )
+        .mount(
+            "api-gateway/workload",
+            "",
+            api_gateway::workload::build_workload_router(workload_state, verifier.clone()),
+        )
+        .mount(
+            "api-gateway/maintenance",
+            "",
+            api_gateway::maintenance::build_maintenance_router(maintenance_state.clone()),
+        )
+        .mount(
+            "api-gateway/llm-audit",
+            "",
+            api_gateway::llm_audit::build_llm_audit_router(llm_audit_state),
+        )
+        .mount(
+            "api-gateway/reporting",
+            "",
+            api_gateway::reporting::build_reporting_router(reporting_state),
+        )
+        .mount(
+            "api-gateway/event-replay",
+            "",
+            api_gateway::event_replay::build_event_replay_router(event_replay_state),
+        )
+        .mount(
+            "api-gateway/projection-failures",
+            "",
+            api_gateway::projection_failures::build_projection_failure_router(
+                projection_failure_state,
+            ),
+        )
+        .mount(
+            "api-gateway/analytics",
+            "",
+            api_gateway::analytics::build_analytics_router(analytics_state, verifier.clone()),
+        )
+        .mount(
+            "api-gateway/usage",
+            "",
+            api_gateway::usage::build_usage_router(usage_state, verifier.clone()),
+        )
+        .mount(
+            "api-gateway/billing",
+            "",
+            api_gateway::billing::build_billing_router(billing_state, verifier.clone()),
+        )
+        .mount(
+            "api-gateway/config-bundles",
+            "",
+            api_gateway::config_bundles::build_config_bundle_router(
+                config_bundle_state,
+                verifier.clone(),
+            ),
+        )
+        .mount(
+            "api-gateway/impersonation",
+            "",
+            api_gateway::impersonation::build_impersonation_router(
+                impersonation_state.clone(),
+                verifier.clone(),
+            ),
+        )
+        .mount(
+            "api-gateway/incidents",
+            "",
+            api_gateway::incidents::build_incident_router(incident_state),
+        )
+        .mount(
+            "api-gateway/websockets",
+            "",
+            api_gateway::websockets::build_websocket_admin_router(websocket_admin_state),
+        )
+        .into_router(maintenance_state.clone())
+        .context("Failed to mount service routes")?;
+
+    let app = routes
         // Add CORS and tracing
         .layer(middleware::from_fn_with_state(
             api_key_state,
             api_gateway::auth::api_key_auth,
         ))
+        .layer(middleware::from_fn_with_state(
+            impersonation_state,
+            api_gateway::impersonation::impersonation_auth,
+        ))
+        .layer(middleware::from_fn_with_state(
+            maintenance_state,
+            api_gateway::maintenance::maintenance_guard,
+        ))
+        // Answers If-None-Match with 304 for handlers that tag their responses
+        .layer(middleware::from_fn(
+            common::etag::conditional_get_middleware,
+        ))
+        // Outermost so that errors from the auth layers are localized too
+        .layer(middleware::from_fn(common::i18n::locale_middleware))
+        // Tags the request's logs and the domain events it publishes with its x-request-id
+        .layer(middleware::from_fn(common::correlation_id_extractor))
         .layer(cors)
+        // Outside CORS so that preflight responses get the headers too
+        .layer(middleware::from_fn_with_state(
+            Arc::new(config.security_headers),
+            common::http_security::security_headers_middleware,
+        ))
         .layer(TraceLayer::new_for_http());
+    // Accept short keys like PROJ-123 in place of story and task ids
+    let app = backlog_api::with_short_key_paths(app, pool.clone());
 
     Ok(app.into())
 }
@@ -0,0 +1,251 @@
+//! Maintenance mode: while enabled, mutating requests get 503 with `Retry-After`
+//! so migrations can run without concurrent writes. Reads keep working, and
+//! requests carrying the admin token are let through.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{HeaderMap, Method, Request},
+    middleware::Next,
+    response::Response,
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+/// How long an instance trusts its cached flag before re-reading it. Toggling through
+/// the admin endpoint updates the local cache at once; other instances follow within this.
+const STATUS_TTL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    pub message: Option<String>,
+    pub retry_after_seconds: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMaintenanceRequest {
+    pub enabled: bool,
+    pub message: Option<String>,
+    pub retry_after_seconds: Option<i32>,
+}
+
+#[derive(Clone)]
+pub struct MaintenanceState {
+    pool: Arc<PgPool>,
+    admin_token: Option<Arc<str>>,
+    cached: Arc<RwLock<Option<(Instant, MaintenanceStatus)>>>,
+}
+
+impl MaintenanceState {
+    /// Without an admin token the switch can only be flipped in the database
+    pub fn new(pool: Arc<PgPool>, admin_token: Option<String>) -> Self {
+        Self {
+            pool,
+            admin_token: admin_token
+                .filter(|token| !token.trim().is_empty())
+                .map(Arc::from),
+            cached: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn is_admin(&self, headers: &HeaderMap) -> bool {
+        let Some(expected) = self.admin_token.as_deref() else {
+            return false;
+        };
+        headers
+            .get(ADMIN_TOKEN_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|provided| constant_time_eq(provided.as_bytes(), expected.as_bytes()))
+    }
+
+    pub(crate) fn require_admin(&self, headers: &HeaderMap) -> Result<(), AppError> {
+        if self.admin_token.is_none() {
+            return Err(AppError::Forbidden(
+                "Admin API is not configured".to_string(),
+            ));
+        }
+        if !self.is_admin(headers) {
+            return Err(AppError::Unauthorized(
+                "A valid admin token is required".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn status(&self) -> Result<MaintenanceStatus, AppError> {
+        if let Some((fetched_at, status)) =
+            self.cached.read().ok().and_then(|cached| cached.clone())
+        {
+            if fetched_at.elapsed() < STATUS_TTL {
+                return Ok(status);
+            }
+        }
+
+        let status = sqlx::query_as::<_, MaintenanceStatus>(
+            "SELECT enabled, message, retry_after_seconds, updated_at
+             FROM maintenance_mode WHERE id",
+        )
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error reading maintenance mode");
+            AppError::InternalServerError
+        })?
+        .unwrap_or_else(|| MaintenanceStatus {
+            enabled: false,
+            message: None,
+            retry_after_seconds: 300,
+            updated_at: Utc::now(),
+        });
+
+        self.remember(status.clone());
+        Ok(status)
+    }
+
+    async fn update(
+        &self,
+        request: UpdateMaintenanceRequest,
+    ) -> Result<MaintenanceStatus, AppError> {
+        if request
+            .retry_after_seconds
+            .is_some_and(|seconds| seconds <= 0)
+        {
+            return Err(AppError::BadRequest(
+                "retryAfterSeconds must be positive".to_string(),
+            ));
+        }
+
+        let status = sqlx::query_as::<_, MaintenanceStatus>(
+            "INSERT INTO maintenance_mode (id, enabled, message, retry_after_seconds, updated_at)
+             VALUES (TRUE, $1, $2, COALESCE($3, 300), NOW())
+             ON CONFLICT (id) DO UPDATE SET
+                 enabled = EXCLUDED.enabled,
+                 message = EXCLUDED.message,
+                 retry_after_seconds = COALESCE($3, maintenance_mode.retry_after_seconds),
+                 updated_at = NOW()
+             RETURNING enabled, message, retry_after_seconds, updated_at",
+        )
+        .bind(request.enabled)
+        .bind(
+            request
+                .message
+                .map(|message| message.trim().to_string())
+                .filter(|message| !message.is_empty()),
+        )
+        .bind(request.retry_after_seconds)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error updating maintenance mode");
+            AppError::InternalServerError
+        })?;
+
+        self.remember(status.clone());
+        Ok(status)
+    }
+
+    fn remember(&self, status: MaintenanceStatus) {
+        if let Ok(mut cached) = self.cached.write() {
+            *cached = Some((Instant::now(), status));
+        }
+    }
+}
+
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Reads and CORS preflights are never blocked
+fn is_mutating(method: &Method) -> bool {
+    !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+pub async fn maintenance_guard(
+    State(state): State<MaintenanceState>,
+    req: Request<Body>,
+    next: Next,
+) -> Result<Response, AppError> {
+    if !is_mutating(req.method()) || state.is_admin(req.headers()) {
+        return Ok(next.run(req).await);
+    }
+
+    // Fail open: an unreadable flag should not take the API down on its own
+    let status = match state.status().await {
+        Ok(status) => status,
+        Err(_) => return Ok(next.run(req).await),
+    };
+    if status.enabled {
+        return Err(AppError::ServiceUnavailable {
+            message: status.message.unwrap_or_else(|| {
+                "The service is undergoing maintenance; please retry shortly".to_string()
+            }),
+            retry_after_secs: status.retry_after_seconds.max(1) as u64,
+        });
+    }
+
+    Ok(next.run(req).await)
+}
+
+/// GET /api/v1/admin/maintenance
+async fn get_maintenance(
+    State(state): State<MaintenanceState>,
+    headers: HeaderMap,
+) -> Result<Json<MaintenanceStatus>, AppError> {
+    state.require_admin(&headers)?;
+    Ok(Json(state.status().await?))
+}
+
+/// PUT /api/v1/admin/maintenance
+async fn update_maintenance(
+    State(state): State<MaintenanceState>,
+    headers: HeaderMap,
+    Json(request): Json<UpdateMaintenanceRequest>,
+) -> Result<Json<MaintenanceStatus>, AppError> {
+    state.require_admin(&headers)?;
+    let status = state.update(request).await?;
+    tracing::warn!(enabled = status.enabled, "Maintenance mode updated");
+    Ok(Json(status))
+}
+
+pub fn build_maintenance_router(state: MaintenanceState) -> Router {
+    ServiceRouter::new("api-gateway/maintenance")
+        .route(
+            "/api/v1/admin/maintenance",
+            get(get_maintenance).put(update_maintenance),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_mutating_methods_are_blocked() {
+        assert!(!is_mutating(&Method::GET));
+        assert!(!is_mutating(&Method::HEAD));
+        assert!(!is_mutating(&Method::OPTIONS));
+        assert!(is_mutating(&Method::POST));
+        assert!(is_mutating(&Method::PATCH));
+        assert!(is_mutating(&Method::DELETE));
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+}
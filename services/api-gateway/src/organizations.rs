@@ -0,0 +1,363 @@
+//! Organization switching for users who belong to several organizations: the
+//! organizations a user can act in, the one they last switched to, and the
+//! cache the auth middleware reads memberships from so resolving a request's
+//! organization does not hit the database every time.
+
+use crate::sql_error;
+use auth_clerk::{Authenticated, JwtVerifier};
+use axum::{
+    extract::State,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// How long memberships are trusted before they are read again. Switching the
+/// active organization refreshes the caller's entry at once; membership changes
+/// take effect within this. Requests from deprovisioned users are refused
+/// without the cache.
+const MEMBERSHIP_TTL: Duration = Duration::from_secs(30);
+/// Entries kept before expired ones are swept out
+const MAX_CACHED_ENTRIES: usize = 10_000;
+
+/// Values remembered for a fixed time
+struct TtlMap<K, V> {
+    ttl: Duration,
+    entries: RwLock<HashMap<K, (Instant, V)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlMap<K, V> {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        let entries = self.entries.read().ok()?;
+        let (stored_at, value) = entries.get(key)?;
+        (stored_at.elapsed() < self.ttl).then(|| value.clone())
+    }
+
+    fn insert(&self, key: K, value: V) {
+        if let Ok(mut entries) = self.entries.write() {
+            if entries.len() >= MAX_CACHED_ENTRIES {
+                let ttl = self.ttl;
+                entries.retain(|_, (stored_at, _)| stored_at.elapsed() < ttl);
+            }
+            entries.insert(key, (Instant::now(), value));
+        }
+    }
+
+    fn remove(&self, key: &K) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.remove(key);
+        }
+    }
+}
+
+/// An organization the user is a member of
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationMembership {
+    pub organization_id: Uuid,
+    pub external_id: String,
+    pub name: String,
+    pub role: String,
+}
+
+/// What the gateway knows about a signed-in user's organizations
+#[derive(Debug, Clone, Default)]
+pub struct UserMemberships {
+    /// `None` until the user has been synced from the identity provider
+    pub user_id: Option<Uuid>,
+    pub organizations: Vec<OrganizationMembership>,
+    /// Organizations whose identity provider has deprovisioned the user
+    pub deprovisioned: Vec<Uuid>,
+    pub active_organization_id: Option<Uuid>,
+}
+
+impl UserMemberships {
+    pub fn is_deprovisioned(&self, organization_id: Uuid) -> bool {
+        self.deprovisioned.contains(&organization_id)
+    }
+
+    /// Organizations the user can currently act in
+    pub fn available(&self) -> impl Iterator<Item = &OrganizationMembership> {
+        self.organizations
+            .iter()
+            .filter(|membership| !self.is_deprovisioned(membership.organization_id))
+    }
+
+    /// The organization the user switched to, while they can still act in it
+    pub fn active_organization(&self) -> Option<&OrganizationMembership> {
+        let active = self.active_organization_id?;
+        self.available()
+            .find(|membership| membership.organization_id == active)
+    }
+}
+
+/// An organization looked up by its identity provider id
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct OrganizationRecord {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// Memberships by user subject and organizations by external id, shared by
+/// the auth middleware and the organization switch endpoints
+#[derive(Clone)]
+pub struct MembershipCache {
+    users: Arc<TtlMap<String, Arc<UserMemberships>>>,
+    organizations: Arc<TtlMap<String, Option<OrganizationRecord>>>,
+}
+
+impl Default for MembershipCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MembershipCache {
+    pub fn new() -> Self {
+        Self {
+            users: Arc::new(TtlMap::new(MEMBERSHIP_TTL)),
+            organizations: Arc::new(TtlMap::new(MEMBERSHIP_TTL)),
+        }
+    }
+
+    /// The memberships of the user with this identity provider subject.
+    /// Unknown users are cached too, as having none.
+    pub async fn memberships(
+        &self,
+        pool: &PgPool,
+        subject: &str,
+    ) -> Result<Arc<UserMemberships>, AppError> {
+        if let Some(memberships) = self.users.get(&subject.to_string()) {
+            return Ok(memberships);
+        }
+        let memberships = Arc::new(load_memberships(pool, subject).await?);
+        self.users.insert(subject.to_string(), memberships.clone());
+        Ok(memberships)
+    }
+
+    pub async fn organization_by_external_id(
+        &self,
+        pool: &PgPool,
+        external_id: &str,
+    ) -> Result<Option<OrganizationRecord>, AppError> {
+        if let Some(record) = self.organizations.get(&external_id.to_string()) {
+            return Ok(record);
+        }
+        let record = sqlx::query_as::<_, OrganizationRecord>(
+            "SELECT id, name FROM organizations WHERE external_id = $1",
+        )
+        .bind(external_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(sql_error("looking up organization by external id"))?;
+        self.organizations
+            .insert(external_id.to_string(), record.clone());
+        Ok(record)
+    }
+
+    /// Forget a user's memberships so the next request reads them again
+    pub fn invalidate(&self, subject: &str) {
+        self.users.remove(&subject.to_string());
+    }
+}
+
+async fn load_memberships(pool: &PgPool, subject: &str) -> Result<UserMemberships, AppError> {
+    let user = sqlx::query_as::<_, (Uuid, Option<Uuid>)>(
+        "SELECT id, active_organization_id FROM users WHERE external_id = $1",
+    )
+    .bind(subject)
+    .fetch_optional(pool)
+    .await
+    .map_err(sql_error("loading user for memberships"))?;
+    let Some((user_id, active_organization_id)) = user else {
+        return Ok(UserMemberships::default());
+    };
+
+    let organizations = sqlx::query_as::<_, OrganizationMembership>(
+        "SELECT o.id AS organization_id, o.external_id, o.name, m.role
+         FROM organization_memberships m
+         JOIN organizations o ON o.id = m.organization_id
+         WHERE m.user_id = $1
+         ORDER BY o.name, o.id",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(sql_error("loading organization memberships"))?;
+    let deprovisioned = sqlx::query_scalar::<_, Uuid>(
+        "SELECT organization_id FROM scim_users WHERE user_id = $1 AND NOT active",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(sql_error("loading deprovisioned organizations"))?;
+
+    Ok(UserMemberships {
+        user_id: Some(user_id),
+        organizations,
+        deprovisioned,
+        active_organization_id,
+    })
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyOrganizations {
+    pub organizations: Vec<OrganizationMembership>,
+    /// `None` when the user works in their personal context
+    pub active_organization_id: Option<Uuid>,
+}
+
+impl From<&UserMemberships> for MyOrganizations {
+    fn from(memberships: &UserMemberships) -> Self {
+        Self {
+            organizations: memberships.available().cloned().collect(),
+            active_organization_id: memberships
+                .active_organization()
+                .map(|membership| membership.organization_id),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetActiveOrganizationRequest {
+    /// `null` switches to the personal context
+    #[serde(alias = "organization_id")]
+    pub organization_id: Option<Uuid>,
+}
+
+#[derive(Clone)]
+pub struct OrganizationSwitchState {
+    pool: Arc<PgPool>,
+    cache: MembershipCache,
+}
+
+impl OrganizationSwitchState {
+    /// `cache` must be the one the auth middleware reads, so a switch is seen at once
+    pub fn new(pool: Arc<PgPool>, cache: MembershipCache) -> Self {
+        Self { pool, cache }
+    }
+}
+
+/// GET /api/v1/me/organizations
+async fn get_my_organizations(
+    auth: Authenticated,
+    State(state): State<OrganizationSwitchState>,
+) -> Result<Json<MyOrganizations>, AppError> {
+    let memberships = state.cache.memberships(&state.pool, &auth.sub).await?;
+    Ok(Json(MyOrganizations::from(memberships.as_ref())))
+}
+
+/// POST /api/v1/me/active-organization
+/// Remember the organization the user works in. Requests that name no
+/// organization and no context run in it from then on.
+async fn set_active_organization(
+    auth: Authenticated,
+    State(state): State<OrganizationSwitchState>,
+    Json(request): Json<SetActiveOrganizationRequest>,
+) -> Result<Json<MyOrganizations>, AppError> {
+    let memberships = state.cache.memberships(&state.pool, &auth.sub).await?;
+    let user_id = memberships
+        .user_id
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    if let Some(organization_id) = request.organization_id {
+        if !memberships
+            .available()
+            .any(|membership| membership.organization_id == organization_id)
+        {
+            return Err(AppError::Forbidden(
+                "You are not a member of this organization".to_string(),
+            ));
+        }
+    }
+
+    sqlx::query("UPDATE users SET active_organization_id = $2, updated_at = NOW() WHERE id = $1")
+        .bind(user_id)
+        .bind(request.organization_id)
+        .execute(&*state.pool)
+        .await
+        .map_err(sql_error("saving active organization"))?;
+    state.cache.invalidate(&auth.sub);
+
+    let memberships = state.cache.memberships(&state.pool, &auth.sub).await?;
+    Ok(Json(MyOrganizations::from(memberships.as_ref())))
+}
+
+pub fn build_organization_switch_router(
+    state: OrganizationSwitchState,
+    verifier: Arc<Mutex<JwtVerifier>>,
+) -> Router {
+    ServiceRouter::new("api-gateway/organizations")
+        .route("/api/v1/me/organizations", get(get_my_organizations))
+        .route(
+            "/api/v1/me/active-organization",
+            post(set_active_organization),
+        )
+        .with_state(state)
+        .layer(Extension(verifier))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn membership(name: &str) -> OrganizationMembership {
+        OrganizationMembership {
+            organization_id: Uuid::new_v4(),
+            external_id: format!("org_{}", name),
+            name: name.to_string(),
+            role: "member".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_deprovisioned_organizations_are_not_available() {
+        let (acme, globex) = (membership("Acme"), membership("Globex"));
+        let memberships = UserMemberships {
+            user_id: Some(Uuid::new_v4()),
+            organizations: vec![acme.clone(), globex.clone()],
+            deprovisioned: vec![globex.organization_id],
+            active_organization_id: Some(globex.organization_id),
+        };
+
+        let listed = MyOrganizations::from(&memberships);
+        assert_eq!(listed.organizations.len(), 1);
+        assert_eq!(
+            listed.organizations[0].organization_id,
+            acme.organization_id
+        );
+        // The preference survives, but is not applied while it cannot be used
+        assert_eq!(listed.active_organization_id, None);
+        assert!(memberships.active_organization().is_none());
+    }
+
+    #[test]
+    fn test_ttl_map_expires_and_forgets() {
+        let map = TtlMap::new(Duration::from_secs(60));
+        map.insert("user", 1);
+        assert_eq!(map.get(&"user"), Some(1));
+        map.remove(&"user");
+        assert_eq!(map.get(&"user"), None);
+
+        let expired = TtlMap::new(Duration::ZERO);
+        expired.insert("user", 1);
+        assert_eq!(expired.get(&"user"), None);
+    }
+}
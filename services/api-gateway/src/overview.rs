@@ -0,0 +1,435 @@
+//! "My organization at a glance": everything the dashboard home page shows for
+//! the signed-in user, assembled in one request from the backlog, sprint and
+//! readiness usecases.
+
+use crate::{BacklogUsecases, ReadinessUsecases};
+use auth_clerk::{AuthenticatedWithOrg, JwtVerifier};
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Extension, Json, Router,
+};
+use backlog_api::TaskResponse;
+use backlog_api::{Story, StoryQuery, StoryRevision, StoryStatus};
+use chrono::{DateTime, Utc};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use futures::future::try_join_all;
+use readiness_api::ReadinessEvaluation;
+use serde::{Deserialize, Serialize};
+use sprint_api::SprintStats;
+use sprint_api::SprintsUsecases;
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const RECENT_ACTIVITY_LIMIT: u32 = 15;
+const MAX_READINESS_ALERTS: usize = 10;
+
+/// Statuses in which a story waits on the person it is assigned to
+const OWNER_ACTION_STATUSES: [StoryStatus; 3] = [
+    StoryStatus::Draft,
+    StoryStatus::NeedsRefinement,
+    StoryStatus::AwaitingAcceptance,
+];
+
+/// Statuses in which a story is expected to be ready to work on
+const SCHEDULED_STATUSES: [StoryStatus; 3] = [
+    StoryStatus::Ready,
+    StoryStatus::Committed,
+    StoryStatus::InProgress,
+];
+
+#[derive(Clone)]
+pub struct OverviewState {
+    pool: Arc<PgPool>,
+    backlog: Arc<BacklogUsecases>,
+    sprints: Arc<SprintsUsecases>,
+    readiness: Arc<ReadinessUsecases>,
+}
+
+impl OverviewState {
+    pub fn new(
+        pool: Arc<PgPool>,
+        backlog: Arc<BacklogUsecases>,
+        sprints: Arc<SprintsUsecases>,
+        readiness: Arc<ReadinessUsecases>,
+    ) -> Self {
+        Self {
+            pool,
+            backlog,
+            sprints,
+            readiness,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverviewQuery {
+    /// Project for the sprint snapshot and readiness alerts. Defaults to the
+    /// project of the user's most recently touched work.
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintSnapshot {
+    pub id: Uuid,
+    pub name: String,
+    pub goal: Option<String>,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub days_remaining: i64,
+    pub stats: SprintStats,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyTasks {
+    pub total: usize,
+    pub by_status: BTreeMap<String, Vec<TaskResponse>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryAwaitingAction {
+    pub story_id: Uuid,
+    pub short_key: Option<String>,
+    pub project_id: Uuid,
+    pub title: String,
+    pub status: String,
+    /// `refine` or `accept`
+    pub action: &'static str,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityItem {
+    pub story_id: Uuid,
+    pub title: String,
+    /// `created` for a story's first revision, otherwise `edited`
+    pub kind: &'static str,
+    pub revision: u32,
+    pub edited_by: Option<Uuid>,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessAlert {
+    pub story_id: Uuid,
+    pub short_key: Option<String>,
+    pub title: String,
+    pub status: String,
+    /// `None` when the story has never been evaluated
+    pub score: Option<i32>,
+    pub missing_items: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyOverview {
+    pub project_id: Option<Uuid>,
+    pub active_sprint: Option<SprintSnapshot>,
+    pub my_tasks: MyTasks,
+    pub awaiting_my_action: Vec<StoryAwaitingAction>,
+    pub recent_activity: Vec<ActivityItem>,
+    pub readiness_alerts: Vec<ReadinessAlert>,
+    pub generated_at: DateTime<Utc>,
+}
+
+pub fn build_overview_router(state: OverviewState, verifier: Arc<Mutex<JwtVerifier>>) -> Router {
+    ServiceRouter::new("api-gateway/overview")
+        .route("/api/v1/me/overview", get(get_my_overview))
+        .with_state(state)
+        .layer(Extension(verifier))
+}
+
+/// GET /api/v1/me/overview?projectId=
+async fn get_my_overview(
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
+    State(state): State<OverviewState>,
+    Query(query): Query<OverviewQuery>,
+) -> Result<Json<MyOverview>, AppError> {
+    let organization_id = org_context.effective_organization_uuid();
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    let requested_project = async {
+        match query.project_id {
+            Some(project_id) => {
+                ensure_project_in_organization(&state.pool, project_id, organization_id).await?;
+                Ok(Some(project_id))
+            }
+            None => Ok(None),
+        }
+    };
+    let (requested_project, tasks, awaiting, revisions) = tokio::try_join!(
+        requested_project,
+        state.backlog.get_user_owned_tasks(user_id, organization_id),
+        state
+            .backlog
+            .get_stories_assigned_to(user_id, organization_id, &OWNER_ACTION_STATUSES),
+        state
+            .backlog
+            .get_recent_story_revisions(organization_id, RECENT_ACTIVITY_LIMIT),
+    )?;
+
+    let project_id = match requested_project {
+        Some(project_id) => Some(project_id),
+        // Tasks come back most recently updated first
+        None => match tasks.first() {
+            Some(task) => state
+                .backlog
+                .get_story(task.story_id, organization_id)
+                .await?
+                .map(|story| story.project_id),
+            None => awaiting.first().map(|story| story.project_id),
+        },
+    };
+
+    let (active_sprint, readiness_alerts) = match project_id {
+        Some(project_id) => tokio::try_join!(
+            sprint_snapshot(&state, project_id),
+            readiness_alerts(&state, project_id, organization_id),
+        )?,
+        None => (None, Vec::new()),
+    };
+
+    let mut by_status: BTreeMap<String, Vec<TaskResponse>> = BTreeMap::new();
+    let total = tasks.len();
+    for task in tasks.into_iter().map(TaskResponse::from) {
+        by_status.entry(task.status.clone()).or_default().push(task);
+    }
+
+    Ok(Json(MyOverview {
+        project_id,
+        active_sprint,
+        my_tasks: MyTasks { total, by_status },
+        awaiting_my_action: awaiting.into_iter().filter_map(awaiting_action).collect(),
+        recent_activity: revisions.into_iter().map(ActivityItem::from).collect(),
+        readiness_alerts,
+        generated_at: Utc::now(),
+    }))
+}
+
+async fn sprint_snapshot(
+    state: &OverviewState,
+    project_id: Uuid,
+) -> Result<Option<SprintSnapshot>, AppError> {
+    let Some(sprint) = state.sprints.get_active_sprint(project_id).await? else {
+        return Ok(None);
+    };
+    let board = state
+        .sprints
+        .get_sprint_task_board(sprint.id, None, None, None)
+        .await?;
+
+    Ok(Some(SprintSnapshot {
+        id: sprint.id,
+        name: sprint.name,
+        goal: sprint.goal,
+        start_date: sprint.start_date,
+        end_date: sprint.end_date,
+        days_remaining: board.sprint.days_remaining.max(0),
+        stats: board.stats,
+    }))
+}
+
+async fn readiness_alerts(
+    state: &OverviewState,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Vec<ReadinessAlert>, AppError> {
+    let stories: Vec<Story> = state
+        .backlog
+        .get_stories_by_project(project_id, organization_id, &StoryQuery::default())
+        .await?
+        .stories
+        .into_iter()
+        .filter(|story| SCHEDULED_STATUSES.contains(&story.status) && !story.readiness_override)
+        .collect();
+    let evaluations = try_join_all(stories.iter().map(|story| {
+        state
+            .readiness
+            .get_latest_evaluation(story.id, organization_id)
+    }))
+    .await?;
+
+    Ok(select_readiness_alerts(stories, evaluations))
+}
+
+/// Scheduled stories whose latest evaluation falls short of ready, or that
+/// were never evaluated, worst first
+fn select_readiness_alerts(
+    stories: Vec<Story>,
+    evaluations: Vec<Option<ReadinessEvaluation>>,
+) -> Vec<ReadinessAlert> {
+    let mut alerts: Vec<ReadinessAlert> = stories
+        .into_iter()
+        .zip(evaluations)
+        .filter(|(_, evaluation)| {
+            !evaluation
+                .as_ref()
+                .is_some_and(ReadinessEvaluation::is_ready)
+        })
+        .map(|(story, evaluation)| ReadinessAlert {
+            story_id: story.id,
+            short_key: story.short_key,
+            title: story.title,
+            status: story.status.to_string(),
+            score: evaluation.as_ref().map(|evaluation| evaluation.score),
+            missing_items: evaluation
+                .map(|evaluation| evaluation.missing_items)
+                .unwrap_or_default(),
+        })
+        .collect();
+    alerts.sort_by_key(|alert| alert.score);
+    alerts.truncate(MAX_READINESS_ALERTS);
+    alerts
+}
+
+fn awaiting_action(story: Story) -> Option<StoryAwaitingAction> {
+    let action = match story.status {
+        StoryStatus::Draft | StoryStatus::NeedsRefinement => "refine",
+        StoryStatus::AwaitingAcceptance => "accept",
+        _ => return None,
+    };
+    Some(StoryAwaitingAction {
+        story_id: story.id,
+        short_key: story.short_key,
+        project_id: story.project_id,
+        title: story.title,
+        status: story.status.to_string(),
+        action,
+        updated_at: story.updated_at,
+    })
+}
+
+impl From<StoryRevision> for ActivityItem {
+    fn from(revision: StoryRevision) -> Self {
+        Self {
+            story_id: revision.story_id,
+            title: revision.title,
+            kind: if revision.revision == 1 {
+                "created"
+            } else {
+                "edited"
+            },
+            revision: revision.revision,
+            edited_by: revision.edited_by,
+            at: revision.created_at,
+        }
+    }
+}
+
+pub(crate) async fn resolve_user_id(pool: &PgPool, clerk_id: &str) -> Result<Uuid, AppError> {
+    sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE external_id = $1")
+        .bind(clerk_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error resolving user");
+            AppError::InternalServerError
+        })?
+        .ok_or_else(|| AppError::Unauthorized("User not found".to_string()))
+}
+
+async fn ensure_project_in_organization(
+    pool: &PgPool,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (
+             SELECT 1 FROM projects
+             WHERE id = $1
+               AND organization_id IS NOT DISTINCT FROM $2
+               AND deleted_at IS NULL
+         )",
+    )
+    .bind(project_id)
+    .bind(organization_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error checking overview project");
+        AppError::InternalServerError
+    })?;
+
+    if !exists {
+        return Err(AppError::NotFound("Project not found".to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(title: &str) -> Story {
+        let mut story = Story::new(Uuid::new_v4(), None, title.to_string(), None).unwrap();
+        story.status = StoryStatus::Committed;
+        story
+    }
+
+    fn evaluation(story: &Story, score: i32, missing: &[&str]) -> ReadinessEvaluation {
+        ReadinessEvaluation::new(
+            story.id,
+            None,
+            score,
+            missing.iter().map(|item| item.to_string()).collect(),
+            String::new(),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn test_alerts_skip_ready_stories_and_list_worst_first() {
+        let ready = story("Ready story");
+        let weak = story("Weak story");
+        let poor = story("Poor story");
+        let unevaluated = story("Never evaluated");
+        let evaluations = vec![
+            Some(evaluation(&ready, 95, &[])),
+            Some(evaluation(&weak, 85, &["No estimate"])),
+            Some(evaluation(&poor, 40, &["No acceptance criteria"])),
+            None,
+        ];
+
+        let alerts = select_readiness_alerts(
+            vec![ready, weak.clone(), poor.clone(), unevaluated.clone()],
+            evaluations,
+        );
+
+        let order: Vec<(Uuid, Option<i32>)> = alerts
+            .iter()
+            .map(|alert| (alert.story_id, alert.score))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (unevaluated.id, None),
+                (poor.id, Some(40)),
+                (weak.id, Some(85)),
+            ]
+        );
+        assert_eq!(alerts[1].missing_items, vec!["No acceptance criteria"]);
+    }
+
+    #[test]
+    fn test_awaiting_action_by_status() {
+        let mut draft = story("Draft");
+        draft.status = StoryStatus::NeedsRefinement;
+        assert_eq!(awaiting_action(draft).unwrap().action, "refine");
+
+        let mut review = story("Review");
+        review.status = StoryStatus::AwaitingAcceptance;
+        assert_eq!(awaiting_action(review).unwrap().action, "accept");
+
+        assert!(awaiting_action(story("Committed")).is_none());
+    }
+}
@@ -0,0 +1,380 @@
+//! Dead letters of projection workers: every event a worker gave up on after
+//! retrying is kept with its envelope, last error and attempt count, so
+//! maintainers holding the admin token can list them and re-drive one, or all
+//! of one projection's, instead of rehydrating everything. A projection can
+//! also be replayed from the event outbox, which keeps 30 days of delivered
+//! events.
+
+use crate::event_replay::event_kind;
+use crate::maintenance::MaintenanceState;
+use crate::sql_error;
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use event_bus::outbox::{replay_projection, OUTBOX_RETENTION_DAYS};
+use event_bus::{EventBus, EventEnvelope, ProjectionFailure, ProjectionFailureSink};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+/// Outbox replays cover the last day unless told otherwise
+const DEFAULT_REPLAY_HOURS: i64 = 24;
+
+/// Persists failed event applications in the background
+#[derive(Clone)]
+pub struct PgProjectionFailureSink {
+    pool: Arc<PgPool>,
+}
+
+impl PgProjectionFailureSink {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+impl ProjectionFailureSink for PgProjectionFailureSink {
+    fn record(&self, failure: ProjectionFailure) {
+        let pool = self.pool.clone();
+        tokio::spawn(async move {
+            if let Err(e) = store_failure(&pool, &failure).await {
+                tracing::error!(
+                    error = %e,
+                    event_id = %failure.envelope.id,
+                    service = failure.projection.service,
+                    projection = failure.projection.name,
+                    "SQL error recording projection failure"
+                );
+            }
+        });
+    }
+}
+
+async fn store_failure(pool: &PgPool, failure: &ProjectionFailure) -> Result<(), sqlx::Error> {
+    let envelope = serde_json::to_value(&failure.envelope).unwrap_or_default();
+    sqlx::query(
+        "INSERT INTO projection_dead_letters
+             (id, service, projection, event_id, event_type, envelope, error, attempts)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(failure.projection.service)
+    .bind(failure.projection.name)
+    .bind(failure.envelope.id)
+    .bind(event_kind(&failure.envelope.event))
+    .bind(envelope)
+    .bind(&failure.error)
+    .bind(failure.attempts.min(i32::MAX as u32) as i32)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectionFailureRecord {
+    pub id: Uuid,
+    pub service: String,
+    pub projection: String,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub envelope: serde_json::Value,
+    pub error: String,
+    pub attempts: i32,
+    pub created_at: DateTime<Utc>,
+    pub last_attempted_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectionFailureQuery {
+    pub service: Option<String>,
+    #[serde(alias = "event_type")]
+    pub event_type: Option<String>,
+    /// Also list failures a retry has since resolved
+    #[serde(default, alias = "include_resolved")]
+    pub include_resolved: bool,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Clone)]
+pub struct ProjectionFailureState {
+    pool: Arc<PgPool>,
+    admin: MaintenanceState,
+    event_bus: Arc<EventBus>,
+}
+
+impl ProjectionFailureState {
+    /// Retries go through the projections registered on `event_bus`
+    pub fn new(pool: Arc<PgPool>, admin: MaintenanceState, event_bus: Arc<EventBus>) -> Self {
+        Self {
+            pool,
+            admin,
+            event_bus,
+        }
+    }
+}
+
+const FAILURE_COLUMNS: &str = "id, service, projection, event_id, event_type, envelope, error,
+    attempts, created_at, last_attempted_at, resolved_at";
+
+/// GET /api/v1/admin/projections/failures?service=&event_type=&include_resolved=&limit=&offset=
+/// Unresolved failures, newest first
+async fn list_projection_failures(
+    State(state): State<ProjectionFailureState>,
+    headers: HeaderMap,
+    Query(query): Query<ProjectionFailureQuery>,
+) -> Result<Json<Vec<ProjectionFailureRecord>>, AppError> {
+    state.admin.require_admin(&headers)?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let records = sqlx::query_as::<_, ProjectionFailureRecord>(&format!(
+        "SELECT {FAILURE_COLUMNS}
+         FROM projection_dead_letters
+         WHERE ($1::text IS NULL OR service = $1)
+           AND ($2::text IS NULL OR event_type = $2)
+           AND ($3 OR resolved_at IS NULL)
+         ORDER BY created_at DESC, id
+         LIMIT $4 OFFSET $5"
+    ))
+    .bind(query.service.as_deref().map(str::trim))
+    .bind(query.event_type.as_deref().map(str::trim))
+    .bind(query.include_resolved)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&*state.pool)
+    .await
+    .map_err(sql_error("listing projection failures"))?;
+
+    Ok(Json(records))
+}
+
+/// POST /api/v1/admin/projections/failures/{id}/retry
+/// Apply the failed event again to the projection that failed it. The failure
+/// is resolved on success; otherwise it keeps the new error.
+async fn retry_projection_failure(
+    State(state): State<ProjectionFailureState>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ProjectionFailureRecord>, AppError> {
+    state.admin.require_admin(&headers)?;
+
+    let failure = sqlx::query_as::<_, ProjectionFailureRecord>(&format!(
+        "SELECT {FAILURE_COLUMNS} FROM projection_dead_letters WHERE id = $1"
+    ))
+    .bind(id)
+    .fetch_optional(&*state.pool)
+    .await
+    .map_err(sql_error("loading projection failure"))?
+    .ok_or_else(|| AppError::NotFound(format!("Projection failure {} not found", id)))?;
+    if failure.resolved_at.is_some() {
+        return Err(AppError::Conflict(
+            "Projection failure is already resolved".to_string(),
+        ));
+    }
+
+    Ok(Json(redrive(&state, failure).await?))
+}
+
+/// Apply a dead letter again and record the outcome
+async fn redrive(
+    state: &ProjectionFailureState,
+    failure: ProjectionFailureRecord,
+) -> Result<ProjectionFailureRecord, AppError> {
+    let id = failure.id;
+    let envelope: EventEnvelope = serde_json::from_value(failure.envelope).map_err(|e| {
+        tracing::error!(
+            error = %e,
+            failure_id = %id,
+            "Stored projection failure envelope is unreadable"
+        );
+        AppError::InternalServerError
+    })?;
+    let outcome = state
+        .event_bus
+        .replay(&failure.service, &failure.projection, &envelope)
+        .await
+        .ok_or_else(|| not_running(&failure.service, &failure.projection))?;
+    if let Err(error) = &outcome {
+        tracing::warn!(failure_id = %id, error = %error, "Projection failure retry failed");
+    }
+
+    sqlx::query_as::<_, ProjectionFailureRecord>(&format!(
+        "UPDATE projection_dead_letters
+         SET attempts = attempts + 1,
+             last_attempted_at = NOW(),
+             error = COALESCE($2, error),
+             resolved_at = CASE WHEN $2 IS NULL THEN NOW() ELSE NULL END
+         WHERE id = $1
+         RETURNING {FAILURE_COLUMNS}"
+    ))
+    .bind(id)
+    .bind(outcome.err())
+    .fetch_one(&*state.pool)
+    .await
+    .map_err(sql_error("updating projection failure"))
+}
+
+fn not_running(service: &str, projection: &str) -> AppError {
+    AppError::Conflict(format!(
+        "Projection {}/{} is not running on this instance",
+        service, projection
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RedriveQuery {
+    pub service: String,
+    pub projection: String,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedriveResponse {
+    pub attempted: usize,
+    pub resolved: usize,
+    /// Dead letters the projection failed to apply again
+    pub still_failing: Vec<Uuid>,
+}
+
+/// POST /api/v1/admin/projections/failures/redrive?service=&projection=&limit=
+/// Re-drive one projection's unresolved dead letters, oldest first, so they
+/// apply in the order they originally failed
+async fn redrive_projection_failures(
+    State(state): State<ProjectionFailureState>,
+    headers: HeaderMap,
+    Query(query): Query<RedriveQuery>,
+) -> Result<Json<RedriveResponse>, AppError> {
+    state.admin.require_admin(&headers)?;
+    let (service, projection) = (query.service.trim(), query.projection.trim());
+    if !state.event_bus.has_projection(service, projection) {
+        return Err(not_running(service, projection));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let failures = sqlx::query_as::<_, ProjectionFailureRecord>(&format!(
+        "SELECT {FAILURE_COLUMNS}
+         FROM projection_dead_letters
+         WHERE service = $1 AND projection = $2 AND resolved_at IS NULL
+         ORDER BY created_at, id
+         LIMIT $3"
+    ))
+    .bind(service)
+    .bind(projection)
+    .bind(limit)
+    .fetch_all(&*state.pool)
+    .await
+    .map_err(sql_error("listing dead letters to re-drive"))?;
+
+    let mut response = RedriveResponse {
+        attempted: failures.len(),
+        resolved: 0,
+        still_failing: Vec::new(),
+    };
+    for failure in failures {
+        let record = redrive(&state, failure).await?;
+        match record.resolved_at {
+            Some(_) => response.resolved += 1,
+            None => response.still_failing.push(record.id),
+        }
+    }
+    tracing::warn!(
+        service = %service,
+        projection = %projection,
+        attempted = response.attempted,
+        resolved = response.resolved,
+        "Re-drove projection dead letters"
+    );
+
+    Ok(Json(response))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OutboxReplayQuery {
+    /// Replay events written from this instant on
+    pub since: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxReplayResponse {
+    pub since: DateTime<Utc>,
+    pub replayed: usize,
+    pub failed_event_ids: Vec<Uuid>,
+    /// More events were written since `since` than one replay applies
+    pub truncated: bool,
+}
+
+/// POST /api/v1/admin/projections/{service}/{projection}/replay?since=
+/// Apply the outbox's delivered events to one projection again, oldest first.
+/// Events it still fails to apply are listed; they are not recorded as failures.
+async fn replay_projection_from_outbox(
+    State(state): State<ProjectionFailureState>,
+    headers: HeaderMap,
+    Path((service, projection)): Path<(String, String)>,
+    Query(query): Query<OutboxReplayQuery>,
+) -> Result<Json<OutboxReplayResponse>, AppError> {
+    state.admin.require_admin(&headers)?;
+    let now = Utc::now();
+    let since = query
+        .since
+        .unwrap_or(now - Duration::hours(DEFAULT_REPLAY_HOURS));
+    if since < now - Duration::days(OUTBOX_RETENTION_DAYS) {
+        return Err(AppError::BadRequest(format!(
+            "The outbox keeps events for {} days",
+            OUTBOX_RETENTION_DAYS
+        )));
+    }
+
+    let replay = replay_projection(&state.pool, &state.event_bus, &service, &projection, since)
+        .await
+        .map_err(sql_error("replaying outbox events"))?
+        .ok_or_else(|| not_running(&service, &projection))?;
+    tracing::warn!(
+        service = %service,
+        projection = %projection,
+        replayed = replay.replayed,
+        failed = replay.failed.len(),
+        "Replayed outbox events into projection"
+    );
+
+    Ok(Json(OutboxReplayResponse {
+        since,
+        replayed: replay.replayed,
+        failed_event_ids: replay.failed,
+        truncated: replay.truncated,
+    }))
+}
+
+pub fn build_projection_failure_router(state: ProjectionFailureState) -> Router {
+    ServiceRouter::new("api-gateway/projection-failures")
+        .route(
+            "/api/v1/admin/projections/failures",
+            get(list_projection_failures),
+        )
+        .route(
+            "/api/v1/admin/projections/failures/{id}/retry",
+            post(retry_projection_failure),
+        )
+        .route(
+            "/api/v1/admin/projections/failures/redrive",
+            post(redrive_projection_failures),
+        )
+        .route(
+            "/api/v1/admin/projections/{service}/{projection}/replay",
+            post(replay_projection_from_outbox),
+        )
+        .with_state(state)
+}
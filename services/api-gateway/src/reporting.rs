@@ -0,0 +1,192 @@
+//! Reporting views: the stable, read-only SQL surface BI tools such as
+//! Metabase connect to. The views are defined by migrations in the `reporting`
+//! schema and their columns documented with SQL comments, which this endpoint
+//! reads back so the published contract cannot drift from the database.
+
+use crate::maintenance::MaintenanceState;
+use axum::{extract::State, http::HeaderMap, routing::get, Json, Router};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+pub const REPORTING_SCHEMA: &str = "reporting";
+/// Bumped whenever a view is added or a deprecated one dropped. Views never
+/// change incompatibly in place; a breaking change ships as a new `_v2` view.
+pub const REPORTING_CONTRACT_VERSION: u32 = 1;
+/// The role BI connections are granted, which can read only the reporting schema
+pub const REPORTING_ROLE: &str = "reporting_reader";
+/// The views covered by the contract, in the order they are listed
+pub const REPORTING_VIEWS: &[&str] = &["vw_stories", "vw_sprint_metrics", "vw_readiness_scores"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingContract {
+    pub version: u32,
+    pub schema: String,
+    pub role: String,
+    pub views: Vec<ReportingView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingView {
+    pub name: String,
+    /// `schema.name`, as it is queried
+    pub qualified_name: String,
+    pub description: Option<String>,
+    pub columns: Vec<ReportingColumn>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingColumn {
+    pub name: String,
+    /// The PostgreSQL type, such as `uuid`, `text[]` or `timestamp with time zone`
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct ViewColumnRow {
+    view_name: String,
+    view_description: Option<String>,
+    column_name: String,
+    data_type: String,
+    column_description: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct ReportingState {
+    pool: Arc<PgPool>,
+    admin: MaintenanceState,
+}
+
+impl ReportingState {
+    pub fn new(pool: Arc<PgPool>, admin: MaintenanceState) -> Self {
+        Self { pool, admin }
+    }
+}
+
+/// Group catalog rows, ordered by view and column position, into the
+/// contracted views. Views missing from the database are left out.
+fn contract_views(rows: Vec<ViewColumnRow>) -> Vec<ReportingView> {
+    let mut views: Vec<ReportingView> = REPORTING_VIEWS
+        .iter()
+        .map(|name| ReportingView {
+            name: name.to_string(),
+            qualified_name: format!("{}.{}", REPORTING_SCHEMA, name),
+            description: None,
+            columns: Vec::new(),
+        })
+        .collect();
+    for row in rows {
+        let Some(view) = views.iter_mut().find(|view| view.name == row.view_name) else {
+            continue;
+        };
+        view.description = row.view_description;
+        view.columns.push(ReportingColumn {
+            name: row.column_name,
+            data_type: row.data_type,
+            description: row.column_description,
+        });
+    }
+
+    views.retain(|view| {
+        if view.columns.is_empty() {
+            tracing::error!(view = %view.qualified_name, "Reporting view is missing from the database");
+        }
+        !view.columns.is_empty()
+    });
+    views
+}
+
+/// GET /api/v1/admin/reporting-views
+async fn get_reporting_views(
+    State(state): State<ReportingState>,
+    headers: HeaderMap,
+) -> Result<Json<ReportingContract>, AppError> {
+    state.admin.require_admin(&headers)?;
+
+    let rows = sqlx::query_as::<_, ViewColumnRow>(
+        "SELECT cls.relname::text AS view_name,
+                obj_description(cls.oid, 'pg_class') AS view_description,
+                att.attname::text AS column_name,
+                format_type(att.atttypid, att.atttypmod) AS data_type,
+                col_description(cls.oid, att.attnum) AS column_description
+         FROM pg_class cls
+         JOIN pg_namespace ns ON ns.oid = cls.relnamespace
+         JOIN pg_attribute att
+           ON att.attrelid = cls.oid AND att.attnum > 0 AND NOT att.attisdropped
+         WHERE ns.nspname = $1 AND cls.relkind = 'v' AND cls.relname = ANY($2)
+         ORDER BY cls.relname, att.attnum",
+    )
+    .bind(REPORTING_SCHEMA)
+    .bind(REPORTING_VIEWS)
+    .fetch_all(state.pool.as_ref())
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error reading reporting views");
+        AppError::InternalServerError
+    })?;
+
+    Ok(Json(ReportingContract {
+        version: REPORTING_CONTRACT_VERSION,
+        schema: REPORTING_SCHEMA.to_string(),
+        role: REPORTING_ROLE.to_string(),
+        views: contract_views(rows),
+    }))
+}
+
+pub fn build_reporting_router(state: ReportingState) -> Router {
+    ServiceRouter::new("api-gateway/reporting")
+        .route("/api/v1/admin/reporting-views", get(get_reporting_views))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_rows_are_grouped_in_contract_order() {
+        let row = |view: &str, column: &str, data_type: &str| ViewColumnRow {
+            view_name: view.to_string(),
+            view_description: Some(format!("About {}", view)),
+            column_name: column.to_string(),
+            data_type: data_type.to_string(),
+            column_description: None,
+        };
+        let views = contract_views(vec![
+            row("vw_internal", "secret", "text"),
+            row("vw_readiness_scores", "story_id", "uuid"),
+            row("vw_readiness_scores", "score", "integer"),
+            row("vw_stories", "story_id", "uuid"),
+            row("vw_stories", "labels", "text[]"),
+        ]);
+
+        let summary: Vec<(&str, Vec<&str>)> = views
+            .iter()
+            .map(|view| {
+                (
+                    view.qualified_name.as_str(),
+                    view.columns
+                        .iter()
+                        .map(|column| column.name.as_str())
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("reporting.vw_stories", vec!["story_id", "labels"]),
+                ("reporting.vw_readiness_scores", vec!["story_id", "score"]),
+            ]
+        );
+        assert_eq!(views[0].description.as_deref(), Some("About vw_stories"));
+        assert_eq!(views[0].columns[1].data_type, "text[]");
+    }
+}
@@ -0,0 +1,227 @@
+//! Every route the gateway serves, with the service that owns it.
+//!
+//! Services are mounted here instead of merged straight into the app, so each
+//! one is namespaced under its prefix and two services claiming the same path
+//! stop the gateway at startup rather than shadowing each other.
+
+use axum::{extract::State, http::HeaderMap, routing::get, Json, Router};
+use common::route_registry::service_paths;
+use common::AppError;
+use serde::Serialize;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::maintenance::MaintenanceState;
+
+const ROUTES_PATH: &str = "/api/v1/admin/routes";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MountedRoute {
+    pub service: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteRegistryError {
+    /// The service's router was not built with `ServiceRouter`, so its paths are unknown
+    Unregistered { service: String },
+    /// Both paths match the same requests
+    Conflict {
+        path: String,
+        service: String,
+        existing_path: String,
+        existing_service: String,
+    },
+}
+
+impl fmt::Display for RouteRegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unregistered { service } => {
+                write!(f, "Service '{}' registered no routes", service)
+            }
+            Self::Conflict {
+                path,
+                service,
+                existing_path,
+                existing_service,
+            } => write!(
+                f,
+                "Route {} of '{}' conflicts with {} of '{}'",
+                path, service, existing_path, existing_service
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RouteRegistryError {}
+
+#[derive(Default)]
+pub struct RouteRegistry {
+    router: Router,
+    routes: Vec<MountedRoute>,
+    errors: Vec<RouteRegistryError>,
+}
+
+impl RouteRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mount a service's router under `prefix`, or at the root when its paths
+    /// are already absolute. A service with a conflicting path is left out and
+    /// the conflict reported by `into_router`.
+    pub fn mount(mut self, service: &str, prefix: &str, router: Router) -> Self {
+        let prefix = prefix.trim_end_matches('/');
+        let paths: Vec<String> = service_paths(service)
+            .into_iter()
+            .map(|path| match path.as_str() {
+                "/" if !prefix.is_empty() => prefix.to_string(),
+                _ => format!("{}{}", prefix, path),
+            })
+            .collect();
+        if paths.is_empty() {
+            self.errors.push(RouteRegistryError::Unregistered {
+                service: service.to_string(),
+            });
+            return self;
+        }
+        let conflicts: Vec<RouteRegistryError> = paths
+            .iter()
+            .filter_map(|path| self.conflict(service, path))
+            .collect();
+        if !conflicts.is_empty() {
+            self.errors.extend(conflicts);
+            return self;
+        }
+
+        self.routes
+            .extend(paths.into_iter().map(|path| MountedRoute {
+                service: service.to_string(),
+                path,
+            }));
+        self.router = if prefix.is_empty() {
+            self.router.merge(router)
+        } else {
+            self.router.nest(prefix, router)
+        };
+        self
+    }
+
+    fn conflict(&self, service: &str, path: &str) -> Option<RouteRegistryError> {
+        let shape = route_shape(path);
+        self.routes
+            .iter()
+            .find(|route| route_shape(&route.path) == shape)
+            .map(|existing| RouteRegistryError::Conflict {
+                path: path.to_string(),
+                service: service.to_string(),
+                existing_path: existing.path.clone(),
+                existing_service: existing.service.clone(),
+            })
+    }
+
+    pub fn routes(&self) -> &[MountedRoute] {
+        &self.routes
+    }
+
+    /// The app router, including `GET /api/v1/admin/routes` listing every
+    /// route, or the first problem found while mounting
+    pub fn into_router(mut self, admin: MaintenanceState) -> Result<Router, RouteRegistryError> {
+        if let Some(error) = self.conflict("api-gateway/routes", ROUTES_PATH) {
+            self.errors.push(error);
+        }
+        for error in &self.errors {
+            tracing::error!(%error, "Failed to mount service routes");
+        }
+        if let Some(error) = self.errors.into_iter().next() {
+            return Err(error);
+        }
+
+        self.routes.push(MountedRoute {
+            service: "api-gateway/routes".to_string(),
+            path: ROUTES_PATH.to_string(),
+        });
+        self.routes
+            .sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.service.cmp(&b.service)));
+
+        let listing = Router::new()
+            .route(ROUTES_PATH, get(list_routes))
+            .with_state(RoutesState {
+                admin,
+                routes: Arc::new(self.routes),
+            });
+        Ok(self.router.merge(listing))
+    }
+}
+
+/// The path with parameter names dropped, since `/a/{id}` and `/a/{key}` match
+/// the same requests
+fn route_shape(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            if segment.starts_with("{*") {
+                "{*}"
+            } else if segment.starts_with('{') {
+                "{}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[derive(Clone)]
+struct RoutesState {
+    admin: MaintenanceState,
+    routes: Arc<Vec<MountedRoute>>,
+}
+
+/// GET /api/v1/admin/routes
+async fn list_routes(
+    State(state): State<RoutesState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<MountedRoute>>, AppError> {
+    state.admin.require_admin(&headers)?;
+    Ok(Json(state.routes.as_ref().clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::route_registry::ServiceRouter;
+
+    #[test]
+    fn test_rejects_paths_two_services_claim() {
+        let things: Router = ServiceRouter::new("route-registry-things")
+            .route("/api/v1/things/{id}", get(|| async {}))
+            .with_state(());
+        let nested: Router = ServiceRouter::new("route-registry-nested")
+            .route("/things/{thing_id}", get(|| async {}))
+            .route("/others", get(|| async {}))
+            .with_state(());
+
+        let registry = RouteRegistry::new()
+            .mount("route-registry-things", "", things)
+            .mount("route-registry-nested", "/api/v1/", nested)
+            .mount("route-registry-missing", "", Router::new());
+
+        assert_eq!(registry.routes().len(), 1);
+        assert_eq!(
+            registry.errors,
+            vec![
+                RouteRegistryError::Conflict {
+                    path: "/api/v1/things/{thing_id}".to_string(),
+                    service: "route-registry-nested".to_string(),
+                    existing_path: "/api/v1/things/{id}".to_string(),
+                    existing_service: "route-registry-things".to_string(),
+                },
+                RouteRegistryError::Unregistered {
+                    service: "route-registry-missing".to_string(),
+                },
+            ]
+        );
+    }
+}
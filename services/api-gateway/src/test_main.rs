@@ -34,11 +34,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let event_bus = Arc::new(EventBus::new());
     let event_publisher: Arc<dyn EventPublisher> = event_bus.clone();
-    let backlog_usecases = backlog::build_usecases(pool.clone(), event_publisher);
-    let readiness_llm: Arc<dyn readiness::application::ports::LlmService> =
-        Arc::new(readiness::adapters::integrations::MockLlmService);
-    let readiness_usecases =
-        readiness::build_usecases(pool.clone(), event_bus.clone(), readiness_llm).await;
+    let backlog_usecases = backlog_api::build_usecases(pool.clone(), event_publisher);
+    let readiness_llm: Arc<dyn readiness_api::LlmService> = Arc::new(readiness_api::MockLlmService);
+    let readiness_usecases = readiness_api::build_usecases(
+        pool.clone(),
+        event_bus.clone(),
+        readiness_llm,
+        Arc::new(common::quota::UnlimitedQuotaGuard),
+    )
+    .await;
 
     let prompt_backlog_service = Arc::new(api_gateway::PromptBacklogServiceAdapter {
         backlog: backlog_usecases.clone(),
@@ -46,33 +50,38 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let prompt_readiness_service = Arc::new(api_gateway::PromptReadinessServiceAdapter {
         readiness: readiness_usecases.clone(),
     });
-    let prompt_llm: Arc<dyn prompt_builder::application::ports::LlmService> =
-        Arc::new(prompt_builder::adapters::integrations::MockLlmService);
-    let prompt_builder_usecases = prompt_builder::build_usecases(
+    let prompt_llm: Arc<dyn prompt_builder_api::ports::LlmService> =
+        Arc::new(prompt_builder_api::MockLlmService);
+    let prompt_builder_usecases = prompt_builder_api::build_usecases(
         pool.clone(),
         event_bus.clone(),
         prompt_backlog_service,
         prompt_readiness_service,
         prompt_llm,
+        Arc::new(common::quota::UnlimitedQuotaGuard),
     );
 
     // Create service routers with shared resources
-    let auth_router = auth_gateway::create_auth_router(pool.clone(), verifier.clone()).await;
-    let projects_router = projects::create_projects_router(pool.clone(), verifier.clone()).await;
-    let context_orchestrator_router = context_orchestrator::create_context_orchestrator_router(
+    let auth_router = auth_gateway_api::create_auth_router(pool.clone(), verifier.clone()).await;
+    let projects_router = projects_api::create_projects_router(
+        pool.clone(),
+        verifier.clone(),
+        Arc::new(common::quota::UnlimitedQuotaGuard),
+    )
+    .await;
+    let context_orchestrator_router = context_orchestrator_api::create_context_orchestrator_router(
         pool.clone(),
         verifier.clone(),
         event_bus.clone(),
     )
     .await;
 
-    let backlog_router = api_gateway::build_backlog_router(backlog_usecases, pool.clone(), verifier.clone());
+    let backlog_router =
+        api_gateway::build_backlog_router(backlog_usecases, pool.clone(), verifier.clone());
     let readiness_router =
         api_gateway::build_readiness_router(pool.clone(), readiness_usecases, verifier.clone());
-    let prompt_builder_router = api_gateway::build_prompt_builder_router(
-        prompt_builder_usecases,
-        verifier.clone(),
-    );
+    let prompt_builder_router =
+        api_gateway::build_prompt_builder_router(prompt_builder_usecases, verifier.clone());
 
     // Create unified router with path-based routing
     let app = Router::new()
@@ -0,0 +1,319 @@
+//! Plan tiers and usage quotas: each organization's plan (configured by
+//! maintainers holding the admin token), the Postgres-backed [`QuotaGuard`]
+//! the service usecases check, and a usage summary for the organization.
+
+use crate::maintenance::MaintenanceState;
+use crate::sql_error;
+use async_trait::async_trait;
+use auth_clerk::{AuthenticatedWithOrg, JwtVerifier};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    routing::get,
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use common::quota::{
+    billing_period_end, billing_period_start, quota_exceeded, OrganizationPlan, PlanLimits,
+    PlanTier, QuotaGuard, QuotaResource, QuotaUsage,
+};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow)]
+struct OrganizationPlanRow {
+    tier: String,
+    max_projects: Option<i64>,
+    max_stories: Option<i64>,
+    max_llm_calls_per_month: Option<i64>,
+}
+
+impl OrganizationPlanRow {
+    fn overrides(&self) -> PlanLimits {
+        let limit = |value: Option<i64>| value.map(|value| value.max(0) as u64);
+        PlanLimits {
+            max_projects: limit(self.max_projects),
+            max_stories: limit(self.max_stories),
+            max_llm_calls_per_month: limit(self.max_llm_calls_per_month),
+        }
+    }
+}
+
+/// The organization's limit overrides; organizations without a configured plan
+/// are on the default tier
+async fn load_plan_row(
+    pool: &PgPool,
+    organization_id: Uuid,
+) -> Result<Option<OrganizationPlanRow>, AppError> {
+    sqlx::query_as::<_, OrganizationPlanRow>(
+        "SELECT tier, max_projects, max_stories, max_llm_calls_per_month
+         FROM organization_plans WHERE organization_id = $1",
+    )
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(sql_error("loading organization plan"))
+}
+
+async fn load_plan(pool: &PgPool, organization_id: Uuid) -> Result<OrganizationPlan, AppError> {
+    Ok(match load_plan_row(pool, organization_id).await? {
+        Some(row) => OrganizationPlan::new(
+            PlanTier::parse(&row.tier).unwrap_or_default(),
+            row.overrides(),
+        ),
+        None => OrganizationPlan::default(),
+    })
+}
+
+async fn current_usage(
+    pool: &PgPool,
+    organization_id: Uuid,
+    resource: QuotaResource,
+) -> Result<u64, AppError> {
+    let query = match resource {
+        QuotaResource::Projects => {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM projects WHERE organization_id = $1")
+                .bind(organization_id)
+        }
+        QuotaResource::Stories => sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM stories WHERE organization_id = $1 AND deleted_at IS NULL",
+        )
+        .bind(organization_id),
+        QuotaResource::LlmCalls => sqlx::query_scalar::<_, i64>(
+            "SELECT COALESCE(SUM(calls), 0)::BIGINT FROM organization_llm_usage
+             WHERE organization_id = $1 AND period_start = $2",
+        )
+        .bind(organization_id)
+        .bind(billing_period_start(Utc::now())),
+    };
+    let used = query
+        .fetch_one(pool)
+        .await
+        .map_err(sql_error("counting quota usage"))?;
+    Ok(used.max(0) as u64)
+}
+
+/// Checks usage against plans stored in `organization_plans`
+#[derive(Clone)]
+pub struct PgQuotaGuard {
+    pool: Arc<PgPool>,
+}
+
+impl PgQuotaGuard {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl QuotaGuard for PgQuotaGuard {
+    async fn ensure_capacity(
+        &self,
+        organization_id: Option<Uuid>,
+        resource: QuotaResource,
+        additional: u64,
+    ) -> Result<(), AppError> {
+        let Some(organization_id) = organization_id else {
+            return Ok(());
+        };
+        let plan = load_plan(&self.pool, organization_id).await?;
+        if resource.limit(&plan.limits).is_none() {
+            return Ok(());
+        }
+        let used = current_usage(&self.pool, organization_id, resource).await?;
+        plan.ensure_within(resource, used, additional)
+    }
+
+    async fn consume_llm_call(&self, organization_id: Option<Uuid>) -> Result<(), AppError> {
+        let Some(organization_id) = organization_id else {
+            return Ok(());
+        };
+        let plan = load_plan(&self.pool, organization_id).await?;
+        let limit = plan.limits.max_llm_calls_per_month;
+        if limit == Some(0) {
+            return Err(quota_exceeded(plan.tier, QuotaResource::LlmCalls, 0));
+        }
+
+        // Check and count in one statement so concurrent calls cannot overshoot
+        let counted = sqlx::query_scalar::<_, i64>(
+            "INSERT INTO organization_llm_usage (organization_id, period_start, calls)
+             VALUES ($1, $2, 1)
+             ON CONFLICT (organization_id, period_start) DO UPDATE
+             SET calls = organization_llm_usage.calls + 1
+             WHERE $3::BIGINT IS NULL OR organization_llm_usage.calls < $3
+             RETURNING calls",
+        )
+        .bind(organization_id)
+        .bind(billing_period_start(Utc::now()))
+        .bind(limit.map(|limit| limit as i64))
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(sql_error("counting LLM call"))?;
+
+        match (counted, limit) {
+            (None, Some(limit)) => Err(quota_exceeded(plan.tier, QuotaResource::LlmCalls, limit)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSummary {
+    pub organization_id: Uuid,
+    pub plan: PlanTier,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub quotas: Vec<QuotaUsage>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationPlanResponse {
+    pub organization_id: Uuid,
+    pub tier: PlanTier,
+    /// Limits set for this organization in place of the tier's
+    pub overrides: PlanLimits,
+    /// The limits that apply
+    pub limits: PlanLimits,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateOrganizationPlanRequest {
+    pub tier: PlanTier,
+    #[serde(default)]
+    pub overrides: PlanLimits,
+}
+
+#[derive(Clone)]
+pub struct UsageState {
+    pool: Arc<PgPool>,
+    admin: MaintenanceState,
+}
+
+impl UsageState {
+    /// Plan changes are checked against the same token as the maintenance switch
+    pub fn new(pool: Arc<PgPool>, admin: MaintenanceState) -> Self {
+        Self { pool, admin }
+    }
+}
+
+/// GET /api/v1/usage
+async fn get_usage(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    State(state): State<UsageState>,
+) -> Result<Json<UsageSummary>, AppError> {
+    let organization_id = org_context
+        .effective_organization_uuid()
+        .ok_or_else(|| AppError::BadRequest("Usage is tracked per organization".to_string()))?;
+
+    let plan = load_plan(&state.pool, organization_id).await?;
+    let (projects, stories, llm_calls) = tokio::try_join!(
+        current_usage(&state.pool, organization_id, QuotaResource::Projects),
+        current_usage(&state.pool, organization_id, QuotaResource::Stories),
+        current_usage(&state.pool, organization_id, QuotaResource::LlmCalls),
+    )?;
+    let now = Utc::now();
+
+    Ok(Json(UsageSummary {
+        organization_id,
+        plan: plan.tier,
+        period_start: billing_period_start(now),
+        period_end: billing_period_end(now),
+        quotas: vec![
+            QuotaUsage::new(QuotaResource::Projects, projects, &plan.limits),
+            QuotaUsage::new(QuotaResource::Stories, stories, &plan.limits),
+            QuotaUsage::new(QuotaResource::LlmCalls, llm_calls, &plan.limits),
+        ],
+    }))
+}
+
+fn plan_response(
+    organization_id: Uuid,
+    row: Option<OrganizationPlanRow>,
+) -> OrganizationPlanResponse {
+    let (tier, overrides) = match row {
+        Some(row) => (
+            PlanTier::parse(&row.tier).unwrap_or_default(),
+            row.overrides(),
+        ),
+        None => (PlanTier::default(), PlanLimits::default()),
+    };
+    OrganizationPlanResponse {
+        organization_id,
+        tier,
+        overrides,
+        limits: OrganizationPlan::new(tier, overrides).limits,
+    }
+}
+
+/// GET /api/v1/admin/plans/organizations/{org_id}
+async fn get_organization_plan(
+    State(state): State<UsageState>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<OrganizationPlanResponse>, AppError> {
+    state.admin.require_admin(&headers)?;
+    let row = load_plan_row(&state.pool, org_id).await?;
+    Ok(Json(plan_response(org_id, row)))
+}
+
+/// PUT /api/v1/admin/plans/organizations/{org_id}
+///
+/// Lowering a limit below current usage does not delete anything; it only
+/// blocks further growth.
+async fn update_organization_plan(
+    State(state): State<UsageState>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Json(request): Json<UpdateOrganizationPlanRequest>,
+) -> Result<Json<OrganizationPlanResponse>, AppError> {
+    state.admin.require_admin(&headers)?;
+    let limit = |value: Option<u64>| value.map(|value| value.min(i64::MAX as u64) as i64);
+
+    let row = sqlx::query_as::<_, OrganizationPlanRow>(
+        "INSERT INTO organization_plans
+             (organization_id, tier, max_projects, max_stories, max_llm_calls_per_month, updated_at)
+         SELECT id, $2, $3, $4, $5, NOW() FROM organizations WHERE id = $1
+         ON CONFLICT (organization_id) DO UPDATE SET
+             tier = EXCLUDED.tier,
+             max_projects = EXCLUDED.max_projects,
+             max_stories = EXCLUDED.max_stories,
+             max_llm_calls_per_month = EXCLUDED.max_llm_calls_per_month,
+             updated_at = NOW()
+         RETURNING tier, max_projects, max_stories, max_llm_calls_per_month",
+    )
+    .bind(org_id)
+    .bind(request.tier.as_str())
+    .bind(limit(request.overrides.max_projects))
+    .bind(limit(request.overrides.max_stories))
+    .bind(limit(request.overrides.max_llm_calls_per_month))
+    .fetch_optional(&*state.pool)
+    .await
+    .map_err(sql_error("updating organization plan"))?
+    .ok_or_else(|| AppError::NotFound(format!("Organization {} not found", org_id)))?;
+
+    tracing::warn!(
+        organization_id = %org_id,
+        tier = request.tier.as_str(),
+        "Organization plan updated"
+    );
+    Ok(Json(plan_response(org_id, Some(row))))
+}
+
+pub fn build_usage_router(state: UsageState, verifier: Arc<Mutex<JwtVerifier>>) -> Router {
+    ServiceRouter::new("api-gateway/usage")
+        .route("/api/v1/usage", get(get_usage))
+        .route(
+            "/api/v1/admin/plans/organizations/{org_id}",
+            get(get_organization_plan).put(update_organization_plan),
+        )
+        .with_state(state)
+        .layer(Extension(verifier))
+}
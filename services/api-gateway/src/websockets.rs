@@ -0,0 +1,55 @@
+//! Admin view of the open real-time connections, so an organization crowding
+//! out the others can be spotted before it reaches its connection limit.
+
+use crate::maintenance::MaintenanceState;
+use axum::{extract::State, http::HeaderMap, routing::get, Json, Router};
+use backlog_api::{ConnectionLimits, OrganizationConnections, WebSocketManager};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebSocketConnectionReport {
+    pub limits: ConnectionLimits,
+    pub active_connections: usize,
+    /// Connections refused since startup because a limit was reached
+    pub rejected_connections: u64,
+    pub organizations: Vec<OrganizationConnections>,
+}
+
+#[derive(Clone)]
+pub struct WebSocketAdminState {
+    manager: Arc<WebSocketManager>,
+    admin: MaintenanceState,
+}
+
+impl WebSocketAdminState {
+    pub fn new(manager: Arc<WebSocketManager>, admin: MaintenanceState) -> Self {
+        Self { manager, admin }
+    }
+}
+
+/// GET /api/v1/admin/websockets
+/// Connections open on this instance, by organization and user
+async fn get_websocket_connections(
+    State(state): State<WebSocketAdminState>,
+    headers: HeaderMap,
+) -> Result<Json<WebSocketConnectionReport>, AppError> {
+    state.admin.require_admin(&headers)?;
+
+    let metrics = state.manager.metrics_snapshot();
+    Ok(Json(WebSocketConnectionReport {
+        limits: state.manager.connection_limits(),
+        active_connections: metrics.active_connections,
+        rejected_connections: metrics.total_rejected_connections,
+        organizations: state.manager.connections_by_organization(),
+    }))
+}
+
+pub fn build_websocket_admin_router(state: WebSocketAdminState) -> Router {
+    ServiceRouter::new("api-gateway/websockets")
+        .route("/api/v1/admin/websockets", get(get_websocket_connections))
+        .with_state(state)
+}
@@ -0,0 +1,441 @@
+//! "My tasks" across projects: the tasks the signed-in user owns and the
+//! available tasks recommended to them from active sprints, for the current
+//! organization or, with `?orgs=all`, every organization they belong to and
+//! their personal workspace. Each task carries its project and sprint, and
+//! tasks are listed soonest deadline first.
+
+use crate::overview::resolve_user_id;
+use crate::BacklogUsecases;
+use auth_clerk::{AuthenticatedWithOrg, JwtVerifier};
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Extension, Json, Router,
+};
+use backlog_api::{RecommendationFilters, Story, Task, TaskResponse};
+use chrono::{DateTime, Utc};
+use common::route_registry::ServiceRouter;
+use common::AppError;
+use futures::future::try_join_all;
+use projects_api::ProjectUsecases;
+use serde::{Deserialize, Serialize};
+use sprint_api::SprintsUsecases;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Projects per organization whose active sprints are searched for recommendations
+const MAX_PROJECTS_PER_ORGANIZATION: i64 = 50;
+const RECOMMENDED_PER_SPRINT: usize = 5;
+const MAX_RECOMMENDED: usize = 25;
+
+#[derive(Clone)]
+pub struct WorkloadState {
+    pool: Arc<PgPool>,
+    backlog: Arc<BacklogUsecases>,
+    sprints: Arc<SprintsUsecases>,
+    projects: Arc<ProjectUsecases>,
+}
+
+impl WorkloadState {
+    pub fn new(
+        pool: Arc<PgPool>,
+        backlog: Arc<BacklogUsecases>,
+        sprints: Arc<SprintsUsecases>,
+        projects: Arc<ProjectUsecases>,
+    ) -> Self {
+        Self {
+            pool,
+            backlog,
+            sprints,
+            projects,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkloadQuery {
+    /// `all` for every organization the user belongs to; the current
+    /// organization when absent
+    pub orgs: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectAnnotation {
+    pub id: Uuid,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintAnnotation {
+    pub id: Uuid,
+    pub name: String,
+    pub status: String,
+    pub end_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadTask {
+    #[serde(flatten)]
+    pub task: TaskResponse,
+    pub organization_id: Option<Uuid>,
+    pub story_title: Option<String>,
+    pub project: Option<ProjectAnnotation>,
+    pub sprint: Option<SprintAnnotation>,
+    /// When the task's sprint ends; tasks have no deadline of their own
+    pub deadline: Option<DateTime<Utc>>,
+    /// Why the task is recommended; absent for owned tasks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recommendation_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyWorkload {
+    /// The scopes searched; `null` is the personal workspace
+    pub organization_ids: Vec<Option<Uuid>>,
+    pub owned: Vec<WorkloadTask>,
+    pub recommended: Vec<WorkloadTask>,
+    pub generated_at: DateTime<Utc>,
+}
+
+struct ScopeWorkload {
+    owned: Vec<WorkloadTask>,
+    recommended: Vec<WorkloadTask>,
+}
+
+pub fn build_workload_router(state: WorkloadState, verifier: Arc<Mutex<JwtVerifier>>) -> Router {
+    ServiceRouter::new("api-gateway/workload")
+        .route("/api/v1/me/tasks", get(get_my_tasks))
+        .with_state(state)
+        .layer(Extension(verifier))
+}
+
+/// GET /api/v1/me/tasks?orgs=all
+async fn get_my_tasks(
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
+    State(state): State<WorkloadState>,
+    Query(query): Query<WorkloadQuery>,
+) -> Result<Json<MyWorkload>, AppError> {
+    let current = org_context.effective_organization_uuid();
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    let organization_ids = match query.orgs.as_deref() {
+        None => vec![current],
+        Some("all") => accessible_organizations(&state.pool, user_id, current).await?,
+        Some(other) => {
+            return Err(AppError::BadRequest(format!(
+                "Unsupported orgs value '{}'; use 'all' or leave it out",
+                other
+            )))
+        }
+    };
+
+    let scopes = try_join_all(
+        organization_ids
+            .iter()
+            .map(|organization_id| scope_workload(&state, user_id, *organization_id)),
+    )
+    .await?;
+
+    let (mut owned, mut recommended) = (Vec::new(), Vec::new());
+    for scope in scopes {
+        owned.extend(scope.owned);
+        recommended.extend(scope.recommended);
+    }
+    sort_by_deadline(&mut owned);
+    sort_by_deadline(&mut recommended);
+    recommended.truncate(MAX_RECOMMENDED);
+
+    Ok(Json(MyWorkload {
+        organization_ids,
+        owned,
+        recommended,
+        generated_at: Utc::now(),
+    }))
+}
+
+/// The current scope first, then the user's organizations and personal workspace
+async fn accessible_organizations(
+    pool: &PgPool,
+    user_id: Uuid,
+    current: Option<Uuid>,
+) -> Result<Vec<Option<Uuid>>, AppError> {
+    let memberships = sqlx::query_scalar::<_, Uuid>(
+        "SELECT organization_id FROM organization_memberships
+         WHERE user_id = $1
+         ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching organization memberships");
+        AppError::InternalServerError
+    })?;
+
+    let mut scopes = vec![current];
+    for scope in memberships.into_iter().map(Some).chain([None]) {
+        if !scopes.contains(&scope) {
+            scopes.push(scope);
+        }
+    }
+    Ok(scopes)
+}
+
+async fn scope_workload(
+    state: &WorkloadState,
+    user_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<ScopeWorkload, AppError> {
+    // Personal projects are not scoped to their owner, so the personal
+    // workspace only lists owned tasks
+    let projects = async {
+        match organization_id {
+            Some(_) => {
+                state
+                    .projects
+                    .list_projects(organization_id, Some(MAX_PROJECTS_PER_ORGANIZATION), None)
+                    .await
+            }
+            None => Ok(Vec::new()),
+        }
+    };
+    let (owned_tasks, projects) = tokio::try_join!(
+        state.backlog.get_user_owned_tasks(user_id, organization_id),
+        projects,
+    )?;
+
+    let active_sprints = try_join_all(
+        projects
+            .iter()
+            .map(|project| state.sprints.get_active_sprint(project.id)),
+    )
+    .await?;
+    let recommendations = try_join_all(active_sprints.iter().flatten().map(|sprint| {
+        state.backlog.get_recommended_tasks(
+            RecommendationFilters {
+                sprint_id: Some(sprint.id),
+                exclude_user_id: Some(user_id),
+                limit: Some(RECOMMENDED_PER_SPRINT),
+                ..Default::default()
+            },
+            organization_id,
+        )
+    }))
+    .await?;
+
+    let mut annotations = Annotations {
+        organization_id,
+        projects: projects
+            .into_iter()
+            .map(|project| (project.id, project.name))
+            .collect(),
+        sprints: active_sprints
+            .into_iter()
+            .flatten()
+            .map(|sprint| {
+                (
+                    sprint.id,
+                    SprintAnnotation {
+                        id: sprint.id,
+                        name: sprint.name,
+                        status: sprint.status,
+                        end_date: sprint.end_date,
+                    },
+                )
+            })
+            .collect(),
+        stories: HashMap::new(),
+    };
+    let recommended: Vec<(Task, String)> = recommendations
+        .into_iter()
+        .flatten()
+        .map(|recommendation| (recommendation.task, recommendation.reason))
+        .collect();
+    let story_ids: HashSet<Uuid> = owned_tasks
+        .iter()
+        .chain(recommended.iter().map(|(task, _)| task))
+        .map(|task| task.story_id)
+        .collect();
+    annotations.load(state, story_ids).await?;
+
+    Ok(ScopeWorkload {
+        owned: owned_tasks
+            .into_iter()
+            .map(|task| annotations.annotate(task, None))
+            .collect(),
+        recommended: recommended
+            .into_iter()
+            .map(|(task, reason)| annotations.annotate(task, Some(reason)))
+            .collect(),
+    })
+}
+
+/// The stories, projects and sprints of one scope's tasks
+struct Annotations {
+    organization_id: Option<Uuid>,
+    stories: HashMap<Uuid, Story>,
+    projects: HashMap<Uuid, String>,
+    sprints: HashMap<Uuid, SprintAnnotation>,
+}
+
+impl Annotations {
+    /// Fetch the tasks' stories, and the projects and sprints not already known
+    async fn load(
+        &mut self,
+        state: &WorkloadState,
+        story_ids: HashSet<Uuid>,
+    ) -> Result<(), AppError> {
+        let stories = try_join_all(
+            story_ids
+                .into_iter()
+                .map(|story_id| state.backlog.get_story(story_id, self.organization_id)),
+        )
+        .await?;
+        self.stories = stories
+            .into_iter()
+            .flatten()
+            .map(|story| (story.id, story))
+            .collect();
+
+        let missing_projects: HashSet<Uuid> = self
+            .stories
+            .values()
+            .map(|story| story.project_id)
+            .filter(|project_id| !self.projects.contains_key(project_id))
+            .collect();
+        let missing_sprints: HashSet<Uuid> = self
+            .stories
+            .values()
+            .filter_map(|story| story.sprint_id)
+            .filter(|sprint_id| !self.sprints.contains_key(sprint_id))
+            .collect();
+        let (projects, sprints) = tokio::try_join!(
+            try_join_all(missing_projects.iter().map(|project_id| {
+                state.projects.get_project(project_id, self.organization_id)
+            }),),
+            try_join_all(
+                missing_sprints
+                    .iter()
+                    .map(|sprint_id| state.sprints.get_sprint(*sprint_id)),
+            ),
+        )?;
+
+        self.projects.extend(
+            projects
+                .into_iter()
+                .flatten()
+                .map(|project| (project.id, project.name)),
+        );
+        self.sprints
+            .extend(sprints.into_iter().flatten().map(|sprint| {
+                (
+                    sprint.id,
+                    SprintAnnotation {
+                        id: sprint.id,
+                        name: sprint.name,
+                        status: sprint.status,
+                        end_date: sprint.end_date,
+                    },
+                )
+            }));
+        Ok(())
+    }
+
+    fn annotate(&self, task: Task, recommendation_reason: Option<String>) -> WorkloadTask {
+        let story = self.stories.get(&task.story_id);
+        let sprint = story
+            .and_then(|story| story.sprint_id)
+            .and_then(|sprint_id| self.sprints.get(&sprint_id))
+            .cloned();
+        WorkloadTask {
+            organization_id: self.organization_id,
+            story_title: story.map(|story| story.title.clone()),
+            project: story.map(|story| ProjectAnnotation {
+                id: story.project_id,
+                name: self.projects.get(&story.project_id).cloned(),
+            }),
+            deadline: sprint.as_ref().map(|sprint| sprint.end_date),
+            sprint,
+            recommendation_reason,
+            task: TaskResponse::from(task),
+        }
+    }
+}
+
+/// Soonest deadline first and tasks without one last, keeping the incoming
+/// order (most recently updated, or best recommended) among equals
+fn sort_by_deadline(tasks: &mut [WorkloadTask]) {
+    tasks.sort_by_key(|task| (task.deadline.is_none(), task.deadline));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_tasks_are_annotated_and_sorted_by_sprint_end() {
+        let project_id = Uuid::new_v4();
+        let now = Utc::now();
+        let sprint = |name: &str, days: i64| SprintAnnotation {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            status: "active".to_string(),
+            end_date: now + Duration::days(days),
+        };
+        let (soon, later) = (sprint("Sprint 4", 2), sprint("Sprint 9", 9));
+
+        let mut stories = HashMap::new();
+        let mut story_in = |sprint_id: Option<Uuid>| {
+            let mut story = Story::new(project_id, None, "Checkout".to_string(), None).unwrap();
+            story.sprint_id = sprint_id;
+            let id = story.id;
+            stories.insert(id, story);
+            id
+        };
+        let (later_story, backlog_story, soon_story) = (
+            story_in(Some(later.id)),
+            story_in(None),
+            story_in(Some(soon.id)),
+        );
+
+        let annotations = Annotations {
+            organization_id: None,
+            projects: HashMap::from([(project_id, "Storefront".to_string())]),
+            sprints: HashMap::from([(soon.id, soon.clone()), (later.id, later.clone())]),
+            stories,
+        };
+        let task = |story_id: Uuid| {
+            annotations.annotate(
+                Task::new(
+                    story_id,
+                    None,
+                    "Wire up".to_string(),
+                    None,
+                    vec!["AC1".to_string()],
+                )
+                .unwrap(),
+                None,
+            )
+        };
+        let mut tasks = vec![task(later_story), task(backlog_story), task(soon_story)];
+        sort_by_deadline(&mut tasks);
+
+        let order: Vec<Uuid> = tasks.iter().map(|task| task.task.story_id).collect();
+        assert_eq!(order, vec![soon_story, later_story, backlog_story]);
+        assert_eq!(tasks[0].deadline, Some(soon.end_date));
+        assert_eq!(tasks[0].sprint.as_ref().unwrap().name, "Sprint 4");
+        assert_eq!(
+            tasks[2].project.as_ref().unwrap().name.as_deref(),
+            Some("Storefront")
+        );
+        assert!(tasks[2].sprint.is_none());
+    }
+}
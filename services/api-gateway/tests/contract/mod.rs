@@ -1,2 +1,3 @@
+pub mod test_client_contract;
 pub mod test_task_analysis_contract;
 pub mod test_unified_gateway_compliance;
@@ -1 +1,4 @@
+pub mod test_deprovisioning;
+pub mod test_event_export;
 pub mod test_gateway;
+pub mod test_route_mounts;
@@ -0,0 +1,4 @@
+//! Public API of the auth gateway service. The router owns its usecases;
+//! nothing else in `auth_gateway` is meant to be used from outside it.
+
+pub use auth_gateway::create_auth_router;
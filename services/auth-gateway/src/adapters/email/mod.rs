@@ -0,0 +1,132 @@
+use crate::application::ports::InvitationSender;
+use crate::domain::invitation::Invitation;
+use crate::domain::organization::Organization;
+use async_trait::async_trait;
+use common::circuit_breaker::CircuitBreaker;
+use common::outbound_http::OutboundHttpClient;
+use common::AppError;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_EMAIL_API_URL: &str = "https://api.resend.com/emails";
+
+#[derive(Debug, Serialize)]
+struct SendEmailRequest<'a> {
+    from: &'a str,
+    to: Vec<&'a str>,
+    subject: String,
+    text: String,
+}
+
+/// Sends invitations through a Resend-compatible transactional email API
+pub struct HttpInvitationSender {
+    client: OutboundHttpClient,
+    api_url: String,
+    api_key: String,
+    from: String,
+}
+
+impl HttpInvitationSender {
+    pub fn new(api_key: String, from: String, api_url: Option<String>) -> Self {
+        Self {
+            client: OutboundHttpClient::builder()
+                .request_timeout(Duration::from_secs(10))
+                .build(),
+            api_url: api_url.unwrap_or_else(|| DEFAULT_EMAIL_API_URL.to_string()),
+            api_key,
+            from,
+        }
+    }
+
+    /// Build from `INVITATION_EMAIL_API_KEY`, `INVITATION_EMAIL_FROM` and optional
+    /// `INVITATION_EMAIL_API_URL`; `None` unless both the key and sender are set
+    pub fn from_env() -> Option<Self> {
+        let non_empty = |name| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        Some(Self::new(
+            non_empty("INVITATION_EMAIL_API_KEY")?,
+            non_empty("INVITATION_EMAIL_FROM")?,
+            non_empty("INVITATION_EMAIL_API_URL"),
+        ))
+    }
+}
+
+#[async_trait]
+impl InvitationSender for HttpInvitationSender {
+    async fn send_invitation(
+        &self,
+        invitation: &Invitation,
+        organization: &Organization,
+        accept_url: &str,
+    ) -> Result<(), AppError> {
+        let request = SendEmailRequest {
+            from: &self.from,
+            to: vec![&invitation.email],
+            subject: format!("You're invited to join {}", organization.name),
+            text: format!(
+                "You have been invited to join {} as {}.\n\n\
+                Accept the invitation here: {}\n\n\
+                The link expires on {}.",
+                organization.name,
+                invitation.role.as_str(),
+                accept_url,
+                invitation.expires_at.format("%Y-%m-%d %H:%M UTC")
+            ),
+        };
+
+        CircuitBreaker::for_dependency("invitation_email")
+            .call(async {
+                let response = self
+                    .client
+                    .send(
+                        "auth_gateway.invitation_email",
+                        self.client
+                            .post(&self.api_url)
+                            .header("Authorization", format!("Bearer {}", self.api_key))
+                            .json(&request),
+                    )
+                    .await
+                    .map_err(|e| {
+                        AppError::ExternalServiceError(format!("Invitation email failed: {}", e))
+                    })?;
+
+                if !response.status().is_success() {
+                    return Err(AppError::ExternalServiceError(format!(
+                        "Invitation email rejected with status {}",
+                        response.status()
+                    )));
+                }
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Used when no email provider is configured: invitations are only shared by link
+pub struct LoggingInvitationSender;
+
+#[async_trait]
+impl InvitationSender for LoggingInvitationSender {
+    async fn send_invitation(
+        &self,
+        invitation: &Invitation,
+        organization: &Organization,
+        _accept_url: &str,
+    ) -> Result<(), AppError> {
+        tracing::warn!(
+            invitation_id = %invitation.id,
+            organization_id = %organization.id,
+            "Invitation email not sent: no email provider configured"
+        );
+        Err(AppError::ExternalServiceError(
+            "No email provider is configured".to_string(),
+        ))
+    }
+}
+
+pub fn invitation_sender_from_env() -> Arc<dyn InvitationSender> {
+    match HttpInvitationSender::from_env() {
+        Some(sender) => Arc::new(sender),
+        None => Arc::new(LoggingInvitationSender),
+    }
+}
@@ -1,6 +1,10 @@
 use crate::application::usecases::{
-    OrganizationUsecases, SprintUsecases, TeamUsecases, UserUsecases,
+    AvailabilityUsecases, InvitationUsecases, OrganizationUsecases, SprintUsecases, TeamUsecases,
+    UserUsecases,
 };
+use crate::domain::availability::{AvailabilityEntry, AvailabilityKind, SprintCapacityPlan};
+use crate::domain::holiday::OrganizationHoliday;
+use crate::domain::invitation::Invitation;
 use crate::domain::organization::{AddMemberRequest, CreateOrganizationRequest, MembershipRole};
 use crate::domain::sprint::{CreateSprintRequest, Sprint};
 use crate::domain::team::{AddTeamMemberRequest, CreateTeamRequest, Team, TeamMembership};
@@ -12,6 +16,7 @@ use axum::{
     response::{IntoResponse, Response},
     Extension, Json,
 };
+use common::calendar::SprintCalendar;
 use common::AppError;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -45,22 +50,29 @@ pub struct EmailAddress {
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateOrganizationDto {
+    #[serde(alias = "external_id")]
     pub external_id: String,
     pub name: String,
     pub slug: String,
     pub description: Option<String>,
+    #[serde(alias = "image_url")]
     pub image_url: Option<String>,
+    #[serde(alias = "owner_user_id")]
     pub owner_user_id: Uuid,
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AddMemberDto {
+    #[serde(alias = "user_id")]
     pub user_id: Uuid,
     pub role: String,
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct OrganizationResponse {
     pub id: Uuid,
     pub external_id: String,
@@ -76,6 +88,7 @@ pub struct UserOrganizationsResponse {
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct OrganizationWithMembership {
     pub id: Uuid,
     pub external_id: String,
@@ -94,7 +107,9 @@ pub struct CreateTeamDto {
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AddTeamMemberDto {
+    #[serde(alias = "user_id")]
     pub user_id: Uuid,
     pub role: String,
     pub specialty: Option<crate::domain::user::ContributorSpecialty>,
@@ -108,6 +123,7 @@ pub struct UpdateTeamDto {
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TeamResponse {
     pub id: Uuid,
     pub name: String,
@@ -157,16 +173,16 @@ pub struct TeamMemberResponse {
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TeamMemberSummaryResponse {
     #[serde(flatten)]
     pub membership: TeamMembershipResponse,
-    #[serde(rename = "userEmail")]
     pub user_email: Option<String>,
-    #[serde(rename = "userName")]
     pub user_name: Option<String>,
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UserResponse {
     pub id: Uuid,
     pub external_id: String,
@@ -186,16 +202,14 @@ impl From<User> for UserResponse {
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CurrentUserResponse {
     pub id: Uuid,
-    #[serde(rename = "externalId")]
     pub external_id: String,
     pub email: String,
     pub role: String,
     pub specialty: Option<String>,
-    #[serde(rename = "createdAt")]
     pub created_at: chrono::DateTime<chrono::Utc>,
-    #[serde(rename = "updatedAt")]
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -253,6 +267,7 @@ fn parse_specialty(value: &str) -> Result<ContributorSpecialty, AppError> {
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TeamMembershipResponse {
     pub team_id: Uuid,
     pub user_id: Uuid,
@@ -275,15 +290,20 @@ impl From<TeamMembership> for TeamMembershipResponse {
 
 // Sprint DTOs
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateSprintDto {
     pub name: String,
     pub goal: Option<String>,
+    #[serde(alias = "capacity_points")]
     pub capacity_points: Option<u32>,
+    #[serde(alias = "start_date")]
     pub start_date: chrono::NaiveDate,
+    #[serde(alias = "end_date")]
     pub end_date: chrono::NaiveDate,
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SprintResponse {
     pub id: Uuid,
     pub team_id: Uuid,
@@ -298,8 +318,9 @@ pub struct SprintResponse {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-impl From<Sprint> for SprintResponse {
-    fn from(sprint: Sprint) -> Self {
+impl SprintResponse {
+    /// Sprint dates are reported as local dates in the team's time zone
+    fn new(sprint: Sprint, calendar: &SprintCalendar) -> Self {
         Self {
             id: sprint.id,
             team_id: sprint.team_id,
@@ -308,8 +329,8 @@ impl From<Sprint> for SprintResponse {
             status: sprint.status.to_string(),
             committed_story_points: sprint.committed_points,
             completed_story_points: sprint.completed_points,
-            start_date: sprint.start_date.date_naive(),
-            end_date: sprint.end_date.date_naive(),
+            start_date: calendar.local_date(sprint.start_date),
+            end_date: calendar.local_date(sprint.end_date),
             created_at: sprint.created_at,
             updated_at: sprint.updated_at,
         }
@@ -332,23 +353,195 @@ pub struct ActionResponse {
     pub message: String,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailabilityEntryDto {
+    pub kind: String,
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+    pub capacity_percent: Option<u8>,
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ReplaceAvailabilityDto {
+    pub entries: Vec<AvailabilityEntryDto>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailabilityEntryResponse {
+    pub id: Uuid,
+    pub kind: String,
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+    pub capacity_percent: u8,
+    pub note: Option<String>,
+}
+
+impl From<AvailabilityEntry> for AvailabilityEntryResponse {
+    fn from(entry: AvailabilityEntry) -> Self {
+        Self {
+            id: entry.id,
+            kind: entry.kind.as_str().to_string(),
+            start_date: entry.start_date,
+            end_date: entry.end_date,
+            capacity_percent: entry.capacity_percent,
+            note: entry.note,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAvailabilityResponse {
+    pub user_id: Uuid,
+    pub entries: Vec<AvailabilityEntryResponse>,
+}
+
+#[derive(Deserialize)]
+pub struct HolidayDto {
+    pub date: chrono::NaiveDate,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct ReplaceHolidaysDto {
+    pub holidays: Vec<HolidayDto>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayResponse {
+    pub id: Uuid,
+    pub date: chrono::NaiveDate,
+    pub name: String,
+}
+
+impl From<OrganizationHoliday> for HolidayResponse {
+    fn from(holiday: OrganizationHoliday) -> Self {
+        Self {
+            id: holiday.id,
+            date: holiday.date,
+            name: holiday.name,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationHolidaysResponse {
+    pub organization_id: Uuid,
+    pub holidays: Vec<HolidayResponse>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberCapacityResponse {
+    pub user_id: Uuid,
+    pub working_days: u32,
+    pub available_days: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintCapacityResponse {
+    pub sprint_id: Uuid,
+    pub capacity_points: u32,
+    pub available_capacity_points: u32,
+    pub committed_points: u32,
+    pub members: Vec<MemberCapacityResponse>,
+}
+
+impl From<SprintCapacityPlan> for SprintCapacityResponse {
+    fn from(plan: SprintCapacityPlan) -> Self {
+        Self {
+            sprint_id: plan.sprint_id,
+            capacity_points: plan.capacity_points,
+            available_capacity_points: plan.available_capacity_points,
+            committed_points: plan.committed_points,
+            members: plan
+                .members
+                .into_iter()
+                .map(|member| MemberCapacityResponse {
+                    user_id: member.user_id,
+                    working_days: member.working_days,
+                    available_days: member.available_days,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CreateInvitationDto {
+    pub email: String,
+    pub role: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvitationResponse {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub email: String,
+    pub role: String,
+    pub status: String,
+    pub invited_by: Option<Uuid>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub accepted_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<Invitation> for InvitationResponse {
+    fn from(invitation: Invitation) -> Self {
+        Self {
+            id: invitation.id,
+            organization_id: invitation.organization_id,
+            email: invitation.email,
+            role: invitation.role.as_str().to_string(),
+            status: invitation.status.as_str().to_string(),
+            invited_by: invitation.invited_by,
+            expires_at: invitation.expires_at,
+            accepted_at: invitation.accepted_at,
+            revoked_at: invitation.revoked_at,
+            created_at: invitation.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedInvitationResponse {
+    #[serde(flatten)]
+    pub invitation: InvitationResponse,
+    pub accept_url: String,
+    pub email_sent: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptInvitationResponse {
+    pub organization_id: Uuid,
+    pub membership_id: Uuid,
+    pub role: String,
+}
+
 #[derive(Deserialize)]
 pub struct SearchUsersQuery {
     pub q: Option<String>,
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SearchUserResponse {
     pub id: Uuid,
-    #[serde(rename = "externalId")]
     pub external_id: String,
     pub email: String,
     pub role: String,
-    #[serde(rename = "specialty")]
     pub specialty: Option<String>,
-    #[serde(rename = "createdAt")]
     pub created_at: chrono::DateTime<chrono::Utc>,
-    #[serde(rename = "updatedAt")]
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -942,19 +1135,24 @@ pub async fn create_sprint(
     Path(team_id): Path<Uuid>,
     Json(dto): Json<CreateSprintDto>,
 ) -> Result<impl IntoResponse, AppError> {
-    use chrono::{TimeZone, Utc};
+    // The sprint runs from local midnight on its first day to the end of its last
+    let calendar = sprint_usecases.team_calendar(&team_id).await?;
+    let (start_date, end_date) = calendar.sprint_bounds(dto.start_date, dto.end_date);
 
     let request = CreateSprintRequest {
         team_id,
         name: dto.name,
         goal: dto.goal.unwrap_or_default(),
         capacity_points: dto.capacity_points.unwrap_or(40),
-        start_date: Utc.from_utc_datetime(&dto.start_date.and_hms_opt(0, 0, 0).unwrap()),
-        end_date: Utc.from_utc_datetime(&dto.end_date.and_hms_opt(23, 59, 59).unwrap()),
+        start_date,
+        end_date,
     };
 
     let sprint = sprint_usecases.create_sprint(&request).await?;
-    Ok((StatusCode::CREATED, Json(SprintResponse::from(sprint))))
+    Ok((
+        StatusCode::CREATED,
+        Json(SprintResponse::new(sprint, &calendar)),
+    ))
 }
 
 pub async fn get_sprint(
@@ -965,7 +1163,8 @@ pub async fn get_sprint(
         .get_sprint(&sprint_id)
         .await?
         .ok_or(AppError::NotFound("Sprint not found".to_string()))?;
-    Ok(Json(SprintResponse::from(sprint)))
+    let calendar = sprint_usecases.team_calendar(&sprint.team_id).await?;
+    Ok(Json(SprintResponse::new(sprint, &calendar)))
 }
 
 pub async fn get_sprints_by_team(
@@ -973,8 +1172,11 @@ pub async fn get_sprints_by_team(
     Path(team_id): Path<Uuid>,
 ) -> Result<Json<Vec<SprintResponse>>, AppError> {
     let sprints = sprint_usecases.get_sprints_by_team(&team_id).await?;
-    let sprint_responses: Vec<SprintResponse> =
-        sprints.into_iter().map(SprintResponse::from).collect();
+    let calendar = sprint_usecases.team_calendar(&team_id).await?;
+    let sprint_responses: Vec<SprintResponse> = sprints
+        .into_iter()
+        .map(|sprint| SprintResponse::new(sprint, &calendar))
+        .collect();
     Ok(Json(sprint_responses))
 }
 
@@ -983,7 +1185,10 @@ pub async fn get_active_sprint_by_team(
     Path(team_id): Path<Uuid>,
 ) -> Result<Json<Option<SprintResponse>>, AppError> {
     let sprint = sprint_usecases.get_active_sprint_by_team(&team_id).await?;
-    Ok(Json(sprint.map(SprintResponse::from)))
+    let calendar = sprint_usecases.team_calendar(&team_id).await?;
+    Ok(Json(
+        sprint.map(|sprint| SprintResponse::new(sprint, &calendar)),
+    ))
 }
 
 pub async fn start_sprint(
@@ -1047,6 +1252,198 @@ pub async fn complete_story_points(
     }))
 }
 
+pub async fn get_sprint_capacity(
+    Extension(sprint_usecases): Extension<Arc<SprintUsecases>>,
+    Path(sprint_id): Path<Uuid>,
+) -> Result<Json<SprintCapacityResponse>, AppError> {
+    let plan = sprint_usecases.get_sprint_capacity(&sprint_id).await?;
+    Ok(Json(SprintCapacityResponse::from(plan)))
+}
+
+pub async fn get_user_availability(
+    AuthenticatedWithOrg { .. }: AuthenticatedWithOrg,
+    Extension(availability_usecases): Extension<Arc<AvailabilityUsecases>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<UserAvailabilityResponse>, AppError> {
+    let entries = availability_usecases.get_availability(&user_id).await?;
+    Ok(Json(UserAvailabilityResponse {
+        user_id,
+        entries: entries.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub async fn replace_user_availability(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    Extension(user_usecases): Extension<Arc<UserUsecases>>,
+    Extension(availability_usecases): Extension<Arc<AvailabilityUsecases>>,
+    Path(user_id): Path<Uuid>,
+    Json(dto): Json<ReplaceAvailabilityDto>,
+) -> Result<Json<UserAvailabilityResponse>, AppError> {
+    let caller = user_usecases
+        .get_user_by_sub(&auth.sub)
+        .await?
+        .ok_or(AppError::Unauthorized("User not found".to_string()))?;
+
+    // Members manage their own calendar; planners may manage anyone's
+    let can_manage_others = matches!(
+        caller.role,
+        UserRole::ProductOwner | UserRole::ManagingContributor
+    );
+    if caller.id != user_id && !can_manage_others {
+        return Err(AppError::Forbidden(
+            "Only the user or a planner can change this availability".to_string(),
+        ));
+    }
+
+    let entries = dto
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let kind = AvailabilityKind::from_str(&entry.kind).ok_or_else(|| {
+                AppError::BadRequest(format!("Invalid availability kind: {}", entry.kind))
+            })?;
+            AvailabilityEntry::new(
+                user_id,
+                kind,
+                entry.start_date,
+                entry.end_date,
+                entry.capacity_percent,
+                entry.note,
+            )
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    let entries = availability_usecases
+        .replace_availability(&user_id, entries)
+        .await?;
+    Ok(Json(UserAvailabilityResponse {
+        user_id,
+        entries: entries.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub async fn get_organization_holidays(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    Extension(user_usecases): Extension<Arc<UserUsecases>>,
+    Extension(availability_usecases): Extension<Arc<AvailabilityUsecases>>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<OrganizationHolidaysResponse>, AppError> {
+    let caller = require_caller(&user_usecases, &auth.sub).await?;
+    let holidays = availability_usecases
+        .get_holidays(&org_id, &caller.id)
+        .await?;
+    Ok(Json(OrganizationHolidaysResponse {
+        organization_id: org_id,
+        holidays: holidays.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub async fn replace_organization_holidays(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    Extension(user_usecases): Extension<Arc<UserUsecases>>,
+    Extension(availability_usecases): Extension<Arc<AvailabilityUsecases>>,
+    Path(org_id): Path<Uuid>,
+    Json(dto): Json<ReplaceHolidaysDto>,
+) -> Result<Json<OrganizationHolidaysResponse>, AppError> {
+    let caller = require_caller(&user_usecases, &auth.sub).await?;
+    let holidays = dto
+        .holidays
+        .into_iter()
+        .map(|holiday| OrganizationHoliday::new(org_id, holiday.date, holiday.name))
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    let holidays = availability_usecases
+        .replace_holidays(&org_id, &caller.id, holidays)
+        .await?;
+    Ok(Json(OrganizationHolidaysResponse {
+        organization_id: org_id,
+        holidays: holidays.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub(crate) async fn require_caller(
+    user_usecases: &UserUsecases,
+    sub: &str,
+) -> Result<User, AppError> {
+    user_usecases
+        .get_user_by_sub(sub)
+        .await?
+        .ok_or(AppError::Unauthorized("User not found".to_string()))
+}
+
+pub async fn create_invitation(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    Extension(user_usecases): Extension<Arc<UserUsecases>>,
+    Extension(invitation_usecases): Extension<Arc<InvitationUsecases>>,
+    Path(org_id): Path<Uuid>,
+    Json(dto): Json<CreateInvitationDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let caller = require_caller(&user_usecases, &auth.sub).await?;
+    let role = match dto.role.as_deref() {
+        None => MembershipRole::Member,
+        Some(value) => MembershipRole::from_str(value)
+            .ok_or_else(|| AppError::BadRequest(format!("Invalid role: {}", value)))?,
+    };
+
+    let created = invitation_usecases
+        .create_invitation(&org_id, &caller.id, &dto.email, role)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedInvitationResponse {
+            invitation: created.invitation.into(),
+            accept_url: created.accept_url,
+            email_sent: created.email_sent,
+        }),
+    ))
+}
+
+pub async fn list_pending_invitations(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    Extension(user_usecases): Extension<Arc<UserUsecases>>,
+    Extension(invitation_usecases): Extension<Arc<InvitationUsecases>>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<Vec<InvitationResponse>>, AppError> {
+    let caller = require_caller(&user_usecases, &auth.sub).await?;
+    let invitations = invitation_usecases
+        .list_pending_invitations(&org_id, &caller.id)
+        .await?;
+    Ok(Json(invitations.into_iter().map(Into::into).collect()))
+}
+
+pub async fn revoke_invitation(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    Extension(user_usecases): Extension<Arc<UserUsecases>>,
+    Extension(invitation_usecases): Extension<Arc<InvitationUsecases>>,
+    Path((org_id, invitation_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<InvitationResponse>, AppError> {
+    let caller = require_caller(&user_usecases, &auth.sub).await?;
+    let invitation = invitation_usecases
+        .revoke_invitation(&org_id, &invitation_id, &caller.id)
+        .await?;
+    Ok(Json(invitation.into()))
+}
+
+pub async fn accept_invitation(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    Extension(user_usecases): Extension<Arc<UserUsecases>>,
+    Extension(invitation_usecases): Extension<Arc<InvitationUsecases>>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let caller = require_caller(&user_usecases, &auth.sub).await?;
+    let (invitation, membership) = invitation_usecases
+        .accept_invitation(&token, &caller)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(AcceptInvitationResponse {
+            organization_id: invitation.organization_id,
+            membership_id: membership.id,
+            role: membership.role.as_str().to_string(),
+        }),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     // TODO: Re-implement multi-org validation tests with proper mocking infrastructure
@@ -1,10 +1,13 @@
+use crate::adapters::email::invitation_sender_from_env;
 use crate::adapters::http::handlers::{
+    accept_invitation,
     add_member_to_organization,
     add_team_member,
     clerk_webhooks,
     commit_story_points,
     complete_sprint,
     complete_story_points,
+    create_invitation,
     create_organization,
     // Sprint handlers
     create_sprint,
@@ -15,30 +18,41 @@ use crate::adapters::http::handlers::{
     get_active_sprint_by_team,
     get_current_user,
     get_organization_by_external_id,
+    get_organization_holidays,
     get_sprint,
+    get_sprint_capacity,
     get_sprints_by_team,
     get_team,
     get_team_members,
     get_teams_by_organization,
     get_teams_for_context,
+    get_user_availability,
     get_user_by_id,
     get_user_organizations,
     get_user_organizations_me,
     get_user_teams,
+    list_pending_invitations,
     move_sprint_to_review,
+    replace_organization_holidays,
+    replace_user_availability,
+    revoke_invitation,
     search_users,
     start_sprint,
     update_current_user_role,
     update_team,
 };
+use crate::adapters::http::scim;
 use crate::application::ports::{
-    OrganizationRepository, SprintRepository, TeamRepository, UserRepository,
+    AvailabilityRepository, InvitationRepository, OrganizationRepository, ScimRepository,
+    SprintRepository, TeamRepository, UserRepository,
 };
 use crate::application::usecases::{
-    OrganizationUsecases, SprintUsecases, TeamUsecases, UserUsecases,
+    AvailabilityUsecases, InvitationUsecases, OrganizationUsecases, ScimUsecases, SprintUsecases,
+    TeamUsecases, UserUsecases,
 };
 use auth_clerk::JwtVerifier;
-use shuttle_axum::axum::routing::{get, patch, post};
+use common::route_registry::ServiceRouter;
+use shuttle_axum::axum::routing::{delete, get, patch, post};
 use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -52,6 +66,9 @@ pub async fn create_auth_router(
     let org_repo: Arc<dyn OrganizationRepository> = pool.clone();
     let team_repo: Arc<dyn TeamRepository> = pool.clone();
     let sprint_repo: Arc<dyn SprintRepository> = pool.clone();
+    let availability_repo: Arc<dyn AvailabilityRepository> = pool.clone();
+    let invitation_repo: Arc<dyn InvitationRepository> = pool.clone();
+    let scim_repo: Arc<dyn ScimRepository> = pool.clone();
 
     let user_usecases = Arc::new(UserUsecases::new(user_repo.clone()));
     let org_usecases = Arc::new(OrganizationUsecases::new(
@@ -63,9 +80,26 @@ pub async fn create_auth_router(
         user_repo.clone(),
         org_repo.clone(),
     ));
-    let sprint_usecases = Arc::new(SprintUsecases::new(sprint_repo, team_repo.clone()));
+    let availability_usecases = Arc::new(AvailabilityUsecases::new(
+        availability_repo.clone(),
+        user_repo.clone(),
+        org_repo.clone(),
+    ));
+    let invitation_usecases = Arc::new(InvitationUsecases::new(
+        invitation_repo,
+        org_repo.clone(),
+        invitation_sender_from_env(),
+        std::env::var("INVITATION_ACCEPT_URL")
+            .unwrap_or_else(|_| "http://localhost:3000/invitations".to_string()),
+    ));
+    let scim_usecases = Arc::new(ScimUsecases::new(scim_repo, org_repo.clone()));
+    let sprint_usecases = Arc::new(SprintUsecases::new(
+        sprint_repo,
+        team_repo.clone(),
+        availability_repo,
+    ));
 
-    shuttle_axum::axum::Router::new()
+    ServiceRouter::new("auth-gateway")
         // Webhooks
         .route("/clerk/webhooks", post(clerk_webhooks))
         // Organization API
@@ -87,6 +121,54 @@ pub async fn create_auth_router(
             "/organizations/{org_id}/members",
             post(add_member_to_organization),
         )
+        .route(
+            "/organizations/{org_id}/invitations",
+            post(create_invitation).get(list_pending_invitations),
+        )
+        .route(
+            "/organizations/{org_id}/invitations/{invitation_id}",
+            delete(revoke_invitation),
+        )
+        .route("/invitations/{token}/accept", post(accept_invitation))
+        .route(
+            "/organizations/{org_id}/holidays",
+            get(get_organization_holidays).put(replace_organization_holidays),
+        )
+        // SCIM provisioning
+        .route(
+            "/organizations/{org_id}/scim-tokens",
+            post(scim::create_scim_token).get(scim::list_scim_tokens),
+        )
+        .route(
+            "/organizations/{org_id}/scim-tokens/{token_id}",
+            delete(scim::revoke_scim_token),
+        )
+        .route(
+            "/scim/v2/ServiceProviderConfig",
+            get(scim::service_provider_config),
+        )
+        .route(
+            "/scim/v2/Users",
+            get(scim::list_users).post(scim::create_user),
+        )
+        .route(
+            "/scim/v2/Users/{user_id}",
+            get(scim::get_user)
+                .put(scim::replace_user)
+                .patch(scim::patch_user)
+                .delete(scim::delete_user),
+        )
+        .route(
+            "/scim/v2/Groups",
+            get(scim::list_groups).post(scim::create_group),
+        )
+        .route(
+            "/scim/v2/Groups/{group_id}",
+            get(scim::get_group)
+                .put(scim::replace_group)
+                .patch(scim::patch_group)
+                .delete(scim::delete_group),
+        )
         // Team API
         .route("/organizations/{org_id}/teams", post(create_team))
         .route(
@@ -104,6 +186,10 @@ pub async fn create_auth_router(
         .route("/teams/{team_id}/members", post(add_team_member))
         .route("/teams/{team_id}/members", get(get_team_members))
         .route("/users/{user_id}/teams", get(get_user_teams))
+        .route(
+            "/users/{user_id}/availability",
+            get(get_user_availability).put(replace_user_availability),
+        )
         // Sprint API
         .route("/teams/{team_id}/sprints", post(create_sprint))
         .route("/teams/{team_id}/sprints", get(get_sprints_by_team))
@@ -112,6 +198,7 @@ pub async fn create_auth_router(
             get(get_active_sprint_by_team),
         )
         .route("/sprints/{sprint_id}", get(get_sprint))
+        .route("/sprints/{sprint_id}/capacity", get(get_sprint_capacity))
         .route("/sprints/{sprint_id}/start", post(start_sprint))
         .route("/sprints/{sprint_id}/review", patch(move_sprint_to_review))
         .route("/sprints/{sprint_id}/complete", post(complete_sprint))
@@ -127,5 +214,9 @@ pub async fn create_auth_router(
         .layer(shuttle_axum::axum::Extension(org_usecases))
         .layer(shuttle_axum::axum::Extension(team_usecases))
         .layer(shuttle_axum::axum::Extension(sprint_usecases))
+        .layer(shuttle_axum::axum::Extension(availability_usecases))
+        .layer(shuttle_axum::axum::Extension(invitation_usecases))
+        .layer(shuttle_axum::axum::Extension(scim_usecases))
         .layer(shuttle_axum::axum::Extension(verifier))
+        .into_router()
 }
@@ -0,0 +1,573 @@
+//! SCIM 2.0 endpoints for identity providers, and the token management that
+//! organization admins use to connect one.
+
+use crate::adapters::http::handlers::require_caller;
+use crate::application::usecases::{ScimPage, ScimUsecases, UserUsecases};
+use crate::domain::scim::{
+    GroupPatch, PatchOperation, ScimGroup, ScimToken, ScimUser, UserPatch, ERROR_SCHEMA,
+    GROUP_SCHEMA, LIST_RESPONSE_SCHEMA, MAX_PAGE_SIZE, SERVICE_PROVIDER_CONFIG_SCHEMA, USER_SCHEMA,
+};
+use auth_clerk::AuthenticatedWithOrg;
+use axum::{
+    extract::{FromRequestParts, Path, Query},
+    http::{header, request::Parts, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use common::i18n::current_locale;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const SCIM_CONTENT_TYPE: &str = "application/scim+json";
+const SCIM_BASE_PATH: &str = "/api/v1/scim/v2";
+
+/// An error in the SCIM error format (RFC 7644 §3.12)
+pub struct ScimError(AppError);
+
+impl From<AppError> for ScimError {
+    fn from(error: AppError) -> Self {
+        Self(error)
+    }
+}
+
+impl IntoResponse for ScimError {
+    fn into_response(self) -> Response {
+        let (status, scim_type, detail) = match self.0 {
+            AppError::BadRequest(message) => {
+                (StatusCode::BAD_REQUEST, Some("invalidValue"), message)
+            }
+            AppError::NotFound(message) => (StatusCode::NOT_FOUND, None, message),
+            AppError::Unauthorized(message) => (StatusCode::UNAUTHORIZED, None, message),
+            AppError::Forbidden(message) => (StatusCode::FORBIDDEN, None, message),
+            AppError::Conflict(message) => (StatusCode::CONFLICT, Some("uniqueness"), message),
+            AppError::Localized {
+                status: StatusCode::BAD_REQUEST,
+                message,
+                ..
+            } => (
+                StatusCode::BAD_REQUEST,
+                Some("invalidValue"),
+                message.render(current_locale()),
+            ),
+            other => {
+                tracing::error!(error = %other, "SCIM request failed");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    None,
+                    "Internal server error".to_string(),
+                )
+            }
+        };
+
+        let mut body = json!({
+            "schemas": [ERROR_SCHEMA],
+            "status": status.as_u16().to_string(),
+            "detail": detail,
+        });
+        if let Some(scim_type) = scim_type {
+            body["scimType"] = json!(scim_type);
+        }
+        scim_json(status, body)
+    }
+}
+
+fn scim_json<T: Serialize>(status: StatusCode, body: T) -> Response {
+    let mut response = (status, Json(body)).into_response();
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(SCIM_CONTENT_TYPE),
+    );
+    response
+}
+
+/// The organization whose SCIM bearer token authenticated the request
+pub struct ScimTenant(pub Uuid);
+
+impl<S> FromRequestParts<S> for ScimTenant
+where
+    S: Send + Sync,
+{
+    type Rejection = ScimError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| AppError::Unauthorized("SCIM bearer token required".to_string()))?;
+        let usecases = parts
+            .extensions
+            .get::<Arc<ScimUsecases>>()
+            .cloned()
+            .ok_or(AppError::InternalServerError)?;
+
+        Ok(ScimTenant(usecases.authenticate(token).await?))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScimMeta {
+    resource_type: &'static str,
+    created: DateTime<Utc>,
+    last_modified: DateTime<Utc>,
+    location: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScimEmail {
+    pub value: String,
+    #[serde(default)]
+    pub primary: bool,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScimUserResource {
+    schemas: [&'static str; 1],
+    id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_id: Option<String>,
+    user_name: String,
+    active: bool,
+    emails: Vec<ScimEmail>,
+    meta: ScimMeta,
+}
+
+impl From<ScimUser> for ScimUserResource {
+    fn from(user: ScimUser) -> Self {
+        Self {
+            schemas: [USER_SCHEMA],
+            id: user.user_id,
+            external_id: user.external_id,
+            user_name: user.email.clone(),
+            active: user.active,
+            emails: vec![ScimEmail {
+                value: user.email,
+                primary: true,
+                kind: Some("work".to_string()),
+            }],
+            meta: ScimMeta {
+                resource_type: "User",
+                created: user.created_at,
+                last_modified: user.updated_at,
+                location: format!("{}/Users/{}", SCIM_BASE_PATH, user.user_id),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScimMemberRef {
+    pub value: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScimGroupResource {
+    schemas: [&'static str; 1],
+    id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_id: Option<String>,
+    display_name: String,
+    members: Vec<ScimMemberRef>,
+    meta: ScimMeta,
+}
+
+impl From<ScimGroup> for ScimGroupResource {
+    fn from(group: ScimGroup) -> Self {
+        Self {
+            schemas: [GROUP_SCHEMA],
+            id: group.team_id,
+            external_id: group.external_id,
+            display_name: group.display_name,
+            members: group
+                .members
+                .into_iter()
+                .map(|member| ScimMemberRef {
+                    value: member.user_id,
+                    display: Some(member.email),
+                })
+                .collect(),
+            meta: ScimMeta {
+                resource_type: "Group",
+                created: group.created_at,
+                last_modified: group.updated_at,
+                location: format!("{}/Groups/{}", SCIM_BASE_PATH, group.team_id),
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScimListResponse<T> {
+    schemas: [&'static str; 1],
+    total_results: usize,
+    start_index: usize,
+    items_per_page: usize,
+    #[serde(rename = "Resources")]
+    resources: Vec<T>,
+}
+
+impl<T> ScimListResponse<T> {
+    fn from_page<D: Into<T>>(page: ScimPage<D>) -> Self {
+        let resources: Vec<T> = page.resources.into_iter().map(Into::into).collect();
+        Self {
+            schemas: [LIST_RESPONSE_SCHEMA],
+            total_results: page.total_results,
+            start_index: page.start_index,
+            items_per_page: resources.len(),
+            resources,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimListQuery {
+    pub filter: Option<String>,
+    pub start_index: Option<usize>,
+    pub count: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUserRequest {
+    pub user_name: String,
+    pub external_id: Option<String>,
+    pub active: Option<bool>,
+    #[serde(default)]
+    pub emails: Vec<ScimEmail>,
+}
+
+impl ScimUserRequest {
+    /// The primary email if one is given, otherwise the user name
+    fn email(&self) -> &str {
+        self.emails
+            .iter()
+            .find(|email| email.primary)
+            .or_else(|| self.emails.first())
+            .map(|email| email.value.as_str())
+            .unwrap_or(&self.user_name)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimGroupRequest {
+    pub display_name: String,
+    pub external_id: Option<String>,
+    #[serde(default)]
+    pub members: Vec<ScimMemberRef>,
+}
+
+impl ScimGroupRequest {
+    fn member_ids(&self) -> Vec<Uuid> {
+        self.members.iter().map(|member| member.value).collect()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ScimPatchRequest {
+    #[serde(rename = "Operations")]
+    pub operations: Vec<PatchOperation>,
+}
+
+/// GET /scim/v2/ServiceProviderConfig
+pub async fn service_provider_config() -> Response {
+    scim_json(
+        StatusCode::OK,
+        json!({
+            "schemas": [SERVICE_PROVIDER_CONFIG_SCHEMA],
+            "patch": { "supported": true },
+            "bulk": { "supported": false, "maxOperations": 0, "maxPayloadSize": 0 },
+            "filter": { "supported": true, "maxResults": MAX_PAGE_SIZE },
+            "changePassword": { "supported": false },
+            "sort": { "supported": false },
+            "etag": { "supported": false },
+            "authenticationSchemes": [{
+                "type": "oauthbearertoken",
+                "name": "OAuth Bearer Token",
+                "description": "Per-organization SCIM token",
+                "primary": true
+            }]
+        }),
+    )
+}
+
+/// GET /scim/v2/Users?filter=&startIndex=&count=
+pub async fn list_users(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Query(query): Query<ScimListQuery>,
+) -> Result<Response, ScimError> {
+    let page = scim
+        .list_users(
+            &org_id,
+            query.filter.as_deref(),
+            query.start_index,
+            query.count,
+        )
+        .await?;
+    Ok(scim_json(
+        StatusCode::OK,
+        ScimListResponse::<ScimUserResource>::from_page(page),
+    ))
+}
+
+/// POST /scim/v2/Users
+pub async fn create_user(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Json(request): Json<ScimUserRequest>,
+) -> Result<Response, ScimError> {
+    let user = scim
+        .create_user(
+            &org_id,
+            request.email(),
+            request.external_id.clone(),
+            request.active.unwrap_or(true),
+        )
+        .await?;
+    Ok(scim_json(StatusCode::CREATED, ScimUserResource::from(user)))
+}
+
+/// GET /scim/v2/Users/{user_id}
+pub async fn get_user(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Response, ScimError> {
+    let user = scim.get_user(&org_id, &user_id).await?;
+    Ok(scim_json(StatusCode::OK, ScimUserResource::from(user)))
+}
+
+/// PUT /scim/v2/Users/{user_id}
+pub async fn replace_user(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<ScimUserRequest>,
+) -> Result<Response, ScimError> {
+    let user = scim
+        .replace_user(
+            &org_id,
+            &user_id,
+            request.external_id,
+            request.active.unwrap_or(true),
+        )
+        .await?;
+    Ok(scim_json(StatusCode::OK, ScimUserResource::from(user)))
+}
+
+/// PATCH /scim/v2/Users/{user_id}
+pub async fn patch_user(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<ScimPatchRequest>,
+) -> Result<Response, ScimError> {
+    let patch = UserPatch::from_operations(&request.operations)?;
+    let user = scim.patch_user(&org_id, &user_id, patch).await?;
+    Ok(scim_json(StatusCode::OK, ScimUserResource::from(user)))
+}
+
+/// DELETE /scim/v2/Users/{user_id}
+pub async fn delete_user(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<StatusCode, ScimError> {
+    scim.delete_user(&org_id, &user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /scim/v2/Groups?filter=&startIndex=&count=
+pub async fn list_groups(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Query(query): Query<ScimListQuery>,
+) -> Result<Response, ScimError> {
+    let page = scim
+        .list_groups(
+            &org_id,
+            query.filter.as_deref(),
+            query.start_index,
+            query.count,
+        )
+        .await?;
+    Ok(scim_json(
+        StatusCode::OK,
+        ScimListResponse::<ScimGroupResource>::from_page(page),
+    ))
+}
+
+/// POST /scim/v2/Groups
+pub async fn create_group(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Json(request): Json<ScimGroupRequest>,
+) -> Result<Response, ScimError> {
+    let group = scim
+        .create_group(
+            &org_id,
+            &request.display_name,
+            request.external_id.clone(),
+            &request.member_ids(),
+        )
+        .await?;
+    Ok(scim_json(
+        StatusCode::CREATED,
+        ScimGroupResource::from(group),
+    ))
+}
+
+/// GET /scim/v2/Groups/{group_id}
+pub async fn get_group(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Path(group_id): Path<Uuid>,
+) -> Result<Response, ScimError> {
+    let group = scim.get_group(&org_id, &group_id).await?;
+    Ok(scim_json(StatusCode::OK, ScimGroupResource::from(group)))
+}
+
+/// PUT /scim/v2/Groups/{group_id}
+pub async fn replace_group(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<ScimGroupRequest>,
+) -> Result<Response, ScimError> {
+    let group = scim
+        .replace_group(
+            &org_id,
+            &group_id,
+            &request.display_name,
+            request.external_id.clone(),
+            &request.member_ids(),
+        )
+        .await?;
+    Ok(scim_json(StatusCode::OK, ScimGroupResource::from(group)))
+}
+
+/// PATCH /scim/v2/Groups/{group_id}
+pub async fn patch_group(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<ScimPatchRequest>,
+) -> Result<Response, ScimError> {
+    let patch = GroupPatch::from_operations(&request.operations)?;
+    let group = scim.patch_group(&org_id, &group_id, patch).await?;
+    Ok(scim_json(StatusCode::OK, ScimGroupResource::from(group)))
+}
+
+/// DELETE /scim/v2/Groups/{group_id}
+pub async fn delete_group(
+    ScimTenant(org_id): ScimTenant,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Path(group_id): Path<Uuid>,
+) -> Result<StatusCode, ScimError> {
+    scim.delete_group(&org_id, &group_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+pub struct CreateScimTokenDto {
+    pub description: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimTokenResponse {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<ScimToken> for ScimTokenResponse {
+    fn from(token: ScimToken) -> Self {
+        Self {
+            id: token.id,
+            organization_id: token.organization_id,
+            description: token.description,
+            created_by: token.created_by,
+            created_at: token.created_at,
+            last_used_at: token.last_used_at,
+            revoked_at: token.revoked_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedScimTokenResponse {
+    #[serde(flatten)]
+    pub token: ScimTokenResponse,
+    /// Shown once; only its hash is stored
+    pub secret: String,
+    pub base_url: &'static str,
+}
+
+/// POST /organizations/{org_id}/scim-tokens
+pub async fn create_scim_token(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    Extension(user_usecases): Extension<Arc<UserUsecases>>,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Path(org_id): Path<Uuid>,
+    Json(dto): Json<CreateScimTokenDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let caller = require_caller(&user_usecases, &auth.sub).await?;
+    let (token, secret) = scim
+        .issue_token(&org_id, &caller.id, dto.description)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedScimTokenResponse {
+            token: token.into(),
+            secret,
+            base_url: SCIM_BASE_PATH,
+        }),
+    ))
+}
+
+/// GET /organizations/{org_id}/scim-tokens
+pub async fn list_scim_tokens(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    Extension(user_usecases): Extension<Arc<UserUsecases>>,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<Vec<ScimTokenResponse>>, AppError> {
+    let caller = require_caller(&user_usecases, &auth.sub).await?;
+    let tokens = scim.list_tokens(&org_id, &caller.id).await?;
+    Ok(Json(tokens.into_iter().map(Into::into).collect()))
+}
+
+/// DELETE /organizations/{org_id}/scim-tokens/{token_id}
+pub async fn revoke_scim_token(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    Extension(user_usecases): Extension<Arc<UserUsecases>>,
+    Extension(scim): Extension<Arc<ScimUsecases>>,
+    Path((org_id, token_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    let caller = require_caller(&user_usecases, &auth.sub).await?;
+    scim.revoke_token(&org_id, &token_id, &caller.id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
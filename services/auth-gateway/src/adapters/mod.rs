@@ -1,2 +1,3 @@
+pub mod email;
 pub mod http;
 pub mod persistence;
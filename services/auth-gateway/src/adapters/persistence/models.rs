@@ -1,6 +1,9 @@
+use crate::domain::invitation::{Invitation, InvitationStatus};
 use crate::domain::organization::{MembershipRole, Organization, OrganizationMembership};
+use crate::domain::scim::{ScimToken, ScimUser};
 use crate::domain::user::{ContributorSpecialty, User, UserRole};
 use chrono::{DateTime, Utc};
+use common::AppError;
 use sqlx::FromRow;
 use uuid::Uuid;
 
@@ -103,3 +106,96 @@ impl From<OrganizationMembershipDb> for OrganizationMembership {
         }
     }
 }
+
+#[derive(FromRow)]
+pub struct InvitationDb {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub email: String,
+    pub role: String,
+    pub invited_by: Option<Uuid>,
+    pub status: String,
+    pub expires_at: DateTime<Utc>,
+    pub accepted_by: Option<Uuid>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<InvitationDb> for Invitation {
+    type Error = AppError;
+
+    fn try_from(row: InvitationDb) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            organization_id: row.organization_id,
+            email: row.email,
+            role: MembershipRole::from_str(&row.role).ok_or(AppError::InternalServerError)?,
+            invited_by: row.invited_by,
+            status: InvitationStatus::from_str(&row.status).ok_or(AppError::InternalServerError)?,
+            expires_at: row.expires_at,
+            accepted_by: row.accepted_by,
+            accepted_at: row.accepted_at,
+            revoked_at: row.revoked_at,
+            created_at: row.created_at,
+        })
+    }
+}
+
+#[derive(FromRow)]
+pub struct ScimTokenDb {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<ScimTokenDb> for ScimToken {
+    fn from(row: ScimTokenDb) -> Self {
+        Self {
+            id: row.id,
+            organization_id: row.organization_id,
+            description: row.description,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            last_used_at: row.last_used_at,
+            revoked_at: row.revoked_at,
+        }
+    }
+}
+
+#[derive(FromRow)]
+pub struct ScimUserDb {
+    pub user_id: Uuid,
+    pub email: String,
+    pub external_id: Option<String>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ScimUserDb> for ScimUser {
+    fn from(row: ScimUserDb) -> Self {
+        Self {
+            user_id: row.user_id,
+            email: row.email,
+            external_id: row.external_id,
+            active: row.active,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(FromRow)]
+pub struct ScimGroupDb {
+    pub team_id: Uuid,
+    pub organization_id: Uuid,
+    pub display_name: String,
+    pub external_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
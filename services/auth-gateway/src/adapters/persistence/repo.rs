@@ -1,18 +1,29 @@
-use crate::adapters::persistence::models::{OrganizationDb, OrganizationMembershipDb, UserDb};
+use crate::adapters::persistence::models::{
+    InvitationDb, OrganizationDb, OrganizationMembershipDb, ScimGroupDb, ScimTokenDb, ScimUserDb,
+    UserDb,
+};
 use crate::application::ports::{
-    OrganizationRepository, SprintRepository, TeamRepository, UserRepository,
+    AvailabilityRepository, InvitationRepository, OrganizationRepository, ScimRepository,
+    SprintRepository, TeamRepository, UserRepository,
 };
+use crate::domain::availability::{AvailabilityEntry, AvailabilityKind};
+use crate::domain::holiday::OrganizationHoliday;
+use crate::domain::invitation::Invitation;
 use crate::domain::organization::{
     AddMemberRequest, CreateOrganizationRequest, MembershipRole, Organization,
     OrganizationMembership,
 };
+use crate::domain::scim::{
+    ScimFilter, ScimGroup, ScimGroupMember, ScimToken, ScimUser, PLACEHOLDER_EXTERNAL_ID_PREFIX,
+};
 use crate::domain::sprint::{CreateSprintRequest, Sprint};
 use crate::domain::team::{AddTeamMemberRequest, CreateTeamRequest, Team, TeamMembership};
 use crate::domain::user::User;
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, Utc};
 use common::AppError;
 use sqlx::PgPool;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 #[async_trait]
@@ -34,6 +45,24 @@ impl UserRepository for PgPool {
             crate::domain::user::ContributorSpecialty::UXDesigner => "ux_designer",
         });
 
+        // An account provisioned over SCIM before its first sign-in is taken over
+        // by the signed-in identity, keeping its memberships
+        sqlx::query(
+            r#"
+            UPDATE users SET external_id = $1, updated_at = $3
+            WHERE LOWER(email) = LOWER($2)
+              AND external_id LIKE $4
+              AND NOT EXISTS (SELECT 1 FROM users WHERE external_id = $1)
+            "#,
+        )
+        .bind(&user.external_id)
+        .bind(&user.email)
+        .bind(user.updated_at)
+        .bind(format!("{}%", PLACEHOLDER_EXTERNAL_ID_PREFIX))
+        .execute(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
         sqlx::query(
             r#"
             INSERT INTO users (id, external_id, email, role, specialty, created_at, updated_at)
@@ -998,4 +1027,892 @@ impl SprintRepository for PgPool {
 
         Ok(())
     }
+
+    async fn get_team_timezones(&self, team_id: &Uuid) -> Result<Vec<String>, AppError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT ps.timezone
+            FROM project_settings ps
+            JOIN projects p ON p.id = ps.project_id
+            WHERE p.team_id = $1
+            "#,
+        )
+        .bind(team_id)
+        .fetch_all(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct AvailabilityRow {
+    id: Uuid,
+    user_id: Uuid,
+    kind: String,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    capacity_percent: i16,
+    note: Option<String>,
+}
+
+impl TryFrom<AvailabilityRow> for AvailabilityEntry {
+    type Error = AppError;
+
+    fn try_from(row: AvailabilityRow) -> Result<Self, Self::Error> {
+        Ok(AvailabilityEntry {
+            id: row.id,
+            user_id: row.user_id,
+            kind: AvailabilityKind::from_str(&row.kind).ok_or(AppError::InternalServerError)?,
+            start_date: row.start_date,
+            end_date: row.end_date,
+            capacity_percent: row.capacity_percent as u8,
+            note: row.note,
+        })
+    }
+}
+
+#[async_trait]
+impl AvailabilityRepository for PgPool {
+    async fn get_user_availability(
+        &self,
+        user_id: &Uuid,
+    ) -> Result<Vec<AvailabilityEntry>, AppError> {
+        let rows = sqlx::query_as::<_, AvailabilityRow>(
+            r#"
+            SELECT id, user_id, kind, start_date, end_date, capacity_percent, note
+            FROM user_availability
+            WHERE user_id = $1
+            ORDER BY start_date
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        rows.into_iter().map(AvailabilityEntry::try_from).collect()
+    }
+
+    async fn replace_user_availability(
+        &self,
+        user_id: &Uuid,
+        entries: &[AvailabilityEntry],
+    ) -> Result<(), AppError> {
+        let mut tx = self
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        sqlx::query("DELETE FROM user_availability WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        for entry in entries {
+            sqlx::query(
+                r#"
+                INSERT INTO user_availability (id, user_id, kind, start_date, end_date, capacity_percent, note, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            )
+            .bind(entry.id)
+            .bind(user_id)
+            .bind(entry.kind.as_str())
+            .bind(entry.start_date)
+            .bind(entry.end_date)
+            .bind(i16::from(entry.capacity_percent))
+            .bind(&entry.note)
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    async fn get_availability_in_window(
+        &self,
+        user_ids: &[Uuid],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<AvailabilityEntry>, AppError> {
+        let rows = sqlx::query_as::<_, AvailabilityRow>(
+            r#"
+            SELECT id, user_id, kind, start_date, end_date, capacity_percent, note
+            FROM user_availability
+            WHERE user_id = ANY($1) AND start_date <= $3 AND end_date >= $2
+            ORDER BY user_id, start_date
+            "#,
+        )
+        .bind(user_ids)
+        .bind(start)
+        .bind(end)
+        .fetch_all(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        rows.into_iter().map(AvailabilityEntry::try_from).collect()
+    }
+
+    async fn get_organization_holidays(
+        &self,
+        organization_id: &Uuid,
+    ) -> Result<Vec<OrganizationHoliday>, AppError> {
+        sqlx::query_as::<_, (Uuid, Uuid, NaiveDate, String)>(
+            r#"
+            SELECT id, organization_id, holiday_date, name
+            FROM organization_holidays
+            WHERE organization_id = $1
+            ORDER BY holiday_date
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_all(self)
+        .await
+        .map(|rows| {
+            rows.into_iter()
+                .map(|(id, organization_id, date, name)| OrganizationHoliday {
+                    id,
+                    organization_id,
+                    date,
+                    name,
+                })
+                .collect()
+        })
+        .map_err(|_| AppError::InternalServerError)
+    }
+
+    async fn replace_organization_holidays(
+        &self,
+        organization_id: &Uuid,
+        holidays: &[OrganizationHoliday],
+    ) -> Result<(), AppError> {
+        let mut tx = self
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        sqlx::query("DELETE FROM organization_holidays WHERE organization_id = $1")
+            .bind(organization_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        for holiday in holidays {
+            sqlx::query(
+                r#"
+                INSERT INTO organization_holidays (id, organization_id, holiday_date, name, created_at)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(holiday.id)
+            .bind(organization_id)
+            .bind(holiday.date)
+            .bind(&holiday.name)
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+}
+
+const INVITATION_COLUMNS: &str = "id, organization_id, email, role, invited_by, status, \
+    expires_at, accepted_by, accepted_at, revoked_at, created_at";
+
+#[async_trait]
+impl InvitationRepository for PgPool {
+    async fn create_invitation(
+        &self,
+        invitation: &Invitation,
+        token_hash: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO organization_invitations
+                (id, organization_id, email, role, token_hash, invited_by, status, expires_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(invitation.id)
+        .bind(invitation.organization_id)
+        .bind(&invitation.email)
+        .bind(invitation.role.as_str())
+        .bind(token_hash)
+        .bind(invitation.invited_by)
+        .bind(invitation.status.as_str())
+        .bind(invitation.expires_at)
+        .bind(invitation.created_at)
+        .execute(self)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            Some(db_error) if db_error.is_unique_violation() => AppError::Conflict(format!(
+                "A pending invitation for {} already exists",
+                invitation.email
+            )),
+            _ => AppError::InternalServerError,
+        })?;
+
+        Ok(())
+    }
+
+    async fn get_invitation(&self, id: &Uuid) -> Result<Option<Invitation>, AppError> {
+        let row = sqlx::query_as::<_, InvitationDb>(&format!(
+            "SELECT {INVITATION_COLUMNS} FROM organization_invitations WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        row.map(Invitation::try_from).transpose()
+    }
+
+    async fn get_invitation_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Invitation>, AppError> {
+        let row = sqlx::query_as::<_, InvitationDb>(&format!(
+            "SELECT {INVITATION_COLUMNS} FROM organization_invitations WHERE token_hash = $1"
+        ))
+        .bind(token_hash)
+        .fetch_optional(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        row.map(Invitation::try_from).transpose()
+    }
+
+    async fn get_pending_invitations(
+        &self,
+        organization_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Invitation>, AppError> {
+        let rows = sqlx::query_as::<_, InvitationDb>(&format!(
+            "SELECT {INVITATION_COLUMNS} FROM organization_invitations
+             WHERE organization_id = $1 AND status = 'pending' AND expires_at > $2
+             ORDER BY created_at DESC"
+        ))
+        .bind(organization_id)
+        .bind(now)
+        .fetch_all(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        rows.into_iter().map(Invitation::try_from).collect()
+    }
+
+    async fn expire_invitations(&self, now: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "UPDATE organization_invitations SET status = 'expired'
+             WHERE status = 'pending' AND expires_at <= $1",
+        )
+        .bind(now)
+        .execute(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn update_invitation(&self, invitation: &Invitation) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE organization_invitations
+            SET status = $2, accepted_by = $3, accepted_at = $4, revoked_at = $5
+            WHERE id = $1
+            "#,
+        )
+        .bind(invitation.id)
+        .bind(invitation.status.as_str())
+        .bind(invitation.accepted_by)
+        .bind(invitation.accepted_at)
+        .bind(invitation.revoked_at)
+        .execute(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    async fn accept_invitation(
+        &self,
+        invitation: &Invitation,
+    ) -> Result<OrganizationMembership, AppError> {
+        let user_id = invitation
+            .accepted_by
+            .ok_or(AppError::InternalServerError)?;
+        let now = invitation.accepted_at.unwrap_or_else(Utc::now);
+
+        let mut tx = self
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        // Guard on the stored status so two concurrent acceptances cannot both win
+        let updated = sqlx::query(
+            r#"
+            UPDATE organization_invitations
+            SET status = 'accepted', accepted_by = $2, accepted_at = $3
+            WHERE id = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(invitation.id)
+        .bind(user_id)
+        .bind(now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::Conflict(
+                "Invitation is no longer pending".to_string(),
+            ));
+        }
+
+        let membership = OrganizationMembership {
+            id: Uuid::new_v4(),
+            organization_id: invitation.organization_id,
+            user_id,
+            role: invitation.role.clone(),
+            created_at: now,
+            updated_at: now,
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO organization_memberships (id, organization_id, user_id, role, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(membership.id)
+        .bind(membership.organization_id)
+        .bind(membership.user_id)
+        .bind(membership.role.as_str())
+        .bind(membership.created_at)
+        .bind(membership.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            Some(db_error) if db_error.is_unique_violation() => AppError::Conflict(
+                "User is already a member of this organization".to_string(),
+            ),
+            _ => AppError::InternalServerError,
+        })?;
+
+        tx.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(membership)
+    }
+}
+
+const SCIM_TOKEN_COLUMNS: &str =
+    "id, organization_id, description, created_by, created_at, last_used_at, revoked_at";
+
+/// Users in scope for an organization: its members plus anyone it provisioned,
+/// including those it has since deactivated but not deleted. Binds the
+/// organization as $1.
+const SCIM_USER_SELECT: &str = r#"
+    SELECT u.id AS user_id, u.email, su.external_id,
+           COALESCE(su.active, TRUE) AS active,
+           COALESCE(su.created_at, m.created_at) AS created_at,
+           GREATEST(su.updated_at, m.updated_at) AS updated_at
+    FROM users u
+    LEFT JOIN organization_memberships m ON m.user_id = u.id AND m.organization_id = $1
+    LEFT JOIN scim_users su ON su.user_id = u.id AND su.organization_id = $1
+        AND su.deleted_at IS NULL
+    WHERE (m.id IS NOT NULL OR su.user_id IS NOT NULL)
+"#;
+
+const SCIM_GROUP_SELECT: &str = r#"
+    SELECT t.id AS team_id, t.organization_id, t.name AS display_name, sg.external_id,
+           t.created_at, t.updated_at
+    FROM teams t
+    LEFT JOIN scim_groups sg ON sg.team_id = t.id
+    WHERE t.organization_id = $1
+"#;
+
+/// Remove everything that lets the user act in the organization: membership,
+/// team seats, API keys and impersonation sessions
+async fn withdraw_organization_access(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    organization_id: &Uuid,
+    user_id: &Uuid,
+) -> Result<(), AppError> {
+    let sole_owner: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM organization_memberships
+            WHERE organization_id = $1 AND user_id = $2 AND role = 'owner'
+        ) AND (
+            SELECT COUNT(*) FROM organization_memberships
+            WHERE organization_id = $1 AND role = 'owner'
+        ) = 1
+        "#,
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|_| AppError::InternalServerError)?;
+    if sole_owner {
+        return Err(AppError::Conflict(
+            "Cannot deprovision the organization's only owner".to_string(),
+        ));
+    }
+
+    let statements = [
+        "DELETE FROM organization_memberships WHERE organization_id = $1 AND user_id = $2",
+        "DELETE FROM team_memberships WHERE user_id = $2
+         AND team_id IN (SELECT id FROM teams WHERE organization_id = $1)",
+        "DELETE FROM api_keys WHERE organization_id = $1 AND user_id = $2",
+        "UPDATE impersonation_sessions SET revoked_at = NOW()
+         WHERE organization_id = $1 AND target_user_id = $2 AND revoked_at IS NULL",
+    ];
+    for statement in statements {
+        sqlx::query(statement)
+            .bind(organization_id)
+            .bind(user_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "SQL error withdrawing organization access");
+                AppError::InternalServerError
+            })?;
+    }
+    Ok(())
+}
+
+async fn scim_group_members(
+    pool: &PgPool,
+    team_ids: &[Uuid],
+) -> Result<Vec<(Uuid, ScimGroupMember)>, AppError> {
+    let rows = sqlx::query_as::<_, (Uuid, Uuid, String)>(
+        r#"
+        SELECT tm.team_id, u.id, u.email
+        FROM team_memberships tm
+        JOIN users u ON u.id = tm.user_id
+        WHERE tm.team_id = ANY($1) AND tm.is_active
+        ORDER BY u.email
+        "#,
+    )
+    .bind(team_ids)
+    .fetch_all(pool)
+    .await
+    .map_err(|_| AppError::InternalServerError)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(team_id, user_id, email)| (team_id, ScimGroupMember { user_id, email }))
+        .collect())
+}
+
+#[async_trait]
+impl ScimRepository for PgPool {
+    async fn create_token(&self, token: &ScimToken, token_hash: &str) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO scim_tokens (id, organization_id, token_hash, description, created_by, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(token.id)
+        .bind(token.organization_id)
+        .bind(token_hash)
+        .bind(&token.description)
+        .bind(token.created_by)
+        .bind(token.created_at)
+        .execute(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    async fn get_tokens(&self, organization_id: &Uuid) -> Result<Vec<ScimToken>, AppError> {
+        let rows = sqlx::query_as::<_, ScimTokenDb>(&format!(
+            "SELECT {SCIM_TOKEN_COLUMNS} FROM scim_tokens
+             WHERE organization_id = $1 ORDER BY created_at DESC"
+        ))
+        .bind(organization_id)
+        .fetch_all(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(rows.into_iter().map(ScimToken::from).collect())
+    }
+
+    async fn use_token(&self, token_hash: &str) -> Result<Option<ScimToken>, AppError> {
+        let row = sqlx::query_as::<_, ScimTokenDb>(&format!(
+            "UPDATE scim_tokens SET last_used_at = NOW()
+             WHERE token_hash = $1 AND revoked_at IS NULL
+             RETURNING {SCIM_TOKEN_COLUMNS}"
+        ))
+        .bind(token_hash)
+        .fetch_optional(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(row.map(ScimToken::from))
+    }
+
+    async fn revoke_token(
+        &self,
+        organization_id: &Uuid,
+        token_id: &Uuid,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE scim_tokens SET revoked_at = NOW()
+             WHERE id = $1 AND organization_id = $2 AND revoked_at IS NULL",
+        )
+        .bind(token_id)
+        .bind(organization_id)
+        .execute(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_users(
+        &self,
+        organization_id: &Uuid,
+        filter: Option<&ScimFilter>,
+    ) -> Result<Vec<ScimUser>, AppError> {
+        let (user_name, external_id) = match filter {
+            Some(ScimFilter::UserName(value)) => (Some(value.as_str()), None),
+            Some(ScimFilter::ExternalId(value)) => (None, Some(value.as_str())),
+            Some(ScimFilter::DisplayName(_)) => return Ok(Vec::new()),
+            None => (None, None),
+        };
+
+        let rows = sqlx::query_as::<_, ScimUserDb>(&format!(
+            "{SCIM_USER_SELECT}
+               AND ($2::text IS NULL OR LOWER(u.email) = LOWER($2))
+               AND ($3::text IS NULL OR su.external_id = $3)
+             ORDER BY u.email"
+        ))
+        .bind(organization_id)
+        .bind(user_name)
+        .bind(external_id)
+        .fetch_all(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(rows.into_iter().map(ScimUser::from).collect())
+    }
+
+    async fn get_user(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Result<Option<ScimUser>, AppError> {
+        let row = sqlx::query_as::<_, ScimUserDb>(&format!("{SCIM_USER_SELECT} AND u.id = $2"))
+            .bind(organization_id)
+            .bind(user_id)
+            .fetch_optional(self)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(row.map(ScimUser::from))
+    }
+
+    async fn find_or_create_user(&self, email: &str) -> Result<Uuid, AppError> {
+        let existing = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM users WHERE LOWER(email) = LOWER($1) ORDER BY created_at LIMIT 1",
+        )
+        .bind(email)
+        .fetch_optional(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+        if let Some(id) = existing {
+            return Ok(id);
+        }
+
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, external_id, email, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $4)
+            "#,
+        )
+        .bind(id)
+        .bind(format!("{}{}", PLACEHOLDER_EXTERNAL_ID_PREFIX, id))
+        .bind(email)
+        .bind(now)
+        .execute(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(id)
+    }
+
+    async fn save_user(&self, organization_id: &Uuid, user: &ScimUser) -> Result<(), AppError> {
+        let mut tx = self
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO scim_users (organization_id, user_id, external_id, active, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (organization_id, user_id) DO UPDATE SET
+                external_id = EXCLUDED.external_id,
+                active = EXCLUDED.active,
+                updated_at = EXCLUDED.updated_at,
+                deleted_at = NULL
+            "#,
+        )
+        .bind(organization_id)
+        .bind(user.user_id)
+        .bind(&user.external_id)
+        .bind(user.active)
+        .bind(user.created_at)
+        .bind(user.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            Some(db_error) if db_error.is_unique_violation() => AppError::Conflict(format!(
+                "externalId {} is already in use",
+                user.external_id.as_deref().unwrap_or_default()
+            )),
+            _ => AppError::InternalServerError,
+        })?;
+
+        if user.active {
+            sqlx::query(
+                r#"
+                INSERT INTO organization_memberships (id, organization_id, user_id, role, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $5)
+                ON CONFLICT (organization_id, user_id) DO NOTHING
+                "#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(organization_id)
+            .bind(user.user_id)
+            .bind(MembershipRole::Member.as_str())
+            .bind(user.updated_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        } else {
+            withdraw_organization_access(&mut tx, organization_id, &user.user_id).await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        Ok(())
+    }
+
+    async fn delete_user(&self, organization_id: &Uuid, user_id: &Uuid) -> Result<(), AppError> {
+        let mut tx = self
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        withdraw_organization_access(&mut tx, organization_id, user_id).await?;
+        // Keep an inactive tombstone rather than deleting the row, so the
+        // gateway goes on refusing the user's existing sessions
+        sqlx::query(
+            r#"
+            INSERT INTO scim_users (organization_id, user_id, active, deleted_at)
+            VALUES ($1, $2, FALSE, NOW())
+            ON CONFLICT (organization_id, user_id) DO UPDATE SET
+                external_id = NULL,
+                active = FALSE,
+                updated_at = NOW(),
+                deleted_at = NOW()
+            "#,
+        )
+        .bind(organization_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        tx.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        Ok(())
+    }
+
+    async fn list_groups(
+        &self,
+        organization_id: &Uuid,
+        filter: Option<&ScimFilter>,
+    ) -> Result<Vec<ScimGroup>, AppError> {
+        let (display_name, external_id) = match filter {
+            Some(ScimFilter::DisplayName(value)) => (Some(value.as_str()), None),
+            Some(ScimFilter::ExternalId(value)) => (None, Some(value.as_str())),
+            Some(ScimFilter::UserName(_)) => return Ok(Vec::new()),
+            None => (None, None),
+        };
+
+        let rows = sqlx::query_as::<_, ScimGroupDb>(&format!(
+            "{SCIM_GROUP_SELECT}
+               AND ($2::text IS NULL OR t.name = $2)
+               AND ($3::text IS NULL OR sg.external_id = $3)
+             ORDER BY t.name"
+        ))
+        .bind(organization_id)
+        .bind(display_name)
+        .bind(external_id)
+        .fetch_all(self)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        let team_ids: Vec<Uuid> = rows.iter().map(|row| row.team_id).collect();
+        let mut members: HashMap<Uuid, Vec<ScimGroupMember>> = HashMap::new();
+        for (team_id, member) in scim_group_members(self, &team_ids).await? {
+            members.entry(team_id).or_default().push(member);
+        }
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ScimGroup {
+                members: members.remove(&row.team_id).unwrap_or_default(),
+                team_id: row.team_id,
+                organization_id: row.organization_id,
+                display_name: row.display_name,
+                external_id: row.external_id,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+            .collect())
+    }
+
+    async fn get_group(
+        &self,
+        organization_id: &Uuid,
+        team_id: &Uuid,
+    ) -> Result<Option<ScimGroup>, AppError> {
+        let row = sqlx::query_as::<_, ScimGroupDb>(&format!("{SCIM_GROUP_SELECT} AND t.id = $2"))
+            .bind(organization_id)
+            .bind(team_id)
+            .fetch_optional(self)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let members = scim_group_members(self, &[row.team_id])
+            .await?
+            .into_iter()
+            .map(|(_, member)| member)
+            .collect();
+        Ok(Some(ScimGroup {
+            team_id: row.team_id,
+            organization_id: row.organization_id,
+            display_name: row.display_name,
+            external_id: row.external_id,
+            members,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }))
+    }
+
+    async fn save_group(&self, group: &ScimGroup) -> Result<(), AppError> {
+        let mut tx = self
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        let conflict = |e: sqlx::Error| match e.as_database_error() {
+            Some(db_error) if db_error.is_unique_violation() => AppError::Conflict(format!(
+                "A group named {} or with this externalId already exists",
+                group.display_name
+            )),
+            _ => AppError::InternalServerError,
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO teams (id, name, organization_id, velocity_history, created_at, updated_at)
+            VALUES ($1, $2, $3, '{}', $4, $5)
+            ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name, updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(group.team_id)
+        .bind(&group.display_name)
+        .bind(group.organization_id)
+        .bind(group.created_at)
+        .bind(group.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(conflict)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO scim_groups (team_id, organization_id, external_id, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (team_id) DO UPDATE SET
+                external_id = EXCLUDED.external_id,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(group.team_id)
+        .bind(group.organization_id)
+        .bind(&group.external_id)
+        .bind(group.created_at)
+        .bind(group.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(conflict)?;
+
+        let member_ids: Vec<Uuid> = group.members.iter().map(|member| member.user_id).collect();
+        sqlx::query("DELETE FROM team_memberships WHERE team_id = $1 AND user_id <> ALL($2)")
+            .bind(group.team_id)
+            .bind(&member_ids)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        sqlx::query(
+            r#"
+            INSERT INTO team_memberships (id, team_id, user_id, role, is_active, joined_at, updated_at)
+            SELECT gen_random_uuid(), $1, member_id, $3, TRUE, $4, $4
+            FROM UNNEST($2::uuid[]) AS member_id
+            ON CONFLICT (team_id, user_id) DO NOTHING
+            "#,
+        )
+        .bind(group.team_id)
+        .bind(&member_ids)
+        .bind(crate::domain::user::UserRole::Contributor.to_string())
+        .bind(group.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        tx.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        Ok(())
+    }
+
+    async fn delete_group(&self, organization_id: &Uuid, team_id: &Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM teams WHERE id = $1 AND organization_id = $2")
+            .bind(team_id)
+            .bind(organization_id)
+            .execute(self)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
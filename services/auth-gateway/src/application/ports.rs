@@ -1,10 +1,15 @@
+use crate::domain::availability::AvailabilityEntry;
+use crate::domain::holiday::OrganizationHoliday;
+use crate::domain::invitation::Invitation;
 use crate::domain::organization::{
     AddMemberRequest, CreateOrganizationRequest, Organization, OrganizationMembership,
 };
+use crate::domain::scim::{ScimFilter, ScimGroup, ScimToken, ScimUser};
 use crate::domain::sprint::{CreateSprintRequest, Sprint};
 use crate::domain::team::{AddTeamMemberRequest, CreateTeamRequest, Team, TeamMembership};
 use crate::domain::user::User;
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 use common::AppError;
 use uuid::Uuid;
 
@@ -36,7 +41,6 @@ pub trait OrganizationRepository: Send + Sync {
         organization_id: &Uuid,
         request: &AddMemberRequest,
     ) -> Result<OrganizationMembership, AppError>;
-    #[allow(dead_code)]
     async fn get_membership(
         &self,
         organization_id: &Uuid,
@@ -81,4 +85,121 @@ pub trait SprintRepository: Send + Sync {
     async fn get_active_sprint_by_team(&self, team_id: &Uuid) -> Result<Option<Sprint>, AppError>;
     async fn update_sprint(&self, sprint: &Sprint) -> Result<(), AppError>;
     async fn delete_sprint(&self, id: &Uuid) -> Result<(), AppError>;
+    /// Time zones configured on the projects that belong to the team
+    async fn get_team_timezones(&self, team_id: &Uuid) -> Result<Vec<String>, AppError>;
+}
+
+#[async_trait]
+pub trait AvailabilityRepository: Send + Sync {
+    async fn get_user_availability(
+        &self,
+        user_id: &Uuid,
+    ) -> Result<Vec<AvailabilityEntry>, AppError>;
+    /// Replace all of a user's entries in one transaction
+    async fn replace_user_availability(
+        &self,
+        user_id: &Uuid,
+        entries: &[AvailabilityEntry],
+    ) -> Result<(), AppError>;
+    /// Entries for any of the users that intersect the inclusive date window
+    async fn get_availability_in_window(
+        &self,
+        user_ids: &[Uuid],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<AvailabilityEntry>, AppError>;
+    async fn get_organization_holidays(
+        &self,
+        organization_id: &Uuid,
+    ) -> Result<Vec<OrganizationHoliday>, AppError>;
+    /// Replace all of an organization's holidays in one transaction
+    async fn replace_organization_holidays(
+        &self,
+        organization_id: &Uuid,
+        holidays: &[OrganizationHoliday],
+    ) -> Result<(), AppError>;
+}
+
+#[async_trait]
+pub trait InvitationRepository: Send + Sync {
+    async fn create_invitation(
+        &self,
+        invitation: &Invitation,
+        token_hash: &str,
+    ) -> Result<(), AppError>;
+    async fn get_invitation(&self, id: &Uuid) -> Result<Option<Invitation>, AppError>;
+    async fn get_invitation_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Invitation>, AppError>;
+    /// Pending invitations of an organization that have not yet expired, newest first
+    async fn get_pending_invitations(
+        &self,
+        organization_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Invitation>, AppError>;
+    /// Flip pending invitations past their expiry to `expired`
+    async fn expire_invitations(&self, now: DateTime<Utc>) -> Result<u64, AppError>;
+    async fn update_invitation(&self, invitation: &Invitation) -> Result<(), AppError>;
+    /// Record the acceptance and add the membership in one transaction
+    async fn accept_invitation(
+        &self,
+        invitation: &Invitation,
+    ) -> Result<OrganizationMembership, AppError>;
+}
+
+#[async_trait]
+pub trait ScimRepository: Send + Sync {
+    async fn create_token(&self, token: &ScimToken, token_hash: &str) -> Result<(), AppError>;
+    async fn get_tokens(&self, organization_id: &Uuid) -> Result<Vec<ScimToken>, AppError>;
+    /// An unrevoked token by hash, recording that it was used
+    async fn use_token(&self, token_hash: &str) -> Result<Option<ScimToken>, AppError>;
+    /// Returns false when no unrevoked token matched
+    async fn revoke_token(&self, organization_id: &Uuid, token_id: &Uuid)
+        -> Result<bool, AppError>;
+
+    /// Members of the organization and users it has deactivated, by email
+    async fn list_users(
+        &self,
+        organization_id: &Uuid,
+        filter: Option<&ScimFilter>,
+    ) -> Result<Vec<ScimUser>, AppError>;
+    async fn get_user(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Result<Option<ScimUser>, AppError>;
+    /// The account with this email, or a placeholder that the first sign-in claims
+    async fn find_or_create_user(&self, email: &str) -> Result<Uuid, AppError>;
+    /// Store the provisioning record and grant or withdraw organization access
+    /// to match `active`, in one transaction
+    async fn save_user(&self, organization_id: &Uuid, user: &ScimUser) -> Result<(), AppError>;
+    /// Withdraw organization access and replace the provisioning record with an
+    /// inactive tombstone that SCIM reads no longer return
+    async fn delete_user(&self, organization_id: &Uuid, user_id: &Uuid) -> Result<(), AppError>;
+
+    async fn list_groups(
+        &self,
+        organization_id: &Uuid,
+        filter: Option<&ScimFilter>,
+    ) -> Result<Vec<ScimGroup>, AppError>;
+    async fn get_group(
+        &self,
+        organization_id: &Uuid,
+        team_id: &Uuid,
+    ) -> Result<Option<ScimGroup>, AppError>;
+    /// Create or update the team and set its members to exactly `group.members`
+    async fn save_group(&self, group: &ScimGroup) -> Result<(), AppError>;
+    async fn delete_group(&self, organization_id: &Uuid, team_id: &Uuid) -> Result<bool, AppError>;
+}
+
+/// Delivers invitation emails
+#[async_trait]
+pub trait InvitationSender: Send + Sync {
+    async fn send_invitation(
+        &self,
+        invitation: &Invitation,
+        organization: &Organization,
+        accept_url: &str,
+    ) -> Result<(), AppError>;
 }
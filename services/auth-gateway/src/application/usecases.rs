@@ -1,13 +1,25 @@
 use crate::application::ports::{
-    OrganizationRepository, SprintRepository, TeamRepository, UserRepository,
+    AvailabilityRepository, InvitationRepository, InvitationSender, OrganizationRepository,
+    ScimRepository, SprintRepository, TeamRepository, UserRepository,
 };
+use crate::domain::availability::{
+    validate_schedule, AvailabilityEntry, MemberAvailability, SprintCapacityPlan,
+};
+use crate::domain::holiday::{validate_holidays, OrganizationHoliday};
+use crate::domain::invitation::{generate_token, hash_token, normalize_email, Invitation};
 use crate::domain::organization::{
-    AddMemberRequest, CreateOrganizationRequest, Organization, OrganizationMembership,
+    AddMemberRequest, CreateOrganizationRequest, MembershipRole, Organization,
+    OrganizationMembership,
+};
+use crate::domain::scim::{
+    GroupPatch, ScimFilter, ScimGroup, ScimGroupMember, ScimToken, ScimUser, UserPatch,
+    MAX_PAGE_SIZE,
 };
 use crate::domain::sprint::{CreateSprintRequest, Sprint};
 use crate::domain::team::{AddTeamMemberRequest, CreateTeamRequest, Team, TeamMembership};
 use crate::domain::user::{ContributorSpecialty, User, UserRole};
 use chrono::Utc;
+use common::calendar::{shared_timezone, SprintCalendar};
 use common::AppError;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -426,16 +438,123 @@ impl TeamUsecases {
     }
 }
 
+pub struct AvailabilityUsecases {
+    availability_repo: Arc<dyn AvailabilityRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    organization_repo: Arc<dyn OrganizationRepository>,
+}
+
+impl AvailabilityUsecases {
+    pub fn new(
+        availability_repo: Arc<dyn AvailabilityRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        organization_repo: Arc<dyn OrganizationRepository>,
+    ) -> Self {
+        Self {
+            availability_repo,
+            user_repo,
+            organization_repo,
+        }
+    }
+
+    async fn require_membership(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Result<OrganizationMembership, AppError> {
+        self.organization_repo
+            .get_organization_by_id(organization_id)
+            .await?
+            .ok_or(AppError::NotFound("Organization not found".to_string()))?;
+
+        self.organization_repo
+            .get_membership(organization_id, user_id)
+            .await?
+            .ok_or(AppError::Forbidden(
+                "Not a member of this organization".to_string(),
+            ))
+    }
+
+    pub async fn get_holidays(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Result<Vec<OrganizationHoliday>, AppError> {
+        self.require_membership(organization_id, user_id).await?;
+        self.availability_repo
+            .get_organization_holidays(organization_id)
+            .await
+    }
+
+    /// Replace the organization's holiday calendar wholesale; owners and admins only
+    pub async fn replace_holidays(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+        holidays: Vec<OrganizationHoliday>,
+    ) -> Result<Vec<OrganizationHoliday>, AppError> {
+        let membership = self.require_membership(organization_id, user_id).await?;
+        if !membership.role.can_manage_organization() {
+            return Err(AppError::Forbidden(
+                "Only organization owners and admins can manage holidays".to_string(),
+            ));
+        }
+
+        validate_holidays(&holidays)?;
+        self.availability_repo
+            .replace_organization_holidays(organization_id, &holidays)
+            .await?;
+
+        let mut holidays = holidays;
+        holidays.sort_by_key(|holiday| holiday.date);
+        Ok(holidays)
+    }
+
+    pub async fn get_availability(
+        &self,
+        user_id: &Uuid,
+    ) -> Result<Vec<AvailabilityEntry>, AppError> {
+        self.availability_repo.get_user_availability(user_id).await
+    }
+
+    /// Replace a user's calendar wholesale, rejecting overlapping entries
+    pub async fn replace_availability(
+        &self,
+        user_id: &Uuid,
+        entries: Vec<AvailabilityEntry>,
+    ) -> Result<Vec<AvailabilityEntry>, AppError> {
+        self.user_repo
+            .get_user_by_id(user_id)
+            .await?
+            .ok_or(AppError::NotFound("User not found".to_string()))?;
+
+        validate_schedule(&entries)?;
+        self.availability_repo
+            .replace_user_availability(user_id, &entries)
+            .await?;
+
+        let mut entries = entries;
+        entries.sort_by_key(|entry| entry.start_date);
+        Ok(entries)
+    }
+}
+
 pub struct SprintUsecases {
     sprint_repo: Arc<dyn SprintRepository>,
     team_repo: Arc<dyn TeamRepository>,
+    availability_repo: Arc<dyn AvailabilityRepository>,
 }
 
 impl SprintUsecases {
-    pub fn new(sprint_repo: Arc<dyn SprintRepository>, team_repo: Arc<dyn TeamRepository>) -> Self {
+    pub fn new(
+        sprint_repo: Arc<dyn SprintRepository>,
+        team_repo: Arc<dyn TeamRepository>,
+        availability_repo: Arc<dyn AvailabilityRepository>,
+    ) -> Self {
         Self {
             sprint_repo,
             team_repo,
+            availability_repo,
         }
     }
 
@@ -487,6 +606,12 @@ impl SprintUsecases {
         self.sprint_repo.get_active_sprint_by_team(team_id).await
     }
 
+    /// Calendar in the time zone shared by the team's projects
+    pub async fn team_calendar(&self, team_id: &Uuid) -> Result<SprintCalendar, AppError> {
+        let zones = self.sprint_repo.get_team_timezones(team_id).await?;
+        Ok(shared_timezone(&zones))
+    }
+
     pub async fn start_sprint(&self, sprint_id: &Uuid) -> Result<(), AppError> {
         let mut sprint = self
             .sprint_repo
@@ -541,10 +666,61 @@ impl SprintUsecases {
             .await?
             .ok_or(AppError::NotFound("Sprint not found".to_string()))?;
 
+        self.capacity_plan(&sprint)
+            .await?
+            .check_commitment(points)?;
         sprint.commit_story_points(points)?;
         self.sprint_repo.update_sprint(&sprint).await
     }
 
+    pub async fn get_sprint_capacity(
+        &self,
+        sprint_id: &Uuid,
+    ) -> Result<SprintCapacityPlan, AppError> {
+        let sprint = self
+            .sprint_repo
+            .get_sprint(sprint_id)
+            .await?
+            .ok_or(AppError::NotFound("Sprint not found".to_string()))?;
+
+        self.capacity_plan(&sprint).await
+    }
+
+    /// Scale the sprint's capacity by the availability of its team's active contributors
+    async fn capacity_plan(&self, sprint: &Sprint) -> Result<SprintCapacityPlan, AppError> {
+        let contributors: Vec<Uuid> = self
+            .team_repo
+            .get_team_members(&sprint.team_id)
+            .await?
+            .into_iter()
+            .filter(|(_, membership)| membership.is_active && membership.role.is_contributor())
+            .map(|(user, _)| user.id)
+            .collect();
+
+        let calendar = self.team_calendar(&sprint.team_id).await?;
+        let start = calendar.local_date(sprint.start_date);
+        let end = calendar.local_date(sprint.end_date);
+        let entries = if contributors.is_empty() {
+            Vec::new()
+        } else {
+            self.availability_repo
+                .get_availability_in_window(&contributors, start, end)
+                .await?
+        };
+
+        let members = contributors
+            .into_iter()
+            .map(|user_id| MemberAvailability::for_window(user_id, &entries, start, end))
+            .collect();
+
+        Ok(SprintCapacityPlan::new(
+            sprint.id,
+            sprint.capacity_points,
+            sprint.committed_points,
+            members,
+        ))
+    }
+
     pub async fn complete_story_points(
         &self,
         sprint_id: &Uuid,
@@ -561,6 +737,536 @@ impl SprintUsecases {
     }
 }
 
+pub struct CreatedInvitation {
+    pub invitation: Invitation,
+    /// Link carrying the one-time token; only ever returned here
+    pub accept_url: String,
+    pub email_sent: bool,
+}
+
+pub struct InvitationUsecases {
+    invitation_repo: Arc<dyn InvitationRepository>,
+    organization_repo: Arc<dyn OrganizationRepository>,
+    sender: Arc<dyn InvitationSender>,
+    accept_url_base: String,
+}
+
+impl InvitationUsecases {
+    pub fn new(
+        invitation_repo: Arc<dyn InvitationRepository>,
+        organization_repo: Arc<dyn OrganizationRepository>,
+        sender: Arc<dyn InvitationSender>,
+        accept_url_base: String,
+    ) -> Self {
+        Self {
+            invitation_repo,
+            organization_repo,
+            sender,
+            accept_url_base: accept_url_base.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// The organization, provided `user_id` is one of its owners or admins
+    async fn require_inviter(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Result<Organization, AppError> {
+        let organization = self
+            .organization_repo
+            .get_organization_by_id(organization_id)
+            .await?
+            .ok_or(AppError::NotFound("Organization not found".to_string()))?;
+
+        let membership = self
+            .organization_repo
+            .get_membership(organization_id, user_id)
+            .await?
+            .ok_or(AppError::Forbidden(
+                "Not a member of this organization".to_string(),
+            ))?;
+        if !membership.role.can_invite_members() {
+            return Err(AppError::Forbidden(
+                "Only organization owners and admins can manage invitations".to_string(),
+            ));
+        }
+
+        Ok(organization)
+    }
+
+    /// Create an invitation and email its link. A failed delivery does not undo the
+    /// invitation; the caller gets the link back to share another way.
+    pub async fn create_invitation(
+        &self,
+        organization_id: &Uuid,
+        inviter_id: &Uuid,
+        email: &str,
+        role: MembershipRole,
+    ) -> Result<CreatedInvitation, AppError> {
+        let organization = self.require_inviter(organization_id, inviter_id).await?;
+
+        let now = Utc::now();
+        let invitation = Invitation::new(*organization_id, email, role, *inviter_id, now)?;
+        let token = generate_token()?;
+
+        // Lapsed invitations must not block inviting the same address again
+        self.invitation_repo.expire_invitations(now).await?;
+        self.invitation_repo
+            .create_invitation(&invitation, &hash_token(&token))
+            .await?;
+
+        let accept_url = format!("{}/{}", self.accept_url_base, token);
+        let email_sent = match self
+            .sender
+            .send_invitation(&invitation, &organization, &accept_url)
+            .await
+        {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!(invitation_id = %invitation.id, error = %e, "Invitation email not delivered");
+                false
+            }
+        };
+
+        Ok(CreatedInvitation {
+            invitation,
+            accept_url,
+            email_sent,
+        })
+    }
+
+    pub async fn list_pending_invitations(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Result<Vec<Invitation>, AppError> {
+        self.require_inviter(organization_id, user_id).await?;
+        self.invitation_repo
+            .get_pending_invitations(organization_id, Utc::now())
+            .await
+    }
+
+    pub async fn revoke_invitation(
+        &self,
+        organization_id: &Uuid,
+        invitation_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Result<Invitation, AppError> {
+        self.require_inviter(organization_id, user_id).await?;
+
+        let mut invitation = self
+            .invitation_repo
+            .get_invitation(invitation_id)
+            .await?
+            .filter(|invitation| invitation.organization_id == *organization_id)
+            .ok_or(AppError::NotFound("Invitation not found".to_string()))?;
+
+        invitation.revoke(Utc::now())?;
+        self.invitation_repo.update_invitation(&invitation).await?;
+        Ok(invitation)
+    }
+
+    /// Join the invitation's organization as `user`, whose email must match the invite
+    pub async fn accept_invitation(
+        &self,
+        token: &str,
+        user: &User,
+    ) -> Result<(Invitation, OrganizationMembership), AppError> {
+        let mut invitation = self
+            .invitation_repo
+            .get_invitation_by_token_hash(&hash_token(token.trim()))
+            .await?
+            .ok_or(AppError::NotFound("Invitation not found".to_string()))?;
+
+        invitation.accept(user.id, &user.email, Utc::now())?;
+
+        if self
+            .organization_repo
+            .get_membership(&invitation.organization_id, &user.id)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Conflict(
+                "User is already a member of this organization".to_string(),
+            ));
+        }
+
+        let membership = self.invitation_repo.accept_invitation(&invitation).await?;
+        Ok((invitation, membership))
+    }
+}
+
+/// One page of a SCIM list, with the total before paging
+pub struct ScimPage<T> {
+    pub total_results: usize,
+    pub start_index: usize,
+    pub resources: Vec<T>,
+}
+
+impl<T> ScimPage<T> {
+    /// `start_index` is 1-based as in SCIM; `count` defaults to and is capped at [`MAX_PAGE_SIZE`]
+    fn of(all: Vec<T>, start_index: Option<usize>, count: Option<usize>) -> Self {
+        let start_index = start_index.unwrap_or(1).max(1);
+        let count = count.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        let total_results = all.len();
+        let resources = all.into_iter().skip(start_index - 1).take(count).collect();
+        Self {
+            total_results,
+            start_index,
+            resources,
+        }
+    }
+}
+
+/// Provisioning requests from an organization's identity provider, plus the
+/// management of the tokens it authenticates with
+pub struct ScimUsecases {
+    scim_repo: Arc<dyn ScimRepository>,
+    organization_repo: Arc<dyn OrganizationRepository>,
+}
+
+impl ScimUsecases {
+    pub fn new(
+        scim_repo: Arc<dyn ScimRepository>,
+        organization_repo: Arc<dyn OrganizationRepository>,
+    ) -> Self {
+        Self {
+            scim_repo,
+            organization_repo,
+        }
+    }
+
+    async fn require_admin(&self, organization_id: &Uuid, user_id: &Uuid) -> Result<(), AppError> {
+        self.organization_repo
+            .get_organization_by_id(organization_id)
+            .await?
+            .ok_or(AppError::NotFound("Organization not found".to_string()))?;
+
+        let membership = self
+            .organization_repo
+            .get_membership(organization_id, user_id)
+            .await?
+            .ok_or(AppError::Forbidden(
+                "Not a member of this organization".to_string(),
+            ))?;
+        if !membership.role.can_manage_organization() {
+            return Err(AppError::Forbidden(
+                "Only organization owners and admins can manage SCIM tokens".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Issue a token for the organization's identity provider. The plaintext
+    /// token is only ever returned here.
+    pub async fn issue_token(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+        description: Option<String>,
+    ) -> Result<(ScimToken, String), AppError> {
+        self.require_admin(organization_id, user_id).await?;
+
+        let plaintext = generate_token()?;
+        let token = ScimToken {
+            id: Uuid::new_v4(),
+            organization_id: *organization_id,
+            description: description
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty()),
+            created_by: Some(*user_id),
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+        self.scim_repo
+            .create_token(&token, &hash_token(&plaintext))
+            .await?;
+        tracing::warn!(organization_id = %organization_id, token_id = %token.id, "SCIM token issued");
+        Ok((token, plaintext))
+    }
+
+    pub async fn list_tokens(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Result<Vec<ScimToken>, AppError> {
+        self.require_admin(organization_id, user_id).await?;
+        self.scim_repo.get_tokens(organization_id).await
+    }
+
+    pub async fn revoke_token(
+        &self,
+        organization_id: &Uuid,
+        token_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Result<(), AppError> {
+        self.require_admin(organization_id, user_id).await?;
+        if !self
+            .scim_repo
+            .revoke_token(organization_id, token_id)
+            .await?
+        {
+            return Err(AppError::NotFound("SCIM token not found".to_string()));
+        }
+        tracing::warn!(organization_id = %organization_id, token_id = %token_id, "SCIM token revoked");
+        Ok(())
+    }
+
+    /// The organization a bearer token provisions for
+    pub async fn authenticate(&self, token: &str) -> Result<Uuid, AppError> {
+        self.scim_repo
+            .use_token(&hash_token(token.trim()))
+            .await?
+            .map(|token| token.organization_id)
+            .ok_or(AppError::Unauthorized("Invalid SCIM token".to_string()))
+    }
+
+    pub async fn list_users(
+        &self,
+        organization_id: &Uuid,
+        filter: Option<&str>,
+        start_index: Option<usize>,
+        count: Option<usize>,
+    ) -> Result<ScimPage<ScimUser>, AppError> {
+        let filter = filter.map(ScimFilter::parse).transpose()?;
+        let users = self
+            .scim_repo
+            .list_users(organization_id, filter.as_ref())
+            .await?;
+        Ok(ScimPage::of(users, start_index, count))
+    }
+
+    pub async fn get_user(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Result<ScimUser, AppError> {
+        self.scim_repo
+            .get_user(organization_id, user_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("User {} not found", user_id)))
+    }
+
+    /// Provision `email` into the organization, reusing an existing account
+    pub async fn create_user(
+        &self,
+        organization_id: &Uuid,
+        email: &str,
+        external_id: Option<String>,
+        active: bool,
+    ) -> Result<ScimUser, AppError> {
+        let email = normalize_email(email)?;
+        let user_id = self.scim_repo.find_or_create_user(&email).await?;
+        if self
+            .scim_repo
+            .get_user(organization_id, &user_id)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Conflict(format!(
+                "User {} is already provisioned",
+                email
+            )));
+        }
+
+        let now = Utc::now();
+        let user = ScimUser {
+            user_id,
+            email,
+            external_id,
+            active,
+            created_at: now,
+            updated_at: now,
+        };
+        self.scim_repo.save_user(organization_id, &user).await?;
+        self.get_user(organization_id, &user_id).await
+    }
+
+    /// Set `active` and `externalId`; deactivation withdraws access immediately
+    pub async fn replace_user(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+        external_id: Option<String>,
+        active: bool,
+    ) -> Result<ScimUser, AppError> {
+        let mut user = self.get_user(organization_id, user_id).await?;
+        user.external_id = external_id;
+        user.active = active;
+        user.updated_at = Utc::now();
+        self.scim_repo.save_user(organization_id, &user).await?;
+        self.get_user(organization_id, user_id).await
+    }
+
+    pub async fn patch_user(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+        patch: UserPatch,
+    ) -> Result<ScimUser, AppError> {
+        let user = self.get_user(organization_id, user_id).await?;
+        self.replace_user(
+            organization_id,
+            user_id,
+            patch.external_id.unwrap_or(user.external_id),
+            patch.active.unwrap_or(user.active),
+        )
+        .await
+    }
+
+    pub async fn delete_user(
+        &self,
+        organization_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Result<(), AppError> {
+        let user = self.get_user(organization_id, user_id).await?;
+        self.scim_repo
+            .delete_user(organization_id, &user.user_id)
+            .await?;
+        tracing::warn!(organization_id = %organization_id, user_id = %user_id, "SCIM user deprovisioned");
+        Ok(())
+    }
+
+    pub async fn list_groups(
+        &self,
+        organization_id: &Uuid,
+        filter: Option<&str>,
+        start_index: Option<usize>,
+        count: Option<usize>,
+    ) -> Result<ScimPage<ScimGroup>, AppError> {
+        let filter = filter.map(ScimFilter::parse).transpose()?;
+        let groups = self
+            .scim_repo
+            .list_groups(organization_id, filter.as_ref())
+            .await?;
+        Ok(ScimPage::of(groups, start_index, count))
+    }
+
+    pub async fn get_group(
+        &self,
+        organization_id: &Uuid,
+        team_id: &Uuid,
+    ) -> Result<ScimGroup, AppError> {
+        self.scim_repo
+            .get_group(organization_id, team_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("Group {} not found", team_id)))
+    }
+
+    /// Groups may only contain active members of the organization
+    async fn resolve_members(
+        &self,
+        organization_id: &Uuid,
+        member_ids: &[Uuid],
+    ) -> Result<Vec<ScimGroupMember>, AppError> {
+        let mut members = Vec::with_capacity(member_ids.len());
+        for member_id in member_ids {
+            let user = self
+                .scim_repo
+                .get_user(organization_id, member_id)
+                .await?
+                .filter(|user| user.active)
+                .ok_or_else(|| {
+                    AppError::BadRequest(format!(
+                        "{} is not an active member of this organization",
+                        member_id
+                    ))
+                })?;
+            members.push(ScimGroupMember {
+                user_id: user.user_id,
+                email: user.email,
+            });
+        }
+        Ok(members)
+    }
+
+    pub async fn create_group(
+        &self,
+        organization_id: &Uuid,
+        display_name: &str,
+        external_id: Option<String>,
+        member_ids: &[Uuid],
+    ) -> Result<ScimGroup, AppError> {
+        let display_name = display_name.trim();
+        if display_name.is_empty() {
+            return Err(AppError::BadRequest(
+                "displayName cannot be empty".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let group = ScimGroup {
+            team_id: Uuid::new_v4(),
+            organization_id: *organization_id,
+            display_name: display_name.to_string(),
+            external_id,
+            members: self.resolve_members(organization_id, member_ids).await?,
+            created_at: now,
+            updated_at: now,
+        };
+        self.scim_repo.save_group(&group).await?;
+        self.get_group(organization_id, &group.team_id).await
+    }
+
+    pub async fn replace_group(
+        &self,
+        organization_id: &Uuid,
+        team_id: &Uuid,
+        display_name: &str,
+        external_id: Option<String>,
+        member_ids: &[Uuid],
+    ) -> Result<ScimGroup, AppError> {
+        let mut group = self.get_group(organization_id, team_id).await?;
+        let display_name = display_name.trim();
+        if display_name.is_empty() {
+            return Err(AppError::BadRequest(
+                "displayName cannot be empty".to_string(),
+            ));
+        }
+
+        group.display_name = display_name.to_string();
+        group.external_id = external_id;
+        group.members = self.resolve_members(organization_id, member_ids).await?;
+        group.updated_at = Utc::now();
+        self.scim_repo.save_group(&group).await?;
+        self.get_group(organization_id, team_id).await
+    }
+
+    pub async fn patch_group(
+        &self,
+        organization_id: &Uuid,
+        team_id: &Uuid,
+        patch: GroupPatch,
+    ) -> Result<ScimGroup, AppError> {
+        let group = self.get_group(organization_id, team_id).await?;
+        let current: Vec<Uuid> = group.members.iter().map(|member| member.user_id).collect();
+        self.replace_group(
+            organization_id,
+            team_id,
+            patch.display_name.as_deref().unwrap_or(&group.display_name),
+            patch.external_id.clone().unwrap_or(group.external_id),
+            &patch.apply_members(&current),
+        )
+        .await
+    }
+
+    pub async fn delete_group(
+        &self,
+        organization_id: &Uuid,
+        team_id: &Uuid,
+    ) -> Result<(), AppError> {
+        if !self
+            .scim_repo
+            .delete_group(organization_id, team_id)
+            .await?
+        {
+            return Err(AppError::NotFound(format!("Group {} not found", team_id)));
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,332 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AvailabilityKind {
+    /// Fully out: vacation, leave, public holiday
+    Vacation,
+    /// Working a reduced schedule, e.g. 60% for a part-time week
+    PartTime,
+}
+
+impl AvailabilityKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AvailabilityKind::Vacation => "vacation",
+            AvailabilityKind::PartTime => "part_time",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "vacation" => Some(AvailabilityKind::Vacation),
+            "part_time" => Some(AvailabilityKind::PartTime),
+            _ => None,
+        }
+    }
+}
+
+/// A date range (inclusive) during which a user works at reduced capacity
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AvailabilityEntry {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: AvailabilityKind,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Share of a normal working day the user is available, 0-100
+    pub capacity_percent: u8,
+    pub note: Option<String>,
+}
+
+impl AvailabilityEntry {
+    pub fn new(
+        user_id: Uuid,
+        kind: AvailabilityKind,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        capacity_percent: Option<u8>,
+        note: Option<String>,
+    ) -> Result<Self, AppError> {
+        if start_date > end_date {
+            return Err(AppError::BadRequest(
+                "Availability start date must not be after end date".to_string(),
+            ));
+        }
+
+        let capacity_percent = match (kind, capacity_percent) {
+            (AvailabilityKind::Vacation, None | Some(0)) => 0,
+            (AvailabilityKind::Vacation, Some(_)) => {
+                return Err(AppError::BadRequest(
+                    "Vacation entries always have 0% capacity".to_string(),
+                ))
+            }
+            (AvailabilityKind::PartTime, Some(percent)) if (1..100).contains(&percent) => percent,
+            (AvailabilityKind::PartTime, _) => {
+                return Err(AppError::BadRequest(
+                    "Part-time entries need a capacity between 1 and 99 percent".to_string(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            user_id,
+            kind,
+            start_date,
+            end_date,
+            capacity_percent,
+            note: note
+                .map(|text| text.trim().to_string())
+                .filter(|text| !text.is_empty()),
+        })
+    }
+
+    pub fn overlaps(&self, other: &AvailabilityEntry) -> bool {
+        self.start_date <= other.end_date && other.start_date <= self.end_date
+    }
+
+    fn covers(&self, day: NaiveDate) -> bool {
+        self.start_date <= day && day <= self.end_date
+    }
+}
+
+/// Reject a user's schedule when any two entries overlap
+pub fn validate_schedule(entries: &[AvailabilityEntry]) -> Result<(), AppError> {
+    let mut sorted: Vec<&AvailabilityEntry> = entries.iter().collect();
+    sorted.sort_by_key(|entry| entry.start_date);
+
+    for pair in sorted.windows(2) {
+        if pair[0].overlaps(pair[1]) {
+            return Err(AppError::BadRequest(format!(
+                "Availability entries overlap: {} to {} and {} to {}",
+                pair[0].start_date, pair[0].end_date, pair[1].start_date, pair[1].end_date
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// A member's capacity over a date window, in working (Mon-Fri) days
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MemberAvailability {
+    pub user_id: Uuid,
+    pub working_days: u32,
+    /// Working days adjusted for vacation and part-time entries
+    pub available_days: f64,
+}
+
+impl MemberAvailability {
+    pub fn for_window(
+        user_id: Uuid,
+        entries: &[AvailabilityEntry],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Self {
+        let mut working_days = 0;
+        let mut available_days = 0.0;
+
+        for day in start.iter_days().take_while(|day| *day <= end) {
+            if matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+                continue;
+            }
+            working_days += 1;
+            let percent = entries
+                .iter()
+                .filter(|entry| entry.user_id == user_id && entry.covers(day))
+                .map(|entry| entry.capacity_percent)
+                .min()
+                .unwrap_or(100);
+            available_days += f64::from(percent) / 100.0;
+        }
+
+        Self {
+            user_id,
+            working_days,
+            available_days,
+        }
+    }
+
+    pub fn ratio(&self) -> f64 {
+        if self.working_days == 0 {
+            1.0
+        } else {
+            self.available_days / f64::from(self.working_days)
+        }
+    }
+}
+
+/// Sprint capacity scaled by how much of the sprint its members are available
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SprintCapacityPlan {
+    pub sprint_id: Uuid,
+    pub capacity_points: u32,
+    pub committed_points: u32,
+    /// `capacity_points` scaled by the members' average availability ratio
+    pub available_capacity_points: u32,
+    pub members: Vec<MemberAvailability>,
+}
+
+impl SprintCapacityPlan {
+    pub fn new(
+        sprint_id: Uuid,
+        capacity_points: u32,
+        committed_points: u32,
+        members: Vec<MemberAvailability>,
+    ) -> Self {
+        let ratio = if members.is_empty() {
+            1.0
+        } else {
+            members.iter().map(MemberAvailability::ratio).sum::<f64>() / members.len() as f64
+        };
+
+        Self {
+            sprint_id,
+            capacity_points,
+            committed_points,
+            available_capacity_points: (f64::from(capacity_points) * ratio).round() as u32,
+            members,
+        }
+    }
+
+    /// Reject commitments that would exceed what the available members can deliver
+    pub fn check_commitment(&self, points: u32) -> Result<(), AppError> {
+        let committed = self.committed_points + points;
+        if committed > self.available_capacity_points {
+            return Err(AppError::BadRequest(format!(
+                "Cannot commit {} points. Would exceed availability-adjusted capacity of {} points",
+                committed, self.available_capacity_points
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        // June 2025: the 2nd is a Monday
+        NaiveDate::from_ymd_opt(2025, 6, day).unwrap()
+    }
+
+    #[test]
+    fn test_entry_validation() {
+        let user = Uuid::new_v4();
+        assert!(AvailabilityEntry::new(
+            user,
+            AvailabilityKind::Vacation,
+            date(5),
+            date(2),
+            None,
+            None
+        )
+        .is_err());
+        assert!(AvailabilityEntry::new(
+            user,
+            AvailabilityKind::PartTime,
+            date(2),
+            date(6),
+            Some(100),
+            None
+        )
+        .is_err());
+        let vacation = AvailabilityEntry::new(
+            user,
+            AvailabilityKind::Vacation,
+            date(2),
+            date(6),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(vacation.capacity_percent, 0);
+    }
+
+    #[test]
+    fn test_validate_schedule_rejects_overlap() {
+        let user = Uuid::new_v4();
+        let first = AvailabilityEntry::new(
+            user,
+            AvailabilityKind::Vacation,
+            date(2),
+            date(4),
+            None,
+            None,
+        )
+        .unwrap();
+        let adjacent = AvailabilityEntry::new(
+            user,
+            AvailabilityKind::PartTime,
+            date(5),
+            date(6),
+            Some(50),
+            None,
+        )
+        .unwrap();
+        let overlapping = AvailabilityEntry::new(
+            user,
+            AvailabilityKind::Vacation,
+            date(4),
+            date(9),
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert!(validate_schedule(&[first.clone(), adjacent.clone()]).is_ok());
+        assert!(validate_schedule(&[adjacent, overlapping, first]).is_err());
+    }
+
+    #[test]
+    fn test_member_availability_counts_weekdays_only() {
+        let user = Uuid::new_v4();
+        let entries = vec![
+            AvailabilityEntry::new(
+                user,
+                AvailabilityKind::Vacation,
+                date(2),
+                date(3),
+                None,
+                None,
+            )
+            .unwrap(),
+            AvailabilityEntry::new(
+                user,
+                AvailabilityKind::PartTime,
+                date(4),
+                date(4),
+                Some(50),
+                None,
+            )
+            .unwrap(),
+        ];
+
+        // Mon 2nd to Sun 15th: ten working days, two out and one at half capacity
+        let availability = MemberAvailability::for_window(user, &entries, date(2), date(15));
+        assert_eq!(availability.working_days, 10);
+        assert_eq!(availability.available_days, 7.5);
+        assert_eq!(availability.ratio(), 0.75);
+    }
+
+    #[test]
+    fn test_capacity_plan_scales_by_member_availability() {
+        let member = |available_days| MemberAvailability {
+            user_id: Uuid::new_v4(),
+            working_days: 10,
+            available_days,
+        };
+        let plan = SprintCapacityPlan::new(Uuid::new_v4(), 40, 20, vec![member(10.0), member(5.0)]);
+        assert_eq!(plan.available_capacity_points, 30);
+        assert!(plan.check_commitment(10).is_ok());
+        assert!(plan.check_commitment(11).is_err());
+
+        let empty_team = SprintCapacityPlan::new(Uuid::new_v4(), 40, 0, Vec::new());
+        assert_eq!(empty_team.available_capacity_points, 40);
+    }
+}
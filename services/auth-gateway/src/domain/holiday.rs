@@ -0,0 +1,76 @@
+use chrono::NaiveDate;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const MAX_HOLIDAY_NAME_LENGTH: usize = 100;
+
+/// A day the whole organization is off, such as a public holiday. Sprint
+/// scheduling never starts or ends a sprint on one.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OrganizationHoliday {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+impl OrganizationHoliday {
+    pub fn new(organization_id: Uuid, date: NaiveDate, name: String) -> Result<Self, AppError> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::BadRequest(
+                "Holiday name cannot be empty".to_string(),
+            ));
+        }
+        if name.chars().count() > MAX_HOLIDAY_NAME_LENGTH {
+            return Err(AppError::BadRequest(format!(
+                "Holiday name cannot exceed {} characters",
+                MAX_HOLIDAY_NAME_LENGTH
+            )));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            organization_id,
+            date,
+            name,
+        })
+    }
+}
+
+/// Reject a holiday calendar that lists the same date twice
+pub fn validate_holidays(holidays: &[OrganizationHoliday]) -> Result<(), AppError> {
+    let mut dates: Vec<NaiveDate> = holidays.iter().map(|holiday| holiday.date).collect();
+    dates.sort();
+    match dates.windows(2).find(|pair| pair[0] == pair[1]) {
+        Some(pair) => Err(AppError::BadRequest(format!(
+            "Holiday date listed more than once: {}",
+            pair[0]
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_holiday_validation() {
+        let org_id = Uuid::new_v4();
+        let christmas = NaiveDate::from_ymd_opt(2025, 12, 25).unwrap();
+        let boxing_day = NaiveDate::from_ymd_opt(2025, 12, 26).unwrap();
+
+        assert!(OrganizationHoliday::new(org_id, christmas, "  ".to_string()).is_err());
+        let holidays = vec![
+            OrganizationHoliday::new(org_id, christmas, " Christmas ".to_string()).unwrap(),
+            OrganizationHoliday::new(org_id, boxing_day, "Boxing Day".to_string()).unwrap(),
+        ];
+        assert_eq!(holidays[0].name, "Christmas");
+        assert!(validate_holidays(&holidays).is_ok());
+
+        let duplicate = OrganizationHoliday::new(org_id, christmas, "Xmas".to_string()).unwrap();
+        assert!(validate_holidays(&[holidays[0].clone(), duplicate]).is_err());
+    }
+}
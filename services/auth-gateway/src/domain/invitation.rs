@@ -0,0 +1,249 @@
+use crate::domain::organization::MembershipRole;
+use chrono::{DateTime, Duration, Utc};
+use common::AppError;
+use ring::digest;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How long an invitation link stays valid
+pub const INVITATION_TTL_DAYS: i64 = 7;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InvitationStatus {
+    Pending,
+    Accepted,
+    Revoked,
+    Expired,
+}
+
+impl InvitationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InvitationStatus::Pending => "pending",
+            InvitationStatus::Accepted => "accepted",
+            InvitationStatus::Revoked => "revoked",
+            InvitationStatus::Expired => "expired",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(InvitationStatus::Pending),
+            "accepted" => Some(InvitationStatus::Accepted),
+            "revoked" => Some(InvitationStatus::Revoked),
+            "expired" => Some(InvitationStatus::Expired),
+            _ => None,
+        }
+    }
+}
+
+/// An emailed offer to join an organization with a given role
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Invitation {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub email: String,
+    pub role: MembershipRole,
+    pub invited_by: Option<Uuid>,
+    pub status: InvitationStatus,
+    pub expires_at: DateTime<Utc>,
+    pub accepted_by: Option<Uuid>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Invitation {
+    pub fn new(
+        organization_id: Uuid,
+        email: &str,
+        role: MembershipRole,
+        invited_by: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Self, AppError> {
+        let email = normalize_email(email)?;
+
+        // Ownership is transferred, never handed out by invitation
+        if role == MembershipRole::Owner {
+            return Err(AppError::BadRequest(
+                "Invitations can only grant the admin or member role".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            organization_id,
+            email,
+            role,
+            invited_by: Some(invited_by),
+            status: InvitationStatus::Pending,
+            expires_at: now + Duration::days(INVITATION_TTL_DAYS),
+            accepted_by: None,
+            accepted_at: None,
+            revoked_at: None,
+            created_at: now,
+        })
+    }
+
+    /// Status as of `now`: a pending invitation past its expiry counts as expired
+    /// even before the stored status catches up
+    pub fn status_at(&self, now: DateTime<Utc>) -> InvitationStatus {
+        if self.status == InvitationStatus::Pending && self.expires_at <= now {
+            InvitationStatus::Expired
+        } else {
+            self.status
+        }
+    }
+
+    /// Accept on behalf of `user_id`, whose account email must match the invited address
+    pub fn accept(
+        &mut self,
+        user_id: Uuid,
+        user_email: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        match self.status_at(now) {
+            InvitationStatus::Pending => {}
+            InvitationStatus::Accepted => {
+                return Err(AppError::Conflict(
+                    "Invitation has already been accepted".to_string(),
+                ))
+            }
+            InvitationStatus::Revoked => {
+                return Err(AppError::BadRequest(
+                    "Invitation has been revoked".to_string(),
+                ))
+            }
+            InvitationStatus::Expired => {
+                return Err(AppError::BadRequest("Invitation has expired".to_string()))
+            }
+        }
+
+        if !self.email.eq_ignore_ascii_case(user_email.trim()) {
+            return Err(AppError::Forbidden(
+                "Invitation was sent to a different email address".to_string(),
+            ));
+        }
+
+        self.status = InvitationStatus::Accepted;
+        self.accepted_by = Some(user_id);
+        self.accepted_at = Some(now);
+        Ok(())
+    }
+
+    pub fn revoke(&mut self, now: DateTime<Utc>) -> Result<(), AppError> {
+        if self.status_at(now) != InvitationStatus::Pending {
+            return Err(AppError::BadRequest(format!(
+                "Only pending invitations can be revoked; this one is {}",
+                self.status_at(now).as_str()
+            )));
+        }
+
+        self.status = InvitationStatus::Revoked;
+        self.revoked_at = Some(now);
+        Ok(())
+    }
+}
+
+pub(crate) fn normalize_email(email: &str) -> Result<String, AppError> {
+    let email = email.trim().to_lowercase();
+    let valid = email
+        .split_once('@')
+        .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.'))
+        && !email.chars().any(char::is_whitespace);
+
+    if !valid {
+        return Err(AppError::BadRequest(format!(
+            "Invalid email address: {}",
+            email
+        )));
+    }
+    Ok(email)
+}
+
+/// A fresh random acceptance token, hex encoded
+pub fn generate_token() -> Result<String, AppError> {
+    let mut bytes = [0u8; 32];
+    SystemRandom::new().fill(&mut bytes).map_err(|_| {
+        tracing::error!("Failed to generate invitation token");
+        AppError::InternalServerError
+    })?;
+    Ok(to_hex(&bytes))
+}
+
+/// What gets stored in place of the token, so a database leak does not leak usable links
+pub fn hash_token(token: &str) -> String {
+    to_hex(digest::digest(&digest::SHA256, token.as_bytes()).as_ref())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invitation(now: DateTime<Utc>) -> Invitation {
+        Invitation::new(
+            Uuid::new_v4(),
+            " New.Person@Example.com ",
+            MembershipRole::Member,
+            Uuid::new_v4(),
+            now,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_new_invitation_validates_email_and_role() {
+        let now = Utc::now();
+        let invite = invitation(now);
+        assert_eq!(invite.email, "new.person@example.com");
+        assert_eq!(invite.expires_at, now + Duration::days(INVITATION_TTL_DAYS));
+
+        let org = Uuid::new_v4();
+        let inviter = Uuid::new_v4();
+        assert!(
+            Invitation::new(org, "not-an-email", MembershipRole::Member, inviter, now).is_err()
+        );
+        assert!(Invitation::new(org, "a@b.com", MembershipRole::Owner, inviter, now).is_err());
+    }
+
+    #[test]
+    fn test_accept_checks_status_expiry_and_email() {
+        let now = Utc::now();
+        let user = Uuid::new_v4();
+
+        let mut invite = invitation(now);
+        assert!(matches!(
+            invite.accept(user, "someone.else@example.com", now),
+            Err(AppError::Forbidden(_))
+        ));
+        invite.accept(user, "NEW.PERSON@example.com", now).unwrap();
+        assert_eq!(invite.status, InvitationStatus::Accepted);
+        assert_eq!(invite.accepted_by, Some(user));
+        assert!(invite.accept(user, "new.person@example.com", now).is_err());
+
+        let mut expired = invitation(now - Duration::days(INVITATION_TTL_DAYS + 1));
+        assert_eq!(expired.status_at(now), InvitationStatus::Expired);
+        assert!(expired.accept(user, "new.person@example.com", now).is_err());
+        assert!(expired.revoke(now).is_err());
+
+        let mut revoked = invitation(now);
+        revoked.revoke(now).unwrap();
+        assert!(revoked.accept(user, "new.person@example.com", now).is_err());
+    }
+
+    #[test]
+    fn test_token_hash_is_stable_and_hides_token() {
+        let token = generate_token().unwrap();
+        assert_eq!(token.len(), 64);
+        assert_eq!(hash_token(&token), hash_token(&token));
+        assert_ne!(hash_token(&token), token);
+        assert_ne!(generate_token().unwrap(), token);
+    }
+}
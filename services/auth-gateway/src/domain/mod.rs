@@ -1,4 +1,8 @@
+pub mod availability;
+pub mod holiday;
+pub mod invitation;
 pub mod organization;
+pub mod scim;
 pub mod sprint;
 pub mod team;
 pub mod user;
@@ -33,12 +33,28 @@ pub enum MembershipRole {
 }
 
 impl MembershipRole {
-    #[allow(dead_code)]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MembershipRole::Owner => "owner",
+            MembershipRole::Admin => "admin",
+            MembershipRole::Member => "member",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "owner" => Some(MembershipRole::Owner),
+            "admin" => Some(MembershipRole::Admin),
+            "member" => Some(MembershipRole::Member),
+            _ => None,
+        }
+    }
+
     pub fn can_manage_organization(&self) -> bool {
         matches!(self, MembershipRole::Owner | MembershipRole::Admin)
     }
 
-    #[allow(dead_code)]
     pub fn can_invite_members(&self) -> bool {
         matches!(self, MembershipRole::Owner | MembershipRole::Admin)
     }
@@ -0,0 +1,409 @@
+//! SCIM 2.0 provisioning (RFC 7643/7644). An organization's identity provider
+//! manages its members as Users and its teams as Groups, authenticating with a
+//! bearer token issued to that organization.
+
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+pub const USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+pub const GROUP_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:Group";
+pub const LIST_RESPONSE_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+pub const PATCH_OP_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:PatchOp";
+pub const ERROR_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:Error";
+pub const SERVICE_PROVIDER_CONFIG_SCHEMA: &str =
+    "urn:ietf:params:scim:schemas:core:2.0:ServiceProviderConfig";
+
+/// `external_id` prefix of accounts created by provisioning before the person
+/// has signed in; their first sign-in takes the account over
+pub const PLACEHOLDER_EXTERNAL_ID_PREFIX: &str = "scim|";
+
+/// Largest page a list request can ask for
+pub const MAX_PAGE_SIZE: usize = 200;
+
+/// Bearer credential an identity provider uses for one organization
+#[derive(Debug, Clone)]
+pub struct ScimToken {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// A user as provisioned into one organization. Inactive users keep their
+/// record but have no membership in the organization.
+#[derive(Debug, Clone)]
+pub struct ScimUser {
+    pub user_id: Uuid,
+    pub email: String,
+    pub external_id: Option<String>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScimGroupMember {
+    pub user_id: Uuid,
+    pub email: String,
+}
+
+/// A team of the organization, seen as a SCIM Group
+#[derive(Debug, Clone)]
+pub struct ScimGroup {
+    pub team_id: Uuid,
+    pub organization_id: Uuid,
+    pub display_name: String,
+    pub external_id: Option<String>,
+    pub members: Vec<ScimGroupMember>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The filters identity providers use to look up existing resources
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScimFilter {
+    UserName(String),
+    ExternalId(String),
+    DisplayName(String),
+}
+
+impl ScimFilter {
+    /// Parse `<attribute> eq "<value>"`; other operators and compound filters
+    /// are not supported
+    pub fn parse(filter: &str) -> Result<Self, AppError> {
+        let unsupported = || {
+            AppError::BadRequest(format!(
+                "Unsupported filter '{}': use userName, externalId or displayName with eq",
+                filter
+            ))
+        };
+
+        let (attribute, rest) = filter
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(unsupported)?;
+        let (operator, value) = rest
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(unsupported)?;
+        if !operator.eq_ignore_ascii_case("eq") {
+            return Err(unsupported());
+        }
+        let value = value
+            .trim()
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .ok_or_else(unsupported)?
+            .replace("\\\"", "\"");
+
+        match attribute.to_ascii_lowercase().as_str() {
+            "username" => Ok(ScimFilter::UserName(value)),
+            "externalid" => Ok(ScimFilter::ExternalId(value)),
+            "displayname" => Ok(ScimFilter::DisplayName(value)),
+            _ => Err(unsupported()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PatchOperation {
+    pub op: String,
+    pub path: Option<String>,
+    pub value: Option<Value>,
+}
+
+impl PatchOperation {
+    fn kind(&self) -> Result<PatchKind, AppError> {
+        match self.op.to_ascii_lowercase().as_str() {
+            "add" => Ok(PatchKind::Add),
+            "replace" => Ok(PatchKind::Replace),
+            "remove" => Ok(PatchKind::Remove),
+            other => Err(AppError::BadRequest(format!(
+                "Unsupported patch op: {}",
+                other
+            ))),
+        }
+    }
+
+    /// `(attribute, value)` pairs the operation sets; an operation without a
+    /// path carries an object of attributes
+    fn assignments(&self) -> Result<Vec<(String, Value)>, AppError> {
+        let value = self.value.clone().unwrap_or(Value::Null);
+        match &self.path {
+            Some(path) => Ok(vec![(path.clone(), value)]),
+            None => match value {
+                Value::Object(attributes) => Ok(attributes.into_iter().collect()),
+                _ => Err(AppError::BadRequest(
+                    "A patch operation without a path needs an object value".to_string(),
+                )),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PatchKind {
+    Add,
+    Replace,
+    Remove,
+}
+
+/// The changes a PatchOp request makes to a user
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct UserPatch {
+    pub active: Option<bool>,
+    pub external_id: Option<Option<String>>,
+}
+
+impl UserPatch {
+    pub fn from_operations(operations: &[PatchOperation]) -> Result<Self, AppError> {
+        let mut patch = UserPatch::default();
+        for operation in operations {
+            if operation.kind()? == PatchKind::Remove {
+                match operation.path.as_deref() {
+                    Some(path) if path.eq_ignore_ascii_case("externalId") => {
+                        patch.external_id = Some(None)
+                    }
+                    _ => {
+                        return Err(AppError::BadRequest(
+                            "Only externalId can be removed from a user".to_string(),
+                        ))
+                    }
+                }
+                continue;
+            }
+
+            for (attribute, value) in operation.assignments()? {
+                match attribute.to_ascii_lowercase().as_str() {
+                    "active" => patch.active = Some(parse_bool(&value)?),
+                    "externalid" => patch.external_id = Some(optional_string(&value)?),
+                    // Profile attributes such as name and emails are owned by the
+                    // sign-in provider, not by SCIM
+                    _ => {}
+                }
+            }
+        }
+        Ok(patch)
+    }
+}
+
+/// The changes a PatchOp request makes to a group
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct GroupPatch {
+    pub display_name: Option<String>,
+    pub external_id: Option<Option<String>>,
+    /// Set when the whole member list is replaced
+    pub replace_members: Option<Vec<Uuid>>,
+    pub add_members: Vec<Uuid>,
+    pub remove_members: Vec<Uuid>,
+}
+
+impl GroupPatch {
+    pub fn from_operations(operations: &[PatchOperation]) -> Result<Self, AppError> {
+        let mut patch = GroupPatch::default();
+        for operation in operations {
+            let kind = operation.kind()?;
+
+            if kind == PatchKind::Remove {
+                let path = operation.path.as_deref().unwrap_or_default();
+                if let Some(member) = member_filter(path)? {
+                    patch.remove_members.push(member);
+                } else if path.eq_ignore_ascii_case("members") {
+                    match &operation.value {
+                        Some(value) => patch.remove_members.extend(member_ids(value)?),
+                        None => patch.replace_members = Some(Vec::new()),
+                    }
+                } else if path.eq_ignore_ascii_case("externalId") {
+                    patch.external_id = Some(None);
+                } else {
+                    return Err(AppError::BadRequest(format!(
+                        "Cannot remove '{}' from a group",
+                        path
+                    )));
+                }
+                continue;
+            }
+
+            for (attribute, value) in operation.assignments()? {
+                match attribute.to_ascii_lowercase().as_str() {
+                    "displayname" => {
+                        patch.display_name = Some(
+                            value
+                                .as_str()
+                                .ok_or_else(|| {
+                                    AppError::BadRequest("displayName must be a string".to_string())
+                                })?
+                                .to_string(),
+                        )
+                    }
+                    "externalid" => patch.external_id = Some(optional_string(&value)?),
+                    "members" if kind == PatchKind::Add => {
+                        patch.add_members.extend(member_ids(&value)?)
+                    }
+                    "members" => patch.replace_members = Some(member_ids(&value)?),
+                    _ => {}
+                }
+            }
+        }
+        Ok(patch)
+    }
+
+    /// The member list after applying the patch to `current`
+    pub fn apply_members(&self, current: &[Uuid]) -> Vec<Uuid> {
+        let mut members = self
+            .replace_members
+            .clone()
+            .unwrap_or_else(|| current.to_vec());
+        for member in &self.add_members {
+            if !members.contains(member) {
+                members.push(*member);
+            }
+        }
+        members.retain(|member| !self.remove_members.contains(member));
+        members
+    }
+}
+
+/// The user id in a `members[value eq "<id>"]` path
+fn member_filter(path: &str) -> Result<Option<Uuid>, AppError> {
+    let Some(filter) = path
+        .strip_prefix("members[")
+        .and_then(|rest| rest.strip_suffix(']'))
+    else {
+        return Ok(None);
+    };
+    let value = filter
+        .trim()
+        .strip_prefix("value")
+        .map(str::trim_start)
+        .and_then(|rest| rest.strip_prefix("eq"))
+        .map(str::trim)
+        .and_then(|rest| rest.strip_prefix('"'))
+        .and_then(|rest| rest.strip_suffix('"'))
+        .ok_or_else(|| AppError::BadRequest(format!("Unsupported member filter: {}", path)))?;
+    parse_member_id(value).map(Some)
+}
+
+/// Ids from a `[{"value": "<id>"}, ...]` member list
+fn member_ids(value: &Value) -> Result<Vec<Uuid>, AppError> {
+    let invalid = || AppError::BadRequest("members must be a list of {\"value\": id}".to_string());
+    value
+        .as_array()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|member| {
+            member
+                .get("value")
+                .and_then(Value::as_str)
+                .ok_or_else(invalid)
+                .and_then(parse_member_id)
+        })
+        .collect()
+}
+
+fn parse_member_id(value: &str) -> Result<Uuid, AppError> {
+    Uuid::parse_str(value).map_err(|_| AppError::BadRequest(format!("Unknown member: {}", value)))
+}
+
+/// Some identity providers send booleans as the strings "True" and "False"
+fn parse_bool(value: &Value) -> Result<bool, AppError> {
+    match value {
+        Value::Bool(flag) => Ok(*flag),
+        Value::String(text) if text.eq_ignore_ascii_case("true") => Ok(true),
+        Value::String(text) if text.eq_ignore_ascii_case("false") => Ok(false),
+        _ => Err(AppError::BadRequest("active must be a boolean".to_string())),
+    }
+}
+
+fn optional_string(value: &Value) -> Result<Option<String>, AppError> {
+    match value {
+        Value::Null => Ok(None),
+        Value::String(text) if text.trim().is_empty() => Ok(None),
+        Value::String(text) => Ok(Some(text.trim().to_string())),
+        _ => Err(AppError::BadRequest(
+            "externalId must be a string".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn operations(value: Value) -> Vec<PatchOperation> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_filter_parsing() {
+        assert_eq!(
+            ScimFilter::parse(r#"userName eq "jane@example.com""#).unwrap(),
+            ScimFilter::UserName("jane@example.com".to_string())
+        );
+        assert_eq!(
+            ScimFilter::parse(r#"externalId EQ "00u1""#).unwrap(),
+            ScimFilter::ExternalId("00u1".to_string())
+        );
+        assert_eq!(
+            ScimFilter::parse(r#"displayName eq "Platform Team""#).unwrap(),
+            ScimFilter::DisplayName("Platform Team".to_string())
+        );
+        assert!(ScimFilter::parse(r#"userName co "jane""#).is_err());
+        assert!(ScimFilter::parse(r#"title eq "x""#).is_err());
+        assert!(ScimFilter::parse("userName eq jane").is_err());
+    }
+
+    #[test]
+    fn test_user_patch_accepts_provider_variants() {
+        // Azure AD style: string boolean with a path
+        let patch = UserPatch::from_operations(&operations(json!([
+            {"op": "Replace", "path": "active", "value": "False"}
+        ])))
+        .unwrap();
+        assert_eq!(patch.active, Some(false));
+
+        // Okta style: no path, attribute object
+        let patch = UserPatch::from_operations(&operations(json!([
+            {"op": "replace", "value": {"active": true, "externalId": "00u1", "name": {"givenName": "Jane"}}}
+        ])))
+        .unwrap();
+        assert_eq!(patch.active, Some(true));
+        assert_eq!(patch.external_id, Some(Some("00u1".to_string())));
+
+        assert!(UserPatch::from_operations(&operations(json!([
+            {"op": "remove", "path": "active"}
+        ])))
+        .is_err());
+    }
+
+    #[test]
+    fn test_group_patch_members() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let patch = GroupPatch::from_operations(&operations(json!([
+            {"op": "add", "path": "members", "value": [{"value": c.to_string()}]},
+            {"op": "remove", "path": format!("members[value eq \"{}\"]", a)},
+            {"op": "replace", "path": "displayName", "value": "Platform"}
+        ])))
+        .unwrap();
+        assert_eq!(patch.display_name.as_deref(), Some("Platform"));
+        assert_eq!(patch.apply_members(&[a, b]), vec![b, c]);
+
+        let cleared = GroupPatch::from_operations(&operations(json!([
+            {"op": "remove", "path": "members"}
+        ])))
+        .unwrap();
+        assert!(cleared.apply_members(&[a, b]).is_empty());
+
+        assert!(GroupPatch::from_operations(&operations(json!([
+            {"op": "add", "path": "members", "value": [{"value": "not-a-uuid"}]}
+        ])))
+        .is_err());
+    }
+}
@@ -0,0 +1,20 @@
+//! Public API of the backlog service: how to construct its usecases and
+//! router, the optional LLM and Web Push integrations, and the types other
+//! services read.
+//! Everything else in `backlog` is internal to it.
+
+pub use backlog::adapters::http::handlers::TaskResponse;
+pub use backlog::adapters::http::with_short_key_paths;
+pub use backlog::adapters::integrations::{OpenAiStandupNarrator, OpenAiTaskSplitter};
+pub use backlog::adapters::notifications::{VapidKeys, WebPushNotifier};
+pub use backlog::adapters::websocket::{
+    ConnectionLimits, OrganizationConnections, UserConnections, WebSocketManager,
+};
+pub use backlog::application::{BacklogUsecases, StoryPage, StoryQuery};
+pub use backlog::domain::{
+    RecommendationFilters, Story, StoryRevision, StoryStatus, Task, TaskRecommendation,
+};
+pub use backlog::{
+    build_usecases, create_backlog_router, create_backlog_router_with_websockets,
+    spawn_sprint_stats_projector,
+};
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, Instant, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+use crate::application::BacklogUsecases;
+use crate::domain::DEFAULT_ARCHIVE_AFTER_MONTHS;
+
+const ARCHIVE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const ARCHIVE_AFTER_ENV: &str = "STORY_ARCHIVE_AFTER_MONTHS";
+
+/// Every hour, move stories accepted more than `STORY_ARCHIVE_AFTER_MONTHS`
+/// months ago (default 12; `0` disables archiving) to the archive tier.
+/// Replicas skip stories another replica is already archiving.
+pub fn spawn_story_archiver(usecases: Arc<BacklogUsecases>) -> Option<JoinHandle<()>> {
+    let after_months = archive_after_months(std::env::var(ARCHIVE_AFTER_ENV).ok().as_deref())?;
+
+    Some(tokio::spawn(async move {
+        let mut ticks = interval_at(Instant::now() + ARCHIVE_INTERVAL, ARCHIVE_INTERVAL);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            match usecases.archive_accepted_stories(after_months).await {
+                Ok(0) => {}
+                Ok(archived) => info!(archived, "Archived accepted stories"),
+                Err(e) => error!(error = %e, "Failed to archive accepted stories"),
+            }
+        }
+    }))
+}
+
+fn archive_after_months(raw: Option<&str>) -> Option<u32> {
+    let months = match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => value.parse::<u32>().unwrap_or_else(|_| {
+            warn!(
+                value,
+                "Invalid {}; using default of {} months",
+                ARCHIVE_AFTER_ENV,
+                DEFAULT_ARCHIVE_AFTER_MONTHS
+            );
+            DEFAULT_ARCHIVE_AFTER_MONTHS
+        }),
+        None => DEFAULT_ARCHIVE_AFTER_MONTHS,
+    };
+    Some(months).filter(|months| *months > 0)
+}
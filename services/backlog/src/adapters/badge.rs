@@ -0,0 +1,75 @@
+//! Flat SVG badges in the style of shields.io, for embedding in READMEs and wikis.
+
+use crate::domain::BadgeSummary;
+
+/// Approximate Verdana 11px glyph width used to size badge segments
+const CHAR_WIDTH: usize = 7;
+const PADDING: usize = 10;
+
+fn color(percent: Option<u8>) -> &'static str {
+    match percent {
+        Some(pct) if pct >= 75 => "#4c1",
+        Some(pct) if pct >= 50 => "#dfb317",
+        Some(_) => "#e05d44",
+        None => "#9f9f9f",
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn render_svg(summary: &BadgeSummary) -> String {
+    let label = escape(&summary.label);
+    let message = escape(&summary.message);
+    let label_width = summary.label.chars().count() * CHAR_WIDTH + PADDING;
+    let message_width = summary.message.chars().count() * CHAR_WIDTH + PADDING;
+    let width = label_width + message_width;
+    let label_x = label_width / 2;
+    let message_x = label_width + message_width / 2;
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}"><title>{label}: {message}</title><linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11"><text x="{label_x}" y="14">{label}</text><text x="{message_x}" y="14">{message}</text></g></svg>"##,
+        color = color(summary.percent),
+    )
+}
+
+/// Weak content hash used as the badge ETag
+pub fn etag(svg: &str) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    svg.hash(&mut hasher);
+    format!("W/\"{:016x}\"", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_svg_uses_threshold_colour() {
+        let svg = render_svg(&BadgeSummary {
+            label: "readiness".to_string(),
+            message: "80% ready".to_string(),
+            percent: Some(80),
+        });
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("#4c1"));
+        assert!(svg.contains("80% ready"));
+    }
+
+    #[test]
+    fn test_render_svg_escapes_text() {
+        let svg = render_svg(&BadgeSummary {
+            label: "a<b".to_string(),
+            message: "x".to_string(),
+            percent: None,
+        });
+        assert!(svg.contains("a&lt;b"));
+        assert!(svg.contains("#9f9f9f"));
+    }
+}
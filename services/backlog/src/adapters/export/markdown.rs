@@ -0,0 +1,58 @@
+use std::fmt::Write;
+
+use super::{ExportBlock, ExportDocument};
+
+/// Render an export document as GitHub-flavoured markdown
+pub fn render(document: &ExportDocument) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", document.title);
+
+    for (label, value) in &document.fields {
+        let _ = writeln!(out, "- **{}:** {}", label, value);
+    }
+
+    for section in &document.sections {
+        let _ = write!(out, "\n## {}\n\n", section.heading);
+        for block in &section.blocks {
+            match block {
+                ExportBlock::Subheading(text) => {
+                    let _ = write!(out, "\n### {}\n\n", text);
+                }
+                ExportBlock::Paragraph(text) => {
+                    let _ = write!(out, "{}\n\n", text);
+                }
+                ExportBlock::Bullet(text) => {
+                    let _ = writeln!(out, "- {}", text);
+                }
+                ExportBlock::Field(label, value) => {
+                    let _ = writeln!(out, "- **{}:** {}", label, value);
+                }
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::export::ExportSection;
+
+    #[test]
+    fn test_render_markdown() {
+        let document = ExportDocument {
+            title: "Story".to_string(),
+            fields: vec![("Status".to_string(), "ready".to_string())],
+            sections: vec![ExportSection {
+                heading: "Tasks".to_string(),
+                blocks: vec![ExportBlock::Bullet("Write docs".to_string())],
+            }],
+        };
+
+        assert_eq!(
+            render(&document),
+            "# Story\n\n- **Status:** ready\n\n## Tasks\n\n- Write docs\n"
+        );
+    }
+}
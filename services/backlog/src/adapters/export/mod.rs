@@ -0,0 +1,275 @@
+//! Printable story exports for offline refinement sessions.
+//!
+//! A story is first turned into a format-neutral [`ExportDocument`], which is then
+//! rendered by the markdown template or the PDF writer.
+
+pub mod markdown;
+pub mod pdf;
+
+use common::AppError;
+
+use crate::domain::{Story, Task};
+
+/// Output formats supported by `GET /api/v1/stories/{id}/export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Pdf,
+}
+
+impl ExportFormat {
+    /// Parse the `format` query parameter, defaulting to markdown
+    pub fn parse(raw: Option<&str>) -> Result<Self, AppError> {
+        match raw
+            .map(|value| value.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            None | Some("") | Some("md") | Some("markdown") => Ok(Self::Markdown),
+            Some("pdf") => Ok(Self::Pdf),
+            Some(other) => Err(AppError::BadRequest(format!(
+                "Unsupported export format '{}'; expected 'pdf' or 'md'",
+                other
+            ))),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Markdown => "text/markdown; charset=utf-8",
+            Self::Pdf => "application/pdf",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Pdf => "pdf",
+        }
+    }
+
+    pub fn render(&self, document: &ExportDocument) -> Vec<u8> {
+        match self {
+            Self::Markdown => markdown::render(document).into_bytes(),
+            Self::Pdf => pdf::render(document),
+        }
+    }
+}
+
+/// Format-neutral representation of an exported document
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportDocument {
+    pub title: String,
+    pub fields: Vec<(String, String)>,
+    pub sections: Vec<ExportSection>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportSection {
+    pub heading: String,
+    pub blocks: Vec<ExportBlock>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportBlock {
+    Subheading(String),
+    Paragraph(String),
+    Bullet(String),
+    Field(String, String),
+}
+
+impl ExportDocument {
+    /// Build the refinement handout for a story: description, acceptance criteria,
+    /// tasks and a readiness summary
+    pub fn for_story(story: &Story, tasks: &[Task]) -> Self {
+        let mut fields = vec![
+            ("Story ID".to_string(), story.id.to_string()),
+            ("Status".to_string(), story.status.to_string()),
+            (
+                "Story points".to_string(),
+                story
+                    .story_points
+                    .map(|points| points.to_string())
+                    .unwrap_or_else(|| "Not estimated".to_string()),
+            ),
+        ];
+        if !story.labels.is_empty() {
+            fields.push(("Labels".to_string(), story.labels.join(", ")));
+        }
+        fields.push((
+            "Exported".to_string(),
+            chrono::Utc::now().format("%Y-%m-%d %H:%M UTC").to_string(),
+        ));
+
+        let description = match story.description.as_deref().map(str::trim) {
+            Some(text) if !text.is_empty() => text
+                .split("\n\n")
+                .map(|paragraph| ExportBlock::Paragraph(paragraph.trim().to_string()))
+                .collect(),
+            _ => vec![ExportBlock::Paragraph(
+                "No description provided.".to_string(),
+            )],
+        };
+
+        let mut criteria = Vec::new();
+        for (index, ac) in story.acceptance_criteria.iter().enumerate() {
+            criteria.push(ExportBlock::Subheading(format!(
+                "AC{}: {}",
+                index + 1,
+                ac.description
+            )));
+            criteria.push(ExportBlock::Field("Given".to_string(), ac.given.clone()));
+            criteria.push(ExportBlock::Field("When".to_string(), ac.when.clone()));
+            criteria.push(ExportBlock::Field("Then".to_string(), ac.then.clone()));
+        }
+        if criteria.is_empty() {
+            criteria.push(ExportBlock::Paragraph(
+                "No acceptance criteria defined.".to_string(),
+            ));
+        }
+
+        let task_blocks = if tasks.is_empty() {
+            vec![ExportBlock::Paragraph("No tasks yet.".to_string())]
+        } else {
+            tasks
+                .iter()
+                .map(|task| {
+                    let estimate = task
+                        .estimated_hours
+                        .map(|hours| format!(", {}h", hours))
+                        .unwrap_or_default();
+                    ExportBlock::Bullet(format!("[{}{}] {}", task.status, estimate, task.title))
+                })
+                .collect()
+        };
+
+        Self {
+            title: story.title.clone(),
+            fields,
+            sections: vec![
+                ExportSection {
+                    heading: "Description".to_string(),
+                    blocks: description,
+                },
+                ExportSection {
+                    heading: "Acceptance Criteria".to_string(),
+                    blocks: criteria,
+                },
+                ExportSection {
+                    heading: "Tasks".to_string(),
+                    blocks: task_blocks,
+                },
+                ExportSection {
+                    heading: "Readiness Summary".to_string(),
+                    blocks: readiness_summary(story, tasks),
+                },
+            ],
+        }
+    }
+}
+
+fn readiness_summary(story: &Story, tasks: &[Task]) -> Vec<ExportBlock> {
+    let check = |ok: bool, text: String| {
+        ExportBlock::Bullet(format!("[{}] {}", if ok { "x" } else { " " }, text))
+    };
+
+    let has_description = story
+        .description
+        .as_deref()
+        .is_some_and(|text| !text.trim().is_empty());
+    let ac_count = story.acceptance_criteria.len();
+    let covered = story
+        .acceptance_criteria
+        .iter()
+        .filter(|ac| {
+            let id = ac.id.to_string();
+            tasks
+                .iter()
+                .any(|task| task.acceptance_criteria_refs.contains(&id))
+        })
+        .count();
+
+    let mut blocks = vec![
+        check(has_description, "Description provided".to_string()),
+        check(
+            ac_count > 0,
+            format!("{} acceptance criteria defined", ac_count),
+        ),
+        check(
+            story.story_points.is_some(),
+            "Story points estimated".to_string(),
+        ),
+        check(
+            ac_count > 0 && covered == ac_count,
+            format!(
+                "{}/{} acceptance criteria covered by tasks",
+                covered, ac_count
+            ),
+        ),
+    ];
+
+    if story.readiness_override {
+        let reason = story
+            .readiness_override_reason
+            .clone()
+            .unwrap_or_else(|| "no reason given".to_string());
+        blocks.push(ExportBlock::Field("Readiness override".to_string(), reason));
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::AcceptanceCriteria;
+    use uuid::Uuid;
+
+    fn story_with_task() -> (Story, Vec<Task>) {
+        let mut story = Story::new(
+            Uuid::new_v4(),
+            None,
+            "Export stories".to_string(),
+            Some("Refinement handout".to_string()),
+        )
+        .unwrap();
+        let ac = AcceptanceCriteria::new(
+            "Download".to_string(),
+            "a story".to_string(),
+            "I export it".to_string(),
+            "I get a document".to_string(),
+        )
+        .unwrap();
+        let task = Task::new(
+            story.id,
+            None,
+            "Render markdown".to_string(),
+            None,
+            vec![ac.id.to_string()],
+        )
+        .unwrap();
+        story.add_acceptance_criteria(ac);
+        (story, vec![task])
+    }
+
+    #[test]
+    fn test_parse_export_format() {
+        assert_eq!(ExportFormat::parse(None).unwrap(), ExportFormat::Markdown);
+        assert_eq!(ExportFormat::parse(Some("PDF")).unwrap(), ExportFormat::Pdf);
+        assert!(ExportFormat::parse(Some("docx")).is_err());
+    }
+
+    #[test]
+    fn test_readiness_summary_counts_covered_criteria() {
+        let (story, tasks) = story_with_task();
+        let document = ExportDocument::for_story(&story, &tasks);
+
+        let readiness = &document.sections[3];
+        assert_eq!(readiness.heading, "Readiness Summary");
+        assert!(readiness.blocks.contains(&ExportBlock::Bullet(
+            "[x] 1/1 acceptance criteria covered by tasks".to_string()
+        )));
+        assert!(readiness.blocks.contains(&ExportBlock::Bullet(
+            "[ ] Story points estimated".to_string()
+        )));
+    }
+}
@@ -0,0 +1,300 @@
+//! Minimal PDF writer for story exports.
+//!
+//! Produces a single-column A4 document using the built-in Helvetica fonts, so no
+//! font files or external renderer are needed. Text outside the WinAnsi range is
+//! replaced with `?`.
+
+use super::{ExportBlock, ExportDocument};
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+/// Rough average Helvetica glyph width as a fraction of the font size
+const AVG_CHAR_WIDTH: f32 = 0.5;
+
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+struct Line {
+    font: Font,
+    size: f32,
+    indent: f32,
+    text: String,
+    space_before: f32,
+}
+
+/// Render an export document as PDF bytes
+pub fn render(document: &ExportDocument) -> Vec<u8> {
+    let pages = paginate(&layout(document));
+    write_pdf(&pages)
+}
+
+fn layout(document: &ExportDocument) -> Vec<Line> {
+    let mut lines = Vec::new();
+    push_wrapped(&mut lines, Font::Bold, 18.0, 0.0, 0.0, &document.title);
+    for (label, value) in &document.fields {
+        push_wrapped(
+            &mut lines,
+            Font::Regular,
+            10.0,
+            0.0,
+            2.0,
+            &format!("{}: {}", label, value),
+        );
+    }
+
+    for section in &document.sections {
+        push_wrapped(&mut lines, Font::Bold, 14.0, 0.0, 16.0, &section.heading);
+        for block in &section.blocks {
+            match block {
+                ExportBlock::Subheading(text) => {
+                    push_wrapped(&mut lines, Font::Bold, 11.0, 0.0, 8.0, text)
+                }
+                ExportBlock::Paragraph(text) => {
+                    for paragraph_line in text.lines() {
+                        push_wrapped(&mut lines, Font::Regular, 10.0, 0.0, 4.0, paragraph_line);
+                    }
+                }
+                ExportBlock::Bullet(text) => push_wrapped(
+                    &mut lines,
+                    Font::Regular,
+                    10.0,
+                    12.0,
+                    2.0,
+                    &format!("- {}", text),
+                ),
+                ExportBlock::Field(label, value) => push_wrapped(
+                    &mut lines,
+                    Font::Regular,
+                    10.0,
+                    12.0,
+                    2.0,
+                    &format!("{}: {}", label, value),
+                ),
+            }
+        }
+    }
+
+    lines
+}
+
+fn push_wrapped(
+    lines: &mut Vec<Line>,
+    font: Font,
+    size: f32,
+    indent: f32,
+    space_before: f32,
+    text: &str,
+) {
+    let max_chars = ((PAGE_WIDTH - 2.0 * MARGIN - indent) / (size * AVG_CHAR_WIDTH)) as usize;
+    for (index, chunk) in wrap(text, max_chars.max(1)).into_iter().enumerate() {
+        lines.push(Line {
+            font,
+            size,
+            indent,
+            text: chunk,
+            space_before: if index == 0 { space_before } else { 0.0 },
+        });
+    }
+}
+
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut wrapped = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word = word.to_string();
+        while word.chars().count() > max_chars {
+            if !current.is_empty() {
+                wrapped.push(std::mem::take(&mut current));
+            }
+            let split_at = word
+                .char_indices()
+                .nth(max_chars)
+                .map(|(i, _)| i)
+                .unwrap_or(word.len());
+            let rest = word.split_off(split_at);
+            wrapped.push(word);
+            word = rest;
+        }
+
+        let needed =
+            current.chars().count() + word.chars().count() + usize::from(!current.is_empty());
+        if needed > max_chars && !current.is_empty() {
+            wrapped.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+
+    if !current.is_empty() || wrapped.is_empty() {
+        wrapped.push(current);
+    }
+    wrapped
+}
+
+/// Position lines on pages, returning each page's content stream
+fn paginate(lines: &[Line]) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut content = String::new();
+    let mut y = PAGE_HEIGHT - MARGIN;
+
+    for line in lines {
+        let advance = line.space_before + line.size * 1.3;
+        if y - advance < MARGIN && !content.is_empty() {
+            pages.push(std::mem::take(&mut content));
+            y = PAGE_HEIGHT - MARGIN;
+        }
+        y -= advance;
+        content.push_str(&format!(
+            "BT /{} {} Tf {:.1} {:.1} Td ({}) Tj ET\n",
+            line.font.resource(),
+            line.size,
+            MARGIN + line.indent,
+            y,
+            escape_text(&line.text)
+        ));
+    }
+
+    if !content.is_empty() || pages.is_empty() {
+        pages.push(content);
+    }
+    pages
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        let ch = match ch {
+            '\u{2018}' | '\u{2019}' => '\'',
+            '\u{201C}' | '\u{201D}' => '"',
+            '\u{2013}' | '\u{2014}' | '\u{2022}' => '-',
+            ' '..='~' => ch,
+            _ => '?',
+        };
+        if matches!(ch, '(' | ')' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn write_pdf(pages: &[String]) -> Vec<u8> {
+    // Objects 1-4 are the catalog, page tree and fonts; each page then takes a
+    // page object and a content stream object
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 5 + i * 2).collect();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    ];
+    for (page_id, content) in page_ids.iter().zip(pages) {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            page_id + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ));
+    }
+
+    let mut out = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+    }
+
+    let xref_offset = out.len();
+    out.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::export::ExportSection;
+
+    #[test]
+    fn test_wrap_respects_width() {
+        let lines = wrap("the quick brown fox jumps over the lazy dog", 10);
+        assert!(lines.iter().all(|line| line.chars().count() <= 10));
+        assert_eq!(
+            lines.join(" "),
+            "the quick brown fox jumps over the lazy dog"
+        );
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a (b) \\ c"), "a \\(b\\) \\\\ c");
+        assert_eq!(escape_text("caf\u{e9} \u{2019}"), "caf? '");
+    }
+
+    #[test]
+    fn test_render_paginates_long_documents() {
+        let document = ExportDocument {
+            title: "Long story".to_string(),
+            fields: Vec::new(),
+            sections: vec![ExportSection {
+                heading: "Tasks".to_string(),
+                blocks: (0..200)
+                    .map(|i| ExportBlock::Bullet(format!("Task {}", i)))
+                    .collect(),
+            }],
+        };
+
+        let bytes = render(&document);
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+        let pages = paginate(&layout(&document));
+        assert!(pages.len() > 1);
+        assert!(text.contains(&format!("/Count {}", pages.len())));
+    }
+}
@@ -1,12 +1,33 @@
+use crate::adapters::badge;
+use crate::adapters::export::{ExportDocument, ExportFormat};
 use crate::adapters::http::BacklogAppState;
-use crate::domain::{AcceptanceCriteria, Story, StoryStatus, Task, TaskEvent, TaskStatus};
+use crate::adapters::integrations::GithubWebhookVerifier;
+use crate::application::{StoryQuery, StorySort};
+use crate::domain::{
+    is_short_key, AcceptanceCriteria, AcceptanceCriteriaBatch, AcceptanceCriterionEdit,
+    ArchiveSearch, ArchivedItem, ArchivedItemKind, BacklogHealth, BacklogHealthSnapshot,
+    BadgeMetric, BoardConfiguration, BugDetails, BugPriority, BugSeverity, BugSla,
+    CriterionVerification, DependencyDirection, DependencyGraph, ImportFormat, ImportItemError,
+    ImportJob, LabelRename, NewAcceptanceCriterion, PushSubscription, ReadinessAnnotation,
+    ReadinessState, ScheduledSprint, SlaComplianceReport, SlaPolicy, SlaTargets, SprintAssignment,
+    SprintCadence, SprintCadenceSettings, SprintRollover, StatsFreshness, Story, StoryBulkUpdate,
+    StoryCondition, StoryDependencies, StoryDependency, StoryFilter, StoryMerge, StoryRevisionDiff,
+    StoryShare, StoryStatus, StoryType, Swimlane, SwimlaneGrouping, Swimlanes, Task,
+    TaskBulkUpdate, TaskCompletionPolicy, TaskEvent, TaskSplitPart, TaskStatus, TaskStatusChange,
+    TaskStatusUpdate, VerificationKind,
+};
 use auth_clerk::AuthenticatedWithOrg;
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
+use common::etag::{EntityTag, Tagged};
+use common::i18n::{current_locale, LocalizedMessage};
+use common::reason_policy::ReasonPolicy;
+use common::references::github_mentions;
 use common::AppError;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -38,26 +59,32 @@ async fn resolve_user_id(pool: &sqlx::PgPool, clerk_id: &str) -> Result<Uuid, Ap
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateStoryRequest {
     pub title: String,
     pub description: Option<String>,
     pub labels: Option<Vec<String>>,
+    #[serde(default)]
+    pub story_type: StoryType,
+    pub timebox_hours: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateStoryResponse {
     pub story_id: Uuid,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdateStoryRequest {
     pub title: Option<String>,
     pub description: Option<String>,
-    #[serde(rename = "storyPoints")]
     pub story_points: Option<u32>,
     pub labels: Option<Vec<String>>,
-    #[serde(rename = "sprintId")]
     pub sprint_id: Option<Option<Uuid>>,
+    pub story_type: Option<StoryType>,
+    pub timebox_hours: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -71,28 +98,139 @@ pub struct OverrideStoryReadyRequest {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateTaskRequest {
     pub title: String,
     pub description: Option<String>,
-    #[serde(default)]
+    #[serde(default, alias = "acceptance_criteria_refs")]
     pub acceptance_criteria_refs: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateTaskResponse {
     pub task_id: Uuid,
 }
 
 #[derive(Debug, Deserialize)]
+pub struct SplitTaskRequest {
+    /// The tasks to split into; omit to have the LLM propose them
+    pub tasks: Option<Vec<SplitTaskPart>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitTaskPart {
+    pub title: String,
+    pub description: Option<String>,
+    /// Defaults to all of the original task's refs
+    #[serde(default, alias = "acceptance_criteria_refs")]
+    pub acceptance_criteria_refs: Vec<String>,
+    #[serde(alias = "estimated_hours")]
+    pub estimated_hours: Option<u32>,
+}
+
+impl From<SplitTaskPart> for TaskSplitPart {
+    fn from(part: SplitTaskPart) -> Self {
+        Self {
+            title: part.title,
+            description: part.description,
+            acceptance_criteria_refs: part.acceptance_criteria_refs,
+            estimated_hours: part.estimated_hours,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SplitTaskResponse {
+    pub original: TaskResponse,
+    pub tasks: Vec<TaskResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SetTaskEstimateRequest {
+    #[serde(alias = "estimated_hours")]
     pub estimated_hours: Option<u32>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTransferRequest {
+    #[serde(alias = "to_user_id")]
+    pub to_user_id: Uuid,
+    pub note: Option<String>,
+    /// Set when the owner is stuck and hands the task off
+    #[serde(default)]
+    pub handoff: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UpdateTaskStatusRequest {
     pub status: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchTaskStatusItem {
+    #[serde(alias = "task_id")]
+    pub task_id: Uuid,
+    pub status: String,
+    pub note: Option<String>,
+}
+
+/// PATCH /api/v1/stories/bulk body. Labels and sprint moves apply to stories only.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkStoryUpdateRequest {
+    pub story_ids: Vec<Uuid>,
+    pub status: Option<String>,
+    #[serde(default)]
+    pub add_labels: Vec<String>,
+    #[serde(default)]
+    pub remove_labels: Vec<String>,
+    /// Commit the stories to this sprint
+    pub sprint_id: Option<Uuid>,
+    /// Move the stories out of their sprints, back to the product backlog
+    #[serde(default)]
+    pub remove_from_sprint: bool,
+}
+
+/// PATCH /api/v1/tasks/bulk body
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTaskUpdateRequest {
+    pub task_ids: Vec<Uuid>,
+    pub status: Option<String>,
+    pub estimated_hours: Option<u32>,
+    #[serde(default)]
+    pub clear_estimate: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchItemError {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchTaskStatusResult {
+    pub task_id: Uuid,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<TaskResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<BatchItemError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchTaskStatusResponse {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BatchTaskStatusResult>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct TaskOwnershipResponse {
     pub success: bool,
@@ -100,12 +238,29 @@ pub struct TaskOwnershipResponse {
 }
 
 #[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct StoriesQuery {
     pub status: Option<String>,
-    #[serde(rename = "sprintId")]
     pub sprint_id: Option<Uuid>,
+    #[serde(rename = "type")]
+    pub story_type: Option<String>,
+    /// Story filter, e.g. `status:ready AND label:backend AND points:>=5 AND no:tasks`
+    pub q: Option<String>,
+    /// Comma-separated labels the stories must all carry
+    pub labels: Option<String>,
+    pub assignee: Option<Uuid>,
+    /// `ready`, `not-ready` or `overridden`
+    pub readiness: Option<String>,
+    /// `title` (default), `created`, `updated` or `points`; prefix `-` for descending
+    pub sort: Option<String>,
+    /// Page size; every matching story when omitted
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
 }
 
+/// Response header carrying how many stories match, across all pages
+const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
 #[derive(Debug, Deserialize)]
 pub struct CreateAcceptanceCriterionRequest {
     pub given: String,
@@ -114,6 +269,7 @@ pub struct CreateAcceptanceCriterionRequest {
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateAcceptanceCriterionResponse {
     pub criterion_id: Uuid,
 }
@@ -125,18 +281,46 @@ pub struct UpdateAcceptanceCriterionRequest {
     pub then: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct BulkAcceptanceCriterionUpdate {
+    pub id: Uuid,
+    pub given: Option<String>,
+    pub when: Option<String>,
+    pub then: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkAcceptanceCriteriaRequest {
+    #[serde(default)]
+    pub create: Vec<CreateAcceptanceCriterionRequest>,
+    #[serde(default)]
+    pub update: Vec<BulkAcceptanceCriterionUpdate>,
+    #[serde(default)]
+    pub delete: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkAcceptanceCriteriaResponse {
+    pub created: Vec<Uuid>,
+    pub criteria: Vec<AcceptanceCriterionResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReorderAcceptanceCriteriaRequest {
+    /// Every criterion ID of the story, in the desired display order
+    pub order: Vec<Uuid>,
+}
+
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AcceptanceCriterionResponse {
     pub id: Uuid,
-    #[serde(rename = "storyId")]
     pub story_id: Uuid,
     pub description: String,
     pub given: String,
-    #[serde(rename = "whenClause")]
     pub when_clause: String,
-    #[serde(rename = "thenClause")]
     pub then_clause: String,
-    #[serde(rename = "createdAt")]
+    pub position: u32,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -149,60 +333,73 @@ impl From<(AcceptanceCriteria, Uuid)> for AcceptanceCriterionResponse {
             given: criteria.given,
             when_clause: criteria.when,
             then_clause: criteria.then,
+            position: criteria.position,
             created_at: criteria.created_at,
         }
     }
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct StoryResponse {
     pub id: Uuid,
-    #[serde(rename = "projectId")]
+    pub short_key: Option<String>,
     pub project_id: Uuid,
     pub title: String,
     pub description: Option<String>,
     pub status: String,
+    pub story_type: StoryType,
     pub labels: Vec<String>,
-    #[serde(rename = "storyPoints")]
     pub story_points: Option<u32>,
-    #[serde(rename = "sprintId")]
+    pub timebox_hours: Option<u32>,
     pub sprint_id: Option<Uuid>,
-    #[serde(rename = "assignedToUserId")]
     pub assigned_to_user_id: Option<Uuid>,
-    #[serde(rename = "readinessOverride")]
     pub readiness_override: bool,
-    #[serde(rename = "readinessOverrideBy")]
     pub readiness_override_by: Option<Uuid>,
-    #[serde(rename = "readinessOverrideReason")]
     pub readiness_override_reason: Option<String>,
-    #[serde(rename = "readinessOverrideAt")]
     pub readiness_override_at: Option<chrono::DateTime<chrono::Utc>>,
-    #[serde(rename = "createdAt")]
+    pub stale_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
-    #[serde(rename = "updatedAt")]
     pub updated_at: chrono::DateTime<chrono::Utc>,
-    #[serde(rename = "acceptanceCriteria")]
     pub acceptance_criteria: Vec<AcceptanceCriterionResponse>,
+    /// Set on stories another organization shared with the caller
+    pub read_only: bool,
+}
+
+impl StoryResponse {
+    /// The story as seen from `organization_id`, read-only if it belongs to
+    /// another organization that shared it
+    pub fn for_viewer(story: Story, organization_id: Option<Uuid>) -> Self {
+        let read_only = story.organization_id != organization_id;
+        Self {
+            read_only,
+            ..Self::from(story)
+        }
+    }
 }
 
 impl From<Story> for StoryResponse {
     fn from(story: Story) -> Self {
         let Story {
             id,
+            short_key,
             project_id,
             organization_id: _,
             title,
             description,
             status,
+            story_type,
             labels,
             acceptance_criteria,
             story_points,
+            timebox_hours,
             sprint_id,
             assigned_to_user_id,
             readiness_override,
             readiness_override_by,
             readiness_override_reason,
             readiness_override_at,
+            stale_at,
             created_at,
             updated_at,
         } = story;
@@ -214,28 +411,35 @@ impl From<Story> for StoryResponse {
 
         Self {
             id,
+            short_key,
             project_id,
             title,
             description,
             status: status.to_string(),
+            story_type,
             labels,
             story_points,
+            timebox_hours,
             sprint_id,
             assigned_to_user_id,
             readiness_override,
             readiness_override_by,
             readiness_override_reason,
             readiness_override_at,
+            stale_at,
             created_at,
             updated_at,
             acceptance_criteria,
+            read_only: false,
         }
     }
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TaskResponse {
     pub id: Uuid,
+    pub short_key: Option<String>,
     pub story_id: Uuid,
     pub title: String,
     pub description: Option<String>,
@@ -247,12 +451,14 @@ pub struct TaskResponse {
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub owned_at: Option<chrono::DateTime<chrono::Utc>>,
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub split_from_task_id: Option<Uuid>,
 }
 
 impl From<Task> for TaskResponse {
     fn from(task: Task) -> Self {
         Self {
             id: task.id,
+            short_key: task.short_key,
             story_id: task.story_id,
             title: task.title,
             description: task.description,
@@ -264,6 +470,7 @@ impl From<Task> for TaskResponse {
             updated_at: task.updated_at,
             owned_at: task.owned_at,
             completed_at: task.completed_at,
+            split_from_task_id: task.split_from_task_id,
         }
     }
 }
@@ -277,6 +484,7 @@ pub async fn create_story(
     let org_id = org_context.effective_organization_uuid();
     info!(%project_id, org_id = ?org_id, user_id = %auth.sub, "Creating story");
 
+    let created_by = resolve_user_id(&state.pool, &auth.sub).await.ok();
     let result = state
         .usecases
         .create_story(
@@ -285,6 +493,9 @@ pub async fn create_story(
             payload.title,
             payload.description,
             payload.labels.unwrap_or_default(),
+            payload.story_type,
+            payload.timebox_hours,
+            created_by,
         )
         .await;
 
@@ -308,12 +519,16 @@ pub async fn get_story(
     let org_id = org_context.effective_organization_uuid();
     info!(%id, org_id = ?org_id, user_id = %auth.sub, "Fetching story");
 
-    let result = state.usecases.get_story(id, org_id).await;
+    let result = state.usecases.get_readable_story(id, org_id).await;
 
     match result {
         Ok(Some(story)) => {
             info!(%id, org_id = ?org_id, user_id = %auth.sub, "Story fetched");
-            Ok(Json(StoryResponse::from(story)))
+            let response = StoryResponse::for_viewer(story, org_id);
+            let tag = EntityTag::new()
+                .version(response.id, response.updated_at)
+                .part(response.read_only);
+            Ok(Tagged(tag, Json(response)))
         }
         Ok(None) => {
             info!(%id, org_id = ?org_id, user_id = %auth.sub, "Story not found");
@@ -338,6 +553,7 @@ pub async fn update_story(
     let org_id = org_context.effective_organization_uuid();
     info!(%id, org_id = ?org_id, user_id = %auth.sub, "Updating story");
 
+    let edited_by = resolve_user_id(&state.pool, &auth.sub).await.ok();
     let result = state
         .usecases
         .update_story(
@@ -348,6 +564,9 @@ pub async fn update_story(
             payload.labels,
             payload.story_points,
             payload.sprint_id,
+            payload.story_type,
+            payload.timebox_hours.map(Some),
+            edited_by,
         )
         .await;
 
@@ -380,131 +599,631 @@ pub async fn override_story_ready(
     Ok(Json(StoryResponse::from(story)))
 }
 
-pub async fn create_task(
+#[derive(Debug, Deserialize)]
+pub struct ExportStoryQuery {
+    pub format: Option<String>,
+}
+
+/// Download a story as a markdown or PDF handout for offline refinement
+pub async fn export_story(
     AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
-    Path(story_id): Path<Uuid>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ExportStoryQuery>,
     State(state): State<Arc<BacklogAppState>>,
-    Json(payload): Json<CreateTaskRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let org_id = org_context.effective_organization_uuid();
-    info!(%story_id, org_id = ?org_id, user_id = %auth.sub, "Creating task");
+    let format = ExportFormat::parse(query.format.as_deref())?;
+    info!(%id, org_id = ?org_id, user_id = %auth.sub, ?format, "Exporting story");
 
-    let result = state
+    let story = state
         .usecases
-        .create_task(
-            story_id,
-            org_id,
-            payload.title,
-            payload.description,
-            payload.acceptance_criteria_refs,
-        )
-        .await;
+        .get_story(id, org_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Story with id {} not found", id)))?;
+    let tasks = state.usecases.get_tasks_by_story(id, org_id).await?;
+
+    let document = ExportDocument::for_story(&story, &tasks);
+    let bytes = format.render(&document);
+    let disposition = format!(
+        "attachment; filename=\"story-{}.{}\"",
+        id,
+        format.extension()
+    );
 
-    match result {
-        Ok(task_id) => {
-            info!(%story_id, %task_id, org_id = ?org_id, user_id = %auth.sub, "Task created");
-            Ok((StatusCode::CREATED, Json(CreateTaskResponse { task_id })))
-        }
-        Err(err) => {
-            error!(%story_id, org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to create task");
-            Err(err)
-        }
-    }
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from(bytes),
+    ))
 }
 
-pub async fn get_tasks_by_story(
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadgeTokenResponse {
+    pub token: String,
+    pub badge_url: String,
+}
+
+/// Create or rotate the secret token embedded in a project's public badge URL
+pub async fn rotate_readiness_badge_token(
     AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
-    Path(story_id): Path<Uuid>,
+    Path(project_id): Path<Uuid>,
     State(state): State<Arc<BacklogAppState>>,
 ) -> Result<impl IntoResponse, AppError> {
     let org_id = org_context.effective_organization_uuid();
-    info!(%story_id, org_id = ?org_id, user_id = %auth.sub, "Fetching tasks for story");
+    info!(%project_id, org_id = ?org_id, user_id = %auth.sub, "Rotating readiness badge token");
 
-    let result = state.usecases.get_tasks_by_story(story_id, org_id).await;
+    let token = state
+        .usecases
+        .rotate_badge_token(project_id, org_id)
+        .await?;
+    let badge_url = format!(
+        "/api/v1/public/projects/{}/readiness-badge.svg?token={}",
+        project_id, token
+    );
 
-    match result {
-        Ok(tasks) => {
-            let count = tasks.len();
-            info!(%story_id, org_id = ?org_id, user_id = %auth.sub, task_count = count, "Tasks fetched");
-            let task_responses: Vec<TaskResponse> =
-                tasks.into_iter().map(TaskResponse::from).collect();
-            Ok(Json(task_responses))
-        }
-        Err(err) => {
-            error!(%story_id, org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to fetch tasks for story");
-            Err(err)
-        }
+    Ok((
+        StatusCode::CREATED,
+        Json(BadgeTokenResponse { token, badge_url }),
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReadinessBadgeQuery {
+    pub token: Option<String>,
+    pub metric: Option<String>,
+}
+
+/// Badge values change slowly, so let browsers and CDNs (including GitHub's
+/// image proxy) cache them and serve stale copies while revalidating
+const BADGE_CACHE_CONTROL: &str =
+    "public, max-age=300, s-maxage=3600, stale-while-revalidate=86400";
+
+/// Public, token-authenticated SVG badge showing backlog readiness or sprint progress
+pub async fn get_readiness_badge(
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<ReadinessBadgeQuery>,
+    headers: HeaderMap,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Response, AppError> {
+    let token = query
+        .token
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| AppError::NotFound("Badge not found".to_string()))?;
+    let metric = BadgeMetric::parse(query.metric.as_deref())?;
+
+    let summary = state
+        .usecases
+        .get_readiness_badge(project_id, &token, metric)
+        .await?;
+    let svg = badge::render_svg(&summary);
+    let etag = badge::etag(&svg);
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value == etag);
+    if not_modified {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [
+                (header::ETAG, etag),
+                (header::CACHE_CONTROL, BADGE_CACHE_CONTROL.to_string()),
+            ],
+        )
+            .into_response());
     }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/svg+xml".to_string()),
+            (header::CACHE_CONTROL, BADGE_CACHE_CONTROL.to_string()),
+            (header::ETAG, etag),
+        ],
+        svg,
+    )
+        .into_response())
 }
 
-pub async fn get_available_tasks(
-    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
-    Path(story_id): Path<Uuid>,
+const DEFAULT_HEALTH_TREND_DAYS: u32 = 90;
+const MAX_HEALTH_TREND_DAYS: u32 = 365;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BacklogHealthQuery {
+    /// How many days of daily snapshots to return; defaults to 90
+    #[serde(alias = "trend_days")]
+    pub trend_days: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BacklogHealthResponse {
+    pub project_id: Uuid,
+    #[serde(flatten)]
+    pub health: BacklogHealth,
+    pub trend: Vec<BacklogHealthSnapshot>,
+}
+
+/// GET /api/v1/projects/{project_id}/backlog-health
+/// Composite backlog score with sub-scores and the daily trend
+pub async fn get_backlog_health(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<BacklogHealthQuery>,
     State(state): State<Arc<BacklogAppState>>,
-) -> Result<impl IntoResponse, AppError> {
-    let tasks = state
+) -> Result<Json<BacklogHealthResponse>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let trend_days = query.trend_days.unwrap_or(DEFAULT_HEALTH_TREND_DAYS);
+    if trend_days > MAX_HEALTH_TREND_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "trend_days cannot exceed {}",
+            MAX_HEALTH_TREND_DAYS
+        )));
+    }
+    info!(%project_id, org_id = ?org_id, user_id = %auth.sub, "Computing backlog health");
+
+    let (health, trend) = state
         .usecases
-        .get_available_tasks(story_id, org_context.effective_organization_uuid())
+        .get_backlog_health(project_id, org_id, trend_days)
         .await?;
-    let task_responses: Vec<TaskResponse> = tasks.into_iter().map(TaskResponse::from).collect();
-    Ok(Json(task_responses))
+
+    Ok(Json(BacklogHealthResponse {
+        project_id,
+        health,
+        trend,
+    }))
 }
 
-pub async fn get_user_owned_tasks(
+/// GET /api/v1/projects/{project_id}/dependency-graph
+/// Stories as nodes coloured by status, their dependencies and the critical path
+pub async fn get_dependency_graph(
     AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
     State(state): State<Arc<BacklogAppState>>,
-) -> Result<impl IntoResponse, AppError> {
-    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+) -> Result<Json<DependencyGraph>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%project_id, org_id = ?org_id, user_id = %auth.sub, "Building dependency graph");
 
-    let tasks = state
+    let graph = state
         .usecases
-        .get_user_owned_tasks(user_id, org_context.effective_organization_uuid())
+        .get_dependency_graph(project_id, org_id)
         .await?;
+    Ok(Json(graph))
+}
 
-    let task_responses: Vec<TaskResponse> = tasks.into_iter().map(TaskResponse::from).collect();
-    Ok(Json(task_responses))
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BugResponse {
+    pub story_id: Uuid,
+    #[serde(flatten)]
+    pub details: BugDetails,
+    pub sla: BugSla,
 }
 
-#[derive(Debug, serde::Deserialize)]
-pub struct GetRecommendedTasksQuery {
-    pub sprint_id: Option<Uuid>,
-    pub project_id: Option<Uuid>,
-    pub story_ids: Option<String>, // Comma-separated UUIDs
-    pub role: Option<String>,
-    pub exclude_mine: Option<bool>,
-    pub limit: Option<usize>,
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBugRequest {
+    pub severity: Option<BugSeverity>,
+    pub priority: Option<BugPriority>,
+    /// An empty string clears it
+    pub environment_found: Option<String>,
 }
 
-#[derive(Debug, serde::Serialize)]
-pub struct TaskRecommendationResponse {
-    pub task: TaskResponse,
-    pub score: f64,
-    pub reason: String,
+/// GET /api/v1/stories/{id}/bug
+/// A bug's severity, priority and environment with its SLA timers
+pub async fn get_bug(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<BugResponse>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let (details, sla) = state.usecases.get_bug(story_id, org_id).await?;
+    Ok(Json(BugResponse {
+        story_id,
+        details,
+        sla,
+    }))
 }
 
-pub async fn get_recommended_tasks(
+/// PATCH /api/v1/stories/{id}/bug
+/// Set a bug's severity, priority or environment; a priority triages it
+pub async fn update_bug(
     AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
-    Query(query): Query<GetRecommendedTasksQuery>,
+    Path(story_id): Path<Uuid>,
     State(state): State<Arc<BacklogAppState>>,
-) -> Result<impl IntoResponse, AppError> {
-    use crate::domain::RecommendationFilters;
+    Json(payload): Json<UpdateBugRequest>,
+) -> Result<Json<BugResponse>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%story_id, org_id = ?org_id, user_id = %auth.sub, "Updating bug details");
 
-    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+    let (details, sla) = state
+        .usecases
+        .update_bug(
+            story_id,
+            org_id,
+            payload.severity,
+            payload.priority,
+            payload.environment_found,
+        )
+        .await?;
+    Ok(Json(BugResponse {
+        story_id,
+        details,
+        sla,
+    }))
+}
 
-    // Parse story_ids if provided
-    let story_ids = if let Some(ids_str) = query.story_ids {
-        let parsed_ids: Result<Vec<Uuid>, _> = ids_str
-            .split(',')
-            .map(|s| s.trim().parse::<Uuid>())
-            .collect();
+/// GET /api/v1/bug-sla/targets
+/// The organization's time-to-triage and time-to-fix targets by severity
+pub async fn get_bug_sla_targets(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<SlaPolicy>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    Ok(Json(state.usecases.get_bug_sla_policy(org_id).await?))
+}
 
-        match parsed_ids {
-            Ok(ids) => Some(ids),
-            Err(_) => {
-                return Err(AppError::BadRequest(
-                    "Invalid story_ids format. Expected comma-separated UUIDs.".to_string(),
-                ))
-            }
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetBugSlaTargetsRequest {
+    pub triage_hours: u32,
+    pub fix_hours: u32,
+}
+
+/// PUT /api/v1/bug-sla/targets/{severity}
+/// Replace the targets for one severity, returning the whole policy
+pub async fn set_bug_sla_targets(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(severity): Path<String>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<SetBugSlaTargetsRequest>,
+) -> Result<Json<SlaPolicy>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let severity = BugSeverity::parse(&severity)
+        .ok_or_else(|| AppError::BadRequest(format!("Unknown severity '{}'", severity)))?;
+    let targets = SlaTargets::new(payload.triage_hours, payload.fix_hours)?;
+    info!(
+        org_id = ?org_id,
+        user_id = %auth.sub,
+        severity = severity.as_str(),
+        "Setting bug SLA targets"
+    );
+
+    let policy = state
+        .usecases
+        .set_bug_sla_targets(org_id, severity, targets)
+        .await?;
+    Ok(Json(policy))
+}
+
+const DEFAULT_SLA_REPORT_DAYS: u32 = 90;
+const MAX_SLA_REPORT_DAYS: u32 = 365;
+
+#[derive(Debug, Deserialize)]
+pub struct BugSlaReportQuery {
+    /// Bugs reported in the last this many days; defaults to 90
+    pub days: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BugSlaReportResponse {
+    pub project_id: Uuid,
+    pub since: chrono::DateTime<chrono::Utc>,
+    #[serde(flatten)]
+    pub report: SlaComplianceReport,
+}
+
+/// GET /api/v1/projects/{project_id}/bug-sla/report
+/// How many of the project's bugs met their triage and fix targets
+pub async fn get_bug_sla_report(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<BugSlaReportQuery>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<BugSlaReportResponse>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let days = query.days.unwrap_or(DEFAULT_SLA_REPORT_DAYS);
+    if days == 0 || days > MAX_SLA_REPORT_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "days must be between 1 and {}",
+            MAX_SLA_REPORT_DAYS
+        )));
+    }
+    info!(%project_id, org_id = ?org_id, user_id = %auth.sub, "Computing bug SLA report");
+
+    let since = chrono::Utc::now() - chrono::Duration::days(days as i64);
+    let report = state
+        .usecases
+        .get_bug_sla_report(project_id, org_id, since)
+        .await?;
+    Ok(Json(BugSlaReportResponse {
+        project_id,
+        since,
+        report,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareStoriesRequest {
+    pub organization_id: Uuid,
+    /// Stories to share; leave empty to share the whole project
+    #[serde(default)]
+    pub story_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorySharesQuery {
+    #[serde(default)]
+    pub include_revoked: bool,
+}
+
+/// POST /api/v1/projects/{project_id}/shares
+/// Share the project, or some of its stories, read-only with another organization.
+/// Owners and admins only.
+pub async fn share_stories(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<ShareStoriesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(
+        %project_id,
+        org_id = ?org_id,
+        user_id = %auth.sub,
+        shared_with = %payload.organization_id,
+        "Sharing stories"
+    );
+
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+    let shares = state
+        .usecases
+        .share_stories(
+            project_id,
+            org_id,
+            payload.organization_id,
+            payload.story_ids,
+            user_id,
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(shares)))
+}
+
+/// GET /api/v1/projects/{project_id}/shares
+pub async fn get_story_shares(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<StorySharesQuery>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<Vec<StoryShare>>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let shares = state
+        .usecases
+        .get_story_shares(project_id, org_id, query.include_revoked)
+        .await?;
+    Ok(Json(shares))
+}
+
+/// DELETE /api/v1/projects/{project_id}/shares/{share_id}
+/// Owners and admins only
+pub async fn revoke_story_share(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path((project_id, share_id)): Path<(Uuid, Uuid)>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<StoryShare>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%project_id, %share_id, org_id = ?org_id, user_id = %auth.sub, "Revoking story share");
+
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+    let share = state
+        .usecases
+        .revoke_story_share(project_id, org_id, share_id, user_id)
+        .await?;
+    Ok(Json(share))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedStoriesQuery {
+    #[serde(alias = "project_id")]
+    pub project_id: Option<Uuid>,
+}
+
+/// GET /api/v1/shared/stories
+/// Stories other organizations shared with the caller's, all read-only
+pub async fn get_shared_stories(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Query(query): Query<SharedStoriesQuery>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<Vec<StoryResponse>>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let stories = state
+        .usecases
+        .get_shared_stories(org_id, query.project_id)
+        .await?;
+    Ok(Json(
+        stories
+            .into_iter()
+            .map(|story| StoryResponse::for_viewer(story, org_id))
+            .collect(),
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveSearchQuery {
+    /// Words to find in titles and descriptions; omit for the latest archived items
+    pub q: Option<String>,
+    /// `story` or `task`; both when omitted
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub project_id: Option<Uuid>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// GET /api/v1/archive/search
+/// Stories and tasks moved to the archive after being accepted long ago
+pub async fn search_archive(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Query(query): Query<ArchiveSearchQuery>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<Vec<ArchivedItem>>, AppError> {
+    let kind = query
+        .kind
+        .as_deref()
+        .map(|value| {
+            ArchivedItemKind::parse(value)
+                .ok_or_else(|| AppError::BadRequest(format!("Invalid type filter: {}", value)))
+        })
+        .transpose()?;
+    let search = ArchiveSearch::new(query.q, kind, query.project_id, query.limit, query.offset)?;
+    let items = state
+        .usecases
+        .search_archive(org_context.effective_organization_uuid(), &search)
+        .await?;
+    Ok(Json(items))
+}
+
+pub async fn create_task(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<CreateTaskRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%story_id, org_id = ?org_id, user_id = %auth.sub, "Creating task");
+
+    let result = state
+        .usecases
+        .create_task(
+            story_id,
+            org_id,
+            payload.title,
+            payload.description,
+            payload.acceptance_criteria_refs,
+        )
+        .await;
+
+    match result {
+        Ok(task_id) => {
+            info!(%story_id, %task_id, org_id = ?org_id, user_id = %auth.sub, "Task created");
+            Ok((StatusCode::CREATED, Json(CreateTaskResponse { task_id })))
+        }
+        Err(err) => {
+            error!(%story_id, org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to create task");
+            Err(err)
+        }
+    }
+}
+
+pub async fn get_tasks_by_story(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%story_id, org_id = ?org_id, user_id = %auth.sub, "Fetching tasks for story");
+
+    let result = state.usecases.get_tasks_by_story(story_id, org_id).await;
+
+    match result {
+        Ok(tasks) => {
+            let count = tasks.len();
+            info!(%story_id, org_id = ?org_id, user_id = %auth.sub, task_count = count, "Tasks fetched");
+            let tag = tasks.iter().fold(EntityTag::new(), |tag, task| {
+                tag.version(task.id, task.updated_at)
+            });
+            let task_responses: Vec<TaskResponse> =
+                tasks.into_iter().map(TaskResponse::from).collect();
+            Ok(Tagged(tag, Json(task_responses)))
+        }
+        Err(err) => {
+            error!(%story_id, org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to fetch tasks for story");
+            Err(err)
+        }
+    }
+}
+
+pub async fn get_available_tasks(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let tasks = state
+        .usecases
+        .get_available_tasks(story_id, org_context.effective_organization_uuid())
+        .await?;
+    let task_responses: Vec<TaskResponse> = tasks.into_iter().map(TaskResponse::from).collect();
+    Ok(Json(task_responses))
+}
+
+pub async fn get_user_owned_tasks(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    let tasks = state
+        .usecases
+        .get_user_owned_tasks(user_id, org_context.effective_organization_uuid())
+        .await?;
+
+    let task_responses: Vec<TaskResponse> = tasks.into_iter().map(TaskResponse::from).collect();
+    Ok(Json(task_responses))
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRecommendedTasksQuery {
+    #[serde(alias = "sprint_id")]
+    pub sprint_id: Option<Uuid>,
+    #[serde(alias = "project_id")]
+    pub project_id: Option<Uuid>,
+    #[serde(alias = "story_ids")]
+    pub story_ids: Option<String>, // Comma-separated UUIDs
+    pub role: Option<String>,
+    #[serde(alias = "exclude_mine")]
+    pub exclude_mine: Option<bool>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct TaskRecommendationResponse {
+    pub task: TaskResponse,
+    pub score: f64,
+    pub reason: String,
+}
+
+pub async fn get_recommended_tasks(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Query(query): Query<GetRecommendedTasksQuery>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    use crate::domain::RecommendationFilters;
+
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    // Parse story_ids if provided
+    let story_ids = if let Some(ids_str) = query.story_ids {
+        let parsed_ids: Result<Vec<Uuid>, _> = ids_str
+            .split(',')
+            .map(|s| s.trim().parse::<Uuid>())
+            .collect();
+
+        match parsed_ids {
+            Ok(ids) => Some(ids),
+            Err(_) => {
+                return Err(AppError::BadRequest(
+                    "Invalid story_ids format. Expected comma-separated UUIDs.".to_string(),
+                ))
+            }
         }
     } else {
         None
@@ -578,7 +1297,7 @@ pub async fn release_task_ownership(
 ) -> Result<impl IntoResponse, AppError> {
     let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
 
-    let task = state
+    let (task, next_claimant) = state
         .usecases
         .release_task_ownership(task_id, org_context.effective_organization_uuid(), user_id)
         .await?;
@@ -594,6 +1313,14 @@ pub async fn release_task_ownership(
         };
         state.ws_manager.broadcast(event);
     }
+    if let Some(next_user_id) = next_claimant {
+        state.ws_manager.broadcast(TaskEvent::ClaimOffered {
+            task_id,
+            story_id: task.story_id,
+            user_id: next_user_id,
+            timestamp: chrono::Utc::now(),
+        });
+    }
 
     Ok((
         StatusCode::OK,
@@ -604,38 +1331,176 @@ pub async fn release_task_ownership(
     ))
 }
 
-pub async fn start_task_work(
+/// POST /api/v1/tasks/{task_id}/transfer
+/// Offer a task you own to someone else, keeping its status and estimate
+pub async fn propose_task_transfer(
     AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
     Path(task_id): Path<Uuid>,
     State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<TaskTransferRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
 
-    state
+    let (task, transfer) = state
         .usecases
-        .start_task_work(task_id, org_context.effective_organization_uuid(), user_id)
+        .propose_task_transfer(
+            task_id,
+            org_context.effective_organization_uuid(),
+            user_id,
+            payload.to_user_id,
+            payload.note,
+            payload.handoff,
+        )
         .await?;
+    info!(%task_id, transfer_id = %transfer.id, handoff = transfer.handoff, "Proposed task transfer");
 
-    Ok((
-        StatusCode::OK,
-        Json(TaskOwnershipResponse {
-            success: true,
-            message: "Task work started successfully".to_string(),
-        }),
-    ))
+    state.ws_manager.broadcast(TaskEvent::TransferProposed {
+        task_id,
+        story_id: task.story_id,
+        transfer_id: transfer.id,
+        from_user_id: transfer.from_user_id,
+        to_user_id: transfer.to_user_id,
+        handoff: transfer.handoff,
+        timestamp: chrono::Utc::now(),
+    });
+
+    Ok((StatusCode::CREATED, Json(transfer)))
 }
 
-pub async fn complete_task_work(
+/// POST /api/v1/tasks/{task_id}/transfer/accept
+pub async fn accept_task_transfer(
     AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
     Path(task_id): Path<Uuid>,
     State(state): State<Arc<BacklogAppState>>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
 
-    info!(
-        task_id = %task_id,
-        clerk_id = %auth.sub,
-        resolved_user_id = %user_id,
+    let (task, transfer) = state
+        .usecases
+        .accept_task_transfer(task_id, org_context.effective_organization_uuid(), user_id)
+        .await?;
+
+    state.ws_manager.broadcast(TaskEvent::TransferResolved {
+        task_id,
+        story_id: task.story_id,
+        transfer_id: transfer.id,
+        from_user_id: transfer.from_user_id,
+        to_user_id: transfer.to_user_id,
+        status: transfer.status,
+        timestamp: chrono::Utc::now(),
+    });
+    state.ws_manager.broadcast(TaskEvent::OwnershipTaken {
+        task_id,
+        story_id: task.story_id,
+        owner_user_id: user_id,
+        timestamp: chrono::Utc::now(),
+    });
+
+    Ok(Json(transfer))
+}
+
+/// POST /api/v1/tasks/{task_id}/transfer/decline
+/// Declines the pending transfer as its recipient, or withdraws it as the owner
+pub async fn decline_task_transfer(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(task_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    let (task, transfer) = state
+        .usecases
+        .decline_task_transfer(task_id, org_context.effective_organization_uuid(), user_id)
+        .await?;
+
+    state.ws_manager.broadcast(TaskEvent::TransferResolved {
+        task_id,
+        story_id: task.story_id,
+        transfer_id: transfer.id,
+        from_user_id: transfer.from_user_id,
+        to_user_id: transfer.to_user_id,
+        status: transfer.status,
+        timestamp: chrono::Utc::now(),
+    });
+
+    Ok(Json(transfer))
+}
+
+/// GET /api/v1/tasks/{task_id}/transfers
+pub async fn get_task_transfers(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(task_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let transfers = state
+        .usecases
+        .get_task_transfers(task_id, org_context.effective_organization_uuid())
+        .await?;
+    Ok(Json(transfers))
+}
+
+pub async fn join_task_claim_queue(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(task_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    let entry = state
+        .usecases
+        .join_claim_queue(task_id, org_context.effective_organization_uuid(), user_id)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+pub async fn leave_task_claim_queue(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(task_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    state
+        .usecases
+        .leave_claim_queue(task_id, org_context.effective_organization_uuid(), user_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn start_task_work(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(task_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    state
+        .usecases
+        .start_task_work(task_id, org_context.effective_organization_uuid(), user_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(TaskOwnershipResponse {
+            success: true,
+            message: "Task work started successfully".to_string(),
+        }),
+    ))
+}
+
+pub async fn complete_task_work(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(task_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    info!(
+        task_id = %task_id,
+        clerk_id = %auth.sub,
+        resolved_user_id = %user_id,
         org_id = ?org_context.effective_organization_uuid(),
         "Attempting to complete task work"
     );
@@ -681,6 +1546,56 @@ pub async fn set_task_estimate(
     ))
 }
 
+/// POST /api/v1/tasks/{task_id}/split
+pub async fn split_task(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(task_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<SplitTaskRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+    let proposed = payload.tasks.is_none();
+    info!(%task_id, org_id = ?org_id, user_id = %auth.sub, proposed, "Splitting task");
+
+    let old_status = state
+        .usecases
+        .get_task(task_id, org_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?
+        .status
+        .to_string();
+
+    let parts = payload
+        .tasks
+        .map(|tasks| tasks.into_iter().map(TaskSplitPart::from).collect());
+    let (original, children) = match state.usecases.split_task(task_id, org_id, parts).await {
+        Ok(split) => split,
+        Err(err) => {
+            error!(%task_id, org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to split task");
+            return Err(err);
+        }
+    };
+
+    info!(%task_id, org_id = ?org_id, user_id = %auth.sub, task_count = children.len(), "Task split");
+    state.ws_manager.broadcast(TaskEvent::StatusChanged {
+        task_id,
+        story_id: original.story_id,
+        old_status,
+        new_status: original.status.to_string(),
+        changed_by_user_id: user_id,
+        timestamp: chrono::Utc::now(),
+    });
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SplitTaskResponse {
+            original: TaskResponse::from(original),
+            tasks: children.into_iter().map(TaskResponse::from).collect(),
+        }),
+    ))
+}
+
 pub async fn update_task_status(
     AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
     Path(task_id): Path<Uuid>,
@@ -732,6 +1647,231 @@ pub async fn update_task_status(
     }
 }
 
+/// Error code and message for one failed entry of a batch. Unexpected
+/// failures are logged and reported without their details.
+fn batch_item_error(err: AppError) -> BatchItemError {
+    let (code, message) = match err {
+        AppError::NotFound(message) => ("NOT_FOUND".to_string(), message),
+        AppError::BadRequest(message) => ("BAD_REQUEST".to_string(), message),
+        AppError::Forbidden(message) => ("FORBIDDEN".to_string(), message),
+        AppError::Conflict(message) => ("CONFLICT".to_string(), message),
+        AppError::ConflictWithCode {
+            message,
+            error_code,
+        } => (error_code, message),
+        AppError::Localized { code, message, .. } => (code, message.render(current_locale())),
+        other => {
+            error!(error = %other, "Batch task status update failed");
+            (
+                "INTERNAL_SERVER_ERROR".to_string(),
+                "Internal server error".to_string(),
+            )
+        }
+    };
+    BatchItemError { code, message }
+}
+
+/// PATCH /api/v1/tasks/batch
+pub async fn batch_update_task_status(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<Vec<BatchTaskStatusItem>>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    let updates = payload
+        .into_iter()
+        .map(|item| {
+            let status = TaskStatus::from_str(&item.status).ok_or_else(|| {
+                AppError::invalid(
+                    LocalizedMessage::new("validation.invalid_value")
+                        .with("field", "status")
+                        .with("value", &item.status),
+                )
+            })?;
+            TaskStatusUpdate::new(item.task_id, status, item.note)
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    info!(org_id = ?org_id, user_id = %auth.sub, count = updates.len(), "Batch updating task status");
+
+    let outcomes = state
+        .usecases
+        .batch_update_task_status(org_id, user_id, updates)
+        .await?;
+
+    let mut changes = Vec::new();
+    let mut results = Vec::with_capacity(outcomes.len());
+    for (task_id, outcome) in outcomes {
+        results.push(match outcome {
+            Ok((task, old_status)) => {
+                if task.status != old_status {
+                    changes.push(TaskStatusChange {
+                        task_id,
+                        story_id: task.story_id,
+                        old_status: old_status.to_string(),
+                        new_status: task.status.to_string(),
+                    });
+                }
+                BatchTaskStatusResult {
+                    task_id,
+                    success: true,
+                    task: Some(TaskResponse::from(task)),
+                    error: None,
+                }
+            }
+            Err(err) => BatchTaskStatusResult {
+                task_id,
+                success: false,
+                task: None,
+                error: Some(batch_item_error(err)),
+            },
+        });
+    }
+
+    // One event for the whole batch so boards refresh once
+    if !changes.is_empty() {
+        state.ws_manager.broadcast(TaskEvent::BatchStatusChanged {
+            changes,
+            changed_by_user_id: user_id,
+            timestamp: chrono::Utc::now(),
+        });
+    }
+
+    let succeeded = results.iter().filter(|result| result.success).count();
+    Ok(Json(BatchTaskStatusResponse {
+        succeeded,
+        failed: results.len() - succeeded,
+        results,
+    }))
+}
+
+/// PATCH /api/v1/tasks/bulk
+/// Unlike the per-item batch, every task changes or none does
+pub async fn bulk_update_tasks(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<BulkTaskUpdateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    let status = payload
+        .status
+        .as_deref()
+        .map(|status| {
+            TaskStatus::from_str(status).ok_or_else(|| {
+                AppError::invalid(
+                    LocalizedMessage::new("validation.invalid_value")
+                        .with("field", "status")
+                        .with("value", status),
+                )
+            })
+        })
+        .transpose()?;
+    let estimated_hours = match (payload.estimated_hours, payload.clear_estimate) {
+        (Some(_), true) => {
+            return Err(AppError::BadRequest(
+                "Set an estimate or clear it, not both".to_string(),
+            ))
+        }
+        (Some(hours), false) => Some(Some(hours)),
+        (None, true) => Some(None),
+        (None, false) => None,
+    };
+    let update = TaskBulkUpdate::new(payload.task_ids, status, estimated_hours)?;
+
+    info!(org_id = ?org_id, user_id = %auth.sub, count = update.task_ids.len(), "Bulk updating tasks");
+
+    let tasks = state
+        .usecases
+        .bulk_update_tasks(org_id, user_id, update)
+        .await
+        .inspect_err(|err| {
+            error!(org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to bulk update tasks");
+        })?;
+
+    let changes: Vec<TaskStatusChange> = tasks
+        .iter()
+        .filter(|(task, old_status)| task.status != *old_status)
+        .map(|(task, old_status)| TaskStatusChange {
+            task_id: task.id,
+            story_id: task.story_id,
+            old_status: old_status.to_string(),
+            new_status: task.status.to_string(),
+        })
+        .collect();
+    if !changes.is_empty() {
+        state.ws_manager.broadcast(TaskEvent::BatchStatusChanged {
+            changes,
+            changed_by_user_id: user_id,
+            timestamp: chrono::Utc::now(),
+        });
+    }
+
+    Ok(Json(
+        tasks
+            .into_iter()
+            .map(|(task, _)| TaskResponse::from(task))
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// PATCH /api/v1/stories/bulk
+/// Every story changes or none does; the response lists them in request order
+pub async fn bulk_update_stories(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<BulkStoryUpdateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+
+    let status = payload
+        .status
+        .as_deref()
+        .map(|status| {
+            StoryStatus::from_str(status)
+                .ok_or_else(|| AppError::BadRequest(format!("Invalid status: {}", status)))
+        })
+        .transpose()?;
+    let sprint = match (payload.sprint_id, payload.remove_from_sprint) {
+        (Some(_), true) => {
+            return Err(AppError::BadRequest(
+                "Move the stories into a sprint or out of their sprints, not both".to_string(),
+            ))
+        }
+        (Some(sprint_id), false) => Some(SprintAssignment::Sprint(sprint_id)),
+        (None, true) => Some(SprintAssignment::Backlog),
+        (None, false) => None,
+    };
+    let update = StoryBulkUpdate::new(
+        payload.story_ids,
+        status,
+        payload.add_labels,
+        payload.remove_labels,
+        sprint,
+    )?;
+
+    info!(org_id = ?org_id, user_id = %auth.sub, count = update.story_ids.len(), "Bulk updating stories");
+
+    let stories = state
+        .usecases
+        .bulk_update_stories(org_id, user_id, update)
+        .await
+        .inspect_err(|err| {
+            error!(org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to bulk update stories");
+        })?;
+
+    Ok(Json(
+        stories
+            .into_iter()
+            .map(StoryResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
 pub async fn update_story_status(
     AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
     Path(id): Path<Uuid>,
@@ -758,15 +1898,27 @@ pub async fn update_story_status(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct DeleteStoryQuery {
+    /// Required when the organization's reason policy covers story deletion
+    pub reason: Option<String>,
+}
+
+/// DELETE /api/v1/stories/{id}?reason=
 pub async fn delete_story(
     AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
     Path(id): Path<Uuid>,
+    Query(query): Query<DeleteStoryQuery>,
     State(state): State<Arc<BacklogAppState>>,
 ) -> Result<impl IntoResponse, AppError> {
     let org_id = org_context.effective_organization_uuid();
     info!(%id, org_id = ?org_id, user_id = %auth.sub, "Deleting story");
 
-    let result = state.usecases.delete_story(id, org_id).await;
+    let deleted_by = resolve_user_id(&state.pool, &auth.sub).await.ok();
+    let result = state
+        .usecases
+        .delete_story(id, org_id, deleted_by, query.reason.as_deref())
+        .await;
 
     match result {
         Ok(_) => {
@@ -780,20 +1932,121 @@ pub async fn delete_story(
     }
 }
 
+/// POST /api/v1/stories/{id}/merge/{source_id}
+pub async fn merge_stories(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path((id, source_id)): Path<(Uuid, Uuid)>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<StoryMerge>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%id, %source_id, org_id = ?org_id, user_id = %auth.sub, "Merging duplicate story");
+
+    let merged_by = resolve_user_id(&state.pool, &auth.sub).await.ok();
+    match state
+        .usecases
+        .merge_stories(id, source_id, org_id, merged_by)
+        .await
+    {
+        Ok(merge) => {
+            info!(%id, %source_id, org_id = ?org_id, moved_tasks = merge.moved_task_ids.len(), "Duplicate story merged");
+            Ok(Json(merge))
+        }
+        Err(err) => {
+            error!(%id, %source_id, org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to merge duplicate story");
+            Err(err)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RenameLabelRequest {
+    pub to: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergeLabelRequest {
+    pub into: String,
+}
+
+/// POST /api/v1/projects/{id}/labels/{label}/rename
+pub async fn rename_label(
+    caller: AuthenticatedWithOrg,
+    Path((id, label)): Path<(Uuid, String)>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<RenameLabelRequest>,
+) -> Result<Json<LabelRename>, AppError> {
+    change_label(caller, &state, id, &label, &payload.to, false).await
+}
+
+/// POST /api/v1/projects/{id}/labels/{label}/merge
+pub async fn merge_label(
+    caller: AuthenticatedWithOrg,
+    Path((id, label)): Path<(Uuid, String)>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<MergeLabelRequest>,
+) -> Result<Json<LabelRename>, AppError> {
+    change_label(caller, &state, id, &label, &payload.into, true).await
+}
+
+async fn change_label(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    state: &BacklogAppState,
+    project_id: Uuid,
+    label: &str,
+    to: &str,
+    merge: bool,
+) -> Result<Json<LabelRename>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%project_id, %label, %to, merge, org_id = ?org_id, user_id = %auth.sub, "Changing project label");
+
+    let changed_by = resolve_user_id(&state.pool, &auth.sub).await.ok();
+    match state
+        .usecases
+        .change_project_label(project_id, org_id, label, to, merge, changed_by)
+        .await
+    {
+        Ok(change) => {
+            info!(%project_id, %label, %to, merge, stories = change.updated_story_ids.len(), "Project label changed");
+            Ok(Json(change))
+        }
+        Err(err) => {
+            error!(%project_id, %label, %to, merge, org_id = ?org_id, error = %err, "Failed to change project label");
+            Err(err)
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateSprintRequest {
     pub name: String,
     pub goal: String,
     pub stories: Vec<Uuid>,
-    #[serde(default)]
+    #[serde(default, alias = "capacity_points")]
     pub capacity_points: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateSprintResponse {
     pub sprint_id: Uuid,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddSprintStoryRequest {
+    #[serde(alias = "story_id")]
+    pub story_id: Uuid,
+}
+
+/// A sprint backlog entry: the story plus where it stands against the Ready bar
+#[derive(Debug, Serialize)]
+pub struct SprintStoryResponse {
+    #[serde(flatten)]
+    pub story: StoryResponse,
+    pub readiness: ReadinessAnnotation,
+}
+
 pub async fn create_sprint(
     AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
     Path(project_id): Path<Uuid>,
@@ -830,18 +2083,241 @@ pub async fn create_sprint(
     }
 }
 
-pub async fn get_stories_by_project(
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleSprintsRequest {
+    pub count: u32,
+    #[serde(default, alias = "length_days")]
+    pub length_days: Option<u32>,
+    /// Day sprints start on, e.g. "monday"
+    #[serde(default, alias = "start_weekday")]
+    pub start_weekday: Option<String>,
+    #[serde(default, alias = "capacity_points")]
+    pub capacity_points: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScheduleSprintsResponse {
+    pub sprints: Vec<ScheduledSprint>,
+}
+
+/// POST /api/v1/projects/{project_id}/sprints/schedule
+pub async fn schedule_sprints(
     AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
     Path(project_id): Path<Uuid>,
-    Query(query): Query<StoriesQuery>,
     State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<ScheduleSprintsRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let org_id = org_context.effective_organization_uuid();
-    info!(%project_id, org_id = ?org_id, user_id = %auth.sub, "Fetching project stories");
+    info!(%project_id, org_id = ?org_id, user_id = %auth.sub, count = payload.count, "Scheduling sprints");
 
-    let status_filter = if let Some(ref value) = query.status {
-        match StoryStatus::from_str(value) {
-            Some(status) => Some(status),
+    let defaults = state
+        .usecases
+        .get_sprint_cadence_settings(org_id)
+        .await?
+        .cadence;
+    let start_weekday = match payload.start_weekday.as_deref() {
+        Some(value) => value
+            .parse::<chrono::Weekday>()
+            .map_err(|_| AppError::BadRequest(format!("Invalid start weekday: {}", value)))?,
+        None => defaults.start_weekday,
+    };
+    let cadence = SprintCadence::new(
+        payload.length_days.unwrap_or(defaults.length_days),
+        start_weekday,
+    )?;
+
+    let sprints = state
+        .usecases
+        .schedule_sprints(
+            project_id,
+            org_id,
+            cadence,
+            payload.count,
+            payload.capacity_points,
+        )
+        .await
+        .map_err(|err| {
+            error!(%project_id, org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to schedule sprints");
+            err
+        })?;
+
+    info!(%project_id, org_id = ?org_id, scheduled = sprints.len(), "Sprints scheduled");
+    Ok((
+        StatusCode::CREATED,
+        Json(ScheduleSprintsResponse { sprints }),
+    ))
+}
+
+/// An organization's sprint cadence as sent and returned by the API
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintCadenceBody {
+    #[serde(alias = "length_days")]
+    pub length_days: u32,
+    /// Day sprints start on, e.g. "monday"
+    #[serde(alias = "start_weekday")]
+    pub start_weekday: String,
+    /// Complete ended sprints and start planned ones automatically
+    #[serde(default, alias = "auto_activate")]
+    pub auto_activate: bool,
+    #[serde(default)]
+    pub rollover: SprintRollover,
+}
+
+impl From<SprintCadenceSettings> for SprintCadenceBody {
+    fn from(settings: SprintCadenceSettings) -> Self {
+        let start_weekday = match settings.cadence.start_weekday {
+            chrono::Weekday::Mon => "monday",
+            chrono::Weekday::Tue => "tuesday",
+            chrono::Weekday::Wed => "wednesday",
+            chrono::Weekday::Thu => "thursday",
+            chrono::Weekday::Fri => "friday",
+            chrono::Weekday::Sat => "saturday",
+            chrono::Weekday::Sun => "sunday",
+        };
+        Self {
+            length_days: settings.cadence.length_days,
+            start_weekday: start_weekday.to_string(),
+            auto_activate: settings.auto_activate,
+            rollover: settings.rollover,
+        }
+    }
+}
+
+/// GET /api/v1/sprint-cadence
+pub async fn get_sprint_cadence(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<SprintCadenceBody>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let settings = state.usecases.get_sprint_cadence_settings(org_id).await?;
+    Ok(Json(settings.into()))
+}
+
+/// PUT /api/v1/sprint-cadence
+pub async fn set_sprint_cadence(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<SprintCadenceBody>,
+) -> Result<Json<SprintCadenceBody>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let start_weekday = payload
+        .start_weekday
+        .parse::<chrono::Weekday>()
+        .map_err(|_| {
+            AppError::BadRequest(format!("Invalid start weekday: {}", payload.start_weekday))
+        })?;
+    let settings = SprintCadenceSettings {
+        cadence: SprintCadence::new(payload.length_days, start_weekday)?,
+        auto_activate: payload.auto_activate,
+        rollover: payload.rollover,
+    };
+    info!(
+        org_id = ?org_id,
+        user_id = %auth.sub,
+        length_days = payload.length_days,
+        auto_activate = payload.auto_activate,
+        rollover = payload.rollover.as_str(),
+        "Setting sprint cadence"
+    );
+
+    let settings = state
+        .usecases
+        .set_sprint_cadence_settings(org_id, settings)
+        .await?;
+    Ok(Json(settings.into()))
+}
+
+/// GET /api/v1/sprints/{sprint_id}/stories
+pub async fn get_sprint_stories(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(sprint_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%sprint_id, org_id = ?org_id, user_id = %auth.sub, "Fetching sprint stories");
+
+    let stories = state.usecases.get_sprint_stories(sprint_id, org_id).await?;
+    // Readiness annotations derive from the stories, so their versions cover them
+    let tag = stories.iter().fold(EntityTag::new(), |tag, story| {
+        tag.version(story.id, story.updated_at)
+    });
+    let response: Vec<SprintStoryResponse> = stories
+        .into_iter()
+        .map(|story| SprintStoryResponse {
+            readiness: story.readiness_annotation(),
+            story: StoryResponse::from(story),
+        })
+        .collect();
+
+    Ok(Tagged(tag, Json(response)))
+}
+
+/// POST /api/v1/sprints/{sprint_id}/stories
+pub async fn add_story_to_sprint(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(sprint_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<AddSprintStoryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let story_id = payload.story_id;
+    info!(%sprint_id, %story_id, org_id = ?org_id, user_id = %auth.sub, "Adding story to sprint");
+
+    match state
+        .usecases
+        .add_story_to_sprint(sprint_id, org_id, story_id)
+        .await
+    {
+        Ok(story) => {
+            info!(%sprint_id, %story_id, org_id = ?org_id, user_id = %auth.sub, "Story added to sprint");
+            Ok(Json(StoryResponse::from(story)))
+        }
+        Err(err) => {
+            error!(%sprint_id, %story_id, org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to add story to sprint");
+            Err(err)
+        }
+    }
+}
+
+/// DELETE /api/v1/sprints/{sprint_id}/stories/{story_id}
+pub async fn remove_story_from_sprint(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path((sprint_id, story_id)): Path<(Uuid, Uuid)>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%sprint_id, %story_id, org_id = ?org_id, user_id = %auth.sub, "Removing story from sprint");
+
+    match state
+        .usecases
+        .remove_story_from_sprint(sprint_id, org_id, story_id)
+        .await
+    {
+        Ok(story) => {
+            info!(%sprint_id, %story_id, org_id = ?org_id, user_id = %auth.sub, "Story removed from sprint");
+            Ok(Json(StoryResponse::from(story)))
+        }
+        Err(err) => {
+            error!(%sprint_id, %story_id, org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to remove story from sprint");
+            Err(err)
+        }
+    }
+}
+
+pub async fn get_stories_by_project(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<StoriesQuery>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%project_id, org_id = ?org_id, user_id = %auth.sub, "Fetching project stories");
+
+    let status_filter = if let Some(ref value) = query.status {
+        match StoryStatus::from_str(value) {
+            Some(status) => Some(status),
             None => {
                 return Err(AppError::BadRequest(format!(
                     "Invalid status filter: {}",
@@ -853,18 +2329,68 @@ pub async fn get_stories_by_project(
         None
     };
 
+    let type_filter = query
+        .story_type
+        .as_deref()
+        .map(|value| {
+            StoryType::parse(value)
+                .ok_or_else(|| AppError::BadRequest(format!("Invalid type filter: {}", value)))
+        })
+        .transpose()?;
+    let readiness_filter = query
+        .readiness
+        .as_deref()
+        .map(|value| {
+            ReadinessState::parse(value)
+                .ok_or_else(|| AppError::BadRequest(format!("Invalid readiness filter: {}", value)))
+        })
+        .transpose()?;
+    let labels = query
+        .labels
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .map(|label| StoryCondition::Label(label.to_string()));
+
+    let conditions: Vec<StoryFilter> = status_filter
+        .map(StoryCondition::Status)
+        .into_iter()
+        .chain(query.sprint_id.map(StoryCondition::Sprint))
+        .chain(type_filter.map(StoryCondition::Type))
+        .chain(labels)
+        .chain(query.assignee.map(StoryCondition::Assignee))
+        .chain(readiness_filter.map(StoryCondition::Readiness))
+        .map(StoryFilter::Condition)
+        .chain(query.q.as_deref().map(StoryFilter::parse).transpose()?)
+        .collect();
+    let mut story_query = StoryQuery::default().paged(query.limit, query.offset)?;
+    if !conditions.is_empty() {
+        story_query = story_query.with_filter(StoryFilter::all(conditions));
+    }
+    if let Some(sort) = query.sort.as_deref() {
+        story_query = story_query.sorted_by(StorySort::parse(sort)?);
+    }
+
     let result = state
         .usecases
-        .get_stories_by_project(project_id, org_id, status_filter, query.sprint_id)
+        .get_stories_by_project(project_id, org_id, &story_query)
         .await;
 
     match result {
-        Ok(stories) => {
-            let count = stories.len();
-            info!(%project_id, org_id = ?org_id, user_id = %auth.sub, story_count = count, "Fetched project stories");
-            let story_responses: Vec<StoryResponse> =
-                stories.into_iter().map(StoryResponse::from).collect();
-            Ok(Json(story_responses))
+        Ok(page) => {
+            let count = page.stories.len();
+            info!(%project_id, org_id = ?org_id, user_id = %auth.sub, story_count = count, total = page.total, "Fetched project stories");
+            let story_responses: Vec<StoryResponse> = page
+                .stories
+                .into_iter()
+                .map(|story| StoryResponse::for_viewer(story, org_id))
+                .collect();
+            Ok((
+                [(TOTAL_COUNT_HEADER, page.total.to_string())],
+                Json(story_responses),
+            ))
         }
         Err(err) => {
             error!(%project_id, org_id = ?org_id, user_id = %auth.sub, error = %err, "Failed to fetch project stories");
@@ -878,15 +2404,19 @@ pub async fn get_acceptance_criteria(
     Path(story_id): Path<Uuid>,
     State(state): State<Arc<BacklogAppState>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let criteria = state
+    let story = state
         .usecases
-        .get_acceptance_criteria(story_id, org_context.effective_organization_uuid())
-        .await?;
-    let criteria_responses: Vec<AcceptanceCriterionResponse> = criteria
+        .get_story(story_id, org_context.effective_organization_uuid())
+        .await?
+        .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+    // Criteria are saved with their story, which bumps its version
+    let tag = EntityTag::new().version(story.id, story.updated_at);
+    let criteria_responses: Vec<AcceptanceCriterionResponse> = story
+        .acceptance_criteria
         .into_iter()
         .map(|c| AcceptanceCriterionResponse::from((c, story_id)))
         .collect();
-    Ok(Json(criteria_responses))
+    Ok(Tagged(tag, Json(criteria_responses)))
 }
 
 pub async fn create_acceptance_criterion(
@@ -949,6 +2479,257 @@ pub async fn delete_acceptance_criterion(
     Ok(StatusCode::OK)
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyAcceptanceCriterionRequest {
+    pub kind: VerificationKind,
+    pub passed: bool,
+    #[serde(default, alias = "evidence_url")]
+    pub evidence_url: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// POST /api/v1/stories/{id}/acceptance-criteria/{criterion_id}/verifications
+/// Record a test result or manual check of a criterion
+pub async fn verify_acceptance_criterion(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path((story_id, criterion_id)): Path<(Uuid, Uuid)>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<VerifyAcceptanceCriterionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let verified_by = resolve_user_id(&state.pool, &auth.sub).await.ok();
+    let verification = CriterionVerification::new(
+        story_id,
+        criterion_id,
+        payload.kind,
+        payload.passed,
+        payload.evidence_url,
+        payload.note,
+        verified_by,
+    )?;
+    info!(%story_id, %criterion_id, kind = verification.kind.as_str(), passed = verification.passed, user_id = %auth.sub, "Verifying acceptance criterion");
+
+    let verification = state
+        .usecases
+        .verify_acceptance_criterion(
+            story_id,
+            org_context.effective_organization_uuid(),
+            verification,
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(verification)))
+}
+
+/// GET /api/v1/stories/{id}/acceptance-criteria/verifications
+pub async fn get_criterion_verifications(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<Vec<CriterionVerification>>, AppError> {
+    let verifications = state
+        .usecases
+        .get_criterion_verifications(story_id, org_context.effective_organization_uuid())
+        .await?;
+    Ok(Json(verifications))
+}
+
+/// GET /api/v1/task-completion-policy
+pub async fn get_task_completion_policy(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<TaskCompletionPolicy>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    Ok(Json(
+        state.usecases.get_task_completion_policy(org_id).await?,
+    ))
+}
+
+/// PUT /api/v1/task-completion-policy
+pub async fn set_task_completion_policy(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<TaskCompletionPolicy>,
+) -> Result<Json<TaskCompletionPolicy>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(
+        org_id = ?org_id,
+        user_id = %auth.sub,
+        require_criteria_coverage = payload.require_criteria_coverage,
+        "Setting task completion policy"
+    );
+    Ok(Json(
+        state
+            .usecases
+            .set_task_completion_policy(org_id, payload)
+            .await?,
+    ))
+}
+
+/// GET /api/v1/reason-policy
+pub async fn get_reason_policy(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<ReasonPolicy>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    Ok(Json(state.usecases.get_reason_policy(org_id).await?))
+}
+
+/// PUT /api/v1/reason-policy
+pub async fn set_reason_policy(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<ReasonPolicy>,
+) -> Result<Json<ReasonPolicy>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(
+        org_id = ?org_id,
+        user_id = %auth.sub,
+        require_reason_for = ?payload.require_reason_for,
+        "Setting reason policy"
+    );
+    Ok(Json(
+        state.usecases.set_reason_policy(org_id, payload).await?,
+    ))
+}
+
+/// A browser's `PushSubscription.toJSON()`
+#[derive(Debug, Deserialize)]
+pub struct PushSubscriptionRequest {
+    pub endpoint: String,
+    pub keys: PushSubscriptionKeys,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PushSubscriptionKeys {
+    pub p256dh: String,
+    pub auth: String,
+}
+
+/// GET /api/v1/notifications/vapid-public-key
+/// The application server key browsers pass to `PushManager.subscribe`
+pub async fn get_vapid_public_key(
+    _auth: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let public_key = state
+        .usecases
+        .push_application_server_key()
+        .ok_or_else(|| AppError::NotFound("Push notifications are not configured".to_string()))?;
+    Ok(Json(serde_json::json!({ "publicKey": public_key })))
+}
+
+/// POST /api/v1/notifications/push-subscriptions
+/// Send the caller task assignments, blocked work and acceptance requests as
+/// push notifications in this browser
+pub async fn create_push_subscription(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<PushSubscriptionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+    let subscription = PushSubscription::new(
+        user_id,
+        payload.endpoint,
+        payload.keys.p256dh,
+        payload.keys.auth,
+    )?;
+    let subscription = state.usecases.subscribe_to_push(subscription).await?;
+    info!(%user_id, subscription_id = %subscription.id, "Stored push subscription");
+
+    Ok((StatusCode::CREATED, Json(subscription)))
+}
+
+/// DELETE /api/v1/notifications/push-subscriptions/{id}
+pub async fn delete_push_subscription(
+    AuthenticatedWithOrg { auth, .. }: AuthenticatedWithOrg,
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+    state.usecases.unsubscribe_from_push(id, user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn bulk_update_acceptance_criteria(
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<BulkAcceptanceCriteriaRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(
+        %story_id,
+        org_id = ?org_id,
+        user_id = %auth.sub,
+        creates = payload.create.len(),
+        updates = payload.update.len(),
+        deletes = payload.delete.len(),
+        "Applying bulk acceptance criteria changes"
+    );
+
+    let batch = AcceptanceCriteriaBatch {
+        create: payload
+            .create
+            .into_iter()
+            .map(|c| NewAcceptanceCriterion {
+                given: c.given,
+                when: c.when,
+                then: c.then,
+            })
+            .collect(),
+        update: payload
+            .update
+            .into_iter()
+            .map(|u| AcceptanceCriterionEdit {
+                id: u.id,
+                given: u.given,
+                when: u.when,
+                then: u.then,
+            })
+            .collect(),
+        delete: payload.delete,
+    };
+
+    let (created, criteria) = state
+        .usecases
+        .apply_acceptance_criteria_batch(story_id, org_id, batch)
+        .await?;
+
+    Ok(Json(BulkAcceptanceCriteriaResponse {
+        created,
+        criteria: criteria
+            .into_iter()
+            .map(|c| AcceptanceCriterionResponse::from((c, story_id)))
+            .collect(),
+    }))
+}
+
+pub async fn reorder_acceptance_criteria(
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<ReorderAcceptanceCriteriaRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(
+        %story_id,
+        org_id = ?org_id,
+        user_id = %auth.sub,
+        "Reordering acceptance criteria"
+    );
+
+    let criteria = state
+        .usecases
+        .reorder_acceptance_criteria(story_id, org_id, payload.order)
+        .await?;
+    let criteria_responses: Vec<AcceptanceCriterionResponse> = criteria
+        .into_iter()
+        .map(|c| AcceptanceCriterionResponse::from((c, story_id)))
+        .collect();
+    Ok(Json(criteria_responses))
+}
+
 // Sprint Task Board DTOs and Handler
 
 #[derive(Debug, Serialize)]
@@ -956,7 +2737,11 @@ pub async fn delete_acceptance_criterion(
 pub struct SprintTaskBoardResponse {
     pub sprint: SprintMetadata,
     pub tasks: Vec<SprintTaskView>,
+    /// Lanes keyed by lane key, with `none` for tasks without one
     pub groups: serde_json::Value,
+    /// How the tasks were split into lanes
+    pub swimlanes: Swimlanes,
+    pub lanes: Vec<Swimlane>,
 }
 
 #[derive(Debug, Serialize)]
@@ -973,6 +2758,10 @@ pub struct SprintMetadata {
     pub total_tasks: i64,
     pub completed_tasks: i64,
     pub progress_percentage: f64,
+    pub total_points: i64,
+    /// Points of accepted stories
+    pub completed_points: i64,
+    pub stats_freshness: StatsFreshness,
 }
 
 #[derive(Debug, Serialize)]
@@ -992,27 +2781,436 @@ pub struct SprintTaskView {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SprintTaskBoardQuery {
     pub status: Option<String>,
-    #[serde(rename = "groupBy")]
+    /// Overrides the user's saved swimlanes for this request
     pub group_by: Option<String>,
+    pub custom_field: Option<String>,
 }
 
+/// GET /api/v1/sprints/{sprint_id}/tasks
 pub async fn get_sprint_task_board(
-    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
     Path(sprint_id): Path<Uuid>,
     Query(query): Query<SprintTaskBoardQuery>,
     State(state): State<Arc<BacklogAppState>>,
 ) -> Result<impl IntoResponse, AppError> {
+    let swimlanes = match query.group_by.as_deref() {
+        Some(group_by) => Some(Swimlanes::new(
+            SwimlaneGrouping::parse(group_by).ok_or_else(|| {
+                AppError::BadRequest(format!("Unknown swimlane grouping: {}", group_by))
+            })?,
+            query.custom_field,
+        )?),
+        None => None,
+    };
+    let user_id = match swimlanes {
+        Some(_) => None,
+        None => resolve_user_id(&state.pool, &auth.sub).await.ok(),
+    };
+
     let response = state
         .usecases
         .get_sprint_task_board(
             sprint_id,
             org_context.effective_organization_uuid(),
             query.status,
-            query.group_by,
+            swimlanes,
+            user_id,
         )
         .await?;
 
     Ok(Json(response))
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardConfigurationRequest {
+    pub group_by: SwimlaneGrouping,
+    pub custom_field: Option<String>,
+}
+
+/// GET /api/v1/task-board-configuration
+pub async fn get_board_configuration(
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<BoardConfiguration>, AppError> {
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+    Ok(Json(
+        state
+            .usecases
+            .get_board_configuration(user_id, org_context.effective_organization_uuid())
+            .await?,
+    ))
+}
+
+/// PUT /api/v1/task-board-configuration
+pub async fn set_board_configuration(
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<BoardConfigurationRequest>,
+) -> Result<Json<BoardConfiguration>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let user_id = resolve_user_id(&state.pool, &auth.sub).await?;
+    let swimlanes = Swimlanes::new(payload.group_by, payload.custom_field)?;
+    info!(
+        org_id = ?org_id,
+        %user_id,
+        group_by = swimlanes.group_by.as_str(),
+        "Saving task board configuration"
+    );
+    Ok(Json(
+        state
+            .usecases
+            .set_board_configuration(user_id, org_id, swimlanes)
+            .await?,
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StandupSummaryQuery {
+    /// Start of the reporting window (RFC 3339); defaults to 24 hours ago
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Include an LLM-written narrative when a narrator is configured
+    #[serde(default)]
+    pub narrative: bool,
+    /// `json` (default) or `slack` for an incoming-webhook payload
+    pub format: Option<String>,
+}
+
+pub async fn get_standup_summary(
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
+    Path(sprint_id): Path<Uuid>,
+    Query(query): Query<StandupSummaryQuery>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Response, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let slack = wants_slack(query.format.as_deref(), "standup")?;
+    info!(
+        %sprint_id,
+        org_id = ?org_id,
+        user_id = %auth.sub,
+        since = ?query.since,
+        "Generating standup summary"
+    );
+
+    let summary = state
+        .usecases
+        .get_standup_summary(sprint_id, org_id, query.since, query.narrative)
+        .await?;
+
+    if slack {
+        let text = summary
+            .narrative
+            .clone()
+            .unwrap_or_else(|| summary.digest());
+        return Ok(Json(serde_json::json!({ "text": text })).into_response());
+    }
+
+    Ok(Json(summary).into_response())
+}
+
+/// Whether a report was asked for as a Slack incoming-webhook payload
+fn wants_slack(format: Option<&str>, report: &str) -> Result<bool, AppError> {
+    match format {
+        None | Some("json") => Ok(false),
+        Some("slack") => Ok(true),
+        Some(other) => Err(AppError::BadRequest(format!(
+            "Unsupported {} format '{}'; expected 'json' or 'slack'",
+            report, other
+        ))),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SprintHealthQuery {
+    /// `json` (default) or `slack` for an incoming-webhook payload
+    pub format: Option<String>,
+}
+
+/// GET /api/v1/sprints/{sprint_id}/health
+/// Mid-sprint risk with a red/amber/green rating and recommendations
+pub async fn get_sprint_health(
+    AuthenticatedWithOrg { auth, org_context }: AuthenticatedWithOrg,
+    Path(sprint_id): Path<Uuid>,
+    Query(query): Query<SprintHealthQuery>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Response, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let slack = wants_slack(query.format.as_deref(), "sprint health")?;
+    info!(%sprint_id, org_id = ?org_id, user_id = %auth.sub, "Checking sprint health");
+
+    let health = state.usecases.get_sprint_health(sprint_id, org_id).await?;
+
+    if slack {
+        return Ok(Json(serde_json::json!({ "text": health.digest() })).into_response());
+    }
+
+    Ok(Json(health).into_response())
+}
+
+/// GET /api/v1/resolve/{key}
+/// Resolves a short key like `PROJ-123` to the story or task it names
+pub async fn resolve_short_key(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(key): Path<String>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let key = key.trim().to_uppercase();
+    info!(%key, org_id = ?org_id, user_id = %auth.sub, "Resolving short key");
+
+    if !is_short_key(&key) {
+        return Err(AppError::BadRequest(format!(
+            "'{}' is not a valid key; expected something like PROJ-123",
+            key
+        )));
+    }
+
+    let resolved = state
+        .usecases
+        .resolve_short_key(&key, org_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("No story or task with key {}", key)))?;
+
+    Ok(Json(resolved))
+}
+
+/// GET /api/v1/stories/{id}/references
+/// Links between this story (or its tasks) and other items, commits, pull requests and comments
+pub async fn get_story_references(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%id, org_id = ?org_id, user_id = %auth.sub, "Fetching story references");
+
+    let references = state.usecases.get_story_references(id, org_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "storyId": id,
+        "references": references,
+    })))
+}
+
+/// GET /api/v1/stories/{id}/dependencies
+/// The stories this story blocks and the ones it is blocked by
+pub async fn get_story_dependencies(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<StoryDependencies>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%id, org_id = ?org_id, user_id = %auth.sub, "Fetching story dependencies");
+
+    let dependencies = state.usecases.get_story_dependencies(id, org_id).await?;
+    Ok(Json(dependencies))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateStoryDependencyRequest {
+    #[serde(alias = "story_id")]
+    pub story_id: Uuid,
+    /// How the other story relates to this one: `blocks` or `blocked_by`
+    #[serde(rename = "type")]
+    pub direction: DependencyDirection,
+}
+
+/// POST /api/v1/stories/{id}/dependencies
+/// Record that this story blocks, or is blocked by, another story
+pub async fn create_story_dependency(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<CreateStoryDependencyRequest>,
+) -> Result<(StatusCode, Json<StoryDependency>), AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%id, other_id = %payload.story_id, direction = ?payload.direction, org_id = ?org_id, user_id = %auth.sub, "Adding story dependency");
+
+    let created_by = resolve_user_id(&state.pool, &auth.sub).await.ok();
+    let dependency = state
+        .usecases
+        .add_story_dependency(id, payload.story_id, payload.direction, org_id, created_by)
+        .await?;
+    Ok((StatusCode::CREATED, Json(dependency)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteStoryDependencyQuery {
+    #[serde(rename = "type")]
+    pub direction: DependencyDirection,
+}
+
+/// DELETE /api/v1/stories/{id}/dependencies/{other_id}?type=blocks|blocked_by
+pub async fn delete_story_dependency(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path((id, other_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<DeleteStoryDependencyQuery>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<StatusCode, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%id, %other_id, direction = ?query.direction, org_id = ?org_id, user_id = %auth.sub, "Removing story dependency");
+
+    state
+        .usecases
+        .remove_story_dependency(id, other_id, query.direction, org_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/v1/stories/{id}/revisions
+/// Title, description and labels after each edit, newest first
+pub async fn get_story_revisions(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%id, org_id = ?org_id, user_id = %auth.sub, "Fetching story revisions");
+
+    let revisions = state.usecases.get_story_revisions(id, org_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "storyId": id,
+        "revisions": revisions,
+    })))
+}
+
+/// GET /api/v1/stories/{id}/revisions/{from}/diff/{to}
+/// Field-level diff between two revisions
+pub async fn get_story_revision_diff(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path((id, from, to)): Path<(Uuid, u32, u32)>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<StoryRevisionDiff>, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%id, from, to, org_id = ?org_id, user_id = %auth.sub, "Diffing story revisions");
+
+    let diff = state
+        .usecases
+        .diff_story_revisions(id, org_id, from, to)
+        .await?;
+
+    Ok(Json(diff))
+}
+
+/// POST /api/v1/webhooks/github
+/// Links stories and tasks mentioned by key in pushed commits, pull requests and comments
+pub async fn github_webhook(
+    headers: HeaderMap,
+    State(state): State<Arc<BacklogAppState>>,
+    body: axum::body::Bytes,
+) -> Result<impl IntoResponse, AppError> {
+    let verifier = GithubWebhookVerifier::from_env().ok_or_else(|| {
+        warn!("GitHub webhook received but GITHUB_WEBHOOK_SECRET is not set");
+        AppError::NotFound("GitHub webhook is not configured".to_string())
+    })?;
+    let signature = headers
+        .get("x-hub-signature-256")
+        .and_then(|value| value.to_str().ok());
+    verifier.verify(&body, signature)?;
+
+    let event = headers
+        .get("x-github-event")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let payload: serde_json::Value = serde_json::from_slice(&body)
+        .map_err(|_| AppError::BadRequest("Webhook payload is not valid JSON".to_string()))?;
+
+    let mentions = github_mentions(event, &payload);
+    let linked = state.usecases.record_external_mentions(&mentions).await?;
+    info!(%event, mentions = mentions.len(), linked, "Processed GitHub webhook");
+
+    Ok(Json(serde_json::json!({ "linked": linked })))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartImportRequest {
+    /// `csv`, `jira` or `text`
+    pub format: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportJobResponse {
+    #[serde(flatten)]
+    pub job: ImportJob,
+    pub percent_complete: u8,
+    /// The first items that could not be imported
+    pub errors: Vec<ImportItemError>,
+}
+
+/// POST /api/v1/projects/{project_id}/imports
+/// Queue an import of stories; progress is reported at `/api/v1/imports/{job_id}`
+pub async fn start_import(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+    Json(payload): Json<StartImportRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    let format = ImportFormat::parse(&payload.format).ok_or_else(|| {
+        AppError::BadRequest(format!("Unknown import format: {}", payload.format))
+    })?;
+    info!(%project_id, org_id = ?org_id, user_id = %auth.sub, format = format.as_str(), "Starting import");
+
+    let created_by = resolve_user_id(&state.pool, &auth.sub).await.ok();
+    let job = state
+        .usecases
+        .start_import(project_id, org_id, created_by, format, &payload.content)
+        .await?;
+    info!(%project_id, job_id = %job.id, items = job.total_items, "Import queued");
+    let percent_complete = job.percent_complete();
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ImportJobResponse {
+            job,
+            percent_complete,
+            errors: Vec::new(),
+        }),
+    ))
+}
+
+/// GET /api/v1/imports/{job_id}
+pub async fn get_import_job(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(job_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<Json<ImportJobResponse>, AppError> {
+    let (job, errors) = state
+        .usecases
+        .get_import_job(job_id, org_context.effective_organization_uuid())
+        .await?;
+    Ok(Json(ImportJobResponse {
+        percent_complete: job.percent_complete(),
+        job,
+        errors,
+    }))
+}
+
+/// POST /api/v1/imports/{job_id}/resume
+/// Queue a failed import again from the first item it had not processed
+pub async fn resume_import(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(job_id): Path<Uuid>,
+    State(state): State<Arc<BacklogAppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = org_context.effective_organization_uuid();
+    info!(%job_id, org_id = ?org_id, user_id = %auth.sub, "Resuming import");
+
+    state.usecases.resume_import(job_id, org_id).await?;
+    let (job, errors) = state.usecases.get_import_job(job_id, org_id).await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ImportJobResponse {
+            percent_complete: job.percent_complete(),
+            job,
+            errors,
+        }),
+    ))
+}
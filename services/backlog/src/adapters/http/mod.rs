@@ -1,4 +1,7 @@
 pub mod handlers;
+pub mod routes;
+pub mod short_keys;
 pub mod state;
 
+pub use short_keys::with_short_key_paths;
 pub use state::BacklogAppState;
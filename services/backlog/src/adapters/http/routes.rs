@@ -0,0 +1,316 @@
+use crate::adapters::archiver::spawn_story_archiver;
+use crate::adapters::http::handlers::{
+    accept_task_transfer, add_story_to_sprint, batch_update_task_status,
+    bulk_update_acceptance_criteria, bulk_update_stories, bulk_update_tasks, complete_task_work,
+    create_acceptance_criterion, create_push_subscription, create_sprint, create_story,
+    create_story_dependency, create_task, decline_task_transfer, delete_acceptance_criterion,
+    delete_push_subscription, delete_story, delete_story_dependency, export_story,
+    get_acceptance_criteria, get_available_tasks, get_backlog_health, get_board_configuration,
+    get_bug, get_bug_sla_report, get_bug_sla_targets, get_criterion_verifications,
+    get_dependency_graph, get_import_job, get_readiness_badge, get_reason_policy,
+    get_recommended_tasks, get_shared_stories, get_sprint_cadence, get_sprint_health,
+    get_sprint_stories, get_sprint_task_board, get_standup_summary, get_stories_by_project,
+    get_story, get_story_dependencies, get_story_references, get_story_revision_diff,
+    get_story_revisions, get_story_shares, get_task_completion_policy, get_task_transfers,
+    get_tasks_by_story, get_user_owned_tasks, get_vapid_public_key, github_webhook,
+    join_task_claim_queue, leave_task_claim_queue, merge_label, merge_stories,
+    override_story_ready, propose_task_transfer, release_task_ownership, remove_story_from_sprint,
+    rename_label, reorder_acceptance_criteria, resolve_short_key, resume_import,
+    revoke_story_share, rotate_readiness_badge_token, schedule_sprints, search_archive,
+    set_board_configuration, set_bug_sla_targets, set_reason_policy, set_sprint_cadence,
+    set_task_completion_policy, set_task_estimate, share_stories, split_task, start_import,
+    start_task_work, take_task_ownership, update_acceptance_criterion, update_bug, update_story,
+    update_story_status, update_task_status, verify_acceptance_criterion,
+};
+use crate::adapters::http::BacklogAppState;
+use crate::adapters::importer::spawn_import_worker;
+use crate::adapters::sla_monitor::spawn_bug_sla_monitor;
+use crate::adapters::sprint_cadence::spawn_sprint_cadence_scheduler;
+use crate::adapters::stale_marker::spawn_stale_marker;
+use crate::adapters::websocket::{websocket_handler, WebSocketManager};
+use crate::application::BacklogUsecases;
+use crate::domain::MAX_IMPORT_BYTES;
+use auth_clerk::JwtVerifier;
+use axum::extract::DefaultBodyLimit;
+use axum::routing::{delete, get, patch, post, put};
+use axum::{Extension, Router};
+use common::route_registry::ServiceRouter;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tower_http::trace::TraceLayer;
+
+/// Room for an import's content once escaped into a JSON string
+const MAX_IMPORT_BODY_BYTES: usize = 2 * MAX_IMPORT_BYTES;
+
+pub fn create_backlog_router(
+    backlog_usecases: Arc<BacklogUsecases>,
+    pool: PgPool,
+    verifier: Arc<Mutex<JwtVerifier>>,
+) -> Router {
+    // Create WebSocket manager for real-time updates
+    let ws_manager = Arc::new(WebSocketManager::from_env());
+    create_backlog_router_with_websockets(backlog_usecases, pool, verifier, ws_manager)
+}
+
+/// Like [`create_backlog_router`], with real-time updates going through a
+/// WebSocket manager the caller keeps, e.g. to report on its connections
+pub fn create_backlog_router_with_websockets(
+    backlog_usecases: Arc<BacklogUsecases>,
+    pool: PgPool,
+    verifier: Arc<Mutex<JwtVerifier>>,
+    ws_manager: Arc<WebSocketManager>,
+) -> Router {
+    spawn_bug_sla_monitor(backlog_usecases.clone(), ws_manager.clone());
+    spawn_story_archiver(backlog_usecases.clone());
+    spawn_import_worker(backlog_usecases.clone());
+    spawn_stale_marker(backlog_usecases.clone(), ws_manager.clone());
+    spawn_sprint_cadence_scheduler(backlog_usecases.clone());
+
+    // Create state with usecases, WebSocket manager, and database pool
+    let state = Arc::new(BacklogAppState::new(
+        backlog_usecases,
+        ws_manager.clone(),
+        pool,
+    ));
+    let trace_layer =
+        TraceLayer::new_for_http().make_span_with(|request: &axum::http::Request<_>| {
+            let org_id = request
+                .headers()
+                .get("x-organization-id")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("none")
+                .to_string();
+            let user_id = request
+                .headers()
+                .get("x-user-id")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("anonymous")
+                .to_string();
+
+            tracing::info_span!(
+                "backlog_http_request",
+                method = %request.method(),
+                uri = %request.uri(),
+                org_id,
+                user_id
+            )
+        });
+
+    ServiceRouter::new("backlog")
+        .route("/api/v1/projects/{project_id}/stories", post(create_story))
+        .route(
+            "/api/v1/projects/{project_id}/stories",
+            get(get_stories_by_project),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/readiness-badge/token",
+            post(rotate_readiness_badge_token),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/backlog-health",
+            get(get_backlog_health),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/dependency-graph",
+            get(get_dependency_graph),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/bug-sla/report",
+            get(get_bug_sla_report),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/shares",
+            post(share_stories).get(get_story_shares),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/shares/{share_id}",
+            delete(revoke_story_share),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/imports",
+            post(start_import).layer(DefaultBodyLimit::max(MAX_IMPORT_BODY_BYTES)),
+        )
+        .route("/api/v1/imports/{job_id}", get(get_import_job))
+        .route("/api/v1/imports/{job_id}/resume", post(resume_import))
+        .route("/api/v1/shared/stories", get(get_shared_stories))
+        .route("/api/v1/archive/search", get(search_archive))
+        .route("/api/v1/bug-sla/targets", get(get_bug_sla_targets))
+        .route(
+            "/api/v1/task-completion-policy",
+            get(get_task_completion_policy).put(set_task_completion_policy),
+        )
+        .route(
+            "/api/v1/reason-policy",
+            get(get_reason_policy).put(set_reason_policy),
+        )
+        .route(
+            "/api/v1/sprint-cadence",
+            get(get_sprint_cadence).put(set_sprint_cadence),
+        )
+        .route(
+            "/api/v1/task-board-configuration",
+            get(get_board_configuration).put(set_board_configuration),
+        )
+        .route(
+            "/api/v1/notifications/vapid-public-key",
+            get(get_vapid_public_key),
+        )
+        .route(
+            "/api/v1/notifications/push-subscriptions",
+            post(create_push_subscription),
+        )
+        .route(
+            "/api/v1/notifications/push-subscriptions/{id}",
+            delete(delete_push_subscription),
+        )
+        .route(
+            "/api/v1/bug-sla/targets/{severity}",
+            put(set_bug_sla_targets),
+        )
+        .route(
+            "/api/v1/public/projects/{project_id}/readiness-badge.svg",
+            get(get_readiness_badge),
+        )
+        .route("/api/v1/projects/{project_id}/sprints", post(create_sprint))
+        .route(
+            "/api/v1/projects/{project_id}/sprints/schedule",
+            post(schedule_sprints),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/standup-summary",
+            get(get_standup_summary),
+        )
+        .route("/api/v1/sprints/{sprint_id}/health", get(get_sprint_health))
+        .route(
+            "/api/v1/sprints/{sprint_id}/tasks",
+            get(get_sprint_task_board),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/stories",
+            get(get_sprint_stories).post(add_story_to_sprint),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/stories/{story_id}",
+            delete(remove_story_from_sprint),
+        )
+        .route("/api/v1/stories/bulk", patch(bulk_update_stories))
+        .route("/api/v1/stories/{id}", get(get_story))
+        .route("/api/v1/resolve/{key}", get(resolve_short_key))
+        .route("/api/v1/stories/{id}/references", get(get_story_references))
+        .route(
+            "/api/v1/stories/{id}/dependencies",
+            get(get_story_dependencies).post(create_story_dependency),
+        )
+        .route(
+            "/api/v1/stories/{id}/dependencies/{other_id}",
+            delete(delete_story_dependency),
+        )
+        .route("/api/v1/stories/{id}/revisions", get(get_story_revisions))
+        .route(
+            "/api/v1/stories/{id}/revisions/{from}/diff/{to}",
+            get(get_story_revision_diff),
+        )
+        .route("/api/v1/webhooks/github", post(github_webhook))
+        .route("/api/v1/stories/{id}", patch(update_story))
+        .route("/api/v1/stories/{id}", delete(delete_story))
+        .route(
+            "/api/v1/stories/{id}/merge/{source_id}",
+            post(merge_stories),
+        )
+        .route(
+            "/api/v1/projects/{id}/labels/{label}/rename",
+            post(rename_label),
+        )
+        .route(
+            "/api/v1/projects/{id}/labels/{label}/merge",
+            post(merge_label),
+        )
+        .route("/api/v1/stories/{id}/export", get(export_story))
+        .route(
+            "/api/v1/stories/{id}/ready-override",
+            put(override_story_ready),
+        )
+        .route("/api/v1/stories/{id}/tasks", post(create_task))
+        .route("/api/v1/stories/{id}/tasks", get(get_tasks_by_story))
+        .route(
+            "/api/v1/stories/{id}/tasks/available",
+            get(get_available_tasks),
+        )
+        .route("/api/v1/stories/{id}/status", patch(update_story_status))
+        .route("/api/v1/stories/{id}/bug", get(get_bug).patch(update_bug))
+        .route(
+            "/api/v1/stories/{id}/acceptance-criteria",
+            get(get_acceptance_criteria),
+        )
+        .route(
+            "/api/v1/stories/{id}/acceptance-criteria",
+            post(create_acceptance_criterion),
+        )
+        .route(
+            "/api/v1/stories/{id}/acceptance-criteria/{criterion_id}",
+            patch(update_acceptance_criterion),
+        )
+        .route(
+            "/api/v1/stories/{id}/acceptance-criteria/{criterion_id}",
+            delete(delete_acceptance_criterion),
+        )
+        .route(
+            "/api/v1/stories/{id}/acceptance-criteria/{criterion_id}/verifications",
+            post(verify_acceptance_criterion),
+        )
+        .route(
+            "/api/v1/stories/{id}/acceptance-criteria/verifications",
+            get(get_criterion_verifications),
+        )
+        .route(
+            "/api/v1/stories/{id}/acceptance-criteria/bulk",
+            post(bulk_update_acceptance_criteria),
+        )
+        .route(
+            "/api/v1/stories/{id}/acceptance-criteria/order",
+            put(reorder_acceptance_criteria),
+        )
+        .route("/api/v1/tasks/owned", get(get_user_owned_tasks))
+        .route("/api/v1/tasks/recommended", get(get_recommended_tasks))
+        .route("/api/v1/tasks/batch", patch(batch_update_task_status))
+        .route("/api/v1/tasks/bulk", patch(bulk_update_tasks))
+        .route(
+            "/api/v1/tasks/{task_id}/ownership",
+            put(take_task_ownership),
+        )
+        .route(
+            "/api/v1/tasks/{task_id}/ownership",
+            delete(release_task_ownership),
+        )
+        .route(
+            "/api/v1/tasks/{task_id}/claim-queue",
+            post(join_task_claim_queue).delete(leave_task_claim_queue),
+        )
+        .route(
+            "/api/v1/tasks/{task_id}/transfer",
+            post(propose_task_transfer),
+        )
+        .route(
+            "/api/v1/tasks/{task_id}/transfer/accept",
+            post(accept_task_transfer),
+        )
+        .route(
+            "/api/v1/tasks/{task_id}/transfer/decline",
+            post(decline_task_transfer),
+        )
+        .route("/api/v1/tasks/{task_id}/transfers", get(get_task_transfers))
+        .route("/api/v1/tasks/{task_id}/work/start", post(start_task_work))
+        .route(
+            "/api/v1/tasks/{task_id}/work/complete",
+            post(complete_task_work),
+        )
+        .route("/api/v1/tasks/{task_id}/status", patch(update_task_status))
+        .route("/api/v1/tasks/{task_id}/estimate", patch(set_task_estimate))
+        .route("/api/v1/tasks/{task_id}/split", post(split_task))
+        // WebSocket endpoint for real-time task updates
+        .route("/api/v1/ws/tasks", get(websocket_handler))
+        .with_state(state)
+        .layer(Extension(verifier))
+        .layer(trace_layer)
+}
@@ -0,0 +1,102 @@
+//! Lets clients use short keys like `PROJ-123` wherever a story or task id is expected
+//! in a path, by rewriting key segments to the UUID before routing.
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::Uri,
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use sqlx::PgPool;
+
+use crate::adapters::persistence::repo;
+use crate::domain::is_short_key;
+
+/// The resolve endpoint takes the key itself and must not be rewritten
+const RESOLVE_PREFIX: &str = "/api/v1/resolve/";
+
+/// Wrap `router` so key segments are resolved before its routes are matched.
+/// A plain `Router::layer` runs after routing, which is too late to change path params.
+pub fn with_short_key_paths(router: Router, pool: PgPool) -> Router {
+    Router::new()
+        .fallback_service(router)
+        .layer(middleware::from_fn_with_state(
+            pool,
+            resolve_short_key_paths,
+        ))
+}
+
+async fn resolve_short_key_paths(
+    State(pool): State<PgPool>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if !path.starts_with(RESOLVE_PREFIX) && path.split('/').any(is_short_key) {
+        let mut ids = Vec::new();
+        for segment in path.split('/').filter(|segment| is_short_key(segment)) {
+            // Unknown keys and lookup errors leave the segment alone so the handler
+            // reports it the same way as any other malformed id
+            if let Ok(Some(id)) = repo::find_id_by_short_key(&pool, segment).await {
+                ids.push((segment.to_string(), id.to_string()));
+            }
+        }
+
+        if let Some(uri) = rewrite_path(request.uri(), &ids) {
+            *request.uri_mut() = uri;
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Replace whole path segments found in `replacements`, keeping the query string
+fn rewrite_path(uri: &Uri, replacements: &[(String, String)]) -> Option<Uri> {
+    if replacements.is_empty() {
+        return None;
+    }
+
+    let path = uri
+        .path()
+        .split('/')
+        .map(|segment| {
+            replacements
+                .iter()
+                .find(|(key, _)| key == segment)
+                .map_or(segment, |(_, id)| id.as_str())
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{}?{}", path, query),
+        None => path,
+    };
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(path_and_query.parse().ok()?);
+    Uri::from_parts(parts).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_path_replaces_whole_segments_only() {
+        let uri: Uri = "/api/v1/stories/PROJ-12/tasks?status=open".parse().unwrap();
+        let id = "7f1c6a36-0d57-4c52-b0b4-8d7c5ef1b1a2".to_string();
+
+        let rewritten = rewrite_path(&uri, &[("PROJ-12".to_string(), id.clone())]).unwrap();
+        assert_eq!(
+            rewritten.to_string(),
+            format!("/api/v1/stories/{}/tasks?status=open", id)
+        );
+
+        let untouched: Uri = "/api/v1/stories/PROJ-123".parse().unwrap();
+        let rewritten = rewrite_path(&untouched, &[("PROJ-12".to_string(), id)]).unwrap();
+        assert_eq!(rewritten.path(), "/api/v1/stories/PROJ-123");
+        assert!(rewrite_path(&uri, &[]).is_none());
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+use crate::application::BacklogUsecases;
+use crate::domain::{ImportJobStatus, ImportThrottle};
+
+const IMPORT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const IMPORT_EVENTS_PER_SECOND_ENV: &str = "IMPORT_EVENTS_PER_SECOND";
+
+/// Every five seconds, work through the queued imports one job at a time,
+/// following up created stories at no more than `IMPORT_EVENTS_PER_SECOND`
+/// a second (default 20). Replicas share the queue; each job is
+/// leased to one of them at a time.
+pub fn spawn_import_worker(usecases: Arc<BacklogUsecases>) -> JoinHandle<()> {
+    let throttle = import_throttle(std::env::var(IMPORT_EVENTS_PER_SECOND_ENV).ok().as_deref());
+
+    tokio::spawn(async move {
+        let mut ticks = interval(IMPORT_POLL_INTERVAL);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            loop {
+                match usecases.run_next_import(&throttle).await {
+                    Ok(None) => break,
+                    Ok(Some(job)) => match job.status {
+                        ImportJobStatus::Completed => info!(
+                            job_id = %job.id,
+                            created = job.created_items,
+                            failed = job.failed_items,
+                            "Import completed"
+                        ),
+                        ImportJobStatus::Failed => {
+                            warn!(job_id = %job.id, error = ?job.last_error, "Import failed")
+                        }
+                        ImportJobStatus::Queued | ImportJobStatus::Running => {}
+                    },
+                    Err(e) => {
+                        error!(error = %e, "Failed to run import");
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn import_throttle(raw: Option<&str>) -> ImportThrottle {
+    let default = ImportThrottle::default();
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => match value.parse::<u32>() {
+            Ok(events_per_second) if events_per_second > 0 => ImportThrottle {
+                events_per_second,
+                ..default
+            },
+            _ => {
+                warn!(
+                    value,
+                    "Invalid {}; using default of {} events a second",
+                    IMPORT_EVENTS_PER_SECOND_ENV,
+                    default.events_per_second
+                );
+                default
+            }
+        },
+        None => default,
+    }
+}
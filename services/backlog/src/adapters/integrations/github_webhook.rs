@@ -0,0 +1,63 @@
+use common::AppError;
+use ring::hmac;
+
+/// Checks `X-Hub-Signature-256` on GitHub webhook deliveries
+pub struct GithubWebhookVerifier {
+    key: hmac::Key,
+}
+
+impl GithubWebhookVerifier {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes()),
+        }
+    }
+
+    /// Build from `GITHUB_WEBHOOK_SECRET`; `None` when no secret is set
+    pub fn from_env() -> Option<Self> {
+        std::env::var("GITHUB_WEBHOOK_SECRET")
+            .ok()
+            .filter(|secret| !secret.trim().is_empty())
+            .map(|secret| Self::new(&secret))
+    }
+
+    /// `signature` is the header value, `sha256=` followed by the hex HMAC of the body
+    pub fn verify(&self, payload: &[u8], signature: Option<&str>) -> Result<(), AppError> {
+        let tag = signature
+            .and_then(|value| value.strip_prefix("sha256="))
+            .and_then(decode_hex)
+            .ok_or_else(|| AppError::Unauthorized("Missing or malformed signature".to_string()))?;
+
+        hmac::verify(&self.key, payload, &tag)
+            .map_err(|_| AppError::Unauthorized("Invalid webhook signature".to_string()))
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_signature() {
+        let verifier = GithubWebhookVerifier::new("It's a Secret to Everybody");
+        // Example delivery from GitHub's webhook documentation
+        let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+        assert!(verifier.verify(b"Hello, World!", Some(signature)).is_ok());
+        assert!(verifier.verify(b"Hello, World?", Some(signature)).is_err());
+        assert!(verifier.verify(b"Hello, World!", None).is_err());
+        assert!(verifier
+            .verify(b"Hello, World!", Some("sha256=zz"))
+            .is_err());
+    }
+}
@@ -1,6 +1,12 @@
+pub mod github_webhook;
 pub mod readiness_client;
+pub mod standup_narrator;
+pub mod task_splitter;
 
+pub use github_webhook::*;
 pub use readiness_client::*;
+pub use standup_narrator::*;
+pub use task_splitter::*;
 
 pub struct MockReadinessService;
 
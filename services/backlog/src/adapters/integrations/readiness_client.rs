@@ -18,6 +18,7 @@ struct ValidateAcRefsResponse {
 
 #[allow(dead_code)]
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct AcceptanceCriterion {
     ac_id: String,
     given: String,
@@ -0,0 +1,148 @@
+use crate::application::ports::StandupNarrator;
+use crate::domain::StandupSummary;
+use async_trait::async_trait;
+use common::circuit_breaker::CircuitBreaker;
+use common::llm_audit::{LlmAuditSink, LlmExchange, NoopLlmAuditSink};
+use common::outbound_http::OutboundHttpClient;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+struct GenerateRequest {
+    model: String,
+    messages: Vec<Message>,
+    temperature: f32,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GenerateResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    content: String,
+}
+
+pub struct OpenAiStandupNarrator {
+    client: OutboundHttpClient,
+    api_key: String,
+    model: String,
+    audit: Arc<dyn LlmAuditSink>,
+}
+
+impl OpenAiStandupNarrator {
+    pub fn new(api_key: String, model: Option<String>) -> Self {
+        Self {
+            client: OutboundHttpClient::builder()
+                .request_timeout(Duration::from_secs(60))
+                .build(),
+            api_key,
+            model: model.unwrap_or_else(|| "gpt-3.5-turbo".to_string()),
+            audit: Arc::new(NoopLlmAuditSink),
+        }
+    }
+
+    /// Record prompts and responses for organizations that opted in to LLM auditing
+    pub fn with_audit_sink(mut self, audit: Arc<dyn LlmAuditSink>) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    /// Build from `OPENAI_API_KEY` and optional `STANDUP_NARRATIVE_MODEL`; `None` when no key is set
+    pub fn from_env() -> Option<Self> {
+        let api_key = std::env::var("OPENAI_API_KEY")
+            .ok()
+            .filter(|key| !key.trim().is_empty())?;
+        Some(Self::new(
+            api_key,
+            std::env::var("STANDUP_NARRATIVE_MODEL").ok(),
+        ))
+    }
+
+    fn create_prompt(&self, summary: &StandupSummary) -> String {
+        format!(
+            "Write a short daily standup update for a team chat channel from the data below.\n\
+            Use one or two sentences per person, call out blocked items first, mention new scope \
+            at the end, and do not invent work that is not listed. Respond with plain text only.\n\n{}",
+            summary.digest()
+        )
+    }
+}
+
+#[async_trait]
+impl StandupNarrator for OpenAiStandupNarrator {
+    async fn narrate(
+        &self,
+        summary: &StandupSummary,
+        organization_id: Option<Uuid>,
+    ) -> Result<String, AppError> {
+        let prompt = self.create_prompt(summary);
+        let request = GenerateRequest {
+            model: self.model.clone(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: prompt.clone(),
+            }],
+            temperature: 0.3,
+        };
+
+        let response_data: Result<GenerateResponse, AppError> =
+            CircuitBreaker::for_dependency("openai")
+                .call(async {
+                    let response = self
+                        .client
+                        .send(
+                            "backlog.openai.standup",
+                            self.client
+                                .post("https://api.openai.com/v1/chat/completions")
+                                .header("Authorization", format!("Bearer {}", self.api_key))
+                                .header("Content-Type", "application/json")
+                                .json(&request),
+                        )
+                        .await
+                        .map_err(|_| AppError::InternalServerError)?;
+
+                    if !response.status().is_success() {
+                        return Err(AppError::InternalServerError);
+                    }
+
+                    response
+                        .json()
+                        .await
+                        .map_err(|_| AppError::InternalServerError)
+                })
+                .await;
+
+        let narrative = response_data.and_then(|data| {
+            data.choices
+                .first()
+                .map(|choice| choice.message.content.trim().to_string())
+                .filter(|content| !content.is_empty())
+                .ok_or(AppError::InternalServerError)
+        });
+        self.audit.record(LlmExchange::new(
+            "backlog.openai.standup",
+            &self.model,
+            organization_id,
+            None,
+            &prompt,
+            narrative.as_deref().map_err(|e| e.to_string()),
+        ));
+        narrative
+    }
+}
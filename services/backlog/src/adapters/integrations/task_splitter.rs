@@ -0,0 +1,188 @@
+use crate::application::ports::TaskSplitProposer;
+use crate::domain::{Story, Task, TaskSplitPart};
+use async_trait::async_trait;
+use common::circuit_breaker::CircuitBreaker;
+use common::llm_audit::{LlmAuditSink, LlmExchange, NoopLlmAuditSink};
+use common::outbound_http::OutboundHttpClient;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, Serialize)]
+struct GenerateRequest {
+    model: String,
+    messages: Vec<Message>,
+    temperature: f32,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GenerateResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProposedPart {
+    title: String,
+    description: Option<String>,
+    #[serde(default)]
+    acceptance_criteria_refs: Vec<String>,
+    estimated_hours: Option<u32>,
+}
+
+pub struct OpenAiTaskSplitter {
+    client: OutboundHttpClient,
+    api_key: String,
+    model: String,
+    audit: Arc<dyn LlmAuditSink>,
+}
+
+impl OpenAiTaskSplitter {
+    pub fn new(api_key: String, model: Option<String>) -> Self {
+        Self {
+            client: OutboundHttpClient::builder()
+                .request_timeout(Duration::from_secs(60))
+                .build(),
+            api_key,
+            model: model.unwrap_or_else(|| "gpt-3.5-turbo".to_string()),
+            audit: Arc::new(NoopLlmAuditSink),
+        }
+    }
+
+    /// Record prompts and responses for organizations that opted in to LLM auditing
+    pub fn with_audit_sink(mut self, audit: Arc<dyn LlmAuditSink>) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    /// Build from `OPENAI_API_KEY` and optional `TASK_SPLIT_MODEL`; `None` when no key is set
+    pub fn from_env() -> Option<Self> {
+        let api_key = std::env::var("OPENAI_API_KEY")
+            .ok()
+            .filter(|key| !key.trim().is_empty())?;
+        Some(Self::new(api_key, std::env::var("TASK_SPLIT_MODEL").ok()))
+    }
+
+    fn create_prompt(&self, story: &Story, task: &Task) -> String {
+        let criteria = story
+            .acceptance_criteria
+            .iter()
+            .map(|ac| {
+                format!(
+                    "- {}: Given {}, when {}, then {}",
+                    ac.id, ac.given, ac.when, ac.then
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            "Split the following development task into 2-5 smaller tasks of at most 40 hours each.\n\n\
+            Story: {}\n\
+            Story acceptance criteria:\n{}\n\n\
+            Task: {}\n\
+            Description: {}\n\
+            Acceptance criteria references: {}\n\n\
+            Please respond with ONLY a JSON array where each item has the format:\n\
+            {{\"title\": \"...\", \"description\": \"...\", \"acceptance_criteria_refs\": [\"...\"], \"estimated_hours\": 8}}\n\n\
+            Only use the task's acceptance criteria references, and make sure every one of them is used by at least one task.",
+            story.title,
+            criteria,
+            task.title,
+            task.description.as_deref().unwrap_or("No description provided"),
+            task.acceptance_criteria_refs.join(", ")
+        )
+    }
+}
+
+#[async_trait]
+impl TaskSplitProposer for OpenAiTaskSplitter {
+    async fn propose_split(
+        &self,
+        story: &Story,
+        task: &Task,
+    ) -> Result<Vec<TaskSplitPart>, AppError> {
+        let prompt = self.create_prompt(story, task);
+        let request = GenerateRequest {
+            model: self.model.clone(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: prompt.clone(),
+            }],
+            temperature: 0.3,
+        };
+
+        let response_data: Result<GenerateResponse, AppError> =
+            CircuitBreaker::for_dependency("openai")
+                .call(async {
+                    let response = self
+                        .client
+                        .send(
+                            "backlog.openai.task_split",
+                            self.client
+                                .post("https://api.openai.com/v1/chat/completions")
+                                .header("Authorization", format!("Bearer {}", self.api_key))
+                                .header("Content-Type", "application/json")
+                                .json(&request),
+                        )
+                        .await
+                        .map_err(|_| AppError::InternalServerError)?;
+
+                    if !response.status().is_success() {
+                        return Err(AppError::InternalServerError);
+                    }
+
+                    response
+                        .json()
+                        .await
+                        .map_err(|_| AppError::InternalServerError)
+                })
+                .await;
+
+        let content = response_data.and_then(|data| {
+            data.choices
+                .into_iter()
+                .next()
+                .map(|choice| choice.message.content)
+                .ok_or(AppError::InternalServerError)
+        });
+        self.audit.record(LlmExchange::new(
+            "backlog.openai.task_split",
+            &self.model,
+            story.organization_id,
+            Some(story.id),
+            &prompt,
+            content.as_deref().map_err(|e| e.to_string()),
+        ));
+        let content = content?;
+
+        let parts: Vec<ProposedPart> = serde_json::from_str(&content)
+            .map_err(|_| AppError::BadRequest("LLM returned invalid JSON format".to_string()))?;
+
+        Ok(parts
+            .into_iter()
+            .map(|part| TaskSplitPart {
+                title: part.title,
+                description: part.description,
+                acceptance_criteria_refs: part.acceptance_criteria_refs,
+                estimated_hours: part.estimated_hours,
+            })
+            .collect())
+    }
+}
@@ -1,4 +1,13 @@
+pub mod archiver;
+pub mod badge;
+pub mod export;
 pub mod http;
+pub mod importer;
 pub mod integrations;
+pub mod notifications;
 pub mod persistence;
+pub mod sla_monitor;
+pub mod sprint_cadence;
+pub mod sprint_stats;
+pub mod stale_marker;
 pub mod websocket;
@@ -0,0 +1,3 @@
+pub mod web_push;
+
+pub use web_push::*;
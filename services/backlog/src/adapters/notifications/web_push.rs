@@ -0,0 +1,346 @@
+use crate::adapters::persistence::repo;
+use crate::application::ports::PushNotifier;
+use crate::domain::{PushNotification, PushSubscription};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use common::outbound_http::OutboundHttpClient;
+use common::AppError;
+use reqwest::StatusCode;
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+use ring::{aead, agreement, hkdf};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Record size written to the aes128gcm header; every payload fits one record
+const RECORD_SIZE: u32 = 4096;
+/// Largest payload push services must accept once the 86-byte header, the
+/// delimiter and the tag are added to it
+const MAX_PAYLOAD_LENGTH: usize = 3993;
+/// How long a push service holds a notification for a browser that is offline
+const PUSH_TTL_SECONDS: u32 = 24 * 60 * 60;
+/// VAPID tokens may live at most 24 hours
+const VAPID_TOKEN_LIFETIME_SECONDS: i64 = 12 * 60 * 60;
+
+/// The deployment's application server identity (RFC 8292)
+pub struct VapidKeys {
+    key_pair: EcdsaKeyPair,
+    public_key: String,
+    subject: String,
+}
+
+impl VapidKeys {
+    /// `public_key` and `private_key` are the base64url raw P-256 keys that
+    /// `web-push generate-vapid-keys` prints. `subject` is a `mailto:` or
+    /// `https:` contact for the push service operators.
+    pub fn new(public_key: &str, private_key: &str, subject: String) -> Option<Self> {
+        let public = decode(public_key)?;
+        let private = decode(private_key)?;
+        let key_pair = EcdsaKeyPair::from_private_key_and_public_key(
+            &ECDSA_P256_SHA256_FIXED_SIGNING,
+            &private,
+            &public,
+            &SystemRandom::new(),
+        )
+        .ok()?;
+        Some(Self {
+            public_key: URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+            key_pair,
+            subject,
+        })
+    }
+
+    /// Read `VAPID_PUBLIC_KEY`, `VAPID_PRIVATE_KEY` and `VAPID_SUBJECT`; `None`
+    /// unless all three are set and the keys form a pair
+    pub fn from_secrets(get: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let value = |key: &str| get(key).filter(|value| !value.trim().is_empty());
+        let public_key = value("VAPID_PUBLIC_KEY")?;
+        let private_key = value("VAPID_PRIVATE_KEY")?;
+        let subject = value("VAPID_SUBJECT")?;
+        let keys = Self::new(&public_key, &private_key, subject);
+        if keys.is_none() {
+            tracing::warn!(
+                "VAPID keys are not a base64url P-256 key pair; push notifications are off"
+            );
+        }
+        keys
+    }
+
+    /// The `Authorization` header value for a push to `endpoint`
+    fn authorization(&self, endpoint: &str) -> Result<String, AppError> {
+        let endpoint = reqwest::Url::parse(endpoint)
+            .map_err(|_| AppError::BadRequest(format!("Invalid push endpoint: {}", endpoint)))?;
+        let header = URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ES256"}"#);
+        let claims = URL_SAFE_NO_PAD.encode(
+            serde_json::json!({
+                "aud": endpoint.origin().ascii_serialization(),
+                "exp": chrono::Utc::now().timestamp() + VAPID_TOKEN_LIFETIME_SECONDS,
+                "sub": self.subject,
+            })
+            .to_string(),
+        );
+        let signing_input = format!("{}.{}", header, claims);
+        let signature = self
+            .key_pair
+            .sign(&SystemRandom::new(), signing_input.as_bytes())
+            .map_err(|_| AppError::InternalServerError)?;
+        Ok(format!(
+            "vapid t={}.{}, k={}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature.as_ref()),
+            self.public_key
+        ))
+    }
+}
+
+/// Sends notifications to every browser the user subscribed, encrypted per
+/// RFC 8291 and signed with the deployment's VAPID keys. Subscriptions the
+/// push service reports gone are deleted.
+#[derive(Clone)]
+pub struct WebPushNotifier {
+    pool: Arc<PgPool>,
+    client: OutboundHttpClient,
+    vapid: Arc<VapidKeys>,
+}
+
+impl WebPushNotifier {
+    pub fn new(pool: Arc<PgPool>, vapid: VapidKeys) -> Self {
+        Self {
+            pool,
+            client: OutboundHttpClient::builder()
+                .request_timeout(Duration::from_secs(10))
+                .build(),
+            vapid: Arc::new(vapid),
+        }
+    }
+
+    async fn deliver(&self, notification: &PushNotification) -> Result<(), AppError> {
+        let subscriptions =
+            repo::get_push_subscriptions_for_user(&self.pool, notification.user_id).await?;
+        if subscriptions.is_empty() {
+            return Ok(());
+        }
+        let payload =
+            serde_json::to_vec(notification).map_err(|_| AppError::InternalServerError)?;
+        for subscription in &subscriptions {
+            if let Err(e) = self.send(subscription, &payload).await {
+                tracing::warn!(
+                    error = %e,
+                    subscription_id = %subscription.id,
+                    "Push notification not delivered"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn send(&self, subscription: &PushSubscription, payload: &[u8]) -> Result<(), AppError> {
+        let (Some(ua_public), Some(auth_secret)) =
+            (subscription.p256dh_bytes(), subscription.auth_bytes())
+        else {
+            return Err(AppError::BadRequest(
+                "Push subscription keys are not base64url".to_string(),
+            ));
+        };
+        let body = encrypt(&ua_public, &auth_secret, payload)?;
+        let authorization = self.vapid.authorization(&subscription.endpoint)?;
+
+        let response = self
+            .client
+            .send(
+                "backlog.web_push",
+                self.client
+                    .post(&subscription.endpoint)
+                    .header("Authorization", authorization)
+                    .header("Content-Encoding", "aes128gcm")
+                    .header("Content-Type", "application/octet-stream")
+                    .header("TTL", PUSH_TTL_SECONDS)
+                    .header("Urgency", "high")
+                    .body(body),
+            )
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Web Push failed: {}", e)))?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND | StatusCode::GONE => {
+                repo::delete_push_subscription_by_endpoint(&self.pool, &subscription.endpoint).await
+            }
+            status if !status.is_success() => Err(AppError::ExternalServiceError(format!(
+                "Web Push rejected with status {}",
+                status
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl PushNotifier for WebPushNotifier {
+    fn notify(&self, notification: PushNotification) {
+        let notifier = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = notifier.deliver(&notification).await {
+                tracing::error!(
+                    error = %e,
+                    user_id = %notification.user_id,
+                    "Failed to send push notification"
+                );
+            }
+        });
+    }
+
+    fn application_server_key(&self) -> String {
+        self.vapid.public_key.clone()
+    }
+}
+
+fn decode(key: &str) -> Option<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(key.trim().trim_end_matches('='))
+        .ok()
+}
+
+/// Encrypt `payload` for the browser holding `ua_public` and `auth_secret`
+/// with a fresh key pair and salt, as an aes128gcm body
+fn encrypt(ua_public: &[u8], auth_secret: &[u8], payload: &[u8]) -> Result<Vec<u8>, AppError> {
+    if payload.len() > MAX_PAYLOAD_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Push payloads must be at most {} bytes",
+            MAX_PAYLOAD_LENGTH
+        )));
+    }
+    let failed = |_| AppError::BadRequest("Push subscription key is not a P-256 key".to_string());
+    let rng = SystemRandom::new();
+    let as_private = agreement::EphemeralPrivateKey::generate(&agreement::ECDH_P256, &rng)
+        .map_err(|_| AppError::InternalServerError)?;
+    let as_public = as_private
+        .compute_public_key()
+        .map_err(|_| AppError::InternalServerError)?;
+    let mut salt = [0u8; 16];
+    rng.fill(&mut salt)
+        .map_err(|_| AppError::InternalServerError)?;
+    let ecdh_secret = agreement::agree_ephemeral(
+        as_private,
+        &agreement::UnparsedPublicKey::new(&agreement::ECDH_P256, ua_public),
+        |secret| secret.to_vec(),
+    )
+    .map_err(failed)?;
+    seal(
+        &ecdh_secret,
+        auth_secret,
+        ua_public,
+        as_public.as_ref(),
+        &salt,
+        payload,
+    )
+    .map_err(failed)
+}
+
+/// The aes128gcm body (RFC 8188) holding `payload` as a single record, with
+/// keys derived per RFC 8291
+fn seal(
+    ecdh_secret: &[u8],
+    auth_secret: &[u8],
+    ua_public: &[u8],
+    as_public: &[u8],
+    salt: &[u8],
+    payload: &[u8],
+) -> Result<Vec<u8>, ring::error::Unspecified> {
+    let key_info = [b"WebPush: info\0".as_slice(), ua_public, as_public].concat();
+    let ikm = expand(
+        &hkdf::Salt::new(hkdf::HKDF_SHA256, auth_secret).extract(ecdh_secret),
+        &key_info,
+        32,
+    )?;
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, salt).extract(&ikm);
+    let cek = expand(&prk, b"Content-Encoding: aes128gcm\0", 16)?;
+    let nonce = expand(&prk, b"Content-Encoding: nonce\0", 12)?;
+
+    let key = aead::LessSafeKey::new(aead::UnboundKey::new(&aead::AES_128_GCM, &cek)?);
+    // 0x02 marks the last record
+    let mut record = [payload, &[0x02]].concat();
+    key.seal_in_place_append_tag(
+        aead::Nonce::try_assume_unique_for_key(&nonce)?,
+        aead::Aad::empty(),
+        &mut record,
+    )?;
+
+    let mut body = Vec::with_capacity(salt.len() + 5 + as_public.len() + record.len());
+    body.extend_from_slice(salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(as_public.len() as u8);
+    body.extend_from_slice(as_public);
+    body.extend_from_slice(&record);
+    Ok(body)
+}
+
+struct OutputLength(usize);
+
+impl hkdf::KeyType for OutputLength {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+fn expand(prk: &hkdf::Prk, info: &[u8], len: usize) -> Result<Vec<u8>, ring::error::Unspecified> {
+    let info = [info];
+    let okm = prk.expand(&info, OutputLength(len))?;
+    let mut out = vec![0u8; len];
+    okm.fill(&mut out)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_FIXED};
+
+    // Example keys from RFC 8291, appendix A
+    const AS_PUBLIC: &str =
+        "BP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A8";
+    const AS_PRIVATE: &str = "yfWPiYE-n46HLnH0KqZOF1fJJU3MYrct3AELtAQ-oRw";
+
+    #[test]
+    fn test_seal_matches_rfc_8291_example() {
+        let body = seal(
+            &decode("kyrL1jIIOHEzg3sM2ZWRHDRB62YACZhhSlknJ672kSs").unwrap(),
+            &decode("BTBZMqHH6r4Tts7J_aSIgg").unwrap(),
+            &decode("BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4").unwrap(),
+            &decode(AS_PUBLIC).unwrap(),
+            &decode("DGv6ra1nlYgDCS1FRnbzlw").unwrap(),
+            b"When I grow up, I want to be a watermelon",
+        )
+        .unwrap();
+
+        assert_eq!(
+            URL_SAFE_NO_PAD.encode(body),
+            "DGv6ra1nlYgDCS1FRnbzlwAAEABBBP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A_yl95bQpu6cVPTpK4Mqgkf1CXztLVBSt2Ks3oZwbuwXPXLWyouBWLVWGNWQexSgSxsj_Qulcy4a-fN"
+        );
+    }
+
+    #[test]
+    fn test_vapid_token_is_signed_for_the_endpoint_origin() {
+        let keys =
+            VapidKeys::new(AS_PUBLIC, AS_PRIVATE, "mailto:ops@example.com".to_string()).unwrap();
+        let authorization = keys
+            .authorization("https://push.example.com/send/abc?x=1")
+            .unwrap();
+
+        let (token, public_key) = authorization
+            .strip_prefix("vapid t=")
+            .and_then(|rest| rest.split_once(", k="))
+            .unwrap();
+        assert_eq!(public_key, AS_PUBLIC);
+        let (signing_input, signature) = token.rsplit_once('.').unwrap();
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, decode(AS_PUBLIC).unwrap())
+            .verify(signing_input.as_bytes(), &decode(signature).unwrap())
+            .unwrap();
+        let claims: serde_json::Value =
+            serde_json::from_slice(&decode(signing_input.split_once('.').unwrap().1).unwrap())
+                .unwrap();
+        assert_eq!(claims["aud"], "https://push.example.com");
+        assert_eq!(claims["sub"], "mailto:ops@example.com");
+
+        assert!(VapidKeys::new(AS_PUBLIC, "not-a-key", String::new()).is_none());
+    }
+}
@@ -1,4 +1,7 @@
 pub mod models;
 pub mod repo;
+pub mod story_filter;
+pub mod unit_of_work;
 
 pub use repo::*;
+pub use unit_of_work::UnitOfWork;
@@ -1,4 +1,10 @@
-use crate::domain::{AcceptanceCriteria, Story, StoryStatus, Task, TaskStatus};
+use crate::domain::{
+    AcceptanceCriteria, ArchivedItem, ArchivedItemKind, BoardConfiguration, BugDetails,
+    BugPriority, BugSeverity, CriterionVerification, ImportFormat, ImportItemError, ImportJob,
+    ImportJobStatus, PushSubscription, SprintStatSnapshot, Story, StoryDependency, StoryRevision,
+    StoryShare, StoryStatus, StoryType, SwimlaneGrouping, Swimlanes, Task, TaskStatus,
+    TaskTransfer, TaskTransferStatus, VerificationKind,
+};
 use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 use uuid::Uuid;
@@ -6,6 +12,7 @@ use uuid::Uuid;
 #[derive(Debug, FromRow)]
 pub struct StoryRow {
     pub id: Uuid,
+    pub short_key: Option<String>,
     pub project_id: Uuid,
     pub organization_id: Option<Uuid>,
     pub title: String,
@@ -21,6 +28,9 @@ pub struct StoryRow {
     pub readiness_override_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub story_type: String,
+    pub timebox_hours: Option<i32>,
+    pub stale_at: Option<DateTime<Utc>>,
 }
 
 impl From<StoryRow> for Story {
@@ -28,29 +38,304 @@ impl From<StoryRow> for Story {
         let status = StoryStatus::from_str(&row.status).unwrap_or(StoryStatus::Draft);
         Story {
             id: row.id,
+            short_key: row.short_key,
             project_id: row.project_id,
             organization_id: row.organization_id,
             title: row.title,
             description: row.description,
             status,
+            story_type: StoryType::parse(&row.story_type).unwrap_or_default(),
             labels: row.labels,
             acceptance_criteria: Vec::new(), // ACs loaded separately
             story_points: row.story_points.map(|p| p as u32),
+            timebox_hours: row.timebox_hours.map(|hours| hours as u32),
             sprint_id: row.sprint_id,
             assigned_to_user_id: row.assigned_to_user_id,
             readiness_override: row.readiness_override,
             readiness_override_by: row.readiness_override_by,
             readiness_override_reason: row.readiness_override_reason,
             readiness_override_at: row.readiness_override_at,
+            stale_at: row.stale_at,
             created_at: row.created_at,
             updated_at: row.updated_at,
         }
     }
 }
 
+/// A bug story with its bug details, if any were recorded
+#[derive(Debug, FromRow)]
+pub struct BugRow {
+    pub story_id: Uuid,
+    pub project_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub title: String,
+    pub reported_at: DateTime<Utc>,
+    pub severity: Option<String>,
+    pub priority: Option<String>,
+    pub environment_found: Option<String>,
+    pub triaged_at: Option<DateTime<Utc>>,
+    pub fixed_at: Option<DateTime<Utc>>,
+}
+
+impl BugRow {
+    pub fn details(&self) -> BugDetails {
+        BugDetails {
+            severity: self
+                .severity
+                .as_deref()
+                .and_then(BugSeverity::parse)
+                .unwrap_or_default(),
+            priority: self.priority.as_deref().and_then(BugPriority::parse),
+            environment_found: self.environment_found.clone(),
+            triaged_at: self.triaged_at,
+            fixed_at: self.fixed_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct SprintStatSnapshotRow {
+    pub sprint_id: Uuid,
+    pub total_stories: i32,
+    pub total_tasks: i32,
+    pub completed_tasks: i32,
+    pub total_points: i32,
+    pub completed_points: i32,
+    pub refreshed_at: DateTime<Utc>,
+}
+
+impl From<SprintStatSnapshotRow> for SprintStatSnapshot {
+    fn from(row: SprintStatSnapshotRow) -> Self {
+        SprintStatSnapshot {
+            sprint_id: row.sprint_id,
+            total_stories: row.total_stories as i64,
+            total_tasks: row.total_tasks as i64,
+            completed_tasks: row.completed_tasks as i64,
+            total_points: row.total_points as i64,
+            completed_points: row.completed_points as i64,
+            refreshed_at: row.refreshed_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct StoryShareRow {
+    pub id: Uuid,
+    pub owner_organization_id: Uuid,
+    pub shared_with_organization_id: Uuid,
+    pub project_id: Uuid,
+    pub story_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<StoryShareRow> for StoryShare {
+    fn from(row: StoryShareRow) -> Self {
+        StoryShare {
+            id: row.id,
+            owner_organization_id: row.owner_organization_id,
+            shared_with_organization_id: row.shared_with_organization_id,
+            project_id: row.project_id,
+            story_id: row.story_id,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            revoked_at: row.revoked_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct StoryDependencyRow {
+    pub story_id: Uuid,
+    pub blocked_by_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StoryDependencyRow> for StoryDependency {
+    fn from(row: StoryDependencyRow) -> Self {
+        StoryDependency {
+            story_id: row.story_id,
+            blocked_by_id: row.blocked_by_id,
+            organization_id: row.organization_id,
+            created_by: row.created_by,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct PushSubscriptionRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub endpoint: String,
+    pub p256dh: String,
+    pub auth: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PushSubscriptionRow> for PushSubscription {
+    fn from(row: PushSubscriptionRow) -> Self {
+        PushSubscription {
+            id: row.id,
+            user_id: row.user_id,
+            endpoint: row.endpoint,
+            p256dh: row.p256dh,
+            auth: row.auth,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct BoardConfigurationRow {
+    pub user_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub group_by: String,
+    pub custom_field: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<BoardConfigurationRow> for BoardConfiguration {
+    fn from(row: BoardConfigurationRow) -> Self {
+        BoardConfiguration {
+            user_id: row.user_id,
+            organization_id: row.organization_id,
+            swimlanes: Swimlanes {
+                group_by: SwimlaneGrouping::parse(&row.group_by).unwrap_or_default(),
+                custom_field: row.custom_field,
+            },
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct ImportJobRow {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub format: String,
+    pub status: String,
+    pub total_items: i32,
+    pub processed_items: i32,
+    pub published_items: i32,
+    pub created_items: i32,
+    pub failed_items: i32,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<ImportJobRow> for ImportJob {
+    fn from(row: ImportJobRow) -> Self {
+        ImportJob {
+            id: row.id,
+            project_id: row.project_id,
+            organization_id: row.organization_id,
+            created_by: row.created_by,
+            format: ImportFormat::parse(&row.format).unwrap_or(ImportFormat::Csv),
+            status: ImportJobStatus::parse(&row.status).unwrap_or(ImportJobStatus::Failed),
+            total_items: row.total_items.max(0) as usize,
+            processed_items: row.processed_items.max(0) as usize,
+            published_items: row.published_items.max(0) as usize,
+            created_items: row.created_items.max(0) as usize,
+            failed_items: row.failed_items.max(0) as usize,
+            attempts: row.attempts.max(0) as u32,
+            last_error: row.last_error,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            completed_at: row.completed_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct ImportItemErrorRow {
+    pub position: i32,
+    pub external_key: Option<String>,
+    pub error: String,
+}
+
+impl From<ImportItemErrorRow> for ImportItemError {
+    fn from(row: ImportItemErrorRow) -> Self {
+        ImportItemError {
+            position: row.position.max(0) as usize,
+            external_key: row.external_key,
+            message: row.error,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct CriterionVerificationRow {
+    pub id: Uuid,
+    pub story_id: Uuid,
+    pub criterion_id: Uuid,
+    pub kind: String,
+    pub passed: bool,
+    pub evidence_url: Option<String>,
+    pub note: Option<String>,
+    pub verified_by: Option<Uuid>,
+    pub verified_at: DateTime<Utc>,
+}
+
+impl From<CriterionVerificationRow> for CriterionVerification {
+    fn from(row: CriterionVerificationRow) -> Self {
+        CriterionVerification {
+            id: row.id,
+            story_id: row.story_id,
+            criterion_id: row.criterion_id,
+            kind: VerificationKind::parse(&row.kind).unwrap_or(VerificationKind::Manual),
+            passed: row.passed,
+            evidence_url: row.evidence_url,
+            note: row.note,
+            verified_by: row.verified_by,
+            verified_at: row.verified_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct TaskTransferRow {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    pub note: Option<String>,
+    pub handoff: bool,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl From<TaskTransferRow> for TaskTransfer {
+    fn from(row: TaskTransferRow) -> Self {
+        TaskTransfer {
+            id: row.id,
+            task_id: row.task_id,
+            organization_id: row.organization_id,
+            from_user_id: row.from_user_id,
+            to_user_id: row.to_user_id,
+            note: row.note,
+            handoff: row.handoff,
+            status: TaskTransferStatus::parse(&row.status).unwrap_or(TaskTransferStatus::Cancelled),
+            created_at: row.created_at,
+            resolved_at: row.resolved_at,
+        }
+    }
+}
+
 #[derive(Debug, FromRow)]
 pub struct TaskRow {
     pub id: Uuid,
+    pub short_key: Option<String>,
     pub story_id: Uuid,
     pub organization_id: Option<Uuid>,
     pub title: String,
@@ -63,6 +348,7 @@ pub struct TaskRow {
     pub updated_at: DateTime<Utc>,
     pub owned_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
+    pub split_from_task_id: Option<Uuid>,
 }
 
 impl From<TaskRow> for Task {
@@ -70,6 +356,7 @@ impl From<TaskRow> for Task {
         let status = TaskStatus::from_str(&row.status).unwrap_or(TaskStatus::Available);
         Task {
             id: row.id,
+            short_key: row.short_key,
             story_id: row.story_id,
             organization_id: row.organization_id,
             title: row.title,
@@ -82,6 +369,7 @@ impl From<TaskRow> for Task {
             updated_at: row.updated_at,
             owned_at: row.owned_at,
             completed_at: row.completed_at,
+            split_from_task_id: row.split_from_task_id,
         }
     }
 }
@@ -94,6 +382,7 @@ pub struct AcceptanceCriteriaRow {
     pub given: String,
     pub when_clause: String,
     pub then_clause: String,
+    pub position: i32,
     pub created_at: DateTime<Utc>,
 }
 
@@ -105,6 +394,7 @@ impl From<AcceptanceCriteriaRow> for AcceptanceCriteria {
             given: row.given,
             when: row.when_clause,
             then: row.then_clause,
+            position: row.position.max(0) as u32,
             created_at: row.created_at,
         }
     }
@@ -156,6 +446,7 @@ mod tests {
             given: "a user is authenticated".to_string(),
             when_clause: "they request data".to_string(),
             then_clause: "the system returns a 200 response".to_string(),
+            position: 2,
             created_at: Utc::now(),
         };
 
@@ -165,5 +456,80 @@ mod tests {
         assert_eq!(ac.given, "a user is authenticated");
         assert_eq!(ac.when, "they request data");
         assert_eq!(ac.then, "the system returns a 200 response");
+        assert_eq!(ac.position, 2);
+    }
+}
+
+/// A sprint with its organization, taken from the owning team
+#[derive(Debug, Clone, FromRow)]
+pub struct SprintRow {
+    pub id: Uuid,
+    pub team_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub name: String,
+    pub goal: String,
+    pub status: String,
+    pub capacity_points: i32,
+    pub committed_points: i32,
+    pub completed_points: i32,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+pub struct StoryRevisionRow {
+    pub id: Uuid,
+    pub story_id: Uuid,
+    pub revision: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub edited_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StoryRevisionRow> for StoryRevision {
+    fn from(row: StoryRevisionRow) -> Self {
+        Self {
+            id: row.id,
+            story_id: row.story_id,
+            revision: row.revision.max(0) as u32,
+            title: row.title,
+            description: row.description,
+            labels: row.labels,
+            edited_by: row.edited_by,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct ArchivedItemRow {
+    pub kind: String,
+    pub id: Uuid,
+    pub story_id: Uuid,
+    pub project_id: Uuid,
+    pub short_key: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub archived_at: DateTime<Utc>,
+}
+
+impl From<ArchivedItemRow> for ArchivedItem {
+    fn from(row: ArchivedItemRow) -> Self {
+        ArchivedItem {
+            kind: ArchivedItemKind::parse(&row.kind).unwrap_or(ArchivedItemKind::Story),
+            id: row.id,
+            story_id: row.story_id,
+            project_id: row.project_id,
+            short_key: row.short_key,
+            title: row.title,
+            description: row.description,
+            status: row.status,
+            archived_at: row.archived_at,
+        }
     }
 }
@@ -1,8 +1,28 @@
-use crate::adapters::persistence::models::{AcceptanceCriteriaRow, ProjectRow, StoryRow, TaskRow};
-use crate::domain::{AcceptanceCriteria, Project, Story, Task};
+use crate::adapters::persistence::models::{
+    AcceptanceCriteriaRow, ArchivedItemRow, BoardConfigurationRow, BugRow,
+    CriterionVerificationRow, ImportItemErrorRow, ImportJobRow, ProjectRow, PushSubscriptionRow,
+    SprintRow, SprintStatSnapshotRow, StoryDependencyRow, StoryRevisionRow, StoryRow,
+    StoryShareRow, TaskRow, TaskTransferRow,
+};
+use crate::adapters::persistence::story_filter::push_story_filter;
+use crate::adapters::persistence::UnitOfWork;
+use crate::application::{StoryPage, StoryQuery, StorySortField};
+use crate::domain::{
+    replace_label, same_label, AcceptanceCriteria, ArchiveSearch, ArchivedItem, ArchivedItemKind,
+    BacklogHealthSnapshot, BacklogHealthSubScores, BoardConfiguration, BugDetails, BugSeverity,
+    ClaimQueueEntry, CriterionVerification, ImportItem, ImportItemError, ImportJob, ItemReference,
+    Project, PushSubscription, ReferenceDirection, ReferenceSourceType, ReferencedItem,
+    ResolvedShortKey, ScopeChange, ShortKeyTarget, SlaPolicy, SlaTargets, SlaTimerKind,
+    SprintCadence, SprintCadenceSettings, SprintRollover, SprintStatSnapshot, StaleStory, Story,
+    StoryDependency, StoryRevision, StoryShare, StoryStatus, Task, TaskCompletionPolicy,
+    TaskTransfer,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use common::calendar::{shared_timezone, SprintCalendar};
+use common::reason_policy::{DestructiveAction, ReasonPolicy};
 use common::AppError;
-use sqlx::{PgPool, Postgres, Transaction};
-use std::collections::HashMap;
+use sqlx::{PgExecutor, PgPool, Postgres, QueryBuilder, Transaction};
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 pub async fn get_project(
@@ -61,14 +81,25 @@ pub async fn get_project(
 
 // Story persistence helpers
 pub async fn create_story(pool: &PgPool, story: &Story) -> Result<Uuid, AppError> {
-    let mut tx = pool
-        .begin()
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
+    let mut uow = UnitOfWork::begin(pool).await?;
+    let result = create_story_with_transaction(uow.tx(), story).await;
+    uow.finish(result).await
+}
 
+pub async fn create_story_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    story: &Story,
+) -> Result<Uuid, AppError> {
+    // Allocate the next per-project key in the same statement so the counter row stays locked
     sqlx::query(
-        "INSERT INTO stories (id, project_id, organization_id, title, description, status, labels, created_at, updated_at)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, NOW(), NOW())",
+        "WITH counter AS (
+             UPDATE projects SET next_item_number = next_item_number + 1
+             WHERE id = $2
+             RETURNING key || '-' || (next_item_number - 1) AS short_key
+         )
+         INSERT INTO stories (id, project_id, organization_id, title, description, status, labels, story_type, timebox_hours, created_at, updated_at, short_key)
+         SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, NOW(), NOW(), counter.short_key
+         FROM (SELECT 1) AS one LEFT JOIN counter ON TRUE",
     )
     .bind(story.id)
     .bind(story.project_id)
@@ -77,17 +108,15 @@ pub async fn create_story(pool: &PgPool, story: &Story) -> Result<Uuid, AppError
     .bind(&story.description)
     .bind(story.status.to_string())
     .bind(&story.labels)
-    .execute(&mut *tx)
+    .bind(story.story_type.as_str())
+    .bind(story.timebox_hours.map(|hours| hours as i32))
+    .execute(&mut **tx)
     .await
     .map_err(|e| {
         tracing::error!(error = %e, "SQL error inserting story");
         AppError::InternalServerError
     })?;
 
-    tx.commit()
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
-
     Ok(story.id)
 }
 
@@ -97,7 +126,7 @@ pub async fn get_story(
     organization_id: Option<Uuid>,
 ) -> Result<Option<Story>, AppError> {
     let story_row = sqlx::query_as::<_, StoryRow>(
-        "SELECT id, project_id, organization_id, title, description, status, labels, story_points, sprint_id, assigned_to_user_id, readiness_override, readiness_override_by, readiness_override_reason, readiness_override_at, created_at, updated_at FROM stories
+        "SELECT id, project_id, organization_id, title, description, status, labels, story_points, sprint_id, assigned_to_user_id, readiness_override, readiness_override_by, readiness_override_reason, readiness_override_at, created_at, updated_at, short_key, story_type, timebox_hours, stale_at FROM stories
          WHERE id = $1 AND (
              (organization_id IS NOT NULL AND organization_id = $2) OR
              (organization_id IS NULL AND $2 IS NULL)
@@ -117,10 +146,10 @@ pub async fn get_story(
             let mut story = Story::from(row);
 
             let acceptance_rows = sqlx::query_as::<_, AcceptanceCriteriaRow>(
-                "SELECT id, story_id, description, given, when_clause, then_clause, created_at
+                "SELECT id, story_id, description, given, when_clause, then_clause, position, created_at
                  FROM acceptance_criteria
                  WHERE story_id = $1
-                 ORDER BY created_at",
+                 ORDER BY position, created_at",
             )
             .bind(story.id)
             .fetch_all(pool)
@@ -142,71 +171,9 @@ pub async fn get_story(
 }
 
 pub async fn update_story(pool: &PgPool, story: &Story) -> Result<(), AppError> {
-    let mut tx = pool
-        .begin()
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
-
-    sqlx::query(
-        "UPDATE stories SET title = $2, description = $3, status = $4, labels = $5, story_points = $6, sprint_id = $7, readiness_override = $8, readiness_override_by = $9, readiness_override_reason = $10, readiness_override_at = $11, updated_at = NOW()
-         WHERE id = $1 AND (
-             (organization_id IS NOT NULL AND organization_id = $12) OR
-             (organization_id IS NULL AND $12 IS NULL)
-         )",
-    )
-    .bind(story.id)
-    .bind(&story.title)
-    .bind(&story.description)
-    .bind(story.status.to_string())
-    .bind(&story.labels)
-    .bind(story.story_points.map(|points| points as i32))
-    .bind(story.sprint_id)
-    .bind(story.readiness_override)
-    .bind(story.readiness_override_by)
-    .bind(&story.readiness_override_reason)
-    .bind(story.readiness_override_at)
-    .bind(story.organization_id)
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| {
-        tracing::error!(error = %e, "SQL error updating story");
-        AppError::InternalServerError
-    })?;
-
-    sqlx::query("DELETE FROM acceptance_criteria WHERE story_id = $1")
-        .bind(story.id)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| {
-            tracing::error!(error = %e, "SQL error deleting acceptance criteria");
-            AppError::InternalServerError
-        })?;
-
-    for ac in &story.acceptance_criteria {
-        sqlx::query(
-            "INSERT INTO acceptance_criteria (id, story_id, organization_id, ac_id, description, given, when_clause, then_clause)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
-        )
-        .bind(ac.id)
-        .bind(story.id)
-        .bind(story.organization_id)
-        .bind(ac.id.to_string())
-        .bind(&ac.description)
-        .bind(&ac.given)
-        .bind(&ac.when)
-        .bind(&ac.then)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| {
-            tracing::error!(error = %e, "SQL error inserting acceptance criterion");
-            AppError::InternalServerError
-        })?;
-    }
-
-    tx.commit()
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
-    Ok(())
+    let mut uow = UnitOfWork::begin(pool).await?;
+    let result = update_story_with_transaction(uow.tx(), story).await;
+    uow.finish(result).await
 }
 
 pub async fn update_story_with_transaction(
@@ -214,7 +181,7 @@ pub async fn update_story_with_transaction(
     story: &Story,
 ) -> Result<(), AppError> {
     sqlx::query(
-        "UPDATE stories SET title = $2, description = $3, status = $4, labels = $5, story_points = $6, sprint_id = $7, readiness_override = $8, readiness_override_by = $9, readiness_override_reason = $10, readiness_override_at = $11, updated_at = NOW()
+        "UPDATE stories SET title = $2, description = $3, status = $4, labels = $5, story_points = $6, sprint_id = $7, readiness_override = $8, readiness_override_by = $9, readiness_override_reason = $10, readiness_override_at = $11, story_type = $13, timebox_hours = $14, stale_at = NULL, updated_at = NOW()
          WHERE id = $1 AND (
              (organization_id IS NOT NULL AND organization_id = $12) OR
              (organization_id IS NULL AND $12 IS NULL)
@@ -232,6 +199,8 @@ pub async fn update_story_with_transaction(
     .bind(&story.readiness_override_reason)
     .bind(story.readiness_override_at)
     .bind(story.organization_id)
+    .bind(story.story_type.as_str())
+    .bind(story.timebox_hours.map(|hours| hours as i32))
     .execute(&mut **tx)
     .await
     .map_err(|e| {
@@ -248,10 +217,10 @@ pub async fn update_story_with_transaction(
             AppError::InternalServerError
         })?;
 
-    for ac in &story.acceptance_criteria {
+    for (position, ac) in story.acceptance_criteria.iter().enumerate() {
         sqlx::query(
-            "INSERT INTO acceptance_criteria (id, story_id, organization_id, ac_id, description, given, when_clause, then_clause)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            "INSERT INTO acceptance_criteria (id, story_id, organization_id, ac_id, description, given, when_clause, then_clause, position, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
         )
         .bind(ac.id)
         .bind(story.id)
@@ -261,6 +230,8 @@ pub async fn update_story_with_transaction(
         .bind(&ac.given)
         .bind(&ac.when)
         .bind(&ac.then)
+        .bind(position as i32)
+        .bind(ac.created_at)
         .execute(&mut **tx)
         .await
         .map_err(|e| {
@@ -294,6 +265,280 @@ pub async fn delete_story(
     Ok(())
 }
 
+/// Merge the duplicate story `source_id` into `target`, which already holds
+/// the duplicate's criteria. Moves the duplicate's tasks and the references
+/// pointing at it, records the merge link and soft-deletes the duplicate.
+pub async fn merge_stories_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    target: &Story,
+    source_id: Uuid,
+    merged_by: Option<Uuid>,
+) -> Result<Vec<Uuid>, AppError> {
+    // Claiming the duplicate first makes a concurrent merge of it fail cleanly
+    let claimed = sqlx::query(
+        "UPDATE stories SET deleted_at = NOW(), updated_at = NOW()
+         WHERE id = $1 AND deleted_at IS NULL",
+    )
+    .bind(source_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error soft-deleting merged story");
+        AppError::InternalServerError
+    })?
+    .rows_affected();
+    if claimed == 0 {
+        return Err(AppError::Conflict(
+            "The duplicate story has already been merged or deleted".to_string(),
+        ));
+    }
+
+    // The target is saved with these criteria under their original ids
+    sqlx::query("DELETE FROM acceptance_criteria WHERE story_id = $1")
+        .bind(source_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error releasing merged acceptance criteria");
+            AppError::InternalServerError
+        })?;
+    update_story_with_transaction(tx, target).await?;
+    record_story_revision_with_transaction(tx, target, merged_by).await?;
+
+    let moved_task_ids = sqlx::query_scalar::<_, Uuid>(
+        "UPDATE tasks SET story_id = $2, updated_at = NOW()
+         WHERE story_id = $1
+         RETURNING id",
+    )
+    .bind(source_id)
+    .bind(target.id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error moving merged tasks");
+        AppError::InternalServerError
+    })?;
+
+    // Commits, pull requests and comments that mentioned the duplicate now
+    // mention the target, unless they already did
+    sqlx::query(
+        "UPDATE item_references r SET target_id = $2
+         WHERE r.target_type = 'story' AND r.target_id = $1
+         AND NOT EXISTS (
+             SELECT 1 FROM item_references existing
+             WHERE existing.target_id = $2
+             AND existing.source_type = r.source_type
+             AND COALESCE(existing.source_id::text, existing.source_url)
+                 = COALESCE(r.source_id::text, r.source_url)
+         )",
+    )
+    .bind(source_id)
+    .bind(target.id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error moving merged story references");
+        AppError::InternalServerError
+    })?;
+
+    sqlx::query(
+        "INSERT INTO story_merges (source_story_id, target_story_id, organization_id, merged_by)
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(source_id)
+    .bind(target.id)
+    .bind(target.organization_id)
+    .bind(merged_by)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error recording story merge");
+        AppError::InternalServerError
+    })?;
+
+    Ok(moved_task_ids)
+}
+
+/// Whether any live story in the project carries `label`, in any casing
+pub async fn project_label_in_use(
+    pool: &PgPool,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+    label: &str,
+) -> Result<bool, AppError> {
+    sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (
+             SELECT 1 FROM stories, unnest(labels) AS label
+             WHERE project_id = $1
+               AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))
+               AND deleted_at IS NULL
+               AND lower(label) = lower($3)
+         )",
+    )
+    .bind(project_id)
+    .bind(organization_id)
+    .bind(label)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error checking project label");
+        AppError::InternalServerError
+    })
+}
+
+/// Replace `from` with `to` on every story of a project and in the labels its
+/// settings name. Returns the stories that changed.
+pub async fn relabel_project_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+    from: &str,
+    to: &str,
+    changed_by: Option<Uuid>,
+) -> Result<Vec<Uuid>, AppError> {
+    let story_rows = sqlx::query_as::<_, StoryRow>(
+        "SELECT id, project_id, organization_id, title, description, status, labels, story_points, sprint_id, assigned_to_user_id, readiness_override, readiness_override_by, readiness_override_reason, readiness_override_at, created_at, updated_at, short_key, story_type, timebox_hours, stale_at FROM stories
+         WHERE project_id = $1
+           AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))
+           AND deleted_at IS NULL
+           AND EXISTS (SELECT 1 FROM unnest(labels) AS label WHERE lower(label) = lower($3))
+         ORDER BY id
+         FOR UPDATE",
+    )
+    .bind(project_id)
+    .bind(organization_id)
+    .bind(from)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error locking labelled stories");
+        AppError::InternalServerError
+    })?;
+
+    let mut updated = Vec::with_capacity(story_rows.len());
+    for row in story_rows {
+        let mut story = Story::from(row);
+        story.labels = replace_label(&story.labels, from, to);
+        sqlx::query("UPDATE stories SET labels = $2, updated_at = NOW() WHERE id = $1")
+            .bind(story.id)
+            .bind(&story.labels)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "SQL error relabelling story");
+                AppError::InternalServerError
+            })?;
+        record_story_revision_with_transaction(tx, &story, changed_by).await?;
+        updated.push(story.id);
+    }
+
+    // The project's label conventions and readiness policy name labels too
+    let settings = sqlx::query_as::<_, (serde_json::Value, serde_json::Value)>(
+        "SELECT conventions, dor_template FROM project_settings WHERE project_id = $1 FOR UPDATE",
+    )
+    .bind(project_id)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error locking project settings");
+        AppError::InternalServerError
+    })?;
+    if let Some((mut conventions, mut dor_template)) = settings {
+        let conventions_changed = replace_label_in_json(&mut conventions, "labels", from, to);
+        // Templates saved before the camelCase switch still use snake_case keys
+        let dor_changed = replace_label_in_json(&mut dor_template, "labelsRequired", from, to)
+            | replace_label_in_json(&mut dor_template, "labels_required", from, to);
+        if conventions_changed || dor_changed {
+            sqlx::query(
+                "UPDATE project_settings SET conventions = $2, dor_template = $3, updated_at = NOW()
+                 WHERE project_id = $1",
+            )
+            .bind(project_id)
+            .bind(&conventions)
+            .bind(&dor_template)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "SQL error relabelling project settings");
+                AppError::InternalServerError
+            })?;
+        }
+    }
+
+    Ok(updated)
+}
+
+/// Replace a label in the string array at `document[key]`, if there is one.
+/// Returns whether anything changed.
+fn replace_label_in_json(
+    document: &mut serde_json::Value,
+    key: &str,
+    from: &str,
+    to: &str,
+) -> bool {
+    let Some(labels) = document.get(key).and_then(|labels| {
+        labels.as_array().map(|labels| {
+            labels
+                .iter()
+                .filter_map(|label| label.as_str().map(str::to_string))
+                .collect::<Vec<_>>()
+        })
+    }) else {
+        return false;
+    };
+    if !labels.iter().any(|label| same_label(label, from)) {
+        return false;
+    }
+    document[key] = serde_json::json!(replace_label(&labels, from, to));
+    true
+}
+
+pub async fn get_stories_by_ids(
+    pool: &PgPool,
+    story_ids: &[Uuid],
+    organization_id: Option<Uuid>,
+) -> Result<Vec<Story>, AppError> {
+    let mut stories = fetch_stories_by_ids(pool, story_ids, organization_id).await?;
+    attach_acceptance_criteria(pool, &mut stories).await?;
+    Ok(stories)
+}
+
+pub async fn get_stories_by_ids_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    story_ids: &[Uuid],
+    organization_id: Option<Uuid>,
+) -> Result<Vec<Story>, AppError> {
+    let mut stories = fetch_stories_by_ids(&mut **tx, story_ids, organization_id).await?;
+    attach_acceptance_criteria(&mut **tx, &mut stories).await?;
+    Ok(stories)
+}
+
+async fn fetch_stories_by_ids<'e, E>(
+    executor: E,
+    story_ids: &[Uuid],
+    organization_id: Option<Uuid>,
+) -> Result<Vec<Story>, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let story_rows = sqlx::query_as::<_, StoryRow>(
+        "SELECT id, project_id, organization_id, title, description, status, labels, story_points, sprint_id, assigned_to_user_id, readiness_override, readiness_override_by, readiness_override_reason, readiness_override_at, created_at, updated_at, short_key, story_type, timebox_hours, stale_at FROM stories
+         WHERE id = ANY($1)
+           AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))
+           AND deleted_at IS NULL",
+    )
+    .bind(story_ids)
+    .bind(organization_id)
+    .fetch_all(executor)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching stories by id");
+        AppError::InternalServerError
+    })?;
+
+    Ok(story_rows.into_iter().map(Story::from).collect())
+}
+
 pub async fn create_sprint(
     pool: &PgPool,
     project_id: Uuid,
@@ -398,6 +643,95 @@ pub async fn get_team_active_sprint(
     }
 }
 
+/// How many sprints the team has had, and when the latest one ends
+pub async fn get_team_sprint_summary(
+    pool: &PgPool,
+    team_id: Uuid,
+) -> Result<(i64, Option<DateTime<Utc>>), AppError> {
+    sqlx::query_as::<_, (i64, Option<DateTime<Utc>>)>(
+        "SELECT COUNT(*), MAX(end_date) FROM sprints WHERE team_id = $1",
+    )
+    .bind(team_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error summarizing team sprints");
+        AppError::InternalServerError
+    })
+}
+
+/// Organization holidays between two dates, inclusive
+pub async fn get_organization_holidays(
+    pool: &PgPool,
+    organization_id: Uuid,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<HashSet<NaiveDate>, AppError> {
+    let dates: Vec<NaiveDate> = sqlx::query_scalar(
+        r#"
+        SELECT holiday_date
+        FROM organization_holidays
+        WHERE organization_id = $1 AND holiday_date BETWEEN $2 AND $3
+        "#,
+    )
+    .bind(organization_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching organization holidays");
+        AppError::InternalServerError
+    })?;
+
+    Ok(dates.into_iter().collect())
+}
+
+/// Calendar in the project's configured time zone
+pub async fn get_project_calendar(
+    pool: &PgPool,
+    project_id: Uuid,
+) -> Result<SprintCalendar, AppError> {
+    let timezone: Option<String> =
+        sqlx::query_scalar("SELECT timezone FROM project_settings WHERE project_id = $1")
+            .bind(project_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "SQL error fetching project time zone");
+                AppError::InternalServerError
+            })?;
+
+    Ok(timezone
+        .map(|name| SprintCalendar::from_name(&name))
+        .unwrap_or_default())
+}
+
+/// Calendar for a sprint, in the time zone shared by its team's projects
+pub async fn get_sprint_calendar(
+    pool: &PgPool,
+    sprint_id: Uuid,
+) -> Result<SprintCalendar, AppError> {
+    let zones: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT DISTINCT ps.timezone
+        FROM sprints s
+        JOIN projects p ON p.team_id = s.team_id
+        JOIN project_settings ps ON ps.project_id = p.id
+        WHERE s.id = $1
+        "#,
+    )
+    .bind(sprint_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching sprint time zones");
+        AppError::InternalServerError
+    })?;
+
+    Ok(shared_timezone(&zones))
+}
+
 pub async fn set_team_active_sprint(
     pool: &PgPool,
     team_id: Uuid,
@@ -452,39 +786,220 @@ pub async fn update_sprint_committed_points(
     Ok(())
 }
 
-pub async fn get_stories_by_project(
+pub async fn get_sprint(
     pool: &PgPool,
-    project_id: Uuid,
+    sprint_id: Uuid,
     organization_id: Option<Uuid>,
-    sprint_id: Option<Uuid>,
-) -> Result<Vec<Story>, AppError> {
-    let story_rows = sqlx::query_as::<_, StoryRow>(
-        "SELECT id, project_id, organization_id, title, description, status, labels, story_points, sprint_id, assigned_to_user_id, readiness_override, readiness_override_by, readiness_override_reason, readiness_override_at, created_at, updated_at FROM stories
-         WHERE project_id = $1
-           AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))
-           AND ($3::uuid IS NULL OR sprint_id = $3)
-           AND deleted_at IS NULL
-         ORDER BY title",
+) -> Result<Option<SprintRow>, AppError> {
+    fetch_sprint(pool, sprint_id, organization_id).await
+}
+
+pub async fn get_sprint_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    sprint_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Option<SprintRow>, AppError> {
+    fetch_sprint(&mut **tx, sprint_id, organization_id).await
+}
+
+async fn fetch_sprint<'e, E>(
+    executor: E,
+    sprint_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Option<SprintRow>, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query_as::<_, SprintRow>(
+        "SELECT sp.id, sp.team_id, t.organization_id, sp.name, sp.goal, sp.status,
+                sp.capacity_points, sp.committed_points, sp.completed_points,
+                sp.start_date, sp.end_date, sp.created_at, sp.updated_at
+         FROM sprints sp
+         INNER JOIN teams t ON t.id = sp.team_id
+         WHERE sp.id = $1 AND t.organization_id IS NOT DISTINCT FROM $2",
     )
-    .bind(project_id)
-    .bind(organization_id)
     .bind(sprint_id)
-    .fetch_all(pool)
+    .bind(organization_id)
+    .fetch_optional(executor)
     .await
     .map_err(|e| {
-        tracing::error!(error = %e, "SQL error fetching stories by project");
+        tracing::error!(error = %e, "SQL error fetching sprint");
         AppError::InternalServerError
-    })?;
-
-    let mut stories: Vec<Story> = story_rows.into_iter().map(Story::from).collect();
-    let story_ids: Vec<Uuid> = stories.iter().map(|story| story.id).collect();
+    })
+}
 
-    if !story_ids.is_empty() {
-        let acceptance_rows = sqlx::query_as::<_, AcceptanceCriteriaRow>(
-            "SELECT id, story_id, description, given, when_clause, then_clause, created_at
+/// Add `delta` (negative to remove) to a sprint's committed points. Never drops
+/// below the completed points, which the table requires.
+pub async fn adjust_sprint_committed_points_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    sprint_id: Uuid,
+    delta: i32,
+) -> Result<i32, AppError> {
+    sqlx::query_scalar::<_, i32>(
+        "UPDATE sprints
+         SET committed_points = GREATEST(committed_points + $2, completed_points), updated_at = NOW()
+         WHERE id = $1
+         RETURNING committed_points",
+    )
+    .bind(sprint_id)
+    .bind(delta)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error adjusting sprint committed points");
+        AppError::InternalServerError
+    })
+}
+
+/// Record a story moving into (`added`) or out of a sprint
+pub async fn record_sprint_scope_change_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    sprint_id: Uuid,
+    organization_id: Option<Uuid>,
+    change: &ScopeChange,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO sprint_scope_changes
+             (id, sprint_id, story_id, organization_id, story_points, added, sprint_status, changed_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(sprint_id)
+    .bind(change.story_id)
+    .bind(organization_id)
+    .bind(change.story_points.map(|points| points as i32))
+    .bind(change.added)
+    .bind(&change.sprint_status)
+    .bind(change.changed_at)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error recording sprint scope change");
+        AppError::InternalServerError
+    })?;
+    Ok(())
+}
+
+/// Every story moved into or out of the sprint, oldest first
+pub async fn get_sprint_scope_changes(
+    pool: &PgPool,
+    sprint_id: Uuid,
+) -> Result<Vec<ScopeChange>, AppError> {
+    let rows: Vec<(Uuid, Option<i32>, bool, String, DateTime<Utc>)> = sqlx::query_as(
+        "SELECT story_id, story_points, added, sprint_status, changed_at
+         FROM sprint_scope_changes
+         WHERE sprint_id = $1
+         ORDER BY changed_at",
+    )
+    .bind(sprint_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching sprint scope changes");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(story_id, story_points, added, sprint_status, changed_at)| ScopeChange {
+                story_id,
+                story_points: story_points.map(|points| points as u32),
+                added,
+                sprint_status,
+                changed_at,
+            },
+        )
+        .collect())
+}
+
+/// Active contributors of a team, who pick up sprint tasks
+pub async fn get_team_contributors(pool: &PgPool, team_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+    sqlx::query_scalar::<_, Uuid>(
+        "SELECT user_id FROM team_memberships
+         WHERE team_id = $1 AND is_active
+           AND role IN ('contributor', 'managing_contributor')
+         ORDER BY joined_at",
+    )
+    .bind(team_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching team contributors");
+        AppError::InternalServerError
+    })
+}
+
+/// Load the acceptance criteria of stories fetched without them
+pub async fn attach_acceptance_criteria<'e, E>(
+    executor: E,
+    stories: &mut [Story],
+) -> Result<(), AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let story_ids: Vec<Uuid> = stories.iter().map(|story| story.id).collect();
+    let acceptance_rows = sqlx::query_as::<_, AcceptanceCriteriaRow>(
+        "SELECT id, story_id, description, given, when_clause, then_clause, position, created_at
+         FROM acceptance_criteria
+         WHERE story_id = ANY($1)
+         ORDER BY position, created_at",
+    )
+    .bind(&story_ids)
+    .fetch_all(executor)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching acceptance criteria");
+        AppError::InternalServerError
+    })?;
+
+    let mut by_story: HashMap<Uuid, Vec<AcceptanceCriteria>> = HashMap::new();
+    for row in acceptance_rows {
+        by_story
+            .entry(row.story_id)
+            .or_default()
+            .push(AcceptanceCriteria::from(row));
+    }
+    for story in stories.iter_mut() {
+        story.acceptance_criteria = by_story.remove(&story.id).unwrap_or_default();
+    }
+
+    Ok(())
+}
+
+pub async fn get_stories_by_project(
+    pool: &PgPool,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+    sprint_id: Option<Uuid>,
+) -> Result<Vec<Story>, AppError> {
+    let story_rows = sqlx::query_as::<_, StoryRow>(
+        "SELECT id, project_id, organization_id, title, description, status, labels, story_points, sprint_id, assigned_to_user_id, readiness_override, readiness_override_by, readiness_override_reason, readiness_override_at, created_at, updated_at, short_key, story_type, timebox_hours, stale_at FROM stories
+         WHERE project_id = $1
+           AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))
+           AND ($3::uuid IS NULL OR sprint_id = $3)
+           AND deleted_at IS NULL
+         ORDER BY title",
+    )
+    .bind(project_id)
+    .bind(organization_id)
+    .bind(sprint_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching stories by project");
+        AppError::InternalServerError
+    })?;
+
+    let mut stories: Vec<Story> = story_rows.into_iter().map(Story::from).collect();
+    let story_ids: Vec<Uuid> = stories.iter().map(|story| story.id).collect();
+
+    if !story_ids.is_empty() {
+        let acceptance_rows = sqlx::query_as::<_, AcceptanceCriteriaRow>(
+            "SELECT id, story_id, description, given, when_clause, then_clause, position, created_at
              FROM acceptance_criteria
              WHERE story_id = ANY($1)
-             ORDER BY created_at",
+             ORDER BY position, created_at",
         )
         .bind(&story_ids)
         .fetch_all(pool)
@@ -512,12 +1027,144 @@ pub async fn get_stories_by_project(
     Ok(stories)
 }
 
+const STORY_COLUMNS: &str = "id, project_id, organization_id, title, description, status, labels, story_points, sprint_id, assigned_to_user_id, readiness_override, readiness_override_by, readiness_override_reason, readiness_override_at, created_at, updated_at, short_key, story_type, timebox_hours, stale_at";
+
+/// A page of the organization's own stories of a project matching `query`
+pub async fn query_project_stories(
+    pool: &PgPool,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+    query: &StoryQuery,
+) -> Result<StoryPage, AppError> {
+    query_stories(pool, query, |builder| {
+        builder.push("stories.project_id = ");
+        builder.push_bind(project_id);
+        builder.push(" AND (stories.organization_id = ");
+        builder.push_bind(organization_id);
+        builder.push(" OR (");
+        builder.push_bind(organization_id);
+        builder.push("::uuid IS NULL AND stories.organization_id IS NULL))");
+    })
+    .await
+}
+
+/// A page of another organization's project stories shared with
+/// `organization_id` and matching `query`
+pub async fn query_shared_project_stories(
+    pool: &PgPool,
+    organization_id: Uuid,
+    project_id: Uuid,
+    query: &StoryQuery,
+) -> Result<StoryPage, AppError> {
+    query_stories(pool, query, |builder| {
+        builder.push("stories.project_id = ");
+        builder.push_bind(project_id);
+        builder.push(
+            " AND EXISTS (
+                SELECT 1 FROM story_shares sh
+                WHERE sh.shared_with_organization_id = ",
+        );
+        builder.push_bind(organization_id);
+        builder.push(
+            " AND sh.revoked_at IS NULL
+                  AND sh.owner_organization_id = stories.organization_id
+                  AND sh.project_id = stories.project_id
+                  AND (sh.story_id IS NULL OR sh.story_id = stories.id))",
+        );
+    })
+    .await
+}
+
+/// Count and fetch the stories within `scope` matching `query`
+async fn query_stories(
+    pool: &PgPool,
+    query: &StoryQuery,
+    scope: impl Fn(&mut QueryBuilder<'_, Postgres>),
+) -> Result<StoryPage, AppError> {
+    let conditions = |builder: &mut QueryBuilder<'_, Postgres>| {
+        scope(builder);
+        builder.push(" AND stories.deleted_at IS NULL");
+        if let Some(filter) = &query.filter {
+            builder.push(" AND ");
+            push_story_filter(builder, filter);
+        }
+    };
+
+    let mut count = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM stories WHERE ");
+    conditions(&mut count);
+    let total: i64 = count
+        .build_query_scalar()
+        .fetch_one(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error counting stories");
+            AppError::InternalServerError
+        })?;
+
+    let mut select =
+        QueryBuilder::<Postgres>::new(format!("SELECT {} FROM stories WHERE ", STORY_COLUMNS));
+    conditions(&mut select);
+    let direction = if query.sort.descending { "DESC" } else { "ASC" };
+    select.push(match query.sort.field {
+        StorySortField::Title => format!(" ORDER BY stories.title {}", direction),
+        StorySortField::CreatedAt => format!(" ORDER BY stories.created_at {}", direction),
+        StorySortField::UpdatedAt => format!(" ORDER BY stories.updated_at {}", direction),
+        StorySortField::Points => {
+            format!(" ORDER BY stories.story_points {} NULLS LAST", direction)
+        }
+    });
+    select.push(format!(", stories.id {}", direction));
+    if let Some(limit) = query.limit {
+        select.push(" LIMIT ");
+        select.push_bind(limit);
+    }
+    if query.offset > 0 {
+        select.push(" OFFSET ");
+        select.push_bind(query.offset);
+    }
+
+    let story_rows = select
+        .build_query_as::<StoryRow>()
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error querying stories");
+            AppError::InternalServerError
+        })?;
+
+    let mut stories: Vec<Story> = story_rows.into_iter().map(Story::from).collect();
+    attach_acceptance_criteria(pool, &mut stories).await?;
+    Ok(StoryPage { stories, total })
+}
+
 // Task persistence helpers
 pub async fn create_task(pool: &PgPool, task: &Task) -> Result<(), AppError> {
+    insert_task(pool, task).await
+}
+
+pub async fn create_task_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    task: &Task,
+) -> Result<(), AppError> {
+    insert_task(&mut **tx, task).await
+}
+
+async fn insert_task<'e, E>(executor: E, task: &Task) -> Result<(), AppError>
+where
+    E: PgExecutor<'e>,
+{
+    // Tasks draw from their story's project counter, shared with stories
     sqlx::query(
-        "INSERT INTO tasks (id, story_id, organization_id, title, description, acceptance_criteria_refs,
-                           status, owner_user_id, estimated_hours, created_at, updated_at, owned_at, completed_at)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
+        "WITH counter AS (
+             UPDATE projects SET next_item_number = next_item_number + 1
+             WHERE id = (SELECT project_id FROM stories WHERE id = $2)
+             RETURNING key || '-' || (next_item_number - 1) AS short_key
+         )
+         INSERT INTO tasks (id, story_id, organization_id, title, description, acceptance_criteria_refs,
+                            status, owner_user_id, estimated_hours, created_at, updated_at, owned_at, completed_at,
+                            short_key, split_from_task_id)
+         SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, counter.short_key, $14
+         FROM (SELECT 1) AS one LEFT JOIN counter ON TRUE",
     )
     .bind(task.id)
     .bind(task.story_id)
@@ -532,7 +1179,8 @@ pub async fn create_task(pool: &PgPool, task: &Task) -> Result<(), AppError> {
     .bind(task.updated_at)
     .bind(task.owned_at)
     .bind(task.completed_at)
-    .execute(pool)
+    .bind(task.split_from_task_id)
+    .execute(executor)
     .await
     .map_err(|e| {
         tracing::error!(error = %e, "SQL error inserting task");
@@ -549,7 +1197,8 @@ pub async fn get_task(
 ) -> Result<Option<Task>, AppError> {
     let task_row = sqlx::query_as::<_, TaskRow>(
         "SELECT id, story_id, organization_id, title, description, acceptance_criteria_refs,
-                status, owner_user_id, estimated_hours, created_at, updated_at, owned_at, completed_at
+                status, owner_user_id, estimated_hours, created_at, updated_at, owned_at, completed_at, short_key,
+                split_from_task_id
          FROM tasks
          WHERE id = $1 AND (
              (organization_id IS NOT NULL AND organization_id = $2) OR
@@ -573,16 +1222,36 @@ pub async fn get_tasks_by_story(
     story_id: Uuid,
     organization_id: Option<Uuid>,
 ) -> Result<Vec<Task>, AppError> {
+    fetch_tasks_by_story(pool, story_id, organization_id).await
+}
+
+pub async fn get_tasks_by_story_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    story_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Vec<Task>, AppError> {
+    fetch_tasks_by_story(&mut **tx, story_id, organization_id).await
+}
+
+async fn fetch_tasks_by_story<'e, E>(
+    executor: E,
+    story_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Vec<Task>, AppError>
+where
+    E: PgExecutor<'e>,
+{
     let task_rows = sqlx::query_as::<_, TaskRow>(
         "SELECT id, story_id, organization_id, title, description, acceptance_criteria_refs,
-                status, owner_user_id, estimated_hours, created_at, updated_at, owned_at, completed_at
+                status, owner_user_id, estimated_hours, created_at, updated_at, owned_at, completed_at, short_key,
+                split_from_task_id
          FROM tasks
          WHERE story_id = $1 AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))
          ORDER BY title",
     )
     .bind(story_id)
     .bind(organization_id)
-    .fetch_all(pool)
+    .fetch_all(executor)
     .await
     .map_err(|e| {
         tracing::error!(error = %e, "SQL error fetching tasks by story");
@@ -592,7 +1261,119 @@ pub async fn get_tasks_by_story(
     Ok(task_rows.into_iter().map(Task::from).collect())
 }
 
+/// Tasks split out of `task_id`, in the order they were created
+pub async fn get_split_tasks(
+    pool: &PgPool,
+    task_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Vec<Task>, AppError> {
+    fetch_split_tasks(pool, task_id, organization_id).await
+}
+
+pub async fn get_split_tasks_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    task_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Vec<Task>, AppError> {
+    fetch_split_tasks(&mut **tx, task_id, organization_id).await
+}
+
+async fn fetch_split_tasks<'e, E>(
+    executor: E,
+    task_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Vec<Task>, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let task_rows = sqlx::query_as::<_, TaskRow>(
+        "SELECT id, story_id, organization_id, title, description, acceptance_criteria_refs,
+                status, owner_user_id, estimated_hours, created_at, updated_at, owned_at, completed_at, short_key,
+                split_from_task_id
+         FROM tasks
+         WHERE split_from_task_id = $1 AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))
+         ORDER BY created_at, short_key",
+    )
+    .bind(task_id)
+    .bind(organization_id)
+    .fetch_all(executor)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching split tasks");
+        AppError::InternalServerError
+    })?;
+
+    Ok(task_rows.into_iter().map(Task::from).collect())
+}
+
 pub async fn update_task(pool: &PgPool, task: &Task) -> Result<(), AppError> {
+    write_task(pool, task).await
+}
+
+/// Atomically give an available, unowned task to `task.owner_user_id`.
+/// Returns false when someone else got there first.
+pub async fn claim_task(pool: &PgPool, task: &Task) -> Result<bool, AppError> {
+    let claimed = sqlx::query_scalar::<_, Uuid>(
+        "UPDATE tasks SET status = $2, owner_user_id = $3, owned_at = $4, updated_at = $5
+         WHERE id = $1 AND (organization_id = $6 OR ($6 IS NULL AND organization_id IS NULL))
+           AND owner_user_id IS NULL AND status = 'available'
+         RETURNING id",
+    )
+    .bind(task.id)
+    .bind(task.status.to_string())
+    .bind(task.owner_user_id)
+    .bind(task.owned_at)
+    .bind(task.updated_at)
+    .bind(task.organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error claiming task");
+        AppError::InternalServerError
+    })?;
+
+    Ok(claimed.is_some())
+}
+
+pub async fn update_task_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    task: &Task,
+) -> Result<(), AppError> {
+    write_task(&mut **tx, task).await
+}
+
+pub async fn record_task_status_note_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    task: &Task,
+    author_user_id: Uuid,
+    from_status: &str,
+    note: &str,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO task_status_notes
+             (task_id, organization_id, author_user_id, from_status, to_status, note)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(task.id)
+    .bind(task.organization_id)
+    .bind(author_user_id)
+    .bind(from_status)
+    .bind(task.status.to_string())
+    .bind(note)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error recording task status note");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+async fn write_task<'e, E>(executor: E, task: &Task) -> Result<(), AppError>
+where
+    E: PgExecutor<'e>,
+{
     sqlx::query(
         "UPDATE tasks SET title = $2, description = $3, acceptance_criteria_refs = $4,
                          status = $5, owner_user_id = $6, estimated_hours = $7,
@@ -610,7 +1391,7 @@ pub async fn update_task(pool: &PgPool, task: &Task) -> Result<(), AppError> {
     .bind(task.owned_at)
     .bind(task.completed_at)
     .bind(task.organization_id)
-    .execute(pool)
+    .execute(executor)
     .await
     .map_err(|e| {
         tracing::error!(error = %e, "SQL error updating task");
@@ -645,7 +1426,8 @@ pub async fn get_tasks_by_owner(
     let task_rows = if let Some(org_id) = organization_id {
         sqlx::query_as::<_, TaskRow>(
             "SELECT id, story_id, organization_id, title, description, acceptance_criteria_refs,
-                    status, owner_user_id, estimated_hours, created_at, updated_at, owned_at, completed_at
+                    status, owner_user_id, estimated_hours, created_at, updated_at, owned_at, completed_at, short_key,
+                    split_from_task_id
              FROM tasks
              WHERE owner_user_id = $1 AND organization_id = $2
              ORDER BY updated_at DESC",
@@ -657,7 +1439,8 @@ pub async fn get_tasks_by_owner(
     } else {
         sqlx::query_as::<_, TaskRow>(
             "SELECT id, story_id, organization_id, title, description, acceptance_criteria_refs,
-                    status, owner_user_id, estimated_hours, created_at, updated_at, owned_at, completed_at
+                    status, owner_user_id, estimated_hours, created_at, updated_at, owned_at, completed_at, short_key,
+                    split_from_task_id
              FROM tasks
              WHERE owner_user_id = $1
              ORDER BY updated_at DESC",
@@ -698,6 +1481,51 @@ pub async fn get_tasks_by_sprint(
     Ok(task_rows.into_iter().map(Task::from).collect())
 }
 
+pub async fn get_sprint_name(
+    pool: &PgPool,
+    sprint_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Option<String>, AppError> {
+    sqlx::query_scalar::<_, String>(
+        "SELECT name FROM sprints
+         WHERE id = $1
+         AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))",
+    )
+    .bind(sprint_id)
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching sprint name");
+        AppError::InternalServerError
+    })
+}
+
+/// Stories committed to a sprint, without their acceptance criteria
+pub async fn get_stories_by_sprint(
+    pool: &PgPool,
+    sprint_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Vec<Story>, AppError> {
+    let story_rows = sqlx::query_as::<_, StoryRow>(
+        "SELECT id, project_id, organization_id, title, description, status, labels, story_points, sprint_id, assigned_to_user_id, readiness_override, readiness_override_by, readiness_override_reason, readiness_override_at, created_at, updated_at, short_key, story_type, timebox_hours, stale_at FROM stories
+         WHERE sprint_id = $1
+           AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))
+           AND deleted_at IS NULL
+         ORDER BY title",
+    )
+    .bind(sprint_id)
+    .bind(organization_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching stories by sprint");
+        AppError::InternalServerError
+    })?;
+
+    Ok(story_rows.into_iter().map(Story::from).collect())
+}
+
 pub async fn get_tasks_by_project(
     pool: &PgPool,
     project_id: Uuid,
@@ -778,3 +1606,2673 @@ pub async fn take_task_ownership_atomic(
 
     Ok(result.rows_affected() > 0)
 }
+
+pub async fn api_key_is_active(pool: &PgPool, token: &str) -> Result<bool, AppError> {
+    sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM api_keys WHERE token = $1)")
+        .bind(token)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error checking API key");
+            AppError::InternalServerError
+        })
+}
+
+pub async fn upsert_project_badge_token(
+    pool: &PgPool,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+    token: &str,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO project_badge_tokens (project_id, organization_id, token, created_at)
+         VALUES ($1, $2, $3, NOW())
+         ON CONFLICT (project_id)
+         DO UPDATE SET token = EXCLUDED.token, created_at = EXCLUDED.created_at",
+    )
+    .bind(project_id)
+    .bind(organization_id)
+    .bind(token)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error storing project badge token");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+pub async fn project_badge_token_is_valid(
+    pool: &PgPool,
+    project_id: Uuid,
+    token: &str,
+) -> Result<bool, AppError> {
+    sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM project_badge_tokens WHERE project_id = $1 AND token = $2)",
+    )
+    .bind(project_id)
+    .bind(token)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error checking project badge token");
+        AppError::InternalServerError
+    })
+}
+
+pub async fn count_stories_by_status(
+    pool: &PgPool,
+    project_id: Uuid,
+) -> Result<Vec<(String, i64)>, AppError> {
+    sqlx::query_as::<_, (String, i64)>(
+        "SELECT status, COUNT(*) FROM stories
+         WHERE project_id = $1 AND deleted_at IS NULL
+         GROUP BY status",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error counting stories by status");
+        AppError::InternalServerError
+    })
+}
+
+/// Story-to-story mention links within a project, as (source, target) pairs
+pub async fn get_story_mention_edges(
+    pool: &PgPool,
+    project_id: Uuid,
+) -> Result<Vec<(Uuid, Uuid)>, AppError> {
+    sqlx::query_as::<_, (Uuid, Uuid)>(
+        "SELECT r.source_id, r.target_id
+         FROM item_references r
+         JOIN stories source ON source.id = r.source_id
+         JOIN stories target ON target.id = r.target_id
+         WHERE r.source_type = 'story' AND r.target_type = 'story'
+           AND source.project_id = $1 AND target.project_id = $1
+           AND source.deleted_at IS NULL AND target.deleted_at IS NULL",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching story mention edges");
+        AppError::InternalServerError
+    })
+}
+
+pub async fn upsert_backlog_health_snapshot(
+    pool: &PgPool,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+    snapshot: &BacklogHealthSnapshot,
+    backlog_stories: usize,
+) -> Result<(), AppError> {
+    let sub_scores = &snapshot.sub_scores;
+    sqlx::query(
+        "INSERT INTO backlog_health_snapshots
+             (project_id, snapshot_date, organization_id, score, readiness, draft_age,
+              estimation, acceptance_criteria, duplicates, dependencies, backlog_stories,
+              updated_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, NOW())
+         ON CONFLICT (project_id, snapshot_date) DO UPDATE SET
+             score = EXCLUDED.score,
+             readiness = EXCLUDED.readiness,
+             draft_age = EXCLUDED.draft_age,
+             estimation = EXCLUDED.estimation,
+             acceptance_criteria = EXCLUDED.acceptance_criteria,
+             duplicates = EXCLUDED.duplicates,
+             dependencies = EXCLUDED.dependencies,
+             backlog_stories = EXCLUDED.backlog_stories,
+             updated_at = NOW()",
+    )
+    .bind(project_id)
+    .bind(snapshot.date)
+    .bind(organization_id)
+    .bind(snapshot.score as i16)
+    .bind(sub_scores.readiness as i16)
+    .bind(sub_scores.draft_age as i16)
+    .bind(sub_scores.estimation as i16)
+    .bind(sub_scores.acceptance_criteria as i16)
+    .bind(sub_scores.duplicates as i16)
+    .bind(sub_scores.dependencies as i16)
+    .bind(backlog_stories as i32)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error storing backlog health snapshot");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+/// Daily snapshots on or after `since`, oldest first
+pub async fn get_backlog_health_snapshots(
+    pool: &PgPool,
+    project_id: Uuid,
+    since: NaiveDate,
+) -> Result<Vec<BacklogHealthSnapshot>, AppError> {
+    let rows = sqlx::query_as::<_, (NaiveDate, i16, i16, i16, i16, i16, i16, i16)>(
+        "SELECT snapshot_date, score, readiness, draft_age, estimation, acceptance_criteria,
+                duplicates, dependencies
+         FROM backlog_health_snapshots
+         WHERE project_id = $1 AND snapshot_date >= $2
+         ORDER BY snapshot_date",
+    )
+    .bind(project_id)
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching backlog health snapshots");
+        AppError::InternalServerError
+    })?;
+
+    let score = |value: i16| value.clamp(0, 100) as u8;
+    Ok(rows
+        .into_iter()
+        .map(
+            |(
+                date,
+                total,
+                readiness,
+                draft_age,
+                estimation,
+                criteria,
+                duplicates,
+                dependencies,
+            )| {
+                BacklogHealthSnapshot {
+                    date,
+                    score: score(total),
+                    sub_scores: BacklogHealthSubScores {
+                        readiness: score(readiness),
+                        draft_age: score(draft_age),
+                        estimation: score(estimation),
+                        acceptance_criteria: score(criteria),
+                        duplicates: score(duplicates),
+                        dependencies: score(dependencies),
+                    },
+                }
+            },
+        )
+        .collect())
+}
+
+/// Completed and total task counts for the project's active sprint, if there is one
+pub async fn get_active_sprint_task_progress(
+    pool: &PgPool,
+    project_id: Uuid,
+) -> Result<Option<(i64, i64)>, AppError> {
+    sqlx::query_as::<_, (i64, i64)>(
+        "SELECT COUNT(t.id) FILTER (WHERE t.status = 'completed'), COUNT(t.id)
+         FROM sprints sp
+         LEFT JOIN stories s ON s.sprint_id = sp.id AND s.deleted_at IS NULL
+         LEFT JOIN tasks t ON t.story_id = s.id AND t.status <> 'superseded'
+         WHERE sp.id = (
+             SELECT id FROM sprints
+             WHERE project_id = $1 AND status = 'active'
+             ORDER BY created_at DESC
+             LIMIT 1
+         )
+         GROUP BY sp.id",
+    )
+    .bind(project_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching active sprint progress");
+        AppError::InternalServerError
+    })
+}
+
+/// Look up the story or task a short key belongs to, scoped to the organization
+pub async fn resolve_short_key(
+    pool: &PgPool,
+    key: &str,
+    organization_id: Option<Uuid>,
+) -> Result<Option<ResolvedShortKey>, AppError> {
+    let row = sqlx::query_as::<_, (String, Uuid, Uuid, Option<Uuid>)>(
+        "SELECT 'story', s.id, s.project_id, NULL::uuid
+         FROM stories s
+         WHERE s.short_key = $1 AND s.deleted_at IS NULL
+           AND (s.organization_id = $2 OR ($2 IS NULL AND s.organization_id IS NULL))
+         UNION ALL
+         SELECT 'task', t.id, s.project_id, t.story_id
+         FROM tasks t
+         JOIN stories s ON s.id = t.story_id
+         WHERE t.short_key = $1
+           AND (t.organization_id = $2 OR ($2 IS NULL AND t.organization_id IS NULL))
+         LIMIT 1",
+    )
+    .bind(key)
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error resolving short key");
+        AppError::InternalServerError
+    })?;
+
+    Ok(
+        row.map(|(kind, id, project_id, story_id)| ResolvedShortKey {
+            key: key.to_string(),
+            target: short_key_target(&kind),
+            id,
+            project_id,
+            story_id,
+        }),
+    )
+}
+
+/// Unscoped short key lookup used to rewrite path parameters; handlers still enforce
+/// organization access on the resolved id
+pub async fn find_id_by_short_key(pool: &PgPool, key: &str) -> Result<Option<Uuid>, AppError> {
+    sqlx::query_scalar(
+        "SELECT id FROM stories WHERE short_key = $1
+         UNION ALL
+         SELECT id FROM tasks WHERE short_key = $1
+         LIMIT 1",
+    )
+    .bind(key)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error looking up short key");
+        AppError::InternalServerError
+    })
+}
+
+fn short_key_target(kind: &str) -> ShortKeyTarget {
+    if kind == "task" {
+        ShortKeyTarget::Task
+    } else {
+        ShortKeyTarget::Story
+    }
+}
+
+/// Stories and tasks with any of the given short keys, across organizations
+pub async fn find_items_by_short_keys(
+    pool: &PgPool,
+    keys: &[String],
+) -> Result<Vec<ReferencedItem>, AppError> {
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query_as::<_, (String, String, Uuid, Option<Uuid>)>(
+        "SELECT short_key, 'story', id, organization_id
+         FROM stories
+         WHERE short_key = ANY($1) AND deleted_at IS NULL
+         UNION ALL
+         SELECT short_key, 'task', id, organization_id
+         FROM tasks
+         WHERE short_key = ANY($1)",
+    )
+    .bind(keys)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error finding items by short key");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(key, kind, id, organization_id)| ReferencedItem {
+            key,
+            target: short_key_target(&kind),
+            id,
+            organization_id,
+        })
+        .collect())
+}
+
+/// Replace the links written by a story or task with the items it mentions now
+pub async fn replace_item_references(
+    pool: &PgPool,
+    source_type: ReferenceSourceType,
+    source_id: Uuid,
+    organization_id: Option<Uuid>,
+    targets: &[ReferencedItem],
+) -> Result<(), AppError> {
+    let mut uow = UnitOfWork::begin(pool).await?;
+    let result = replace_item_references_with_transaction(
+        uow.tx(),
+        source_type,
+        source_id,
+        organization_id,
+        targets,
+    )
+    .await;
+    uow.finish(result).await
+}
+
+async fn replace_item_references_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    source_type: ReferenceSourceType,
+    source_id: Uuid,
+    organization_id: Option<Uuid>,
+    targets: &[ReferencedItem],
+) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM item_references WHERE source_type = $1 AND source_id = $2")
+        .bind(source_type.as_str())
+        .bind(source_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error clearing item references");
+            AppError::InternalServerError
+        })?;
+
+    for target in targets {
+        insert_item_reference(
+            &mut **tx,
+            organization_id,
+            source_type,
+            Some(source_id),
+            None,
+            None,
+            target,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Record links from a commit, pull request or comment; repeated deliveries are ignored
+pub async fn add_external_references(
+    pool: &PgPool,
+    source_type: ReferenceSourceType,
+    url: &str,
+    title: &str,
+    targets: &[ReferencedItem],
+) -> Result<(), AppError> {
+    for target in targets {
+        insert_item_reference(
+            pool,
+            target.organization_id,
+            source_type,
+            None,
+            Some(url),
+            Some(title),
+            target,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+async fn insert_item_reference<'e, E>(
+    executor: E,
+    organization_id: Option<Uuid>,
+    source_type: ReferenceSourceType,
+    source_id: Option<Uuid>,
+    source_url: Option<&str>,
+    source_title: Option<&str>,
+    target: &ReferencedItem,
+) -> Result<(), AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query(
+        "INSERT INTO item_references
+            (id, organization_id, source_type, source_id, source_url, source_title, target_type, target_id)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(Uuid::new_v4())
+    .bind(organization_id)
+    .bind(source_type.as_str())
+    .bind(source_id)
+    .bind(source_url)
+    .bind(source_title)
+    .bind(ReferenceSourceType::from(target.target).as_str())
+    .bind(target.id)
+    .execute(executor)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error inserting item reference");
+        AppError::InternalServerError
+    })?;
+    Ok(())
+}
+
+#[derive(sqlx::FromRow)]
+struct ItemReferenceRow {
+    id: Uuid,
+    outgoing: bool,
+    source_type: String,
+    source_id: Option<Uuid>,
+    source_key: Option<String>,
+    source_url: Option<String>,
+    source_title: Option<String>,
+    target_type: String,
+    target_id: Uuid,
+    target_key: Option<String>,
+    target_title: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Links from and to a story or any of its tasks, oldest first
+pub async fn get_story_references(
+    pool: &PgPool,
+    story_id: Uuid,
+) -> Result<Vec<ItemReference>, AppError> {
+    let rows = sqlx::query_as::<_, ItemReferenceRow>(
+        "WITH scope AS (
+             SELECT $1::uuid AS id
+             UNION ALL
+             SELECT id FROM tasks WHERE story_id = $1
+         )
+         SELECT r.id,
+                COALESCE(r.source_id IN (SELECT id FROM scope), FALSE) AS outgoing,
+                r.source_type,
+                r.source_id,
+                COALESCE(source_story.short_key, source_task.short_key) AS source_key,
+                r.source_url,
+                COALESCE(source_story.title, source_task.title, r.source_title) AS source_title,
+                r.target_type,
+                r.target_id,
+                COALESCE(target_story.short_key, target_task.short_key) AS target_key,
+                COALESCE(target_story.title, target_task.title) AS target_title,
+                r.created_at
+         FROM item_references r
+         LEFT JOIN stories source_story ON r.source_type = 'story' AND source_story.id = r.source_id
+         LEFT JOIN tasks source_task ON r.source_type = 'task' AND source_task.id = r.source_id
+         LEFT JOIN stories target_story ON r.target_type = 'story' AND target_story.id = r.target_id
+         LEFT JOIN tasks target_task ON r.target_type = 'task' AND target_task.id = r.target_id
+         WHERE (r.source_id IN (SELECT id FROM scope) OR r.target_id IN (SELECT id FROM scope))
+           AND source_story.deleted_at IS NULL
+           AND target_story.deleted_at IS NULL
+         ORDER BY r.created_at, r.id",
+    )
+    .bind(story_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error loading story references");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(ItemReference {
+                id: row.id,
+                direction: if row.outgoing {
+                    ReferenceDirection::Outgoing
+                } else {
+                    ReferenceDirection::Incoming
+                },
+                source_type: ReferenceSourceType::from_str(&row.source_type)?,
+                source_id: row.source_id,
+                source_key: row.source_key,
+                source_url: row.source_url,
+                source_title: row.source_title,
+                target_type: short_key_target(&row.target_type),
+                target_id: row.target_id,
+                target_key: row.target_key,
+                target_title: row.target_title,
+                created_at: row.created_at,
+            })
+        })
+        .collect())
+}
+
+const STORY_REVISION_COLUMNS: &str =
+    "id, story_id, revision, title, description, labels, edited_by, created_at";
+
+/// Record the story's current title, description and labels as its next
+/// revision, unless they are unchanged since the latest one. Run it after the
+/// story row is written in the same transaction so the row lock orders
+/// concurrent edits.
+pub async fn record_story_revision_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    story: &Story,
+    edited_by: Option<Uuid>,
+) -> Result<Option<u32>, AppError> {
+    let latest = sqlx::query_as::<_, StoryRevisionRow>(&format!(
+        "SELECT {} FROM story_revisions WHERE story_id = $1 ORDER BY revision DESC LIMIT 1",
+        STORY_REVISION_COLUMNS
+    ))
+    .bind(story.id)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching latest story revision");
+        AppError::InternalServerError
+    })?
+    .map(StoryRevision::from);
+
+    if latest.as_ref().is_some_and(|latest| latest.matches(story)) {
+        return Ok(None);
+    }
+    let revision = latest.map_or(1, |latest| latest.revision + 1);
+
+    sqlx::query(
+        "INSERT INTO story_revisions
+             (id, story_id, organization_id, revision, title, description, labels, edited_by, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())",
+    )
+    .bind(Uuid::new_v4())
+    .bind(story.id)
+    .bind(story.organization_id)
+    .bind(revision as i32)
+    .bind(&story.title)
+    .bind(&story.description)
+    .bind(&story.labels)
+    .bind(edited_by)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error inserting story revision");
+        AppError::InternalServerError
+    })?;
+
+    Ok(Some(revision))
+}
+
+pub async fn get_story_revisions(
+    pool: &PgPool,
+    story_id: Uuid,
+) -> Result<Vec<StoryRevision>, AppError> {
+    let rows = sqlx::query_as::<_, StoryRevisionRow>(&format!(
+        "SELECT {} FROM story_revisions WHERE story_id = $1 ORDER BY revision DESC",
+        STORY_REVISION_COLUMNS
+    ))
+    .bind(story_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching story revisions");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows.into_iter().map(StoryRevision::from).collect())
+}
+
+pub async fn get_story_revision(
+    pool: &PgPool,
+    story_id: Uuid,
+    revision: u32,
+) -> Result<Option<StoryRevision>, AppError> {
+    let row = sqlx::query_as::<_, StoryRevisionRow>(&format!(
+        "SELECT {} FROM story_revisions WHERE story_id = $1 AND revision = $2",
+        STORY_REVISION_COLUMNS
+    ))
+    .bind(story_id)
+    .bind(revision as i32)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching story revision");
+        AppError::InternalServerError
+    })?;
+
+    Ok(row.map(StoryRevision::from))
+}
+
+/// Stories owned by the user in any of the given statuses, most recently updated
+/// first. Acceptance criteria are not loaded.
+pub async fn get_stories_assigned_to(
+    pool: &PgPool,
+    user_id: Uuid,
+    organization_id: Option<Uuid>,
+    statuses: &[StoryStatus],
+) -> Result<Vec<Story>, AppError> {
+    let statuses: Vec<String> = statuses.iter().map(ToString::to_string).collect();
+    let rows = sqlx::query_as::<_, StoryRow>(
+        "SELECT id, project_id, organization_id, title, description, status, labels, story_points, sprint_id, assigned_to_user_id, readiness_override, readiness_override_by, readiness_override_reason, readiness_override_at, created_at, updated_at, short_key, story_type, timebox_hours, stale_at FROM stories
+         WHERE assigned_to_user_id = $1
+           AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))
+           AND status = ANY($3)
+           AND deleted_at IS NULL
+         ORDER BY updated_at DESC",
+    )
+    .bind(user_id)
+    .bind(organization_id)
+    .bind(&statuses)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching assigned stories");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows.into_iter().map(Story::from).collect())
+}
+
+/// The organization's latest story edits across all projects, newest first
+pub async fn get_recent_story_revisions(
+    pool: &PgPool,
+    organization_id: Option<Uuid>,
+    limit: i64,
+) -> Result<Vec<StoryRevision>, AppError> {
+    let rows = sqlx::query_as::<_, StoryRevisionRow>(
+        "SELECT r.id, r.story_id, r.revision, r.title, r.description, r.labels, r.edited_by, r.created_at
+         FROM story_revisions r
+         JOIN stories s ON s.id = r.story_id
+         WHERE (r.organization_id = $1 OR ($1 IS NULL AND r.organization_id IS NULL))
+           AND s.deleted_at IS NULL
+         ORDER BY r.created_at DESC
+         LIMIT $2",
+    )
+    .bind(organization_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching recent story revisions");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows.into_iter().map(StoryRevision::from).collect())
+}
+
+/// Add the user to the task's claim queue, or extend their existing place
+pub async fn enqueue_task_claim(
+    pool: &PgPool,
+    task_id: Uuid,
+    user_id: Uuid,
+    organization_id: Option<Uuid>,
+    expires_at: DateTime<Utc>,
+) -> Result<ClaimQueueEntry, AppError> {
+    let (queued_at, expires_at, position) =
+        sqlx::query_as::<_, (DateTime<Utc>, DateTime<Utc>, i64)>(
+            "WITH entry AS (
+                 INSERT INTO task_claim_queue (task_id, user_id, organization_id, expires_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (task_id, user_id) DO UPDATE SET expires_at = EXCLUDED.expires_at
+                 RETURNING queued_at, expires_at
+             )
+             SELECT e.queued_at, e.expires_at,
+                    1 + (SELECT COUNT(*) FROM task_claim_queue q
+                         WHERE q.task_id = $1 AND q.user_id <> $2
+                           AND q.expires_at > NOW() AND q.queued_at < e.queued_at)
+             FROM entry e",
+        )
+        .bind(task_id)
+        .bind(user_id)
+        .bind(organization_id)
+        .bind(expires_at)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error queueing task claim");
+            AppError::InternalServerError
+        })?;
+
+    Ok(ClaimQueueEntry {
+        task_id,
+        user_id,
+        position: position as u32,
+        queued_at,
+        expires_at,
+    })
+}
+
+/// Remove the user from the task's claim queue; false if they were not in it
+pub async fn remove_task_claim(
+    pool: &PgPool,
+    task_id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, AppError> {
+    let result = sqlx::query("DELETE FROM task_claim_queue WHERE task_id = $1 AND user_id = $2")
+        .bind(task_id)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error removing task claim");
+            AppError::InternalServerError
+        })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Take the first unexpired entry off the task's claim queue, dropping expired ones
+pub async fn pop_next_task_claimant(
+    pool: &PgPool,
+    task_id: Uuid,
+) -> Result<Option<Uuid>, AppError> {
+    let map_err = |e: sqlx::Error| {
+        tracing::error!(error = %e, "SQL error taking next task claimant");
+        AppError::InternalServerError
+    };
+
+    sqlx::query("DELETE FROM task_claim_queue WHERE task_id = $1 AND expires_at <= NOW()")
+        .bind(task_id)
+        .execute(pool)
+        .await
+        .map_err(map_err)?;
+
+    sqlx::query_scalar::<_, Uuid>(
+        "DELETE FROM task_claim_queue
+         WHERE (task_id, user_id) = (
+             SELECT task_id, user_id FROM task_claim_queue
+             WHERE task_id = $1 AND expires_at > NOW()
+             ORDER BY queued_at
+             LIMIT 1
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING user_id",
+    )
+    .bind(task_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(map_err)
+}
+
+const TASK_TRANSFER_COLUMNS: &str = "id, task_id, organization_id, from_user_id, to_user_id, note,
+     handoff, status, created_at, resolved_at";
+
+/// Whether `user_id` can be handed tasks in the workspace: a member of the
+/// organization, or any user for personal workspaces
+pub async fn can_receive_tasks(
+    pool: &PgPool,
+    user_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<bool, AppError> {
+    let query = match organization_id {
+        Some(_) => {
+            "SELECT EXISTS (SELECT 1 FROM organization_memberships
+                            WHERE user_id = $1 AND organization_id = $2)"
+        }
+        None => "SELECT EXISTS (SELECT 1 FROM users WHERE id = $1 AND $2::uuid IS NULL)",
+    };
+    sqlx::query_scalar::<_, bool>(query)
+        .bind(user_id)
+        .bind(organization_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error checking transfer recipient");
+            AppError::InternalServerError
+        })
+}
+
+pub async fn create_task_transfer(pool: &PgPool, transfer: &TaskTransfer) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO task_ownership_transfers
+             (id, task_id, organization_id, from_user_id, to_user_id, note, handoff, status, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+    )
+    .bind(transfer.id)
+    .bind(transfer.task_id)
+    .bind(transfer.organization_id)
+    .bind(transfer.from_user_id)
+    .bind(transfer.to_user_id)
+    .bind(&transfer.note)
+    .bind(transfer.handoff)
+    .bind(transfer.status.as_str())
+    .bind(transfer.created_at)
+    .execute(pool)
+    .await
+    .map_err(|e| match e.as_database_error() {
+        Some(db_error) if db_error.is_unique_violation() => AppError::Conflict(
+            "The task already has a pending transfer; cancel it first".to_string(),
+        ),
+        _ => {
+            tracing::error!(error = %e, "SQL error creating task transfer");
+            AppError::InternalServerError
+        }
+    })?;
+
+    Ok(())
+}
+
+pub async fn get_pending_task_transfer(
+    pool: &PgPool,
+    task_id: Uuid,
+) -> Result<Option<TaskTransfer>, AppError> {
+    let row = sqlx::query_as::<_, TaskTransferRow>(&format!(
+        "SELECT {} FROM task_ownership_transfers WHERE task_id = $1 AND status = 'pending'",
+        TASK_TRANSFER_COLUMNS
+    ))
+    .bind(task_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching pending task transfer");
+        AppError::InternalServerError
+    })?;
+
+    Ok(row.map(TaskTransfer::from))
+}
+
+/// Every transfer proposed for the task, newest first
+pub async fn get_task_transfers(
+    pool: &PgPool,
+    task_id: Uuid,
+) -> Result<Vec<TaskTransfer>, AppError> {
+    let rows = sqlx::query_as::<_, TaskTransferRow>(&format!(
+        "SELECT {} FROM task_ownership_transfers WHERE task_id = $1 ORDER BY created_at DESC",
+        TASK_TRANSFER_COLUMNS
+    ))
+    .bind(task_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching task transfers");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows.into_iter().map(TaskTransfer::from).collect())
+}
+
+/// Record how a pending transfer ended; false when it was already resolved
+pub async fn resolve_task_transfer(
+    pool: &PgPool,
+    transfer: &TaskTransfer,
+) -> Result<bool, AppError> {
+    write_transfer_resolution(pool, transfer).await
+}
+
+pub async fn resolve_task_transfer_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    transfer: &TaskTransfer,
+) -> Result<bool, AppError> {
+    write_transfer_resolution(&mut **tx, transfer).await
+}
+
+async fn write_transfer_resolution<'e, E>(
+    executor: E,
+    transfer: &TaskTransfer,
+) -> Result<bool, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let result = sqlx::query(
+        "UPDATE task_ownership_transfers SET status = $2, resolved_at = $3
+         WHERE id = $1 AND status = 'pending'",
+    )
+    .bind(transfer.id)
+    .bind(transfer.status.as_str())
+    .bind(transfer.resolved_at)
+    .execute(executor)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error resolving task transfer");
+        AppError::InternalServerError
+    })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Withdraw the task's pending transfer, if any, because its owner let go of it
+pub async fn cancel_pending_task_transfer(pool: &PgPool, task_id: Uuid) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE task_ownership_transfers SET status = 'cancelled', resolved_at = NOW()
+         WHERE task_id = $1 AND status = 'pending'",
+    )
+    .bind(task_id)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error cancelling task transfer");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+/// Atomically give the task to its new owner if `from_user_id` still owns it.
+/// Returns false when it changed hands in the meantime.
+pub async fn transfer_task_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    task: &Task,
+    from_user_id: Uuid,
+) -> Result<bool, AppError> {
+    let transferred = sqlx::query_scalar::<_, Uuid>(
+        "UPDATE tasks SET owner_user_id = $2, owned_at = $3, updated_at = $4
+         WHERE id = $1 AND owner_user_id = $5 AND status IN ('owned', 'inprogress')
+         RETURNING id",
+    )
+    .bind(task.id)
+    .bind(task.owner_user_id)
+    .bind(task.owned_at)
+    .bind(task.updated_at)
+    .bind(from_user_id)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error transferring task");
+        AppError::InternalServerError
+    })?;
+
+    Ok(transferred.is_some())
+}
+
+const BUG_COLUMNS: &str = "s.id AS story_id, s.project_id, s.organization_id, s.title,
+     s.created_at AS reported_at, b.severity, b.priority, b.environment_found,
+     b.triaged_at, b.fixed_at
+     FROM stories s
+     LEFT JOIN bug_details b ON b.story_id = s.id";
+
+pub async fn get_bug(
+    pool: &PgPool,
+    story_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Option<BugRow>, AppError> {
+    sqlx::query_as::<_, BugRow>(&format!(
+        "SELECT {}
+         WHERE s.id = $1 AND s.story_type = 'bug' AND s.deleted_at IS NULL AND (
+             (s.organization_id IS NOT NULL AND s.organization_id = $2) OR
+             (s.organization_id IS NULL AND $2 IS NULL)
+         )",
+        BUG_COLUMNS
+    ))
+    .bind(story_id)
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching bug");
+        AppError::InternalServerError
+    })
+}
+
+/// Bugs of the project reported at or after `since`
+pub async fn get_project_bugs(
+    pool: &PgPool,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+    since: DateTime<Utc>,
+) -> Result<Vec<BugRow>, AppError> {
+    sqlx::query_as::<_, BugRow>(&format!(
+        "SELECT {}
+         WHERE s.project_id = $1 AND s.story_type = 'bug' AND s.deleted_at IS NULL
+           AND s.created_at >= $3 AND (
+             (s.organization_id IS NOT NULL AND s.organization_id = $2) OR
+             (s.organization_id IS NULL AND $2 IS NULL)
+         )
+         ORDER BY s.created_at",
+        BUG_COLUMNS
+    ))
+    .bind(project_id)
+    .bind(organization_id)
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching project bugs");
+        AppError::InternalServerError
+    })
+}
+
+/// Unfixed bugs across all organizations whose fix timer has not been alerted on
+pub async fn get_bugs_awaiting_sla(pool: &PgPool) -> Result<Vec<BugRow>, AppError> {
+    sqlx::query_as::<_, BugRow>(&format!(
+        "SELECT {}
+         WHERE s.story_type = 'bug' AND s.deleted_at IS NULL AND b.fixed_at IS NULL
+           AND NOT EXISTS (
+               SELECT 1 FROM bug_sla_breaches x WHERE x.story_id = s.id AND x.timer = 'fix'
+           )",
+        BUG_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching bugs awaiting their SLA");
+        AppError::InternalServerError
+    })
+}
+
+pub async fn upsert_bug_details(
+    pool: &PgPool,
+    story_id: Uuid,
+    details: &BugDetails,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO bug_details
+             (story_id, severity, priority, environment_found, triaged_at, fixed_at, updated_at)
+         VALUES ($1, $2, $3, $4, $5, $6, NOW())
+         ON CONFLICT (story_id) DO UPDATE SET
+             severity = EXCLUDED.severity,
+             priority = EXCLUDED.priority,
+             environment_found = EXCLUDED.environment_found,
+             triaged_at = EXCLUDED.triaged_at,
+             fixed_at = EXCLUDED.fixed_at,
+             updated_at = NOW()",
+    )
+    .bind(story_id)
+    .bind(details.severity.as_str())
+    .bind(details.priority.map(|priority| priority.as_str()))
+    .bind(&details.environment_found)
+    .bind(details.triaged_at)
+    .bind(details.fixed_at)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error storing bug details");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+/// Stop the fix timer; a bug that was already fixed keeps its first fix time
+pub async fn mark_bug_fixed(
+    pool: &PgPool,
+    story_id: Uuid,
+    fixed_at: DateTime<Utc>,
+) -> Result<(), AppError> {
+    write_bug_fixed(pool, story_id, fixed_at).await
+}
+
+pub async fn mark_bug_fixed_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    story_id: Uuid,
+    fixed_at: DateTime<Utc>,
+) -> Result<(), AppError> {
+    write_bug_fixed(&mut **tx, story_id, fixed_at).await
+}
+
+async fn write_bug_fixed<'e, E>(
+    executor: E,
+    story_id: Uuid,
+    fixed_at: DateTime<Utc>,
+) -> Result<(), AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query(
+        "INSERT INTO bug_details (story_id, fixed_at, updated_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (story_id) DO UPDATE SET
+             fixed_at = COALESCE(bug_details.fixed_at, EXCLUDED.fixed_at),
+             updated_at = NOW()",
+    )
+    .bind(story_id)
+    .bind(fixed_at)
+    .execute(executor)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error marking bug fixed");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+/// SLA policies by organization, only for organizations that changed a
+/// target; `organization_id` narrows it to one organization
+pub async fn get_bug_sla_policies(
+    pool: &PgPool,
+    organization_id: Option<Uuid>,
+) -> Result<HashMap<Uuid, SlaPolicy>, AppError> {
+    let rows = sqlx::query_as::<_, (Uuid, String, i32, i32)>(
+        "SELECT organization_id, severity, triage_hours, fix_hours
+         FROM bug_sla_targets
+         WHERE $1::UUID IS NULL OR organization_id = $1",
+    )
+    .bind(organization_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching bug SLA targets");
+        AppError::InternalServerError
+    })?;
+
+    let mut policies: HashMap<Uuid, SlaPolicy> = HashMap::new();
+    for (organization_id, severity, triage_hours, fix_hours) in rows {
+        if let Some(severity) = BugSeverity::parse(&severity) {
+            policies.entry(organization_id).or_default().set_targets(
+                severity,
+                SlaTargets {
+                    triage_hours: triage_hours as u32,
+                    fix_hours: fix_hours as u32,
+                },
+            );
+        }
+    }
+    Ok(policies)
+}
+
+pub async fn upsert_bug_sla_targets(
+    pool: &PgPool,
+    organization_id: Uuid,
+    severity: BugSeverity,
+    targets: SlaTargets,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO bug_sla_targets (organization_id, severity, triage_hours, fix_hours, updated_at)
+         VALUES ($1, $2, $3, $4, NOW())
+         ON CONFLICT (organization_id, severity) DO UPDATE SET
+             triage_hours = EXCLUDED.triage_hours,
+             fix_hours = EXCLUDED.fix_hours,
+             updated_at = NOW()",
+    )
+    .bind(organization_id)
+    .bind(severity.as_str())
+    .bind(targets.triage_hours as i32)
+    .bind(targets.fix_hours as i32)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error storing bug SLA targets");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+pub async fn record_criterion_verification(
+    pool: &PgPool,
+    verification: &CriterionVerification,
+    organization_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO acceptance_criteria_verifications
+             (id, story_id, criterion_id, organization_id, kind, passed, evidence_url, note, verified_by, verified_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+    )
+    .bind(verification.id)
+    .bind(verification.story_id)
+    .bind(verification.criterion_id)
+    .bind(organization_id)
+    .bind(verification.kind.as_str())
+    .bind(verification.passed)
+    .bind(&verification.evidence_url)
+    .bind(&verification.note)
+    .bind(verification.verified_by)
+    .bind(verification.verified_at)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error recording acceptance criterion verification");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+/// Every verification of the story's criteria, newest first
+pub async fn get_criterion_verifications(
+    pool: &PgPool,
+    story_id: Uuid,
+) -> Result<Vec<CriterionVerification>, AppError> {
+    let rows = sqlx::query_as::<_, CriterionVerificationRow>(
+        "SELECT id, story_id, criterion_id, kind, passed, evidence_url, note, verified_by, verified_at
+         FROM acceptance_criteria_verifications
+         WHERE story_id = $1
+         ORDER BY verified_at DESC",
+    )
+    .bind(story_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching acceptance criterion verifications");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows.into_iter().map(CriterionVerification::from).collect())
+}
+
+pub async fn get_task_completion_policy(
+    pool: &PgPool,
+    organization_id: Uuid,
+) -> Result<TaskCompletionPolicy, AppError> {
+    let require_criteria_coverage = sqlx::query_scalar::<_, bool>(
+        "SELECT require_criteria_coverage FROM organization_task_policies
+         WHERE organization_id = $1",
+    )
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching task completion policy");
+        AppError::InternalServerError
+    })?;
+
+    Ok(TaskCompletionPolicy {
+        require_criteria_coverage: require_criteria_coverage.unwrap_or_default(),
+    })
+}
+
+pub async fn upsert_task_completion_policy(
+    pool: &PgPool,
+    organization_id: Uuid,
+    policy: TaskCompletionPolicy,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO organization_task_policies (organization_id, require_criteria_coverage, updated_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (organization_id) DO UPDATE SET
+             require_criteria_coverage = EXCLUDED.require_criteria_coverage,
+             updated_at = NOW()",
+    )
+    .bind(organization_id)
+    .bind(policy.require_criteria_coverage)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error storing task completion policy");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+pub async fn get_reason_policy(
+    pool: &PgPool,
+    organization_id: Uuid,
+) -> Result<ReasonPolicy, AppError> {
+    let actions = sqlx::query_scalar::<_, Vec<String>>(
+        "SELECT require_reason_for FROM organization_reason_policies
+         WHERE organization_id = $1",
+    )
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching reason policy");
+        AppError::InternalServerError
+    })?;
+
+    Ok(ReasonPolicy::from_stored(&actions.unwrap_or_default()))
+}
+
+pub async fn upsert_reason_policy(
+    pool: &PgPool,
+    organization_id: Uuid,
+    policy: &ReasonPolicy,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO organization_reason_policies (organization_id, require_reason_for, updated_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (organization_id) DO UPDATE SET
+             require_reason_for = EXCLUDED.require_reason_for,
+             updated_at = NOW()",
+    )
+    .bind(organization_id)
+    .bind(policy.stored())
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error storing reason policy");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+/// Keep who performed a destructive action on `entity_id`, and why
+pub async fn record_destructive_action(
+    pool: &PgPool,
+    organization_id: Option<Uuid>,
+    user_id: Option<Uuid>,
+    action: DestructiveAction,
+    entity_id: Uuid,
+    reason: Option<&str>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO destructive_action_audit_log
+             (id, organization_id, user_id, action, entity_type, entity_id, reason, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())",
+    )
+    .bind(Uuid::new_v4())
+    .bind(organization_id)
+    .bind(user_id)
+    .bind(action.as_str())
+    .bind(action.entity_type())
+    .bind(entity_id)
+    .bind(reason)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error recording destructive action");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+/// The organization's sprint cadence settings, or the defaults when it has none
+pub async fn get_sprint_cadence_settings(
+    pool: &PgPool,
+    organization_id: Uuid,
+) -> Result<SprintCadenceSettings, AppError> {
+    let row = sqlx::query_as::<_, (i32, i16, bool, String)>(
+        "SELECT length_days, start_weekday, auto_activate, rollover
+         FROM organization_sprint_cadences
+         WHERE organization_id = $1",
+    )
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching sprint cadence settings");
+        AppError::InternalServerError
+    })?;
+
+    let Some((length_days, start_weekday, auto_activate, rollover)) = row else {
+        return Ok(SprintCadenceSettings::default());
+    };
+    let defaults = SprintCadence::default();
+    Ok(SprintCadenceSettings {
+        cadence: SprintCadence {
+            length_days: length_days as u32,
+            start_weekday: chrono::Weekday::try_from(start_weekday as u8)
+                .unwrap_or(defaults.start_weekday),
+        },
+        auto_activate,
+        rollover: SprintRollover::parse(&rollover).unwrap_or_default(),
+    })
+}
+
+pub async fn upsert_sprint_cadence_settings(
+    pool: &PgPool,
+    organization_id: Uuid,
+    settings: SprintCadenceSettings,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO organization_sprint_cadences
+             (organization_id, length_days, start_weekday, auto_activate, rollover, updated_at)
+         VALUES ($1, $2, $3, $4, $5, NOW())
+         ON CONFLICT (organization_id) DO UPDATE SET
+             length_days = EXCLUDED.length_days,
+             start_weekday = EXCLUDED.start_weekday,
+             auto_activate = EXCLUDED.auto_activate,
+             rollover = EXCLUDED.rollover,
+             updated_at = NOW()",
+    )
+    .bind(organization_id)
+    .bind(settings.cadence.length_days as i32)
+    .bind(settings.cadence.start_weekday.num_days_from_monday() as i16)
+    .bind(settings.auto_activate)
+    .bind(settings.rollover.as_str())
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error storing sprint cadence settings");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+/// Planning and active sprints of organizations that start and complete
+/// sprints on their cadence, by team and start date
+pub async fn get_auto_cadence_sprints(pool: &PgPool) -> Result<Vec<SprintRow>, AppError> {
+    sqlx::query_as::<_, SprintRow>(
+        "SELECT sp.id, sp.team_id, t.organization_id, sp.name, sp.goal, sp.status,
+                sp.capacity_points, sp.committed_points, sp.completed_points,
+                sp.start_date, sp.end_date, sp.created_at, sp.updated_at
+         FROM sprints sp
+         INNER JOIN teams t ON t.id = sp.team_id
+         INNER JOIN organization_sprint_cadences c
+             ON c.organization_id = t.organization_id AND c.auto_activate
+         WHERE sp.status IN ('planning', 'active')
+         ORDER BY sp.team_id, sp.start_date",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching sprints on cadence");
+        AppError::InternalServerError
+    })
+}
+
+/// Complete an active sprint with `completed_points` done (capped at its
+/// committed points) and clear it as its team's active sprint. Returns the
+/// points recorded, or `None` when the sprint is no longer active.
+pub async fn complete_sprint_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    sprint_id: Uuid,
+    completed_points: i32,
+) -> Result<Option<i32>, AppError> {
+    let completed_points = sqlx::query_scalar::<_, i32>(
+        "UPDATE sprints
+         SET status = 'completed', completed_points = LEAST($2, committed_points), updated_at = NOW()
+         WHERE id = $1 AND status = 'active'
+         RETURNING completed_points",
+    )
+    .bind(sprint_id)
+    .bind(completed_points)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error completing sprint");
+        AppError::InternalServerError
+    })?;
+
+    if completed_points.is_some() {
+        sqlx::query(
+            "UPDATE teams SET active_sprint_id = NULL, updated_at = NOW()
+             WHERE active_sprint_id = $1",
+        )
+        .bind(sprint_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error clearing team active sprint");
+            AppError::InternalServerError
+        })?;
+    }
+
+    Ok(completed_points)
+}
+
+/// Activate a planned sprint and make it its team's active sprint, returning
+/// whether it was still planned
+pub async fn activate_sprint_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    sprint_id: Uuid,
+    team_id: Uuid,
+) -> Result<bool, AppError> {
+    let activated = sqlx::query(
+        "UPDATE sprints SET status = 'active', updated_at = NOW()
+         WHERE id = $1 AND status = 'planning'",
+    )
+    .bind(sprint_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error activating sprint");
+        AppError::InternalServerError
+    })?
+    .rows_affected()
+        > 0;
+
+    if activated {
+        set_team_active_sprint_with_transaction(tx, team_id, sprint_id).await?;
+    }
+    Ok(activated)
+}
+
+/// Record a breach, returning whether it was new
+pub async fn record_bug_sla_breach(
+    pool: &PgPool,
+    story_id: Uuid,
+    timer: SlaTimerKind,
+    due_at: DateTime<Utc>,
+) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        "INSERT INTO bug_sla_breaches (story_id, timer, due_at)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (story_id, timer) DO NOTHING",
+    )
+    .bind(story_id)
+    .bind(timer.as_str())
+    .bind(due_at)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error recording bug SLA breach");
+        AppError::InternalServerError
+    })?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+const STORY_SHARE_COLUMNS: &str =
+    "id, owner_organization_id, shared_with_organization_id, project_id,
+     story_id, created_by, created_at, revoked_at";
+
+/// Stories the organization bound to `$1` may read through an active share.
+/// Shared stories are only ever read with this filter; every write path keeps
+/// matching on the story's own organization.
+const SHARED_WITH_ORGANIZATION: &str = "EXISTS (
+    SELECT 1 FROM story_shares sh
+    WHERE sh.shared_with_organization_id = $1
+      AND sh.revoked_at IS NULL
+      AND sh.owner_organization_id = stories.organization_id
+      AND sh.project_id = stories.project_id
+      AND (sh.story_id IS NULL OR sh.story_id = stories.id)
+)";
+
+/// Record a share, returning `false` when an identical one is already active
+pub async fn create_story_share(pool: &PgPool, share: &StoryShare) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        "INSERT INTO story_shares
+             (id, owner_organization_id, shared_with_organization_id, project_id, story_id,
+              created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(share.id)
+    .bind(share.owner_organization_id)
+    .bind(share.shared_with_organization_id)
+    .bind(share.project_id)
+    .bind(share.story_id)
+    .bind(share.created_by)
+    .bind(share.created_at)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error creating story share");
+        AppError::InternalServerError
+    })?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Shares of the project made by its organization, newest first
+pub async fn get_story_shares(
+    pool: &PgPool,
+    project_id: Uuid,
+    owner_organization_id: Uuid,
+    include_revoked: bool,
+) -> Result<Vec<StoryShare>, AppError> {
+    let rows = sqlx::query_as::<_, StoryShareRow>(&format!(
+        "SELECT {} FROM story_shares
+         WHERE project_id = $1 AND owner_organization_id = $2
+           AND ($3 OR revoked_at IS NULL)
+         ORDER BY created_at DESC",
+        STORY_SHARE_COLUMNS
+    ))
+    .bind(project_id)
+    .bind(owner_organization_id)
+    .bind(include_revoked)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching story shares");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows.into_iter().map(StoryShare::from).collect())
+}
+
+/// Revoke an active share of the project, returning it if there was one
+pub async fn revoke_story_share(
+    pool: &PgPool,
+    share_id: Uuid,
+    project_id: Uuid,
+    owner_organization_id: Uuid,
+) -> Result<Option<StoryShare>, AppError> {
+    let row = sqlx::query_as::<_, StoryShareRow>(&format!(
+        "UPDATE story_shares SET revoked_at = NOW()
+         WHERE id = $1 AND project_id = $2 AND owner_organization_id = $3
+           AND revoked_at IS NULL
+         RETURNING {}",
+        STORY_SHARE_COLUMNS
+    ))
+    .bind(share_id)
+    .bind(project_id)
+    .bind(owner_organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error revoking story share");
+        AppError::InternalServerError
+    })?;
+
+    Ok(row.map(StoryShare::from))
+}
+
+const STORY_DEPENDENCY_COLUMNS: &str =
+    "story_id, blocked_by_id, organization_id, created_by, created_at";
+
+/// Dependencies a story is on either side of, oldest first
+pub async fn get_story_dependencies(
+    pool: &PgPool,
+    story_id: Uuid,
+) -> Result<Vec<StoryDependency>, AppError> {
+    let rows = sqlx::query_as::<_, StoryDependencyRow>(&format!(
+        "SELECT {} FROM story_dependencies
+         WHERE story_id = $1 OR blocked_by_id = $1
+         ORDER BY created_at, story_id, blocked_by_id",
+        STORY_DEPENDENCY_COLUMNS
+    ))
+    .bind(story_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching story dependencies");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows.into_iter().map(StoryDependency::from).collect())
+}
+
+/// Every dependency in the organization, for cycle checks
+pub async fn get_organization_story_dependencies(
+    pool: &PgPool,
+    organization_id: Option<Uuid>,
+) -> Result<Vec<StoryDependency>, AppError> {
+    let rows = sqlx::query_as::<_, StoryDependencyRow>(&format!(
+        "SELECT {} FROM story_dependencies WHERE organization_id IS NOT DISTINCT FROM $1",
+        STORY_DEPENDENCY_COLUMNS
+    ))
+    .bind(organization_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching organization story dependencies");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows.into_iter().map(StoryDependency::from).collect())
+}
+
+/// The stories `story_id` waits on
+pub async fn get_story_blockers(pool: &PgPool, story_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+    sqlx::query_scalar::<_, Uuid>(
+        "SELECT blocked_by_id FROM story_dependencies WHERE story_id = $1
+         ORDER BY created_at, blocked_by_id",
+    )
+    .bind(story_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching story blockers");
+        AppError::InternalServerError
+    })
+}
+
+/// Returns false when the dependency was already recorded
+pub async fn create_story_dependency(
+    pool: &PgPool,
+    dependency: &StoryDependency,
+) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        "INSERT INTO story_dependencies
+             (story_id, blocked_by_id, organization_id, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(dependency.story_id)
+    .bind(dependency.blocked_by_id)
+    .bind(dependency.organization_id)
+    .bind(dependency.created_by)
+    .bind(dependency.created_at)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error creating story dependency");
+        AppError::InternalServerError
+    })?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Returns false when there was no such dependency
+pub async fn delete_story_dependency(
+    pool: &PgPool,
+    story_id: Uuid,
+    blocked_by_id: Uuid,
+) -> Result<bool, AppError> {
+    let result =
+        sqlx::query("DELETE FROM story_dependencies WHERE story_id = $1 AND blocked_by_id = $2")
+            .bind(story_id)
+            .bind(blocked_by_id)
+            .execute(pool)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "SQL error deleting story dependency");
+                AppError::InternalServerError
+            })?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+pub async fn organization_exists(pool: &PgPool, organization_id: Uuid) -> Result<bool, AppError> {
+    sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM organizations WHERE id = $1)")
+        .bind(organization_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error checking organization");
+            AppError::InternalServerError
+        })
+}
+
+/// The user's role in the organization, if they are a member
+pub async fn get_organization_role(
+    pool: &PgPool,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<String>, AppError> {
+    sqlx::query_scalar::<_, String>(
+        "SELECT role FROM organization_memberships WHERE organization_id = $1 AND user_id = $2",
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error checking organization role");
+        AppError::InternalServerError
+    })
+}
+
+/// A story another organization shared with `organization_id`
+pub async fn get_shared_story(
+    pool: &PgPool,
+    id: Uuid,
+    organization_id: Uuid,
+) -> Result<Option<Story>, AppError> {
+    let row = sqlx::query_as::<_, StoryRow>(&format!(
+        "SELECT id, project_id, organization_id, title, description, status, labels, story_points, sprint_id, assigned_to_user_id, readiness_override, readiness_override_by, readiness_override_reason, readiness_override_at, created_at, updated_at, short_key, story_type, timebox_hours, stale_at FROM stories
+         WHERE id = $2 AND deleted_at IS NULL AND {}",
+        SHARED_WITH_ORGANIZATION
+    ))
+    .bind(organization_id)
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching shared story");
+        AppError::InternalServerError
+    })?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+    let mut stories = vec![Story::from(row)];
+    attach_acceptance_criteria(pool, &mut stories).await?;
+    Ok(stories.pop())
+}
+
+/// Stories other organizations shared with `organization_id`, optionally only
+/// those of one project or sprint
+pub async fn get_shared_stories(
+    pool: &PgPool,
+    organization_id: Uuid,
+    project_id: Option<Uuid>,
+    sprint_id: Option<Uuid>,
+) -> Result<Vec<Story>, AppError> {
+    let rows = sqlx::query_as::<_, StoryRow>(&format!(
+        "SELECT id, project_id, organization_id, title, description, status, labels, story_points, sprint_id, assigned_to_user_id, readiness_override, readiness_override_by, readiness_override_reason, readiness_override_at, created_at, updated_at, short_key, story_type, timebox_hours, stale_at FROM stories
+         WHERE deleted_at IS NULL
+           AND ($2::uuid IS NULL OR project_id = $2)
+           AND ($3::uuid IS NULL OR sprint_id = $3)
+           AND {}
+         ORDER BY title",
+        SHARED_WITH_ORGANIZATION
+    ))
+    .bind(organization_id)
+    .bind(project_id)
+    .bind(sprint_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching shared stories");
+        AppError::InternalServerError
+    })?;
+
+    let mut stories: Vec<Story> = rows.into_iter().map(Story::from).collect();
+    attach_acceptance_criteria(pool, &mut stories).await?;
+    Ok(stories)
+}
+
+const SPRINT_STAT_SNAPSHOT_COLUMNS: &str = "sprint_id, total_stories, total_tasks, completed_tasks,
+     total_points, completed_points, refreshed_at";
+
+pub async fn get_sprint_stat_snapshot(
+    pool: &PgPool,
+    sprint_id: Uuid,
+) -> Result<Option<SprintStatSnapshot>, AppError> {
+    let row = sqlx::query_as::<_, SprintStatSnapshotRow>(&format!(
+        "SELECT {} FROM sprint_stat_snapshots WHERE sprint_id = $1",
+        SPRINT_STAT_SNAPSHOT_COLUMNS
+    ))
+    .bind(sprint_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching sprint stat snapshot");
+        AppError::InternalServerError
+    })?;
+
+    Ok(row.map(SprintStatSnapshot::from))
+}
+
+/// Recount the sprint's stories, tasks and points and store them as its
+/// snapshot. Returns `None` when the sprint no longer exists.
+pub async fn refresh_sprint_stat_snapshot(
+    pool: &PgPool,
+    sprint_id: Uuid,
+) -> Result<Option<SprintStatSnapshot>, AppError> {
+    let row = sqlx::query_as::<_, SprintStatSnapshotRow>(&format!(
+        "WITH sprint_stories AS (
+             SELECT id, status, story_points FROM stories
+             WHERE sprint_id = $1 AND deleted_at IS NULL
+         ),
+         sprint_tasks AS (
+             SELECT status FROM tasks
+             WHERE story_id IN (SELECT id FROM sprint_stories) AND status <> 'superseded'
+         )
+         INSERT INTO sprint_stat_snapshots (
+             sprint_id, total_stories, total_tasks, completed_tasks,
+             total_points, completed_points, refreshed_at
+         )
+         SELECT s.id,
+                (SELECT COUNT(*) FROM sprint_stories),
+                (SELECT COUNT(*) FROM sprint_tasks),
+                (SELECT COUNT(*) FROM sprint_tasks WHERE status = 'completed'),
+                (SELECT COALESCE(SUM(story_points), 0) FROM sprint_stories),
+                (SELECT COALESCE(SUM(story_points), 0) FROM sprint_stories WHERE status = 'accepted'),
+                NOW()
+         FROM sprints s
+         WHERE s.id = $1
+         ON CONFLICT (sprint_id) DO UPDATE SET
+             total_stories = EXCLUDED.total_stories,
+             total_tasks = EXCLUDED.total_tasks,
+             completed_tasks = EXCLUDED.completed_tasks,
+             total_points = EXCLUDED.total_points,
+             completed_points = EXCLUDED.completed_points,
+             refreshed_at = EXCLUDED.refreshed_at
+         RETURNING {}",
+        SPRINT_STAT_SNAPSHOT_COLUMNS
+    ))
+    .bind(sprint_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error refreshing sprint stat snapshot");
+        AppError::InternalServerError
+    })?;
+
+    Ok(row.map(SprintStatSnapshot::from))
+}
+
+/// The sprint a story is planned into, deleted stories included
+pub async fn get_story_sprint_id(pool: &PgPool, story_id: Uuid) -> Result<Option<Uuid>, AppError> {
+    let sprint_id: Option<Option<Uuid>> =
+        sqlx::query_scalar("SELECT sprint_id FROM stories WHERE id = $1")
+            .bind(story_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "SQL error fetching story sprint");
+                AppError::InternalServerError
+            })?;
+
+    Ok(sprint_id.flatten())
+}
+
+/// Move up to `limit` stories accepted before `cutoff`, with their tasks and
+/// acceptance criteria, from the hot tables to the archive in one
+/// transaction. Returns the archived stories' ids and organizations.
+pub async fn archive_accepted_stories(
+    pool: &PgPool,
+    cutoff: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<(Uuid, Option<Uuid>)>, AppError> {
+    archive_stories(
+        pool,
+        "status = 'accepted' AND deleted_at IS NULL AND updated_at < $1",
+        cutoff,
+        limit,
+    )
+    .await
+}
+
+/// Archive up to `limit` drafts flagged as stale before `cutoff` and not
+/// edited since
+pub async fn archive_stale_stories(
+    pool: &PgPool,
+    cutoff: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<(Uuid, Option<Uuid>)>, AppError> {
+    archive_stories(
+        pool,
+        "status = 'draft' AND deleted_at IS NULL AND stale_at < $1 AND updated_at <= stale_at",
+        cutoff,
+        limit,
+    )
+    .await
+}
+
+/// Archive up to `limit` stories matching `due`, a condition on `stories`
+/// that binds the cutoff as `$1`
+async fn archive_stories(
+    pool: &PgPool,
+    due: &str,
+    cutoff: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<(Uuid, Option<Uuid>)>, AppError> {
+    let sql_error = |action: &'static str| {
+        move |e: sqlx::Error| {
+            tracing::error!(error = %e, "SQL error {}", action);
+            AppError::InternalServerError
+        }
+    };
+    let mut uow = UnitOfWork::begin(pool).await?;
+
+    let archived: Vec<(Uuid, Option<Uuid>)> = sqlx::query_as(&format!(
+        "WITH due AS (
+             SELECT id FROM stories
+             WHERE {}
+             ORDER BY updated_at
+             LIMIT $2
+             FOR UPDATE SKIP LOCKED
+         )
+         INSERT INTO archived_stories (
+             id, project_id, organization_id, short_key, title, description, status,
+             story_type, labels, story_points, sprint_id, assigned_to_user_id,
+             acceptance_criteria, created_at, updated_at
+         )
+         SELECT s.id, s.project_id, s.organization_id, s.short_key, s.title, s.description,
+                s.status, s.story_type, COALESCE(s.labels, '{{}}'), s.story_points, s.sprint_id,
+                s.assigned_to_user_id,
+                COALESCE((
+                    SELECT jsonb_agg(jsonb_build_object(
+                               'id', ac.id,
+                               'acId', ac.ac_id,
+                               'description', ac.description,
+                               'given', ac.given,
+                               'when', ac.when_clause,
+                               'then', ac.then_clause
+                           ) ORDER BY ac.position, ac.created_at)
+                    FROM acceptance_criteria ac WHERE ac.story_id = s.id
+                ), '[]'::jsonb),
+                s.created_at, s.updated_at
+         FROM stories s JOIN due ON due.id = s.id
+         ON CONFLICT (id) DO NOTHING
+         RETURNING id, organization_id",
+        due
+    ))
+    .bind(cutoff)
+    .bind(limit)
+    .fetch_all(&mut **uow.tx())
+    .await
+    .map_err(sql_error("archiving stories"))?;
+
+    if archived.is_empty() {
+        uow.commit().await?;
+        return Ok(archived);
+    }
+    let story_ids: Vec<Uuid> = archived.iter().map(|(id, _)| *id).collect();
+
+    let result = async {
+        sqlx::query(
+            "INSERT INTO archived_tasks (
+                 id, story_id, organization_id, short_key, title, description,
+                 acceptance_criteria_refs, status, owner_user_id, estimated_hours,
+                 created_at, updated_at, completed_at
+             )
+             SELECT id, story_id, organization_id, short_key, title, description,
+                    acceptance_criteria_refs, status, owner_user_id, estimated_hours,
+                    created_at, updated_at, completed_at
+             FROM tasks WHERE story_id = ANY($1)
+             ON CONFLICT (id) DO NOTHING",
+        )
+        .bind(&story_ids)
+        .execute(&mut **uow.tx())
+        .await
+        .map_err(sql_error("archiving tasks"))?;
+
+        // Acceptance criteria, revisions and shares go with the stories by cascade
+        for (table, action) in [
+            ("tasks", "removing archived tasks"),
+            ("story_labels", "removing archived story labels"),
+        ] {
+            sqlx::query(&format!("DELETE FROM {} WHERE story_id = ANY($1)", table))
+                .bind(&story_ids)
+                .execute(&mut **uow.tx())
+                .await
+                .map_err(sql_error(action))?;
+        }
+        sqlx::query("DELETE FROM stories WHERE id = ANY($1)")
+            .bind(&story_ids)
+            .execute(&mut **uow.tx())
+            .await
+            .map_err(sql_error("removing archived stories"))?;
+        Ok(archived)
+    }
+    .await;
+
+    uow.finish(result).await
+}
+
+/// Unflag stale stories that were edited or left draft since they were
+/// flagged
+pub async fn clear_revived_stale_stories(pool: &PgPool) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        "UPDATE stories SET stale_at = NULL
+         WHERE stale_at IS NOT NULL
+           AND (status <> 'draft' OR deleted_at IS NOT NULL OR updated_at > stale_at)",
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error clearing stale flags");
+        AppError::InternalServerError
+    })?;
+    Ok(result.rows_affected())
+}
+
+/// Flag up to `limit` drafts last updated before `cutoff` as stale. The
+/// update trigger moves `updated_at` to `stale_at`, so a later edit shows as
+/// `updated_at > stale_at`. Returns the newly flagged stories with their
+/// creators.
+pub async fn mark_stale_stories(
+    pool: &PgPool,
+    cutoff: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<StaleStory>, AppError> {
+    let rows: Vec<(
+        Uuid,
+        Uuid,
+        Option<Uuid>,
+        String,
+        Option<Uuid>,
+        DateTime<Utc>,
+    )> = sqlx::query_as(
+        "WITH due AS (
+                 SELECT id FROM stories
+                 WHERE status = 'draft' AND deleted_at IS NULL AND stale_at IS NULL
+                   AND updated_at < $1
+                 ORDER BY updated_at
+                 LIMIT $2
+                 FOR UPDATE SKIP LOCKED
+             )
+             UPDATE stories s SET stale_at = NOW()
+             FROM due
+             WHERE s.id = due.id
+             RETURNING s.id, s.project_id, s.organization_id, s.title,
+                       (SELECT r.edited_by FROM story_revisions r
+                        WHERE r.story_id = s.id
+                        ORDER BY r.revision
+                        LIMIT 1),
+                       s.stale_at",
+    )
+    .bind(cutoff)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error marking stale stories");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(story_id, project_id, organization_id, title, created_by, stale_at)| StaleStory {
+                story_id,
+                project_id,
+                organization_id,
+                title,
+                created_by,
+                stale_at,
+            },
+        )
+        .collect())
+}
+
+/// Archived stories and tasks of the organization matching `search`, best
+/// matches first when there is a query and newest first otherwise
+pub async fn search_archive(
+    pool: &PgPool,
+    organization_id: Option<Uuid>,
+    search: &ArchiveSearch,
+) -> Result<Vec<ArchivedItem>, AppError> {
+    let mut builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM (");
+    let mut first = true;
+    for kind in [ArchivedItemKind::Story, ArchivedItemKind::Task] {
+        if !search.includes(kind) {
+            continue;
+        }
+        if !first {
+            builder.push(" UNION ALL ");
+        }
+        first = false;
+
+        let (item, story) = match kind {
+            ArchivedItemKind::Story => ("s", "s"),
+            ArchivedItemKind::Task => ("t", "s"),
+        };
+        builder.push("SELECT ");
+        builder.push_bind(kind.as_str());
+        builder.push(format!(
+            " AS kind, {item}.id, {story}.id AS story_id, {story}.project_id, {item}.short_key,
+              {item}.title, {item}.description, {item}.status, {story}.archived_at, "
+        ));
+        match &search.query {
+            Some(query) => {
+                builder.push(format!(
+                    "ts_rank({item}.search_vector, websearch_to_tsquery('simple', "
+                ));
+                builder.push_bind(query.clone());
+                builder.push("))");
+            }
+            None => {
+                builder.push("0::real");
+            }
+        }
+        builder.push(" AS rank FROM archived_stories s");
+        if kind == ArchivedItemKind::Task {
+            builder.push(" JOIN archived_tasks t ON t.story_id = s.id");
+        }
+        builder.push(" WHERE s.organization_id IS NOT DISTINCT FROM ");
+        builder.push_bind(organization_id);
+        if let Some(project_id) = search.project_id {
+            builder.push(" AND s.project_id = ");
+            builder.push_bind(project_id);
+        }
+        if let Some(query) = &search.query {
+            builder.push(format!(
+                " AND {item}.search_vector @@ websearch_to_tsquery('simple', "
+            ));
+            builder.push_bind(query.clone());
+            builder.push(")");
+        }
+    }
+    builder.push(") matches ORDER BY rank DESC, archived_at DESC, id LIMIT ");
+    builder.push_bind(search.limit);
+    builder.push(" OFFSET ");
+    builder.push_bind(search.offset);
+
+    let rows = builder
+        .build_query_as::<ArchivedItemRow>()
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error searching archive");
+            AppError::InternalServerError
+        })?;
+
+    Ok(rows.into_iter().map(ArchivedItem::from).collect())
+}
+
+/// Store `subscription`, or take over the row of an earlier subscription of the
+/// same browser. Returns the stored subscription.
+pub async fn upsert_push_subscription(
+    pool: &PgPool,
+    subscription: &PushSubscription,
+) -> Result<PushSubscription, AppError> {
+    let row = sqlx::query_as::<_, PushSubscriptionRow>(
+        "INSERT INTO push_subscriptions (id, user_id, endpoint, p256dh, auth, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (endpoint) DO UPDATE SET
+             user_id = EXCLUDED.user_id,
+             p256dh = EXCLUDED.p256dh,
+             auth = EXCLUDED.auth,
+             created_at = EXCLUDED.created_at
+         RETURNING id, user_id, endpoint, p256dh, auth, created_at",
+    )
+    .bind(subscription.id)
+    .bind(subscription.user_id)
+    .bind(&subscription.endpoint)
+    .bind(&subscription.p256dh)
+    .bind(&subscription.auth)
+    .bind(subscription.created_at)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error storing push subscription");
+        AppError::InternalServerError
+    })?;
+
+    Ok(row.into())
+}
+
+pub async fn get_push_subscriptions_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<PushSubscription>, AppError> {
+    let rows = sqlx::query_as::<_, PushSubscriptionRow>(
+        "SELECT id, user_id, endpoint, p256dh, auth, created_at
+         FROM push_subscriptions WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching push subscriptions");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows.into_iter().map(PushSubscription::from).collect())
+}
+
+/// Delete one of the user's subscriptions; `false` when they have no such subscription
+pub async fn delete_push_subscription(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, AppError> {
+    let result = sqlx::query("DELETE FROM push_subscriptions WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error deleting push subscription");
+            AppError::InternalServerError
+        })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Forget a subscription the push service no longer accepts
+pub async fn delete_push_subscription_by_endpoint(
+    pool: &PgPool,
+    endpoint: &str,
+) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM push_subscriptions WHERE endpoint = $1")
+        .bind(endpoint)
+        .execute(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error deleting expired push subscription");
+            AppError::InternalServerError
+        })?;
+
+    Ok(())
+}
+
+pub async fn get_board_configuration(
+    pool: &PgPool,
+    user_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Option<BoardConfiguration>, AppError> {
+    let row = sqlx::query_as::<_, BoardConfigurationRow>(
+        "SELECT user_id, organization_id, group_by, custom_field, updated_at
+         FROM task_board_configurations
+         WHERE user_id = $1 AND organization_id IS NOT DISTINCT FROM $2",
+    )
+    .bind(user_id)
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching task board configuration");
+        AppError::InternalServerError
+    })?;
+
+    Ok(row.map(BoardConfiguration::from))
+}
+
+pub async fn upsert_board_configuration(
+    pool: &PgPool,
+    configuration: &BoardConfiguration,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO task_board_configurations
+             (user_id, organization_id, group_by, custom_field, updated_at)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (user_id, COALESCE(organization_id, '00000000-0000-0000-0000-000000000000'::uuid))
+         DO UPDATE SET
+             group_by = EXCLUDED.group_by,
+             custom_field = EXCLUDED.custom_field,
+             updated_at = EXCLUDED.updated_at",
+    )
+    .bind(configuration.user_id)
+    .bind(configuration.organization_id)
+    .bind(configuration.swimlanes.group_by.as_str())
+    .bind(&configuration.swimlanes.custom_field)
+    .bind(configuration.updated_at)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error storing task board configuration");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+const IMPORT_JOB_COLUMNS: &str = "id, project_id, organization_id, created_by, format, status,
+     total_items, processed_items, published_items, created_items, failed_items, attempts,
+     last_error, created_at, updated_at, completed_at";
+/// Import items inserted per statement
+const IMPORT_ITEM_INSERT_BATCH: usize = 1000;
+
+/// Queue `job` with its items, numbered from 0 in order
+pub async fn create_import_job(
+    pool: &PgPool,
+    job: &ImportJob,
+    items: &[ImportItem],
+) -> Result<(), AppError> {
+    let sql_error = |action: &'static str| {
+        move |e: sqlx::Error| {
+            tracing::error!(error = %e, "SQL error {}", action);
+            AppError::InternalServerError
+        }
+    };
+    let mut uow = UnitOfWork::begin(pool).await?;
+    let result = async {
+        sqlx::query(
+            "INSERT INTO import_jobs
+                 (id, project_id, organization_id, created_by, format, status, total_items,
+                  created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $8)",
+        )
+        .bind(job.id)
+        .bind(job.project_id)
+        .bind(job.organization_id)
+        .bind(job.created_by)
+        .bind(job.format.as_str())
+        .bind(job.status.as_str())
+        .bind(job.total_items as i32)
+        .bind(job.created_at)
+        .execute(&mut **uow.tx())
+        .await
+        .map_err(sql_error("creating import job"))?;
+
+        for (batch, chunk) in items.chunks(IMPORT_ITEM_INSERT_BATCH).enumerate() {
+            let offset = batch * IMPORT_ITEM_INSERT_BATCH;
+            let positions: Vec<i32> = (0..chunk.len())
+                .map(|index| (offset + index) as i32)
+                .collect();
+            let payloads = chunk
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<Result<Vec<String>, _>>()
+                .map_err(|e| {
+                    tracing::error!(error = %e, "Failed to serialize import item");
+                    AppError::InternalServerError
+                })?;
+            sqlx::query(
+                "INSERT INTO import_job_items (job_id, position, payload)
+                 SELECT $1, item.position, item.payload::jsonb
+                 FROM UNNEST($2::int[], $3::text[]) AS item(position, payload)",
+            )
+            .bind(job.id)
+            .bind(&positions)
+            .bind(&payloads)
+            .execute(&mut **uow.tx())
+            .await
+            .map_err(sql_error("storing import items"))?;
+        }
+        Ok(())
+    }
+    .await;
+    uow.finish(result).await
+}
+
+pub async fn get_import_job(
+    pool: &PgPool,
+    job_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Option<ImportJob>, AppError> {
+    let row = sqlx::query_as::<_, ImportJobRow>(&format!(
+        "SELECT {} FROM import_jobs
+         WHERE id = $1 AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))",
+        IMPORT_JOB_COLUMNS
+    ))
+    .bind(job_id)
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching import job");
+        AppError::InternalServerError
+    })?;
+
+    Ok(row.map(ImportJob::from))
+}
+
+/// The first `limit` items of the job that could not be imported
+pub async fn get_import_item_errors(
+    pool: &PgPool,
+    job_id: Uuid,
+    limit: usize,
+) -> Result<Vec<ImportItemError>, AppError> {
+    let rows = sqlx::query_as::<_, ImportItemErrorRow>(
+        "SELECT position, payload->>'external_key' AS external_key, error
+         FROM import_job_items
+         WHERE job_id = $1 AND error IS NOT NULL
+         ORDER BY position
+         LIMIT $2",
+    )
+    .bind(job_id)
+    .bind(limit as i64)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching import errors");
+        AppError::InternalServerError
+    })?;
+
+    Ok(rows.into_iter().map(ImportItemError::from).collect())
+}
+
+/// Lease the oldest queued import job, or a running one whose worker stopped
+/// renewing its lease, for `lease_seconds`
+pub async fn claim_import_job(
+    pool: &PgPool,
+    lease_seconds: u64,
+) -> Result<Option<ImportJob>, AppError> {
+    let row = sqlx::query_as::<_, ImportJobRow>(&format!(
+        "UPDATE import_jobs
+         SET status = 'running',
+             attempts = attempts + 1,
+             lease_expires_at = NOW() + make_interval(secs => $1),
+             updated_at = NOW()
+         WHERE id = (
+             SELECT id FROM import_jobs
+             WHERE status = 'queued' OR (status = 'running' AND lease_expires_at < NOW())
+             ORDER BY created_at
+             LIMIT 1
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING {}",
+        IMPORT_JOB_COLUMNS
+    ))
+    .bind(lease_seconds as f64)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error claiming import job");
+        AppError::InternalServerError
+    })?;
+
+    Ok(row.map(ImportJob::from))
+}
+
+/// Up to `limit` items of the job from position `from` on, in order
+pub async fn get_import_items(
+    pool: &PgPool,
+    job_id: Uuid,
+    from: usize,
+    limit: usize,
+) -> Result<Vec<(usize, ImportItem)>, AppError> {
+    let rows: Vec<(i32, String)> = sqlx::query_as(
+        "SELECT position, payload::text FROM import_job_items
+         WHERE job_id = $1 AND position >= $2
+         ORDER BY position
+         LIMIT $3",
+    )
+    .bind(job_id)
+    .bind(from as i32)
+    .bind(limit as i64)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching import items");
+        AppError::InternalServerError
+    })?;
+
+    rows.into_iter()
+        .map(|(position, payload)| {
+            serde_json::from_str(&payload)
+                .map(|item| (position.max(0) as usize, item))
+                .map_err(|e| {
+                    tracing::error!(error = %e, %job_id, position, "Unreadable import item");
+                    AppError::InternalServerError
+                })
+        })
+        .collect()
+}
+
+/// Record the outcome of a chunk of items, a created story's ID or why the
+/// item failed, and move the job past them while renewing its lease. Fails
+/// with a conflict when the job is no longer at `processed_items`, because
+/// another worker took it over.
+pub async fn record_import_chunk_with_transaction(
+    tx: &mut Transaction<'_, Postgres>,
+    job_id: Uuid,
+    processed_items: usize,
+    outcomes: &[(usize, Result<Uuid, String>)],
+    lease_seconds: u64,
+) -> Result<(), AppError> {
+    let sql_error = |action: &'static str| {
+        move |e: sqlx::Error| {
+            tracing::error!(error = %e, "SQL error {}", action);
+            AppError::InternalServerError
+        }
+    };
+    let positions: Vec<i32> = outcomes
+        .iter()
+        .map(|(position, _)| *position as i32)
+        .collect();
+    let story_ids: Vec<Option<Uuid>> = outcomes
+        .iter()
+        .map(|(_, outcome)| outcome.as_ref().ok().copied())
+        .collect();
+    let errors: Vec<Option<String>> = outcomes
+        .iter()
+        .map(|(_, outcome)| outcome.as_ref().err().cloned())
+        .collect();
+    let created = story_ids.iter().filter(|id| id.is_some()).count();
+
+    let advanced = sqlx::query(
+        "UPDATE import_jobs
+         SET processed_items = processed_items + $3,
+             created_items = created_items + $4,
+             failed_items = failed_items + $5,
+             lease_expires_at = NOW() + make_interval(secs => $6),
+             updated_at = NOW()
+         WHERE id = $1 AND processed_items = $2 AND status = 'running'",
+    )
+    .bind(job_id)
+    .bind(processed_items as i32)
+    .bind(outcomes.len() as i32)
+    .bind(created as i32)
+    .bind((outcomes.len() - created) as i32)
+    .bind(lease_seconds as f64)
+    .execute(&mut **tx)
+    .await
+    .map_err(sql_error("advancing import job"))?;
+    if advanced.rows_affected() == 0 {
+        return Err(AppError::Conflict(
+            "The import job was taken over by another worker".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        "UPDATE import_job_items AS items
+         SET story_id = outcome.story_id, error = outcome.error
+         FROM UNNEST($2::int[], $3::uuid[], $4::text[]) AS outcome(position, story_id, error)
+         WHERE items.job_id = $1 AND items.position = outcome.position",
+    )
+    .bind(job_id)
+    .bind(&positions)
+    .bind(&story_ids)
+    .bind(&errors)
+    .execute(&mut **tx)
+    .await
+    .map_err(sql_error("recording import items"))?;
+
+    Ok(())
+}
+
+/// IDs of the stories created for the job's items at positions `from..to`, in order
+pub async fn get_imported_story_ids(
+    pool: &PgPool,
+    job_id: Uuid,
+    from: usize,
+    to: usize,
+) -> Result<Vec<Uuid>, AppError> {
+    sqlx::query_scalar(
+        "SELECT story_id FROM import_job_items
+         WHERE job_id = $1 AND position >= $2 AND position < $3 AND story_id IS NOT NULL
+         ORDER BY position",
+    )
+    .bind(job_id)
+    .bind(from as i32)
+    .bind(to as i32)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching imported stories");
+        AppError::InternalServerError
+    })
+}
+
+pub async fn mark_import_published(
+    pool: &PgPool,
+    job_id: Uuid,
+    published_items: usize,
+) -> Result<(), AppError> {
+    sqlx::query("UPDATE import_jobs SET published_items = $2, updated_at = NOW() WHERE id = $1")
+        .bind(job_id)
+        .bind(published_items as i32)
+        .execute(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error recording published import items");
+            AppError::InternalServerError
+        })?;
+    Ok(())
+}
+
+pub async fn complete_import_job(pool: &PgPool, job_id: Uuid) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE import_jobs
+         SET status = 'completed', last_error = NULL, lease_expires_at = NULL,
+             completed_at = NOW(), updated_at = NOW()
+         WHERE id = $1",
+    )
+    .bind(job_id)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error completing import job");
+        AppError::InternalServerError
+    })?;
+    Ok(())
+}
+
+/// Give up the job's lease after an error: back in the queue when `retry`,
+/// otherwise failed until someone resumes it
+pub async fn release_import_job(
+    pool: &PgPool,
+    job_id: Uuid,
+    error: &str,
+    retry: bool,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE import_jobs
+         SET status = $2, last_error = $3, lease_expires_at = NULL, updated_at = NOW()
+         WHERE id = $1 AND status = 'running'",
+    )
+    .bind(job_id)
+    .bind(if retry { "queued" } else { "failed" })
+    .bind(error)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error releasing import job");
+        AppError::InternalServerError
+    })?;
+    Ok(())
+}
+
+/// Queue a failed job again with fresh attempts; `None` when the
+/// organization has no failed job with that ID
+pub async fn resume_import_job(
+    pool: &PgPool,
+    job_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Option<ImportJob>, AppError> {
+    let row = sqlx::query_as::<_, ImportJobRow>(&format!(
+        "UPDATE import_jobs
+         SET status = 'queued', attempts = 0, updated_at = NOW()
+         WHERE id = $1
+           AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))
+           AND status = 'failed'
+         RETURNING {}",
+        IMPORT_JOB_COLUMNS
+    ))
+    .bind(job_id)
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error resuming import job");
+        AppError::InternalServerError
+    })?;
+
+    Ok(row.map(ImportJob::from))
+}
@@ -0,0 +1,136 @@
+use crate::domain::{
+    Comparison, ReadinessState, StoryAttribute, StoryCondition, StoryFilter, StoryType,
+    REPRODUCTION_STEP_MARKERS,
+};
+use sqlx::{Postgres, QueryBuilder};
+
+/// Append `filter` to a query over `stories` as a parenthesized condition.
+/// Every value is bound as a parameter.
+pub fn push_story_filter(builder: &mut QueryBuilder<'_, Postgres>, filter: &StoryFilter) {
+    match filter {
+        StoryFilter::And(filters) => push_joined(builder, filters, " AND "),
+        StoryFilter::Or(filters) => push_joined(builder, filters, " OR "),
+        StoryFilter::Not(filter) => {
+            // A condition on a missing value is unknown, and NOT of unknown
+            // would drop the story; treat it as not matching instead
+            builder.push("NOT COALESCE(");
+            push_story_filter(builder, filter);
+            builder.push(", FALSE)");
+        }
+        StoryFilter::Condition(condition) => push_condition(builder, condition),
+    }
+}
+
+fn push_joined(builder: &mut QueryBuilder<'_, Postgres>, filters: &[StoryFilter], operator: &str) {
+    builder.push("(");
+    for (index, filter) in filters.iter().enumerate() {
+        if index > 0 {
+            builder.push(operator);
+        }
+        push_story_filter(builder, filter);
+    }
+    builder.push(")");
+}
+
+fn push_condition(builder: &mut QueryBuilder<'_, Postgres>, condition: &StoryCondition) {
+    match condition {
+        StoryCondition::Status(status) => {
+            builder.push("(stories.status = ");
+            builder.push_bind(status.to_string());
+        }
+        StoryCondition::Type(story_type) => {
+            builder.push("(stories.story_type = ");
+            builder.push_bind(story_type.as_str());
+        }
+        StoryCondition::Label(label) => {
+            builder.push(
+                "(EXISTS (SELECT 1 FROM unnest(stories.labels) AS label WHERE lower(label) = lower(",
+            );
+            builder.push_bind(label.clone());
+            builder.push("))");
+        }
+        StoryCondition::Points(comparison, points) => {
+            builder.push("(stories.story_points ");
+            builder.push(match comparison {
+                Comparison::Eq => "= ",
+                Comparison::Lt => "< ",
+                Comparison::Le => "<= ",
+                Comparison::Gt => "> ",
+                Comparison::Ge => ">= ",
+            });
+            builder.push_bind(i64::from(*points));
+        }
+        StoryCondition::Sprint(sprint_id) => {
+            builder.push("(stories.sprint_id = ");
+            builder.push_bind(*sprint_id);
+        }
+        StoryCondition::Assignee(user_id) => {
+            builder.push("(stories.assigned_to_user_id = ");
+            builder.push_bind(*user_id);
+        }
+        StoryCondition::Text(text) => {
+            let pattern = format!(
+                "%{}%",
+                text.replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_")
+            );
+            builder.push("(stories.title ILIKE ");
+            builder.push_bind(pattern.clone());
+            builder.push(" OR COALESCE(stories.description, '') ILIKE ");
+            builder.push_bind(pattern);
+        }
+        StoryCondition::Missing(attribute) => {
+            builder.push(match attribute {
+                StoryAttribute::Tasks => {
+                    "(NOT EXISTS (SELECT 1 FROM tasks WHERE tasks.story_id = stories.id)"
+                }
+                StoryAttribute::Points => "(stories.story_points IS NULL",
+                StoryAttribute::Assignee => "(stories.assigned_to_user_id IS NULL",
+                StoryAttribute::Sprint => "(stories.sprint_id IS NULL",
+                StoryAttribute::Labels => "(COALESCE(cardinality(stories.labels), 0) = 0",
+                StoryAttribute::AcceptanceCriteria => {
+                    "(NOT EXISTS (SELECT 1 FROM acceptance_criteria WHERE acceptance_criteria.story_id = stories.id)"
+                }
+                StoryAttribute::Stale => "(stories.stale_at IS NULL",
+            });
+        }
+        StoryCondition::Readiness(ReadinessState::Overridden) => {
+            builder.push("(stories.readiness_override");
+        }
+        StoryCondition::Readiness(ReadinessState::Ready) => {
+            builder.push("(");
+            push_meets_ready_bar(builder);
+        }
+        StoryCondition::Readiness(ReadinessState::NotReady) => {
+            builder.push("(NOT stories.readiness_override AND NOT ");
+            push_meets_ready_bar(builder);
+        }
+    }
+    builder.push(")");
+}
+
+/// The requirements of `Story::readiness_gaps` as a condition that is never
+/// unknown; keep the two in step
+fn push_meets_ready_bar(builder: &mut QueryBuilder<'_, Postgres>) {
+    builder.push(
+        "((SELECT count(*) FROM acceptance_criteria WHERE acceptance_criteria.story_id = stories.id) >= 3
+          AND CASE WHEN stories.story_type = ",
+    );
+    builder.push_bind(StoryType::Spike.as_str());
+    builder.push(
+        " THEN stories.timebox_hours IS NOT NULL
+               ELSE COALESCE(stories.story_points BETWEEN 1 AND 8, FALSE) END
+          AND btrim(COALESCE(stories.description, ''), E' \\t\\r\\n') <> ''
+          AND (stories.story_type <> ",
+    );
+    builder.push_bind(StoryType::Bug.as_str());
+    builder.push(" OR lower(stories.description) LIKE ANY (");
+    builder.push_bind(
+        REPRODUCTION_STEP_MARKERS
+            .iter()
+            .map(|marker| format!("%{}%", marker))
+            .collect::<Vec<_>>(),
+    );
+    builder.push(")))");
+}
@@ -0,0 +1,72 @@
+use common::AppError;
+use event_bus::{outbox, DomainEvent, EventEnvelope};
+use sqlx::{PgPool, Postgres, Transaction};
+
+/// A request-scoped database transaction shared by several repository calls.
+///
+/// Usecases open a unit of work, pass [`UnitOfWork::tx`] to the `*_with_transaction`
+/// repository helpers, then hand the overall result to [`UnitOfWork::finish`], which
+/// commits on `Ok` and rolls back on `Err`. Dropping an unfinished unit of work (for
+/// example after an early `?` return) also rolls the transaction back.
+///
+/// Events published through [`UnitOfWork::publish`] are written to the outbox in the
+/// same transaction, so they exist exactly when the change commits; the outbox
+/// dispatcher delivers them afterwards.
+pub struct UnitOfWork {
+    tx: Transaction<'static, Postgres>,
+}
+
+impl UnitOfWork {
+    pub async fn begin(pool: &PgPool) -> Result<Self, AppError> {
+        let tx = pool.begin().await.map_err(|e| {
+            tracing::error!(error = %e, "Failed to begin transaction");
+            AppError::InternalServerError
+        })?;
+
+        Ok(Self { tx })
+    }
+
+    pub fn tx(&mut self) -> &mut Transaction<'static, Postgres> {
+        &mut self.tx
+    }
+
+    pub async fn publish(&mut self, event: DomainEvent) -> Result<(), AppError> {
+        let envelope = EventEnvelope::new(event);
+        outbox::enqueue(&mut *self.tx, &envelope)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, event_id = %envelope.id, "Failed to store event in outbox");
+                AppError::InternalServerError
+            })
+    }
+
+    pub async fn commit(self) -> Result<(), AppError> {
+        self.tx.commit().await.map_err(|e| {
+            tracing::error!(error = %e, "Failed to commit transaction");
+            AppError::InternalServerError
+        })
+    }
+
+    pub async fn rollback(self) -> Result<(), AppError> {
+        self.tx.rollback().await.map_err(|e| {
+            tracing::error!(error = %e, "Failed to roll back transaction");
+            AppError::InternalServerError
+        })
+    }
+
+    /// Commit when `result` is `Ok`, otherwise roll back and return the original error.
+    pub async fn finish<T>(self, result: Result<T, AppError>) -> Result<T, AppError> {
+        match result {
+            Ok(value) => {
+                self.commit().await?;
+                Ok(value)
+            }
+            Err(err) => {
+                if let Err(rollback_err) = self.rollback().await {
+                    tracing::warn!(error = %rollback_err, "Rollback failed after usecase error");
+                }
+                Err(err)
+            }
+        }
+    }
+}
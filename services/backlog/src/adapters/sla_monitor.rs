@@ -0,0 +1,54 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, Instant, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+use crate::adapters::websocket::WebSocketManager;
+use crate::application::BacklogUsecases;
+
+const DEFAULT_CHECK_INTERVAL_SECS: u64 = 300;
+const CHECK_INTERVAL_ENV: &str = "BUG_SLA_CHECK_INTERVAL_SECS";
+
+/// Check bug SLA timers every `BUG_SLA_CHECK_INTERVAL_SECS` (default five
+/// minutes; `0` disables it) and broadcast an alert for each new breach.
+/// Breaches are recorded once, so with several replicas only the one that
+/// records a breach alerts its connected clients.
+pub fn spawn_bug_sla_monitor(
+    usecases: Arc<BacklogUsecases>,
+    ws_manager: Arc<WebSocketManager>,
+) -> Option<JoinHandle<()>> {
+    let period = check_interval(std::env::var(CHECK_INTERVAL_ENV).ok().as_deref())?;
+
+    Some(tokio::spawn(async move {
+        let mut ticks = interval_at(Instant::now() + period, period);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            match usecases.check_bug_sla_breaches().await {
+                Ok(alerts) => {
+                    for alert in alerts {
+                        info!(story_id = %alert.story_id(), "Bug SLA breached");
+                        ws_manager.broadcast(alert);
+                    }
+                }
+                Err(e) => error!(error = %e, "Failed to check bug SLA timers"),
+            }
+        }
+    }))
+}
+
+fn check_interval(raw: Option<&str>) -> Option<Duration> {
+    let secs = match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => value.parse::<u64>().unwrap_or_else(|_| {
+            warn!(
+                value,
+                "Invalid {}; using default of {}s", CHECK_INTERVAL_ENV, DEFAULT_CHECK_INTERVAL_SECS
+            );
+            DEFAULT_CHECK_INTERVAL_SECS
+        }),
+        None => DEFAULT_CHECK_INTERVAL_SECS,
+    };
+    Some(Duration::from_secs(secs)).filter(|period| !period.is_zero())
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, Instant, MissedTickBehavior};
+use tracing::{error, info};
+
+use crate::application::BacklogUsecases;
+
+const CADENCE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Every fifteen minutes, complete ended sprints and activate planned ones
+/// for organizations whose sprint cadence has auto-activation on. Replicas
+/// skip sprints another replica has already moved on.
+pub fn spawn_sprint_cadence_scheduler(usecases: Arc<BacklogUsecases>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticks = interval_at(Instant::now() + CADENCE_INTERVAL, CADENCE_INTERVAL);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            match usecases.advance_sprint_cadence().await {
+                Ok(0) => {}
+                Ok(changed) => info!(changed, "Advanced sprints on cadence"),
+                Err(e) => error!(error = %e, "Failed to advance sprint cadence"),
+            }
+        }
+    })
+}
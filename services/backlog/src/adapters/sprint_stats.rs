@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use common::AppError;
+use event_bus::{BacklogEvent, DomainEvent, EventBus, SprintEvent};
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tracing::error;
+
+use crate::adapters::persistence::repo;
+
+/// Recalculate a sprint's stat snapshot whenever one of its stories or tasks
+/// changes, so that sprint boards read stored totals instead of counting.
+/// Boards recompute snapshots this misses once they pass the maximum age.
+pub fn spawn_sprint_stats_projector(pool: Arc<PgPool>, event_bus: Arc<EventBus>) -> JoinHandle<()> {
+    let subscription = event_bus.subscribe();
+    tokio::spawn(async move {
+        loop {
+            let envelope = subscription.recv().await;
+            if let Err(e) = envelope.handle(apply_event(&pool, &envelope.event)).await {
+                error!(
+                    error = %e,
+                    event_id = %envelope.id,
+                    correlation_id = ?envelope.correlation_id,
+                    "Failed to refresh sprint stats"
+                );
+            }
+        }
+    })
+}
+
+async fn apply_event(pool: &PgPool, event: &DomainEvent) -> Result<(), AppError> {
+    let sprint_id = match event {
+        DomainEvent::Backlog(backlog_event) => match backlog_event {
+            BacklogEvent::StoryCreated { story } | BacklogEvent::StoryUpdated { story } => {
+                story.sprint_id
+            }
+            BacklogEvent::StoryDeleted { story_id, .. } => {
+                repo::get_story_sprint_id(pool, *story_id).await?
+            }
+            // Archived stories are long accepted; their sprint's totals stand as they were
+            BacklogEvent::StoryArchived { .. } => None,
+            BacklogEvent::StoryDependenciesChanged { .. }
+            | BacklogEvent::StoryBlockersChanged { .. } => None,
+            BacklogEvent::TaskCreated { task } | BacklogEvent::TaskUpdated { task } => {
+                repo::get_story_sprint_id(pool, task.story_id).await?
+            }
+            BacklogEvent::TaskDeleted { story_id, .. } => {
+                repo::get_story_sprint_id(pool, *story_id).await?
+            }
+        },
+        // A story leaving a sprint is only visible here; its update names the new sprint
+        DomainEvent::Sprint(SprintEvent::StoryRemoved { change }) => Some(change.sprint_id),
+        DomainEvent::Sprint(_) => None,
+    };
+
+    if let Some(sprint_id) = sprint_id {
+        repo::refresh_sprint_stat_snapshot(pool, sprint_id).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, Instant, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+use crate::adapters::websocket::WebSocketManager;
+use crate::application::BacklogUsecases;
+use crate::domain::DEFAULT_STALE_AFTER_DAYS;
+
+const STALE_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const STALE_AFTER_ENV: &str = "STALE_STORY_AFTER_DAYS";
+const STALE_ARCHIVE_AFTER_ENV: &str = "STALE_STORY_ARCHIVE_AFTER_DAYS";
+
+/// Every hour, flag drafts untouched for `STALE_STORY_AFTER_DAYS` days
+/// (default 30; `0` disables it) as stale and alert their creators. With
+/// `STALE_STORY_ARCHIVE_AFTER_DAYS` set, drafts that stay stale and untouched
+/// that many more days are archived.
+pub fn spawn_stale_marker(
+    usecases: Arc<BacklogUsecases>,
+    ws_manager: Arc<WebSocketManager>,
+) -> Option<JoinHandle<()>> {
+    let after_days = days_from_env(STALE_AFTER_ENV, DEFAULT_STALE_AFTER_DAYS)?;
+    let archive_after_days = days_from_env(STALE_ARCHIVE_AFTER_ENV, 0);
+
+    Some(tokio::spawn(async move {
+        let mut ticks = interval_at(Instant::now() + STALE_CHECK_INTERVAL, STALE_CHECK_INTERVAL);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            if let Some(archive_after_days) = archive_after_days {
+                match usecases.archive_stale_stories(archive_after_days).await {
+                    Ok(0) => {}
+                    Ok(archived) => info!(archived, "Archived stale draft stories"),
+                    Err(e) => error!(error = %e, "Failed to archive stale stories"),
+                }
+            }
+            match usecases
+                .mark_stale_stories(after_days, archive_after_days)
+                .await
+            {
+                Ok(alerts) => {
+                    for alert in alerts {
+                        info!(story_id = %alert.story_id(), "Draft story went stale");
+                        ws_manager.broadcast(alert);
+                    }
+                }
+                Err(e) => error!(error = %e, "Failed to mark stale stories"),
+            }
+        }
+    }))
+}
+
+fn days_from_env(name: &str, default: u32) -> Option<u32> {
+    let raw = std::env::var(name).ok();
+    let days = match raw
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        Some(value) => value.parse::<u32>().unwrap_or_else(|_| {
+            warn!(value, "Invalid {}; using default of {} days", name, default);
+            default
+        }),
+        None => default,
+    };
+    Some(days).filter(|days| *days > 0)
+}
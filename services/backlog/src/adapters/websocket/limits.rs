@@ -0,0 +1,223 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::ConnectionMetrics;
+
+const DEFAULT_MAX_CONNECTIONS: usize = 10_000;
+const DEFAULT_MAX_CONNECTIONS_PER_ORG: usize = 1_000;
+const DEFAULT_ALERT_PERCENT: usize = 80;
+
+const MAX_CONNECTIONS_ENV: &str = "WEBSOCKET_MAX_CONNECTIONS";
+const MAX_CONNECTIONS_PER_ORG_ENV: &str = "WEBSOCKET_MAX_CONNECTIONS_PER_ORG";
+const ALERT_PERCENT_ENV: &str = "WEBSOCKET_CONNECTION_ALERT_PERCENT";
+
+/// Close code sent when a connection is refused because a connection limit is reached
+pub const CLOSE_CONNECTION_LIMIT: u16 = 4002;
+
+/// How many connections the manager accepts, overall and per organization.
+/// Personal (organization-less) connections share one budget. Crossing
+/// `alert_percent` of either limit logs a warning before connections are refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionLimits {
+    pub max_connections: usize,
+    pub max_connections_per_organization: usize,
+    pub alert_percent: usize,
+}
+
+impl Default for ConnectionLimits {
+    fn default() -> Self {
+        Self {
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            max_connections_per_organization: DEFAULT_MAX_CONNECTIONS_PER_ORG,
+            alert_percent: DEFAULT_ALERT_PERCENT,
+        }
+    }
+}
+
+impl ConnectionLimits {
+    /// Read `WEBSOCKET_MAX_CONNECTIONS`, `WEBSOCKET_MAX_CONNECTIONS_PER_ORG` and
+    /// `WEBSOCKET_CONNECTION_ALERT_PERCENT`, falling back to the defaults
+    pub fn from_env() -> Self {
+        let read = |name: &str, default: usize| {
+            parse_count(name, std::env::var(name).ok().as_deref(), default)
+        };
+
+        Self {
+            max_connections: read(MAX_CONNECTIONS_ENV, DEFAULT_MAX_CONNECTIONS),
+            max_connections_per_organization: read(
+                MAX_CONNECTIONS_PER_ORG_ENV,
+                DEFAULT_MAX_CONNECTIONS_PER_ORG,
+            ),
+            alert_percent: read(ALERT_PERCENT_ENV, DEFAULT_ALERT_PERCENT).min(100),
+        }
+    }
+
+    /// Decide whether one more connection fits next to `total` open ones,
+    /// `in_organization` of them in the same organization
+    pub fn admit(&self, total: usize, in_organization: usize) -> Result<(), ConnectionRejection> {
+        if total >= self.max_connections {
+            return Err(ConnectionRejection::GlobalLimit);
+        }
+        if in_organization >= self.max_connections_per_organization {
+            return Err(ConnectionRejection::OrganizationLimit);
+        }
+        Ok(())
+    }
+
+    fn alert_threshold(&self, limit: usize) -> usize {
+        (limit * self.alert_percent / 100).max(1)
+    }
+
+    /// Warn when an admitted connection brings a count up to the alert threshold
+    pub(crate) fn alert_if_crossed(
+        &self,
+        organization_id: Option<Uuid>,
+        total: usize,
+        in_organization: usize,
+    ) {
+        if total == self.alert_threshold(self.max_connections) {
+            warn!(
+                connections = total,
+                limit = self.max_connections,
+                "WebSocket connections approaching the global limit"
+            );
+        }
+        if in_organization == self.alert_threshold(self.max_connections_per_organization) {
+            warn!(
+                org_id = ?organization_id,
+                connections = in_organization,
+                limit = self.max_connections_per_organization,
+                "WebSocket connections of an organization approaching its limit"
+            );
+        }
+    }
+}
+
+fn parse_count(name: &str, raw: Option<&str>, default: usize) -> usize {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => match value.parse::<usize>() {
+            Ok(count) if count > 0 => count,
+            _ => {
+                warn!(value, "Invalid {}; using default of {}", name, default);
+                default
+            }
+        },
+        None => default,
+    }
+}
+
+/// Why a connection was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionRejection {
+    GlobalLimit,
+    OrganizationLimit,
+}
+
+impl ConnectionRejection {
+    /// The close frame reason, which clients may show
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::GlobalLimit => "server connection limit reached",
+            Self::OrganizationLimit => "organization connection limit reached",
+        }
+    }
+}
+
+/// A user's open connections within an organization
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserConnections {
+    pub user_id: String,
+    pub connections: usize,
+    pub oldest_connected_at: DateTime<Utc>,
+}
+
+/// An organization's open connections, busiest users first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationConnections {
+    /// `None` for personal connections
+    pub organization_id: Option<Uuid>,
+    pub connections: usize,
+    pub users: Vec<UserConnections>,
+}
+
+/// Group connections by organization and user, busiest organizations first
+pub fn group_connections<'a>(
+    connections: impl IntoIterator<Item = &'a ConnectionMetrics>,
+) -> Vec<OrganizationConnections> {
+    let mut by_organization: HashMap<Option<Uuid>, HashMap<&str, UserConnections>> = HashMap::new();
+    for connection in connections {
+        by_organization
+            .entry(connection.organization_id)
+            .or_default()
+            .entry(connection.user_id.as_str())
+            .and_modify(|user| {
+                user.connections += 1;
+                user.oldest_connected_at = user.oldest_connected_at.min(connection.connected_at);
+            })
+            .or_insert_with(|| UserConnections {
+                user_id: connection.user_id.clone(),
+                connections: 1,
+                oldest_connected_at: connection.connected_at,
+            });
+    }
+
+    let mut organizations: Vec<OrganizationConnections> = by_organization
+        .into_iter()
+        .map(|(organization_id, users)| {
+            let mut users: Vec<UserConnections> = users.into_values().collect();
+            users.sort_by(|a, b| {
+                b.connections
+                    .cmp(&a.connections)
+                    .then(a.user_id.cmp(&b.user_id))
+            });
+            OrganizationConnections {
+                organization_id,
+                connections: users.iter().map(|user| user.connections).sum(),
+                users,
+            }
+        })
+        .collect();
+    organizations.sort_by(|a, b| {
+        b.connections
+            .cmp(&a.connections)
+            .then(a.organization_id.cmp(&b.organization_id))
+    });
+    organizations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admit_checks_both_limits() {
+        let limits = ConnectionLimits {
+            max_connections: 10,
+            max_connections_per_organization: 3,
+            alert_percent: 80,
+        };
+        assert_eq!(limits.admit(0, 0), Ok(()));
+        assert_eq!(limits.admit(9, 2), Ok(()));
+        assert_eq!(
+            limits.admit(5, 3),
+            Err(ConnectionRejection::OrganizationLimit)
+        );
+        assert_eq!(limits.admit(10, 0), Err(ConnectionRejection::GlobalLimit));
+        assert_eq!(limits.alert_threshold(10), 8);
+        assert_eq!(limits.alert_threshold(1), 1);
+    }
+
+    #[test]
+    fn test_parse_count() {
+        assert_eq!(parse_count("X", None, 50), 50);
+        assert_eq!(parse_count("X", Some("200"), 50), 200);
+        assert_eq!(parse_count("X", Some("0"), 50), 50);
+        assert_eq!(parse_count("X", Some("many"), 50), 50);
+    }
+}
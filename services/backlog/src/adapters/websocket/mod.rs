@@ -1,32 +1,185 @@
+pub mod limits;
+pub mod presence;
+pub mod session;
+
+pub use limits::{ConnectionLimits, ConnectionRejection, OrganizationConnections, UserConnections};
+pub use presence::{PresenceEvent, StoryPresence, StoryViewer};
+pub use session::{SessionAuth, SessionConfig, WsCredential};
+
 use axum::{
+    body::Bytes,
     extract::{
-        ws::{Message, WebSocket},
+        ws::{CloseFrame, Message, WebSocket},
         FromRequestParts, State, WebSocketUpgrade,
     },
     http::{header::HeaderName, header::AUTHORIZATION, request::Parts, HeaderValue},
     response::IntoResponse,
 };
-use futures::{sink::SinkExt, stream::StreamExt};
+use futures::{
+    sink::SinkExt,
+    stream::{SplitSink, StreamExt},
+};
 use percent_encoding::percent_decode_str;
-use std::sync::Arc;
-use tokio::sync::broadcast;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::Instant;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::domain::TaskEvent;
-use auth_clerk::AuthenticatedWithOrg;
+use auth_clerk::{AuthenticatedWithOrg, JwtVerifier};
 use common::AppError;
+use event_bus::EventCorrelation;
+
+const DEFAULT_CHANNEL_CAPACITY: usize = 100;
+const CHANNEL_CAPACITY_ENV: &str = "WEBSOCKET_CHANNEL_CAPACITY";
+
+/// Control messages the server sends alongside task events
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ControlMessage {
+    /// The connection fell behind the broadcast channel and missed events;
+    /// clients should refetch the state they display
+    Resync {
+        dropped_events: u64,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// A `refresh_token` message was accepted; the session now lasts until `expires_at`
+    TokenRefreshed {
+        expires_at: chrono::DateTime<chrono::Utc>,
+    },
+    /// A `refresh_token` message was rejected; the previous credential stays in effect
+    TokenRefreshFailed { reason: String },
+}
+
+/// Messages clients may send over the socket
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Extend the session with a fresh JWT before the current one expires
+    RefreshToken { token: String },
+    /// The client opened a story; `editing` when the user is changing it
+    ViewStory {
+        story_id: Uuid,
+        #[serde(default)]
+        editing: bool,
+    },
+    /// The client closed the story
+    LeaveStory { story_id: Uuid },
+}
+
+/// Per-connection delivery statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionMetrics {
+    pub connection_id: Uuid,
+    pub user_id: String,
+    pub organization_id: Option<Uuid>,
+    pub dropped_events: u64,
+    pub resyncs_sent: u64,
+    pub connected_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Point-in-time view of broadcast channel health
+#[derive(Debug, Clone, Serialize)]
+pub struct WebSocketMetricsSnapshot {
+    pub channel_capacity: usize,
+    pub active_connections: usize,
+    pub total_dropped_events: u64,
+    pub total_resyncs_sent: u64,
+    /// Connections refused since startup because a connection limit was reached
+    pub total_rejected_connections: u64,
+    pub connections: Vec<ConnectionMetrics>,
+}
+
+#[derive(Default)]
+struct WebSocketMetrics {
+    dropped_events: AtomicU64,
+    resyncs_sent: AtomicU64,
+    rejected_connections: AtomicU64,
+    connections: Mutex<HashMap<Uuid, ConnectionMetrics>>,
+}
 
 /// WebSocket connection manager that broadcasts task events to connected clients
+/// A task event as sent to clients, tagged with the `x-request-id` of the
+/// request that caused it
+#[derive(Debug, Clone, Serialize)]
+pub struct CorrelatedTaskEvent {
+    #[serde(flatten)]
+    pub event: TaskEvent,
+    /// Absent for events raised by background jobs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+impl Deref for CorrelatedTaskEvent {
+    type Target = TaskEvent;
+
+    fn deref(&self) -> &TaskEvent {
+        &self.event
+    }
+}
+
 #[derive(Clone)]
 pub struct WebSocketManager {
-    tx: broadcast::Sender<TaskEvent>,
+    tx: broadcast::Sender<CorrelatedTaskEvent>,
+    presence_tx: broadcast::Sender<PresenceEvent>,
+    presence: Arc<Mutex<StoryPresence>>,
+    capacity: usize,
+    session_config: SessionConfig,
+    limits: ConnectionLimits,
+    metrics: Arc<WebSocketMetrics>,
 }
 
 impl WebSocketManager {
     pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
         let (tx, _rx) = broadcast::channel(capacity);
-        Self { tx }
+        let (presence_tx, _presence_rx) = broadcast::channel(capacity);
+        Self {
+            tx,
+            presence_tx,
+            presence: Arc::new(Mutex::new(StoryPresence::default())),
+            capacity,
+            session_config: SessionConfig::default(),
+            limits: ConnectionLimits::default(),
+            metrics: Arc::new(WebSocketMetrics::default()),
+        }
+    }
+
+    /// Create a manager sized by `WEBSOCKET_CHANNEL_CAPACITY`, with heartbeat and
+    /// re-authentication timings from [`SessionConfig::from_env`] and connection
+    /// limits from [`ConnectionLimits::from_env`]
+    pub fn from_env() -> Self {
+        let capacity = parse_channel_capacity(std::env::var(CHANNEL_CAPACITY_ENV).ok().as_deref());
+        Self::new(capacity)
+            .with_session_config(SessionConfig::from_env())
+            .with_connection_limits(ConnectionLimits::from_env())
+    }
+
+    pub fn with_session_config(mut self, session_config: SessionConfig) -> Self {
+        self.session_config = session_config;
+        self
+    }
+
+    pub fn with_connection_limits(mut self, limits: ConnectionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn connection_limits(&self) -> ConnectionLimits {
+        self.limits
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn session_config(&self) -> SessionConfig {
+        self.session_config
     }
 
     /// Broadcast a task event to all connected clients
@@ -38,18 +191,172 @@ impl WebSocketManager {
             subscriber_count
         );
 
+        let event = CorrelatedTaskEvent {
+            event,
+            correlation_id: EventCorrelation::current().correlation_id,
+        };
         if let Err(e) = self.tx.send(event) {
             warn!("Failed to broadcast event: {}", e);
         }
     }
 
     /// Subscribe to task events
-    pub fn subscribe(&self) -> broadcast::Receiver<TaskEvent> {
+    pub fn subscribe(&self) -> broadcast::Receiver<CorrelatedTaskEvent> {
         self.tx.subscribe()
     }
+
+    /// Subscribe to story presence changes
+    pub fn subscribe_presence(&self) -> broadcast::Receiver<PresenceEvent> {
+        self.presence_tx.subscribe()
+    }
+
+    /// Mark the connection as having the story open and tell other clients
+    pub fn join_story(
+        &self,
+        connection_id: Uuid,
+        user_id: &str,
+        organization_id: Option<Uuid>,
+        story_id: Uuid,
+        editing: bool,
+    ) {
+        let events =
+            self.presence()
+                .join(connection_id, user_id, organization_id, story_id, editing);
+        for event in events {
+            self.broadcast_presence(event);
+        }
+    }
+
+    /// Take the connection off its story (only if it is `story_id`, when given)
+    pub fn leave_story(&self, connection_id: Uuid, story_id: Option<Uuid>) {
+        let event = self.presence().leave(connection_id, story_id);
+        if let Some(event) = event {
+            self.broadcast_presence(event);
+        }
+    }
+
+    /// Current viewers of a story
+    pub fn story_viewers(&self, story_id: Uuid) -> Vec<StoryViewer> {
+        self.presence().viewers(story_id)
+    }
+
+    fn broadcast_presence(&self, event: PresenceEvent) {
+        // No subscribers just means nobody else is connected
+        if self.presence_tx.send(event).is_err() {
+            debug!("No subscribers for presence event");
+        }
+    }
+
+    fn presence(&self) -> std::sync::MutexGuard<'_, StoryPresence> {
+        self.presence
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Admit a new connection within the connection limits and start tracking
+    /// its delivery statistics
+    pub fn register_connection(
+        &self,
+        user_id: &str,
+        organization_id: Option<Uuid>,
+    ) -> Result<Uuid, ConnectionRejection> {
+        let mut connections = self.connections();
+        let in_organization = connections
+            .values()
+            .filter(|connection| connection.organization_id == organization_id)
+            .count();
+        if let Err(rejection) = self.limits.admit(connections.len(), in_organization) {
+            self.metrics
+                .rejected_connections
+                .fetch_add(1, Ordering::Relaxed);
+            return Err(rejection);
+        }
+
+        let connection_id = Uuid::new_v4();
+        let metrics = ConnectionMetrics {
+            connection_id,
+            user_id: user_id.to_string(),
+            organization_id,
+            dropped_events: 0,
+            resyncs_sent: 0,
+            connected_at: chrono::Utc::now(),
+        };
+        connections.insert(connection_id, metrics);
+        self.limits
+            .alert_if_crossed(organization_id, connections.len(), in_organization + 1);
+        Ok(connection_id)
+    }
+
+    /// Stop tracking a connection, returning its final statistics. Any story it
+    /// had open is left.
+    pub fn unregister_connection(&self, connection_id: Uuid) -> Option<ConnectionMetrics> {
+        self.leave_story(connection_id, None);
+        self.connections().remove(&connection_id)
+    }
+
+    /// Record that a connection lagged behind and was sent a resync message
+    pub fn record_lag(&self, connection_id: Uuid, dropped_events: u64) {
+        self.metrics
+            .dropped_events
+            .fetch_add(dropped_events, Ordering::Relaxed);
+        self.metrics.resyncs_sent.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(connection) = self.connections().get_mut(&connection_id) {
+            connection.dropped_events += dropped_events;
+            connection.resyncs_sent += 1;
+        }
+    }
+
+    pub fn metrics_snapshot(&self) -> WebSocketMetricsSnapshot {
+        let connections: Vec<ConnectionMetrics> = self.connections().values().cloned().collect();
+        WebSocketMetricsSnapshot {
+            channel_capacity: self.capacity,
+            active_connections: connections.len(),
+            total_dropped_events: self.metrics.dropped_events.load(Ordering::Relaxed),
+            total_resyncs_sent: self.metrics.resyncs_sent.load(Ordering::Relaxed),
+            total_rejected_connections: self.metrics.rejected_connections.load(Ordering::Relaxed),
+            connections,
+        }
+    }
+
+    /// Open connections grouped by organization and user, busiest first
+    pub fn connections_by_organization(&self) -> Vec<OrganizationConnections> {
+        limits::group_connections(self.connections().values())
+    }
+
+    fn connections(&self) -> std::sync::MutexGuard<'_, HashMap<Uuid, ConnectionMetrics>> {
+        self.metrics
+            .connections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
 }
 
-pub struct WsAuthenticatedWithOrg(pub AuthenticatedWithOrg);
+fn parse_channel_capacity(raw: Option<&str>) -> usize {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => match value.parse::<usize>() {
+            Ok(capacity) if capacity > 0 => capacity,
+            _ => {
+                warn!(
+                    value,
+                    "Invalid {}; using default of {}",
+                    CHANNEL_CAPACITY_ENV,
+                    DEFAULT_CHANNEL_CAPACITY
+                );
+                DEFAULT_CHANNEL_CAPACITY
+            }
+        },
+        None => DEFAULT_CHANNEL_CAPACITY,
+    }
+}
+
+/// Authenticated WebSocket caller, along with the credential used so the
+/// connection can be re-authenticated while it stays open
+pub struct WsAuthenticatedWithOrg {
+    pub auth: AuthenticatedWithOrg,
+    pub credential: WsCredential,
+    pub verifier: Option<Arc<tokio::sync::Mutex<JwtVerifier>>>,
+}
 
 impl<S> FromRequestParts<S> for WsAuthenticatedWithOrg
 where
@@ -90,22 +397,57 @@ where
                 parts.headers.insert(X_API_KEY, header);
             }
 
-            match AuthenticatedWithOrg::from_request_parts(parts, state).await {
-                Ok(auth) => {
-                    debug!(user_id = %auth.auth.sub, "WebSocket authentication succeeded");
-                    Ok(WsAuthenticatedWithOrg(auth))
-                }
+            let auth = match AuthenticatedWithOrg::from_request_parts(parts, state).await {
+                Ok(auth) => auth,
                 Err(err) => {
                     error!(?err, "WebSocket authentication failed");
-                    Err(err)
+                    return Err(err);
                 }
-            }
+            };
+
+            let credential = credential_from_headers(parts).ok_or_else(|| {
+                AppError::Unauthorized("WebSocket credential missing".to_string())
+            })?;
+            debug!(user_id = %auth.auth.sub, ?credential, "WebSocket authentication succeeded");
+
+            Ok(WsAuthenticatedWithOrg {
+                auth,
+                credential,
+                verifier: parts
+                    .extensions
+                    .get::<Arc<tokio::sync::Mutex<JwtVerifier>>>()
+                    .cloned(),
+            })
         }
     }
 }
 
 const X_API_KEY: HeaderName = HeaderName::from_static("x-api-key");
 
+fn credential_from_headers(parts: &Parts) -> Option<WsCredential> {
+    let header = |name: &HeaderName| {
+        parts
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+
+    if let Some(key) = header(&X_API_KEY) {
+        return Some(WsCredential::ApiKey(key.to_string()));
+    }
+
+    let authorization = header(&AUTHORIZATION)?;
+    if let Some(token) = authorization.strip_prefix("Bearer ") {
+        Some(WsCredential::Jwt(token.trim().to_string()))
+    } else {
+        authorization
+            .strip_prefix("ApiKey ")
+            .map(|key| WsCredential::ApiKey(key.trim().to_string()))
+    }
+}
+
 fn parse_auth_from_query(query: &str) -> (Option<HeaderValue>, Option<HeaderValue>) {
     let mut auth_header: Option<HeaderValue> = None;
     let mut api_key_header: Option<HeaderValue> = None;
@@ -150,11 +492,16 @@ fn decode_query_value(raw: &str) -> Option<String> {
 /// Authenticated users can connect to receive task event notifications
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
-    WsAuthenticatedWithOrg(AuthenticatedWithOrg { org_context, auth }): WsAuthenticatedWithOrg,
+    WsAuthenticatedWithOrg {
+        auth: AuthenticatedWithOrg { org_context, auth },
+        credential,
+        verifier,
+    }: WsAuthenticatedWithOrg,
     State(state): State<Arc<crate::adapters::http::BacklogAppState>>,
 ) -> impl IntoResponse {
     let org_id = org_context.effective_organization_uuid();
     let user_id = auth.sub.clone();
+    let session = SessionAuth::new(credential, user_id.clone(), verifier, state.pool.clone());
 
     info!(
         org_id = ?org_id,
@@ -162,117 +509,270 @@ pub async fn websocket_handler(
         "WebSocket connection established"
     );
 
-    ws.on_upgrade(move |socket| handle_socket(socket, org_id, user_id, state.ws_manager.clone()))
+    ws.on_upgrade(move |socket| {
+        handle_socket(socket, org_id, user_id, session, state.ws_manager.clone())
+    })
+}
+
+type WsSender = SplitSink<WebSocket, Message>;
+
+async fn send_json<T: Serialize>(sender: &mut WsSender, value: &T) -> Result<(), ()> {
+    let json = serde_json::to_string(value).map_err(|e| {
+        error!("Failed to serialize WebSocket message: {}", e);
+    })?;
+    sender
+        .send(Message::Text(json.into()))
+        .await
+        .map_err(|_| ())
+}
+
+fn is_auth_failure(err: &AppError) -> bool {
+    matches!(
+        err,
+        AppError::Unauthorized(_) | AppError::UnauthorizedWithContext { .. }
+    )
 }
 
 async fn handle_socket(
     socket: WebSocket,
     org_id: Option<Uuid>,
     user_id: String,
+    mut session: SessionAuth,
     ws_manager: Arc<WebSocketManager>,
 ) {
     let (mut sender, mut receiver) = socket.split();
+    let config = ws_manager.session_config();
+
+    let connection_id = match ws_manager.register_connection(&user_id, org_id) {
+        Ok(connection_id) => connection_id,
+        Err(rejection) => {
+            warn!(
+                org_id = ?org_id,
+                user_id = %user_id,
+                reason = rejection.reason(),
+                "Refusing WebSocket connection"
+            );
+            let _ = sender
+                .send(Message::Close(Some(CloseFrame {
+                    code: limits::CLOSE_CONNECTION_LIMIT,
+                    reason: rejection.reason().into(),
+                })))
+                .await;
+            return;
+        }
+    };
 
     // Subscribe to task events
     let mut rx = ws_manager.subscribe();
-
-    // Clone user_id for both async tasks
-    let user_id_send = user_id.clone();
-    let user_id_recv = user_id.clone();
-
-    // Send task for receiving events and forwarding to client
-    let mut send_task = tokio::spawn(async move {
-        while let Ok(event) = rx.recv().await {
-            // Serialize event to JSON
-            match serde_json::to_string(&event) {
-                Ok(json) => {
-                    debug!(
-                        org_id = ?org_id,
-                        user_id = %user_id_send,
-                        "Sending event to client: {}",
-                        json
-                    );
-
-                    if sender.send(Message::Text(json.into())).await.is_err() {
-                        error!(
+    let mut presence_rx = ws_manager.subscribe_presence();
+
+    let mut ping =
+        tokio::time::interval_at(Instant::now() + config.ping_interval, config.ping_interval);
+    ping.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut last_seen = Instant::now();
+
+    // Revalidate straight away so the credential's expiry is known
+    let revalidate = tokio::time::sleep(std::time::Duration::ZERO);
+    tokio::pin!(revalidate);
+
+    let close_frame = loop {
+        tokio::select! {
+            event = rx.recv() => {
+                let sent = match event {
+                    Ok(event) if !event.is_visible_to(org_id) => Ok(()),
+                    Ok(event) => {
+                        debug!(
                             org_id = ?org_id,
-                            user_id = %user_id_send,
-                            "Failed to send message to client, closing connection"
+                            user_id = %user_id,
+                            "Sending event for task {} to client",
+                            event.task_id()
                         );
-                        break;
+                        send_json(&mut sender, &event).await
                     }
-                }
-                Err(e) => {
+                    Err(RecvError::Lagged(skipped)) => {
+                        ws_manager.record_lag(connection_id, skipped);
+                        warn!(
+                            org_id = ?org_id,
+                            user_id = %user_id,
+                            %connection_id,
+                            dropped_events = skipped,
+                            "WebSocket client lagged behind broadcast channel, requesting resync"
+                        );
+                        send_json(
+                            &mut sender,
+                            &ControlMessage::Resync {
+                                dropped_events: skipped,
+                                timestamp: chrono::Utc::now(),
+                            },
+                        )
+                        .await
+                    }
+                    Err(RecvError::Closed) => break None,
+                };
+
+                if sent.is_err() {
                     error!(
                         org_id = ?org_id,
-                        user_id = %user_id_send,
-                        "Failed to serialize event: {}",
-                        e
+                        user_id = %user_id,
+                        "Failed to send message to client, closing connection"
                     );
+                    break None;
                 }
             }
-        }
-    });
-
-    // Task for receiving client messages (ping/pong for keepalive)
-    let mut recv_task = tokio::spawn(async move {
-        while let Some(Ok(msg)) = receiver.next().await {
-            match msg {
-                Message::Close(_) => {
-                    info!(
-                        org_id = ?org_id,
-                        user_id = %user_id_recv,
-                        "Client closed WebSocket connection"
-                    );
-                    break;
+            presence = presence_rx.recv() => {
+                match presence {
+                    Ok(event) if event.organization_id() == org_id => {
+                        if send_json(&mut sender, &event).await.is_err() {
+                            break None;
+                        }
+                    }
+                    Ok(_) => {}
+                    // Missed presence changes are corrected by the next event for the story
+                    Err(RecvError::Lagged(skipped)) => {
+                        debug!(%connection_id, skipped, "WebSocket client lagged on presence events");
+                    }
+                    Err(RecvError::Closed) => break None,
                 }
-                Message::Ping(_data) => {
-                    debug!(
-                        org_id = ?org_id,
-                        user_id = %user_id_recv,
-                        "Received ping from client"
-                    );
-                    // Pong is sent automatically by axum
+            }
+            message = receiver.next() => {
+                let Some(Ok(message)) = message else {
+                    break None;
+                };
+                last_seen = Instant::now();
+
+                match message {
+                    Message::Close(_) => {
+                        info!(
+                            org_id = ?org_id,
+                            user_id = %user_id,
+                            "Client closed WebSocket connection"
+                        );
+                        break None;
+                    }
+                    Message::Ping(_) => {
+                        // Pong is sent automatically by axum
+                        debug!(org_id = ?org_id, user_id = %user_id, "Received ping from client");
+                    }
+                    Message::Pong(_) => {
+                        debug!(org_id = ?org_id, user_id = %user_id, "Received pong from client");
+                    }
+                    Message::Text(text) => match serde_json::from_str::<ClientMessage>(&text) {
+                        Ok(ClientMessage::RefreshToken { token }) => {
+                            let reply = match session.refresh(token).await {
+                                Ok(expires_at) => {
+                                    info!(
+                                        org_id = ?org_id,
+                                        user_id = %user_id,
+                                        %expires_at,
+                                        "WebSocket session token refreshed"
+                                    );
+                                    revalidate
+                                        .as_mut()
+                                        .reset(Instant::now() + session.next_check_in(&config));
+                                    ControlMessage::TokenRefreshed { expires_at }
+                                }
+                                Err(err) => {
+                                    warn!(
+                                        org_id = ?org_id,
+                                        user_id = %user_id,
+                                        error = %err,
+                                        "Rejected WebSocket token refresh"
+                                    );
+                                    ControlMessage::TokenRefreshFailed {
+                                        reason: err.to_string(),
+                                    }
+                                }
+                            };
+                            if send_json(&mut sender, &reply).await.is_err() {
+                                break None;
+                            }
+                        }
+                        Ok(ClientMessage::ViewStory { story_id, editing }) => {
+                            ws_manager.join_story(connection_id, &user_id, org_id, story_id, editing);
+                        }
+                        Ok(ClientMessage::LeaveStory { story_id }) => {
+                            ws_manager.leave_story(connection_id, Some(story_id));
+                        }
+                        Err(_) => {
+                            debug!(
+                                org_id = ?org_id,
+                                user_id = %user_id,
+                                "Received text message from client: {}",
+                                text
+                            );
+                        }
+                    },
+                    Message::Binary(_) => {
+                        warn!(
+                            org_id = ?org_id,
+                            user_id = %user_id,
+                            "Received unexpected binary message from client"
+                        );
+                    }
                 }
-                Message::Pong(_) => {
-                    debug!(
+            }
+            _ = ping.tick() => {
+                if last_seen.elapsed() >= config.idle_timeout {
+                    warn!(
                         org_id = ?org_id,
-                        user_id = %user_id_recv,
-                        "Received pong from client"
+                        user_id = %user_id,
+                        %connection_id,
+                        "WebSocket client idle past timeout, closing connection"
                     );
+                    break Some(CloseFrame {
+                        code: session::CLOSE_IDLE_TIMEOUT,
+                        reason: "idle timeout".into(),
+                    });
                 }
-                Message::Text(text) => {
-                    debug!(
-                        org_id = ?org_id,
-                        user_id = %user_id_recv,
-                        "Received text message from client: {}",
-                        text
-                    );
+                if sender.send(Message::Ping(Bytes::new())).await.is_err() {
+                    break None;
                 }
-                Message::Binary(_) => {
-                    warn!(
-                        org_id = ?org_id,
-                        user_id = %user_id_recv,
-                        "Received unexpected binary message from client"
-                    );
+            }
+            _ = &mut revalidate => {
+                match session.revalidate().await {
+                    Ok(()) => {}
+                    Err(err) if is_auth_failure(&err) => {
+                        info!(
+                            org_id = ?org_id,
+                            user_id = %user_id,
+                            %connection_id,
+                            error = %err,
+                            "WebSocket credential no longer valid, closing connection"
+                        );
+                        break Some(CloseFrame {
+                            code: session::CLOSE_AUTH_EXPIRED,
+                            reason: "authentication expired".into(),
+                        });
+                    }
+                    Err(err) => {
+                        // Transient failures (e.g. database unavailable) keep the
+                        // connection open and retry on the next interval
+                        warn!(
+                            org_id = ?org_id,
+                            user_id = %user_id,
+                            error = %err,
+                            "WebSocket credential revalidation failed"
+                        );
+                    }
                 }
+                revalidate
+                    .as_mut()
+                    .reset(Instant::now() + session.next_check_in(&config));
             }
         }
-    });
+    };
 
-    // Wait for either task to finish
-    tokio::select! {
-        _ = (&mut send_task) => {
-            recv_task.abort();
-        },
-        _ = (&mut recv_task) => {
-            send_task.abort();
-        },
+    if let Some(frame) = close_frame {
+        let _ = sender.send(Message::Close(Some(frame))).await;
     }
 
+    let stats = ws_manager.unregister_connection(connection_id);
     info!(
         org_id = ?org_id,
         user_id = %user_id,
+        %connection_id,
+        dropped_events = stats.as_ref().map(|s| s.dropped_events).unwrap_or(0),
+        resyncs_sent = stats.as_ref().map(|s| s.resyncs_sent).unwrap_or(0),
         "WebSocket connection closed"
     );
 }
@@ -312,4 +812,149 @@ mod tests {
         let received = rx.recv().await.expect("Failed to receive event");
         assert_eq!(received.task_id(), event.task_id());
     }
+
+    #[tokio::test]
+    async fn test_broadcast_carries_request_correlation() {
+        let manager = WebSocketManager::new(100);
+        let mut rx = manager.subscribe();
+        let task_id = Uuid::new_v4();
+
+        EventCorrelation::from_request("req-42")
+            .scope(async {
+                manager.broadcast(TaskEvent::OwnershipReleased {
+                    task_id,
+                    story_id: Uuid::new_v4(),
+                    previous_owner_user_id: Uuid::new_v4(),
+                    timestamp: chrono::Utc::now(),
+                });
+            })
+            .await;
+
+        let received = rx.recv().await.expect("Failed to receive event");
+        assert_eq!(received.correlation_id.as_deref(), Some("req-42"));
+        let payload = serde_json::to_value(&received).unwrap();
+        assert_eq!(payload["type"], "ownership_released");
+        assert_eq!(payload["task_id"], task_id.to_string());
+        assert_eq!(payload["correlation_id"], "req-42");
+    }
+
+    #[test]
+    fn test_parse_channel_capacity() {
+        assert_eq!(parse_channel_capacity(None), DEFAULT_CHANNEL_CAPACITY);
+        assert_eq!(parse_channel_capacity(Some("256")), 256);
+        assert_eq!(parse_channel_capacity(Some("0")), DEFAULT_CHANNEL_CAPACITY);
+        assert_eq!(
+            parse_channel_capacity(Some("lots")),
+            DEFAULT_CHANNEL_CAPACITY
+        );
+    }
+
+    #[test]
+    fn test_record_lag_tracks_dropped_events_per_connection() {
+        let manager = WebSocketManager::new(4);
+        let first = manager.register_connection("user-1", None).unwrap();
+        let second = manager.register_connection("user-2", None).unwrap();
+
+        manager.record_lag(first, 7);
+        manager.record_lag(first, 3);
+
+        let snapshot = manager.metrics_snapshot();
+        assert_eq!(snapshot.channel_capacity, 4);
+        assert_eq!(snapshot.active_connections, 2);
+        assert_eq!(snapshot.total_dropped_events, 10);
+        assert_eq!(snapshot.total_resyncs_sent, 2);
+
+        let stats = manager.unregister_connection(first).unwrap();
+        assert_eq!(stats.dropped_events, 10);
+        assert_eq!(stats.resyncs_sent, 2);
+        assert_eq!(
+            manager
+                .unregister_connection(second)
+                .unwrap()
+                .dropped_events,
+            0
+        );
+        assert_eq!(manager.metrics_snapshot().active_connections, 0);
+    }
+
+    #[test]
+    fn test_connections_beyond_the_organization_limit_are_refused() {
+        let manager = WebSocketManager::new(4).with_connection_limits(ConnectionLimits {
+            max_connections: 3,
+            max_connections_per_organization: 2,
+            alert_percent: 80,
+        });
+        let (acme, globex) = (Some(Uuid::new_v4()), Some(Uuid::new_v4()));
+
+        let first = manager.register_connection("alice", acme).unwrap();
+        manager.register_connection("alice", acme).unwrap();
+        assert_eq!(
+            manager.register_connection("bob", acme),
+            Err(ConnectionRejection::OrganizationLimit)
+        );
+        manager.register_connection("carol", globex).unwrap();
+        assert_eq!(
+            manager.register_connection("dave", globex),
+            Err(ConnectionRejection::GlobalLimit)
+        );
+
+        let organizations = manager.connections_by_organization();
+        assert_eq!(organizations[0].organization_id, acme);
+        assert_eq!(organizations[0].connections, 2);
+        assert_eq!(organizations[0].users.len(), 1);
+        assert_eq!(organizations[1].users[0].user_id, "carol");
+        assert_eq!(manager.metrics_snapshot().total_rejected_connections, 2);
+
+        manager.unregister_connection(first);
+        assert!(manager.register_connection("bob", acme).is_ok());
+    }
+
+    #[test]
+    fn test_refresh_token_client_message() {
+        let message: ClientMessage =
+            serde_json::from_str(r#"{"type":"refresh_token","token":"abc"}"#).unwrap();
+        let ClientMessage::RefreshToken { token } = message else {
+            panic!("unexpected message: {:?}", message);
+        };
+        assert_eq!(token, "abc");
+    }
+
+    #[tokio::test]
+    async fn test_presence_is_broadcast_and_cleared_on_disconnect() {
+        let manager = WebSocketManager::new(16);
+        let mut rx = manager.subscribe_presence();
+        let story_id = Uuid::new_v4();
+        let connection_id = manager.register_connection("user-1", None).unwrap();
+
+        let message: ClientMessage = serde_json::from_str(&format!(
+            r#"{{"type":"view_story","story_id":"{}","editing":true}}"#,
+            story_id
+        ))
+        .unwrap();
+        let ClientMessage::ViewStory { editing, .. } = message else {
+            panic!("unexpected message: {:?}", message);
+        };
+        manager.join_story(connection_id, "user-1", None, story_id, editing);
+
+        let joined = serde_json::to_value(rx.recv().await.unwrap()).unwrap();
+        assert_eq!(joined["type"], "presence_joined");
+        assert_eq!(joined["viewers"][0]["editing"], true);
+        assert!(joined.get("organization_id").is_none());
+
+        manager.unregister_connection(connection_id);
+        let left = serde_json::to_value(rx.recv().await.unwrap()).unwrap();
+        assert_eq!(left["type"], "presence_left");
+        assert!(manager.story_viewers(story_id).is_empty());
+    }
+
+    #[test]
+    fn test_resync_message_serialization() {
+        let message = ControlMessage::Resync {
+            dropped_events: 5,
+            timestamp: chrono::Utc::now(),
+        };
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["type"], "resync");
+        assert_eq!(json["dropped_events"], 5);
+    }
 }
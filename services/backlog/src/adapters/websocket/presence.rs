@@ -0,0 +1,220 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Someone with a story open, merged across their connections
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StoryViewer {
+    pub user_id: String,
+    pub editing: bool,
+    pub since: DateTime<Utc>,
+}
+
+/// Presence changes broadcast to clients of the same organization. Each event
+/// carries the story's current viewers so clients can render them directly.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PresenceEvent {
+    /// A user opened the story, or switched between viewing and editing it
+    PresenceJoined {
+        story_id: Uuid,
+        #[serde(skip)]
+        organization_id: Option<Uuid>,
+        user_id: String,
+        editing: bool,
+        viewers: Vec<StoryViewer>,
+        timestamp: DateTime<Utc>,
+    },
+    /// A user's last connection left the story
+    PresenceLeft {
+        story_id: Uuid,
+        #[serde(skip)]
+        organization_id: Option<Uuid>,
+        user_id: String,
+        viewers: Vec<StoryViewer>,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl PresenceEvent {
+    pub fn organization_id(&self) -> Option<Uuid> {
+        match self {
+            PresenceEvent::PresenceJoined {
+                organization_id, ..
+            }
+            | PresenceEvent::PresenceLeft {
+                organization_id, ..
+            } => *organization_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Presence {
+    user_id: String,
+    organization_id: Option<Uuid>,
+    editing: bool,
+    since: DateTime<Utc>,
+}
+
+/// Which story each connection has open. A connection is on at most one story
+/// at a time; opening another one leaves the previous.
+#[derive(Debug, Default)]
+pub struct StoryPresence {
+    stories: HashMap<Uuid, HashMap<Uuid, Presence>>,
+    by_connection: HashMap<Uuid, Uuid>,
+}
+
+impl StoryPresence {
+    /// Record that the connection has the story open, returning the events to broadcast
+    pub fn join(
+        &mut self,
+        connection_id: Uuid,
+        user_id: &str,
+        organization_id: Option<Uuid>,
+        story_id: Uuid,
+        editing: bool,
+    ) -> Vec<PresenceEvent> {
+        let mut events = Vec::new();
+        if self.by_connection.get(&connection_id) != Some(&story_id) {
+            events.extend(self.leave(connection_id, None));
+        }
+
+        let now = Utc::now();
+        let presence = self
+            .stories
+            .entry(story_id)
+            .or_default()
+            .entry(connection_id)
+            .or_insert_with(|| Presence {
+                user_id: user_id.to_string(),
+                organization_id,
+                editing,
+                since: now,
+            });
+        presence.editing = editing;
+        self.by_connection.insert(connection_id, story_id);
+
+        events.push(PresenceEvent::PresenceJoined {
+            story_id,
+            organization_id,
+            user_id: user_id.to_string(),
+            editing,
+            viewers: self.viewers(story_id),
+            timestamp: now,
+        });
+        events
+    }
+
+    /// Remove the connection from its story (only if it is `story_id`, when
+    /// given). Returns a leave event when that was the user's last connection
+    /// on the story.
+    pub fn leave(&mut self, connection_id: Uuid, story_id: Option<Uuid>) -> Option<PresenceEvent> {
+        let current = *self.by_connection.get(&connection_id)?;
+        if story_id.is_some_and(|story_id| story_id != current) {
+            return None;
+        }
+        self.by_connection.remove(&connection_id);
+
+        let connections = self.stories.get_mut(&current)?;
+        let presence = connections.remove(&connection_id)?;
+        if connections.is_empty() {
+            self.stories.remove(&current);
+        }
+
+        let viewers = self.viewers(current);
+        if viewers
+            .iter()
+            .any(|viewer| viewer.user_id == presence.user_id)
+        {
+            return None;
+        }
+        Some(PresenceEvent::PresenceLeft {
+            story_id: current,
+            organization_id: presence.organization_id,
+            user_id: presence.user_id,
+            viewers,
+            timestamp: Utc::now(),
+        })
+    }
+
+    /// Current viewers of the story, one entry per user, earliest first
+    pub fn viewers(&self, story_id: Uuid) -> Vec<StoryViewer> {
+        let mut by_user: HashMap<&str, StoryViewer> = HashMap::new();
+        for presence in self
+            .stories
+            .get(&story_id)
+            .into_iter()
+            .flat_map(|c| c.values())
+        {
+            by_user
+                .entry(presence.user_id.as_str())
+                .and_modify(|viewer| {
+                    viewer.editing |= presence.editing;
+                    viewer.since = viewer.since.min(presence.since);
+                })
+                .or_insert_with(|| StoryViewer {
+                    user_id: presence.user_id.clone(),
+                    editing: presence.editing,
+                    since: presence.since,
+                });
+        }
+
+        let mut viewers: Vec<StoryViewer> = by_user.into_values().collect();
+        viewers.sort_by(|a, b| a.since.cmp(&b.since).then(a.user_id.cmp(&b.user_id)));
+        viewers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_switch_and_leave() {
+        let mut presence = StoryPresence::default();
+        let (first_story, second_story) = (Uuid::new_v4(), Uuid::new_v4());
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+
+        presence.join(alice, "alice", None, first_story, false);
+        let events = presence.join(bob, "bob", None, first_story, true);
+        match &events[..] {
+            [PresenceEvent::PresenceJoined { viewers, .. }] => {
+                assert_eq!(viewers.len(), 2);
+                assert!(viewers.iter().any(|v| v.user_id == "bob" && v.editing));
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+
+        // Opening another story leaves the first
+        let events = presence.join(alice, "alice", None, second_story, false);
+        assert!(matches!(
+            &events[..],
+            [PresenceEvent::PresenceLeft { user_id, .. }, PresenceEvent::PresenceJoined { .. }]
+                if user_id == "alice"
+        ));
+        assert_eq!(presence.viewers(first_story).len(), 1);
+
+        // Leaving a story the connection is not on does nothing
+        assert!(presence.leave(bob, Some(second_story)).is_none());
+        assert!(presence.leave(bob, Some(first_story)).is_some());
+        assert!(presence.viewers(first_story).is_empty());
+    }
+
+    #[test]
+    fn test_user_leaves_with_last_connection() {
+        let mut presence = StoryPresence::default();
+        let story_id = Uuid::new_v4();
+        let (tab1, tab2) = (Uuid::new_v4(), Uuid::new_v4());
+
+        presence.join(tab1, "alice", None, story_id, true);
+        presence.join(tab2, "alice", None, story_id, false);
+        assert_eq!(presence.viewers(story_id).len(), 1);
+        assert!(presence.viewers(story_id)[0].editing);
+
+        assert!(presence.leave(tab1, None).is_none());
+        assert!(!presence.viewers(story_id)[0].editing);
+        assert!(presence.leave(tab2, None).is_some());
+    }
+}
@@ -0,0 +1,240 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use auth_clerk::JwtVerifier;
+use chrono::{DateTime, Utc};
+use common::AppError;
+use sqlx::PgPool;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::adapters::persistence::repo;
+
+const DEFAULT_PING_INTERVAL_SECS: u64 = 30;
+const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 90;
+const DEFAULT_REVALIDATE_INTERVAL_SECS: u64 = 300;
+
+const PING_INTERVAL_ENV: &str = "WEBSOCKET_PING_INTERVAL_SECS";
+const IDLE_TIMEOUT_ENV: &str = "WEBSOCKET_IDLE_TIMEOUT_SECS";
+const REVALIDATE_INTERVAL_ENV: &str = "WEBSOCKET_REVALIDATE_INTERVAL_SECS";
+
+/// Close code sent when a client stops responding to server pings
+pub const CLOSE_IDLE_TIMEOUT: u16 = 4000;
+/// Close code sent when the credential a connection authenticated with expires or is revoked
+pub const CLOSE_AUTH_EXPIRED: u16 = 4001;
+
+/// Heartbeat and re-authentication timings for WebSocket connections
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionConfig {
+    pub ping_interval: Duration,
+    pub idle_timeout: Duration,
+    pub revalidate_interval: Duration,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            ping_interval: Duration::from_secs(DEFAULT_PING_INTERVAL_SECS),
+            idle_timeout: Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECS),
+            revalidate_interval: Duration::from_secs(DEFAULT_REVALIDATE_INTERVAL_SECS),
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Read `WEBSOCKET_PING_INTERVAL_SECS`, `WEBSOCKET_IDLE_TIMEOUT_SECS` and
+    /// `WEBSOCKET_REVALIDATE_INTERVAL_SECS`, falling back to the defaults
+    pub fn from_env() -> Self {
+        let read = |name: &str, default: u64| {
+            parse_secs(name, std::env::var(name).ok().as_deref(), default)
+        };
+
+        Self {
+            ping_interval: read(PING_INTERVAL_ENV, DEFAULT_PING_INTERVAL_SECS),
+            idle_timeout: read(IDLE_TIMEOUT_ENV, DEFAULT_IDLE_TIMEOUT_SECS),
+            revalidate_interval: read(REVALIDATE_INTERVAL_ENV, DEFAULT_REVALIDATE_INTERVAL_SECS),
+        }
+    }
+}
+
+fn parse_secs(name: &str, raw: Option<&str>, default: u64) -> Duration {
+    let secs = match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => match value.parse::<u64>() {
+            Ok(secs) if secs > 0 => secs,
+            _ => {
+                warn!(value, "Invalid {}; using default of {}s", name, default);
+                default
+            }
+        },
+        None => default,
+    };
+    Duration::from_secs(secs)
+}
+
+/// The credential a connection authenticated with, kept so it can be re-checked
+#[derive(Clone)]
+pub enum WsCredential {
+    Jwt(String),
+    ApiKey(String),
+}
+
+impl std::fmt::Debug for WsCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WsCredential::Jwt(_) => f.write_str("Jwt(<redacted>)"),
+            WsCredential::ApiKey(_) => f.write_str("ApiKey(<redacted>)"),
+        }
+    }
+}
+
+/// Tracks whether a connection's credential is still valid for its lifetime
+pub struct SessionAuth {
+    credential: WsCredential,
+    user_id: String,
+    verifier: Option<Arc<Mutex<JwtVerifier>>>,
+    pool: PgPool,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl SessionAuth {
+    pub fn new(
+        credential: WsCredential,
+        user_id: String,
+        verifier: Option<Arc<Mutex<JwtVerifier>>>,
+        pool: PgPool,
+    ) -> Self {
+        Self {
+            credential,
+            user_id,
+            verifier,
+            pool,
+            expires_at: None,
+        }
+    }
+
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+    }
+
+    /// Re-check the current credential. JWTs are re-verified against the JWKS and
+    /// API keys are looked up again so revoked keys are caught.
+    pub async fn revalidate(&mut self) -> Result<(), AppError> {
+        match self.credential.clone() {
+            WsCredential::Jwt(token) => {
+                self.expires_at = Some(self.verify_jwt(&token).await?);
+            }
+            WsCredential::ApiKey(key) => {
+                if !repo::api_key_is_active(&self.pool, &key).await? {
+                    return Err(AppError::Unauthorized(
+                        "API key has been revoked".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the connection's credential with a fresh JWT for the same user
+    pub async fn refresh(&mut self, token: String) -> Result<DateTime<Utc>, AppError> {
+        let expires_at = self.verify_jwt(&token).await?;
+        self.credential = WsCredential::Jwt(token);
+        self.expires_at = Some(expires_at);
+        Ok(expires_at)
+    }
+
+    /// How long to wait before the next revalidation: the configured interval,
+    /// or sooner if the credential expires first
+    pub fn next_check_in(&self, config: &SessionConfig) -> Duration {
+        match self.expires_at {
+            Some(expires_at) => (expires_at - Utc::now())
+                .to_std()
+                .unwrap_or(Duration::ZERO)
+                .min(config.revalidate_interval),
+            None => config.revalidate_interval,
+        }
+    }
+
+    async fn verify_jwt(&self, token: &str) -> Result<DateTime<Utc>, AppError> {
+        let verifier = self.verifier.as_ref().ok_or_else(|| {
+            AppError::Unauthorized("JWT verifier not configured for WebSocket session".to_string())
+        })?;
+        let claims = verifier.lock().await.verify(token).await?;
+
+        if claims.sub != self.user_id {
+            return Err(AppError::Unauthorized(
+                "Token subject does not match the connected user".to_string(),
+            ));
+        }
+
+        let expires_at = DateTime::<Utc>::from_timestamp(claims.exp as i64, 0)
+            .ok_or_else(|| AppError::Unauthorized("Token has an invalid expiry".to_string()))?;
+        if expires_at <= Utc::now() {
+            return Err(AppError::Unauthorized("Token has expired".to_string()));
+        }
+
+        Ok(expires_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(credential: WsCredential) -> SessionAuth {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        SessionAuth::new(
+            credential,
+            "01234567-89ab-cdef-0123-456789abcdef".to_string(),
+            Some(Arc::new(Mutex::new(JwtVerifier::new_test_verifier()))),
+            pool,
+        )
+    }
+
+    #[test]
+    fn test_parse_secs() {
+        assert_eq!(parse_secs("X", None, 30), Duration::from_secs(30));
+        assert_eq!(parse_secs("X", Some("45"), 30), Duration::from_secs(45));
+        assert_eq!(parse_secs("X", Some("0"), 30), Duration::from_secs(30));
+        assert_eq!(parse_secs("X", Some("soon"), 30), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_credential_debug_is_redacted() {
+        let rendered = format!("{:?}", WsCredential::ApiKey("secret".to_string()));
+        assert!(!rendered.contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn test_next_check_in_is_capped_by_expiry() {
+        let config = SessionConfig::default();
+        let mut session = session(WsCredential::Jwt("valid-test-token".to_string()));
+        assert_eq!(session.next_check_in(&config), config.revalidate_interval);
+
+        session.expires_at = Some(Utc::now() + chrono::Duration::seconds(10));
+        assert!(session.next_check_in(&config) <= Duration::from_secs(10));
+
+        session.expires_at = Some(Utc::now() - chrono::Duration::seconds(10));
+        assert_eq!(session.next_check_in(&config), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rejects_token_for_another_user() {
+        let mut session = session(WsCredential::Jwt("valid-test-token".to_string()));
+        session.user_id = "someone-else".to_string();
+
+        let result = session.refresh("valid-test-token".to_string()).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_records_expiry() {
+        let mut session = session(WsCredential::ApiKey("key".to_string()));
+
+        let expires_at = session
+            .refresh("valid-test-token".to_string())
+            .await
+            .unwrap();
+        assert_eq!(session.expires_at(), Some(expires_at));
+        assert!(matches!(session.credential, WsCredential::Jwt(_)));
+    }
+}
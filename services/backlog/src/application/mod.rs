@@ -1,4 +1,6 @@
 pub mod ports;
+pub mod story_query;
 pub mod usecases;
 
+pub use story_query::*;
 pub use usecases::*;
@@ -2,6 +2,8 @@ use async_trait::async_trait;
 use common::AppError;
 use uuid::Uuid;
 
+use crate::domain::{PushNotification, StandupSummary, Story, Task, TaskSplitPart};
+
 #[async_trait]
 pub trait ReadinessService: Send + Sync {
     async fn validate_acceptance_criteria_refs(
@@ -10,3 +12,33 @@ pub trait ReadinessService: Send + Sync {
         ac_refs: &[String],
     ) -> Result<Vec<String>, AppError>;
 }
+
+/// Turns a structured standup summary into a short narrative for team channels
+#[async_trait]
+pub trait StandupNarrator: Send + Sync {
+    async fn narrate(
+        &self,
+        summary: &StandupSummary,
+        organization_id: Option<Uuid>,
+    ) -> Result<String, AppError>;
+}
+
+/// Proposes how to break an oversized task into smaller ones
+#[async_trait]
+pub trait TaskSplitProposer: Send + Sync {
+    async fn propose_split(
+        &self,
+        story: &Story,
+        task: &Task,
+    ) -> Result<Vec<TaskSplitPart>, AppError>;
+}
+
+/// Delivers high-priority notifications to a user's subscribed browsers.
+/// Notifying must not fail or slow down the request, so implementations send
+/// in the background and only log their own errors.
+pub trait PushNotifier: Send + Sync {
+    fn notify(&self, notification: PushNotification);
+
+    /// The base64url public key browsers subscribe with
+    fn application_server_key(&self) -> String;
+}
@@ -0,0 +1,149 @@
+use crate::domain::{Story, StoryCondition, StoryFilter};
+use common::AppError;
+
+const MAX_PAGE_SIZE: i64 = 200;
+
+/// What project story listings can be ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorySortField {
+    #[default]
+    Title,
+    CreatedAt,
+    UpdatedAt,
+    /// Unestimated stories come last either way
+    Points,
+}
+
+/// The order of a story listing; ties are broken by id so pages are stable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StorySort {
+    pub field: StorySortField,
+    pub descending: bool,
+}
+
+impl StorySort {
+    /// `title`, `created`, `updated` or `points`, with a leading `-` for
+    /// descending order, e.g. `-updated`
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        let value = value.trim();
+        let (field, descending) = match value.strip_prefix('-') {
+            Some(field) => (field, true),
+            None => (value, false),
+        };
+        let field = match field.to_lowercase().replace('_', "").as_str() {
+            "title" => StorySortField::Title,
+            "created" | "createdat" => StorySortField::CreatedAt,
+            "updated" | "updatedat" => StorySortField::UpdatedAt,
+            "points" | "storypoints" => StorySortField::Points,
+            _ => return Err(AppError::BadRequest(format!("Invalid sort: {}", value))),
+        };
+        Ok(Self { field, descending })
+    }
+}
+
+/// Which of a project's stories to list, in what order, and which page of them.
+/// Without a limit every matching story is returned.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StoryQuery {
+    pub filter: Option<StoryFilter>,
+    pub sort: StorySort,
+    pub limit: Option<i64>,
+    pub offset: i64,
+}
+
+impl StoryQuery {
+    /// Narrow the query to stories also matching `filter`
+    pub fn with_filter(mut self, filter: StoryFilter) -> Self {
+        self.filter = Some(match self.filter.take() {
+            Some(existing) => StoryFilter::all(vec![existing, filter]),
+            None => filter,
+        });
+        self
+    }
+
+    pub fn with_condition(self, condition: StoryCondition) -> Self {
+        self.with_filter(StoryFilter::Condition(condition))
+    }
+
+    pub fn sorted_by(mut self, sort: StorySort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Return at most `limit` stories, skipping the first `offset`
+    pub fn paged(mut self, limit: Option<i64>, offset: Option<i64>) -> Result<Self, AppError> {
+        if let Some(limit) = limit {
+            if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+                return Err(AppError::BadRequest(format!(
+                    "limit must be between 1 and {}",
+                    MAX_PAGE_SIZE
+                )));
+            }
+        }
+        let offset = offset.unwrap_or(0);
+        if offset < 0 {
+            return Err(AppError::BadRequest(
+                "offset must not be negative".to_string(),
+            ));
+        }
+        self.limit = limit;
+        self.offset = offset;
+        Ok(self)
+    }
+}
+
+/// A page of stories and how many match the query in total
+#[derive(Debug, Clone)]
+pub struct StoryPage {
+    pub stories: Vec<Story>,
+    pub total: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::StoryStatus;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_sort_parses_fields_and_direction() {
+        assert_eq!(
+            StorySort::parse("-updated_at").unwrap(),
+            StorySort {
+                field: StorySortField::UpdatedAt,
+                descending: true,
+            }
+        );
+        assert_eq!(
+            StorySort::parse("points").unwrap(),
+            StorySort {
+                field: StorySortField::Points,
+                descending: false,
+            }
+        );
+        assert!(StorySort::parse("priority").is_err());
+    }
+
+    #[test]
+    fn test_conditions_combine_and_paging_is_validated() {
+        let sprint_id = Uuid::new_v4();
+        let query = StoryQuery::default()
+            .with_condition(StoryCondition::Status(StoryStatus::Ready))
+            .with_condition(StoryCondition::Sprint(sprint_id));
+        assert_eq!(
+            query.filter,
+            Some(StoryFilter::And(vec![
+                StoryFilter::Condition(StoryCondition::Status(StoryStatus::Ready)),
+                StoryFilter::Condition(StoryCondition::Sprint(sprint_id)),
+            ]))
+        );
+
+        let query = query.paged(Some(50), None).unwrap();
+        assert_eq!((query.limit, query.offset), (Some(50), 0));
+        assert!(StoryQuery::default().paged(Some(0), None).is_err());
+        assert!(StoryQuery::default()
+            .paged(Some(MAX_PAGE_SIZE + 1), None)
+            .is_err());
+        assert!(StoryQuery::default().paged(None, Some(-1)).is_err());
+    }
+}
@@ -1,28 +1,118 @@
-use crate::adapters::persistence::repo;
-use crate::domain::{AcceptanceCriteria, Story, StoryStatus, Task, TaskStatus};
+use crate::adapters::persistence::models::{BugRow, SprintRow};
+use crate::adapters::persistence::{repo, UnitOfWork};
+use crate::application::ports::{PushNotifier, StandupNarrator, TaskSplitProposer};
+use crate::application::{StoryPage, StoryQuery};
+use crate::domain::{
+    absorb_duplicate, already_claimed, archive_cutoff, claim_queue_window, ensure_criteria_covered,
+    ensure_queueable, linkable_items, normalize_label, parse_import, plan_sprint_transitions,
+    plan_sprints, rollover_target, same_label, stale_cutoff, validate_task_batch,
+    AcceptanceCriteria, AcceptanceCriteriaBatch, ArchiveSearch, ArchivedItem, BacklogHealth,
+    BacklogHealthSnapshot, BadgeMetric, BadgeSummary, BoardConfiguration, BugDetails, BugPriority,
+    BugSeverity, BugSla, CadenceSprint, ClaimQueueEntry, CriterionVerification,
+    DependencyDirection, DependencyGraph, DependencyLink, ImportFormat, ImportItem,
+    ImportItemError, ImportJob, ImportJobStatus, ImportThrottle, ItemReference, LabelRename,
+    LaneStory, LaneTask, PushNotification, PushSubscription, ReferenceSourceType, ResolvedShortKey,
+    RolloverTarget, ScheduledSprint, ScopeChange, ShortKeyTarget, SlaComplianceReport, SlaPolicy,
+    SlaTargets, SlaTimerKind, SprintAssignment, SprintCadence, SprintCadenceSettings, SprintHealth,
+    SprintPeriod, SprintRollover, SprintStatSnapshot, SprintTransitions, StandupSummary,
+    StatsFreshness, StatsSource, Story, StoryBulkUpdate, StoryDependencies, StoryDependency,
+    StoryMerge, StoryRevision, StoryRevisionDiff, StoryShare, StoryStatus, StoryType, Swimlanes,
+    Task, TaskBulkUpdate, TaskCompletionPolicy, TaskEvent, TaskSplitPart, TaskStatus,
+    TaskStatusUpdate, TaskTransfer, IMPORT_CHUNK_SIZE, MAX_REPORTED_IMPORT_ERRORS,
+};
+use common::analytics::{
+    AnalyticsEmitter, AnalyticsEvent, NoopAnalyticsEmitter, FEATURE_STANDUP_NARRATIVE,
+    FEATURE_TASK_SPLIT, STORY_ACCEPTED, STORY_CREATED, STORY_READY,
+};
+use common::quota::{QuotaGuard, QuotaResource, UnlimitedQuotaGuard};
+use common::reason_policy::{DestructiveAction, ReasonPolicy};
+use common::references::{extract_short_keys, ExternalMention};
 use common::AppError;
 use event_bus::{
     AcceptanceCriterionRecord, BacklogEvent, DomainEvent, EventPublisher, SprintEvent,
-    SprintRecord, StoryRecord, TaskRecord,
+    SprintRecord, SprintScopeChange, StoryRecord, TaskRecord,
 };
 use sqlx::PgPool;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Most stories shared in one request; larger sets share the project
+const MAX_SHARED_STORIES: usize = 100;
+/// Stories moved to the archive per transaction
+const ARCHIVE_BATCH_SIZE: i64 = 200;
+/// Drafts flagged as stale per statement
+const STALE_BATCH_SIZE: i64 = 200;
+/// Relabelled stories loaded and announced at a time
+const LABEL_EVENT_BATCH_SIZE: usize = 100;
+/// How long a worker holds an import job without making progress before
+/// another worker may take it over
+const IMPORT_LEASE_SECONDS: u64 = 5 * 60;
+
 pub struct BacklogUsecases {
     pool: Arc<PgPool>,
     events: Arc<dyn EventPublisher>,
+    standup_narrator: Option<Arc<dyn StandupNarrator>>,
+    task_split_proposer: Option<Arc<dyn TaskSplitProposer>>,
+    push_notifier: Option<Arc<dyn PushNotifier>>,
+    quota_guard: Arc<dyn QuotaGuard>,
+    analytics: Arc<dyn AnalyticsEmitter>,
 }
 
 impl BacklogUsecases {
     pub fn new(pool: Arc<PgPool>, events: Arc<dyn EventPublisher>) -> Self {
-        Self { pool, events }
+        Self {
+            pool,
+            events,
+            standup_narrator: None,
+            task_split_proposer: None,
+            push_notifier: None,
+            quota_guard: Arc::new(UnlimitedQuotaGuard),
+            analytics: Arc::new(NoopAnalyticsEmitter),
+        }
+    }
+
+    /// Enable narrative standup summaries
+    pub fn with_standup_narrator(mut self, narrator: Arc<dyn StandupNarrator>) -> Self {
+        self.standup_narrator = Some(narrator);
+        self
+    }
+
+    /// Enable LLM-proposed task splits
+    pub fn with_task_split_proposer(mut self, proposer: Arc<dyn TaskSplitProposer>) -> Self {
+        self.task_split_proposer = Some(proposer);
+        self
+    }
+
+    /// Push high-priority task events to subscribed browsers
+    pub fn with_push_notifier(mut self, notifier: Arc<dyn PushNotifier>) -> Self {
+        self.push_notifier = Some(notifier);
+        self
+    }
+
+    /// Enforce plan limits on stories and LLM calls
+    pub fn with_quota_guard(mut self, quota_guard: Arc<dyn QuotaGuard>) -> Self {
+        self.quota_guard = quota_guard;
+        self
+    }
+
+    /// Report the story funnel and feature usage to product analytics
+    pub fn with_analytics(mut self, analytics: Arc<dyn AnalyticsEmitter>) -> Self {
+        self.analytics = analytics;
+        self
     }
 
     async fn publish(&self, event: DomainEvent) {
         self.events.publish(event).await;
     }
 
+    fn push(&self, notification: PushNotification) {
+        if let Some(notifier) = &self.push_notifier {
+            notifier.notify(notification);
+        }
+    }
+
     fn acceptance_record(story_id: Uuid, ac: &AcceptanceCriteria) -> AcceptanceCriterionRecord {
         AcceptanceCriterionRecord {
             id: ac.id,
@@ -31,6 +121,7 @@ impl BacklogUsecases {
             given: ac.given.clone(),
             when: ac.when.clone(),
             then: ac.then.clone(),
+            position: ac.position,
             created_at: ac.created_at,
         }
     }
@@ -79,6 +170,24 @@ impl BacklogUsecases {
         }
     }
 
+    fn sprint_record(sprint: &SprintRow) -> SprintRecord {
+        SprintRecord {
+            id: sprint.id,
+            team_id: sprint.team_id,
+            organization_id: sprint.organization_id,
+            name: sprint.name.clone(),
+            goal: Some(sprint.goal.clone()).filter(|goal| !goal.is_empty()),
+            capacity_points: Some(sprint.capacity_points as u32),
+            status: sprint.status.clone(),
+            start_date: Some(sprint.start_date),
+            end_date: Some(sprint.end_date),
+            committed_points: Some(sprint.committed_points as u32),
+            completed_points: Some(sprint.completed_points as u32),
+            created_at: sprint.created_at,
+            updated_at: sprint.updated_at,
+        }
+    }
+
     pub async fn create_story(
         &self,
         project_id: Uuid,
@@ -86,17 +195,42 @@ impl BacklogUsecases {
         title: String,
         description: Option<String>,
         labels: Vec<String>,
+        story_type: StoryType,
+        timebox_hours: Option<u32>,
+        created_by: Option<Uuid>,
     ) -> Result<Uuid, AppError> {
         let mut story = Story::new(project_id, organization_id, title, description)?;
         for label in labels {
             story.add_label(label);
         }
-        repo::create_story(&self.pool, &story).await?;
-        let record = Self::story_record(&story);
-        self.publish(DomainEvent::Backlog(BacklogEvent::StoryCreated {
-            story: record,
-        }))
+        story.set_story_type(story_type);
+        story.set_timebox_hours(timebox_hours)?;
+        self.quota_guard
+            .ensure_capacity(organization_id, QuotaResource::Stories, 1)
+            .await?;
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            repo::create_story_with_transaction(uow.tx(), &story).await?;
+            repo::record_story_revision_with_transaction(uow.tx(), &story, created_by).await?;
+            uow.publish(DomainEvent::Backlog(BacklogEvent::StoryCreated {
+                story: Self::story_record(&story),
+            }))
+            .await
+        }
+        .await;
+        uow.finish(result).await?;
+        self.link_mentions(
+            ReferenceSourceType::Story,
+            story.id,
+            story.organization_id,
+            &[Some(&story.title), story.description.as_deref()],
+        )
         .await;
+        self.analytics.emit(AnalyticsEvent::new(
+            STORY_CREATED,
+            story.organization_id,
+            Some(story.id),
+        ));
         Ok(story.id)
     }
 
@@ -108,6 +242,291 @@ impl BacklogUsecases {
         repo::get_story(&self.pool, id, organization_id).await
     }
 
+    /// The story if the organization owns it, or read-only when another
+    /// organization shared it. Writes must keep using [`Self::get_story`].
+    pub async fn get_readable_story(
+        &self,
+        id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<Story>, AppError> {
+        if let Some(story) = self.get_story(id, organization_id).await? {
+            return Ok(Some(story));
+        }
+        match organization_id {
+            Some(organization_id) => repo::get_shared_story(&self.pool, id, organization_id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// A story's revisions, newest first
+    pub async fn get_story_revisions(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<StoryRevision>, AppError> {
+        self.get_story(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+        repo::get_story_revisions(&self.pool, story_id).await
+    }
+
+    /// Field-level changes from revision `from` to revision `to`
+    pub async fn diff_story_revisions(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        from: u32,
+        to: u32,
+    ) -> Result<StoryRevisionDiff, AppError> {
+        self.get_story(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+        let revision = |number: u32| async move {
+            repo::get_story_revision(&self.pool, story_id, number)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Revision {} not found", number)))
+        };
+        let from = revision(from).await?;
+        let to = revision(to).await?;
+        Ok(StoryRevisionDiff::between(&from, &to))
+    }
+
+    /// The organization's latest story edits, newest first
+    pub async fn get_recent_story_revisions(
+        &self,
+        organization_id: Option<Uuid>,
+        limit: u32,
+    ) -> Result<Vec<StoryRevision>, AppError> {
+        repo::get_recent_story_revisions(&self.pool, organization_id, limit as i64).await
+    }
+
+    /// Stories the user owns that are in one of `statuses`, most recently updated first
+    pub async fn get_stories_assigned_to(
+        &self,
+        user_id: Uuid,
+        organization_id: Option<Uuid>,
+        statuses: &[StoryStatus],
+    ) -> Result<Vec<Story>, AppError> {
+        repo::get_stories_assigned_to(&self.pool, user_id, organization_id, statuses).await
+    }
+
+    /// Links to and from a story, including mentions of and by its tasks
+    pub async fn get_story_references(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<ItemReference>, AppError> {
+        self.get_story(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+        repo::get_story_references(&self.pool, story_id).await
+    }
+
+    /// The stories this story blocks and the ones it is blocked by
+    pub async fn get_story_dependencies(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<StoryDependencies, AppError> {
+        self.get_story(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+
+        let dependencies = repo::get_story_dependencies(&self.pool, story_id).await?;
+        let other_ids: Vec<Uuid> = dependencies
+            .iter()
+            .map(|dependency| {
+                if dependency.story_id == story_id {
+                    dependency.blocked_by_id
+                } else {
+                    dependency.story_id
+                }
+            })
+            .collect();
+        let others: HashMap<Uuid, Story> =
+            repo::get_stories_by_ids(&self.pool, &other_ids, organization_id)
+                .await?
+                .into_iter()
+                .map(|story| (story.id, story))
+                .collect();
+
+        let mut listed = StoryDependencies {
+            story_id,
+            ..Default::default()
+        };
+        for dependency in &dependencies {
+            if dependency.story_id == story_id {
+                if let Some(blocker) = others.get(&dependency.blocked_by_id) {
+                    listed
+                        .blocked_by
+                        .push(DependencyLink::new(blocker, dependency));
+                }
+            } else if let Some(blocked) = others.get(&dependency.story_id) {
+                listed.blocks.push(DependencyLink::new(blocked, dependency));
+            }
+        }
+        Ok(listed)
+    }
+
+    /// Record that `story_id` blocks, or is blocked by, `other_id`. Refused when
+    /// the stories would end up blocking each other in a loop.
+    pub async fn add_story_dependency(
+        &self,
+        story_id: Uuid,
+        other_id: Uuid,
+        direction: DependencyDirection,
+        organization_id: Option<Uuid>,
+        created_by: Option<Uuid>,
+    ) -> Result<StoryDependency, AppError> {
+        let dependency =
+            StoryDependency::new(story_id, other_id, direction, organization_id, created_by)?;
+        for id in [story_id, other_id] {
+            self.get_story(id, organization_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Story {} not found", id)))?;
+        }
+
+        let existing =
+            repo::get_organization_story_dependencies(&self.pool, organization_id).await?;
+        if let Some(recorded) = existing.iter().find(|recorded| {
+            recorded.story_id == dependency.story_id
+                && recorded.blocked_by_id == dependency.blocked_by_id
+        }) {
+            return Ok(recorded.clone());
+        }
+        dependency.ensure_acyclic(&existing)?;
+
+        if repo::create_story_dependency(&self.pool, &dependency).await? {
+            self.publish_story_blockers(dependency.story_id, organization_id)
+                .await?;
+        }
+        Ok(dependency)
+    }
+
+    /// Drop the dependency between `story_id` and `other_id` in `direction`
+    pub async fn remove_story_dependency(
+        &self,
+        story_id: Uuid,
+        other_id: Uuid,
+        direction: DependencyDirection,
+        organization_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        self.get_story(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+        let (blocked_id, blocker_id) = match direction {
+            DependencyDirection::BlockedBy => (story_id, other_id),
+            DependencyDirection::Blocks => (other_id, story_id),
+        };
+
+        if !repo::delete_story_dependency(&self.pool, blocked_id, blocker_id).await? {
+            return Err(AppError::NotFound("Dependency not found".to_string()));
+        }
+        self.publish_story_blockers(blocked_id, organization_id)
+            .await
+    }
+
+    async fn publish_story_blockers(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        let blocked_by = repo::get_story_blockers(&self.pool, story_id).await?;
+        self.publish(DomainEvent::Backlog(BacklogEvent::StoryBlockersChanged {
+            story_id,
+            organization_id,
+            blocked_by,
+        }))
+        .await;
+        Ok(())
+    }
+
+    /// Link the stories and tasks named in GitHub commits, pull requests and comments.
+    /// Returns how many links the mentions resolved to.
+    pub async fn record_external_mentions(
+        &self,
+        mentions: &[ExternalMention],
+    ) -> Result<usize, AppError> {
+        let mut linked = 0;
+        for mention in mentions {
+            let targets = repo::find_items_by_short_keys(&self.pool, &mention.keys).await?;
+            repo::add_external_references(
+                &self.pool,
+                mention.source_type.into(),
+                &mention.url,
+                &mention.title,
+                &targets,
+            )
+            .await?;
+            linked += targets.len();
+        }
+        Ok(linked)
+    }
+
+    /// Keep a story's or task's outgoing links in step with the keys in its text.
+    /// Linking is best effort and never fails the write that triggered it.
+    async fn link_mentions(
+        &self,
+        source_type: ReferenceSourceType,
+        source_id: Uuid,
+        organization_id: Option<Uuid>,
+        texts: &[Option<&str>],
+    ) {
+        let keys = extract_short_keys(
+            &texts
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        let result = async {
+            let items = repo::find_items_by_short_keys(&self.pool, &keys).await?;
+            let targets = linkable_items(source_id, organization_id, items);
+            repo::replace_item_references(
+                &self.pool,
+                source_type,
+                source_id,
+                organization_id,
+                &targets,
+            )
+            .await?;
+            Ok::<_, AppError>(targets)
+        }
+        .await;
+
+        match result {
+            // The stories a story mentions are the ones it depends on
+            Ok(targets) if source_type == ReferenceSourceType::Story => {
+                let depends_on = targets
+                    .iter()
+                    .filter(|item| item.target == ShortKeyTarget::Story)
+                    .map(|item| item.id)
+                    .collect();
+                self.publish(DomainEvent::Backlog(
+                    BacklogEvent::StoryDependenciesChanged {
+                        story_id: source_id,
+                        organization_id,
+                        depends_on,
+                    },
+                ))
+                .await;
+            }
+            Ok(_) => {}
+            Err(err) => {
+                tracing::warn!(%source_id, error = %err, "Failed to record item references");
+            }
+        }
+    }
+
+    pub async fn resolve_short_key(
+        &self,
+        key: &str,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<ResolvedShortKey>, AppError> {
+        repo::resolve_short_key(&self.pool, key, organization_id).await
+    }
+
     pub async fn update_story(
         &self,
         id: Uuid,
@@ -117,18 +536,39 @@ impl BacklogUsecases {
         labels: Option<Vec<String>>,
         story_points: Option<u32>,
         sprint_id: Option<Option<Uuid>>,
+        story_type: Option<StoryType>,
+        timebox_hours: Option<Option<u32>>,
+        edited_by: Option<Uuid>,
     ) -> Result<(), AppError> {
         let mut story = self
             .get_story(id, organization_id)
             .await?
             .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
 
+        if let Some(story_type) = story_type {
+            story.set_story_type(story_type);
+        }
+        if let Some(timebox_hours) = timebox_hours {
+            story.set_timebox_hours(timebox_hours)?;
+        }
         story.update(title, description, labels, story_points, sprint_id)?;
-        repo::update_story(&self.pool, &story).await?;
-        let record = Self::story_record(&story);
-        self.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
-            story: record,
-        }))
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            repo::update_story_with_transaction(uow.tx(), &story).await?;
+            repo::record_story_revision_with_transaction(uow.tx(), &story, edited_by).await?;
+            uow.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
+                story: Self::story_record(&story),
+            }))
+            .await
+        }
+        .await;
+        uow.finish(result).await?;
+        self.link_mentions(
+            ReferenceSourceType::Story,
+            story.id,
+            story.organization_id,
+            &[Some(&story.title), story.description.as_deref()],
+        )
         .await;
         Ok(())
     }
@@ -140,13 +580,29 @@ impl BacklogUsecases {
         user_id: Uuid,
         reason: Option<String>,
     ) -> Result<Story, AppError> {
+        let reason = self
+            .require_reason(
+                organization_id,
+                DestructiveAction::OverrideReadiness,
+                reason.as_deref(),
+            )
+            .await?;
         let mut story = self
             .get_story(id, organization_id)
             .await?
             .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
 
-        story.apply_readiness_override(user_id, reason);
+        story.apply_readiness_override(user_id, reason.clone());
         repo::update_story(&self.pool, &story).await?;
+        repo::record_destructive_action(
+            &self.pool,
+            organization_id,
+            Some(user_id),
+            DestructiveAction::OverrideReadiness,
+            story.id,
+            reason.as_deref(),
+        )
+        .await?;
         let record = Self::story_record(&story);
         self.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
             story: record,
@@ -166,22 +622,96 @@ impl BacklogUsecases {
             .await?
             .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
 
+        let funnel_stage = Self::funnel_stage(&status);
+        let fixes_bug = Self::ships_bug(story.story_type, &status);
+        let previous_status = story.status.clone();
         story.update_status(status)?;
         repo::update_story(&self.pool, &story).await?;
+        if fixes_bug {
+            repo::mark_bug_fixed(&self.pool, story.id, chrono::Utc::now()).await?;
+        }
         let record = Self::story_record(&story);
         self.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
             story: record,
         }))
         .await;
+        if story.status != previous_status {
+            self.push_story_status_change(&story).await?;
+        }
+        if let Some(stage) = funnel_stage {
+            self.analytics.emit(AnalyticsEvent::new(
+                stage,
+                story.organization_id,
+                Some(story.id),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The analytics funnel stage a story reaches with this status
+    fn funnel_stage(status: &StoryStatus) -> Option<&'static str> {
+        match status {
+            StoryStatus::Ready => Some(STORY_READY),
+            StoryStatus::Accepted => Some(STORY_ACCEPTED),
+            _ => None,
+        }
+    }
+
+    /// Shipping a bug stops its time-to-fix clock
+    fn ships_bug(story_type: StoryType, status: &StoryStatus) -> bool {
+        story_type == StoryType::Bug
+            && matches!(
+                status,
+                StoryStatus::Deployed | StoryStatus::AwaitingAcceptance | StoryStatus::Accepted
+            )
+    }
+
+    /// Tell the people a story's new status waits on
+    async fn push_story_status_change(&self, story: &Story) -> Result<(), AppError> {
+        if self.push_notifier.is_none() {
+            return Ok(());
+        }
+        match story.status {
+            StoryStatus::AwaitingAcceptance => {
+                if let Some(notification) = PushNotification::acceptance_requested(story) {
+                    self.push(notification);
+                }
+            }
+            StoryStatus::NeedsRefinement => {
+                let tasks =
+                    repo::get_tasks_by_story(&self.pool, story.id, story.organization_id).await?;
+                tasks
+                    .iter()
+                    .filter(|task| task.status != TaskStatus::Completed)
+                    .filter_map(|task| PushNotification::task_blocked(story, task))
+                    .for_each(|notification| self.push(notification));
+            }
+            _ => {}
+        }
         Ok(())
     }
 
+    /// Delete a story, keeping who deleted it and why in the audit log
     pub async fn delete_story(
         &self,
         id: Uuid,
         organization_id: Option<Uuid>,
+        deleted_by: Option<Uuid>,
+        reason: Option<&str>,
     ) -> Result<(), AppError> {
+        let reason = self
+            .require_reason(organization_id, DestructiveAction::DeleteStory, reason)
+            .await?;
         repo::delete_story(&self.pool, id, organization_id).await?;
+        repo::record_destructive_action(
+            &self.pool,
+            organization_id,
+            deleted_by,
+            DestructiveAction::DeleteStory,
+            id,
+            reason.as_deref(),
+        )
+        .await?;
         self.publish(DomainEvent::Backlog(BacklogEvent::StoryDeleted {
             story_id: id,
             organization_id,
@@ -190,6 +720,149 @@ impl BacklogUsecases {
         Ok(())
     }
 
+    /// Merge the duplicate `source_id` into `target_id`: its tasks, criteria,
+    /// labels and references move to the target and it is soft-deleted
+    pub async fn merge_stories(
+        &self,
+        target_id: Uuid,
+        source_id: Uuid,
+        organization_id: Option<Uuid>,
+        merged_by: Option<Uuid>,
+    ) -> Result<StoryMerge, AppError> {
+        let mut target = self
+            .get_story(target_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+        let source = self
+            .get_story(source_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Duplicate story not found".to_string()))?;
+
+        let moved_criteria = absorb_duplicate(&mut target, &source)?;
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            let moved_task_ids =
+                repo::merge_stories_with_transaction(uow.tx(), &target, source_id, merged_by)
+                    .await?;
+
+            // Projections see the target grow before the duplicate disappears, so
+            // none of them drops the moved tasks along with it
+            uow.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
+                story: Self::story_record(&target),
+            }))
+            .await?;
+            let moved_tasks =
+                repo::get_tasks_by_story_with_transaction(uow.tx(), target_id, organization_id)
+                    .await?;
+            for task in moved_tasks
+                .iter()
+                .filter(|task| moved_task_ids.contains(&task.id))
+            {
+                uow.publish(DomainEvent::Backlog(BacklogEvent::TaskUpdated {
+                    task: Self::task_record(task),
+                }))
+                .await?;
+            }
+            uow.publish(DomainEvent::Backlog(BacklogEvent::StoryDeleted {
+                story_id: source_id,
+                organization_id,
+            }))
+            .await?;
+            Ok(moved_task_ids)
+        }
+        .await;
+        let moved_task_ids = uow.finish(result).await?;
+
+        Ok(StoryMerge {
+            source_story_id: source_id,
+            target_story_id: target_id,
+            moved_task_ids,
+            moved_criteria,
+            merged_by,
+            merged_at: target.updated_at,
+        })
+    }
+
+    /// Rename `label` to `to` on every story of a project, or with `merge`
+    /// fold it into the existing label `to`. The stories and the project's
+    /// settings change in one transaction, which announces them in batches.
+    pub async fn change_project_label(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        label: &str,
+        to: &str,
+        merge: bool,
+        changed_by: Option<Uuid>,
+    ) -> Result<LabelRename, AppError> {
+        let from = normalize_label(label)?;
+        let to = normalize_label(to)?;
+        if from == to {
+            return Err(AppError::BadRequest(
+                "The label would not change".to_string(),
+            ));
+        }
+        repo::get_project(&self.pool, project_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+        if !repo::project_label_in_use(&self.pool, project_id, organization_id, &from).await? {
+            return Err(AppError::NotFound(format!(
+                "Label '{}' is not used in this project",
+                from
+            )));
+        }
+
+        let target_in_use = !same_label(&from, &to)
+            && repo::project_label_in_use(&self.pool, project_id, organization_id, &to).await?;
+        if merge && !target_in_use {
+            return Err(AppError::NotFound(format!(
+                "Label '{}' is not used in this project; rename instead",
+                to
+            )));
+        }
+        if !merge && target_in_use {
+            return Err(AppError::Conflict(format!(
+                "Label '{}' already exists; merge into it instead",
+                to
+            )));
+        }
+
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            let updated_story_ids = repo::relabel_project_with_transaction(
+                uow.tx(),
+                project_id,
+                organization_id,
+                &from,
+                &to,
+                changed_by,
+            )
+            .await?;
+            for batch in updated_story_ids.chunks(LABEL_EVENT_BATCH_SIZE) {
+                let stories =
+                    repo::get_stories_by_ids_with_transaction(uow.tx(), batch, organization_id)
+                        .await?;
+                for story in &stories {
+                    uow.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
+                        story: Self::story_record(story),
+                    }))
+                    .await?;
+                }
+            }
+            Ok(updated_story_ids)
+        }
+        .await;
+        let updated_story_ids = uow.finish(result).await?;
+
+        Ok(LabelRename {
+            project_id,
+            from,
+            to,
+            merged: merge,
+            updated_story_ids,
+        })
+    }
+
     pub async fn create_sprint(
         &self,
         project_id: Uuid,
@@ -229,8 +902,14 @@ impl BacklogUsecases {
             ));
         }
 
+        // The sprint starts now and runs to the end of its last local day
+        let calendar = repo::get_project_calendar(&self.pool, project_id).await?;
         let start_date = chrono::Utc::now();
-        let end_date = start_date + chrono::Duration::days(DEFAULT_SPRINT_DURATION_DAYS);
+        let today = calendar.local_date(start_date);
+        let (_, end_date) = calendar.sprint_bounds(
+            today,
+            today + chrono::Duration::days(DEFAULT_SPRINT_DURATION_DAYS - 1),
+        );
 
         let mut stories_to_commit: Vec<Story> = Vec::new();
         for story_id in &stories {
@@ -255,95 +934,1530 @@ impl BacklogUsecases {
 
         let goal = goal.trim().to_string();
 
-        // Start a transaction to ensure all operations succeed or fail together
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(|_| AppError::InternalServerError)?;
-
-        let sprint_id = repo::create_sprint_with_transaction(
-            &mut tx,
-            project_id,
-            team_id,
-            effective_org_id,
-            name.clone(),
-            goal.clone(),
-            capacity_points,
-            "active",
-            start_date,
-            end_date,
-            committed_points,
-            0,
-        )
-        .await?;
-
-        // Update all stories within the same transaction
-        for story in &mut stories_to_commit {
-            story.assign_to_sprint(sprint_id)?;
-            story.update_status(StoryStatus::Committed)?;
-            repo::update_story_with_transaction(&mut tx, story).await?;
-        }
+        // Run every write in one unit of work so a failure rolls the whole sprint back
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            let sprint_id = repo::create_sprint_with_transaction(
+                uow.tx(),
+                project_id,
+                team_id,
+                effective_org_id,
+                name.clone(),
+                goal.clone(),
+                capacity_points,
+                "active",
+                start_date,
+                end_date,
+                committed_points,
+                0,
+            )
+            .await?;
 
-        // Set team active sprint within the same transaction
-        repo::set_team_active_sprint_with_transaction(&mut tx, team_id, sprint_id).await?;
+            for story in &mut stories_to_commit {
+                story.assign_to_sprint(sprint_id)?;
+                story.update_status(StoryStatus::Committed)?;
+                repo::update_story_with_transaction(uow.tx(), story).await?;
+            }
 
-        // Commit the transaction - if any operation failed, this will rollback everything
-        tx.commit()
-            .await
-            .map_err(|_| AppError::InternalServerError)?;
+            repo::set_team_active_sprint_with_transaction(uow.tx(), team_id, sprint_id).await?;
 
-        // Publish events after successful transaction commit
-        for story in &stories_to_commit {
-            let record = Self::story_record(story);
-            self.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
-                story: record,
+            for story in &stories_to_commit {
+                uow.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
+                    story: Self::story_record(story),
+                }))
+                .await?;
+            }
+            uow.publish(DomainEvent::Sprint(SprintEvent::Created {
+                sprint: SprintRecord {
+                    id: sprint_id,
+                    team_id,
+                    organization_id: effective_org_id,
+                    name,
+                    goal: if goal.is_empty() { None } else { Some(goal) },
+                    capacity_points: Some(capacity_points),
+                    status: "Active".to_string(),
+                    start_date: Some(start_date),
+                    end_date: Some(end_date),
+                    committed_points: Some(committed_points),
+                    completed_points: Some(0),
+                    created_at: start_date,
+                    updated_at: start_date,
+                },
             }))
-            .await;
+            .await?;
+            Ok(sprint_id)
         }
-
-        self.publish(DomainEvent::Sprint(SprintEvent::Created {
-            sprint: SprintRecord {
-                id: sprint_id,
-                team_id,
-                organization_id: effective_org_id,
-                name,
-                goal: if goal.is_empty() { None } else { Some(goal) },
-                capacity_points: Some(capacity_points),
-                status: "Active".to_string(),
-                start_date: Some(start_date),
-                end_date: Some(end_date),
-                committed_points: Some(committed_points),
-                completed_points: Some(0),
-                created_at: start_date,
-                updated_at: start_date,
-            },
-        }))
         .await;
-
-        Ok(sprint_id)
+        uow.finish(result).await
     }
 
-    pub async fn get_stories_by_project(
+    /// Create the team's next `count` sprints on a cadence, after its latest
+    /// sprint. Boundaries skip the organization's holidays.
+    pub async fn schedule_sprints(
         &self,
         project_id: Uuid,
         organization_id: Option<Uuid>,
-        status: Option<StoryStatus>,
-        sprint_id: Option<Uuid>,
-    ) -> Result<Vec<Story>, AppError> {
-        let stories =
-            repo::get_stories_by_project(&self.pool, project_id, organization_id, sprint_id)
-                .await?;
+        cadence: SprintCadence,
+        count: u32,
+        capacity_points: Option<u32>,
+    ) -> Result<Vec<ScheduledSprint>, AppError> {
+        let project = repo::get_project(&self.pool, project_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
 
-        if let Some(status_filter) = status {
-            Ok(stories
-                .into_iter()
-                .filter(|story| story.status == status_filter)
-                .collect())
-        } else {
-            Ok(stories)
-        }
-    }
+        let team_id = project.team_id.ok_or_else(|| {
+            AppError::BadRequest(
+                "Project is missing a team assignment; assign a team before scheduling sprints."
+                    .to_string(),
+            )
+        })?;
+
+        const DEFAULT_SPRINT_CAPACITY: u32 = 40;
+        let capacity_points = capacity_points.unwrap_or(DEFAULT_SPRINT_CAPACITY);
+        if capacity_points == 0 {
+            return Err(AppError::BadRequest(
+                "Sprint capacity must be greater than 0".to_string(),
+            ));
+        }
+
+        let effective_org_id = organization_id.or(project.organization_id);
+        let calendar = repo::get_project_calendar(&self.pool, project_id).await?;
+        let (existing_sprints, latest_end) =
+            repo::get_team_sprint_summary(&self.pool, team_id).await?;
+
+        // Continue after the latest sprint, never in the past
+        let today = calendar.local_date(chrono::Utc::now());
+        let from = latest_end
+            .map(|end| calendar.local_date(end).succ_opt().unwrap_or(today))
+            .map_or(today, |next| next.max(today));
+
+        let horizon = from + chrono::Duration::days(((count + 1) * cadence.length_days) as i64);
+        let holidays = match effective_org_id {
+            Some(org_id) => {
+                repo::get_organization_holidays(&self.pool, org_id, from, horizon).await?
+            }
+            None => Default::default(),
+        };
+        let planned = plan_sprints(cadence, from, count, &holidays)?;
+
+        let now = chrono::Utc::now();
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            let mut scheduled = Vec::with_capacity(planned.len());
+            for (index, sprint) in planned.iter().enumerate() {
+                let name = format!("Sprint {}", existing_sprints + index as i64 + 1);
+                let (start_date, end_date) = calendar.sprint_bounds(sprint.start, sprint.end);
+                let id = repo::create_sprint_with_transaction(
+                    uow.tx(),
+                    project_id,
+                    team_id,
+                    effective_org_id,
+                    name.clone(),
+                    String::new(),
+                    capacity_points,
+                    "planning",
+                    start_date,
+                    end_date,
+                    0,
+                    0,
+                )
+                .await?;
+                uow.publish(DomainEvent::Sprint(SprintEvent::Created {
+                    sprint: SprintRecord {
+                        id,
+                        team_id,
+                        organization_id: effective_org_id,
+                        name: name.clone(),
+                        goal: None,
+                        capacity_points: Some(capacity_points),
+                        status: "Planning".to_string(),
+                        start_date: Some(start_date),
+                        end_date: Some(end_date),
+                        committed_points: Some(0),
+                        completed_points: Some(0),
+                        created_at: now,
+                        updated_at: now,
+                    },
+                }))
+                .await?;
+                scheduled.push(ScheduledSprint {
+                    id,
+                    name,
+                    status: "planning".to_string(),
+                    capacity_points,
+                    start_date,
+                    end_date,
+                    working_days: sprint.working_days,
+                });
+            }
+            Ok(scheduled)
+        }
+        .await;
+        uow.finish(result).await
+    }
+
+    pub async fn get_sprint_cadence_settings(
+        &self,
+        organization_id: Option<Uuid>,
+    ) -> Result<SprintCadenceSettings, AppError> {
+        match organization_id {
+            Some(organization_id) => {
+                repo::get_sprint_cadence_settings(&self.pool, organization_id).await
+            }
+            None => Ok(SprintCadenceSettings::default()),
+        }
+    }
+
+    pub async fn set_sprint_cadence_settings(
+        &self,
+        organization_id: Option<Uuid>,
+        settings: SprintCadenceSettings,
+    ) -> Result<SprintCadenceSettings, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest("Sprint cadences can only be set for an organization".to_string())
+        })?;
+        repo::upsert_sprint_cadence_settings(&self.pool, organization_id, settings).await?;
+        Ok(settings)
+    }
+
+    /// For organizations with auto-activation on, complete each team's sprint
+    /// once it has ended, rolling its unfinished stories over, and activate the
+    /// planned sprint whose dates have started. Returns how many sprints
+    /// changed status; a team that fails is logged and skipped.
+    pub async fn advance_sprint_cadence(&self) -> Result<usize, AppError> {
+        let now = chrono::Utc::now();
+        let sprints = repo::get_auto_cadence_sprints(&self.pool).await?;
+
+        let mut changed = 0;
+        for team_sprints in sprints.chunk_by(|a, b| a.team_id == b.team_id) {
+            let windows: Vec<CadenceSprint> = team_sprints
+                .iter()
+                .map(|sprint| CadenceSprint {
+                    id: sprint.id,
+                    status: sprint.status.clone(),
+                    start_date: sprint.start_date,
+                    end_date: sprint.end_date,
+                })
+                .collect();
+            let transitions = plan_sprint_transitions(&windows, now);
+            if transitions.is_empty() {
+                continue;
+            }
+
+            let team_id = team_sprints[0].team_id;
+            match self
+                .apply_sprint_transitions(team_sprints, &transitions)
+                .await
+            {
+                Ok(count) => changed += count,
+                Err(e) => {
+                    tracing::error!(error = %e, %team_id, "Failed to advance sprint cadence")
+                }
+            }
+        }
+        Ok(changed)
+    }
+
+    async fn apply_sprint_transitions(
+        &self,
+        team_sprints: &[SprintRow],
+        transitions: &SprintTransitions,
+    ) -> Result<usize, AppError> {
+        let find = |id: Uuid| team_sprints.iter().find(|sprint| sprint.id == id);
+        let rollover = self
+            .get_sprint_cadence_settings(team_sprints[0].organization_id)
+            .await?
+            .rollover;
+        let next = transitions.roll_over_to.and_then(find);
+
+        let mut changed = 0;
+        for sprint in transitions.complete.iter().filter_map(|id| find(*id)) {
+            if self
+                .complete_sprint_on_cadence(sprint, next, rollover)
+                .await?
+            {
+                changed += 1;
+            }
+        }
+        if let Some(sprint) = transitions.activate.and_then(find) {
+            if self.activate_sprint_on_cadence(sprint).await? {
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Complete an ended sprint, moving its unfinished stories as `rollover`
+    /// says. Returns false when another replica got there first.
+    async fn complete_sprint_on_cadence(
+        &self,
+        sprint: &SprintRow,
+        next: Option<&SprintRow>,
+        rollover: SprintRollover,
+    ) -> Result<bool, AppError> {
+        let stories =
+            repo::get_stories_by_sprint(&self.pool, sprint.id, sprint.organization_id).await?;
+        let completed_points: u32 = stories
+            .iter()
+            .filter(|story| story.status == StoryStatus::Accepted)
+            .map(|story| story.story_points.unwrap_or(0))
+            .sum();
+
+        let mut moved: Vec<(Story, RolloverTarget)> = Vec::new();
+        for mut story in stories {
+            let Some(target) = rollover_target(rollover, &story.status, next.map(|next| next.id))
+            else {
+                continue;
+            };
+            match target {
+                RolloverTarget::Sprint(next_id) => story.carry_over_to_sprint(next_id),
+                RolloverTarget::Backlog => story.remove_from_sprint()?,
+            }
+            moved.push((story, target));
+        }
+
+        // Carrying stories out of an ending sprint is not a scope cut
+        let completed = SprintRow {
+            status: "completed".to_string(),
+            ..sprint.clone()
+        };
+        let changes: Vec<(ScopeChange, Option<ScopeChange>)> = moved
+            .iter()
+            .map(|(story, target)| {
+                let added = match (target, next) {
+                    (RolloverTarget::Sprint(_), Some(next)) => {
+                        Some(Self::scope_change(next, story, true))
+                    }
+                    _ => None,
+                };
+                (Self::scope_change(&completed, story, false), added)
+            })
+            .collect();
+
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            let Some(_) = repo::complete_sprint_with_transaction(
+                uow.tx(),
+                sprint.id,
+                completed_points as i32,
+            )
+            .await?
+            else {
+                return Ok(false);
+            };
+            for ((story, _), (removed, added)) in moved.iter().zip(&changes) {
+                repo::update_story_with_transaction(uow.tx(), story).await?;
+                repo::record_sprint_scope_change_with_transaction(
+                    uow.tx(),
+                    sprint.id,
+                    sprint.organization_id,
+                    removed,
+                )
+                .await?;
+                if let (Some(added), Some(next)) = (added, next) {
+                    repo::record_sprint_scope_change_with_transaction(
+                        uow.tx(),
+                        next.id,
+                        next.organization_id,
+                        added,
+                    )
+                    .await?;
+                    repo::adjust_sprint_committed_points_with_transaction(
+                        uow.tx(),
+                        next.id,
+                        story.story_points.unwrap_or(0) as i32,
+                    )
+                    .await?;
+                }
+            }
+
+            for ((story, _), (removed, added)) in moved.iter().zip(changes) {
+                uow.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
+                    story: Self::story_record(story),
+                }))
+                .await?;
+                uow.publish(DomainEvent::Sprint(SprintEvent::StoryRemoved {
+                    change: Self::sprint_scope_change(sprint, removed),
+                }))
+                .await?;
+                if let (Some(added), Some(next)) = (added, next) {
+                    uow.publish(DomainEvent::Sprint(SprintEvent::StoryAdded {
+                        change: Self::sprint_scope_change(next, added),
+                    }))
+                    .await?;
+                }
+            }
+            Self::publish_sprint_updated(&mut uow, sprint).await?;
+            if let Some(next) = next.filter(|_| !moved.is_empty()) {
+                Self::publish_sprint_updated(&mut uow, next).await?;
+            }
+            Ok(true)
+        }
+        .await;
+        if !uow.finish(result).await? {
+            return Ok(false);
+        }
+
+        tracing::info!(
+            sprint_id = %sprint.id,
+            team_id = %sprint.team_id,
+            rolled_over = moved.len(),
+            "Completed sprint on cadence"
+        );
+        Ok(true)
+    }
+
+    /// Start a planned sprint whose dates have begun. Returns false when it is
+    /// no longer planned.
+    async fn activate_sprint_on_cadence(&self, sprint: &SprintRow) -> Result<bool, AppError> {
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            if !repo::activate_sprint_with_transaction(uow.tx(), sprint.id, sprint.team_id).await? {
+                return Ok(false);
+            }
+            Self::publish_sprint_updated(&mut uow, sprint).await?;
+            Ok(true)
+        }
+        .await;
+        if !uow.finish(result).await? {
+            return Ok(false);
+        }
+
+        tracing::info!(sprint_id = %sprint.id, team_id = %sprint.team_id, "Activated sprint on cadence");
+        Ok(true)
+    }
+
+    /// Publish the sprint as it stands in the unit of work
+    async fn publish_sprint_updated(
+        uow: &mut UnitOfWork,
+        sprint: &SprintRow,
+    ) -> Result<(), AppError> {
+        if let Some(sprint) =
+            repo::get_sprint_with_transaction(uow.tx(), sprint.id, sprint.organization_id).await?
+        {
+            uow.publish(DomainEvent::Sprint(SprintEvent::Updated {
+                sprint: Self::sprint_record(&sprint),
+            }))
+            .await?;
+        }
+        Ok(())
+    }
+
+    fn sprint_scope_change(sprint: &SprintRow, change: ScopeChange) -> SprintScopeChange {
+        SprintScopeChange {
+            sprint_id: sprint.id,
+            story_id: change.story_id,
+            organization_id: sprint.organization_id,
+            story_points: change.story_points,
+            sprint_status: change.sprint_status,
+            changed_at: change.changed_at,
+        }
+    }
+
+    /// A sprint that exists in the organization and still accepts scope changes
+    async fn open_sprint(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<SprintRow, AppError> {
+        let sprint = repo::get_sprint(&self.pool, sprint_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Sprint not found".to_string()))?;
+        if matches!(sprint.status.as_str(), "completed" | "cancelled") {
+            return Err(AppError::BadRequest(format!(
+                "Cannot change the scope of a {} sprint",
+                sprint.status
+            )));
+        }
+        Ok(sprint)
+    }
+
+    /// Move a Ready story from the product backlog into a sprint
+    pub async fn add_story_to_sprint(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+        story_id: Uuid,
+    ) -> Result<Story, AppError> {
+        let mut sprint = self.open_sprint(sprint_id, organization_id).await?;
+        let mut story = self
+            .get_story(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+
+        match story.sprint_id {
+            Some(current) if current == sprint_id => {
+                return Err(AppError::Conflict(
+                    "Story is already in this sprint".to_string(),
+                ))
+            }
+            Some(_) => {
+                return Err(AppError::Conflict(
+                    "Story is already in another sprint; remove it from that sprint first"
+                        .to_string(),
+                ))
+            }
+            None => {}
+        }
+
+        let project = repo::get_project(&self.pool, story.project_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+        if project.team_id != Some(sprint.team_id) {
+            return Err(AppError::BadRequest(
+                "Story belongs to a project of another team".to_string(),
+            ));
+        }
+
+        let points = story.story_points.unwrap_or(0);
+        if sprint.committed_points as u32 + points > sprint.capacity_points as u32 {
+            return Err(AppError::BadRequest(format!(
+                "Adding {} points would exceed sprint capacity ({} of {} committed)",
+                points, sprint.committed_points, sprint.capacity_points
+            )));
+        }
+
+        story.assign_to_sprint(sprint_id)?;
+        story.update_status(StoryStatus::Committed)?;
+        let change = Self::scope_change(&sprint, &story, true);
+
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            repo::update_story_with_transaction(uow.tx(), &story).await?;
+            repo::record_sprint_scope_change_with_transaction(
+                uow.tx(),
+                sprint_id,
+                sprint.organization_id,
+                &change,
+            )
+            .await?;
+            sprint.committed_points = repo::adjust_sprint_committed_points_with_transaction(
+                uow.tx(),
+                sprint_id,
+                points as i32,
+            )
+            .await?;
+            sprint.updated_at = chrono::Utc::now();
+            Self::publish_scope_change(&mut uow, &sprint, &story, true).await
+        }
+        .await;
+        uow.finish(result).await?;
+        Ok(story)
+    }
+
+    /// Move a story out of a sprint and back to the product backlog
+    pub async fn remove_story_from_sprint(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+        story_id: Uuid,
+    ) -> Result<Story, AppError> {
+        let mut sprint = self.open_sprint(sprint_id, organization_id).await?;
+        let mut story = self
+            .get_story(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+        if story.sprint_id != Some(sprint_id) {
+            return Err(AppError::NotFound(
+                "Story is not in this sprint".to_string(),
+            ));
+        }
+
+        story.remove_from_sprint()?;
+        let points = story.story_points.unwrap_or(0);
+        let change = Self::scope_change(&sprint, &story, false);
+
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            repo::update_story_with_transaction(uow.tx(), &story).await?;
+            repo::record_sprint_scope_change_with_transaction(
+                uow.tx(),
+                sprint_id,
+                sprint.organization_id,
+                &change,
+            )
+            .await?;
+            sprint.committed_points = repo::adjust_sprint_committed_points_with_transaction(
+                uow.tx(),
+                sprint_id,
+                -(points as i32),
+            )
+            .await?;
+            sprint.updated_at = chrono::Utc::now();
+            Self::publish_scope_change(&mut uow, &sprint, &story, false).await
+        }
+        .await;
+        uow.finish(result).await?;
+        Ok(story)
+    }
+
+    fn scope_change(sprint: &SprintRow, story: &Story, added: bool) -> ScopeChange {
+        ScopeChange {
+            story_id: story.id,
+            story_points: story.story_points,
+            added,
+            sprint_status: sprint.status.clone(),
+            changed_at: chrono::Utc::now(),
+        }
+    }
+
+    async fn publish_scope_change(
+        uow: &mut UnitOfWork,
+        sprint: &SprintRow,
+        story: &Story,
+        added: bool,
+    ) -> Result<(), AppError> {
+        let change = SprintScopeChange {
+            sprint_id: sprint.id,
+            story_id: story.id,
+            organization_id: sprint.organization_id,
+            story_points: story.story_points,
+            sprint_status: sprint.status.clone(),
+            changed_at: chrono::Utc::now(),
+        };
+
+        uow.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
+            story: Self::story_record(story),
+        }))
+        .await?;
+        uow.publish(DomainEvent::Sprint(SprintEvent::Updated {
+            sprint: Self::sprint_record(sprint),
+        }))
+        .await?;
+        uow.publish(DomainEvent::Sprint(if added {
+            SprintEvent::StoryAdded { change }
+        } else {
+            SprintEvent::StoryRemoved { change }
+        }))
+        .await
+    }
+
+    /// Apply one update to several stories in a single transaction: every
+    /// story changes or none does. Each story the update changes is announced
+    /// with its own event; the others come back as they were.
+    pub async fn bulk_update_stories(
+        &self,
+        organization_id: Option<Uuid>,
+        user_id: Uuid,
+        update: StoryBulkUpdate,
+    ) -> Result<Vec<Story>, AppError> {
+        let mut stories = Vec::with_capacity(update.story_ids.len());
+        for story_id in &update.story_ids {
+            let story = self
+                .get_story(*story_id, organization_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Story {} not found", story_id)))?;
+            stories.push(story);
+        }
+        let previous_statuses: Vec<StoryStatus> =
+            stories.iter().map(|story| story.status.clone()).collect();
+        let mut changed = vec![false; stories.len()];
+
+        let mut sprints: HashMap<Uuid, SprintRow> = HashMap::new();
+        let mut scope_changes: Vec<(Uuid, ScopeChange)> = Vec::new();
+        match update.sprint {
+            Some(SprintAssignment::Sprint(sprint_id)) => {
+                let sprint = self.open_sprint(sprint_id, organization_id).await?;
+                let mut in_sprint_team: HashMap<Uuid, bool> = HashMap::new();
+                let mut added_points = 0;
+                for (story, changed) in stories.iter_mut().zip(changed.iter_mut()) {
+                    match story.sprint_id {
+                        Some(current) if current == sprint_id => continue,
+                        Some(_) => {
+                            return Err(AppError::Conflict(format!(
+                                "Story {} is already in another sprint; remove it from that sprint first",
+                                story.id
+                            )))
+                        }
+                        None => {}
+                    }
+                    if let Entry::Vacant(entry) = in_sprint_team.entry(story.project_id) {
+                        let project =
+                            repo::get_project(&self.pool, story.project_id, organization_id)
+                                .await?
+                                .ok_or_else(|| {
+                                    AppError::NotFound("Project not found".to_string())
+                                })?;
+                        entry.insert(project.team_id == Some(sprint.team_id));
+                    }
+                    if !in_sprint_team[&story.project_id] {
+                        return Err(AppError::BadRequest(format!(
+                            "Story {} belongs to a project of another team",
+                            story.id
+                        )));
+                    }
+
+                    story
+                        .assign_to_sprint(sprint_id)
+                        .and_then(|_| story.update_status(StoryStatus::Committed))
+                        .map_err(|e| bulk_item_error("Story", story.id, e))?;
+                    added_points += story.story_points.unwrap_or(0);
+                    scope_changes.push((sprint_id, Self::scope_change(&sprint, story, true)));
+                    *changed = true;
+                }
+                if sprint.committed_points as u32 + added_points > sprint.capacity_points as u32 {
+                    return Err(AppError::BadRequest(format!(
+                        "Adding {} points would exceed sprint capacity ({} of {} committed)",
+                        added_points, sprint.committed_points, sprint.capacity_points
+                    )));
+                }
+                sprints.insert(sprint_id, sprint);
+            }
+            Some(SprintAssignment::Backlog) => {
+                for (story, changed) in stories.iter_mut().zip(changed.iter_mut()) {
+                    let Some(sprint_id) = story.sprint_id else {
+                        continue;
+                    };
+                    if let Entry::Vacant(entry) = sprints.entry(sprint_id) {
+                        entry.insert(self.open_sprint(sprint_id, organization_id).await?);
+                    }
+                    story
+                        .remove_from_sprint()
+                        .map_err(|e| bulk_item_error("Story", story.id, e))?;
+                    scope_changes.push((
+                        sprint_id,
+                        Self::scope_change(&sprints[&sprint_id], story, false),
+                    ));
+                    *changed = true;
+                }
+            }
+            None => {}
+        }
+
+        for (story, changed) in stories.iter_mut().zip(changed.iter_mut()) {
+            if update.apply_labels(story) {
+                *changed = true;
+            }
+            if let Some(status) = &update.status {
+                if story.status != *status {
+                    story
+                        .update_status(status.clone())
+                        .map_err(|e| bulk_item_error("Story", story.id, e))?;
+                    *changed = true;
+                }
+            }
+        }
+
+        let fixed_at = chrono::Utc::now();
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            for ((story, previous_status), changed) in
+                stories.iter().zip(&previous_statuses).zip(&changed)
+            {
+                if !changed {
+                    continue;
+                }
+                repo::update_story_with_transaction(uow.tx(), story).await?;
+                repo::record_story_revision_with_transaction(uow.tx(), story, Some(user_id))
+                    .await?;
+                if story.status != *previous_status
+                    && Self::ships_bug(story.story_type, &story.status)
+                {
+                    repo::mark_bug_fixed_with_transaction(uow.tx(), story.id, fixed_at).await?;
+                }
+            }
+
+            for (sprint_id, change) in &scope_changes {
+                repo::record_sprint_scope_change_with_transaction(
+                    uow.tx(),
+                    *sprint_id,
+                    sprints[sprint_id].organization_id,
+                    change,
+                )
+                .await?;
+                let points = change.story_points.unwrap_or(0) as i32;
+                let delta = if change.added { points } else { -points };
+                let committed = repo::adjust_sprint_committed_points_with_transaction(
+                    uow.tx(),
+                    *sprint_id,
+                    delta,
+                )
+                .await?;
+                if let Some(sprint) = sprints.get_mut(sprint_id) {
+                    sprint.committed_points = committed;
+                    sprint.updated_at = chrono::Utc::now();
+                }
+            }
+
+            for (story, changed) in stories.iter().zip(&changed) {
+                if *changed {
+                    uow.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
+                        story: Self::story_record(story),
+                    }))
+                    .await?;
+                }
+            }
+            for (sprint_id, change) in &scope_changes {
+                let sprint = &sprints[sprint_id];
+                let change_event = SprintScopeChange {
+                    sprint_id: *sprint_id,
+                    story_id: change.story_id,
+                    organization_id: sprint.organization_id,
+                    story_points: change.story_points,
+                    sprint_status: change.sprint_status.clone(),
+                    changed_at: change.changed_at,
+                };
+                uow.publish(DomainEvent::Sprint(if change.added {
+                    SprintEvent::StoryAdded {
+                        change: change_event,
+                    }
+                } else {
+                    SprintEvent::StoryRemoved {
+                        change: change_event,
+                    }
+                }))
+                .await?;
+            }
+            for sprint in sprints.values() {
+                uow.publish(DomainEvent::Sprint(SprintEvent::Updated {
+                    sprint: Self::sprint_record(sprint),
+                }))
+                .await?;
+            }
+            Ok(())
+        }
+        .await;
+        uow.finish(result).await?;
+
+        for ((story, previous_status), changed) in
+            stories.iter().zip(&previous_statuses).zip(&changed)
+        {
+            if *changed && story.status != *previous_status {
+                self.push_story_status_change(story).await?;
+                if let Some(stage) = Self::funnel_stage(&story.status) {
+                    self.analytics.emit(AnalyticsEvent::new(
+                        stage,
+                        story.organization_id,
+                        Some(story.id),
+                    ));
+                }
+            }
+        }
+
+        Ok(stories)
+    }
+
+    /// The sprint backlog, with acceptance criteria loaded for readiness annotations
+    pub async fn get_sprint_stories(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<Story>, AppError> {
+        repo::get_sprint(&self.pool, sprint_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Sprint not found".to_string()))?;
+        let mut stories =
+            repo::get_stories_by_sprint(&self.pool, sprint_id, organization_id).await?;
+        repo::attach_acceptance_criteria(&*self.pool, &mut stories).await?;
+        Ok(stories)
+    }
+
+    /// A page of the project's stories matching `query`
+    pub async fn get_stories_by_project(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        query: &StoryQuery,
+    ) -> Result<StoryPage, AppError> {
+        let page =
+            repo::query_project_stories(&self.pool, project_id, organization_id, query).await?;
+        // A project belongs to one organization and shares with it are
+        // rejected, so an empty page means the caller either doesn't own the
+        // project or owns no stories matching `query`. Only the first can
+        // find anything shared.
+        if let (0, Some(organization_id)) = (page.total, organization_id) {
+            return repo::query_shared_project_stories(
+                &self.pool,
+                organization_id,
+                project_id,
+                query,
+            )
+            .await;
+        }
+        Ok(page)
+    }
+
+    /// Issue a new badge token for a project, invalidating any previous badge URLs
+    pub async fn rotate_badge_token(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<String, AppError> {
+        repo::get_project(&self.pool, project_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+
+        let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        repo::upsert_project_badge_token(&self.pool, project_id, organization_id, &token).await?;
+        Ok(token)
+    }
+
+    /// Compute a public badge for a project. Unknown projects and bad tokens are
+    /// both reported as not found so badge URLs cannot be probed.
+    pub async fn get_readiness_badge(
+        &self,
+        project_id: Uuid,
+        token: &str,
+        metric: BadgeMetric,
+    ) -> Result<BadgeSummary, AppError> {
+        if !repo::project_badge_token_is_valid(&self.pool, project_id, token).await? {
+            return Err(AppError::NotFound("Badge not found".to_string()));
+        }
+
+        match metric {
+            BadgeMetric::Ready => {
+                let counts: Vec<(StoryStatus, i64)> =
+                    repo::count_stories_by_status(&self.pool, project_id)
+                        .await?
+                        .into_iter()
+                        .filter_map(|(status, count)| {
+                            StoryStatus::from_str(&status).map(|status| (status, count))
+                        })
+                        .collect();
+                Ok(BadgeSummary::readiness(&counts))
+            }
+            BadgeMetric::Sprint => Ok(BadgeSummary::sprint(
+                repo::get_active_sprint_task_progress(&self.pool, project_id).await?,
+            )),
+        }
+    }
+
+    /// Score the project's backlog and record today's snapshot, returning the
+    /// score together with the daily snapshots from the last `trend_days` days
+    pub async fn get_backlog_health(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        trend_days: u32,
+    ) -> Result<(BacklogHealth, Vec<BacklogHealthSnapshot>), AppError> {
+        repo::get_project(&self.pool, project_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+
+        let stories =
+            repo::get_stories_by_project(&self.pool, project_id, organization_id, None).await?;
+        let mentions = repo::get_story_mention_edges(&self.pool, project_id).await?;
+        let now = chrono::Utc::now();
+        let health = BacklogHealth::compute(&stories, &mentions, now);
+        // Snapshots are bucketed by the project's local date
+        let today = repo::get_project_calendar(&self.pool, project_id)
+            .await?
+            .local_date(now);
+
+        if let (Some(score), Some(sub_scores)) = (health.score, health.sub_scores) {
+            let snapshot = BacklogHealthSnapshot {
+                date: today,
+                score,
+                sub_scores,
+            };
+            repo::upsert_backlog_health_snapshot(
+                &self.pool,
+                project_id,
+                organization_id,
+                &snapshot,
+                health.metrics.backlog_stories,
+            )
+            .await?;
+        }
+
+        let since = today - chrono::Duration::days(trend_days as i64);
+        let trend = repo::get_backlog_health_snapshots(&self.pool, project_id, since).await?;
+        Ok((health, trend))
+    }
+
+    /// Stories of the project with the dependencies between them and the
+    /// critical path to delivering all of them
+    pub async fn get_dependency_graph(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<DependencyGraph, AppError> {
+        repo::get_project(&self.pool, project_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+
+        let stories =
+            repo::get_stories_by_project(&self.pool, project_id, organization_id, None).await?;
+        let mentions = repo::get_story_mention_edges(&self.pool, project_id).await?;
+        Ok(DependencyGraph::build(project_id, &stories, &mentions))
+    }
+
+    /// Share a project, or some of its stories, read-only with another
+    /// organization; owners and admins only. Returns the shares created; ones
+    /// already active are skipped.
+    pub async fn share_stories(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        shared_with_organization_id: Uuid,
+        story_ids: Vec<Uuid>,
+        shared_by: Uuid,
+    ) -> Result<Vec<StoryShare>, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest("Only organizations can share stories".to_string())
+        })?;
+        self.require_organization_admin(organization_id, shared_by, "share stories")
+            .await?;
+        if story_ids.len() > MAX_SHARED_STORIES {
+            return Err(AppError::BadRequest(format!(
+                "At most {} stories can be shared at once",
+                MAX_SHARED_STORIES
+            )));
+        }
+        repo::get_project(&self.pool, project_id, Some(organization_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+        if !repo::organization_exists(&self.pool, shared_with_organization_id).await? {
+            return Err(AppError::NotFound("Organization not found".to_string()));
+        }
+
+        let mut targets: Vec<Option<Uuid>> = Vec::new();
+        for story_id in story_ids {
+            if targets.contains(&Some(story_id)) {
+                continue;
+            }
+            match self.get_story(story_id, Some(organization_id)).await? {
+                Some(story) if story.project_id == project_id => targets.push(Some(story_id)),
+                _ => {
+                    return Err(AppError::BadRequest(format!(
+                        "Story {} is not in this project",
+                        story_id
+                    )))
+                }
+            }
+        }
+        if targets.is_empty() {
+            targets.push(None);
+        }
+
+        let mut shares = Vec::new();
+        for story_id in targets {
+            let share = StoryShare::new(
+                organization_id,
+                shared_with_organization_id,
+                project_id,
+                story_id,
+                Some(shared_by),
+            )?;
+            if repo::create_story_share(&self.pool, &share).await? {
+                shares.push(share);
+            }
+        }
+        tracing::info!(
+            %project_id,
+            %organization_id,
+            %shared_with_organization_id,
+            created = shares.len(),
+            "Shared stories with another organization"
+        );
+        Ok(shares)
+    }
+
+    pub async fn get_story_shares(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        include_revoked: bool,
+    ) -> Result<Vec<StoryShare>, AppError> {
+        let Some(organization_id) = organization_id else {
+            return Ok(Vec::new());
+        };
+        repo::get_story_shares(&self.pool, project_id, organization_id, include_revoked).await
+    }
+
+    /// Revoke a share; the other organization loses access straight away.
+    /// Owners and admins only.
+    pub async fn revoke_story_share(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        share_id: Uuid,
+        revoked_by: Uuid,
+    ) -> Result<StoryShare, AppError> {
+        let share = match organization_id {
+            Some(organization_id) => {
+                self.require_organization_admin(organization_id, revoked_by, "revoke shares")
+                    .await?;
+                repo::revoke_story_share(&self.pool, share_id, project_id, organization_id).await?
+            }
+            None => None,
+        };
+        share.ok_or_else(|| AppError::NotFound("Share not found".to_string()))
+    }
+
+    async fn require_organization_admin(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        action: &str,
+    ) -> Result<(), AppError> {
+        let role = repo::get_organization_role(&self.pool, organization_id, user_id).await?;
+        if !matches!(role.as_deref(), Some("owner" | "admin")) {
+            return Err(AppError::Forbidden(format!(
+                "Only organization owners and admins can {}",
+                action
+            )));
+        }
+        Ok(())
+    }
+
+    /// Stories other organizations shared with this one
+    pub async fn get_shared_stories(
+        &self,
+        organization_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Story>, AppError> {
+        match organization_id {
+            Some(organization_id) => {
+                repo::get_shared_stories(&self.pool, organization_id, project_id, None).await
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// A bug story's severity, priority and environment with its SLA status
+    pub async fn get_bug(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<(BugDetails, BugSla), AppError> {
+        let row = self.find_bug(story_id, organization_id).await?;
+        let details = row.details();
+        let policy = self.get_bug_sla_policy(organization_id).await?;
+        let sla = BugSla::evaluate(row.reported_at, &details, &policy, chrono::Utc::now());
+        Ok((details, sla))
+    }
+
+    /// Change a bug's details; giving it a priority triages it
+    pub async fn update_bug(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        severity: Option<BugSeverity>,
+        priority: Option<BugPriority>,
+        environment_found: Option<String>,
+    ) -> Result<(BugDetails, BugSla), AppError> {
+        let row = self.find_bug(story_id, organization_id).await?;
+        let mut details = row.details();
+        let now = chrono::Utc::now();
+        if let Some(severity) = severity {
+            details.set_severity(severity);
+        }
+        if let Some(priority) = priority {
+            details.set_priority(priority, now);
+        }
+        if environment_found.is_some() {
+            details.set_environment_found(environment_found)?;
+        }
+        repo::upsert_bug_details(&self.pool, story_id, &details).await?;
+
+        let policy = self.get_bug_sla_policy(organization_id).await?;
+        let sla = BugSla::evaluate(row.reported_at, &details, &policy, now);
+        Ok((details, sla))
+    }
+
+    async fn find_bug(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<BugRow, AppError> {
+        if let Some(row) = repo::get_bug(&self.pool, story_id, organization_id).await? {
+            return Ok(row);
+        }
+        match self.get_story(story_id, organization_id).await? {
+            Some(_) => Err(AppError::BadRequest(
+                "Only bug stories have bug details".to_string(),
+            )),
+            None => Err(AppError::NotFound("Story not found".to_string())),
+        }
+    }
+
+    /// The organization's SLA targets, defaults where it set none
+    pub async fn get_bug_sla_policy(
+        &self,
+        organization_id: Option<Uuid>,
+    ) -> Result<SlaPolicy, AppError> {
+        let Some(organization_id) = organization_id else {
+            return Ok(SlaPolicy::default());
+        };
+        Ok(
+            repo::get_bug_sla_policies(&self.pool, Some(organization_id))
+                .await?
+                .remove(&organization_id)
+                .unwrap_or_default(),
+        )
+    }
+
+    pub async fn set_bug_sla_targets(
+        &self,
+        organization_id: Option<Uuid>,
+        severity: BugSeverity,
+        targets: SlaTargets,
+    ) -> Result<SlaPolicy, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest("SLA targets can only be set for an organization".to_string())
+        })?;
+        repo::upsert_bug_sla_targets(&self.pool, organization_id, severity, targets).await?;
+        self.get_bug_sla_policy(Some(organization_id)).await
+    }
+
+    /// SLA compliance of the project's bugs reported since `since`
+    pub async fn get_bug_sla_report(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<SlaComplianceReport, AppError> {
+        repo::get_project(&self.pool, project_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+
+        let bugs: Vec<_> = repo::get_project_bugs(&self.pool, project_id, organization_id, since)
+            .await?
+            .into_iter()
+            .map(|row| (row.reported_at, row.details()))
+            .collect();
+        let policy = self.get_bug_sla_policy(organization_id).await?;
+        Ok(SlaComplianceReport::compute(
+            &bugs,
+            &policy,
+            chrono::Utc::now(),
+        ))
+    }
+
+    /// Record SLA timers that ran out since the last check, across all
+    /// organizations, returning an alert for each
+    pub async fn check_bug_sla_breaches(&self) -> Result<Vec<TaskEvent>, AppError> {
+        let bugs = repo::get_bugs_awaiting_sla(&self.pool).await?;
+        if bugs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let policies = repo::get_bug_sla_policies(&self.pool, None).await?;
+        let default_policy = SlaPolicy::default();
+        let now = chrono::Utc::now();
+
+        let mut alerts = Vec::new();
+        for row in bugs {
+            let details = row.details();
+            let policy = row
+                .organization_id
+                .and_then(|organization_id| policies.get(&organization_id))
+                .unwrap_or(&default_policy);
+            let sla = BugSla::evaluate(row.reported_at, &details, policy, now);
+
+            for kind in [SlaTimerKind::Triage, SlaTimerKind::Fix] {
+                let timer = sla.timer(kind);
+                if timer.breached
+                    && repo::record_bug_sla_breach(&self.pool, row.story_id, kind, timer.due_at)
+                        .await?
+                {
+                    alerts.push(TaskEvent::BugSlaBreached {
+                        story_id: row.story_id,
+                        project_id: row.project_id,
+                        organization_id: row.organization_id,
+                        title: row.title.clone(),
+                        severity: details.severity,
+                        timer: kind,
+                        due_at: timer.due_at,
+                        timestamp: now,
+                    });
+                }
+            }
+        }
+        Ok(alerts)
+    }
+
+    /// Flag drafts untouched for `after_days` days as stale, after unflagging
+    /// those picked up again, and return an alert for each newly stale story.
+    /// `archive_after_days` is how long stale drafts are kept, if they are
+    /// archived at all.
+    pub async fn mark_stale_stories(
+        &self,
+        after_days: u32,
+        archive_after_days: Option<u32>,
+    ) -> Result<Vec<TaskEvent>, AppError> {
+        repo::clear_revived_stale_stories(&self.pool).await?;
+        let now = chrono::Utc::now();
+        let cutoff = stale_cutoff(now, after_days);
+        let mut alerts = Vec::new();
+        loop {
+            let marked = repo::mark_stale_stories(&self.pool, cutoff, STALE_BATCH_SIZE).await?;
+            alerts.extend(
+                marked
+                    .iter()
+                    .map(|story| story.alert(archive_after_days, now)),
+            );
+            if (marked.len() as i64) < STALE_BATCH_SIZE {
+                return Ok(alerts);
+            }
+        }
+    }
+
+    /// Archive drafts that stayed stale and untouched for `after_days` days,
+    /// in batches. Returns how many stories were archived.
+    pub async fn archive_stale_stories(&self, after_days: u32) -> Result<usize, AppError> {
+        let cutoff = stale_cutoff(chrono::Utc::now(), after_days);
+        let mut total = 0;
+        loop {
+            let archived =
+                repo::archive_stale_stories(&self.pool, cutoff, ARCHIVE_BATCH_SIZE).await?;
+            total += archived.len();
+            for (story_id, organization_id) in &archived {
+                self.publish(DomainEvent::Backlog(BacklogEvent::StoryArchived {
+                    story_id: *story_id,
+                    organization_id: *organization_id,
+                }))
+                .await;
+            }
+            if (archived.len() as i64) < ARCHIVE_BATCH_SIZE {
+                return Ok(total);
+            }
+        }
+    }
+
+    /// Move stories accepted more than `after_months` months ago to the
+    /// archive, in batches, and drop them from every projection. Returns how
+    /// many stories were archived.
+    pub async fn archive_accepted_stories(&self, after_months: u32) -> Result<usize, AppError> {
+        let cutoff = archive_cutoff(chrono::Utc::now(), after_months);
+        let mut total = 0;
+        loop {
+            let archived =
+                repo::archive_accepted_stories(&self.pool, cutoff, ARCHIVE_BATCH_SIZE).await?;
+            total += archived.len();
+            for (story_id, organization_id) in &archived {
+                self.publish(DomainEvent::Backlog(BacklogEvent::StoryArchived {
+                    story_id: *story_id,
+                    organization_id: *organization_id,
+                }))
+                .await;
+            }
+            if (archived.len() as i64) < ARCHIVE_BATCH_SIZE {
+                return Ok(total);
+            }
+        }
+    }
+
+    /// Queue an import of `content` into the project. The items are parsed
+    /// and stored up front, so a bad file fails here rather than in the
+    /// worker, and the organization's quota must fit every item.
+    pub async fn start_import(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        created_by: Option<Uuid>,
+        format: ImportFormat,
+        content: &str,
+    ) -> Result<ImportJob, AppError> {
+        repo::get_project(&self.pool, project_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+        let items = parse_import(format, content)?;
+        self.quota_guard
+            .ensure_capacity(organization_id, QuotaResource::Stories, items.len() as u64)
+            .await?;
+        let job = ImportJob::new(project_id, organization_id, created_by, format, items.len());
+        repo::create_import_job(&self.pool, &job, &items).await?;
+        Ok(job)
+    }
+
+    /// An import job with the first items that could not be imported
+    pub async fn get_import_job(
+        &self,
+        job_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<(ImportJob, Vec<ImportItemError>), AppError> {
+        let job = repo::get_import_job(&self.pool, job_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Import job not found".to_string()))?;
+        let errors =
+            repo::get_import_item_errors(&self.pool, job_id, MAX_REPORTED_IMPORT_ERRORS).await?;
+        Ok((job, errors))
+    }
+
+    /// Queue a failed import again. It carries on from the first item it had
+    /// not processed.
+    pub async fn resume_import(
+        &self,
+        job_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<ImportJob, AppError> {
+        if let Some(job) = repo::resume_import_job(&self.pool, job_id, organization_id).await? {
+            return Ok(job);
+        }
+        let job = repo::get_import_job(&self.pool, job_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Import job not found".to_string()))?;
+        Err(AppError::Conflict(format!(
+            "Only failed imports can be resumed; this one is {}",
+            job.status.as_str()
+        )))
+    }
+
+    /// Claim the next queued import and work through it. Returns the job as
+    /// it was left, or `None` when nothing was queued. A failed attempt puts
+    /// the job back in the queue until it runs out of attempts.
+    pub async fn run_next_import(
+        &self,
+        throttle: &ImportThrottle,
+    ) -> Result<Option<ImportJob>, AppError> {
+        let Some(mut job) = repo::claim_import_job(&self.pool, IMPORT_LEASE_SECONDS).await? else {
+            return Ok(None);
+        };
+
+        match self.process_import(&mut job, throttle).await {
+            Ok(()) => {
+                repo::complete_import_job(&self.pool, job.id).await?;
+                job.status = ImportJobStatus::Completed;
+                job.last_error = None;
+            }
+            // Another worker took over after our lease ran out; the job is theirs now
+            Err(AppError::Conflict(message)) => {
+                tracing::warn!(job_id = %job.id, %message, "Lost the lease on an import job");
+            }
+            Err(err) => {
+                let retry = job.can_retry();
+                let message = import_error_message(err);
+                tracing::warn!(job_id = %job.id, attempts = job.attempts, retry, error = %message, "Import attempt failed");
+                repo::release_import_job(&self.pool, job.id, &message, retry).await?;
+                job.status = if retry {
+                    ImportJobStatus::Queued
+                } else {
+                    ImportJobStatus::Failed
+                };
+                job.last_error = Some(message);
+            }
+        }
+        Ok(Some(job))
+    }
+
+    /// Create the job's remaining stories a chunk per transaction, each story
+    /// announced in its chunk's transaction and followed up before the next
+    async fn process_import(
+        &self,
+        job: &mut ImportJob,
+        throttle: &ImportThrottle,
+    ) -> Result<(), AppError> {
+        // Stories created before an earlier attempt stopped may not have been followed up
+        self.follow_up_imported_stories(job, throttle).await?;
+
+        while job.processed_items < job.total_items {
+            let items =
+                repo::get_import_items(&self.pool, job.id, job.processed_items, IMPORT_CHUNK_SIZE)
+                    .await?;
+            if items.is_empty() {
+                return Err(AppError::InternalServerError);
+            }
+
+            let mut stories = Vec::new();
+            let mut outcomes = Vec::with_capacity(items.len());
+            for (position, item) in items {
+                match Self::imported_story(job, item) {
+                    Ok(story) => {
+                        outcomes.push((position, Ok(story.id)));
+                        stories.push(story);
+                    }
+                    Err(err) => outcomes.push((position, Err(import_error_message(err)))),
+                }
+            }
+
+            let mut uow = UnitOfWork::begin(&self.pool).await?;
+            let result = async {
+                for story in &stories {
+                    repo::create_story_with_transaction(uow.tx(), story).await?;
+                    repo::record_story_revision_with_transaction(uow.tx(), story, job.created_by)
+                        .await?;
+                    uow.publish(DomainEvent::Backlog(BacklogEvent::StoryCreated {
+                        story: Self::story_record(story),
+                    }))
+                    .await?;
+                }
+                repo::record_import_chunk_with_transaction(
+                    uow.tx(),
+                    job.id,
+                    job.processed_items,
+                    &outcomes,
+                    IMPORT_LEASE_SECONDS,
+                )
+                .await
+            }
+            .await;
+            uow.finish(result).await?;
+
+            job.processed_items += outcomes.len();
+            job.created_items += stories.len();
+            job.failed_items += outcomes.len() - stories.len();
+
+            self.follow_up_imported_stories(job, throttle).await?;
+            tokio::time::sleep(throttle.chunk_pause).await;
+        }
+        Ok(())
+    }
+
+    fn imported_story(job: &ImportJob, item: ImportItem) -> Result<Story, AppError> {
+        let mut story = Story::new(
+            job.project_id,
+            job.organization_id,
+            item.title,
+            item.description,
+        )?;
+        for label in item.labels {
+            story.add_label(label);
+        }
+        story.set_story_type(item.story_type);
+        Ok(story)
+    }
+
+    /// Link the mentions of the stories created since the job last followed
+    /// up and report them to analytics, paced to the throttle's event rate. A
+    /// worker stopping part way through follows up some of them twice rather
+    /// than not at all.
+    async fn follow_up_imported_stories(
+        &self,
+        job: &mut ImportJob,
+        throttle: &ImportThrottle,
+    ) -> Result<(), AppError> {
+        if job.published_items >= job.processed_items {
+            return Ok(());
+        }
+        let story_ids = repo::get_imported_story_ids(
+            &self.pool,
+            job.id,
+            job.published_items,
+            job.processed_items,
+        )
+        .await?;
+        let stories = repo::get_stories_by_ids(&self.pool, &story_ids, job.organization_id).await?;
+
+        let mut pace = tokio::time::interval(throttle.event_interval());
+        for story in &stories {
+            pace.tick().await;
+            self.link_mentions(
+                ReferenceSourceType::Story,
+                story.id,
+                story.organization_id,
+                &[Some(&story.title), story.description.as_deref()],
+            )
+            .await;
+            self.analytics.emit(AnalyticsEvent::new(
+                STORY_CREATED,
+                story.organization_id,
+                Some(story.id),
+            ));
+        }
+
+        repo::mark_import_published(&self.pool, job.id, job.processed_items).await?;
+        job.published_items = job.processed_items;
+        Ok(())
+    }
+
+    pub async fn search_archive(
+        &self,
+        organization_id: Option<Uuid>,
+        search: &ArchiveSearch,
+    ) -> Result<Vec<ArchivedItem>, AppError> {
+        repo::search_archive(&self.pool, organization_id, search).await
+    }
 
     pub async fn get_task(
         &self,
@@ -369,6 +2483,13 @@ impl BacklogUsecases {
             acceptance_criteria_refs,
         )?;
         repo::create_task(&self.pool, &task).await?;
+        self.link_mentions(
+            ReferenceSourceType::Task,
+            task.id,
+            task.organization_id,
+            &[Some(&task.title), task.description.as_deref()],
+        )
+        .await;
         let record = Self::task_record(&task);
         self.publish(DomainEvent::Backlog(BacklogEvent::TaskCreated {
             task: record,
@@ -443,8 +2564,15 @@ impl BacklogUsecases {
             .await?
             .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
 
+        if task.owner_user_id.is_some_and(|owner| owner != user_id) {
+            return Err(already_claimed());
+        }
         task.take_ownership(user_id)?;
-        repo::update_task(&self.pool, &task).await?;
+        // Compare-and-swap so that only one of several simultaneous claims wins
+        if !repo::claim_task(&self.pool, &task).await? {
+            return Err(already_claimed());
+        }
+        repo::remove_task_claim(&self.pool, task_id, user_id).await?;
         let record = Self::task_record(&task);
         self.publish(DomainEvent::Backlog(BacklogEvent::TaskUpdated {
             task: record,
@@ -453,12 +2581,156 @@ impl BacklogUsecases {
         Ok(task)
     }
 
+    /// Wait in line for a task someone else owns. If it is released before the
+    /// entry expires, the first user in line is offered it.
+    pub async fn join_claim_queue(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+        user_id: Uuid,
+    ) -> Result<ClaimQueueEntry, AppError> {
+        let task = self
+            .get_task(task_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+
+        ensure_queueable(&task, user_id)?;
+        let expires_at = chrono::Utc::now() + claim_queue_window();
+        repo::enqueue_task_claim(&self.pool, task_id, user_id, organization_id, expires_at).await
+    }
+
+    pub async fn leave_claim_queue(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        self.get_task(task_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+
+        if !repo::remove_task_claim(&self.pool, task_id, user_id).await? {
+            return Err(AppError::NotFound(
+                "You are not in this task's claim queue".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The owner offers the task to `to_user_id`, who takes it over as it is
+    /// once they accept
+    pub async fn propose_task_transfer(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+        from_user_id: Uuid,
+        to_user_id: Uuid,
+        note: Option<String>,
+        handoff: bool,
+    ) -> Result<(Task, TaskTransfer), AppError> {
+        let task = self
+            .get_task(task_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+
+        let transfer = TaskTransfer::propose(&task, from_user_id, to_user_id, note, handoff)?;
+        if !repo::can_receive_tasks(&self.pool, to_user_id, task.organization_id).await? {
+            return Err(AppError::BadRequest(
+                "The recipient is not a member of this organization".to_string(),
+            ));
+        }
+        repo::create_task_transfer(&self.pool, &transfer).await?;
+        self.push(PushNotification::task_transfer(&task, &transfer));
+        Ok((task, transfer))
+    }
+
+    /// The recipient takes over the task with its status, estimate and history
+    pub async fn accept_task_transfer(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+        user_id: Uuid,
+    ) -> Result<(Task, TaskTransfer), AppError> {
+        let (mut task, mut transfer) = self.pending_task_transfer(task_id, organization_id).await?;
+        let from_user_id = transfer.from_user_id;
+        transfer.accept(&mut task, user_id)?;
+
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            if !repo::resolve_task_transfer_with_transaction(uow.tx(), &transfer).await? {
+                return Err(AppError::Conflict(
+                    "The transfer was already resolved".to_string(),
+                ));
+            }
+            if !repo::transfer_task_with_transaction(uow.tx(), &task, from_user_id).await? {
+                return Err(AppError::Conflict(
+                    "The task changed hands since the transfer was proposed".to_string(),
+                ));
+            }
+            uow.publish(DomainEvent::Backlog(BacklogEvent::TaskUpdated {
+                task: Self::task_record(&task),
+            }))
+            .await
+        }
+        .await;
+        uow.finish(result).await?;
+
+        repo::remove_task_claim(&self.pool, task_id, user_id).await?;
+        self.push(PushNotification::task_transfer(&task, &transfer));
+        Ok((task, transfer))
+    }
+
+    /// The recipient declines the pending transfer, or the owner withdraws it
+    pub async fn decline_task_transfer(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+        user_id: Uuid,
+    ) -> Result<(Task, TaskTransfer), AppError> {
+        let (task, mut transfer) = self.pending_task_transfer(task_id, organization_id).await?;
+        transfer.decline(user_id)?;
+        if !repo::resolve_task_transfer(&self.pool, &transfer).await? {
+            return Err(AppError::Conflict(
+                "The transfer was already resolved".to_string(),
+            ));
+        }
+        self.push(PushNotification::task_transfer(&task, &transfer));
+        Ok((task, transfer))
+    }
+
+    /// Every transfer proposed for the task, newest first
+    pub async fn get_task_transfers(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<TaskTransfer>, AppError> {
+        self.get_task(task_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+        repo::get_task_transfers(&self.pool, task_id).await
+    }
+
+    async fn pending_task_transfer(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<(Task, TaskTransfer), AppError> {
+        let task = self
+            .get_task(task_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+        let transfer = repo::get_pending_task_transfer(&self.pool, task_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task has no pending transfer".to_string()))?;
+        Ok((task, transfer))
+    }
+
     pub async fn release_task_ownership(
         &self,
         task_id: Uuid,
         organization_id: Option<Uuid>,
         user_id: Uuid,
-    ) -> Result<Task, AppError> {
+    ) -> Result<(Task, Option<Uuid>), AppError> {
         let mut task = self
             .get_task(task_id, organization_id)
             .await?
@@ -467,6 +2739,7 @@ impl BacklogUsecases {
         let previous_owner = task.owner_user_id;
         task.release_ownership(user_id)?;
         repo::update_task(&self.pool, &task).await?;
+        repo::cancel_pending_task_transfer(&self.pool, task_id).await?;
         let record = Self::task_record(&task);
         self.publish(DomainEvent::Backlog(BacklogEvent::TaskUpdated {
             task: record,
@@ -478,7 +2751,11 @@ impl BacklogUsecases {
         if let Some(prev_owner) = previous_owner {
             task_with_prev_owner.owner_user_id = Some(prev_owner);
         }
-        Ok(task_with_prev_owner)
+        let next_claimant = repo::pop_next_task_claimant(&self.pool, task_id).await?;
+        if let Some(next_user_id) = next_claimant {
+            self.push(PushNotification::task_assigned(&task, next_user_id));
+        }
+        Ok((task_with_prev_owner, next_claimant))
     }
 
     pub async fn start_task_work(
@@ -522,6 +2799,8 @@ impl BacklogUsecases {
         );
 
         task.complete(user_id)?;
+        self.ensure_completion_allowed(&task, organization_id)
+            .await?;
         repo::update_task(&self.pool, &task).await?;
         let record = Self::task_record(&task);
         self.publish(DomainEvent::Backlog(BacklogEvent::TaskUpdated {
@@ -531,6 +2810,149 @@ impl BacklogUsecases {
         Ok(())
     }
 
+    /// Apply the organization's completion policy to a task about to be
+    /// completed
+    async fn ensure_completion_allowed(
+        &self,
+        task: &Task,
+        organization_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        let policy = self.get_task_completion_policy(organization_id).await?;
+        if !policy.require_criteria_coverage {
+            return Ok(());
+        }
+        let verifications = repo::get_criterion_verifications(&self.pool, task.story_id).await?;
+        ensure_criteria_covered(task, &verifications)
+    }
+
+    pub async fn get_task_completion_policy(
+        &self,
+        organization_id: Option<Uuid>,
+    ) -> Result<TaskCompletionPolicy, AppError> {
+        match organization_id {
+            Some(organization_id) => {
+                repo::get_task_completion_policy(&self.pool, organization_id).await
+            }
+            None => Ok(TaskCompletionPolicy::default()),
+        }
+    }
+
+    pub async fn set_task_completion_policy(
+        &self,
+        organization_id: Option<Uuid>,
+        policy: TaskCompletionPolicy,
+    ) -> Result<TaskCompletionPolicy, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest(
+                "Task completion policies can only be set for an organization".to_string(),
+            )
+        })?;
+        repo::upsert_task_completion_policy(&self.pool, organization_id, policy).await?;
+        Ok(policy)
+    }
+
+    pub async fn get_reason_policy(
+        &self,
+        organization_id: Option<Uuid>,
+    ) -> Result<ReasonPolicy, AppError> {
+        match organization_id {
+            Some(organization_id) => repo::get_reason_policy(&self.pool, organization_id).await,
+            None => Ok(ReasonPolicy::default()),
+        }
+    }
+
+    pub async fn set_reason_policy(
+        &self,
+        organization_id: Option<Uuid>,
+        policy: ReasonPolicy,
+    ) -> Result<ReasonPolicy, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest("Reason policies can only be set for an organization".to_string())
+        })?;
+        repo::upsert_reason_policy(&self.pool, organization_id, &policy).await?;
+        repo::get_reason_policy(&self.pool, organization_id).await
+    }
+
+    /// The reason to record for `action`, failing when the organization
+    /// requires one and none was given
+    async fn require_reason(
+        &self,
+        organization_id: Option<Uuid>,
+        action: DestructiveAction,
+        reason: Option<&str>,
+    ) -> Result<Option<String>, AppError> {
+        self.get_reason_policy(organization_id)
+            .await?
+            .check(action, reason)
+    }
+
+    /// The key browsers subscribe to push with, or `None` when this deployment
+    /// has no VAPID keys
+    pub fn push_application_server_key(&self) -> Option<String> {
+        self.push_notifier
+            .as_ref()
+            .map(|notifier| notifier.application_server_key())
+    }
+
+    /// Store a browser's push subscription. A browser that subscribes again
+    /// replaces its previous subscription.
+    pub async fn subscribe_to_push(
+        &self,
+        subscription: PushSubscription,
+    ) -> Result<PushSubscription, AppError> {
+        repo::upsert_push_subscription(&self.pool, &subscription).await
+    }
+
+    pub async fn unsubscribe_from_push(
+        &self,
+        subscription_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        if !repo::delete_push_subscription(&self.pool, subscription_id, user_id).await? {
+            return Err(AppError::NotFound(
+                "Push subscription not found".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Record a test result or manual check of one of the story's criteria
+    pub async fn verify_acceptance_criterion(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        verification: CriterionVerification,
+    ) -> Result<CriterionVerification, AppError> {
+        let story = self
+            .get_story(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+        if !story
+            .acceptance_criteria
+            .iter()
+            .any(|criterion| criterion.id == verification.criterion_id)
+        {
+            return Err(AppError::NotFound(
+                "Acceptance criterion not found".to_string(),
+            ));
+        }
+        repo::record_criterion_verification(&self.pool, &verification, story.organization_id)
+            .await?;
+        Ok(verification)
+    }
+
+    /// Verifications of the story's criteria, newest first
+    pub async fn get_criterion_verifications(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<CriterionVerification>, AppError> {
+        self.get_story(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+        repo::get_criterion_verifications(&self.pool, story_id).await
+    }
+
     pub async fn update_task_status(
         &self,
         task_id: Uuid,
@@ -544,6 +2966,10 @@ impl BacklogUsecases {
             .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
 
         task.transition_to_status(status, user_id)?;
+        if task.status == TaskStatus::Completed {
+            self.ensure_completion_allowed(&task, organization_id)
+                .await?;
+        }
         repo::update_task(&self.pool, &task).await?;
         let record = Self::task_record(&task);
         self.publish(DomainEvent::Backlog(BacklogEvent::TaskUpdated {
@@ -554,6 +2980,123 @@ impl BacklogUsecases {
         Ok(task)
     }
 
+    /// Apply several status updates, each in its own transaction. One update
+    /// failing (not found, not the owner, invalid transition) does not affect
+    /// the others; results come back in request order with the previous status.
+    pub async fn batch_update_task_status(
+        &self,
+        organization_id: Option<Uuid>,
+        user_id: Uuid,
+        updates: Vec<TaskStatusUpdate>,
+    ) -> Result<Vec<(Uuid, Result<(Task, TaskStatus), AppError>)>, AppError> {
+        validate_task_batch(&updates)?;
+
+        let mut results = Vec::with_capacity(updates.len());
+        for update in updates {
+            let result = self
+                .apply_task_status_update(organization_id, user_id, &update)
+                .await;
+            results.push((update.task_id, result));
+        }
+        Ok(results)
+    }
+
+    /// Apply one update to several tasks in a single transaction: every task
+    /// changes or none does. Results come back in request order with each
+    /// task's previous status; only changed tasks are announced.
+    pub async fn bulk_update_tasks(
+        &self,
+        organization_id: Option<Uuid>,
+        user_id: Uuid,
+        update: TaskBulkUpdate,
+    ) -> Result<Vec<(Task, TaskStatus)>, AppError> {
+        let mut tasks = Vec::with_capacity(update.task_ids.len());
+        let mut changed = Vec::with_capacity(update.task_ids.len());
+        for task_id in &update.task_ids {
+            let mut task = self
+                .get_task(*task_id, organization_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Task {} not found", task_id)))?;
+            let old_status = task.status.clone();
+            let old_estimate = task.estimated_hours;
+
+            if let Some(status) = &update.status {
+                task.transition_to_status(status.clone(), user_id)
+                    .map_err(|e| bulk_item_error("Task", task.id, e))?;
+                if task.status == TaskStatus::Completed {
+                    self.ensure_completion_allowed(&task, organization_id)
+                        .await
+                        .map_err(|e| bulk_item_error("Task", task.id, e))?;
+                }
+            }
+            if let Some(hours) = update.estimated_hours {
+                task.set_estimated_hours(hours)
+                    .map_err(|e| bulk_item_error("Task", task.id, e))?;
+            }
+
+            changed.push(task.status != old_status || task.estimated_hours != old_estimate);
+            tasks.push((task, old_status));
+        }
+
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            for ((task, _), changed) in tasks.iter().zip(&changed) {
+                if *changed {
+                    repo::update_task_with_transaction(uow.tx(), task).await?;
+                    uow.publish(DomainEvent::Backlog(BacklogEvent::TaskUpdated {
+                        task: Self::task_record(task),
+                    }))
+                    .await?;
+                }
+            }
+            Ok(())
+        }
+        .await;
+        uow.finish(result).await?;
+        Ok(tasks)
+    }
+
+    async fn apply_task_status_update(
+        &self,
+        organization_id: Option<Uuid>,
+        user_id: Uuid,
+        update: &TaskStatusUpdate,
+    ) -> Result<(Task, TaskStatus), AppError> {
+        let mut task = self
+            .get_task(update.task_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+        let old_status = task.status.clone();
+
+        task.transition_to_status(update.status.clone(), user_id)?;
+        if task.status == TaskStatus::Completed {
+            self.ensure_completion_allowed(&task, organization_id)
+                .await?;
+        }
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            repo::update_task_with_transaction(uow.tx(), &task).await?;
+            if let Some(note) = &update.note {
+                repo::record_task_status_note_with_transaction(
+                    uow.tx(),
+                    &task,
+                    user_id,
+                    &old_status.to_string(),
+                    note,
+                )
+                .await?;
+            }
+            uow.publish(DomainEvent::Backlog(BacklogEvent::TaskUpdated {
+                task: Self::task_record(&task),
+            }))
+            .await
+        }
+        .await;
+        uow.finish(result).await?;
+
+        Ok((task, old_status))
+    }
+
     pub async fn set_task_estimate(
         &self,
         task_id: Uuid,
@@ -576,17 +3119,81 @@ impl BacklogUsecases {
         Ok(())
     }
 
-    pub async fn get_acceptance_criteria(
+    /// Split a task into smaller ones. Without `parts` the configured proposer is
+    /// asked for a split. Returns the superseded original and the new tasks.
+    pub async fn split_task(
         &self,
-        story_id: Uuid,
+        task_id: Uuid,
         organization_id: Option<Uuid>,
-    ) -> Result<Vec<AcceptanceCriteria>, AppError> {
-        let story = self
-            .get_story(story_id, organization_id)
+        parts: Option<Vec<TaskSplitPart>>,
+    ) -> Result<(Task, Vec<Task>), AppError> {
+        let mut task = self
+            .get_task(task_id, organization_id)
             .await?
-            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
 
-        Ok(story.acceptance_criteria)
+        let parts = match parts {
+            Some(parts) => parts,
+            None => {
+                let proposer = self.task_split_proposer.as_ref().ok_or_else(|| {
+                    AppError::BadRequest(
+                        "Automatic split proposals are not configured; provide the tasks to split into"
+                            .to_string(),
+                    )
+                })?;
+                let story = self
+                    .get_story(task.story_id, organization_id)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+                self.quota_guard.consume_llm_call(organization_id).await?;
+                let parts = proposer.propose_split(&story, &task).await?;
+                self.analytics.emit(AnalyticsEvent::new(
+                    FEATURE_TASK_SPLIT,
+                    organization_id,
+                    Some(task.id),
+                ));
+                parts
+            }
+        };
+
+        let children = task.split(parts)?;
+
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let result = async {
+            for child in &children {
+                repo::create_task_with_transaction(uow.tx(), child).await?;
+            }
+            repo::update_task_with_transaction(uow.tx(), &task).await?;
+
+            // Reload so the new tasks carry their short keys
+            let children =
+                repo::get_split_tasks_with_transaction(uow.tx(), task.id, organization_id).await?;
+            for child in &children {
+                uow.publish(DomainEvent::Backlog(BacklogEvent::TaskCreated {
+                    task: Self::task_record(child),
+                }))
+                .await?;
+            }
+            uow.publish(DomainEvent::Backlog(BacklogEvent::TaskUpdated {
+                task: Self::task_record(&task),
+            }))
+            .await?;
+            Ok(children)
+        }
+        .await;
+        let children = uow.finish(result).await?;
+
+        for child in &children {
+            self.link_mentions(
+                ReferenceSourceType::Task,
+                child.id,
+                child.organization_id,
+                &[Some(&child.title), child.description.as_deref()],
+            )
+            .await;
+        }
+
+        Ok((task, children))
     }
 
     pub async fn create_acceptance_criterion(
@@ -677,18 +3284,183 @@ impl BacklogUsecases {
         Ok(())
     }
 
+    /// Apply a batch of acceptance criteria changes with one write and one event,
+    /// returning the created IDs and the resulting ordered criteria
+    pub async fn apply_acceptance_criteria_batch(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        batch: AcceptanceCriteriaBatch,
+    ) -> Result<(Vec<Uuid>, Vec<AcceptanceCriteria>), AppError> {
+        let mut story = self
+            .get_story(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+
+        let created = story.apply_acceptance_criteria_batch(batch)?;
+        repo::update_story(&self.pool, &story).await?;
+        let record = Self::story_record(&story);
+        self.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
+            story: record,
+        }))
+        .await;
+        Ok((created, story.acceptance_criteria))
+    }
+
+    pub async fn reorder_acceptance_criteria(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        ordered_ids: Vec<Uuid>,
+    ) -> Result<Vec<AcceptanceCriteria>, AppError> {
+        let mut story = self
+            .get_story(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))?;
+
+        story.reorder_acceptance_criteria(&ordered_ids)?;
+        repo::update_story(&self.pool, &story).await?;
+        let record = Self::story_record(&story);
+        self.publish(DomainEvent::Backlog(BacklogEvent::StoryUpdated {
+            story: record,
+        }))
+        .await;
+        Ok(story.acceptance_criteria)
+    }
+
+    /// Per-member standup summary for a sprint: work completed since `since`, work in
+    /// progress, blocked items and new scope. `since` defaults to 24 hours ago.
+    pub async fn get_standup_summary(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        include_narrative: bool,
+    ) -> Result<StandupSummary, AppError> {
+        let now = chrono::Utc::now();
+        let since = since.unwrap_or_else(|| now - chrono::Duration::hours(24));
+        if since > now {
+            return Err(AppError::BadRequest(
+                "'since' cannot be in the future".to_string(),
+            ));
+        }
+
+        let sprint_name = repo::get_sprint_name(&self.pool, sprint_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Sprint not found".to_string()))?;
+        let stories = repo::get_stories_by_sprint(&self.pool, sprint_id, organization_id).await?;
+        let tasks = repo::get_tasks_by_sprint(&self.pool, sprint_id, organization_id).await?;
+
+        let mut summary = StandupSummary::build(sprint_id, sprint_name, since, &stories, &tasks);
+
+        if include_narrative {
+            if let Some(narrator) = &self.standup_narrator {
+                // The structured summary is still useful when the narrator is unavailable
+                // or the organization has used up its LLM calls
+                let narrative = match self.quota_guard.consume_llm_call(organization_id).await {
+                    Ok(()) => narrator.narrate(&summary, organization_id).await,
+                    Err(e) => Err(e),
+                };
+                match narrative {
+                    Ok(narrative) => {
+                        summary.narrative = Some(narrative);
+                        self.analytics.emit(AnalyticsEvent::new(
+                            FEATURE_STANDUP_NARRATIVE,
+                            organization_id,
+                            Some(sprint_id),
+                        ));
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, %sprint_id, "Standup narrative generation failed")
+                    }
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Mid-sprint risk report: burn against the ideal line, blocked tasks,
+    /// unstarted stories, scope churn and load across the team's contributors
+    pub async fn get_sprint_health(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<SprintHealth, AppError> {
+        let sprint = repo::get_sprint(&self.pool, sprint_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Sprint not found".to_string()))?;
+        let stories = repo::get_stories_by_sprint(&self.pool, sprint_id, organization_id).await?;
+        let tasks = repo::get_tasks_by_sprint(&self.pool, sprint_id, organization_id).await?;
+        let contributors = repo::get_team_contributors(&self.pool, sprint.team_id).await?;
+        let scope_changes = repo::get_sprint_scope_changes(&self.pool, sprint_id).await?;
+
+        let period = SprintPeriod {
+            sprint_id,
+            name: sprint.name,
+            start_date: sprint.start_date,
+            end_date: sprint.end_date,
+        };
+        Ok(SprintHealth::assess(
+            &period,
+            &stories,
+            &tasks,
+            &contributors,
+            &scope_changes,
+            chrono::Utc::now(),
+        ))
+    }
+
+    /// The user's saved task board swimlanes; one lane per story until they save their own
+    pub async fn get_board_configuration(
+        &self,
+        user_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<BoardConfiguration, AppError> {
+        Ok(
+            repo::get_board_configuration(&self.pool, user_id, organization_id)
+                .await?
+                .unwrap_or_else(|| {
+                    BoardConfiguration::new(user_id, organization_id, Swimlanes::default())
+                }),
+        )
+    }
+
+    pub async fn set_board_configuration(
+        &self,
+        user_id: Uuid,
+        organization_id: Option<Uuid>,
+        swimlanes: Swimlanes,
+    ) -> Result<BoardConfiguration, AppError> {
+        let configuration = BoardConfiguration::new(user_id, organization_id, swimlanes);
+        repo::upsert_board_configuration(&self.pool, &configuration).await?;
+        Ok(configuration)
+    }
+
+    /// The sprint's tasks split into swimlanes: the requested ones, else those
+    /// `user_id` saved, else one lane per story
     pub async fn get_sprint_task_board(
         &self,
         sprint_id: Uuid,
         organization_id: Option<Uuid>,
         status_filter: Option<String>,
-        group_by: Option<String>,
+        swimlanes: Option<Swimlanes>,
+        user_id: Option<Uuid>,
     ) -> Result<crate::adapters::http::handlers::SprintTaskBoardResponse, AppError> {
         use crate::adapters::http::handlers::{
             SprintMetadata, SprintTaskBoardResponse, SprintTaskView,
         };
         use chrono::Utc;
-        use std::collections::HashMap;
+
+        let swimlanes = match (swimlanes, user_id) {
+            (Some(swimlanes), _) => swimlanes,
+            (None, Some(user_id)) => {
+                self.get_board_configuration(user_id, organization_id)
+                    .await?
+                    .swimlanes
+            }
+            (None, None) => Swimlanes::default(),
+        };
 
         // Get sprint metadata
         let sprint_row = sqlx::query!(
@@ -708,15 +3480,26 @@ impl BacklogUsecases {
         })?
         .ok_or_else(|| AppError::NotFound("Sprint not found".to_string()))?;
 
-        // Calculate days remaining
-        let now = Utc::now();
-        let days_remaining = (sprint_row.end_date - now).num_days().max(0);
+        // Calculate days remaining in the team's local calendar days
+        let calendar = repo::get_sprint_calendar(self.pool.as_ref(), sprint_id).await?;
+        let days_remaining = calendar
+            .days_remaining(sprint_row.end_date, Utc::now())
+            .max(0);
+
+        #[derive(sqlx::FromRow)]
+        struct StoryRowData {
+            id: Uuid,
+            title: String,
+            labels: Vec<String>,
+            story_type: String,
+            story_points: Option<i32>,
+        }
 
         // Get all stories in the sprint with organization filter
-        let stories_query: Vec<(Uuid, String)> = if let Some(org_id) = organization_id {
-            sqlx::query_as::<_, (Uuid, String)>(
+        let stories_query: Vec<StoryRowData> = if let Some(org_id) = organization_id {
+            sqlx::query_as::<_, StoryRowData>(
                 r#"
-                SELECT id, title
+                SELECT id, title, labels, story_type, story_points
                 FROM stories
                 WHERE sprint_id = $1 AND organization_id = $2
                 "#,
@@ -730,9 +3513,9 @@ impl BacklogUsecases {
                 AppError::InternalServerError
             })?
         } else {
-            sqlx::query_as::<_, (Uuid, String)>(
+            sqlx::query_as::<_, StoryRowData>(
                 r#"
-                SELECT id, title
+                SELECT id, title, labels, story_type, story_points
                 FROM stories
                 WHERE sprint_id = $1
                 "#,
@@ -746,8 +3529,20 @@ impl BacklogUsecases {
             })?
         };
 
-        let total_stories = stories_query.len() as i64;
-        let story_map: HashMap<Uuid, String> = stories_query.into_iter().collect();
+        let story_map: HashMap<Uuid, LaneStory> = stories_query
+            .into_iter()
+            .map(|row| {
+                (
+                    row.id,
+                    LaneStory {
+                        title: row.title,
+                        labels: row.labels,
+                        story_type: StoryType::parse(&row.story_type).unwrap_or_default(),
+                        story_points: row.story_points.map(|points| points as u32),
+                    },
+                )
+            })
+            .collect();
 
         if story_map.is_empty() {
             // No stories in sprint - return empty response
@@ -764,9 +3559,18 @@ impl BacklogUsecases {
                     total_tasks: 0,
                     completed_tasks: 0,
                     progress_percentage: 0.0,
+                    total_points: 0,
+                    completed_points: 0,
+                    stats_freshness: StatsFreshness {
+                        source: StatsSource::Computed,
+                        refreshed_at: Utc::now(),
+                        age_seconds: 0,
+                    },
                 },
                 tasks: vec![],
                 groups: serde_json::json!({}),
+                swimlanes,
+                lanes: vec![],
             });
         }
 
@@ -814,7 +3618,7 @@ impl BacklogUsecases {
                        owner_user_id, acceptance_criteria_refs,
                        estimated_hours, created_at, updated_at
                 FROM tasks
-                WHERE story_id = ANY($1)
+                WHERE story_id = ANY($1) AND status <> 'superseded'
                 ORDER BY story_id, created_at
                 "#,
             )
@@ -829,16 +3633,11 @@ impl BacklogUsecases {
 
         // Build task views
         let mut tasks: Vec<SprintTaskView> = Vec::new();
-        let mut completed_tasks = 0i64;
 
         for row in tasks_rows {
-            if row.status == "completed" {
-                completed_tasks += 1;
-            }
-
             let story_title = story_map
                 .get(&row.story_id)
-                .cloned()
+                .map(|story| story.title.clone())
                 .unwrap_or_else(|| "Unknown Story".to_string());
 
             tasks.push(SprintTaskView {
@@ -856,69 +3655,34 @@ impl BacklogUsecases {
             });
         }
 
-        let total_tasks = tasks.len() as i64;
-        let progress_percentage = if total_tasks > 0 {
-            (completed_tasks as f64 / total_tasks as f64) * 100.0
-        } else {
-            0.0
-        };
-
-        // Build groups based on group_by parameter
-        let groups = match group_by.as_deref() {
-            Some("status") => {
-                let mut status_groups: HashMap<String, serde_json::Value> = HashMap::new();
-
-                for task in &tasks {
-                    let entry = status_groups.entry(task.status.clone()).or_insert_with(|| {
-                        serde_json::json!({
-                            "count": 0,
-                            "tasks": Vec::<Uuid>::new()
-                        })
-                    });
-
-                    if let Some(obj) = entry.as_object_mut() {
-                        if let Some(count) = obj.get_mut("count") {
-                            *count = serde_json::json!(count.as_i64().unwrap_or(0) + 1);
-                        }
-                        if let Some(task_list) = obj.get_mut("tasks") {
-                            if let Some(arr) = task_list.as_array_mut() {
-                                arr.push(serde_json::json!(task.id.to_string()));
-                            }
-                        }
-                    }
-                }
+        // Totals cover the whole sprint, whatever the status filter
+        let (stats, stats_freshness) = self.get_sprint_stats(sprint_id).await?;
 
-                serde_json::to_value(status_groups).unwrap_or_else(|_| serde_json::json!({}))
-            }
-            _ => {
-                // Default to grouping by story
-                let mut story_groups: HashMap<String, serde_json::Value> = HashMap::new();
-
-                for task in &tasks {
-                    let entry = story_groups
-                        .entry(task.story_id.to_string())
-                        .or_insert_with(|| {
-                            serde_json::json!({
-                                "count": 0,
-                                "tasks": Vec::<Uuid>::new()
-                            })
-                        });
-
-                    if let Some(obj) = entry.as_object_mut() {
-                        if let Some(count) = obj.get_mut("count") {
-                            *count = serde_json::json!(count.as_i64().unwrap_or(0) + 1);
-                        }
-                        if let Some(task_list) = obj.get_mut("tasks") {
-                            if let Some(arr) = task_list.as_array_mut() {
-                                arr.push(serde_json::json!(task.id.to_string()));
-                            }
-                        }
-                    }
-                }
+        let lane_tasks: Vec<LaneTask> = tasks
+            .iter()
+            .map(|task| LaneTask {
+                id: task.id,
+                story_id: task.story_id,
+                status: task.status.clone(),
+                owner_user_id: task.owner_user_id,
+            })
+            .collect();
+        let lanes = swimlanes.lanes(&lane_tasks, &story_map);
 
-                serde_json::to_value(story_groups).unwrap_or_else(|_| serde_json::json!({}))
-            }
-        };
+        // `groups` keeps the keyed form board clients read before lanes were ordered
+        let groups: serde_json::Map<String, serde_json::Value> = lanes
+            .iter()
+            .map(|lane| {
+                (
+                    lane.key.clone().unwrap_or_else(|| "none".to_string()),
+                    serde_json::json!({
+                        "count": lane.task_count,
+                        "points": lane.story_points,
+                        "tasks": lane.task_ids,
+                    }),
+                )
+            })
+            .collect();
 
         Ok(SprintTaskBoardResponse {
             sprint: SprintMetadata {
@@ -929,13 +3693,62 @@ impl BacklogUsecases {
                 end_date: sprint_row.end_date,
                 days_remaining,
                 status: sprint_row.status,
-                total_stories,
-                total_tasks,
-                completed_tasks,
-                progress_percentage,
+                total_stories: stats.total_stories,
+                total_tasks: stats.total_tasks,
+                completed_tasks: stats.completed_tasks,
+                progress_percentage: stats.progress_percentage(),
+                total_points: stats.total_points,
+                completed_points: stats.completed_points,
+                stats_freshness,
             },
             tasks,
-            groups,
+            groups: serde_json::Value::Object(groups),
+            swimlanes,
+            lanes,
         })
     }
+
+    /// The sprint's board totals from its background snapshot, recomputed
+    /// here when the snapshot is missing or older than the maximum age
+    pub async fn get_sprint_stats(
+        &self,
+        sprint_id: Uuid,
+    ) -> Result<(SprintStatSnapshot, StatsFreshness), AppError> {
+        let now = chrono::Utc::now();
+        if let Some(snapshot) = repo::get_sprint_stat_snapshot(&self.pool, sprint_id).await? {
+            if snapshot.is_fresh(now) {
+                let freshness = StatsFreshness::new(StatsSource::Snapshot, &snapshot, now);
+                return Ok((snapshot, freshness));
+            }
+        }
+
+        let snapshot = repo::refresh_sprint_stat_snapshot(&self.pool, sprint_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Sprint not found".to_string()))?;
+        let freshness = StatsFreshness::new(StatsSource::Computed, &snapshot, now);
+        Ok((snapshot, freshness))
+    }
+}
+
+/// What went wrong, without the error kind prefix users need not see
+fn import_error_message(err: AppError) -> String {
+    match err {
+        AppError::BadRequest(message) | AppError::NotFound(message) => message,
+        err => err.to_string(),
+    }
+}
+
+/// Name the story or task a bulk update failed on, so the caller can tell which
+/// item stopped the batch
+fn bulk_item_error(kind: &str, id: Uuid, err: AppError) -> AppError {
+    match err {
+        AppError::BadRequest(message) => {
+            AppError::BadRequest(format!("{} {}: {}", kind, id, message))
+        }
+        AppError::Conflict(message) => AppError::Conflict(format!("{} {}: {}", kind, id, message)),
+        AppError::Forbidden(message) => {
+            AppError::Forbidden(format!("{} {}: {}", kind, id, message))
+        }
+        err => err,
+    }
 }
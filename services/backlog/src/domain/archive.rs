@@ -0,0 +1,135 @@
+use chrono::{DateTime, Months, Utc};
+use common::AppError;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Accepted stories untouched for this many months move to the archive
+pub const DEFAULT_ARCHIVE_AFTER_MONTHS: u32 = 12;
+const DEFAULT_SEARCH_LIMIT: i64 = 20;
+const MAX_SEARCH_LIMIT: i64 = 100;
+
+/// Accepted stories last updated before this are due for the archive
+pub fn archive_cutoff(now: DateTime<Utc>, after_months: u32) -> DateTime<Utc> {
+    now.checked_sub_months(Months::new(after_months))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchivedItemKind {
+    Story,
+    Task,
+}
+
+impl ArchivedItemKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Story => "story",
+            Self::Task => "task",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "story" | "stories" => Some(Self::Story),
+            "task" | "tasks" => Some(Self::Task),
+            _ => None,
+        }
+    }
+}
+
+/// A search of the organization's archived stories and tasks. Without a
+/// query, the most recently archived items come first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveSearch {
+    pub query: Option<String>,
+    pub kind: Option<ArchivedItemKind>,
+    pub project_id: Option<Uuid>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl ArchiveSearch {
+    pub fn new(
+        query: Option<String>,
+        kind: Option<ArchivedItemKind>,
+        project_id: Option<Uuid>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<Self, AppError> {
+        let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+        if !(1..=MAX_SEARCH_LIMIT).contains(&limit) {
+            return Err(AppError::BadRequest(format!(
+                "limit must be between 1 and {}",
+                MAX_SEARCH_LIMIT
+            )));
+        }
+        let offset = offset.unwrap_or(0);
+        if offset < 0 {
+            return Err(AppError::BadRequest(
+                "offset must not be negative".to_string(),
+            ));
+        }
+        Ok(Self {
+            query: query
+                .map(|query| query.trim().to_string())
+                .filter(|query| !query.is_empty()),
+            kind,
+            project_id,
+            limit,
+            offset,
+        })
+    }
+
+    pub fn includes(&self, kind: ArchivedItemKind) -> bool {
+        self.kind.is_none_or(|wanted| wanted == kind)
+    }
+}
+
+/// An archived story or task matching a search
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedItem {
+    pub kind: ArchivedItemKind,
+    pub id: Uuid,
+    /// The story itself, or the task's story
+    pub story_id: Uuid,
+    pub project_id: Uuid,
+    pub short_key: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub archived_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_cutoff_counts_calendar_months() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 31, 12, 0, 0).unwrap();
+        assert_eq!(
+            archive_cutoff(now, 1),
+            Utc.with_ymd_and_hms(2025, 2, 28, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            archive_cutoff(now, 12),
+            Utc.with_ymd_and_hms(2024, 3, 31, 12, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_search_validates_paging_and_ignores_blank_queries() {
+        let search = ArchiveSearch::new(Some("  ".to_string()), None, None, None, None).unwrap();
+        assert_eq!((search.query, search.limit, search.offset), (None, 20, 0));
+        assert!(ArchiveSearch::new(None, None, None, Some(101), None).is_err());
+        assert!(ArchiveSearch::new(None, None, None, None, Some(-1)).is_err());
+
+        let tasks =
+            ArchiveSearch::new(None, Some(ArchivedItemKind::Task), None, None, None).unwrap();
+        assert!(tasks.includes(ArchivedItemKind::Task));
+        assert!(!tasks.includes(ArchivedItemKind::Story));
+    }
+}
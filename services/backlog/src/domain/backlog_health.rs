@@ -0,0 +1,329 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::domain::{Story, StoryStatus};
+
+/// Average Draft age at or under which the draft-age sub-score is perfect
+pub const DRAFT_AGE_TARGET_DAYS: f64 = 14.0;
+/// Average Draft age at which the draft-age sub-score bottoms out
+pub const DRAFT_AGE_LIMIT_DAYS: f64 = 90.0;
+
+/// Each dimension scored 0-100, higher is healthier
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BacklogHealthSubScores {
+    pub readiness: u8,
+    pub draft_age: u8,
+    pub estimation: u8,
+    pub acceptance_criteria: u8,
+    pub duplicates: u8,
+    pub dependencies: u8,
+}
+
+impl BacklogHealthSubScores {
+    /// Unweighted mean of the sub-scores
+    pub fn composite(&self) -> u8 {
+        let total = self.readiness as u32
+            + self.draft_age as u32
+            + self.estimation as u32
+            + self.acceptance_criteria as u32
+            + self.duplicates as u32
+            + self.dependencies as u32;
+        ((total as f64) / 6.0).round() as u8
+    }
+}
+
+/// The raw measurements behind the sub-scores
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BacklogHealthMetrics {
+    /// Stories in refinement: draft, needs refinement or ready
+    pub backlog_stories: usize,
+    pub ready_percent: f64,
+    /// `None` when there are no Draft stories
+    pub average_draft_age_days: Option<f64>,
+    pub estimation_coverage_percent: f64,
+    pub acceptance_criteria_coverage_percent: f64,
+    pub duplicate_rate_percent: f64,
+    /// Stories sharing a title once case and punctuation are ignored
+    pub duplicate_groups: Vec<Vec<Uuid>>,
+    /// Stories that mention each other in a cycle, so none can be finished first
+    pub dependency_tangles: Vec<Vec<Uuid>>,
+}
+
+/// Composite health of a project's backlog. `score` and `sub_scores` are
+/// `None` when nothing is in refinement.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BacklogHealth {
+    pub score: Option<u8>,
+    pub sub_scores: Option<BacklogHealthSubScores>,
+    pub metrics: BacklogHealthMetrics,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// One day's recorded score, for trends
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BacklogHealthSnapshot {
+    pub date: NaiveDate,
+    pub score: u8,
+    pub sub_scores: BacklogHealthSubScores,
+}
+
+impl BacklogHealth {
+    /// Score the stories in refinement. `mentions` are story-to-story links
+    /// (source, target) from short-key mentions, used as dependencies.
+    pub fn compute(stories: &[Story], mentions: &[(Uuid, Uuid)], now: DateTime<Utc>) -> Self {
+        let backlog: Vec<&Story> = stories
+            .iter()
+            .filter(|story| {
+                matches!(
+                    story.status,
+                    StoryStatus::Draft | StoryStatus::NeedsRefinement | StoryStatus::Ready
+                )
+            })
+            .collect();
+        let total = backlog.len();
+        let share = |count: usize| -> f64 {
+            if total == 0 {
+                0.0
+            } else {
+                round1(count as f64 * 100.0 / total as f64)
+            }
+        };
+
+        let ready = backlog
+            .iter()
+            .filter(|story| story.status == StoryStatus::Ready)
+            .count();
+        let estimated = backlog
+            .iter()
+            .filter(|story| story.story_points.is_some())
+            .count();
+        let with_criteria = backlog
+            .iter()
+            .filter(|story| !story.acceptance_criteria.is_empty())
+            .count();
+
+        let draft_ages: Vec<f64> = backlog
+            .iter()
+            .filter(|story| story.status == StoryStatus::Draft)
+            .map(|story| (now - story.created_at).num_seconds().max(0) as f64 / 86_400.0)
+            .collect();
+        let average_draft_age_days = (!draft_ages.is_empty())
+            .then(|| round1(draft_ages.iter().sum::<f64>() / draft_ages.len() as f64));
+
+        let duplicate_groups = duplicate_groups(&backlog);
+        let duplicated = duplicate_groups.iter().map(Vec::len).sum();
+        let backlog_ids: HashSet<Uuid> = backlog.iter().map(|story| story.id).collect();
+        let dependency_tangles = mention_cycles(&backlog_ids, mentions);
+        let tangled = dependency_tangles.iter().map(Vec::len).sum();
+
+        let metrics = BacklogHealthMetrics {
+            backlog_stories: total,
+            ready_percent: share(ready),
+            average_draft_age_days,
+            estimation_coverage_percent: share(estimated),
+            acceptance_criteria_coverage_percent: share(with_criteria),
+            duplicate_rate_percent: share(duplicated),
+            duplicate_groups,
+            dependency_tangles,
+        };
+
+        let sub_scores = (total > 0).then(|| BacklogHealthSubScores {
+            readiness: to_score(metrics.ready_percent),
+            draft_age: draft_age_score(metrics.average_draft_age_days),
+            estimation: to_score(metrics.estimation_coverage_percent),
+            acceptance_criteria: to_score(metrics.acceptance_criteria_coverage_percent),
+            duplicates: to_score(100.0 - metrics.duplicate_rate_percent),
+            dependencies: to_score(100.0 - share(tangled)),
+        });
+
+        Self {
+            score: sub_scores.as_ref().map(BacklogHealthSubScores::composite),
+            sub_scores,
+            metrics,
+            computed_at: now,
+        }
+    }
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn to_score(percent: f64) -> u8 {
+    percent.round().clamp(0.0, 100.0) as u8
+}
+
+fn draft_age_score(average_days: Option<f64>) -> u8 {
+    match average_days {
+        None => 100,
+        Some(days) if days <= DRAFT_AGE_TARGET_DAYS => 100,
+        Some(days) => to_score(
+            100.0 * (DRAFT_AGE_LIMIT_DAYS - days) / (DRAFT_AGE_LIMIT_DAYS - DRAFT_AGE_TARGET_DAYS),
+        ),
+    }
+}
+
+fn normalized_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn duplicate_groups(backlog: &[&Story]) -> Vec<Vec<Uuid>> {
+    let mut by_title: HashMap<String, Vec<Uuid>> = HashMap::new();
+    for story in backlog {
+        by_title
+            .entry(normalized_title(&story.title))
+            .or_default()
+            .push(story.id);
+    }
+    let mut groups: Vec<Vec<Uuid>> = by_title.into_values().filter(|ids| ids.len() > 1).collect();
+    groups.sort();
+    groups
+}
+
+/// Strongly connected groups of two or more stories in the mention graph
+pub(crate) fn mention_cycles(stories: &HashSet<Uuid>, mentions: &[(Uuid, Uuid)]) -> Vec<Vec<Uuid>> {
+    let mut forward: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    let mut backward: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for (source, target) in mentions {
+        if source != target && stories.contains(source) && stories.contains(target) {
+            forward.entry(*source).or_default().push(*target);
+            backward.entry(*target).or_default().push(*source);
+        }
+    }
+
+    // Kosaraju: order nodes by DFS finish time, then collect components on the
+    // reversed graph in reverse finish order
+    let mut nodes: Vec<Uuid> = forward.keys().copied().collect();
+    nodes.sort();
+    let mut visited: HashSet<Uuid> = HashSet::new();
+    let mut finished: Vec<Uuid> = Vec::new();
+    for start in &nodes {
+        if !visited.insert(*start) {
+            continue;
+        }
+        let mut stack = vec![(*start, 0usize)];
+        while let Some((node, next)) = stack.pop() {
+            let neighbours = forward.get(&node).map(Vec::as_slice).unwrap_or_default();
+            if let Some(neighbour) = neighbours.get(next) {
+                stack.push((node, next + 1));
+                if visited.insert(*neighbour) {
+                    stack.push((*neighbour, 0));
+                }
+            } else {
+                finished.push(node);
+            }
+        }
+    }
+
+    let mut assigned: HashSet<Uuid> = HashSet::new();
+    let mut cycles = Vec::new();
+    for start in finished.iter().rev() {
+        if !assigned.insert(*start) {
+            continue;
+        }
+        let mut component = vec![*start];
+        let mut stack = vec![*start];
+        while let Some(node) = stack.pop() {
+            for neighbour in backward.get(&node).map(Vec::as_slice).unwrap_or_default() {
+                if assigned.insert(*neighbour) {
+                    component.push(*neighbour);
+                    stack.push(*neighbour);
+                }
+            }
+        }
+        if component.len() > 1 {
+            component.sort();
+            cycles.push(component);
+        }
+    }
+    cycles.sort();
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::AcceptanceCriteria;
+    use chrono::Duration;
+
+    fn story(title: &str, status: StoryStatus, age_days: i64, now: DateTime<Utc>) -> Story {
+        let mut story = Story::new(Uuid::new_v4(), None, title.to_string(), None).unwrap();
+        story.status = status;
+        story.created_at = now - Duration::days(age_days);
+        story
+    }
+
+    #[test]
+    fn test_empty_backlog_has_no_score() {
+        let now = Utc::now();
+        let done = story("Shipped", StoryStatus::Accepted, 3, now);
+        let health = BacklogHealth::compute(&[done], &[], now);
+        assert_eq!(health.score, None);
+        assert_eq!(health.metrics.backlog_stories, 0);
+    }
+
+    #[test]
+    fn test_sub_scores_reflect_backlog() {
+        let now = Utc::now();
+        let mut ready = story("Export CSV", StoryStatus::Ready, 5, now);
+        ready.story_points = Some(3);
+        ready.acceptance_criteria.push(
+            AcceptanceCriteria::new(
+                "export".to_string(),
+                "a report".to_string(),
+                "I export".to_string(),
+                "a CSV downloads".to_string(),
+            )
+            .unwrap(),
+        );
+        let old_draft = story("Login page", StoryStatus::Draft, 52, now);
+        let duplicate = story("login  page!", StoryStatus::NeedsRefinement, 1, now);
+        let fresh_draft = story("Dark mode", StoryStatus::Draft, 0, now);
+        let in_progress = story("Search", StoryStatus::InProgress, 100, now);
+        let stories = vec![ready, old_draft, duplicate, fresh_draft, in_progress];
+
+        let health = BacklogHealth::compute(&stories, &[], now);
+        let metrics = &health.metrics;
+        assert_eq!(metrics.backlog_stories, 4);
+        assert_eq!(metrics.ready_percent, 25.0);
+        assert_eq!(metrics.average_draft_age_days, Some(26.0));
+        assert_eq!(metrics.estimation_coverage_percent, 25.0);
+        assert_eq!(metrics.duplicate_rate_percent, 50.0);
+        assert_eq!(metrics.duplicate_groups.len(), 1);
+
+        let sub_scores = health.sub_scores.unwrap();
+        // 26 days sits 12 days into the 14..90 day ramp
+        assert_eq!(sub_scores.draft_age, 84);
+        assert_eq!(sub_scores.duplicates, 50);
+        assert_eq!(sub_scores.dependencies, 100);
+        assert_eq!(health.score, Some(sub_scores.composite()));
+    }
+
+    #[test]
+    fn test_mention_cycles_are_tangles() {
+        let now = Utc::now();
+        let stories: Vec<Story> = (0..4)
+            .map(|i| story(&format!("Story {}", i), StoryStatus::Draft, 0, now))
+            .collect();
+        let [a, b, c, d] = [stories[0].id, stories[1].id, stories[2].id, stories[3].id];
+        // a -> b -> c -> a is a tangle; c -> d is an ordinary dependency
+        let mentions = vec![(a, b), (b, c), (c, a), (c, d), (d, d)];
+
+        let health = BacklogHealth::compute(&stories, &mentions, now);
+        let mut expected = vec![a, b, c];
+        expected.sort();
+        assert_eq!(health.metrics.dependency_tangles, vec![expected]);
+        assert_eq!(health.sub_scores.unwrap().dependencies, 25);
+    }
+}
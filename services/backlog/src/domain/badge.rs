@@ -0,0 +1,119 @@
+use common::AppError;
+
+use crate::domain::StoryStatus;
+
+/// What a project health badge reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeMetric {
+    /// Share of stories in refinement (draft, needs refinement, ready) that are ready
+    Ready,
+    /// Share of tasks in the active sprint that are completed
+    Sprint,
+}
+
+impl BadgeMetric {
+    pub fn parse(raw: Option<&str>) -> Result<Self, AppError> {
+        match raw.map(str::trim) {
+            None | Some("") | Some("ready") => Ok(Self::Ready),
+            Some("sprint") => Ok(Self::Sprint),
+            Some(other) => Err(AppError::BadRequest(format!(
+                "Unsupported badge metric '{}'; expected 'ready' or 'sprint'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Label/message pair shown on a badge, with the percentage used to pick a colour
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadgeSummary {
+    pub label: String,
+    pub message: String,
+    pub percent: Option<u8>,
+}
+
+impl BadgeSummary {
+    pub fn readiness(status_counts: &[(StoryStatus, i64)]) -> Self {
+        let count = |wanted: &[StoryStatus]| -> i64 {
+            status_counts
+                .iter()
+                .filter(|(status, _)| wanted.contains(status))
+                .map(|(_, count)| count)
+                .sum()
+        };
+        let ready = count(&[StoryStatus::Ready]);
+        let in_refinement = count(&[
+            StoryStatus::Draft,
+            StoryStatus::NeedsRefinement,
+            StoryStatus::Ready,
+        ]);
+
+        match percent(ready, in_refinement) {
+            Some(pct) => Self {
+                label: "readiness".to_string(),
+                message: format!("{}% ready", pct),
+                percent: Some(pct),
+            },
+            None => Self {
+                label: "readiness".to_string(),
+                message: "no backlog".to_string(),
+                percent: None,
+            },
+        }
+    }
+
+    pub fn sprint(progress: Option<(i64, i64)>) -> Self {
+        let label = "sprint".to_string();
+        match progress {
+            None => Self {
+                label,
+                message: "no active sprint".to_string(),
+                percent: None,
+            },
+            Some((completed, total)) => match percent(completed, total) {
+                Some(pct) => Self {
+                    label,
+                    message: format!("{}% done", pct),
+                    percent: Some(pct),
+                },
+                None => Self {
+                    label,
+                    message: "no tasks".to_string(),
+                    percent: None,
+                },
+            },
+        }
+    }
+}
+
+fn percent(part: i64, whole: i64) -> Option<u8> {
+    if whole <= 0 {
+        return None;
+    }
+    Some(((part.clamp(0, whole) * 100) / whole) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readiness_ignores_stories_past_refinement() {
+        let summary = BadgeSummary::readiness(&[
+            (StoryStatus::Draft, 1),
+            (StoryStatus::Ready, 3),
+            (StoryStatus::InProgress, 10),
+        ]);
+        assert_eq!(summary.percent, Some(75));
+        assert_eq!(summary.message, "75% ready");
+
+        assert_eq!(BadgeSummary::readiness(&[]).percent, None);
+    }
+
+    #[test]
+    fn test_sprint_progress() {
+        assert_eq!(BadgeSummary::sprint(Some((1, 4))).message, "25% done");
+        assert_eq!(BadgeSummary::sprint(Some((0, 0))).message, "no tasks");
+        assert_eq!(BadgeSummary::sprint(None).message, "no active sprint");
+    }
+}
@@ -0,0 +1,442 @@
+use chrono::{DateTime, Duration, Utc};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+
+const MAX_ENVIRONMENT_LENGTH: usize = 100;
+/// SLA targets are capped at a year
+pub const MAX_SLA_TARGET_HOURS: u32 = 24 * 365;
+
+/// How badly a bug hurts, as assessed when it is reported
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum BugSeverity {
+    Critical,
+    High,
+    #[default]
+    Medium,
+    Low,
+}
+
+impl BugSeverity {
+    pub const ALL: [BugSeverity; 4] = [Self::Critical, Self::High, Self::Medium, Self::Low];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::High => "high",
+            Self::Medium => "medium",
+            Self::Low => "low",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|severity| severity.as_str() == value.trim().to_lowercase())
+    }
+}
+
+/// How soon the team will fix a bug, decided when it is triaged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BugPriority {
+    Urgent,
+    High,
+    Normal,
+    Low,
+}
+
+impl BugPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Urgent => "urgent",
+            Self::High => "high",
+            Self::Normal => "normal",
+            Self::Low => "low",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "urgent" => Some(Self::Urgent),
+            "high" => Some(Self::High),
+            "normal" => Some(Self::Normal),
+            "low" => Some(Self::Low),
+            _ => None,
+        }
+    }
+}
+
+/// Bug-only fields of a story. A bug is triaged once it is given a priority
+/// and fixed once the story is deployed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BugDetails {
+    pub severity: BugSeverity,
+    pub priority: Option<BugPriority>,
+    /// Where the bug was found, e.g. `production` or `Safari 17 on macOS`
+    pub environment_found: Option<String>,
+    pub triaged_at: Option<DateTime<Utc>>,
+    pub fixed_at: Option<DateTime<Utc>>,
+}
+
+impl BugDetails {
+    pub fn set_severity(&mut self, severity: BugSeverity) {
+        self.severity = severity;
+    }
+
+    /// Prioritizing the bug triages it; the first triage time is kept
+    pub fn set_priority(&mut self, priority: BugPriority, now: DateTime<Utc>) {
+        self.priority = Some(priority);
+        self.triaged_at.get_or_insert(now);
+    }
+
+    pub fn set_environment_found(&mut self, environment: Option<String>) -> Result<(), AppError> {
+        let environment = environment
+            .map(|environment| environment.trim().to_string())
+            .filter(|environment| !environment.is_empty());
+        if let Some(environment) = &environment {
+            if environment.chars().count() > MAX_ENVIRONMENT_LENGTH {
+                return Err(AppError::BadRequest(format!(
+                    "Environment cannot exceed {} characters",
+                    MAX_ENVIRONMENT_LENGTH
+                )));
+            }
+        }
+        self.environment_found = environment;
+        Ok(())
+    }
+}
+
+/// Hours a bug of some severity may take to be triaged and to be fixed,
+/// both counted from when it was reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlaTargets {
+    pub triage_hours: u32,
+    pub fix_hours: u32,
+}
+
+impl SlaTargets {
+    pub fn new(triage_hours: u32, fix_hours: u32) -> Result<Self, AppError> {
+        if triage_hours == 0 || fix_hours == 0 {
+            return Err(AppError::BadRequest(
+                "SLA targets must be at least one hour".to_string(),
+            ));
+        }
+        if triage_hours > MAX_SLA_TARGET_HOURS || fix_hours > MAX_SLA_TARGET_HOURS {
+            return Err(AppError::BadRequest(format!(
+                "SLA targets cannot exceed {} hours",
+                MAX_SLA_TARGET_HOURS
+            )));
+        }
+        if triage_hours > fix_hours {
+            return Err(AppError::BadRequest(
+                "The time to triage cannot exceed the time to fix".to_string(),
+            ));
+        }
+        Ok(Self {
+            triage_hours,
+            fix_hours,
+        })
+    }
+}
+
+/// An organization's SLA targets by severity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlaPolicy {
+    pub critical: SlaTargets,
+    pub high: SlaTargets,
+    pub medium: SlaTargets,
+    pub low: SlaTargets,
+}
+
+impl Default for SlaPolicy {
+    fn default() -> Self {
+        Self {
+            critical: SlaTargets {
+                triage_hours: 4,
+                fix_hours: 24,
+            },
+            high: SlaTargets {
+                triage_hours: 24,
+                fix_hours: 72,
+            },
+            medium: SlaTargets {
+                triage_hours: 72,
+                fix_hours: 336,
+            },
+            low: SlaTargets {
+                triage_hours: 168,
+                fix_hours: 720,
+            },
+        }
+    }
+}
+
+impl SlaPolicy {
+    pub fn targets(&self, severity: BugSeverity) -> SlaTargets {
+        match severity {
+            BugSeverity::Critical => self.critical,
+            BugSeverity::High => self.high,
+            BugSeverity::Medium => self.medium,
+            BugSeverity::Low => self.low,
+        }
+    }
+
+    pub fn set_targets(&mut self, severity: BugSeverity, targets: SlaTargets) {
+        match severity {
+            BugSeverity::Critical => self.critical = targets,
+            BugSeverity::High => self.high = targets,
+            BugSeverity::Medium => self.medium = targets,
+            BugSeverity::Low => self.low = targets,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlaTimerKind {
+    Triage,
+    Fix,
+}
+
+impl SlaTimerKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Triage => "triage",
+            Self::Fix => "fix",
+        }
+    }
+}
+
+/// One SLA clock. `breached` once it stops, or is still running, after `due_at`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlaTimer {
+    pub due_at: DateTime<Utc>,
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub breached: bool,
+}
+
+impl SlaTimer {
+    fn evaluate(
+        reported_at: DateTime<Utc>,
+        hours: u32,
+        stopped_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let due_at = reported_at + Duration::hours(hours as i64);
+        Self {
+            due_at,
+            stopped_at,
+            breached: stopped_at.unwrap_or(now) > due_at,
+        }
+    }
+}
+
+/// Where a bug stands against its SLA targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BugSla {
+    pub targets: SlaTargets,
+    pub triage: SlaTimer,
+    pub fix: SlaTimer,
+}
+
+impl BugSla {
+    pub fn evaluate(
+        reported_at: DateTime<Utc>,
+        details: &BugDetails,
+        policy: &SlaPolicy,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let targets = policy.targets(details.severity);
+        Self {
+            targets,
+            triage: SlaTimer::evaluate(
+                reported_at,
+                targets.triage_hours,
+                // A bug fixed without ever being prioritized was triaged by fixing it
+                details.triaged_at.or(details.fixed_at),
+                now,
+            ),
+            fix: SlaTimer::evaluate(reported_at, targets.fix_hours, details.fixed_at, now),
+        }
+    }
+
+    pub fn timer(&self, kind: SlaTimerKind) -> SlaTimer {
+        match kind {
+            SlaTimerKind::Triage => self.triage,
+            SlaTimerKind::Fix => self.fix,
+        }
+    }
+}
+
+/// How many timers of one kind were met, breached or are still running in time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerCompliance {
+    pub met: usize,
+    pub breached: usize,
+    pub open: usize,
+    /// Met as a percentage of met and breached; `None` until a timer is decided
+    pub compliance_percent: Option<f64>,
+}
+
+impl TimerCompliance {
+    fn record(&mut self, timer: &SlaTimer) {
+        if timer.breached {
+            self.breached += 1;
+        } else if timer.stopped_at.is_some() {
+            self.met += 1;
+        } else {
+            self.open += 1;
+        }
+        let decided = self.met + self.breached;
+        self.compliance_percent = Some(self.met as f64 * 100.0 / decided as f64)
+            .filter(|_| decided > 0)
+            .map(|percent| (percent * 10.0).round() / 10.0);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeverityCompliance {
+    pub severity: BugSeverity,
+    pub bugs: usize,
+    pub triage: TimerCompliance,
+    pub fix: TimerCompliance,
+}
+
+/// SLA compliance of a set of bugs, overall and by severity
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlaComplianceReport {
+    pub bugs: usize,
+    pub triage: TimerCompliance,
+    pub fix: TimerCompliance,
+    pub severities: Vec<SeverityCompliance>,
+}
+
+impl SlaComplianceReport {
+    /// `bugs` are (reported at, details) pairs
+    pub fn compute(
+        bugs: &[(DateTime<Utc>, BugDetails)],
+        policy: &SlaPolicy,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut report = Self {
+            bugs: bugs.len(),
+            triage: TimerCompliance::default(),
+            fix: TimerCompliance::default(),
+            severities: BugSeverity::ALL
+                .into_iter()
+                .map(|severity| SeverityCompliance {
+                    severity,
+                    bugs: 0,
+                    triage: TimerCompliance::default(),
+                    fix: TimerCompliance::default(),
+                })
+                .collect(),
+        };
+
+        for (reported_at, details) in bugs {
+            let sla = BugSla::evaluate(*reported_at, details, policy, now);
+            report.triage.record(&sla.triage);
+            report.fix.record(&sla.fix);
+            if let Some(severity) = report
+                .severities
+                .iter_mut()
+                .find(|entry| entry.severity == details.severity)
+            {
+                severity.bugs += 1;
+                severity.triage.record(&sla.triage);
+                severity.fix.record(&sla.fix);
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timers_run_from_the_report_until_triage_and_fix() {
+        let reported_at = Utc::now() - Duration::hours(30);
+        let policy = SlaPolicy::default();
+        let mut details = BugDetails {
+            severity: BugSeverity::Critical,
+            ..Default::default()
+        };
+
+        let sla = BugSla::evaluate(reported_at, &details, &policy, Utc::now());
+        assert_eq!(sla.triage.due_at, reported_at + Duration::hours(4));
+        assert!(sla.triage.breached && sla.fix.breached);
+
+        details.set_priority(BugPriority::Urgent, reported_at + Duration::hours(1));
+        details.set_priority(BugPriority::High, reported_at + Duration::hours(10));
+        assert_eq!(details.triaged_at, Some(reported_at + Duration::hours(1)));
+        details.set_severity(BugSeverity::High);
+        let sla = BugSla::evaluate(reported_at, &details, &policy, Utc::now());
+        assert!(!sla.triage.breached);
+        assert!(!sla.fix.breached);
+        assert_eq!(sla.fix.stopped_at, None);
+
+        assert!(SlaTargets::new(0, 10).is_err());
+        assert!(SlaTargets::new(48, 24).is_err());
+        assert!(SlaTargets::new(2, MAX_SLA_TARGET_HOURS + 1).is_err());
+        assert!(details
+            .set_environment_found(Some("x".repeat(MAX_ENVIRONMENT_LENGTH + 1)))
+            .is_err());
+    }
+
+    #[test]
+    fn test_compliance_report_counts_met_breached_and_open_timers() {
+        let now = Utc::now();
+        let policy = SlaPolicy::default();
+        let reported_at = now - Duration::hours(100);
+        let bugs = vec![
+            // Triaged and fixed in time
+            (
+                reported_at,
+                BugDetails {
+                    severity: BugSeverity::High,
+                    triaged_at: Some(reported_at + Duration::hours(2)),
+                    fixed_at: Some(reported_at + Duration::hours(50)),
+                    ..Default::default()
+                },
+            ),
+            // Never triaged, fix overdue
+            (
+                reported_at,
+                BugDetails {
+                    severity: BugSeverity::High,
+                    ..Default::default()
+                },
+            ),
+            // Still within both targets
+            (now, BugDetails::default()),
+        ];
+
+        let report = SlaComplianceReport::compute(&bugs, &policy, now);
+        assert_eq!(report.bugs, 3);
+        assert_eq!(
+            (
+                report.triage.met,
+                report.triage.breached,
+                report.triage.open
+            ),
+            (1, 1, 1)
+        );
+        assert_eq!(report.fix.compliance_percent, Some(50.0));
+        let high = &report.severities[1];
+        assert_eq!((high.severity, high.bugs), (BugSeverity::High, 2));
+        assert_eq!(report.severities[2].triage.compliance_percent, None);
+    }
+}
@@ -0,0 +1,222 @@
+use common::i18n::LocalizedMessage;
+use common::AppError;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::domain::{
+    normalize_label, same_label, Story, StoryStatus, TaskStatus, MAX_TASK_BATCH_SIZE,
+};
+
+/// Where a bulk update moves its stories
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprintAssignment {
+    /// Commit the stories to this sprint; stories already in it are left as they are
+    Sprint(Uuid),
+    /// Take the stories out of their sprints, back to the product backlog
+    Backlog,
+}
+
+/// One partial update applied to every story in `story_ids`, all or nothing.
+/// A sprint move is applied first, then labels, then the status.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoryBulkUpdate {
+    pub story_ids: Vec<Uuid>,
+    pub status: Option<StoryStatus>,
+    pub add_labels: Vec<String>,
+    pub remove_labels: Vec<String>,
+    pub sprint: Option<SprintAssignment>,
+}
+
+impl StoryBulkUpdate {
+    /// Labels are trimmed; an update that changes nothing is rejected
+    pub fn new(
+        story_ids: Vec<Uuid>,
+        status: Option<StoryStatus>,
+        add_labels: Vec<String>,
+        remove_labels: Vec<String>,
+        sprint: Option<SprintAssignment>,
+    ) -> Result<Self, AppError> {
+        validate_ids(&story_ids, "validation.batch_duplicate_story", "story_id")?;
+        let add_labels = normalize_labels(&add_labels)?;
+        let remove_labels = normalize_labels(&remove_labels)?;
+        if let Some(label) = add_labels.iter().find(|label| {
+            remove_labels
+                .iter()
+                .any(|removed| same_label(removed, label))
+        }) {
+            return Err(AppError::BadRequest(format!(
+                "Label '{}' cannot be both added and removed",
+                label
+            )));
+        }
+        if status.is_none() && add_labels.is_empty() && remove_labels.is_empty() && sprint.is_none()
+        {
+            return Err(AppError::BadRequest(
+                "A bulk update must change the status, labels or sprint".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            story_ids,
+            status,
+            add_labels,
+            remove_labels,
+            sprint,
+        })
+    }
+
+    /// Add and remove the update's labels, matching them case-insensitively.
+    /// Returns whether the story's labels changed.
+    pub fn apply_labels(&self, story: &mut Story) -> bool {
+        let before = story.labels.clone();
+        story.labels.retain(|label| {
+            !self
+                .remove_labels
+                .iter()
+                .any(|removed| same_label(removed, label))
+        });
+        for label in &self.add_labels {
+            if !story
+                .labels
+                .iter()
+                .any(|existing| same_label(existing, label))
+            {
+                story.labels.push(label.clone());
+            }
+        }
+        let changed = story.labels != before;
+        if changed {
+            story.updated_at = chrono::Utc::now();
+        }
+        changed
+    }
+}
+
+/// One partial update applied to every task in `task_ids`, all or nothing
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskBulkUpdate {
+    pub task_ids: Vec<Uuid>,
+    pub status: Option<TaskStatus>,
+    /// `Some(None)` clears the estimates
+    pub estimated_hours: Option<Option<u32>>,
+}
+
+impl TaskBulkUpdate {
+    pub fn new(
+        task_ids: Vec<Uuid>,
+        status: Option<TaskStatus>,
+        estimated_hours: Option<Option<u32>>,
+    ) -> Result<Self, AppError> {
+        validate_ids(&task_ids, "validation.batch_duplicate", "task_id")?;
+        if status.is_none() && estimated_hours.is_none() {
+            return Err(AppError::BadRequest(
+                "A bulk update must change the status or estimate".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            task_ids,
+            status,
+            estimated_hours,
+        })
+    }
+}
+
+/// Reject id lists that are empty, too large, or name an item more than once
+fn validate_ids(
+    ids: &[Uuid],
+    duplicate_key: &'static str,
+    id_param: &'static str,
+) -> Result<(), AppError> {
+    if ids.is_empty() {
+        return Err(AppError::invalid(LocalizedMessage::new(
+            "validation.batch_empty",
+        )));
+    }
+    if ids.len() > MAX_TASK_BATCH_SIZE {
+        return Err(AppError::invalid(
+            LocalizedMessage::new("validation.batch_too_large").with("max", MAX_TASK_BATCH_SIZE),
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    for id in ids {
+        if !seen.insert(id) {
+            return Err(AppError::invalid(
+                LocalizedMessage::new(duplicate_key).with(id_param, id),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn normalize_labels(labels: &[String]) -> Result<Vec<String>, AppError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(labels.len());
+    for label in labels {
+        let label = normalize_label(label)?;
+        if !normalized.iter().any(|kept| same_label(kept, &label)) {
+            normalized.push(label);
+        }
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_story_bulk_update_validation() {
+        let story_id = Uuid::new_v4();
+        let labels = |labels: &[&str]| labels.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+
+        assert!(
+            StoryBulkUpdate::new(vec![], Some(StoryStatus::Ready), vec![], vec![], None).is_err()
+        );
+        assert!(StoryBulkUpdate::new(vec![story_id], None, vec![], vec![], None).is_err());
+        assert!(StoryBulkUpdate::new(
+            vec![story_id, story_id],
+            Some(StoryStatus::Ready),
+            vec![],
+            vec![],
+            None
+        )
+        .is_err());
+        assert!(StoryBulkUpdate::new(
+            vec![story_id],
+            None,
+            labels(&["backend"]),
+            labels(&["Backend"]),
+            None
+        )
+        .is_err());
+
+        let update = StoryBulkUpdate::new(
+            vec![story_id],
+            None,
+            labels(&[" api ", "API"]),
+            vec![],
+            Some(SprintAssignment::Backlog),
+        )
+        .unwrap();
+        assert_eq!(update.add_labels, labels(&["api"]));
+    }
+
+    #[test]
+    fn test_apply_labels_matches_case_insensitively() {
+        let mut story = Story::new(Uuid::new_v4(), None, "Bulk".to_string(), None).unwrap();
+        story.labels = vec!["Backend".to_string(), "urgent".to_string()];
+        let update = StoryBulkUpdate::new(
+            vec![story.id],
+            None,
+            vec!["backend".to_string(), "api".to_string()],
+            vec!["URGENT".to_string()],
+            None,
+        )
+        .unwrap();
+
+        assert!(update.apply_labels(&mut story));
+        assert_eq!(story.labels, vec!["Backend".to_string(), "api".to_string()]);
+        assert!(!update.apply_labels(&mut story));
+    }
+}
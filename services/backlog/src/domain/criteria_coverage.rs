@@ -0,0 +1,255 @@
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::Task;
+
+/// Error code returned when a task's acceptance criteria block its completion
+pub const CRITERIA_NOT_VERIFIED: &str = "ACCEPTANCE_CRITERIA_NOT_VERIFIED";
+
+const MAX_EVIDENCE_URL_LENGTH: usize = 2000;
+const MAX_NOTE_LENGTH: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VerificationKind {
+    /// A test run or other automated check, linked by `evidence_url`
+    Evidence,
+    /// Someone checked the criterion by hand
+    Manual,
+}
+
+impl VerificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Evidence => "evidence",
+            Self::Manual => "manual",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "evidence" => Some(Self::Evidence),
+            "manual" => Some(Self::Manual),
+            _ => None,
+        }
+    }
+}
+
+/// One check of an acceptance criterion. The latest check of a criterion
+/// decides whether it is covered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriterionVerification {
+    pub id: Uuid,
+    pub story_id: Uuid,
+    pub criterion_id: Uuid,
+    pub kind: VerificationKind,
+    pub passed: bool,
+    pub evidence_url: Option<String>,
+    pub note: Option<String>,
+    pub verified_by: Option<Uuid>,
+    pub verified_at: DateTime<Utc>,
+}
+
+impl CriterionVerification {
+    pub fn new(
+        story_id: Uuid,
+        criterion_id: Uuid,
+        kind: VerificationKind,
+        passed: bool,
+        evidence_url: Option<String>,
+        note: Option<String>,
+        verified_by: Option<Uuid>,
+    ) -> Result<Self, AppError> {
+        let evidence_url = evidence_url
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty());
+        let note = note
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty());
+        if kind == VerificationKind::Evidence && evidence_url.is_none() {
+            return Err(AppError::BadRequest(
+                "Evidence needs an evidenceUrl linking to the test run".to_string(),
+            ));
+        }
+        if evidence_url
+            .as_ref()
+            .is_some_and(|url| url.chars().count() > MAX_EVIDENCE_URL_LENGTH)
+        {
+            return Err(AppError::BadRequest(format!(
+                "evidenceUrl must be at most {} characters",
+                MAX_EVIDENCE_URL_LENGTH
+            )));
+        }
+        if note
+            .as_ref()
+            .is_some_and(|note| note.chars().count() > MAX_NOTE_LENGTH)
+        {
+            return Err(AppError::BadRequest(format!(
+                "note must be at most {} characters",
+                MAX_NOTE_LENGTH
+            )));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            story_id,
+            criterion_id,
+            kind,
+            passed,
+            evidence_url,
+            note,
+            verified_by,
+            verified_at: Utc::now(),
+        })
+    }
+}
+
+/// An organization's rules for completing tasks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCompletionPolicy {
+    /// Only complete a task once every criterion it references has passed
+    /// its latest verification
+    pub require_criteria_coverage: bool,
+}
+
+/// The task's criterion references whose latest verification is missing or
+/// failed, in the task's order
+pub fn uncovered_criteria(task: &Task, verifications: &[CriterionVerification]) -> Vec<String> {
+    let mut latest: HashMap<String, &CriterionVerification> = HashMap::new();
+    for verification in verifications {
+        latest
+            .entry(verification.criterion_id.to_string())
+            .and_modify(|current| {
+                if verification.verified_at > current.verified_at {
+                    *current = verification;
+                }
+            })
+            .or_insert(verification);
+    }
+    task.acceptance_criteria_refs
+        .iter()
+        .filter(|criterion| {
+            !latest
+                .get(criterion.trim())
+                .is_some_and(|verification| verification.passed)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Refuse to complete `task` while any criterion it references is uncovered
+pub fn ensure_criteria_covered(
+    task: &Task,
+    verifications: &[CriterionVerification],
+) -> Result<(), AppError> {
+    let uncovered = uncovered_criteria(task, verifications);
+    if uncovered.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::ConflictWithCode {
+        message: format!(
+            "Task cannot be completed until its acceptance criteria have passing evidence or a manual verification. Uncovered: {}",
+            uncovered.join(", ")
+        ),
+        error_code: CRITERIA_NOT_VERIFIED.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn verification(story_id: Uuid, criterion_id: Uuid, passed: bool) -> CriterionVerification {
+        CriterionVerification::new(
+            story_id,
+            criterion_id,
+            VerificationKind::Evidence,
+            passed,
+            Some("https://ci.example.com/runs/42".to_string()),
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_latest_passing_verification_covers_a_criterion() {
+        let story_id = Uuid::new_v4();
+        let (tested, signed_off, regressed, untouched) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let task = Task::new(
+            story_id,
+            None,
+            "Wire up checkout".to_string(),
+            None,
+            [tested, signed_off, regressed, untouched]
+                .iter()
+                .map(Uuid::to_string)
+                .collect(),
+        )
+        .unwrap();
+
+        let manual = CriterionVerification::new(
+            story_id,
+            signed_off,
+            VerificationKind::Manual,
+            true,
+            None,
+            Some("Checked on staging".to_string()),
+            None,
+        )
+        .unwrap();
+        let mut failed_later = verification(story_id, regressed, false);
+        failed_later.verified_at += Duration::minutes(5);
+        let verifications = vec![
+            verification(story_id, tested, true),
+            manual,
+            verification(story_id, regressed, true),
+            failed_later,
+        ];
+
+        assert_eq!(
+            uncovered_criteria(&task, &verifications),
+            vec![regressed.to_string(), untouched.to_string()]
+        );
+        match ensure_criteria_covered(&task, &verifications) {
+            Err(AppError::ConflictWithCode {
+                message,
+                error_code,
+            }) => {
+                assert_eq!(error_code, CRITERIA_NOT_VERIFIED);
+                assert!(message.contains(&regressed.to_string()));
+                assert!(message.contains(&untouched.to_string()));
+            }
+            other => panic!("expected a coverage conflict, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_evidence_needs_a_link() {
+        let result = CriterionVerification::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            VerificationKind::Evidence,
+            true,
+            Some("  ".to_string()),
+            None,
+            None,
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            VerificationKind::parse("manual"),
+            Some(VerificationKind::Manual)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use uuid::Uuid;
+
+use crate::domain::backlog_health::mention_cycles;
+use crate::domain::{Story, StoryStatus};
+
+/// How a story is drawn on the dependency board
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeColor {
+    /// Deployed, awaiting acceptance or accepted
+    Green,
+    /// Being worked on
+    Blue,
+    /// Refined or committed, not started yet
+    Amber,
+    /// Waiting on an undelivered dependency, or part of a dependency cycle
+    Red,
+    /// Still in refinement
+    Grey,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyNode {
+    pub story_id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub story_points: Option<u32>,
+    pub color: NodeColor,
+    pub blocked: bool,
+    pub on_critical_path: bool,
+}
+
+/// `story_id` depends on `depends_on_id` because it mentions it by short key
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyEdge {
+    pub story_id: Uuid,
+    pub depends_on_id: Uuid,
+    /// The dependency has not been delivered yet
+    pub blocking: bool,
+}
+
+/// A project's stories and the dependencies between them
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraph {
+    pub project_id: Uuid,
+    pub nodes: Vec<DependencyNode>,
+    pub edges: Vec<DependencyEdge>,
+    /// Undelivered stories on the heaviest chain of dependencies, in the order
+    /// they have to be delivered
+    pub critical_path: Vec<Uuid>,
+    pub critical_path_points: u32,
+    /// Stories that depend on each other in a loop; left off the critical path
+    pub cycles: Vec<Vec<Uuid>>,
+}
+
+fn is_delivered(status: &StoryStatus) -> bool {
+    matches!(
+        status,
+        StoryStatus::Deployed | StoryStatus::AwaitingAcceptance | StoryStatus::Accepted
+    )
+}
+
+/// Weight of a story on the critical path; unestimated stories count as one point
+fn path_weight(story: &Story) -> u32 {
+    story.story_points.unwrap_or(1).max(1)
+}
+
+impl DependencyGraph {
+    /// Build the graph from the project's stories and their story-to-story
+    /// mentions (source, target), where the source depends on the target
+    pub fn build(project_id: Uuid, stories: &[Story], mentions: &[(Uuid, Uuid)]) -> Self {
+        let by_id: HashMap<Uuid, &Story> = stories.iter().map(|story| (story.id, story)).collect();
+
+        let mut seen = HashSet::new();
+        let edges: Vec<DependencyEdge> = mentions
+            .iter()
+            .filter(|(source, target)| {
+                source != target && by_id.contains_key(source) && by_id.contains_key(target)
+            })
+            .filter(|edge| seen.insert(**edge))
+            .map(|(source, target)| DependencyEdge {
+                story_id: *source,
+                depends_on_id: *target,
+                blocking: !is_delivered(&by_id[target].status),
+            })
+            .collect();
+
+        let ids: HashSet<Uuid> = by_id.keys().copied().collect();
+        let cycles = mention_cycles(&ids, mentions);
+        let in_cycle: HashSet<Uuid> = cycles.iter().flatten().copied().collect();
+
+        let (critical_path, critical_path_points) = critical_path(&by_id, &edges, &in_cycle);
+        let on_path: HashSet<Uuid> = critical_path.iter().copied().collect();
+
+        let blocked: HashSet<Uuid> = edges
+            .iter()
+            .filter(|edge| edge.blocking && !is_delivered(&by_id[&edge.story_id].status))
+            .map(|edge| edge.story_id)
+            .chain(in_cycle.iter().copied())
+            .collect();
+
+        let nodes = stories
+            .iter()
+            .map(|story| {
+                let is_blocked = blocked.contains(&story.id) && !is_delivered(&story.status);
+                let color = match &story.status {
+                    status if is_delivered(status) => NodeColor::Green,
+                    _ if is_blocked => NodeColor::Red,
+                    StoryStatus::InProgress | StoryStatus::TasksComplete => NodeColor::Blue,
+                    StoryStatus::Ready | StoryStatus::Committed => NodeColor::Amber,
+                    _ => NodeColor::Grey,
+                };
+                DependencyNode {
+                    story_id: story.id,
+                    title: story.title.clone(),
+                    status: story.status.to_string(),
+                    story_points: story.story_points,
+                    color,
+                    blocked: is_blocked,
+                    on_critical_path: on_path.contains(&story.id),
+                }
+            })
+            .collect();
+
+        Self {
+            project_id,
+            nodes,
+            edges,
+            critical_path,
+            critical_path_points,
+            cycles,
+        }
+    }
+}
+
+/// Heaviest chain through the undelivered stories outside cycles, which form a DAG
+fn critical_path(
+    by_id: &HashMap<Uuid, &Story>,
+    edges: &[DependencyEdge],
+    in_cycle: &HashSet<Uuid>,
+) -> (Vec<Uuid>, u32) {
+    let open = |id: &Uuid| !in_cycle.contains(id) && !is_delivered(&by_id[id].status);
+
+    // Kahn's algorithm from dependencies to their dependents
+    let mut dependents: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    let mut pending: HashMap<Uuid, usize> = by_id
+        .keys()
+        .filter(|id| open(id))
+        .map(|id| (*id, 0))
+        .collect();
+    for edge in edges {
+        if open(&edge.story_id) && open(&edge.depends_on_id) {
+            dependents
+                .entry(edge.depends_on_id)
+                .or_default()
+                .push(edge.story_id);
+            *pending.entry(edge.story_id).or_default() += 1;
+        }
+    }
+
+    let mut roots: Vec<Uuid> = pending
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    roots.sort();
+    let mut queue: VecDeque<Uuid> = roots.into();
+    let mut weight: HashMap<Uuid, u32> = HashMap::new();
+    let mut previous: HashMap<Uuid, Uuid> = HashMap::new();
+
+    while let Some(id) = queue.pop_front() {
+        let total = *weight.entry(id).or_insert_with(|| path_weight(by_id[&id]));
+        for dependent in dependents.get(&id).map(Vec::as_slice).unwrap_or_default() {
+            let candidate = total + path_weight(by_id[dependent]);
+            let best = weight.entry(*dependent).or_insert(0);
+            if candidate > *best {
+                *best = candidate;
+                previous.insert(*dependent, id);
+            }
+            let count = pending
+                .get_mut(dependent)
+                .expect("open stories are pending");
+            *count -= 1;
+            if *count == 0 {
+                queue.push_back(*dependent);
+            }
+        }
+    }
+
+    let Some((&end, &points)) = weight
+        .iter()
+        .max_by(|(a_id, a), (b_id, b)| a.cmp(b).then(b_id.cmp(a_id)))
+    else {
+        return (Vec::new(), 0);
+    };
+
+    let mut path = vec![end];
+    while let Some(dependency) = previous.get(path.last().expect("path is not empty")) {
+        path.push(*dependency);
+    }
+    path.reverse();
+    (path, points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(title: &str, status: StoryStatus, points: Option<u32>) -> Story {
+        let mut story = Story::new(Uuid::new_v4(), None, title.to_string(), None).unwrap();
+        story.status = status;
+        story.story_points = points;
+        story
+    }
+
+    #[test]
+    fn test_critical_path_follows_heaviest_open_chain() {
+        let schema = story("Schema", StoryStatus::InProgress, Some(5));
+        let api = story("API", StoryStatus::Ready, Some(3));
+        let ui = story("UI", StoryStatus::Draft, None);
+        let docs = story("Docs", StoryStatus::Ready, Some(8));
+        let auth = story("Auth", StoryStatus::Accepted, Some(13));
+        let mentions = vec![
+            // UI -> API -> Schema is 1 + 3 + 5 = 9 points, Docs alone is 8
+            (ui.id, api.id),
+            (api.id, schema.id),
+            (api.id, auth.id),
+            (api.id, schema.id),
+        ];
+        let stories = vec![schema.clone(), api.clone(), ui.clone(), docs, auth.clone()];
+
+        let graph = DependencyGraph::build(Uuid::new_v4(), &stories, &mentions);
+        assert_eq!(graph.edges.len(), 3);
+        assert_eq!(graph.critical_path, vec![schema.id, api.id, ui.id]);
+        assert_eq!(graph.critical_path_points, 9);
+
+        let node = |id: Uuid| graph.nodes.iter().find(|n| n.story_id == id).unwrap();
+        assert_eq!(node(schema.id).color, NodeColor::Blue);
+        assert_eq!(node(api.id).color, NodeColor::Red);
+        assert_eq!(node(auth.id).color, NodeColor::Green);
+        assert!(node(ui.id).on_critical_path && node(ui.id).blocked);
+        assert!(
+            !graph
+                .edges
+                .iter()
+                .find(|e| e.depends_on_id == auth.id)
+                .unwrap()
+                .blocking
+        );
+    }
+
+    #[test]
+    fn test_cycles_are_blocked_and_off_the_path() {
+        let a = story("A", StoryStatus::Ready, Some(2));
+        let b = story("B", StoryStatus::Ready, Some(2));
+        let c = story("C", StoryStatus::Committed, Some(1));
+        let mentions = vec![(a.id, b.id), (b.id, a.id), (c.id, a.id)];
+
+        let graph = DependencyGraph::build(Uuid::new_v4(), &[a.clone(), b, c.clone()], &mentions);
+        assert_eq!(graph.cycles.len(), 1);
+        assert_eq!(graph.critical_path, vec![c.id]);
+        assert!(graph.nodes.iter().all(|node| node.color == NodeColor::Red));
+    }
+}
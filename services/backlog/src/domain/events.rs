@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::{BugSeverity, SlaTimerKind, TaskTransferStatus};
+
 /// Domain events for real-time WebSocket updates
 /// These events represent state changes that should be broadcast to connected clients
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +31,71 @@ pub enum TaskEvent {
         changed_by_user_id: Uuid,
         timestamp: chrono::DateTime<chrono::Utc>,
     },
+    /// A released task is offered to the first user waiting in its claim queue
+    ClaimOffered {
+        task_id: Uuid,
+        story_id: Uuid,
+        user_id: Uuid,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// The owner proposed handing the task to `to_user_id`, who has to accept
+    TransferProposed {
+        task_id: Uuid,
+        story_id: Uuid,
+        transfer_id: Uuid,
+        from_user_id: Uuid,
+        to_user_id: Uuid,
+        handoff: bool,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// A proposed transfer was accepted, declined or withdrawn
+    TransferResolved {
+        task_id: Uuid,
+        story_id: Uuid,
+        transfer_id: Uuid,
+        from_user_id: Uuid,
+        to_user_id: Uuid,
+        status: TaskTransferStatus,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Several task statuses changed in one batch request
+    BatchStatusChanged {
+        changes: Vec<TaskStatusChange>,
+        changed_by_user_id: Uuid,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// A bug ran past its time-to-triage or time-to-fix target
+    BugSlaBreached {
+        story_id: Uuid,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        title: String,
+        severity: BugSeverity,
+        timer: SlaTimerKind,
+        due_at: chrono::DateTime<chrono::Utc>,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// A draft story went untouched long enough to be flagged as stale.
+    /// `archive_at` is when it will be archived unless someone edits it.
+    StoryWentStale {
+        story_id: Uuid,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        title: String,
+        created_by: Option<Uuid>,
+        stale_at: chrono::DateTime<chrono::Utc>,
+        archive_at: Option<chrono::DateTime<chrono::Utc>>,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+}
+
+/// A single task's status change within [`TaskEvent::BatchStatusChanged`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskStatusChange {
+    pub task_id: Uuid,
+    pub story_id: Uuid,
+    pub old_status: String,
+    pub new_status: String,
 }
 
 impl TaskEvent {
@@ -36,7 +103,17 @@ impl TaskEvent {
         match self {
             TaskEvent::OwnershipTaken { task_id, .. }
             | TaskEvent::OwnershipReleased { task_id, .. }
-            | TaskEvent::StatusChanged { task_id, .. } => *task_id,
+            | TaskEvent::StatusChanged { task_id, .. }
+            | TaskEvent::ClaimOffered { task_id, .. }
+            | TaskEvent::TransferProposed { task_id, .. }
+            | TaskEvent::TransferResolved { task_id, .. } => *task_id,
+            // Batches are only broadcast when at least one task changed
+            TaskEvent::BatchStatusChanged { changes, .. } => changes
+                .first()
+                .map(|change| change.task_id)
+                .unwrap_or_default(),
+            // Not about a task
+            TaskEvent::BugSlaBreached { .. } | TaskEvent::StoryWentStale { .. } => Uuid::nil(),
         }
     }
 
@@ -44,7 +121,33 @@ impl TaskEvent {
         match self {
             TaskEvent::OwnershipTaken { story_id, .. }
             | TaskEvent::OwnershipReleased { story_id, .. }
-            | TaskEvent::StatusChanged { story_id, .. } => *story_id,
+            | TaskEvent::StatusChanged { story_id, .. }
+            | TaskEvent::ClaimOffered { story_id, .. }
+            | TaskEvent::TransferProposed { story_id, .. }
+            | TaskEvent::TransferResolved { story_id, .. } => *story_id,
+            TaskEvent::BatchStatusChanged { changes, .. } => changes
+                .first()
+                .map(|change| change.story_id)
+                .unwrap_or_default(),
+            TaskEvent::BugSlaBreached { story_id, .. }
+            | TaskEvent::StoryWentStale { story_id, .. } => *story_id,
+        }
+    }
+
+    /// Whether a client of `organization_id` should receive the event. Task
+    /// events go to everyone; SLA and stale story alerts only to the story's
+    /// organization.
+    pub fn is_visible_to(&self, organization_id: Option<Uuid>) -> bool {
+        match self {
+            TaskEvent::BugSlaBreached {
+                organization_id: event_organization_id,
+                ..
+            }
+            | TaskEvent::StoryWentStale {
+                organization_id: event_organization_id,
+                ..
+            } => *event_organization_id == organization_id,
+            _ => true,
         }
     }
 }
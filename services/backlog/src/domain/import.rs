@@ -0,0 +1,437 @@
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::StoryType;
+
+/// Largest import accepted in one request
+pub const MAX_IMPORT_BYTES: usize = 8 * 1024 * 1024;
+pub const MAX_IMPORT_ITEMS: usize = 10_000;
+/// Stories created per transaction; progress advances a chunk at a time
+pub const IMPORT_CHUNK_SIZE: usize = 100;
+/// Item errors reported with a job's progress
+pub const MAX_REPORTED_IMPORT_ERRORS: usize = 50;
+/// Tries at a job before it is left failed for someone to resume
+pub const MAX_IMPORT_ATTEMPTS: u32 = 3;
+
+/// The formats stories can be imported from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    /// A header row naming `title`, and optionally `description`, `labels`
+    /// (separated by `;` or `,`), `type` and `key` columns
+    Csv,
+    /// Jira's CSV export: `Summary`, `Description`, `Issue key`, `Issue Type`
+    /// and one `Labels` column per label
+    Jira,
+    /// One story per paragraph: its first line is the title, the rest the description
+    Text,
+}
+
+impl ImportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jira => "jira",
+            Self::Text => "text",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "jira" => Some(Self::Jira),
+            "text" => Some(Self::Text),
+            _ => None,
+        }
+    }
+}
+
+/// One story to import, as read from the source. It is validated when the
+/// story is created, so one bad row fails alone rather than the whole import.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportItem {
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub story_type: StoryType,
+    /// The item's key in the source, such as a Jira issue key
+    pub external_key: Option<String>,
+}
+
+/// Read the items of an import, failing on content that cannot be read at all
+pub fn parse_import(format: ImportFormat, content: &str) -> Result<Vec<ImportItem>, AppError> {
+    if content.len() > MAX_IMPORT_BYTES {
+        return Err(AppError::BadRequest(format!(
+            "Imports can be at most {} MB",
+            MAX_IMPORT_BYTES / (1024 * 1024)
+        )));
+    }
+    let content = content.trim_start_matches('\u{feff}');
+
+    let items = match format {
+        ImportFormat::Text => parse_text(content),
+        ImportFormat::Csv | ImportFormat::Jira => parse_table(&parse_csv(content)?)?,
+    };
+    if items.is_empty() {
+        return Err(AppError::BadRequest(
+            "The import contains no stories".to_string(),
+        ));
+    }
+    if items.len() > MAX_IMPORT_ITEMS {
+        return Err(AppError::BadRequest(format!(
+            "Imports can hold at most {} stories",
+            MAX_IMPORT_ITEMS
+        )));
+    }
+    Ok(items)
+}
+
+fn parse_text(content: &str) -> Vec<ImportItem> {
+    let mut items = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    for line in content.lines().chain(std::iter::once("")) {
+        if !line.trim().is_empty() {
+            paragraph.push(line.trim_end());
+            continue;
+        }
+        if paragraph.is_empty() {
+            continue;
+        }
+        let title = paragraph[0]
+            .trim()
+            .trim_start_matches(['-', '*', '#'])
+            .trim()
+            .to_string();
+        let description = paragraph[1..].join("\n").trim().to_string();
+        items.push(ImportItem {
+            title,
+            description: Some(description).filter(|description| !description.is_empty()),
+            labels: Vec::new(),
+            story_type: StoryType::default(),
+            external_key: None,
+        });
+        paragraph.clear();
+    }
+    items
+}
+
+/// Rows of RFC 4180 CSV: quoted fields may hold commas, newlines and `""`
+fn parse_csv(content: &str) -> Result<Vec<Vec<String>>, AppError> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(AppError::BadRequest(
+            "The CSV has a quoted field that is never closed".to_string(),
+        ));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    Ok(rows)
+}
+
+/// Items from CSV rows whose first row names the columns, in either the
+/// plain or the Jira spelling
+fn parse_table(rows: &[Vec<String>]) -> Result<Vec<ImportItem>, AppError> {
+    let Some((header, rows)) = rows.split_first() else {
+        return Ok(Vec::new());
+    };
+    let columns = |names: &[&str]| -> Vec<usize> {
+        header
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| {
+                names
+                    .iter()
+                    .any(|name| column.trim().eq_ignore_ascii_case(name))
+            })
+            .map(|(index, _)| index)
+            .collect()
+    };
+    let title = columns(&["title", "summary"])
+        .first()
+        .copied()
+        .ok_or_else(|| {
+            AppError::BadRequest("The CSV needs a title or Summary column".to_string())
+        })?;
+    let description = columns(&["description"]).first().copied();
+    let story_type = columns(&["type", "story type", "issue type"])
+        .first()
+        .copied();
+    let key = columns(&["key", "issue key", "external key"])
+        .first()
+        .copied();
+    let labels = columns(&["labels", "label"]);
+
+    let cell = |row: &Vec<String>, index: Option<usize>| -> Option<String> {
+        index
+            .and_then(|index| row.get(index))
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let mut item_labels: Vec<String> = Vec::new();
+            for label in labels
+                .iter()
+                .filter_map(|index| cell(row, Some(*index)))
+                .flat_map(|value| {
+                    value
+                        .split([';', ','])
+                        .map(|label| label.trim().to_string())
+                        .collect::<Vec<_>>()
+                })
+            {
+                if !label.is_empty() && !item_labels.contains(&label) {
+                    item_labels.push(label);
+                }
+            }
+            ImportItem {
+                title: cell(row, Some(title)).unwrap_or_default(),
+                description: cell(row, description),
+                labels: item_labels,
+                story_type: cell(row, story_type)
+                    .map(|value| imported_story_type(&value))
+                    .unwrap_or_default(),
+                external_key: cell(row, key),
+            }
+        })
+        .collect())
+}
+
+/// Our story type for a source's issue type; Jira tasks and sub-tasks are chores
+fn imported_story_type(value: &str) -> StoryType {
+    StoryType::parse(value).unwrap_or_else(|| {
+        match value.to_lowercase().replace(['-', ' '], "").as_str() {
+            "task" | "subtask" | "technicaltask" => StoryType::Chore,
+            _ => StoryType::Feature,
+        }
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportJobStatus {
+    /// Waiting for an import worker
+    Queued,
+    /// A worker is creating its stories; a job whose worker died is picked up
+    /// again where it stopped
+    Running,
+    Completed,
+    /// Stopped after repeated errors; it can be resumed
+    Failed,
+}
+
+impl ImportJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "queued" => Some(Self::Queued),
+            "running" => Some(Self::Running),
+            "completed" => Some(Self::Completed),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// A queued import of stories into a project, worked through a chunk at a
+/// time. `processed_items` is the position of the next item to create, so a
+/// resumed job never creates a story twice; `published_items` trails it
+/// until the created stories have been followed up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportJob {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    #[serde(skip)]
+    pub organization_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub format: ImportFormat,
+    pub status: ImportJobStatus,
+    pub total_items: usize,
+    pub processed_items: usize,
+    #[serde(skip)]
+    pub published_items: usize,
+    pub created_items: usize,
+    pub failed_items: usize,
+    pub attempts: u32,
+    /// Why the last attempt stopped
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ImportJob {
+    pub fn new(
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        created_by: Option<Uuid>,
+        format: ImportFormat,
+        total_items: usize,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            project_id,
+            organization_id,
+            created_by,
+            format,
+            status: ImportJobStatus::Queued,
+            total_items,
+            processed_items: 0,
+            published_items: 0,
+            created_items: 0,
+            failed_items: 0,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        }
+    }
+
+    /// Whole percent of the items processed
+    pub fn percent_complete(&self) -> u8 {
+        if self.total_items == 0 {
+            return 100;
+        }
+        (self.processed_items.min(self.total_items) * 100 / self.total_items) as u8
+    }
+
+    /// Whether another attempt is allowed after the current one fails
+    pub fn can_retry(&self) -> bool {
+        self.attempts < MAX_IMPORT_ATTEMPTS
+    }
+}
+
+/// How hard an import may push: created stories are followed up at no more
+/// than `events_per_second`, and the worker rests `chunk_pause` between
+/// chunks so interactive requests keep their share of the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportThrottle {
+    pub events_per_second: u32,
+    pub chunk_pause: Duration,
+}
+
+impl Default for ImportThrottle {
+    fn default() -> Self {
+        Self {
+            events_per_second: 20,
+            chunk_pause: Duration::from_millis(250),
+        }
+    }
+}
+
+impl ImportThrottle {
+    /// The gap between two events
+    pub fn event_interval(&self) -> Duration {
+        Duration::from_secs(1) / self.events_per_second.max(1)
+    }
+}
+
+/// An item that could not be imported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportItemError {
+    /// The item's position in the import, from 0
+    pub position: usize,
+    pub external_key: Option<String>,
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_and_jira_imports_are_parsed() {
+        let csv = "\u{feff}Title,Description,Labels,Type\r\n\
+                   Pay by card,\"Use the \"\"saved\"\" card,\nthen confirm\",web; payments,bug\r\n\
+                   \r\n\
+                   Search orders,,,\r\n";
+        let items = parse_import(ImportFormat::Csv, csv).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title, "Pay by card");
+        assert_eq!(
+            items[0].description.as_deref(),
+            Some("Use the \"saved\" card,\nthen confirm")
+        );
+        assert_eq!(items[0].labels, vec!["web", "payments"]);
+        assert_eq!(items[0].story_type, StoryType::Bug);
+        assert_eq!(items[1].description, None);
+
+        let jira = "Summary,Issue key,Issue Type,Labels,Labels\n\
+                    Export invoices,SHOP-12,Sub-task,billing,web\n\
+                    Refund orders,SHOP-13,Story,,";
+        let items = parse_import(ImportFormat::Jira, jira).unwrap();
+        assert_eq!(items[0].external_key.as_deref(), Some("SHOP-12"));
+        assert_eq!(items[0].story_type, StoryType::Chore);
+        assert_eq!(items[0].labels, vec!["billing", "web"]);
+        assert_eq!(items[1].story_type, StoryType::Feature);
+
+        assert!(parse_import(ImportFormat::Csv, "Name\nPay by card").is_err());
+        assert!(parse_import(ImportFormat::Csv, "Title\n\"Pay by card").is_err());
+        assert!(parse_import(ImportFormat::Csv, "Title\n").is_err());
+    }
+
+    #[test]
+    fn test_text_imports_have_a_story_per_paragraph() {
+        let items = parse_import(
+            ImportFormat::Text,
+            "- Pay by card\nCustomers pay with a saved card.\n\n\n# Search orders\n",
+        )
+        .unwrap();
+        assert_eq!(
+            items
+                .iter()
+                .map(|item| (item.title.as_str(), item.description.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Pay by card", Some("Customers pay with a saved card.")),
+                ("Search orders", None)
+            ]
+        );
+
+        let mut job = ImportJob::new(Uuid::new_v4(), None, None, ImportFormat::Text, 3);
+        job.processed_items = 2;
+        assert_eq!(job.percent_complete(), 66);
+    }
+}
@@ -0,0 +1,80 @@
+use common::AppError;
+use serde::Serialize;
+use uuid::Uuid;
+
+const MAX_LABEL_LENGTH: usize = 50;
+
+/// The result of renaming a label across a project, or merging it into
+/// another label
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelRename {
+    pub project_id: Uuid,
+    pub from: String,
+    pub to: String,
+    /// Whether `to` was already in use, so both labels are now one
+    pub merged: bool,
+    pub updated_story_ids: Vec<Uuid>,
+}
+
+/// A label as given in a path or request body, trimmed
+pub fn normalize_label(label: &str) -> Result<String, AppError> {
+    let label = label.trim();
+    if label.is_empty() || label.chars().count() > MAX_LABEL_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Labels must be between 1 and {} characters",
+            MAX_LABEL_LENGTH
+        )));
+    }
+    Ok(label.to_string())
+}
+
+/// Whether two labels are the same label. Labels match case-insensitively,
+/// as in story filters.
+pub fn same_label(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+/// `labels` with every spelling of `from` replaced by `to`, keeping order and
+/// dropping the duplicates a merge would leave
+pub fn replace_label(labels: &[String], from: &str, to: &str) -> Vec<String> {
+    let mut replaced: Vec<String> = Vec::with_capacity(labels.len());
+    for label in labels {
+        let label = if same_label(label, from) {
+            to.to_string()
+        } else {
+            label.clone()
+        };
+        if !replaced.iter().any(|kept| same_label(kept, &label)) {
+            replaced.push(label);
+        }
+    }
+    replaced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| label.to_string()).collect()
+    }
+
+    #[test]
+    fn test_replace_label_renames_and_merges() {
+        assert_eq!(
+            replace_label(&labels(&["ui", "Backend", "urgent"]), "backend", "api"),
+            labels(&["ui", "api", "urgent"])
+        );
+        assert_eq!(
+            replace_label(&labels(&["backend", "api", "BACKEND"]), "backend", "api"),
+            labels(&["api"])
+        );
+        assert_eq!(
+            replace_label(&labels(&["ui"]), "backend", "api"),
+            labels(&["ui"])
+        );
+        assert!(normalize_label("   ").is_err());
+        assert_eq!(normalize_label(" api ").unwrap(), "api");
+    }
+}
@@ -1,12 +1,64 @@
+pub mod archive;
+pub mod backlog_health;
+pub mod badge;
+pub mod bug_sla;
+pub mod bulk_update;
+pub mod criteria_coverage;
+pub mod dependency_graph;
 pub mod events;
+pub mod import;
+pub mod label;
+pub mod push_notification;
 pub mod recommendation;
+pub mod reference;
+pub mod short_key;
+pub mod sprint_health;
+pub mod sprint_schedule;
+pub mod sprint_stats;
+pub mod standup;
 pub mod story;
+pub mod story_aging;
+pub mod story_dependency;
+pub mod story_filter;
+pub mod story_merge;
+pub mod story_revision;
+pub mod story_share;
+pub mod swimlane;
 pub mod task;
+pub mod task_batch;
+pub mod task_claim;
+pub mod task_transfer;
 
+pub use archive::*;
+pub use backlog_health::*;
+pub use badge::*;
+pub use bug_sla::*;
+pub use bulk_update::*;
+pub use criteria_coverage::*;
+pub use dependency_graph::*;
 pub use events::*;
+pub use import::*;
+pub use label::*;
+pub use push_notification::*;
 pub use recommendation::*;
+pub use reference::*;
+pub use short_key::*;
+pub use sprint_health::*;
+pub use sprint_schedule::*;
+pub use sprint_stats::*;
+pub use standup::*;
 pub use story::*;
+pub use story_aging::*;
+pub use story_dependency::*;
+pub use story_filter::*;
+pub use story_merge::*;
+pub use story_revision::*;
+pub use story_share::*;
+pub use swimlane::*;
 pub use task::*;
+pub use task_batch::*;
+pub use task_claim::*;
+pub use task_transfer::*;
 
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
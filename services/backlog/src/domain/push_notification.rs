@@ -0,0 +1,228 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::{Story, Task, TaskTransfer, TaskTransferStatus};
+
+const MAX_ENDPOINT_LENGTH: usize = 2000;
+/// An uncompressed P-256 point
+const P256DH_LENGTH: usize = 65;
+const AUTH_SECRET_LENGTH: usize = 16;
+
+/// A browser's Web Push subscription, as handed out by `PushManager.subscribe`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushSubscription {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub endpoint: String,
+    /// base64url public key the payload is encrypted to
+    pub p256dh: String,
+    /// base64url authentication secret mixed into the encryption keys
+    pub auth: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PushSubscription {
+    pub fn new(
+        user_id: Uuid,
+        endpoint: String,
+        p256dh: String,
+        auth: String,
+    ) -> Result<Self, AppError> {
+        let endpoint = endpoint.trim().to_string();
+        if !endpoint.starts_with("https://") || endpoint.chars().count() > MAX_ENDPOINT_LENGTH {
+            return Err(AppError::BadRequest(format!(
+                "endpoint must be an https URL of at most {} characters",
+                MAX_ENDPOINT_LENGTH
+            )));
+        }
+        let p256dh = p256dh.trim().trim_end_matches('=').to_string();
+        if !decode_key(&p256dh).is_some_and(|key| key.len() == P256DH_LENGTH && key[0] == 0x04) {
+            return Err(AppError::BadRequest(
+                "keys.p256dh must be a base64url uncompressed P-256 public key".to_string(),
+            ));
+        }
+        let auth = auth.trim().trim_end_matches('=').to_string();
+        if decode_key(&auth).is_none_or(|key| key.len() != AUTH_SECRET_LENGTH) {
+            return Err(AppError::BadRequest(format!(
+                "keys.auth must be a base64url {}-byte secret",
+                AUTH_SECRET_LENGTH
+            )));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            user_id,
+            endpoint,
+            p256dh,
+            auth,
+            created_at: Utc::now(),
+        })
+    }
+
+    pub fn p256dh_bytes(&self) -> Option<Vec<u8>> {
+        decode_key(&self.p256dh)
+    }
+
+    pub fn auth_bytes(&self) -> Option<Vec<u8>> {
+        decode_key(&self.auth)
+    }
+}
+
+fn decode_key(key: &str) -> Option<Vec<u8>> {
+    URL_SAFE_NO_PAD.decode(key.trim_end_matches('=')).ok()
+}
+
+/// The task events worth interrupting someone for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PushNotificationKind {
+    /// A task someone queued for is now theirs to claim
+    TaskAssigned,
+    /// Work someone owns cannot go on until the story is refined
+    TaskBlocked,
+    /// A story is done and waits for its owner to accept it
+    AcceptanceRequested,
+    /// The owner of a task wants to hand it over
+    TransferRequested,
+    /// The recipient took over the task
+    TransferAccepted,
+    /// The recipient turned the task down, or the owner withdrew the offer
+    TransferDeclined,
+}
+
+/// A notification for one user's subscribed browsers. The JSON form is the
+/// push payload the service worker receives.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushNotification {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub kind: PushNotificationKind,
+    pub title: String,
+    pub body: String,
+    pub story_id: Uuid,
+    pub task_id: Option<Uuid>,
+}
+
+impl PushNotification {
+    /// `task` was offered to `user_id`, next in its claim queue
+    pub fn task_assigned(task: &Task, user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            kind: PushNotificationKind::TaskAssigned,
+            title: "A task is yours to claim".to_string(),
+            body: task.title.clone(),
+            story_id: task.story_id,
+            task_id: Some(task.id),
+        }
+    }
+
+    /// `task`, owned by someone, is blocked because `story` went back to refinement
+    pub fn task_blocked(story: &Story, task: &Task) -> Option<Self> {
+        let user_id = task.owner_user_id?;
+        Some(Self {
+            user_id,
+            kind: PushNotificationKind::TaskBlocked,
+            title: "Your task is blocked".to_string(),
+            body: format!("{} needs refinement: {}", story.title, task.title),
+            story_id: story.id,
+            task_id: Some(task.id),
+        })
+    }
+
+    /// `story` awaits acceptance by the person it is assigned to
+    pub fn acceptance_requested(story: &Story) -> Option<Self> {
+        let user_id = story.assigned_to_user_id?;
+        Some(Self {
+            user_id,
+            kind: PushNotificationKind::AcceptanceRequested,
+            title: "Acceptance requested".to_string(),
+            body: story.title.clone(),
+            story_id: story.id,
+            task_id: None,
+        })
+    }
+
+    /// Tell the other party about `transfer`'s latest change
+    pub fn task_transfer(task: &Task, transfer: &TaskTransfer) -> Self {
+        let (kind, title) = match transfer.status {
+            TaskTransferStatus::Pending if transfer.handoff => (
+                PushNotificationKind::TransferRequested,
+                "Someone is stuck and hands you a task",
+            ),
+            TaskTransferStatus::Pending => (
+                PushNotificationKind::TransferRequested,
+                "You are asked to take over a task",
+            ),
+            TaskTransferStatus::Accepted => (
+                PushNotificationKind::TransferAccepted,
+                "Your task was taken over",
+            ),
+            TaskTransferStatus::Declined => (
+                PushNotificationKind::TransferDeclined,
+                "Your task transfer was declined",
+            ),
+            TaskTransferStatus::Cancelled => (
+                PushNotificationKind::TransferDeclined,
+                "A task transfer to you was withdrawn",
+            ),
+        };
+        let body = match &transfer.note {
+            Some(note) if transfer.status == TaskTransferStatus::Pending => {
+                format!("{}: {}", task.title, note)
+            }
+            _ => task.title.clone(),
+        };
+        Self {
+            user_id: transfer.counterpart(),
+            kind,
+            title: title.to_string(),
+            body,
+            story_id: task.story_id,
+            task_id: Some(task.id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscription_keys_are_validated() {
+        let user_id = Uuid::new_v4();
+        let p256dh = URL_SAFE_NO_PAD.encode([[0x04u8].as_slice(), &[7u8; 64]].concat());
+        let auth = URL_SAFE_NO_PAD.encode([9u8; 16]);
+
+        let subscription = PushSubscription::new(
+            user_id,
+            " https://push.example.com/send/abc ".to_string(),
+            format!("{}=", p256dh),
+            auth.clone(),
+        )
+        .unwrap();
+        assert_eq!(subscription.endpoint, "https://push.example.com/send/abc");
+        assert_eq!(subscription.p256dh, p256dh);
+        assert_eq!(subscription.auth_bytes().unwrap(), vec![9u8; 16]);
+
+        assert!(PushSubscription::new(
+            user_id,
+            "http://push.example.com/send/abc".to_string(),
+            p256dh.clone(),
+            auth.clone(),
+        )
+        .is_err());
+        assert!(PushSubscription::new(
+            user_id,
+            "https://push.example.com/send/abc".to_string(),
+            p256dh,
+            URL_SAFE_NO_PAD.encode([9u8; 8]),
+        )
+        .is_err());
+    }
+}
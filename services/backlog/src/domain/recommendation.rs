@@ -233,6 +233,7 @@ mod tests {
     fn create_test_task(title: &str, estimated_hours: Option<u32>, days_old: i64) -> Task {
         Task {
             id: Uuid::new_v4(),
+            short_key: None,
             story_id: Uuid::new_v4(),
             organization_id: None,
             title: title.to_string(),
@@ -245,6 +246,7 @@ mod tests {
             updated_at: Utc::now(),
             owned_at: None,
             completed_at: None,
+            split_from_task_id: None,
         }
     }
 
@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use common::references::ExternalSourceType;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::ShortKeyTarget;
+
+/// Where a mention was written: another story or task, or something on GitHub
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceSourceType {
+    Story,
+    Task,
+    Commit,
+    PullRequest,
+    Comment,
+}
+
+impl ReferenceSourceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReferenceSourceType::Story => "story",
+            ReferenceSourceType::Task => "task",
+            ReferenceSourceType::Commit => "commit",
+            ReferenceSourceType::PullRequest => "pull_request",
+            ReferenceSourceType::Comment => "comment",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "story" => Some(ReferenceSourceType::Story),
+            "task" => Some(ReferenceSourceType::Task),
+            "commit" => Some(ReferenceSourceType::Commit),
+            "pull_request" => Some(ReferenceSourceType::PullRequest),
+            "comment" => Some(ReferenceSourceType::Comment),
+            _ => None,
+        }
+    }
+}
+
+impl From<ShortKeyTarget> for ReferenceSourceType {
+    fn from(target: ShortKeyTarget) -> Self {
+        match target {
+            ShortKeyTarget::Story => ReferenceSourceType::Story,
+            ShortKeyTarget::Task => ReferenceSourceType::Task,
+        }
+    }
+}
+
+impl From<ExternalSourceType> for ReferenceSourceType {
+    fn from(source: ExternalSourceType) -> Self {
+        match source {
+            ExternalSourceType::Commit => ReferenceSourceType::Commit,
+            ExternalSourceType::PullRequest => ReferenceSourceType::PullRequest,
+            ExternalSourceType::Comment => ReferenceSourceType::Comment,
+        }
+    }
+}
+
+/// A story or task found by its short key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferencedItem {
+    pub key: String,
+    pub target: ShortKeyTarget,
+    pub id: Uuid,
+    pub organization_id: Option<Uuid>,
+}
+
+/// Items a story or task may link to: same organization, and never itself
+pub fn linkable_items(
+    source_id: Uuid,
+    organization_id: Option<Uuid>,
+    items: Vec<ReferencedItem>,
+) -> Vec<ReferencedItem> {
+    items
+        .into_iter()
+        .filter(|item| item.id != source_id && item.organization_id == organization_id)
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceDirection {
+    /// The story (or one of its tasks) mentions the other item
+    Outgoing,
+    /// The other item, commit, pull request or comment mentions the story or one of its tasks
+    Incoming,
+}
+
+/// One link as seen from a story. Internal ends carry their current key and title.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemReference {
+    pub id: Uuid,
+    pub direction: ReferenceDirection,
+    pub source_type: ReferenceSourceType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    pub source_title: Option<String>,
+    pub target_type: ShortKeyTarget,
+    pub target_id: Uuid,
+    pub target_key: Option<String>,
+    pub target_title: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linkable_items_skips_self_and_other_orgs() {
+        let org = Some(Uuid::new_v4());
+        let source = Uuid::new_v4();
+        let item = |id, organization_id| ReferencedItem {
+            key: "PROJ-1".to_string(),
+            target: ShortKeyTarget::Story,
+            id,
+            organization_id,
+        };
+        let other = Uuid::new_v4();
+
+        let linked = linkable_items(
+            source,
+            org,
+            vec![
+                item(source, org),
+                item(other, org),
+                item(Uuid::new_v4(), Some(Uuid::new_v4())),
+                item(Uuid::new_v4(), None),
+            ],
+        );
+        assert_eq!(linked.len(), 1);
+        assert_eq!(linked[0].id, other);
+    }
+}
@@ -0,0 +1,25 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+pub use common::references::is_short_key;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortKeyTarget {
+    Story,
+    Task,
+}
+
+/// What a short key points at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedShortKey {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub target: ShortKeyTarget,
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Parent story, for task keys
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub story_id: Option<Uuid>,
+}
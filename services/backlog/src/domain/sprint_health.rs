@@ -0,0 +1,560 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::domain::standup::blocked_reason;
+use crate::domain::{StandupItem, Story, StoryStatus, Task, TaskStatus};
+
+/// Below this share of the sprint, burn and unstarted work are too early to judge
+const EARLY_SPRINT: f64 = 0.1;
+const MIDPOINT: f64 = 0.5;
+/// Completed share of the work over the elapsed share of the sprint
+const AMBER_BURN_RATIO: f64 = 0.8;
+const RED_BURN_RATIO: f64 = 0.5;
+const RED_BLOCKED_TASKS: usize = 3;
+const RED_BLOCKED_SHARE: f64 = 0.25;
+/// Points moved in or out since the start over the points committed at the start
+const AMBER_CHURN: f64 = 0.2;
+const RED_CHURN: f64 = 0.4;
+/// The busiest contributor's open tasks over the team average
+const AMBER_IMBALANCE: f64 = 1.75;
+const RED_IMBALANCE: f64 = 2.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthRating {
+    Green,
+    Amber,
+    Red,
+}
+
+impl HealthRating {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Green => "green",
+            Self::Amber => "amber",
+            Self::Red => "red",
+        }
+    }
+}
+
+/// The sprint being checked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SprintPeriod {
+    pub sprint_id: Uuid,
+    pub name: String,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+}
+
+/// A story moved into or out of a sprint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeChange {
+    pub story_id: Uuid,
+    pub story_points: Option<u32>,
+    pub added: bool,
+    /// Sprint status at the time of the change
+    pub sprint_status: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BurnRate {
+    /// Share of the sprint's time gone
+    pub elapsed: f64,
+    /// Share of the sprint's tasks completed
+    pub completed: f64,
+    /// `completed / elapsed`; 1.0 is on the ideal line
+    pub ratio: Option<f64>,
+    pub rating: HealthRating,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnstartedStory {
+    pub story_id: Uuid,
+    pub title: String,
+    pub story_points: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeChurn {
+    /// Points committed when the sprint started
+    pub baseline_points: u32,
+    pub added_points: u32,
+    pub removed_points: u32,
+    pub stories_added: usize,
+    pub stories_removed: usize,
+    /// Points moved in or out over the baseline
+    pub ratio: f64,
+    pub rating: HealthRating,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberLoad {
+    pub user_id: Uuid,
+    /// Owned and in-progress tasks
+    pub open_tasks: u32,
+    pub remaining_hours: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadBalance {
+    /// Busiest first
+    pub members: Vec<MemberLoad>,
+    /// The busiest member's open tasks over the average
+    pub imbalance: Option<f64>,
+    pub rating: HealthRating,
+}
+
+/// Mid-sprint risk: how the sprint is burning against the ideal line and what
+/// puts the remaining work at risk
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintHealth {
+    pub sprint_id: Uuid,
+    pub sprint_name: String,
+    pub generated_at: DateTime<Utc>,
+    /// The worst rating of the checks below
+    pub rating: HealthRating,
+    pub burn: BurnRate,
+    pub blocked_tasks: Vec<StandupItem>,
+    pub blocked_rating: HealthRating,
+    pub unstarted_stories: Vec<UnstartedStory>,
+    pub unstarted_rating: HealthRating,
+    pub scope_churn: ScopeChurn,
+    pub load: LoadBalance,
+    pub recommendations: Vec<String>,
+}
+
+impl SprintHealth {
+    /// Assess `sprint` from its stories and tasks, the team's contributors and
+    /// the sprint's scope changes
+    pub fn assess(
+        sprint: &SprintPeriod,
+        stories: &[Story],
+        tasks: &[Task],
+        contributors: &[Uuid],
+        scope_changes: &[ScopeChange],
+        now: DateTime<Utc>,
+    ) -> Self {
+        let tasks: Vec<&Task> = tasks
+            .iter()
+            .filter(|task| task.status != TaskStatus::Superseded)
+            .collect();
+
+        let burn = burn_rate(sprint, &tasks, now);
+        let blocked_tasks = blocked_tasks(stories, &tasks);
+        let open_tasks = tasks.iter().filter(|task| is_open(task)).count();
+        let blocked_rating = if blocked_tasks.is_empty() {
+            HealthRating::Green
+        } else if blocked_tasks.len() >= RED_BLOCKED_TASKS
+            || blocked_tasks.len() as f64 >= open_tasks as f64 * RED_BLOCKED_SHARE
+        {
+            HealthRating::Red
+        } else {
+            HealthRating::Amber
+        };
+
+        let unstarted_stories = unstarted_stories(stories, &tasks);
+        let unstarted_rating = if burn.elapsed < MIDPOINT || unstarted_stories.is_empty() {
+            HealthRating::Green
+        } else if unstarted_stories.len() * 2 >= stories.len() {
+            HealthRating::Red
+        } else {
+            HealthRating::Amber
+        };
+
+        let scope_churn = scope_churn(sprint, stories, scope_changes);
+        let load = load_balance(contributors, &tasks);
+
+        let rating = [
+            burn.rating,
+            blocked_rating,
+            unstarted_rating,
+            scope_churn.rating,
+            load.rating,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(HealthRating::Green);
+
+        let mut health = Self {
+            sprint_id: sprint.sprint_id,
+            sprint_name: sprint.name.clone(),
+            generated_at: now,
+            rating,
+            burn,
+            blocked_tasks,
+            blocked_rating,
+            unstarted_stories,
+            unstarted_rating,
+            scope_churn,
+            load,
+            recommendations: Vec::new(),
+        };
+        health.recommendations = health.recommend();
+        health
+    }
+
+    fn recommend(&self) -> Vec<String> {
+        let mut recommendations = Vec::new();
+        if self.burn.rating != HealthRating::Green {
+            recommendations.push(format!(
+                "{:.0}% of the work is done with {:.0}% of the sprint gone; swarm on stories in progress or cut scope",
+                self.burn.completed * 100.0,
+                self.burn.elapsed * 100.0
+            ));
+        }
+        if self.blocked_rating != HealthRating::Green {
+            recommendations.push(format!(
+                "Unblock {} task(s), starting with {}",
+                self.blocked_tasks.len(),
+                self.blocked_tasks[0].title
+            ));
+        }
+        if self.unstarted_rating != HealthRating::Green {
+            recommendations.push(format!(
+                "{} committed story(ies) have not started; start them now or move them back to the backlog",
+                self.unstarted_stories.len()
+            ));
+        }
+        if self.scope_churn.rating != HealthRating::Green {
+            recommendations.push(format!(
+                "Scope has changed by {:.0}% since the sprint started; hold new work for the next sprint",
+                self.scope_churn.ratio * 100.0
+            ));
+        }
+        if self.load.rating != HealthRating::Green {
+            recommendations.push(
+                "Work is uneven across the team; hand open tasks from the busiest members to those with capacity"
+                    .to_string(),
+            );
+        }
+        recommendations
+    }
+
+    /// Plain-text report for Slack
+    pub fn digest(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "Sprint health for {}: {}",
+                self.sprint_name,
+                self.rating.as_str().to_uppercase()
+            ),
+            format!(
+                "- Burn: {:.0}% done, {:.0}% of time gone ({})",
+                self.burn.completed * 100.0,
+                self.burn.elapsed * 100.0,
+                self.burn.rating.as_str()
+            ),
+            format!(
+                "- Blocked tasks: {} ({})",
+                self.blocked_tasks.len(),
+                self.blocked_rating.as_str()
+            ),
+            format!(
+                "- Unstarted stories: {} ({})",
+                self.unstarted_stories.len(),
+                self.unstarted_rating.as_str()
+            ),
+            format!(
+                "- Scope churn: +{} / -{} points on {} ({})",
+                self.scope_churn.added_points,
+                self.scope_churn.removed_points,
+                self.scope_churn.baseline_points,
+                self.scope_churn.rating.as_str()
+            ),
+            format!("- Load balance: {}", self.load.rating.as_str()),
+        ];
+        if !self.recommendations.is_empty() {
+            lines.push("Recommendations:".to_string());
+            for recommendation in &self.recommendations {
+                lines.push(format!("- {}", recommendation));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+fn is_open(task: &Task) -> bool {
+    matches!(task.status, TaskStatus::Owned | TaskStatus::InProgress)
+}
+
+fn burn_rate(sprint: &SprintPeriod, tasks: &[&Task], now: DateTime<Utc>) -> BurnRate {
+    let length = (sprint.end_date - sprint.start_date).num_seconds();
+    let elapsed = if length > 0 {
+        ((now - sprint.start_date).num_seconds() as f64 / length as f64).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    let completed = if tasks.is_empty() {
+        0.0
+    } else {
+        tasks
+            .iter()
+            .filter(|task| task.status == TaskStatus::Completed)
+            .count() as f64
+            / tasks.len() as f64
+    };
+    let ratio = (elapsed > 0.0).then(|| completed / elapsed);
+    let rating = match ratio {
+        _ if elapsed < EARLY_SPRINT => HealthRating::Green,
+        Some(ratio) if ratio < RED_BURN_RATIO => HealthRating::Red,
+        Some(ratio) if ratio < AMBER_BURN_RATIO => HealthRating::Amber,
+        _ => HealthRating::Green,
+    };
+    BurnRate {
+        elapsed,
+        completed,
+        ratio,
+        rating,
+    }
+}
+
+fn blocked_tasks(stories: &[Story], tasks: &[&Task]) -> Vec<StandupItem> {
+    tasks
+        .iter()
+        .filter(|task| is_open(task))
+        .filter_map(|task| {
+            let story = stories.iter().find(|story| story.id == task.story_id)?;
+            let reason = blocked_reason(story, task)?;
+            Some(StandupItem {
+                task_id: task.id,
+                title: task.title.clone(),
+                story_id: story.id,
+                story_title: story.title.clone(),
+                status: task.status.to_string(),
+                reason: Some(reason),
+            })
+        })
+        .collect()
+}
+
+/// Committed stories none of whose tasks has been picked up
+fn unstarted_stories(stories: &[Story], tasks: &[&Task]) -> Vec<UnstartedStory> {
+    stories
+        .iter()
+        .filter(|story| matches!(story.status, StoryStatus::Ready | StoryStatus::Committed))
+        .filter(|story| {
+            tasks
+                .iter()
+                .filter(|task| task.story_id == story.id)
+                .all(|task| task.status == TaskStatus::Available)
+        })
+        .map(|story| UnstartedStory {
+            story_id: story.id,
+            title: story.title.clone(),
+            story_points: story.story_points,
+        })
+        .collect()
+}
+
+fn scope_churn(sprint: &SprintPeriod, stories: &[Story], changes: &[ScopeChange]) -> ScopeChurn {
+    let mut churn = ScopeChurn {
+        baseline_points: 0,
+        added_points: 0,
+        removed_points: 0,
+        stories_added: 0,
+        stories_removed: 0,
+        ratio: 0.0,
+        rating: HealthRating::Green,
+    };
+    for change in changes
+        .iter()
+        .filter(|change| change.sprint_status == "active" && change.changed_at >= sprint.start_date)
+    {
+        let points = change.story_points.unwrap_or(0);
+        if change.added {
+            churn.added_points += points;
+            churn.stories_added += 1;
+        } else {
+            churn.removed_points += points;
+            churn.stories_removed += 1;
+        }
+    }
+
+    let committed: u32 = stories.iter().filter_map(|story| story.story_points).sum();
+    churn.baseline_points = (committed + churn.removed_points).saturating_sub(churn.added_points);
+    let moved = churn.added_points + churn.removed_points;
+    churn.ratio = moved as f64 / churn.baseline_points.max(1) as f64;
+    churn.rating = if churn.ratio > RED_CHURN {
+        HealthRating::Red
+    } else if churn.ratio > AMBER_CHURN {
+        HealthRating::Amber
+    } else {
+        HealthRating::Green
+    };
+    churn
+}
+
+/// Open work per contributor. Owners outside the team still count.
+fn load_balance(contributors: &[Uuid], tasks: &[&Task]) -> LoadBalance {
+    let mut loads: BTreeMap<Uuid, MemberLoad> = contributors
+        .iter()
+        .map(|user_id| {
+            (
+                *user_id,
+                MemberLoad {
+                    user_id: *user_id,
+                    open_tasks: 0,
+                    remaining_hours: 0,
+                },
+            )
+        })
+        .collect();
+    for task in tasks.iter().filter(|task| is_open(task)) {
+        let Some(owner) = task.owner_user_id else {
+            continue;
+        };
+        let load = loads.entry(owner).or_insert(MemberLoad {
+            user_id: owner,
+            open_tasks: 0,
+            remaining_hours: 0,
+        });
+        load.open_tasks += 1;
+        load.remaining_hours += task.estimated_hours.unwrap_or(0);
+    }
+
+    let mut members: Vec<MemberLoad> = loads.into_values().collect();
+    members.sort_by_key(|member| std::cmp::Reverse(member.open_tasks));
+    let total: u32 = members.iter().map(|member| member.open_tasks).sum();
+    let imbalance = (members.len() > 1 && total > 0).then(|| {
+        let average = total as f64 / members.len() as f64;
+        members[0].open_tasks as f64 / average
+    });
+    let rating = match imbalance {
+        Some(imbalance) if imbalance >= RED_IMBALANCE => HealthRating::Red,
+        Some(imbalance) if imbalance >= AMBER_IMBALANCE => HealthRating::Amber,
+        _ => HealthRating::Green,
+    };
+    LoadBalance {
+        members,
+        imbalance,
+        rating,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn sprint(now: DateTime<Utc>, elapsed_days: i64) -> SprintPeriod {
+        SprintPeriod {
+            sprint_id: Uuid::new_v4(),
+            name: "Sprint 7".to_string(),
+            start_date: now - Duration::days(elapsed_days),
+            end_date: now - Duration::days(elapsed_days) + Duration::days(10),
+        }
+    }
+
+    fn story(title: &str, status: StoryStatus, points: u32) -> Story {
+        let mut story = Story::new(Uuid::new_v4(), None, title.to_string(), None).unwrap();
+        story.status = status;
+        story.story_points = Some(points);
+        story
+    }
+
+    fn task(story: &Story, owner: Option<Uuid>, status: TaskStatus) -> Task {
+        let mut task = Task::new(
+            story.id,
+            None,
+            format!("{} task", story.title),
+            None,
+            vec!["AC1".to_string()],
+        )
+        .unwrap();
+        task.owner_user_id = owner;
+        task.status = status;
+        task
+    }
+
+    #[test]
+    fn test_on_track_sprint_is_green() {
+        let now = Utc::now();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let checkout = story("Checkout", StoryStatus::InProgress, 5);
+        let tasks = vec![
+            task(&checkout, Some(alice), TaskStatus::Completed),
+            task(&checkout, Some(bob), TaskStatus::InProgress),
+            task(&checkout, Some(alice), TaskStatus::InProgress),
+        ];
+
+        let health = SprintHealth::assess(
+            &sprint(now, 3),
+            &[checkout],
+            &tasks,
+            &[alice, bob],
+            &[],
+            now,
+        );
+        assert_eq!(health.rating, HealthRating::Green);
+        assert!(health.recommendations.is_empty());
+        assert_eq!(health.load.members.len(), 2);
+    }
+
+    #[test]
+    fn test_late_sprint_with_blockers_and_churn_is_red() {
+        let now = Utc::now();
+        let period = sprint(now, 6);
+        let (alice, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let checkout = story("Checkout", StoryStatus::InProgress, 5);
+        let refunds = story("Refunds", StoryStatus::NeedsRefinement, 3);
+        let invoices = story("Invoices", StoryStatus::Committed, 8);
+        let tasks = vec![
+            task(&checkout, Some(alice), TaskStatus::Completed),
+            task(&checkout, Some(alice), TaskStatus::InProgress),
+            task(&checkout, Some(alice), TaskStatus::Owned),
+            task(&refunds, Some(alice), TaskStatus::InProgress),
+            task(&invoices, None, TaskStatus::Available),
+        ];
+        let changes = vec![
+            ScopeChange {
+                story_id: invoices.id,
+                story_points: Some(8),
+                added: true,
+                sprint_status: "active".to_string(),
+                changed_at: now - Duration::days(1),
+            },
+            ScopeChange {
+                story_id: checkout.id,
+                story_points: Some(5),
+                added: true,
+                sprint_status: "planning".to_string(),
+                changed_at: period.start_date - Duration::days(1),
+            },
+        ];
+
+        let health = SprintHealth::assess(
+            &period,
+            &[checkout, refunds, invoices],
+            &tasks,
+            &[alice, bob, carol],
+            &changes,
+            now,
+        );
+        assert_eq!(health.rating, HealthRating::Red);
+        assert_eq!(health.burn.rating, HealthRating::Red);
+        assert_eq!(health.blocked_tasks.len(), 1);
+        assert_eq!(health.unstarted_stories.len(), 1);
+        assert_eq!(health.unstarted_stories[0].title, "Invoices");
+        assert_eq!(
+            (
+                health.scope_churn.baseline_points,
+                health.scope_churn.added_points
+            ),
+            (8, 8)
+        );
+        assert_eq!(health.scope_churn.rating, HealthRating::Red);
+        assert_eq!(health.load.members[0].user_id, alice);
+        assert_eq!(health.load.rating, HealthRating::Red);
+        assert_eq!(health.recommendations.len(), 5);
+        assert!(health.digest().contains("Sprint health for Sprint 7: RED"));
+    }
+}
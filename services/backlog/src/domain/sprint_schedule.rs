@@ -0,0 +1,355 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::domain::StoryStatus;
+
+pub const MIN_SPRINT_LENGTH_DAYS: u32 = 7;
+pub const MAX_SPRINT_LENGTH_DAYS: u32 = 28;
+pub const MAX_SCHEDULED_SPRINTS: u32 = 12;
+
+/// How often a team's sprints start and how long they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SprintCadence {
+    pub length_days: u32,
+    pub start_weekday: Weekday,
+}
+
+impl Default for SprintCadence {
+    fn default() -> Self {
+        Self {
+            length_days: 14,
+            start_weekday: Weekday::Mon,
+        }
+    }
+}
+
+impl SprintCadence {
+    pub fn new(length_days: u32, start_weekday: Weekday) -> Result<Self, AppError> {
+        if !(MIN_SPRINT_LENGTH_DAYS..=MAX_SPRINT_LENGTH_DAYS).contains(&length_days) {
+            return Err(AppError::BadRequest(format!(
+                "Sprint length must be between {} and {} days",
+                MIN_SPRINT_LENGTH_DAYS, MAX_SPRINT_LENGTH_DAYS
+            )));
+        }
+        Ok(Self {
+            length_days,
+            start_weekday,
+        })
+    }
+}
+
+/// What happens to a sprint's unfinished stories when it is completed on cadence
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SprintRollover {
+    /// Carry them into the team's next sprint
+    #[default]
+    NextSprint,
+    /// Return stories nobody started to the product backlog; started ones
+    /// still carry into the next sprint
+    Backlog,
+}
+
+impl SprintRollover {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NextSprint => "next_sprint",
+            Self::Backlog => "backlog",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "next_sprint" => Some(Self::NextSprint),
+            "backlog" => Some(Self::Backlog),
+            _ => None,
+        }
+    }
+}
+
+/// An organization's default cadence, and whether its sprints are started and
+/// completed on it automatically
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SprintCadenceSettings {
+    pub cadence: SprintCadence,
+    pub auto_activate: bool,
+    pub rollover: SprintRollover,
+}
+
+/// A planning or active sprint, as the cadence job sees it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CadenceSprint {
+    pub id: Uuid,
+    pub status: String,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+}
+
+/// What the cadence job does to one team's sprints
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SprintTransitions {
+    /// Active sprints that have ended
+    pub complete: Vec<Uuid>,
+    /// The planned sprint to start, when no sprint stays active
+    pub activate: Option<Uuid>,
+    /// Where unfinished stories of the completed sprints go
+    pub roll_over_to: Option<Uuid>,
+}
+
+impl SprintTransitions {
+    pub fn is_empty(&self) -> bool {
+        self.complete.is_empty() && self.activate.is_none()
+    }
+}
+
+/// Decide which of a team's sprints end and which starts at `now`. The planned
+/// sprint whose window contains `now` is activated once no sprint is active;
+/// planned sprints whose window has already passed are left for the team.
+pub fn plan_sprint_transitions(sprints: &[CadenceSprint], now: DateTime<Utc>) -> SprintTransitions {
+    let (ended, active): (Vec<&CadenceSprint>, Vec<&CadenceSprint>) = sprints
+        .iter()
+        .filter(|sprint| sprint.status == "active")
+        .partition(|sprint| sprint.end_date <= now);
+    let mut planned: Vec<&CadenceSprint> = sprints
+        .iter()
+        .filter(|sprint| sprint.status == "planning" && sprint.end_date > now)
+        .collect();
+    planned.sort_by_key(|sprint| sprint.start_date);
+
+    let activate = if active.is_empty() {
+        planned
+            .iter()
+            .rev()
+            .find(|sprint| sprint.start_date <= now)
+            .map(|sprint| sprint.id)
+    } else {
+        None
+    };
+    let roll_over_to = activate
+        .or_else(|| active.first().map(|sprint| sprint.id))
+        .or_else(|| {
+            planned
+                .iter()
+                .find(|sprint| sprint.start_date > now)
+                .map(|sprint| sprint.id)
+        });
+
+    SprintTransitions {
+        complete: ended.iter().map(|sprint| sprint.id).collect(),
+        activate,
+        roll_over_to,
+    }
+}
+
+/// Where a story of a completed sprint goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RolloverTarget {
+    Sprint(Uuid),
+    Backlog,
+}
+
+/// Where `rollover` sends a story with `status` when its sprint completes, or
+/// `None` when it stays: accepted stories count towards the completed sprint,
+/// and started stories with no next sprint are left for the team to move
+pub fn rollover_target(
+    rollover: SprintRollover,
+    status: &StoryStatus,
+    next_sprint: Option<Uuid>,
+) -> Option<RolloverTarget> {
+    let started = !matches!(status, StoryStatus::Committed | StoryStatus::Ready);
+    match (status, next_sprint) {
+        (StoryStatus::Accepted, _) => None,
+        (_, Some(next)) if started || rollover == SprintRollover::NextSprint => {
+            Some(RolloverTarget::Sprint(next))
+        }
+        _ if started => None,
+        _ => Some(RolloverTarget::Backlog),
+    }
+}
+
+/// Local dates of one scheduled sprint, both inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedSprint {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Monday to Friday in the sprint, excluding holidays
+    pub working_days: u32,
+}
+
+/// A sprint created by the scheduler, with its bounds in UTC
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledSprint {
+    pub id: Uuid,
+    pub name: String,
+    pub status: String,
+    pub capacity_points: u32,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub working_days: u32,
+}
+
+fn is_weekend(day: NaiveDate) -> bool {
+    matches!(day.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Lay out `count` back-to-back sprints on the cadence, starting on the first
+/// cadence weekday on or after `from`. A sprint that would start on a holiday
+/// starts on the next working day instead, and one that would end on a holiday
+/// ends the working day before, so the cadence itself never drifts.
+pub fn plan_sprints(
+    cadence: SprintCadence,
+    from: NaiveDate,
+    count: u32,
+    holidays: &HashSet<NaiveDate>,
+) -> Result<Vec<PlannedSprint>, AppError> {
+    if !(1..=MAX_SCHEDULED_SPRINTS).contains(&count) {
+        return Err(AppError::BadRequest(format!(
+            "Between 1 and {} sprints can be scheduled at once",
+            MAX_SCHEDULED_SPRINTS
+        )));
+    }
+
+    let offset = (cadence.start_weekday.num_days_from_monday() + 7
+        - from.weekday().num_days_from_monday())
+        % 7;
+    let anchor = from + Duration::days(offset as i64);
+    let length = Duration::days(cadence.length_days as i64);
+
+    let sprints = (0..count as i32)
+        .map(|index| {
+            let nominal_start = anchor + length * index;
+            let mut start = nominal_start;
+            while holidays.contains(&start) || (start != nominal_start && is_weekend(start)) {
+                start += Duration::days(1);
+            }
+
+            let mut end = nominal_start + length - Duration::days(1);
+            while holidays.contains(&end) && end > start {
+                end -= Duration::days(1);
+            }
+
+            let working_days = start
+                .iter_days()
+                .take_while(|day| *day <= end)
+                .filter(|day| !is_weekend(*day) && !holidays.contains(day))
+                .count() as u32;
+
+            PlannedSprint {
+                start,
+                end,
+                working_days,
+            }
+        })
+        .collect();
+
+    Ok(sprints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_sprints_follow_cadence_from_next_start_weekday() {
+        // Wednesday 3 December 2025; the next Monday is the 8th
+        let sprints = plan_sprints(
+            SprintCadence::default(),
+            date(2025, 12, 3),
+            2,
+            &HashSet::new(),
+        )
+        .unwrap();
+
+        assert_eq!(sprints[0].start, date(2025, 12, 8));
+        assert_eq!(sprints[0].end, date(2025, 12, 21));
+        assert_eq!(sprints[0].working_days, 10);
+        assert_eq!(sprints[1].start, date(2025, 12, 22));
+        assert_eq!(sprints[1].end, date(2026, 1, 4));
+    }
+
+    #[test]
+    fn test_ended_sprint_completes_and_next_starts() {
+        let now = Utc::now();
+        let sprint = |status: &str, start_days: i64, end_days: i64| CadenceSprint {
+            id: Uuid::new_v4(),
+            status: status.to_string(),
+            start_date: now + Duration::days(start_days),
+            end_date: now + Duration::days(end_days),
+        };
+        let ended = sprint("active", -14, -1);
+        let missed = sprint("planning", -10, -2);
+        let current = sprint("planning", -1, 13);
+        let later = sprint("planning", 13, 27);
+
+        let transitions = plan_sprint_transitions(
+            &[later.clone(), ended.clone(), missed, current.clone()],
+            now,
+        );
+        assert_eq!(transitions.complete, vec![ended.id]);
+        assert_eq!(transitions.activate, Some(current.id));
+        assert_eq!(transitions.roll_over_to, Some(current.id));
+
+        // Nothing starts while a sprint is still running
+        let running = sprint("active", -3, 11);
+        let transitions = plan_sprint_transitions(&[running.clone(), current, later.clone()], now);
+        assert!(transitions.is_empty());
+        assert_eq!(transitions.roll_over_to, Some(running.id));
+
+        let next = Some(later.id);
+        assert_eq!(
+            rollover_target(SprintRollover::Backlog, &StoryStatus::Committed, next),
+            Some(RolloverTarget::Backlog)
+        );
+        assert_eq!(
+            rollover_target(SprintRollover::Backlog, &StoryStatus::InProgress, next),
+            Some(RolloverTarget::Sprint(later.id))
+        );
+        assert_eq!(
+            rollover_target(SprintRollover::NextSprint, &StoryStatus::Committed, next),
+            Some(RolloverTarget::Sprint(later.id))
+        );
+        assert_eq!(
+            rollover_target(SprintRollover::NextSprint, &StoryStatus::Accepted, next),
+            None
+        );
+        assert_eq!(
+            rollover_target(SprintRollover::NextSprint, &StoryStatus::InProgress, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_holidays_move_boundaries_without_drifting_the_cadence() {
+        // Christmas falls on the Thursday, Boxing Day on the Friday
+        let holidays: HashSet<NaiveDate> = [
+            date(2025, 12, 25),
+            date(2025, 12, 26),
+            date(2026, 1, 5),
+            date(2026, 1, 18),
+        ]
+        .into_iter()
+        .collect();
+        let cadence = SprintCadence::new(14, Weekday::Mon).unwrap();
+
+        let sprints = plan_sprints(cadence, date(2025, 12, 22), 2, &holidays).unwrap();
+        assert_eq!(sprints[0].start, date(2025, 12, 22));
+        assert_eq!(sprints[0].working_days, 8);
+        // Monday 5 January is a holiday, so the sprint starts on Tuesday and
+        // its Sunday end on the 18th is pulled back to Saturday
+        assert_eq!(sprints[1].start, date(2026, 1, 6));
+        assert_eq!(sprints[1].end, date(2026, 1, 17));
+        assert_eq!(sprints[1].working_days, 9);
+
+        assert!(SprintCadence::new(3, Weekday::Mon).is_err());
+        assert!(plan_sprints(cadence, date(2025, 12, 22), 0, &holidays).is_err());
+    }
+}
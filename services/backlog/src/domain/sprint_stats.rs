@@ -0,0 +1,91 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Snapshots older than this are recomputed on read, which covers events
+/// missed while the projector was down or published by another replica
+pub const SPRINT_STATS_MAX_AGE_SECS: i64 = 300;
+
+/// Totals for a sprint board, counted over the sprint's live stories and
+/// their tasks other than superseded ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SprintStatSnapshot {
+    pub sprint_id: Uuid,
+    pub total_stories: i64,
+    pub total_tasks: i64,
+    pub completed_tasks: i64,
+    pub total_points: i64,
+    /// Points of accepted stories
+    pub completed_points: i64,
+    pub refreshed_at: DateTime<Utc>,
+}
+
+impl SprintStatSnapshot {
+    pub fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        now - self.refreshed_at <= Duration::seconds(SPRINT_STATS_MAX_AGE_SECS)
+    }
+
+    pub fn progress_percentage(&self) -> f64 {
+        if self.total_tasks > 0 {
+            (self.completed_tasks as f64 / self.total_tasks as f64) * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsSource {
+    /// Read from the background snapshot
+    Snapshot,
+    /// Computed for this request because the snapshot was missing or stale
+    Computed,
+}
+
+/// How current the stats in a response are
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsFreshness {
+    pub source: StatsSource,
+    pub refreshed_at: DateTime<Utc>,
+    pub age_seconds: i64,
+}
+
+impl StatsFreshness {
+    pub fn new(source: StatsSource, snapshot: &SprintStatSnapshot, now: DateTime<Utc>) -> Self {
+        Self {
+            source,
+            refreshed_at: snapshot.refreshed_at,
+            age_seconds: (now - snapshot.refreshed_at).num_seconds().max(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshots_go_stale_after_max_age() {
+        let now = Utc::now();
+        let mut snapshot = SprintStatSnapshot {
+            sprint_id: Uuid::new_v4(),
+            total_stories: 2,
+            total_tasks: 4,
+            completed_tasks: 1,
+            total_points: 8,
+            completed_points: 3,
+            refreshed_at: now - Duration::seconds(30),
+        };
+        assert!(snapshot.is_fresh(now));
+        assert_eq!(snapshot.progress_percentage(), 25.0);
+        let freshness = StatsFreshness::new(StatsSource::Snapshot, &snapshot, now);
+        assert_eq!(freshness.age_seconds, 30);
+
+        snapshot.refreshed_at = now - Duration::seconds(SPRINT_STATS_MAX_AGE_SECS + 1);
+        assert!(!snapshot.is_fresh(now));
+        snapshot.total_tasks = 0;
+        assert_eq!(snapshot.progress_percentage(), 0.0);
+    }
+}
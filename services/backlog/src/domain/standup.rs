@@ -0,0 +1,231 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::domain::{Story, StoryStatus, Task, TaskStatus};
+
+/// A task as it appears in a standup post
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandupItem {
+    pub task_id: Uuid,
+    pub title: String,
+    pub story_id: Uuid,
+    pub story_title: String,
+    pub status: String,
+    /// Why the item is blocked; only set for blocked items
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// What one contributor did and is doing. `user_id` is `None` for unowned work.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberStandup {
+    pub user_id: Option<Uuid>,
+    pub completed: Vec<StandupItem>,
+    pub in_progress: Vec<StandupItem>,
+    pub blocked: Vec<StandupItem>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandupSummary {
+    pub sprint_id: Uuid,
+    pub sprint_name: String,
+    pub since: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub members: Vec<MemberStandup>,
+    /// Tasks added to the sprint since the last standup
+    pub new_scope: Vec<StandupItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub narrative: Option<String>,
+}
+
+impl StandupSummary {
+    pub fn build(
+        sprint_id: Uuid,
+        sprint_name: String,
+        since: DateTime<Utc>,
+        stories: &[Story],
+        tasks: &[Task],
+    ) -> Self {
+        let mut members: BTreeMap<Option<Uuid>, MemberStandup> = BTreeMap::new();
+        let mut new_scope = Vec::new();
+
+        for task in tasks {
+            let Some(story) = stories.iter().find(|story| story.id == task.story_id) else {
+                continue;
+            };
+            let item = |reason: Option<String>| StandupItem {
+                task_id: task.id,
+                title: task.title.clone(),
+                story_id: story.id,
+                story_title: story.title.clone(),
+                status: task.status.to_string(),
+                reason,
+            };
+
+            if task.created_at >= since {
+                new_scope.push(item(None));
+            }
+
+            let member = members
+                .entry(task.owner_user_id)
+                .or_insert_with(|| MemberStandup {
+                    user_id: task.owner_user_id,
+                    completed: Vec::new(),
+                    in_progress: Vec::new(),
+                    blocked: Vec::new(),
+                });
+
+            match task.status {
+                TaskStatus::Completed => {
+                    if task.completed_at.is_some_and(|at| at >= since) {
+                        member.completed.push(item(None));
+                    }
+                }
+                TaskStatus::Owned | TaskStatus::InProgress => match blocked_reason(story, task) {
+                    Some(reason) => member.blocked.push(item(Some(reason))),
+                    None => member.in_progress.push(item(None)),
+                },
+                TaskStatus::Available | TaskStatus::Superseded => {}
+            }
+        }
+
+        // Owned members first, in a stable order, then unowned work
+        let (mut members, unowned): (Vec<_>, Vec<_>) = members
+            .into_values()
+            .filter(|member| {
+                !(member.completed.is_empty()
+                    && member.in_progress.is_empty()
+                    && member.blocked.is_empty())
+            })
+            .partition(|member| member.user_id.is_some());
+        members.extend(unowned);
+
+        Self {
+            sprint_id,
+            sprint_name,
+            since,
+            generated_at: Utc::now(),
+            members,
+            new_scope,
+            narrative: None,
+        }
+    }
+
+    /// Plain-text digest used as narrative input and as a Slack-friendly fallback
+    pub fn digest(&self) -> String {
+        let mut lines = vec![format!(
+            "Standup for {} since {}",
+            self.sprint_name,
+            self.since.format("%Y-%m-%d %H:%M UTC")
+        )];
+        for member in &self.members {
+            let who = member
+                .user_id
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| "Unowned".to_string());
+            lines.push(format!("{}:", who));
+            for (label, items) in [
+                ("Done", &member.completed),
+                ("Doing", &member.in_progress),
+                ("Blocked", &member.blocked),
+            ] {
+                for item in items {
+                    let reason = item
+                        .reason
+                        .as_ref()
+                        .map(|reason| format!(" ({})", reason))
+                        .unwrap_or_default();
+                    lines.push(format!(
+                        "- {}: {} [{}]{}",
+                        label, item.title, item.story_title, reason
+                    ));
+                }
+            }
+        }
+        if !self.new_scope.is_empty() {
+            lines.push(format!("New scope: {} task(s)", self.new_scope.len()));
+            for item in &self.new_scope {
+                lines.push(format!("- {} [{}]", item.title, item.story_title));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+pub(crate) fn blocked_reason(story: &Story, task: &Task) -> Option<String> {
+    if task.is_blocked() {
+        return Some("task has no owner".to_string());
+    }
+    match story.status {
+        StoryStatus::NeedsRefinement => Some("story needs refinement".to_string()),
+        StoryStatus::AwaitingAcceptance => Some("story is awaiting acceptance".to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn task(story: &Story, owner: Option<Uuid>, status: TaskStatus) -> Task {
+        let mut task = Task::new(
+            story.id,
+            None,
+            format!("{} task", status),
+            None,
+            vec!["AC1".to_string()],
+        )
+        .unwrap();
+        task.owner_user_id = owner;
+        task.status = status;
+        task
+    }
+
+    #[test]
+    fn test_build_groups_work_by_member() {
+        let since = Utc::now() - Duration::hours(24);
+        let story = Story::new(Uuid::new_v4(), None, "Checkout".to_string(), None).unwrap();
+        let mut blocked_story =
+            Story::new(Uuid::new_v4(), None, "Refunds".to_string(), None).unwrap();
+        blocked_story.status = StoryStatus::NeedsRefinement;
+        let alice = Uuid::new_v4();
+
+        let mut done = task(&story, Some(alice), TaskStatus::Completed);
+        done.completed_at = Some(Utc::now());
+        done.created_at = since - Duration::hours(1);
+        let mut old_done = task(&story, Some(alice), TaskStatus::Completed);
+        old_done.completed_at = Some(since - Duration::hours(2));
+        old_done.created_at = since - Duration::hours(3);
+        let doing = task(&story, Some(alice), TaskStatus::InProgress);
+        let stuck = task(&blocked_story, Some(alice), TaskStatus::Owned);
+        let available = task(&story, None, TaskStatus::Available);
+
+        let summary = StandupSummary::build(
+            Uuid::new_v4(),
+            "Sprint 1".to_string(),
+            since,
+            &[story, blocked_story],
+            &[done, old_done, doing, stuck, available],
+        );
+
+        assert_eq!(summary.members.len(), 1);
+        let member = &summary.members[0];
+        assert_eq!(member.user_id, Some(alice));
+        assert_eq!(member.completed.len(), 1);
+        assert_eq!(member.in_progress.len(), 1);
+        assert_eq!(member.blocked.len(), 1);
+        assert_eq!(
+            member.blocked[0].reason.as_deref(),
+            Some("story needs refinement")
+        );
+        // doing, stuck and available were created after `since`
+        assert_eq!(summary.new_scope.len(), 3);
+        assert!(summary.digest().contains("Blocked: owned task"));
+    }
+}
@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use common::i18n::LocalizedMessage;
 use common::AppError;
 use serde::{Deserialize, Serialize};
 use tracing::info;
@@ -104,6 +105,47 @@ impl std::fmt::Display for StoryStatus {
     }
 }
 
+/// What kind of work a story is; bugs and spikes have their own Ready bar
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StoryType {
+    #[default]
+    Feature,
+    /// Needs reproduction steps in its description to be ready
+    Bug,
+    /// Time-boxed research; needs a timebox instead of story points
+    Spike,
+    Chore,
+}
+
+impl StoryType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Feature => "feature",
+            Self::Bug => "bug",
+            Self::Spike => "spike",
+            Self::Chore => "chore",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "feature" => Some(Self::Feature),
+            "bug" => Some(Self::Bug),
+            "spike" => Some(Self::Spike),
+            "chore" => Some(Self::Chore),
+            _ => None,
+        }
+    }
+}
+
+/// Longest timebox a spike can have: two weeks of full-time work
+pub const MAX_SPIKE_TIMEBOX_HOURS: u32 = 80;
+
+/// Phrases one of which a bug's description needs before it can be ready
+pub const REPRODUCTION_STEP_MARKERS: [&str; 3] =
+    ["steps to reproduce", "reproduction steps", "repro steps"];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AcceptanceCriteria {
     pub id: Uuid,
@@ -111,6 +153,9 @@ pub struct AcceptanceCriteria {
     pub given: String, // Given context
     pub when: String,  // When action
     pub then: String,  // Then outcome
+    /// Zero-based display order within the story
+    #[serde(default)]
+    pub position: u32,
     pub created_at: DateTime<Utc>,
 }
 
@@ -148,28 +193,51 @@ impl AcceptanceCriteria {
             given: given.trim().to_string(),
             when: when.trim().to_string(),
             then: then.trim().to_string(),
+            position: 0,
             created_at: Utc::now(),
         })
     }
 }
 
+/// How a story stands against the Ready bar. `gaps` lists unmet requirements;
+/// an overridden story can be planned despite them.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessAnnotation {
+    pub meets_ready_bar: bool,
+    pub overridden: bool,
+    pub override_reason: Option<String>,
+    pub gaps: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Story {
     pub id: Uuid,
+    /// Per-project key such as `PROJ-123`, assigned when the story is stored
+    #[serde(default)]
+    pub short_key: Option<String>,
     pub project_id: Uuid,
     pub organization_id: Option<Uuid>,
     pub title: String,
     pub description: Option<String>,
     pub status: StoryStatus,
+    #[serde(default)]
+    pub story_type: StoryType,
     pub labels: Vec<String>,
     pub acceptance_criteria: Vec<AcceptanceCriteria>,
     pub story_points: Option<u32>,
+    /// How long a spike may take, in hours
+    #[serde(default)]
+    pub timebox_hours: Option<u32>,
     pub sprint_id: Option<Uuid>,
     pub assigned_to_user_id: Option<Uuid>, // Product Owner or Managing Contributor who owns this story
     pub readiness_override: bool,
     pub readiness_override_by: Option<Uuid>,
     pub readiness_override_reason: Option<String>,
     pub readiness_override_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the story was flagged for sitting in draft untouched too long
+    #[serde(default)]
+    pub stale_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -182,34 +250,38 @@ impl Story {
         description: Option<String>,
     ) -> Result<Self, AppError> {
         if title.trim().is_empty() {
-            return Err(AppError::BadRequest(
-                "Story title cannot be empty".to_string(),
-            ));
+            return Err(AppError::invalid(LocalizedMessage::new(
+                "story.title_required",
+            )));
         }
 
         if title.trim().len() > 255 {
-            return Err(AppError::BadRequest(
-                "Story title cannot exceed 255 characters".to_string(),
+            return Err(AppError::invalid(
+                LocalizedMessage::new("story.title_too_long").with("max", 255),
             ));
         }
 
         let now = Utc::now();
         Ok(Self {
             id: Uuid::new_v4(),
+            short_key: None,
             project_id,
             organization_id,
             title: title.trim().to_string(),
             description,
             status: StoryStatus::Draft,
+            story_type: StoryType::default(),
             labels: Vec::new(),
             acceptance_criteria: Vec::new(),
             story_points: None,
+            timebox_hours: None,
             sprint_id: None,
             assigned_to_user_id: None,
             readiness_override: false,
             readiness_override_by: None,
             readiness_override_reason: None,
             readiness_override_at: None,
+            stale_at: None,
             created_at: now,
             updated_at: now,
         })
@@ -250,44 +322,73 @@ impl Story {
 
     /// Validate that story meets requirements to be marked as Ready
     fn validate_ready_requirements(&self) -> Result<(), AppError> {
+        match self.readiness_gaps().into_iter().next() {
+            Some(gap) => Err(AppError::BadRequest(gap)),
+            None => Ok(()),
+        }
+    }
+
+    /// Every requirement for Ready the story misses, most fundamental first
+    pub fn readiness_gaps(&self) -> Vec<String> {
+        let mut gaps = Vec::new();
+
         // Must have at least 3 acceptance criteria
         if self.acceptance_criteria.len() < 3 {
-            return Err(AppError::BadRequest(format!(
+            gaps.push(format!(
                 "Story must have at least 3 acceptance criteria. Currently has: {}",
                 self.acceptance_criteria.len()
-            )));
-        }
-
-        // Must have story points
-        if self.story_points.is_none() {
-            return Err(AppError::BadRequest(
-                "Story must have story points to be ready".to_string(),
             ));
         }
 
-        // Story points must be <= 8 (opinionated maximum)
-        if let Some(points) = self.story_points {
-            if points > 8 {
-                return Err(AppError::BadRequest(format!(
-                    "Story points cannot exceed 8. Current: {}",
-                    points
-                )));
+        // Spikes are time-boxed rather than estimated; everything else needs
+        // story points, at most 8 (opinionated maximum)
+        if self.story_type == StoryType::Spike {
+            if self.timebox_hours.is_none() {
+                gaps.push("Spike must have a timebox to be ready".to_string());
             }
-            if points == 0 {
-                return Err(AppError::BadRequest(
-                    "Story points must be greater than 0".to_string(),
-                ));
+        } else {
+            match self.story_points {
+                None => gaps.push("Story must have story points to be ready".to_string()),
+                Some(0) => gaps.push("Story points must be greater than 0".to_string()),
+                Some(points) if points > 8 => {
+                    gaps.push(format!("Story points cannot exceed 8. Current: {}", points))
+                }
+                Some(_) => {}
             }
         }
 
-        // Must have a description
-        if self.description.is_none() || self.description.as_ref().unwrap().trim().is_empty() {
-            return Err(AppError::BadRequest(
-                "Story must have a description to be ready".to_string(),
-            ));
+        // Must have a description; a bug's must say how to reproduce it
+        match self
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+        {
+            None => gaps.push("Story must have a description to be ready".to_string()),
+            Some(description) if self.story_type == StoryType::Bug => {
+                let description = description.to_lowercase();
+                if !REPRODUCTION_STEP_MARKERS
+                    .iter()
+                    .any(|marker| description.contains(marker))
+                {
+                    gaps.push("Bug must describe its steps to reproduce to be ready".to_string());
+                }
+            }
+            Some(_) => {}
         }
 
-        Ok(())
+        gaps
+    }
+
+    /// Where the story stands against the Ready bar
+    pub fn readiness_annotation(&self) -> ReadinessAnnotation {
+        let gaps = self.readiness_gaps();
+        ReadinessAnnotation {
+            meets_ready_bar: gaps.is_empty(),
+            overridden: self.readiness_override,
+            override_reason: self.readiness_override_reason.clone(),
+            gaps,
+        }
     }
 
     pub fn apply_readiness_override(&mut self, user_id: Uuid, reason: Option<String>) {
@@ -330,12 +431,118 @@ impl Story {
         self.clear_readiness_override();
     }
 
-    /// Add acceptance criteria to the story
-    pub fn add_acceptance_criteria(&mut self, ac: AcceptanceCriteria) {
+    /// Add acceptance criteria to the end of the story's list
+    pub fn add_acceptance_criteria(&mut self, mut ac: AcceptanceCriteria) {
+        ac.position = self.acceptance_criteria.len() as u32;
         self.acceptance_criteria.push(ac);
         self.updated_at = Utc::now();
     }
 
+    /// Reorder acceptance criteria; `ordered_ids` must list every criterion exactly once
+    pub fn reorder_acceptance_criteria(&mut self, ordered_ids: &[Uuid]) -> Result<(), AppError> {
+        let mut seen = std::collections::HashSet::new();
+        if ordered_ids.len() != self.acceptance_criteria.len()
+            || !ordered_ids.iter().all(|id| seen.insert(*id))
+        {
+            return Err(AppError::BadRequest(
+                "Reorder must list every acceptance criterion of the story exactly once"
+                    .to_string(),
+            ));
+        }
+
+        if let Some(id) = ordered_ids
+            .iter()
+            .find(|id| !self.acceptance_criteria.iter().any(|ac| ac.id == **id))
+        {
+            return Err(AppError::BadRequest(format!(
+                "Acceptance criterion {} does not belong to this story",
+                id
+            )));
+        }
+
+        let mut reordered = Vec::with_capacity(ordered_ids.len());
+        for id in ordered_ids {
+            if let Some(index) = self.acceptance_criteria.iter().position(|ac| ac.id == *id) {
+                reordered.push(self.acceptance_criteria.swap_remove(index));
+            }
+        }
+        self.acceptance_criteria = reordered;
+        self.renumber_acceptance_criteria();
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Apply creates, edits and deletes to the acceptance criteria as a single change.
+    ///
+    /// Everything is validated before the story is touched, so a failing batch leaves
+    /// it unchanged. New criteria are appended in request order. Returns the IDs of the
+    /// created criteria.
+    pub fn apply_acceptance_criteria_batch(
+        &mut self,
+        batch: AcceptanceCriteriaBatch,
+    ) -> Result<Vec<Uuid>, AppError> {
+        let known = |id: &Uuid| self.acceptance_criteria.iter().any(|ac| ac.id == *id);
+        if let Some(id) = batch.delete.iter().find(|id| !known(id)) {
+            return Err(AppError::NotFound(format!(
+                "Acceptance criterion {} not found",
+                id
+            )));
+        }
+
+        let mut criteria = self.acceptance_criteria.clone();
+        for edit in batch.update {
+            if batch.delete.contains(&edit.id) {
+                return Err(AppError::BadRequest(format!(
+                    "Acceptance criterion {} cannot be both updated and deleted",
+                    edit.id
+                )));
+            }
+            let ac = criteria
+                .iter_mut()
+                .find(|ac| ac.id == edit.id)
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Acceptance criterion {} not found", edit.id))
+                })?;
+            for (clause, value, current) in [
+                ("given", edit.given, &mut ac.given),
+                ("when", edit.when, &mut ac.when),
+                ("then", edit.then, &mut ac.then),
+            ] {
+                if let Some(value) = value {
+                    if value.trim().is_empty() {
+                        return Err(AppError::BadRequest(format!(
+                            "AC '{}' cannot be empty",
+                            clause
+                        )));
+                    }
+                    *current = value.trim().to_string();
+                }
+            }
+            ac.description = format!("Given {}, when {}, then {}", ac.given, ac.when, ac.then);
+        }
+
+        criteria.retain(|ac| !batch.delete.contains(&ac.id));
+
+        let mut created = Vec::with_capacity(batch.create.len());
+        for new in batch.create {
+            let description = format!("Given {}, when {}, then {}", new.given, new.when, new.then);
+            let ac = AcceptanceCriteria::new(description, new.given, new.when, new.then)?;
+            created.push(ac.id);
+            criteria.push(ac);
+        }
+
+        self.acceptance_criteria = criteria;
+        self.renumber_acceptance_criteria();
+        self.updated_at = Utc::now();
+        Ok(created)
+    }
+
+    fn renumber_acceptance_criteria(&mut self) {
+        for (index, ac) in self.acceptance_criteria.iter_mut().enumerate() {
+            ac.position = index as u32;
+        }
+    }
+
     /// Remove acceptance criteria by ID
     pub fn remove_acceptance_criteria(&mut self, ac_id: Uuid) -> Result<(), AppError> {
         let initial_len = self.acceptance_criteria.len();
@@ -347,6 +554,7 @@ impl Story {
             ));
         }
 
+        self.renumber_acceptance_criteria();
         self.updated_at = Utc::now();
         Ok(())
     }
@@ -369,6 +577,29 @@ impl Story {
         Ok(())
     }
 
+    /// Change what kind of work the story is. Readiness is not re-checked;
+    /// a Ready story that no longer meets its type's bar stays Ready.
+    pub fn set_story_type(&mut self, story_type: StoryType) {
+        self.story_type = story_type;
+        self.updated_at = Utc::now();
+    }
+
+    /// Set or clear a spike's timebox
+    pub fn set_timebox_hours(&mut self, hours: Option<u32>) -> Result<(), AppError> {
+        if let Some(hours) = hours {
+            if hours == 0 || hours > MAX_SPIKE_TIMEBOX_HOURS {
+                return Err(AppError::BadRequest(format!(
+                    "Timeboxes must be between 1 and {} hours",
+                    MAX_SPIKE_TIMEBOX_HOURS
+                )));
+            }
+        }
+
+        self.timebox_hours = hours;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
     /// Assign story to a sprint (only allowed for Ready stories)
     pub fn assign_to_sprint(&mut self, sprint_id: Uuid) -> Result<(), AppError> {
         if !self.status.is_ready_for_sprint() {
@@ -411,6 +642,13 @@ impl Story {
         Ok(())
     }
 
+    /// Carry an unfinished story into the next sprint when its sprint
+    /// completes, keeping its progress
+    pub fn carry_over_to_sprint(&mut self, sprint_id: Uuid) {
+        self.sprint_id = Some(sprint_id);
+        self.updated_at = Utc::now();
+    }
+
     /// Assign story to a user (Product Owner or Managing Contributor)
     pub fn assign_to_user(&mut self, user_id: Uuid) {
         self.assigned_to_user_id = Some(user_id);
@@ -467,13 +705,13 @@ impl Story {
     ) -> Result<(), AppError> {
         if let Some(new_title) = title {
             if new_title.trim().is_empty() {
-                return Err(AppError::BadRequest(
-                    "Story title cannot be empty".to_string(),
-                ));
+                return Err(AppError::invalid(LocalizedMessage::new(
+                    "story.title_required",
+                )));
             }
             if new_title.trim().len() > 255 {
-                return Err(AppError::BadRequest(
-                    "Story title cannot exceed 255 characters".to_string(),
+                return Err(AppError::invalid(
+                    LocalizedMessage::new("story.title_too_long").with("max", 255),
                 ));
             }
             self.title = new_title.trim().to_string();
@@ -528,6 +766,31 @@ pub struct CreateAcceptanceCriteriaRequest {
     pub then: String,
 }
 
+/// Given/when/then clauses for a criterion created in a batch
+#[derive(Debug, Clone)]
+pub struct NewAcceptanceCriterion {
+    pub given: String,
+    pub when: String,
+    pub then: String,
+}
+
+/// Clause edits for an existing criterion; unset clauses are kept
+#[derive(Debug, Clone)]
+pub struct AcceptanceCriterionEdit {
+    pub id: Uuid,
+    pub given: Option<String>,
+    pub when: Option<String>,
+    pub then: Option<String>,
+}
+
+/// Acceptance criteria changes applied to a story in one operation
+#[derive(Debug, Clone, Default)]
+pub struct AcceptanceCriteriaBatch {
+    pub create: Vec<NewAcceptanceCriterion>,
+    pub update: Vec<AcceptanceCriterionEdit>,
+    pub delete: Vec<Uuid>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -647,6 +910,56 @@ mod tests {
         assert_eq!(story.status, StoryStatus::Ready);
     }
 
+    #[test]
+    fn test_readiness_annotation_lists_every_gap() {
+        let mut story = create_test_story();
+        story.description = Some("   ".to_string());
+
+        let annotation = story.readiness_annotation();
+        assert!(!annotation.meets_ready_bar);
+        assert_eq!(annotation.gaps.len(), 3);
+        assert!(annotation.gaps[0].contains("at least 3 acceptance criteria"));
+
+        story.description = Some("Proper description".to_string());
+        story.set_story_points(3).unwrap();
+        for _ in 0..3 {
+            story.add_acceptance_criteria(create_test_ac());
+        }
+        let annotation = story.readiness_annotation();
+        assert!(annotation.meets_ready_bar);
+        assert!(annotation.gaps.is_empty());
+        assert!(!annotation.overridden);
+    }
+
+    #[test]
+    fn test_spikes_and_bugs_have_their_own_ready_bar() {
+        let mut spike = create_test_story();
+        spike.set_story_type(StoryType::Spike);
+        for _ in 0..3 {
+            spike.add_acceptance_criteria(create_test_ac());
+        }
+        assert_eq!(
+            spike.readiness_gaps(),
+            vec!["Spike must have a timebox to be ready".to_string()]
+        );
+        assert!(spike
+            .set_timebox_hours(Some(MAX_SPIKE_TIMEBOX_HOURS + 1))
+            .is_err());
+        spike.set_timebox_hours(Some(16)).unwrap();
+        assert!(spike.readiness_gaps().is_empty());
+
+        let mut bug = create_test_story();
+        bug.set_story_type(StoryType::Bug);
+        bug.set_story_points(2).unwrap();
+        for _ in 0..3 {
+            bug.add_acceptance_criteria(create_test_ac());
+        }
+        assert_eq!(bug.readiness_gaps().len(), 1);
+        assert!(bug.readiness_gaps()[0].contains("steps to reproduce"));
+        bug.description = Some("Steps to reproduce:\n1. Sign in".to_string());
+        bug.update_status(StoryStatus::Ready).unwrap();
+    }
+
     #[test]
     fn test_story_points_validation() {
         let mut story = create_test_story();
@@ -695,6 +1008,102 @@ mod tests {
         assert!(story.remove_acceptance_criteria(Uuid::new_v4()).is_err());
     }
 
+    #[test]
+    fn test_reorder_acceptance_criteria() {
+        let mut story = create_test_story();
+        for _ in 0..3 {
+            story.add_acceptance_criteria(create_test_ac());
+        }
+        let ids: Vec<Uuid> = story.acceptance_criteria.iter().map(|ac| ac.id).collect();
+
+        story
+            .reorder_acceptance_criteria(&[ids[2], ids[0], ids[1]])
+            .unwrap();
+        let reordered: Vec<(Uuid, u32)> = story
+            .acceptance_criteria
+            .iter()
+            .map(|ac| (ac.id, ac.position))
+            .collect();
+        assert_eq!(reordered, vec![(ids[2], 0), (ids[0], 1), (ids[1], 2)]);
+
+        // Partial, duplicated or foreign ID lists are rejected
+        assert!(story
+            .reorder_acceptance_criteria(&[ids[0], ids[1]])
+            .is_err());
+        assert!(story
+            .reorder_acceptance_criteria(&[ids[0], ids[0], ids[1]])
+            .is_err());
+        assert!(story
+            .reorder_acceptance_criteria(&[ids[0], ids[1], Uuid::new_v4()])
+            .is_err());
+
+        story.remove_acceptance_criteria(ids[2]).unwrap();
+        assert_eq!(story.acceptance_criteria[0].position, 0);
+        assert_eq!(story.acceptance_criteria[1].position, 1);
+    }
+
+    #[test]
+    fn test_acceptance_criteria_batch() {
+        let mut story = create_test_story();
+        story.add_acceptance_criteria(create_test_ac());
+        story.add_acceptance_criteria(create_test_ac());
+        let ids: Vec<Uuid> = story.acceptance_criteria.iter().map(|ac| ac.id).collect();
+
+        let created = story
+            .apply_acceptance_criteria_batch(AcceptanceCriteriaBatch {
+                create: vec![NewAcceptanceCriterion {
+                    given: "a draft".to_string(),
+                    when: "it is saved".to_string(),
+                    then: "it persists".to_string(),
+                }],
+                update: vec![AcceptanceCriterionEdit {
+                    id: ids[1],
+                    given: None,
+                    when: Some("they log in twice".to_string()),
+                    then: None,
+                }],
+                delete: vec![ids[0]],
+            })
+            .unwrap();
+
+        assert_eq!(story.acceptance_criteria.len(), 2);
+        assert_eq!(story.acceptance_criteria[0].id, ids[1]);
+        assert_eq!(story.acceptance_criteria[0].when, "they log in twice");
+        assert_eq!(story.acceptance_criteria[1].id, created[0]);
+        assert_eq!(story.acceptance_criteria[1].position, 1);
+    }
+
+    #[test]
+    fn test_invalid_acceptance_criteria_batch_leaves_story_unchanged() {
+        let mut story = create_test_story();
+        story.add_acceptance_criteria(create_test_ac());
+        let id = story.acceptance_criteria[0].id;
+
+        let result = story.apply_acceptance_criteria_batch(AcceptanceCriteriaBatch {
+            create: vec![NewAcceptanceCriterion {
+                given: " ".to_string(),
+                when: "x".to_string(),
+                then: "y".to_string(),
+            }],
+            update: Vec::new(),
+            delete: vec![id],
+        });
+        assert!(result.is_err());
+        assert_eq!(story.acceptance_criteria.len(), 1);
+
+        let result = story.apply_acceptance_criteria_batch(AcceptanceCriteriaBatch {
+            update: vec![AcceptanceCriterionEdit {
+                id,
+                given: None,
+                when: None,
+                then: None,
+            }],
+            delete: vec![id],
+            ..Default::default()
+        });
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
     #[test]
     fn test_sprint_assignment() {
         let mut story = create_test_story();
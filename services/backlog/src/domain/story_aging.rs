@@ -0,0 +1,80 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use super::TaskEvent;
+
+/// Drafts untouched for this many days are flagged as stale
+pub const DEFAULT_STALE_AFTER_DAYS: u32 = 30;
+
+/// Drafts last updated before this are stale
+pub fn stale_cutoff(now: DateTime<Utc>, after_days: u32) -> DateTime<Utc> {
+    now.checked_sub_signed(Duration::days(after_days.into()))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+/// A draft story that was just flagged as stale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleStory {
+    pub story_id: Uuid,
+    pub project_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub title: String,
+    /// Who created the story, from its first revision
+    pub created_by: Option<Uuid>,
+    pub stale_at: DateTime<Utc>,
+}
+
+impl StaleStory {
+    /// The alert for the story's creator. `archive_after_days` is how long a
+    /// stale draft may stay untouched before it is archived, if at all.
+    pub fn alert(&self, archive_after_days: Option<u32>, now: DateTime<Utc>) -> TaskEvent {
+        TaskEvent::StoryWentStale {
+            story_id: self.story_id,
+            project_id: self.project_id,
+            organization_id: self.organization_id,
+            title: self.title.clone(),
+            created_by: self.created_by,
+            stale_at: self.stale_at,
+            archive_at: archive_after_days.map(|days| self.stale_at + Duration::days(days.into())),
+            timestamp: now,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_alert_says_when_the_draft_will_be_archived() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 31, 12, 0, 0).unwrap();
+        assert_eq!(
+            stale_cutoff(now, 30),
+            Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap()
+        );
+
+        let stale = StaleStory {
+            story_id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            organization_id: Some(Uuid::new_v4()),
+            title: "Export invoices".to_string(),
+            created_by: Some(Uuid::new_v4()),
+            stale_at: now,
+        };
+        match stale.alert(Some(14), now) {
+            TaskEvent::StoryWentStale { archive_at, .. } => assert_eq!(
+                archive_at,
+                Some(Utc.with_ymd_and_hms(2025, 4, 14, 12, 0, 0).unwrap())
+            ),
+            other => panic!("expected a stale alert, got {:?}", other),
+        }
+        assert!(matches!(
+            stale.alert(None, now),
+            TaskEvent::StoryWentStale {
+                archive_at: None,
+                ..
+            }
+        ));
+    }
+}
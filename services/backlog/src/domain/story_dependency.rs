@@ -0,0 +1,200 @@
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use uuid::Uuid;
+
+use crate::domain::Story;
+
+/// Error code of a dependency that would make stories block each other in a loop
+pub const DEPENDENCY_CYCLE: &str = "STORY_DEPENDENCY_CYCLE";
+
+/// How the other story of a dependency relates to the one it is recorded on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyDirection {
+    /// The story blocks the other one
+    Blocks,
+    /// The story waits on the other one
+    BlockedBy,
+}
+
+/// `story_id` cannot be delivered before `blocked_by_id` is accepted.
+/// Recorded explicitly, unlike the dependencies implied by short-key mentions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryDependency {
+    pub story_id: Uuid,
+    pub blocked_by_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoryDependency {
+    /// The dependency between `story_id` and `other_id`, read from `story_id`'s side
+    pub fn new(
+        story_id: Uuid,
+        other_id: Uuid,
+        direction: DependencyDirection,
+        organization_id: Option<Uuid>,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, AppError> {
+        if story_id == other_id {
+            return Err(AppError::BadRequest(
+                "A story cannot depend on itself".to_string(),
+            ));
+        }
+        let (story_id, blocked_by_id) = match direction {
+            DependencyDirection::BlockedBy => (story_id, other_id),
+            DependencyDirection::Blocks => (other_id, story_id),
+        };
+        Ok(Self {
+            story_id,
+            blocked_by_id,
+            organization_id,
+            created_by,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Refuse the dependency when, together with the `existing` ones, stories
+    /// would end up blocking each other in a loop
+    pub fn ensure_acyclic(&self, existing: &[StoryDependency]) -> Result<(), AppError> {
+        let Some(cycle) = self.find_cycle(existing) else {
+            return Ok(());
+        };
+        Err(AppError::ConflictWithCode {
+            message: format!(
+                "Dependency would create a cycle: {}",
+                cycle
+                    .iter()
+                    .map(Uuid::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            ),
+            error_code: DEPENDENCY_CYCLE.to_string(),
+        })
+    }
+
+    /// The loop this dependency would close, from its story through the
+    /// stories blocking it back to the story
+    pub fn find_cycle(&self, existing: &[StoryDependency]) -> Option<Vec<Uuid>> {
+        let mut blockers: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for dependency in existing {
+            blockers
+                .entry(dependency.story_id)
+                .or_default()
+                .push(dependency.blocked_by_id);
+        }
+
+        // Breadth-first through whatever blocks the new blocker; reaching the
+        // story again means the story would be waiting on itself
+        let mut previous: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut seen = HashSet::from([self.blocked_by_id]);
+        let mut queue = VecDeque::from([self.blocked_by_id]);
+        while let Some(current) = queue.pop_front() {
+            if current == self.story_id {
+                let mut path = vec![current];
+                let mut step = current;
+                while let Some(before) = previous.get(&step) {
+                    path.push(*before);
+                    step = *before;
+                }
+                path.push(self.story_id);
+                path.reverse();
+                return Some(path);
+            }
+            for next in blockers
+                .get(&current)
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+            {
+                if seen.insert(*next) {
+                    previous.insert(*next, current);
+                    queue.push_back(*next);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The other story of a dependency, as listed on a story
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyLink {
+    pub story_id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DependencyLink {
+    pub fn new(story: &Story, dependency: &StoryDependency) -> Self {
+        Self {
+            story_id: story.id,
+            title: story.title.clone(),
+            status: story.status.to_string(),
+            created_at: dependency.created_at,
+        }
+    }
+}
+
+/// The stories a story blocks and the ones it waits on
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryDependencies {
+    pub story_id: Uuid,
+    pub blocks: Vec<DependencyLink>,
+    pub blocked_by: Vec<DependencyLink>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocked_by(story_id: Uuid, blocker: Uuid) -> StoryDependency {
+        StoryDependency::new(
+            story_id,
+            blocker,
+            DependencyDirection::BlockedBy,
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_direction_decides_which_story_waits() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let dependency =
+            StoryDependency::new(a, b, DependencyDirection::Blocks, None, None).unwrap();
+        assert_eq!((dependency.story_id, dependency.blocked_by_id), (b, a));
+        assert_eq!(blocked_by(a, b).blocked_by_id, b);
+        assert!(StoryDependency::new(a, a, DependencyDirection::Blocks, None, None).is_err());
+    }
+
+    #[test]
+    fn test_cycles_are_detected() {
+        let (a, b, c, d) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        // a waits on b, b waits on c
+        let existing = vec![blocked_by(a, b), blocked_by(b, c)];
+
+        assert_eq!(
+            blocked_by(c, a).find_cycle(&existing),
+            Some(vec![c, a, b, c])
+        );
+        assert!(matches!(
+            blocked_by(c, a).ensure_acyclic(&existing),
+            Err(AppError::ConflictWithCode { error_code, .. }) if error_code == DEPENDENCY_CYCLE
+        ));
+        assert!(blocked_by(c, d).ensure_acyclic(&existing).is_ok());
+        assert!(blocked_by(a, c).ensure_acyclic(&existing).is_ok());
+    }
+}
@@ -0,0 +1,446 @@
+use crate::domain::{StoryStatus, StoryType};
+use common::AppError;
+use uuid::Uuid;
+
+const MAX_QUERY_LENGTH: usize = 500;
+const MAX_CONDITIONS: usize = 20;
+const MAX_DEPTH: usize = 8;
+const MAX_TEXT_LENGTH: usize = 100;
+
+/// A parsed story filter such as
+/// `status:ready AND label:backend AND points:>=5 AND no:tasks`.
+///
+/// Conditions are `field:value` pairs combined with `AND`, `OR`, `NOT` (or a
+/// leading `-`) and parentheses; adjacent conditions are ANDed. Values with
+/// spaces are quoted: `title:"login page"`. Fields:
+///
+/// - `status:<status>`, e.g. `status:inprogress`
+/// - `type:<type>`: `feature`, `bug`, `spike` or `chore`
+/// - `label:<label>`, case-insensitive
+/// - `points:<n>`, `points:>=<n>` (also `>`, `<`, `<=`)
+/// - `sprint:<id>`, `assignee:<user id>`
+/// - `title:<text>`, matching the title or description
+/// - `no:<field>` / `has:<field>` for `tasks`, `points`, `assignee`,
+///   `sprint`, `labels` and `criteria`
+/// - `has:stale` for drafts flagged as stale
+/// - `readiness:<state>`: `ready`, `not-ready` or `overridden`, see
+///   [`ReadinessState`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoryFilter {
+    And(Vec<StoryFilter>),
+    Or(Vec<StoryFilter>),
+    Not(Box<StoryFilter>),
+    Condition(StoryCondition),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoryCondition {
+    Status(StoryStatus),
+    Type(StoryType),
+    Label(String),
+    Points(Comparison, u32),
+    Sprint(Uuid),
+    Assignee(Uuid),
+    Text(String),
+    Missing(StoryAttribute),
+    Readiness(ReadinessState),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// What `no:` and `has:` can test for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoryAttribute {
+    Tasks,
+    Points,
+    Assignee,
+    Sprint,
+    Labels,
+    AcceptanceCriteria,
+    /// Flagged as a stale draft
+    Stale,
+}
+
+/// Where a story stands against the Ready bar, as in
+/// [`crate::domain::Story::readiness_annotation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadinessState {
+    /// Meets every requirement for Ready
+    Ready,
+    /// Misses a requirement and has no override
+    NotReady,
+    /// Plannable despite any gaps because someone overrode readiness
+    Overridden,
+}
+
+impl ReadinessState {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().replace('_', "-").as_str() {
+            "ready" => Some(Self::Ready),
+            "not-ready" | "notready" => Some(Self::NotReady),
+            "overridden" => Some(Self::Overridden),
+            _ => None,
+        }
+    }
+}
+
+impl StoryFilter {
+    pub fn parse(query: &str) -> Result<Self, AppError> {
+        if query.chars().count() > MAX_QUERY_LENGTH {
+            return Err(invalid(format!(
+                "filters cannot exceed {} characters",
+                MAX_QUERY_LENGTH
+            )));
+        }
+        let tokens = tokenize(query)?;
+        if tokens.is_empty() {
+            return Err(invalid("the filter is empty".to_string()));
+        }
+
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            conditions: 0,
+        };
+        let filter = parser.or(0)?;
+        if let Some(token) = parser.tokens.get(parser.position) {
+            return Err(invalid(format!("unexpected '{}'", token.text())));
+        }
+        Ok(filter)
+    }
+
+    /// All of `filters`; a single filter is returned as is
+    pub fn all(mut filters: Vec<StoryFilter>) -> Self {
+        if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            Self::And(filters)
+        }
+    }
+}
+
+fn invalid(reason: String) -> AppError {
+    AppError::BadRequest(format!("Invalid story filter: {}", reason))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Term(String, String),
+}
+
+impl Token {
+    fn text(&self) -> String {
+        match self {
+            Self::Open => "(".to_string(),
+            Self::Close => ")".to_string(),
+            Self::And => "AND".to_string(),
+            Self::Or => "OR".to_string(),
+            Self::Not => "NOT".to_string(),
+            Self::Term(field, value) => format!("{}:{}", field, value),
+        }
+    }
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, AppError> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '-' => {
+                chars.next();
+                tokens.push(Token::Not);
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' || c == '"' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+
+                match word.as_str() {
+                    "AND" => tokens.push(Token::And),
+                    "OR" => tokens.push(Token::Or),
+                    "NOT" => tokens.push(Token::Not),
+                    _ => {
+                        let Some((field, value)) = word.split_once(':') else {
+                            return Err(invalid(format!("expected field:value, found '{}'", word)));
+                        };
+                        let value = if value.is_empty() && chars.peek() == Some(&'"') {
+                            chars.next();
+                            let quoted: String = chars.by_ref().take_while(|&c| c != '"').collect();
+                            quoted
+                        } else {
+                            value.to_string()
+                        };
+                        if value.trim().is_empty() {
+                            return Err(invalid(format!("'{}:' needs a value", field)));
+                        }
+                        tokens.push(Token::Term(field.to_lowercase(), value));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    conditions: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn or(&mut self, depth: usize) -> Result<StoryFilter, AppError> {
+        let mut operands = vec![self.and(depth)?];
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            operands.push(self.and(depth)?);
+        }
+        Ok(if operands.len() == 1 {
+            operands.remove(0)
+        } else {
+            StoryFilter::Or(operands)
+        })
+    }
+
+    fn and(&mut self, depth: usize) -> Result<StoryFilter, AppError> {
+        let mut operands = vec![self.unary(depth)?];
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.position += 1;
+                    operands.push(self.unary(depth)?);
+                }
+                Some(Token::Open | Token::Not | Token::Term(..)) => {
+                    operands.push(self.unary(depth)?);
+                }
+                _ => break,
+            }
+        }
+        Ok(StoryFilter::all(operands))
+    }
+
+    fn unary(&mut self, depth: usize) -> Result<StoryFilter, AppError> {
+        if depth > MAX_DEPTH {
+            return Err(invalid(format!(
+                "filters cannot be nested more than {} levels deep",
+                MAX_DEPTH
+            )));
+        }
+
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or_else(|| invalid("the filter ends unexpectedly".to_string()))?;
+        self.position += 1;
+
+        match token {
+            Token::Not => Ok(StoryFilter::Not(Box::new(self.unary(depth + 1)?))),
+            Token::Open => {
+                let inner = self.or(depth + 1)?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(invalid("missing ')'".to_string()));
+                }
+                self.position += 1;
+                Ok(inner)
+            }
+            Token::Term(field, value) => {
+                self.conditions += 1;
+                if self.conditions > MAX_CONDITIONS {
+                    return Err(invalid(format!(
+                        "filters can have at most {} conditions",
+                        MAX_CONDITIONS
+                    )));
+                }
+                condition(&field, &value)
+            }
+            other => Err(invalid(format!("unexpected '{}'", other.text()))),
+        }
+    }
+}
+
+fn condition(field: &str, value: &str) -> Result<StoryFilter, AppError> {
+    let condition = match field {
+        "status" => StoryCondition::Status(
+            StoryStatus::from_str(value)
+                .ok_or_else(|| invalid(format!("unknown status '{}'", value)))?,
+        ),
+        "type" => StoryCondition::Type(
+            StoryType::parse(value)
+                .ok_or_else(|| invalid(format!("unknown story type '{}'", value)))?,
+        ),
+        "label" => StoryCondition::Label(text(field, value)?),
+        "points" => {
+            let (comparison, number) = [
+                (">=", Comparison::Ge),
+                ("<=", Comparison::Le),
+                (">", Comparison::Gt),
+                ("<", Comparison::Lt),
+                ("=", Comparison::Eq),
+            ]
+            .into_iter()
+            .find_map(|(prefix, comparison)| {
+                value
+                    .strip_prefix(prefix)
+                    .map(|number| (comparison, number))
+            })
+            .unwrap_or((Comparison::Eq, value));
+            let points = number
+                .parse()
+                .map_err(|_| invalid(format!("'{}' is not a number of points", number)))?;
+            StoryCondition::Points(comparison, points)
+        }
+        "sprint" => StoryCondition::Sprint(id(field, value)?),
+        "assignee" => StoryCondition::Assignee(id(field, value)?),
+        "title" => StoryCondition::Text(text(field, value)?),
+        "no" => StoryCondition::Missing(attribute(value)?),
+        "readiness" => StoryCondition::Readiness(
+            ReadinessState::parse(value)
+                .ok_or_else(|| invalid(format!("unknown readiness '{}'", value)))?,
+        ),
+        "has" => {
+            return Ok(StoryFilter::Not(Box::new(StoryFilter::Condition(
+                StoryCondition::Missing(attribute(value)?),
+            ))))
+        }
+        _ => return Err(invalid(format!("unknown field '{}'", field))),
+    };
+    Ok(StoryFilter::Condition(condition))
+}
+
+fn text(field: &str, value: &str) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.chars().count() > MAX_TEXT_LENGTH {
+        return Err(invalid(format!(
+            "'{}:' values cannot exceed {} characters",
+            field, MAX_TEXT_LENGTH
+        )));
+    }
+    Ok(value.to_string())
+}
+
+fn id(field: &str, value: &str) -> Result<Uuid, AppError> {
+    Uuid::parse_str(value).map_err(|_| invalid(format!("'{}:' needs an id", field)))
+}
+
+fn attribute(value: &str) -> Result<StoryAttribute, AppError> {
+    match value.to_lowercase().as_str() {
+        "tasks" => Ok(StoryAttribute::Tasks),
+        "points" => Ok(StoryAttribute::Points),
+        "assignee" => Ok(StoryAttribute::Assignee),
+        "sprint" => Ok(StoryAttribute::Sprint),
+        "labels" => Ok(StoryAttribute::Labels),
+        "criteria" => Ok(StoryAttribute::AcceptanceCriteria),
+        "stale" => Ok(StoryAttribute::Stale),
+        _ => Err(invalid(format!("cannot test for '{}'", value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(condition: StoryCondition) -> StoryFilter {
+        StoryFilter::Condition(condition)
+    }
+
+    #[test]
+    fn test_parses_conditions_and_operators() {
+        let filter =
+            StoryFilter::parse("status:ready AND type:bug label:backend points:>=5 AND no:tasks")
+                .unwrap();
+        assert_eq!(
+            filter,
+            StoryFilter::And(vec![
+                condition(StoryCondition::Status(StoryStatus::Ready)),
+                condition(StoryCondition::Type(StoryType::Bug)),
+                condition(StoryCondition::Label("backend".to_string())),
+                condition(StoryCondition::Points(Comparison::Ge, 5)),
+                condition(StoryCondition::Missing(StoryAttribute::Tasks)),
+            ])
+        );
+
+        let filter =
+            StoryFilter::parse(r#"(label:api OR title:"login page") -has:assignee"#).unwrap();
+        assert_eq!(
+            filter,
+            StoryFilter::And(vec![
+                StoryFilter::Or(vec![
+                    condition(StoryCondition::Label("api".to_string())),
+                    condition(StoryCondition::Text("login page".to_string())),
+                ]),
+                StoryFilter::Not(Box::new(StoryFilter::Not(Box::new(condition(
+                    StoryCondition::Missing(StoryAttribute::Assignee)
+                ))))),
+            ])
+        );
+
+        assert_eq!(
+            StoryFilter::parse("readiness:not_ready").unwrap(),
+            condition(StoryCondition::Readiness(ReadinessState::NotReady))
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_filters() {
+        for query in [
+            "",
+            "backend",
+            "status:shipped",
+            "points:>=lots",
+            "priority:high",
+            "no:owner",
+            "sprint:current",
+            "(status:ready",
+            "status:ready OR",
+            "status:ready)",
+            "title:",
+            "readiness:maybe",
+        ] {
+            assert!(
+                matches!(StoryFilter::parse(query), Err(AppError::BadRequest(_))),
+                "{} should be rejected",
+                query
+            );
+        }
+
+        let too_many = vec!["label:a"; MAX_CONDITIONS + 1].join(" ");
+        assert!(StoryFilter::parse(&too_many).is_err());
+        let too_deep = format!("{}label:a{}", "(".repeat(12), ")".repeat(12));
+        assert!(StoryFilter::parse(&too_deep).is_err());
+    }
+}
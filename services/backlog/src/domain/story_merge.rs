@@ -0,0 +1,121 @@
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::{Story, StoryStatus};
+
+/// The link left when a duplicate story is merged into another. The
+/// duplicate is soft-deleted; its tasks, criteria and references now belong
+/// to the target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryMerge {
+    pub source_story_id: Uuid,
+    pub target_story_id: Uuid,
+    pub moved_task_ids: Vec<Uuid>,
+    pub moved_criteria: usize,
+    pub merged_by: Option<Uuid>,
+    pub merged_at: DateTime<Utc>,
+}
+
+/// Fold `duplicate`'s acceptance criteria and labels into `target`, keeping
+/// criterion ids so tasks that reference them stay valid. Returns how many
+/// criteria moved.
+pub fn absorb_duplicate(target: &mut Story, duplicate: &Story) -> Result<usize, AppError> {
+    if target.id == duplicate.id {
+        return Err(AppError::BadRequest(
+            "A story cannot be merged into itself".to_string(),
+        ));
+    }
+    if target.project_id != duplicate.project_id {
+        return Err(AppError::BadRequest(
+            "Only stories in the same project can be merged".to_string(),
+        ));
+    }
+    if target.status == StoryStatus::Accepted || duplicate.status == StoryStatus::Accepted {
+        return Err(AppError::Conflict(
+            "Accepted stories cannot be merged".to_string(),
+        ));
+    }
+
+    let position = target.acceptance_criteria.len() as u32;
+    target.acceptance_criteria.extend(
+        duplicate
+            .acceptance_criteria
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(offset, mut criterion)| {
+                criterion.position = position + offset as u32;
+                criterion
+            }),
+    );
+    for label in &duplicate.labels {
+        if !target.labels.contains(label) {
+            target.labels.push(label.clone());
+        }
+    }
+    if target.story_points.is_none() {
+        target.story_points = duplicate.story_points;
+    }
+    target.updated_at = Utc::now();
+    Ok(duplicate.acceptance_criteria.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::AcceptanceCriteria;
+
+    fn story(project_id: Uuid, title: &str, labels: &[&str]) -> Story {
+        let mut story = Story::new(project_id, None, title.to_string(), None).unwrap();
+        story.labels = labels.iter().map(|label| label.to_string()).collect();
+        story.acceptance_criteria.push(
+            AcceptanceCriteria::new(
+                format!("{} works", title),
+                "a shopper".to_string(),
+                "they check out".to_string(),
+                "the order is placed".to_string(),
+            )
+            .unwrap(),
+        );
+        story
+    }
+
+    #[test]
+    fn test_absorbs_criteria_labels_and_points() {
+        let project_id = Uuid::new_v4();
+        let mut target = story(project_id, "Checkout", &["payments"]);
+        let mut duplicate = story(project_id, "Pay for order", &["payments", "backend"]);
+        duplicate.story_points = Some(5);
+        let moved_criterion = duplicate.acceptance_criteria[0].id;
+
+        assert_eq!(absorb_duplicate(&mut target, &duplicate).unwrap(), 1);
+        assert_eq!(target.acceptance_criteria.len(), 2);
+        assert_eq!(target.acceptance_criteria[1].id, moved_criterion);
+        assert_eq!(target.acceptance_criteria[1].position, 1);
+        assert_eq!(target.labels, vec!["payments", "backend"]);
+        assert_eq!(target.story_points, Some(5));
+    }
+
+    #[test]
+    fn test_rejects_self_cross_project_and_accepted_merges() {
+        let project_id = Uuid::new_v4();
+        let mut target = story(project_id, "Checkout", &[]);
+
+        let itself = target.clone();
+        assert!(absorb_duplicate(&mut target, &itself).is_err());
+
+        let elsewhere = story(Uuid::new_v4(), "Checkout", &[]);
+        assert!(absorb_duplicate(&mut target, &elsewhere).is_err());
+
+        let mut accepted = story(project_id, "Checkout again", &[]);
+        accepted.status = StoryStatus::Accepted;
+        assert!(matches!(
+            absorb_duplicate(&mut target, &accepted),
+            Err(AppError::Conflict(_))
+        ));
+        assert_eq!(target.acceptance_criteria.len(), 1);
+    }
+}
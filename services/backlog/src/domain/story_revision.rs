@@ -0,0 +1,252 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::Story;
+
+/// The refinement-relevant fields of a story as they stood after one edit.
+/// Revisions are numbered from 1 per story.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryRevision {
+    pub id: Uuid,
+    pub story_id: Uuid,
+    pub revision: u32,
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub edited_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoryRevision {
+    /// Whether the story still has this revision's title, description and labels
+    pub fn matches(&self, story: &Story) -> bool {
+        self.title == story.title
+            && self.description == story.description
+            && self.labels == story.labels
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    Equal,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TitleChange {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptionChange {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Line-by-line diff for rendering inline
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelChange {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Field-level changes between two revisions of a story. A field is `None`
+/// when it is the same in both.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryRevisionDiff {
+    pub story_id: Uuid,
+    pub from_revision: u32,
+    pub to_revision: u32,
+    pub title: Option<TitleChange>,
+    pub description: Option<DescriptionChange>,
+    pub labels: Option<LabelChange>,
+}
+
+impl StoryRevisionDiff {
+    pub fn between(from: &StoryRevision, to: &StoryRevision) -> Self {
+        let title = (from.title != to.title).then(|| TitleChange {
+            from: from.title.clone(),
+            to: to.title.clone(),
+        });
+
+        let description = (from.description != to.description).then(|| DescriptionChange {
+            from: from.description.clone(),
+            to: to.description.clone(),
+            lines: diff_lines(
+                from.description.as_deref().unwrap_or_default(),
+                to.description.as_deref().unwrap_or_default(),
+            ),
+        });
+
+        let added: Vec<String> = to
+            .labels
+            .iter()
+            .filter(|label| !from.labels.contains(label))
+            .cloned()
+            .collect();
+        let removed: Vec<String> = from
+            .labels
+            .iter()
+            .filter(|label| !to.labels.contains(label))
+            .cloned()
+            .collect();
+        let labels =
+            (!added.is_empty() || !removed.is_empty()).then_some(LabelChange { added, removed });
+
+        Self {
+            story_id: to.story_id,
+            from_revision: from.revision,
+            to_revision: to.revision,
+            title,
+            description,
+            labels,
+        }
+    }
+}
+
+/// Longest-common-subsequence line diff, removals listed before additions
+fn diff_lines(from: &str, to: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = from.lines().collect();
+    let new: Vec<&str> = to.lines().collect();
+
+    // lcs[i][j] is the common subsequence length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let line = |op, text: &str| DiffLine {
+        op,
+        text: text.to_string(),
+    };
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(line(DiffOp::Equal, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(line(DiffOp::Removed, old[i]));
+            i += 1;
+        } else {
+            lines.push(line(DiffOp::Added, new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|text| line(DiffOp::Removed, text)));
+    lines.extend(new[j..].iter().map(|text| line(DiffOp::Added, text)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revision(
+        revision: u32,
+        title: &str,
+        description: Option<&str>,
+        labels: &[&str],
+    ) -> StoryRevision {
+        StoryRevision {
+            id: Uuid::new_v4(),
+            story_id: Uuid::nil(),
+            revision,
+            title: title.to_string(),
+            description: description.map(str::to_string),
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+            edited_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_only_changed_fields() {
+        let first = revision(1, "Login", Some("As a user"), &["auth", "web"]);
+        let second = revision(2, "Login", Some("As a user"), &["auth", "mobile"]);
+
+        let diff = StoryRevisionDiff::between(&first, &second);
+        assert!(diff.title.is_none());
+        assert!(diff.description.is_none());
+        let labels = diff.labels.unwrap();
+        assert_eq!(labels.added, vec!["mobile".to_string()]);
+        assert_eq!(labels.removed, vec!["web".to_string()]);
+        assert_eq!((diff.from_revision, diff.to_revision), (1, 2));
+    }
+
+    #[test]
+    fn test_description_diff_is_line_based() {
+        let first = revision(
+            1,
+            "Login",
+            Some("As a user\nI want to log in\nSo that"),
+            &[],
+        );
+        let second = revision(
+            3,
+            "Sign in",
+            Some("As a user\nI want to sign in\nSo that"),
+            &[],
+        );
+
+        let diff = StoryRevisionDiff::between(&first, &second);
+        assert_eq!(diff.title.unwrap().to, "Sign in");
+        let ops: Vec<(DiffOp, &str)> = diff
+            .description
+            .as_ref()
+            .unwrap()
+            .lines
+            .iter()
+            .map(|line| (line.op, line.text.as_str()))
+            .collect();
+        assert_eq!(
+            ops,
+            vec![
+                (DiffOp::Equal, "As a user"),
+                (DiffOp::Removed, "I want to log in"),
+                (DiffOp::Added, "I want to sign in"),
+                (DiffOp::Equal, "So that"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_added_description_is_all_additions() {
+        let first = revision(1, "Login", None, &[]);
+        let second = revision(2, "Login", Some("One\nTwo"), &[]);
+
+        let description = StoryRevisionDiff::between(&first, &second)
+            .description
+            .unwrap();
+        assert!(description.from.is_none());
+        assert!(description
+            .lines
+            .iter()
+            .all(|line| line.op == DiffOp::Added));
+        assert_eq!(description.lines.len(), 2);
+    }
+}
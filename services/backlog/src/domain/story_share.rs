@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Read-only access for another organization to one story, or to every
+/// story of a project when `story_id` is `None`. Used by agencies to show
+/// a client the work done for them. Revoked shares are kept for the record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryShare {
+    pub id: Uuid,
+    pub owner_organization_id: Uuid,
+    pub shared_with_organization_id: Uuid,
+    pub project_id: Uuid,
+    pub story_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl StoryShare {
+    pub fn new(
+        owner_organization_id: Uuid,
+        shared_with_organization_id: Uuid,
+        project_id: Uuid,
+        story_id: Option<Uuid>,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, AppError> {
+        if owner_organization_id == shared_with_organization_id {
+            return Err(AppError::BadRequest(
+                "Stories cannot be shared with their own organization".to_string(),
+            ));
+        }
+        Ok(Self {
+            id: Uuid::new_v4(),
+            owner_organization_id,
+            shared_with_organization_id,
+            project_id,
+            story_id,
+            created_by,
+            created_at: Utc::now(),
+            revoked_at: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stories_cannot_be_shared_with_their_own_organization() {
+        let (agency, client, project) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        assert!(StoryShare::new(agency, agency, project, None, None).is_err());
+
+        let share = StoryShare::new(agency, client, project, Some(Uuid::new_v4()), None).unwrap();
+        assert_eq!(share.shared_with_organization_id, client);
+        assert!(share.revoked_at.is_none());
+    }
+}
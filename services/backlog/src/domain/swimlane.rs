@@ -0,0 +1,343 @@
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::domain::{same_label, StoryType};
+
+const MAX_CUSTOM_FIELD_LENGTH: usize = 50;
+
+/// What the sprint task board groups its tasks into lanes by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SwimlaneGrouping {
+    #[default]
+    Story,
+    Status,
+    /// The task's owner
+    Assignee,
+    /// The story's labels; a story with several labels shows in each of their lanes
+    Label,
+    StoryType,
+    /// The value of the story's `field:value` labels for one field
+    CustomField,
+}
+
+impl SwimlaneGrouping {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Story => "story",
+            Self::Status => "status",
+            Self::Assignee => "assignee",
+            Self::Label => "label",
+            Self::StoryType => "story_type",
+            Self::CustomField => "custom_field",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().replace('_', "").as_str() {
+            "story" => Some(Self::Story),
+            "status" => Some(Self::Status),
+            "assignee" => Some(Self::Assignee),
+            "label" => Some(Self::Label),
+            "storytype" => Some(Self::StoryType),
+            "customfield" => Some(Self::CustomField),
+            _ => None,
+        }
+    }
+}
+
+/// How the board is split into lanes. Custom-field lanes name the field, so
+/// `component` groups stories labelled `component:payments`, `component:search`
+/// and so on.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Swimlanes {
+    pub group_by: SwimlaneGrouping,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_field: Option<String>,
+}
+
+impl Swimlanes {
+    pub fn new(group_by: SwimlaneGrouping, custom_field: Option<String>) -> Result<Self, AppError> {
+        if group_by != SwimlaneGrouping::CustomField {
+            return Ok(Self {
+                group_by,
+                custom_field: None,
+            });
+        }
+
+        let field = custom_field
+            .map(|field| field.trim().to_string())
+            .filter(|field| !field.is_empty())
+            .ok_or_else(|| {
+                AppError::BadRequest("Custom field swimlanes need a customField".to_string())
+            })?;
+        if field.contains(':') || field.chars().count() > MAX_CUSTOM_FIELD_LENGTH {
+            return Err(AppError::BadRequest(format!(
+                "customField must be at most {} characters and cannot contain ':'",
+                MAX_CUSTOM_FIELD_LENGTH
+            )));
+        }
+        Ok(Self {
+            group_by,
+            custom_field: Some(field),
+        })
+    }
+
+    /// Split `tasks` into lanes, ordered by lane label with the lane of tasks
+    /// that fit no other last
+    pub fn lanes(&self, tasks: &[LaneTask], stories: &HashMap<Uuid, LaneStory>) -> Vec<Swimlane> {
+        let mut lanes: Vec<Swimlane> = Vec::new();
+        let mut lane_stories: Vec<HashSet<Uuid>> = Vec::new();
+
+        for task in tasks {
+            let story = stories.get(&task.story_id);
+            for (key, label) in self.lane_keys(task, story) {
+                let index = match lanes.iter().position(|lane| match (&lane.key, &key) {
+                    (Some(existing), Some(key)) => same_label(existing, key),
+                    (None, None) => true,
+                    _ => false,
+                }) {
+                    Some(index) => index,
+                    None => {
+                        lanes.push(Swimlane {
+                            key,
+                            label,
+                            task_count: 0,
+                            story_points: 0,
+                            task_ids: Vec::new(),
+                        });
+                        lane_stories.push(HashSet::new());
+                        lanes.len() - 1
+                    }
+                };
+
+                let lane = &mut lanes[index];
+                lane.task_count += 1;
+                lane.task_ids.push(task.id);
+                if lane_stories[index].insert(task.story_id) {
+                    lane.story_points += story.and_then(|story| story.story_points).unwrap_or(0);
+                }
+            }
+        }
+
+        lanes.sort_by(|a, b| {
+            a.key
+                .is_none()
+                .cmp(&b.key.is_none())
+                .then_with(|| a.label.to_lowercase().cmp(&b.label.to_lowercase()))
+        });
+        lanes
+    }
+
+    /// The lanes a task belongs in, as (key, label); a `None` key is the lane
+    /// of tasks without a value
+    fn lane_keys(
+        &self,
+        task: &LaneTask,
+        story: Option<&LaneStory>,
+    ) -> Vec<(Option<String>, String)> {
+        let story_title = || {
+            story
+                .map(|story| story.title.clone())
+                .unwrap_or_else(|| "Unknown Story".to_string())
+        };
+        match self.group_by {
+            SwimlaneGrouping::Story => vec![(Some(task.story_id.to_string()), story_title())],
+            SwimlaneGrouping::Status => vec![(Some(task.status.clone()), task.status.clone())],
+            SwimlaneGrouping::Assignee => match task.owner_user_id {
+                Some(owner) => vec![(Some(owner.to_string()), owner.to_string())],
+                None => vec![(None, "Unassigned".to_string())],
+            },
+            SwimlaneGrouping::StoryType => {
+                let story_type = story
+                    .map(|story| story.story_type)
+                    .unwrap_or_default()
+                    .as_str();
+                vec![(Some(story_type.to_string()), story_type.to_string())]
+            }
+            SwimlaneGrouping::Label => {
+                let labels = story.map(|story| story.labels.as_slice()).unwrap_or(&[]);
+                if labels.is_empty() {
+                    return vec![(None, "No label".to_string())];
+                }
+                labels
+                    .iter()
+                    .map(|label| (Some(label.clone()), label.clone()))
+                    .collect()
+            }
+            SwimlaneGrouping::CustomField => {
+                let field = self.custom_field.as_deref().unwrap_or_default();
+                let values: Vec<(Option<String>, String)> = story
+                    .map(|story| story.labels.as_slice())
+                    .unwrap_or(&[])
+                    .iter()
+                    .filter_map(|label| label.split_once(':'))
+                    .filter(|(name, value)| {
+                        same_label(name.trim(), field) && !value.trim().is_empty()
+                    })
+                    .map(|(_, value)| (Some(value.trim().to_string()), value.trim().to_string()))
+                    .collect();
+                if values.is_empty() {
+                    vec![(None, format!("No {}", field))]
+                } else {
+                    values
+                }
+            }
+        }
+    }
+}
+
+/// The parts of a board task that decide its lane
+#[derive(Debug, Clone)]
+pub struct LaneTask {
+    pub id: Uuid,
+    pub story_id: Uuid,
+    pub status: String,
+    pub owner_user_id: Option<Uuid>,
+}
+
+/// The parts of a sprint story that decide its tasks' lanes
+#[derive(Debug, Clone)]
+pub struct LaneStory {
+    pub title: String,
+    pub labels: Vec<String>,
+    pub story_type: StoryType,
+    pub story_points: Option<u32>,
+}
+
+/// One lane of the task board. Points count each story once per lane, so a
+/// story whose tasks sit in several lanes adds its points to each of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Swimlane {
+    /// The story ID, status, owner ID, label, story type or field value the
+    /// lane holds; `null` for tasks without one
+    pub key: Option<String>,
+    pub label: String,
+    pub task_count: usize,
+    pub story_points: u32,
+    pub task_ids: Vec<Uuid>,
+}
+
+/// The swimlanes a user has saved for their task board
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardConfiguration {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    #[serde(skip)]
+    pub organization_id: Option<Uuid>,
+    #[serde(flatten)]
+    pub swimlanes: Swimlanes,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BoardConfiguration {
+    pub fn new(user_id: Uuid, organization_id: Option<Uuid>, swimlanes: Swimlanes) -> Self {
+        Self {
+            user_id,
+            organization_id,
+            swimlanes,
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_field_swimlanes_need_a_field() {
+        assert_eq!(
+            Swimlanes::new(SwimlaneGrouping::Label, Some("component".to_string())).unwrap(),
+            Swimlanes {
+                group_by: SwimlaneGrouping::Label,
+                custom_field: None,
+            }
+        );
+        assert_eq!(
+            Swimlanes::new(
+                SwimlaneGrouping::CustomField,
+                Some(" component ".to_string())
+            )
+            .unwrap()
+            .custom_field
+            .as_deref(),
+            Some("component")
+        );
+        assert!(Swimlanes::new(SwimlaneGrouping::CustomField, None).is_err());
+        assert!(Swimlanes::new(SwimlaneGrouping::CustomField, Some("a:b".to_string())).is_err());
+        assert_eq!(
+            SwimlaneGrouping::parse("storyType"),
+            Some(SwimlaneGrouping::StoryType)
+        );
+    }
+
+    #[test]
+    fn test_lanes_count_tasks_and_points() {
+        let (payments, search) = (Uuid::new_v4(), Uuid::new_v4());
+        let stories = HashMap::from([
+            (
+                payments,
+                LaneStory {
+                    title: "Pay by card".to_string(),
+                    labels: vec!["Component:Payments".to_string(), "web".to_string()],
+                    story_type: StoryType::Feature,
+                    story_points: Some(5),
+                },
+            ),
+            (
+                search,
+                LaneStory {
+                    title: "Search orders".to_string(),
+                    labels: vec!["Web".to_string()],
+                    story_type: StoryType::Bug,
+                    story_points: Some(3),
+                },
+            ),
+        ]);
+        let owner = Uuid::new_v4();
+        let task = |story_id: Uuid, owner_user_id: Option<Uuid>| LaneTask {
+            id: Uuid::new_v4(),
+            story_id,
+            status: "available".to_string(),
+            owner_user_id,
+        };
+        let tasks = vec![
+            task(payments, Some(owner)),
+            task(payments, None),
+            task(search, Some(owner)),
+        ];
+        let summary = |swimlanes: Swimlanes| -> Vec<(Option<String>, usize, u32)> {
+            swimlanes
+                .lanes(&tasks, &stories)
+                .into_iter()
+                .map(|lane| (lane.key, lane.task_count, lane.story_points))
+                .collect()
+        };
+
+        assert_eq!(
+            summary(Swimlanes::new(SwimlaneGrouping::Assignee, None).unwrap()),
+            vec![(Some(owner.to_string()), 2, 8), (None, 1, 5)]
+        );
+        assert_eq!(
+            summary(Swimlanes::new(SwimlaneGrouping::Label, None).unwrap()),
+            vec![
+                (Some("Component:Payments".to_string()), 2, 5),
+                (Some("web".to_string()), 3, 8)
+            ]
+        );
+        assert_eq!(
+            summary(
+                Swimlanes::new(SwimlaneGrouping::CustomField, Some("component".to_string()))
+                    .unwrap()
+            ),
+            vec![(Some("Payments".to_string()), 2, 5), (None, 1, 3)]
+        );
+    }
+}
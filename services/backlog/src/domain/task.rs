@@ -1,8 +1,11 @@
 use chrono::{DateTime, Utc};
+use common::i18n::LocalizedMessage;
 use common::AppError;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+const MAX_TASK_ESTIMATE_HOURS: u32 = 40;
+
 /// Task status representing the lifecycle with self-selection ownership model
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum TaskStatus {
@@ -14,6 +17,8 @@ pub enum TaskStatus {
     InProgress,
     /// Task is completed
     Completed,
+    /// Task was split into smaller tasks, which carry the work from here on
+    Superseded,
 }
 
 impl TaskStatus {
@@ -24,6 +29,7 @@ impl TaskStatus {
             "owned" => Some(Self::Owned),
             "inprogress" => Some(Self::InProgress),
             "completed" => Some(Self::Completed),
+            "superseded" => Some(Self::Superseded),
             _ => None,
         }
     }
@@ -35,6 +41,7 @@ impl TaskStatus {
             Self::Owned => vec![Self::InProgress, Self::Available], // Can release ownership
             Self::InProgress => vec![Self::Completed, Self::Owned], // Can go back to owned if need to pause
             Self::Completed => vec![],                              // Terminal state
+            Self::Superseded => vec![],                             // Terminal state
         }
     }
 
@@ -57,6 +64,11 @@ impl TaskStatus {
     pub fn is_completed(&self) -> bool {
         matches!(self, Self::Completed)
     }
+
+    /// Check if task was replaced by the tasks it was split into
+    pub fn is_superseded(&self) -> bool {
+        matches!(self, Self::Superseded)
+    }
 }
 
 impl std::fmt::Display for TaskStatus {
@@ -66,6 +78,7 @@ impl std::fmt::Display for TaskStatus {
             Self::Owned => write!(f, "owned"),
             Self::InProgress => write!(f, "inprogress"),
             Self::Completed => write!(f, "completed"),
+            Self::Superseded => write!(f, "superseded"),
         }
     }
 }
@@ -73,6 +86,9 @@ impl std::fmt::Display for TaskStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
     pub id: Uuid,
+    /// Per-project key such as `PROJ-124`, assigned when the task is stored
+    #[serde(default)]
+    pub short_key: Option<String>,
     pub story_id: Uuid,
     pub organization_id: Option<Uuid>,
     pub title: String,
@@ -85,6 +101,19 @@ pub struct Task {
     pub updated_at: DateTime<Utc>,
     pub owned_at: Option<DateTime<Utc>>, // When task was taken ownership
     pub completed_at: Option<DateTime<Utc>>, // When task was completed
+    /// The task this one was split out of, if any
+    #[serde(default)]
+    pub split_from_task_id: Option<Uuid>,
+}
+
+/// One of the tasks a task is split into
+#[derive(Debug, Clone)]
+pub struct TaskSplitPart {
+    pub title: String,
+    pub description: Option<String>,
+    /// Refs this part covers; empty means all of the original task's refs
+    pub acceptance_criteria_refs: Vec<String>,
+    pub estimated_hours: Option<u32>,
 }
 
 impl Task {
@@ -97,30 +126,31 @@ impl Task {
     ) -> Result<Self, AppError> {
         // Validate title is not empty or whitespace-only
         if title.trim().is_empty() {
-            return Err(AppError::BadRequest(
-                "Task title cannot be empty".to_string(),
-            ));
+            return Err(AppError::invalid(LocalizedMessage::new(
+                "task.title_required",
+            )));
         }
 
         // Validate acceptance criteria refs vector is not empty
         if acceptance_criteria_refs.is_empty() {
-            return Err(AppError::BadRequest(
-                "Task must have at least one acceptance criteria reference".to_string(),
-            ));
+            return Err(AppError::invalid(LocalizedMessage::new(
+                "task.ac_refs_required",
+            )));
         }
 
         // Validate AC refs are not empty strings
         for ac_ref in &acceptance_criteria_refs {
             if ac_ref.trim().is_empty() {
-                return Err(AppError::BadRequest(
-                    "Acceptance criteria references cannot be empty".to_string(),
-                ));
+                return Err(AppError::invalid(LocalizedMessage::new(
+                    "task.ac_ref_blank",
+                )));
             }
         }
 
         let now = Utc::now();
         Ok(Self {
             id: Uuid::new_v4(),
+            short_key: None,
             story_id,
             organization_id,
             title: title.trim().to_string(),
@@ -133,6 +163,7 @@ impl Task {
             updated_at: now,
             owned_at: None,
             completed_at: None,
+            split_from_task_id: None,
         })
     }
 
@@ -288,6 +319,9 @@ impl Task {
             },
             TaskStatus::InProgress => self.start_work(actor),
             TaskStatus::Completed => self.complete(actor),
+            TaskStatus::Superseded => Err(AppError::BadRequest(
+                "Tasks are superseded by splitting them".to_string(),
+            )),
         }
     }
 
@@ -295,15 +329,16 @@ impl Task {
     pub fn set_estimated_hours(&mut self, hours: Option<u32>) -> Result<(), AppError> {
         if let Some(h) = hours {
             if h == 0 {
-                return Err(AppError::BadRequest(
-                    "Estimated hours must be greater than 0".to_string(),
-                ));
+                return Err(AppError::invalid(LocalizedMessage::new(
+                    "task.estimate_zero",
+                )));
             }
 
             // Maximum 40 hours per task (1 week of work) - opinionated constraint
-            if h > 40 {
-                return Err(AppError::BadRequest(
-                    "Task cannot exceed 40 hours (split into smaller tasks)".to_string(),
+            if h > MAX_TASK_ESTIMATE_HOURS {
+                return Err(AppError::invalid(
+                    LocalizedMessage::new("task.estimate_too_large")
+                        .with("max", MAX_TASK_ESTIMATE_HOURS),
                 ));
             }
         }
@@ -316,25 +351,26 @@ impl Task {
     /// Update acceptance criteria refs
     pub fn update_acceptance_criteria_refs(&mut self, refs: Vec<String>) -> Result<(), AppError> {
         // Can only update ACs if task is not completed
-        if self.status.is_completed() {
-            return Err(AppError::BadRequest(
-                "Cannot update acceptance criteria for completed task".to_string(),
-            ));
+        if self.status.is_completed() || self.status.is_superseded() {
+            return Err(AppError::BadRequest(format!(
+                "Cannot update acceptance criteria for {} task",
+                self.status
+            )));
         }
 
         // Validate acceptance criteria refs vector is not empty
         if refs.is_empty() {
-            return Err(AppError::BadRequest(
-                "Task must have at least one acceptance criteria reference".to_string(),
-            ));
+            return Err(AppError::invalid(LocalizedMessage::new(
+                "task.ac_refs_required",
+            )));
         }
 
         // Validate AC refs are not empty strings
         for ac_ref in &refs {
             if ac_ref.trim().is_empty() {
-                return Err(AppError::BadRequest(
-                    "Acceptance criteria references cannot be empty".to_string(),
-                ));
+                return Err(AppError::invalid(LocalizedMessage::new(
+                    "task.ac_ref_blank",
+                )));
             }
         }
 
@@ -343,6 +379,74 @@ impl Task {
         Ok(())
     }
 
+    /// Replace this task with smaller ones. Every part keeps a subset of this task's
+    /// acceptance criteria refs and together they cover all of them. The new tasks
+    /// start out available and point back here; this task becomes superseded.
+    pub fn split(&mut self, parts: Vec<TaskSplitPart>) -> Result<Vec<Task>, AppError> {
+        if self.status.is_completed() || self.status.is_superseded() {
+            return Err(AppError::BadRequest(format!(
+                "Cannot split a {} task",
+                self.status
+            )));
+        }
+
+        if parts.len() < 2 {
+            return Err(AppError::BadRequest(
+                "A task must be split into at least two tasks".to_string(),
+            ));
+        }
+
+        let mut children = Vec::with_capacity(parts.len());
+        for part in parts {
+            let refs = if part.acceptance_criteria_refs.is_empty() {
+                self.acceptance_criteria_refs.clone()
+            } else {
+                part.acceptance_criteria_refs
+            };
+            if let Some(unknown) = refs
+                .iter()
+                .find(|ac_ref| !self.acceptance_criteria_refs.contains(ac_ref))
+            {
+                return Err(AppError::BadRequest(format!(
+                    "Acceptance criteria reference {} is not on the task being split",
+                    unknown
+                )));
+            }
+
+            let mut child = Task::new(
+                self.story_id,
+                self.organization_id,
+                part.title,
+                part.description,
+                refs,
+            )?;
+            child.set_estimated_hours(part.estimated_hours)?;
+            child.split_from_task_id = Some(self.id);
+            children.push(child);
+        }
+
+        let uncovered: Vec<&str> = self
+            .acceptance_criteria_refs
+            .iter()
+            .filter(|ac_ref| {
+                !children
+                    .iter()
+                    .any(|child| child.acceptance_criteria_refs.contains(ac_ref))
+            })
+            .map(String::as_str)
+            .collect();
+        if !uncovered.is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "Acceptance criteria references not covered by the split: {}",
+                uncovered.join(", ")
+            )));
+        }
+
+        self.status = TaskStatus::Superseded;
+        self.updated_at = Utc::now();
+        Ok(children)
+    }
+
     /// Check if task is owned by a specific user
     pub fn is_owned_by(&self, user_id: Uuid) -> bool {
         self.owner_user_id == Some(user_id)
@@ -698,4 +802,69 @@ mod tests {
         assert!(task.start_work(user_id).is_err());
         assert!(task.take_ownership(Uuid::new_v4()).is_err());
     }
+
+    fn part(title: &str, refs: &[&str]) -> TaskSplitPart {
+        TaskSplitPart {
+            title: title.to_string(),
+            description: None,
+            acceptance_criteria_refs: refs.iter().map(|r| r.to_string()).collect(),
+            estimated_hours: Some(8),
+        }
+    }
+
+    #[test]
+    fn test_split_moves_refs_and_supersedes_original() {
+        let mut task = Task::new(
+            Uuid::new_v4(),
+            None,
+            "Build checkout".to_string(),
+            None,
+            vec!["AC1".to_string(), "AC2".to_string()],
+        )
+        .unwrap();
+
+        let children = task
+            .split(vec![
+                part("Cart API", &["AC1"]),
+                part("Payment form", &["AC2"]),
+            ])
+            .unwrap();
+
+        assert_eq!(task.status, TaskStatus::Superseded);
+        assert_eq!(children.len(), 2);
+        assert!(children
+            .iter()
+            .all(|child| child.split_from_task_id == Some(task.id)
+                && child.story_id == task.story_id
+                && child.status == TaskStatus::Available));
+        assert_eq!(children[1].acceptance_criteria_refs, vec!["AC2"]);
+        assert!(task.split(vec![part("a", &[]), part("b", &[])]).is_err());
+    }
+
+    #[test]
+    fn test_split_rejects_unknown_or_uncovered_refs() {
+        let mut task = Task::new(
+            Uuid::new_v4(),
+            None,
+            "Build checkout".to_string(),
+            None,
+            vec!["AC1".to_string(), "AC2".to_string()],
+        )
+        .unwrap();
+
+        assert!(task.split(vec![part("Only one", &[])]).is_err());
+        assert!(task
+            .split(vec![part("a", &["AC1"]), part("b", &["AC3"])])
+            .is_err());
+        assert!(task
+            .split(vec![part("a", &["AC1"]), part("b", &["AC1"])])
+            .is_err());
+        assert_eq!(task.status, TaskStatus::Available);
+
+        // Parts without refs inherit all of them
+        let children = task
+            .split(vec![part("a", &[]), part("b", &["AC1"])])
+            .unwrap();
+        assert_eq!(children[0].acceptance_criteria_refs, vec!["AC1", "AC2"]);
+    }
 }
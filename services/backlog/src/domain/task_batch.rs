@@ -0,0 +1,96 @@
+use common::i18n::LocalizedMessage;
+use common::AppError;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::domain::TaskStatus;
+
+/// Most updates accepted in one batch request
+pub const MAX_TASK_BATCH_SIZE: usize = 100;
+const MAX_NOTE_LENGTH: usize = 2000;
+
+/// One entry of a batch status update
+#[derive(Debug, Clone)]
+pub struct TaskStatusUpdate {
+    pub task_id: Uuid,
+    pub status: TaskStatus,
+    pub note: Option<String>,
+}
+
+impl TaskStatusUpdate {
+    /// Blank notes are dropped; long ones are rejected
+    pub fn new(task_id: Uuid, status: TaskStatus, note: Option<String>) -> Result<Self, AppError> {
+        let note = note
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty());
+        if note
+            .as_ref()
+            .is_some_and(|note| note.chars().count() > MAX_NOTE_LENGTH)
+        {
+            return Err(AppError::invalid(
+                LocalizedMessage::new("validation.too_long")
+                    .with("field", "note")
+                    .with("max", MAX_NOTE_LENGTH),
+            ));
+        }
+
+        Ok(Self {
+            task_id,
+            status,
+            note,
+        })
+    }
+}
+
+/// Reject batches that are empty, too large, or name a task more than once
+pub fn validate_task_batch(updates: &[TaskStatusUpdate]) -> Result<(), AppError> {
+    if updates.is_empty() {
+        return Err(AppError::invalid(LocalizedMessage::new(
+            "validation.batch_empty",
+        )));
+    }
+    if updates.len() > MAX_TASK_BATCH_SIZE {
+        return Err(AppError::invalid(
+            LocalizedMessage::new("validation.batch_too_large").with("max", MAX_TASK_BATCH_SIZE),
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    for update in updates {
+        if !seen.insert(update.task_id) {
+            return Err(AppError::invalid(
+                LocalizedMessage::new("validation.batch_duplicate").with("task_id", update.task_id),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blank_note_is_dropped() {
+        let update =
+            TaskStatusUpdate::new(Uuid::new_v4(), TaskStatus::Completed, Some("  ".into()))
+                .unwrap();
+        assert!(update.note.is_none());
+
+        let too_long = "x".repeat(MAX_NOTE_LENGTH + 1);
+        assert!(
+            TaskStatusUpdate::new(Uuid::new_v4(), TaskStatus::Completed, Some(too_long)).is_err()
+        );
+    }
+
+    #[test]
+    fn test_batch_validation() {
+        let task_id = Uuid::new_v4();
+        let update = TaskStatusUpdate::new(task_id, TaskStatus::Completed, None).unwrap();
+
+        assert!(validate_task_batch(&[]).is_err());
+        assert!(validate_task_batch(std::slice::from_ref(&update)).is_ok());
+        assert!(validate_task_batch(&[update.clone(), update.clone()]).is_err());
+        assert!(validate_task_batch(&vec![update; MAX_TASK_BATCH_SIZE + 1]).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Duration, Utc};
+use common::AppError;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::{Task, TaskStatus};
+
+/// Error code returned when someone else already owns the task, including when
+/// they won a simultaneous claim
+pub const TASK_ALREADY_CLAIMED: &str = "TASK_ALREADY_CLAIMED";
+
+/// How long a place in a task's claim queue lasts. If the owner releases the
+/// task within this window, the first user still waiting is offered it.
+pub fn claim_queue_window() -> Duration {
+    Duration::hours(1)
+}
+
+pub fn already_claimed() -> AppError {
+    AppError::ConflictWithCode {
+        message: "Task is already owned by someone else. Join its claim queue to be offered it if it is released within the hour.".to_string(),
+        error_code: TASK_ALREADY_CLAIMED.to_string(),
+    }
+}
+
+/// A user's place in line for a task someone else owns
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaimQueueEntry {
+    pub task_id: Uuid,
+    pub user_id: Uuid,
+    /// 1 for the user who will be offered the task first
+    pub position: u32,
+    pub queued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Whether `user_id` may wait in the task's claim queue: only while someone
+/// else is working on it
+pub fn ensure_queueable(task: &Task, user_id: Uuid) -> Result<(), AppError> {
+    match task.status {
+        TaskStatus::Available => Err(AppError::BadRequest(
+            "Task is available; take ownership instead".to_string(),
+        )),
+        TaskStatus::Owned | TaskStatus::InProgress if task.owner_user_id == Some(user_id) => Err(
+            AppError::BadRequest("You already own this task".to_string()),
+        ),
+        TaskStatus::Owned | TaskStatus::InProgress => Ok(()),
+        TaskStatus::Completed | TaskStatus::Superseded => Err(AppError::BadRequest(format!(
+            "Task can no longer be claimed. Current status: {}",
+            task.status
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(status: TaskStatus, owner: Option<Uuid>) -> Task {
+        let mut task = Task::new(
+            Uuid::new_v4(),
+            None,
+            "Wire up payments".to_string(),
+            None,
+            vec!["AC1".to_string()],
+        )
+        .unwrap();
+        task.status = status;
+        task.owner_user_id = owner;
+        task
+    }
+
+    #[test]
+    fn test_queue_only_for_tasks_someone_else_works_on() {
+        let me = Uuid::new_v4();
+        let someone_else = Uuid::new_v4();
+
+        assert!(ensure_queueable(&task(TaskStatus::Owned, Some(someone_else)), me).is_ok());
+        assert!(ensure_queueable(&task(TaskStatus::InProgress, Some(someone_else)), me).is_ok());
+        assert!(ensure_queueable(&task(TaskStatus::Owned, Some(me)), me).is_err());
+        assert!(ensure_queueable(&task(TaskStatus::Available, None), me).is_err());
+        assert!(ensure_queueable(&task(TaskStatus::Completed, Some(someone_else)), me).is_err());
+    }
+
+    #[test]
+    fn test_already_claimed_has_code() {
+        match already_claimed() {
+            AppError::ConflictWithCode { error_code, .. } => {
+                assert_eq!(error_code, TASK_ALREADY_CLAIMED)
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{Task, TaskStatus};
+
+const MAX_NOTE_LENGTH: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskTransferStatus {
+    /// Waiting for the recipient
+    Pending,
+    Accepted,
+    /// The recipient turned it down
+    Declined,
+    /// The owner withdrew it
+    Cancelled,
+}
+
+impl TaskTransferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Accepted => "accepted",
+            Self::Declined => "declined",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "accepted" => Some(Self::Accepted),
+            "declined" => Some(Self::Declined),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// A proposal to hand a task someone owns to someone else. Unlike releasing
+/// the task, the recipient takes it over as it is: in progress, estimated and
+/// with its history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTransfer {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    pub note: Option<String>,
+    /// The owner is stuck and cannot go on, as when an AI agent hands the
+    /// task to a person
+    pub handoff: bool,
+    pub status: TaskTransferStatus,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl TaskTransfer {
+    /// The owner `from_user_id` proposes giving `task` to `to_user_id`. A
+    /// handoff has to say where the owner got stuck.
+    pub fn propose(
+        task: &Task,
+        from_user_id: Uuid,
+        to_user_id: Uuid,
+        note: Option<String>,
+        handoff: bool,
+    ) -> Result<Self, AppError> {
+        if task.owner_user_id != Some(from_user_id)
+            || !matches!(task.status, TaskStatus::Owned | TaskStatus::InProgress)
+        {
+            return Err(AppError::BadRequest(
+                "Only the task owner can transfer it, while the task is owned or in progress"
+                    .to_string(),
+            ));
+        }
+        if to_user_id == from_user_id {
+            return Err(AppError::BadRequest(
+                "You already own this task".to_string(),
+            ));
+        }
+        let note = note
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty());
+        if note
+            .as_ref()
+            .is_some_and(|note| note.chars().count() > MAX_NOTE_LENGTH)
+        {
+            return Err(AppError::BadRequest(format!(
+                "note must be at most {} characters",
+                MAX_NOTE_LENGTH
+            )));
+        }
+        if handoff && note.is_none() {
+            return Err(AppError::BadRequest(
+                "A handoff needs a note saying where the work is stuck".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            task_id: task.id,
+            organization_id: task.organization_id,
+            from_user_id,
+            to_user_id,
+            note,
+            handoff,
+            status: TaskTransferStatus::Pending,
+            created_at: Utc::now(),
+            resolved_at: None,
+        })
+    }
+
+    /// The recipient accepts: `task` becomes theirs, keeping its status and
+    /// estimate
+    pub fn accept(&mut self, task: &mut Task, user_id: Uuid) -> Result<(), AppError> {
+        if user_id != self.to_user_id {
+            return Err(AppError::Forbidden(
+                "Only the recipient can accept a transfer".to_string(),
+            ));
+        }
+        if task.owner_user_id != Some(self.from_user_id)
+            || !matches!(task.status, TaskStatus::Owned | TaskStatus::InProgress)
+        {
+            return Err(AppError::Conflict(
+                "The task changed hands since the transfer was proposed".to_string(),
+            ));
+        }
+        task.owner_user_id = Some(user_id);
+        task.owned_at = Some(Utc::now());
+        task.updated_at = Utc::now();
+        self.resolve(TaskTransferStatus::Accepted);
+        Ok(())
+    }
+
+    /// The recipient declines, or the owner withdraws the proposal
+    pub fn decline(&mut self, user_id: Uuid) -> Result<(), AppError> {
+        let status = if user_id == self.to_user_id {
+            TaskTransferStatus::Declined
+        } else if user_id == self.from_user_id {
+            TaskTransferStatus::Cancelled
+        } else {
+            return Err(AppError::Forbidden(
+                "Only the owner or the recipient can decline a transfer".to_string(),
+            ));
+        };
+        self.resolve(status);
+        Ok(())
+    }
+
+    /// Who to tell about the transfer's latest change: the recipient of a new
+    /// or withdrawn proposal, otherwise the owner who proposed it
+    pub fn counterpart(&self) -> Uuid {
+        match self.status {
+            TaskTransferStatus::Pending | TaskTransferStatus::Cancelled => self.to_user_id,
+            TaskTransferStatus::Accepted | TaskTransferStatus::Declined => self.from_user_id,
+        }
+    }
+
+    fn resolve(&mut self, status: TaskTransferStatus) {
+        self.status = status;
+        self.resolved_at = Some(Utc::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owned_task(owner: Uuid) -> Task {
+        let mut task = Task::new(
+            Uuid::new_v4(),
+            None,
+            "Wire up payments".to_string(),
+            None,
+            vec!["AC1".to_string()],
+        )
+        .unwrap();
+        task.take_ownership(owner).unwrap();
+        task.start_work(owner).unwrap();
+        task.estimated_hours = Some(5);
+        task
+    }
+
+    #[test]
+    fn test_accepting_keeps_the_work_in_progress() {
+        let (agent, person, bystander) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut task = owned_task(agent);
+
+        assert!(TaskTransfer::propose(&task, person, agent, None, false).is_err());
+        assert!(TaskTransfer::propose(&task, agent, person, None, true).is_err());
+        let mut transfer = TaskTransfer::propose(
+            &task,
+            agent,
+            person,
+            Some(" Tests need a staging card ".to_string()),
+            true,
+        )
+        .unwrap();
+        assert_eq!(transfer.note.as_deref(), Some("Tests need a staging card"));
+        assert_eq!(transfer.counterpart(), person);
+
+        assert!(transfer.accept(&mut task, bystander).is_err());
+        transfer.accept(&mut task, person).unwrap();
+        assert_eq!(transfer.status, TaskTransferStatus::Accepted);
+        assert_eq!(transfer.counterpart(), agent);
+        assert_eq!(task.owner_user_id, Some(person));
+        assert_eq!(task.status, TaskStatus::InProgress);
+        assert_eq!(task.estimated_hours, Some(5));
+    }
+
+    #[test]
+    fn test_decline_by_owner_cancels() {
+        let (owner, recipient) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut task = owned_task(owner);
+        let mut transfer = TaskTransfer::propose(&task, owner, recipient, None, false).unwrap();
+
+        transfer.decline(owner).unwrap();
+        assert_eq!(transfer.status, TaskTransferStatus::Cancelled);
+        assert_eq!(transfer.counterpart(), recipient);
+
+        task.release_ownership(owner).unwrap();
+        let mut stale = TaskTransfer {
+            status: TaskTransferStatus::Pending,
+            ..transfer
+        };
+        assert!(matches!(
+            stale.accept(&mut task, recipient),
+            Err(AppError::Conflict(_))
+        ));
+    }
+}
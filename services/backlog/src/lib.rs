@@ -3,6 +3,8 @@ pub mod application;
 pub mod config;
 pub mod domain;
 
+pub use adapters::http::routes::{create_backlog_router, create_backlog_router_with_websockets};
+pub use adapters::sprint_stats::spawn_sprint_stats_projector;
 pub use config::AppConfig;
 
 use application::BacklogUsecases;
@@ -68,7 +68,10 @@ pub async fn setup_test_db() -> PgPool {
             ADD COLUMN IF NOT EXISTS readiness_override BOOLEAN DEFAULT FALSE,
             ADD COLUMN IF NOT EXISTS readiness_override_by UUID,
             ADD COLUMN IF NOT EXISTS readiness_override_reason TEXT,
-            ADD COLUMN IF NOT EXISTS readiness_override_at TIMESTAMPTZ;
+            ADD COLUMN IF NOT EXISTS readiness_override_at TIMESTAMPTZ,
+            ADD COLUMN IF NOT EXISTS short_key TEXT,
+            ADD COLUMN IF NOT EXISTS timebox_hours INTEGER,
+            ADD COLUMN IF NOT EXISTS stale_at TIMESTAMPTZ;
         "#,
     )
     .execute(&pool)
@@ -262,7 +265,43 @@ pub async fn build_backlog_router_for_tests(pool: PgPool) -> Router {
             "/api/v1/projects/{project_id}/stories",
             get(backlog_handlers::get_stories_by_project),
         )
+        .route(
+            "/api/v1/projects/{project_id}/readiness-badge/token",
+            post(backlog_handlers::rotate_readiness_badge_token),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/backlog-health",
+            get(backlog_handlers::get_backlog_health),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/dependency-graph",
+            get(backlog_handlers::get_dependency_graph),
+        )
+        .route(
+            "/api/v1/public/projects/{project_id}/readiness-badge.svg",
+            get(backlog_handlers::get_readiness_badge),
+        )
         .route("/api/v1/stories/{id}", get(backlog_handlers::get_story))
+        .route(
+            "/api/v1/resolve/{key}",
+            get(backlog_handlers::resolve_short_key),
+        )
+        .route(
+            "/api/v1/stories/{id}/references",
+            get(backlog_handlers::get_story_references),
+        )
+        .route(
+            "/api/v1/stories/{id}/revisions",
+            get(backlog_handlers::get_story_revisions),
+        )
+        .route(
+            "/api/v1/stories/{id}/revisions/{from}/diff/{to}",
+            get(backlog_handlers::get_story_revision_diff),
+        )
+        .route(
+            "/api/v1/webhooks/github",
+            post(backlog_handlers::github_webhook),
+        )
         .route(
             "/api/v1/stories/{id}",
             patch(backlog_handlers::update_story),
@@ -271,6 +310,10 @@ pub async fn build_backlog_router_for_tests(pool: PgPool) -> Router {
             "/api/v1/stories/{id}",
             delete(backlog_handlers::delete_story),
         )
+        .route(
+            "/api/v1/stories/{id}/export",
+            get(backlog_handlers::export_story),
+        )
         .route(
             "/api/v1/stories/{id}/ready-override",
             put(backlog_handlers::override_story_ready),
@@ -307,10 +350,22 @@ pub async fn build_backlog_router_for_tests(pool: PgPool) -> Router {
             "/api/v1/stories/{id}/acceptance-criteria/{criterion_id}",
             delete(backlog_handlers::delete_acceptance_criterion),
         )
+        .route(
+            "/api/v1/stories/{id}/acceptance-criteria/bulk",
+            post(backlog_handlers::bulk_update_acceptance_criteria),
+        )
+        .route(
+            "/api/v1/stories/{id}/acceptance-criteria/order",
+            put(backlog_handlers::reorder_acceptance_criteria),
+        )
         .route(
             "/api/v1/tasks/owned",
             get(backlog_handlers::get_user_owned_tasks),
         )
+        .route(
+            "/api/v1/tasks/batch",
+            patch(backlog_handlers::batch_update_task_status),
+        )
         .route(
             "/api/v1/tasks/{task_id}/ownership",
             put(backlog_handlers::take_task_ownership),
@@ -319,6 +374,11 @@ pub async fn build_backlog_router_for_tests(pool: PgPool) -> Router {
             "/api/v1/tasks/{task_id}/ownership",
             delete(backlog_handlers::release_task_ownership),
         )
+        .route(
+            "/api/v1/tasks/{task_id}/claim-queue",
+            post(backlog_handlers::join_task_claim_queue)
+                .delete(backlog_handlers::leave_task_claim_queue),
+        )
         .route(
             "/api/v1/tasks/{task_id}/work/start",
             post(backlog_handlers::start_task_work),
@@ -335,10 +395,30 @@ pub async fn build_backlog_router_for_tests(pool: PgPool) -> Router {
             "/api/v1/tasks/{task_id}/estimate",
             patch(backlog_handlers::set_task_estimate),
         )
+        .route(
+            "/api/v1/tasks/{task_id}/split",
+            post(backlog_handlers::split_task),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/sprints/schedule",
+            post(backlog_handlers::schedule_sprints),
+        )
         .route(
             "/api/v1/sprints/{sprint_id}/tasks",
             get(backlog_handlers::get_sprint_task_board),
         )
+        .route(
+            "/api/v1/sprints/{sprint_id}/standup-summary",
+            get(backlog_handlers::get_standup_summary),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/stories",
+            get(backlog_handlers::get_sprint_stories).post(backlog_handlers::add_story_to_sprint),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/stories/{story_id}",
+            delete(backlog_handlers::remove_story_from_sprint),
+        )
         .with_state(state)
         .layer(Extension(verifier))
         .layer(TraceLayer::new_for_http())
@@ -369,6 +449,11 @@ async fn clean_test_data(pool: &PgPool) -> Result<(), sqlx::Error> {
         .await
         .ok();
 
+    sqlx::query("TRUNCATE TABLE import_jobs CASCADE")
+        .execute(pool)
+        .await
+        .ok();
+
     sqlx::query("TRUNCATE TABLE stories CASCADE")
         .execute(pool)
         .await
@@ -1 +1,2 @@
+pub mod test_dto_casing;
 pub mod test_openapi_compliance;
@@ -0,0 +1,82 @@
+use backlog::adapters::http::handlers::{
+    BatchTaskStatusItem, CreateTaskRequest, CreateTaskResponse, TaskResponse,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Responses use camelCase keys; requests also accept the snake_case keys
+// clients sent before responses were made consistent
+
+fn keys(value: &Value) -> Vec<String> {
+    value.as_object().unwrap().keys().cloned().collect()
+}
+
+#[test]
+fn test_task_response_keys_are_camel_case() {
+    let task = TaskResponse {
+        id: Uuid::new_v4(),
+        short_key: Some("PROJ-1".to_string()),
+        story_id: Uuid::new_v4(),
+        title: "Add login form".to_string(),
+        description: None,
+        acceptance_criteria_refs: vec!["AC1".to_string()],
+        status: "available".to_string(),
+        owner_user_id: None,
+        estimated_hours: Some(3),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        owned_at: None,
+        completed_at: None,
+        split_from_task_id: None,
+    };
+
+    let mut task_keys = keys(&serde_json::to_value(&task).unwrap());
+    task_keys.sort();
+    assert_eq!(
+        task_keys,
+        vec![
+            "acceptanceCriteriaRefs",
+            "completedAt",
+            "createdAt",
+            "description",
+            "estimatedHours",
+            "id",
+            "ownedAt",
+            "ownerUserId",
+            "shortKey",
+            "splitFromTaskId",
+            "status",
+            "storyId",
+            "title",
+            "updatedAt",
+        ]
+    );
+    let created = CreateTaskResponse {
+        task_id: Uuid::new_v4(),
+    };
+    assert_eq!(
+        keys(&serde_json::to_value(&created).unwrap()),
+        vec!["taskId"]
+    );
+}
+
+#[test]
+fn test_requests_accept_camel_and_snake_case_keys() {
+    for body in [
+        json!({"title": "Add login form", "acceptanceCriteriaRefs": ["AC1"]}),
+        json!({"title": "Add login form", "acceptance_criteria_refs": ["AC1"]}),
+    ] {
+        let request: CreateTaskRequest = serde_json::from_value(body).unwrap();
+        assert_eq!(request.acceptance_criteria_refs, vec!["AC1".to_string()]);
+    }
+
+    let task_id = Uuid::new_v4();
+    for body in [
+        json!({"taskId": task_id, "status": "completed"}),
+        json!({"task_id": task_id, "status": "completed"}),
+    ] {
+        let item: BatchTaskStatusItem = serde_json::from_value(body).unwrap();
+        assert_eq!(item.task_id, task_id);
+    }
+}
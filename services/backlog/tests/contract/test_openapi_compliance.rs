@@ -20,7 +20,7 @@ async fn test_story_response_schema() {
 
     let story_data = json!({
         "id": "550e8400-e29b-41d4-a716-446655440000",
-        "project_id": "550e8400-e29b-41d4-a716-446655440001",
+        "projectId": "550e8400-e29b-41d4-a716-446655440001",
         "title": "Test Story",
         "description": "Test description",
         "status": "Ready",
@@ -29,14 +29,14 @@ async fn test_story_response_schema() {
 
     // Validate required fields exist
     assert!(story_data.get("id").is_some());
-    assert!(story_data.get("project_id").is_some());
+    assert!(story_data.get("projectId").is_some());
     assert!(story_data.get("title").is_some());
     assert!(story_data.get("status").is_some());
     assert!(story_data.get("labels").is_some());
 
     // Validate types
     assert!(story_data["id"].is_string());
-    assert!(story_data["project_id"].is_string());
+    assert!(story_data["projectId"].is_string());
     assert!(story_data["title"].is_string());
     assert!(story_data["status"].is_string());
     assert!(story_data["labels"].is_array());
@@ -58,38 +58,38 @@ async fn test_task_response_schema() {
     // Expected Task schema:
     // {
     //   "id": "uuid",
-    //   "story_id": "uuid",
+    //   "storyId": "uuid",
     //   "title": "string",
     //   "description": "string|null",
-    //   "acceptance_criteria_refs": ["string"]
+    //   "acceptanceCriteriaRefs": ["string"]
     // }
 
     let task_data = json!({
         "id": "550e8400-e29b-41d4-a716-446655440000",
-        "story_id": "550e8400-e29b-41d4-a716-446655440001",
+        "storyId": "550e8400-e29b-41d4-a716-446655440001",
         "title": "Test Task",
         "description": "Test description",
-        "acceptance_criteria_refs": ["AC1", "AC2"]
+        "acceptanceCriteriaRefs": ["AC1", "AC2"]
     });
 
     // Validate required fields
     assert!(task_data.get("id").is_some());
-    assert!(task_data.get("story_id").is_some());
+    assert!(task_data.get("storyId").is_some());
     assert!(task_data.get("title").is_some());
-    assert!(task_data.get("acceptance_criteria_refs").is_some());
+    assert!(task_data.get("acceptanceCriteriaRefs").is_some());
 
     // Validate types
     assert!(task_data["id"].is_string());
-    assert!(task_data["story_id"].is_string());
+    assert!(task_data["storyId"].is_string());
     assert!(task_data["title"].is_string());
-    assert!(task_data["acceptance_criteria_refs"].is_array());
+    assert!(task_data["acceptanceCriteriaRefs"].is_array());
 
     // Validate UUID format
     let id_str = task_data["id"].as_str().unwrap();
     assert!(Uuid::parse_str(id_str).is_ok());
 
     // Validate AC refs are non-empty strings
-    let ac_refs = task_data["acceptance_criteria_refs"].as_array().unwrap();
+    let ac_refs = task_data["acceptanceCriteriaRefs"].as_array().unwrap();
     for ac_ref in ac_refs {
         assert!(ac_ref.is_string());
         assert!(!ac_ref.as_str().unwrap().is_empty());
@@ -157,26 +157,24 @@ async fn test_create_task_request_schema() {
     // {
     //   "title": "string" (required),
     //   "description": "string|null" (optional),
-    //   "acceptance_criteria_refs": ["string"] (required, non-empty)
+    //   "acceptanceCriteriaRefs": ["string"] (required, non-empty)
     // }
 
     let valid_request = json!({
         "title": "Test Task",
         "description": "Task description",
-        "acceptance_criteria_refs": ["AC1", "AC2"]
+        "acceptanceCriteriaRefs": ["AC1", "AC2"]
     });
 
     assert!(valid_request.get("title").is_some());
-    assert!(valid_request.get("acceptance_criteria_refs").is_some());
+    assert!(valid_request.get("acceptanceCriteriaRefs").is_some());
 
     assert!(valid_request["title"].is_string());
-    assert!(valid_request["acceptance_criteria_refs"].is_array());
+    assert!(valid_request["acceptanceCriteriaRefs"].is_array());
 
     assert!(!valid_request["title"].as_str().unwrap().trim().is_empty());
 
-    let ac_refs = valid_request["acceptance_criteria_refs"]
-        .as_array()
-        .unwrap();
+    let ac_refs = valid_request["acceptanceCriteriaRefs"].as_array().unwrap();
     assert!(!ac_refs.is_empty());
 
     for ac_ref in ac_refs {
@@ -230,7 +228,7 @@ async fn test_stories_list_response_schema() {
         "stories": [
             {
                 "id": "550e8400-e29b-41d4-a716-446655440000",
-                "project_id": "550e8400-e29b-41d4-a716-446655440001",
+                "projectId": "550e8400-e29b-41d4-a716-446655440001",
                 "title": "Story 1",
                 "description": null,
                 "status": "Ready",
@@ -1,4 +1,7 @@
 pub mod test_concurrent_task_ownership;
 pub mod test_http_handlers;
 pub mod test_sprint_task_board;
+pub mod test_story_import;
 pub mod test_story_management;
+pub mod test_story_sharing;
+pub mod test_unit_of_work;
@@ -80,7 +80,7 @@ async fn create_test_task(app: &axum::Router) -> (Uuid, Uuid, Uuid) {
         .await
         .unwrap();
     let story_result: serde_json::Value = serde_json::from_slice(&story_body).unwrap();
-    let story_id = Uuid::parse_str(story_result["story_id"].as_str().unwrap()).unwrap();
+    let story_id = Uuid::parse_str(story_result["storyId"].as_str().unwrap()).unwrap();
 
     // Create task via HTTP API
     let task_request = json!({
@@ -110,7 +110,7 @@ async fn create_test_task(app: &axum::Router) -> (Uuid, Uuid, Uuid) {
         .await
         .unwrap();
     let task_result: serde_json::Value = serde_json::from_slice(&task_body).unwrap();
-    let task_id = Uuid::parse_str(task_result["task_id"].as_str().unwrap()).unwrap();
+    let task_id = Uuid::parse_str(task_result["taskId"].as_str().unwrap()).unwrap();
 
     (org_id, story_id, task_id)
 }
@@ -382,7 +382,7 @@ async fn test_high_load_task_creation_and_ownership() -> Result<(), Box<dyn std:
         .await
         .unwrap();
     let story_result: serde_json::Value = serde_json::from_slice(&story_body).unwrap();
-    let story_id = Uuid::parse_str(story_result["story_id"].as_str().unwrap()).unwrap();
+    let story_id = Uuid::parse_str(story_result["storyId"].as_str().unwrap()).unwrap();
 
     let num_tasks = 50;
     let mut join_set = JoinSet::new();
@@ -223,7 +223,7 @@ async fn test_delete_story_not_found() {
         .await
         .unwrap();
     let story_result: serde_json::Value = serde_json::from_slice(&story_body).unwrap();
-    let story_id = story_result["story_id"].as_str().unwrap();
+    let story_id = story_result["storyId"].as_str().unwrap();
 
     // Delete the story
     let response = app
@@ -382,7 +382,7 @@ async fn test_task_ownership_workflow_integration() {
         .await
         .unwrap();
     let story_result: serde_json::Value = serde_json::from_slice(&story_body).unwrap();
-    let story_id = story_result["story_id"].as_str().unwrap();
+    let story_id = story_result["storyId"].as_str().unwrap();
     println!("Created story with ID: {}", story_id);
     println!("Using org_id: {}", org_id);
 
@@ -425,7 +425,7 @@ async fn test_task_ownership_workflow_integration() {
         .await
         .unwrap();
     let task_result: serde_json::Value = serde_json::from_slice(&task_body).unwrap();
-    let task_id = task_result["task_id"].as_str().unwrap();
+    let task_id = task_result["taskId"].as_str().unwrap();
 
     // Test 1: Get available tasks (should include our new task)
     let available_response = app
@@ -597,7 +597,7 @@ async fn test_task_ownership_authorization() {
         .await
         .unwrap();
     let story_result: serde_json::Value = serde_json::from_slice(&story_body).unwrap();
-    let story_id = story_result["story_id"].as_str().unwrap();
+    let story_id = story_result["storyId"].as_str().unwrap();
 
     let task_request = json!({
         "title": "Authorization Test Task",
@@ -636,7 +636,7 @@ async fn test_task_ownership_authorization() {
         .await
         .unwrap();
     let task_result: serde_json::Value = serde_json::from_slice(&task_body).unwrap();
-    let task_id = task_result["task_id"].as_str().unwrap();
+    let task_id = task_result["taskId"].as_str().unwrap();
 
     // First, take ownership of the task
     let ownership_response = app
@@ -715,7 +715,7 @@ async fn test_task_ownership_state_transitions() {
         .await
         .unwrap();
     let story_result: serde_json::Value = serde_json::from_slice(&story_body).unwrap();
-    let story_id = story_result["story_id"].as_str().unwrap();
+    let story_id = story_result["storyId"].as_str().unwrap();
 
     let task_request = json!({
         "title": "State Test Task",
@@ -754,7 +754,7 @@ async fn test_task_ownership_state_transitions() {
         .await
         .unwrap();
     let task_result: serde_json::Value = serde_json::from_slice(&task_body).unwrap();
-    let task_id = task_result["task_id"].as_str().unwrap();
+    let task_id = task_result["taskId"].as_str().unwrap();
 
     // Test ownership workflow: Available → Owned → InProgress → Completed
 
@@ -380,6 +380,61 @@ async fn test_get_sprint_task_board_groups_by_status() {
     assert_eq!(groups["inprogress"]["count"], 1);
 }
 
+#[tokio::test]
+#[serial]
+async fn test_get_sprint_task_board_swimlanes_by_assignee() {
+    let pool = setup_test_db().await;
+    let project_id = Uuid::new_v4();
+    let (sprint_id, org_id) = create_test_sprint(&pool, project_id).await;
+    let checkout =
+        create_test_story_in_sprint(&pool, project_id, sprint_id, org_id, "Checkout").await;
+    let search = create_test_story_in_sprint(&pool, project_id, sprint_id, org_id, "Search").await;
+
+    let owner_id = Uuid::new_v4();
+    create_test_task(
+        &pool,
+        checkout,
+        org_id,
+        "Card form",
+        "owned",
+        Some(owner_id),
+    )
+    .await;
+    create_test_task(&pool, checkout, org_id, "Receipts", "available", None).await;
+    create_test_task(&pool, search, org_id, "Index", "inprogress", Some(owner_id)).await;
+
+    let router = build_backlog_router_for_tests(pool.clone()).await;
+
+    let request = Request::builder()
+        .uri(format!(
+            "/api/v1/sprints/{}/tasks?groupBy=assignee",
+            sprint_id
+        ))
+        .header(header::AUTHORIZATION, "Bearer valid-test-token")
+        .header("x-organization-id", org_id.to_string())
+        .body(Body::empty())
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let data: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(data["swimlanes"]["groupBy"], "assignee");
+    let lanes = data["lanes"].as_array().unwrap();
+    assert_eq!(lanes.len(), 2);
+    assert_eq!(lanes[0]["key"], owner_id.to_string());
+    assert_eq!(lanes[0]["taskCount"], 2);
+    assert_eq!(lanes[0]["storyPoints"], 10);
+    assert_eq!(lanes[1]["key"], Value::Null);
+    assert_eq!(lanes[1]["taskCount"], 1);
+    assert_eq!(lanes[1]["storyPoints"], 5);
+    assert_eq!(data["groups"]["none"]["count"], 1);
+}
+
 #[tokio::test]
 #[serial]
 async fn test_get_sprint_task_board_shows_task_ownership() {
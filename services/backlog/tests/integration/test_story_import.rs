@@ -0,0 +1,83 @@
+use backlog::domain::{ImportFormat, ImportJobStatus, ImportThrottle};
+use common::AppError;
+use event_bus::{EventBus, EventPublisher};
+use serial_test::serial;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::common::setup_test_db;
+
+#[tokio::test]
+#[serial]
+async fn test_import_creates_stories_and_reports_progress() {
+    let pool = setup_test_db().await;
+    let events: Arc<dyn EventPublisher> = Arc::new(EventBus::new());
+    let usecases = backlog::build_usecases(pool.clone(), events);
+    let (project_id, org_id) = (Uuid::new_v4(), Uuid::new_v4());
+    sqlx::query(
+        "INSERT INTO projects (id, organization_id, name, created_at, updated_at)
+         VALUES ($1, $2, 'Import Project', NOW(), NOW())",
+    )
+    .bind(project_id)
+    .bind(org_id)
+    .execute(&pool)
+    .await
+    .expect("Failed to create test project");
+
+    let too_long = "x".repeat(300);
+    let content = format!(
+        "Summary,Issue key,Issue Type,Labels\nPay by card,PAY-1,Story,payments\n{},PAY-2,Task,\nRefund orders,PAY-3,Bug,payments\n",
+        too_long
+    );
+    let job = usecases
+        .start_import(project_id, Some(org_id), None, ImportFormat::Jira, &content)
+        .await
+        .expect("import should be queued");
+    assert_eq!(job.status, ImportJobStatus::Queued);
+    assert_eq!(job.total_items, 3);
+
+    let throttle = ImportThrottle {
+        events_per_second: 1000,
+        chunk_pause: Duration::ZERO,
+    };
+    let finished = usecases
+        .run_next_import(&throttle)
+        .await
+        .expect("import should run")
+        .expect("a job should be claimed");
+    assert_eq!(finished.id, job.id);
+    assert_eq!(finished.status, ImportJobStatus::Completed);
+    assert!(usecases.run_next_import(&throttle).await.unwrap().is_none());
+
+    let (job, errors) = usecases
+        .get_import_job(job.id, Some(org_id))
+        .await
+        .expect("job should be found");
+    assert_eq!(job.status, ImportJobStatus::Completed);
+    assert_eq!(
+        (job.processed_items, job.created_items, job.failed_items),
+        (3, 2, 1)
+    );
+    assert_eq!(job.percent_complete(), 100);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].position, 1);
+    assert_eq!(errors[0].external_key.as_deref(), Some("PAY-2"));
+
+    let titles: Vec<String> =
+        sqlx::query_scalar("SELECT title FROM stories WHERE project_id = $1 ORDER BY title")
+            .bind(project_id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(titles, vec!["Pay by card", "Refund orders"]);
+
+    assert!(matches!(
+        usecases.resume_import(job.id, Some(org_id)).await,
+        Err(AppError::Conflict(_))
+    ));
+    assert!(matches!(
+        usecases.get_import_job(job.id, Some(Uuid::new_v4())).await,
+        Err(AppError::NotFound(_))
+    ));
+}
@@ -105,7 +105,7 @@ async fn test_story_lifecycle_integration() -> Result<(), Box<dyn std::error::Er
 
     let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
     let create_response: Value = serde_json::from_slice(&body)?;
-    let story_id = create_response["story_id"].as_str().unwrap();
+    let story_id = create_response["storyId"].as_str().unwrap();
     let _story_uuid = Uuid::parse_str(story_id)?;
 
     // 2. Get the story and verify it was created correctly
@@ -352,7 +352,7 @@ async fn test_story_authorization() -> Result<(), Box<dyn std::error::Error>> {
 
     let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
     let create_response: Value = serde_json::from_slice(&body)?;
-    let story_id = create_response["story_id"].as_str().unwrap();
+    let story_id = create_response["storyId"].as_str().unwrap();
 
     // Try to access the story from a different organization - should fail
     let unauthorized_request = Request::builder()
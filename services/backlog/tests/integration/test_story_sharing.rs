@@ -0,0 +1,211 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use backlog::domain::StoryType;
+use common::AppError;
+use event_bus::{EventBus, EventPublisher};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use serial_test::serial;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use crate::common::{build_backlog_router_for_tests, setup_test_db};
+
+async fn insert_organization(pool: &PgPool, name: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO organizations (id, external_id, name, slug, created_at, updated_at)
+         VALUES ($1, $2, $3, $2, NOW(), NOW())",
+    )
+    .bind(id)
+    .bind(format!("org_{}", id.simple()))
+    .bind(name)
+    .execute(pool)
+    .await
+    .expect("Failed to create test organization");
+    id
+}
+
+/// A user holding `role` in the organization
+async fn insert_member(pool: &PgPool, organization_id: Uuid, role: &str) -> Uuid {
+    let user_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO users (id, external_id, email, role, created_at, updated_at)
+         VALUES ($1, $2, $3, 'product_owner', NOW(), NOW())",
+    )
+    .bind(user_id)
+    .bind(format!("user_{}", user_id.simple()))
+    .bind(format!("{}@example.com", user_id.simple()))
+    .execute(pool)
+    .await
+    .expect("Failed to create test user");
+    sqlx::query(
+        "INSERT INTO organization_memberships (id, organization_id, user_id, role, created_at, updated_at)
+         VALUES ($1, $2, $3, $4, NOW(), NOW())",
+    )
+    .bind(Uuid::new_v4())
+    .bind(organization_id)
+    .bind(user_id)
+    .bind(role)
+    .execute(pool)
+    .await
+    .expect("Failed to create test membership");
+    user_id
+}
+
+async fn get_as(app: &Router, uri: String, org_id: Uuid) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(uri)
+                .header("authorization", "Bearer valid-test-token")
+                .header("x-organization-id", org_id.to_string())
+                .header("x-context-type", "organization")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_shared_stories_are_readable_until_revoked() {
+    let pool = setup_test_db().await;
+    let events: Arc<dyn EventPublisher> = Arc::new(EventBus::new());
+    let usecases = backlog::build_usecases(pool.clone(), events);
+    let app = build_backlog_router_for_tests(pool.clone()).await;
+
+    let agency = insert_organization(&pool, "Agency").await;
+    let client = insert_organization(&pool, "Client").await;
+    let stranger = insert_organization(&pool, "Stranger").await;
+    let admin = insert_member(&pool, agency, "admin").await;
+    let member = insert_member(&pool, agency, "member").await;
+    let project_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO projects (id, organization_id, name, created_at, updated_at)
+         VALUES ($1, $2, 'Client Work', NOW(), NOW())",
+    )
+    .bind(project_id)
+    .bind(agency)
+    .execute(&pool)
+    .await
+    .expect("Failed to create test project");
+
+    let mut story_ids = Vec::new();
+    for title in ["Checkout redesign", "Internal tooling"] {
+        let story_id = usecases
+            .create_story(
+                project_id,
+                Some(agency),
+                title.to_string(),
+                None,
+                vec![],
+                StoryType::Feature,
+                None,
+                None,
+            )
+            .await
+            .expect("story should be created");
+        story_ids.push(story_id);
+    }
+    let (shared, private) = (story_ids[0], story_ids[1]);
+
+    // Only owners and admins share
+    let denied = usecases
+        .share_stories(project_id, Some(agency), client, vec![shared], member)
+        .await;
+    assert!(matches!(denied, Err(AppError::Forbidden(_))));
+
+    let shares = usecases
+        .share_stories(project_id, Some(agency), client, vec![shared], admin)
+        .await
+        .expect("story should be shared");
+    assert_eq!(shares.len(), 1);
+
+    let (status, story) = get_as(&app, format!("/api/v1/stories/{}", shared), client).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(story["id"], shared.to_string());
+    assert_eq!(story["readOnly"], true);
+
+    let (status, story) = get_as(&app, format!("/api/v1/stories/{}", shared), agency).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(story["readOnly"], false);
+
+    let (status, _) = get_as(&app, format!("/api/v1/stories/{}", private), client).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_as(&app, format!("/api/v1/stories/{}", shared), stranger).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // The owner sees the whole project, the client only what was shared
+    let project_stories = format!("/api/v1/projects/{}/stories", project_id);
+    let (status, stories) = get_as(&app, project_stories.clone(), agency).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(stories.as_array().unwrap().len(), 2);
+    let (status, stories) = get_as(&app, project_stories.clone(), client).await;
+    assert_eq!(status, StatusCode::OK);
+    let stories = stories.as_array().unwrap();
+    assert_eq!(stories.len(), 1);
+    assert_eq!(stories[0]["id"], shared.to_string());
+    let (_, stories) = get_as(&app, project_stories.clone(), stranger).await;
+    assert!(stories.as_array().unwrap().is_empty());
+
+    let denied = usecases
+        .revoke_story_share(project_id, Some(agency), shares[0].id, member)
+        .await;
+    assert!(matches!(denied, Err(AppError::Forbidden(_))));
+    let (_, stories) = get_as(&app, project_stories.clone(), client).await;
+    assert_eq!(stories.as_array().unwrap().len(), 1);
+
+    let revoked = usecases
+        .revoke_story_share(project_id, Some(agency), shares[0].id, admin)
+        .await
+        .expect("share should be revoked");
+    assert!(revoked.revoked_at.is_some());
+
+    let (status, _) = get_as(&app, format!("/api/v1/stories/{}", shared), client).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, stories) = get_as(&app, project_stories, client).await;
+    assert!(stories.as_array().unwrap().is_empty());
+    assert!(usecases
+        .get_readable_story(shared, Some(agency))
+        .await
+        .unwrap()
+        .is_some());
+
+    sqlx::query("DELETE FROM story_shares WHERE project_id = $1")
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM stories WHERE project_id = $1")
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM projects WHERE id = $1")
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM organizations WHERE id = ANY($1)")
+        .bind(vec![agency, client, stranger])
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+        .bind(vec![admin, member])
+        .execute(&pool)
+        .await
+        .ok();
+}
@@ -0,0 +1,125 @@
+use backlog::adapters::persistence::{repo, UnitOfWork};
+use backlog::domain::{Story, Task};
+use common::AppError;
+use event_bus::{BacklogEvent, DomainEvent};
+use serial_test::serial;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::setup_test_db;
+
+async fn insert_project(pool: &PgPool, org_id: Uuid) -> Uuid {
+    let project_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO projects (id, organization_id, name, description, created_at, updated_at)
+         VALUES ($1, $2, $3, $4, NOW(), NOW())",
+    )
+    .bind(project_id)
+    .bind(org_id)
+    .bind(format!("Unit of Work Project {}", project_id))
+    .bind("Test Description")
+    .execute(pool)
+    .await
+    .expect("Failed to create test project");
+    project_id
+}
+
+#[tokio::test]
+#[serial]
+async fn test_unit_of_work_commits_story_and_tasks_together() {
+    let pool = setup_test_db().await;
+    let org_id = Uuid::new_v4();
+    let project_id = insert_project(&pool, org_id).await;
+
+    let story = Story::new(project_id, Some(org_id), "Atomic story".to_string(), None).unwrap();
+    let task = Task::new(
+        story.id,
+        Some(org_id),
+        "Atomic task".to_string(),
+        None,
+        vec!["AC1".to_string()],
+    )
+    .unwrap();
+
+    let mut uow = UnitOfWork::begin(&pool).await.unwrap();
+    let result = async {
+        repo::create_story_with_transaction(uow.tx(), &story).await?;
+        repo::create_task_with_transaction(uow.tx(), &task).await?;
+        Ok(())
+    }
+    .await;
+    uow.finish(result).await.unwrap();
+
+    assert!(repo::get_story(&pool, story.id, Some(org_id))
+        .await
+        .unwrap()
+        .is_some());
+    assert!(repo::get_task(&pool, task.id, Some(org_id))
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_unit_of_work_rolls_back_on_app_error() {
+    let pool = setup_test_db().await;
+    let org_id = Uuid::new_v4();
+    let project_id = insert_project(&pool, org_id).await;
+
+    let story = Story::new(project_id, Some(org_id), "Doomed story".to_string(), None).unwrap();
+
+    let mut uow = UnitOfWork::begin(&pool).await.unwrap();
+    let result: Result<(), AppError> = async {
+        repo::create_story_with_transaction(uow.tx(), &story).await?;
+        Err(AppError::BadRequest("task validation failed".to_string()))
+    }
+    .await;
+    let outcome = uow.finish(result).await;
+
+    assert!(matches!(outcome, Err(AppError::BadRequest(_))));
+    assert!(repo::get_story(&pool, story.id, Some(org_id))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+async fn outbox_has_story_event(pool: &PgPool, story_id: Uuid) -> bool {
+    sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (
+             SELECT 1 FROM event_outbox
+             WHERE envelope #>> '{event,Backlog,StoryDeleted,story_id}' = $1
+         )",
+    )
+    .bind(story_id.to_string())
+    .fetch_one(pool)
+    .await
+    .expect("Failed to read outbox")
+}
+
+#[tokio::test]
+#[serial]
+async fn test_unit_of_work_events_commit_and_roll_back_with_the_change() {
+    let pool = setup_test_db().await;
+    let (kept, dropped) = (Uuid::new_v4(), Uuid::new_v4());
+    let deleted = |story_id| {
+        DomainEvent::Backlog(BacklogEvent::StoryDeleted {
+            story_id,
+            organization_id: None,
+        })
+    };
+
+    let mut uow = UnitOfWork::begin(&pool).await.unwrap();
+    uow.publish(deleted(kept)).await.unwrap();
+    uow.finish(Ok(())).await.unwrap();
+
+    let mut uow = UnitOfWork::begin(&pool).await.unwrap();
+    uow.publish(deleted(dropped)).await.unwrap();
+    let outcome: Result<(), AppError> = uow
+        .finish(Err(AppError::BadRequest("rejected".to_string())))
+        .await;
+
+    assert!(outcome.is_err());
+    assert!(outbox_has_story_event(&pool, kept).await);
+    assert!(!outbox_has_story_event(&pool, dropped).await);
+}
@@ -0,0 +1,5 @@
+//! Public API of the context orchestrator service. The router owns its
+//! usecases; nothing else in `context_orchestrator` is meant to be used from
+//! outside it.
+
+pub use context_orchestrator::create_context_orchestrator_router;
@@ -1,4 +1,12 @@
+use crate::application::ports::UtteranceAuditRepository;
+use crate::domain::{
+    analytics_window, apply_clarification, clarification_threshold, clarify, utterance_hash,
+    CandidateEntity, Clarification, ClarificationAnswer, ClarificationReason, EntityReference,
+    IntentType, ParsedIntent, UtteranceAction, UtteranceAnalytics, UtteranceInterpretation,
+    UtteranceOutcome,
+};
 use crate::projections;
+use common::route_registry::ServiceRouter;
 use common::AppError;
 use shuttle_axum::axum::extract::{Query, State};
 use shuttle_axum::axum::response::Json;
@@ -12,7 +20,7 @@ use shuttle_axum::axum::{
 };
 
 // use crate::AppState; // Comment out since AppState is in main.rs
-use auth_clerk::JwtVerifier;
+use auth_clerk::{AuthenticatedWithOrg, JwtVerifier};
 use chrono::Utc;
 use event_bus::EventBus;
 use serde::{Deserialize, Serialize};
@@ -28,33 +36,69 @@ const MAX_REQUEST_BODY_SIZE: usize = 10 * 1024 * 1024;
 #[derive(Clone)]
 pub struct OrchestratorState {
     pub pool: PgPool,
+    /// Interpretations less confident than this ask the user before acting
+    pub clarification_threshold: f32,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InterpretRequest {
     pub utterance: String,
+    #[serde(alias = "context_limit")]
     pub context_limit: Option<usize>,
+    #[serde(alias = "entity_types")]
     pub entity_types: Option<Vec<String>>,
+    #[serde(alias = "require_confirmation")]
     pub require_confirmation: Option<bool>,
+    /// The user's reply to the previous turn's `needsClarification`
+    pub clarification: Option<ClarificationReplyDto>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClarificationReplyDto {
+    /// Session token of the interpretation that asked
+    #[serde(alias = "session_token")]
+    pub session_token: Uuid,
+    #[serde(alias = "intent_type")]
+    pub intent_type: Option<String>,
+    #[serde(alias = "entity_id")]
+    pub entity_id: Option<Uuid>,
+    #[serde(alias = "entity_type")]
+    pub entity_type: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InterpretResponse {
     pub intent: ParsedIntentDto,
     pub confidence: ConfidenceDto,
     pub candidates: Vec<CandidateEntityDto>,
     pub requires_confirmation: bool,
     pub session_token: Option<Uuid>,
+    pub needs_clarification: Option<ClarificationDto>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClarificationDto {
+    pub reasons: Vec<ClarificationReason>,
+    pub question: String,
+    pub intent_options: Vec<String>,
+    pub entity_options: Vec<CandidateEntityDto>,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ActRequest {
     pub action: ActionCommandDto,
+    #[serde(alias = "session_token")]
     pub session_token: Option<Uuid>,
     pub confirmed: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ActResponse {
     pub success: bool,
     pub results: Vec<ActionResultDto>,
@@ -63,20 +107,26 @@ pub struct ActResponse {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ParsedIntentDto {
+    #[serde(alias = "intent_type")]
     pub intent_type: String,
     pub entities: Vec<EntityReferenceDto>,
     pub parameters: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EntityReferenceDto {
+    #[serde(alias = "entity_id")]
     pub entity_id: Uuid,
+    #[serde(alias = "entity_type")]
     pub entity_type: String,
     pub role: String,
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ConfidenceDto {
     pub llm_confidence: f32,
     pub service_confidence: f32,
@@ -84,6 +134,7 @@ pub struct ConfidenceDto {
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CandidateEntityDto {
     pub id: Uuid,
     pub entity_type: String,
@@ -95,15 +146,21 @@ pub struct CandidateEntityDto {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ActionCommandDto {
+    #[serde(alias = "action_type")]
     pub action_type: String,
+    #[serde(alias = "target_entities")]
     pub target_entities: Vec<Uuid>,
     pub parameters: HashMap<String, serde_json::Value>,
+    #[serde(alias = "require_confirmation")]
     pub require_confirmation: bool,
+    #[serde(alias = "risk_level")]
     pub risk_level: String,
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ActionResultDto {
     pub service: String,
     pub success: bool,
@@ -112,19 +169,28 @@ pub struct ActionResultDto {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InterpretQueryParams {
     pub debug: Option<bool>,
+    #[serde(alias = "disable_llm")]
     pub disable_llm: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SuggestionsQuery {
-    #[serde(alias = "projectId")]
+    #[serde(alias = "project_id")]
     pub project_id: Option<String>,
     pub cursor: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsQuery {
+    pub days: Option<i64>,
+}
+
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SuggestionDto {
     pub id: String,
     #[serde(rename = "type")]
@@ -134,24 +200,23 @@ pub struct SuggestionDto {
     pub priority: String,
     pub confidence: f32,
     pub actionable: bool,
-    #[serde(rename = "entityId", skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub entity_id: Option<String>,
-    #[serde(rename = "entityType", skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub entity_type: Option<String>,
-    #[serde(rename = "suggestedAction", skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub suggested_action: Option<String>,
-    #[serde(rename = "createdAt")]
     pub created_at: String,
-    #[serde(rename = "dismissedAt", skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dismissed_at: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SuggestionsResponseDto {
     pub suggestions: Vec<SuggestionDto>,
-    #[serde(rename = "hasMore")]
     pub has_more: bool,
-    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub next_cursor: Option<String>,
 }
 
@@ -160,6 +225,7 @@ pub fn create_routes(state: OrchestratorState) -> Router<OrchestratorState> {
         .route("/interpret", post(interpret_handler))
         .route("/act", post(act_handler))
         .route("/suggestions", get(suggestions_handler))
+        .route("/analytics", get(analytics_handler))
         .route("/ready", shuttle_axum::axum::routing::get(ready_handler))
         .with_state(state)
 }
@@ -174,12 +240,20 @@ pub async fn create_context_orchestrator_router(
 
     projections::SprintProjectionWorker::spawn(Arc::new(pool.clone()), event_bus);
 
-    let state = OrchestratorState { pool };
+    let state = OrchestratorState {
+        pool,
+        clarification_threshold: clarification_threshold(
+            std::env::var("CONTEXT_CLARIFICATION_THRESHOLD")
+                .ok()
+                .as_deref(),
+        ),
+    };
 
-    Router::new()
+    ServiceRouter::new("context-orchestrator")
         .route("/interpret", post(interpret_handler))
         .route("/act", post(act_handler))
         .route("/suggestions", get(suggestions_handler))
+        .route("/analytics", get(analytics_handler))
         .layer(shuttle_axum::axum::Extension(verifier))
         .layer(middleware::from_fn(log_request_body))
         .with_state(state)
@@ -471,8 +545,9 @@ pub async fn suggestions_handler(
 }
 
 pub async fn interpret_handler(
-    // State(state): State<AppState>,
-    // claims: Authenticated,
+    State(state): State<OrchestratorState>,
+    // Anonymous callers are still served; their utterances are audited without a user
+    caller: Result<AuthenticatedWithOrg, AppError>,
     // Query(params): Query<InterpretQueryParams>,
     Json(request): Json<InterpretRequest>,
 ) -> Result<Json<InterpretResponse>, AppError> {
@@ -489,35 +564,235 @@ pub async fn interpret_handler(
         ));
     }
 
-    // Stub response for testing
-    let response = InterpretResponse {
-        intent: ParsedIntentDto {
-            intent_type: "update_status".to_string(),
-            entities: vec![EntityReferenceDto {
-                entity_id: Uuid::new_v4(),
-                entity_type: "story".to_string(),
-                role: "target".to_string(),
-            }],
-            parameters: std::collections::HashMap::new(),
-        },
-        confidence: ConfidenceDto {
-            llm_confidence: 0.8,
-            service_confidence: 0.7,
-            overall_confidence: 0.75,
-        },
-        candidates: vec![],
+    let caller = caller.ok();
+    let organization_id = caller
+        .as_ref()
+        .and_then(|caller| caller.org_context.effective_organization_uuid());
+    let answer = match &request.clarification {
+        Some(reply) => Some(clarification_answer(&state.pool, reply, organization_id).await?),
+        None => None,
+    };
+
+    // Stub interpretation for testing
+    let mut intent = ParsedIntent {
+        intent_type: IntentType::UpdateStatus,
+        entities: vec![EntityReference {
+            entity_id: Uuid::new_v4(),
+            entity_type: "story".to_string(),
+            role: "target".to_string(),
+        }],
+        parameters: HashMap::new(),
+    };
+    let candidates: Vec<CandidateEntity> = vec![];
+    let confidence = ConfidenceDto {
+        llm_confidence: 0.8,
+        service_confidence: 0.7,
+        overall_confidence: 0.75,
+    };
+
+    if let Some(answer) = &answer {
+        apply_clarification(&mut intent, answer);
+    }
+    let clarification = clarify(
+        request.utterance.trim(),
+        &intent,
+        confidence.overall_confidence,
+        &candidates,
+        state.clarification_threshold,
+        answer.as_ref(),
+    );
+
+    let mut response = InterpretResponse {
+        intent: parsed_intent_dto(&intent),
+        confidence,
+        candidates: candidates.iter().map(candidate_entity_dto).collect(),
         requires_confirmation: false,
         session_token: None,
+        needs_clarification: clarification.map(clarification_dto),
     };
 
+    let interpretation = UtteranceInterpretation {
+        id: Uuid::new_v4(),
+        organization_id,
+        user_id: caller.map(|caller| caller.auth.sub),
+        utterance_hash: utterance_hash(request.utterance.trim()),
+        intent_type: response.intent.intent_type.clone(),
+        parsed_intent: serde_json::to_value(&response.intent).unwrap_or_default(),
+        entity_ids: response
+            .intent
+            .entities
+            .iter()
+            .map(|entity| entity.entity_id)
+            .collect(),
+        candidate_ids: response
+            .candidates
+            .iter()
+            .map(|candidate| candidate.id)
+            .collect(),
+        confidence: response.confidence.overall_confidence,
+        outcome: UtteranceOutcome::Pending,
+        interpreted_at: Utc::now(),
+    };
+    // The audit must never cost the caller their answer
+    match state.pool.record_interpretation(&interpretation).await {
+        Ok(()) => response.session_token = Some(interpretation.id),
+        Err(error) => tracing::warn!(%error, "Failed to audit interpreted utterance"),
+    }
+
     Ok(Json(response))
 }
 
+/// Check a clarification reply against the interpretation that asked for it.
+/// Only the entities offered then can be picked now.
+async fn clarification_answer(
+    pool: &PgPool,
+    reply: &ClarificationReplyDto,
+    organization_id: Option<Uuid>,
+) -> Result<ClarificationAnswer, AppError> {
+    let asked = pool
+        .get_interpretation(reply.session_token, organization_id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Unknown clarification session".to_string()))?;
+
+    let intent_type = reply
+        .intent_type
+        .as_deref()
+        .map(IntentType::from_string)
+        .transpose()?;
+    let entity = match (reply.entity_id, &reply.entity_type) {
+        (None, _) => None,
+        (Some(_), None) => {
+            return Err(AppError::BadRequest(
+                "entityType is required with entityId".to_string(),
+            ))
+        }
+        (Some(entity_id), Some(entity_type)) => {
+            if !asked.candidate_ids.contains(&entity_id) && !asked.entity_ids.contains(&entity_id) {
+                return Err(AppError::BadRequest(
+                    "Entity was not among the clarification options".to_string(),
+                ));
+            }
+            Some(EntityReference {
+                entity_id,
+                entity_type: entity_type.clone(),
+                role: "target".to_string(),
+            })
+        }
+    };
+    Ok(ClarificationAnswer {
+        intent_type,
+        entity,
+    })
+}
+
+fn parsed_intent_dto(intent: &ParsedIntent) -> ParsedIntentDto {
+    ParsedIntentDto {
+        intent_type: intent.intent_type.to_string(),
+        entities: intent
+            .entities
+            .iter()
+            .map(|entity| EntityReferenceDto {
+                entity_id: entity.entity_id,
+                entity_type: entity.entity_type.clone(),
+                role: entity.role.clone(),
+            })
+            .collect(),
+        parameters: intent.parameters.clone(),
+    }
+}
+
+fn candidate_entity_dto(candidate: &CandidateEntity) -> CandidateEntityDto {
+    CandidateEntityDto {
+        id: candidate.id,
+        entity_type: candidate.entity_type.clone(),
+        title: candidate.title.clone(),
+        description: candidate.description.clone(),
+        status: candidate.status.clone(),
+        similarity_score: candidate.similarity_score,
+        boost_reason: None,
+    }
+}
+
+fn clarification_dto(clarification: Clarification) -> ClarificationDto {
+    ClarificationDto {
+        reasons: clarification.reasons,
+        question: clarification.question,
+        intent_options: clarification
+            .intent_options
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        entity_options: clarification
+            .entity_options
+            .iter()
+            .map(candidate_entity_dto)
+            .collect(),
+    }
+}
+
 pub async fn act_handler(
-    // State(state): State<AppState>,
-    // claims: Authenticated,
+    State(state): State<OrchestratorState>,
+    caller: Result<AuthenticatedWithOrg, AppError>,
     Json(request): Json<ActRequest>,
 ) -> Result<Json<ActResponse>, AppError> {
+    let action = UtteranceAction {
+        action_type: request.action.action_type.clone(),
+        target_ids: request.action.target_entities.clone(),
+        error: None,
+    };
+    let session_token = request.session_token;
+    let result = perform_action(request);
+
+    if let Some(session_token) = session_token {
+        let organization_id = caller
+            .ok()
+            .and_then(|caller| caller.org_context.effective_organization_uuid());
+        let action = UtteranceAction {
+            error: match &result {
+                Ok(response) if response.success => None,
+                Ok(response) => Some(
+                    response
+                        .results
+                        .iter()
+                        .filter(|result| !result.success)
+                        .map(|result| result.message.clone())
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                ),
+                Err(error) => Some(error.to_string()),
+            },
+            ..action
+        };
+        if let Err(error) =
+            resolve_utterance(&state.pool, session_token, organization_id, &action).await
+        {
+            tracing::warn!(%error, %session_token, "Failed to audit utterance outcome");
+        }
+    }
+
+    result.map(Json)
+}
+
+/// Record how the action taken on an interpretation turned out
+async fn resolve_utterance(
+    audit: &impl UtteranceAuditRepository,
+    session_token: Uuid,
+    organization_id: Option<Uuid>,
+    action: &UtteranceAction,
+) -> Result<(), AppError> {
+    let Some(interpretation) = audit
+        .get_interpretation(session_token, organization_id)
+        .await?
+    else {
+        return Ok(());
+    };
+    audit
+        .resolve_interpretation(session_token, action, interpretation.outcome_of(action))
+        .await?;
+    Ok(())
+}
+
+fn perform_action(request: ActRequest) -> Result<ActResponse, AppError> {
     // Validate action type
     let _action_type = crate::domain::ActionType::from_string(&request.action.action_type)?;
     let _risk_level = crate::domain::RiskLevel::from_string(&request.action.risk_level)?;
@@ -535,7 +810,22 @@ pub async fn act_handler(
         partial_success: false,
     };
 
-    Ok(Json(response))
+    Ok(response)
+}
+
+/// GET /api/v1/context/analytics?days=30
+pub async fn analytics_handler(
+    State(state): State<OrchestratorState>,
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Query(query): Query<AnalyticsQuery>,
+) -> Result<Json<UtteranceAnalytics>, AppError> {
+    let days = analytics_window(query.days)?;
+    let since = Utc::now() - chrono::Duration::days(days);
+    let counts = state
+        .pool
+        .count_outcomes(org_context.effective_organization_uuid(), since)
+        .await?;
+    Ok(Json(UtteranceAnalytics::summarize(since, &counts)))
 }
 
 pub async fn ready_handler() -> Result<Json<serde_json::Value>, AppError> {
@@ -561,6 +851,34 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    #[test]
+    fn test_act_payloads_are_camel_case() {
+        let response = ActResponse {
+            success: true,
+            results: Vec::new(),
+            rollback_token: None,
+            partial_success: false,
+        };
+        let value = serde_json::to_value(&response).unwrap();
+        assert!(value.get("rollbackToken").is_some());
+        assert!(value.get("partialSuccess").is_some());
+
+        // Snake case keys are still accepted from older clients
+        let request: ActRequest = serde_json::from_value(serde_json::json!({
+            "action": {
+                "action_type": "update_status",
+                "targetEntities": [],
+                "parameters": {},
+                "requireConfirmation": false,
+                "risk_level": "low"
+            },
+            "session_token": null
+        }))
+        .unwrap();
+        assert_eq!(request.action.action_type, "update_status");
+        assert_eq!(request.action.risk_level, "low");
+    }
+
     #[test]
     fn test_action_type_conversion() {
         let action_type = crate::domain::ActionType::UpdateStatus;
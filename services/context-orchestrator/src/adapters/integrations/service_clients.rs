@@ -474,6 +474,15 @@ impl ReadinessServiceClient for HttpReadinessClient {
                             .collect()
                     })
                     .unwrap_or_default(),
+                fixes: result
+                    .get("fixes")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(|v| serde_json::from_value(v.clone()).ok())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
             })
         } else if response.status() == StatusCode::NOT_FOUND {
             Err(AppError::NotFound("Resource not found".to_string()))
@@ -51,6 +51,48 @@ pub struct ContextSnapshotRow {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, FromRow)]
+pub struct UtteranceAuditRow {
+    pub id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub user_id: Option<String>,
+    pub utterance_hash: String,
+    pub intent_type: String,
+    pub parsed_intent: serde_json::Value,
+    pub entity_ids: Vec<Uuid>,
+    pub candidate_ids: Vec<Uuid>,
+    pub confidence: f64,
+    pub outcome: String,
+    pub interpreted_at: DateTime<Utc>,
+}
+
+impl From<UtteranceAuditRow> for crate::domain::UtteranceInterpretation {
+    fn from(row: UtteranceAuditRow) -> Self {
+        Self {
+            id: row.id,
+            organization_id: row.organization_id,
+            user_id: row.user_id,
+            utterance_hash: row.utterance_hash,
+            intent_type: row.intent_type,
+            parsed_intent: row.parsed_intent,
+            entity_ids: row.entity_ids,
+            candidate_ids: row.candidate_ids,
+            confidence: row.confidence as f32,
+            outcome: crate::domain::UtteranceOutcome::parse(&row.outcome)
+                .unwrap_or(crate::domain::UtteranceOutcome::Pending),
+            interpreted_at: row.interpreted_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct IntentOutcomeCountRow {
+    pub intent_type: String,
+    pub outcome: String,
+    pub count: i64,
+    pub confidence_sum: f64,
+}
+
 impl From<IntentHistoryRow> for crate::domain::IntentRecord {
     fn from(row: IntentHistoryRow) -> Self {
         let parsed_intent: crate::domain::ParsedIntent = serde_json::from_value(row.parsed_intent)
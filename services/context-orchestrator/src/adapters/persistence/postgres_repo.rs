@@ -1,8 +1,12 @@
+use crate::adapters::persistence::models::{IntentOutcomeCountRow, UtteranceAuditRow};
 use crate::application::ports::{
     AuditLogRepository, ContextSnapshotRepository, IntentAnalytics, IntentHistoryRepository,
-    RateLimitBucket, RateLimitRepository,
+    RateLimitBucket, RateLimitRepository, UtteranceAuditRepository,
+};
+use crate::domain::{
+    ContextSnapshot, IntentOutcomeCount, IntentRecord, UtteranceAction, UtteranceInterpretation,
+    UtteranceOutcome,
 };
-use crate::domain::{ContextSnapshot, IntentRecord};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use common::AppError;
@@ -48,6 +52,118 @@ impl IntentHistoryRepository for PgPool {
     }
 }
 
+#[async_trait]
+impl UtteranceAuditRepository for PgPool {
+    async fn record_interpretation(
+        &self,
+        interpretation: &UtteranceInterpretation,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO utterance_audit
+                (id, organization_id, user_id, utterance_hash, intent_type, parsed_intent,
+                 entity_ids, candidate_ids, confidence, outcome, interpreted_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+        )
+        .bind(interpretation.id)
+        .bind(interpretation.organization_id)
+        .bind(&interpretation.user_id)
+        .bind(&interpretation.utterance_hash)
+        .bind(&interpretation.intent_type)
+        .bind(&interpretation.parsed_intent)
+        .bind(&interpretation.entity_ids)
+        .bind(&interpretation.candidate_ids)
+        .bind(interpretation.confidence.clamp(0.0, 1.0) as f64)
+        .bind(interpretation.outcome.as_str())
+        .bind(interpretation.interpreted_at)
+        .execute(self)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error recording utterance interpretation");
+            AppError::InternalServerError
+        })?;
+        Ok(())
+    }
+
+    async fn get_interpretation(
+        &self,
+        id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<UtteranceInterpretation>, AppError> {
+        let row = sqlx::query_as::<_, UtteranceAuditRow>(
+            "SELECT id, organization_id, user_id, utterance_hash, intent_type, parsed_intent,
+                    entity_ids, candidate_ids, confidence, outcome, interpreted_at
+             FROM utterance_audit
+             WHERE id = $1 AND organization_id IS NOT DISTINCT FROM $2",
+        )
+        .bind(id)
+        .bind(organization_id)
+        .fetch_optional(self)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error fetching utterance interpretation");
+            AppError::InternalServerError
+        })?;
+        Ok(row.map(UtteranceInterpretation::from))
+    }
+
+    async fn resolve_interpretation(
+        &self,
+        id: Uuid,
+        action: &UtteranceAction,
+        outcome: UtteranceOutcome,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE utterance_audit
+             SET outcome = $2, action_type = $3, target_ids = $4, error = $5, acted_at = NOW()
+             WHERE id = $1 AND outcome = 'pending'",
+        )
+        .bind(id)
+        .bind(outcome.as_str())
+        .bind(&action.action_type)
+        .bind(&action.target_ids)
+        .bind(&action.error)
+        .execute(self)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error resolving utterance interpretation");
+            AppError::InternalServerError
+        })?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn count_outcomes(
+        &self,
+        organization_id: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<IntentOutcomeCount>, AppError> {
+        let rows = sqlx::query_as::<_, IntentOutcomeCountRow>(
+            "SELECT intent_type, outcome, COUNT(*) AS count, SUM(confidence) AS confidence_sum
+             FROM utterance_audit
+             WHERE organization_id IS NOT DISTINCT FROM $1 AND interpreted_at >= $2
+             GROUP BY intent_type, outcome",
+        )
+        .bind(organization_id)
+        .bind(since)
+        .fetch_all(self)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "SQL error counting utterance outcomes");
+            AppError::InternalServerError
+        })?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(IntentOutcomeCount {
+                    outcome: UtteranceOutcome::parse(&row.outcome)?,
+                    intent_type: row.intent_type,
+                    count: row.count,
+                    confidence_sum: row.confidence_sum,
+                })
+            })
+            .collect())
+    }
+}
+
 #[async_trait]
 impl RateLimitRepository for PgPool {
     async fn get_rate_limit_bucket(
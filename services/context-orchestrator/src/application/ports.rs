@@ -1,4 +1,7 @@
-use crate::domain::{CandidateEntity, ContextSnapshot, IntentRecord};
+use crate::domain::{
+    CandidateEntity, ContextSnapshot, IntentOutcomeCount, IntentRecord, UtteranceAction,
+    UtteranceInterpretation, UtteranceOutcome,
+};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use common::AppError;
@@ -23,6 +26,33 @@ pub trait IntentHistoryRepository: Send + Sync {
     ) -> Result<IntentAnalytics, AppError>;
 }
 
+/// The audit of interpreted utterances and the actions that resolved them
+#[async_trait]
+pub trait UtteranceAuditRepository: Send + Sync {
+    async fn record_interpretation(
+        &self,
+        interpretation: &UtteranceInterpretation,
+    ) -> Result<(), AppError>;
+    async fn get_interpretation(
+        &self,
+        id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<UtteranceInterpretation>, AppError>;
+    /// Record the action on a still-pending interpretation; false if it was
+    /// already resolved
+    async fn resolve_interpretation(
+        &self,
+        id: Uuid,
+        action: &UtteranceAction,
+        outcome: UtteranceOutcome,
+    ) -> Result<bool, AppError>;
+    async fn count_outcomes(
+        &self,
+        organization_id: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<IntentOutcomeCount>, AppError>;
+}
+
 #[async_trait]
 pub trait RateLimitRepository: Send + Sync {
     async fn get_rate_limit_bucket(
@@ -277,6 +307,17 @@ pub struct ReadinessResult {
     pub missing_criteria: Vec<String>,
     pub readiness_score: f32,
     pub blockers: Vec<String>,
+    /// Actionable fixes for the missing criteria, as reported by the readiness service
+    pub fixes: Vec<ReadinessFixSuggestion>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReadinessFixSuggestion {
+    #[serde(rename = "type")]
+    pub fix_type: String,
+    pub target: Uuid,
+    pub method: String,
+    pub path: String,
 }
 
 // Health check helpers
@@ -445,11 +445,22 @@ impl ActUseCase {
                 Ok(readiness_result) => {
                     if !readiness_result.is_ready {
                         success = false;
-                        results.push(format!(
+                        let mut message = format!(
                             "Story {} not ready: missing {}",
                             entity_id,
                             readiness_result.missing_criteria.join(", ")
-                        ));
+                        );
+                        if !readiness_result.fixes.is_empty() {
+                            let fixes: Vec<String> = readiness_result
+                                .fixes
+                                .iter()
+                                .map(|fix| {
+                                    format!("{} ({} {})", fix.fix_type, fix.method, fix.path)
+                                })
+                                .collect();
+                            message.push_str(&format!("; suggested fixes: {}", fixes.join(", ")));
+                        }
+                        results.push(message);
                         continue;
                     }
 
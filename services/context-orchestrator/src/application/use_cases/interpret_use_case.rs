@@ -2,7 +2,8 @@ use crate::application::ports::{
     IntentHistoryRepository, LlmClient, RateLimitRepository, VectorSearchRepository,
 };
 use crate::domain::{
-    candidate_selector, intent_parser, CandidateEntity, IntentRecord, IntentType, ParsedIntent,
+    candidate_selector, clarify, intent_parser, CandidateEntity, Clarification, IntentRecord,
+    IntentType, ParsedIntent, DEFAULT_CLARIFICATION_THRESHOLD,
 };
 use chrono::Utc;
 use common::AppError;
@@ -14,6 +15,7 @@ pub struct InterpretUseCase {
     rate_limit_repo: Arc<dyn RateLimitRepository>,
     qdrant_repo: Arc<dyn VectorSearchRepository>,
     llm_client: Arc<dyn LlmClient>,
+    clarification_threshold: f32,
 }
 
 #[derive(Debug)]
@@ -22,6 +24,8 @@ pub struct InterpretResult {
     pub candidates: Vec<CandidateEntity>,
     pub requires_confirmation: bool,
     pub session_token: Option<Uuid>,
+    /// Set when the intent or its target is too uncertain to act on
+    pub clarification: Option<Clarification>,
 }
 
 impl InterpretUseCase {
@@ -36,9 +40,15 @@ impl InterpretUseCase {
             rate_limit_repo,
             qdrant_repo,
             llm_client,
+            clarification_threshold: DEFAULT_CLARIFICATION_THRESHOLD,
         }
     }
 
+    pub fn with_clarification_threshold(mut self, threshold: f32) -> Self {
+        self.clarification_threshold = threshold;
+        self
+    }
+
     pub async fn execute(
         &self,
         user_id: Uuid,
@@ -69,7 +79,7 @@ impl InterpretUseCase {
         // 4. Rank and filter candidates
         let filtered_candidates = candidate_selector::filter_by_tenant(candidates, tenant_id);
 
-        let _ranked_candidates =
+        let ranked_candidates =
             candidate_selector::rank_candidates(filtered_candidates.clone(), utterance);
 
         // 5. Parse intent using LLM or fallback heuristics
@@ -111,11 +121,22 @@ impl InterpretUseCase {
             None
         };
 
+        // 10. Ask rather than guess when the intent or target is unclear
+        let clarification = clarify(
+            utterance,
+            &parsed_intent,
+            intent_record.service_confidence,
+            &ranked_candidates,
+            self.clarification_threshold,
+            None,
+        );
+
         Ok(InterpretResult {
             intent: intent_record,
             candidates: filtered_candidates,
             requires_confirmation,
             session_token,
+            clarification,
         })
     }
 
@@ -191,19 +212,8 @@ impl InterpretUseCase {
             .await?;
 
         // Convert LLM response to domain ParsedIntent
-        let intent_type = match llm_response.intent_type.as_str() {
-            "update_status" => IntentType::UpdateStatus,
-            "assign_task" => IntentType::AssignTask,
-            "create_item" => IntentType::CreateItem,
-            "query_status" => IntentType::QueryStatus,
-            "search_items" => IntentType::SearchItems,
-            "update_priority" => IntentType::UpdatePriority,
-            "add_comment" => IntentType::AddComment,
-            "move_to_sprint" => IntentType::MoveToSprint,
-            "generate_report" => IntentType::GenerateReport,
-            "archive" => IntentType::Archive,
-            _ => IntentType::Unknown,
-        };
+        let intent_type =
+            IntentType::from_string(&llm_response.intent_type).unwrap_or(IntentType::Unknown);
 
         let entities: Result<Vec<crate::domain::EntityReference>, AppError> = llm_response
             .entities
@@ -390,6 +400,7 @@ mod tests {
                 rate_limit_repo: Arc::new(MockRateLimitRepository),
                 qdrant_repo: Arc::new(MockRepository),
                 llm_client: Arc::new(MockLlmClient),
+                clarification_threshold: DEFAULT_CLARIFICATION_THRESHOLD,
             }
         }
     }
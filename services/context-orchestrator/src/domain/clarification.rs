@@ -0,0 +1,308 @@
+use crate::domain::{
+    CandidateEntity, CandidateSelector, EntityReference, IntentType, ParsedIntent,
+};
+use serde::Serialize;
+
+/// Below this confidence the interpret flow asks instead of guessing
+pub const DEFAULT_CLARIFICATION_THRESHOLD: f32 = 0.6;
+/// Top candidates scoring closer than this are too close to call
+const AMBIGUOUS_SIMILARITY_GAP: f32 = 0.05;
+const MAX_INTENT_OPTIONS: usize = 3;
+const MAX_ENTITY_OPTIONS: usize = 5;
+
+/// Offered when the utterance gives nothing away
+const COMMON_INTENTS: [IntentType; 4] = [
+    IntentType::UpdateStatus,
+    IntentType::TakeOwnership,
+    IntentType::CompleteTask,
+    IntentType::QueryStatus,
+];
+
+/// Words that hint at each intent, in the order the heuristic parser tries them
+const INTENT_CUES: [(IntentType, &[&str]); 13] = [
+    (
+        IntentType::TakeOwnership,
+        &["take", "ownership", "picking up"],
+    ),
+    (
+        IntentType::ReleaseOwnership,
+        &["release", "give up", "drop"],
+    ),
+    (IntentType::CompleteTask, &["done", "finished", "complete"]),
+    (IntentType::StartWork, &["start", "begin", "working on"]),
+    (IntentType::UpdateStatus, &["move", "change", "status"]),
+    (IntentType::MoveToSprint, &["sprint"]),
+    (IntentType::CreateItem, &["create", "add", "new"]),
+    (IntentType::Archive, &["delete", "remove", "archive"]),
+    (IntentType::GenerateReport, &["generate", "report", "plan"]),
+    (IntentType::QueryStatus, &["what", "show", "get", "find"]),
+    (IntentType::SearchItems, &["search"]),
+    (IntentType::UpdatePriority, &["priority"]),
+    (IntentType::AddComment, &["comment"]),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClarificationReason {
+    /// The parsed intent is below the confidence threshold
+    UncertainIntent,
+    /// Several entities fit the utterance about equally well
+    AmbiguousEntity,
+}
+
+/// What the interpret flow asks the user before acting on an utterance
+#[derive(Debug, Clone)]
+pub struct Clarification {
+    pub reasons: Vec<ClarificationReason>,
+    pub question: String,
+    pub intent_options: Vec<IntentType>,
+    pub entity_options: Vec<CandidateEntity>,
+}
+
+/// The user's reply to a clarification, sent with the next utterance
+#[derive(Debug, Clone, Default)]
+pub struct ClarificationAnswer {
+    pub intent_type: Option<IntentType>,
+    pub entity: Option<EntityReference>,
+}
+
+/// The threshold from configuration, falling back to the default when the
+/// value is missing or not a confidence between 0 and 1
+pub fn clarification_threshold(raw: Option<&str>) -> f32 {
+    raw.and_then(|value| value.trim().parse::<f32>().ok())
+        .filter(|threshold| (0.0..=1.0).contains(threshold))
+        .unwrap_or(DEFAULT_CLARIFICATION_THRESHOLD)
+}
+
+/// Take the user's answer as the intent and target
+pub fn apply_clarification(intent: &mut ParsedIntent, answer: &ClarificationAnswer) {
+    if let Some(intent_type) = &answer.intent_type {
+        intent.intent_type = intent_type.clone();
+    }
+    if let Some(entity) = &answer.entity {
+        intent.entities.retain(|existing| existing.role != "target");
+        intent.entities.insert(0, entity.clone());
+    }
+}
+
+/// Whether the interpretation is too unsure to act on, and if so what to ask.
+/// Whatever `answer` settled is not asked again.
+pub fn clarify(
+    utterance: &str,
+    intent: &ParsedIntent,
+    confidence: f32,
+    candidates: &[CandidateEntity],
+    threshold: f32,
+    answer: Option<&ClarificationAnswer>,
+) -> Option<Clarification> {
+    let answer = answer.cloned().unwrap_or_default();
+    let mut ranked = candidates.to_vec();
+    ranked.sort_by(|a, b| b.similarity_score.total_cmp(&a.similarity_score));
+
+    let mut reasons = Vec::new();
+    let mut intent_options = Vec::new();
+    let mut entity_options = Vec::new();
+
+    if answer.intent_type.is_none()
+        && (intent.intent_type == IntentType::Unknown || confidence < threshold)
+    {
+        reasons.push(ClarificationReason::UncertainIntent);
+        intent_options = intent_alternatives(utterance, &intent.intent_type);
+    }
+
+    let has_target = intent.entities.iter().any(|entity| entity.role == "target");
+    let close_call = ranked.len() > 1
+        && ranked[0].similarity_score - ranked[1].similarity_score < AMBIGUOUS_SIMILARITY_GAP;
+    let unsure_of_entity = CandidateSelector::calculate_confidence(&ranked) < threshold;
+    if answer.entity.is_none()
+        && needs_target(&intent.intent_type)
+        && ranked.len() > 1
+        && (!has_target || close_call || unsure_of_entity)
+    {
+        reasons.push(ClarificationReason::AmbiguousEntity);
+        entity_options = ranked.into_iter().take(MAX_ENTITY_OPTIONS).collect();
+    }
+
+    let question = match reasons.as_slice() {
+        [] => return None,
+        [ClarificationReason::UncertainIntent] => "What would you like to do?",
+        [ClarificationReason::AmbiguousEntity] => "Which item did you mean?",
+        _ => "What would you like to do, and to which item?",
+    };
+    Some(Clarification {
+        reasons,
+        question: question.to_string(),
+        intent_options,
+        entity_options,
+    })
+}
+
+fn needs_target(intent_type: &IntentType) -> bool {
+    !matches!(
+        intent_type,
+        IntentType::CreateItem | IntentType::SearchItems | IntentType::GenerateReport
+    )
+}
+
+/// Intents the utterance hints at besides `chosen`, or the common intents
+/// when it hints at none
+fn intent_alternatives(utterance: &str, chosen: &IntentType) -> Vec<IntentType> {
+    let utterance = utterance.to_lowercase();
+    let hinted: Vec<IntentType> = INTENT_CUES
+        .iter()
+        .filter(|(_, cues)| cues.iter().any(|cue| utterance.contains(cue)))
+        .map(|(intent_type, _)| intent_type.clone())
+        .collect();
+    let options = if hinted.iter().any(|intent_type| intent_type != chosen) {
+        hinted
+    } else {
+        COMMON_INTENTS.to_vec()
+    };
+
+    let mut alternatives: Vec<IntentType> = Vec::new();
+    if *chosen != IntentType::Unknown {
+        alternatives.push(chosen.clone());
+    }
+    for intent_type in options {
+        if !alternatives.contains(&intent_type) {
+            alternatives.push(intent_type);
+        }
+    }
+    alternatives.truncate(MAX_INTENT_OPTIONS);
+    alternatives
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn candidate(title: &str, similarity_score: f32) -> CandidateEntity {
+        CandidateEntity {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            entity_type: "story".to_string(),
+            title: title.to_string(),
+            description: None,
+            status: None,
+            priority: None,
+            tags: vec![],
+            metadata: HashMap::new(),
+            similarity_score,
+            last_updated: Utc::now(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn intent(intent_type: IntentType, target: Option<&CandidateEntity>) -> ParsedIntent {
+        ParsedIntent {
+            intent_type,
+            entities: target
+                .map(|target| EntityReference {
+                    entity_id: target.id,
+                    entity_type: target.entity_type.clone(),
+                    role: "target".to_string(),
+                })
+                .into_iter()
+                .collect(),
+            parameters: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_asks_only_when_unsure() {
+        let checkout = candidate("Checkout with saved card", 0.91);
+        let guest = candidate("Guest checkout", 0.89);
+        let wishlist = candidate("Wishlist", 0.4);
+
+        let confident = intent(IntentType::UpdateStatus, Some(&checkout));
+        assert!(clarify(
+            "move checkout to done",
+            &confident,
+            0.9,
+            &[checkout.clone(), wishlist.clone()],
+            DEFAULT_CLARIFICATION_THRESHOLD,
+            None,
+        )
+        .is_none());
+
+        let close_call = clarify(
+            "move checkout to done",
+            &confident,
+            0.9,
+            &[guest.clone(), checkout.clone(), wishlist],
+            DEFAULT_CLARIFICATION_THRESHOLD,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            close_call.reasons,
+            vec![ClarificationReason::AmbiguousEntity]
+        );
+        assert_eq!(close_call.entity_options[0].id, checkout.id);
+        assert!(close_call.intent_options.is_empty());
+
+        let unsure = clarify(
+            "checkout thing",
+            &intent(IntentType::Unknown, None),
+            0.3,
+            &[],
+            DEFAULT_CLARIFICATION_THRESHOLD,
+            None,
+        )
+        .unwrap();
+        assert_eq!(unsure.reasons, vec![ClarificationReason::UncertainIntent]);
+        assert_eq!(unsure.intent_options, COMMON_INTENTS[..3].to_vec());
+    }
+
+    #[test]
+    fn test_answer_settles_what_it_covers() {
+        let checkout = candidate("Checkout with saved card", 0.7);
+        let guest = candidate("Guest checkout", 0.69);
+        let mut parsed = intent(IntentType::Unknown, None);
+        let utterance = "is checkout done or should I start it";
+
+        let asked = clarify(
+            utterance,
+            &parsed,
+            0.3,
+            &[checkout.clone(), guest.clone()],
+            0.6,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            asked.intent_options,
+            vec![IntentType::CompleteTask, IntentType::StartWork]
+        );
+
+        let answer = ClarificationAnswer {
+            intent_type: Some(IntentType::CompleteTask),
+            entity: Some(EntityReference {
+                entity_id: guest.id,
+                entity_type: "story".to_string(),
+                role: "target".to_string(),
+            }),
+        };
+        apply_clarification(&mut parsed, &answer);
+        assert_eq!(parsed.intent_type, IntentType::CompleteTask);
+        assert_eq!(parsed.entities[0].entity_id, guest.id);
+        assert!(clarify(
+            utterance,
+            &parsed,
+            0.3,
+            &[checkout, guest],
+            0.6,
+            Some(&answer)
+        )
+        .is_none());
+
+        assert_eq!(clarification_threshold(Some("0.8")), 0.8);
+        assert_eq!(
+            clarification_threshold(Some("2")),
+            DEFAULT_CLARIFICATION_THRESHOLD
+        );
+    }
+}
@@ -1,7 +1,6 @@
 use chrono::{DateTime, Utc};
 use common::AppError;
 use serde::{Deserialize, Serialize};
-use sha2::Digest;
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -222,14 +221,7 @@ impl IntentRecord {
         service_confidence: f32,
         candidates_considered: Vec<Uuid>,
     ) -> Self {
-        let utterance_hash = sha2::Sha256::digest(utterance.as_bytes()).iter().fold(
-            String::new(),
-            |mut output, b| {
-                use std::fmt::Write;
-                let _ = write!(output, "{:02x}", b);
-                output
-            },
-        );
+        let utterance_hash = super::utterance_hash(utterance);
 
         Self {
             id: Uuid::new_v4(),
@@ -326,3 +318,26 @@ impl std::fmt::Display for IntentType {
         }
     }
 }
+
+impl IntentType {
+    pub fn from_string(s: &str) -> Result<Self, AppError> {
+        match s {
+            "update_status" => Ok(Self::UpdateStatus),
+            "assign_task" => Ok(Self::AssignTask),
+            "take_ownership" => Ok(Self::TakeOwnership),
+            "release_ownership" => Ok(Self::ReleaseOwnership),
+            "start_work" => Ok(Self::StartWork),
+            "complete_task" => Ok(Self::CompleteTask),
+            "create_item" => Ok(Self::CreateItem),
+            "query_status" => Ok(Self::QueryStatus),
+            "search_items" => Ok(Self::SearchItems),
+            "update_priority" => Ok(Self::UpdatePriority),
+            "add_comment" => Ok(Self::AddComment),
+            "move_to_sprint" => Ok(Self::MoveToSprint),
+            "generate_report" => Ok(Self::GenerateReport),
+            "archive" => Ok(Self::Archive),
+            "unknown" => Ok(Self::Unknown),
+            _ => Err(AppError::BadRequest(format!("Invalid intent type: {}", s))),
+        }
+    }
+}
@@ -1,9 +1,13 @@
 pub mod action_validator;
 pub mod candidate_selector;
+pub mod clarification;
 pub mod context_entity;
 pub mod intent_parser;
+pub mod utterance_audit;
 
 pub use action_validator::*;
 pub use candidate_selector::*;
+pub use clarification::*;
 pub use context_entity::*;
 pub use intent_parser::*;
+pub use utterance_audit::*;
@@ -0,0 +1,299 @@
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::Serialize;
+use sha2::Digest;
+use uuid::Uuid;
+
+/// Hex SHA-256 of an utterance; the audit keeps this instead of the words
+pub fn utterance_hash(utterance: &str) -> String {
+    sha2::Sha256::digest(utterance.as_bytes())
+        .iter()
+        .fold(String::new(), |mut output, b| {
+            use std::fmt::Write;
+            let _ = write!(output, "{:02x}", b);
+            output
+        })
+}
+
+/// What became of an interpreted utterance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UtteranceOutcome {
+    /// Interpreted, not acted on yet
+    Pending,
+    /// Acted on as interpreted
+    Accepted,
+    /// Acted on, but with a different action or different targets
+    Overridden,
+    /// The action failed
+    Failed,
+}
+
+impl UtteranceOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Accepted => "accepted",
+            Self::Overridden => "overridden",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "accepted" => Some(Self::Accepted),
+            "overridden" => Some(Self::Overridden),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// An `/interpret` call as kept in the audit. Its id is the session token the
+/// matching `/act` call sends back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UtteranceInterpretation {
+    pub id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub user_id: Option<String>,
+    pub utterance_hash: String,
+    pub intent_type: String,
+    pub parsed_intent: serde_json::Value,
+    /// Entities the intent names, which an accepted action targets
+    pub entity_ids: Vec<Uuid>,
+    /// Candidates offered alongside the intent
+    pub candidate_ids: Vec<Uuid>,
+    pub confidence: f32,
+    pub outcome: UtteranceOutcome,
+    pub interpreted_at: DateTime<Utc>,
+}
+
+/// The `/act` call that resolved an interpretation
+#[derive(Debug, Clone, PartialEq)]
+pub struct UtteranceAction {
+    pub action_type: String,
+    pub target_ids: Vec<Uuid>,
+    pub error: Option<String>,
+}
+
+impl UtteranceInterpretation {
+    /// How `action` resolves this interpretation. Choosing one of the offered
+    /// candidates still counts as acting on the interpretation.
+    pub fn outcome_of(&self, action: &UtteranceAction) -> UtteranceOutcome {
+        if action.error.is_some() {
+            return UtteranceOutcome::Failed;
+        }
+        let suggested = |id: &Uuid| self.entity_ids.contains(id) || self.candidate_ids.contains(id);
+        if action.action_type == self.intent_type && action.target_ids.iter().all(suggested) {
+            UtteranceOutcome::Accepted
+        } else {
+            UtteranceOutcome::Overridden
+        }
+    }
+}
+
+const DEFAULT_ANALYTICS_DAYS: i64 = 30;
+const MAX_ANALYTICS_DAYS: i64 = 365;
+
+/// Days of history the analytics cover
+pub fn analytics_window(days: Option<i64>) -> Result<i64, AppError> {
+    let days = days.unwrap_or(DEFAULT_ANALYTICS_DAYS);
+    if !(1..=MAX_ANALYTICS_DAYS).contains(&days) {
+        return Err(AppError::BadRequest(format!(
+            "days must be between 1 and {}",
+            MAX_ANALYTICS_DAYS
+        )));
+    }
+    Ok(days)
+}
+
+/// Interpretations of one intent type with one outcome
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntentOutcomeCount {
+    pub intent_type: String,
+    pub outcome: UtteranceOutcome,
+    pub count: i64,
+    pub confidence_sum: f64,
+}
+
+/// How often interpretations of one intent type were acted on as interpreted
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntentSuccess {
+    pub intent_type: String,
+    pub total: i64,
+    pub accepted: i64,
+    pub overridden: i64,
+    pub failed: i64,
+    pub pending: i64,
+    /// Accepted share of the resolved interpretations; `None` until one is resolved
+    pub success_rate: Option<f64>,
+    pub average_confidence: f64,
+}
+
+impl IntentSuccess {
+    fn new(intent_type: String) -> Self {
+        Self {
+            intent_type,
+            total: 0,
+            accepted: 0,
+            overridden: 0,
+            failed: 0,
+            pending: 0,
+            success_rate: None,
+            average_confidence: 0.0,
+        }
+    }
+
+    fn resolved(&self) -> i64 {
+        self.accepted + self.overridden + self.failed
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtteranceAnalytics {
+    pub since: DateTime<Utc>,
+    pub total: i64,
+    pub resolved: i64,
+    pub success_rate: Option<f64>,
+    /// Most used intent types first
+    pub intents: Vec<IntentSuccess>,
+}
+
+impl UtteranceAnalytics {
+    pub fn summarize(since: DateTime<Utc>, counts: &[IntentOutcomeCount]) -> Self {
+        let mut intents: Vec<IntentSuccess> = Vec::new();
+        let mut confidence_sums: Vec<f64> = Vec::new();
+        for count in counts {
+            let index = match intents
+                .iter()
+                .position(|intent| intent.intent_type == count.intent_type)
+            {
+                Some(index) => index,
+                None => {
+                    intents.push(IntentSuccess::new(count.intent_type.clone()));
+                    confidence_sums.push(0.0);
+                    intents.len() - 1
+                }
+            };
+            let intent = &mut intents[index];
+            intent.total += count.count;
+            confidence_sums[index] += count.confidence_sum;
+            match count.outcome {
+                UtteranceOutcome::Pending => intent.pending += count.count,
+                UtteranceOutcome::Accepted => intent.accepted += count.count,
+                UtteranceOutcome::Overridden => intent.overridden += count.count,
+                UtteranceOutcome::Failed => intent.failed += count.count,
+            }
+        }
+        for (intent, confidence_sum) in intents.iter_mut().zip(confidence_sums) {
+            intent.average_confidence = confidence_sum / intent.total.max(1) as f64;
+            intent.success_rate = rate(intent.accepted, intent.resolved());
+        }
+        intents.sort_by(|a, b| {
+            b.total
+                .cmp(&a.total)
+                .then_with(|| a.intent_type.cmp(&b.intent_type))
+        });
+
+        let total = intents.iter().map(|intent| intent.total).sum();
+        let accepted = intents.iter().map(|intent| intent.accepted).sum();
+        let resolved = intents.iter().map(IntentSuccess::resolved).sum();
+        Self {
+            since,
+            total,
+            resolved,
+            success_rate: rate(accepted, resolved),
+            intents,
+        }
+    }
+}
+
+fn rate(part: i64, whole: i64) -> Option<f64> {
+    (whole > 0).then(|| part as f64 / whole as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interpretation(entity_ids: Vec<Uuid>, candidate_ids: Vec<Uuid>) -> UtteranceInterpretation {
+        UtteranceInterpretation {
+            id: Uuid::new_v4(),
+            organization_id: None,
+            user_id: None,
+            utterance_hash: utterance_hash("move checkout to done"),
+            intent_type: "update_status".to_string(),
+            parsed_intent: serde_json::json!({}),
+            entity_ids,
+            candidate_ids,
+            confidence: 0.8,
+            outcome: UtteranceOutcome::Pending,
+            interpreted_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_outcome_compares_action_with_interpretation() {
+        let (story, candidate, other) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let interpreted = interpretation(vec![story], vec![candidate]);
+        let action =
+            |action_type: &str, target_ids: Vec<Uuid>, error: Option<&str>| UtteranceAction {
+                action_type: action_type.to_string(),
+                target_ids,
+                error: error.map(str::to_string),
+            };
+
+        assert_eq!(
+            interpreted.outcome_of(&action("update_status", vec![story], None)),
+            UtteranceOutcome::Accepted
+        );
+        assert_eq!(
+            interpreted.outcome_of(&action("update_status", vec![candidate], None)),
+            UtteranceOutcome::Accepted
+        );
+        assert_eq!(
+            interpreted.outcome_of(&action("update_status", vec![other], None)),
+            UtteranceOutcome::Overridden
+        );
+        assert_eq!(
+            interpreted.outcome_of(&action("assign_task", vec![story], None)),
+            UtteranceOutcome::Overridden
+        );
+        assert_eq!(
+            interpreted.outcome_of(&action("update_status", vec![story], Some("timeout"))),
+            UtteranceOutcome::Failed
+        );
+    }
+
+    #[test]
+    fn test_summary_rates_resolved_interpretations_per_intent() {
+        let count = |intent_type: &str, outcome, count, confidence_sum| IntentOutcomeCount {
+            intent_type: intent_type.to_string(),
+            outcome,
+            count,
+            confidence_sum,
+        };
+        let analytics = UtteranceAnalytics::summarize(
+            Utc::now(),
+            &[
+                count("update_status", UtteranceOutcome::Accepted, 3, 2.7),
+                count("update_status", UtteranceOutcome::Overridden, 1, 0.5),
+                count("update_status", UtteranceOutcome::Pending, 1, 0.8),
+                count("create_task", UtteranceOutcome::Pending, 2, 1.2),
+            ],
+        );
+
+        assert_eq!((analytics.total, analytics.resolved), (7, 4));
+        assert_eq!(analytics.success_rate, Some(0.75));
+        let update = &analytics.intents[0];
+        assert_eq!(update.intent_type, "update_status");
+        assert_eq!(update.success_rate, Some(0.75));
+        assert!((update.average_confidence - 0.8).abs() < 1e-9);
+        assert_eq!(analytics.intents[1].success_rate, None);
+        assert!(analytics_window(Some(0)).is_err());
+        assert_eq!(analytics_window(None).unwrap(), 30);
+    }
+}
@@ -1,10 +1,17 @@
-use event_bus::{DomainEvent, EventBus, EventEnvelope, SprintEvent, SprintRecord};
+use async_trait::async_trait;
+use event_bus::{
+    apply_with_retry, DomainEvent, EventBus, EventEnvelope, ProjectionId, ProjectionReplayer,
+    RetryPolicy, SprintEvent, SprintRecord,
+};
 use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::task::JoinHandle;
 use tracing::error;
 use uuid::Uuid;
 
+/// Sprints, for resolving sprint references in commands
+const PROJECTION: ProjectionId = ProjectionId::new("context-orchestrator", "sprints");
+
 #[derive(Clone)]
 struct SprintProjectionStore {
     pool: Arc<PgPool>,
@@ -15,14 +22,17 @@ impl SprintProjectionStore {
         Self { pool }
     }
 
-    async fn handle_event(&self, envelope: &EventEnvelope) {
-        if let Err(err) = self.apply_event(&envelope.event).await {
+    async fn handle_event(&self, envelope: &EventEnvelope) -> Result<(), sqlx::Error> {
+        let result = envelope.handle(self.apply_event(&envelope.event)).await;
+        if let Err(err) = &result {
             error!(
                 error = %err,
                 event_id = %envelope.id,
+                correlation_id = ?envelope.correlation_id,
                 "Failed to apply sprint event to context projections"
             );
         }
+        result
     }
 
     async fn apply_event(&self, event: &DomainEvent) -> Result<(), sqlx::Error> {
@@ -32,6 +42,8 @@ impl SprintProjectionStore {
                     self.upsert_sprint(sprint).await?
                 }
                 SprintEvent::Deleted { sprint_id, .. } => self.delete_sprint(*sprint_id).await?,
+                // Story membership arrives through story events; committed points through Updated
+                SprintEvent::StoryAdded { .. } | SprintEvent::StoryRemoved { .. } => {}
             }
         }
         Ok(())
@@ -102,13 +114,30 @@ impl SprintProjectionWorker {
     pub fn spawn(pool: Arc<PgPool>, event_bus: Arc<EventBus>) -> Self {
         let store = SprintProjectionStore::new(pool);
         let subscription = event_bus.subscribe();
+        event_bus.register_projection(PROJECTION, Arc::new(store.clone()));
         let handle = tokio::spawn(async move {
             loop {
                 let envelope = subscription.recv().await;
-                store.handle_event(&envelope).await;
+                apply_with_retry(
+                    &event_bus,
+                    PROJECTION,
+                    &envelope,
+                    RetryPolicy::default(),
+                    || store.handle_event(&envelope),
+                )
+                .await;
             }
         });
 
         Self { handle }
     }
 }
+
+#[async_trait]
+impl ProjectionReplayer for SprintProjectionStore {
+    async fn reapply(&self, envelope: &EventEnvelope) -> Result<(), String> {
+        self.handle_event(envelope)
+            .await
+            .map_err(|err| err.to_string())
+    }
+}
@@ -95,21 +95,21 @@ mod dto_tests {
         };
 
         let json = serde_json::to_value(&response).unwrap();
-        assert_eq!(json["intent"]["intent_type"], "query_status");
+        assert_eq!(json["intent"]["intentType"], "query_status");
         assert_eq!(json["intent"]["entities"].as_array().unwrap().len(), 1);
         assert_eq!(
-            json["intent"]["entities"][0]["entity_id"],
+            json["intent"]["entities"][0]["entityId"],
             entity_id.to_string()
         );
-        let confidence = json["confidence"]["overall_confidence"].as_f64().unwrap();
+        let confidence = json["confidence"]["overallConfidence"].as_f64().unwrap();
         assert!(
             (confidence - 0.85).abs() < 0.01,
             "Expected confidence ~0.85, got {}",
             confidence
         );
         assert_eq!(json["candidates"].as_array().unwrap().len(), 1);
-        assert_eq!(json["requires_confirmation"], false);
-        assert_eq!(json["session_token"], session_token.to_string());
+        assert_eq!(json["requiresConfirmation"], false);
+        assert_eq!(json["sessionToken"], session_token.to_string());
     }
 
     #[test]
@@ -136,11 +136,11 @@ mod dto_tests {
         assert_eq!(json["results"][0]["success"], true);
         assert_eq!(json["results"][0]["message"], "Status updated successfully");
         assert_eq!(
-            json["results"][0]["affected_entities"][0],
+            json["results"][0]["affectedEntities"][0],
             target_entity.to_string()
         );
-        assert_eq!(json["rollback_token"], rollback_token.to_string());
-        assert_eq!(json["partial_success"], false);
+        assert_eq!(json["rollbackToken"], rollback_token.to_string());
+        assert_eq!(json["partialSuccess"], false);
     }
 }
 
@@ -292,6 +292,7 @@ impl ReadinessServiceClient for MockReadinessClient {
             missing_criteria: vec![],
             readiness_score: 1.0,
             blockers: vec![],
+            fixes: vec![],
         })
     }
 
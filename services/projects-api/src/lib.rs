@@ -0,0 +1,7 @@
+//! Public API of the projects service: its router, and its usecases with the
+//! blueprint types for callers that copy blueprints between organizations.
+//! Everything else in `projects` is internal to it.
+
+pub use projects::application::usecases::ProjectUsecases;
+pub use projects::domain::blueprint::ProjectBlueprint;
+pub use projects::{build_usecases, create_projects_router};
@@ -1,4 +1,10 @@
 use crate::application::usecases::ProjectUsecases;
+use crate::domain::blueprint::{
+    CreateBlueprintRequest, CreateProjectFromBlueprintRequest, ProjectBlueprint, ProjectConventions,
+};
+use crate::domain::onboarding::{
+    NextAction, OnboardingAction, OnboardingStep, OnboardingStepState, ProjectOnboarding,
+};
 use crate::domain::project::{
     CreateProjectRequest, Project, ProjectSettings, UpdateProjectRequest,
     UpdateProjectSettingsRequest,
@@ -53,6 +59,8 @@ pub struct ProjectSettingsResponse {
     pub project_id: Uuid,
     pub estimation_scale: String,
     pub dor_template: serde_json::Value,
+    pub timezone: String,
+    pub conventions: ProjectConventions,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -71,12 +79,42 @@ impl From<ProjectSettings> for ProjectSettingsResponse {
             project_id: settings.project_id,
             estimation_scale: estimation_scale.to_string(),
             dor_template: serde_json::to_value(settings.dor_template).unwrap(),
+            timezone: settings.timezone,
+            conventions: settings.conventions,
             created_at: settings.created_at.to_rfc3339(),
             updated_at: settings.updated_at.to_rfc3339(),
         }
     }
 }
 
+#[derive(Deserialize)]
+pub struct UpdateOnboardingRequest {
+    pub step: OnboardingStep,
+    pub action: OnboardingAction,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingResponse {
+    pub project_id: Uuid,
+    pub steps: Vec<OnboardingStepState>,
+    pub current_step: Option<OnboardingStep>,
+    pub is_complete: bool,
+    pub next_actions: Vec<NextAction>,
+}
+
+impl From<ProjectOnboarding> for OnboardingResponse {
+    fn from(onboarding: ProjectOnboarding) -> Self {
+        Self {
+            project_id: onboarding.project_id,
+            current_step: onboarding.current_step(),
+            is_complete: onboarding.is_complete(),
+            next_actions: onboarding.next_actions(),
+            steps: onboarding.steps,
+        }
+    }
+}
+
 pub async fn create_project(
     AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
     Extension(usecases): Extension<Arc<ProjectUsecases>>,
@@ -89,6 +127,18 @@ pub async fn create_project(
     Ok(Json(project.into()))
 }
 
+pub async fn create_project_from_blueprint(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Extension(usecases): Extension<Arc<ProjectUsecases>>,
+    Json(request): Json<CreateProjectFromBlueprintRequest>,
+) -> Result<(StatusCode, Json<ProjectResponse>), AppError> {
+    let project = usecases
+        .create_project_from_blueprint(&request, org_context.effective_organization_uuid())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(project.into())))
+}
+
 pub async fn get_projects(
     AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
     Extension(usecases): Extension<Arc<ProjectUsecases>>,
@@ -177,3 +227,68 @@ pub async fn update_project_settings(
 
     Ok(Json(settings.into()))
 }
+
+pub async fn get_project_onboarding(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Extension(usecases): Extension<Arc<ProjectUsecases>>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<OnboardingResponse>, AppError> {
+    let onboarding = usecases
+        .get_onboarding(&project_id, org_context.effective_organization_uuid())
+        .await?;
+
+    Ok(Json(onboarding.into()))
+}
+
+pub async fn update_project_onboarding(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Extension(usecases): Extension<Arc<ProjectUsecases>>,
+    Path(project_id): Path<Uuid>,
+    Json(request): Json<UpdateOnboardingRequest>,
+) -> Result<Json<OnboardingResponse>, AppError> {
+    let onboarding = usecases
+        .update_onboarding(
+            &project_id,
+            request.step,
+            request.action,
+            org_context.effective_organization_uuid(),
+        )
+        .await?;
+
+    Ok(Json(onboarding.into()))
+}
+
+pub async fn list_blueprints(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Extension(usecases): Extension<Arc<ProjectUsecases>>,
+) -> Result<Json<Vec<ProjectBlueprint>>, AppError> {
+    let blueprints = usecases
+        .list_blueprints(org_context.effective_organization_uuid())
+        .await?;
+
+    Ok(Json(blueprints))
+}
+
+pub async fn create_blueprint(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Extension(usecases): Extension<Arc<ProjectUsecases>>,
+    Json(request): Json<CreateBlueprintRequest>,
+) -> Result<(StatusCode, Json<ProjectBlueprint>), AppError> {
+    let blueprint = usecases
+        .create_blueprint(&request, org_context.effective_organization_uuid())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(blueprint)))
+}
+
+pub async fn delete_blueprint(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Extension(usecases): Extension<Arc<ProjectUsecases>>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, AppError> {
+    usecases
+        .delete_blueprint(&key, org_context.effective_organization_uuid())
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,29 +1,56 @@
 use crate::adapters::http::handlers::{
-    create_project, delete_project, get_project, get_project_settings, get_projects,
-    update_project, update_project_settings,
+    create_blueprint, create_project, create_project_from_blueprint, delete_blueprint,
+    delete_project, get_project, get_project_onboarding, get_project_settings, get_projects,
+    list_blueprints, update_project, update_project_onboarding, update_project_settings,
+};
+use crate::application::ports::{
+    BlueprintRepository, OnboardingRepository, ProjectRepository, ProjectSettingsRepository,
 };
-use crate::application::ports::{ProjectRepository, ProjectSettingsRepository};
 use crate::application::usecases::ProjectUsecases;
 use auth_clerk::JwtVerifier;
-use shuttle_axum::axum::routing::get;
+use common::quota::QuotaGuard;
+use common::route_registry::ServiceRouter;
+use shuttle_axum::axum::routing::{delete, get, post};
 use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-pub async fn create_projects_router(
-    pool: PgPool,
-    verifier: Arc<Mutex<JwtVerifier>>,
-) -> shuttle_axum::axum::Router {
+pub fn build_usecases(pool: PgPool, quota_guard: Arc<dyn QuotaGuard>) -> Arc<ProjectUsecases> {
     let pool = Arc::new(pool);
     let project_repo: Arc<dyn ProjectRepository> = pool.clone();
     let settings_repo: Arc<dyn ProjectSettingsRepository> = pool.clone();
+    let onboarding_repo: Arc<dyn OnboardingRepository> = pool.clone();
+    let blueprint_repo: Arc<dyn BlueprintRepository> = pool;
 
-    // Create use cases
-    let project_usecases = Arc::new(ProjectUsecases::new(project_repo, settings_repo));
+    Arc::new(ProjectUsecases::new(
+        project_repo,
+        settings_repo,
+        onboarding_repo,
+        blueprint_repo,
+        quota_guard,
+    ))
+}
 
-    shuttle_axum::axum::Router::new()
+pub async fn create_projects_router(
+    pool: PgPool,
+    verifier: Arc<Mutex<JwtVerifier>>,
+    quota_guard: Arc<dyn QuotaGuard>,
+) -> shuttle_axum::axum::Router {
+    let project_usecases = build_usecases(pool, quota_guard);
+
+    ServiceRouter::new("projects")
         // Project management
         .route("/projects", get(get_projects).post(create_project))
+        // Blueprints: project settings to start new projects from
+        .route(
+            "/projects/blueprints",
+            get(list_blueprints).post(create_blueprint),
+        )
+        .route("/projects/blueprints/{key}", delete(delete_blueprint))
+        .route(
+            "/projects/from-blueprint",
+            post(create_project_from_blueprint),
+        )
         .route(
             "/projects/{project_id}",
             get(get_project).put(update_project).delete(delete_project),
@@ -33,7 +60,13 @@ pub async fn create_projects_router(
             "/projects/{project_id}/settings",
             get(get_project_settings).put(update_project_settings),
         )
+        // Guided setup wizard
+        .route(
+            "/projects/{project_id}/onboarding",
+            get(get_project_onboarding).post(update_project_onboarding),
+        )
         // Add extensions
         .layer(shuttle_axum::axum::Extension(project_usecases))
         .layer(shuttle_axum::axum::Extension(verifier))
+        .into_router()
 }
@@ -1,3 +1,5 @@
+use crate::domain::blueprint::ProjectBlueprint;
+use crate::domain::onboarding::{OnboardingStep, OnboardingStepState, StepStatus};
 use crate::domain::project::{DorTemplate, EstimationScale, Project, ProjectSettings};
 use chrono::{DateTime, Utc};
 use sqlx::FromRow;
@@ -34,6 +36,8 @@ pub struct ProjectSettingsDb {
     pub project_id: Uuid,
     pub estimation_scale: String,
     pub dor_template: serde_json::Value,
+    pub timezone: String,
+    pub conventions: serde_json::Value,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -42,23 +46,83 @@ impl TryFrom<ProjectSettingsDb> for ProjectSettings {
     type Error = serde_json::Error;
 
     fn try_from(settings_db: ProjectSettingsDb) -> Result<Self, Self::Error> {
-        let estimation_scale = match settings_db.estimation_scale.as_str() {
-            "fibonacci" => EstimationScale::Fibonacci,
-            "power_of_two" => EstimationScale::PowerOfTwo,
-            "linear" => EstimationScale::Linear,
-            "t_shirt_sizes" => EstimationScale::TShirtSizes,
-            _ => EstimationScale::Fibonacci, // Default fallback
-        };
-
+        let estimation_scale = parse_estimation_scale(&settings_db.estimation_scale);
         let dor_template: DorTemplate = serde_json::from_value(settings_db.dor_template)?;
+        let conventions = serde_json::from_value(settings_db.conventions)?;
 
         Ok(Self {
             id: settings_db.id,
             project_id: settings_db.project_id,
             estimation_scale,
             dor_template,
+            timezone: settings_db.timezone,
+            conventions,
             created_at: settings_db.created_at,
             updated_at: settings_db.updated_at,
         })
     }
 }
+
+pub fn parse_estimation_scale(value: &str) -> EstimationScale {
+    match value {
+        "fibonacci" => EstimationScale::Fibonacci,
+        "power_of_two" => EstimationScale::PowerOfTwo,
+        "linear" => EstimationScale::Linear,
+        "t_shirt_sizes" => EstimationScale::TShirtSizes,
+        _ => EstimationScale::Fibonacci, // Default fallback
+    }
+}
+
+pub fn estimation_scale_str(scale: &EstimationScale) -> &'static str {
+    match scale {
+        EstimationScale::Fibonacci => "fibonacci",
+        EstimationScale::PowerOfTwo => "power_of_two",
+        EstimationScale::Linear => "linear",
+        EstimationScale::TShirtSizes => "t_shirt_sizes",
+    }
+}
+
+#[derive(FromRow)]
+pub struct ProjectBlueprintDb {
+    pub key: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub estimation_scale: String,
+    pub dor_template: serde_json::Value,
+    pub conventions: serde_json::Value,
+}
+
+impl TryFrom<ProjectBlueprintDb> for ProjectBlueprint {
+    type Error = serde_json::Error;
+
+    fn try_from(blueprint_db: ProjectBlueprintDb) -> Result<Self, Self::Error> {
+        Ok(Self {
+            key: blueprint_db.key,
+            name: blueprint_db.name,
+            description: blueprint_db.description,
+            built_in: false,
+            estimation_scale: parse_estimation_scale(&blueprint_db.estimation_scale),
+            dor_template: serde_json::from_value(blueprint_db.dor_template)?,
+            conventions: serde_json::from_value(blueprint_db.conventions)?,
+        })
+    }
+}
+
+#[derive(FromRow)]
+pub struct OnboardingStepDb {
+    pub step: String,
+    pub status: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OnboardingStepDb {
+    /// Rows with unknown step or status values are ignored
+    pub fn into_state(self) -> Option<OnboardingStepState> {
+        Some(OnboardingStepState {
+            step: OnboardingStep::from_str(&self.step)?,
+            status: StepStatus::from_str(&self.status)?,
+            auto_detected: false,
+            updated_at: Some(self.updated_at),
+        })
+    }
+}
@@ -1,11 +1,18 @@
-use crate::adapters::persistence::models::{ProjectDb, ProjectSettingsDb};
-use crate::application::ports::{ProjectRepository, ProjectSettingsRepository};
+use crate::adapters::persistence::models::{
+    estimation_scale_str, OnboardingStepDb, ProjectBlueprintDb, ProjectDb, ProjectSettingsDb,
+};
+use crate::application::ports::{
+    BlueprintRepository, OnboardingRepository, ProjectRepository, ProjectSettingsRepository,
+};
+use crate::domain::blueprint::{ProjectBlueprint, ProjectConventions};
+use crate::domain::onboarding::OnboardingStepState;
 use crate::domain::project::{
     CreateProjectRequest, DorTemplate, EstimationScale, Project, ProjectSettings,
     UpdateProjectRequest, UpdateProjectSettingsRequest,
 };
 use async_trait::async_trait;
 use chrono::Utc;
+use common::calendar::DEFAULT_TIMEZONE;
 use common::AppError;
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -59,22 +66,26 @@ impl ProjectRepository for PgPool {
             .as_ref()
             .unwrap_or(&default_dor_template);
 
-        let estimation_scale_str = match estimation_scale {
-            EstimationScale::Fibonacci => "fibonacci",
-            EstimationScale::PowerOfTwo => "power_of_two",
-            EstimationScale::Linear => "linear",
-            EstimationScale::TShirtSizes => "t_shirt_sizes",
-        };
+        let default_conventions = ProjectConventions::default();
+        let conventions = request.conventions.as_ref().unwrap_or(&default_conventions);
 
         sqlx::query(
             r#"
-            INSERT INTO project_settings (project_id, estimation_scale, dor_template, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO project_settings (project_id, estimation_scale, dor_template, timezone, conventions, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             "#,
         )
         .bind(project_id)
-        .bind(estimation_scale_str)
+        .bind(estimation_scale_str(estimation_scale))
         .bind(serde_json::to_value(dor_template).unwrap())
+        .bind(
+            request
+                .timezone
+                .as_deref()
+                .map(str::trim)
+                .unwrap_or(DEFAULT_TIMEZONE),
+        )
+        .bind(serde_json::to_value(conventions).unwrap())
         .bind(now)
         .bind(now)
         .execute(&mut *tx)
@@ -256,23 +267,24 @@ impl ProjectSettingsRepository for PgPool {
         }
 
         // Prepare updates
-        let estimation_scale_str = request.estimation_scale.as_ref().map(|scale| match scale {
-            EstimationScale::Fibonacci => "fibonacci",
-            EstimationScale::PowerOfTwo => "power_of_two",
-            EstimationScale::Linear => "linear",
-            EstimationScale::TShirtSizes => "t_shirt_sizes",
-        });
+        let estimation_scale_str = request.estimation_scale.as_ref().map(estimation_scale_str);
 
         let dor_template_json = request
             .dor_template
             .as_ref()
             .map(|template| serde_json::to_value(template).unwrap());
+        let conventions_json = request
+            .conventions
+            .as_ref()
+            .map(|conventions| serde_json::to_value(conventions).unwrap());
 
         let settings_db = sqlx::query_as::<_, ProjectSettingsDb>(
             r#"
             UPDATE project_settings
             SET estimation_scale = COALESCE($2, estimation_scale),
                 dor_template = COALESCE($3, dor_template),
+                timezone = COALESCE($5, timezone),
+                conventions = COALESCE($6, conventions),
                 updated_at = $4
             WHERE project_id = $1
             RETURNING *
@@ -282,6 +294,8 @@ impl ProjectSettingsRepository for PgPool {
         .bind(estimation_scale_str)
         .bind(dor_template_json)
         .bind(now)
+        .bind(request.timezone.as_deref().map(str::trim))
+        .bind(conventions_json)
         .fetch_one(self)
         .await
         .map_err(|e| {
@@ -294,3 +308,223 @@ impl ProjectSettingsRepository for PgPool {
             .map_err(|_| AppError::InternalServerError)
     }
 }
+
+#[async_trait]
+impl OnboardingRepository for PgPool {
+    async fn get_recorded_steps(
+        &self,
+        project_id: &Uuid,
+    ) -> Result<Vec<OnboardingStepState>, AppError> {
+        let rows = sqlx::query_as::<_, OnboardingStepDb>(
+            r#"
+            SELECT step, status, updated_at FROM project_onboarding_steps
+            WHERE project_id = $1
+            "#,
+        )
+        .bind(project_id)
+        .fetch_all(self)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load onboarding steps: {}", e);
+            AppError::InternalServerError
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(OnboardingStepDb::into_state)
+            .collect())
+    }
+
+    async fn record_step(
+        &self,
+        project_id: &Uuid,
+        state: &OnboardingStepState,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO project_onboarding_steps (project_id, step, status, updated_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (project_id, step)
+            DO UPDATE SET status = EXCLUDED.status, updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(project_id)
+        .bind(state.step.as_str())
+        .bind(state.status.as_str())
+        .bind(state.updated_at.unwrap_or_else(Utc::now))
+        .execute(self)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to record onboarding step: {}", e);
+            AppError::InternalServerError
+        })?;
+
+        Ok(())
+    }
+
+    async fn project_has_stories(&self, project_id: &Uuid) -> Result<bool, AppError> {
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM stories
+                WHERE project_id = $1 AND deleted_at IS NULL
+            )
+            "#,
+        )
+        .bind(project_id)
+        .fetch_one(self)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to check project stories: {}", e);
+            AppError::InternalServerError
+        })
+    }
+}
+
+#[async_trait]
+impl BlueprintRepository for PgPool {
+    async fn list_blueprints(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<ProjectBlueprint>, AppError> {
+        let rows = sqlx::query_as::<_, ProjectBlueprintDb>(
+            r#"
+            SELECT key, name, description, estimation_scale, dor_template, conventions
+            FROM project_blueprints
+            WHERE organization_id = $1
+            ORDER BY name
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_all(self)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to list project blueprints: {}", e);
+            AppError::InternalServerError
+        })?;
+
+        rows.into_iter()
+            .map(|row| row.try_into().map_err(|_| AppError::InternalServerError))
+            .collect()
+    }
+
+    async fn get_blueprint(
+        &self,
+        organization_id: Uuid,
+        key: &str,
+    ) -> Result<Option<ProjectBlueprint>, AppError> {
+        let row = sqlx::query_as::<_, ProjectBlueprintDb>(
+            r#"
+            SELECT key, name, description, estimation_scale, dor_template, conventions
+            FROM project_blueprints
+            WHERE organization_id = $1 AND key = $2
+            "#,
+        )
+        .bind(organization_id)
+        .bind(key)
+        .fetch_optional(self)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load project blueprint: {}", e);
+            AppError::InternalServerError
+        })?;
+
+        row.map(|row| row.try_into().map_err(|_| AppError::InternalServerError))
+            .transpose()
+    }
+
+    async fn create_blueprint(
+        &self,
+        organization_id: Uuid,
+        blueprint: &ProjectBlueprint,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO project_blueprints (organization_id, key, name, description, estimation_scale, dor_template, conventions, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (organization_id, key) DO NOTHING
+            "#,
+        )
+        .bind(organization_id)
+        .bind(&blueprint.key)
+        .bind(&blueprint.name)
+        .bind(&blueprint.description)
+        .bind(estimation_scale_str(&blueprint.estimation_scale))
+        .bind(serde_json::to_value(&blueprint.dor_template).unwrap())
+        .bind(serde_json::to_value(&blueprint.conventions).unwrap())
+        .bind(Utc::now())
+        .execute(self)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to create project blueprint: {}", e);
+            AppError::InternalServerError
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::Conflict(format!(
+                "Blueprint '{}' already exists",
+                blueprint.key
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn save_blueprint(
+        &self,
+        organization_id: Uuid,
+        blueprint: &ProjectBlueprint,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO project_blueprints (organization_id, key, name, description, estimation_scale, dor_template, conventions, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (organization_id, key) DO UPDATE
+            SET name = EXCLUDED.name,
+                description = EXCLUDED.description,
+                estimation_scale = EXCLUDED.estimation_scale,
+                dor_template = EXCLUDED.dor_template,
+                conventions = EXCLUDED.conventions
+            "#,
+        )
+        .bind(organization_id)
+        .bind(&blueprint.key)
+        .bind(&blueprint.name)
+        .bind(&blueprint.description)
+        .bind(estimation_scale_str(&blueprint.estimation_scale))
+        .bind(serde_json::to_value(&blueprint.dor_template).unwrap())
+        .bind(serde_json::to_value(&blueprint.conventions).unwrap())
+        .bind(Utc::now())
+        .execute(self)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to save project blueprint: {}", e);
+            AppError::InternalServerError
+        })?;
+
+        Ok(())
+    }
+
+    async fn delete_blueprint(&self, organization_id: Uuid, key: &str) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM project_blueprints
+            WHERE organization_id = $1 AND key = $2
+            "#,
+        )
+        .bind(organization_id)
+        .bind(key)
+        .execute(self)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete project blueprint: {}", e);
+            AppError::InternalServerError
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Blueprint not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
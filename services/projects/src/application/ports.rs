@@ -1,3 +1,5 @@
+use crate::domain::blueprint::ProjectBlueprint;
+use crate::domain::onboarding::OnboardingStepState;
 use crate::domain::project::{
     CreateProjectRequest, Project, ProjectSettings, UpdateProjectRequest,
     UpdateProjectSettingsRequest,
@@ -56,3 +58,51 @@ pub trait ProjectSettingsRepository: Send + Sync {
         organization_id: Option<Uuid>,
     ) -> Result<ProjectSettings, AppError>;
 }
+
+#[async_trait]
+pub trait OnboardingRepository: Send + Sync {
+    /// Step states explicitly recorded through the onboarding API
+    async fn get_recorded_steps(
+        &self,
+        project_id: &Uuid,
+    ) -> Result<Vec<OnboardingStepState>, AppError>;
+
+    async fn record_step(
+        &self,
+        project_id: &Uuid,
+        state: &OnboardingStepState,
+    ) -> Result<(), AppError>;
+
+    async fn project_has_stories(&self, project_id: &Uuid) -> Result<bool, AppError>;
+}
+
+/// Blueprints an organization has saved; built-in blueprints are not stored
+#[async_trait]
+pub trait BlueprintRepository: Send + Sync {
+    async fn list_blueprints(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<ProjectBlueprint>, AppError>;
+
+    async fn get_blueprint(
+        &self,
+        organization_id: Uuid,
+        key: &str,
+    ) -> Result<Option<ProjectBlueprint>, AppError>;
+
+    /// Fails with a conflict when the organization already has a blueprint with the key
+    async fn create_blueprint(
+        &self,
+        organization_id: Uuid,
+        blueprint: &ProjectBlueprint,
+    ) -> Result<(), AppError>;
+
+    /// Creates the blueprint, or replaces the organization's blueprint with the same key
+    async fn save_blueprint(
+        &self,
+        organization_id: Uuid,
+        blueprint: &ProjectBlueprint,
+    ) -> Result<(), AppError>;
+
+    async fn delete_blueprint(&self, organization_id: Uuid, key: &str) -> Result<(), AppError>;
+}
@@ -1,8 +1,18 @@
-use crate::application::ports::{ProjectRepository, ProjectSettingsRepository};
+use crate::application::ports::{
+    BlueprintRepository, OnboardingRepository, ProjectRepository, ProjectSettingsRepository,
+};
+use crate::domain::blueprint::{
+    CreateBlueprintRequest, CreateProjectFromBlueprintRequest, ProjectBlueprint,
+};
+use crate::domain::onboarding::{
+    OnboardingAction, OnboardingSignals, OnboardingStep, ProjectOnboarding,
+};
 use crate::domain::project::{
-    CreateProjectRequest, Project, ProjectSettings, UpdateProjectRequest,
+    CreateProjectRequest, EstimationScale, Project, ProjectSettings, UpdateProjectRequest,
     UpdateProjectSettingsRequest,
 };
+use common::calendar::parse_timezone;
+use common::quota::{QuotaGuard, QuotaResource};
 use common::AppError;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -10,16 +20,25 @@ use uuid::Uuid;
 pub struct ProjectUsecases {
     project_repo: Arc<dyn ProjectRepository>,
     settings_repo: Arc<dyn ProjectSettingsRepository>,
+    onboarding_repo: Arc<dyn OnboardingRepository>,
+    blueprint_repo: Arc<dyn BlueprintRepository>,
+    quota_guard: Arc<dyn QuotaGuard>,
 }
 
 impl ProjectUsecases {
     pub fn new(
         project_repo: Arc<dyn ProjectRepository>,
         settings_repo: Arc<dyn ProjectSettingsRepository>,
+        onboarding_repo: Arc<dyn OnboardingRepository>,
+        blueprint_repo: Arc<dyn BlueprintRepository>,
+        quota_guard: Arc<dyn QuotaGuard>,
     ) -> Self {
         Self {
             project_repo,
             settings_repo,
+            onboarding_repo,
+            blueprint_repo,
+            quota_guard,
         }
     }
 
@@ -28,11 +47,184 @@ impl ProjectUsecases {
         request: &CreateProjectRequest,
         organization_id: Option<Uuid>,
     ) -> Result<Project, AppError> {
+        if let Some(timezone) = &request.timezone {
+            parse_timezone(timezone)?;
+        }
+        if let Some(conventions) = &request.conventions {
+            conventions.validate()?;
+        }
+        self.quota_guard
+            .ensure_capacity(organization_id, QuotaResource::Projects, 1)
+            .await?;
         self.project_repo
             .create_project(request, organization_id)
             .await
     }
 
+    /// Creates a project with a blueprint's settings. The blueprint's
+    /// Definition of Ready counts as the project's readiness policy.
+    pub async fn create_project_from_blueprint(
+        &self,
+        request: &CreateProjectFromBlueprintRequest,
+        organization_id: Option<Uuid>,
+    ) -> Result<Project, AppError> {
+        let blueprint = self
+            .get_blueprint(&request.blueprint, organization_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Blueprint '{}' not found", request.blueprint))
+            })?;
+
+        let project = self
+            .create_project(&blueprint.project_request(request), organization_id)
+            .await?;
+
+        let mut onboarding = self.load_onboarding(&project).await?;
+        let state = onboarding.apply(
+            OnboardingStep::ReadinessPolicySet,
+            OnboardingAction::Complete,
+        )?;
+        self.onboarding_repo
+            .record_step(&project.id, &state)
+            .await?;
+
+        Ok(project)
+    }
+
+    /// Built-in blueprints followed by the organization's own
+    pub async fn list_blueprints(
+        &self,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<ProjectBlueprint>, AppError> {
+        let mut blueprints = ProjectBlueprint::built_ins();
+        if let Some(organization_id) = organization_id {
+            blueprints.extend(self.blueprint_repo.list_blueprints(organization_id).await?);
+        }
+        Ok(blueprints)
+    }
+
+    pub async fn get_blueprint(
+        &self,
+        key: &str,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<ProjectBlueprint>, AppError> {
+        let key = key.trim().to_lowercase();
+        if let Some(blueprint) = ProjectBlueprint::built_ins()
+            .into_iter()
+            .find(|blueprint| blueprint.key == key)
+        {
+            return Ok(Some(blueprint));
+        }
+        match organization_id {
+            Some(organization_id) => {
+                self.blueprint_repo
+                    .get_blueprint(organization_id, &key)
+                    .await
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub async fn create_blueprint(
+        &self,
+        request: &CreateBlueprintRequest,
+        organization_id: Option<Uuid>,
+    ) -> Result<ProjectBlueprint, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest("Custom blueprints require an organization".to_string())
+        })?;
+
+        let source = match request.source_project_id {
+            Some(project_id) => Some(
+                self.get_project_settings(&project_id, Some(organization_id))
+                    .await?
+                    .ok_or(AppError::NotFound("Project settings not found".to_string()))?,
+            ),
+            None => None,
+        };
+        let estimation_scale = request
+            .estimation_scale
+            .clone()
+            .or_else(|| {
+                source
+                    .as_ref()
+                    .map(|settings| settings.estimation_scale.clone())
+            })
+            .unwrap_or(EstimationScale::Fibonacci);
+        let dor_template = request
+            .dor_template
+            .clone()
+            .or_else(|| {
+                source
+                    .as_ref()
+                    .map(|settings| settings.dor_template.clone())
+            })
+            .unwrap_or_default();
+        let conventions = request
+            .conventions
+            .clone()
+            .or_else(|| source.map(|settings| settings.conventions))
+            .unwrap_or_default();
+
+        let blueprint = ProjectBlueprint::custom(
+            &request.key,
+            &request.name,
+            request.description.clone(),
+            estimation_scale,
+            dor_template,
+            conventions,
+        )?;
+        self.blueprint_repo
+            .create_blueprint(organization_id, &blueprint)
+            .await?;
+        Ok(blueprint)
+    }
+
+    /// Create or replace an organization blueprint as given, e.g. one copied
+    /// from another organization
+    pub async fn save_blueprint(
+        &self,
+        blueprint: &ProjectBlueprint,
+        organization_id: Option<Uuid>,
+    ) -> Result<ProjectBlueprint, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest("Custom blueprints require an organization".to_string())
+        })?;
+        let blueprint = ProjectBlueprint::custom(
+            &blueprint.key,
+            &blueprint.name,
+            blueprint.description.clone(),
+            blueprint.estimation_scale.clone(),
+            blueprint.dor_template.clone(),
+            blueprint.conventions.clone(),
+        )?;
+        self.blueprint_repo
+            .save_blueprint(organization_id, &blueprint)
+            .await?;
+        Ok(blueprint)
+    }
+
+    pub async fn delete_blueprint(
+        &self,
+        key: &str,
+        organization_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        let key = key.trim().to_lowercase();
+        if ProjectBlueprint::built_ins()
+            .iter()
+            .any(|blueprint| blueprint.key == key)
+        {
+            return Err(AppError::BadRequest(
+                "Built-in blueprints cannot be deleted".to_string(),
+            ));
+        }
+        let organization_id =
+            organization_id.ok_or(AppError::NotFound("Blueprint not found".to_string()))?;
+        self.blueprint_repo
+            .delete_blueprint(organization_id, &key)
+            .await
+    }
+
     pub async fn get_project(
         &self,
         id: &Uuid,
@@ -111,14 +303,73 @@ impl ProjectUsecases {
         organization_id: Option<Uuid>,
     ) -> Result<ProjectSettings, AppError> {
         // First verify the project exists and belongs to the organization
-        let _project = self
+        let project = self
             .project_repo
             .get_project_by_id(project_id, organization_id)
             .await?
             .ok_or(AppError::NotFound("Project not found".to_string()))?;
+        if let Some(timezone) = &request.timezone {
+            parse_timezone(timezone)?;
+        }
+        if let Some(conventions) = &request.conventions {
+            conventions.validate()?;
+        }
 
-        self.settings_repo
+        let settings = self
+            .settings_repo
             .update_settings(project_id, request, organization_id)
-            .await
+            .await?;
+
+        // Choosing a Definition of Ready completes that onboarding step
+        if request.dor_template.is_some() {
+            let mut onboarding = self.load_onboarding(&project).await?;
+            let state = onboarding.apply(
+                OnboardingStep::ReadinessPolicySet,
+                OnboardingAction::Complete,
+            )?;
+            self.onboarding_repo.record_step(project_id, &state).await?;
+        }
+
+        Ok(settings)
+    }
+
+    pub async fn get_onboarding(
+        &self,
+        project_id: &Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<ProjectOnboarding, AppError> {
+        let project = self
+            .project_repo
+            .get_project_by_id(project_id, organization_id)
+            .await?
+            .ok_or(AppError::NotFound("Project not found".to_string()))?;
+
+        self.load_onboarding(&project).await
+    }
+
+    pub async fn update_onboarding(
+        &self,
+        project_id: &Uuid,
+        step: OnboardingStep,
+        action: OnboardingAction,
+        organization_id: Option<Uuid>,
+    ) -> Result<ProjectOnboarding, AppError> {
+        let mut onboarding = self.get_onboarding(project_id, organization_id).await?;
+        let state = onboarding.apply(step, action)?;
+        self.onboarding_repo.record_step(project_id, &state).await?;
+        Ok(onboarding)
+    }
+
+    async fn load_onboarding(&self, project: &Project) -> Result<ProjectOnboarding, AppError> {
+        let signals = OnboardingSignals {
+            has_team: project.team_id.is_some(),
+            has_stories: self
+                .onboarding_repo
+                .project_has_stories(&project.id)
+                .await?,
+        };
+        let recorded = self.onboarding_repo.get_recorded_steps(&project.id).await?;
+
+        Ok(ProjectOnboarding::new(project.id, &recorded, signals))
     }
 }
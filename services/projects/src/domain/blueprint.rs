@@ -0,0 +1,380 @@
+use crate::domain::project::{CreateProjectRequest, DorTemplate, EstimationScale};
+use chrono::Weekday;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+const MAX_LABELS: usize = 50;
+const MAX_LABEL_LENGTH: usize = 50;
+const MAX_DOD_ITEMS: usize = 30;
+const MAX_STORY_TEMPLATES: usize = 20;
+const MIN_SPRINT_LENGTH_DAYS: u32 = 7;
+const MAX_SPRINT_LENGTH_DAYS: u32 = 28;
+const MAX_KEY_LENGTH: usize = 40;
+
+/// When a project's sprints start and how long they run
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintCadence {
+    pub length_days: u32,
+    pub start_weekday: Weekday,
+}
+
+/// A starting point for new stories, offered when a story is created
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryTemplate {
+    pub name: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub acceptance_criteria: Vec<String>,
+}
+
+/// How a project's team works: the labels it uses, its Definition of Done,
+/// story templates and sprint cadence. Kanban projects have no cadence.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectConventions {
+    pub labels: Vec<String>,
+    pub definition_of_done: Vec<String>,
+    pub story_templates: Vec<StoryTemplate>,
+    pub sprint_cadence: Option<SprintCadence>,
+}
+
+impl ProjectConventions {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.labels.len() > MAX_LABELS {
+            return Err(AppError::BadRequest(format!(
+                "A project can have at most {} labels",
+                MAX_LABELS
+            )));
+        }
+        let mut seen = HashSet::new();
+        for label in &self.labels {
+            let label = label.trim();
+            if label.is_empty() || label.chars().count() > MAX_LABEL_LENGTH {
+                return Err(AppError::BadRequest(format!(
+                    "Labels must be between 1 and {} characters",
+                    MAX_LABEL_LENGTH
+                )));
+            }
+            if !seen.insert(label.to_lowercase()) {
+                return Err(AppError::BadRequest(format!(
+                    "Label '{}' is listed more than once",
+                    label
+                )));
+            }
+        }
+
+        if self.definition_of_done.len() > MAX_DOD_ITEMS
+            || self
+                .definition_of_done
+                .iter()
+                .any(|item| item.trim().is_empty())
+        {
+            return Err(AppError::BadRequest(format!(
+                "The Definition of Done must have at most {} non-empty items",
+                MAX_DOD_ITEMS
+            )));
+        }
+
+        if self.story_templates.len() > MAX_STORY_TEMPLATES {
+            return Err(AppError::BadRequest(format!(
+                "A project can have at most {} story templates",
+                MAX_STORY_TEMPLATES
+            )));
+        }
+        let mut names = HashSet::new();
+        for template in &self.story_templates {
+            if template.name.trim().is_empty() || template.title.trim().is_empty() {
+                return Err(AppError::BadRequest(
+                    "Story templates need a name and a title".to_string(),
+                ));
+            }
+            if !names.insert(template.name.trim().to_lowercase()) {
+                return Err(AppError::BadRequest(format!(
+                    "Story template '{}' is listed more than once",
+                    template.name
+                )));
+            }
+        }
+
+        if let Some(cadence) = self.sprint_cadence {
+            if !(MIN_SPRINT_LENGTH_DAYS..=MAX_SPRINT_LENGTH_DAYS).contains(&cadence.length_days) {
+                return Err(AppError::BadRequest(format!(
+                    "Sprint length must be between {} and {} days",
+                    MIN_SPRINT_LENGTH_DAYS, MAX_SPRINT_LENGTH_DAYS
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Settings a new project is created with. Built-in blueprints are available
+/// to everyone; organizations can save their own alongside them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectBlueprint {
+    /// Stable identifier, e.g. "scrum-starter"
+    pub key: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub built_in: bool,
+    pub estimation_scale: EstimationScale,
+    /// The readiness policy projects created from the blueprint start with
+    pub dor_template: DorTemplate,
+    pub conventions: ProjectConventions,
+}
+
+/// Save an organization blueprint. Settings left out are copied from
+/// `sourceProjectId` when given, and otherwise fall back to the defaults.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBlueprintRequest {
+    pub key: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(alias = "source_project_id")]
+    pub source_project_id: Option<Uuid>,
+    #[serde(alias = "estimation_scale")]
+    pub estimation_scale: Option<EstimationScale>,
+    #[serde(alias = "dor_template")]
+    pub dor_template: Option<DorTemplate>,
+    pub conventions: Option<ProjectConventions>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateProjectFromBlueprintRequest {
+    /// Key of a built-in or organization blueprint
+    pub blueprint: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(alias = "team_id")]
+    pub team_id: Option<Uuid>,
+    pub timezone: Option<String>,
+}
+
+impl ProjectBlueprint {
+    /// An organization's own blueprint; its key cannot shadow a built-in one
+    pub fn custom(
+        key: &str,
+        name: &str,
+        description: Option<String>,
+        estimation_scale: EstimationScale,
+        dor_template: DorTemplate,
+        conventions: ProjectConventions,
+    ) -> Result<Self, AppError> {
+        let key = key.trim().to_lowercase();
+        if key.is_empty()
+            || key.len() > MAX_KEY_LENGTH
+            || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return Err(AppError::BadRequest(format!(
+                "Blueprint keys must be 1 to {} lowercase letters, digits or hyphens",
+                MAX_KEY_LENGTH
+            )));
+        }
+        if Self::built_ins()
+            .iter()
+            .any(|blueprint| blueprint.key == key)
+        {
+            return Err(AppError::Conflict(format!(
+                "'{}' is a built-in blueprint",
+                key
+            )));
+        }
+        if name.trim().is_empty() {
+            return Err(AppError::BadRequest(
+                "Blueprint name cannot be empty".to_string(),
+            ));
+        }
+        conventions.validate()?;
+
+        Ok(Self {
+            key,
+            name: name.trim().to_string(),
+            description: description
+                .map(|description| description.trim().to_string())
+                .filter(|description| !description.is_empty()),
+            built_in: false,
+            estimation_scale,
+            dor_template,
+            conventions,
+        })
+    }
+
+    /// A project request that starts with the blueprint's settings
+    pub fn project_request(
+        &self,
+        request: &CreateProjectFromBlueprintRequest,
+    ) -> CreateProjectRequest {
+        CreateProjectRequest {
+            name: request.name.clone(),
+            description: request.description.clone(),
+            team_id: request.team_id,
+            estimation_scale: Some(self.estimation_scale.clone()),
+            dor_template: Some(self.dor_template.clone()),
+            timezone: request.timezone.clone(),
+            conventions: Some(self.conventions.clone()),
+        }
+    }
+
+    pub fn built_ins() -> Vec<Self> {
+        let definition_of_done = vec![
+            "Code reviewed and merged".to_string(),
+            "Automated tests pass".to_string(),
+            "Acceptance criteria verified".to_string(),
+            "Documentation updated where behaviour changed".to_string(),
+        ];
+        let bug_template = StoryTemplate {
+            name: "Bug".to_string(),
+            title: "Fix: <what is broken>".to_string(),
+            description: "Steps to reproduce:\n\nExpected:\n\nActual:".to_string(),
+            acceptance_criteria: vec![
+                "Given the steps to reproduce, when they are followed, then the expected behaviour occurs".to_string(),
+            ],
+        };
+
+        vec![
+            Self {
+                key: "scrum-starter".to_string(),
+                name: "Scrum starter".to_string(),
+                description: Some(
+                    "Two-week sprints starting on Monday, Fibonacci estimates and a Definition of Ready that requires acceptance criteria and story points."
+                        .to_string(),
+                ),
+                built_in: true,
+                estimation_scale: EstimationScale::Fibonacci,
+                dor_template: DorTemplate {
+                    story_points_required: true,
+                    ..DorTemplate::default()
+                },
+                conventions: ProjectConventions {
+                    labels: ["feature", "bug", "tech-debt", "spike"]
+                        .map(String::from)
+                        .to_vec(),
+                    definition_of_done: definition_of_done.clone(),
+                    story_templates: vec![
+                        StoryTemplate {
+                            name: "User story".to_string(),
+                            title: "As a <persona>, I want <action> so that <outcome>".to_string(),
+                            description: String::new(),
+                            acceptance_criteria: vec![
+                                "Given <context>, when <action>, then <outcome>".to_string(),
+                            ],
+                        },
+                        bug_template.clone(),
+                    ],
+                    sprint_cadence: Some(SprintCadence {
+                        length_days: 14,
+                        start_weekday: Weekday::Mon,
+                    }),
+                },
+            },
+            Self {
+                key: "kanban-starter".to_string(),
+                name: "Kanban starter".to_string(),
+                description: Some(
+                    "Continuous flow without sprints, T-shirt sizes and a light Definition of Ready."
+                        .to_string(),
+                ),
+                built_in: true,
+                estimation_scale: EstimationScale::TShirtSizes,
+                dor_template: DorTemplate::default(),
+                conventions: ProjectConventions {
+                    labels: ["feature", "bug", "expedite", "blocked"]
+                        .map(String::from)
+                        .to_vec(),
+                    definition_of_done,
+                    story_templates: vec![bug_template],
+                    sprint_cadence: None,
+                },
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_blueprints_are_valid() {
+        let built_ins = ProjectBlueprint::built_ins();
+        assert_eq!(built_ins.len(), 2);
+        for blueprint in &built_ins {
+            assert!(blueprint.built_in);
+            blueprint.conventions.validate().unwrap();
+        }
+        assert!(built_ins[1].conventions.sprint_cadence.is_none());
+    }
+
+    #[test]
+    fn test_custom_blueprints_are_validated() {
+        let custom = |key: &str, conventions: ProjectConventions| {
+            ProjectBlueprint::custom(
+                key,
+                "Platform team",
+                None,
+                EstimationScale::Linear,
+                DorTemplate::default(),
+                conventions,
+            )
+        };
+
+        let blueprint = custom("Platform-Team", ProjectConventions::default()).unwrap();
+        assert_eq!(blueprint.key, "platform-team");
+        assert!(!blueprint.built_in);
+
+        assert!(matches!(
+            custom("scrum-starter", ProjectConventions::default()),
+            Err(AppError::Conflict(_))
+        ));
+        assert!(custom("platform team", ProjectConventions::default()).is_err());
+        assert!(custom(
+            "platform",
+            ProjectConventions {
+                labels: vec!["Bug".to_string(), "bug".to_string()],
+                ..ProjectConventions::default()
+            }
+        )
+        .is_err());
+        assert!(custom(
+            "platform",
+            ProjectConventions {
+                sprint_cadence: Some(SprintCadence {
+                    length_days: 3,
+                    start_weekday: Weekday::Mon,
+                }),
+                ..ProjectConventions::default()
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_blueprints_use_camel_case_and_read_snake_case_templates() {
+        let blueprint = &ProjectBlueprint::built_ins()[0];
+        let json = serde_json::to_value(blueprint).unwrap();
+        assert!(json["dorTemplate"]["labelsRequired"].is_array());
+        assert!(json["dorTemplate"].get("labels_required").is_none());
+
+        // Templates stored before the switch keep their snake_case keys
+        let stored: DorTemplate = serde_json::from_value(serde_json::json!({
+            "required_fields": ["title"],
+            "acceptance_criteria_required": false,
+            "story_points_required": true,
+            "labels_required": ["backend"]
+        }))
+        .unwrap();
+        assert!(stored.story_points_required);
+        assert_eq!(stored.labels_required, vec!["backend".to_string()]);
+    }
+}
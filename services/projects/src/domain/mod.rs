@@ -1 +1,3 @@
+pub mod blueprint;
+pub mod onboarding;
 pub mod project;
@@ -0,0 +1,303 @@
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Guided setup steps for a new project, in the order the wizard presents them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    TeamAdded,
+    FirstStory,
+    ReadinessPolicySet,
+    GithubLinked,
+}
+
+impl OnboardingStep {
+    pub const ALL: [OnboardingStep; 4] = [
+        OnboardingStep::TeamAdded,
+        OnboardingStep::FirstStory,
+        OnboardingStep::ReadinessPolicySet,
+        OnboardingStep::GithubLinked,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OnboardingStep::TeamAdded => "team_added",
+            OnboardingStep::FirstStory => "first_story",
+            OnboardingStep::ReadinessPolicySet => "readiness_policy_set",
+            OnboardingStep::GithubLinked => "github_linked",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|step| step.as_str() == s)
+    }
+
+    fn next_action(&self, project_id: Uuid) -> NextAction {
+        let (title, description, endpoint) = match self {
+            OnboardingStep::TeamAdded => (
+                "Add a team",
+                "Assign the team that will deliver this project.",
+                format!("PUT /api/v1/projects/{}", project_id),
+            ),
+            OnboardingStep::FirstStory => (
+                "Write your first story",
+                "Capture a user story so the team has something to refine.",
+                format!("POST /api/v1/projects/{}/stories", project_id),
+            ),
+            OnboardingStep::ReadinessPolicySet => (
+                "Set a readiness policy",
+                "Choose the Definition of Ready that stories must meet before a sprint.",
+                format!("PUT /api/v1/projects/{}/settings", project_id),
+            ),
+            OnboardingStep::GithubLinked => (
+                "Link a GitHub repository",
+                "Connect the repository so work items can be traced to code.",
+                format!("POST /api/v1/projects/{}/onboarding", project_id),
+            ),
+        };
+
+        NextAction {
+            step: *self,
+            title: title.to_string(),
+            description: description.to_string(),
+            endpoint,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Pending,
+    Completed,
+    Skipped,
+}
+
+impl StepStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StepStatus::Pending => "pending",
+            StepStatus::Completed => "completed",
+            StepStatus::Skipped => "skipped",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(StepStatus::Pending),
+            "completed" => Some(StepStatus::Completed),
+            "skipped" => Some(StepStatus::Skipped),
+            _ => None,
+        }
+    }
+}
+
+/// Transitions the wizard can request for a step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingAction {
+    Complete,
+    Skip,
+    Reset,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingStepState {
+    pub step: OnboardingStep,
+    pub status: StepStatus,
+    /// True when the step was completed because the project data shows it done
+    pub auto_detected: bool,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NextAction {
+    pub step: OnboardingStep,
+    pub title: String,
+    pub description: String,
+    pub endpoint: String,
+}
+
+/// Facts about the project used to complete detectable steps
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OnboardingSignals {
+    pub has_team: bool,
+    pub has_stories: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectOnboarding {
+    pub project_id: Uuid,
+    pub steps: Vec<OnboardingStepState>,
+}
+
+impl ProjectOnboarding {
+    /// Combine recorded step states with what the project data shows
+    pub fn new(
+        project_id: Uuid,
+        recorded: &[OnboardingStepState],
+        signals: OnboardingSignals,
+    ) -> Self {
+        let steps = OnboardingStep::ALL
+            .into_iter()
+            .map(|step| {
+                let detected = match step {
+                    OnboardingStep::TeamAdded => signals.has_team,
+                    OnboardingStep::FirstStory => signals.has_stories,
+                    _ => false,
+                };
+                if detected {
+                    return OnboardingStepState {
+                        step,
+                        status: StepStatus::Completed,
+                        auto_detected: true,
+                        updated_at: None,
+                    };
+                }
+
+                recorded
+                    .iter()
+                    .find(|state| state.step == step)
+                    .cloned()
+                    .unwrap_or(OnboardingStepState {
+                        step,
+                        status: StepStatus::Pending,
+                        auto_detected: false,
+                        updated_at: None,
+                    })
+            })
+            .collect();
+
+        Self { project_id, steps }
+    }
+
+    /// Apply a wizard action, returning the step state to persist
+    pub fn apply(
+        &mut self,
+        step: OnboardingStep,
+        action: OnboardingAction,
+    ) -> Result<OnboardingStepState, AppError> {
+        let state = self
+            .steps
+            .iter_mut()
+            .find(|state| state.step == step)
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown step {}", step.as_str())))?;
+
+        if state.auto_detected {
+            return Err(AppError::Conflict(format!(
+                "Step {} is completed from project data and cannot be changed",
+                step.as_str()
+            )));
+        }
+
+        let status = match (state.status, action) {
+            (_, OnboardingAction::Complete) => StepStatus::Completed,
+            (StepStatus::Completed, OnboardingAction::Skip) => {
+                return Err(AppError::Conflict(format!(
+                    "Step {} is already completed; reset it before skipping",
+                    step.as_str()
+                )))
+            }
+            (_, OnboardingAction::Skip) => StepStatus::Skipped,
+            (_, OnboardingAction::Reset) => StepStatus::Pending,
+        };
+
+        if state.status != status {
+            state.status = status;
+            state.updated_at = Some(Utc::now());
+        }
+
+        Ok(state.clone())
+    }
+
+    /// The first step still waiting on the user
+    pub fn current_step(&self) -> Option<OnboardingStep> {
+        self.steps
+            .iter()
+            .find(|state| state.status == StepStatus::Pending)
+            .map(|state| state.step)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.current_step().is_none()
+    }
+
+    /// Suggested actions for every pending step, current step first
+    pub fn next_actions(&self) -> Vec<NextAction> {
+        self.steps
+            .iter()
+            .filter(|state| state.status == StepStatus::Pending)
+            .map(|state| state.step.next_action(self.project_id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn onboarding(signals: OnboardingSignals) -> ProjectOnboarding {
+        ProjectOnboarding::new(Uuid::new_v4(), &[], signals)
+    }
+
+    #[test]
+    fn test_new_project_starts_with_team_step() {
+        let onboarding = onboarding(OnboardingSignals::default());
+        assert_eq!(onboarding.current_step(), Some(OnboardingStep::TeamAdded));
+        assert_eq!(onboarding.next_actions().len(), 4);
+        assert!(!onboarding.is_complete());
+    }
+
+    #[test]
+    fn test_detected_steps_are_completed_and_locked() {
+        let mut onboarding = onboarding(OnboardingSignals {
+            has_team: true,
+            has_stories: true,
+        });
+        assert_eq!(
+            onboarding.current_step(),
+            Some(OnboardingStep::ReadinessPolicySet)
+        );
+
+        let result = onboarding.apply(OnboardingStep::TeamAdded, OnboardingAction::Reset);
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+
+    #[test]
+    fn test_skip_complete_and_reset_transitions() {
+        let mut onboarding = onboarding(OnboardingSignals {
+            has_team: true,
+            has_stories: true,
+        });
+
+        onboarding
+            .apply(
+                OnboardingStep::ReadinessPolicySet,
+                OnboardingAction::Complete,
+            )
+            .unwrap();
+        let skipped = onboarding
+            .apply(OnboardingStep::GithubLinked, OnboardingAction::Skip)
+            .unwrap();
+        assert_eq!(skipped.status, StepStatus::Skipped);
+        assert!(onboarding.is_complete());
+        assert!(onboarding.next_actions().is_empty());
+
+        let result = onboarding.apply(OnboardingStep::ReadinessPolicySet, OnboardingAction::Skip);
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+
+        onboarding
+            .apply(OnboardingStep::GithubLinked, OnboardingAction::Reset)
+            .unwrap();
+        assert_eq!(
+            onboarding.current_step(),
+            Some(OnboardingStep::GithubLinked)
+        );
+    }
+}
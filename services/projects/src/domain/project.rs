@@ -1,3 +1,4 @@
+use crate::domain::blueprint::ProjectConventions;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -21,11 +22,14 @@ pub struct ProjectSettings {
     pub project_id: Uuid,
     pub estimation_scale: EstimationScale,
     pub dor_template: DorTemplate,
+    /// IANA time zone for sprint boundaries and day-based reporting
+    pub timezone: String,
+    pub conventions: ProjectConventions,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum EstimationScale {
     #[default]
@@ -35,11 +39,16 @@ pub enum EstimationScale {
     TShirtSizes,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct DorTemplate {
+    #[serde(alias = "required_fields")]
     pub required_fields: Vec<String>,
+    #[serde(alias = "acceptance_criteria_required")]
     pub acceptance_criteria_required: bool,
+    #[serde(alias = "story_points_required")]
     pub story_points_required: bool,
+    #[serde(alias = "labels_required")]
     pub labels_required: Vec<String>,
 }
 
@@ -63,9 +72,14 @@ impl Default for DorTemplate {
 pub struct CreateProjectRequest {
     pub name: String,
     pub description: Option<String>,
+    #[serde(alias = "team_id")]
     pub team_id: Option<Uuid>,
+    #[serde(alias = "estimation_scale")]
     pub estimation_scale: Option<EstimationScale>,
+    #[serde(alias = "dor_template")]
     pub dor_template: Option<DorTemplate>,
+    pub timezone: Option<String>,
+    pub conventions: Option<ProjectConventions>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -73,11 +87,17 @@ pub struct CreateProjectRequest {
 pub struct UpdateProjectRequest {
     pub name: Option<String>,
     pub description: Option<String>,
+    #[serde(alias = "team_id")]
     pub team_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdateProjectSettingsRequest {
+    #[serde(alias = "estimation_scale")]
     pub estimation_scale: Option<EstimationScale>,
+    #[serde(alias = "dor_template")]
     pub dor_template: Option<DorTemplate>,
+    pub timezone: Option<String>,
+    pub conventions: Option<ProjectConventions>,
 }
@@ -3,5 +3,5 @@ pub mod application;
 pub mod config;
 pub mod domain;
 
-pub use adapters::http::routes::create_projects_router;
+pub use adapters::http::routes::{build_usecases, create_projects_router};
 pub use config::AppConfig;
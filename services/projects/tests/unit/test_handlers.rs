@@ -152,7 +152,7 @@ mod integration_tests {
             Some("test-audience".to_string()),
         )));
 
-        create_projects_router(pool, verifier).await
+        create_projects_router(pool, verifier, Arc::new(common::quota::UnlimitedQuotaGuard)).await
     }
 
     #[tokio::test]
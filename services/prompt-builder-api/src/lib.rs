@@ -0,0 +1,15 @@
+//! Public API of the prompt-builder service: how to construct its usecases and
+//! router, and the ports through which it reads stories, tasks and readiness
+//! from other services. Everything else in `prompt_builder` is internal to it.
+
+pub use prompt_builder::adapters::integrations::MockLlmService;
+pub use prompt_builder::application::PromptBuilderUsecases;
+pub use prompt_builder::{build_usecases, create_prompt_builder_router};
+
+/// What prompt-builder needs from the services it depends on
+pub mod ports {
+    pub use prompt_builder::application::ports::{
+        AcceptanceCriterion, BacklogService, CoveringTests, GlossaryTerm, LlmService,
+        ReadinessEvaluation, ReadinessService, SourceRepository, StoryInfo, TaskInfo,
+    };
+}
@@ -1,18 +1,42 @@
+use crate::application::ports::SourceRepository;
 use crate::application::PromptBuilderUsecases;
-use crate::domain::{PlanPack, TaskPack};
-use auth_clerk::Authenticated;
+use crate::domain::{
+    GuardrailCategory, Guardrails, PlanPack, PlanPackSettings, PlanPackStatus, ProjectGuardrails,
+    TaskPack, TestScaffold, TestScaffoldFormat,
+};
+use auth_clerk::organization::AuthenticatedWithOrg;
 use axum::{
-    extract::{Path, State},
-    http::{HeaderMap, HeaderValue, StatusCode},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::IntoResponse,
     Json,
 };
 use common::AppError;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// The guardrails section of a plan or task pack
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GuardrailsResponse {
+    pub architecture_rules: Vec<String>,
+    pub forbidden_dependencies: Vec<String>,
+    pub coding_standards: Vec<String>,
+}
+
+impl From<Guardrails> for GuardrailsResponse {
+    fn from(guardrails: Guardrails) -> Self {
+        Self {
+            architecture_rules: guardrails.architecture_rules,
+            forbidden_dependencies: guardrails.forbidden_dependencies,
+            coding_standards: guardrails.coding_standards,
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PlanPackResponse {
     pub id: Uuid,
     pub story_id: Uuid,
@@ -21,11 +45,20 @@ pub struct PlanPackResponse {
     pub architecture_impact: Option<String>,
     pub risks: Vec<String>,
     pub unknowns: Vec<String>,
+    pub guardrails: GuardrailsResponse,
+    pub status: PlanPackStatus,
+    pub approved_by: Option<String>,
+    pub approved_at: Option<String>,
+    /// Set once the story's description or acceptance criteria changed after generation
+    pub stale: bool,
+    pub stale_reason: Option<String>,
+    pub stale_at: Option<String>,
     pub created_at: String,
 }
 
 impl From<PlanPack> for PlanPackResponse {
     fn from(plan_pack: PlanPack) -> Self {
+        let stale = plan_pack.is_stale();
         Self {
             id: plan_pack.id,
             story_id: plan_pack.story_id,
@@ -35,12 +68,20 @@ impl From<PlanPack> for PlanPackResponse {
             architecture_impact: plan_pack.architecture_impact,
             risks: plan_pack.risks,
             unknowns: plan_pack.unknowns,
+            guardrails: plan_pack.guardrails.into(),
+            status: plan_pack.status,
+            approved_by: plan_pack.approved_by,
+            approved_at: plan_pack.approved_at.map(|at| at.to_rfc3339()),
+            stale,
+            stale_reason: plan_pack.stale_reason,
+            stale_at: plan_pack.stale_at.map(|at| at.to_rfc3339()),
             created_at: plan_pack.created_at.to_rfc3339(),
         }
     }
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TaskPackResponse {
     pub id: Uuid,
     pub task_id: Uuid,
@@ -54,6 +95,7 @@ pub struct TaskPackResponse {
     pub do_not_list: serde_json::Value,
     pub commit_plan: serde_json::Value,
     pub run_instructions: Vec<String>,
+    pub guardrails: GuardrailsResponse,
     pub markdown_content: String,
     pub json_content: serde_json::Value,
     pub created_at: String,
@@ -77,6 +119,7 @@ impl From<TaskPack> for TaskPackResponse {
             do_not_list: serde_json::to_value(&task_pack.do_not_list).unwrap_or_default(),
             commit_plan: serde_json::to_value(&task_pack.commit_plan).unwrap_or_default(),
             run_instructions: task_pack.run_instructions,
+            guardrails: task_pack.guardrails.into(),
             markdown_content: task_pack.markdown_content,
             json_content: task_pack.json_content,
             created_at: task_pack.created_at.to_rfc3339(),
@@ -85,20 +128,23 @@ impl From<TaskPack> for TaskPackResponse {
 }
 
 pub async fn generate_plan_pack_from_story(
-    _auth: Authenticated,
+    auth: AuthenticatedWithOrg,
     Path(story_id): Path<Uuid>,
     State(usecases): State<Arc<PromptBuilderUsecases>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let plan_pack = usecases.generate_plan_pack(story_id).await?;
+    let plan_pack = usecases
+        .generate_plan_pack(story_id, auth.org_context.effective_organization_uuid())
+        .await?;
     Ok((StatusCode::CREATED, Json(PlanPackResponse::from(plan_pack))))
 }
 
 pub async fn get_plan_pack_by_story(
+    auth: AuthenticatedWithOrg,
     Path(story_id): Path<Uuid>,
     State(usecases): State<Arc<PromptBuilderUsecases>>,
 ) -> Result<impl IntoResponse, AppError> {
     let plan_pack = usecases
-        .get_plan_pack(story_id)
+        .get_plan_pack(story_id, auth.org_context.effective_organization_uuid())
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Plan Pack for story {} not found", story_id)))?;
 
@@ -106,29 +152,233 @@ pub async fn get_plan_pack_by_story(
 }
 
 pub async fn regenerate_plan_pack(
-    _auth: Authenticated,
+    auth: AuthenticatedWithOrg,
     Path(story_id): Path<Uuid>,
     State(usecases): State<Arc<PromptBuilderUsecases>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let plan_pack = usecases.regenerate_plan_pack(story_id).await?;
+    let plan_pack = usecases
+        .regenerate_plan_pack(story_id, auth.org_context.effective_organization_uuid())
+        .await?;
     Ok(Json(PlanPackResponse::from(plan_pack)))
 }
 
+/// POST /api/v1/prompt-builder/plans/story/{story_id}/approve
+pub async fn approve_plan_pack(
+    auth: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    State(usecases): State<Arc<PromptBuilderUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let plan_pack = usecases
+        .approve_plan_pack(
+            story_id,
+            auth.org_context.effective_organization_uuid(),
+            &auth.auth.sub,
+        )
+        .await?;
+    Ok(Json(PlanPackResponse::from(plan_pack)))
+}
+
+/// The sprint context the plan generator sees for a project, for debugging prompts
+pub async fn get_sprint_context(
+    auth: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    State(usecases): State<Arc<PromptBuilderUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = usecases
+        .get_sprint_context(project_id, auth.org_context.effective_organization_uuid())
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("No active sprint for project {}", project_id))
+        })?;
+
+    Ok(Json(context))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectGuardrailsRequest {
+    #[serde(default)]
+    pub architecture_rules: Vec<String>,
+    #[serde(default)]
+    pub forbidden_dependencies: Vec<String>,
+    #[serde(default)]
+    pub coding_standards: Vec<String>,
+    /// Categories that must have rules before packs are generated
+    #[serde(default)]
+    pub required_categories: Vec<GuardrailCategory>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectGuardrailsResponse {
+    pub project_id: Uuid,
+    pub architecture_rules: Vec<String>,
+    pub forbidden_dependencies: Vec<String>,
+    pub coding_standards: Vec<String>,
+    pub required_categories: Vec<GuardrailCategory>,
+    pub updated_at: String,
+}
+
+impl From<ProjectGuardrails> for ProjectGuardrailsResponse {
+    fn from(project: ProjectGuardrails) -> Self {
+        Self {
+            project_id: project.project_id,
+            architecture_rules: project.guardrails.architecture_rules,
+            forbidden_dependencies: project.guardrails.forbidden_dependencies,
+            coding_standards: project.guardrails.coding_standards,
+            required_categories: project.required_categories,
+            updated_at: project.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// GET /api/v1/prompt-builder/guardrails/project/{project_id}
+pub async fn get_project_guardrails(
+    auth: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    State(usecases): State<Arc<PromptBuilderUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let guardrails = usecases
+        .get_project_guardrails(project_id, auth.org_context.effective_organization_uuid())
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("No guardrails for project {}", project_id)))?;
+
+    Ok(Json(ProjectGuardrailsResponse::from(guardrails)))
+}
+
+/// PUT /api/v1/prompt-builder/guardrails/project/{project_id}
+pub async fn update_project_guardrails(
+    auth: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    State(usecases): State<Arc<PromptBuilderUsecases>>,
+    Json(request): Json<ProjectGuardrailsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let guardrails = usecases
+        .set_project_guardrails(
+            project_id,
+            auth.org_context.effective_organization_uuid(),
+            Guardrails {
+                architecture_rules: request.architecture_rules,
+                forbidden_dependencies: request.forbidden_dependencies,
+                coding_standards: request.coding_standards,
+            },
+            request.required_categories,
+        )
+        .await?;
+
+    Ok(Json(ProjectGuardrailsResponse::from(guardrails)))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanPackSettingsRequest {
+    pub auto_regenerate_stale: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanPackSettingsResponse {
+    pub organization_id: Uuid,
+    pub auto_regenerate_stale: bool,
+    pub updated_at: String,
+}
+
+impl From<PlanPackSettings> for PlanPackSettingsResponse {
+    fn from(settings: PlanPackSettings) -> Self {
+        Self {
+            organization_id: settings.organization_id,
+            auto_regenerate_stale: settings.auto_regenerate_stale,
+            updated_at: settings.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// GET /api/v1/prompt-builder/settings/plan-packs
+pub async fn get_plan_pack_settings(
+    auth: AuthenticatedWithOrg,
+    State(usecases): State<Arc<PromptBuilderUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let settings = usecases
+        .get_plan_pack_settings(auth.org_context.effective_organization_uuid())
+        .await?;
+    Ok(Json(PlanPackSettingsResponse::from(settings)))
+}
+
+/// PUT /api/v1/prompt-builder/settings/plan-packs
+/// Turning on auto-regeneration spends LLM calls whenever a story with a plan changes
+pub async fn update_plan_pack_settings(
+    auth: AuthenticatedWithOrg,
+    State(usecases): State<Arc<PromptBuilderUsecases>>,
+    Json(request): Json<PlanPackSettingsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if !is_maintainer(&auth) {
+        return Err(AppError::Forbidden(
+            "Only organization owners and admins can change plan pack settings".to_string(),
+        ));
+    }
+    let settings = usecases
+        .set_plan_pack_settings(
+            auth.org_context.effective_organization_uuid(),
+            request.auto_regenerate_stale,
+        )
+        .await?;
+    Ok(Json(PlanPackSettingsResponse::from(settings)))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TaskPackQuery {
+    /// Generate without an approved plan pack; maintainers only
+    #[serde(default)]
+    pub force: bool,
+}
+
+impl TaskPackQuery {
+    fn force(&self, auth: &AuthenticatedWithOrg) -> Result<bool, AppError> {
+        if self.force && !is_maintainer(auth) {
+            return Err(AppError::Forbidden(
+                "Only organization owners and admins can generate task packs without an approved plan"
+                    .to_string(),
+            ));
+        }
+        Ok(self.force)
+    }
+}
+
+/// Organization owners and admins, and anyone in their personal workspace
+fn is_maintainer(auth: &AuthenticatedWithOrg) -> bool {
+    auth.org_context.is_personal()
+        || matches!(
+            auth.auth
+                .org_role
+                .as_deref()
+                .map(|role| role.trim_start_matches("org:")),
+            Some("owner" | "admin")
+        )
+}
+
 pub async fn generate_task_pack_from_task(
-    _auth: Authenticated,
+    auth: AuthenticatedWithOrg,
     Path(task_id): Path<Uuid>,
+    Query(query): Query<TaskPackQuery>,
     State(usecases): State<Arc<PromptBuilderUsecases>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let task_pack = usecases.generate_task_pack(task_id).await?;
+    let task_pack = usecases
+        .generate_task_pack(
+            task_id,
+            auth.org_context.effective_organization_uuid(),
+            query.force(&auth)?,
+        )
+        .await?;
     Ok((StatusCode::CREATED, Json(TaskPackResponse::from(task_pack))))
 }
 
 pub async fn get_task_pack_by_task(
+    auth: AuthenticatedWithOrg,
     Path(task_id): Path<Uuid>,
     State(usecases): State<Arc<PromptBuilderUsecases>>,
 ) -> Result<impl IntoResponse, AppError> {
     let task_pack = usecases
-        .get_task_pack(task_id)
+        .get_task_pack(task_id, auth.org_context.effective_organization_uuid())
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Task Pack for task {} not found", task_id)))?;
 
@@ -136,11 +386,12 @@ pub async fn get_task_pack_by_task(
 }
 
 pub async fn get_task_pack_markdown(
+    auth: AuthenticatedWithOrg,
     Path(task_id): Path<Uuid>,
     State(usecases): State<Arc<PromptBuilderUsecases>>,
 ) -> Result<impl IntoResponse, AppError> {
     let task_pack = usecases
-        .get_task_pack(task_id)
+        .get_task_pack(task_id, auth.org_context.effective_organization_uuid())
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Task Pack for task {} not found", task_id)))?;
 
@@ -154,11 +405,12 @@ pub async fn get_task_pack_markdown(
 }
 
 pub async fn get_task_pack_json(
+    auth: AuthenticatedWithOrg,
     Path(task_id): Path<Uuid>,
     State(usecases): State<Arc<PromptBuilderUsecases>>,
 ) -> Result<impl IntoResponse, AppError> {
     let task_pack = usecases
-        .get_task_pack(task_id)
+        .get_task_pack(task_id, auth.org_context.effective_organization_uuid())
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Task Pack for task {} not found", task_id)))?;
 
@@ -166,10 +418,250 @@ pub async fn get_task_pack_json(
 }
 
 pub async fn regenerate_task_pack(
-    _auth: Authenticated,
+    auth: AuthenticatedWithOrg,
     Path(task_id): Path<Uuid>,
+    Query(query): Query<TaskPackQuery>,
     State(usecases): State<Arc<PromptBuilderUsecases>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let task_pack = usecases.regenerate_task_pack(task_id).await?;
+    let task_pack = usecases
+        .regenerate_task_pack(
+            task_id,
+            auth.org_context.effective_organization_uuid(),
+            query.force(&auth)?,
+        )
+        .await?;
     Ok(Json(TaskPackResponse::from(task_pack)))
 }
+
+#[derive(Debug, Deserialize)]
+pub struct TestScaffoldQuery {
+    /// `gherkin` (default) or `rust`
+    pub format: Option<String>,
+    /// Defaults to the latest version
+    pub version: Option<i32>,
+    /// When generating, a GitHub repository (`owner/name`) whose test files
+    /// are checked for existing coverage of the criteria
+    pub repository: Option<String>,
+    /// Branch, tag or commit of `repository`
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+}
+
+impl TestScaffoldQuery {
+    fn format(&self) -> Result<TestScaffoldFormat, AppError> {
+        self.format
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    fn repository(&self) -> Option<SourceRepository> {
+        self.repository
+            .as_deref()
+            .map(str::trim)
+            .filter(|repository| !repository.is_empty())
+            .map(|repository| SourceRepository {
+                repository: repository.to_string(),
+                git_ref: self.git_ref.clone(),
+            })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestScaffoldResponse {
+    pub id: Uuid,
+    pub story_id: Uuid,
+    pub plan_pack_id: Option<Uuid>,
+    pub format: String,
+    pub version: i32,
+    pub file_name: String,
+    pub content: String,
+    pub created_at: String,
+}
+
+impl From<TestScaffold> for TestScaffoldResponse {
+    fn from(scaffold: TestScaffold) -> Self {
+        Self {
+            id: scaffold.id,
+            story_id: scaffold.story_id,
+            plan_pack_id: scaffold.plan_pack_id,
+            format: scaffold.format.as_str().to_string(),
+            version: scaffold.version,
+            file_name: scaffold.file_name,
+            content: scaffold.content,
+            created_at: scaffold.created_at.to_rfc3339(),
+        }
+    }
+}
+
+pub async fn generate_test_scaffold_from_story(
+    auth: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    Query(query): Query<TestScaffoldQuery>,
+    State(usecases): State<Arc<PromptBuilderUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let scaffold = usecases
+        .generate_test_scaffold(
+            story_id,
+            query.format()?,
+            query.repository().as_ref(),
+            auth.org_context.effective_organization_uuid(),
+        )
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(TestScaffoldResponse::from(scaffold)),
+    ))
+}
+
+pub async fn get_test_scaffolds_by_story(
+    auth: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    State(usecases): State<Arc<PromptBuilderUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let scaffolds = usecases
+        .get_test_scaffolds(story_id, auth.org_context.effective_organization_uuid())
+        .await?;
+    Ok(Json(
+        scaffolds
+            .into_iter()
+            .map(TestScaffoldResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// The scaffold as a file attachment
+pub async fn download_test_scaffold(
+    auth: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    Query(query): Query<TestScaffoldQuery>,
+    State(usecases): State<Arc<PromptBuilderUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let format = query.format()?;
+    let scaffold = usecases
+        .get_test_scaffold(
+            story_id,
+            format,
+            query.version,
+            auth.org_context.effective_organization_uuid(),
+        )
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "{} test scaffold for story {} not found",
+                format.as_str(),
+                story_id
+            ))
+        })?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(format.content_type()),
+    );
+    let disposition = format!("attachment; filename=\"{}\"", scaffold.file_name);
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&disposition).map_err(|_| AppError::InternalServerError)?,
+    );
+
+    Ok((headers, scaffold.content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        AcceptanceCriteriaMap, AcceptanceCriterionCoverage, CommitPlan, DoNotList, TaskConstraints,
+        TestPlan,
+    };
+    use std::collections::HashMap;
+
+    fn guardrails() -> Guardrails {
+        Guardrails {
+            architecture_rules: vec!["Keep domain types free of HTTP concerns".to_string()],
+            forbidden_dependencies: vec!["openssl".to_string()],
+            coding_standards: vec!["Run cargo fmt".to_string()],
+        }
+    }
+
+    fn assert_camel_case_guardrails(body: &serde_json::Value) {
+        let guardrails = &body["guardrails"];
+        assert_eq!(
+            guardrails["architectureRules"][0],
+            "Keep domain types free of HTTP concerns"
+        );
+        assert_eq!(guardrails["forbiddenDependencies"][0], "openssl");
+        assert_eq!(guardrails["codingStandards"][0], "Run cargo fmt");
+        assert!(guardrails.get("architecture_rules").is_none());
+        assert!(guardrails.get("forbidden_dependencies").is_none());
+        assert!(guardrails.get("coding_standards").is_none());
+    }
+
+    #[test]
+    fn test_plan_pack_response_serializes_guardrails_in_camel_case() {
+        let plan_pack = PlanPack::new(
+            Uuid::new_v4(),
+            AcceptanceCriteriaMap {
+                criteria: HashMap::new(),
+            },
+            vec![],
+            None,
+            vec![],
+            vec![],
+        )
+        .unwrap()
+        .with_guardrails(guardrails());
+
+        let body = serde_json::to_value(PlanPackResponse::from(plan_pack)).unwrap();
+        assert_camel_case_guardrails(&body);
+    }
+
+    #[test]
+    fn test_task_pack_response_serializes_guardrails_in_camel_case() {
+        let task_pack = TaskPack::new(
+            Uuid::new_v4(),
+            None,
+            "Add the save endpoint".to_string(),
+            vec![],
+            "Saving drafts".to_string(),
+            vec![AcceptanceCriterionCoverage {
+                ac_id: "AC1".to_string(),
+                given: "a draft".to_string(),
+                when: "the user saves".to_string(),
+                then: "the draft is stored".to_string(),
+                test_approach: "Integration test".to_string(),
+            }],
+            TaskConstraints {
+                file_paths: vec![],
+                ports_to_implement: vec![],
+                dtos_to_create: vec![],
+                architecture_notes: String::new(),
+            },
+            TestPlan {
+                unit_tests: vec![],
+                integration_tests: vec![],
+                contract_tests: vec![],
+                coverage_threshold: None,
+            },
+            DoNotList {
+                forbidden_actions: vec![],
+                no_shortcuts: vec![],
+                required_practices: vec![],
+            },
+            CommitPlan {
+                commit_message_template: "feat: save drafts".to_string(),
+                pre_commit_checks: vec![],
+                branch_naming_convention: None,
+            },
+            vec![],
+        )
+        .unwrap()
+        .with_guardrails(guardrails());
+
+        let body = serde_json::to_value(TaskPackResponse::from(task_pack)).unwrap();
+        assert_camel_case_guardrails(&body);
+    }
+}
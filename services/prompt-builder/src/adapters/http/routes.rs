@@ -0,0 +1,85 @@
+use crate::adapters::http::handlers::{
+    approve_plan_pack, download_test_scaffold, generate_plan_pack_from_story,
+    generate_task_pack_from_task, generate_test_scaffold_from_story, get_plan_pack_by_story,
+    get_plan_pack_settings, get_project_guardrails, get_sprint_context, get_task_pack_by_task,
+    get_task_pack_json, get_task_pack_markdown, get_test_scaffolds_by_story, regenerate_plan_pack,
+    regenerate_task_pack, update_plan_pack_settings, update_project_guardrails,
+};
+use crate::application::PromptBuilderUsecases;
+use auth_clerk::JwtVerifier;
+use axum::routing::{get, post, put};
+use axum::{Extension, Router};
+use common::route_registry::ServiceRouter;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tower_http::trace::TraceLayer;
+
+pub fn create_prompt_builder_router(
+    prompt_usecases: Arc<PromptBuilderUsecases>,
+    verifier: Arc<Mutex<JwtVerifier>>,
+) -> Router {
+    ServiceRouter::new("prompt-builder")
+        .route(
+            "/api/v1/prompt-builder/plans/from-story/{story_id}",
+            post(generate_plan_pack_from_story),
+        )
+        .route(
+            "/api/v1/prompt-builder/plans/story/{story_id}",
+            get(get_plan_pack_by_story),
+        )
+        .route(
+            "/api/v1/prompt-builder/plans/story/{story_id}/regenerate",
+            put(regenerate_plan_pack),
+        )
+        .route(
+            "/api/v1/prompt-builder/plans/story/{story_id}/approve",
+            post(approve_plan_pack),
+        )
+        .route(
+            "/api/v1/prompt-builder/settings/plan-packs",
+            get(get_plan_pack_settings).put(update_plan_pack_settings),
+        )
+        .route(
+            "/api/v1/prompt-builder/work-packets/from-task/{task_id}",
+            post(generate_task_pack_from_task),
+        )
+        .route(
+            "/api/v1/prompt-builder/work-packets/task/{task_id}",
+            get(get_task_pack_by_task),
+        )
+        .route(
+            "/api/v1/prompt-builder/work-packets/task/{task_id}/markdown",
+            get(get_task_pack_markdown),
+        )
+        .route(
+            "/api/v1/prompt-builder/work-packets/task/{task_id}/json",
+            get(get_task_pack_json),
+        )
+        .route(
+            "/api/v1/prompt-builder/work-packets/task/{task_id}/regenerate",
+            put(regenerate_task_pack),
+        )
+        .route(
+            "/api/v1/prompt-builder/context/sprint/{project_id}",
+            get(get_sprint_context),
+        )
+        .route(
+            "/api/v1/prompt-builder/guardrails/project/{project_id}",
+            get(get_project_guardrails).put(update_project_guardrails),
+        )
+        .route(
+            "/api/v1/prompt-builder/tests/from-story/{story_id}",
+            post(generate_test_scaffold_from_story),
+        )
+        .route(
+            "/api/v1/prompt-builder/tests/story/{story_id}",
+            get(get_test_scaffolds_by_story),
+        )
+        .route(
+            "/api/v1/prompt-builder/tests/story/{story_id}/download",
+            get(download_test_scaffold),
+        )
+        .with_state(prompt_usecases)
+        .layer(Extension(verifier))
+        .layer(TraceLayer::new_for_http())
+}
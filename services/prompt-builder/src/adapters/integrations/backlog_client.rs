@@ -10,6 +10,8 @@ struct StoryResponse {
     id: Uuid,
     #[serde(rename = "projectId")]
     project_id: Uuid,
+    #[serde(rename = "organizationId", default)]
+    organization_id: Option<Uuid>,
     title: String,
     description: Option<String>,
     status: String,
@@ -43,7 +45,11 @@ impl HttpBacklogService {
 
 #[async_trait]
 impl BacklogService for HttpBacklogService {
-    async fn get_story_info(&self, story_id: Uuid) -> Result<Option<StoryInfo>, AppError> {
+    async fn get_story_info(
+        &self,
+        story_id: Uuid,
+        _organization_id: Option<Uuid>,
+    ) -> Result<Option<StoryInfo>, AppError> {
         let url = format!("{}/stories/{}", self.base_url, story_id);
         let response = self
             .client
@@ -67,13 +73,19 @@ impl BacklogService for HttpBacklogService {
 
         Ok(Some(StoryInfo {
             id: story.id,
+            project_id: story.project_id,
+            organization_id: story.organization_id,
             title: story.title,
             description: story.description,
             status: story.status,
         }))
     }
 
-    async fn get_task_info(&self, task_id: Uuid) -> Result<Option<TaskInfo>, AppError> {
+    async fn get_task_info(
+        &self,
+        task_id: Uuid,
+        _organization_id: Option<Uuid>,
+    ) -> Result<Option<TaskInfo>, AppError> {
         // For now, we'll need to get the task by searching through stories
         // This could be optimized with a direct task endpoint in the future
         let url = format!("{}/tasks/{}", self.base_url, task_id);
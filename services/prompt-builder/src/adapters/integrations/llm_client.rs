@@ -1,10 +1,14 @@
 use crate::application::ports::{
-    AcceptanceCriterion, LlmService, PlanPackGeneration, ProposedTaskGeneration, StoryInfo,
-    TaskInfo, TaskPackGeneration,
+    AcceptanceCriterion, CoveringTests, GlossaryTerm, LlmService, PlanPackGeneration,
+    ProposedTaskGeneration, StoryInfo, TaskInfo, TaskPackGeneration,
 };
+use crate::domain::{render_test_scaffold, SprintContext, TestScaffoldFormat};
 use async_trait::async_trait;
+use common::circuit_breaker::CircuitBreaker;
+use common::outbound_http::OutboundHttpClient;
 use common::AppError;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[derive(Debug, Serialize)]
 struct GenerateRequest {
@@ -35,7 +39,7 @@ struct ResponseMessage {
 }
 
 pub struct OpenAiLlmService {
-    client: reqwest::Client,
+    client: OutboundHttpClient,
     api_key: String,
     model: String,
 }
@@ -43,7 +47,9 @@ pub struct OpenAiLlmService {
 impl OpenAiLlmService {
     pub fn new(api_key: String, model: Option<String>) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: OutboundHttpClient::builder()
+                .request_timeout(Duration::from_secs(60))
+                .build(),
             api_key,
             model: model.unwrap_or_else(|| "gpt-3.5-turbo".to_string()),
         }
@@ -59,24 +65,31 @@ impl OpenAiLlmService {
             temperature: 0.3,
         };
 
-        let response = self
-            .client
-            .post("https://api.openai.com/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .map_err(|_| AppError::InternalServerError)?;
-
-        if !response.status().is_success() {
-            return Err(AppError::InternalServerError);
-        }
+        let response_data: GenerateResponse = CircuitBreaker::for_dependency("openai")
+            .call(async {
+                let response = self
+                    .client
+                    .send(
+                        "prompt_builder.openai.chat",
+                        self.client
+                            .post("https://api.openai.com/v1/chat/completions")
+                            .header("Authorization", format!("Bearer {}", self.api_key))
+                            .header("Content-Type", "application/json")
+                            .json(&request),
+                    )
+                    .await
+                    .map_err(|_| AppError::InternalServerError)?;
+
+                if !response.status().is_success() {
+                    return Err(AppError::InternalServerError);
+                }
 
-        let response_data: GenerateResponse = response
-            .json()
-            .await
-            .map_err(|_| AppError::InternalServerError)?;
+                response
+                    .json()
+                    .await
+                    .map_err(|_| AppError::InternalServerError)
+            })
+            .await?;
 
         Ok(response_data
             .choices
@@ -91,6 +104,8 @@ impl OpenAiLlmService {
         &self,
         story: &StoryInfo,
         criteria: &[AcceptanceCriterion],
+        sprint: Option<&SprintContext>,
+        glossary: &[GlossaryTerm],
     ) -> String {
         let description = story
             .description
@@ -107,11 +122,21 @@ impl OpenAiLlmService {
             .collect::<Vec<_>>()
             .join("\n");
 
+        let sprint_text = match sprint {
+            Some(sprint) => format!(
+                "Current Sprint Context (size tasks to fit the remaining time and avoid \
+                duplicating committed work):\n{}\n\n",
+                sprint.prompt_section()
+            ),
+            None => String::new(),
+        };
+
         format!(
             "Generate a Plan Pack for this user story. Analyze the story and acceptance criteria to propose 2-5 implementation tasks.\n\n\
             Story: {}\n\
             Description: {}\n\n\
             Acceptance Criteria:\n{}\n\n\
+            {}{}\
             Respond with ONLY a JSON object with this structure:\n\
             {{\n  \
               \"proposed_tasks\": [\n    \
@@ -128,7 +153,11 @@ impl OpenAiLlmService {
               \"unknowns\": [\"Unknown 1\", \"Unknown 2\"]\n\
             }}\n\n\
             Ensure all acceptance criteria are covered by at least one task. Each task should be small enough to complete in 1-2 days.",
-            story.title, description, criteria_text
+            story.title,
+            description,
+            criteria_text,
+            sprint_text,
+            glossary_section(glossary)
         )
     }
 
@@ -137,6 +166,7 @@ impl OpenAiLlmService {
         task: &TaskInfo,
         story: &StoryInfo,
         criteria: &[AcceptanceCriterion],
+        glossary: &[GlossaryTerm],
     ) -> String {
         let task_description = task
             .description
@@ -162,6 +192,7 @@ impl OpenAiLlmService {
             Story Context: {} - {}\n\
             Task: {} - {}\n\n\
             Acceptance Criteria to Cover:\n{}\n\n\
+            {}\
             Respond with ONLY a JSON object with this structure:\n\
             {{\n  \
               \"objectives\": \"Clear, specific objectives for this task\",\n  \
@@ -182,19 +213,114 @@ impl OpenAiLlmService {
               \"run_instructions\": [\"Run cargo test\", \"Run integration tests\"]\n\
             }}\n\n\
             Focus on creating safe, testable, architecture-compliant code. Include specific do-not items to prevent shortcuts.",
-            story.title, story_description, task.title, task_description, criteria_text
+            story.title,
+            story_description,
+            task.title,
+            task_description,
+            criteria_text,
+            glossary_section(glossary)
+        )
+    }
+
+    fn create_test_scaffold_prompt(
+        &self,
+        story: &StoryInfo,
+        criteria: &[AcceptanceCriterion],
+        format: TestScaffoldFormat,
+        existing_tests: &[CoveringTests],
+    ) -> String {
+        let description = story
+            .description
+            .as_deref()
+            .unwrap_or("No description provided");
+        let criteria_text = criteria
+            .iter()
+            .map(|ac| {
+                format!(
+                    "- {}: Given {}, when {}, then {}",
+                    ac.ac_id, ac.given, ac.when, ac.then
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let instructions = match format {
+            TestScaffoldFormat::Gherkin => {
+                "Write a Gherkin feature file for this story. Add one Scenario per acceptance \
+                criterion and start each scenario name with the criterion ID, for example \
+                \"Scenario: AC1 - ...\". Use concrete Given/When/Then steps derived from the criterion."
+            }
+            TestScaffoldFormat::Rust => {
+                "Write a Rust acceptance test module for this story. Add one #[test] function per \
+                acceptance criterion, marked #[ignore = \"not implemented\"], with a doc comment \
+                starting with the criterion ID. Lay out the Given/When/Then steps as comments \
+                and end each test with todo!()."
+            }
+        };
+
+        let mut existing_text = String::new();
+        if !existing_tests.is_empty() {
+            existing_text.push_str(
+                "These existing test files may already cover some criteria. Where one does, \
+                 add a comment to the criterion's scenario or test naming the file so reviewers \
+                 can check it instead of duplicating it:\n",
+            );
+            for covering in existing_tests {
+                existing_text.push_str(&format!(
+                    "- {}: {}\n",
+                    covering.ac_id,
+                    covering.test_files.join(", ")
+                ));
+            }
+            existing_text.push('\n');
+        }
+
+        format!(
+            "{}\n\n\
+            Story: {}\n\
+            Description: {}\n\n\
+            Acceptance Criteria:\n{}\n\n\
+            {}\
+            Respond with ONLY the file content, without Markdown code fences or commentary.",
+            instructions, story.title, description, criteria_text, existing_text
         )
     }
 }
 
+/// The prompt section explaining the team's terms, empty when none are used
+fn glossary_section(glossary: &[GlossaryTerm]) -> String {
+    if glossary.is_empty() {
+        return String::new();
+    }
+
+    let mut section =
+        String::from("Team Glossary (use these terms with the meanings the team gives them):\n");
+    for entry in glossary {
+        if entry.synonyms.is_empty() {
+            section.push_str(&format!("- {}: {}\n", entry.term, entry.definition));
+        } else {
+            section.push_str(&format!(
+                "- {} (also: {}): {}\n",
+                entry.term,
+                entry.synonyms.join(", "),
+                entry.definition
+            ));
+        }
+    }
+    section.push('\n');
+    section
+}
+
 #[async_trait]
 impl LlmService for OpenAiLlmService {
     async fn generate_plan_pack(
         &self,
         story: &StoryInfo,
         criteria: &[AcceptanceCriterion],
+        sprint: Option<&SprintContext>,
+        glossary: &[GlossaryTerm],
     ) -> Result<PlanPackGeneration, AppError> {
-        let prompt = self.create_plan_pack_prompt(story, criteria);
+        let prompt = self.create_plan_pack_prompt(story, criteria, sprint, glossary);
         let response = self.generate_completion(prompt).await?;
 
         #[derive(Deserialize)]
@@ -240,8 +366,9 @@ impl LlmService for OpenAiLlmService {
         task: &TaskInfo,
         story: &StoryInfo,
         criteria: &[AcceptanceCriterion],
+        glossary: &[GlossaryTerm],
     ) -> Result<TaskPackGeneration, AppError> {
-        let prompt = self.create_task_pack_prompt(task, story, criteria);
+        let prompt = self.create_task_pack_prompt(task, story, criteria, glossary);
         let response = self.generate_completion(prompt).await?;
 
         let parsed: TaskPackGeneration = serde_json::from_str(&response)
@@ -249,6 +376,17 @@ impl LlmService for OpenAiLlmService {
 
         Ok(parsed)
     }
+
+    async fn generate_test_scaffold(
+        &self,
+        story: &StoryInfo,
+        criteria: &[AcceptanceCriterion],
+        format: TestScaffoldFormat,
+        existing_tests: &[CoveringTests],
+    ) -> Result<String, AppError> {
+        let prompt = self.create_test_scaffold_prompt(story, criteria, format, existing_tests);
+        self.generate_completion(prompt).await
+    }
 }
 
 // Mock implementation for development
@@ -260,6 +398,8 @@ impl LlmService for MockLlmService {
         &self,
         story: &StoryInfo,
         criteria: &[AcceptanceCriterion],
+        _sprint: Option<&SprintContext>,
+        _glossary: &[GlossaryTerm],
     ) -> Result<PlanPackGeneration, AppError> {
         let ac_refs: Vec<String> = criteria.iter().map(|c| c.ac_id.clone()).collect();
 
@@ -291,6 +431,7 @@ impl LlmService for MockLlmService {
         task: &TaskInfo,
         _story: &StoryInfo,
         criteria: &[AcceptanceCriterion],
+        _glossary: &[GlossaryTerm],
     ) -> Result<TaskPackGeneration, AppError> {
         let _ac_refs: Vec<String> = criteria.iter().map(|c| c.ac_id.clone()).collect();
 
@@ -334,4 +475,19 @@ impl LlmService for MockLlmService {
             ],
         })
     }
+
+    async fn generate_test_scaffold(
+        &self,
+        story: &StoryInfo,
+        criteria: &[AcceptanceCriterion],
+        format: TestScaffoldFormat,
+        _existing_tests: &[CoveringTests],
+    ) -> Result<String, AppError> {
+        let criteria: Vec<_> = criteria
+            .iter()
+            .enumerate()
+            .map(|(position, ac)| ac.to_info(position as u32))
+            .collect();
+        Ok(render_test_scaffold(format, &story.title, &criteria))
+    }
 }
@@ -1,4 +1,7 @@
-use crate::application::ports::{AcceptanceCriterion, ReadinessEvaluation, ReadinessService};
+use crate::application::ports::{
+    AcceptanceCriterion, CoveringTests, GlossaryTerm, ReadinessEvaluation, ReadinessService,
+    SourceRepository,
+};
 use async_trait::async_trait;
 use common::AppError;
 use serde::Deserialize;
@@ -6,9 +9,9 @@ use uuid::Uuid;
 
 #[allow(dead_code)]
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct AcceptanceCriterionResponse {
     id: Uuid,
-    #[serde(rename = "storyId")]
     story_id: Uuid,
     ac_id: String,
     given: String,
@@ -17,12 +20,30 @@ struct AcceptanceCriterionResponse {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct ReadinessEvaluationResponse {
     score: i32,
-    #[serde(rename = "missingItems")]
     missing_items: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestMappingResponse {
+    criteria: Vec<CriterionTestMappingResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CriterionTestMappingResponse {
+    ac_id: String,
+    suggestions: Vec<TestFileSuggestionResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TestFileSuggestionResponse {
+    path: String,
+}
+
 pub struct HttpReadinessService {
     client: reqwest::Client,
     base_url: String,
@@ -42,6 +63,7 @@ impl ReadinessService for HttpReadinessService {
     async fn get_acceptance_criteria(
         &self,
         story_id: Uuid,
+        _organization_id: Option<Uuid>,
     ) -> Result<Vec<AcceptanceCriterion>, AppError> {
         let url = format!("{}/criteria/{}", self.base_url, story_id);
         let response = self
@@ -71,7 +93,11 @@ impl ReadinessService for HttpReadinessService {
             .collect())
     }
 
-    async fn evaluate_readiness(&self, story_id: Uuid) -> Result<ReadinessEvaluation, AppError> {
+    async fn evaluate_readiness(
+        &self,
+        story_id: Uuid,
+        _organization_id: Option<Uuid>,
+    ) -> Result<ReadinessEvaluation, AppError> {
         let url = format!("{}/readiness/{}/evaluate", self.base_url, story_id);
         let response = self
             .client
@@ -94,4 +120,74 @@ impl ReadinessService for HttpReadinessService {
             missing_items: evaluation.missing_items,
         })
     }
+
+    async fn get_glossary_terms(
+        &self,
+        _organization_id: Option<Uuid>,
+        text: &str,
+    ) -> Result<Vec<GlossaryTerm>, AppError> {
+        let url = format!("{}/glossary", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("text", text)])
+            .send()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if !response.status().is_success() {
+            return Err(AppError::InternalServerError);
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|_| AppError::InternalServerError)
+    }
+
+    async fn get_covering_tests(
+        &self,
+        story_id: Uuid,
+        _organization_id: Option<Uuid>,
+        repository: &SourceRepository,
+    ) -> Result<Vec<CoveringTests>, AppError> {
+        let url = format!(
+            "{}/readiness/stories/{}/test-mapping",
+            self.base_url, story_id
+        );
+        let mut query = vec![("repository", repository.repository.as_str())];
+        if let Some(git_ref) = &repository.git_ref {
+            query.push(("ref", git_ref));
+        }
+        let response = self
+            .client
+            .get(&url)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if !response.status().is_success() {
+            return Err(AppError::InternalServerError);
+        }
+
+        let mapping: TestMappingResponse = response
+            .json()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(mapping
+            .criteria
+            .into_iter()
+            .filter(|criterion| !criterion.suggestions.is_empty())
+            .map(|criterion| CoveringTests {
+                ac_id: criterion.ac_id,
+                test_files: criterion
+                    .suggestions
+                    .into_iter()
+                    .map(|suggestion| suggestion.path)
+                    .collect(),
+            })
+            .collect())
+    }
 }
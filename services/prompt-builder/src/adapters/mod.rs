@@ -1,3 +1,4 @@
 pub mod http;
 pub mod integrations;
 pub mod persistence;
+pub mod storage;
@@ -1,4 +1,7 @@
-use crate::domain::{AcceptanceCriteriaMap, PlanPack, ProposedTask, TaskPack};
+use crate::domain::{
+    AcceptanceCriteriaMap, Guardrails, PlanPack, PlanPackSettings, PlanPackStatus,
+    ProjectGuardrails, ProposedTask, SprintContextStory, TaskPack, TestScaffold,
+};
 use common::AppError;
 use serde_json;
 use sqlx::FromRow;
@@ -13,6 +16,13 @@ pub struct PlanPackRow {
     pub architecture_impact: Option<String>,
     pub risks: Vec<String>,
     pub unknowns: Vec<String>,
+    pub guardrails: serde_json::Value,
+    pub status: String,
+    pub approved_by: Option<String>,
+    pub approved_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub story_description: Option<String>,
+    pub stale_reason: Option<String>,
+    pub stale_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -27,6 +37,9 @@ impl TryFrom<PlanPackRow> for PlanPack {
         let proposed_tasks: Vec<ProposedTask> = serde_json::from_value(row.proposed_tasks)
             .map_err(|_| AppError::InternalServerError)?;
 
+        let guardrails: Guardrails =
+            serde_json::from_value(row.guardrails).map_err(|_| AppError::InternalServerError)?;
+
         Ok(PlanPack {
             id: row.id,
             story_id: row.story_id,
@@ -35,11 +48,35 @@ impl TryFrom<PlanPackRow> for PlanPack {
             architecture_impact: row.architecture_impact,
             risks: row.risks,
             unknowns: row.unknowns,
+            guardrails,
+            status: PlanPackStatus::parse(&row.status).ok_or(AppError::InternalServerError)?,
+            approved_by: row.approved_by,
+            approved_at: row.approved_at,
+            story_description: row.story_description,
+            stale_reason: row.stale_reason,
+            stale_at: row.stale_at,
             created_at: row.created_at,
         })
     }
 }
 
+#[derive(Debug, FromRow)]
+pub struct PlanPackSettingsRow {
+    pub organization_id: Uuid,
+    pub auto_regenerate_stale: bool,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<PlanPackSettingsRow> for PlanPackSettings {
+    fn from(row: PlanPackSettingsRow) -> Self {
+        Self {
+            organization_id: row.organization_id,
+            auto_regenerate_stale: row.auto_regenerate_stale,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
 #[derive(Debug, FromRow)]
 pub struct TaskPackRow {
     pub id: Uuid,
@@ -54,8 +91,11 @@ pub struct TaskPackRow {
     pub do_not_list: serde_json::Value,
     pub commit_plan: serde_json::Value,
     pub run_instructions: serde_json::Value,
+    pub guardrails: serde_json::Value,
     pub markdown_content: String,
     pub json_content: serde_json::Value,
+    /// Set when the markdown and JSON renderings are in blob storage
+    pub content_key: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -89,6 +129,9 @@ impl TryFrom<TaskPackRow> for TaskPack {
         let run_instructions: Vec<String> = serde_json::from_value(row.run_instructions)
             .map_err(|_| AppError::InternalServerError)?;
 
+        let guardrails: Guardrails =
+            serde_json::from_value(row.guardrails).map_err(|_| AppError::InternalServerError)?;
+
         Ok(TaskPack {
             id: row.id,
             task_id: row.task_id,
@@ -102,9 +145,110 @@ impl TryFrom<TaskPackRow> for TaskPack {
             do_not_list,
             commit_plan,
             run_instructions,
+            guardrails,
             markdown_content: row.markdown_content,
             json_content: row.json_content,
             created_at: row.created_at,
         })
     }
 }
+
+#[derive(Debug, FromRow)]
+pub struct TestScaffoldRow {
+    pub id: Uuid,
+    pub story_id: Uuid,
+    pub plan_pack_id: Option<Uuid>,
+    pub format: String,
+    pub version: i32,
+    pub file_name: String,
+    pub content: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<TestScaffoldRow> for TestScaffold {
+    type Error = AppError;
+
+    fn try_from(row: TestScaffoldRow) -> Result<Self, Self::Error> {
+        Ok(TestScaffold {
+            id: row.id,
+            story_id: row.story_id,
+            plan_pack_id: row.plan_pack_id,
+            format: row
+                .format
+                .parse()
+                .map_err(|_| AppError::InternalServerError)?,
+            version: row.version,
+            file_name: row.file_name,
+            content: row.content,
+            created_at: row.created_at,
+        })
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct SprintProjectionRow {
+    pub id: Uuid,
+    pub name: String,
+    pub goal: Option<String>,
+    pub status: String,
+    pub start_date: Option<chrono::DateTime<chrono::Utc>>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
+    pub capacity_points: Option<i32>,
+    pub committed_points: Option<i32>,
+    pub completed_points: Option<i32>,
+}
+
+#[derive(Debug, FromRow)]
+pub struct SprintStoryProjectionRow {
+    pub story_id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub story_points: Option<i32>,
+}
+
+impl From<SprintStoryProjectionRow> for SprintContextStory {
+    fn from(row: SprintStoryProjectionRow) -> Self {
+        Self {
+            story_id: row.story_id,
+            title: row.title,
+            status: row.status,
+            story_points: row.story_points.map(|points| points.max(0) as u32),
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct ProjectGuardrailsRow {
+    pub project_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub architecture_rules: Vec<String>,
+    pub forbidden_dependencies: Vec<String>,
+    pub coding_standards: Vec<String>,
+    pub required_categories: Vec<String>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<ProjectGuardrailsRow> for ProjectGuardrails {
+    type Error = AppError;
+
+    fn try_from(row: ProjectGuardrailsRow) -> Result<Self, Self::Error> {
+        let required_categories = row
+            .required_categories
+            .iter()
+            .map(|category| category.parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(ProjectGuardrails {
+            project_id: row.project_id,
+            organization_id: row.organization_id,
+            guardrails: Guardrails {
+                architecture_rules: row.architecture_rules,
+                forbidden_dependencies: row.forbidden_dependencies,
+                coding_standards: row.coding_standards,
+            },
+            required_categories,
+            updated_at: row.updated_at,
+        })
+    }
+}
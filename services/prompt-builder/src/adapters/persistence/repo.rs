@@ -1,12 +1,26 @@
-use crate::adapters::persistence::models::{PlanPackRow, TaskPackRow};
-use crate::application::ports::{PlanPackRepository, TaskPackRepository};
-use crate::domain::{PlanPack, TaskPack};
+use crate::adapters::persistence::models::{
+    PlanPackRow, PlanPackSettingsRow, ProjectGuardrailsRow, SprintProjectionRow,
+    SprintStoryProjectionRow, TaskPackRow, TestScaffoldRow,
+};
+use crate::application::ports::{
+    BlobStore, GuardrailRepository, PlanPackRepository, PlanPackSettingsRepository,
+    SprintContextRepository, TaskPackRepository, TestScaffoldRepository,
+};
+use crate::domain::{
+    PlanPack, PlanPackSettings, ProjectGuardrails, SprintContext, TaskPack, TestScaffold,
+};
 use async_trait::async_trait;
 use common::AppError;
+use serde::{Deserialize, Serialize};
 use serde_json;
 use sqlx::PgPool;
+use std::collections::HashSet;
+use std::sync::Arc;
 use uuid::Uuid;
 
+/// Task packs whose markdown and JSON renderings exceed this are offloaded
+pub const DEFAULT_OFFLOAD_THRESHOLD_BYTES: usize = 256 * 1024;
+
 pub async fn save_plan_pack(pool: &PgPool, plan_pack: &PlanPack) -> Result<(), AppError> {
     let ac_map_json = serde_json::to_value(&plan_pack.acceptance_criteria_map)
         .map_err(|_| AppError::InternalServerError)?;
@@ -14,16 +28,27 @@ pub async fn save_plan_pack(pool: &PgPool, plan_pack: &PlanPack) -> Result<(), A
     let tasks_json = serde_json::to_value(&plan_pack.proposed_tasks)
         .map_err(|_| AppError::InternalServerError)?;
 
+    let guardrails_json =
+        serde_json::to_value(&plan_pack.guardrails).map_err(|_| AppError::InternalServerError)?;
+
     sqlx::query(
         "INSERT INTO plan_packs (id, story_id, acceptance_criteria_map, proposed_tasks, \
-         architecture_impact, risks, unknowns, created_at) \
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+         architecture_impact, risks, unknowns, guardrails, status, approved_by, approved_at, \
+         story_description, stale_reason, stale_at, created_at) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15) \
          ON CONFLICT (story_id) DO UPDATE SET \
          acceptance_criteria_map = EXCLUDED.acceptance_criteria_map, \
          proposed_tasks = EXCLUDED.proposed_tasks, \
          architecture_impact = EXCLUDED.architecture_impact, \
          risks = EXCLUDED.risks, \
-         unknowns = EXCLUDED.unknowns",
+         unknowns = EXCLUDED.unknowns, \
+         guardrails = EXCLUDED.guardrails, \
+         status = EXCLUDED.status, \
+         approved_by = EXCLUDED.approved_by, \
+         approved_at = EXCLUDED.approved_at, \
+         story_description = EXCLUDED.story_description, \
+         stale_reason = EXCLUDED.stale_reason, \
+         stale_at = EXCLUDED.stale_at",
     )
     .bind(plan_pack.id)
     .bind(plan_pack.story_id)
@@ -32,6 +57,13 @@ pub async fn save_plan_pack(pool: &PgPool, plan_pack: &PlanPack) -> Result<(), A
     .bind(&plan_pack.architecture_impact)
     .bind(&plan_pack.risks)
     .bind(&plan_pack.unknowns)
+    .bind(guardrails_json)
+    .bind(plan_pack.status.as_str())
+    .bind(&plan_pack.approved_by)
+    .bind(plan_pack.approved_at)
+    .bind(&plan_pack.story_description)
+    .bind(&plan_pack.stale_reason)
+    .bind(plan_pack.stale_at)
     .bind(plan_pack.created_at)
     .execute(pool)
     .await
@@ -43,7 +75,8 @@ pub async fn save_plan_pack(pool: &PgPool, plan_pack: &PlanPack) -> Result<(), A
 pub async fn get_plan_pack(pool: &PgPool, id: Uuid) -> Result<Option<PlanPack>, AppError> {
     let row = sqlx::query_as::<_, PlanPackRow>(
         "SELECT id, story_id, acceptance_criteria_map, proposed_tasks, \
-         architecture_impact, risks, unknowns, created_at \
+         architecture_impact, risks, unknowns, guardrails, status, approved_by, approved_at, \
+         story_description, stale_reason, stale_at, created_at \
          FROM plan_packs WHERE id = $1",
     )
     .bind(id)
@@ -63,7 +96,8 @@ pub async fn get_plan_pack_by_story(
 ) -> Result<Option<PlanPack>, AppError> {
     let row = sqlx::query_as::<_, PlanPackRow>(
         "SELECT id, story_id, acceptance_criteria_map, proposed_tasks, \
-         architecture_impact, risks, unknowns, created_at \
+         architecture_impact, risks, unknowns, guardrails, status, approved_by, approved_at, \
+         story_description, stale_reason, stale_at, created_at \
          FROM plan_packs WHERE story_id = $1",
     )
     .bind(story_id)
@@ -87,7 +121,56 @@ pub async fn delete_plan_pack(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
     Ok(())
 }
 
-pub async fn save_task_pack(pool: &PgPool, task_pack: &TaskPack) -> Result<(), AppError> {
+pub async fn get_plan_pack_settings(
+    pool: &PgPool,
+    organization_id: Uuid,
+) -> Result<Option<PlanPackSettings>, AppError> {
+    let row = sqlx::query_as::<_, PlanPackSettingsRow>(
+        "SELECT organization_id, auto_regenerate_stale, updated_at \
+         FROM plan_pack_settings WHERE organization_id = $1",
+    )
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching plan pack settings");
+        AppError::InternalServerError
+    })?;
+
+    Ok(row.map(Into::into))
+}
+
+pub async fn save_plan_pack_settings(
+    pool: &PgPool,
+    settings: &PlanPackSettings,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO plan_pack_settings (organization_id, auto_regenerate_stale, updated_at) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (organization_id) DO UPDATE SET \
+         auto_regenerate_stale = EXCLUDED.auto_regenerate_stale, \
+         updated_at = EXCLUDED.updated_at",
+    )
+    .bind(settings.organization_id)
+    .bind(settings.auto_regenerate_stale)
+    .bind(settings.updated_at)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error saving plan pack settings");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+/// Save the pack, with its markdown and JSON renderings inline unless
+/// `content_key` names the blob holding them
+pub async fn save_task_pack(
+    pool: &PgPool,
+    task_pack: &TaskPack,
+    content_key: Option<&str>,
+) -> Result<(), AppError> {
     let non_goals_json =
         serde_json::to_value(&task_pack.non_goals).map_err(|_| AppError::InternalServerError)?;
 
@@ -109,11 +192,15 @@ pub async fn save_task_pack(pool: &PgPool, task_pack: &TaskPack) -> Result<(), A
     let run_instructions_json = serde_json::to_value(&task_pack.run_instructions)
         .map_err(|_| AppError::InternalServerError)?;
 
+    let guardrails_json =
+        serde_json::to_value(&task_pack.guardrails).map_err(|_| AppError::InternalServerError)?;
+
     sqlx::query(
         "INSERT INTO task_packs (id, task_id, plan_pack_id, objectives, non_goals, \
          story_context, acceptance_criteria_covered, constraints, test_plan, do_not_list, \
-         commit_plan, run_instructions, markdown_content, json_content, created_at) \
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15) \
+         commit_plan, run_instructions, guardrails, markdown_content, json_content, content_key, \
+         created_at) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17) \
          ON CONFLICT (task_id) DO UPDATE SET \
          plan_pack_id = EXCLUDED.plan_pack_id, \
          objectives = EXCLUDED.objectives, \
@@ -125,8 +212,10 @@ pub async fn save_task_pack(pool: &PgPool, task_pack: &TaskPack) -> Result<(), A
          do_not_list = EXCLUDED.do_not_list, \
          commit_plan = EXCLUDED.commit_plan, \
          run_instructions = EXCLUDED.run_instructions, \
+         guardrails = EXCLUDED.guardrails, \
          markdown_content = EXCLUDED.markdown_content, \
-         json_content = EXCLUDED.json_content",
+         json_content = EXCLUDED.json_content, \
+         content_key = EXCLUDED.content_key",
     )
     .bind(task_pack.id)
     .bind(task_pack.task_id)
@@ -140,8 +229,18 @@ pub async fn save_task_pack(pool: &PgPool, task_pack: &TaskPack) -> Result<(), A
     .bind(do_not_list_json)
     .bind(commit_plan_json)
     .bind(run_instructions_json)
-    .bind(&task_pack.markdown_content)
-    .bind(&task_pack.json_content)
+    .bind(guardrails_json)
+    .bind(if content_key.is_some() {
+        ""
+    } else {
+        task_pack.markdown_content.as_str()
+    })
+    .bind(if content_key.is_some() {
+        &serde_json::Value::Null
+    } else {
+        &task_pack.json_content
+    })
+    .bind(content_key)
     .bind(task_pack.created_at)
     .execute(pool)
     .await
@@ -150,55 +249,349 @@ pub async fn save_task_pack(pool: &PgPool, task_pack: &TaskPack) -> Result<(), A
     Ok(())
 }
 
-pub async fn get_task_pack(pool: &PgPool, id: Uuid) -> Result<Option<TaskPack>, AppError> {
-    let row = sqlx::query_as::<_, TaskPackRow>(
-        "SELECT id, task_id, plan_pack_id, objectives, non_goals, story_context, \
-         acceptance_criteria_covered, constraints, test_plan, do_not_list, commit_plan, \
-         run_instructions, markdown_content, json_content, created_at \
-         FROM task_packs WHERE id = $1",
-    )
+const TASK_PACK_COLUMNS: &str = "id, task_id, plan_pack_id, objectives, non_goals, story_context, \
+     acceptance_criteria_covered, constraints, test_plan, do_not_list, commit_plan, \
+     run_instructions, guardrails, markdown_content, json_content, content_key, created_at";
+
+/// The stored row; offloaded packs still need their content loaded from blob storage
+pub async fn get_task_pack(pool: &PgPool, id: Uuid) -> Result<Option<TaskPackRow>, AppError> {
+    sqlx::query_as::<_, TaskPackRow>(&format!(
+        "SELECT {} FROM task_packs WHERE id = $1",
+        TASK_PACK_COLUMNS
+    ))
     .bind(id)
     .fetch_optional(pool)
     .await
-    .map_err(|_| AppError::InternalServerError)?;
-
-    match row {
-        Some(row) => Ok(Some(row.try_into()?)),
-        None => Ok(None),
-    }
+    .map_err(|_| AppError::InternalServerError)
 }
 
 pub async fn get_task_pack_by_task(
     pool: &PgPool,
     task_id: Uuid,
-) -> Result<Option<TaskPack>, AppError> {
-    let row = sqlx::query_as::<_, TaskPackRow>(
-        "SELECT id, task_id, plan_pack_id, objectives, non_goals, story_context, \
-         acceptance_criteria_covered, constraints, test_plan, do_not_list, commit_plan, \
-         run_instructions, markdown_content, json_content, created_at \
-         FROM task_packs WHERE task_id = $1",
-    )
+) -> Result<Option<TaskPackRow>, AppError> {
+    sqlx::query_as::<_, TaskPackRow>(&format!(
+        "SELECT {} FROM task_packs WHERE task_id = $1",
+        TASK_PACK_COLUMNS
+    ))
     .bind(task_id)
     .fetch_optional(pool)
     .await
-    .map_err(|_| AppError::InternalServerError)?;
+    .map_err(|_| AppError::InternalServerError)
+}
 
-    match row {
-        Some(row) => Ok(Some(row.try_into()?)),
-        None => Ok(None),
-    }
+/// Delete the pack, returning the key of its offloaded content if it had any
+pub async fn delete_task_pack(pool: &PgPool, id: Uuid) -> Result<Option<String>, AppError> {
+    let content_key: Option<Option<String>> =
+        sqlx::query_scalar("DELETE FROM task_packs WHERE id = $1 RETURNING content_key")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+    Ok(content_key.flatten())
 }
 
-pub async fn delete_task_pack(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
-    sqlx::query("DELETE FROM task_packs WHERE id = $1")
-        .bind(id)
-        .execute(pool)
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
+/// Inline packs whose markdown and JSON renderings together exceed
+/// `min_bytes`, largest first
+pub async fn get_large_inline_task_pack_ids(
+    pool: &PgPool,
+    min_bytes: usize,
+    limit: i64,
+) -> Result<Vec<Uuid>, AppError> {
+    sqlx::query_scalar(
+        "SELECT id FROM task_packs \
+         WHERE content_key IS NULL \
+           AND octet_length(markdown_content) + octet_length(json_content::TEXT) > $1 \
+         ORDER BY octet_length(markdown_content) + octet_length(json_content::TEXT) DESC \
+         LIMIT $2",
+    )
+    .bind(min_bytes as i64)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error finding large task packs");
+        AppError::InternalServerError
+    })
+}
+
+/// How many inline packs exceed `min_bytes`, and their total size
+pub async fn count_large_inline_task_packs(
+    pool: &PgPool,
+    min_bytes: usize,
+) -> Result<(i64, i64), AppError> {
+    sqlx::query_as(
+        "SELECT COUNT(*), COALESCE(SUM(size), 0)::BIGINT FROM ( \
+             SELECT octet_length(markdown_content) + octet_length(json_content::TEXT) AS size \
+             FROM task_packs WHERE content_key IS NULL \
+         ) packs \
+         WHERE size > $1",
+    )
+    .bind(min_bytes as i64)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error counting large task packs");
+        AppError::InternalServerError
+    })
+}
+
+/// Point an inline pack at its offloaded content and clear the inline copy.
+/// Returns false when the pack was deleted or offloaded in the meantime.
+pub async fn mark_task_pack_offloaded(
+    pool: &PgPool,
+    id: Uuid,
+    content_key: &str,
+) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        "UPDATE task_packs SET markdown_content = '', json_content = 'null'::JSONB, \
+         content_key = $2 \
+         WHERE id = $1 AND content_key IS NULL",
+    )
+    .bind(id)
+    .bind(content_key)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error marking task pack offloaded");
+        AppError::InternalServerError
+    })?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+pub async fn get_project_guardrails(
+    pool: &PgPool,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Option<ProjectGuardrails>, AppError> {
+    let row = sqlx::query_as::<_, ProjectGuardrailsRow>(
+        "SELECT project_id, organization_id, architecture_rules, forbidden_dependencies, \
+         coding_standards, required_categories, updated_at \
+         FROM project_guardrails \
+         WHERE project_id = $1 AND organization_id IS NOT DISTINCT FROM $2",
+    )
+    .bind(project_id)
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching project guardrails");
+        AppError::InternalServerError
+    })?;
+
+    row.map(TryInto::try_into).transpose()
+}
+
+/// Returns false when the project's guardrails belong to another organization
+pub async fn save_project_guardrails(
+    pool: &PgPool,
+    guardrails: &ProjectGuardrails,
+) -> Result<bool, AppError> {
+    let required_categories: Vec<&str> = guardrails
+        .required_categories
+        .iter()
+        .map(|category| category.as_str())
+        .collect();
+
+    let result = sqlx::query(
+        "INSERT INTO project_guardrails (project_id, organization_id, architecture_rules, \
+         forbidden_dependencies, coding_standards, required_categories, updated_at) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7) \
+         ON CONFLICT (project_id) DO UPDATE SET \
+         architecture_rules = EXCLUDED.architecture_rules, \
+         forbidden_dependencies = EXCLUDED.forbidden_dependencies, \
+         coding_standards = EXCLUDED.coding_standards, \
+         required_categories = EXCLUDED.required_categories, \
+         updated_at = EXCLUDED.updated_at \
+         WHERE project_guardrails.organization_id IS NOT DISTINCT FROM EXCLUDED.organization_id",
+    )
+    .bind(guardrails.project_id)
+    .bind(guardrails.organization_id)
+    .bind(&guardrails.guardrails.architecture_rules)
+    .bind(&guardrails.guardrails.forbidden_dependencies)
+    .bind(&guardrails.guardrails.coding_standards)
+    .bind(&required_categories)
+    .bind(guardrails.updated_at)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error saving project guardrails");
+        AppError::InternalServerError
+    })?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+pub async fn save_test_scaffold(pool: &PgPool, scaffold: &TestScaffold) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO test_scaffolds (id, story_id, plan_pack_id, format, version, file_name, \
+         content, created_at) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+    )
+    .bind(scaffold.id)
+    .bind(scaffold.story_id)
+    .bind(scaffold.plan_pack_id)
+    .bind(scaffold.format.as_str())
+    .bind(scaffold.version)
+    .bind(&scaffold.file_name)
+    .bind(&scaffold.content)
+    .bind(scaffold.created_at)
+    .execute(pool)
+    .await
+    .map_err(|e| match e.as_database_error() {
+        Some(db_error) if db_error.is_unique_violation() => AppError::Conflict(format!(
+            "Version {} of the {} test scaffold was generated concurrently",
+            scaffold.version,
+            scaffold.format.as_str()
+        )),
+        _ => {
+            tracing::error!(error = %e, "SQL error saving test scaffold");
+            AppError::InternalServerError
+        }
+    })?;
 
     Ok(())
 }
 
+pub async fn get_test_scaffolds_by_story(
+    pool: &PgPool,
+    story_id: Uuid,
+) -> Result<Vec<TestScaffold>, AppError> {
+    let rows = sqlx::query_as::<_, TestScaffoldRow>(
+        "SELECT id, story_id, plan_pack_id, format, version, file_name, content, created_at \
+         FROM test_scaffolds WHERE story_id = $1 \
+         ORDER BY version DESC, format",
+    )
+    .bind(story_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error fetching test scaffolds");
+        AppError::InternalServerError
+    })?;
+
+    rows.into_iter().map(TryInto::try_into).collect()
+}
+
+/// The project's active sprint from the sprint projections, with its committed stories
+pub async fn get_current_sprint_context(
+    pool: &PgPool,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Option<SprintContext>, AppError> {
+    let sprint = sqlx::query_as::<_, SprintProjectionRow>(
+        "SELECT sp.id, sp.name, sp.goal, sp.status, sp.start_date, sp.end_date, \
+         sp.capacity_points, sp.committed_points, sp.completed_points \
+         FROM prompt_sprint_projections sp \
+         WHERE LOWER(sp.status) = 'active' \
+           AND (sp.organization_id = $2 OR ($2 IS NULL AND sp.organization_id IS NULL)) \
+           AND EXISTS ( \
+               SELECT 1 FROM prompt_sprint_story_projections st \
+               WHERE st.sprint_id = sp.id AND st.project_id = $1 \
+           ) \
+         ORDER BY sp.start_date DESC NULLS LAST \
+         LIMIT 1",
+    )
+    .bind(project_id)
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error reading current sprint projection");
+        AppError::InternalServerError
+    })?;
+
+    let Some(sprint) = sprint else {
+        return Ok(None);
+    };
+
+    let stories = sqlx::query_as::<_, SprintStoryProjectionRow>(
+        "SELECT story_id, title, status, story_points \
+         FROM prompt_sprint_story_projections \
+         WHERE sprint_id = $1 \
+         ORDER BY title",
+    )
+    .bind(sprint.id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "SQL error reading sprint story projections");
+        AppError::InternalServerError
+    })?;
+
+    let points = |value: Option<i32>| value.map(|points| points.max(0) as u32);
+    Ok(Some(SprintContext {
+        sprint_id: sprint.id,
+        name: sprint.name,
+        goal: sprint.goal,
+        status: sprint.status,
+        start_date: sprint.start_date,
+        end_date: sprint.end_date,
+        remaining_days: SprintContext::remaining_days(sprint.end_date, chrono::Utc::now()),
+        capacity_points: points(sprint.capacity_points),
+        committed_points: points(sprint.committed_points),
+        completed_points: points(sprint.completed_points),
+        committed_stories: stories.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub struct SqlSprintContextRepository {
+    pool: PgPool,
+}
+
+impl SqlSprintContextRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SprintContextRepository for SqlSprintContextRepository {
+    async fn get_current_sprint_context(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<SprintContext>, AppError> {
+        get_current_sprint_context(&self.pool, project_id, organization_id).await
+    }
+}
+
+pub struct SqlGuardrailRepository {
+    pool: PgPool,
+}
+
+impl SqlGuardrailRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GuardrailRepository for SqlGuardrailRepository {
+    async fn get_project_guardrails(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<ProjectGuardrails>, AppError> {
+        get_project_guardrails(&self.pool, project_id, organization_id).await
+    }
+
+    async fn save_project_guardrails(
+        &self,
+        guardrails: &ProjectGuardrails,
+    ) -> Result<(), AppError> {
+        if save_project_guardrails(&self.pool, guardrails).await? {
+            Ok(())
+        } else {
+            Err(AppError::NotFound(format!(
+                "Project {} not found",
+                guardrails.project_id
+            )))
+        }
+    }
+}
+
 pub struct SqlPlanPackRepository {
     pool: PgPool,
 }
@@ -228,31 +621,218 @@ impl PlanPackRepository for SqlPlanPackRepository {
     }
 }
 
+pub struct SqlPlanPackSettingsRepository {
+    pool: PgPool,
+}
+
+impl SqlPlanPackSettingsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PlanPackSettingsRepository for SqlPlanPackSettingsRepository {
+    async fn get_plan_pack_settings(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Option<PlanPackSettings>, AppError> {
+        get_plan_pack_settings(&self.pool, organization_id).await
+    }
+
+    async fn save_plan_pack_settings(&self, settings: &PlanPackSettings) -> Result<(), AppError> {
+        save_plan_pack_settings(&self.pool, settings).await
+    }
+}
+
+/// Task packs over the offload threshold keep their markdown and JSON
+/// renderings in the blob store when one is configured
 pub struct SqlTaskPackRepository {
     pool: PgPool,
+    blob_store: Option<Arc<dyn BlobStore>>,
+    offload_threshold_bytes: usize,
+}
+
+/// What an offloaded pack's blob holds
+#[derive(Serialize, Deserialize)]
+struct TaskPackContent {
+    markdown: String,
+    json: serde_json::Value,
+}
+
+/// How many packs `offload_large_task_packs` moved to the blob store
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OffloadSummary {
+    pub packs: usize,
+    pub bytes: usize,
 }
 
 impl SqlTaskPackRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            blob_store: None,
+            offload_threshold_bytes: DEFAULT_OFFLOAD_THRESHOLD_BYTES,
+        }
+    }
+
+    pub fn with_blob_store(
+        mut self,
+        blob_store: Arc<dyn BlobStore>,
+        offload_threshold_bytes: usize,
+    ) -> Self {
+        self.blob_store = Some(blob_store);
+        self.offload_threshold_bytes = offload_threshold_bytes;
+        self
+    }
+
+    fn content_key(id: Uuid) -> String {
+        format!("task-packs/{}.json", id)
+    }
+
+    fn blob_store(&self) -> Result<&Arc<dyn BlobStore>, AppError> {
+        self.blob_store.as_ref().ok_or_else(|| {
+            tracing::error!("Task pack content is offloaded but no blob store is configured");
+            AppError::InternalServerError
+        })
+    }
+
+    /// The pack, with offloaded content fetched from the blob store
+    async fn load(&self, row: Option<TaskPackRow>) -> Result<Option<TaskPack>, AppError> {
+        let Some(mut row) = row else {
+            return Ok(None);
+        };
+        if let Some(key) = row.content_key.take() {
+            let bytes = self.blob_store()?.get(&key).await?;
+            let content: TaskPackContent = serde_json::from_slice(&bytes).map_err(|e| {
+                tracing::error!(error = %e, key, "Unreadable task pack blob");
+                AppError::InternalServerError
+            })?;
+            row.markdown_content = content.markdown;
+            row.json_content = content.json;
+        }
+        Ok(Some(row.try_into()?))
+    }
+
+    /// Move the content of inline packs over the threshold to the blob store,
+    /// `batch_size` packs at a time. With `dry_run` nothing is moved and the
+    /// summary reports what would be.
+    pub async fn offload_large_task_packs(
+        &self,
+        batch_size: i64,
+        dry_run: bool,
+    ) -> Result<OffloadSummary, AppError> {
+        if dry_run {
+            let (packs, bytes) =
+                count_large_inline_task_packs(&self.pool, self.offload_threshold_bytes).await?;
+            return Ok(OffloadSummary {
+                packs: packs as usize,
+                bytes: bytes as usize,
+            });
+        }
+
+        let blob_store = self.blob_store()?;
+        let mut summary = OffloadSummary::default();
+        // Packs regenerated while uploading are left for the next run
+        let mut skipped = HashSet::new();
+        loop {
+            let ids: Vec<Uuid> = get_large_inline_task_pack_ids(
+                &self.pool,
+                self.offload_threshold_bytes,
+                batch_size + skipped.len() as i64,
+            )
+            .await?
+            .into_iter()
+            .filter(|id| !skipped.contains(id))
+            .collect();
+            if ids.is_empty() {
+                return Ok(summary);
+            }
+
+            for id in ids {
+                let Some(row) = get_task_pack(&self.pool, id).await? else {
+                    continue;
+                };
+                let content = serde_json::to_vec(&TaskPackContent {
+                    markdown: row.markdown_content,
+                    json: row.json_content,
+                })
+                .map_err(|_| AppError::InternalServerError)?;
+                let bytes = content.len();
+
+                let key = Self::content_key(id);
+                blob_store.put(&key, content).await?;
+                if mark_task_pack_offloaded(&self.pool, id, &key).await? {
+                    summary.packs += 1;
+                    summary.bytes += bytes;
+                } else {
+                    skipped.insert(id);
+                }
+            }
+        }
     }
 }
 
 #[async_trait]
 impl TaskPackRepository for SqlTaskPackRepository {
     async fn save_task_pack(&self, task_pack: &TaskPack) -> Result<(), AppError> {
-        save_task_pack(&self.pool, task_pack).await
+        let content = serde_json::to_vec(&TaskPackContent {
+            markdown: task_pack.markdown_content.clone(),
+            json: task_pack.json_content.clone(),
+        })
+        .map_err(|_| AppError::InternalServerError)?;
+
+        match &self.blob_store {
+            Some(blob_store) if content.len() > self.offload_threshold_bytes => {
+                let key = Self::content_key(task_pack.id);
+                blob_store.put(&key, content).await?;
+                save_task_pack(&self.pool, task_pack, Some(&key)).await
+            }
+            _ => save_task_pack(&self.pool, task_pack, None).await,
+        }
     }
 
     async fn get_task_pack(&self, id: Uuid) -> Result<Option<TaskPack>, AppError> {
-        get_task_pack(&self.pool, id).await
+        let row = get_task_pack(&self.pool, id).await?;
+        self.load(row).await
     }
 
     async fn get_task_pack_by_task(&self, task_id: Uuid) -> Result<Option<TaskPack>, AppError> {
-        get_task_pack_by_task(&self.pool, task_id).await
+        let row = get_task_pack_by_task(&self.pool, task_id).await?;
+        self.load(row).await
     }
 
     async fn delete_task_pack(&self, id: Uuid) -> Result<(), AppError> {
-        delete_task_pack(&self.pool, id).await
+        if let Some(key) = delete_task_pack(&self.pool, id).await? {
+            // The pack is gone either way; a leftover blob is only wasted space
+            if let Err(e) = self.blob_store()?.delete(&key).await {
+                tracing::warn!(error = %e, key, "Failed to delete task pack blob");
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct SqlTestScaffoldRepository {
+    pool: PgPool,
+}
+
+impl SqlTestScaffoldRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TestScaffoldRepository for SqlTestScaffoldRepository {
+    async fn save_test_scaffold(&self, scaffold: &TestScaffold) -> Result<(), AppError> {
+        save_test_scaffold(&self.pool, scaffold).await
+    }
+
+    async fn get_test_scaffolds_by_story(
+        &self,
+        story_id: Uuid,
+    ) -> Result<Vec<TestScaffold>, AppError> {
+        get_test_scaffolds_by_story(&self.pool, story_id).await
     }
 }
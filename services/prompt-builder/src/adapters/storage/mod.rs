@@ -0,0 +1,3 @@
+pub mod s3;
+
+pub use s3::*;
@@ -1,4 +1,7 @@
-use crate::domain::{PlanPack, TaskPack};
+use crate::domain::{
+    AcceptanceCriterionInfo, PlanPack, PlanPackSettings, ProjectGuardrails, SprintContext,
+    TaskPack, TestScaffold, TestScaffoldFormat,
+};
 use async_trait::async_trait;
 use common::AppError;
 use serde::{Deserialize, Serialize};
@@ -13,6 +16,16 @@ pub trait PlanPackRepository: Send + Sync {
     async fn delete_plan_pack(&self, id: Uuid) -> Result<(), AppError>;
 }
 
+/// Organization-level plan pack preferences
+#[async_trait]
+pub trait PlanPackSettingsRepository: Send + Sync {
+    async fn get_plan_pack_settings(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Option<PlanPackSettings>, AppError>;
+    async fn save_plan_pack_settings(&self, settings: &PlanPackSettings) -> Result<(), AppError>;
+}
+
 #[async_trait]
 pub trait TaskPackRepository: Send + Sync {
     async fn save_task_pack(&self, task_pack: &TaskPack) -> Result<(), AppError>;
@@ -22,9 +35,55 @@ pub trait TaskPackRepository: Send + Sync {
     async fn delete_task_pack(&self, id: Uuid) -> Result<(), AppError>;
 }
 
+/// Object storage for pack content too large to keep in Postgres
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    async fn put(&self, key: &str, content: Vec<u8>) -> Result<(), AppError>;
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError>;
+    /// Succeeds when the blob is already gone
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+#[async_trait]
+pub trait TestScaffoldRepository: Send + Sync {
+    /// Fails with a conflict when the story already has this version in this format
+    async fn save_test_scaffold(&self, scaffold: &TestScaffold) -> Result<(), AppError>;
+    /// Every version generated for the story, newest first
+    async fn get_test_scaffolds_by_story(
+        &self,
+        story_id: Uuid,
+    ) -> Result<Vec<TestScaffold>, AppError>;
+}
+
+/// Reads the sprint projections this service maintains from sprint and story events
+#[async_trait]
+pub trait SprintContextRepository: Send + Sync {
+    /// The project's active sprint, if any of its stories are committed to one
+    async fn get_current_sprint_context(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<SprintContext>, AppError>;
+}
+
+/// Per-project guardrail configuration, scoped to the owning organization
+#[async_trait]
+pub trait GuardrailRepository: Send + Sync {
+    async fn get_project_guardrails(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<ProjectGuardrails>, AppError>;
+    /// Fails with not found when the project's guardrails belong to another organization
+    async fn save_project_guardrails(&self, guardrails: &ProjectGuardrails)
+        -> Result<(), AppError>;
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoryInfo {
     pub id: Uuid,
+    pub project_id: Uuid,
+    pub organization_id: Option<Uuid>,
     pub title: String,
     pub description: Option<String>,
     pub status: String,
@@ -47,10 +106,31 @@ pub struct AcceptanceCriterion {
     pub then: String,
 }
 
+impl AcceptanceCriterion {
+    pub fn to_info(&self, position: u32) -> AcceptanceCriterionInfo {
+        AcceptanceCriterionInfo {
+            ac_id: self.ac_id.clone(),
+            given: self.given.clone(),
+            when: self.when.clone(),
+            then: self.then.clone(),
+            position,
+        }
+    }
+}
+
 #[async_trait]
 pub trait BacklogService: Send + Sync {
-    async fn get_story_info(&self, story_id: Uuid) -> Result<Option<StoryInfo>, AppError>;
-    async fn get_task_info(&self, task_id: Uuid) -> Result<Option<TaskInfo>, AppError>;
+    /// `None` when the story does not exist or belongs to another organization
+    async fn get_story_info(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<StoryInfo>, AppError>;
+    async fn get_task_info(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<TaskInfo>, AppError>;
 }
 
 #[async_trait]
@@ -58,8 +138,48 @@ pub trait ReadinessService: Send + Sync {
     async fn get_acceptance_criteria(
         &self,
         story_id: Uuid,
+        organization_id: Option<Uuid>,
     ) -> Result<Vec<AcceptanceCriterion>, AppError>;
-    async fn evaluate_readiness(&self, story_id: Uuid) -> Result<ReadinessEvaluation, AppError>;
+    async fn evaluate_readiness(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<ReadinessEvaluation, AppError>;
+    /// The organization's glossary terms that appear in `text`
+    async fn get_glossary_terms(
+        &self,
+        organization_id: Option<Uuid>,
+        text: &str,
+    ) -> Result<Vec<GlossaryTerm>, AppError>;
+    /// Test files in `repository` that likely cover each of the story's criteria
+    async fn get_covering_tests(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        repository: &SourceRepository,
+    ) -> Result<Vec<CoveringTests>, AppError>;
+}
+
+/// A GitHub repository, as `owner/name`, and the branch, tag or commit to read
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceRepository {
+    pub repository: String,
+    pub git_ref: Option<String>,
+}
+
+/// Existing test files that likely cover an acceptance criterion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoveringTests {
+    pub ac_id: String,
+    pub test_files: Vec<String>,
+}
+
+/// A term from the organization's glossary, with the meaning the team gives it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GlossaryTerm {
+    pub term: String,
+    pub definition: String,
+    pub synonyms: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,13 +194,26 @@ pub trait LlmService: Send + Sync {
         &self,
         story: &StoryInfo,
         criteria: &[AcceptanceCriterion],
+        sprint: Option<&SprintContext>,
+        glossary: &[GlossaryTerm],
     ) -> Result<PlanPackGeneration, AppError>;
     async fn generate_task_pack(
         &self,
         task: &TaskInfo,
         story: &StoryInfo,
         criteria: &[AcceptanceCriterion],
+        glossary: &[GlossaryTerm],
     ) -> Result<TaskPackGeneration, AppError>;
+    /// The content of a test file with a scenario or test per criterion, named
+    /// by its AC ID. `existing_tests` are test files that may already cover
+    /// some criteria.
+    async fn generate_test_scaffold(
+        &self,
+        story: &StoryInfo,
+        criteria: &[AcceptanceCriterion],
+        format: TestScaffoldFormat,
+        existing_tests: &[CoveringTests],
+    ) -> Result<String, AppError>;
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
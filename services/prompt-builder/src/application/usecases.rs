@@ -1,11 +1,15 @@
 use crate::application::ports::{
-    AcceptanceCriterion, BacklogService, LlmService, PlanPackRepository, ReadinessService,
-    TaskPackRepository,
+    AcceptanceCriterion, BacklogService, GlossaryTerm, GuardrailRepository, LlmService,
+    PlanPackRepository, PlanPackSettingsRepository, ReadinessService, SourceRepository,
+    SprintContextRepository, StoryInfo, TaskInfo, TaskPackRepository, TestScaffoldRepository,
 };
 use crate::domain::{
     AcceptanceCriteriaMap, AcceptanceCriterionCoverage, AcceptanceCriterionInfo, CommitPlan,
-    DoNotList, PlanPack, ProposedTask, TaskConstraints, TaskPack, TestPlan,
+    DoNotList, GuardrailCategory, Guardrails, PlanPack, PlanPackSettings, ProjectGuardrails,
+    ProposedTask, SprintContext, TaskConstraints, TaskPack, TestPlan, TestScaffold,
+    TestScaffoldFormat,
 };
+use common::quota::{QuotaGuard, UnlimitedQuotaGuard};
 use common::AppError;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -14,15 +18,21 @@ use uuid::Uuid;
 pub struct PromptBuilderUsecases {
     plan_pack_repo: Arc<dyn PlanPackRepository>,
     task_pack_repo: Arc<dyn TaskPackRepository>,
+    test_scaffold_repo: Arc<dyn TestScaffoldRepository>,
     backlog_service: Arc<dyn BacklogService>,
     readiness_service: Arc<dyn ReadinessService>,
     llm_service: Arc<dyn LlmService>,
+    sprint_context_repo: Option<Arc<dyn SprintContextRepository>>,
+    guardrail_repo: Option<Arc<dyn GuardrailRepository>>,
+    plan_pack_settings_repo: Option<Arc<dyn PlanPackSettingsRepository>>,
+    quota_guard: Arc<dyn QuotaGuard>,
 }
 
 impl PromptBuilderUsecases {
     pub fn new(
         plan_pack_repo: Arc<dyn PlanPackRepository>,
         task_pack_repo: Arc<dyn TaskPackRepository>,
+        test_scaffold_repo: Arc<dyn TestScaffoldRepository>,
         backlog_service: Arc<dyn BacklogService>,
         readiness_service: Arc<dyn ReadinessService>,
         llm_service: Arc<dyn LlmService>,
@@ -30,20 +40,245 @@ impl PromptBuilderUsecases {
         Self {
             plan_pack_repo,
             task_pack_repo,
+            test_scaffold_repo,
             backlog_service,
             readiness_service,
             llm_service,
+            sprint_context_repo: None,
+            guardrail_repo: None,
+            plan_pack_settings_repo: None,
+            quota_guard: Arc::new(UnlimitedQuotaGuard),
         }
     }
 
-    pub async fn generate_plan_pack(&self, story_id: Uuid) -> Result<PlanPack, AppError> {
+    /// Count plan pack, task pack and test scaffold generation against the
+    /// organization's LLM calls
+    pub fn with_quota_guard(mut self, quota_guard: Arc<dyn QuotaGuard>) -> Self {
+        self.quota_guard = quota_guard;
+        self
+    }
+
+    /// Give plan pack generation the current sprint's goal, remaining days and committed stories
+    pub fn with_sprint_context_repository(
+        mut self,
+        sprint_context_repo: Arc<dyn SprintContextRepository>,
+    ) -> Self {
+        self.sprint_context_repo = Some(sprint_context_repo);
+        self
+    }
+
+    /// The sprint context plan pack generation sees for a project
+    pub async fn get_sprint_context(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<SprintContext>, AppError> {
+        match &self.sprint_context_repo {
+            Some(repo) => {
+                repo.get_current_sprint_context(project_id, organization_id)
+                    .await
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Inject each project's configured guardrails into its plan and task packs
+    pub fn with_guardrail_repository(
+        mut self,
+        guardrail_repo: Arc<dyn GuardrailRepository>,
+    ) -> Self {
+        self.guardrail_repo = Some(guardrail_repo);
+        self
+    }
+
+    pub async fn get_project_guardrails(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<ProjectGuardrails>, AppError> {
+        match &self.guardrail_repo {
+            Some(repo) => {
+                repo.get_project_guardrails(project_id, organization_id)
+                    .await
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Replace the project's guardrails. Packs generated from now on carry them.
+    pub async fn set_project_guardrails(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        guardrails: Guardrails,
+        required_categories: Vec<GuardrailCategory>,
+    ) -> Result<ProjectGuardrails, AppError> {
+        let repo = self.guardrail_repo.as_ref().ok_or_else(|| {
+            tracing::error!("Guardrails are updated but no guardrail repository is configured");
+            AppError::InternalServerError
+        })?;
+        let guardrails =
+            ProjectGuardrails::new(project_id, organization_id, guardrails, required_categories)?;
+        repo.save_project_guardrails(&guardrails).await?;
+        Ok(guardrails)
+    }
+
+    /// Let organizations opt into regenerating stale plan packs automatically
+    pub fn with_plan_pack_settings_repository(
+        mut self,
+        plan_pack_settings_repo: Arc<dyn PlanPackSettingsRepository>,
+    ) -> Self {
+        self.plan_pack_settings_repo = Some(plan_pack_settings_repo);
+        self
+    }
+
+    /// The organization's plan pack settings; organizations that never saved
+    /// any leave stale packs alone
+    pub async fn get_plan_pack_settings(
+        &self,
+        organization_id: Option<Uuid>,
+    ) -> Result<PlanPackSettings, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest("Plan pack settings belong to an organization".to_string())
+        })?;
+        let settings = match &self.plan_pack_settings_repo {
+            Some(repo) => repo.get_plan_pack_settings(organization_id).await?,
+            None => None,
+        };
+        Ok(settings.unwrap_or_else(|| PlanPackSettings::new(organization_id, false)))
+    }
+
+    pub async fn set_plan_pack_settings(
+        &self,
+        organization_id: Option<Uuid>,
+        auto_regenerate_stale: bool,
+    ) -> Result<PlanPackSettings, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest("Plan pack settings belong to an organization".to_string())
+        })?;
+        let repo = self.plan_pack_settings_repo.as_ref().ok_or_else(|| {
+            tracing::error!(
+                "Plan pack settings are updated but no settings repository is configured"
+            );
+            AppError::InternalServerError
+        })?;
+        let settings = PlanPackSettings::new(organization_id, auto_regenerate_stale);
+        repo.save_plan_pack_settings(&settings).await?;
+        Ok(settings)
+    }
+
+    /// Mark the story's plan pack stale when the story's description or
+    /// acceptance criteria no longer match it, and regenerate it when the
+    /// organization asks for that. Returns the pack when it changed.
+    pub async fn invalidate_plan_pack(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        description: Option<&str>,
+        criteria: &[AcceptanceCriterionInfo],
+    ) -> Result<Option<PlanPack>, AppError> {
+        let Some(mut plan_pack) = self.plan_pack_repo.get_plan_pack_by_story(story_id).await?
+        else {
+            return Ok(None);
+        };
+        let Some(reason) = plan_pack.staleness(description, criteria) else {
+            return Ok(None);
+        };
+        if plan_pack.stale_reason.as_deref() == Some(reason.as_str()) {
+            return Ok(None);
+        }
+
+        tracing::info!(%story_id, plan_pack_id = %plan_pack.id, %reason, "Plan pack is stale");
+        plan_pack.mark_stale(reason);
+        self.plan_pack_repo.save_plan_pack(&plan_pack).await?;
+
+        let auto_regenerate = match organization_id {
+            Some(_) => {
+                self.get_plan_pack_settings(organization_id)
+                    .await?
+                    .auto_regenerate_stale
+            }
+            None => false,
+        };
+        if !auto_regenerate {
+            return Ok(Some(plan_pack));
+        }
+
+        // A failed regeneration leaves the stale pack for someone to regenerate by hand
+        match self.regenerate_plan_pack(story_id, organization_id).await {
+            Ok(regenerated) => Ok(Some(regenerated)),
+            Err(e) => {
+                tracing::warn!(error = %e, %story_id, "Stale plan pack could not be regenerated");
+                Ok(Some(plan_pack))
+            }
+        }
+    }
+
+    /// The guardrails section for the story's packs; generation fails while
+    /// the project is missing a required category
+    async fn pack_guardrails(&self, story: &StoryInfo) -> Result<Guardrails, AppError> {
+        Ok(self
+            .get_project_guardrails(story.project_id, story.organization_id)
+            .await?
+            .map(|guardrails| guardrails.section())
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    /// The organization's glossary terms used in `text`. The glossary sharpens
+    /// generated packs but is not required for them.
+    async fn glossary_terms(&self, organization_id: Option<Uuid>, text: &str) -> Vec<GlossaryTerm> {
+        self.readiness_service
+            .get_glossary_terms(organization_id, text)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Glossary unavailable for pack generation");
+                Vec::new()
+            })
+    }
+
+    /// The story, unless it does not exist or belongs to another organization
+    async fn visible_story(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<StoryInfo, AppError> {
+        self.backlog_service
+            .get_story_info(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Story {} not found", story_id)))
+    }
+
+    /// The task, unless it does not exist or belongs to another organization
+    async fn visible_task(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<TaskInfo, AppError> {
+        self.backlog_service
+            .get_task_info(task_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Task {} not found", task_id)))
+    }
+
+    pub async fn generate_plan_pack(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<PlanPack, AppError> {
+        // Get story information, which also keeps other organizations' packs out of reach
+        let story = self.visible_story(story_id, organization_id).await?;
+
         // Check if Plan Pack already exists (idempotency)
         if let Some(existing) = self.plan_pack_repo.get_plan_pack_by_story(story_id).await? {
             return Ok(existing);
         }
 
         // Verify story readiness
-        let readiness_eval = self.readiness_service.evaluate_readiness(story_id).await?;
+        let readiness_eval = self
+            .readiness_service
+            .evaluate_readiness(story_id, organization_id)
+            .await?;
         if !readiness_eval.missing_items.is_empty() {
             return Err(AppError::BadRequest(format!(
                 "Story is not ready for Plan Pack generation. Missing: {}",
@@ -51,17 +286,10 @@ impl PromptBuilderUsecases {
             )));
         }
 
-        // Get story information
-        let story = self
-            .backlog_service
-            .get_story_info(story_id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Story {} not found", story_id)))?;
-
         // Get acceptance criteria
         let criteria = self
             .readiness_service
-            .get_acceptance_criteria(story_id)
+            .get_acceptance_criteria(story_id, organization_id)
             .await?;
         if criteria.is_empty() {
             return Err(AppError::BadRequest(
@@ -69,15 +297,40 @@ impl PromptBuilderUsecases {
             ));
         }
 
+        let guardrails = self.pack_guardrails(&story).await?;
+
+        // Sprint context sharpens the plan but is not required for one
+        let sprint = self
+            .get_sprint_context(story.project_id, story.organization_id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, %story_id, "Sprint context unavailable for plan pack");
+                None
+            });
+
+        let glossary = self
+            .glossary_terms(
+                organization_id,
+                &format!(
+                    "{} {}",
+                    story.title,
+                    story.description.as_deref().unwrap_or("")
+                ),
+            )
+            .await;
+
         // Generate Plan Pack using LLM
+        self.quota_guard
+            .consume_llm_call(story.organization_id)
+            .await?;
         let generation = self
             .llm_service
-            .generate_plan_pack(&story, &criteria)
+            .generate_plan_pack(&story, &criteria, sprint.as_ref(), &glossary)
             .await?;
 
         // Build acceptance criteria map
         let mut criteria_map = HashMap::new();
-        for ac in &criteria {
+        for (position, ac) in criteria.iter().enumerate() {
             criteria_map.insert(
                 ac.ac_id.clone(),
                 AcceptanceCriterionInfo {
@@ -85,6 +338,7 @@ impl PromptBuilderUsecases {
                     given: ac.given.clone(),
                     when: ac.when.clone(),
                     then: ac.then.clone(),
+                    position: position as u32,
                 },
             );
         }
@@ -114,7 +368,9 @@ impl PromptBuilderUsecases {
             generation.architecture_impact,
             generation.risks,
             generation.unknowns,
-        )?;
+        )?
+        .with_guardrails(guardrails)
+        .with_story_description(story.description.as_deref());
 
         // Save Plan Pack
         self.plan_pack_repo.save_plan_pack(&plan_pack).await?;
@@ -122,34 +378,56 @@ impl PromptBuilderUsecases {
         Ok(plan_pack)
     }
 
-    pub async fn get_plan_pack(&self, story_id: Uuid) -> Result<Option<PlanPack>, AppError> {
+    pub async fn get_plan_pack(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<PlanPack>, AppError> {
+        self.visible_story(story_id, organization_id).await?;
         self.plan_pack_repo.get_plan_pack_by_story(story_id).await
     }
 
-    pub async fn generate_task_pack(&self, task_id: Uuid) -> Result<TaskPack, AppError> {
+    /// Approve the story's plan pack so task packs can be generated from it
+    pub async fn approve_plan_pack(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        approved_by: &str,
+    ) -> Result<PlanPack, AppError> {
+        let mut plan_pack = self
+            .get_plan_pack(story_id, organization_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Plan Pack for story {} not found", story_id))
+            })?;
+        plan_pack.approve(approved_by);
+        self.plan_pack_repo.save_plan_pack(&plan_pack).await?;
+        Ok(plan_pack)
+    }
+
+    /// Generate the task's pack. Unless `force` is set, the story's plan pack
+    /// has to be approved first.
+    pub async fn generate_task_pack(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+        force: bool,
+    ) -> Result<TaskPack, AppError> {
+        // Get task information, which also keeps other organizations' packs out of reach
+        let task = self.visible_task(task_id, organization_id).await?;
+
         // Check if Task Pack already exists (idempotency)
         if let Some(existing) = self.task_pack_repo.get_task_pack_by_task(task_id).await? {
             return Ok(existing);
         }
 
-        // Get task information
-        let task = self
-            .backlog_service
-            .get_task_info(task_id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Task {} not found", task_id)))?;
-
         // Get story information
-        let story = self
-            .backlog_service
-            .get_story_info(task.story_id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Story {} not found", task.story_id)))?;
+        let story = self.visible_story(task.story_id, organization_id).await?;
 
         // Get acceptance criteria for the story
         let all_criteria = self
             .readiness_service
-            .get_acceptance_criteria(task.story_id)
+            .get_acceptance_criteria(task.story_id, organization_id)
             .await?;
 
         // Filter criteria that this task covers
@@ -164,17 +442,38 @@ impl PromptBuilderUsecases {
             ));
         }
 
+        let guardrails = self.pack_guardrails(&story).await?;
+
         // Get associated Plan Pack (if exists)
-        let plan_pack_id = self
+        let plan_pack = self
             .plan_pack_repo
             .get_plan_pack_by_story(task.story_id)
-            .await?
-            .map(|pp| pp.id);
+            .await?;
+        if !force {
+            PlanPack::ensure_approved(plan_pack.as_ref())?;
+        }
+        let plan_pack_id = plan_pack.map(|pp| pp.id);
+
+        let glossary = self
+            .glossary_terms(
+                organization_id,
+                &format!(
+                    "{} {} {} {}",
+                    story.title,
+                    story.description.as_deref().unwrap_or(""),
+                    task.title,
+                    task.description.as_deref().unwrap_or("")
+                ),
+            )
+            .await;
 
         // Generate Task Pack using LLM
+        self.quota_guard
+            .consume_llm_call(story.organization_id)
+            .await?;
         let generation = self
             .llm_service
-            .generate_task_pack(&task, &story, &relevant_criteria)
+            .generate_task_pack(&task, &story, &relevant_criteria, &glossary)
             .await?;
 
         // Build task constraints
@@ -237,6 +536,7 @@ impl PromptBuilderUsecases {
             commit_plan,
             generation.run_instructions,
         )?
+        .with_guardrails(guardrails)
         .with_generated_content()?;
 
         // Save Task Pack
@@ -245,28 +545,152 @@ impl PromptBuilderUsecases {
         Ok(task_pack)
     }
 
-    pub async fn get_task_pack(&self, task_id: Uuid) -> Result<Option<TaskPack>, AppError> {
+    pub async fn get_task_pack(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<TaskPack>, AppError> {
+        self.visible_task(task_id, organization_id).await?;
         self.task_pack_repo.get_task_pack_by_task(task_id).await
     }
 
-    pub async fn regenerate_plan_pack(&self, story_id: Uuid) -> Result<PlanPack, AppError> {
+    /// Generate the next version of the story's acceptance test file in
+    /// `format`. With a `repository`, the test files that already look like
+    /// they cover a criterion are pointed out to the generator.
+    pub async fn generate_test_scaffold(
+        &self,
+        story_id: Uuid,
+        format: TestScaffoldFormat,
+        repository: Option<&SourceRepository>,
+        organization_id: Option<Uuid>,
+    ) -> Result<TestScaffold, AppError> {
+        let story = self.visible_story(story_id, organization_id).await?;
+
+        let criteria = self
+            .readiness_service
+            .get_acceptance_criteria(story_id, organization_id)
+            .await?;
+        if criteria.is_empty() {
+            return Err(AppError::BadRequest(
+                "Story must have acceptance criteria before generating test scaffolds".to_string(),
+            ));
+        }
+
+        let existing_tests = match repository {
+            Some(repository) => {
+                self.readiness_service
+                    .get_covering_tests(story_id, organization_id, repository)
+                    .await?
+            }
+            None => Vec::new(),
+        };
+
+        self.quota_guard
+            .consume_llm_call(story.organization_id)
+            .await?;
+        let content = self
+            .llm_service
+            .generate_test_scaffold(&story, &criteria, format, &existing_tests)
+            .await?;
+
+        let plan_pack_id = self
+            .plan_pack_repo
+            .get_plan_pack_by_story(story_id)
+            .await?
+            .map(|pp| pp.id);
+        let version = self
+            .test_scaffold_repo
+            .get_test_scaffolds_by_story(story_id)
+            .await?
+            .iter()
+            .filter(|scaffold| scaffold.format == format)
+            .map(|scaffold| scaffold.version)
+            .max()
+            .unwrap_or(0)
+            + 1;
+
+        let criteria: Vec<AcceptanceCriterionInfo> = criteria
+            .iter()
+            .enumerate()
+            .map(|(position, ac)| ac.to_info(position as u32))
+            .collect();
+        let scaffold = TestScaffold::new(
+            story_id,
+            plan_pack_id,
+            format,
+            version,
+            &story.title,
+            &criteria,
+            content,
+        )?;
+        self.test_scaffold_repo
+            .save_test_scaffold(&scaffold)
+            .await?;
+
+        Ok(scaffold)
+    }
+
+    /// Every generated test scaffold for the story, newest first
+    pub async fn get_test_scaffolds(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<TestScaffold>, AppError> {
+        self.visible_story(story_id, organization_id).await?;
+        self.test_scaffold_repo
+            .get_test_scaffolds_by_story(story_id)
+            .await
+    }
+
+    /// One version of the story's test scaffold, or the latest when `version` is omitted
+    pub async fn get_test_scaffold(
+        &self,
+        story_id: Uuid,
+        format: TestScaffoldFormat,
+        version: Option<i32>,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<TestScaffold>, AppError> {
+        Ok(self
+            .get_test_scaffolds(story_id, organization_id)
+            .await?
+            .into_iter()
+            .find(|scaffold| {
+                scaffold.format == format && version.is_none_or(|v| scaffold.version == v)
+            }))
+    }
+
+    pub async fn regenerate_plan_pack(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<PlanPack, AppError> {
+        self.visible_story(story_id, organization_id).await?;
+
         // Delete existing Plan Pack if it exists
         if let Some(existing) = self.plan_pack_repo.get_plan_pack_by_story(story_id).await? {
             self.plan_pack_repo.delete_plan_pack(existing.id).await?;
         }
 
         // Generate new Plan Pack
-        self.generate_plan_pack(story_id).await
+        self.generate_plan_pack(story_id, organization_id).await
     }
 
-    pub async fn regenerate_task_pack(&self, task_id: Uuid) -> Result<TaskPack, AppError> {
+    pub async fn regenerate_task_pack(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+        force: bool,
+    ) -> Result<TaskPack, AppError> {
+        self.visible_task(task_id, organization_id).await?;
+
         // Delete existing Task Pack if it exists
         if let Some(existing) = self.task_pack_repo.get_task_pack_by_task(task_id).await? {
             self.task_pack_repo.delete_task_pack(existing.id).await?;
         }
 
         // Generate new Task Pack
-        self.generate_task_pack(task_id).await
+        self.generate_task_pack(task_id, organization_id, force)
+            .await
     }
 }
 
@@ -366,16 +790,50 @@ mod tests {
         }
     }
 
-    struct MockBacklogService;
+    #[derive(Default)]
+    struct MockTestScaffoldRepository {
+        scaffolds: Mutex<Vec<TestScaffold>>,
+    }
+
+    #[async_trait]
+    impl TestScaffoldRepository for MockTestScaffoldRepository {
+        async fn save_test_scaffold(&self, scaffold: &TestScaffold) -> Result<(), AppError> {
+            self.scaffolds.lock().unwrap().insert(0, scaffold.clone());
+            Ok(())
+        }
+
+        async fn get_test_scaffolds_by_story(
+            &self,
+            story_id: Uuid,
+        ) -> Result<Vec<TestScaffold>, AppError> {
+            let scaffolds = self.scaffolds.lock().unwrap();
+            Ok(scaffolds
+                .iter()
+                .filter(|scaffold| scaffold.story_id == story_id)
+                .cloned()
+                .collect())
+        }
+    }
+
+    /// Every story and task belongs to `organization_id` and is hidden from other organizations
+    struct MockBacklogService {
+        organization_id: Option<Uuid>,
+    }
 
     #[async_trait]
     impl BacklogService for MockBacklogService {
         async fn get_story_info(
             &self,
             story_id: Uuid,
+            organization_id: Option<Uuid>,
         ) -> Result<Option<crate::application::ports::StoryInfo>, AppError> {
+            if organization_id != self.organization_id {
+                return Ok(None);
+            }
             Ok(Some(crate::application::ports::StoryInfo {
                 id: story_id,
+                project_id: Uuid::nil(),
+                organization_id: self.organization_id,
                 title: "Test Story".to_string(),
                 description: Some("Test description".to_string()),
                 status: "Ready".to_string(),
@@ -385,10 +843,15 @@ mod tests {
         async fn get_task_info(
             &self,
             task_id: Uuid,
+            organization_id: Option<Uuid>,
         ) -> Result<Option<crate::application::ports::TaskInfo>, AppError> {
+            if organization_id != self.organization_id {
+                return Ok(None);
+            }
+            // Every task belongs to the nil story
             Ok(Some(crate::application::ports::TaskInfo {
                 id: task_id,
-                story_id: Uuid::new_v4(),
+                story_id: Uuid::nil(),
                 title: "Test Task".to_string(),
                 description: Some("Test task description".to_string()),
                 acceptance_criteria_refs: vec!["AC1".to_string()],
@@ -403,6 +866,7 @@ mod tests {
         async fn get_acceptance_criteria(
             &self,
             _story_id: Uuid,
+            _organization_id: Option<Uuid>,
         ) -> Result<Vec<AcceptanceCriterion>, AppError> {
             Ok(vec![AcceptanceCriterion {
                 ac_id: "AC1".to_string(),
@@ -415,12 +879,33 @@ mod tests {
         async fn evaluate_readiness(
             &self,
             _story_id: Uuid,
+            _organization_id: Option<Uuid>,
         ) -> Result<crate::application::ports::ReadinessEvaluation, AppError> {
             Ok(crate::application::ports::ReadinessEvaluation {
                 score: 85,
                 missing_items: vec![],
             })
         }
+
+        async fn get_glossary_terms(
+            &self,
+            _organization_id: Option<Uuid>,
+            _text: &str,
+        ) -> Result<Vec<GlossaryTerm>, AppError> {
+            Ok(vec![])
+        }
+
+        async fn get_covering_tests(
+            &self,
+            _story_id: Uuid,
+            _organization_id: Option<Uuid>,
+            _repository: &SourceRepository,
+        ) -> Result<Vec<crate::application::ports::CoveringTests>, AppError> {
+            Ok(vec![crate::application::ports::CoveringTests {
+                ac_id: "AC1".to_string(),
+                test_files: vec!["tests/action_test.rs".to_string()],
+            }])
+        }
     }
 
     struct MockLlmService;
@@ -431,6 +916,8 @@ mod tests {
             &self,
             _story: &crate::application::ports::StoryInfo,
             _criteria: &[AcceptanceCriterion],
+            sprint: Option<&SprintContext>,
+            _glossary: &[GlossaryTerm],
         ) -> Result<crate::application::ports::PlanPackGeneration, AppError> {
             Ok(crate::application::ports::PlanPackGeneration {
                 proposed_tasks: vec![crate::application::ports::ProposedTaskGeneration {
@@ -442,7 +929,11 @@ mod tests {
                 }],
                 architecture_impact: Some("Minimal impact".to_string()),
                 risks: vec!["Low risk".to_string()],
-                unknowns: vec![],
+                // Echo the sprint goal so tests can see what the generator was given
+                unknowns: sprint
+                    .and_then(|sprint| sprint.goal.clone())
+                    .into_iter()
+                    .collect(),
             })
         }
 
@@ -451,6 +942,7 @@ mod tests {
             _task: &crate::application::ports::TaskInfo,
             _story: &crate::application::ports::StoryInfo,
             _criteria: &[AcceptanceCriterion],
+            _glossary: &[GlossaryTerm],
         ) -> Result<crate::application::ports::TaskPackGeneration, AppError> {
             Ok(crate::application::ports::TaskPackGeneration {
                 objectives: "Complete the task".to_string(),
@@ -471,18 +963,125 @@ mod tests {
                 run_instructions: vec!["Run cargo test".to_string()],
             })
         }
+
+        async fn generate_test_scaffold(
+            &self,
+            _story: &crate::application::ports::StoryInfo,
+            criteria: &[AcceptanceCriterion],
+            format: TestScaffoldFormat,
+            existing_tests: &[crate::application::ports::CoveringTests],
+        ) -> Result<String, AppError> {
+            let criteria: Vec<_> = criteria.iter().map(|ac| ac.to_info(0)).collect();
+            let mut content = crate::domain::render_test_scaffold(format, "Test Story", &criteria);
+            for covering in existing_tests {
+                content.push_str(&format!(
+                    "\n// {} may be covered by {}",
+                    covering.ac_id,
+                    covering.test_files.join(", ")
+                ));
+            }
+            Ok(content)
+        }
+    }
+
+    struct MockSprintContextRepository;
+
+    #[async_trait]
+    impl SprintContextRepository for MockSprintContextRepository {
+        async fn get_current_sprint_context(
+            &self,
+            _project_id: Uuid,
+            _organization_id: Option<Uuid>,
+        ) -> Result<Option<SprintContext>, AppError> {
+            Ok(Some(SprintContext {
+                sprint_id: Uuid::new_v4(),
+                name: "Sprint 1".to_string(),
+                goal: Some("Ship checkout".to_string()),
+                status: "active".to_string(),
+                start_date: None,
+                end_date: None,
+                remaining_days: Some(3),
+                capacity_points: Some(20),
+                committed_points: Some(8),
+                completed_points: Some(0),
+                committed_stories: vec![],
+            }))
+        }
+    }
+
+    #[derive(Default)]
+    struct MockGuardrailRepository {
+        guardrails: Mutex<HashMap<Uuid, ProjectGuardrails>>,
+    }
+
+    #[async_trait]
+    impl GuardrailRepository for MockGuardrailRepository {
+        async fn get_project_guardrails(
+            &self,
+            project_id: Uuid,
+            organization_id: Option<Uuid>,
+        ) -> Result<Option<ProjectGuardrails>, AppError> {
+            let guardrails = self.guardrails.lock().unwrap();
+            Ok(guardrails
+                .get(&project_id)
+                .filter(|guardrails| guardrails.organization_id == organization_id)
+                .cloned())
+        }
+
+        async fn save_project_guardrails(
+            &self,
+            guardrails: &ProjectGuardrails,
+        ) -> Result<(), AppError> {
+            self.guardrails
+                .lock()
+                .unwrap()
+                .insert(guardrails.project_id, guardrails.clone());
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockPlanPackSettingsRepository {
+        settings: Mutex<HashMap<Uuid, PlanPackSettings>>,
+    }
+
+    #[async_trait]
+    impl PlanPackSettingsRepository for MockPlanPackSettingsRepository {
+        async fn get_plan_pack_settings(
+            &self,
+            organization_id: Uuid,
+        ) -> Result<Option<PlanPackSettings>, AppError> {
+            Ok(self.settings.lock().unwrap().get(&organization_id).cloned())
+        }
+
+        async fn save_plan_pack_settings(
+            &self,
+            settings: &PlanPackSettings,
+        ) -> Result<(), AppError> {
+            self.settings
+                .lock()
+                .unwrap()
+                .insert(settings.organization_id, settings.clone());
+            Ok(())
+        }
     }
 
     fn setup_usecases() -> PromptBuilderUsecases {
+        setup_usecases_for_organization(None)
+    }
+
+    fn setup_usecases_for_organization(organization_id: Option<Uuid>) -> PromptBuilderUsecases {
         let plan_pack_repo = Arc::new(MockPlanPackRepository::default());
         let task_pack_repo = Arc::new(MockTaskPackRepository::default());
-        let backlog_service = Arc::new(MockBacklogService);
+        let test_scaffold_repo = Arc::new(MockTestScaffoldRepository::default());
+        let backlog_service = Arc::new(MockBacklogService { organization_id });
         let readiness_service = Arc::new(MockReadinessService);
         let llm_service = Arc::new(MockLlmService);
 
         PromptBuilderUsecases::new(
             plan_pack_repo,
             task_pack_repo,
+            test_scaffold_repo,
             backlog_service,
             readiness_service,
             llm_service,
@@ -494,7 +1093,7 @@ mod tests {
         let usecases = setup_usecases();
         let story_id = Uuid::new_v4();
 
-        let result = usecases.generate_plan_pack(story_id).await;
+        let result = usecases.generate_plan_pack(story_id, None).await;
         assert!(result.is_ok());
 
         let plan_pack = result.unwrap();
@@ -506,26 +1105,294 @@ mod tests {
     async fn test_generate_task_pack() {
         let usecases = setup_usecases();
         let task_id = Uuid::new_v4();
-
-        let result = usecases.generate_task_pack(task_id).await;
+        let plan_pack = usecases
+            .generate_plan_pack(Uuid::nil(), None)
+            .await
+            .unwrap();
+        usecases
+            .approve_plan_pack(Uuid::nil(), None, "user_reviewer")
+            .await
+            .unwrap();
+
+        let result = usecases.generate_task_pack(task_id, None, false).await;
         assert!(result.is_ok());
 
         let task_pack = result.unwrap();
         assert_eq!(task_pack.task_id, task_id);
+        assert_eq!(task_pack.plan_pack_id, Some(plan_pack.id));
         assert!(!task_pack.markdown_content.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_task_packs_need_an_approved_plan() {
+        let usecases = setup_usecases();
+        let not_approved = |result: Result<TaskPack, AppError>| matches!(result, Err(AppError::ConflictWithCode { error_code, .. }) if error_code == crate::domain::PLAN_NOT_APPROVED);
+
+        assert!(not_approved(
+            usecases
+                .generate_task_pack(Uuid::new_v4(), None, false)
+                .await
+        ));
+        usecases
+            .generate_plan_pack(Uuid::nil(), None)
+            .await
+            .unwrap();
+        assert!(not_approved(
+            usecases
+                .generate_task_pack(Uuid::new_v4(), None, false)
+                .await
+        ));
+        assert!(usecases
+            .generate_task_pack(Uuid::new_v4(), None, true)
+            .await
+            .is_ok());
+
+        let approved = usecases
+            .approve_plan_pack(Uuid::nil(), None, "user_reviewer")
+            .await
+            .unwrap();
+        assert_eq!(approved.status, crate::domain::PlanPackStatus::Approved);
+        assert!(usecases
+            .generate_task_pack(Uuid::new_v4(), None, false)
+            .await
+            .is_ok());
+
+        // A regenerated plan is a new draft that needs review again
+        usecases
+            .regenerate_plan_pack(Uuid::nil(), None)
+            .await
+            .unwrap();
+        assert!(not_approved(
+            usecases
+                .generate_task_pack(Uuid::new_v4(), None, false)
+                .await
+        ));
+    }
+
     #[tokio::test]
     async fn test_idempotency() {
         let usecases = setup_usecases();
         let story_id = Uuid::new_v4();
 
         // Generate first time
-        let first = usecases.generate_plan_pack(story_id).await.unwrap();
+        let first = usecases.generate_plan_pack(story_id, None).await.unwrap();
 
         // Generate second time - should return same pack
-        let second = usecases.generate_plan_pack(story_id).await.unwrap();
+        let second = usecases.generate_plan_pack(story_id, None).await.unwrap();
 
         assert_eq!(first.id, second.id);
     }
+
+    #[tokio::test]
+    async fn test_plan_pack_generation_sees_sprint_context() {
+        let usecases =
+            setup_usecases().with_sprint_context_repository(Arc::new(MockSprintContextRepository));
+
+        let plan_pack = usecases
+            .generate_plan_pack(Uuid::new_v4(), None)
+            .await
+            .unwrap();
+        assert_eq!(plan_pack.unknowns, vec!["Ship checkout".to_string()]);
+
+        let without_context = setup_usecases()
+            .generate_plan_pack(Uuid::new_v4(), None)
+            .await
+            .unwrap();
+        assert!(without_context.unknowns.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_packs_carry_project_guardrails() {
+        let repo = Arc::new(MockGuardrailRepository::default());
+        let usecases = setup_usecases().with_guardrail_repository(repo.clone());
+        // The mock backlog puts every story in the nil project
+        let project_id = Uuid::nil();
+
+        usecases
+            .set_project_guardrails(
+                project_id,
+                None,
+                Guardrails {
+                    forbidden_dependencies: vec!["openssl".to_string()],
+                    ..Guardrails::default()
+                },
+                vec![GuardrailCategory::ForbiddenDependencies],
+            )
+            .await
+            .unwrap();
+
+        let plan_pack = usecases
+            .generate_plan_pack(Uuid::new_v4(), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            plan_pack.guardrails.forbidden_dependencies,
+            vec!["openssl".to_string()]
+        );
+        let task_pack = usecases
+            .generate_task_pack(Uuid::new_v4(), None, true)
+            .await
+            .unwrap();
+        assert!(task_pack
+            .markdown_content
+            .contains("### Forbidden Dependencies\n- openssl"));
+
+        // A configuration missing a required category blocks generation
+        repo.guardrails
+            .lock()
+            .unwrap()
+            .get_mut(&project_id)
+            .unwrap()
+            .required_categories
+            .push(GuardrailCategory::CodingStandards);
+        assert!(matches!(
+            usecases
+                .generate_task_pack(Uuid::new_v4(), None, true)
+                .await,
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_test_scaffolds_are_versioned_per_format() {
+        let usecases = setup_usecases();
+        let story_id = Uuid::new_v4();
+        let plan_pack = usecases.generate_plan_pack(story_id, None).await.unwrap();
+
+        let first = usecases
+            .generate_test_scaffold(story_id, TestScaffoldFormat::Gherkin, None, None)
+            .await
+            .unwrap();
+        let second = usecases
+            .generate_test_scaffold(story_id, TestScaffoldFormat::Gherkin, None, None)
+            .await
+            .unwrap();
+        let repository = SourceRepository {
+            repository: "acme/shop".to_string(),
+            git_ref: None,
+        };
+        let rust = usecases
+            .generate_test_scaffold(story_id, TestScaffoldFormat::Rust, Some(&repository), None)
+            .await
+            .unwrap();
+
+        assert_eq!((first.version, second.version, rust.version), (1, 2, 1));
+        assert!(!second.content.contains("may be covered by"));
+        assert!(rust
+            .content
+            .contains("AC1 may be covered by tests/action_test.rs"));
+        assert_eq!(second.plan_pack_id, Some(plan_pack.id));
+        assert_eq!(second.file_name, "test-story.feature");
+
+        let latest = usecases
+            .get_test_scaffold(story_id, TestScaffoldFormat::Gherkin, None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.id, second.id);
+        let original = usecases
+            .get_test_scaffold(story_id, TestScaffoldFormat::Gherkin, Some(1), None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(original.id, first.id);
+    }
+
+    #[tokio::test]
+    async fn test_packs_are_hidden_from_other_organizations() {
+        let organization_id = Some(Uuid::new_v4());
+        let usecases = setup_usecases_for_organization(organization_id);
+        let story_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        usecases
+            .generate_plan_pack(story_id, organization_id)
+            .await
+            .unwrap();
+        usecases
+            .generate_task_pack(task_id, organization_id, true)
+            .await
+            .unwrap();
+
+        let other = Some(Uuid::new_v4());
+        assert!(matches!(
+            usecases.get_plan_pack(story_id, other).await,
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(
+            usecases.get_task_pack(task_id, other).await,
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(
+            usecases.regenerate_plan_pack(story_id, other).await,
+            Err(AppError::NotFound(_))
+        ));
+        assert!(usecases
+            .get_plan_pack(story_id, organization_id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_story_changes_make_plan_packs_stale() {
+        let organization_id = Some(Uuid::new_v4());
+        let usecases = setup_usecases_for_organization(organization_id)
+            .with_plan_pack_settings_repository(
+                Arc::new(MockPlanPackSettingsRepository::default()),
+            );
+        let story_id = Uuid::new_v4();
+        let plan_pack = usecases
+            .generate_plan_pack(story_id, organization_id)
+            .await
+            .unwrap();
+        let criteria: Vec<AcceptanceCriterionInfo> = plan_pack
+            .acceptance_criteria_map
+            .ordered()
+            .into_iter()
+            .cloned()
+            .collect();
+
+        assert!(usecases
+            .invalidate_plan_pack(
+                story_id,
+                organization_id,
+                Some("Test description"),
+                &criteria
+            )
+            .await
+            .unwrap()
+            .is_none());
+
+        let stale = usecases
+            .invalidate_plan_pack(
+                story_id,
+                organization_id,
+                Some("New description"),
+                &criteria,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stale.id, plan_pack.id);
+        assert_eq!(stale.stale_reason.as_deref(), Some("Description changed"));
+        assert!(usecases
+            .get_plan_pack(story_id, organization_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .is_stale());
+
+        // With auto-regeneration on, the stale pack is replaced by a fresh one
+        usecases
+            .set_plan_pack_settings(organization_id, true)
+            .await
+            .unwrap();
+        let regenerated = usecases
+            .invalidate_plan_pack(story_id, organization_id, Some("Test description"), &[])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_ne!(regenerated.id, plan_pack.id);
+        assert!(!regenerated.is_stale());
+    }
 }
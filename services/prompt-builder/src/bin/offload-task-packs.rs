@@ -0,0 +1,41 @@
+//! Moves the content of existing large task packs to S3, for packs saved
+//! before offloading was configured or while the threshold was higher.
+//!
+//! ```text
+//! DATABASE_URL=... PACK_STORAGE_S3_BUCKET=... AWS_ACCESS_KEY_ID=... AWS_SECRET_ACCESS_KEY=... \
+//!     cargo run -p prompt-builder --bin offload-task-packs -- [--dry-run]
+//! ```
+
+use anyhow::{bail, Context, Result};
+use sqlx::PgPool;
+
+const BATCH_SIZE: i64 = 100;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let dry_run = std::env::args().any(|arg| arg == "--dry-run");
+    if std::env::var("PACK_STORAGE_S3_BUCKET").is_err() {
+        bail!("PACK_STORAGE_S3_BUCKET and the AWS credentials must be set");
+    }
+    let database_url = std::env::var("DATABASE_URL").context("DATABASE_URL must be set")?;
+    let pool = PgPool::connect(&database_url)
+        .await
+        .context("connecting to the database")?;
+
+    let summary = prompt_builder::task_pack_repository(pool)
+        .offload_large_task_packs(BATCH_SIZE, dry_run)
+        .await
+        .map_err(|e| anyhow::anyhow!("offloading task packs failed: {}", e))?;
+
+    println!(
+        "{} {} task packs ({} bytes)",
+        if dry_run {
+            "Would offload"
+        } else {
+            "Offloaded"
+        },
+        summary.packs,
+        summary.bytes
+    );
+    Ok(())
+}
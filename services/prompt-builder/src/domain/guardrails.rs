@@ -0,0 +1,270 @@
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+
+const MAX_RULES_PER_CATEGORY: usize = 50;
+const MAX_RULE_LENGTH: usize = 500;
+
+/// The kinds of rule a project can hold generated work to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardrailCategory {
+    /// Layering, module boundaries and patterns the code must follow
+    ArchitectureRules,
+    /// Crates, packages or services the code must not pull in
+    ForbiddenDependencies,
+    /// Style, naming, testing and review conventions
+    CodingStandards,
+}
+
+impl GuardrailCategory {
+    pub const ALL: [Self; 3] = [
+        Self::ArchitectureRules,
+        Self::ForbiddenDependencies,
+        Self::CodingStandards,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ArchitectureRules => "architecture_rules",
+            Self::ForbiddenDependencies => "forbidden_dependencies",
+            Self::CodingStandards => "coding_standards",
+        }
+    }
+
+    pub fn heading(&self) -> &'static str {
+        match self {
+            Self::ArchitectureRules => "Architecture Rules",
+            Self::ForbiddenDependencies => "Forbidden Dependencies",
+            Self::CodingStandards => "Coding Standards",
+        }
+    }
+}
+
+impl FromStr for GuardrailCategory {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str() == value)
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown guardrail category: {}", value)))
+    }
+}
+
+/// The guardrails section injected into every plan and task pack. Packs
+/// stored before guardrails existed read back with no rules.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Guardrails {
+    pub architecture_rules: Vec<String>,
+    pub forbidden_dependencies: Vec<String>,
+    pub coding_standards: Vec<String>,
+}
+
+impl Guardrails {
+    pub fn rules(&self, category: GuardrailCategory) -> &[String] {
+        match category {
+            GuardrailCategory::ArchitectureRules => &self.architecture_rules,
+            GuardrailCategory::ForbiddenDependencies => &self.forbidden_dependencies,
+            GuardrailCategory::CodingStandards => &self.coding_standards,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        GuardrailCategory::ALL
+            .into_iter()
+            .all(|category| self.rules(category).is_empty())
+    }
+
+    /// Fails naming every category in `required` that has no rules
+    pub fn ensure_categories(&self, required: &[GuardrailCategory]) -> Result<(), AppError> {
+        let missing: Vec<&str> = required
+            .iter()
+            .filter(|category| self.rules(**category).is_empty())
+            .map(|category| category.as_str())
+            .collect();
+        if !missing.is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "Required guardrail categories have no rules: {}",
+                missing.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// The `## Guardrails` section of a pack's markdown
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("## 🛡️ Guardrails\n");
+        if self.is_empty() {
+            markdown.push_str("No guardrails are configured for this project.\n\n");
+            return markdown;
+        }
+
+        for category in GuardrailCategory::ALL {
+            let rules = self.rules(category);
+            if rules.is_empty() {
+                continue;
+            }
+            markdown.push_str(&format!("### {}\n", category.heading()));
+            for rule in rules {
+                markdown.push_str(&format!("- {}\n", rule));
+            }
+            markdown.push('\n');
+        }
+        markdown
+    }
+
+    /// Trimmed rules without blanks or repeats, within the size limits
+    fn normalized(self) -> Result<Self, AppError> {
+        let normalize = |category: GuardrailCategory, rules: Vec<String>| {
+            let mut normalized: Vec<String> = Vec::new();
+            for rule in rules {
+                let rule = rule.trim();
+                if rule.is_empty() || normalized.iter().any(|existing| existing == rule) {
+                    continue;
+                }
+                if rule.chars().count() > MAX_RULE_LENGTH {
+                    return Err(AppError::BadRequest(format!(
+                        "{} rules must be at most {} characters",
+                        category.as_str(),
+                        MAX_RULE_LENGTH
+                    )));
+                }
+                normalized.push(rule.to_string());
+            }
+            if normalized.len() > MAX_RULES_PER_CATEGORY {
+                return Err(AppError::BadRequest(format!(
+                    "{} can have at most {} rules",
+                    category.as_str(),
+                    MAX_RULES_PER_CATEGORY
+                )));
+            }
+            Ok(normalized)
+        };
+
+        Ok(Self {
+            architecture_rules: normalize(
+                GuardrailCategory::ArchitectureRules,
+                self.architecture_rules,
+            )?,
+            forbidden_dependencies: normalize(
+                GuardrailCategory::ForbiddenDependencies,
+                self.forbidden_dependencies,
+            )?,
+            coding_standards: normalize(GuardrailCategory::CodingStandards, self.coding_standards)?,
+        })
+    }
+}
+
+/// A project's guardrail configuration. Categories in `required_categories`
+/// must have at least one rule, so no pack is generated without them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectGuardrails {
+    pub project_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub guardrails: Guardrails,
+    pub required_categories: Vec<GuardrailCategory>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProjectGuardrails {
+    pub fn new(
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        guardrails: Guardrails,
+        required_categories: Vec<GuardrailCategory>,
+    ) -> Result<Self, AppError> {
+        let guardrails = guardrails.normalized()?;
+        let required_categories: Vec<GuardrailCategory> = GuardrailCategory::ALL
+            .into_iter()
+            .filter(|category| required_categories.contains(category))
+            .collect();
+        guardrails.ensure_categories(&required_categories)?;
+
+        Ok(Self {
+            project_id,
+            organization_id,
+            guardrails,
+            required_categories,
+            updated_at: Utc::now(),
+        })
+    }
+
+    /// The section for a pack, checking again that required categories are present
+    pub fn section(&self) -> Result<Guardrails, AppError> {
+        self.guardrails
+            .ensure_categories(&self.required_categories)?;
+        Ok(self.guardrails.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_guardrails_are_normalized_and_validated() {
+        let guardrails = Guardrails {
+            architecture_rules: vec![
+                " Domain code never imports adapters ".to_string(),
+                "".to_string(),
+                "Domain code never imports adapters".to_string(),
+            ],
+            forbidden_dependencies: vec!["openssl".to_string()],
+            coding_standards: vec![],
+        };
+
+        let project = ProjectGuardrails::new(
+            Uuid::new_v4(),
+            None,
+            guardrails.clone(),
+            vec![
+                GuardrailCategory::ForbiddenDependencies,
+                GuardrailCategory::ArchitectureRules,
+                GuardrailCategory::ArchitectureRules,
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            project.guardrails.architecture_rules,
+            vec!["Domain code never imports adapters".to_string()]
+        );
+        assert_eq!(
+            project.required_categories,
+            vec![
+                GuardrailCategory::ArchitectureRules,
+                GuardrailCategory::ForbiddenDependencies
+            ]
+        );
+
+        let missing = ProjectGuardrails::new(
+            Uuid::new_v4(),
+            None,
+            guardrails,
+            vec![GuardrailCategory::CodingStandards],
+        );
+        assert!(
+            matches!(missing, Err(AppError::BadRequest(message)) if message.contains("coding_standards"))
+        );
+    }
+
+    #[test]
+    fn test_markdown_lists_configured_categories() {
+        let guardrails = Guardrails {
+            architecture_rules: vec![],
+            forbidden_dependencies: vec!["openssl".to_string()],
+            coding_standards: vec!["Errors go through AppError".to_string()],
+        };
+
+        let markdown = guardrails.to_markdown();
+        assert!(markdown.contains("### Forbidden Dependencies\n- openssl\n"));
+        assert!(markdown.contains("### Coding Standards\n- Errors go through AppError\n"));
+        assert!(!markdown.contains("### Architecture Rules"));
+        assert!(Guardrails::default()
+            .to_markdown()
+            .contains("No guardrails are configured"));
+    }
+}
@@ -1,5 +1,11 @@
+pub mod guardrails;
 pub mod plan_pack;
+pub mod sprint_context;
 pub mod task_pack;
+pub mod test_scaffold;
 
+pub use guardrails::*;
 pub use plan_pack::*;
+pub use sprint_context::*;
 pub use task_pack::*;
+pub use test_scaffold::*;
@@ -1,3 +1,4 @@
+use crate::domain::Guardrails;
 use common::AppError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -8,12 +9,53 @@ pub struct AcceptanceCriteriaMap {
     pub criteria: HashMap<String, AcceptanceCriterionInfo>,
 }
 
+impl AcceptanceCriteriaMap {
+    /// Criteria in the story's display order
+    pub fn ordered(&self) -> Vec<&AcceptanceCriterionInfo> {
+        let mut criteria: Vec<_> = self.criteria.values().collect();
+        criteria.sort_by(|a, b| a.position.cmp(&b.position).then(a.ac_id.cmp(&b.ac_id)));
+        criteria
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AcceptanceCriterionInfo {
     pub ac_id: String,
     pub given: String,
     pub when: String,
     pub then: String,
+    /// Display order on the story; packs stored before ordering existed default to 0
+    #[serde(default)]
+    pub position: u32,
+}
+
+pub const PLAN_NOT_APPROVED: &str = "PLAN_NOT_APPROVED";
+
+/// Where a plan pack is in review. Task packs are generated from approved plans.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PlanPackStatus {
+    /// Generated and waiting for someone to review it
+    #[default]
+    Draft,
+    Approved,
+}
+
+impl PlanPackStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Approved => "approved",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(Self::Draft),
+            "approved" => Some(Self::Approved),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -34,6 +76,25 @@ pub struct PlanPack {
     pub architecture_impact: Option<String>,
     pub risks: Vec<String>,
     pub unknowns: Vec<String>,
+    /// The project's guardrails when the pack was generated
+    #[serde(default)]
+    pub guardrails: Guardrails,
+    #[serde(default)]
+    pub status: PlanPackStatus,
+    /// The subject of whoever approved the plan
+    #[serde(default)]
+    pub approved_by: Option<String>,
+    #[serde(default)]
+    pub approved_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The story's description when the pack was generated; `None` for packs
+    /// generated before descriptions were recorded
+    #[serde(default)]
+    pub story_description: Option<String>,
+    /// Why the pack no longer matches its story, once the story has changed
+    #[serde(default)]
+    pub stale_reason: Option<String>,
+    #[serde(default)]
+    pub stale_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -68,8 +129,12 @@ impl PlanPack {
             .cloned()
             .collect();
 
-        let uncovered_acs: Vec<String> =
-            valid_ac_ids.difference(&covered_ac_ids).cloned().collect();
+        let uncovered_acs: Vec<String> = acceptance_criteria_map
+            .ordered()
+            .into_iter()
+            .filter(|ac| !covered_ac_ids.contains(&ac.ac_id))
+            .map(|ac| ac.ac_id.clone())
+            .collect();
 
         if !uncovered_acs.is_empty() {
             return Err(AppError::BadRequest(format!(
@@ -86,10 +151,92 @@ impl PlanPack {
             architecture_impact,
             risks,
             unknowns,
+            guardrails: Guardrails::default(),
+            status: PlanPackStatus::Draft,
+            approved_by: None,
+            approved_at: None,
+            story_description: None,
+            stale_reason: None,
+            stale_at: None,
             created_at: chrono::Utc::now(),
         })
     }
 
+    /// Mark the plan reviewed. Approving an approved plan keeps its first approval.
+    pub fn approve(&mut self, approved_by: &str) {
+        if self.status == PlanPackStatus::Approved {
+            return;
+        }
+        self.status = PlanPackStatus::Approved;
+        self.approved_by = Some(approved_by.to_string());
+        self.approved_at = Some(chrono::Utc::now());
+    }
+
+    /// Refuse task pack generation for a story whose plan is missing or still a draft
+    pub fn ensure_approved(plan_pack: Option<&PlanPack>) -> Result<(), AppError> {
+        let message = match plan_pack {
+            Some(plan_pack) if plan_pack.status == PlanPackStatus::Approved => return Ok(()),
+            Some(_) => "The story's plan pack must be approved before task packs are generated",
+            None => "The story needs an approved plan pack before task packs are generated",
+        };
+        Err(AppError::ConflictWithCode {
+            message: message.to_string(),
+            error_code: PLAN_NOT_APPROVED.to_string(),
+        })
+    }
+
+    pub fn with_guardrails(mut self, guardrails: Guardrails) -> Self {
+        self.guardrails = guardrails;
+        self
+    }
+
+    /// Record the description the pack was generated from, so later edits can be detected
+    pub fn with_story_description(mut self, description: Option<&str>) -> Self {
+        self.story_description = Some(description.unwrap_or_default().trim().to_string());
+        self
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.stale_reason.is_some()
+    }
+
+    /// Why the pack no longer matches a story with this description and these
+    /// criteria, or `None` while it still does. Criteria are compared by content
+    /// in display order; the description only when the pack recorded one.
+    pub fn staleness(
+        &self,
+        description: Option<&str>,
+        criteria: &[AcceptanceCriterionInfo],
+    ) -> Option<String> {
+        let mut story_criteria: Vec<&AcceptanceCriterionInfo> = criteria.iter().collect();
+        story_criteria.sort_by_key(|ac| ac.position);
+        let pack_criteria = self.acceptance_criteria_map.ordered();
+
+        let criteria_changed = pack_criteria.len() != story_criteria.len()
+            || pack_criteria
+                .iter()
+                .zip(&story_criteria)
+                .any(|(pack, story)| !same_criterion(pack, story));
+        let description_changed = self
+            .story_description
+            .as_deref()
+            .is_some_and(|recorded| recorded != description.unwrap_or_default().trim());
+
+        match (criteria_changed, description_changed) {
+            (true, true) => Some("Acceptance criteria and description changed".to_string()),
+            (true, false) => Some("Acceptance criteria changed".to_string()),
+            (false, true) => Some("Description changed".to_string()),
+            (false, false) => None,
+        }
+    }
+
+    /// Flag the pack as out of date. A pack that is already stale keeps when
+    /// it first went stale and takes the latest reason.
+    pub fn mark_stale(&mut self, reason: String) {
+        self.stale_reason = Some(reason);
+        self.stale_at.get_or_insert_with(chrono::Utc::now);
+    }
+
     #[allow(dead_code)]
     pub fn get_coverage_map(&self) -> HashMap<String, Vec<String>> {
         let mut coverage = HashMap::new();
@@ -135,6 +282,31 @@ impl PlanPack {
     }
 }
 
+fn same_criterion(a: &AcceptanceCriterionInfo, b: &AcceptanceCriterionInfo) -> bool {
+    a.given.trim() == b.given.trim()
+        && a.when.trim() == b.when.trim()
+        && a.then.trim() == b.then.trim()
+}
+
+/// An organization's plan pack preferences
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlanPackSettings {
+    pub organization_id: Uuid,
+    /// Regenerate a plan pack as soon as its story's changes make it stale
+    pub auto_regenerate_stale: bool,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PlanPackSettings {
+    pub fn new(organization_id: Uuid, auto_regenerate_stale: bool) -> Self {
+        Self {
+            organization_id,
+            auto_regenerate_stale,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,6 +320,7 @@ mod tests {
                 given: "user is logged in".to_string(),
                 when: "user clicks save".to_string(),
                 then: "data is saved".to_string(),
+                position: 0,
             },
         );
         criteria.insert(
@@ -157,6 +330,7 @@ mod tests {
                 given: "user has data".to_string(),
                 when: "user submits form".to_string(),
                 then: "form is validated".to_string(),
+                position: 1,
             },
         );
 
@@ -228,6 +402,45 @@ mod tests {
         assert!(plan_pack.is_err());
     }
 
+    #[test]
+    fn test_ordered_criteria_follow_position() {
+        let mut ac_map = create_test_ac_map();
+        ac_map.criteria.get_mut("AC1").unwrap().position = 5;
+
+        let order: Vec<&str> = ac_map
+            .ordered()
+            .into_iter()
+            .map(|ac| ac.ac_id.as_str())
+            .collect();
+        assert_eq!(order, vec!["AC2", "AC1"]);
+    }
+
+    #[test]
+    fn test_approval_gates_task_generation() {
+        let mut plan_pack = PlanPack::new(
+            Uuid::new_v4(),
+            create_test_ac_map(),
+            create_test_tasks(),
+            None,
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(plan_pack.status, PlanPackStatus::Draft);
+        assert!(PlanPack::ensure_approved(None).is_err());
+        assert!(matches!(
+            PlanPack::ensure_approved(Some(&plan_pack)),
+            Err(AppError::ConflictWithCode { error_code, .. }) if error_code == PLAN_NOT_APPROVED
+        ));
+
+        plan_pack.approve("user_reviewer");
+        let approved_at = plan_pack.approved_at;
+        plan_pack.approve("user_other");
+        assert_eq!(plan_pack.approved_by.as_deref(), Some("user_reviewer"));
+        assert_eq!(plan_pack.approved_at, approved_at);
+        assert!(PlanPack::ensure_approved(Some(&plan_pack)).is_ok());
+    }
+
     #[test]
     fn test_coverage_map() {
         let story_id = Uuid::new_v4();
@@ -241,4 +454,56 @@ mod tests {
         assert!(coverage.contains_key("AC1"));
         assert!(coverage.contains_key("AC2"));
     }
+
+    #[test]
+    fn test_staleness_tracks_criteria_and_description() {
+        let plan_pack = PlanPack::new(
+            Uuid::new_v4(),
+            create_test_ac_map(),
+            create_test_tasks(),
+            None,
+            vec![],
+            vec![],
+        )
+        .unwrap()
+        .with_story_description(Some("Save a form "));
+
+        // The story's own criterion ids and whitespace do not matter
+        let mut criteria: Vec<AcceptanceCriterionInfo> = create_test_ac_map()
+            .ordered()
+            .into_iter()
+            .map(|ac| AcceptanceCriterionInfo {
+                ac_id: Uuid::new_v4().to_string(),
+                ..ac.clone()
+            })
+            .collect();
+        assert_eq!(plan_pack.staleness(Some("Save a form"), &criteria), None);
+
+        assert_eq!(
+            plan_pack.staleness(Some("Save and share a form"), &criteria),
+            Some("Description changed".to_string())
+        );
+
+        criteria[1].then = "errors are shown".to_string();
+        assert_eq!(
+            plan_pack.staleness(Some("Save a form"), &criteria),
+            Some("Acceptance criteria changed".to_string())
+        );
+        criteria.pop();
+        assert_eq!(
+            plan_pack.staleness(None, &criteria),
+            Some("Acceptance criteria and description changed".to_string())
+        );
+
+        let mut plan_pack = plan_pack;
+        plan_pack.mark_stale("Description changed".to_string());
+        let first_stale_at = plan_pack.stale_at;
+        plan_pack.mark_stale("Acceptance criteria changed".to_string());
+        assert!(plan_pack.is_stale());
+        assert_eq!(plan_pack.stale_at, first_stale_at);
+        assert_eq!(
+            plan_pack.stale_reason.as_deref(),
+            Some("Acceptance criteria changed")
+        );
+    }
 }
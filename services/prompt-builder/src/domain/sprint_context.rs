@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A story committed to the current sprint, as the prompt builder's projection sees it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintContextStory {
+    pub story_id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub story_points: Option<u32>,
+}
+
+/// The current sprint of a project, read from the prompt builder's projections
+/// and given to the generator alongside the story
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintContext {
+    pub sprint_id: Uuid,
+    pub name: String,
+    pub goal: Option<String>,
+    pub status: String,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    /// Whole days left until the sprint ends, rounded up; `None` without an end date
+    pub remaining_days: Option<i64>,
+    pub capacity_points: Option<u32>,
+    pub committed_points: Option<u32>,
+    pub completed_points: Option<u32>,
+    pub committed_stories: Vec<SprintContextStory>,
+}
+
+impl SprintContext {
+    pub fn remaining_days(end_date: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<i64> {
+        end_date.map(|end| {
+            let seconds = (end - now).num_seconds().max(0);
+            (seconds + 86_399) / 86_400
+        })
+    }
+
+    /// Plain-text summary for inclusion in a generation prompt
+    pub fn prompt_section(&self) -> String {
+        let mut lines = vec![format!("Sprint: {} ({})", self.name, self.status)];
+        if let Some(goal) = self.goal.as_deref().filter(|goal| !goal.trim().is_empty()) {
+            lines.push(format!("Sprint goal: {}", goal));
+        }
+        if let Some(days) = self.remaining_days {
+            lines.push(format!("Days remaining: {}", days));
+        }
+        if let (Some(committed), Some(capacity)) = (self.committed_points, self.capacity_points) {
+            lines.push(format!(
+                "Committed points: {} of {} capacity",
+                committed, capacity
+            ));
+        }
+        if self.committed_stories.is_empty() {
+            lines.push("Committed stories: none".to_string());
+        } else {
+            lines.push("Committed stories:".to_string());
+            lines.extend(
+                self.committed_stories
+                    .iter()
+                    .map(|story| match story.story_points {
+                        Some(points) => {
+                            format!("- {} [{}, {} pts]", story.title, story.status, points)
+                        }
+                        None => format!("- {} [{}]", story.title, story.status),
+                    }),
+            );
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_remaining_days_rounds_up_and_stops_at_zero() {
+        let now = Utc::now();
+        assert_eq!(
+            SprintContext::remaining_days(Some(now + Duration::hours(30)), now),
+            Some(2)
+        );
+        assert_eq!(
+            SprintContext::remaining_days(Some(now + Duration::days(3)), now),
+            Some(3)
+        );
+        assert_eq!(
+            SprintContext::remaining_days(Some(now - Duration::days(1)), now),
+            Some(0)
+        );
+        assert_eq!(SprintContext::remaining_days(None, now), None);
+    }
+
+    #[test]
+    fn test_prompt_section_lists_goal_and_stories() {
+        let context = SprintContext {
+            sprint_id: Uuid::new_v4(),
+            name: "Sprint 7".to_string(),
+            goal: Some("Ship checkout".to_string()),
+            status: "active".to_string(),
+            start_date: None,
+            end_date: None,
+            remaining_days: Some(4),
+            capacity_points: Some(20),
+            committed_points: Some(13),
+            completed_points: Some(5),
+            committed_stories: vec![SprintContextStory {
+                story_id: Uuid::new_v4(),
+                title: "Card payments".to_string(),
+                status: "inprogress".to_string(),
+                story_points: Some(8),
+            }],
+        };
+
+        let section = context.prompt_section();
+        assert!(section.contains("Sprint goal: Ship checkout"));
+        assert!(section.contains("Days remaining: 4"));
+        assert!(section.contains("Committed points: 13 of 20 capacity"));
+        assert!(section.contains("- Card payments [inprogress, 8 pts]"));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::domain::Guardrails;
 use common::AppError;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -46,6 +47,9 @@ pub struct TaskPack {
     pub do_not_list: DoNotList,
     pub commit_plan: CommitPlan,
     pub run_instructions: Vec<String>,
+    /// The project's guardrails when the pack was generated
+    #[serde(default)]
+    pub guardrails: Guardrails,
     pub markdown_content: String,
     pub json_content: serde_json::Value,
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -99,6 +103,7 @@ impl TaskPack {
             do_not_list,
             commit_plan,
             run_instructions,
+            guardrails: Guardrails::default(),
             markdown_content: String::new(), // Will be generated
             json_content: serde_json::Value::Null, // Will be generated
             created_at: chrono::Utc::now(),
@@ -200,6 +205,9 @@ impl TaskPack {
             markdown.push('\n');
         }
 
+        // Guardrails
+        markdown.push_str(&self.guardrails.to_markdown());
+
         // Do Not List
         markdown.push_str("## ⚠️ DO NOT LIST\n");
         if !self.do_not_list.forbidden_actions.is_empty() {
@@ -260,6 +268,12 @@ impl TaskPack {
         serde_json::to_value(self).map_err(|_| AppError::InternalServerError)
     }
 
+    /// Call before `with_generated_content` so the renderings include them
+    pub fn with_guardrails(mut self, guardrails: Guardrails) -> Self {
+        self.guardrails = guardrails;
+        self
+    }
+
     pub fn with_generated_content(mut self) -> Result<Self, AppError> {
         self.markdown_content = self.generate_markdown();
         self.json_content = self.generate_json()?;
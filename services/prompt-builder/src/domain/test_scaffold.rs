@@ -0,0 +1,260 @@
+use crate::domain::AcceptanceCriterionInfo;
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// What kind of test file is generated from a story's acceptance criteria
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TestScaffoldFormat {
+    /// A Gherkin feature file with one scenario per criterion
+    #[default]
+    Gherkin,
+    /// A Rust test module with one ignored test per criterion
+    Rust,
+}
+
+impl TestScaffoldFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gherkin => "gherkin",
+            Self::Rust => "rust",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Gherkin => "text/x-gherkin; charset=utf-8",
+            Self::Rust => "text/x-rust; charset=utf-8",
+        }
+    }
+
+    /// File name for a story, e.g. `checkout-with-saved-card.feature`
+    pub fn file_name(&self, story_title: &str) -> String {
+        match self {
+            Self::Gherkin => format!("{}.feature", slug(story_title, '-')),
+            Self::Rust => format!("{}_acceptance.rs", slug(story_title, '_')),
+        }
+    }
+}
+
+impl FromStr for TestScaffoldFormat {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "gherkin" | "feature" => Ok(Self::Gherkin),
+            "rust" => Ok(Self::Rust),
+            other => Err(AppError::BadRequest(format!(
+                "Unknown test scaffold format: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// A generated acceptance test file. Each generation for a story and format
+/// is a new version; earlier versions are kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestScaffold {
+    pub id: Uuid,
+    pub story_id: Uuid,
+    /// The story's plan pack when the scaffold was generated
+    pub plan_pack_id: Option<Uuid>,
+    pub format: TestScaffoldFormat,
+    pub version: i32,
+    pub file_name: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TestScaffold {
+    pub fn new(
+        story_id: Uuid,
+        plan_pack_id: Option<Uuid>,
+        format: TestScaffoldFormat,
+        version: i32,
+        story_title: &str,
+        criteria: &[AcceptanceCriterionInfo],
+        content: String,
+    ) -> Result<Self, AppError> {
+        let content = strip_code_fence(&content);
+        if content.is_empty() {
+            return Err(AppError::BadRequest(
+                "Generated test scaffold is empty".to_string(),
+            ));
+        }
+
+        // Every criterion must be traceable to a scenario or test by its ID
+        let missing: Vec<&str> = criteria
+            .iter()
+            .filter(|ac| !content.contains(&ac.ac_id))
+            .map(|ac| ac.ac_id.as_str())
+            .collect();
+        if !missing.is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "Generated test scaffold does not cover: {}",
+                missing.join(", ")
+            )));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            story_id,
+            plan_pack_id,
+            format,
+            version,
+            file_name: format.file_name(story_title),
+            content: format!("{}\n", content),
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// Deterministic scaffold used when no LLM is configured
+pub fn render_test_scaffold(
+    format: TestScaffoldFormat,
+    story_title: &str,
+    criteria: &[AcceptanceCriterionInfo],
+) -> String {
+    match format {
+        TestScaffoldFormat::Gherkin => {
+            let scenarios: Vec<String> = criteria
+                .iter()
+                .map(|ac| {
+                    format!(
+                        "  Scenario: {}\n    Given {}\n    When {}\n    Then {}",
+                        ac.ac_id, ac.given, ac.when, ac.then
+                    )
+                })
+                .collect();
+            format!("Feature: {}\n\n{}\n", story_title, scenarios.join("\n\n"))
+        }
+        TestScaffoldFormat::Rust => {
+            let tests: Vec<String> = criteria
+                .iter()
+                .map(|ac| {
+                    format!(
+                        "/// {}\n#[test]\n#[ignore = \"not implemented\"]\nfn {}() {{\n    \
+                         // Given {}\n    // When {}\n    // Then {}\n    todo!()\n}}",
+                        ac.ac_id,
+                        slug(&ac.ac_id, '_'),
+                        ac.given,
+                        ac.when,
+                        ac.then
+                    )
+                })
+                .collect();
+            format!(
+                "//! Acceptance tests for \"{}\"\n\n{}\n",
+                story_title,
+                tests.join("\n\n")
+            )
+        }
+    }
+}
+
+/// LLMs often wrap file content in a Markdown code fence
+fn strip_code_fence(content: &str) -> &str {
+    let trimmed = content.trim();
+    match trimmed.strip_prefix("```") {
+        Some(rest) => rest
+            .split_once('\n')
+            .map(|(_, body)| body)
+            .unwrap_or_default()
+            .trim_end()
+            .trim_end_matches("```")
+            .trim(),
+        None => trimmed,
+    }
+}
+
+fn slug(text: &str, separator: char) -> String {
+    let slug = text
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(&separator.to_string());
+    if slug.is_empty() {
+        "story".to_string()
+    } else {
+        slug
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn criterion(ac_id: &str, position: u32) -> AcceptanceCriterionInfo {
+        AcceptanceCriterionInfo {
+            ac_id: ac_id.to_string(),
+            given: "a saved card".to_string(),
+            when: "I check out".to_string(),
+            then: "the order is paid".to_string(),
+            position,
+        }
+    }
+
+    #[test]
+    fn test_rendered_scaffolds_cover_every_criterion() {
+        let criteria = vec![criterion("AC1", 0), criterion("AC2", 1)];
+        for format in [TestScaffoldFormat::Gherkin, TestScaffoldFormat::Rust] {
+            let content = render_test_scaffold(format, "Checkout with saved card", &criteria);
+            let scaffold = TestScaffold::new(
+                Uuid::new_v4(),
+                None,
+                format,
+                1,
+                "Checkout with saved card",
+                &criteria,
+                content,
+            )
+            .unwrap();
+            assert!(scaffold.content.contains("AC2"));
+        }
+
+        assert_eq!(
+            TestScaffoldFormat::Gherkin.file_name("Checkout with saved card!"),
+            "checkout-with-saved-card.feature"
+        );
+        assert_eq!(
+            TestScaffoldFormat::Rust.file_name("Checkout with saved card!"),
+            "checkout_with_saved_card_acceptance.rs"
+        );
+    }
+
+    #[test]
+    fn test_fenced_content_is_unwrapped_and_checked() {
+        let criteria = vec![criterion("AC1", 0), criterion("AC2", 1)];
+        let fenced = "```gherkin\nFeature: Checkout\n\n  Scenario: AC1\n    Given x\n```";
+
+        let error = TestScaffold::new(
+            Uuid::new_v4(),
+            None,
+            TestScaffoldFormat::Gherkin,
+            1,
+            "Checkout",
+            &criteria,
+            fenced.to_string(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(message) if message.ends_with("AC2")));
+
+        let scaffold = TestScaffold::new(
+            Uuid::new_v4(),
+            None,
+            TestScaffoldFormat::Gherkin,
+            1,
+            "Checkout",
+            &criteria[..1],
+            fenced.to_string(),
+        )
+        .unwrap();
+        assert!(scaffold.content.starts_with("Feature: Checkout"));
+        assert!(!scaffold.content.contains("```"));
+    }
+}
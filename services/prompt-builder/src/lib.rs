@@ -4,15 +4,26 @@ pub mod config;
 pub mod domain;
 mod projections;
 
-use adapters::persistence::repo::{SqlPlanPackRepository, SqlTaskPackRepository};
+use adapters::persistence::repo::{
+    SqlGuardrailRepository, SqlPlanPackRepository, SqlPlanPackSettingsRepository,
+    SqlSprintContextRepository, SqlTaskPackRepository, SqlTestScaffoldRepository,
+    DEFAULT_OFFLOAD_THRESHOLD_BYTES,
+};
+use adapters::storage::S3BlobStore;
 use application::{
-    ports::{BacklogService, LlmService, PlanPackRepository, ReadinessService, TaskPackRepository},
+    ports::{
+        BacklogService, GuardrailRepository, LlmService, PlanPackRepository,
+        PlanPackSettingsRepository, ReadinessService, SprintContextRepository, TaskPackRepository,
+        TestScaffoldRepository,
+    },
     PromptBuilderUsecases,
 };
+use common::quota::QuotaGuard;
 use event_bus::EventBus;
 use sqlx::PgPool;
 use std::sync::Arc;
 
+pub use adapters::http::routes::create_prompt_builder_router;
 pub use config::AppConfig;
 
 pub fn build_usecases(
@@ -21,19 +32,54 @@ pub fn build_usecases(
     backlog_service: Arc<dyn BacklogService>,
     readiness_service: Arc<dyn ReadinessService>,
     llm_service: Arc<dyn LlmService>,
+    quota_guard: Arc<dyn QuotaGuard>,
 ) -> Arc<PromptBuilderUsecases> {
     let pool = Arc::new(pool);
-    projections::SprintProjectionWorker::spawn(pool.clone(), event_bus);
+    projections::SprintProjectionWorker::spawn(pool.clone(), event_bus.clone());
     let plan_pack_repo: Arc<dyn PlanPackRepository> =
         Arc::new(SqlPlanPackRepository::new((*pool).clone()));
     let task_pack_repo: Arc<dyn TaskPackRepository> =
-        Arc::new(SqlTaskPackRepository::new((*pool).clone()));
+        Arc::new(task_pack_repository((*pool).clone()));
+    let test_scaffold_repo: Arc<dyn TestScaffoldRepository> =
+        Arc::new(SqlTestScaffoldRepository::new((*pool).clone()));
+    let sprint_context_repo: Arc<dyn SprintContextRepository> =
+        Arc::new(SqlSprintContextRepository::new((*pool).clone()));
+    let guardrail_repo: Arc<dyn GuardrailRepository> =
+        Arc::new(SqlGuardrailRepository::new((*pool).clone()));
+    let plan_pack_settings_repo: Arc<dyn PlanPackSettingsRepository> =
+        Arc::new(SqlPlanPackSettingsRepository::new((*pool).clone()));
+
+    let usecases = Arc::new(
+        PromptBuilderUsecases::new(
+            plan_pack_repo,
+            task_pack_repo,
+            test_scaffold_repo,
+            backlog_service,
+            readiness_service,
+            llm_service,
+        )
+        .with_sprint_context_repository(sprint_context_repo)
+        .with_guardrail_repository(guardrail_repo)
+        .with_plan_pack_settings_repository(plan_pack_settings_repo)
+        .with_quota_guard(quota_guard),
+    );
+    projections::PlanPackInvalidationWorker::spawn(usecases.clone(), event_bus);
+    usecases
+}
 
-    Arc::new(PromptBuilderUsecases::new(
-        plan_pack_repo,
-        task_pack_repo,
-        backlog_service,
-        readiness_service,
-        llm_service,
-    ))
+/// Task pack storage, offloading large packs to S3 when `PACK_STORAGE_S3_BUCKET`
+/// is configured. `PACK_OFFLOAD_THRESHOLD_BYTES` (default 256 KiB) sets what
+/// counts as large.
+pub fn task_pack_repository(pool: PgPool) -> SqlTaskPackRepository {
+    let repo = SqlTaskPackRepository::new(pool);
+    match S3BlobStore::from_env() {
+        Some(store) => {
+            let threshold = std::env::var("PACK_OFFLOAD_THRESHOLD_BYTES")
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or(DEFAULT_OFFLOAD_THRESHOLD_BYTES);
+            repo.with_blob_store(Arc::new(store), threshold)
+        }
+        None => repo,
+    }
 }
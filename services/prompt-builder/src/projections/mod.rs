@@ -1,10 +1,21 @@
-use event_bus::{DomainEvent, EventBus, EventEnvelope, SprintEvent, SprintRecord};
+mod plan_packs;
+
+pub use plan_packs::PlanPackInvalidationWorker;
+
+use async_trait::async_trait;
+use event_bus::{
+    apply_with_retry, BacklogEvent, DomainEvent, EventBus, EventEnvelope, ProjectionId,
+    ProjectionReplayer, RetryPolicy, SprintEvent, SprintRecord, StoryRecord,
+};
 use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::task::JoinHandle;
 use tracing::error;
 use uuid::Uuid;
 
+/// Sprints and their stories, for sprint-aware prompts
+const PROJECTION: ProjectionId = ProjectionId::new("prompt-builder", "sprints");
+
 #[derive(Clone)]
 struct SprintProjectionStore {
     pool: Arc<PgPool>,
@@ -15,28 +26,133 @@ impl SprintProjectionStore {
         Self { pool }
     }
 
-    async fn handle_event(&self, envelope: &EventEnvelope) {
-        if let Err(err) = self.apply_event(&envelope.event).await {
+    async fn handle_event(&self, envelope: &EventEnvelope) -> Result<(), sqlx::Error> {
+        let result = envelope.handle(self.apply_event(&envelope.event)).await;
+        if let Err(err) = &result {
             error!(
                 error = %err,
                 event_id = %envelope.id,
+                correlation_id = ?envelope.correlation_id,
                 "Failed to apply sprint event to prompt builder projections"
             );
         }
+        result
     }
 
     async fn apply_event(&self, event: &DomainEvent) -> Result<(), sqlx::Error> {
-        if let DomainEvent::Sprint(sprint_event) = event {
-            match sprint_event {
+        match event {
+            DomainEvent::Sprint(sprint_event) => match sprint_event {
                 SprintEvent::Created { sprint } | SprintEvent::Updated { sprint } => {
                     self.upsert_sprint(sprint).await?
                 }
                 SprintEvent::Deleted { sprint_id, .. } => self.delete_sprint(*sprint_id).await?,
-            }
+                // Membership mostly arrives through story events; sprint lifecycle
+                // rollovers move stories without one
+                SprintEvent::StoryAdded { change } => {
+                    self.move_sprint_story(change.story_id, change.sprint_id)
+                        .await?
+                }
+                SprintEvent::StoryRemoved { change } => {
+                    self.remove_sprint_story(change.story_id, change.sprint_id)
+                        .await?
+                }
+            },
+            DomainEvent::Backlog(backlog_event) => match backlog_event {
+                BacklogEvent::StoryCreated { story } | BacklogEvent::StoryUpdated { story } => {
+                    self.sync_sprint_story(story).await?
+                }
+                BacklogEvent::StoryDeleted { story_id, .. }
+                | BacklogEvent::StoryArchived { story_id, .. } => {
+                    self.delete_sprint_story(*story_id).await?
+                }
+                BacklogEvent::TaskCreated { .. }
+                | BacklogEvent::TaskUpdated { .. }
+                | BacklogEvent::TaskDeleted { .. }
+                | BacklogEvent::StoryDependenciesChanged { .. }
+                | BacklogEvent::StoryBlockersChanged { .. } => {}
+            },
         }
         Ok(())
     }
 
+    /// Track the story while it sits in a sprint, and forget it once it leaves
+    async fn sync_sprint_story(&self, story: &StoryRecord) -> Result<(), sqlx::Error> {
+        let Some(sprint_id) = story.sprint_id else {
+            return self.delete_sprint_story(story.id).await;
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO prompt_sprint_story_projections (
+                story_id,
+                sprint_id,
+                project_id,
+                organization_id,
+                title,
+                status,
+                story_points,
+                updated_at
+            ) VALUES (
+                $1,$2,$3,$4,$5,$6,$7,$8
+            )
+            ON CONFLICT (story_id) DO UPDATE SET
+                sprint_id = EXCLUDED.sprint_id,
+                project_id = EXCLUDED.project_id,
+                organization_id = EXCLUDED.organization_id,
+                title = EXCLUDED.title,
+                status = EXCLUDED.status,
+                story_points = EXCLUDED.story_points,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(story.id)
+        .bind(sprint_id)
+        .bind(story.project_id)
+        .bind(story.organization_id)
+        .bind(&story.title)
+        .bind(&story.status)
+        .bind(story.story_points.map(|v| v as i32))
+        .bind(story.updated_at)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Stories not yet tracked arrive with their story event
+    async fn move_sprint_story(&self, story_id: Uuid, sprint_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE prompt_sprint_story_projections SET sprint_id = $2 WHERE story_id = $1",
+        )
+        .bind(story_id)
+        .bind(sprint_id)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn remove_sprint_story(
+        &self,
+        story_id: Uuid,
+        sprint_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "DELETE FROM prompt_sprint_story_projections WHERE story_id = $1 AND sprint_id = $2",
+        )
+        .bind(story_id)
+        .bind(sprint_id)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_sprint_story(&self, story_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM prompt_sprint_story_projections WHERE story_id = $1")
+            .bind(story_id)
+            .execute(&*self.pool)
+            .await?;
+        Ok(())
+    }
+
     async fn upsert_sprint(&self, sprint: &SprintRecord) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
@@ -108,13 +224,30 @@ impl SprintProjectionWorker {
     pub fn spawn(pool: Arc<PgPool>, event_bus: Arc<EventBus>) -> Self {
         let store = SprintProjectionStore::new(pool);
         let subscription = event_bus.subscribe();
+        event_bus.register_projection(PROJECTION, Arc::new(store.clone()));
         let handle = tokio::spawn(async move {
             loop {
                 let envelope = subscription.recv().await;
-                store.handle_event(&envelope).await;
+                apply_with_retry(
+                    &event_bus,
+                    PROJECTION,
+                    &envelope,
+                    RetryPolicy::default(),
+                    || store.handle_event(&envelope),
+                )
+                .await;
             }
         });
 
         Self { handle }
     }
 }
+
+#[async_trait]
+impl ProjectionReplayer for SprintProjectionStore {
+    async fn reapply(&self, envelope: &EventEnvelope) -> Result<(), String> {
+        self.handle_event(envelope)
+            .await
+            .map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::application::PromptBuilderUsecases;
+use crate::domain::AcceptanceCriterionInfo;
+use event_bus::{BacklogEvent, DomainEvent, EventBus, EventEnvelope, StoryRecord};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::error;
+
+/// Marks a story's plan pack stale when an update leaves its description or
+/// acceptance criteria out of line with the pack
+pub struct PlanPackInvalidationWorker {
+    #[allow(dead_code)]
+    handle: JoinHandle<()>,
+}
+
+impl PlanPackInvalidationWorker {
+    pub fn spawn(usecases: Arc<PromptBuilderUsecases>, event_bus: Arc<EventBus>) -> Self {
+        let subscription = event_bus.subscribe();
+        let handle = tokio::spawn(async move {
+            loop {
+                let envelope = subscription.recv().await;
+                handle_event(&usecases, &envelope).await;
+            }
+        });
+
+        Self { handle }
+    }
+}
+
+async fn handle_event(usecases: &PromptBuilderUsecases, envelope: &EventEnvelope) {
+    let DomainEvent::Backlog(BacklogEvent::StoryUpdated { story }) = &envelope.event else {
+        return;
+    };
+
+    let criteria = story_criteria(story);
+    let result = envelope
+        .handle(usecases.invalidate_plan_pack(
+            story.id,
+            story.organization_id,
+            story.description.as_deref(),
+            &criteria,
+        ))
+        .await;
+    if let Err(err) = result {
+        error!(
+            error = %err,
+            event_id = %envelope.id,
+            story_id = %story.id,
+            "Failed to check the story's plan pack for staleness"
+        );
+    }
+}
+
+fn story_criteria(story: &StoryRecord) -> Vec<AcceptanceCriterionInfo> {
+    story
+        .acceptance_criteria
+        .iter()
+        .map(|ac| AcceptanceCriterionInfo {
+            ac_id: ac.id.to_string(),
+            given: ac.given.clone(),
+            when: ac.when.clone(),
+            then: ac.then.clone(),
+            position: ac.position,
+        })
+        .collect()
+}
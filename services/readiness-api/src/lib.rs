@@ -0,0 +1,13 @@
+//! Public API of the readiness service: how to construct its usecases and
+//! router, the LLM port it needs, and the types other services read.
+//! Everything else in `readiness` is internal to it.
+
+pub use readiness::adapters::http::handlers::ScoringProfilePayload;
+pub use readiness::adapters::integrations::MockLlmService;
+pub use readiness::application::ports::LlmService;
+pub use readiness::application::ReadinessUsecases;
+pub use readiness::domain::{
+    validate_vague_term_overrides, ContentLanguage, ReadinessEvaluation, RepositoryRef,
+    ScoringProfile, TestMapping, VagueTermOverride,
+};
+pub use readiness::{build_usecases, create_readiness_router};
@@ -1,12 +1,15 @@
 use crate::application::ports::StoryInfo;
 use crate::application::{ReadinessUsecases, TaskEnrichmentSuggestion};
 use crate::domain::{
-    AcceptanceCriterion, GapType, ReadinessEvaluation, Recommendation, TaskAnalysis,
+    AcceptanceCriterion, AnalysisFeedback, ContentLanguage, FeedbackVerdict, GapType,
+    GlossaryEntry, ReadinessEvaluation, ReadinessFix, Recommendation, RepositoryRef,
+    ScoringProfile, StoryAnalysisSummary, StorySelection, TaskAnalysis, TestMapping, VagueTerm,
+    VagueTermDictionary, VagueTermOverride,
 };
 use crate::rebuild_projections;
 use auth_clerk::organization::AuthenticatedWithOrg;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -33,7 +36,9 @@ pub struct AddCriteriaRequest {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CriterionRequest {
+    #[serde(alias = "ac_id")]
     pub ac_id: String,
     pub given: String,
     pub when: String,
@@ -41,6 +46,7 @@ pub struct CriterionRequest {
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AcceptanceCriterionResponse {
     pub id: Uuid,
     pub story_id: Uuid,
@@ -64,11 +70,11 @@ impl From<AcceptanceCriterion> for AcceptanceCriterionResponse {
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct StorySummaryResponse {
     pub id: Uuid,
     pub title: String,
     pub description: Option<String>,
-    #[serde(rename = "storyPoints")]
     pub story_points: Option<u32>,
 }
 
@@ -95,6 +101,8 @@ struct TaskAnalysisResponse {
     ai_compatibility_issues: Vec<String>,
     examples: Vec<TaskExampleResponse>,
     recommendations: Vec<RecommendationResponse>,
+    /// Language detected in the task, e.g. "de"
+    language: ContentLanguage,
     analyzed_at: String,
 }
 
@@ -171,6 +179,280 @@ struct TaskExampleResponse {
     task_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisFeedbackRequest {
+    /// The `id` of the recommendation in the task's latest analysis, e.g. "rec-2"
+    pub recommendation_id: String,
+    /// "helpful" or "incorrect"
+    pub verdict: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AnalysisFeedbackResponse {
+    id: Uuid,
+    task_id: Uuid,
+    recommendation_id: String,
+    heuristic: String,
+    verdict: FeedbackVerdict,
+    comment: Option<String>,
+    /// The organization's feedback now keeps this heuristic out of analyses
+    heuristic_suppressed: bool,
+    created_at: String,
+}
+
+impl AnalysisFeedbackResponse {
+    fn new(feedback: AnalysisFeedback, recommendation_id: String, suppressed: bool) -> Self {
+        Self {
+            id: feedback.id,
+            task_id: feedback.task_id,
+            recommendation_id,
+            heuristic: feedback.gap_type.as_str().to_string(),
+            verdict: feedback.verdict,
+            comment: feedback.comment,
+            heuristic_suppressed: suppressed,
+            created_at: feedback.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Stories to evaluate together: the listed IDs, or every story in the
+/// project matching the filters
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BatchEvaluationRequest {
+    pub story_ids: Option<Vec<Uuid>>,
+    pub status: Option<String>,
+    pub label: Option<String>,
+    pub sprint_id: Option<Uuid>,
+}
+
+impl From<BatchEvaluationRequest> for StorySelection {
+    fn from(request: BatchEvaluationRequest) -> Self {
+        Self {
+            story_ids: request.story_ids,
+            status: request.status,
+            label: request.label,
+            sprint_id: request.sprint_id,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VagueTermOverrideRequest {
+    pub term: String,
+    pub suggestion: Option<String>,
+    #[serde(default)]
+    pub removed: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct VagueTermsQuery {
+    /// Language of the dictionary to return, e.g. "de"; English by default
+    pub language: Option<String>,
+}
+
+impl VagueTermsQuery {
+    fn language(&self) -> Result<ContentLanguage, AppError> {
+        self.language
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplaceVagueTermsRequest {
+    pub terms: Vec<VagueTermOverrideRequest>,
+}
+
+#[derive(Debug, Serialize)]
+struct VagueTermsResponse {
+    /// The dictionary the analyzer uses
+    terms: Vec<VagueTerm>,
+    /// The organization's changes to the built-in dictionary
+    overrides: Vec<VagueTermOverride>,
+}
+
+impl VagueTermsResponse {
+    fn new(dictionary: VagueTermDictionary, overrides: Vec<VagueTermOverride>) -> Self {
+        Self {
+            terms: dictionary.terms().to_vec(),
+            overrides,
+        }
+    }
+}
+
+/// A scoring profile as clients send and receive it; omitted fields take the default
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScoringProfilePayload {
+    pub missing_description: i32,
+    pub missing_technical_details: i32,
+    pub vague_language: i32,
+    pub missing_ai_agent_compatibility: i32,
+    pub missing_acceptance_criteria: i32,
+    pub invalid_acceptance_criteria: i32,
+    pub missing_estimate: i32,
+    pub ai_ready_threshold: i32,
+}
+
+impl Default for ScoringProfilePayload {
+    fn default() -> Self {
+        ScoringProfile::default().into()
+    }
+}
+
+impl From<ScoringProfile> for ScoringProfilePayload {
+    fn from(profile: ScoringProfile) -> Self {
+        Self {
+            missing_description: profile.missing_description,
+            missing_technical_details: profile.missing_technical_details,
+            vague_language: profile.vague_language,
+            missing_ai_agent_compatibility: profile.missing_ai_agent_compatibility,
+            missing_acceptance_criteria: profile.missing_acceptance_criteria,
+            invalid_acceptance_criteria: profile.invalid_acceptance_criteria,
+            missing_estimate: profile.missing_estimate,
+            ai_ready_threshold: profile.ai_ready_threshold,
+        }
+    }
+}
+
+impl From<ScoringProfilePayload> for ScoringProfile {
+    fn from(payload: ScoringProfilePayload) -> Self {
+        Self {
+            missing_description: payload.missing_description,
+            missing_technical_details: payload.missing_technical_details,
+            vague_language: payload.vague_language,
+            missing_ai_agent_compatibility: payload.missing_ai_agent_compatibility,
+            missing_acceptance_criteria: payload.missing_acceptance_criteria,
+            invalid_acceptance_criteria: payload.invalid_acceptance_criteria,
+            missing_estimate: payload.missing_estimate,
+            ai_ready_threshold: payload.ai_ready_threshold,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct GlossaryQuery {
+    /// Only return the entries that appear in this text, e.g. for tooltips
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GlossaryEntryRequest {
+    pub term: String,
+    pub definition: String,
+    #[serde(default)]
+    pub synonyms: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GlossaryEntryResponse {
+    id: Uuid,
+    term: String,
+    definition: String,
+    synonyms: Vec<String>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<GlossaryEntry> for GlossaryEntryResponse {
+    fn from(entry: GlossaryEntry) -> Self {
+        Self {
+            id: entry.id,
+            term: entry.term,
+            definition: entry.definition,
+            synonyms: entry.synonyms,
+            updated_at: entry.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AnalysisSummaryQuery {
+    /// Rebuild the summary from the task analyses instead of serving the stored one
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StoryAnalysisSummaryResponse {
+    story_id: Uuid,
+    task_count: usize,
+    average_clarity: Option<i32>,
+    level: Option<String>,
+    lowest_clarity: Option<TaskClarityResponse>,
+    /// Tasks below the organization's AI-readiness threshold
+    tasks_needing_attention: Vec<Uuid>,
+    ai_ready_threshold: i32,
+    missing_elements: Vec<MissingElementCountResponse>,
+    /// When the summary last changed; clients flag old summaries as stale
+    updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskClarityResponse {
+    task_id: Uuid,
+    score: i32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MissingElementCountResponse {
+    element: String,
+    task_count: usize,
+}
+
+impl StoryAnalysisSummaryResponse {
+    fn new(summary: StoryAnalysisSummary, scoring: &ScoringProfile) -> Self {
+        let average_clarity = summary.average_clarity();
+        Self {
+            story_id: summary.story_id,
+            task_count: summary.tasks.len(),
+            average_clarity,
+            level: average_clarity.map(|score| clarity_level(score).to_string()),
+            lowest_clarity: summary
+                .lowest_clarity()
+                .map(|(task_id, score)| TaskClarityResponse { task_id, score }),
+            tasks_needing_attention: summary.tasks_needing_attention(scoring),
+            ai_ready_threshold: scoring.ai_ready_threshold,
+            missing_elements: summary
+                .missing_element_counts()
+                .into_iter()
+                .map(|(element, task_count)| MissingElementCountResponse {
+                    element,
+                    task_count,
+                })
+                .collect(),
+            updated_at: summary.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TestMappingQuery {
+    /// GitHub repository as `owner/name`
+    pub repository: String,
+    /// Branch, tag or commit; the default branch when omitted
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TestMappingResponse {
+    #[serde(flatten)]
+    mapping: TestMapping,
+    /// Criteria no test file looks related to; candidates for new tests
+    uncovered_criteria: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskEnrichmentRequest {
@@ -214,6 +496,7 @@ impl TaskAnalysisResponse {
             ai_compatibility_issues: build_ai_compatibility_issues(&analysis.recommendations),
             examples: default_examples(),
             recommendations: build_recommendations(&analysis.recommendations),
+            language: analysis.language,
             analyzed_at: analyzed_at.to_rfc3339(),
         }
     }
@@ -565,14 +848,14 @@ fn default_examples() -> Vec<TaskExampleResponse> {
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ReadinessEvaluationResponse {
     pub score: i32,
-    #[serde(rename = "missingItems")]
     pub missing_items: Vec<String>,
     pub recommendations: Vec<String>,
     pub summary: String,
-    #[serde(rename = "isReady")]
     pub is_ready: bool,
+    pub fixes: Vec<ReadinessFix>,
 }
 
 impl From<ReadinessEvaluation> for ReadinessEvaluationResponse {
@@ -583,6 +866,7 @@ impl From<ReadinessEvaluation> for ReadinessEvaluationResponse {
             missing_items,
             recommendations,
             summary,
+            fixes,
             ..
         } = eval;
 
@@ -592,10 +876,80 @@ impl From<ReadinessEvaluation> for ReadinessEvaluationResponse {
             recommendations,
             summary,
             is_ready,
+            fixes,
         }
     }
 }
 
+/// Drafts with more criteria than this are rejected; lint runs on every keystroke
+const MAX_DRAFT_CRITERIA: usize = 50;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryDraftRequest {
+    #[serde(default)]
+    pub title: String,
+    pub description: Option<String>,
+    pub story_points: Option<u32>,
+    #[serde(default)]
+    pub acceptance_criteria: Vec<DraftCriterionRequest>,
+}
+
+/// A criterion as typed so far; blank parts are linted rather than rejected
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DraftCriterionRequest {
+    pub ac_id: Option<String>,
+    pub given: String,
+    pub when: String,
+    pub then: String,
+}
+
+pub async fn lint_story_draft(
+    auth: AuthenticatedWithOrg,
+    State(state): State<ReadinessAppState>,
+    Json(payload): Json<StoryDraftRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if payload.acceptance_criteria.len() > MAX_DRAFT_CRITERIA {
+        return Err(AppError::BadRequest(format!(
+            "A draft can have at most {} acceptance criteria",
+            MAX_DRAFT_CRITERIA
+        )));
+    }
+
+    let organization_id = auth.org_context.effective_organization_uuid();
+    let draft = StoryInfo {
+        id: Uuid::nil(),
+        title: payload.title,
+        description: payload
+            .description
+            .filter(|description| !description.trim().is_empty()),
+        story_points: payload.story_points,
+    };
+    let criteria: Vec<AcceptanceCriterion> = payload
+        .acceptance_criteria
+        .into_iter()
+        .enumerate()
+        .map(|(index, criterion)| AcceptanceCriterion {
+            id: Uuid::new_v4(),
+            story_id: draft.id,
+            organization_id,
+            ac_id: criterion
+                .ac_id
+                .filter(|ac_id| !ac_id.trim().is_empty())
+                .unwrap_or_else(|| format!("AC{}", index + 1)),
+            given: criterion.given.trim().to_string(),
+            when: criterion.when.trim().to_string(),
+            then: criterion.then.trim().to_string(),
+        })
+        .collect();
+
+    let evaluation = state
+        .usecases
+        .lint_story_draft(organization_id, &draft, &criteria);
+    Ok(Json(ReadinessEvaluationResponse::from(evaluation)))
+}
+
 pub async fn evaluate_readiness(
     auth: AuthenticatedWithOrg,
     Path(story_id): Path<Uuid>,
@@ -639,6 +993,36 @@ pub async fn evaluate_readiness(
     Ok(Json(ReadinessEvaluationResponse::from(evaluation)))
 }
 
+pub async fn evaluate_readiness_batch(
+    auth: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    State(state): State<ReadinessAppState>,
+    Json(payload): Json<BatchEvaluationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let organization_id = auth.org_context.effective_organization_uuid();
+    info!(
+        %project_id,
+        org_id = ?organization_id,
+        user = %auth.auth.sub,
+        "Evaluating readiness for a batch of stories"
+    );
+
+    let certification = state
+        .usecases
+        .evaluate_story_batch(project_id, organization_id, &payload.into())
+        .await?;
+
+    info!(
+        %project_id,
+        org_id = ?organization_id,
+        evaluated = certification.evaluated,
+        passed = certification.passed,
+        "Batch readiness evaluation completed"
+    );
+
+    Ok(Json(certification))
+}
+
 pub async fn generate_criteria(
     auth: AuthenticatedWithOrg,
     Path(story_id): Path<Uuid>,
@@ -788,6 +1172,224 @@ pub async fn get_task_analysis(
     Ok(Json(response))
 }
 
+pub async fn get_vague_terms(
+    auth: AuthenticatedWithOrg,
+    Query(query): Query<VagueTermsQuery>,
+    State(state): State<ReadinessAppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let language = query.language()?;
+    let org_id = auth.org_context.effective_organization_uuid();
+    let overrides = state.usecases.get_vague_term_overrides(org_id).await?;
+    let dictionary = state.usecases.vague_terms(org_id, language).await?;
+    Ok(Json(VagueTermsResponse::new(dictionary, overrides)))
+}
+
+pub async fn replace_vague_terms(
+    auth: AuthenticatedWithOrg,
+    Query(query): Query<VagueTermsQuery>,
+    State(state): State<ReadinessAppState>,
+    Json(payload): Json<ReplaceVagueTermsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let language = query.language()?;
+    let org_id = auth.org_context.effective_organization_uuid();
+    let overrides = payload
+        .terms
+        .into_iter()
+        .map(|entry| VagueTermOverride::new(&entry.term, entry.suggestion, entry.removed))
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    let dictionary = state
+        .usecases
+        .replace_vague_term_overrides(org_id, overrides.clone(), language)
+        .await?;
+    info!(org_id = ?org_id, user = %auth.auth.sub, changes = overrides.len(), "Vague terms updated");
+    Ok(Json(VagueTermsResponse::new(dictionary, overrides)))
+}
+
+pub async fn get_scoring_profile(
+    auth: AuthenticatedWithOrg,
+    State(state): State<ReadinessAppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = auth.org_context.effective_organization_uuid();
+    let profile = state.usecases.scoring_profile(org_id).await?;
+    Ok(Json(ScoringProfilePayload::from(profile)))
+}
+
+pub async fn replace_scoring_profile(
+    auth: AuthenticatedWithOrg,
+    State(state): State<ReadinessAppState>,
+    Json(payload): Json<ScoringProfilePayload>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = auth.org_context.effective_organization_uuid();
+    let profile = state
+        .usecases
+        .replace_scoring_profile(org_id, payload.into())
+        .await?;
+    info!(
+        org_id = ?org_id,
+        user = %auth.auth.sub,
+        ai_ready_threshold = profile.ai_ready_threshold,
+        "Scoring profile updated"
+    );
+    Ok(Json(ScoringProfilePayload::from(profile)))
+}
+
+pub async fn get_glossary(
+    auth: AuthenticatedWithOrg,
+    Query(query): Query<GlossaryQuery>,
+    State(state): State<ReadinessAppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = auth.org_context.effective_organization_uuid();
+    let entries = match query.text.as_deref() {
+        Some(text) => state.usecases.glossary_for_text(org_id, text).await?,
+        None => state.usecases.get_glossary(org_id).await?,
+    };
+    Ok(Json(
+        entries
+            .into_iter()
+            .map(GlossaryEntryResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+pub async fn add_glossary_entry(
+    auth: AuthenticatedWithOrg,
+    State(state): State<ReadinessAppState>,
+    Json(payload): Json<GlossaryEntryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = auth.org_context.effective_organization_uuid();
+    let entry = state
+        .usecases
+        .add_glossary_entry(org_id, &payload.term, &payload.definition, payload.synonyms)
+        .await?;
+    info!(org_id = ?org_id, user = %auth.auth.sub, term = %entry.term, "Glossary term added");
+    Ok((
+        StatusCode::CREATED,
+        Json(GlossaryEntryResponse::from(entry)),
+    ))
+}
+
+pub async fn update_glossary_entry(
+    auth: AuthenticatedWithOrg,
+    Path(entry_id): Path<Uuid>,
+    State(state): State<ReadinessAppState>,
+    Json(payload): Json<GlossaryEntryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = auth.org_context.effective_organization_uuid();
+    let entry = state
+        .usecases
+        .update_glossary_entry(
+            org_id,
+            entry_id,
+            &payload.term,
+            &payload.definition,
+            payload.synonyms,
+        )
+        .await?;
+    info!(org_id = ?org_id, user = %auth.auth.sub, term = %entry.term, "Glossary term updated");
+    Ok(Json(GlossaryEntryResponse::from(entry)))
+}
+
+pub async fn delete_glossary_entry(
+    auth: AuthenticatedWithOrg,
+    Path(entry_id): Path<Uuid>,
+    State(state): State<ReadinessAppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = auth.org_context.effective_organization_uuid();
+    state
+        .usecases
+        .delete_glossary_entry(org_id, entry_id)
+        .await?;
+    info!(org_id = ?org_id, user = %auth.auth.sub, %entry_id, "Glossary term deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn submit_analysis_feedback(
+    auth: AuthenticatedWithOrg,
+    Path(task_id): Path<Uuid>,
+    State(state): State<ReadinessAppState>,
+    Json(payload): Json<AnalysisFeedbackRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let verdict = FeedbackVerdict::parse(&payload.verdict)
+        .ok_or_else(|| AppError::BadRequest(format!("Invalid verdict: {}", payload.verdict)))?;
+
+    let org_id = auth.org_context.effective_organization_uuid();
+    let feedback = state
+        .usecases
+        .submit_analysis_feedback(
+            task_id,
+            org_id,
+            &auth.auth.sub,
+            &payload.recommendation_id,
+            verdict,
+            payload.comment,
+        )
+        .await?;
+    let suppressed = state
+        .usecases
+        .is_heuristic_suppressed(org_id, feedback.gap_type)
+        .await?;
+    info!(
+        %task_id,
+        org_id = ?org_id,
+        heuristic = feedback.gap_type.as_str(),
+        verdict = feedback.verdict.as_str(),
+        suppressed,
+        "Task analysis feedback recorded"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AnalysisFeedbackResponse::new(
+            feedback,
+            payload.recommendation_id,
+            suppressed,
+        )),
+    ))
+}
+
+pub async fn get_story_analysis_summary(
+    auth: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    Query(query): Query<AnalysisSummaryQuery>,
+    State(state): State<ReadinessAppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let org_id = auth.org_context.effective_organization_uuid();
+    let summary = state
+        .usecases
+        .get_story_analysis_summary(story_id, org_id, query.refresh)
+        .await?;
+    let scoring = state.usecases.scoring_profile(org_id).await?;
+
+    Ok(Json(StoryAnalysisSummaryResponse::new(summary, &scoring)))
+}
+
+pub async fn get_story_test_mapping(
+    auth: AuthenticatedWithOrg,
+    Path(story_id): Path<Uuid>,
+    Query(query): Query<TestMappingQuery>,
+    State(state): State<ReadinessAppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let repository = RepositoryRef::new(&query.repository, query.git_ref.as_deref())?;
+    let mapping = state
+        .usecases
+        .suggest_test_mapping(
+            story_id,
+            auth.org_context.effective_organization_uuid(),
+            repository,
+        )
+        .await?;
+
+    Ok(Json(TestMappingResponse {
+        uncovered_criteria: mapping
+            .uncovered_criteria()
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        mapping,
+    }))
+}
+
 pub async fn enrich_task(
     auth: AuthenticatedWithOrg,
     Path(task_id): Path<Uuid>,
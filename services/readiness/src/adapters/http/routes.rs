@@ -0,0 +1,91 @@
+use crate::adapters::http::handlers::{
+    add_criteria, add_glossary_entry, analyze_task, delete_glossary_entry, enrich_task,
+    evaluate_readiness, evaluate_readiness_batch, generate_criteria, get_criteria, get_glossary,
+    get_scoring_profile, get_story_analysis_summary, get_story_test_mapping, get_task_analysis,
+    get_vague_terms, lint_story_draft, rehydrate_projections, replace_scoring_profile,
+    replace_vague_terms, submit_analysis_feedback, update_glossary_entry, ReadinessAppState,
+};
+use crate::application::ReadinessUsecases;
+use auth_clerk::JwtVerifier;
+use axum::routing::{get, post, put};
+use axum::{Extension, Router};
+use common::route_registry::ServiceRouter;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tower_http::trace::TraceLayer;
+
+pub fn create_readiness_router(
+    pool: PgPool,
+    readiness_usecases: Arc<ReadinessUsecases>,
+    verifier: Arc<Mutex<JwtVerifier>>,
+) -> Router {
+    let state = ReadinessAppState {
+        usecases: readiness_usecases,
+        pool: Arc::new(pool),
+    };
+
+    ServiceRouter::new("readiness")
+        .route("/api/v1/readiness/lint", post(lint_story_draft))
+        .route(
+            "/api/v1/readiness/{story_id}/evaluate",
+            post(evaluate_readiness),
+        )
+        .route(
+            "/api/v1/readiness/projects/{project_id}/evaluate-batch",
+            post(evaluate_readiness_batch),
+        )
+        .route(
+            "/api/v1/readiness/criteria/{story_id}/generate",
+            post(generate_criteria),
+        )
+        .route("/api/v1/readiness/criteria/{story_id}", get(get_criteria))
+        .route("/api/v1/readiness/criteria/{story_id}", post(add_criteria))
+        .route(
+            "/api/v1/readiness/projections/hydrate",
+            post(rehydrate_projections),
+        )
+        .route(
+            "/api/v1/readiness/tasks/{task_id}/analyze",
+            post(analyze_task),
+        )
+        .route(
+            "/api/v1/readiness/tasks/{task_id}/analysis",
+            get(get_task_analysis),
+        )
+        .route(
+            "/api/v1/readiness/tasks/{task_id}/analysis/feedback",
+            post(submit_analysis_feedback),
+        )
+        .route(
+            "/api/v1/readiness/tasks/{task_id}/enrich",
+            post(enrich_task),
+        )
+        .route(
+            "/api/v1/readiness/stories/{story_id}/analysis-summary",
+            get(get_story_analysis_summary),
+        )
+        .route(
+            "/api/v1/readiness/stories/{story_id}/test-mapping",
+            get(get_story_test_mapping),
+        )
+        .route(
+            "/api/v1/readiness/vague-terms",
+            get(get_vague_terms).put(replace_vague_terms),
+        )
+        .route(
+            "/api/v1/readiness/scoring-profile",
+            get(get_scoring_profile).put(replace_scoring_profile),
+        )
+        .route(
+            "/api/v1/readiness/glossary",
+            get(get_glossary).post(add_glossary_entry),
+        )
+        .route(
+            "/api/v1/readiness/glossary/{entry_id}",
+            put(update_glossary_entry).delete(delete_glossary_entry),
+        )
+        .with_state(state)
+        .layer(Extension(verifier))
+        .layer(TraceLayer::new_for_http())
+}
@@ -1,5 +1,8 @@
 use crate::application::ports::{StoryInfo, StoryService, TaskInfo};
+use crate::domain::StorySelection;
 use async_trait::async_trait;
+use backlog::application::StoryQuery;
+use backlog::domain::StoryCondition;
 use common::AppError;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -74,6 +77,45 @@ impl StoryService for InProcessBacklogService {
             estimated_hours: task.estimated_hours,
         }))
     }
+
+    async fn find_project_stories(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        selection: &StorySelection,
+    ) -> Result<Vec<Uuid>, AppError> {
+        let status = match selection.status.as_deref() {
+            Some(value) => Some(backlog::domain::StoryStatus::from_str(value).ok_or_else(
+                || AppError::BadRequest(format!("Invalid status filter: {}", value)),
+            )?),
+            None => None,
+        };
+        let query = status
+            .map(StoryCondition::Status)
+            .into_iter()
+            .chain(selection.sprint_id.map(StoryCondition::Sprint))
+            .fold(StoryQuery::default(), StoryQuery::with_condition);
+        let page = self
+            .backlog
+            .get_stories_by_project(project_id, organization_id, &query)
+            .await?;
+
+        Ok(page
+            .stories
+            .into_iter()
+            .filter(|story| {
+                selection
+                    .story_ids
+                    .as_ref()
+                    .is_none_or(|ids| ids.contains(&story.id))
+                    && selection
+                        .label
+                        .as_ref()
+                        .is_none_or(|label| story.labels.contains(label))
+            })
+            .map(|story| story.id)
+            .collect())
+    }
 }
 
 #[allow(dead_code)]
@@ -278,4 +320,72 @@ impl StoryService for HttpBacklogService {
             estimated_hours: task.estimated_hours,
         }))
     }
+    async fn find_project_stories(
+        &self,
+        project_id: Uuid,
+        _organization_id: Option<Uuid>,
+        selection: &StorySelection,
+    ) -> Result<Vec<Uuid>, AppError> {
+        let url = format!("{}/projects/{}/stories", self.base_url, project_id);
+        let mut query = Vec::new();
+        if let Some(status) = &selection.status {
+            query.push(("status", status.clone()));
+        }
+        if let Some(sprint_id) = selection.sprint_id {
+            query.push(("sprintId", sprint_id.to_string()));
+        }
+        let response = self
+            .client
+            .get(&url)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|err| {
+                error!(
+                    error = %err,
+                    %project_id,
+                    "Failed to contact backlog service for project stories"
+                );
+                AppError::InternalServerError
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "<body unavailable>".to_string());
+            warn!(
+                %project_id,
+                %status,
+                body = %body,
+                "Backlog service returned non-success status for project stories"
+            );
+            return Err(AppError::InternalServerError);
+        }
+
+        let stories: Vec<StoryResponse> = response.json().await.map_err(|err| {
+            error!(
+                error = %err,
+                %project_id,
+                "Failed to deserialize project stories from backlog service"
+            );
+            AppError::InternalServerError
+        })?;
+
+        Ok(stories
+            .into_iter()
+            .filter(|story| {
+                selection
+                    .story_ids
+                    .as_ref()
+                    .is_none_or(|ids| ids.contains(&story.id))
+                    && selection
+                        .label
+                        .as_ref()
+                        .is_none_or(|label| story.labels.contains(label))
+            })
+            .map(|story| story.id)
+            .collect())
+    }
 }
@@ -0,0 +1,122 @@
+use crate::application::ports::RepositoryService;
+use crate::domain::RepositoryRef;
+use async_trait::async_trait;
+use common::circuit_breaker::CircuitBreaker;
+use common::outbound_http::OutboundHttpClient;
+use common::AppError;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::time::Duration;
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+#[derive(Debug, Deserialize)]
+struct TreeResponse {
+    tree: Vec<TreeEntry>,
+    #[serde(default)]
+    truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TreeEntry {
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+/// Reads repository file trees from the GitHub REST API. Without a token
+/// only public repositories are visible, at GitHub's lower rate limit.
+pub struct GitHubRepositoryService {
+    client: OutboundHttpClient,
+    api_url: String,
+    token: Option<String>,
+}
+
+impl GitHubRepositoryService {
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            client: OutboundHttpClient::builder()
+                .request_timeout(Duration::from_secs(30))
+                .build(),
+            api_url: GITHUB_API_URL.to_string(),
+            token,
+        }
+    }
+
+    /// Authenticates with `GITHUB_TOKEN` when it is set
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var("GITHUB_TOKEN")
+                .ok()
+                .filter(|token| !token.is_empty()),
+        )
+    }
+}
+
+#[async_trait]
+impl RepositoryService for GitHubRepositoryService {
+    async fn list_files(&self, repository: &RepositoryRef) -> Result<Vec<String>, AppError> {
+        let url = format!(
+            "{}/repos/{}/git/trees/{}",
+            self.api_url, repository.repository, repository.git_ref
+        );
+
+        let tree: Option<TreeResponse> = CircuitBreaker::for_dependency("github")
+            .call(async {
+                let mut request = self
+                    .client
+                    .get(&url)
+                    .query(&[("recursive", "1")])
+                    .header("Accept", "application/vnd.github+json")
+                    .header("User-Agent", "gamalan-readiness")
+                    .header("X-GitHub-Api-Version", "2022-11-28");
+                if let Some(token) = &self.token {
+                    request = request.header("Authorization", format!("Bearer {}", token));
+                }
+                let response = self
+                    .client
+                    .send("readiness.github.tree", request)
+                    .await
+                    .map_err(|e| {
+                        AppError::ExternalServiceError(format!("GitHub request failed: {}", e))
+                    })?;
+
+                match response.status() {
+                    // Unknown repositories and refs are the caller's mistake, not an outage
+                    StatusCode::NOT_FOUND | StatusCode::UNPROCESSABLE_ENTITY => Ok(None),
+                    status if !status.is_success() => Err(AppError::ExternalServiceError(format!(
+                        "GitHub responded with {}",
+                        status
+                    ))),
+                    _ => response.json().await.map(Some).map_err(|e| {
+                        AppError::ExternalServiceError(format!(
+                            "GitHub returned an unreadable tree: {}",
+                            e
+                        ))
+                    }),
+                }
+            })
+            .await?;
+
+        let tree = tree.ok_or_else(|| {
+            AppError::NotFound(format!(
+                "Repository {} at {} not found",
+                repository.repository, repository.git_ref
+            ))
+        })?;
+        if tree.truncated {
+            tracing::warn!(
+                repository = %repository.repository,
+                git_ref = %repository.git_ref,
+                "GitHub truncated the repository tree; matching against part of it"
+            );
+        }
+
+        Ok(tree
+            .tree
+            .into_iter()
+            .filter(|entry| entry.kind == "blob")
+            .map(|entry| entry.path)
+            .collect())
+    }
+}
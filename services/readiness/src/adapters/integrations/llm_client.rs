@@ -1,8 +1,11 @@
-use crate::application::ports::{LlmService, StoryInfo};
+use crate::application::ports::{LlmService, PromptGuidance, StoryInfo};
 use crate::domain::AcceptanceCriterion;
 use async_trait::async_trait;
+use common::circuit_breaker::CircuitBreaker;
+use common::outbound_http::OutboundHttpClient;
 use common::AppError;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 // Mock implementation for development
 pub struct MockLlmService;
@@ -12,6 +15,7 @@ impl LlmService for MockLlmService {
     async fn generate_acceptance_criteria(
         &self,
         story_info: &StoryInfo,
+        _guidance: &PromptGuidance,
     ) -> Result<Vec<AcceptanceCriterion>, AppError> {
         // Generate simple default criteria for development
         let criteria = vec![
@@ -66,7 +70,7 @@ struct ResponseMessage {
 }
 
 pub struct OpenAiLlmService {
-    client: reqwest::Client,
+    client: OutboundHttpClient,
     api_key: String,
     model: String,
 }
@@ -74,19 +78,21 @@ pub struct OpenAiLlmService {
 impl OpenAiLlmService {
     pub fn new(api_key: String, model: Option<String>) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: OutboundHttpClient::builder()
+                .request_timeout(Duration::from_secs(60))
+                .build(),
             api_key,
             model: model.unwrap_or_else(|| "gpt-3.5-turbo".to_string()),
         }
     }
 
-    fn create_prompt(&self, story_info: &StoryInfo) -> String {
+    fn create_prompt(&self, story_info: &StoryInfo, guidance: &PromptGuidance) -> String {
         let description = story_info
             .description
             .as_deref()
             .unwrap_or("No description provided");
 
-        format!(
+        let mut prompt = format!(
             "Given the following user story, generate 2-5 acceptance criteria in Given/When/Then format.\n\n\
             Story Title: {}\n\
             Description: {}\n\n\
@@ -94,7 +100,52 @@ impl OpenAiLlmService {
             {{\"ac_id\": \"AC1\", \"given\": \"...\", \"when\": \"...\", \"then\": \"...\"}}\n\n\
             Make sure each ac_id is unique (AC1, AC2, AC3, etc.) and each criterion is specific and testable.",
             story_info.title, description
-        )
+        );
+
+        prompt.push_str(&format!(
+            "\n\nThe story is written in {language}. Write the given, when and then text in {language}; \
+             keep the JSON keys and ac_id values in English.",
+            language = guidance.language.name()
+        ));
+
+        if !guidance.vague_terms.is_empty() {
+            prompt.push_str(
+                "\n\nThis team considers the following terms vague. Do not use them in the criteria:\n",
+            );
+            for term in &guidance.vague_terms {
+                prompt.push_str(&format!("- \"{}\": {}\n", term.term, term.suggestion));
+            }
+        }
+
+        if !guidance.glossary.is_empty() {
+            prompt.push_str(
+                "\n\nThe story uses the following team terms. Use them with these meanings:\n",
+            );
+            for entry in &guidance.glossary {
+                if entry.synonyms.is_empty() {
+                    prompt.push_str(&format!("- {}: {}\n", entry.term, entry.definition));
+                } else {
+                    prompt.push_str(&format!(
+                        "- {} (also: {}): {}\n",
+                        entry.term,
+                        entry.synonyms.join(", "),
+                        entry.definition
+                    ));
+                }
+            }
+        }
+
+        if !guidance.calibration.is_empty() {
+            prompt.push_str(
+                "\n\nThis team has given the following feedback on earlier readiness suggestions. \
+                 Avoid what they marked incorrect and keep to what they found helpful:\n",
+            );
+            for note in &guidance.calibration {
+                prompt.push_str(&format!("- {}\n", note));
+            }
+        }
+
+        prompt
     }
 }
 
@@ -103,8 +154,9 @@ impl LlmService for OpenAiLlmService {
     async fn generate_acceptance_criteria(
         &self,
         story_info: &StoryInfo,
+        guidance: &PromptGuidance,
     ) -> Result<Vec<AcceptanceCriterion>, AppError> {
-        let prompt = self.create_prompt(story_info);
+        let prompt = self.create_prompt(story_info, guidance);
 
         let request = GenerateRequest {
             model: self.model.clone(),
@@ -115,24 +167,31 @@ impl LlmService for OpenAiLlmService {
             temperature: 0.3,
         };
 
-        let response = self
-            .client
-            .post("https://api.openai.com/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .map_err(|_| AppError::InternalServerError)?;
-
-        if !response.status().is_success() {
-            return Err(AppError::InternalServerError);
-        }
-
-        let response_data: GenerateResponse = response
-            .json()
-            .await
-            .map_err(|_| AppError::InternalServerError)?;
+        let response_data: GenerateResponse = CircuitBreaker::for_dependency("openai")
+            .call(async {
+                let response = self
+                    .client
+                    .send(
+                        "readiness.openai.chat",
+                        self.client
+                            .post("https://api.openai.com/v1/chat/completions")
+                            .header("Authorization", format!("Bearer {}", self.api_key))
+                            .header("Content-Type", "application/json")
+                            .json(&request),
+                    )
+                    .await
+                    .map_err(|_| AppError::InternalServerError)?;
+
+                if !response.status().is_success() {
+                    return Err(AppError::InternalServerError);
+                }
+
+                response
+                    .json()
+                    .await
+                    .map_err(|_| AppError::InternalServerError)
+            })
+            .await?;
 
         let content = response_data
             .choices
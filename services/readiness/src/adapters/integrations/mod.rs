@@ -1,5 +1,7 @@
 pub mod backlog_client;
+pub mod github_client;
 pub mod llm_client;
 
 pub use backlog_client::*;
+pub use github_client::*;
 pub use llm_client::*;
@@ -1,4 +1,5 @@
 use crate::domain::{AcceptanceCriterion, ReadinessEvaluation};
+use serde_json::Value;
 use sqlx::FromRow;
 use uuid::Uuid;
 
@@ -36,6 +37,7 @@ pub struct ReadinessEvaluationRow {
     pub missing_items: Vec<String>,
     pub summary: String,
     pub recommendations: Vec<String>,
+    pub fixes: Value,
 }
 
 impl From<ReadinessEvaluationRow> for ReadinessEvaluation {
@@ -48,6 +50,8 @@ impl From<ReadinessEvaluationRow> for ReadinessEvaluation {
             missing_items: row.missing_items,
             summary: row.summary,
             recommendations: row.recommendations,
+            // Rows written before fixes were recorded have an empty list
+            fixes: serde_json::from_value(row.fixes).unwrap_or_default(),
         }
     }
 }
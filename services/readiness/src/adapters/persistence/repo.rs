@@ -1,8 +1,12 @@
 use crate::adapters::persistence::models::{AcceptanceCriterionRow, ReadinessEvaluationRow};
 use crate::application::ports::{
-    AcceptanceCriteriaRepository, ReadinessEvaluationRepository, TaskAnalysisRepository,
+    AcceptanceCriteriaRepository, GlossaryRepository, ReadinessEvaluationRepository,
+    ScoringProfileRepository, TaskAnalysisRepository, VagueTermRepository,
+};
+use crate::domain::{
+    AcceptanceCriterion, AnalysisFeedback, FeedbackVerdict, GapType, GlossaryEntry, HeuristicTally,
+    ReadinessEvaluation, ScoringProfile, StoryAnalysisSummary, TaskAnalysis, VagueTermOverride,
 };
-use crate::domain::{AcceptanceCriterion, ReadinessEvaluation, TaskAnalysis};
 use async_trait::async_trait;
 use common::AppError;
 use event_bus::AcceptanceCriterionRecord;
@@ -314,9 +318,14 @@ pub async fn get_criterion_by_story_and_ac_id(
 }
 
 pub async fn save_evaluation(pool: &PgPool, eval: &ReadinessEvaluation) -> Result<(), AppError> {
+    let fixes = serde_json::to_value(&eval.fixes).map_err(|err| {
+        error!(error = %err, eval_id = %eval.id, "Failed to serialize readiness fixes");
+        AppError::InternalServerError
+    })?;
+
     sqlx::query(
-        "INSERT INTO readiness_evals (id, story_id, organization_id, score, missing_items, summary, recommendations) \
-         VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        "INSERT INTO readiness_evals (id, story_id, organization_id, score, missing_items, summary, recommendations, fixes) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
     )
     .bind(eval.id)
     .bind(eval.story_id)
@@ -325,6 +334,7 @@ pub async fn save_evaluation(pool: &PgPool, eval: &ReadinessEvaluation) -> Resul
     .bind(&eval.missing_items)
     .bind(&eval.summary)
     .bind(&eval.recommendations)
+    .bind(fixes)
     .execute(pool)
     .await
     .map_err(|err| {
@@ -346,9 +356,9 @@ pub async fn get_latest_evaluation(
     organization_id: Option<Uuid>,
 ) -> Result<Option<ReadinessEvaluation>, AppError> {
     let row = sqlx::query_as::<_, ReadinessEvaluationRow>(
-        "SELECT id, story_id, organization_id, score, missing_items, summary, recommendations FROM readiness_evals \
+        "SELECT id, story_id, organization_id, score, missing_items, summary, recommendations, fixes FROM readiness_evals \
          WHERE story_id = $1 AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL)) \
-         ORDER BY id DESC \
+         ORDER BY evaluated_at DESC \
          LIMIT 1",
     )
     .bind(story_id)
@@ -476,4 +486,483 @@ impl TaskAnalysisRepository for PgPool {
             Ok(None)
         }
     }
+
+    async fn get_latest_analyses_for_story(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<TaskAnalysis>, AppError> {
+        let rows = sqlx::query(
+            "SELECT DISTINCT ON (task_id) analysis_json FROM task_analyses \
+             WHERE story_id = $1 AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL)) \
+             ORDER BY task_id, created_at DESC",
+        )
+        .bind(story_id)
+        .bind(organization_id)
+        .fetch_all(self)
+        .await
+        .map_err(|err| {
+            error!(error = %err, %story_id, "Failed to fetch task analyses for story");
+            AppError::InternalServerError
+        })?;
+
+        rows.into_iter()
+            .map(|row| {
+                serde_json::from_value(row.get("analysis_json")).map_err(|err| {
+                    error!(error = %err, %story_id, "Failed to deserialize task analysis");
+                    AppError::InternalServerError
+                })
+            })
+            .collect()
+    }
+
+    async fn get_story_summary(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<StoryAnalysisSummary>, AppError> {
+        let row = sqlx::query(
+            "SELECT tasks, updated_at FROM story_analysis_summaries \
+             WHERE story_id = $1 AND (organization_id = $2 OR ($2 IS NULL AND organization_id IS NULL))",
+        )
+        .bind(story_id)
+        .bind(organization_id)
+        .fetch_optional(self)
+        .await
+        .map_err(|err| {
+            error!(error = %err, %story_id, "Failed to fetch story analysis summary");
+            AppError::InternalServerError
+        })?;
+
+        row.map(|row| {
+            let tasks = serde_json::from_value(row.get("tasks")).map_err(|err| {
+                error!(error = %err, %story_id, "Failed to deserialize story analysis summary");
+                AppError::InternalServerError
+            })?;
+            Ok(StoryAnalysisSummary {
+                story_id,
+                organization_id,
+                tasks,
+                updated_at: row.get("updated_at"),
+            })
+        })
+        .transpose()
+    }
+
+    async fn save_story_summary(&self, summary: &StoryAnalysisSummary) -> Result<(), AppError> {
+        write_story_summary(self, summary).await
+    }
+
+    async fn record_in_story_summary(&self, analysis: &TaskAnalysis) -> Result<bool, AppError> {
+        let story_id = analysis.story_id;
+        let mut tx = self.begin().await.map_err(|err| {
+            error!(error = %err, %story_id, "Failed to begin story analysis summary update");
+            AppError::InternalServerError
+        })?;
+
+        // Lock the row so concurrent analyses of sibling tasks are not lost
+        let row = sqlx::query(
+            "SELECT tasks, updated_at FROM story_analysis_summaries WHERE story_id = $1 FOR UPDATE",
+        )
+        .bind(story_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| {
+            error!(error = %err, %story_id, "Failed to lock story analysis summary");
+            AppError::InternalServerError
+        })?;
+        let Some(row) = row else {
+            return Ok(false);
+        };
+
+        let mut summary = StoryAnalysisSummary {
+            story_id,
+            organization_id: analysis.organization_id,
+            tasks: serde_json::from_value(row.get("tasks")).unwrap_or_default(),
+            updated_at: row.get("updated_at"),
+        };
+        summary.record(analysis);
+        write_story_summary(&mut *tx, &summary).await?;
+
+        tx.commit().await.map_err(|err| {
+            error!(error = %err, %story_id, "Failed to commit story analysis summary update");
+            AppError::InternalServerError
+        })?;
+        Ok(true)
+    }
+
+    async fn save_feedback(&self, feedback: &AnalysisFeedback) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO task_analysis_feedback \
+                 (id, task_id, story_id, organization_id, user_id, heuristic, recommendation, verdict, comment, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+             ON CONFLICT (task_id, heuristic, user_id) DO UPDATE SET \
+                 recommendation = EXCLUDED.recommendation, \
+                 verdict = EXCLUDED.verdict, \
+                 comment = EXCLUDED.comment, \
+                 created_at = EXCLUDED.created_at",
+        )
+        .bind(feedback.id)
+        .bind(feedback.task_id)
+        .bind(feedback.story_id)
+        .bind(feedback.organization_id)
+        .bind(&feedback.user_id)
+        .bind(feedback.gap_type.as_str())
+        .bind(&feedback.recommendation)
+        .bind(feedback.verdict.as_str())
+        .bind(&feedback.comment)
+        .bind(feedback.created_at)
+        .execute(self)
+        .await
+        .map_err(|err| {
+            error!(error = %err, task_id = %feedback.task_id, "Failed to save analysis feedback");
+            AppError::InternalServerError
+        })?;
+
+        Ok(())
+    }
+
+    async fn get_feedback_tallies(
+        &self,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<HeuristicTally>, AppError> {
+        let rows = sqlx::query_as::<_, (String, i64, i64)>(
+            "SELECT heuristic, \
+                    COUNT(*) FILTER (WHERE verdict = 'helpful'), \
+                    COUNT(*) FILTER (WHERE verdict = 'incorrect') \
+             FROM task_analysis_feedback \
+             WHERE organization_id = $1 OR ($1 IS NULL AND organization_id IS NULL) \
+             GROUP BY heuristic",
+        )
+        .bind(organization_id)
+        .fetch_all(self)
+        .await
+        .map_err(|err| {
+            error!(error = %err, "Failed to tally analysis feedback");
+            AppError::InternalServerError
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(heuristic, helpful, incorrect)| {
+                Some(HeuristicTally {
+                    gap_type: GapType::parse(&heuristic)?,
+                    helpful: helpful as u32,
+                    incorrect: incorrect as u32,
+                })
+            })
+            .collect())
+    }
+
+    async fn get_recent_feedback(
+        &self,
+        organization_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<AnalysisFeedback>, AppError> {
+        let rows = sqlx::query(
+            "SELECT id, task_id, story_id, organization_id, user_id, heuristic, recommendation, verdict, comment, created_at \
+             FROM task_analysis_feedback \
+             WHERE organization_id = $1 OR ($1 IS NULL AND organization_id IS NULL) \
+             ORDER BY created_at DESC \
+             LIMIT $2",
+        )
+        .bind(organization_id)
+        .bind(limit)
+        .fetch_all(self)
+        .await
+        .map_err(|err| {
+            error!(error = %err, "Failed to fetch analysis feedback");
+            AppError::InternalServerError
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(AnalysisFeedback {
+                    id: row.get("id"),
+                    task_id: row.get("task_id"),
+                    story_id: row.get("story_id"),
+                    organization_id: row.get("organization_id"),
+                    user_id: row.get("user_id"),
+                    gap_type: GapType::parse(row.get("heuristic"))?,
+                    recommendation: row.get("recommendation"),
+                    verdict: FeedbackVerdict::parse(row.get("verdict"))?,
+                    comment: row.get("comment"),
+                    created_at: row.get("created_at"),
+                })
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl VagueTermRepository for PgPool {
+    async fn get_vague_term_overrides(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<VagueTermOverride>, AppError> {
+        sqlx::query_as::<_, (String, Option<String>, bool)>(
+            "SELECT term, suggestion, removed FROM organization_vague_terms \
+             WHERE organization_id = $1 \
+             ORDER BY position",
+        )
+        .bind(organization_id)
+        .fetch_all(self)
+        .await
+        .map(|rows| {
+            rows.into_iter()
+                .map(|(term, suggestion, removed)| VagueTermOverride {
+                    term,
+                    suggestion,
+                    removed,
+                })
+                .collect()
+        })
+        .map_err(|err| {
+            error!(error = %err, %organization_id, "Failed to fetch vague terms");
+            AppError::InternalServerError
+        })
+    }
+
+    async fn replace_vague_term_overrides(
+        &self,
+        organization_id: Uuid,
+        overrides: &[VagueTermOverride],
+    ) -> Result<(), AppError> {
+        let mut tx = self.begin().await.map_err(|err| {
+            error!(error = %err, %organization_id, "Failed to begin vague term update");
+            AppError::InternalServerError
+        })?;
+
+        sqlx::query("DELETE FROM organization_vague_terms WHERE organization_id = $1")
+            .bind(organization_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                error!(error = %err, %organization_id, "Failed to clear vague terms");
+                AppError::InternalServerError
+            })?;
+
+        for (position, entry) in overrides.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO organization_vague_terms \
+                     (organization_id, term, suggestion, removed, position, updated_at) \
+                 VALUES ($1, $2, $3, $4, $5, NOW())",
+            )
+            .bind(organization_id)
+            .bind(&entry.term)
+            .bind(&entry.suggestion)
+            .bind(entry.removed)
+            .bind(position as i32)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                error!(error = %err, %organization_id, "Failed to save vague term");
+                AppError::InternalServerError
+            })?;
+        }
+
+        tx.commit().await.map_err(|err| {
+            error!(error = %err, %organization_id, "Failed to commit vague term update");
+            AppError::InternalServerError
+        })
+    }
+}
+
+#[async_trait]
+impl GlossaryRepository for PgPool {
+    async fn get_glossary(&self, organization_id: Uuid) -> Result<Vec<GlossaryEntry>, AppError> {
+        sqlx::query(
+            "SELECT id, organization_id, term, definition, synonyms, created_at, updated_at \
+             FROM organization_glossary_terms \
+             WHERE organization_id = $1 \
+             ORDER BY LOWER(term)",
+        )
+        .bind(organization_id)
+        .fetch_all(self)
+        .await
+        .map(|rows| {
+            rows.into_iter()
+                .map(|row| GlossaryEntry {
+                    id: row.get("id"),
+                    organization_id: row.get("organization_id"),
+                    term: row.get("term"),
+                    definition: row.get("definition"),
+                    synonyms: row.get("synonyms"),
+                    created_at: row.get("created_at"),
+                    updated_at: row.get("updated_at"),
+                })
+                .collect()
+        })
+        .map_err(|err| {
+            error!(error = %err, %organization_id, "Failed to fetch glossary");
+            AppError::InternalServerError
+        })
+    }
+
+    async fn save_glossary_entry(&self, entry: &GlossaryEntry) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO organization_glossary_terms \
+                 (id, organization_id, term, definition, synonyms, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7) \
+             ON CONFLICT (id) DO UPDATE SET \
+                 term = EXCLUDED.term, \
+                 definition = EXCLUDED.definition, \
+                 synonyms = EXCLUDED.synonyms, \
+                 updated_at = EXCLUDED.updated_at \
+             WHERE organization_glossary_terms.organization_id = EXCLUDED.organization_id",
+        )
+        .bind(entry.id)
+        .bind(entry.organization_id)
+        .bind(&entry.term)
+        .bind(&entry.definition)
+        .bind(&entry.synonyms)
+        .bind(entry.created_at)
+        .bind(entry.updated_at)
+        .execute(self)
+        .await
+        .map_err(|err| match err.as_database_error() {
+            Some(db_error) if db_error.is_unique_violation() => {
+                AppError::Conflict(format!("The glossary already defines '{}'", entry.term))
+            }
+            _ => {
+                error!(error = %err, entry_id = %entry.id, "Failed to save glossary entry");
+                AppError::InternalServerError
+            }
+        })?;
+        Ok(())
+    }
+
+    async fn delete_glossary_entry(
+        &self,
+        organization_id: Uuid,
+        entry_id: Uuid,
+    ) -> Result<bool, AppError> {
+        sqlx::query(
+            "DELETE FROM organization_glossary_terms WHERE id = $1 AND organization_id = $2",
+        )
+        .bind(entry_id)
+        .bind(organization_id)
+        .execute(self)
+        .await
+        .map(|result| result.rows_affected() == 1)
+        .map_err(|err| {
+            error!(error = %err, %entry_id, "Failed to delete glossary entry");
+            AppError::InternalServerError
+        })
+    }
+}
+
+#[async_trait]
+impl ScoringProfileRepository for PgPool {
+    async fn get_scoring_profile(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Option<ScoringProfile>, AppError> {
+        sqlx::query(
+            "SELECT missing_description, missing_technical_details, vague_language, \
+                    missing_ai_agent_compatibility, missing_acceptance_criteria, \
+                    invalid_acceptance_criteria, missing_estimate, ai_ready_threshold \
+             FROM organization_scoring_profiles \
+             WHERE organization_id = $1",
+        )
+        .bind(organization_id)
+        .fetch_optional(self)
+        .await
+        .map(|row| {
+            row.map(|row| ScoringProfile {
+                missing_description: row.get("missing_description"),
+                missing_technical_details: row.get("missing_technical_details"),
+                vague_language: row.get("vague_language"),
+                missing_ai_agent_compatibility: row.get("missing_ai_agent_compatibility"),
+                missing_acceptance_criteria: row.get("missing_acceptance_criteria"),
+                invalid_acceptance_criteria: row.get("invalid_acceptance_criteria"),
+                missing_estimate: row.get("missing_estimate"),
+                ai_ready_threshold: row.get("ai_ready_threshold"),
+            })
+        })
+        .map_err(|err| {
+            error!(error = %err, %organization_id, "Failed to fetch scoring profile");
+            AppError::InternalServerError
+        })
+    }
+
+    async fn save_scoring_profile(
+        &self,
+        organization_id: Uuid,
+        profile: &ScoringProfile,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO organization_scoring_profiles \
+                 (organization_id, missing_description, missing_technical_details, vague_language, \
+                  missing_ai_agent_compatibility, missing_acceptance_criteria, \
+                  invalid_acceptance_criteria, missing_estimate, ai_ready_threshold, updated_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW()) \
+             ON CONFLICT (organization_id) DO UPDATE SET \
+                 missing_description = EXCLUDED.missing_description, \
+                 missing_technical_details = EXCLUDED.missing_technical_details, \
+                 vague_language = EXCLUDED.vague_language, \
+                 missing_ai_agent_compatibility = EXCLUDED.missing_ai_agent_compatibility, \
+                 missing_acceptance_criteria = EXCLUDED.missing_acceptance_criteria, \
+                 invalid_acceptance_criteria = EXCLUDED.invalid_acceptance_criteria, \
+                 missing_estimate = EXCLUDED.missing_estimate, \
+                 ai_ready_threshold = EXCLUDED.ai_ready_threshold, \
+                 updated_at = NOW()",
+        )
+        .bind(organization_id)
+        .bind(profile.missing_description)
+        .bind(profile.missing_technical_details)
+        .bind(profile.vague_language)
+        .bind(profile.missing_ai_agent_compatibility)
+        .bind(profile.missing_acceptance_criteria)
+        .bind(profile.invalid_acceptance_criteria)
+        .bind(profile.missing_estimate)
+        .bind(profile.ai_ready_threshold)
+        .execute(self)
+        .await
+        .map(|_| ())
+        .map_err(|err| {
+            error!(error = %err, %organization_id, "Failed to save scoring profile");
+            AppError::InternalServerError
+        })
+    }
+}
+
+async fn write_story_summary<'e, E>(
+    executor: E,
+    summary: &StoryAnalysisSummary,
+) -> Result<(), AppError>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    let tasks = serde_json::to_value(&summary.tasks).map_err(|err| {
+        error!(error = %err, story_id = %summary.story_id, "Failed to serialize story analysis summary");
+        AppError::InternalServerError
+    })?;
+
+    sqlx::query(
+        "INSERT INTO story_analysis_summaries \
+             (story_id, organization_id, tasks, task_count, average_clarity, updated_at) \
+         VALUES ($1, $2, $3, $4, $5, $6) \
+         ON CONFLICT (story_id) DO UPDATE SET \
+             organization_id = EXCLUDED.organization_id, \
+             tasks = EXCLUDED.tasks, \
+             task_count = EXCLUDED.task_count, \
+             average_clarity = EXCLUDED.average_clarity, \
+             updated_at = EXCLUDED.updated_at",
+    )
+    .bind(summary.story_id)
+    .bind(summary.organization_id)
+    .bind(tasks)
+    .bind(summary.tasks.len() as i32)
+    .bind(summary.average_clarity())
+    .bind(summary.updated_at)
+    .execute(executor)
+    .await
+    .map_err(|err| {
+        error!(error = %err, story_id = %summary.story_id, "Failed to save story analysis summary");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
 }
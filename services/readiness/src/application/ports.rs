@@ -1,4 +1,8 @@
-use crate::domain::{AcceptanceCriterion, ReadinessEvaluation, TaskAnalysis};
+use crate::domain::{
+    AcceptanceCriterion, AnalysisFeedback, ContentLanguage, GlossaryEntry, HeuristicTally,
+    ReadinessEvaluation, RepositoryRef, ScoringProfile, StoryAnalysisSummary, StorySelection,
+    TaskAnalysis, VagueTerm, VagueTermOverride,
+};
 use async_trait::async_trait;
 use common::AppError;
 use uuid::Uuid;
@@ -31,7 +35,6 @@ pub trait AcceptanceCriteriaRepository: Send + Sync {
 #[async_trait]
 pub trait ReadinessEvaluationRepository: Send + Sync {
     async fn save_evaluation(&self, eval: &ReadinessEvaluation) -> Result<(), AppError>;
-    #[allow(dead_code)]
     async fn get_latest_evaluation(
         &self,
         story_id: Uuid,
@@ -56,6 +59,45 @@ pub trait StoryService: Send + Sync {
         task_id: Uuid,
         organization_id: Option<Uuid>,
     ) -> Result<Option<TaskInfo>, AppError>;
+    /// IDs of the project's stories matching the selection, oldest first
+    async fn find_project_stories(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        selection: &StorySelection,
+    ) -> Result<Vec<Uuid>, AppError>;
+}
+
+/// A story another story depends on, with its status as last projected
+#[derive(Debug, Clone)]
+pub struct StoryDependency {
+    pub story_id: Uuid,
+    pub title: String,
+    pub status: String,
+    /// Recorded as blocking the story, rather than only mentioned by it
+    pub blocking: bool,
+}
+
+impl StoryDependency {
+    /// Holds back the stories depending on it: a blocker until it is accepted,
+    /// a mentioned story while it is still a draft
+    pub fn is_unready(&self) -> bool {
+        if self.blocking {
+            !self.status.eq_ignore_ascii_case("accepted")
+        } else {
+            self.status.eq_ignore_ascii_case("draft")
+        }
+    }
+}
+
+#[async_trait]
+pub trait StoryDependencyService: Send + Sync {
+    /// The stories `story_id` depends on, fed from the backlog's dependency events
+    async fn get_story_dependencies(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<StoryDependency>, AppError>;
 }
 
 #[derive(Debug, Clone)]
@@ -76,14 +118,78 @@ pub struct TaskInfo {
     pub estimated_hours: Option<u32>,
 }
 
+/// Organization-specific context for LLM prompts
+#[derive(Debug, Clone, Default)]
+pub struct PromptGuidance {
+    /// Feedback on earlier recommendations, phrased for the model
+    pub calibration: Vec<String>,
+    /// Terms the organization treats as vague, with what to write instead
+    pub vague_terms: Vec<VagueTerm>,
+    /// Language of the story; generated content is written in it
+    pub language: ContentLanguage,
+    /// The organization's glossary entries that appear in the story
+    pub glossary: Vec<GlossaryEntry>,
+}
+
 #[async_trait]
 pub trait LlmService: Send + Sync {
     async fn generate_acceptance_criteria(
         &self,
         story_info: &StoryInfo,
+        guidance: &PromptGuidance,
     ) -> Result<Vec<AcceptanceCriterion>, AppError>;
 }
 
+#[async_trait]
+pub trait RepositoryService: Send + Sync {
+    /// Path of every file in the repository at the ref. Fails with not found
+    /// when the repository or ref does not exist or is not visible.
+    async fn list_files(&self, repository: &RepositoryRef) -> Result<Vec<String>, AppError>;
+}
+
+#[async_trait]
+pub trait VagueTermRepository: Send + Sync {
+    /// The organization's changes to the built-in vague-term dictionary
+    async fn get_vague_term_overrides(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<VagueTermOverride>, AppError>;
+    async fn replace_vague_term_overrides(
+        &self,
+        organization_id: Uuid,
+        overrides: &[VagueTermOverride],
+    ) -> Result<(), AppError>;
+}
+
+#[async_trait]
+pub trait GlossaryRepository: Send + Sync {
+    /// The organization's glossary, alphabetically
+    async fn get_glossary(&self, organization_id: Uuid) -> Result<Vec<GlossaryEntry>, AppError>;
+    /// Insert or update the entry; fails with a conflict when the
+    /// organization already defines the term
+    async fn save_glossary_entry(&self, entry: &GlossaryEntry) -> Result<(), AppError>;
+    /// Returns false when the organization has no such entry
+    async fn delete_glossary_entry(
+        &self,
+        organization_id: Uuid,
+        entry_id: Uuid,
+    ) -> Result<bool, AppError>;
+}
+
+#[async_trait]
+pub trait ScoringProfileRepository: Send + Sync {
+    /// The organization's scoring profile, if it has replaced the default one
+    async fn get_scoring_profile(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Option<ScoringProfile>, AppError>;
+    async fn save_scoring_profile(
+        &self,
+        organization_id: Uuid,
+        profile: &ScoringProfile,
+    ) -> Result<(), AppError>;
+}
+
 #[async_trait]
 pub trait TaskAnalysisRepository: Send + Sync {
     async fn save_analysis(&self, analysis: &TaskAnalysis) -> Result<(), AppError>;
@@ -92,4 +198,31 @@ pub trait TaskAnalysisRepository: Send + Sync {
         task_id: Uuid,
         organization_id: Option<Uuid>,
     ) -> Result<Option<TaskAnalysis>, AppError>;
+    /// Latest analysis of every analyzed task in the story
+    async fn get_latest_analyses_for_story(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<TaskAnalysis>, AppError>;
+    async fn get_story_summary(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<StoryAnalysisSummary>, AppError>;
+    async fn save_story_summary(&self, summary: &StoryAnalysisSummary) -> Result<(), AppError>;
+    /// Fold a new analysis into its story's stored summary. Returns false when
+    /// the story has no summary yet.
+    async fn record_in_story_summary(&self, analysis: &TaskAnalysis) -> Result<bool, AppError>;
+    /// Store a verdict, replacing the user's earlier verdict on the same task
+    /// and heuristic
+    async fn save_feedback(&self, feedback: &AnalysisFeedback) -> Result<(), AppError>;
+    async fn get_feedback_tallies(
+        &self,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<HeuristicTally>, AppError>;
+    async fn get_recent_feedback(
+        &self,
+        organization_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<AnalysisFeedback>, AppError>;
 }
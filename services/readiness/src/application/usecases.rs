@@ -1,21 +1,47 @@
 use crate::application::ports::{
-    AcceptanceCriteriaRepository, LlmService, ReadinessEvaluationRepository, StoryService,
-    TaskAnalysisRepository,
+    AcceptanceCriteriaRepository, GlossaryRepository, LlmService, PromptGuidance,
+    ReadinessEvaluationRepository, RepositoryService, ScoringProfileRepository, StoryDependency,
+    StoryDependencyService, StoryInfo, StoryService, TaskAnalysisRepository, TaskInfo,
+    VagueTermRepository,
 };
 use crate::domain::{
-    AcceptanceCriterion, ReadinessCheck, ReadinessEvaluation, TaskAnalysis, TaskAnalyzer,
+    calibration_notes, relevant_glossary_entries, suppressed_heuristics,
+    validate_vague_term_overrides, AcceptanceCriterion, AnalysisFeedback, BatchCertification,
+    ContentLanguage, FeedbackVerdict, GlossaryEntry, ReadinessCheck, ReadinessEvaluation,
+    ReadinessFix, ReadinessFixType, RepositoryRef, ScoringProfile, StoryAnalysisSummary,
+    StorySelection, TaskAnalysis, TaskAnalyzer, TestMapping, VagueTermDictionary,
+    VagueTermOverride, BATCH_EVALUATION_CONCURRENCY, MAX_BATCH_STORIES, MAX_GLOSSARY_ENTRIES,
 };
 use chrono::{DateTime, Utc};
+use common::quota::{QuotaGuard, UnlimitedQuotaGuard};
 use common::AppError;
-use std::sync::Arc;
+use futures::{StreamExt, TryStreamExt};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+/// How much recent analysis feedback is passed to the LLM
+const CALIBRATION_FEEDBACK_LIMIT: i64 = 10;
+/// How long an organization's vague-term dictionary and scoring profile are
+/// reused before they are reloaded, so changes made through another instance
+/// are picked up
+const ORGANIZATION_SETTINGS_CACHE_TTL: Duration = Duration::from_secs(300);
+
 pub struct ReadinessUsecases {
     criteria_repo: Arc<dyn AcceptanceCriteriaRepository>,
     readiness_repo: Arc<dyn ReadinessEvaluationRepository>,
     task_analysis_repo: Arc<dyn TaskAnalysisRepository>,
+    vague_term_repo: Arc<dyn VagueTermRepository>,
+    scoring_profile_repo: Arc<dyn ScoringProfileRepository>,
+    glossary_repo: Arc<dyn GlossaryRepository>,
     story_service: Arc<dyn StoryService>,
+    dependency_service: Arc<dyn StoryDependencyService>,
     llm_service: Arc<dyn LlmService>,
+    quota_guard: Arc<dyn QuotaGuard>,
+    repository_service: Option<Arc<dyn RepositoryService>>,
+    vague_term_cache: RwLock<HashMap<Uuid, (Instant, Vec<VagueTermOverride>)>>,
+    scoring_profile_cache: RwLock<HashMap<Uuid, (Instant, ScoringProfile)>>,
 }
 
 #[derive(Debug, Clone)]
@@ -36,18 +62,267 @@ impl ReadinessUsecases {
         criteria_repo: Arc<dyn AcceptanceCriteriaRepository>,
         readiness_repo: Arc<dyn ReadinessEvaluationRepository>,
         task_analysis_repo: Arc<dyn TaskAnalysisRepository>,
+        vague_term_repo: Arc<dyn VagueTermRepository>,
+        scoring_profile_repo: Arc<dyn ScoringProfileRepository>,
+        glossary_repo: Arc<dyn GlossaryRepository>,
         story_service: Arc<dyn StoryService>,
+        dependency_service: Arc<dyn StoryDependencyService>,
         llm_service: Arc<dyn LlmService>,
     ) -> Self {
         Self {
             criteria_repo,
             readiness_repo,
             task_analysis_repo,
+            vague_term_repo,
+            scoring_profile_repo,
+            glossary_repo,
             story_service,
+            dependency_service,
             llm_service,
+            quota_guard: Arc::new(UnlimitedQuotaGuard),
+            repository_service: None,
+            vague_term_cache: RwLock::new(HashMap::new()),
+            scoring_profile_cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Count acceptance criteria generation against the organization's LLM calls
+    pub fn with_quota_guard(mut self, quota_guard: Arc<dyn QuotaGuard>) -> Self {
+        self.quota_guard = quota_guard;
+        self
+    }
+
+    /// Read source repositories, for matching acceptance criteria to test files
+    pub fn with_repository_service(
+        mut self,
+        repository_service: Arc<dyn RepositoryService>,
+    ) -> Self {
+        self.repository_service = Some(repository_service);
+        self
+    }
+
+    /// The organization's vague-term dictionary for the language; the built-in
+    /// one outside organizations
+    pub async fn vague_terms(
+        &self,
+        organization_id: Option<Uuid>,
+        language: ContentLanguage,
+    ) -> Result<VagueTermDictionary, AppError> {
+        let Some(organization_id) = organization_id else {
+            return Ok(VagueTermDictionary::for_language(language));
+        };
+
+        if let Some((fetched_at, overrides)) = self
+            .vague_term_cache
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&organization_id).cloned())
+        {
+            if fetched_at.elapsed() < ORGANIZATION_SETTINGS_CACHE_TTL {
+                return Ok(VagueTermDictionary::with_overrides(language, &overrides));
+            }
+        }
+
+        let overrides = self
+            .vague_term_repo
+            .get_vague_term_overrides(organization_id)
+            .await?;
+        let dictionary = VagueTermDictionary::with_overrides(language, &overrides);
+        self.remember_vague_terms(organization_id, overrides);
+        Ok(dictionary)
+    }
+
+    fn remember_vague_terms(&self, organization_id: Uuid, overrides: Vec<VagueTermOverride>) {
+        if let Ok(mut cache) = self.vague_term_cache.write() {
+            cache.insert(organization_id, (Instant::now(), overrides));
+        }
+    }
+
+    pub async fn get_vague_term_overrides(
+        &self,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<VagueTermOverride>, AppError> {
+        match organization_id {
+            Some(organization_id) => {
+                self.vague_term_repo
+                    .get_vague_term_overrides(organization_id)
+                    .await
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Replace the organization's changes to the built-in dictionaries. The
+    /// changes apply to every language; the dictionary for `language` is returned.
+    pub async fn replace_vague_term_overrides(
+        &self,
+        organization_id: Option<Uuid>,
+        overrides: Vec<VagueTermOverride>,
+        language: ContentLanguage,
+    ) -> Result<VagueTermDictionary, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest(
+                "Vague terms can only be customized for an organization".to_string(),
+            )
+        })?;
+        validate_vague_term_overrides(&overrides)?;
+
+        self.vague_term_repo
+            .replace_vague_term_overrides(organization_id, &overrides)
+            .await?;
+        let dictionary = VagueTermDictionary::with_overrides(language, &overrides);
+        self.remember_vague_terms(organization_id, overrides);
+        Ok(dictionary)
+    }
+
+    /// The organization's scoring profile; the default one outside organizations
+    /// or until the organization saves its own
+    pub async fn scoring_profile(
+        &self,
+        organization_id: Option<Uuid>,
+    ) -> Result<ScoringProfile, AppError> {
+        let Some(organization_id) = organization_id else {
+            return Ok(ScoringProfile::default());
+        };
+
+        if let Some((fetched_at, profile)) = self
+            .scoring_profile_cache
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&organization_id).copied())
+        {
+            if fetched_at.elapsed() < ORGANIZATION_SETTINGS_CACHE_TTL {
+                return Ok(profile);
+            }
+        }
+
+        let profile = self
+            .scoring_profile_repo
+            .get_scoring_profile(organization_id)
+            .await?
+            .unwrap_or_default();
+        self.remember_scoring_profile(organization_id, profile);
+        Ok(profile)
+    }
+
+    /// The organization's glossary; empty outside organizations
+    pub async fn get_glossary(
+        &self,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<GlossaryEntry>, AppError> {
+        match organization_id {
+            Some(organization_id) => self.glossary_repo.get_glossary(organization_id).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// The glossary entries whose term or synonyms appear in `text`
+    pub async fn glossary_for_text(
+        &self,
+        organization_id: Option<Uuid>,
+        text: &str,
+    ) -> Result<Vec<GlossaryEntry>, AppError> {
+        let glossary = self.get_glossary(organization_id).await?;
+        Ok(relevant_glossary_entries(&glossary, text))
+    }
+
+    pub async fn add_glossary_entry(
+        &self,
+        organization_id: Option<Uuid>,
+        term: &str,
+        definition: &str,
+        synonyms: Vec<String>,
+    ) -> Result<GlossaryEntry, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest("A glossary can only be kept for an organization".to_string())
+        })?;
+        let entry = GlossaryEntry::new(organization_id, term, definition, synonyms)?;
+        if self
+            .glossary_repo
+            .get_glossary(organization_id)
+            .await?
+            .len()
+            >= MAX_GLOSSARY_ENTRIES
+        {
+            return Err(AppError::BadRequest(format!(
+                "A glossary can hold at most {} terms",
+                MAX_GLOSSARY_ENTRIES
+            )));
+        }
+
+        self.glossary_repo.save_glossary_entry(&entry).await?;
+        Ok(entry)
+    }
+
+    pub async fn update_glossary_entry(
+        &self,
+        organization_id: Option<Uuid>,
+        entry_id: Uuid,
+        term: &str,
+        definition: &str,
+        synonyms: Vec<String>,
+    ) -> Result<GlossaryEntry, AppError> {
+        let mut entry = self
+            .get_glossary(organization_id)
+            .await?
+            .into_iter()
+            .find(|entry| entry.id == entry_id)
+            .ok_or_else(|| AppError::NotFound(format!("Glossary entry {} not found", entry_id)))?;
+        entry.update(term, definition, synonyms)?;
+
+        self.glossary_repo.save_glossary_entry(&entry).await?;
+        Ok(entry)
+    }
+
+    pub async fn delete_glossary_entry(
+        &self,
+        organization_id: Option<Uuid>,
+        entry_id: Uuid,
+    ) -> Result<(), AppError> {
+        let deleted = match organization_id {
+            Some(organization_id) => {
+                self.glossary_repo
+                    .delete_glossary_entry(organization_id, entry_id)
+                    .await?
+            }
+            None => false,
+        };
+        if !deleted {
+            return Err(AppError::NotFound(format!(
+                "Glossary entry {} not found",
+                entry_id
+            )));
+        }
+        Ok(())
+    }
+
+    fn remember_scoring_profile(&self, organization_id: Uuid, profile: ScoringProfile) {
+        if let Ok(mut cache) = self.scoring_profile_cache.write() {
+            cache.insert(organization_id, (Instant::now(), profile));
         }
     }
 
+    /// Replace the organization's scoring profile. Existing analyses keep their
+    /// scores until their tasks are analyzed again.
+    pub async fn replace_scoring_profile(
+        &self,
+        organization_id: Option<Uuid>,
+        profile: ScoringProfile,
+    ) -> Result<ScoringProfile, AppError> {
+        let organization_id = organization_id.ok_or_else(|| {
+            AppError::BadRequest(
+                "Scoring profiles can only be customized for an organization".to_string(),
+            )
+        })?;
+        profile.validate()?;
+
+        self.scoring_profile_repo
+            .save_scoring_profile(organization_id, &profile)
+            .await?;
+        self.remember_scoring_profile(organization_id, profile);
+        Ok(profile)
+    }
+
     pub async fn generate_acceptance_criteria(
         &self,
         story_id: Uuid,
@@ -59,9 +334,30 @@ impl ReadinessUsecases {
             .await?
             .ok_or_else(|| AppError::NotFound(format!("Story {} not found", story_id)))?;
 
+        let feedback = self
+            .task_analysis_repo
+            .get_recent_feedback(organization_id, CALIBRATION_FEEDBACK_LIMIT)
+            .await?;
+        let story_text = format!(
+            "{} {}",
+            story_info.title,
+            story_info.description.as_deref().unwrap_or("")
+        );
+        let language = ContentLanguage::detect(&story_text);
+        let guidance = PromptGuidance {
+            calibration: calibration_notes(&feedback),
+            vague_terms: self
+                .vague_terms(organization_id, language)
+                .await?
+                .terms()
+                .to_vec(),
+            language,
+            glossary: self.glossary_for_text(organization_id, &story_text).await?,
+        };
+        self.quota_guard.consume_llm_call(organization_id).await?;
         let generated_criteria = self
             .llm_service
-            .generate_acceptance_criteria(&story_info)
+            .generate_acceptance_criteria(&story_info, &guidance)
             .await?;
 
         // Save generated criteria
@@ -82,364 +378,159 @@ impl ReadinessUsecases {
             .await
     }
 
-    pub async fn evaluate_story_readiness(
+    /// Suggest which of the repository's test files cover each of the story's
+    /// acceptance criteria
+    pub async fn suggest_test_mapping(
         &self,
         story_id: Uuid,
         organization_id: Option<Uuid>,
-    ) -> Result<ReadinessEvaluation, AppError> {
-        let mut missing_items = Vec::new();
-        let mut recommendations = Vec::new();
-        let mut score = 100;
-
-        let story_info = self
+        repository: RepositoryRef,
+    ) -> Result<TestMapping, AppError> {
+        let repository_service = self.repository_service.as_ref().ok_or_else(|| {
+            AppError::BadRequest("No source repository integration is configured".to_string())
+        })?;
+        let story = self
             .story_service
             .get_story_info(story_id, organization_id)
-            .await?;
-
-        if let Some(info) = story_info.as_ref() {
-            let title = info.title.trim();
-            if title.len() < 12 {
-                missing_items.push("Story title is too short to convey user value".to_string());
-                score -= 10;
-                recommendations.push(
-                    "Rewrite the story title to capture the user, action, and benefit".to_string(),
-                );
-            } else {
-                let lower_title = title.to_lowercase();
-                let persona_pattern = lower_title.starts_with("as a ")
-                    || lower_title.starts_with("as an ")
-                    || lower_title.contains(" as a ")
-                    || lower_title.contains(" as an ");
-                if !persona_pattern {
-                    missing_items.push(
-                        "Story title does not describe a user persona, desired action, and outcome"
-                            .to_string(),
-                    );
-                    score -= 15;
-                    recommendations.push(
-                        "Reframe the title like 'As a <persona>, I want <action> so that <outcome>' to emphasise user value"
-                            .to_string(),
-                    );
-                }
-            }
-
-            match info.description.as_ref().map(|d| d.trim()) {
-                Some(desc) if desc.len() < 60 => {
-                    missing_items
-                        .push("Story description is too brief to guide implementation".to_string());
-                    score -= 10;
-                    recommendations.push(
-                        "Expand the description with context, constraints, or personas".to_string(),
-                    );
-                }
-                None => {
-                    missing_items.push("Story description is missing".to_string());
-                    score -= 10;
-                    recommendations.push(
-                        "Provide a concise description that explains the need and desired outcome"
-                            .to_string(),
-                    );
-                }
-                _ => {}
-            }
-
-            match info.story_points {
-                None => {
-                    missing_items.push("Story points are not set".to_string());
-                    score -= 15;
-                    recommendations.push(
-                        "Estimate the story (1-8 points) to support sprint planning".to_string(),
-                    );
-                }
-                Some(points) => {
-                    if points == 0 || points > 8 {
-                        missing_items.push(format!(
-                            "Story points ({}) are outside the agreed range (1-8)",
-                            points
-                        ));
-                        score -= 10;
-                        recommendations.push(
-                            "Re-estimate the story so it fits within a single sprint".to_string(),
-                        );
-                    } else if points >= 8 {
-                        recommendations.push(
-                            "Consider splitting large stories (>5 points) to reduce risk"
-                                .to_string(),
-                        );
-                    }
-                }
-            }
-        } else {
-            missing_items.push("Story details could not be retrieved".to_string());
-            score -= 50;
-            recommendations.push(
-                "Verify the story exists and that you have access to it before re-running readiness"
-                    .to_string(),
-            );
-        }
-
-        // Check 1: Story must have acceptance criteria
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Story {} not found", story_id)))?;
         let criteria = self
             .criteria_repo
             .get_criteria_by_story(story_id, organization_id)
             .await?;
         if criteria.is_empty() {
-            missing_items.push(ReadinessCheck::AcceptanceCriteria.description().to_string());
-            score -= 25;
-            recommendations.push(
-                "Add at least three acceptance criteria that capture Given/When/Then".to_string(),
-            );
-        } else if criteria.len() < 3 {
-            missing_items.push(format!(
-                "Story only has {} acceptance criteria; aim for at least 3",
-                criteria.len()
+            return Err(AppError::BadRequest(
+                "Story must have acceptance criteria before mapping them to tests".to_string(),
             ));
-            score -= 15;
-            recommendations.push(
-                "Work with the product owner to define additional acceptance criteria".to_string(),
-            );
-        }
-
-        for criterion in &criteria {
-            let combined_length =
-                criterion.given.len() + criterion.when.len() + criterion.then.len();
-            if combined_length < 60 {
-                missing_items.push(format!(
-                    "Acceptance criterion '{}' looks too vague—expand the Given/When/Then details",
-                    criterion.ac_id
-                ));
-                score -= 5;
-                recommendations.push("Ensure each acceptance criterion captures context, trigger, and expected outcome".to_string());
-            }
-        }
-
-        if !criteria.is_empty() {
-            let doc_subject_keywords = [
-                "documentation",
-                "docs",
-                "wiki",
-                "confluence",
-                "notion",
-                "handbook",
-                "runbook",
-                "knowledge base",
-                "guide",
-                "manual",
-                "playbook",
-                "readme",
-                "faq",
-                "sop",
-                "spec document",
-                "spec doc",
-                "internal doc",
-                "release notes",
-                "meeting notes",
-            ];
-            let behaviour_keywords = [
-                "user",
-                "system",
-                "api",
-                "request",
-                "response",
-                "ui",
-                "button",
-                "click",
-                "screen",
-                "page",
-                "endpoint",
-                "service",
-                "email",
-                "notification",
-                "modal",
-                "field",
-                "form",
-                "validation",
-                "error",
-                "success",
-                "display",
-                "render",
-                "backend",
-                "database",
-            ];
-
-            let doc_like = criteria
-                .iter()
-                .filter(|criterion| {
-                    // Only mark ACs as doc-like when they explicitly reference documentation artefacts
-                    // and lack common indicators of observable system behaviour.
-                    let text = format!(
-                        "{} {} {}",
-                        criterion.given.to_lowercase(),
-                        criterion.when.to_lowercase(),
-                        criterion.then.to_lowercase()
-                    );
-                    let mentions_doc_subject =
-                        doc_subject_keywords.iter().any(|kw| text.contains(kw));
-                    let mentions_behaviour = behaviour_keywords.iter().any(|kw| text.contains(kw));
-                    mentions_doc_subject && !mentions_behaviour
-                })
-                .count();
-
-            if doc_like == criteria.len() {
-                missing_items.push(
-                    "Acceptance criteria focus on internal documentation rather than observable system behaviour"
-                        .to_string(),
-                );
-                score -= 15;
-                recommendations.push(
-                    "Rewrite the acceptance criteria to describe measurable product outcomes"
-                        .to_string(),
-                );
-            } else if doc_like > 0 && doc_like * 2 >= criteria.len() {
-                recommendations.push(
-                    "Several acceptance criteria read like internal tasks; consider reframing them in terms of system behaviour"
-                        .to_string(),
-                );
-            }
         }
 
-        // Check 2: All acceptance criteria must be covered by tasks
-        if !criteria.is_empty() {
-            let tasks = self
-                .story_service
-                .get_tasks_for_story(story_id, organization_id)
-                .await?;
-            let covered_ac_ids: std::collections::HashSet<String> = tasks
-                .iter()
-                .flat_map(|t| &t.acceptance_criteria_refs)
-                .cloned()
-                .collect();
-
-            let story_ac_ids: std::collections::HashSet<String> =
-                criteria.iter().map(|c| c.ac_id.clone()).collect();
-
-            let uncovered: Vec<String> = story_ac_ids
-                .difference(&covered_ac_ids)
-                .filter_map(|ac_id| {
-                    criteria
-                        .iter()
-                        .find(|criterion| &criterion.ac_id == ac_id)
-                        .map(|criterion| {
-                            let display = format_gwt_summary(
-                                &criterion.given,
-                                &criterion.when,
-                                &criterion.then,
-                            );
-                            format!(
-                                "Acceptance criterion \"{}\" is not covered by any task",
-                                display
-                            )
-                        })
-                })
-                .collect();
-
-            if !uncovered.is_empty() {
-                missing_items.extend(uncovered);
-                score -= 25;
-                recommendations.push(
-                    "Create tasks that explicitly reference each acceptance criterion".to_string(),
-                );
-            }
-        }
+        let paths = repository_service.list_files(&repository).await?;
+        Ok(TestMapping::suggest(
+            story_id,
+            &story.title,
+            &criteria,
+            repository,
+            &paths,
+        ))
+    }
 
-        // Check 3: Story should have tasks
+    pub async fn evaluate_story_readiness(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<ReadinessEvaluation, AppError> {
+        let story_info = self
+            .story_service
+            .get_story_info(story_id, organization_id)
+            .await?;
+        let criteria = self
+            .criteria_repo
+            .get_criteria_by_story(story_id, organization_id)
+            .await?;
         let tasks = self
             .story_service
             .get_tasks_for_story(story_id, organization_id)
             .await?;
-        if tasks.is_empty() {
-            missing_items.push("Story has no implementation tasks".to_string());
-            score -= 20;
-            recommendations.push(
-                "Break the story into contributor-sized tasks covering the acceptance criteria"
-                    .to_string(),
-            );
-        } else if tasks.len() < criteria.len() {
-            recommendations.push(
-                "Consider adding tasks so every acceptance criterion has dedicated coverage"
-                    .to_string(),
-            );
-        }
+        let dependencies = self
+            .dependency_service
+            .get_story_dependencies(story_id, organization_id)
+            .await?;
 
-        // Soft heuristics: encourage test coverage and measurement tasks
-        if !tasks.is_empty() {
-            let has_test_task = tasks.iter().any(|task| {
-                let haystack = task.title.to_lowercase();
-                haystack.contains("test")
-                    || haystack.contains("qa")
-                    || haystack.contains("verification")
-            });
-            if !has_test_task {
-                recommendations.push(
-                    "Add a task covering automated or acceptance tests so criteria can be validated"
-                        .to_string(),
-                );
-            }
+        let evaluation = evaluate_readiness_rules(
+            story_id,
+            organization_id,
+            story_info.as_ref(),
+            &criteria,
+            Some(&tasks),
+            &dependencies,
+        );
+        self.readiness_repo.save_evaluation(&evaluation).await?;
 
-            let has_measure_task = tasks.iter().any(|task| {
-                let haystack = task.title.to_lowercase();
-                haystack.contains("metric")
-                    || haystack.contains("measure")
-                    || haystack.contains("performance")
-                    || haystack.contains("analytics")
-            });
-            if !has_measure_task {
-                recommendations.push(
-                    "Consider adding a task to capture before/after metrics or monitor impact"
-                        .to_string(),
-                );
+        Ok(evaluation)
+    }
+
+    /// Evaluate and store the readiness of a batch of the project's stories,
+    /// either the listed ones or every story matching the filters, a few at a time
+    pub async fn evaluate_story_batch(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        selection: &StorySelection,
+    ) -> Result<BatchCertification, AppError> {
+        if let Some(requested) = &selection.story_ids {
+            if requested.is_empty() {
+                return Err(AppError::BadRequest(
+                    "storyIds must not be empty".to_string(),
+                ));
+            }
+            if requested.len() > MAX_BATCH_STORIES {
+                return Err(AppError::BadRequest(format!(
+                    "At most {} stories can be evaluated at once",
+                    MAX_BATCH_STORIES
+                )));
             }
         }
 
-        if !criteria.is_empty() {
-            let has_measurable_ac = criteria.iter().any(|criterion| {
-                let text = format!(
-                    "{} {} {}",
-                    criterion.given.to_lowercase(),
-                    criterion.when.to_lowercase(),
-                    criterion.then.to_lowercase()
-                );
-                text.chars().any(|c| c.is_ascii_digit())
-                    || text.contains("seconds")
-                    || text.contains("percent")
-                    || text.contains("ms")
-                    || text.contains("throughput")
-            });
-            if !has_measurable_ac {
-                recommendations.push(
-                    "Add a measurable outcome to at least one acceptance criterion (e.g., SLA, count, or percentage)"
-                        .to_string(),
-                );
+        let story_ids = self
+            .story_service
+            .find_project_stories(project_id, organization_id, selection)
+            .await?;
+        if let Some(requested) = &selection.story_ids {
+            let unknown: Vec<String> = requested
+                .iter()
+                .filter(|id| !story_ids.contains(id))
+                .map(|id| id.to_string())
+                .collect();
+            if !unknown.is_empty() {
+                return Err(AppError::NotFound(format!(
+                    "Stories not found in project: {}",
+                    unknown.join(", ")
+                )));
             }
         }
+        if story_ids.len() > MAX_BATCH_STORIES {
+            return Err(AppError::BadRequest(format!(
+                "{} stories match; narrow the selection to at most {}",
+                story_ids.len(),
+                MAX_BATCH_STORIES
+            )));
+        }
 
-        score = score.clamp(0, 100);
-
-        let summary = if missing_items.is_empty() {
-            "Story meets the readiness bar and can be scheduled for a sprint.".to_string()
-        } else {
-            format!(
-                "Story is not ready yet. Address the following {} item(s) to improve readiness.",
-                missing_items.len()
-            )
-        };
+        let evaluations: Vec<ReadinessEvaluation> = futures::stream::iter(story_ids)
+            .map(|story_id| self.evaluate_story_readiness(story_id, organization_id))
+            .buffer_unordered(BATCH_EVALUATION_CONCURRENCY)
+            .try_collect()
+            .await?;
 
-        if missing_items.is_empty() && recommendations.is_empty() {
-            recommendations
-                .push("Verify dependencies and add the story to the upcoming sprint.".to_string());
-        }
+        Ok(BatchCertification::from_evaluations(
+            project_id,
+            &evaluations,
+        ))
+    }
 
-        let evaluation = ReadinessEvaluation::new(
-            story_id,
-            organization_id,
-            score,
-            missing_items,
-            summary,
-            recommendations,
-        );
-        self.readiness_repo.save_evaluation(&evaluation).await?;
+    /// Rule-based readiness of a story draft that has not been saved yet, so
+    /// the story form can lint as it is filled in. Nothing is stored, checks
+    /// that need tasks are skipped and no one-click fixes are offered, since
+    /// the draft has no IDs to attach them to.
+    pub fn lint_story_draft(
+        &self,
+        organization_id: Option<Uuid>,
+        draft: &StoryInfo,
+        criteria: &[AcceptanceCriterion],
+    ) -> ReadinessEvaluation {
+        evaluate_readiness_rules(draft.id, organization_id, Some(draft), criteria, None, &[])
+            .with_fixes(Vec::new())
+    }
 
-        Ok(evaluation)
+    /// The story's most recent readiness evaluation, without evaluating again
+    pub async fn get_latest_evaluation(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<ReadinessEvaluation>, AppError> {
+        self.readiness_repo
+            .get_latest_evaluation(story_id, organization_id)
+            .await
     }
 
     pub async fn add_acceptance_criteria(
@@ -507,16 +598,141 @@ impl ReadinessUsecases {
             .get_latest_analysis(task_id, organization_id)
             .await?;
 
-        let mut analysis =
-            TaskAnalyzer::analyze(&task_info, &valid_ac_ids, previous_analysis.as_ref());
+        let language = TaskAnalyzer::detect_language(&task_info);
+        let vague_terms = self.vague_terms(organization_id, language).await?;
+        let scoring = self.scoring_profile(organization_id).await?;
+        let mut analysis = TaskAnalyzer::analyze_for_organization(
+            &task_info,
+            &valid_ac_ids,
+            previous_analysis.as_ref(),
+            language,
+            &vague_terms,
+            &scoring,
+        );
         analysis.organization_id = organization_id;
 
+        // Leave out heuristics the organization keeps marking as incorrect
+        let suppressed = suppressed_heuristics(
+            &self
+                .task_analysis_repo
+                .get_feedback_tallies(organization_id)
+                .await?,
+        );
+        analysis
+            .recommendations
+            .retain(|recommendation| !suppressed.contains(&recommendation.gap_type));
+
         // Save the analysis
         self.task_analysis_repo.save_analysis(&analysis).await?;
 
+        // Keep the story's summary current; the first analysis after the
+        // summary was introduced builds it from the analysis history
+        if !self
+            .task_analysis_repo
+            .record_in_story_summary(&analysis)
+            .await?
+        {
+            self.rebuild_story_analysis_summary(analysis.story_id, organization_id)
+                .await?;
+        }
+
         Ok(analysis)
     }
 
+    /// Record a user's verdict on a recommendation from the task's latest
+    /// analysis. `recommendation_id` is the `rec-N` ID the analysis response
+    /// gave it.
+    pub async fn submit_analysis_feedback(
+        &self,
+        task_id: Uuid,
+        organization_id: Option<Uuid>,
+        user_id: &str,
+        recommendation_id: &str,
+        verdict: FeedbackVerdict,
+        comment: Option<String>,
+    ) -> Result<AnalysisFeedback, AppError> {
+        let analysis = self
+            .task_analysis_repo
+            .get_latest_analysis(task_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task analysis not found".to_string()))?;
+
+        let recommendation = recommendation_id
+            .strip_prefix("rec-")
+            .and_then(|index| index.parse::<usize>().ok())
+            .and_then(|index| index.checked_sub(1))
+            .and_then(|index| analysis.recommendations.get(index))
+            .ok_or_else(|| {
+                AppError::BadRequest(format!("Unknown recommendation: {}", recommendation_id))
+            })?;
+
+        let feedback = AnalysisFeedback::new(
+            task_id,
+            analysis.story_id,
+            organization_id,
+            user_id.to_string(),
+            recommendation,
+            verdict,
+            comment,
+        )?;
+        self.task_analysis_repo.save_feedback(&feedback).await?;
+        Ok(feedback)
+    }
+
+    /// Whether feedback has switched the heuristic off for the organization
+    pub async fn is_heuristic_suppressed(
+        &self,
+        organization_id: Option<Uuid>,
+        gap_type: crate::domain::GapType,
+    ) -> Result<bool, AppError> {
+        let tallies = self
+            .task_analysis_repo
+            .get_feedback_tallies(organization_id)
+            .await?;
+        Ok(suppressed_heuristics(&tallies).contains(&gap_type))
+    }
+
+    /// Aggregate readiness of a story's analyzed tasks. Served from the stored
+    /// summary unless `refresh` is set or none exists yet.
+    pub async fn get_story_analysis_summary(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        refresh: bool,
+    ) -> Result<StoryAnalysisSummary, AppError> {
+        if !refresh {
+            if let Some(summary) = self
+                .task_analysis_repo
+                .get_story_summary(story_id, organization_id)
+                .await?
+            {
+                return Ok(summary);
+            }
+        }
+
+        self.story_service
+            .get_story_info(story_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Story {} not found", story_id)))?;
+
+        self.rebuild_story_analysis_summary(story_id, organization_id)
+            .await
+    }
+
+    async fn rebuild_story_analysis_summary(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<StoryAnalysisSummary, AppError> {
+        let analyses = self
+            .task_analysis_repo
+            .get_latest_analyses_for_story(story_id, organization_id)
+            .await?;
+        let summary = StoryAnalysisSummary::from_analyses(story_id, organization_id, &analyses);
+        self.task_analysis_repo.save_story_summary(&summary).await?;
+        Ok(summary)
+    }
+
     /// Get task analysis by task ID
     pub async fn get_task_analysis(
         &self,
@@ -625,24 +841,434 @@ impl ReadinessUsecases {
                     .to_string(),
             );
         }
-        if include_related_tasks {
-            reasoning_parts.push("Related tasks considered to avoid duplicate work.".to_string());
+        if include_related_tasks {
+            reasoning_parts.push("Related tasks considered to avoid duplicate work.".to_string());
+        }
+
+        let reasoning = reasoning_parts.join(" ");
+
+        Ok(TaskEnrichmentSuggestion {
+            task_id,
+            story_id,
+            suggested_title: Some(format!("{} (clarified)", task_info.title)),
+            suggested_description,
+            suggested_ac_refs: ac_ids,
+            confidence: 0.82,
+            reasoning,
+            generated_at: Utc::now(),
+            original_description: task_info.description,
+        })
+    }
+}
+
+/// The readiness rules shared by stored stories and drafts. `tasks` is `None`
+/// for drafts, which skips the checks on task coverage.
+fn evaluate_readiness_rules(
+    story_id: Uuid,
+    organization_id: Option<Uuid>,
+    story_info: Option<&StoryInfo>,
+    criteria: &[AcceptanceCriterion],
+    tasks: Option<&[TaskInfo]>,
+    dependencies: &[StoryDependency],
+) -> ReadinessEvaluation {
+    let mut missing_items: Vec<String> = Vec::new();
+    let mut fixes = Vec::new();
+    let mut recommendations = Vec::new();
+    let mut score = 100;
+    let mut flag = |item: String, fix: Option<(ReadinessFixType, Uuid)>| {
+        if let Some((fix_type, target)) = fix {
+            fixes.push(if target == story_id {
+                ReadinessFix::for_story(fix_type, story_id, &item)
+            } else {
+                ReadinessFix::for_criterion(fix_type, story_id, target, &item)
+            });
+        }
+        missing_items.push(item);
+    };
+
+    if let Some(info) = story_info {
+        let title = info.title.trim();
+        if title.len() < 12 {
+            flag(
+                "Story title is too short to convey user value".to_string(),
+                Some((ReadinessFixType::EditTitle, story_id)),
+            );
+            score -= 10;
+            recommendations.push(
+                "Rewrite the story title to capture the user, action, and benefit".to_string(),
+            );
+        } else {
+            let lower_title = title.to_lowercase();
+            let persona_pattern = lower_title.starts_with("as a ")
+                || lower_title.starts_with("as an ")
+                || lower_title.contains(" as a ")
+                || lower_title.contains(" as an ");
+            if !persona_pattern {
+                flag(
+                    "Story title does not describe a user persona, desired action, and outcome"
+                        .to_string(),
+                    Some((ReadinessFixType::EditTitle, story_id)),
+                );
+                score -= 15;
+                recommendations.push(
+                        "Reframe the title like 'As a <persona>, I want <action> so that <outcome>' to emphasise user value"
+                            .to_string(),
+                    );
+            }
+        }
+
+        match info.description.as_ref().map(|d| d.trim()) {
+            Some(desc) if desc.len() < 60 => {
+                flag(
+                    "Story description is too brief to guide implementation".to_string(),
+                    Some((ReadinessFixType::EditDescription, story_id)),
+                );
+                score -= 10;
+                recommendations.push(
+                    "Expand the description with context, constraints, or personas".to_string(),
+                );
+            }
+            None => {
+                flag(
+                    "Story description is missing".to_string(),
+                    Some((ReadinessFixType::EditDescription, story_id)),
+                );
+                score -= 10;
+                recommendations.push(
+                    "Provide a concise description that explains the need and desired outcome"
+                        .to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        match info.story_points {
+            None => {
+                flag(
+                    "Story points are not set".to_string(),
+                    Some((ReadinessFixType::SetPoints, story_id)),
+                );
+                score -= 15;
+                recommendations
+                    .push("Estimate the story (1-8 points) to support sprint planning".to_string());
+            }
+            Some(points) => {
+                if points == 0 || points > 8 {
+                    flag(
+                        format!(
+                            "Story points ({}) are outside the agreed range (1-8)",
+                            points
+                        ),
+                        Some((ReadinessFixType::SetPoints, story_id)),
+                    );
+                    score -= 10;
+                    recommendations.push(
+                        "Re-estimate the story so it fits within a single sprint".to_string(),
+                    );
+                } else if points >= 8 {
+                    recommendations.push(
+                        "Consider splitting large stories (>5 points) to reduce risk".to_string(),
+                    );
+                }
+            }
+        }
+    } else {
+        flag("Story details could not be retrieved".to_string(), None);
+        score -= 50;
+        recommendations.push(
+            "Verify the story exists and that you have access to it before re-running readiness"
+                .to_string(),
+        );
+    }
+
+    // Check 1: Story must have acceptance criteria
+    if criteria.is_empty() {
+        flag(
+            ReadinessCheck::AcceptanceCriteria.description().to_string(),
+            Some((ReadinessFixType::AddCriteria, story_id)),
+        );
+        score -= 25;
+        recommendations.push(
+            "Add at least three acceptance criteria that capture Given/When/Then".to_string(),
+        );
+    } else if criteria.len() < 3 {
+        flag(
+            format!(
+                "Story only has {} acceptance criteria; aim for at least 3",
+                criteria.len()
+            ),
+            Some((ReadinessFixType::AddCriteria, story_id)),
+        );
+        score -= 15;
+        recommendations.push(
+            "Work with the product owner to define additional acceptance criteria".to_string(),
+        );
+    }
+
+    for criterion in criteria {
+        let combined_length = criterion.given.len() + criterion.when.len() + criterion.then.len();
+        if combined_length < 60 {
+            flag(
+                format!(
+                    "Acceptance criterion '{}' looks too vague—expand the Given/When/Then details",
+                    criterion.ac_id
+                ),
+                Some((ReadinessFixType::RefineCriterion, criterion.id)),
+            );
+            score -= 5;
+            recommendations.push(
+                "Ensure each acceptance criterion captures context, trigger, and expected outcome"
+                    .to_string(),
+            );
+        }
+    }
+
+    if !criteria.is_empty() {
+        let doc_subject_keywords = [
+            "documentation",
+            "docs",
+            "wiki",
+            "confluence",
+            "notion",
+            "handbook",
+            "runbook",
+            "knowledge base",
+            "guide",
+            "manual",
+            "playbook",
+            "readme",
+            "faq",
+            "sop",
+            "spec document",
+            "spec doc",
+            "internal doc",
+            "release notes",
+            "meeting notes",
+        ];
+        let behaviour_keywords = [
+            "user",
+            "system",
+            "api",
+            "request",
+            "response",
+            "ui",
+            "button",
+            "click",
+            "screen",
+            "page",
+            "endpoint",
+            "service",
+            "email",
+            "notification",
+            "modal",
+            "field",
+            "form",
+            "validation",
+            "error",
+            "success",
+            "display",
+            "render",
+            "backend",
+            "database",
+        ];
+
+        let doc_like = criteria
+            .iter()
+            .filter(|criterion| {
+                // Only mark ACs as doc-like when they explicitly reference documentation artefacts
+                // and lack common indicators of observable system behaviour.
+                let text = format!(
+                    "{} {} {}",
+                    criterion.given.to_lowercase(),
+                    criterion.when.to_lowercase(),
+                    criterion.then.to_lowercase()
+                );
+                let mentions_doc_subject = doc_subject_keywords.iter().any(|kw| text.contains(kw));
+                let mentions_behaviour = behaviour_keywords.iter().any(|kw| text.contains(kw));
+                mentions_doc_subject && !mentions_behaviour
+            })
+            .count();
+
+        if doc_like == criteria.len() {
+            flag(
+                    "Acceptance criteria focus on internal documentation rather than observable system behaviour"
+                        .to_string(),
+                    None,
+                );
+            score -= 15;
+            recommendations.push(
+                "Rewrite the acceptance criteria to describe measurable product outcomes"
+                    .to_string(),
+            );
+        } else if doc_like > 0 && doc_like * 2 >= criteria.len() {
+            recommendations.push(
+                    "Several acceptance criteria read like internal tasks; consider reframing them in terms of system behaviour"
+                        .to_string(),
+                );
+        }
+    }
+
+    // Check 2: All acceptance criteria must be covered by tasks
+    if let Some(tasks) = tasks.filter(|_| !criteria.is_empty()) {
+        let covered_ac_ids: std::collections::HashSet<String> = tasks
+            .iter()
+            .flat_map(|t| &t.acceptance_criteria_refs)
+            .cloned()
+            .collect();
+
+        let story_ac_ids: std::collections::HashSet<String> =
+            criteria.iter().map(|c| c.ac_id.clone()).collect();
+
+        let uncovered: Vec<(String, Uuid)> = story_ac_ids
+            .difference(&covered_ac_ids)
+            .filter_map(|ac_id| {
+                criteria
+                    .iter()
+                    .find(|criterion| &criterion.ac_id == ac_id)
+                    .map(|criterion| {
+                        let display =
+                            format_gwt_summary(&criterion.given, &criterion.when, &criterion.then);
+                        (
+                            format!(
+                                "Acceptance criterion \"{}\" is not covered by any task",
+                                display
+                            ),
+                            criterion.id,
+                        )
+                    })
+            })
+            .collect();
+
+        if !uncovered.is_empty() {
+            for (item, criterion_id) in uncovered {
+                flag(item, Some((ReadinessFixType::CoverCriterion, criterion_id)));
+            }
+            score -= 25;
+            recommendations.push(
+                "Create tasks that explicitly reference each acceptance criterion".to_string(),
+            );
+        }
+    }
+
+    // Checks 3 onwards need the story's tasks, which drafts do not have yet
+    if let Some(tasks) = tasks {
+        if tasks.is_empty() {
+            flag(
+                "Story has no implementation tasks".to_string(),
+                Some((ReadinessFixType::CreateTasks, story_id)),
+            );
+            score -= 20;
+            recommendations.push(
+                "Break the story into contributor-sized tasks covering the acceptance criteria"
+                    .to_string(),
+            );
+        } else if tasks.len() < criteria.len() {
+            recommendations.push(
+                "Consider adding tasks so every acceptance criterion has dedicated coverage"
+                    .to_string(),
+            );
+        }
+
+        // Soft heuristics: encourage test coverage and measurement tasks
+        if !tasks.is_empty() {
+            let has_test_task = tasks.iter().any(|task| {
+                let haystack = task.title.to_lowercase();
+                haystack.contains("test")
+                    || haystack.contains("qa")
+                    || haystack.contains("verification")
+            });
+            if !has_test_task {
+                recommendations.push(
+                    "Add a task covering automated or acceptance tests so criteria can be validated"
+                        .to_string(),
+                );
+            }
+
+            let has_measure_task = tasks.iter().any(|task| {
+                let haystack = task.title.to_lowercase();
+                haystack.contains("metric")
+                    || haystack.contains("measure")
+                    || haystack.contains("performance")
+                    || haystack.contains("analytics")
+            });
+            if !has_measure_task {
+                recommendations.push(
+                    "Consider adding a task to capture before/after metrics or monitor impact"
+                        .to_string(),
+                );
+            }
+        }
+    }
+
+    if !criteria.is_empty() {
+        let has_measurable_ac = criteria.iter().any(|criterion| {
+            let text = format!(
+                "{} {} {}",
+                criterion.given.to_lowercase(),
+                criterion.when.to_lowercase(),
+                criterion.then.to_lowercase()
+            );
+            text.chars().any(|c| c.is_ascii_digit())
+                || text.contains("seconds")
+                || text.contains("percent")
+                || text.contains("ms")
+                || text.contains("throughput")
+        });
+        if !has_measurable_ac {
+            recommendations.push(
+                "Add a measurable outcome to at least one acceptance criterion (e.g., SLA, count, or percentage)"
+                    .to_string(),
+            );
+        }
+    }
+
+    // A story cannot be ready while the stories it depends on are still drafts,
+    // or while a story blocking it has not been accepted
+    let unready: Vec<&StoryDependency> = dependencies
+        .iter()
+        .filter(|dependency| dependency.is_unready())
+        .collect();
+    if !unready.is_empty() {
+        for dependency in unready {
+            let item = if dependency.blocking {
+                format!("Blocked by unaccepted story \"{}\"", dependency.title)
+            } else {
+                format!("Depends on unready story \"{}\"", dependency.title)
+            };
+            flag(item, None);
         }
+        score -= 20;
+        recommendations.push(
+            "Refine the stories this one depends on, or drop the dependency, before scheduling it"
+                .to_string(),
+        );
+    }
 
-        let reasoning = reasoning_parts.join(" ");
+    score = score.clamp(0, 100);
 
-        Ok(TaskEnrichmentSuggestion {
-            task_id,
-            story_id,
-            suggested_title: Some(format!("{} (clarified)", task_info.title)),
-            suggested_description,
-            suggested_ac_refs: ac_ids,
-            confidence: 0.82,
-            reasoning,
-            generated_at: Utc::now(),
-            original_description: task_info.description,
-        })
+    let summary = if missing_items.is_empty() {
+        "Story meets the readiness bar and can be scheduled for a sprint.".to_string()
+    } else {
+        format!(
+            "Story is not ready yet. Address the following {} item(s) to improve readiness.",
+            missing_items.len()
+        )
+    };
+
+    if missing_items.is_empty() && recommendations.is_empty() {
+        recommendations
+            .push("Verify dependencies and add the story to the upcoming sprint.".to_string());
     }
+
+    ReadinessEvaluation::new(
+        story_id,
+        organization_id,
+        score,
+        missing_items,
+        summary,
+        recommendations,
+    )
+    .with_fixes(fixes)
 }
 
 fn format_gwt_summary(given: &str, when_clause: &str, then_clause: &str) -> String {
@@ -688,6 +1314,7 @@ fn format_gwt_summary(given: &str, when_clause: &str, then_clause: &str) -> Stri
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::HeuristicTally;
     use async_trait::async_trait;
     use std::collections::HashMap;
     use std::sync::Mutex;
@@ -772,20 +1399,218 @@ mod tests {
     }
 
     #[derive(Default)]
-    struct MockTaskAnalysisRepository;
+    struct MockTaskAnalysisRepository {
+        analyses: Mutex<Vec<TaskAnalysis>>,
+        summaries: Mutex<HashMap<Uuid, StoryAnalysisSummary>>,
+        feedback: Mutex<Vec<AnalysisFeedback>>,
+    }
 
     #[async_trait]
     impl TaskAnalysisRepository for MockTaskAnalysisRepository {
-        async fn save_analysis(&self, _analysis: &TaskAnalysis) -> Result<(), AppError> {
+        async fn save_analysis(&self, analysis: &TaskAnalysis) -> Result<(), AppError> {
+            self.analyses.lock().unwrap().push(analysis.clone());
             Ok(())
         }
 
         async fn get_latest_analysis(
             &self,
-            _task_id: Uuid,
+            task_id: Uuid,
             _organization_id: Option<Uuid>,
         ) -> Result<Option<TaskAnalysis>, AppError> {
-            Ok(None)
+            let analyses = self.analyses.lock().unwrap();
+            Ok(analyses
+                .iter()
+                .rev()
+                .find(|a| a.task_id == task_id)
+                .cloned())
+        }
+
+        async fn get_latest_analyses_for_story(
+            &self,
+            story_id: Uuid,
+            _organization_id: Option<Uuid>,
+        ) -> Result<Vec<TaskAnalysis>, AppError> {
+            let mut latest: HashMap<Uuid, TaskAnalysis> = HashMap::new();
+            for analysis in self.analyses.lock().unwrap().iter() {
+                if analysis.story_id == story_id {
+                    latest.insert(analysis.task_id, analysis.clone());
+                }
+            }
+            Ok(latest.into_values().collect())
+        }
+
+        async fn get_story_summary(
+            &self,
+            story_id: Uuid,
+            _organization_id: Option<Uuid>,
+        ) -> Result<Option<StoryAnalysisSummary>, AppError> {
+            Ok(self.summaries.lock().unwrap().get(&story_id).cloned())
+        }
+
+        async fn save_story_summary(&self, summary: &StoryAnalysisSummary) -> Result<(), AppError> {
+            self.summaries
+                .lock()
+                .unwrap()
+                .insert(summary.story_id, summary.clone());
+            Ok(())
+        }
+
+        async fn record_in_story_summary(&self, analysis: &TaskAnalysis) -> Result<bool, AppError> {
+            let mut summaries = self.summaries.lock().unwrap();
+            match summaries.get_mut(&analysis.story_id) {
+                Some(summary) => {
+                    summary.record(analysis);
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+
+        async fn save_feedback(&self, feedback: &AnalysisFeedback) -> Result<(), AppError> {
+            let mut stored = self.feedback.lock().unwrap();
+            stored.retain(|f| {
+                (f.task_id, f.gap_type, &f.user_id)
+                    != (feedback.task_id, feedback.gap_type, &feedback.user_id)
+            });
+            stored.push(feedback.clone());
+            Ok(())
+        }
+
+        async fn get_feedback_tallies(
+            &self,
+            _organization_id: Option<Uuid>,
+        ) -> Result<Vec<HeuristicTally>, AppError> {
+            let mut tallies: Vec<HeuristicTally> = Vec::new();
+            for feedback in self.feedback.lock().unwrap().iter() {
+                let index = match tallies.iter().position(|t| t.gap_type == feedback.gap_type) {
+                    Some(index) => index,
+                    None => {
+                        tallies.push(HeuristicTally {
+                            gap_type: feedback.gap_type,
+                            helpful: 0,
+                            incorrect: 0,
+                        });
+                        tallies.len() - 1
+                    }
+                };
+                match feedback.verdict {
+                    FeedbackVerdict::Helpful => tallies[index].helpful += 1,
+                    FeedbackVerdict::Incorrect => tallies[index].incorrect += 1,
+                }
+            }
+            Ok(tallies)
+        }
+
+        async fn get_recent_feedback(
+            &self,
+            _organization_id: Option<Uuid>,
+            limit: i64,
+        ) -> Result<Vec<AnalysisFeedback>, AppError> {
+            let feedback = self.feedback.lock().unwrap();
+            Ok(feedback
+                .iter()
+                .rev()
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockVagueTermRepository {
+        overrides: Mutex<HashMap<Uuid, Vec<VagueTermOverride>>>,
+        loads: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl VagueTermRepository for MockVagueTermRepository {
+        async fn get_vague_term_overrides(
+            &self,
+            organization_id: Uuid,
+        ) -> Result<Vec<VagueTermOverride>, AppError> {
+            *self.loads.lock().unwrap() += 1;
+            let overrides = self.overrides.lock().unwrap();
+            Ok(overrides.get(&organization_id).cloned().unwrap_or_default())
+        }
+
+        async fn replace_vague_term_overrides(
+            &self,
+            organization_id: Uuid,
+            overrides: &[VagueTermOverride],
+        ) -> Result<(), AppError> {
+            self.overrides
+                .lock()
+                .unwrap()
+                .insert(organization_id, overrides.to_vec());
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockScoringProfileRepository {
+        profiles: Mutex<HashMap<Uuid, ScoringProfile>>,
+    }
+
+    #[async_trait]
+    impl ScoringProfileRepository for MockScoringProfileRepository {
+        async fn get_scoring_profile(
+            &self,
+            organization_id: Uuid,
+        ) -> Result<Option<ScoringProfile>, AppError> {
+            Ok(self.profiles.lock().unwrap().get(&organization_id).copied())
+        }
+
+        async fn save_scoring_profile(
+            &self,
+            organization_id: Uuid,
+            profile: &ScoringProfile,
+        ) -> Result<(), AppError> {
+            self.profiles
+                .lock()
+                .unwrap()
+                .insert(organization_id, *profile);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockGlossaryRepository {
+        entries: Mutex<Vec<GlossaryEntry>>,
+    }
+
+    #[async_trait]
+    impl GlossaryRepository for MockGlossaryRepository {
+        async fn get_glossary(
+            &self,
+            organization_id: Uuid,
+        ) -> Result<Vec<GlossaryEntry>, AppError> {
+            Ok(self
+                .entries
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|entry| entry.organization_id == organization_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn save_glossary_entry(&self, entry: &GlossaryEntry) -> Result<(), AppError> {
+            let mut entries = self.entries.lock().unwrap();
+            entries.retain(|existing| existing.id != entry.id);
+            entries.push(entry.clone());
+            Ok(())
+        }
+
+        async fn delete_glossary_entry(
+            &self,
+            organization_id: Uuid,
+            entry_id: Uuid,
+        ) -> Result<bool, AppError> {
+            let mut entries = self.entries.lock().unwrap();
+            let before = entries.len();
+            entries
+                .retain(|entry| entry.id != entry_id || entry.organization_id != organization_id);
+            Ok(entries.len() < before)
         }
     }
 
@@ -835,6 +1660,42 @@ mod tests {
                 estimated_hours: Some(3),
             }))
         }
+
+        async fn find_project_stories(
+            &self,
+            _project_id: Uuid,
+            _organization_id: Option<Uuid>,
+            selection: &StorySelection,
+        ) -> Result<Vec<Uuid>, AppError> {
+            Ok(selection.story_ids.clone().unwrap_or_default())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockStoryDependencyService {
+        dependencies: Vec<StoryDependency>,
+    }
+
+    #[async_trait]
+    impl StoryDependencyService for MockStoryDependencyService {
+        async fn get_story_dependencies(
+            &self,
+            _story_id: Uuid,
+            _organization_id: Option<Uuid>,
+        ) -> Result<Vec<StoryDependency>, AppError> {
+            Ok(self.dependencies.clone())
+        }
+    }
+
+    struct MockRepositoryService {
+        paths: Vec<String>,
+    }
+
+    #[async_trait]
+    impl RepositoryService for MockRepositoryService {
+        async fn list_files(&self, _repository: &RepositoryRef) -> Result<Vec<String>, AppError> {
+            Ok(self.paths.clone())
+        }
     }
 
     struct MockLlmService;
@@ -844,6 +1705,7 @@ mod tests {
         async fn generate_acceptance_criteria(
             &self,
             story_info: &crate::application::ports::StoryInfo,
+            _guidance: &PromptGuidance,
         ) -> Result<Vec<AcceptanceCriterion>, AppError> {
             Ok(vec![AcceptanceCriterion::new(
                 story_info.id,
@@ -857,9 +1719,22 @@ mod tests {
     }
 
     fn setup_usecases() -> ReadinessUsecases {
+        setup_usecases_with_vague_terms(Arc::new(MockVagueTermRepository::default()))
+    }
+
+    fn setup_usecases_with_vague_terms(
+        vague_term_repo: Arc<MockVagueTermRepository>,
+    ) -> ReadinessUsecases {
+        setup_usecases_with(vague_term_repo, Vec::new())
+    }
+
+    fn setup_usecases_with(
+        vague_term_repo: Arc<MockVagueTermRepository>,
+        dependencies: Vec<StoryDependency>,
+    ) -> ReadinessUsecases {
         let criteria_repo = Arc::new(MockAcceptanceCriteriaRepository::default());
         let readiness_repo = Arc::new(MockReadinessEvaluationRepository);
-        let task_analysis_repo = Arc::new(MockTaskAnalysisRepository);
+        let task_analysis_repo = Arc::new(MockTaskAnalysisRepository::default());
         let story_service = Arc::new(MockStoryService);
         let llm_service = Arc::new(MockLlmService);
 
@@ -867,7 +1742,11 @@ mod tests {
             criteria_repo,
             readiness_repo,
             task_analysis_repo,
+            vague_term_repo,
+            Arc::new(MockScoringProfileRepository::default()),
+            Arc::new(MockGlossaryRepository::default()),
             story_service,
+            Arc::new(MockStoryDependencyService { dependencies }),
             llm_service,
         )
     }
@@ -913,6 +1792,47 @@ mod tests {
         assert!(!evaluation.summary.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_draft_dependencies_and_open_blockers_make_a_story_unready() {
+        let dependency = |title: &str, status: &str, blocking: bool| StoryDependency {
+            story_id: Uuid::new_v4(),
+            title: title.to_string(),
+            status: status.to_string(),
+            blocking,
+        };
+        let story_id = Uuid::new_v4();
+
+        let independent = setup_usecases()
+            .evaluate_story_readiness(story_id, None)
+            .await
+            .unwrap();
+        let usecases = setup_usecases_with(
+            Arc::new(MockVagueTermRepository::default()),
+            vec![
+                dependency("Payment provider sandbox", "draft", false),
+                dependency("Checkout page", "ready", false),
+                dependency("Fraud checks", "ready", true),
+                dependency("Sign in", "accepted", true),
+            ],
+        );
+        let dependent = usecases
+            .evaluate_story_readiness(story_id, None)
+            .await
+            .unwrap();
+
+        assert_eq!(dependent.score, (independent.score - 20).max(0));
+        assert!(dependent
+            .missing_items
+            .contains(&"Depends on unready story \"Payment provider sandbox\"".to_string()));
+        assert!(dependent
+            .missing_items
+            .contains(&"Blocked by unaccepted story \"Fraud checks\"".to_string()));
+        assert!(!dependent
+            .missing_items
+            .iter()
+            .any(|item| item.contains("Checkout page") || item.contains("Sign in")));
+    }
+
     #[tokio::test]
     async fn test_validate_acceptance_criteria_refs() {
         let usecases = setup_usecases();
@@ -995,4 +1915,317 @@ mod tests {
         assert_eq!(criteria[1].ac_id, "AC2");
         assert_eq!(criteria[2].ac_id, "AC3");
     }
+
+    #[tokio::test]
+    async fn test_story_analysis_summary_follows_task_analyses() {
+        let usecases = setup_usecases();
+        let task_id = Uuid::new_v4();
+
+        let first = usecases.analyze_task(task_id, None).await.unwrap();
+        let summary = usecases
+            .get_story_analysis_summary(first.story_id, None, false)
+            .await
+            .unwrap();
+        assert_eq!(summary.tasks.len(), 1);
+        assert_eq!(summary.average_clarity(), Some(first.clarity_score));
+
+        // Re-analyzing the task replaces its entry rather than adding another
+        let stored_at = summary.updated_at;
+        let mut second = first.clone();
+        second.clarity_score = 100;
+        usecases
+            .task_analysis_repo
+            .record_in_story_summary(&second)
+            .await
+            .unwrap();
+        let summary = usecases
+            .get_story_analysis_summary(first.story_id, None, false)
+            .await
+            .unwrap();
+        assert_eq!(summary.tasks.len(), 1);
+        assert_eq!(summary.average_clarity(), Some(100));
+        assert!(summary.updated_at >= stored_at);
+
+        // A forced refresh rebuilds from the analysis history
+        let refreshed = usecases
+            .get_story_analysis_summary(first.story_id, None, true)
+            .await
+            .unwrap();
+        assert_eq!(refreshed.average_clarity(), Some(first.clarity_score));
+    }
+
+    #[tokio::test]
+    async fn test_downvoted_heuristic_is_left_out_of_later_analyses() {
+        let usecases = setup_usecases();
+        let task_id = Uuid::new_v4();
+
+        let analysis = usecases.analyze_task(task_id, None).await.unwrap();
+        let gap_type = analysis.recommendations[0].gap_type;
+
+        for user in ["user_1", "user_2", "user_3"] {
+            let feedback = usecases
+                .submit_analysis_feedback(
+                    task_id,
+                    None,
+                    user,
+                    "rec-1",
+                    FeedbackVerdict::Incorrect,
+                    None,
+                )
+                .await
+                .unwrap();
+            assert_eq!(feedback.gap_type, gap_type);
+        }
+        assert!(usecases
+            .is_heuristic_suppressed(None, gap_type)
+            .await
+            .unwrap());
+
+        let reanalyzed = usecases.analyze_task(task_id, None).await.unwrap();
+        assert!(reanalyzed
+            .recommendations
+            .iter()
+            .all(|recommendation| recommendation.gap_type != gap_type));
+
+        let unknown = usecases
+            .submit_analysis_feedback(
+                task_id,
+                None,
+                "user_1",
+                "rec-99",
+                FeedbackVerdict::Helpful,
+                None,
+            )
+            .await;
+        assert!(matches!(unknown, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_organization_vague_terms_are_cached_and_used_by_analysis() {
+        let vague_term_repo = Arc::new(MockVagueTermRepository::default());
+        let usecases = setup_usecases_with_vague_terms(vague_term_repo.clone());
+        let org_id = Some(Uuid::new_v4());
+
+        let dictionary = usecases
+            .replace_vague_term_overrides(
+                org_id,
+                vec![VagueTermOverride::new(
+                    "description",
+                    Some("Say what the task changes".to_string()),
+                    false,
+                )
+                .unwrap()],
+                ContentLanguage::English,
+            )
+            .await
+            .unwrap();
+        assert!(dictionary.terms().iter().any(|t| t.term == "description"));
+
+        let analysis = usecases.analyze_task(Uuid::new_v4(), org_id).await.unwrap();
+        let vague = analysis
+            .recommendations
+            .iter()
+            .find(|r| r.gap_type == crate::domain::GapType::VagueLanguage)
+            .expect("custom term is flagged");
+        assert!(vague
+            .specific_suggestions
+            .iter()
+            .any(|s| s.contains("'description': Say what the task changes")));
+
+        // Served from the cache filled by the write
+        let german = usecases
+            .vague_terms(org_id, ContentLanguage::German)
+            .await
+            .unwrap();
+        assert!(german.terms().iter().any(|t| t.term == "description"));
+        assert_eq!(*vague_term_repo.loads.lock().unwrap(), 0);
+
+        // Outside an organization the built-in dictionary applies
+        let personal = usecases.analyze_task(Uuid::new_v4(), None).await.unwrap();
+        assert!(personal
+            .recommendations
+            .iter()
+            .all(|r| r.gap_type != crate::domain::GapType::VagueLanguage));
+        assert!(usecases
+            .replace_vague_term_overrides(None, Vec::new(), ContentLanguage::English)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_organization_scoring_profile_drives_analysis_and_summary() {
+        let usecases = setup_usecases();
+        let org_id = Some(Uuid::new_v4());
+
+        let default_analysis = usecases.analyze_task(Uuid::new_v4(), org_id).await.unwrap();
+        assert!(default_analysis.clarity_score < 100);
+
+        let lenient = ScoringProfile {
+            missing_technical_details: 0,
+            vague_language: 0,
+            missing_ai_agent_compatibility: 0,
+            invalid_acceptance_criteria: 0,
+            ai_ready_threshold: 95,
+            ..ScoringProfile::default()
+        };
+        usecases
+            .replace_scoring_profile(org_id, lenient)
+            .await
+            .unwrap();
+        assert_eq!(usecases.scoring_profile(org_id).await.unwrap(), lenient);
+
+        let analysis = usecases.analyze_task(Uuid::new_v4(), org_id).await.unwrap();
+        assert_eq!(analysis.clarity_score, 100);
+        assert_eq!(
+            analysis.summary,
+            "Task is well-defined and ready for implementation"
+        );
+
+        let summary = usecases
+            .get_story_analysis_summary(default_analysis.story_id, org_id, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            summary.tasks_needing_attention(&lenient),
+            vec![default_analysis.task_id]
+        );
+
+        let invalid = ScoringProfile {
+            ai_ready_threshold: 101,
+            ..lenient
+        };
+        assert!(usecases
+            .replace_scoring_profile(org_id, invalid)
+            .await
+            .is_err());
+        assert!(usecases
+            .replace_scoring_profile(None, lenient)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_story_draft_lint_skips_task_checks() {
+        let usecases = setup_usecases();
+        let draft = StoryInfo {
+            id: Uuid::nil(),
+            title: "As a shopper, I want to save my card so that checkout is faster".to_string(),
+            description: None,
+            story_points: Some(3),
+        };
+
+        let evaluation = usecases.lint_story_draft(None, &draft, &[]);
+        assert!(evaluation
+            .missing_items
+            .iter()
+            .any(|item| item == "Story description is missing"));
+        assert!(evaluation
+            .missing_items
+            .iter()
+            .all(|item| !item.contains("tasks")));
+        assert!(evaluation.fixes.is_empty());
+        assert_eq!(evaluation.score, 65);
+    }
+
+    #[tokio::test]
+    async fn test_glossary_is_scoped_to_the_organization() {
+        let usecases = setup_usecases();
+        let organization_id = Uuid::new_v4();
+
+        assert!(matches!(
+            usecases
+                .add_glossary_entry(None, "Pod", "A team", vec![])
+                .await,
+            Err(AppError::BadRequest(_))
+        ));
+        let entry = usecases
+            .add_glossary_entry(
+                Some(organization_id),
+                "Pod",
+                "A team",
+                vec!["squad".to_string()],
+            )
+            .await
+            .unwrap();
+        let relevant = usecases
+            .glossary_for_text(Some(organization_id), "Each squad owns a service")
+            .await
+            .unwrap();
+        assert_eq!(relevant, vec![entry.clone()]);
+
+        assert!(matches!(
+            usecases
+                .update_glossary_entry(Some(Uuid::new_v4()), entry.id, "Pod", "A crew", vec![])
+                .await,
+            Err(AppError::NotFound(_))
+        ));
+        let updated = usecases
+            .update_glossary_entry(Some(organization_id), entry.id, "Pod", "A crew", vec![])
+            .await
+            .unwrap();
+        assert_eq!(updated.definition, "A crew");
+        assert!(usecases
+            .glossary_for_text(Some(organization_id), "Each squad owns a service")
+            .await
+            .unwrap()
+            .is_empty());
+
+        usecases
+            .delete_glossary_entry(Some(organization_id), entry.id)
+            .await
+            .unwrap();
+        assert!(usecases
+            .get_glossary(Some(organization_id))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_test_mapping_needs_criteria_and_a_repository_integration() {
+        let story_id = Uuid::new_v4();
+        let repository = || RepositoryRef::new("acme/shop", None).unwrap();
+        assert!(matches!(
+            setup_usecases()
+                .suggest_test_mapping(story_id, None, repository())
+                .await,
+            Err(AppError::BadRequest(_))
+        ));
+
+        let usecases = setup_usecases().with_repository_service(Arc::new(MockRepositoryService {
+            paths: vec![
+                "services/orders/tests/refund_flow.rs".to_string(),
+                "services/orders/src/refund.rs".to_string(),
+            ],
+        }));
+        assert!(matches!(
+            usecases
+                .suggest_test_mapping(story_id, None, repository())
+                .await,
+            Err(AppError::BadRequest(_))
+        ));
+
+        usecases
+            .add_acceptance_criteria(
+                story_id,
+                None,
+                vec![(
+                    "AC1".to_string(),
+                    "an order was paid".to_string(),
+                    "the customer asks for a refund".to_string(),
+                    "the payment is returned".to_string(),
+                )],
+            )
+            .await
+            .unwrap();
+        let mapping = usecases
+            .suggest_test_mapping(story_id, None, repository())
+            .await
+            .unwrap();
+        assert_eq!(mapping.test_files_scanned, 1);
+        assert_eq!(
+            mapping.criteria[0].suggestions[0].path,
+            "services/orders/tests/refund_flow.rs"
+        );
+    }
 }
@@ -0,0 +1,195 @@
+use crate::domain::{GapType, Recommendation};
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+const MAX_COMMENT_LENGTH: usize = 1000;
+/// A heuristic needs at least this many votes in an organization before it can be suppressed
+pub const MIN_VOTES_TO_SUPPRESS: u32 = 3;
+/// Share of "incorrect" votes at which a heuristic stops being reported
+pub const SUPPRESS_INCORRECT_RATIO: f64 = 0.75;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedbackVerdict {
+    Helpful,
+    Incorrect,
+}
+
+impl FeedbackVerdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Helpful => "helpful",
+            Self::Incorrect => "incorrect",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "helpful" => Some(Self::Helpful),
+            "incorrect" => Some(Self::Incorrect),
+            _ => None,
+        }
+    }
+}
+
+/// A user's verdict on one recommendation from a task analysis. Each user has
+/// one verdict per task and heuristic; voting again replaces it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisFeedback {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub story_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub user_id: String,
+    pub gap_type: GapType,
+    /// The recommendation as it was shown
+    pub recommendation: String,
+    pub verdict: FeedbackVerdict,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AnalysisFeedback {
+    pub fn new(
+        task_id: Uuid,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        user_id: String,
+        recommendation: &Recommendation,
+        verdict: FeedbackVerdict,
+        comment: Option<String>,
+    ) -> Result<Self, AppError> {
+        let comment = comment
+            .map(|comment| comment.trim().to_string())
+            .filter(|comment| !comment.is_empty());
+        if comment
+            .as_ref()
+            .is_some_and(|comment| comment.chars().count() > MAX_COMMENT_LENGTH)
+        {
+            return Err(AppError::BadRequest(format!(
+                "Feedback comment cannot exceed {} characters",
+                MAX_COMMENT_LENGTH
+            )));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            task_id,
+            story_id,
+            organization_id,
+            user_id,
+            gap_type: recommendation.gap_type,
+            recommendation: recommendation.message.clone(),
+            verdict,
+            comment,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// Votes on one heuristic across an organization's tasks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeuristicTally {
+    pub gap_type: GapType,
+    pub helpful: u32,
+    pub incorrect: u32,
+}
+
+impl HeuristicTally {
+    /// Whether the organization has consistently marked the heuristic as wrong
+    pub fn is_suppressed(&self) -> bool {
+        let votes = self.helpful + self.incorrect;
+        votes >= MIN_VOTES_TO_SUPPRESS
+            && self.incorrect as f64 / votes as f64 >= SUPPRESS_INCORRECT_RATIO
+    }
+}
+
+pub fn suppressed_heuristics(tallies: &[HeuristicTally]) -> HashSet<GapType> {
+    tallies
+        .iter()
+        .filter(|tally| tally.is_suppressed())
+        .map(|tally| tally.gap_type)
+        .collect()
+}
+
+/// Feedback phrased for an LLM prompt, so generated content avoids what
+/// reviewers rejected and keeps what they found useful
+pub fn calibration_notes(feedback: &[AnalysisFeedback]) -> Vec<String> {
+    feedback
+        .iter()
+        .map(|entry| {
+            let verdict = match entry.verdict {
+                FeedbackVerdict::Helpful => "Helpful",
+                FeedbackVerdict::Incorrect => "Incorrect",
+            };
+            match &entry.comment {
+                Some(comment) => format!("{}: \"{}\" ({})", verdict, entry.recommendation, comment),
+                None => format!("{}: \"{}\"", verdict, entry.recommendation),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(helpful: u32, incorrect: u32) -> HeuristicTally {
+        HeuristicTally {
+            gap_type: GapType::VagueLanguage,
+            helpful,
+            incorrect,
+        }
+    }
+
+    #[test]
+    fn test_heuristics_are_suppressed_only_when_consistently_downvoted() {
+        assert!(!tally(0, 2).is_suppressed());
+        assert!(tally(0, 3).is_suppressed());
+        assert!(tally(1, 3).is_suppressed());
+        assert!(!tally(2, 3).is_suppressed());
+
+        let suppressed = suppressed_heuristics(&[
+            tally(1, 5),
+            HeuristicTally {
+                gap_type: GapType::MissingAcceptanceCriteria,
+                helpful: 4,
+                incorrect: 1,
+            },
+        ]);
+        assert_eq!(
+            suppressed.into_iter().collect::<Vec<_>>(),
+            vec![GapType::VagueLanguage]
+        );
+    }
+
+    #[test]
+    fn test_feedback_comment_is_trimmed_into_calibration_notes() {
+        let recommendation = Recommendation {
+            gap_type: GapType::VagueLanguage,
+            message: "Task contains vague or ambiguous language".to_string(),
+            specific_suggestions: vec![],
+            ac_references: vec![],
+        };
+        let feedback = AnalysisFeedback::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            None,
+            "user_1".to_string(),
+            &recommendation,
+            FeedbackVerdict::Incorrect,
+            Some("  'fix' is our ticket prefix  ".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            calibration_notes(&[feedback]),
+            vec![
+                "Incorrect: \"Task contains vague or ambiguous language\" ('fix' is our ticket prefix)"
+            ]
+        );
+    }
+}
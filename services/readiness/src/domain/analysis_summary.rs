@@ -0,0 +1,154 @@
+use crate::domain::{ScoringProfile, TaskAnalysis};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// The part of a task's latest analysis the story summary aggregates
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TaskClarity {
+    pub clarity_score: i32,
+    pub missing_elements: Vec<String>,
+}
+
+/// Readiness of a story's tasks, kept up to date as tasks are analyzed rather
+/// than recomputed from the analysis history on every read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoryAnalysisSummary {
+    pub story_id: Uuid,
+    pub organization_id: Option<Uuid>,
+    /// Latest analysis of each task in the story
+    pub tasks: BTreeMap<Uuid, TaskClarity>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StoryAnalysisSummary {
+    pub fn new(story_id: Uuid, organization_id: Option<Uuid>) -> Self {
+        Self {
+            story_id,
+            organization_id,
+            tasks: BTreeMap::new(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Rebuild from the latest analysis of each task
+    pub fn from_analyses(
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        analyses: &[TaskAnalysis],
+    ) -> Self {
+        let mut summary = Self::new(story_id, organization_id);
+        for analysis in analyses {
+            summary.record(analysis);
+        }
+        summary
+    }
+
+    /// Replace the task's entry with its newest analysis
+    pub fn record(&mut self, analysis: &TaskAnalysis) {
+        self.tasks.insert(
+            analysis.task_id,
+            TaskClarity {
+                clarity_score: analysis.clarity_score.clamp(0, 100),
+                missing_elements: analysis.missing_elements.clone(),
+            },
+        );
+        self.updated_at = Utc::now();
+    }
+
+    pub fn average_clarity(&self) -> Option<i32> {
+        if self.tasks.is_empty() {
+            return None;
+        }
+        let total: i32 = self.tasks.values().map(|task| task.clarity_score).sum();
+        Some((total as f64 / self.tasks.len() as f64).round() as i32)
+    }
+
+    pub fn lowest_clarity(&self) -> Option<(Uuid, i32)> {
+        self.tasks
+            .iter()
+            .min_by_key(|(_, task)| task.clarity_score)
+            .map(|(task_id, task)| (*task_id, task.clarity_score))
+    }
+
+    /// Tasks scoring below the organization's AI-readiness threshold
+    pub fn tasks_needing_attention(&self, scoring: &ScoringProfile) -> Vec<Uuid> {
+        self.tasks
+            .iter()
+            .filter(|(_, task)| !scoring.is_ai_ready(task.clarity_score))
+            .map(|(task_id, _)| *task_id)
+            .collect()
+    }
+
+    /// How many tasks lack each element, most common first
+    pub fn missing_element_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for element in self.tasks.values().flat_map(|task| &task.missing_elements) {
+            *counts.entry(element.as_str()).or_default() += 1;
+        }
+
+        let mut counts: Vec<(String, usize)> = counts
+            .into_iter()
+            .map(|(element, count)| (element.to_string(), count))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analysis(task_id: Uuid, story_id: Uuid, score: i32, missing: &[&str]) -> TaskAnalysis {
+        TaskAnalysis {
+            id: Uuid::new_v4(),
+            task_id,
+            story_id,
+            organization_id: None,
+            clarity_score: score,
+            missing_elements: missing.iter().map(|m| m.to_string()).collect(),
+            summary: String::new(),
+            recommendations: Vec::new(),
+            language: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_record_replaces_a_tasks_previous_analysis() {
+        let story_id = Uuid::new_v4();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut summary = StoryAnalysisSummary::from_analyses(
+            story_id,
+            None,
+            &[
+                analysis(first, story_id, 40, &["acceptance criteria", "tests"]),
+                analysis(second, story_id, 90, &["tests"]),
+            ],
+        );
+        assert_eq!(summary.average_clarity(), Some(65));
+        let scoring = ScoringProfile::default();
+        assert_eq!(summary.tasks_needing_attention(&scoring), vec![first]);
+        assert_eq!(
+            summary.missing_element_counts()[0],
+            ("tests".to_string(), 2)
+        );
+
+        let before = summary.updated_at;
+        summary.record(&analysis(first, story_id, 80, &[]));
+        assert_eq!(summary.tasks.len(), 2);
+        assert_eq!(summary.average_clarity(), Some(85));
+        assert_eq!(summary.lowest_clarity(), Some((first, 80)));
+        assert!(summary.tasks_needing_attention(&scoring).is_empty());
+        let stricter = ScoringProfile {
+            ai_ready_threshold: 85,
+            ..scoring
+        };
+        assert_eq!(summary.tasks_needing_attention(&stricter), vec![first]);
+        assert!(summary.updated_at >= before);
+
+        let empty = StoryAnalysisSummary::new(story_id, None);
+        assert_eq!(empty.average_clarity(), None);
+    }
+}
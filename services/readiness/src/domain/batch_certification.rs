@@ -0,0 +1,138 @@
+use crate::domain::ReadinessEvaluation;
+use serde::Serialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Most stories a single batch evaluates
+pub const MAX_BATCH_STORIES: usize = 100;
+/// Evaluations run at the same time within a batch
+pub const BATCH_EVALUATION_CONCURRENCY: usize = 8;
+/// How many blocking issues a batch summary reports
+const TOP_BLOCKING_ISSUES: usize = 5;
+
+/// Which of a project's stories a batch evaluates; the filters narrow one another
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorySelection {
+    pub story_ids: Option<Vec<Uuid>>,
+    pub status: Option<String>,
+    pub label: Option<String>,
+    pub sprint_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStoryResult {
+    pub story_id: Uuid,
+    pub passed: bool,
+    pub score: i32,
+    pub missing_items: Vec<String>,
+}
+
+/// A missing item and how many stories in the batch it blocks
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockingIssue {
+    pub issue: String,
+    pub story_count: usize,
+}
+
+/// Pass/fail certification of a batch of stories ahead of sprint planning
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchCertification {
+    pub project_id: Uuid,
+    pub evaluated: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub results: Vec<BatchStoryResult>,
+    pub top_blocking_issues: Vec<BlockingIssue>,
+}
+
+impl BatchCertification {
+    /// Summarize evaluations, failing stories first and lowest score first
+    pub fn from_evaluations(project_id: Uuid, evaluations: &[ReadinessEvaluation]) -> Self {
+        let mut results: Vec<BatchStoryResult> = evaluations
+            .iter()
+            .map(|evaluation| BatchStoryResult {
+                story_id: evaluation.story_id,
+                passed: evaluation.is_ready(),
+                score: evaluation.score,
+                missing_items: evaluation.missing_items.clone(),
+            })
+            .collect();
+        results.sort_by_key(|result| (result.passed, result.score));
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for result in results.iter().filter(|result| !result.passed) {
+            for item in &result.missing_items {
+                *counts.entry(item.as_str()).or_default() += 1;
+            }
+        }
+        let mut top_blocking_issues: Vec<BlockingIssue> = counts
+            .into_iter()
+            .map(|(issue, story_count)| BlockingIssue {
+                issue: issue.to_string(),
+                story_count,
+            })
+            .collect();
+        top_blocking_issues.sort_by(|a, b| {
+            b.story_count
+                .cmp(&a.story_count)
+                .then_with(|| a.issue.cmp(&b.issue))
+        });
+        top_blocking_issues.truncate(TOP_BLOCKING_ISSUES);
+
+        let passed = results.iter().filter(|result| result.passed).count();
+        Self {
+            project_id,
+            evaluated: results.len(),
+            passed,
+            failed: results.len() - passed,
+            results,
+            top_blocking_issues,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluation(score: i32, missing_items: &[&str]) -> ReadinessEvaluation {
+        ReadinessEvaluation::new(
+            Uuid::new_v4(),
+            None,
+            score,
+            missing_items.iter().map(|item| item.to_string()).collect(),
+            String::new(),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn test_summarizes_failures_and_ranks_blocking_issues() {
+        let project_id = Uuid::new_v4();
+        let evaluations = vec![
+            evaluation(100, &[]),
+            evaluation(40, &["No acceptance criteria", "No story points"]),
+            evaluation(60, &["No acceptance criteria"]),
+        ];
+
+        let summary = BatchCertification::from_evaluations(project_id, &evaluations);
+
+        assert_eq!(
+            (summary.evaluated, summary.passed, summary.failed),
+            (3, 1, 2)
+        );
+        assert_eq!(summary.results[0].score, 40);
+        assert!(summary.results[2].passed);
+        assert_eq!(
+            summary.top_blocking_issues[0],
+            BlockingIssue {
+                issue: "No acceptance criteria".to_string(),
+                story_count: 2,
+            }
+        );
+        assert_eq!(summary.top_blocking_issues.len(), 2);
+    }
+}
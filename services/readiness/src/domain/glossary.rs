@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const MAX_TERM_LENGTH: usize = 100;
+const MAX_DEFINITION_LENGTH: usize = 1000;
+const MAX_SYNONYMS: usize = 20;
+pub const MAX_GLOSSARY_ENTRIES: usize = 500;
+/// How many matching entries are passed to the LLM with one prompt
+pub const MAX_PROMPT_GLOSSARY_ENTRIES: usize = 20;
+
+/// A term with the meaning an organization gives it, so generated content
+/// uses the team's vocabulary the way the team does
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub term: String,
+    pub definition: String,
+    /// Other words for the same thing; they match like the term does
+    pub synonyms: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl GlossaryEntry {
+    pub fn new(
+        organization_id: Uuid,
+        term: &str,
+        definition: &str,
+        synonyms: Vec<String>,
+    ) -> Result<Self, AppError> {
+        let now = Utc::now();
+        let mut entry = Self {
+            id: Uuid::new_v4(),
+            organization_id,
+            term: String::new(),
+            definition: String::new(),
+            synonyms: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        };
+        entry.update(term, definition, synonyms)?;
+        Ok(entry)
+    }
+
+    pub fn update(
+        &mut self,
+        term: &str,
+        definition: &str,
+        synonyms: Vec<String>,
+    ) -> Result<(), AppError> {
+        let term = term.trim();
+        if term.is_empty() || term.chars().count() > MAX_TERM_LENGTH {
+            return Err(AppError::BadRequest(format!(
+                "Glossary terms must be between 1 and {} characters",
+                MAX_TERM_LENGTH
+            )));
+        }
+        let definition = definition.trim();
+        if definition.is_empty() || definition.chars().count() > MAX_DEFINITION_LENGTH {
+            return Err(AppError::BadRequest(format!(
+                "Definitions must be between 1 and {} characters",
+                MAX_DEFINITION_LENGTH
+            )));
+        }
+
+        let mut kept: Vec<String> = Vec::new();
+        for synonym in synonyms {
+            let synonym = synonym.trim();
+            if synonym.is_empty()
+                || synonym.eq_ignore_ascii_case(term)
+                || kept.iter().any(|kept| kept.eq_ignore_ascii_case(synonym))
+            {
+                continue;
+            }
+            if synonym.chars().count() > MAX_TERM_LENGTH {
+                return Err(AppError::BadRequest(format!(
+                    "Synonyms cannot exceed {} characters",
+                    MAX_TERM_LENGTH
+                )));
+            }
+            kept.push(synonym.to_string());
+        }
+        if kept.len() > MAX_SYNONYMS {
+            return Err(AppError::BadRequest(format!(
+                "A term can have at most {} synonyms",
+                MAX_SYNONYMS
+            )));
+        }
+
+        self.term = term.to_string();
+        self.definition = definition.to_string();
+        self.synonyms = kept;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Whether the term or one of its synonyms appears in `text` as whole
+    /// words, ignoring case
+    pub fn appears_in(&self, text: &str) -> bool {
+        let text = format!(" {} ", words(text));
+        std::iter::once(&self.term)
+            .chain(&self.synonyms)
+            .map(|phrase| words(phrase))
+            .any(|phrase| !phrase.is_empty() && text.contains(&format!(" {} ", phrase)))
+    }
+}
+
+/// Lowercase words separated by single spaces
+fn words(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The entries whose term or synonyms appear in `text`, in glossary order
+/// and at most `MAX_PROMPT_GLOSSARY_ENTRIES` of them
+pub fn relevant_glossary_entries(entries: &[GlossaryEntry], text: &str) -> Vec<GlossaryEntry> {
+    entries
+        .iter()
+        .filter(|entry| entry.appears_in(text))
+        .take(MAX_PROMPT_GLOSSARY_ENTRIES)
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_are_validated() {
+        let organization_id = Uuid::new_v4();
+        let entry = GlossaryEntry::new(
+            organization_id,
+            " Pod ",
+            " A cross-functional team of up to six people ",
+            vec![
+                "squad".to_string(),
+                "Squad".to_string(),
+                "pod".to_string(),
+                " ".to_string(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(entry.term, "Pod");
+        assert_eq!(
+            entry.definition,
+            "A cross-functional team of up to six people"
+        );
+        assert_eq!(entry.synonyms, vec!["squad".to_string()]);
+
+        assert!(GlossaryEntry::new(organization_id, "Pod", " ", vec![]).is_err());
+        assert!(GlossaryEntry::new(organization_id, "", "Team", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_relevant_entries_match_whole_words() {
+        let organization_id = Uuid::new_v4();
+        let entries = vec![
+            GlossaryEntry::new(organization_id, "Pod", "A team", vec!["squad".to_string()])
+                .unwrap(),
+            GlossaryEntry::new(organization_id, "Ledger entry", "A posting", vec![]).unwrap(),
+        ];
+
+        let relevant = |text: &str| -> Vec<String> {
+            relevant_glossary_entries(&entries, text)
+                .into_iter()
+                .map(|entry| entry.term)
+                .collect()
+        };
+        assert_eq!(relevant("Each Squad owns a service"), vec!["Pod"]);
+        assert_eq!(
+            relevant("Show the pod's LEDGER-entry history"),
+            vec!["Pod", "Ledger entry"]
+        );
+        assert!(relevant("Tripods and ledgers").is_empty());
+    }
+}
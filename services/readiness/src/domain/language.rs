@@ -0,0 +1,226 @@
+use crate::domain::ScoringProfile;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// A detection needs at least this many marker words before it overrides English
+const MIN_DETECTION_HITS: usize = 2;
+
+/// Language a story or task is written in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum ContentLanguage {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "es")]
+    Spanish,
+}
+
+/// Something the analyzer looks for in a task description
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DescriptionCheck {
+    FilePaths,
+    FunctionsOrComponents,
+    InputsAndOutputs,
+    TechnicalApproach,
+    SuccessCriteria,
+    Dependencies,
+    EnvironmentSetup,
+    TestCoverage,
+    DefinitionOfDone,
+}
+
+impl ContentLanguage {
+    pub const ALL: [ContentLanguage; 3] = [Self::English, Self::German, Self::Spanish];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+            Self::Spanish => "es",
+        }
+    }
+
+    /// English name, as used in LLM prompts
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "German",
+            Self::Spanish => "Spanish",
+        }
+    }
+
+    /// Common words that only occur in running text of the language
+    fn marker_words(&self) -> &'static [&'static str] {
+        match self {
+            Self::English => &[
+                "the", "and", "is", "to", "of", "for", "with", "should", "be", "when", "that",
+                "it", "on", "are", "this",
+            ],
+            Self::German => &[
+                "der", "die", "das", "und", "ist", "nicht", "mit", "für", "ein", "eine", "den",
+                "dem", "zu", "auf", "wird", "werden", "soll", "sollen", "von", "im", "wenn",
+                "dass",
+            ],
+            Self::Spanish => &[
+                "el", "la", "los", "las", "de", "que", "y", "en", "un", "una", "es", "para", "con",
+                "por", "del", "al", "debe", "como", "su", "cuando",
+            ],
+        }
+    }
+
+    /// The language the text is most likely written in; English unless another
+    /// language's marker words clearly outnumber English ones
+    pub fn detect(text: &str) -> Self {
+        let lower = text.to_lowercase();
+        let words: Vec<&str> = lower
+            .split(|c: char| !c.is_alphabetic())
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        let hits = |language: ContentLanguage| {
+            words
+                .iter()
+                .filter(|word| language.marker_words().contains(word))
+                .count()
+        };
+        let english = hits(Self::English);
+        [Self::German, Self::Spanish]
+            .into_iter()
+            .map(|language| (language, hits(language)))
+            .filter(|(_, count)| *count >= MIN_DETECTION_HITS && *count > english)
+            .max_by_key(|(_, count)| *count)
+            .map(|(language, _)| language)
+            .unwrap_or(Self::English)
+    }
+
+    /// Keywords that show a description covers the check. Code-level terms are
+    /// often written in English whatever the language, so English keywords
+    /// count for every language.
+    fn keywords(&self, check: DescriptionCheck) -> &'static [&'static str] {
+        use DescriptionCheck::*;
+        match (self, check) {
+            (Self::English, FilePaths) => &["file", "path", ".rs", ".ts"],
+            (Self::English, FunctionsOrComponents) => &["function", "component", "struct", "class"],
+            (Self::English, InputsAndOutputs) => &["input", "output", "return", "parameter"],
+            (Self::English, TechnicalApproach) => {
+                &["architecture", "approach", "design", "pattern"]
+            }
+            (Self::English, SuccessCriteria) => &["success", "complete", "done"],
+            (Self::English, Dependencies) => &["depend", "require", "prerequisite"],
+            (Self::English, EnvironmentSetup) => &["environment", "setup", "configuration"],
+            (Self::English, TestCoverage) => &["test", "coverage"],
+            (Self::English, DefinitionOfDone) => &["definition of done", "completion criteria"],
+            (Self::German, FilePaths) => &["datei", "pfad"],
+            (Self::German, FunctionsOrComponents) => &["funktion", "komponente", "klasse"],
+            (Self::German, InputsAndOutputs) => &["eingabe", "ausgabe", "rückgabe"],
+            (Self::German, TechnicalApproach) => &["architektur", "ansatz", "entwurf", "muster"],
+            (Self::German, SuccessCriteria) => &["erfolg", "abgeschlossen", "fertig"],
+            (Self::German, Dependencies) => &["abhängig", "voraussetzung", "benötigt"],
+            (Self::German, EnvironmentSetup) => &["umgebung", "einrichtung", "konfiguration"],
+            (Self::German, TestCoverage) => &["abdeckung"],
+            (Self::German, DefinitionOfDone) => &["abnahmekriterien", "fertigstellungskriterien"],
+            (Self::Spanish, FilePaths) => &["archivo", "fichero", "ruta"],
+            (Self::Spanish, FunctionsOrComponents) => {
+                &["función", "funcion", "componente", "clase"]
+            }
+            (Self::Spanish, InputsAndOutputs) => &["entrada", "salida", "devuelve", "parámetro"],
+            (Self::Spanish, TechnicalApproach) => &["arquitectura", "enfoque", "diseño", "patrón"],
+            (Self::Spanish, SuccessCriteria) => &["éxito", "completo", "completa", "terminado"],
+            (Self::Spanish, Dependencies) => &["requiere", "requisito"],
+            (Self::Spanish, EnvironmentSetup) => &["entorno", "instalación", "configuración"],
+            (Self::Spanish, TestCoverage) => &["prueba", "cobertura"],
+            (Self::Spanish, DefinitionOfDone) => {
+                &["definición de terminado", "criterios de finalización"]
+            }
+        }
+    }
+
+    /// Whether the lowercased description mentions any keyword for the check
+    pub fn mentions(&self, description_lower: &str, check: DescriptionCheck) -> bool {
+        [Self::English, *self]
+            .iter()
+            .flat_map(|language| language.keywords(check))
+            .any(|keyword| description_lower.contains(keyword))
+    }
+
+    /// One-line verdict for a task's clarity score
+    pub fn clarity_summary(&self, scoring: &ScoringProfile, clarity_score: i32) -> &'static str {
+        let band = if scoring.is_ai_ready(clarity_score) {
+            0
+        } else if scoring.needs_improvements(clarity_score) {
+            1
+        } else {
+            2
+        };
+        let summaries = match self {
+            Self::English => [
+                "Task is well-defined and ready for implementation",
+                "Task needs some improvements before it's ready",
+                "Task requires significant clarification",
+            ],
+            Self::German => [
+                "Die Aufgabe ist klar beschrieben und bereit zur Umsetzung",
+                "Die Aufgabe braucht noch einige Verbesserungen",
+                "Die Aufgabe muss deutlich präzisiert werden",
+            ],
+            Self::Spanish => [
+                "La tarea está bien definida y lista para implementarse",
+                "La tarea necesita algunas mejoras antes de estar lista",
+                "La tarea requiere aclaraciones importantes",
+            ],
+        };
+        summaries[band]
+    }
+}
+
+impl FromStr for ContentLanguage {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|language| {
+                value.eq_ignore_ascii_case(language.code())
+                    || value.eq_ignore_ascii_case(language.name())
+            })
+            .ok_or_else(|| AppError::BadRequest(format!("Unsupported language: {}", value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_german_and_spanish_and_defaults_to_english() {
+        assert_eq!(
+            ContentLanguage::detect("Die Anmeldung soll mit dem Passwort funktionieren"),
+            ContentLanguage::German
+        );
+        assert_eq!(
+            ContentLanguage::detect("El usuario debe poder iniciar sesión con su correo"),
+            ContentLanguage::Spanish
+        );
+        assert_eq!(
+            ContentLanguage::detect("The user should be able to log in with the password"),
+            ContentLanguage::English
+        );
+        assert_eq!(ContentLanguage::detect("OAuth"), ContentLanguage::English);
+        assert_eq!(
+            "DE".parse::<ContentLanguage>().unwrap(),
+            ContentLanguage::German
+        );
+        assert!("fr".parse::<ContentLanguage>().is_err());
+    }
+
+    #[test]
+    fn test_english_keywords_count_for_every_language() {
+        let german = ContentLanguage::German;
+        assert!(german.mentions("ändere die datei config.rs", DescriptionCheck::FilePaths));
+        assert!(german.mentions("unit test ergänzen", DescriptionCheck::TestCoverage));
+        assert!(!ContentLanguage::English.mentions("ändere die datei", DescriptionCheck::FilePaths));
+    }
+}
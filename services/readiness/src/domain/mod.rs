@@ -1,11 +1,27 @@
 pub mod acceptance_criteria;
+pub mod analysis_feedback;
+pub mod analysis_summary;
+pub mod batch_certification;
+pub mod glossary;
+pub mod language;
 pub mod readiness_eval;
 pub mod recommendation_generator;
+pub mod scoring_profile;
 pub mod task_analysis;
 pub mod task_analyzer;
+pub mod test_mapping;
+pub mod vague_terms;
 
 pub use acceptance_criteria::*;
+pub use analysis_feedback::*;
+pub use analysis_summary::*;
+pub use batch_certification::*;
+pub use glossary::*;
+pub use language::*;
 pub use readiness_eval::*;
 pub use recommendation_generator::*;
+pub use scoring_profile::*;
 pub use task_analysis::*;
 pub use task_analyzer::*;
+pub use test_mapping::*;
+pub use vague_terms::*;
@@ -10,6 +10,9 @@ pub struct ReadinessEvaluation {
     pub missing_items: Vec<String>,
     pub summary: String,
     pub recommendations: Vec<String>,
+    /// One-click fixes for the gaps in `missing_items`
+    #[serde(default)]
+    pub fixes: Vec<ReadinessFix>,
 }
 
 impl ReadinessEvaluation {
@@ -29,15 +32,93 @@ impl ReadinessEvaluation {
             missing_items,
             summary,
             recommendations,
+            fixes: Vec::new(),
         }
     }
 
-    #[allow(dead_code)]
+    pub fn with_fixes(mut self, fixes: Vec<ReadinessFix>) -> Self {
+        self.fixes = fixes;
+        self
+    }
+
     pub fn is_ready(&self) -> bool {
         self.score >= 80 && self.missing_items.is_empty()
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessFixType {
+    EditTitle,
+    EditDescription,
+    SetPoints,
+    AddCriteria,
+    RefineCriterion,
+    CreateTasks,
+    CoverCriterion,
+}
+
+/// A machine-actionable fix for one missing item, naming the endpoint that resolves it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessFix {
+    #[serde(rename = "type")]
+    pub fix_type: ReadinessFixType,
+    /// The story, or the acceptance criterion for criterion-level fixes
+    pub target: Uuid,
+    pub story_id: Uuid,
+    /// The missing item this fix addresses, verbatim
+    pub missing_item: String,
+    pub method: String,
+    pub path: String,
+}
+
+impl ReadinessFix {
+    pub fn for_story(fix_type: ReadinessFixType, story_id: Uuid, missing_item: &str) -> Self {
+        Self::build(fix_type, story_id, story_id, missing_item)
+    }
+
+    pub fn for_criterion(
+        fix_type: ReadinessFixType,
+        story_id: Uuid,
+        criterion_id: Uuid,
+        missing_item: &str,
+    ) -> Self {
+        Self::build(fix_type, story_id, criterion_id, missing_item)
+    }
+
+    fn build(fix_type: ReadinessFixType, story_id: Uuid, target: Uuid, missing_item: &str) -> Self {
+        let (method, path) = match fix_type {
+            ReadinessFixType::EditTitle
+            | ReadinessFixType::EditDescription
+            | ReadinessFixType::SetPoints => ("PATCH", format!("/api/v1/stories/{}", story_id)),
+            ReadinessFixType::AddCriteria => (
+                "POST",
+                format!("/api/v1/stories/{}/acceptance-criteria", story_id),
+            ),
+            ReadinessFixType::RefineCriterion => (
+                "PATCH",
+                format!(
+                    "/api/v1/stories/{}/acceptance-criteria/{}",
+                    story_id, target
+                ),
+            ),
+            ReadinessFixType::CreateTasks | ReadinessFixType::CoverCriterion => {
+                ("POST", format!("/api/v1/stories/{}/tasks", story_id))
+            }
+        };
+
+        Self {
+            fix_type,
+            target,
+            story_id,
+            missing_item: missing_item.to_string(),
+            method: method.to_string(),
+            path,
+        }
+    }
+}
+
 #[derive(Debug)]
 #[allow(dead_code)]
 pub enum ReadinessCheck {
@@ -103,4 +184,28 @@ mod tests {
         let eval = ReadinessEvaluation::new(story_id, None, -50, vec![], "".to_string(), vec![]);
         assert_eq!(eval.score, 0);
     }
+
+    #[test]
+    fn test_fix_descriptors_point_at_resolving_endpoints() {
+        let story_id = Uuid::new_v4();
+        let criterion_id = Uuid::new_v4();
+
+        let points = ReadinessFix::for_story(ReadinessFixType::SetPoints, story_id, "No points");
+        assert_eq!(points.target, story_id);
+        assert_eq!(points.method, "PATCH");
+        assert_eq!(points.path, format!("/api/v1/stories/{}", story_id));
+
+        let cover = ReadinessFix::for_criterion(
+            ReadinessFixType::CoverCriterion,
+            story_id,
+            criterion_id,
+            "AC not covered",
+        );
+        assert_eq!(cover.target, criterion_id);
+        assert_eq!(cover.path, format!("/api/v1/stories/{}/tasks", story_id));
+
+        let json = serde_json::to_value(&points).unwrap();
+        assert_eq!(json["type"], "set_points");
+        assert_eq!(json["missingItem"], "No points");
+    }
 }
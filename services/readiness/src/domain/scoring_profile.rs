@@ -0,0 +1,102 @@
+use common::AppError;
+use serde::{Deserialize, Serialize};
+
+const MAX_SCORE: i32 = 100;
+
+/// How many clarity points the task analyzer deducts for each gap, and the
+/// score at which a task counts as ready for an AI agent to pick up
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScoringProfile {
+    pub missing_description: i32,
+    pub missing_technical_details: i32,
+    pub vague_language: i32,
+    pub missing_ai_agent_compatibility: i32,
+    pub missing_acceptance_criteria: i32,
+    pub invalid_acceptance_criteria: i32,
+    pub missing_estimate: i32,
+    pub ai_ready_threshold: i32,
+}
+
+impl Default for ScoringProfile {
+    fn default() -> Self {
+        Self {
+            missing_description: 20,
+            missing_technical_details: 15,
+            vague_language: 10,
+            missing_ai_agent_compatibility: 10,
+            missing_acceptance_criteria: 15,
+            invalid_acceptance_criteria: 10,
+            missing_estimate: 10,
+            ai_ready_threshold: 80,
+        }
+    }
+}
+
+impl ScoringProfile {
+    /// Reject weights or thresholds outside 0..=100
+    pub fn validate(&self) -> Result<(), AppError> {
+        let values = [
+            ("missingDescription", self.missing_description),
+            ("missingTechnicalDetails", self.missing_technical_details),
+            ("vagueLanguage", self.vague_language),
+            (
+                "missingAiAgentCompatibility",
+                self.missing_ai_agent_compatibility,
+            ),
+            (
+                "missingAcceptanceCriteria",
+                self.missing_acceptance_criteria,
+            ),
+            (
+                "invalidAcceptanceCriteria",
+                self.invalid_acceptance_criteria,
+            ),
+            ("missingEstimate", self.missing_estimate),
+            ("aiReadyThreshold", self.ai_ready_threshold),
+        ];
+        for (name, value) in values {
+            if !(0..=MAX_SCORE).contains(&value) {
+                return Err(AppError::BadRequest(format!(
+                    "{} must be between 0 and {}",
+                    name, MAX_SCORE
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_ai_ready(&self, clarity_score: i32) -> bool {
+        clarity_score >= self.ai_ready_threshold
+    }
+
+    /// Up to 20 points below the threshold a task only needs some improvements
+    pub fn needs_improvements(&self, clarity_score: i32) -> bool {
+        !self.is_ai_ready(clarity_score) && clarity_score >= self.ai_ready_threshold - 20
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_bands_follow_the_threshold() {
+        let profile = ScoringProfile {
+            ai_ready_threshold: 70,
+            ..ScoringProfile::default()
+        };
+        profile.validate().unwrap();
+        assert!(profile.is_ai_ready(70));
+        assert!(profile.needs_improvements(50));
+        assert!(!profile.needs_improvements(49));
+
+        let invalid = ScoringProfile {
+            vague_language: -5,
+            ..ScoringProfile::default()
+        };
+        assert!(matches!(
+            invalid.validate(),
+            Err(AppError::BadRequest(message)) if message.starts_with("vagueLanguage")
+        ));
+    }
+}
@@ -1,7 +1,8 @@
+use crate::domain::ContentLanguage;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum GapType {
     MissingTechnicalDetails,
     VagueLanguage,
@@ -14,6 +15,39 @@ pub enum GapType {
     MissingDefinitionOfDone,
 }
 
+impl GapType {
+    /// Stable name of the heuristic that reports this gap
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MissingTechnicalDetails => "missing_technical_details",
+            Self::VagueLanguage => "vague_language",
+            Self::MissingAcceptanceCriteria => "missing_acceptance_criteria",
+            Self::MissingAiAgentCompatibility => "missing_ai_agent_compatibility",
+            Self::MissingSuccessCriteria => "missing_success_criteria",
+            Self::MissingDependencies => "missing_dependencies",
+            Self::MissingEnvironmentSetup => "missing_environment_setup",
+            Self::MissingTestCoverage => "missing_test_coverage",
+            Self::MissingDefinitionOfDone => "missing_definition_of_done",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            Self::MissingTechnicalDetails,
+            Self::VagueLanguage,
+            Self::MissingAcceptanceCriteria,
+            Self::MissingAiAgentCompatibility,
+            Self::MissingSuccessCriteria,
+            Self::MissingDependencies,
+            Self::MissingEnvironmentSetup,
+            Self::MissingTestCoverage,
+            Self::MissingDefinitionOfDone,
+        ]
+        .into_iter()
+        .find(|gap_type| gap_type.as_str() == value)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Recommendation {
     pub gap_type: GapType,
@@ -32,4 +66,7 @@ pub struct TaskAnalysis {
     pub missing_elements: Vec<String>,
     pub summary: String,
     pub recommendations: Vec<Recommendation>,
+    /// Language detected in the task; analyses stored before detection are English
+    #[serde(default)]
+    pub language: ContentLanguage,
 }
@@ -1,4 +1,7 @@
-use super::{GapType, Recommendation, RecommendationGenerator, TaskAnalysis};
+use super::{
+    ContentLanguage, DescriptionCheck, GapType, Recommendation, RecommendationGenerator,
+    ScoringProfile, TaskAnalysis, VagueTermDictionary,
+};
 use crate::application::ports::TaskInfo;
 use uuid::Uuid;
 
@@ -6,9 +9,39 @@ pub struct TaskAnalyzer;
 
 impl TaskAnalyzer {
     pub fn analyze(
+        task_info: &TaskInfo,
+        valid_ac_ids: &[String],
+        previous_analysis: Option<&TaskAnalysis>,
+    ) -> TaskAnalysis {
+        let language = Self::detect_language(task_info);
+        Self::analyze_for_organization(
+            task_info,
+            valid_ac_ids,
+            previous_analysis,
+            language,
+            &VagueTermDictionary::for_language(language),
+            &ScoringProfile::default(),
+        )
+    }
+
+    /// The language the task's title and description are written in
+    pub fn detect_language(task_info: &TaskInfo) -> ContentLanguage {
+        ContentLanguage::detect(&format!(
+            "{} {}",
+            task_info.title,
+            task_info.description.as_deref().unwrap_or("")
+        ))
+    }
+
+    /// Analyze using an organization's vague-term dictionary for the task's
+    /// language and the organization's scoring profile
+    pub fn analyze_for_organization(
         task_info: &TaskInfo,
         valid_ac_ids: &[String],
         _previous_analysis: Option<&TaskAnalysis>,
+        language: ContentLanguage,
+        vague_terms: &VagueTermDictionary,
+        scoring: &ScoringProfile,
     ) -> TaskAnalysis {
         let mut clarity_score = 100;
         let mut missing_elements = Vec::new();
@@ -23,12 +56,12 @@ impl TaskAnalyzer {
 
         if !has_description {
             missing_elements.push("Task lacks a detailed description".to_string());
-            clarity_score -= 20;
+            clarity_score -= scoring.missing_description;
         }
 
         // Analyze technical details
         if let Some(desc) = &task_info.description {
-            let technical_gaps = Self::check_technical_details(desc);
+            let technical_gaps = Self::check_technical_details(desc, language);
             if !technical_gaps.is_empty() {
                 // Use enhanced recommendation generator for context-aware suggestions
                 let enhanced_recommendation =
@@ -41,23 +74,23 @@ impl TaskAnalyzer {
                             .collect::<Vec<_>>(),
                     );
                 recommendations.push(enhanced_recommendation);
-                clarity_score -= 15;
+                clarity_score -= scoring.missing_technical_details;
             }
 
             // Check for vague language
-            let vague_terms = Self::check_vague_language(desc);
-            if !vague_terms.is_empty() {
+            let found_terms = Self::check_vague_language(desc, vague_terms);
+            if !found_terms.is_empty() {
                 recommendations.push(Recommendation {
                     gap_type: GapType::VagueLanguage,
                     message: "Task contains vague or ambiguous language".to_string(),
-                    specific_suggestions: vague_terms,
+                    specific_suggestions: found_terms,
                     ac_references: vec![],
                 });
-                clarity_score -= 10;
+                clarity_score -= scoring.vague_language;
             }
 
             // Check AI agent compatibility requirements
-            let ai_gaps = Self::check_ai_compatibility(desc);
+            let ai_gaps = Self::check_ai_compatibility(desc, language);
             if !ai_gaps.is_empty() {
                 // Use enhanced recommendation generator for AI compatibility suggestions
                 let enhanced_ai_recommendation =
@@ -67,7 +100,7 @@ impl TaskAnalyzer {
                         &ai_gaps.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
                     );
                 recommendations.push(enhanced_ai_recommendation);
-                clarity_score -= 10;
+                clarity_score -= scoring.missing_ai_agent_compatibility;
             }
         }
 
@@ -84,7 +117,7 @@ impl TaskAnalyzer {
                 vec![],
             );
             recommendations.push(enhanced_ac_recommendation);
-            clarity_score -= 15;
+            clarity_score -= scoring.missing_acceptance_criteria;
         } else {
             // Validate that referenced ACs exist
             let invalid_refs: Vec<String> = task_info
@@ -105,23 +138,17 @@ impl TaskAnalyzer {
                         invalid_refs,
                     );
                 recommendations.push(enhanced_ac_recommendation);
-                clarity_score -= 10;
+                clarity_score -= scoring.invalid_acceptance_criteria;
             }
         }
 
         // Check time estimate
         if task_info.estimated_hours.is_none() {
             missing_elements.push("Task has no time estimate".to_string());
-            clarity_score -= 10;
+            clarity_score -= scoring.missing_estimate;
         }
 
-        let summary = if clarity_score >= 80 {
-            "Task is well-defined and ready for implementation".to_string()
-        } else if clarity_score >= 60 {
-            "Task needs some improvements before it's ready".to_string()
-        } else {
-            "Task requires significant clarification".to_string()
-        };
+        let summary = language.clarity_summary(scoring, clarity_score).to_string();
 
         TaskAnalysis {
             id: Uuid::new_v4(),
@@ -132,75 +159,43 @@ impl TaskAnalyzer {
             missing_elements,
             summary,
             recommendations,
+            language,
         }
     }
 
-    fn check_technical_details(description: &str) -> Vec<String> {
+    fn check_technical_details(description: &str, language: ContentLanguage) -> Vec<String> {
         let mut suggestions = Vec::new();
         let desc_lower = description.to_lowercase();
 
         // Check for file paths
-        if !desc_lower.contains("file")
-            && !desc_lower.contains("path")
-            && !desc_lower.contains(".rs")
-            && !desc_lower.contains(".ts")
-        {
+        if !language.mentions(&desc_lower, DescriptionCheck::FilePaths) {
             suggestions.push("Add specific file paths to modify".to_string());
         }
 
         // Check for functions/components
-        if !desc_lower.contains("function")
-            && !desc_lower.contains("component")
-            && !desc_lower.contains("struct")
-            && !desc_lower.contains("class")
-        {
+        if !language.mentions(&desc_lower, DescriptionCheck::FunctionsOrComponents) {
             suggestions.push("Specify functions or components to create or change".to_string());
         }
 
         // Check for inputs/outputs
-        if !desc_lower.contains("input")
-            && !desc_lower.contains("output")
-            && !desc_lower.contains("return")
-            && !desc_lower.contains("parameter")
-        {
+        if !language.mentions(&desc_lower, DescriptionCheck::InputsAndOutputs) {
             suggestions.push("Define expected inputs and outputs".to_string());
         }
 
         // Check for technical approach
-        if !desc_lower.contains("architecture")
-            && !desc_lower.contains("approach")
-            && !desc_lower.contains("design")
-            && !desc_lower.contains("pattern")
-        {
+        if !language.mentions(&desc_lower, DescriptionCheck::TechnicalApproach) {
             suggestions.push("Describe technical approach or architecture decisions".to_string());
         }
 
         suggestions
     }
 
-    fn check_vague_language(description: &str) -> Vec<String> {
-        let mut suggestions = Vec::new();
-        let desc_lower = description.to_lowercase();
-
-        let vague_terms = [
-            ("implement", "Specify what to implement and how"),
-            ("create", "Detail what to create and its structure"),
-            ("build", "Describe what to build with technical specifics"),
-            ("add", "Clarify what to add and where"),
-            ("fix", "Identify the specific issue and solution approach"),
-            ("update", "Specify what to update and the expected changes"),
-            (
-                "improve",
-                "Define concrete improvements with measurable outcomes",
-            ),
-            ("enhance", "List specific enhancements and success criteria"),
-        ];
-
-        for (term, suggestion) in vague_terms.iter() {
-            if desc_lower.contains(term) {
-                suggestions.push(format!("Found vague term '{}': {}", term, suggestion));
-            }
-        }
+    fn check_vague_language(description: &str, dictionary: &VagueTermDictionary) -> Vec<String> {
+        let mut suggestions: Vec<String> = dictionary
+            .find_in(description)
+            .into_iter()
+            .map(|term| format!("Found vague term '{}': {}", term.term, term.suggestion))
+            .collect();
 
         if !suggestions.is_empty() {
             suggestions.insert(
@@ -212,42 +207,32 @@ impl TaskAnalyzer {
         suggestions
     }
 
-    fn check_ai_compatibility(description: &str) -> Vec<String> {
+    fn check_ai_compatibility(description: &str, language: ContentLanguage) -> Vec<String> {
         let mut suggestions = Vec::new();
         let desc_lower = description.to_lowercase();
 
         // Check for success criteria
-        if !desc_lower.contains("success")
-            && !desc_lower.contains("complete")
-            && !desc_lower.contains("done")
-        {
+        if !language.mentions(&desc_lower, DescriptionCheck::SuccessCriteria) {
             suggestions.push("Define clear success criteria".to_string());
         }
 
         // Check for dependencies
-        if !desc_lower.contains("depend")
-            && !desc_lower.contains("require")
-            && !desc_lower.contains("prerequisite")
-        {
+        if !language.mentions(&desc_lower, DescriptionCheck::Dependencies) {
             suggestions.push("List explicit dependencies or prerequisites".to_string());
         }
 
         // Check for environment setup
-        if !desc_lower.contains("environment")
-            && !desc_lower.contains("setup")
-            && !desc_lower.contains("configuration")
-        {
+        if !language.mentions(&desc_lower, DescriptionCheck::EnvironmentSetup) {
             suggestions.push("Describe required environment setup or configuration".to_string());
         }
 
         // Check for test coverage expectations
-        if !desc_lower.contains("test") && !desc_lower.contains("coverage") {
+        if !language.mentions(&desc_lower, DescriptionCheck::TestCoverage) {
             suggestions.push("Specify expected test coverage and types".to_string());
         }
 
         // Check for definition of done
-        if !desc_lower.contains("definition of done") && !desc_lower.contains("completion criteria")
-        {
+        if !language.mentions(&desc_lower, DescriptionCheck::DefinitionOfDone) {
             suggestions.push("Include definition of done with concrete checkpoints".to_string());
         }
 
@@ -0,0 +1,479 @@
+use common::AppError;
+use serde::Serialize;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::domain::AcceptanceCriterion;
+
+const MAX_REPOSITORY_PART_LENGTH: usize = 100;
+const MAX_GIT_REF_LENGTH: usize = 255;
+const DEFAULT_GIT_REF: &str = "HEAD";
+/// Repositories with more test files than this are matched against the first ones only
+pub const MAX_SCANNED_TEST_FILES: usize = 5000;
+const MAX_SUGGESTIONS_PER_CRITERION: usize = 5;
+const MIN_CONFIDENCE: f32 = 0.25;
+/// Shortest term that counts as a prefix of another (`auth` for `authentication`)
+const MIN_PREFIX_LENGTH: usize = 4;
+const MIN_TRIGRAM_SIMILARITY: f32 = 0.6;
+
+const TEST_DIRECTORIES: &[&str] = &["tests", "test", "__tests__", "spec", "specs", "e2e"];
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "ts", "tsx", "js", "jsx", "py", "go", "java", "kt", "rb", "cs", "swift", "php", "feature",
+];
+/// Path segments every test suite is full of; they say nothing about what is tested
+const GENERIC_PATH_TERMS: &[&str] = &[
+    "test", "spec", "src", "lib", "mod", "unit", "e2e", "main", "index", "util", "helper",
+    "common", "fixture",
+];
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "are", "was", "has", "have", "when", "then",
+    "given", "user", "can", "should", "will", "into", "from", "their", "they", "them", "its",
+    "not", "all", "any",
+];
+
+/// A GitHub repository and the branch, tag or commit to read it at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryRef {
+    /// `owner/name`
+    pub repository: String,
+    pub git_ref: String,
+}
+
+impl RepositoryRef {
+    pub fn new(repository: &str, git_ref: Option<&str>) -> Result<Self, AppError> {
+        let repository = repository.trim();
+        let valid_part = |part: &str| {
+            !part.is_empty()
+                && part.len() <= MAX_REPOSITORY_PART_LENGTH
+                && part != "."
+                && part != ".."
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+        if !repository
+            .split_once('/')
+            .is_some_and(|(owner, name)| valid_part(owner) && valid_part(name))
+        {
+            return Err(AppError::BadRequest(
+                "repository must be a GitHub repository in the form owner/name".to_string(),
+            ));
+        }
+
+        let git_ref = git_ref
+            .map(str::trim)
+            .filter(|git_ref| !git_ref.is_empty())
+            .unwrap_or(DEFAULT_GIT_REF);
+        if git_ref.len() > MAX_GIT_REF_LENGTH
+            || git_ref.contains("..")
+            || !git_ref
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+        {
+            return Err(AppError::BadRequest(format!(
+                "ref must be a branch, tag or commit of at most {} characters",
+                MAX_GIT_REF_LENGTH
+            )));
+        }
+
+        Ok(Self {
+            repository: repository.to_string(),
+            git_ref: git_ref.to_string(),
+        })
+    }
+}
+
+/// Why a test file was suggested for a criterion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestMatchKind {
+    /// The file's name contains a term of the criterion
+    Path,
+    /// A directory on the path contains a term of the criterion or the story title
+    Keyword,
+    /// A path term resembles a criterion term without matching it, by prefix
+    /// or shared character trigrams (`auth` and `authentication`)
+    Similarity,
+}
+
+/// An existing test file that likely covers a criterion
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestFileSuggestion {
+    pub path: String,
+    /// 0 to 1; the share of the path's meaningful terms that relate to the criterion
+    pub confidence: f32,
+    pub matched_terms: Vec<String>,
+    pub match_kinds: Vec<TestMatchKind>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriterionTestMapping {
+    pub ac_id: String,
+    /// Most likely first; empty when no test file looks related
+    pub suggestions: Vec<TestFileSuggestion>,
+}
+
+/// Suggestions of which of a repository's test files cover each of a story's
+/// acceptance criteria, for reviewers checking coverage. They come from the
+/// file paths alone, so they point at where to look rather than prove coverage.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMapping {
+    pub story_id: Uuid,
+    #[serde(flatten)]
+    pub repository: RepositoryRef,
+    pub test_files_scanned: usize,
+    pub criteria: Vec<CriterionTestMapping>,
+}
+
+impl TestMapping {
+    /// Match the criteria of the story titled `story_title` against the test
+    /// files among `paths`
+    pub fn suggest(
+        story_id: Uuid,
+        story_title: &str,
+        criteria: &[AcceptanceCriterion],
+        repository: RepositoryRef,
+        paths: &[String],
+    ) -> Self {
+        let test_files: Vec<TestFile> = paths
+            .iter()
+            .filter(|path| is_test_file(path))
+            .take(MAX_SCANNED_TEST_FILES)
+            .filter_map(|path| TestFile::new(path))
+            .collect();
+        let title_terms = terms(story_title);
+
+        let criteria = criteria
+            .iter()
+            .map(|criterion| {
+                let criterion_terms = terms(&format!(
+                    "{} {} {}",
+                    criterion.given, criterion.when, criterion.then
+                ));
+                let mut suggestions: Vec<TestFileSuggestion> = test_files
+                    .iter()
+                    .filter_map(|file| file.suggestion(&criterion_terms, &title_terms))
+                    .collect();
+                suggestions.sort_by(|a, b| {
+                    b.confidence
+                        .total_cmp(&a.confidence)
+                        .then_with(|| a.path.cmp(&b.path))
+                });
+                suggestions.truncate(MAX_SUGGESTIONS_PER_CRITERION);
+                CriterionTestMapping {
+                    ac_id: criterion.ac_id.clone(),
+                    suggestions,
+                }
+            })
+            .collect();
+
+        Self {
+            story_id,
+            repository,
+            test_files_scanned: test_files.len(),
+            criteria,
+        }
+    }
+
+    /// IDs of the criteria no test file was suggested for
+    pub fn uncovered_criteria(&self) -> Vec<&str> {
+        self.criteria
+            .iter()
+            .filter(|criterion| criterion.suggestions.is_empty())
+            .map(|criterion| criterion.ac_id.as_str())
+            .collect()
+    }
+}
+
+/// Whether `path` looks like a test: a source file in a test directory, or
+/// one named the way test files are (`*_test.go`, `test_*.py`, `*.spec.ts`,
+/// `FooTest.java`, Gherkin `.feature` files)
+pub fn is_test_file(path: &str) -> bool {
+    let (directories, file) = path.rsplit_once('/').unwrap_or(("", path));
+    let Some((stem, extension)) = file.rsplit_once('.') else {
+        return false;
+    };
+    let extension = extension.to_lowercase();
+    if !SOURCE_EXTENSIONS.contains(&extension.as_str()) {
+        return false;
+    }
+
+    let lower_stem = stem.to_lowercase();
+    extension == "feature"
+        || directories
+            .split('/')
+            .any(|directory| TEST_DIRECTORIES.contains(&directory.to_lowercase().as_str()))
+        || lower_stem.starts_with("test_")
+        || [
+            "_test", ".test", "_spec", ".spec", "-test", "-spec", "_tests",
+        ]
+        .iter()
+        .any(|suffix| lower_stem.ends_with(suffix))
+        || stem.ends_with("Test")
+        || stem.ends_with("Tests")
+}
+
+/// A test file's path, split into terms
+struct TestFile {
+    path: String,
+    name_terms: HashSet<String>,
+    directory_terms: HashSet<String>,
+}
+
+impl TestFile {
+    fn new(path: &str) -> Option<Self> {
+        let (directories, file) = path.rsplit_once('/').unwrap_or(("", path));
+        let stem = file.split('.').next().unwrap_or(file);
+        let name_terms = terms(stem);
+        let directory_terms: HashSet<String> = terms(directories)
+            .difference(&name_terms)
+            .cloned()
+            .collect();
+        if name_terms.is_empty() && directory_terms.is_empty() {
+            return None;
+        }
+        Some(Self {
+            path: path.to_string(),
+            name_terms,
+            directory_terms,
+        })
+    }
+
+    /// Scores each path term by how it relates to the criterion; `None` when
+    /// no term relates to the criterion itself
+    fn suggestion(
+        &self,
+        criterion_terms: &HashSet<String>,
+        title_terms: &HashSet<String>,
+    ) -> Option<TestFileSuggestion> {
+        let mut score = 0.0;
+        let mut matched_terms = Vec::new();
+        let mut match_kinds = Vec::new();
+        let mut matches_criterion = false;
+        let mut record = |kind: TestMatchKind, term: &str, weight: f32| {
+            score += weight;
+            matched_terms.push(term.to_string());
+            if !match_kinds.contains(&kind) {
+                match_kinds.push(kind);
+            }
+        };
+
+        let mut scored: Vec<(&String, bool)> = self
+            .name_terms
+            .iter()
+            .map(|term| (term, true))
+            .chain(self.directory_terms.iter().map(|term| (term, false)))
+            .collect();
+        scored.sort();
+        for (term, in_name) in scored {
+            if criterion_terms.contains(term) {
+                matches_criterion = true;
+                if in_name {
+                    record(TestMatchKind::Path, term, 1.0);
+                } else {
+                    record(TestMatchKind::Keyword, term, 0.7);
+                }
+            } else if title_terms.contains(term) {
+                record(TestMatchKind::Keyword, term, 0.5);
+            } else if criterion_terms
+                .iter()
+                .any(|criterion_term| resembles(term, criterion_term))
+            {
+                matches_criterion = true;
+                record(TestMatchKind::Similarity, term, 0.4);
+            }
+        }
+
+        let term_count = (self.name_terms.len() + self.directory_terms.len()) as f32;
+        let confidence = ((score / term_count).min(1.0) * 100.0).round() / 100.0;
+        (matches_criterion && confidence >= MIN_CONFIDENCE).then(|| TestFileSuggestion {
+            path: self.path.clone(),
+            confidence,
+            matched_terms,
+            match_kinds,
+        })
+    }
+}
+
+/// Lowercase, singular terms of `text`, with camelCase split apart and
+/// short or meaningless words dropped
+fn terms(text: &str) -> HashSet<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut previous: Option<char> = None;
+    for c in text.chars() {
+        let camel_break = c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase());
+        if !c.is_alphanumeric() || camel_break {
+            words.push(std::mem::take(&mut word));
+        }
+        if c.is_alphanumeric() {
+            word.extend(c.to_lowercase());
+        }
+        previous = Some(c);
+    }
+    words.push(word);
+
+    words
+        .into_iter()
+        .filter(|word| word.chars().count() >= 3 && !word.chars().all(|c| c.is_numeric()))
+        .map(|word| singular(&word))
+        .filter(|word| {
+            !GENERIC_PATH_TERMS.contains(&word.as_str()) && !STOPWORDS.contains(&word.as_str())
+        })
+        .collect()
+}
+
+fn singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+/// Whether one term abbreviates the other, or most of their character
+/// trigrams are shared
+fn resembles(a: &str, b: &str) -> bool {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if shorter.len() >= MIN_PREFIX_LENGTH && longer.starts_with(shorter) {
+        return true;
+    }
+
+    let trigrams = |term: &str| -> HashSet<Vec<char>> {
+        let chars: Vec<char> = term.chars().collect();
+        chars.windows(3).map(<[char]>::to_vec).collect()
+    };
+    let (a, b) = (trigrams(a), trigrams(b));
+    let shared = a.intersection(&b).count() as f32;
+    2.0 * shared / (a.len() + b.len()).max(1) as f32 >= MIN_TRIGRAM_SIMILARITY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repository_refs_and_test_files_are_recognised() {
+        let repository = RepositoryRef::new(" acme/shop-api ", None).unwrap();
+        assert_eq!(repository.repository, "acme/shop-api");
+        assert_eq!(repository.git_ref, "HEAD");
+        assert_eq!(
+            RepositoryRef::new("acme/shop", Some("release/1.2"))
+                .unwrap()
+                .git_ref,
+            "release/1.2"
+        );
+        assert!(RepositoryRef::new("acme", None).is_err());
+        assert!(RepositoryRef::new("acme/shop/extra", None).is_err());
+        assert!(RepositoryRef::new("acme/shop", Some("main..evil")).is_err());
+
+        for path in [
+            "services/orders/tests/integration/checkout.rs",
+            "web/src/__tests__/cart.tsx",
+            "pkg/payment/refund_test.go",
+            "app/test_login.py",
+            "web/src/cart.spec.ts",
+            "src/main/java/ShopCheckoutTest.java",
+            "features/checkout.feature",
+        ] {
+            assert!(is_test_file(path), "{} should be a test file", path);
+        }
+        for path in [
+            "services/orders/src/checkout.rs",
+            "tests/fixtures/orders.json",
+            "docs/latest.md",
+            "src/contest.rs",
+        ] {
+            assert!(!is_test_file(path), "{} should not be a test file", path);
+        }
+    }
+
+    #[test]
+    fn test_criteria_are_matched_to_test_paths() {
+        let story_id = Uuid::new_v4();
+        let criterion = |ac_id: &str, given: &str, when: &str, then: &str| {
+            AcceptanceCriterion::new(
+                story_id,
+                None,
+                ac_id.to_string(),
+                given.to_string(),
+                when.to_string(),
+                then.to_string(),
+            )
+            .unwrap()
+        };
+        let criteria = vec![
+            criterion(
+                "AC1",
+                "a customer has items in their cart",
+                "they apply a discount code",
+                "the order total is reduced",
+            ),
+            criterion(
+                "AC2",
+                "a customer is signed in",
+                "their authentication session expires",
+                "they are asked to sign in again",
+            ),
+            criterion(
+                "AC3",
+                "a warehouse has stock",
+                "a courier collects a parcel",
+                "the shipment is tracked",
+            ),
+        ];
+        let paths: Vec<String> = [
+            "services/checkout/tests/discount_codes.rs",
+            "services/checkout/src/discount.rs",
+            "web/src/cart/__tests__/CartTotal.test.tsx",
+            "services/auth/tests/session_test.rs",
+            "services/search/tests/indexing.rs",
+        ]
+        .iter()
+        .map(|path| path.to_string())
+        .collect();
+
+        let mapping = TestMapping::suggest(
+            story_id,
+            "Checkout discounts",
+            &criteria,
+            RepositoryRef::new("acme/shop", None).unwrap(),
+            &paths,
+        );
+        assert_eq!(mapping.test_files_scanned, 4);
+
+        let suggested = |index: usize| -> Vec<&str> {
+            mapping.criteria[index]
+                .suggestions
+                .iter()
+                .map(|suggestion| suggestion.path.as_str())
+                .collect()
+        };
+        assert_eq!(
+            suggested(0),
+            vec![
+                "web/src/cart/__tests__/CartTotal.test.tsx",
+                "services/checkout/tests/discount_codes.rs"
+            ]
+        );
+        let discount = &mapping.criteria[0].suggestions[1];
+        assert_eq!(
+            discount.match_kinds,
+            vec![TestMatchKind::Keyword, TestMatchKind::Path]
+        );
+        assert_eq!(
+            mapping.criteria[1].suggestions[0].path,
+            "services/auth/tests/session_test.rs"
+        );
+        assert!(mapping.criteria[1].suggestions[0]
+            .match_kinds
+            .contains(&TestMatchKind::Similarity));
+        assert_eq!(mapping.uncovered_criteria(), vec!["AC3"]);
+    }
+}
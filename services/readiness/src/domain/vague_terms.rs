@@ -0,0 +1,262 @@
+use crate::domain::ContentLanguage;
+use common::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const MAX_TERM_LENGTH: usize = 40;
+const MAX_SUGGESTION_LENGTH: usize = 200;
+pub const MAX_VAGUE_TERM_OVERRIDES: usize = 200;
+
+/// A word or phrase that makes a task description ambiguous, and what to write instead
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VagueTerm {
+    pub term: String,
+    pub suggestion: String,
+}
+
+impl VagueTerm {
+    fn new(term: &str, suggestion: &str) -> Self {
+        Self {
+            term: term.to_string(),
+            suggestion: suggestion.to_string(),
+        }
+    }
+}
+
+/// An organization's change to the built-in dictionary: a new term, a
+/// different suggestion for a built-in term, or a built-in term removed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VagueTermOverride {
+    pub term: String,
+    pub suggestion: Option<String>,
+    pub removed: bool,
+}
+
+impl VagueTermOverride {
+    pub fn new(term: &str, suggestion: Option<String>, removed: bool) -> Result<Self, AppError> {
+        let term = term.trim().to_lowercase();
+        if term.is_empty() || term.chars().count() > MAX_TERM_LENGTH {
+            return Err(AppError::BadRequest(format!(
+                "Vague terms must be between 1 and {} characters",
+                MAX_TERM_LENGTH
+            )));
+        }
+
+        let suggestion = suggestion
+            .map(|suggestion| suggestion.trim().to_string())
+            .filter(|suggestion| !suggestion.is_empty());
+        if removed && suggestion.is_some() {
+            return Err(AppError::BadRequest(format!(
+                "Removed term '{}' cannot have a suggestion",
+                term
+            )));
+        }
+        if suggestion
+            .as_ref()
+            .is_some_and(|suggestion| suggestion.chars().count() > MAX_SUGGESTION_LENGTH)
+        {
+            return Err(AppError::BadRequest(format!(
+                "Suggestions cannot exceed {} characters",
+                MAX_SUGGESTION_LENGTH
+            )));
+        }
+
+        Ok(Self {
+            term,
+            suggestion,
+            removed,
+        })
+    }
+}
+
+/// Reject override lists with repeated terms or more than the allowed number of entries
+pub fn validate_vague_term_overrides(overrides: &[VagueTermOverride]) -> Result<(), AppError> {
+    if overrides.len() > MAX_VAGUE_TERM_OVERRIDES {
+        return Err(AppError::BadRequest(format!(
+            "At most {} vague-term changes are allowed",
+            MAX_VAGUE_TERM_OVERRIDES
+        )));
+    }
+
+    let mut seen = HashSet::new();
+    for entry in overrides {
+        if !seen.insert(entry.term.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Vague term '{}' is listed more than once",
+                entry.term
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The vague terms the task analyzer flags
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VagueTermDictionary {
+    terms: Vec<VagueTerm>,
+}
+
+impl Default for VagueTermDictionary {
+    fn default() -> Self {
+        Self::for_language(ContentLanguage::English)
+    }
+}
+
+impl VagueTermDictionary {
+    /// The built-in dictionary for the language
+    pub fn for_language(language: ContentLanguage) -> Self {
+        let terms = match language {
+            ContentLanguage::English => vec![
+                VagueTerm::new("implement", "Specify what to implement and how"),
+                VagueTerm::new("create", "Detail what to create and its structure"),
+                VagueTerm::new("build", "Describe what to build with technical specifics"),
+                VagueTerm::new("add", "Clarify what to add and where"),
+                VagueTerm::new("fix", "Identify the specific issue and solution approach"),
+                VagueTerm::new("update", "Specify what to update and the expected changes"),
+                VagueTerm::new(
+                    "improve",
+                    "Define concrete improvements with measurable outcomes",
+                ),
+                VagueTerm::new("enhance", "List specific enhancements and success criteria"),
+            ],
+            ContentLanguage::German => vec![
+                VagueTerm::new(
+                    "implementieren",
+                    "Beschreiben Sie, was implementiert wird und wie",
+                ),
+                VagueTerm::new(
+                    "erstellen",
+                    "Beschreiben Sie, was erstellt wird und wie es aufgebaut ist",
+                ),
+                VagueTerm::new("bauen", "Beschreiben Sie technisch, was gebaut wird"),
+                VagueTerm::new("hinzufügen", "Klären Sie, was wo hinzugefügt wird"),
+                VagueTerm::new(
+                    "beheben",
+                    "Benennen Sie das konkrete Problem und den Lösungsansatz",
+                ),
+                VagueTerm::new("aktualisieren", "Geben Sie an, was sich wie ändern soll"),
+                VagueTerm::new("verbessern", "Definieren Sie messbare Verbesserungen"),
+                VagueTerm::new(
+                    "optimieren",
+                    "Nennen Sie das Ziel der Optimierung mit Messwerten",
+                ),
+            ],
+            ContentLanguage::Spanish => vec![
+                VagueTerm::new("implementar", "Especifique qué se implementa y cómo"),
+                VagueTerm::new("crear", "Detalle qué se crea y su estructura"),
+                VagueTerm::new(
+                    "construir",
+                    "Describa qué se construye con detalles técnicos",
+                ),
+                VagueTerm::new("añadir", "Aclare qué se añade y dónde"),
+                VagueTerm::new("arreglar", "Identifique el problema concreto y la solución"),
+                VagueTerm::new(
+                    "actualizar",
+                    "Especifique qué se actualiza y los cambios esperados",
+                ),
+                VagueTerm::new("mejorar", "Defina mejoras concretas y medibles"),
+                VagueTerm::new(
+                    "optimizar",
+                    "Indique el objetivo de la optimización con métricas",
+                ),
+            ],
+        };
+        Self { terms }
+    }
+
+    /// The language's built-in dictionary with an organization's changes applied
+    pub fn with_overrides(language: ContentLanguage, overrides: &[VagueTermOverride]) -> Self {
+        let mut terms = Self::for_language(language).terms;
+        for entry in overrides {
+            let existing = terms.iter().position(|term| term.term == entry.term);
+            match (existing, entry.removed, &entry.suggestion) {
+                (Some(index), true, _) => {
+                    terms.remove(index);
+                }
+                (Some(index), false, Some(suggestion)) => {
+                    terms[index].suggestion = suggestion.clone();
+                }
+                (None, false, suggestion) => terms.push(VagueTerm {
+                    term: entry.term.clone(),
+                    suggestion: suggestion.clone().unwrap_or_else(|| {
+                        format!(
+                            "Replace '{}' with a concrete, measurable action",
+                            entry.term
+                        )
+                    }),
+                }),
+                _ => {}
+            }
+        }
+        Self { terms }
+    }
+
+    pub fn terms(&self) -> &[VagueTerm] {
+        &self.terms
+    }
+
+    /// Terms that appear in the text, in dictionary order
+    pub fn find_in(&self, text: &str) -> Vec<&VagueTerm> {
+        let lower = text.to_lowercase();
+        self.terms
+            .iter()
+            .filter(|term| lower.contains(term.term.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_add_replace_and_remove_terms() {
+        let overrides = vec![
+            VagueTermOverride::new("Fix", None, true).unwrap(),
+            VagueTermOverride::new("update", Some("Name the fields".to_string()), false).unwrap(),
+            VagueTermOverride::new("as needed", None, false).unwrap(),
+        ];
+        validate_vague_term_overrides(&overrides).unwrap();
+
+        let dictionary = VagueTermDictionary::with_overrides(ContentLanguage::English, &overrides);
+        let found: Vec<&str> = dictionary
+            .find_in("Fix the login bug and update retries as needed")
+            .iter()
+            .map(|term| term.term.as_str())
+            .collect();
+        assert_eq!(found, vec!["update", "as needed"]);
+        assert_eq!(
+            dictionary
+                .terms()
+                .iter()
+                .find(|term| term.term == "update")
+                .unwrap()
+                .suggestion,
+            "Name the fields"
+        );
+    }
+
+    #[test]
+    fn test_organization_overrides_apply_to_every_language() {
+        let overrides = vec![VagueTermOverride::new("asap", None, false).unwrap()];
+        let dictionary = VagueTermDictionary::with_overrides(ContentLanguage::Spanish, &overrides);
+        let found: Vec<&str> = dictionary
+            .find_in("Mejorar el informe asap")
+            .iter()
+            .map(|term| term.term.as_str())
+            .collect();
+        assert_eq!(found, vec!["mejorar", "asap"]);
+    }
+
+    #[test]
+    fn test_invalid_overrides_are_rejected() {
+        assert!(VagueTermOverride::new("  ", None, false).is_err());
+        assert!(VagueTermOverride::new("fix", Some("x".to_string()), true).is_err());
+
+        let duplicate = vec![
+            VagueTermOverride::new("asap", None, false).unwrap(),
+            VagueTermOverride::new("ASAP", None, false).unwrap(),
+        ];
+        assert!(validate_vague_term_overrides(&duplicate).is_err());
+    }
+}
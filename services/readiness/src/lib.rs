@@ -2,44 +2,63 @@ pub mod adapters;
 pub mod application;
 pub mod config;
 pub mod domain;
-mod projections;
+pub mod projections;
 
 use application::{
     ports::{
-        AcceptanceCriteriaRepository, LlmService, ReadinessEvaluationRepository,
-        TaskAnalysisRepository,
+        AcceptanceCriteriaRepository, GlossaryRepository, LlmService,
+        ReadinessEvaluationRepository, ScoringProfileRepository, TaskAnalysisRepository,
+        VagueTermRepository,
     },
     ReadinessUsecases,
 };
+use common::quota::QuotaGuard;
 use event_bus::EventBus;
 use sqlx::PgPool;
 use std::sync::Arc;
 
+pub use adapters::http::routes::create_readiness_router;
 pub use config::AppConfig;
 
 pub async fn build_usecases(
     pool: PgPool,
     event_bus: Arc<EventBus>,
     llm_service: Arc<dyn LlmService>,
+    quota_guard: Arc<dyn QuotaGuard>,
 ) -> Arc<ReadinessUsecases> {
     let pool = Arc::new(pool);
     let store = projections::ProjectionStore::new(pool.clone());
     store.hydrate().await;
     projections::ProjectionWorker::spawn(store.clone(), event_bus);
 
-    let story_service = Arc::new(projections::ProjectionStoryService::new(pool.clone()))
-        as Arc<dyn application::ports::StoryService>;
+    let projection_service = Arc::new(projections::ProjectionStoryService::new(pool.clone()));
+    let story_service = projection_service.clone() as Arc<dyn application::ports::StoryService>;
+    let dependency_service =
+        projection_service as Arc<dyn application::ports::StoryDependencyService>;
     let criteria_repo: Arc<dyn AcceptanceCriteriaRepository> = pool.clone();
     let readiness_repo: Arc<dyn ReadinessEvaluationRepository> = pool.clone();
     let task_analysis_repo: Arc<dyn TaskAnalysisRepository> = pool.clone();
+    let vague_term_repo: Arc<dyn VagueTermRepository> = pool.clone();
+    let scoring_profile_repo: Arc<dyn ScoringProfileRepository> = pool.clone();
+    let glossary_repo: Arc<dyn GlossaryRepository> = pool.clone();
 
-    Arc::new(ReadinessUsecases::new(
-        criteria_repo,
-        readiness_repo,
-        task_analysis_repo,
-        story_service,
-        llm_service,
-    ))
+    Arc::new(
+        ReadinessUsecases::new(
+            criteria_repo,
+            readiness_repo,
+            task_analysis_repo,
+            vague_term_repo,
+            scoring_profile_repo,
+            glossary_repo,
+            story_service,
+            dependency_service,
+            llm_service,
+        )
+        .with_quota_guard(quota_guard)
+        .with_repository_service(Arc::new(
+            adapters::integrations::GitHubRepositoryService::from_env(),
+        )),
+    )
 }
 
 pub async fn rebuild_projections(pool: Arc<PgPool>) {
@@ -1,18 +1,31 @@
-use crate::application::ports::{StoryInfo, StoryService, TaskInfo};
+use crate::application::ports::{
+    StoryDependency, StoryDependencyService, StoryInfo, StoryService, TaskInfo,
+};
+use crate::domain::StorySelection;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use common::AppError;
 use event_bus::{
-    BacklogEvent, DomainEvent, EventBus, EventEnvelope, SprintEvent, StoryRecord, TaskRecord,
+    apply_with_retry, BacklogEvent, DomainEvent, EventBus, EventEnvelope, ProjectionId,
+    ProjectionReplayer, RetryPolicy, SprintEvent, StoryRecord, TaskRecord,
 };
 use serde_json::json;
 use sqlx::{FromRow, PgPool};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::task::JoinHandle;
-use tracing::error;
+use tracing::{error, warn};
 use uuid::Uuid;
 
+/// Stories, tasks and dependencies mirrored from the backlog
+const PROJECTION: ProjectionId = ProjectionId::new("readiness", "stories");
+
+const STORY_SELECT: &str = "SELECT id, project_id, organization_id, title, description, status,
+    labels, story_points, sprint_id, assigned_to_user_id, readiness_override,
+    readiness_override_by, readiness_override_reason, readiness_override_at, created_at,
+    updated_at
+FROM stories";
+
 #[derive(FromRow)]
 struct StoryRow {
     id: Uuid,
@@ -41,6 +54,7 @@ struct AcceptanceRow {
     given: String,
     when_clause: String,
     then_clause: String,
+    position: i32,
     created_at: DateTime<Utc>,
 }
 
@@ -69,6 +83,14 @@ struct StoryProjectionRow {
     story_points: Option<i32>,
 }
 
+#[derive(FromRow)]
+struct DependencyProjectionRow {
+    id: Uuid,
+    title: String,
+    status: String,
+    blocking: bool,
+}
+
 #[derive(FromRow)]
 struct TaskProjectionRow {
     id: Uuid,
@@ -96,32 +118,90 @@ impl ProjectionStore {
     }
 
     async fn hydrate_internal(&self) -> Result<(), sqlx::Error> {
-        let stories = sqlx::query_as::<_, StoryRow>(
+        let stories =
+            sqlx::query_as::<_, StoryRow>(&format!("{} WHERE deleted_at IS NULL", STORY_SELECT))
+                .fetch_all(&*self.pool)
+                .await?;
+
+        let story_ids: Vec<Uuid> = stories.iter().map(|row| row.id).collect();
+        self.project_stories(stories).await?;
+        self.project_dependencies(&story_ids).await?;
+        Ok(())
+    }
+
+    /// Replace the dependencies of `story_ids` with the story-to-story
+    /// mentions and explicit blockers the backlog has recorded for them
+    async fn project_dependencies(&self, story_ids: &[Uuid]) -> Result<(), sqlx::Error> {
+        if story_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM readiness_story_dependencies WHERE story_id = ANY($1)")
+            .bind(story_ids)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
             r#"
-            SELECT
-                id,
-                project_id,
-                organization_id,
-                title,
-                description,
-                status,
-                labels,
-                story_points,
-                sprint_id,
-                assigned_to_user_id,
-                readiness_override,
-                readiness_override_by,
-                readiness_override_reason,
-                readiness_override_at,
-                created_at,
-                updated_at
-            FROM stories
-            WHERE deleted_at IS NULL
+            INSERT INTO readiness_story_dependencies (story_id, depends_on_id, organization_id)
+            SELECT r.source_id, r.target_id, source.organization_id
+            FROM item_references r
+            JOIN stories source ON source.id = r.source_id
+            JOIN stories target ON target.id = r.target_id
+            WHERE r.source_type = 'story' AND r.target_type = 'story'
+              AND r.source_id = ANY($1)
+              AND source.deleted_at IS NULL AND target.deleted_at IS NULL
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(story_ids)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO readiness_story_dependencies
+                (story_id, depends_on_id, organization_id, blocking)
+            SELECT d.story_id, d.blocked_by_id, d.organization_id, TRUE
+            FROM story_dependencies d
+            JOIN stories blocker ON blocker.id = d.blocked_by_id
+            WHERE d.story_id = ANY($1) AND blocker.deleted_at IS NULL
+            ON CONFLICT DO NOTHING
             "#,
         )
+        .bind(story_ids)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+
+    /// Read a story the projection is missing straight from the backlog's
+    /// stories table, scoped to the organization, and project it along with
+    /// its criteria and tasks
+    pub async fn backfill_story(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<StoryRecord>, sqlx::Error> {
+        let stories = sqlx::query_as::<_, StoryRow>(&format!(
+            "{} WHERE id = $1 AND organization_id IS NOT DISTINCT FROM $2 AND deleted_at IS NULL",
+            STORY_SELECT
+        ))
+        .bind(story_id)
+        .bind(organization_id)
         .fetch_all(&*self.pool)
         .await?;
 
+        let projected = self.project_stories(stories).await?;
+        let story_ids: Vec<Uuid> = projected.iter().map(|story| story.id).collect();
+        self.project_dependencies(&story_ids).await?;
+        Ok(projected.into_iter().next())
+    }
+
+    /// Project `stories` with their acceptance criteria and tasks
+    async fn project_stories(
+        &self,
+        stories: Vec<StoryRow>,
+    ) -> Result<Vec<StoryRecord>, sqlx::Error> {
         let story_ids: Vec<Uuid> = stories.iter().map(|row| row.id).collect();
 
         let mut acceptance_map: HashMap<Uuid, Vec<_>> = HashMap::new();
@@ -135,9 +215,11 @@ impl ProjectionStore {
                     given,
                     when_clause,
                     then_clause,
+                    position,
                     created_at
                 FROM acceptance_criteria
                 WHERE story_id = ANY($1)
+                ORDER BY position, created_at
                 "#,
             )
             .bind(&story_ids)
@@ -145,14 +227,7 @@ impl ProjectionStore {
             .await?;
 
             for row in acceptance_rows {
-                acceptance_map.entry(row.story_id).or_default().push((
-                    row.id,
-                    row.description,
-                    row.given,
-                    row.when_clause,
-                    row.then_clause,
-                    row.created_at,
-                ));
+                acceptance_map.entry(row.story_id).or_default().push(row);
             }
         }
 
@@ -187,6 +262,7 @@ impl ProjectionStore {
             }
         }
 
+        let mut projected = Vec::with_capacity(stories.len());
         for story in stories {
             let record = StoryRecord {
                 id: story.id,
@@ -200,19 +276,16 @@ impl ProjectionStore {
                     .remove(&story.id)
                     .unwrap_or_default()
                     .into_iter()
-                    .map(
-                        |(id, description, given, when_clause, then_clause, created_at)| {
-                            event_bus::AcceptanceCriterionRecord {
-                                id,
-                                story_id: story.id,
-                                description,
-                                given,
-                                when: when_clause,
-                                then: then_clause,
-                                created_at,
-                            }
-                        },
-                    )
+                    .map(|row| event_bus::AcceptanceCriterionRecord {
+                        id: row.id,
+                        story_id: story.id,
+                        description: row.description,
+                        given: row.given,
+                        when: row.when_clause,
+                        then: row.then_clause,
+                        position: row.position.max(0) as u32,
+                        created_at: row.created_at,
+                    })
                     .collect(),
                 story_points: story.story_points.map(|val| val as u32),
                 sprint_id: story.sprint_id,
@@ -247,19 +320,23 @@ impl ProjectionStore {
                     self.upsert_task(&record).await?;
                 }
             }
+            projected.push(record);
         }
 
-        Ok(())
+        Ok(projected)
     }
 
-    pub async fn handle_event(&self, envelope: &EventEnvelope) {
-        if let Err(err) = self.apply_event(&envelope.event).await {
+    pub async fn handle_event(&self, envelope: &EventEnvelope) -> Result<(), sqlx::Error> {
+        let result = envelope.handle(self.apply_event(&envelope.event)).await;
+        if let Err(err) = &result {
             error!(
                 error = %err,
                 event_id = %envelope.id,
+                correlation_id = ?envelope.correlation_id,
                 "Failed to apply backlog event to readiness projections"
             );
         }
+        result
     }
 
     // ... (rest of the file)
@@ -279,10 +356,27 @@ impl ProjectionStore {
                 self.upsert_story(story).await?
             }
             BacklogEvent::StoryDeleted { story_id, .. } => self.delete_story(*story_id).await?,
+            BacklogEvent::StoryArchived { story_id, .. } => self.archive_story(*story_id).await?,
             BacklogEvent::TaskCreated { task } | BacklogEvent::TaskUpdated { task } => {
                 self.upsert_task(task).await?
             }
             BacklogEvent::TaskDeleted { task_id, .. } => self.delete_task(*task_id).await?,
+            BacklogEvent::StoryDependenciesChanged {
+                story_id,
+                organization_id,
+                depends_on,
+            } => {
+                self.replace_dependencies(*story_id, *organization_id, depends_on, false)
+                    .await?
+            }
+            BacklogEvent::StoryBlockersChanged {
+                story_id,
+                organization_id,
+                blocked_by,
+            } => {
+                self.replace_dependencies(*story_id, *organization_id, blocked_by, true)
+                    .await?
+            }
         }
 
         Ok(())
@@ -293,6 +387,15 @@ impl ProjectionStore {
             SprintEvent::Deleted { sprint_id, .. } => {
                 self.clear_sprint_assignments(*sprint_id).await?;
             }
+            // Sprint lifecycle moves stories without a story event
+            SprintEvent::StoryAdded { change } => {
+                self.move_story_to_sprint(change.story_id, Some(change.sprint_id), None)
+                    .await?;
+            }
+            SprintEvent::StoryRemoved { change } => {
+                self.move_story_to_sprint(change.story_id, None, Some(change.sprint_id))
+                    .await?;
+            }
             SprintEvent::Created { .. } | SprintEvent::Updated { .. } => {
                 // No projection changes required yet; sprint data is sourced via backlog stories.
             }
@@ -301,6 +404,30 @@ impl ProjectionStore {
         Ok(())
     }
 
+    /// Put the story in `sprint_id`; a removal (`from`) only applies while the
+    /// story is still in that sprint
+    async fn move_story_to_sprint(
+        &self,
+        story_id: Uuid,
+        sprint_id: Option<Uuid>,
+        from: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE readiness_story_projections
+            SET sprint_id = $2
+            WHERE id = $1 AND ($3::uuid IS NULL OR sprint_id = $3)
+            "#,
+        )
+        .bind(story_id)
+        .bind(sprint_id)
+        .bind(from)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     async fn clear_sprint_assignments(&self, sprint_id: Uuid) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
@@ -317,7 +444,10 @@ impl ProjectionStore {
     }
 
     async fn upsert_story(&self, story: &StoryRecord) -> Result<(), sqlx::Error> {
-        let criteria_json = serde_json::to_value(&story.acceptance_criteria).unwrap_or(json!([]));
+        // The projection stores criteria in display order; readers derive AC1..ACn from it
+        let mut criteria = story.acceptance_criteria.clone();
+        criteria.sort_by_key(|criterion| (criterion.position, criterion.created_at));
+        let criteria_json = serde_json::to_value(&criteria).unwrap_or(json!([]));
         sqlx::query(
             r#"
             INSERT INTO readiness_story_projections (
@@ -384,6 +514,10 @@ impl ProjectionStore {
     }
 
     async fn delete_story(&self, story_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM readiness_story_dependencies WHERE story_id = $1")
+            .bind(story_id)
+            .execute(&*self.pool)
+            .await?;
         sqlx::query("DELETE FROM readiness_story_projections WHERE id = $1")
             .bind(story_id)
             .execute(&*self.pool)
@@ -391,6 +525,50 @@ impl ProjectionStore {
         Ok(())
     }
 
+    /// Replace one kind of a story's dependencies: its explicit blockers when
+    /// `blocking`, otherwise the stories it mentions
+    async fn replace_dependencies(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+        depends_on: &[Uuid],
+        blocking: bool,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "DELETE FROM readiness_story_dependencies WHERE story_id = $1 AND blocking = $2",
+        )
+        .bind(story_id)
+        .bind(blocking)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO readiness_story_dependencies
+                (story_id, depends_on_id, organization_id, blocking)
+            SELECT $1, depends_on_id, $2, $4
+            FROM UNNEST($3::uuid[]) AS depends_on_id
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(story_id)
+        .bind(organization_id)
+        .bind(depends_on)
+        .bind(blocking)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+
+    /// Archived stories take their tasks with them
+    async fn archive_story(&self, story_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM readiness_task_projections WHERE story_id = $1")
+            .bind(story_id)
+            .execute(&*self.pool)
+            .await?;
+        self.delete_story(story_id).await
+    }
+
     async fn upsert_task(&self, task: &TaskRecord) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
@@ -461,11 +639,19 @@ pub struct ProjectionWorker {
 impl ProjectionWorker {
     pub fn spawn(store: ProjectionStore, event_bus: Arc<EventBus>) -> Self {
         let subscription = event_bus.subscribe();
+        event_bus.register_projection(PROJECTION, Arc::new(store.clone()));
 
         let handle = tokio::spawn(async move {
             loop {
                 let envelope = subscription.recv().await;
-                store.handle_event(&envelope).await;
+                apply_with_retry(
+                    &event_bus,
+                    PROJECTION,
+                    &envelope,
+                    RetryPolicy::default(),
+                    || store.handle_event(&envelope),
+                )
+                .await;
             }
         });
 
@@ -473,14 +659,61 @@ impl ProjectionWorker {
     }
 }
 
+#[async_trait]
+impl ProjectionReplayer for ProjectionStore {
+    async fn reapply(&self, envelope: &EventEnvelope) -> Result<(), String> {
+        self.handle_event(envelope)
+            .await
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// Reads stories and tasks from the readiness projections. A story the
+/// projection missed is read through from the backlog's tables and backfilled.
 #[derive(Clone)]
 pub struct ProjectionStoryService {
     pool: Arc<PgPool>,
+    store: ProjectionStore,
 }
 
 impl ProjectionStoryService {
     pub fn new(pool: Arc<PgPool>) -> Self {
-        Self { pool }
+        Self {
+            store: ProjectionStore::new(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Backfill a story missing from the projection, most likely because its
+    /// `StoryCreated` event was missed
+    async fn read_through(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<StoryInfo>, AppError> {
+        let record = self
+            .store
+            .backfill_story(story_id, organization_id)
+            .await
+            .map_err(|err| {
+                error!(error = %err, %story_id, "Failed to backfill story projection");
+                AppError::InternalServerError
+            })?;
+        let Some(record) = record else {
+            return Ok(None);
+        };
+
+        warn!(
+            %story_id,
+            organization_id = ?organization_id,
+            "Readiness story projection miss; backfilled from stories"
+        );
+        Ok(Some(StoryInfo {
+            id: record.id,
+            title: record.title,
+            description: record.description,
+            story_points: record.story_points,
+        }))
     }
 }
 
@@ -489,7 +722,7 @@ impl StoryService for ProjectionStoryService {
     async fn get_story_info(
         &self,
         story_id: Uuid,
-        _organization_id: Option<Uuid>,
+        organization_id: Option<Uuid>,
     ) -> Result<Option<StoryInfo>, AppError> {
         let row = sqlx::query_as::<_, StoryProjectionRow>(
             r#"
@@ -506,12 +739,15 @@ impl StoryService for ProjectionStoryService {
             AppError::InternalServerError
         })?;
 
-        Ok(row.map(|record| StoryInfo {
-            id: record.id,
-            title: record.title,
-            description: record.description,
-            story_points: record.story_points.map(|v| v as u32),
-        }))
+        match row {
+            Some(record) => Ok(Some(StoryInfo {
+                id: record.id,
+                title: record.title,
+                description: record.description,
+                story_points: record.story_points.map(|v| v as u32),
+            })),
+            None => self.read_through(story_id, organization_id).await,
+        }
     }
 
     async fn get_tasks_for_story(
@@ -577,4 +813,77 @@ impl StoryService for ProjectionStoryService {
             estimated_hours: row.estimated_hours.map(|v| v as u32),
         }))
     }
+
+    async fn find_project_stories(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        selection: &StorySelection,
+    ) -> Result<Vec<Uuid>, AppError> {
+        // Statuses compare the way the backlog parses them, so `in_progress` matches `inprogress`
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT id
+            FROM readiness_story_projections
+            WHERE project_id = $1
+              AND organization_id IS NOT DISTINCT FROM $2
+              AND ($3::uuid[] IS NULL OR id = ANY($3))
+              AND ($4::text IS NULL
+                   OR replace(lower(status), '_', '') = replace(lower($4), '_', ''))
+              AND ($5::text IS NULL OR $5 = ANY(labels))
+              AND ($6::uuid IS NULL OR sprint_id = $6)
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(project_id)
+        .bind(organization_id)
+        .bind(selection.story_ids.as_deref())
+        .bind(selection.status.as_deref())
+        .bind(selection.label.as_deref())
+        .bind(selection.sprint_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|err| {
+            error!(error = %err, %project_id, "Failed to select story projections");
+            AppError::InternalServerError
+        })
+    }
+}
+
+#[async_trait]
+impl StoryDependencyService for ProjectionStoryService {
+    async fn get_story_dependencies(
+        &self,
+        story_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<StoryDependency>, AppError> {
+        let rows = sqlx::query_as::<_, DependencyProjectionRow>(
+            r#"
+            SELECT s.id, s.title, s.status, BOOL_OR(d.blocking) AS blocking
+            FROM readiness_story_dependencies d
+            JOIN readiness_story_projections s ON s.id = d.depends_on_id
+            WHERE d.story_id = $1 AND d.organization_id IS NOT DISTINCT FROM $2
+            GROUP BY s.id, s.title, s.status, s.created_at
+            ORDER BY s.created_at, s.id
+            "#,
+        )
+        .bind(story_id)
+        .bind(organization_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|err| {
+            error!(error = %err, %story_id, "Failed to fetch story dependency projections");
+            AppError::InternalServerError
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| StoryDependency {
+                story_id: row.id,
+                title: row.title,
+                status: row.status,
+                blocking: row.blocking,
+            })
+            .collect())
+    }
 }
@@ -1,2 +1,3 @@
 pub mod test_api_endpoints;
 pub mod test_concurrent_requests;
+pub mod test_projection_read_through;
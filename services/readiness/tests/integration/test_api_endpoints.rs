@@ -123,7 +123,7 @@ async fn test_get_criteria_success() {
     assert!(result.is_array());
     let criteria_array = result.as_array().unwrap();
     assert_eq!(criteria_array.len(), 1);
-    assert_eq!(criteria_array[0]["ac_id"], "AC1");
+    assert_eq!(criteria_array[0]["acId"], "AC1");
 }
 
 #[tokio::test]
@@ -238,8 +238,8 @@ async fn test_generate_criteria_success() {
     // Verify structure of generated criteria
     for criterion in criteria_array {
         assert!(criterion.get("id").is_some());
-        assert!(criterion.get("story_id").is_some());
-        assert!(criterion.get("ac_id").is_some());
+        assert!(criterion.get("storyId").is_some());
+        assert!(criterion.get("acId").is_some());
         assert!(criterion.get("given").is_some());
         assert!(criterion.get("when").is_some());
         assert!(criterion.get("then").is_some());
@@ -701,14 +701,14 @@ async fn test_get_criteria_response_schema() {
         "Criterion must have 'id' field"
     );
     assert!(
-        criterion.get("story_id").is_some(),
-        "Criterion must have 'story_id' field"
+        criterion.get("storyId").is_some(),
+        "Criterion must have 'storyId' field"
     );
     assert!(
-        criterion.get("ac_id").is_some(),
-        "Criterion must have 'ac_id' field"
+        criterion.get("acId").is_some(),
+        "Criterion must have 'acId' field"
     );
-    assert!(criterion["ac_id"].is_string(), "'ac_id' must be a string");
+    assert!(criterion["acId"].is_string(), "'acId' must be a string");
     assert!(
         criterion.get("given").is_some(),
         "Criterion must have 'given' field"
@@ -782,14 +782,14 @@ async fn test_add_criteria_response_schema() {
         "Criterion must have 'id' field"
     );
     assert!(
-        criterion.get("story_id").is_some(),
-        "Criterion must have 'story_id' field"
+        criterion.get("storyId").is_some(),
+        "Criterion must have 'storyId' field"
     );
     assert!(
-        criterion["story_id"].as_str().unwrap() == story_id.to_string(),
-        "'story_id' must match the request"
+        criterion["storyId"].as_str().unwrap() == story_id.to_string(),
+        "'storyId' must match the request"
     );
-    assert_eq!(criterion["ac_id"], "AC1", "'ac_id' must match the request");
+    assert_eq!(criterion["acId"], "AC1", "'acId' must match the request");
     assert_eq!(
         criterion["given"], "I am a user",
         "'given' must match the request"
@@ -851,18 +851,18 @@ async fn test_generate_criteria_response_schema() {
             "Criterion must have 'id' field"
         );
         assert!(
-            criterion.get("story_id").is_some(),
-            "Criterion must have 'story_id' field"
+            criterion.get("storyId").is_some(),
+            "Criterion must have 'storyId' field"
         );
         assert!(
-            criterion["story_id"].as_str().unwrap() == story_id.to_string(),
-            "'story_id' must match the request"
+            criterion["storyId"].as_str().unwrap() == story_id.to_string(),
+            "'storyId' must match the request"
         );
         assert!(
-            criterion.get("ac_id").is_some(),
-            "Criterion must have 'ac_id' field"
+            criterion.get("acId").is_some(),
+            "Criterion must have 'acId' field"
         );
-        assert!(criterion["ac_id"].is_string(), "'ac_id' must be a string");
+        assert!(criterion["acId"].is_string(), "'acId' must be a string");
         assert!(
             criterion.get("given").is_some(),
             "Criterion must have 'given' field"
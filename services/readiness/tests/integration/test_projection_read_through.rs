@@ -0,0 +1,100 @@
+/// Integration tests for the readiness projection read-through, against the
+/// backlog's full story tables
+use readiness::application::ports::StoryService;
+use readiness::projections::ProjectionStoryService;
+use serial_test::serial;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::common::setup_migrated_test_db;
+
+async fn insert_story(pool: &PgPool, organization_id: Uuid, title: &str) -> Uuid {
+    let story_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO stories (id, project_id, organization_id, title, status)
+         VALUES ($1, $2, $3, $4, 'draft')",
+    )
+    .bind(story_id)
+    .bind(Uuid::new_v4())
+    .bind(organization_id)
+    .bind(title)
+    .execute(pool)
+    .await
+    .unwrap();
+    story_id
+}
+
+async fn projected_title(pool: &PgPool, story_id: Uuid) -> Option<String> {
+    sqlx::query_scalar("SELECT title FROM readiness_story_projections WHERE id = $1")
+        .bind(story_id)
+        .fetch_optional(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[serial]
+async fn test_projection_miss_reads_through_and_backfills() {
+    let pool = setup_migrated_test_db().await;
+    let organization_id = Uuid::new_v4();
+    let story_id = insert_story(&pool, organization_id, "Missed story").await;
+    let service = ProjectionStoryService::new(Arc::new(pool.clone()));
+    assert_eq!(projected_title(&pool, story_id).await, None);
+
+    let story = service
+        .get_story_info(story_id, Some(organization_id))
+        .await
+        .unwrap()
+        .expect("read through to stories");
+
+    assert_eq!(story.title, "Missed story");
+    assert_eq!(
+        projected_title(&pool, story_id).await.as_deref(),
+        Some("Missed story")
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_backfilled_story_is_read_from_the_projection() {
+    let pool = setup_migrated_test_db().await;
+    let organization_id = Uuid::new_v4();
+    let story_id = insert_story(&pool, organization_id, "Missed story").await;
+    let service = ProjectionStoryService::new(Arc::new(pool.clone()));
+    service
+        .get_story_info(story_id, Some(organization_id))
+        .await
+        .unwrap();
+
+    // Without an event the projection keeps the backfilled title
+    sqlx::query("UPDATE stories SET title = 'Renamed story' WHERE id = $1")
+        .bind(story_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let story = service
+        .get_story_info(story_id, Some(organization_id))
+        .await
+        .unwrap()
+        .expect("projected story");
+
+    assert_eq!(story.title, "Missed story");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_read_through_is_scoped_to_the_organization() {
+    let pool = setup_migrated_test_db().await;
+    let story_id = insert_story(&pool, Uuid::new_v4(), "Other org's story").await;
+    let service = ProjectionStoryService::new(Arc::new(pool.clone()));
+
+    for organization_id in [Some(Uuid::new_v4()), None] {
+        let story = service
+            .get_story_info(story_id, organization_id)
+            .await
+            .unwrap();
+        assert!(story.is_none());
+    }
+    assert_eq!(projected_title(&pool, story_id).await, None);
+}
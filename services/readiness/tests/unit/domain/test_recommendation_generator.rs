@@ -196,7 +196,7 @@ fn test_recommendation_prioritization() {
     let gap_types: Vec<GapType> = analysis
         .recommendations
         .iter()
-        .map(|r| r.gap_type.clone())
+        .map(|r| r.gap_type)
         .collect();
 
     // First few should be high-priority gaps
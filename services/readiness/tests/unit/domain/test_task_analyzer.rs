@@ -1,5 +1,5 @@
 use readiness::application::ports::TaskInfo;
-use readiness::domain::{GapType, TaskAnalyzer};
+use readiness::domain::{ContentLanguage, GapType, TaskAnalyzer};
 use uuid::Uuid;
 
 #[test]
@@ -1228,3 +1228,42 @@ fn test_idempotency_of_analysis() {
     assert_eq!(analysis1.summary, analysis2.summary);
     assert_eq!(analysis2.summary, analysis3.summary);
 }
+
+#[test]
+fn test_german_task_is_analyzed_with_german_keywords() {
+    // Given: a task written in German that covers paths, inputs and tests
+    let task_info = TaskInfo {
+        id: Uuid::new_v4(),
+        story_id: Uuid::new_v4(),
+        title: "Anmeldung verbessern".to_string(),
+        description: Some(
+            "Die Anmeldung verbessern: Die Funktion im Pfad src/auth.rs soll die Eingabe \
+             prüfen und wird mit einem Test abgedeckt. Der Ansatz ist abgeschlossen, wenn die Konfiguration der \
+             Umgebung steht."
+                .to_string(),
+        ),
+        acceptance_criteria_refs: vec!["AC1".to_string()],
+        estimated_hours: Some(4),
+    };
+
+    // When: I analyze the task
+    let analysis = TaskAnalyzer::analyze(&task_info, &["AC1".to_string()], None);
+
+    // Then: the language is detected, German vague terms are flagged and the
+    // German keywords count towards the technical details
+    assert_eq!(analysis.language, ContentLanguage::German);
+    let vague = analysis
+        .recommendations
+        .iter()
+        .find(|r| r.gap_type == GapType::VagueLanguage)
+        .expect("'verbessern' is a vague term");
+    assert!(vague
+        .specific_suggestions
+        .iter()
+        .any(|s| s.contains("'verbessern'")));
+    assert!(analysis
+        .recommendations
+        .iter()
+        .all(|r| r.gap_type != GapType::MissingTechnicalDetails));
+    assert!(analysis.summary.starts_with("Die Aufgabe"));
+}
@@ -0,0 +1,5 @@
+//! Public API of the sprint service: its usecases, router and the types
+//! other services read. Everything else in `sprint` is internal to it.
+
+pub use sprint::domain::SprintStats;
+pub use sprint::{create_sprint_router, SprintsUsecases};
@@ -1,9 +1,14 @@
+use crate::domain::SprintRollover;
+use crate::snapshot::SnapshotFormat;
 use crate::SprintsUsecases;
+use auth_clerk::AuthenticatedWithOrg;
 use axum::{
     extract::{Path, Query, State},
+    http::{header, StatusCode},
     response::IntoResponse,
     Json,
 };
+use chrono::{DateTime, Utc};
 use common::AppError;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -18,12 +23,15 @@ pub async fn get_active_sprint(
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SprintTaskBoardQuery {
     /// Filter by task status (available, owned, inprogress, completed)
     pub status: Option<String>,
     /// Filter by task owner user ID
+    #[serde(alias = "owner_id")]
     pub owner_id: Option<Uuid>,
     /// Group tasks by: story or status
+    #[serde(alias = "group_by")]
     pub group_by: Option<String>,
 }
 
@@ -40,3 +48,287 @@ pub async fn get_sprint_task_board(
 
     Ok(Json(response))
 }
+
+/// GET /api/v1/sprints/{sprint_id}/burndown
+pub async fn get_sprint_burndown(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(sprint_id): Path<Uuid>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let burndown = usecases
+        .get_sprint_burndown(sprint_id, org_context.effective_organization_uuid())
+        .await?;
+    Ok(Json(burndown))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SnapshotQuery {
+    /// `json` (the default) or `html`
+    pub format: Option<String>,
+}
+
+/// GET /api/v1/sprints/{sprint_id}/snapshot
+/// Downloads the board as a self-contained file for workshops and archival
+pub async fn get_sprint_snapshot(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(sprint_id): Path<Uuid>,
+    Query(query): Query<SnapshotQuery>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let format = SnapshotFormat::parse(query.format.as_deref())?;
+    let snapshot = usecases
+        .get_sprint_snapshot(sprint_id, org_context.effective_organization_uuid())
+        .await?;
+    let disposition = format!(
+        "attachment; filename=\"sprint-{}-snapshot-{}.{}\"",
+        sprint_id,
+        snapshot.generated_at.format("%Y%m%d%H%M"),
+        format.extension()
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        snapshot.render(format)?,
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VelocityQuery {
+    /// How many completed sprints to cover, newest first
+    pub sprints: Option<u32>,
+}
+
+/// GET /api/v1/projects/{project_id}/velocity
+pub async fn get_project_velocity(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<VelocityQuery>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let velocity = usecases
+        .get_project_velocity(
+            project_id,
+            org_context.effective_organization_uuid(),
+            query.sprints,
+        )
+        .await?;
+    Ok(Json(velocity))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThroughputQuery {
+    /// How many finished weeks to count, newest last
+    pub weeks: Option<u32>,
+    /// How many weeks ahead to forecast
+    pub forecast_weeks: Option<u32>,
+}
+
+/// GET /api/v1/teams/{team_id}/throughput
+pub async fn get_team_throughput(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(team_id): Path<Uuid>,
+    Query(query): Query<ThroughputQuery>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let throughput = usecases
+        .get_team_throughput(
+            team_id,
+            org_context.effective_organization_uuid(),
+            query.weeks,
+            query.forecast_weeks,
+        )
+        .await?;
+    Ok(Json(throughput))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSprintGoalRequest {
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(default, alias = "story_ids")]
+    pub story_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkGoalStoriesRequest {
+    #[serde(alias = "story_ids")]
+    pub story_ids: Vec<Uuid>,
+}
+
+/// GET /api/v1/sprints/{sprint_id}/goals
+/// Returns the sprint's goals with progress computed from linked stories
+pub async fn get_sprint_goals(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(sprint_id): Path<Uuid>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let report = usecases
+        .get_sprint_goal_report(sprint_id, org_context.effective_organization_uuid())
+        .await?;
+    Ok(Json(report))
+}
+
+/// POST /api/v1/sprints/{sprint_id}/goals
+pub async fn create_sprint_goal(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(sprint_id): Path<Uuid>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+    Json(payload): Json<CreateSprintGoalRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let goal = usecases
+        .create_sprint_goal(
+            sprint_id,
+            org_context.effective_organization_uuid(),
+            payload.title,
+            payload.description,
+            payload.story_ids,
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(goal)))
+}
+
+/// PUT /api/v1/sprints/{sprint_id}/goals/{goal_id}/stories
+/// Replaces the stories linked to the goal
+pub async fn link_goal_stories(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path((sprint_id, goal_id)): Path<(Uuid, Uuid)>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+    Json(payload): Json<LinkGoalStoriesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    usecases
+        .link_goal_stories(
+            sprint_id,
+            org_context.effective_organization_uuid(),
+            goal_id,
+            payload.story_ids,
+        )
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// DELETE /api/v1/sprints/{sprint_id}/goals/{goal_id}
+pub async fn delete_sprint_goal(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path((sprint_id, goal_id)): Path<(Uuid, Uuid)>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    usecases
+        .delete_sprint_goal(
+            sprint_id,
+            org_context.effective_organization_uuid(),
+            goal_id,
+        )
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSprintRequest {
+    pub name: String,
+    pub goal: Option<String>,
+    #[serde(alias = "capacity_points")]
+    pub capacity_points: u32,
+    #[serde(alias = "start_date")]
+    pub start_date: DateTime<Utc>,
+    #[serde(alias = "end_date")]
+    pub end_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompleteSprintRequest {
+    /// Where unfinished stories go: next_sprint (default) or backlog
+    #[serde(default)]
+    pub rollover: SprintRollover,
+    /// Sprint to roll stories into instead of the team's next planned one
+    #[serde(alias = "next_sprint_id")]
+    pub next_sprint_id: Option<Uuid>,
+}
+
+/// POST /api/v1/projects/{project_id}/sprints/planned
+/// Creates a sprint in planning for the project's team
+pub async fn create_planned_sprint(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(project_id): Path<Uuid>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+    Json(payload): Json<CreateSprintRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let sprint = usecases
+        .create_sprint(
+            project_id,
+            org_context.effective_organization_uuid(),
+            payload.name,
+            payload.goal,
+            payload.capacity_points,
+            payload.start_date,
+            payload.end_date,
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(sprint)))
+}
+
+/// POST /api/v1/sprints/{sprint_id}/lifecycle/start
+pub async fn start_sprint(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(sprint_id): Path<Uuid>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+) -> Result<impl IntoResponse, AppError> {
+    let sprint = usecases
+        .start_sprint(sprint_id, org_context.effective_organization_uuid())
+        .await?;
+    Ok(Json(sprint))
+}
+
+/// POST /api/v1/sprints/{sprint_id}/lifecycle/complete
+/// Completes the sprint and rolls its unfinished stories over; the body is optional
+pub async fn complete_sprint(
+    AuthenticatedWithOrg { org_context, .. }: AuthenticatedWithOrg,
+    Path(sprint_id): Path<Uuid>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+    payload: Option<Json<CompleteSprintRequest>>,
+) -> Result<impl IntoResponse, AppError> {
+    let Json(payload) = payload.unwrap_or_default();
+    let closure = usecases
+        .complete_sprint(
+            sprint_id,
+            org_context.effective_organization_uuid(),
+            payload.rollover,
+            payload.next_sprint_id,
+        )
+        .await?;
+    Ok(Json(closure))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CancelSprintRequest {
+    /// Required when the organization's reason policy covers cancelling sprints
+    pub reason: Option<String>,
+}
+
+/// POST /api/v1/sprints/{sprint_id}/lifecycle/cancel
+/// Cancels the sprint and returns its unaccepted stories to the backlog; the body is optional
+pub async fn cancel_sprint(
+    AuthenticatedWithOrg { org_context, auth }: AuthenticatedWithOrg,
+    Path(sprint_id): Path<Uuid>,
+    State(usecases): State<Arc<SprintsUsecases>>,
+    payload: Option<Json<CancelSprintRequest>>,
+) -> Result<impl IntoResponse, AppError> {
+    let Json(payload) = payload.unwrap_or_default();
+    let cancelled_by = usecases.get_user_id(&auth.sub).await?;
+    let closure = usecases
+        .cancel_sprint(
+            sprint_id,
+            org_context.effective_organization_uuid(),
+            cancelled_by,
+            payload.reason.as_deref(),
+        )
+        .await?;
+    Ok(Json(closure))
+}
@@ -0,0 +1,71 @@
+use crate::adapters::http::handlers::{
+    cancel_sprint, complete_sprint, create_planned_sprint, create_sprint_goal, delete_sprint_goal,
+    get_active_sprint, get_project_velocity, get_sprint_burndown, get_sprint_goals,
+    get_sprint_snapshot, get_team_throughput, link_goal_stories, start_sprint,
+};
+use crate::SprintsUsecases;
+use auth_clerk::JwtVerifier;
+use axum::routing::{delete, get, post, put};
+use axum::{Extension, Router};
+use common::route_registry::ServiceRouter;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tower_http::trace::TraceLayer;
+
+pub fn create_sprint_router(
+    sprint_usecases: Arc<SprintsUsecases>,
+    verifier: Arc<Mutex<JwtVerifier>>,
+) -> Router {
+    ServiceRouter::new("sprint")
+        .route(
+            "/api/v1/projects/{project_id}/sprints/active",
+            get(get_active_sprint),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/sprints/planned",
+            post(create_planned_sprint),
+        )
+        .route(
+            "/api/v1/projects/{project_id}/velocity",
+            get(get_project_velocity),
+        )
+        .route(
+            "/api/v1/teams/{team_id}/throughput",
+            get(get_team_throughput),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/lifecycle/start",
+            post(start_sprint),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/burndown",
+            get(get_sprint_burndown),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/snapshot",
+            get(get_sprint_snapshot),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/lifecycle/complete",
+            post(complete_sprint),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/lifecycle/cancel",
+            post(cancel_sprint),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/goals",
+            get(get_sprint_goals).post(create_sprint_goal),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/goals/{goal_id}",
+            delete(delete_sprint_goal),
+        )
+        .route(
+            "/api/v1/sprints/{sprint_id}/goals/{goal_id}/stories",
+            put(link_goal_stories),
+        )
+        .with_state(sprint_usecases)
+        .layer(Extension(verifier))
+        .layer(TraceLayer::new_for_http())
+}
@@ -1,4 +1,10 @@
-use crate::domain::{Sprint, TaskWithStory};
+use crate::domain::{
+    GoalStory, RolloverTarget, Sprint, SprintGoal, SprintStory, StoryMove, StoryTypeStats,
+    TaskWithStory, SPRINT_PLANNING,
+};
+use crate::snapshot::SnapshotStory;
+use common::calendar::SprintCalendar;
+use common::reason_policy::{DestructiveAction, ReasonPolicy};
 use common::AppError;
 use sqlx::{PgPool, Row};
 use tracing::error;
@@ -35,6 +41,26 @@ pub async fn get_sprint_by_id(pool: &PgPool, sprint_id: Uuid) -> Result<Option<S
     Ok(sprint)
 }
 
+/// Calendar in the project's configured time zone
+pub async fn get_project_calendar(
+    pool: &PgPool,
+    project_id: Uuid,
+) -> Result<SprintCalendar, AppError> {
+    let timezone: Option<String> =
+        sqlx::query_scalar("SELECT timezone FROM project_settings WHERE project_id = $1")
+            .bind(project_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "SQL error fetching project time zone");
+                AppError::InternalServerError
+            })?;
+
+    Ok(timezone
+        .map(|name| SprintCalendar::from_name(&name))
+        .unwrap_or_default())
+}
+
 /// Fetch all tasks from stories in the sprint with optional filters
 /// Note: This queries the backlog database directly for read model purposes.
 /// In a more mature architecture, this could be replaced with an API call or event-driven read model.
@@ -59,7 +85,7 @@ pub async fn get_sprint_tasks(
             t.updated_at
          FROM tasks t
          INNER JOIN stories s ON t.story_id = s.id
-         WHERE s.sprint_id = $1",
+         WHERE s.sprint_id = $1 AND t.status <> 'superseded'",
     );
 
     let mut param_count = 1;
@@ -131,3 +157,644 @@ pub async fn count_sprint_stories(pool: &PgPool, sprint_id: Uuid) -> Result<usiz
 
     Ok(count as usize)
 }
+
+/// The sprint's stories, in the order they were created
+pub async fn get_snapshot_stories(
+    pool: &PgPool,
+    sprint_id: Uuid,
+) -> Result<Vec<SnapshotStory>, AppError> {
+    sqlx::query_as::<_, SnapshotStory>(
+        "SELECT id AS story_id, title, status, story_type, story_points
+         FROM stories
+         WHERE sprint_id = $1 AND deleted_at IS NULL
+         ORDER BY created_at, id",
+    )
+    .bind(sprint_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching sprint snapshot stories");
+        AppError::InternalServerError
+    })
+}
+
+/// Story count, points and accepted points per story type
+pub async fn get_sprint_story_type_stats(
+    pool: &PgPool,
+    sprint_id: Uuid,
+) -> Result<Vec<StoryTypeStats>, AppError> {
+    sqlx::query_as::<_, StoryTypeStats>(
+        "SELECT story_type,
+                COUNT(*) AS stories,
+                COALESCE(SUM(story_points), 0)::BIGINT AS points,
+                COALESCE(SUM(story_points) FILTER (WHERE status = 'accepted'), 0)::BIGINT AS accepted_points
+         FROM stories
+         WHERE sprint_id = $1 AND deleted_at IS NULL
+         GROUP BY story_type
+         ORDER BY story_type",
+    )
+    .bind(sprint_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error computing sprint story type stats");
+        AppError::InternalServerError
+    })
+}
+
+/// Fetch a sprint's goals in display order, each with its linked story ids
+pub async fn get_sprint_goals(
+    pool: &PgPool,
+    sprint_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Vec<SprintGoal>, AppError> {
+    sqlx::query_as::<_, SprintGoal>(
+        "SELECT g.id, g.sprint_id, g.title, g.description, g.position,
+            COALESCE(array_agg(gs.story_id) FILTER (WHERE gs.story_id IS NOT NULL), '{}') AS story_ids
+         FROM sprint_goals g
+         INNER JOIN sprints sp ON sp.id = g.sprint_id
+         INNER JOIN teams t ON t.id = sp.team_id
+         LEFT JOIN sprint_goal_stories gs ON gs.goal_id = g.id
+         WHERE g.sprint_id = $1 AND t.organization_id IS NOT DISTINCT FROM $2
+         GROUP BY g.id
+         ORDER BY g.position, g.created_at",
+    )
+    .bind(sprint_id)
+    .bind(organization_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching sprint goals");
+        AppError::InternalServerError
+    })
+}
+
+/// Fetch the organization's stories linked to any goal of the sprint
+pub async fn get_goal_stories(
+    pool: &PgPool,
+    sprint_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Vec<GoalStory>, AppError> {
+    sqlx::query_as::<_, GoalStory>(
+        "SELECT gs.goal_id, s.id AS story_id, s.title, s.status, s.story_points
+         FROM sprint_goal_stories gs
+         INNER JOIN sprint_goals g ON g.id = gs.goal_id
+         INNER JOIN stories s ON s.id = gs.story_id
+         WHERE g.sprint_id = $1 AND s.organization_id IS NOT DISTINCT FROM $2",
+    )
+    .bind(sprint_id)
+    .bind(organization_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching sprint goal stories");
+        AppError::InternalServerError
+    })
+}
+
+/// Count how many of the given stories are not the organization's stories
+/// planned into the sprint
+pub async fn count_stories_outside_sprint(
+    pool: &PgPool,
+    sprint_id: Uuid,
+    organization_id: Option<Uuid>,
+    story_ids: &[Uuid],
+) -> Result<usize, AppError> {
+    let in_sprint: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM stories
+         WHERE id = ANY($1) AND sprint_id = $2 AND organization_id IS NOT DISTINCT FROM $3",
+    )
+    .bind(story_ids)
+    .bind(sprint_id)
+    .bind(organization_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error checking sprint goal stories");
+        AppError::InternalServerError
+    })?;
+
+    Ok(story_ids.len().saturating_sub(in_sprint as usize))
+}
+
+/// Returns false when the sprint does not belong to a team of the organization
+pub async fn create_sprint_goal(
+    pool: &PgPool,
+    goal: &SprintGoal,
+    organization_id: Option<Uuid>,
+) -> Result<bool, AppError> {
+    let mut tx = pool.begin().await.map_err(|e| {
+        error!(error = %e, "Failed to start sprint goal transaction");
+        AppError::InternalServerError
+    })?;
+
+    let result = sqlx::query(
+        "INSERT INTO sprint_goals (id, sprint_id, title, description, position)
+         SELECT $1, sp.id, $3, $4,
+            (SELECT COALESCE(MAX(position) + 1, 0) FROM sprint_goals WHERE sprint_id = $2)
+         FROM sprints sp
+         INNER JOIN teams t ON t.id = sp.team_id
+         WHERE sp.id = $2 AND t.organization_id IS NOT DISTINCT FROM $5",
+    )
+    .bind(goal.id)
+    .bind(goal.sprint_id)
+    .bind(&goal.title)
+    .bind(&goal.description)
+    .bind(organization_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error creating sprint goal");
+        AppError::InternalServerError
+    })?;
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    insert_goal_stories(&mut tx, goal.id, &goal.story_ids).await?;
+
+    tx.commit().await.map_err(|e| {
+        error!(error = %e, "Failed to commit sprint goal transaction");
+        AppError::InternalServerError
+    })?;
+
+    Ok(true)
+}
+
+/// Replace the set of stories linked to a goal; returns false when the goal
+/// does not exist in the organization
+pub async fn replace_goal_stories(
+    pool: &PgPool,
+    sprint_id: Uuid,
+    organization_id: Option<Uuid>,
+    goal_id: Uuid,
+    story_ids: &[Uuid],
+) -> Result<bool, AppError> {
+    let mut tx = pool.begin().await.map_err(|e| {
+        error!(error = %e, "Failed to start sprint goal transaction");
+        AppError::InternalServerError
+    })?;
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(
+            SELECT 1
+            FROM sprint_goals g
+            INNER JOIN sprints sp ON sp.id = g.sprint_id
+            INNER JOIN teams t ON t.id = sp.team_id
+            WHERE g.id = $1 AND g.sprint_id = $2 AND t.organization_id IS NOT DISTINCT FROM $3
+        )",
+    )
+    .bind(goal_id)
+    .bind(sprint_id)
+    .bind(organization_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching sprint goal");
+        AppError::InternalServerError
+    })?;
+    if !exists {
+        return Ok(false);
+    }
+
+    sqlx::query("DELETE FROM sprint_goal_stories WHERE goal_id = $1")
+        .bind(goal_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "SQL error clearing sprint goal stories");
+            AppError::InternalServerError
+        })?;
+
+    insert_goal_stories(&mut tx, goal_id, story_ids).await?;
+
+    sqlx::query("UPDATE sprint_goals SET updated_at = NOW() WHERE id = $1")
+        .bind(goal_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "SQL error touching sprint goal");
+            AppError::InternalServerError
+        })?;
+
+    tx.commit().await.map_err(|e| {
+        error!(error = %e, "Failed to commit sprint goal transaction");
+        AppError::InternalServerError
+    })?;
+
+    Ok(true)
+}
+
+async fn insert_goal_stories(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    goal_id: Uuid,
+    story_ids: &[Uuid],
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO sprint_goal_stories (goal_id, story_id)
+         SELECT $1, UNNEST($2::uuid[])
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(goal_id)
+    .bind(story_ids)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error linking sprint goal stories");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+/// Returns false when no goal with that id belongs to the organization's sprint
+pub async fn delete_sprint_goal(
+    pool: &PgPool,
+    sprint_id: Uuid,
+    organization_id: Option<Uuid>,
+    goal_id: Uuid,
+) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        "DELETE FROM sprint_goals g
+         USING sprints sp, teams t
+         WHERE g.id = $1 AND g.sprint_id = $2
+           AND sp.id = g.sprint_id AND t.id = sp.team_id
+           AND t.organization_id IS NOT DISTINCT FROM $3",
+    )
+    .bind(goal_id)
+    .bind(sprint_id)
+    .bind(organization_id)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error deleting sprint goal");
+        AppError::InternalServerError
+    })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// The sprint, when it belongs to a team of the organization
+pub async fn get_organization_sprint(
+    pool: &PgPool,
+    sprint_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Option<Sprint>, AppError> {
+    sqlx::query_as::<_, Sprint>(
+        "SELECT sp.*
+         FROM sprints sp
+         INNER JOIN teams t ON t.id = sp.team_id
+         WHERE sp.id = $1 AND t.organization_id IS NOT DISTINCT FROM $2",
+    )
+    .bind(sprint_id)
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching organization sprint");
+        AppError::InternalServerError
+    })
+}
+
+/// The project's team and organization, when the project is in the organization
+pub async fn get_project_team(
+    pool: &PgPool,
+    project_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<Option<(Option<Uuid>, Option<Uuid>)>, AppError> {
+    sqlx::query_as::<_, (Option<Uuid>, Option<Uuid>)>(
+        "SELECT team_id, organization_id FROM projects
+         WHERE id = $1 AND organization_id IS NOT DISTINCT FROM $2 AND deleted_at IS NULL",
+    )
+    .bind(project_id)
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching project team");
+        AppError::InternalServerError
+    })
+}
+
+pub async fn create_sprint(pool: &PgPool, sprint: &Sprint) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO sprints (id, project_id, team_id, organization_id, name, goal, status,
+             capacity_points, committed_points, completed_points, start_date, end_date,
+             created_at, updated_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)",
+    )
+    .bind(sprint.id)
+    .bind(sprint.project_id)
+    .bind(sprint.team_id)
+    .bind(sprint.organization_id)
+    .bind(&sprint.name)
+    .bind(sprint.goal.as_deref().unwrap_or_default())
+    .bind(&sprint.status)
+    .bind(sprint.capacity_points)
+    .bind(sprint.committed_points)
+    .bind(sprint.completed_points)
+    .bind(sprint.start_date)
+    .bind(sprint.end_date)
+    .bind(sprint.created_at)
+    .bind(sprint.updated_at)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error creating sprint");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+/// The team's other sprint that is currently active, if any
+pub async fn get_team_active_sprint_id(
+    pool: &PgPool,
+    team_id: Uuid,
+    except_sprint_id: Uuid,
+) -> Result<Option<Uuid>, AppError> {
+    sqlx::query_scalar(
+        "SELECT id FROM sprints WHERE team_id = $1 AND status = 'active' AND id <> $2 LIMIT 1",
+    )
+    .bind(team_id)
+    .bind(except_sprint_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching team active sprint");
+        AppError::InternalServerError
+    })
+}
+
+/// The team's earliest planned sprint starting after `sprint_id`
+pub async fn get_next_planned_sprint(
+    pool: &PgPool,
+    team_id: Uuid,
+    sprint_id: Uuid,
+) -> Result<Option<Sprint>, AppError> {
+    sqlx::query_as::<_, Sprint>(
+        "SELECT * FROM sprints
+         WHERE team_id = $1 AND status = $3 AND id <> $2
+           AND start_date >= (SELECT start_date FROM sprints WHERE id = $2)
+         ORDER BY start_date, created_at
+         LIMIT 1",
+    )
+    .bind(team_id)
+    .bind(sprint_id)
+    .bind(SPRINT_PLANNING)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching next planned sprint");
+        AppError::InternalServerError
+    })
+}
+
+pub async fn get_sprint_stories(
+    pool: &PgPool,
+    sprint_id: Uuid,
+) -> Result<Vec<SprintStory>, AppError> {
+    sqlx::query_as::<_, SprintStory>(
+        "SELECT id, status, story_points FROM stories
+         WHERE sprint_id = $1 AND deleted_at IS NULL
+         ORDER BY created_at, id",
+    )
+    .bind(sprint_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching sprint stories");
+        AppError::InternalServerError
+    })
+}
+
+/// Activate a planned sprint as its team's active sprint. Returns false when
+/// it is no longer planned.
+pub async fn start_sprint(pool: &PgPool, sprint: &Sprint) -> Result<bool, AppError> {
+    let mut tx = pool.begin().await.map_err(|e| {
+        error!(error = %e, "Failed to start sprint transaction");
+        AppError::InternalServerError
+    })?;
+
+    let started = sqlx::query(
+        "UPDATE sprints SET status = 'active', updated_at = NOW()
+         WHERE id = $1 AND status = 'planning'",
+    )
+    .bind(sprint.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error starting sprint");
+        AppError::InternalServerError
+    })?
+    .rows_affected()
+        > 0;
+    if !started {
+        return Ok(false);
+    }
+
+    sqlx::query("UPDATE teams SET active_sprint_id = $2, updated_at = NOW() WHERE id = $1")
+        .bind(sprint.team_id)
+        .bind(sprint.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "SQL error updating team active sprint");
+            AppError::InternalServerError
+        })?;
+
+    tx.commit().await.map_err(|e| {
+        error!(error = %e, "Failed to commit sprint transaction");
+        AppError::InternalServerError
+    })?;
+
+    Ok(true)
+}
+
+/// Move a sprint from one of the `from` statuses to `status`, moving its
+/// stories as planned and recording each move as a scope change. Returns false
+/// when the sprint had already left those statuses.
+pub async fn close_sprint(
+    pool: &PgPool,
+    sprint: &Sprint,
+    from: &[&str],
+    status: &str,
+    completed_points: u32,
+    moves: &[StoryMove],
+    next: Option<&Sprint>,
+) -> Result<bool, AppError> {
+    let mut tx = pool.begin().await.map_err(|e| {
+        error!(error = %e, "Failed to start sprint transaction");
+        AppError::InternalServerError
+    })?;
+
+    let closed = sqlx::query(
+        "UPDATE sprints
+         SET status = $2, completed_points = LEAST($3, committed_points), updated_at = NOW()
+         WHERE id = $1 AND status = ANY($4)",
+    )
+    .bind(sprint.id)
+    .bind(status)
+    .bind(completed_points as i32)
+    .bind(from)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error closing sprint");
+        AppError::InternalServerError
+    })?
+    .rows_affected()
+        > 0;
+    if !closed {
+        return Ok(false);
+    }
+
+    sqlx::query(
+        "UPDATE teams SET active_sprint_id = NULL, updated_at = NOW() WHERE active_sprint_id = $1",
+    )
+    .bind(sprint.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error clearing team active sprint");
+        AppError::InternalServerError
+    })?;
+
+    for story_move in moves {
+        let next_sprint = match story_move.target {
+            RolloverTarget::Sprint(next_id) => next.filter(|next| next.id == next_id),
+            RolloverTarget::Backlog => None,
+        };
+        // Committed stories go back to Ready; the backlog never holds committed work
+        sqlx::query(
+            "UPDATE stories
+             SET sprint_id = $3,
+                 status = CASE WHEN $3 IS NULL AND status = 'committed' THEN 'ready' ELSE status END,
+                 updated_at = NOW()
+             WHERE id = $1 AND sprint_id = $2",
+        )
+        .bind(story_move.story_id)
+        .bind(sprint.id)
+        .bind(next_sprint.map(|next| next.id))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "SQL error moving sprint story");
+            AppError::InternalServerError
+        })?;
+
+        insert_scope_change(&mut tx, sprint, status, story_move, false).await?;
+        if let Some(next) = next_sprint {
+            insert_scope_change(&mut tx, next, &next.status, story_move, true).await?;
+            sqlx::query(
+                "UPDATE sprints SET committed_points = committed_points + $2, updated_at = NOW()
+                 WHERE id = $1",
+            )
+            .bind(next.id)
+            .bind(story_move.story_points.unwrap_or(0) as i32)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "SQL error adjusting sprint committed points");
+                AppError::InternalServerError
+            })?;
+        }
+    }
+
+    tx.commit().await.map_err(|e| {
+        error!(error = %e, "Failed to commit sprint transaction");
+        AppError::InternalServerError
+    })?;
+
+    Ok(true)
+}
+
+async fn insert_scope_change(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    sprint: &Sprint,
+    sprint_status: &str,
+    story_move: &StoryMove,
+    added: bool,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO sprint_scope_changes
+             (id, sprint_id, story_id, organization_id, story_points, added, sprint_status, changed_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())",
+    )
+    .bind(Uuid::new_v4())
+    .bind(sprint.id)
+    .bind(story_move.story_id)
+    .bind(sprint.organization_id)
+    .bind(story_move.story_points.map(|points| points as i32))
+    .bind(added)
+    .bind(sprint_status)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error recording sprint scope change");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
+
+pub async fn get_user_id(pool: &PgPool, external_id: &str) -> Result<Option<Uuid>, AppError> {
+    sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE external_id = $1")
+        .bind(external_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "SQL error looking up user");
+            AppError::InternalServerError
+        })
+}
+
+/// The organization's reason policy, shared with the backlog service
+pub async fn get_reason_policy(
+    pool: &PgPool,
+    organization_id: Uuid,
+) -> Result<ReasonPolicy, AppError> {
+    let actions = sqlx::query_scalar::<_, Vec<String>>(
+        "SELECT require_reason_for FROM organization_reason_policies
+         WHERE organization_id = $1",
+    )
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching reason policy");
+        AppError::InternalServerError
+    })?;
+
+    Ok(ReasonPolicy::from_stored(&actions.unwrap_or_default()))
+}
+
+pub async fn record_destructive_action(
+    pool: &PgPool,
+    organization_id: Option<Uuid>,
+    user_id: Option<Uuid>,
+    action: DestructiveAction,
+    entity_id: Uuid,
+    reason: Option<&str>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO destructive_action_audit_log
+             (id, organization_id, user_id, action, entity_type, entity_id, reason, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())",
+    )
+    .bind(Uuid::new_v4())
+    .bind(organization_id)
+    .bind(user_id)
+    .bind(action.as_str())
+    .bind(action.entity_type())
+    .bind(entity_id)
+    .bind(reason)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error recording destructive action");
+        AppError::InternalServerError
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! Queries behind the sprint reports. Like the task board, they read the
+//! backlog's stories and tasks directly.
+
+use crate::domain::SPRINT_COMPLETED;
+use crate::reporting::{BurndownItem, SprintVelocity};
+use chrono::{DateTime, Utc};
+use common::AppError;
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+/// Every task of the sprint's stories, plus one row for each story without tasks
+pub async fn get_burndown_items(
+    pool: &PgPool,
+    sprint_id: Uuid,
+) -> Result<Vec<BurndownItem>, AppError> {
+    sqlx::query_as::<_, BurndownItem>(
+        "SELECT s.id AS story_id, s.story_points, t.id AS task_id, t.completed_at
+         FROM stories s
+         LEFT JOIN tasks t ON t.story_id = s.id
+         WHERE s.sprint_id = $1 AND s.deleted_at IS NULL
+         ORDER BY s.created_at, s.id, t.created_at",
+    )
+    .bind(sprint_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching sprint burndown");
+        AppError::InternalServerError
+    })
+}
+
+/// The project's most recently completed sprints, newest first
+pub async fn get_completed_sprint_velocities(
+    pool: &PgPool,
+    project_id: Uuid,
+    limit: u32,
+) -> Result<Vec<SprintVelocity>, AppError> {
+    sqlx::query_as::<_, SprintVelocity>(
+        "SELECT id AS sprint_id, name, start_date, end_date, committed_points, completed_points
+         FROM sprints
+         WHERE project_id = $1 AND status = $2
+         ORDER BY end_date DESC, id
+         LIMIT $3",
+    )
+    .bind(project_id)
+    .bind(SPRINT_COMPLETED)
+    .bind(i64::from(limit))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching sprint velocities");
+        AppError::InternalServerError
+    })
+}
+
+/// Whether the team belongs to the organization
+pub async fn team_in_organization(
+    pool: &PgPool,
+    team_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> Result<bool, AppError> {
+    sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (
+             SELECT 1 FROM teams WHERE id = $1 AND organization_id IS NOT DISTINCT FROM $2
+         )",
+    )
+    .bind(team_id)
+    .bind(organization_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error checking team organization");
+        AppError::InternalServerError
+    })
+}
+
+/// When each story of the team's projects accepted since `since` was last
+/// updated, archived stories included. Accepted stories stay untouched, so
+/// this is when they were accepted.
+pub async fn get_team_acceptance_times(
+    pool: &PgPool,
+    team_id: Uuid,
+    since: DateTime<Utc>,
+) -> Result<Vec<DateTime<Utc>>, AppError> {
+    sqlx::query_scalar::<_, DateTime<Utc>>(
+        "SELECT s.updated_at
+         FROM stories s
+         INNER JOIN projects p ON p.id = s.project_id
+         WHERE p.team_id = $1 AND s.status = 'accepted' AND s.deleted_at IS NULL
+           AND s.updated_at >= $2
+         UNION ALL
+         SELECT a.updated_at
+         FROM archived_stories a
+         INNER JOIN projects p ON p.id = a.project_id
+         WHERE p.team_id = $1 AND a.status = 'accepted' AND a.updated_at >= $2",
+    )
+    .bind(team_id)
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "SQL error fetching team story acceptances");
+        AppError::InternalServerError
+    })
+}
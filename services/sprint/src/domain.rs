@@ -1,35 +1,233 @@
 use chrono::{DateTime, Utc};
+use common::calendar::SprintCalendar;
+use common::AppError;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, sqlx::FromRow, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Sprint {
     pub id: Uuid,
     pub project_id: Uuid,
+    pub team_id: Uuid,
+    pub organization_id: Option<Uuid>,
     pub name: String,
     pub goal: Option<String>,
     pub status: String,
+    pub capacity_points: i32,
+    pub committed_points: i32,
+    pub completed_points: i32,
     pub start_date: DateTime<Utc>,
     pub end_date: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Sprints longer than this are rejected, as the sprints table requires
+pub const MAX_SPRINT_DAYS: i64 = 28;
+
+impl Sprint {
+    /// A new sprint in planning. Its team and organization are the project's.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        project_id: Uuid,
+        team_id: Uuid,
+        organization_id: Option<Uuid>,
+        name: String,
+        goal: Option<String>,
+        capacity_points: u32,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> Result<Self, AppError> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::BadRequest(
+                "Sprint name cannot be empty".to_string(),
+            ));
+        }
+        if capacity_points == 0 {
+            return Err(AppError::BadRequest(
+                "Sprint capacity must be greater than 0".to_string(),
+            ));
+        }
+        let length = end_date - start_date;
+        if length < chrono::Duration::days(1) || length > chrono::Duration::days(MAX_SPRINT_DAYS) {
+            return Err(AppError::BadRequest(format!(
+                "A sprint must last between 1 and {} days",
+                MAX_SPRINT_DAYS
+            )));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            project_id,
+            team_id,
+            organization_id,
+            name,
+            goal: goal
+                .map(|goal| goal.trim().to_string())
+                .filter(|goal| !goal.is_empty()),
+            status: SPRINT_PLANNING.to_string(),
+            capacity_points: capacity_points as i32,
+            committed_points: 0,
+            completed_points: 0,
+            start_date,
+            end_date,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Refuse `action` unless the sprint is in one of the `allowed` statuses
+    pub fn ensure_status(&self, allowed: &[&str], action: &str) -> Result<(), AppError> {
+        if allowed.contains(&self.status.as_str()) {
+            return Ok(());
+        }
+        Err(AppError::Conflict(format!(
+            "Cannot {} a sprint that is {}",
+            action, self.status
+        )))
+    }
+}
+
+pub const SPRINT_PLANNING: &str = "planning";
+pub const SPRINT_ACTIVE: &str = "active";
+pub const SPRINT_REVIEW: &str = "review";
+pub const SPRINT_COMPLETED: &str = "completed";
+pub const SPRINT_CANCELLED: &str = "cancelled";
+
+/// Where a completing sprint sends its unfinished stories
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SprintRollover {
+    /// Carry them into the team's next sprint
+    #[default]
+    NextSprint,
+    /// Return stories nobody started to the product backlog; started ones
+    /// still carry into the next sprint
+    Backlog,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "sprintId")]
+pub enum RolloverTarget {
+    Sprint(Uuid),
+    Backlog,
+}
+
+/// A story in a sprint being completed or cancelled
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SprintStory {
+    pub id: Uuid,
+    pub status: String,
+    pub story_points: Option<i32>,
+}
+
+impl SprintStory {
+    pub fn is_accepted(&self) -> bool {
+        self.status == "accepted"
+    }
+
+    /// Whether anyone has begun work on the story
+    pub fn is_started(&self) -> bool {
+        !matches!(self.status.as_str(), "committed" | "ready")
+    }
+
+    pub fn points(&self) -> u32 {
+        self.story_points.unwrap_or(0).max(0) as u32
+    }
+
+    /// Where `rollover` sends the story when its sprint completes, or `None`
+    /// when it stays: accepted stories count towards the completed sprint, and
+    /// started stories with no next sprint are left for the team to move
+    pub fn rollover_target(
+        &self,
+        rollover: SprintRollover,
+        next_sprint: Option<Uuid>,
+    ) -> Option<RolloverTarget> {
+        match next_sprint {
+            _ if self.is_accepted() => None,
+            Some(next) if self.is_started() || rollover == SprintRollover::NextSprint => {
+                Some(RolloverTarget::Sprint(next))
+            }
+            _ if self.is_started() => None,
+            _ => Some(RolloverTarget::Backlog),
+        }
+    }
+}
+
+/// A story moved out of a sprint as it completed or was cancelled
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryMove {
+    pub story_id: Uuid,
+    pub story_points: Option<u32>,
+    pub target: RolloverTarget,
+}
+
+/// Plan how completing a sprint moves its stories
+pub fn plan_completion(
+    stories: &[SprintStory],
+    rollover: SprintRollover,
+    next_sprint: Option<Uuid>,
+) -> Vec<StoryMove> {
+    stories
+        .iter()
+        .filter_map(|story| {
+            story
+                .rollover_target(rollover, next_sprint)
+                .map(|target| StoryMove {
+                    story_id: story.id,
+                    story_points: story.story_points.map(|_| story.points()),
+                    target,
+                })
+        })
+        .collect()
+}
+
+/// Plan how cancelling a sprint moves its stories: every story not yet accepted
+/// returns to the product backlog
+pub fn plan_cancellation(stories: &[SprintStory]) -> Vec<StoryMove> {
+    stories
+        .iter()
+        .filter(|story| !story.is_accepted())
+        .map(|story| StoryMove {
+            story_id: story.id,
+            story_points: story.story_points.map(|_| story.points()),
+            target: RolloverTarget::Backlog,
+        })
+        .collect()
+}
+
+/// A completed or cancelled sprint and where its stories went
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintClosure {
+    pub sprint: Sprint,
+    pub next_sprint_id: Option<Uuid>,
+    pub moved_stories: Vec<StoryMove>,
+}
+
 /// Sprint metadata for task board response
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SprintMetadata {
     pub id: Uuid,
     pub name: String,
+    #[serde(alias = "start_date")]
     pub start_date: DateTime<Utc>,
+    #[serde(alias = "end_date")]
     pub end_date: DateTime<Utc>,
+    #[serde(alias = "days_remaining")]
     pub days_remaining: i64,
     pub status: String,
 }
 
-impl From<Sprint> for SprintMetadata {
-    fn from(sprint: Sprint) -> Self {
-        let now = Utc::now();
-        let days_remaining = (sprint.end_date - now).num_days();
+impl SprintMetadata {
+    /// Days remaining are counted in local calendar days of the project's time zone
+    pub fn new(sprint: Sprint, calendar: &SprintCalendar) -> Self {
+        let days_remaining = calendar.days_remaining(sprint.end_date, Utc::now());
 
         Self {
             id: sprint.id,
@@ -44,11 +242,32 @@ impl From<Sprint> for SprintMetadata {
 
 /// Sprint statistics for progress tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SprintStats {
+    #[serde(alias = "total_stories")]
     pub total_stories: usize,
+    #[serde(alias = "total_tasks")]
     pub total_tasks: usize,
+    #[serde(alias = "completed_tasks")]
     pub completed_tasks: usize,
+    #[serde(alias = "completion_percentage")]
     pub completion_percentage: f64,
+    /// The sprint's stories broken down by story type
+    #[serde(default, alias = "story_types")]
+    pub story_types: Vec<StoryTypeStats>,
+}
+
+/// Stories of one type in a sprint. `accepted_points` is the sprint's
+/// velocity for the type.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryTypeStats {
+    #[serde(alias = "story_type")]
+    pub story_type: String,
+    pub stories: i64,
+    pub points: i64,
+    #[serde(alias = "accepted_points")]
+    pub accepted_points: i64,
 }
 
 impl SprintStats {
@@ -64,28 +283,45 @@ impl SprintStats {
             total_tasks,
             completed_tasks,
             completion_percentage,
+            story_types: Vec::new(),
         }
     }
+
+    pub fn with_story_types(mut self, story_types: Vec<StoryTypeStats>) -> Self {
+        self.story_types = story_types;
+        self
+    }
 }
 
 /// Task with story information for sprint board
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TaskWithStory {
+    #[serde(alias = "task_id")]
     pub task_id: Uuid,
     pub title: String,
     pub status: String,
+    #[serde(alias = "owner_user_id")]
     pub owner_user_id: Option<Uuid>,
+    #[serde(alias = "owner_name")]
     pub owner_name: Option<String>,
+    #[serde(alias = "story_id")]
     pub story_id: Uuid,
+    #[serde(alias = "story_title")]
     pub story_title: String,
+    #[serde(alias = "acceptance_criteria_refs")]
     pub acceptance_criteria_refs: Vec<String>,
+    #[serde(alias = "estimated_hours")]
     pub estimated_hours: Option<i32>,
+    #[serde(alias = "created_at")]
     pub created_at: DateTime<Utc>,
+    #[serde(alias = "updated_at")]
     pub updated_at: DateTime<Utc>,
 }
 
 /// Grouped tasks by story or status
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GroupedTasks {
     pub groups: std::collections::HashMap<String, Vec<TaskWithStory>>,
     pub counts: std::collections::HashMap<String, usize>,
@@ -127,13 +363,136 @@ impl GroupedTasks {
 
 /// Sprint task board response
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SprintTaskBoardResponse {
     pub sprint: SprintMetadata,
     pub stats: SprintStats,
     pub tasks: Vec<TaskWithStory>,
+    #[serde(alias = "grouped_tasks")]
     pub grouped_tasks: Option<GroupedTasks>,
 }
 
+/// A structured sprint goal linked to the stories that deliver it
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintGoal {
+    pub id: Uuid,
+    #[serde(alias = "sprint_id")]
+    pub sprint_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub position: i32,
+    #[serde(alias = "story_ids")]
+    pub story_ids: Vec<Uuid>,
+}
+
+/// A story linked to a sprint goal, as stored in the backlog
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalStory {
+    #[serde(alias = "goal_id")]
+    pub goal_id: Uuid,
+    #[serde(alias = "story_id")]
+    pub story_id: Uuid,
+    pub title: String,
+    pub status: String,
+    #[serde(alias = "story_points")]
+    pub story_points: Option<i32>,
+}
+
+impl GoalStory {
+    /// Only accepted stories count towards a goal
+    pub fn is_done(&self) -> bool {
+        self.status == "accepted"
+    }
+}
+
+/// Progress of one goal computed from its linked stories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintGoalProgress {
+    pub goal: SprintGoal,
+    #[serde(alias = "total_stories")]
+    pub total_stories: usize,
+    #[serde(alias = "completed_stories")]
+    pub completed_stories: usize,
+    #[serde(alias = "total_points")]
+    pub total_points: u32,
+    #[serde(alias = "completed_points")]
+    pub completed_points: u32,
+    #[serde(alias = "completion_percentage")]
+    pub completion_percentage: f64,
+    /// A goal is achieved once it has linked stories and all of them are done
+    pub achieved: bool,
+}
+
+impl SprintGoalProgress {
+    pub fn new(goal: SprintGoal, stories: &[GoalStory]) -> Self {
+        let linked: Vec<&GoalStory> = stories
+            .iter()
+            .filter(|story| story.goal_id == goal.id)
+            .collect();
+        let points = |story: &&GoalStory| story.story_points.unwrap_or(0).max(0) as u32;
+
+        let total_stories = linked.len();
+        let completed_stories = linked.iter().filter(|story| story.is_done()).count();
+        let total_points = linked.iter().map(points).sum();
+        let completed_points = linked
+            .iter()
+            .filter(|story| story.is_done())
+            .map(points)
+            .sum();
+
+        // Weight by points when the linked stories are estimated, otherwise by count
+        let completion_percentage = if total_points > 0 {
+            f64::from(completed_points) / f64::from(total_points) * 100.0
+        } else if total_stories > 0 {
+            completed_stories as f64 / total_stories as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        Self {
+            goal,
+            total_stories,
+            completed_stories,
+            total_points,
+            completed_points,
+            completion_percentage,
+            achieved: total_stories > 0 && completed_stories == total_stories,
+        }
+    }
+}
+
+/// Goal completion for a sprint, for reviews and progress tracking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintGoalReport {
+    #[serde(alias = "sprint_id")]
+    pub sprint_id: Uuid,
+    pub goals: Vec<SprintGoalProgress>,
+    #[serde(alias = "achieved_goals")]
+    pub achieved_goals: usize,
+    #[serde(alias = "total_goals")]
+    pub total_goals: usize,
+}
+
+impl SprintGoalReport {
+    pub fn new(sprint_id: Uuid, goals: Vec<SprintGoal>, stories: &[GoalStory]) -> Self {
+        let goals: Vec<SprintGoalProgress> = goals
+            .into_iter()
+            .map(|goal| SprintGoalProgress::new(goal, stories))
+            .collect();
+
+        Self {
+            sprint_id,
+            achieved_goals: goals.iter().filter(|goal| goal.achieved).count(),
+            total_goals: goals.len(),
+            goals,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,16 +523,21 @@ mod tests {
         let sprint = Sprint {
             id: Uuid::new_v4(),
             project_id: Uuid::new_v4(),
+            team_id: Uuid::new_v4(),
+            organization_id: None,
             name: "Sprint 1".to_string(),
             goal: Some("Test goal".to_string()),
             status: "active".to_string(),
+            capacity_points: 20,
+            committed_points: 0,
+            completed_points: 0,
             start_date: Utc::now(),
             end_date: Utc::now() + chrono::Duration::days(7),
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
 
-        let metadata = SprintMetadata::from(sprint);
+        let metadata = SprintMetadata::new(sprint, &SprintCalendar::default());
         assert!(metadata.days_remaining >= 6 && metadata.days_remaining <= 7);
     }
 
@@ -228,4 +592,149 @@ mod tests {
         assert_eq!(grouped.groups.get("inprogress").unwrap().len(), 1);
         assert_eq!(grouped.groups.get("completed").unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_completion_rolls_unfinished_stories_over() {
+        let story = |status: &str, points: Option<i32>| SprintStory {
+            id: Uuid::new_v4(),
+            status: status.to_string(),
+            story_points: points,
+        };
+        let accepted = story("accepted", Some(3));
+        let started = story("inprogress", Some(5));
+        let unstarted = story("committed", None);
+        let stories = vec![accepted, started.clone(), unstarted.clone()];
+        let next = Uuid::new_v4();
+
+        let moves = plan_completion(&stories, SprintRollover::NextSprint, Some(next));
+        assert_eq!(moves.len(), 2);
+        assert!(moves
+            .iter()
+            .all(|story_move| story_move.target == RolloverTarget::Sprint(next)));
+
+        let moves = plan_completion(&stories, SprintRollover::Backlog, Some(next));
+        assert_eq!(moves[0].story_id, started.id);
+        assert_eq!(moves[0].target, RolloverTarget::Sprint(next));
+        assert_eq!(moves[1].story_id, unstarted.id);
+        assert_eq!(moves[1].target, RolloverTarget::Backlog);
+
+        // Without a next sprint, started stories stay where they are
+        let moves = plan_completion(&stories, SprintRollover::NextSprint, None);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].target, RolloverTarget::Backlog);
+
+        assert_eq!(plan_cancellation(&stories).len(), 2);
+    }
+
+    #[test]
+    fn test_new_sprint_validation() {
+        let start = Utc::now();
+        let sprint = |capacity: u32, days: i64| {
+            Sprint::new(
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                None,
+                " Sprint 4 ".to_string(),
+                Some("  ".to_string()),
+                capacity,
+                start,
+                start + chrono::Duration::days(days),
+            )
+        };
+
+        let created = sprint(30, 14).unwrap();
+        assert_eq!(created.name, "Sprint 4");
+        assert_eq!(created.goal, None);
+        assert_eq!(created.status, SPRINT_PLANNING);
+        assert!(sprint(0, 14).is_err());
+        assert!(sprint(30, 0).is_err());
+        assert!(sprint(30, 29).is_err());
+        assert!(created.ensure_status(&[SPRINT_ACTIVE], "complete").is_err());
+    }
+
+    #[test]
+    fn test_goal_report_computes_progress_from_linked_stories() {
+        let sprint_id = Uuid::new_v4();
+        let goal = |title: &str| SprintGoal {
+            id: Uuid::new_v4(),
+            sprint_id,
+            title: title.to_string(),
+            description: None,
+            position: 0,
+            story_ids: Vec::new(),
+        };
+        let checkout = goal("Ship checkout");
+        let search = goal("Faster search");
+        let empty = goal("Unlinked");
+        let story = |goal_id: Uuid, status: &str, points: Option<i32>| GoalStory {
+            goal_id,
+            story_id: Uuid::new_v4(),
+            title: "Story".to_string(),
+            status: status.to_string(),
+            story_points: points,
+        };
+        let stories = vec![
+            story(checkout.id, "accepted", Some(3)),
+            story(checkout.id, "inprogress", Some(5)),
+            story(search.id, "accepted", None),
+        ];
+
+        let report = SprintGoalReport::new(sprint_id, vec![checkout, search, empty], &stories);
+
+        assert_eq!(report.total_goals, 3);
+        assert_eq!(report.achieved_goals, 1);
+        assert_eq!(report.goals[0].completed_points, 3);
+        assert_eq!(report.goals[0].completion_percentage, 37.5);
+        assert!(!report.goals[0].achieved);
+        assert_eq!(report.goals[1].completion_percentage, 100.0);
+        assert!(report.goals[1].achieved);
+        assert!(!report.goals[2].achieved);
+    }
+
+    #[test]
+    fn test_board_and_closure_use_camel_case_keys() {
+        let story_id = Uuid::new_v4();
+        let board = SprintTaskBoardResponse {
+            sprint: SprintMetadata {
+                id: Uuid::new_v4(),
+                name: "Sprint 1".to_string(),
+                start_date: Utc::now(),
+                end_date: Utc::now(),
+                days_remaining: 3,
+                status: SPRINT_ACTIVE.to_string(),
+            },
+            stats: SprintStats::new(1, 1, 0),
+            tasks: vec![create_test_task(
+                Uuid::new_v4(),
+                story_id,
+                "Story 1",
+                "available",
+            )],
+            grouped_tasks: None,
+        };
+        let json = serde_json::to_value(&board).unwrap();
+        assert_eq!(json["sprint"]["daysRemaining"], 3);
+        assert_eq!(json["stats"]["totalStories"], 1);
+        assert_eq!(json["tasks"][0]["storyTitle"], "Story 1");
+        assert!(json.get("groupedTasks").is_some());
+
+        let story_move = StoryMove {
+            story_id,
+            story_points: Some(3),
+            target: RolloverTarget::Sprint(story_id),
+        };
+        let json = serde_json::to_value(&story_move).unwrap();
+        assert_eq!(json["storyId"], story_id.to_string());
+        assert_eq!(json["target"]["sprintId"], story_id.to_string());
+
+        // Snake_case keys are still accepted
+        let stats: SprintStats = serde_json::from_value(serde_json::json!({
+            "total_stories": 2,
+            "total_tasks": 4,
+            "completed_tasks": 1,
+            "completion_percentage": 25.0
+        }))
+        .unwrap();
+        assert_eq!(stats.total_tasks, 4);
+    }
 }
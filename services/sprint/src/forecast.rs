@@ -0,0 +1,96 @@
+//! Monte Carlo throughput forecasts.
+//!
+//! A forecast replays history: each trial draws one past week at random for
+//! every week ahead and adds up the stories finished in them. Across many
+//! trials, the count reached in `confidence`% of them is what the team can
+//! expect to finish with that confidence.
+
+use rand::Rng;
+use serde::Serialize;
+
+/// Trials run for one forecast
+pub const FORECAST_TRIALS: usize = 10_000;
+/// Confidence levels every forecast reports, in percent
+pub const CONFIDENCE_LEVELS: [u8; 3] = [50, 85, 95];
+
+/// At least `stories` finished with `confidence`% probability
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastOutlook {
+    pub confidence: u8,
+    pub stories: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThroughputForecast {
+    /// Weeks ahead the forecast covers
+    pub weeks: u32,
+    pub trials: usize,
+    /// Most confident last
+    pub outlooks: Vec<ForecastOutlook>,
+}
+
+/// Forecast the stories finished over the next `weeks` from past weekly
+/// counts. `None` without any history to sample.
+pub fn forecast_throughput<R: Rng>(
+    weekly_stories: &[u32],
+    weeks: u32,
+    trials: usize,
+    rng: &mut R,
+) -> Option<ThroughputForecast> {
+    if weekly_stories.is_empty() || trials == 0 {
+        return None;
+    }
+    let mut totals: Vec<u32> = (0..trials)
+        .map(|_| {
+            (0..weeks)
+                .map(|_| weekly_stories[rng.gen_range(0..weekly_stories.len())])
+                .sum()
+        })
+        .collect();
+    totals.sort_unstable();
+
+    // Reached in `confidence`% of trials: the total that many trials beat or match
+    let outlooks = CONFIDENCE_LEVELS
+        .iter()
+        .map(|&confidence| {
+            let beaten_by = trials * usize::from(confidence) / 100;
+            ForecastOutlook {
+                confidence,
+                stories: totals[trials - beaten_by.max(1)],
+            }
+        })
+        .collect();
+
+    Some(ThroughputForecast {
+        weeks,
+        trials,
+        outlooks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_forecast_is_less_ambitious_the_more_confident_it_is() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let forecast =
+            forecast_throughput(&[2, 4, 6, 8, 10], 2, FORECAST_TRIALS, &mut rng).unwrap();
+
+        let stories: Vec<u32> = forecast.outlooks.iter().map(|o| o.stories).collect();
+        assert_eq!(forecast.outlooks.len(), CONFIDENCE_LEVELS.len());
+        assert!(stories.windows(2).all(|pair| pair[0] >= pair[1]));
+        // Two weeks of 2 to 10 stories each
+        assert!(stories.iter().all(|&s| (4..=20).contains(&s)));
+        assert!((10..=14).contains(&stories[0]));
+
+        let steady = forecast_throughput(&[3, 3, 3], 4, 100, &mut rng).unwrap();
+        assert!(steady.outlooks.iter().all(|o| o.stories == 12));
+        assert!(forecast_throughput(&[], 2, 100, &mut rng).is_none());
+    }
+}
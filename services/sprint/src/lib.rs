@@ -1,25 +1,49 @@
 pub mod adapters;
 pub mod domain;
+pub mod forecast;
+pub mod reporting;
+pub mod snapshot;
 
+pub use adapters::http::routes::create_sprint_router;
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use common::reason_policy::{DestructiveAction, ReasonPolicy};
 use common::AppError;
-use domain::{GroupedTasks, Sprint, SprintMetadata, SprintStats, SprintTaskBoardResponse};
+use domain::{
+    plan_cancellation, plan_completion, GroupedTasks, RolloverTarget, Sprint, SprintClosure,
+    SprintGoal, SprintGoalReport, SprintMetadata, SprintRollover, SprintStats,
+    SprintTaskBoardResponse, StoryMove, SPRINT_ACTIVE, SPRINT_CANCELLED, SPRINT_COMPLETED,
+    SPRINT_PLANNING, SPRINT_REVIEW,
+};
+use event_bus::{DomainEvent, EventPublisher, SprintEvent, SprintRecord, SprintScopeChange};
+use reporting::{
+    ProjectVelocity, SprintBurndown, TeamThroughput, DEFAULT_FORECAST_WEEKS,
+    DEFAULT_THROUGHPUT_WEEKS, DEFAULT_VELOCITY_SPRINTS, MAX_FORECAST_WEEKS, MAX_THROUGHPUT_WEEKS,
+    MAX_VELOCITY_SPRINTS,
+};
+use snapshot::SprintSnapshot;
 use sqlx::PgPool;
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct SprintsUsecases {
     pool: Arc<PgPool>,
+    events: Arc<dyn EventPublisher>,
 }
 
 impl SprintsUsecases {
-    pub fn new(pool: Arc<PgPool>) -> Self {
-        Self { pool }
+    pub fn new(pool: Arc<PgPool>, events: Arc<dyn EventPublisher>) -> Self {
+        Self { pool, events }
     }
 
     pub async fn get_active_sprint(&self, project_id: Uuid) -> Result<Option<Sprint>, AppError> {
         adapters::persistence::repo::get_active_sprint(&self.pool, project_id).await
     }
 
+    pub async fn get_sprint(&self, sprint_id: Uuid) -> Result<Option<Sprint>, AppError> {
+        adapters::persistence::repo::get_sprint_by_id(&self.pool, sprint_id).await
+    }
+
     /// Get sprint task board with all tasks from stories in the sprint
     pub async fn get_sprint_task_board(
         &self,
@@ -32,6 +56,9 @@ impl SprintsUsecases {
         let sprint = adapters::persistence::repo::get_sprint_by_id(&self.pool, sprint_id)
             .await?
             .ok_or_else(|| AppError::NotFound("Sprint not found".to_string()))?;
+        let calendar =
+            adapters::persistence::repo::get_project_calendar(&self.pool, sprint.project_id)
+                .await?;
 
         // Fetch tasks with filters
         let tasks = adapters::persistence::repo::get_sprint_tasks(
@@ -48,7 +75,11 @@ impl SprintsUsecases {
         let total_tasks = tasks.len();
         let completed_tasks = tasks.iter().filter(|t| t.status == "completed").count();
 
-        let stats = SprintStats::new(total_stories, total_tasks, completed_tasks);
+        let story_types =
+            adapters::persistence::repo::get_sprint_story_type_stats(&self.pool, sprint_id).await?;
+
+        let stats = SprintStats::new(total_stories, total_tasks, completed_tasks)
+            .with_story_types(story_types);
 
         // Group tasks if requested
         let grouped_tasks = group_by.as_ref().and_then(|group| match group.as_str() {
@@ -58,10 +89,579 @@ impl SprintsUsecases {
         });
 
         Ok(SprintTaskBoardResponse {
-            sprint: SprintMetadata::from(sprint),
+            sprint: SprintMetadata::new(sprint, &calendar),
             stats,
             tasks,
             grouped_tasks,
         })
     }
+
+    /// Goals for the sprint with progress computed from their linked stories
+    pub async fn get_sprint_goal_report(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<SprintGoalReport, AppError> {
+        self.organization_sprint(sprint_id, organization_id).await?;
+        self.goal_report(sprint_id, organization_id).await
+    }
+
+    async fn goal_report(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<SprintGoalReport, AppError> {
+        let goals =
+            adapters::persistence::repo::get_sprint_goals(&self.pool, sprint_id, organization_id)
+                .await?;
+        let stories =
+            adapters::persistence::repo::get_goal_stories(&self.pool, sprint_id, organization_id)
+                .await?;
+
+        Ok(SprintGoalReport::new(sprint_id, goals, &stories))
+    }
+
+    /// Remaining points and tasks at the end of each day of the sprint
+    pub async fn get_sprint_burndown(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<SprintBurndown, AppError> {
+        let sprint = self.organization_sprint(sprint_id, organization_id).await?;
+        let calendar =
+            adapters::persistence::repo::get_project_calendar(&self.pool, sprint.project_id)
+                .await?;
+        let items =
+            adapters::persistence::reporting::get_burndown_items(&self.pool, sprint.id).await?;
+        let goals = self.goal_report(sprint.id, organization_id).await?;
+
+        Ok(SprintBurndown::new(
+            &sprint,
+            &items,
+            &calendar,
+            goals,
+            Utc::now(),
+        ))
+    }
+
+    /// The sprint board as it is now, for offline use
+    pub async fn get_sprint_snapshot(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<SprintSnapshot, AppError> {
+        let sprint = self.organization_sprint(sprint_id, organization_id).await?;
+        let stories =
+            adapters::persistence::repo::get_snapshot_stories(&self.pool, sprint.id).await?;
+        let tasks =
+            adapters::persistence::repo::get_sprint_tasks(&self.pool, sprint.id, None, None)
+                .await?;
+        let story_types =
+            adapters::persistence::repo::get_sprint_story_type_stats(&self.pool, sprint.id).await?;
+        let completed_tasks = tasks.iter().filter(|t| t.status == "completed").count();
+        let stats = SprintStats::new(stories.len(), tasks.len(), completed_tasks)
+            .with_story_types(story_types);
+
+        let goals = self.goal_report(sprint.id, organization_id).await?;
+
+        Ok(SprintSnapshot::new(
+            sprint,
+            stats,
+            goals,
+            stories,
+            tasks,
+            Utc::now(),
+        ))
+    }
+
+    /// Completed points over the project's last `sprints` completed sprints
+    pub async fn get_project_velocity(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        sprints: Option<u32>,
+    ) -> Result<ProjectVelocity, AppError> {
+        let sprints = sprints.unwrap_or(DEFAULT_VELOCITY_SPRINTS);
+        if !(1..=MAX_VELOCITY_SPRINTS).contains(&sprints) {
+            return Err(AppError::BadRequest(format!(
+                "sprints must be between 1 and {}",
+                MAX_VELOCITY_SPRINTS
+            )));
+        }
+        adapters::persistence::repo::get_project_team(&self.pool, project_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+        let velocities = adapters::persistence::reporting::get_completed_sprint_velocities(
+            &self.pool, project_id, sprints,
+        )
+        .await?;
+
+        Ok(ProjectVelocity::new(project_id, velocities))
+    }
+
+    /// Stories the team accepted in each of the last `weeks` weeks, with a
+    /// forecast of how many it finishes in the next `forecast_weeks`
+    pub async fn get_team_throughput(
+        &self,
+        team_id: Uuid,
+        organization_id: Option<Uuid>,
+        weeks: Option<u32>,
+        forecast_weeks: Option<u32>,
+    ) -> Result<TeamThroughput, AppError> {
+        let weeks = weeks.unwrap_or(DEFAULT_THROUGHPUT_WEEKS);
+        if !(1..=MAX_THROUGHPUT_WEEKS).contains(&weeks) {
+            return Err(AppError::BadRequest(format!(
+                "weeks must be between 1 and {}",
+                MAX_THROUGHPUT_WEEKS
+            )));
+        }
+        let forecast_weeks = forecast_weeks.unwrap_or(DEFAULT_FORECAST_WEEKS);
+        if !(1..=MAX_FORECAST_WEEKS).contains(&forecast_weeks) {
+            return Err(AppError::BadRequest(format!(
+                "forecastWeeks must be between 1 and {}",
+                MAX_FORECAST_WEEKS
+            )));
+        }
+        if !adapters::persistence::reporting::team_in_organization(
+            &self.pool,
+            team_id,
+            organization_id,
+        )
+        .await?
+        {
+            return Err(AppError::NotFound("Team not found".to_string()));
+        }
+
+        let now = Utc::now();
+        let since = (reporting::week_start(now) - Duration::weeks(i64::from(weeks)))
+            .and_time(NaiveTime::MIN)
+            .and_utc();
+        let accepted_at =
+            adapters::persistence::reporting::get_team_acceptance_times(&self.pool, team_id, since)
+                .await?;
+
+        Ok(TeamThroughput::new(
+            team_id,
+            &accepted_at,
+            weeks,
+            forecast_weeks,
+            now,
+            &mut rand::thread_rng(),
+        ))
+    }
+
+    pub async fn create_sprint_goal(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+        title: String,
+        description: Option<String>,
+        story_ids: Vec<Uuid>,
+    ) -> Result<SprintGoal, AppError> {
+        if title.trim().is_empty() {
+            return Err(AppError::BadRequest(
+                "Sprint goal title cannot be empty".to_string(),
+            ));
+        }
+        self.organization_sprint(sprint_id, organization_id).await?;
+        let story_ids = self
+            .validate_goal_stories(sprint_id, organization_id, story_ids)
+            .await?;
+
+        let goal = SprintGoal {
+            id: Uuid::new_v4(),
+            sprint_id,
+            title: title.trim().to_string(),
+            description: description.filter(|text| !text.trim().is_empty()),
+            position: 0,
+            story_ids,
+        };
+        let created =
+            adapters::persistence::repo::create_sprint_goal(&self.pool, &goal, organization_id)
+                .await?;
+        if !created {
+            return Err(AppError::NotFound("Sprint not found".to_string()));
+        }
+
+        Ok(goal)
+    }
+
+    /// Replace the stories linked to a goal
+    pub async fn link_goal_stories(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+        goal_id: Uuid,
+        story_ids: Vec<Uuid>,
+    ) -> Result<(), AppError> {
+        let story_ids = self
+            .validate_goal_stories(sprint_id, organization_id, story_ids)
+            .await?;
+
+        let updated = adapters::persistence::repo::replace_goal_stories(
+            &self.pool,
+            sprint_id,
+            organization_id,
+            goal_id,
+            &story_ids,
+        )
+        .await?;
+        if !updated {
+            return Err(AppError::NotFound("Sprint goal not found".to_string()));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_sprint_goal(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+        goal_id: Uuid,
+    ) -> Result<(), AppError> {
+        let deleted = adapters::persistence::repo::delete_sprint_goal(
+            &self.pool,
+            sprint_id,
+            organization_id,
+            goal_id,
+        )
+        .await?;
+        if !deleted {
+            return Err(AppError::NotFound("Sprint goal not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Plan a new sprint for the project's team
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_sprint(
+        &self,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+        name: String,
+        goal: Option<String>,
+        capacity_points: u32,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> Result<Sprint, AppError> {
+        let (team_id, project_org_id) =
+            adapters::persistence::repo::get_project_team(&self.pool, project_id, organization_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+        let team_id = team_id.ok_or_else(|| {
+            AppError::BadRequest(
+                "Project is missing a team assignment; assign a team before creating sprints."
+                    .to_string(),
+            )
+        })?;
+
+        let sprint = Sprint::new(
+            project_id,
+            team_id,
+            project_org_id,
+            name,
+            goal,
+            capacity_points,
+            start_date,
+            end_date,
+        )?;
+        adapters::persistence::repo::create_sprint(&self.pool, &sprint).await?;
+
+        self.publish(DomainEvent::Sprint(SprintEvent::Created {
+            sprint: Self::sprint_record(&sprint),
+        }))
+        .await;
+        Ok(sprint)
+    }
+
+    /// Start a planned sprint, making it its team's active sprint. A team runs
+    /// one sprint at a time.
+    pub async fn start_sprint(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Sprint, AppError> {
+        let sprint = self.organization_sprint(sprint_id, organization_id).await?;
+        sprint.ensure_status(&[SPRINT_PLANNING], "start")?;
+        if let Some(active_id) = adapters::persistence::repo::get_team_active_sprint_id(
+            &self.pool,
+            sprint.team_id,
+            sprint.id,
+        )
+        .await?
+        {
+            return Err(AppError::Conflict(format!(
+                "The team already has an active sprint ({}); complete or cancel it first",
+                active_id
+            )));
+        }
+
+        if !adapters::persistence::repo::start_sprint(&self.pool, &sprint).await? {
+            return Err(AppError::Conflict(
+                "Sprint is no longer in planning".to_string(),
+            ));
+        }
+        self.publish_sprint_updated(sprint_id, organization_id)
+            .await
+    }
+
+    /// Complete an active sprint. Accepted stories count as its completed
+    /// points; unfinished ones move as `rollover` says, into `next_sprint_id`
+    /// or else the team's next planned sprint.
+    pub async fn complete_sprint(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+        rollover: SprintRollover,
+        next_sprint_id: Option<Uuid>,
+    ) -> Result<SprintClosure, AppError> {
+        let sprint = self.organization_sprint(sprint_id, organization_id).await?;
+        sprint.ensure_status(&[SPRINT_ACTIVE, SPRINT_REVIEW], "complete")?;
+
+        let next = match next_sprint_id {
+            Some(next_id) => {
+                let next = self.organization_sprint(next_id, organization_id).await?;
+                if next.id == sprint.id || next.team_id != sprint.team_id {
+                    return Err(AppError::BadRequest(
+                        "Stories can only roll over into another sprint of the same team"
+                            .to_string(),
+                    ));
+                }
+                next.ensure_status(&[SPRINT_PLANNING], "roll stories into")?;
+                Some(next)
+            }
+            None => {
+                adapters::persistence::repo::get_next_planned_sprint(
+                    &self.pool,
+                    sprint.team_id,
+                    sprint.id,
+                )
+                .await?
+            }
+        };
+
+        let stories =
+            adapters::persistence::repo::get_sprint_stories(&self.pool, sprint.id).await?;
+        let completed_points: u32 = stories
+            .iter()
+            .filter(|story| story.is_accepted())
+            .map(|story| story.points())
+            .sum();
+        let moves = plan_completion(&stories, rollover, next.as_ref().map(|next| next.id));
+
+        self.close_sprint(
+            sprint,
+            &[SPRINT_ACTIVE, SPRINT_REVIEW],
+            SPRINT_COMPLETED,
+            completed_points,
+            moves,
+            next,
+        )
+        .await
+    }
+
+    /// Cancel a sprint that has not completed. Every story not yet accepted
+    /// returns to the product backlog.
+    pub async fn cancel_sprint(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+        cancelled_by: Option<Uuid>,
+        reason: Option<&str>,
+    ) -> Result<SprintClosure, AppError> {
+        let policy = match organization_id {
+            Some(organization_id) => {
+                adapters::persistence::repo::get_reason_policy(&self.pool, organization_id).await?
+            }
+            None => ReasonPolicy::default(),
+        };
+        let reason = policy.check(DestructiveAction::CancelSprint, reason)?;
+        let sprint = self.organization_sprint(sprint_id, organization_id).await?;
+        let open = [SPRINT_PLANNING, SPRINT_ACTIVE, SPRINT_REVIEW];
+        sprint.ensure_status(&open, "cancel")?;
+
+        let stories =
+            adapters::persistence::repo::get_sprint_stories(&self.pool, sprint.id).await?;
+        let completed_points: u32 = stories
+            .iter()
+            .filter(|story| story.is_accepted())
+            .map(|story| story.points())
+            .sum();
+        let moves = plan_cancellation(&stories);
+
+        let closure = self
+            .close_sprint(
+                sprint,
+                &open,
+                SPRINT_CANCELLED,
+                completed_points,
+                moves,
+                None,
+            )
+            .await?;
+        adapters::persistence::repo::record_destructive_action(
+            &self.pool,
+            organization_id,
+            cancelled_by,
+            DestructiveAction::CancelSprint,
+            sprint_id,
+            reason.as_deref(),
+        )
+        .await?;
+        Ok(closure)
+    }
+
+    /// The internal id of the user with this identity provider subject
+    pub async fn get_user_id(&self, external_id: &str) -> Result<Option<Uuid>, AppError> {
+        adapters::persistence::repo::get_user_id(&self.pool, external_id).await
+    }
+
+    async fn close_sprint(
+        &self,
+        sprint: Sprint,
+        from: &[&str],
+        status: &str,
+        completed_points: u32,
+        moves: Vec<StoryMove>,
+        next: Option<Sprint>,
+    ) -> Result<SprintClosure, AppError> {
+        let closed = adapters::persistence::repo::close_sprint(
+            &self.pool,
+            &sprint,
+            from,
+            status,
+            completed_points,
+            &moves,
+            next.as_ref(),
+        )
+        .await?;
+        if !closed {
+            return Err(AppError::Conflict(format!(
+                "Sprint is no longer {}",
+                from.join(" or ")
+            )));
+        }
+        tracing::info!(
+            sprint_id = %sprint.id,
+            team_id = %sprint.team_id,
+            status,
+            moved_stories = moves.len(),
+            "Closed sprint"
+        );
+
+        let now = Utc::now();
+        for story_move in &moves {
+            let change = |sprint: &Sprint, sprint_status: &str| SprintScopeChange {
+                sprint_id: sprint.id,
+                story_id: story_move.story_id,
+                organization_id: sprint.organization_id,
+                story_points: story_move.story_points,
+                sprint_status: sprint_status.to_string(),
+                changed_at: now,
+            };
+            self.publish(DomainEvent::Sprint(SprintEvent::StoryRemoved {
+                change: change(&sprint, status),
+            }))
+            .await;
+            if let (RolloverTarget::Sprint(_), Some(next)) = (story_move.target, &next) {
+                self.publish(DomainEvent::Sprint(SprintEvent::StoryAdded {
+                    change: change(next, &next.status),
+                }))
+                .await;
+            }
+        }
+
+        let rolled_into_next = moves
+            .iter()
+            .any(|story_move| matches!(story_move.target, RolloverTarget::Sprint(_)));
+        let next_sprint_id = next.filter(|_| rolled_into_next).map(|next| next.id);
+        if let Some(next_id) = next_sprint_id {
+            self.publish_sprint_updated(next_id, sprint.organization_id)
+                .await?;
+        }
+        let sprint = self
+            .publish_sprint_updated(sprint.id, sprint.organization_id)
+            .await?;
+
+        Ok(SprintClosure {
+            sprint,
+            next_sprint_id,
+            moved_stories: moves,
+        })
+    }
+
+    /// Publish the sprint as it now stands, and return it
+    async fn publish_sprint_updated(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Sprint, AppError> {
+        let sprint = self.organization_sprint(sprint_id, organization_id).await?;
+        self.publish(DomainEvent::Sprint(SprintEvent::Updated {
+            sprint: Self::sprint_record(&sprint),
+        }))
+        .await;
+        Ok(sprint)
+    }
+
+    async fn organization_sprint(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Sprint, AppError> {
+        adapters::persistence::repo::get_organization_sprint(&self.pool, sprint_id, organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Sprint not found".to_string()))
+    }
+
+    async fn publish(&self, event: DomainEvent) {
+        self.events.publish(event).await;
+    }
+
+    fn sprint_record(sprint: &Sprint) -> SprintRecord {
+        SprintRecord {
+            id: sprint.id,
+            team_id: sprint.team_id,
+            organization_id: sprint.organization_id,
+            name: sprint.name.clone(),
+            goal: sprint.goal.clone().filter(|goal| !goal.is_empty()),
+            capacity_points: Some(sprint.capacity_points.max(0) as u32),
+            status: sprint.status.clone(),
+            start_date: Some(sprint.start_date),
+            end_date: Some(sprint.end_date),
+            committed_points: Some(sprint.committed_points.max(0) as u32),
+            completed_points: Some(sprint.completed_points.max(0) as u32),
+            created_at: sprint.created_at,
+            updated_at: sprint.updated_at,
+        }
+    }
+
+    /// Goals may only link the organization's stories that are planned into
+    /// the same sprint
+    async fn validate_goal_stories(
+        &self,
+        sprint_id: Uuid,
+        organization_id: Option<Uuid>,
+        mut story_ids: Vec<Uuid>,
+    ) -> Result<Vec<Uuid>, AppError> {
+        story_ids.sort();
+        story_ids.dedup();
+        if story_ids.is_empty() {
+            return Ok(story_ids);
+        }
+
+        let outside = adapters::persistence::repo::count_stories_outside_sprint(
+            &self.pool,
+            sprint_id,
+            organization_id,
+            &story_ids,
+        )
+        .await?;
+        if outside > 0 {
+            return Err(AppError::BadRequest(format!(
+                "Only stories in this sprint can be linked to its goals ({} outside)",
+                outside
+            )));
+        }
+        Ok(story_ids)
+    }
 }
@@ -0,0 +1,469 @@
+//! Sprint reports: the burndown of one sprint, day by day, the velocity of
+//! a project over its recently completed sprints, and the weekly throughput
+//! of a team. All are computed on read from stories, tasks and sprints;
+//! nothing is stored.
+
+use crate::domain::{Sprint, SprintGoalReport};
+use crate::forecast::{forecast_throughput, ThroughputForecast, FORECAST_TRIALS};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use common::calendar::SprintCalendar;
+use rand::Rng;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Completed sprints a velocity report covers unless asked otherwise
+pub const DEFAULT_VELOCITY_SPRINTS: u32 = 6;
+/// Most completed sprints a velocity report covers
+pub const MAX_VELOCITY_SPRINTS: u32 = 26;
+/// Past weeks a throughput report covers unless asked otherwise
+pub const DEFAULT_THROUGHPUT_WEEKS: u32 = 12;
+pub const MAX_THROUGHPUT_WEEKS: u32 = 52;
+/// Weeks ahead a throughput forecast covers unless asked otherwise
+pub const DEFAULT_FORECAST_WEEKS: u32 = 2;
+pub const MAX_FORECAST_WEEKS: u32 = 12;
+
+/// A story in the sprint with one of its tasks, or without a task when it has none
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct BurndownItem {
+    pub story_id: Uuid,
+    pub story_points: Option<i32>,
+    pub task_id: Option<Uuid>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// What was left at the end of one local day of the sprint
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BurndownDay {
+    pub date: NaiveDate,
+    /// `None` for days that have not started yet; today's are as of now
+    pub remaining_points: Option<u32>,
+    pub remaining_tasks: Option<u32>,
+    /// Points that would be left if the sprint burned down evenly
+    pub ideal_points: f64,
+}
+
+#[derive(Debug, Default)]
+struct StoryProgress {
+    points: u32,
+    tasks: usize,
+    open_tasks: usize,
+    last_completed: Option<DateTime<Utc>>,
+}
+
+impl StoryProgress {
+    fn done_at(&self) -> Option<DateTime<Utc>> {
+        (self.tasks > 0 && self.open_tasks == 0)
+            .then_some(self.last_completed)
+            .flatten()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintBurndown {
+    pub sprint_id: Uuid,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub total_points: u32,
+    pub total_tasks: u32,
+    pub days: Vec<BurndownDay>,
+    /// How far the sprint's goals are, as of now
+    pub goals: SprintGoalReport,
+}
+
+impl SprintBurndown {
+    /// Days are local calendar days of the project's time zone. A task burns
+    /// down on the day it was completed; a story's points burn down on the
+    /// day its last task was completed, so stories without tasks never do.
+    pub fn new(
+        sprint: &Sprint,
+        items: &[BurndownItem],
+        calendar: &SprintCalendar,
+        goals: SprintGoalReport,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut stories: HashMap<Uuid, StoryProgress> = HashMap::new();
+        let mut task_completions = Vec::new();
+        for item in items {
+            let story = stories.entry(item.story_id).or_insert(StoryProgress {
+                points: item.story_points.unwrap_or(0).max(0) as u32,
+                ..StoryProgress::default()
+            });
+            if item.task_id.is_none() {
+                continue;
+            }
+            task_completions.push(item.completed_at);
+            story.tasks += 1;
+            match item.completed_at {
+                Some(at) => story.last_completed = story.last_completed.max(Some(at)),
+                None => story.open_tasks += 1,
+            }
+        }
+        let story_done_at: Vec<(u32, Option<DateTime<Utc>>)> = stories
+            .into_values()
+            .map(|story| (story.points, story.done_at()))
+            .collect();
+
+        let total_points: u32 = story_done_at.iter().map(|(points, _)| points).sum();
+        let total_tasks = task_completions.len() as u32;
+        let buckets = calendar.daily_buckets(sprint.start_date, sprint.end_date);
+        let last_index = buckets.len().saturating_sub(1).max(1) as f64;
+
+        let days = buckets
+            .iter()
+            .enumerate()
+            .map(|(index, bucket)| {
+                let started = bucket.starts_at <= now;
+                let open_by = |done_at: &Option<DateTime<Utc>>| match done_at {
+                    Some(at) => *at >= bucket.ends_at,
+                    None => true,
+                };
+                BurndownDay {
+                    date: bucket.date,
+                    remaining_points: started.then(|| {
+                        story_done_at
+                            .iter()
+                            .filter(|(_, done_at)| open_by(done_at))
+                            .map(|(points, _)| points)
+                            .sum()
+                    }),
+                    remaining_tasks: started.then(|| {
+                        task_completions
+                            .iter()
+                            .filter(|done_at| open_by(done_at))
+                            .count() as u32
+                    }),
+                    ideal_points: f64::from(total_points) * (1.0 - index as f64 / last_index),
+                }
+            })
+            .collect();
+
+        Self {
+            sprint_id: sprint.id,
+            start_date: sprint.start_date,
+            end_date: sprint.end_date,
+            total_points,
+            total_tasks,
+            days,
+            goals,
+        }
+    }
+}
+
+/// Points a completed sprint committed to and delivered
+#[derive(Debug, Clone, PartialEq, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintVelocity {
+    pub sprint_id: Uuid,
+    pub name: String,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub committed_points: i32,
+    pub completed_points: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectVelocity {
+    pub project_id: Uuid,
+    /// Oldest first
+    pub sprints: Vec<SprintVelocity>,
+    pub average_points: f64,
+    pub min_points: i32,
+    pub max_points: i32,
+}
+
+impl ProjectVelocity {
+    /// `sprints` may come in any order
+    pub fn new(project_id: Uuid, mut sprints: Vec<SprintVelocity>) -> Self {
+        sprints.sort_by_key(|sprint| (sprint.end_date, sprint.sprint_id));
+        let completed = sprints.iter().map(|sprint| sprint.completed_points);
+        let average_points = if sprints.is_empty() {
+            0.0
+        } else {
+            completed.clone().map(f64::from).sum::<f64>() / sprints.len() as f64
+        };
+
+        Self {
+            project_id,
+            average_points,
+            min_points: completed.clone().min().unwrap_or(0),
+            max_points: completed.max().unwrap_or(0),
+            sprints,
+        }
+    }
+}
+
+/// Stories finished in the week starting on Monday `week_start`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyThroughput {
+    pub week_start: NaiveDate,
+    pub stories: u32,
+}
+
+/// How many weeks finished `stories` stories
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThroughputBucket {
+    pub stories: u32,
+    pub weeks: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamThroughput {
+    pub team_id: Uuid,
+    /// Oldest first
+    pub weeks: Vec<WeeklyThroughput>,
+    /// Fewest stories first
+    pub histogram: Vec<ThroughputBucket>,
+    pub average_stories: f64,
+    /// `None` while there is no finished week to learn from
+    pub forecast: Option<ThroughputForecast>,
+}
+
+/// Monday of the UTC week `at` falls in
+pub fn week_start(at: DateTime<Utc>) -> NaiveDate {
+    let date = at.date_naive();
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+impl TeamThroughput {
+    /// Counts the stories accepted in each of the `weeks` UTC weeks before the
+    /// current one, which is left out because it is not over yet, and
+    /// forecasts the next `forecast_weeks` from them.
+    pub fn new<R: Rng>(
+        team_id: Uuid,
+        accepted_at: &[DateTime<Utc>],
+        weeks: u32,
+        forecast_weeks: u32,
+        now: DateTime<Utc>,
+        rng: &mut R,
+    ) -> Self {
+        let current_week = week_start(now);
+        let first_week = current_week - Duration::weeks(i64::from(weeks));
+        let mut counts: BTreeMap<NaiveDate, u32> = (0..weeks)
+            .map(|offset| (first_week + Duration::weeks(i64::from(offset)), 0))
+            .collect();
+        for at in accepted_at {
+            if let Some(count) = counts.get_mut(&week_start(*at)) {
+                *count += 1;
+            }
+        }
+
+        let history: Vec<u32> = counts.values().copied().collect();
+        let mut histogram: BTreeMap<u32, u32> = BTreeMap::new();
+        for stories in &history {
+            *histogram.entry(*stories).or_insert(0) += 1;
+        }
+        let average_stories = if history.is_empty() {
+            0.0
+        } else {
+            history
+                .iter()
+                .map(|&stories| f64::from(stories))
+                .sum::<f64>()
+                / history.len() as f64
+        };
+
+        Self {
+            team_id,
+            forecast: forecast_throughput(&history, forecast_weeks, FORECAST_TRIALS, rng),
+            average_stories,
+            histogram: histogram
+                .into_iter()
+                .map(|(stories, weeks)| ThroughputBucket { stories, weeks })
+                .collect(),
+            weeks: counts
+                .into_iter()
+                .map(|(week_start, stories)| WeeklyThroughput {
+                    week_start,
+                    stories,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{GoalStory, SprintGoal};
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn sprint(start: DateTime<Utc>, end: DateTime<Utc>) -> Sprint {
+        Sprint {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            team_id: Uuid::new_v4(),
+            organization_id: None,
+            name: "Sprint 1".to_string(),
+            goal: None,
+            status: "active".to_string(),
+            capacity_points: 20,
+            committed_points: 8,
+            completed_points: 0,
+            start_date: start,
+            end_date: end,
+            created_at: start,
+            updated_at: start,
+        }
+    }
+
+    #[test]
+    fn test_burndown_counts_what_was_open_at_the_end_of_each_day() {
+        let (login, search, spike) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let item = |story_id, points, completed_at| BurndownItem {
+            story_id,
+            story_points: Some(points),
+            task_id: Some(Uuid::new_v4()),
+            completed_at,
+        };
+        let items = vec![
+            item(login, 3, Some(at(2, 10))),
+            item(login, 3, Some(at(3, 9))),
+            item(search, 5, None),
+            BurndownItem {
+                story_id: spike,
+                story_points: Some(2),
+                task_id: None,
+                completed_at: None,
+            },
+        ];
+
+        let sprint = sprint(at(2, 0), at(5, 23));
+        let goal = SprintGoal {
+            id: Uuid::new_v4(),
+            sprint_id: sprint.id,
+            title: "Ship login".to_string(),
+            description: None,
+            position: 0,
+            story_ids: vec![login],
+        };
+        let goal_story = GoalStory {
+            goal_id: goal.id,
+            story_id: login,
+            title: "Login".to_string(),
+            status: "accepted".to_string(),
+            story_points: Some(3),
+        };
+        let goals = SprintGoalReport::new(sprint.id, vec![goal], &[goal_story]);
+
+        let burndown = SprintBurndown::new(
+            &sprint,
+            &items,
+            &SprintCalendar::default(),
+            goals,
+            at(3, 12),
+        );
+
+        assert_eq!((burndown.total_points, burndown.total_tasks), (10, 3));
+        let remaining: Vec<_> = burndown
+            .days
+            .iter()
+            .map(|day| (day.remaining_points, day.remaining_tasks))
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![
+                (Some(10), Some(2)),
+                (Some(7), Some(1)),
+                (None, None),
+                (None, None)
+            ]
+        );
+        assert_eq!(burndown.days[0].ideal_points, 10.0);
+        assert_eq!(burndown.days[3].ideal_points, 0.0);
+        assert_eq!(burndown.goals.achieved_goals, 1);
+
+        let json = serde_json::to_value(&burndown).unwrap();
+        assert_eq!(json["totalPoints"], 10);
+        assert_eq!(json["days"][1]["remainingPoints"], 7);
+        assert_eq!(json["goals"]["achievedGoals"], 1);
+    }
+
+    #[test]
+    fn test_velocity_orders_sprints_and_averages_completed_points() {
+        let velocity = |day: u32, completed: i32| SprintVelocity {
+            sprint_id: Uuid::new_v4(),
+            name: format!("Sprint {}", day),
+            start_date: at(day, 0),
+            end_date: at(day + 1, 0),
+            committed_points: 10,
+            completed_points: completed,
+        };
+
+        let report = ProjectVelocity::new(
+            Uuid::new_v4(),
+            vec![velocity(20, 9), velocity(1, 6), velocity(10, 12)],
+        );
+
+        assert_eq!(
+            report
+                .sprints
+                .iter()
+                .map(|sprint| sprint.completed_points)
+                .collect::<Vec<_>>(),
+            vec![6, 12, 9]
+        );
+        assert_eq!(report.average_points, 9.0);
+        assert_eq!((report.min_points, report.max_points), (6, 12));
+        assert_eq!(
+            ProjectVelocity::new(Uuid::new_v4(), vec![]).average_points,
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_throughput_counts_finished_weeks_only() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        // 2026-03-02 and 2026-03-09 are Mondays; "now" is Wednesday the 11th
+        let accepted = vec![at(2, 9), at(4, 17), at(8, 23), at(10, 8), at(11, 9)];
+        let throughput = TeamThroughput::new(
+            Uuid::new_v4(),
+            &accepted,
+            3,
+            2,
+            at(11, 12),
+            &mut StdRng::seed_from_u64(1),
+        );
+
+        assert_eq!(
+            throughput
+                .weeks
+                .iter()
+                .map(|week| (week.week_start.day(), week.stories))
+                .collect::<Vec<_>>(),
+            vec![(16, 0), (23, 0), (2, 3)]
+        );
+        assert_eq!(
+            throughput.histogram,
+            vec![
+                ThroughputBucket {
+                    stories: 0,
+                    weeks: 2
+                },
+                ThroughputBucket {
+                    stories: 3,
+                    weeks: 1
+                }
+            ]
+        );
+        assert_eq!(throughput.average_stories, 1.0);
+
+        let json = serde_json::to_value(&throughput).unwrap();
+        assert_eq!(json["weeks"][2]["weekStart"], "2026-03-02");
+        assert_eq!(json["averageStories"], 1.0);
+        assert_eq!(json["forecast"]["weeks"], 2);
+        assert_eq!(throughput.forecast.unwrap().weeks, 2);
+    }
+}
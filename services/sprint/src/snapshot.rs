@@ -0,0 +1,348 @@
+//! Offline snapshots of a sprint board for workshops and archival.
+//!
+//! A snapshot freezes the board as it is when generated: the sprint, its
+//! stories and tasks, the board statistics, goal progress, and the tasks
+//! grouped by story and by status. It is served as JSON or as a single HTML
+//! page with its styles inline, so it opens without the app or a network.
+
+use crate::domain::{GroupedTasks, Sprint, SprintGoalReport, SprintStats, TaskWithStory};
+use chrono::{DateTime, Utc};
+use common::AppError;
+use serde::Serialize;
+use std::fmt::Write;
+use uuid::Uuid;
+
+/// Task statuses in board order; any other status is shown after them
+const TASK_STATUS_ORDER: [&str; 4] = ["available", "owned", "inprogress", "completed"];
+
+/// Output formats supported by `GET /api/v1/sprints/{sprint_id}/snapshot`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    Json,
+    Html,
+}
+
+impl SnapshotFormat {
+    /// Parse the `format` query parameter, defaulting to JSON
+    pub fn parse(raw: Option<&str>) -> Result<Self, AppError> {
+        match raw
+            .map(|value| value.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            None | Some("") | Some("json") => Ok(Self::Json),
+            Some("html") => Ok(Self::Html),
+            Some(other) => Err(AppError::BadRequest(format!(
+                "Unsupported snapshot format '{}'; expected 'json' or 'html'",
+                other
+            ))),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Html => "text/html; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// A story planned into the sprint
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotStory {
+    pub story_id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub story_type: String,
+    pub story_points: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintSnapshot {
+    pub generated_at: DateTime<Utc>,
+    pub sprint: Sprint,
+    pub stats: SprintStats,
+    pub goals: SprintGoalReport,
+    pub stories: Vec<SnapshotStory>,
+    pub tasks: Vec<TaskWithStory>,
+    pub tasks_by_story: GroupedTasks,
+    pub tasks_by_status: GroupedTasks,
+}
+
+impl SprintSnapshot {
+    pub fn new(
+        sprint: Sprint,
+        stats: SprintStats,
+        goals: SprintGoalReport,
+        stories: Vec<SnapshotStory>,
+        tasks: Vec<TaskWithStory>,
+        generated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            generated_at,
+            sprint,
+            stats,
+            goals,
+            tasks_by_story: GroupedTasks::by_story(&tasks),
+            tasks_by_status: GroupedTasks::by_status(&tasks),
+            stories,
+            tasks,
+        }
+    }
+
+    pub fn render(&self, format: SnapshotFormat) -> Result<Vec<u8>, AppError> {
+        match format {
+            SnapshotFormat::Json => serde_json::to_vec_pretty(self).map_err(|e| {
+                tracing::error!(error = %e, "Failed to serialize sprint snapshot");
+                AppError::InternalServerError
+            }),
+            SnapshotFormat::Html => Ok(self.to_html().into_bytes()),
+        }
+    }
+
+    /// A standalone page; every value from the board is escaped
+    pub fn to_html(&self) -> String {
+        let sprint = &self.sprint;
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{name} snapshot</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+             <h1>{name}</h1>\n<p class=\"meta\">{status} &middot; {start} to {end} &middot; \
+             snapshot taken {generated}</p>\n",
+            name = escape(&sprint.name),
+            status = escape(&sprint.status),
+            start = sprint.start_date.format("%Y-%m-%d"),
+            end = sprint.end_date.format("%Y-%m-%d"),
+            generated = self.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        );
+        if let Some(goal) = &sprint.goal {
+            let _ = writeln!(html, "<p class=\"goal\">{}</p>", escape(goal));
+        }
+
+        let stats = &self.stats;
+        let _ = write!(
+            html,
+            "<h2>Stats</h2>\n<table>\n\
+             <tr><th>Stories</th><td>{}</td></tr>\n\
+             <tr><th>Tasks completed</th><td>{} of {} ({:.0}%)</td></tr>\n\
+             <tr><th>Points committed</th><td>{}</td></tr>\n\
+             <tr><th>Points completed</th><td>{}</td></tr>\n\
+             <tr><th>Capacity</th><td>{}</td></tr>\n</table>\n",
+            stats.total_stories,
+            stats.completed_tasks,
+            stats.total_tasks,
+            stats.completion_percentage,
+            sprint.committed_points,
+            sprint.completed_points,
+            sprint.capacity_points,
+        );
+
+        if !self.goals.goals.is_empty() {
+            let _ = writeln!(
+                html,
+                "<h2>Goals ({} of {} achieved)</h2>\n<ul>",
+                self.goals.achieved_goals, self.goals.total_goals
+            );
+            for progress in &self.goals.goals {
+                let _ = writeln!(
+                    html,
+                    "<li>{}{} &middot; {:.0}%</li>",
+                    escape(&progress.goal.title),
+                    if progress.achieved { " &#10003;" } else { "" },
+                    progress.completion_percentage,
+                );
+            }
+            html.push_str("</ul>\n");
+        }
+
+        html.push_str(
+            "<h2>Stories</h2>\n<table>\n\
+             <tr><th>Story</th><th>Type</th><th>Status</th><th>Points</th><th>Tasks</th></tr>\n",
+        );
+        for story in &self.stories {
+            let tasks = self
+                .tasks
+                .iter()
+                .filter(|task| task.story_id == story.story_id)
+                .count();
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&story.title),
+                escape(&story.story_type),
+                escape(&story.status),
+                story
+                    .story_points
+                    .map(|points| points.to_string())
+                    .unwrap_or_default(),
+                tasks,
+            );
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Tasks by status</h2>\n<div class=\"board\">\n");
+        for status in ordered_keys(&self.tasks_by_status, |a, b| {
+            status_rank(a).cmp(&status_rank(b)).then_with(|| a.cmp(b))
+        }) {
+            let _ = writeln!(
+                html,
+                "<section class=\"column\">\n<h3>{} ({})</h3>",
+                escape(status),
+                self.tasks_by_status.groups[status].len()
+            );
+            for task in &self.tasks_by_status.groups[status] {
+                let _ = writeln!(
+                    html,
+                    "<div class=\"card\"><strong>{}</strong><br><small>{}{}</small></div>",
+                    escape(&task.title),
+                    escape(&task.story_title),
+                    task.owner_name
+                        .as_deref()
+                        .map(|owner| format!(" &middot; {}", escape(owner)))
+                        .unwrap_or_default(),
+                );
+            }
+            html.push_str("</section>\n");
+        }
+        html.push_str("</div>\n");
+
+        html.push_str("<h2>Tasks by story</h2>\n");
+        for story in ordered_keys(&self.tasks_by_story, |a, b| a.cmp(b)) {
+            let _ = writeln!(html, "<h3>{}</h3>\n<ul>", escape(story));
+            for task in &self.tasks_by_story.groups[story] {
+                let _ = writeln!(
+                    html,
+                    "<li>{} <small>({})</small></li>",
+                    escape(&task.title),
+                    escape(&task.status)
+                );
+            }
+            html.push_str("</ul>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#1f2933}\
+    table{border-collapse:collapse;margin-bottom:1rem}\
+    th,td{border:1px solid #cbd2d9;padding:.3rem .6rem;text-align:left}\
+    .meta{color:#616e7c}.goal{font-style:italic}\
+    .board{display:flex;gap:1rem;align-items:flex-start}\
+    .column{flex:1;background:#f5f7fa;padding:.5rem;border-radius:4px}\
+    .card{background:#fff;border:1px solid #e4e7eb;border-radius:4px;padding:.4rem;margin:.4rem 0}";
+
+fn status_rank(status: &str) -> usize {
+    TASK_STATUS_ORDER
+        .iter()
+        .position(|known| *known == status)
+        .unwrap_or(TASK_STATUS_ORDER.len())
+}
+
+fn ordered_keys(
+    grouped: &GroupedTasks,
+    compare: impl Fn(&str, &str) -> std::cmp::Ordering,
+) -> Vec<&str> {
+    let mut keys: Vec<&str> = grouped.groups.keys().map(String::as_str).collect();
+    keys.sort_by(|a, b| compare(a, b));
+    keys
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(title: &str, status: &str, story_id: Uuid, story_title: &str) -> TaskWithStory {
+        TaskWithStory {
+            task_id: Uuid::new_v4(),
+            title: title.to_string(),
+            status: status.to_string(),
+            owner_user_id: None,
+            owner_name: None,
+            story_id,
+            story_title: story_title.to_string(),
+            acceptance_criteria_refs: vec![],
+            estimated_hours: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_html_snapshot_escapes_board_content_and_orders_columns() {
+        let now = Utc::now();
+        let sprint = Sprint {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            team_id: Uuid::new_v4(),
+            organization_id: None,
+            name: "Sprint <1>".to_string(),
+            goal: Some("Ship search & filters".to_string()),
+            status: "active".to_string(),
+            capacity_points: 20,
+            committed_points: 8,
+            completed_points: 3,
+            start_date: now,
+            end_date: now + chrono::Duration::days(14),
+            created_at: now,
+            updated_at: now,
+        };
+        let story_id = Uuid::new_v4();
+        let snapshot = SprintSnapshot::new(
+            sprint.clone(),
+            SprintStats::new(1, 2, 1),
+            SprintGoalReport::new(sprint.id, vec![], &[]),
+            vec![SnapshotStory {
+                story_id,
+                title: "Search".to_string(),
+                status: "inprogress".to_string(),
+                story_type: "feature".to_string(),
+                story_points: Some(8),
+            }],
+            vec![
+                task("<script>alert(1)</script>", "completed", story_id, "Search"),
+                task("Index titles", "available", story_id, "Search"),
+            ],
+            now,
+        );
+
+        let html = snapshot.to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>Sprint &lt;1&gt;</h1>"));
+        assert!(html.contains("Ship search &amp; filters"));
+        assert!(!html.contains("<script>"));
+        assert!(html.find("available (1)").unwrap() < html.find("completed (1)").unwrap());
+
+        assert_eq!(
+            SnapshotFormat::parse(Some(" HTML ")).unwrap(),
+            SnapshotFormat::Html
+        );
+        assert_eq!(SnapshotFormat::parse(None).unwrap(), SnapshotFormat::Json);
+        assert!(SnapshotFormat::parse(Some("pdf")).is_err());
+    }
+}
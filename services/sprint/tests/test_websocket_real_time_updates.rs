@@ -57,7 +57,7 @@ async fn spec_test_websocket_broadcasts_ownership_taken() {
     );
 
     // Verify event details
-    match received {
+    match received.event {
         TaskEvent::OwnershipTaken {
             task_id: recv_task_id,
             owner_user_id: recv_owner,
@@ -112,7 +112,7 @@ async fn spec_test_websocket_broadcasts_ownership_released() {
     );
 
     // Verify event details
-    match received {
+    match received.event {
         TaskEvent::OwnershipReleased {
             task_id: recv_task_id,
             previous_owner_user_id: recv_prev_owner,
@@ -174,7 +174,7 @@ async fn spec_test_websocket_broadcasts_status_changed() {
     );
 
     // Verify event details
-    match received {
+    match received.event {
         TaskEvent::StatusChanged {
             task_id: recv_task_id,
             old_status: recv_old,
@@ -428,7 +428,7 @@ async fn spec_test_sprint_task_board_real_time_update_workflow() {
         .await
         .expect("Viewer should receive status change in real-time");
 
-    match received2 {
+    match received2.event {
         TaskEvent::StatusChanged {
             new_status,
             task_id: recv_task_id,